@@ -0,0 +1,510 @@
+//! Complex-valued tensors
+//!
+//! A [`ComplexTensor`] is a pair of real tensors holding the real and imaginary
+//! parts, so `ComplexTensor<f32>` and `ComplexTensor<f64>` evaluate to arrays of
+//! [`Complex32`](num_complex::Complex32) and [`Complex64`](num_complex::Complex64).
+//! The complex ops are built from real ops, hence every complex computation can
+//! be differentiated (also repeatedly) with the usual machinery.
+//!
+//! ## Wirtinger calculus
+//! A function f(z) of z = x + iy is differentiated with the Wirtinger operators
+//!
+//! ```text
+//! ∂f/∂z = (∂f/∂x - i ∂f/∂y) / 2,    ∂f/∂z̄ = (∂f/∂x + i ∂f/∂y) / 2
+//! ```
+//!
+//! f is holomorphic iff ∂f/∂z̄ = 0, in which case ∂f/∂z is the complex derivative.
+//! Both are returned by [`wirtinger_grad`]. For a real-valued loss, [`grad`]
+//! returns ∂L/∂x + i ∂L/∂y = 2 ∂L/∂z̄, the direction of steepest ascent, so
+//! gradient descent is `z -= lr * grad` as in the real case.
+//!
+//! ```
+//! use ndarray::array;
+//! use num_complex::Complex64;
+//! use scirs2_autograd as ag;
+//! use ag::complex::{self, ComplexTensor};
+//!
+//! ag::run(|ctx| {
+//!     let z = complex::variable(array![Complex64::new(1., 2.)], ctx);
+//!
+//!     // |z|^2 = z z̄
+//!     let loss = ag::tensor_ops::sum_all(complex::norm_sqr(z));
+//!     let g = complex::grad(&[loss], &[z])[0];
+//!     assert_eq!(g.eval(ctx).unwrap()[0], Complex64::new(2., 4.));
+//!
+//!     // z^2 is holomorphic
+//!     let (dz, dz_conj) = complex::wirtinger_grad(z * z, &[z])[0];
+//!     assert_eq!(dz.eval(ctx).unwrap()[0], Complex64::new(2., 4.));
+//!     assert_eq!(dz_conj.eval(ctx).unwrap()[0], Complex64::new(0., 0.));
+//! });
+//! ```
+
+use crate::ndarray_ext::NdArray;
+use crate::op;
+use crate::tensor::Tensor;
+use crate::tensor_ops as T;
+use crate::{Context, EvalError, Float};
+use ndarray::{Dimension, Zip};
+use num_complex::Complex;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Array of complex numbers, the evaluation result of a [`ComplexTensor`]
+pub type ComplexNdArray<F> = ndarray::Array<Complex<F>, ndarray::IxDyn>;
+
+/// Lazy-evaluated complex array made of its real and imaginary parts
+///
+/// Like [`Tensor`], this is a cheap `Copy` handle into the graph.
+#[derive(Clone, Copy, Debug)]
+pub struct ComplexTensor<'graph, F: Float> {
+    re: Tensor<'graph, F>,
+    im: Tensor<'graph, F>,
+}
+
+impl<'graph, F: Float> ComplexTensor<'graph, F> {
+    /// Creates a complex tensor from its real and imaginary parts.
+    ///
+    /// Placeholders or variables for the two parts make a complex placeholder
+    /// or variable.
+    pub fn new(re: Tensor<'graph, F>, im: Tensor<'graph, F>) -> Self {
+        ComplexTensor { re, im }
+    }
+
+    /// Creates a complex tensor with zero imaginary part.
+    pub fn from_real(re: Tensor<'graph, F>) -> Self {
+        let im = T::zeros(&T::shape(re), re.graph());
+        ComplexTensor { re, im }
+    }
+
+    /// Real part
+    pub fn re(&self) -> Tensor<'graph, F> {
+        self.re
+    }
+
+    /// Imaginary part
+    pub fn im(&self) -> Tensor<'graph, F> {
+        self.im
+    }
+
+    /// Evaluates this tensor as an array of complex numbers.
+    pub fn eval(&self, ctx: &Context<'graph, F>) -> Result<ComplexNdArray<F>, EvalError> {
+        let mut results = ctx.evaluator().push(&self.re).push(&self.im).run();
+        let im = results.pop().unwrap()?;
+        let re = results.pop().unwrap()?;
+        combine(&re, &im)
+    }
+}
+
+/// Creates a constant complex tensor from an array of complex numbers.
+///
+/// Like [`tensor_ops::convert_to_tensor`](crate::tensor_ops::convert_to_tensor),
+/// the result is not differentiable; use [`variable`] for tensors to
+/// differentiate with respect to.
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex32;
+/// use scirs2_autograd as ag;
+///
+/// ag::run(|ctx| {
+///     let z = ag::complex::convert_to_complex_tensor(array![Complex32::new(1., -1.)], ctx);
+///     assert_eq!(z.im().eval(ctx), Ok(array![-1.].into_dyn()));
+/// });
+/// ```
+pub fn convert_to_complex_tensor<'graph, F: Float, D: Dimension>(
+    arr: ndarray::Array<Complex<F>, D>,
+    ctx: &'graph Context<'graph, F>,
+) -> ComplexTensor<'graph, F> {
+    let (re, im) = split(&arr.into_dyn());
+    ComplexTensor::new(T::convert_to_tensor(re, ctx), T::convert_to_tensor(im, ctx))
+}
+
+/// Creates a differentiable complex tensor from an array of complex numbers.
+pub fn variable<'graph, F: Float, D: Dimension>(
+    arr: ndarray::Array<Complex<F>, D>,
+    ctx: &'graph Context<'graph, F>,
+) -> ComplexTensor<'graph, F> {
+    let (re, im) = split(&arr.into_dyn());
+    let part = |arr: NdArray<F>| {
+        let shape: Vec<isize> = arr.shape().iter().map(|&n| n as isize).collect();
+        Tensor::builder(ctx)
+            .set_known_shape(&shape)
+            .build(T::const_gen_ops::ConvertToTensor { arr })
+    };
+    ComplexTensor::new(part(re), part(im))
+}
+
+/// Splits an array of complex numbers into its real and imaginary parts,
+/// e.g. to feed the placeholders of a [`ComplexTensor`].
+pub fn split<F: Float>(arr: &ComplexNdArray<F>) -> (NdArray<F>, NdArray<F>) {
+    (arr.mapv(|z| z.re), arr.mapv(|z| z.im))
+}
+
+/// Combines real and imaginary parts of the same shape into complex numbers.
+pub fn combine<F: Float>(re: &NdArray<F>, im: &NdArray<F>) -> Result<ComplexNdArray<F>, EvalError> {
+    if re.shape() != im.shape() {
+        return Err(EvalError::OpError(op::OpError::IncompatibleShape(format!(
+            "Real part has shape {:?} but imaginary part has shape {:?}",
+            re.shape(),
+            im.shape()
+        ))));
+    }
+    Ok(Zip::from(re)
+        .and(im)
+        .map_collect(|&re, &im| Complex::new(re, im)))
+}
+
+/// Elementwise addition
+pub fn add<'graph, F: Float>(
+    a: ComplexTensor<'graph, F>,
+    b: ComplexTensor<'graph, F>,
+) -> ComplexTensor<'graph, F> {
+    ComplexTensor::new(a.re + b.re, a.im + b.im)
+}
+
+/// Elementwise subtraction
+pub fn sub<'graph, F: Float>(
+    a: ComplexTensor<'graph, F>,
+    b: ComplexTensor<'graph, F>,
+) -> ComplexTensor<'graph, F> {
+    ComplexTensor::new(a.re - b.re, a.im - b.im)
+}
+
+/// Elementwise multiplication
+pub fn mul<'graph, F: Float>(
+    a: ComplexTensor<'graph, F>,
+    b: ComplexTensor<'graph, F>,
+) -> ComplexTensor<'graph, F> {
+    ComplexTensor::new(a.re * b.re - a.im * b.im, a.re * b.im + a.im * b.re)
+}
+
+/// Multiplication by a real tensor
+pub fn scale<'graph, F: Float>(
+    a: ComplexTensor<'graph, F>,
+    s: Tensor<'graph, F>,
+) -> ComplexTensor<'graph, F> {
+    ComplexTensor::new(a.re * s, a.im * s)
+}
+
+/// Matrix multiplication
+///
+/// (A + iB)(C + iD) = (AC - BD) + i(AD + BC)
+pub fn matmul<'graph, F: Float>(
+    a: ComplexTensor<'graph, F>,
+    b: ComplexTensor<'graph, F>,
+) -> ComplexTensor<'graph, F> {
+    ComplexTensor::new(
+        T::matmul(a.re, b.re) - T::matmul(a.im, b.im),
+        T::matmul(a.re, b.im) + T::matmul(a.im, b.re),
+    )
+}
+
+/// Elementwise complex conjugate
+pub fn conj<F: Float>(a: ComplexTensor<F>) -> ComplexTensor<F> {
+    ComplexTensor::new(a.re, T::neg(a.im))
+}
+
+/// Conjugate transpose of a matrix
+pub fn adjoint<F: Float>(a: ComplexTensor<F>) -> ComplexTensor<F> {
+    ComplexTensor::new(
+        T::transpose(a.re, &[1, 0]),
+        T::neg(T::transpose(a.im, &[1, 0])),
+    )
+}
+
+/// Real part as a real tensor
+pub fn real<F: Float>(a: ComplexTensor<F>) -> Tensor<F> {
+    a.re
+}
+
+/// Imaginary part as a real tensor
+pub fn imag<F: Float>(a: ComplexTensor<F>) -> Tensor<F> {
+    a.im
+}
+
+/// Elementwise squared modulus |z|² = z z̄
+pub fn norm_sqr<F: Float>(a: ComplexTensor<F>) -> Tensor<F> {
+    T::square(a.re) + T::square(a.im)
+}
+
+/// Elementwise modulus |z|
+///
+/// The modulus is not differentiable at 0; its gradient is taken as 0 there.
+pub fn abs<F: Float>(a: ComplexTensor<F>) -> Tensor<F> {
+    Tensor::builder(a.re.graph())
+        .append_input(a.re, false)
+        .append_input(a.im, false)
+        .set_shape(&T::shape(a.re))
+        .build(ComplexAbs)
+}
+
+/// Sum of all elements
+pub fn sum_all<F: Float>(a: ComplexTensor<F>) -> ComplexTensor<F> {
+    ComplexTensor::new(T::sum_all(a.re), T::sum_all(a.im))
+}
+
+/// Gradients of real-valued `ys` with respect to the complex tensors `zs`
+///
+/// Returns ∂L/∂x + i ∂L/∂y = 2 ∂L/∂z̄ for each z = x + iy, where L is the sum
+/// of all elements of `ys` (see the [module documentation](self)).
+pub fn grad<'graph, F: Float, A>(
+    ys: &[A],
+    zs: &[ComplexTensor<'graph, F>],
+) -> Vec<ComplexTensor<'graph, F>>
+where
+    A: AsRef<Tensor<'graph, F>>,
+{
+    let parts = real_and_imaginary_parts(zs);
+    let grads = T::grad(ys, &parts);
+    grads
+        .chunks(2)
+        .map(|g| ComplexTensor::new(g[0], g[1]))
+        .collect()
+}
+
+/// Wirtinger derivatives (∂w/∂z, ∂w/∂z̄) of the sum of the elements of `w` with
+/// respect to each of `zs`
+pub fn wirtinger_grad<'graph, F: Float>(
+    w: ComplexTensor<'graph, F>,
+    zs: &[ComplexTensor<'graph, F>],
+) -> Vec<(ComplexTensor<'graph, F>, ComplexTensor<'graph, F>)> {
+    let parts = real_and_imaginary_parts(zs);
+    // Derivatives of u = Re w and v = Im w with respect to x and y
+    let du = T::grad(&[w.re], &parts);
+    let dv = T::grad(&[w.im], &parts);
+    let half = T::scalar(F::from(0.5).unwrap(), w.re.graph());
+
+    du.chunks(2)
+        .zip(dv.chunks(2))
+        .map(|(du, dv)| {
+            let (u_x, u_y, v_x, v_y) = (du[0], du[1], dv[0], dv[1]);
+            let dz = ComplexTensor::new((u_x + v_y) * half, (v_x - u_y) * half);
+            let dz_conj = ComplexTensor::new((u_x - v_y) * half, (v_x + u_y) * half);
+            (dz, dz_conj)
+        })
+        .collect()
+}
+
+fn real_and_imaginary_parts<'graph, F: Float>(
+    zs: &[ComplexTensor<'graph, F>],
+) -> Vec<Tensor<'graph, F>> {
+    zs.iter().flat_map(|z| [z.re, z.im]).collect()
+}
+
+/// Modulus of a complex number given by its real and imaginary parts
+struct ComplexAbs;
+
+/// 1/x for x > 0 and 0 otherwise
+struct SafeReciprocal;
+
+impl<F: Float> op::Op<F> for ComplexAbs {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), op::OpError> {
+        let (re, im) = (ctx.input(0), ctx.input(1));
+        if re.shape() != im.shape() {
+            return Err(op::OpError::IncompatibleShape(format!(
+                "ComplexAbs: real part has shape {:?} but imaginary part has shape {:?}",
+                re.shape(),
+                im.shape()
+            )));
+        }
+        let ret = Zip::from(&re).and(&im).map_collect(|&x, &y| x.hypot(y));
+        ctx.append_output(ret);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        // d|z|/dx = x/|z|, d|z|/dy = y/|z|
+        let inv = Tensor::builder(ctx.graph())
+            .append_input(ctx.output(), false)
+            .build(SafeReciprocal);
+        let gy = *ctx.output_grad() * inv;
+        ctx.append_input_grad(0, Some(gy * *ctx.input(0)));
+        ctx.append_input_grad(1, Some(gy * *ctx.input(1)));
+    }
+}
+
+impl<F: Float> op::Op<F> for SafeReciprocal {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), op::OpError> {
+        let ret = ctx
+            .input(0)
+            .mapv(|x| if x > F::zero() { x.recip() } else { F::zero() });
+        ctx.append_output(ret);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        let y = ctx.output();
+        ctx.append_input_grad(0, Some(T::neg(*ctx.output_grad() * *y * *y)));
+    }
+}
+
+impl<'graph, F: Float> Add for ComplexTensor<'graph, F> {
+    type Output = ComplexTensor<'graph, F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        add(self, rhs)
+    }
+}
+
+impl<'graph, F: Float> Sub for ComplexTensor<'graph, F> {
+    type Output = ComplexTensor<'graph, F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        sub(self, rhs)
+    }
+}
+
+impl<'graph, F: Float> Mul for ComplexTensor<'graph, F> {
+    type Output = ComplexTensor<'graph, F>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        mul(self, rhs)
+    }
+}
+
+impl<'graph, F: Float> Neg for ComplexTensor<'graph, F> {
+    type Output = ComplexTensor<'graph, F>;
+
+    fn neg(self) -> Self::Output {
+        ComplexTensor::new(T::neg(self.re), T::neg(self.im))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+    use num_complex::Complex64;
+
+    fn c(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    fn assert_complex_eq(actual: &ComplexNdArray<f64>, expected: &ComplexNdArray<f64>) {
+        assert_eq!(actual.shape(), expected.shape());
+        for (a, e) in actual.iter().zip(expected) {
+            assert_relative_eq!(a.re, e.re, epsilon = 1e-12);
+            assert_relative_eq!(a.im, e.im, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let a_val = array![[c(1., 2.), c(0., -1.)], [c(3., 0.5), c(-2., 1.)]];
+        let b_val = array![[c(0.5, 1.), c(2., 0.)], [c(-1., 1.), c(1., 3.)]];
+
+        crate::run(|ctx| {
+            let a = convert_to_complex_tensor(a_val.clone(), ctx);
+            let b = convert_to_complex_tensor(b_val.clone(), ctx);
+
+            let sum = (a + b).eval(ctx).unwrap();
+            assert_complex_eq(&sum, &(&a_val + &b_val).into_dyn());
+            let prod = (a * b).eval(ctx).unwrap();
+            assert_complex_eq(&prod, &(&a_val * &b_val).into_dyn());
+            let diff = (a - b).eval(ctx).unwrap();
+            assert_complex_eq(&diff, &(&a_val - &b_val).into_dyn());
+            let mm = matmul(a, b).eval(ctx).unwrap();
+            assert_complex_eq(&mm, &a_val.dot(&b_val).into_dyn());
+            let conj_val = conj(a).eval(ctx).unwrap();
+            assert_complex_eq(&conj_val, &a_val.mapv(|z| z.conj()).into_dyn());
+            let adj = adjoint(a).eval(ctx).unwrap();
+            assert_complex_eq(&adj, &a_val.t().mapv(|z| z.conj()).into_dyn());
+
+            let modulus = abs(a).eval(ctx).unwrap();
+            for (m, z) in modulus.iter().zip(&a_val) {
+                assert_relative_eq!(*m, z.norm(), epsilon = 1e-12);
+            }
+        });
+    }
+
+    #[test]
+    fn test_wirtinger_derivatives() {
+        let z_val = array![c(1.5, -0.5), c(-1., 2.)];
+
+        crate::run(|ctx| {
+            let z = variable(z_val.clone(), ctx);
+
+            // z^2 is holomorphic: ∂/∂z = 2z, ∂/∂z̄ = 0
+            let (dz, dz_conj) = wirtinger_grad(z * z, &[z])[0];
+            assert_complex_eq(&dz.eval(ctx).unwrap(), &z_val.mapv(|z| 2. * z).into_dyn());
+            assert_complex_eq(
+                &dz_conj.eval(ctx).unwrap(),
+                &array![c(0., 0.), c(0., 0.)].into_dyn(),
+            );
+
+            // z̄ is antiholomorphic: ∂/∂z = 0, ∂/∂z̄ = 1
+            let (dz, dz_conj) = wirtinger_grad(conj(z), &[z])[0];
+            assert_complex_eq(
+                &dz.eval(ctx).unwrap(),
+                &array![c(0., 0.), c(0., 0.)].into_dyn(),
+            );
+            assert_complex_eq(
+                &dz_conj.eval(ctx).unwrap(),
+                &array![c(1., 0.), c(1., 0.)].into_dyn(),
+            );
+
+            // z z̄: ∂/∂z = z̄, ∂/∂z̄ = z
+            let (dz, dz_conj) = wirtinger_grad(z * conj(z), &[z])[0];
+            assert_complex_eq(&dz.eval(ctx).unwrap(), &z_val.mapv(|z| z.conj()).into_dyn());
+            assert_complex_eq(&dz_conj.eval(ctx).unwrap(), &z_val.clone().into_dyn());
+        });
+    }
+
+    #[test]
+    fn test_real_loss_gradient() {
+        // L = ||A z||² has gradient 2 ∂L/∂z̄ = 2 Aᴴ A z
+        let a_val = array![[c(1., 1.), c(0., 2.)], [c(-1., 0.5), c(2., -1.)]];
+        let z_val = array![[c(0.5, -1.)], [c(1., 1.)]];
+        let expected = a_val.t().mapv(|a| 2. * a.conj()).dot(&a_val.dot(&z_val));
+
+        crate::run(|ctx| {
+            let a = convert_to_complex_tensor(a_val.clone(), ctx);
+            let z = ComplexTensor::new(
+                ctx.placeholder("z_re", &[2, 1]),
+                ctx.placeholder("z_im", &[2, 1]),
+            );
+            let loss = T::sum_all(norm_sqr(matmul(a, z)));
+            let g = grad(&[loss], &[z])[0];
+
+            let (z_re, z_im) = split(&z_val.clone().into_dyn());
+            let results = ctx
+                .evaluator()
+                .push(&g.re())
+                .push(&g.im())
+                .feed("z_re", z_re.view())
+                .feed("z_im", z_im.view())
+                .run();
+            let g_val =
+                combine(results[0].as_ref().unwrap(), results[1].as_ref().unwrap()).unwrap();
+            assert_complex_eq(&g_val, &expected.into_dyn());
+        });
+    }
+
+    #[test]
+    fn test_abs_gradient() {
+        crate::run(|ctx| {
+            let z = variable(array![c(3., -4.), c(0., 0.)], ctx);
+            let g = grad(&[abs(z)], &[z])[0].eval(ctx).unwrap();
+
+            // z/|z| away from the origin, 0 at the origin
+            assert_complex_eq(&g, &array![c(0.6, -0.8), c(0., 0.)].into_dyn());
+        });
+    }
+
+    #[test]
+    fn test_complex_from_real() {
+        crate::run(|ctx| {
+            let x = T::convert_to_tensor(array![1.0_f32, -2.0], ctx);
+            let z = ComplexTensor::from_real(x);
+            let z_val = z.eval(ctx).unwrap();
+            assert_eq!(
+                z_val,
+                array![
+                    num_complex::Complex32::new(1., 0.),
+                    num_complex::Complex32::new(-2., 0.)
+                ]
+                .into_dyn()
+            );
+        });
+    }
+}
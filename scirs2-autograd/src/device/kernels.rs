@@ -0,0 +1,185 @@
+//! Op implementations with the kernels of `scirs2_core::gpu`
+//!
+//! The kernels compute in `f32`; half-precision arrays are converted on the
+//! way. Each function returns `Ok(false)` without appending an output if it
+//! can't handle the inputs, leaving the op to the CPU.
+
+use super::Gpu;
+use crate::mixed_precision::is_half;
+use crate::ndarray_ext::{NdArray, NdArrayView};
+use crate::op::{ComputeContext, OpError};
+use crate::{same_type, Float};
+
+/// Threads per work group of the 1-D kernels
+const WORK_GROUP_SIZE: usize = 256;
+
+/// Side of the square tiles of the GEMM kernel
+const GEMM_TILE_SIZE: usize = 16;
+
+fn supported<F: Float>() -> bool {
+    same_type::<F, f32>() || is_half::<F>()
+}
+
+fn to_f32<F: Float>(x: &NdArrayView<F>) -> Vec<f32> {
+    x.iter().map(|a| a.to_f32().unwrap()).collect()
+}
+
+fn from_f32<F: Float>(shape: &[usize], data: Vec<f32>) -> Result<NdArray<F>, OpError> {
+    let data = data.into_iter().map(|a| F::from(a).unwrap()).collect();
+    NdArray::from_shape_vec(shape, data).map_err(|e| OpError::NdArrayError("GPU".into(), e))
+}
+
+fn num_groups(n: usize, per_group: usize) -> u32 {
+    n.div_ceil(per_group) as u32
+}
+
+/// Applies the elementwise kernel `name` to the input.
+pub(crate) fn unary<F: Float>(
+    ctx: &mut ComputeContext<F>,
+    gpu: &Gpu,
+    name: &'static str,
+) -> Result<bool, OpError> {
+    if !supported::<F>() {
+        return Ok(false);
+    }
+    let Some(kernel) = gpu.kernel(name)? else {
+        return Ok(false);
+    };
+    let x = ctx.input(0);
+    let n = x.len();
+    let input = gpu.context().create_buffer_from_slice(&to_f32(&x));
+    let output = gpu.context().create_buffer::<f32>(n);
+    kernel.set_buffer("input", &input);
+    kernel.set_buffer("output", &output);
+    kernel.set_u32("n", n as u32);
+    kernel.dispatch([num_groups(n, WORK_GROUP_SIZE), 1, 1]);
+    let y = from_f32(x.shape(), output.to_vec())?;
+    ctx.append_output(y);
+    Ok(true)
+}
+
+/// Computes `input(0) + alpha * input(1)` for inputs of the same shape.
+pub(crate) fn axpy<F: Float>(
+    ctx: &mut ComputeContext<F>,
+    gpu: &Gpu,
+    alpha: f32,
+) -> Result<bool, OpError> {
+    if !supported::<F>() || ctx.input(0).shape() != ctx.input(1).shape() {
+        return Ok(false);
+    }
+    let Some(kernel) = gpu.kernel("axpy")? else {
+        return Ok(false);
+    };
+    let (a, b) = (ctx.input(0), ctx.input(1));
+    let n = a.len();
+    let x = gpu.context().create_buffer_from_slice(&to_f32(&b));
+    let y = gpu.context().create_buffer_from_slice(&to_f32(&a));
+    kernel.set_buffer("x", &x);
+    kernel.set_buffer("y", &y);
+    kernel.set_f32("alpha", alpha);
+    kernel.set_u32("n", n as u32);
+    kernel.dispatch([num_groups(n, WORK_GROUP_SIZE), 1, 1]);
+    let ret = from_f32(a.shape(), y.to_vec())?;
+    ctx.append_output(ret);
+    Ok(true)
+}
+
+/// Multiplies two matrices, optionally transposed.
+pub(crate) fn gemm<F: Float>(
+    ctx: &mut ComputeContext<F>,
+    gpu: &Gpu,
+    transpose_a: bool,
+    transpose_b: bool,
+) -> Result<bool, OpError> {
+    let (a, b) = (ctx.input(0), ctx.input(1));
+    if !supported::<F>() || a.ndim() != 2 || b.ndim() != 2 {
+        return Ok(false);
+    }
+    let a = if transpose_a { a.reversed_axes() } else { a };
+    let b = if transpose_b { b.reversed_axes() } else { b };
+    let (m, k, n) = (a.shape()[0], a.shape()[1], b.shape()[1]);
+    if b.shape()[0] != k {
+        return Ok(false);
+    }
+    let Some(kernel) = gpu.kernel("gemm_standard")? else {
+        return Ok(false);
+    };
+    let a = gpu.context().create_buffer_from_slice(&to_f32(&a));
+    let b = gpu.context().create_buffer_from_slice(&to_f32(&b));
+    let c = gpu.context().create_buffer::<f32>(m * n);
+    kernel.set_buffer("a", &a);
+    kernel.set_buffer("b", &b);
+    kernel.set_buffer("c", &c);
+    kernel.set_u32("m", m as u32);
+    kernel.set_u32("n", n as u32);
+    kernel.set_u32("k", k as u32);
+    kernel.set_f32("alpha", 1.);
+    kernel.set_f32("beta", 0.);
+    kernel.dispatch([
+        num_groups(n, GEMM_TILE_SIZE),
+        num_groups(m, GEMM_TILE_SIZE),
+        1,
+    ]);
+    let ret = from_f32(&[m, n], c.to_vec())?;
+    ctx.append_output(ret);
+    Ok(true)
+}
+
+/// Sums all the elements of the input, or averages them if `mean`.
+pub(crate) fn sum<F: Float>(
+    ctx: &mut ComputeContext<F>,
+    gpu: &Gpu,
+    mean: bool,
+) -> Result<bool, OpError> {
+    let x = ctx.input(0);
+    if !supported::<F>() || x.is_empty() {
+        return Ok(false);
+    }
+    let Some(kernel) = gpu.kernel("sum_reduce")? else {
+        return Ok(false);
+    };
+    let n = x.len();
+    // Each work group reduces two elements per thread into its own partial sum
+    let groups = num_groups(n, 2 * WORK_GROUP_SIZE);
+    let input = gpu.context().create_buffer_from_slice(&to_f32(&x));
+    let output = gpu.context().create_buffer::<f32>(groups as usize);
+    kernel.set_buffer("input", &input);
+    kernel.set_buffer("output", &output);
+    kernel.set_u32("n", n as u32);
+    kernel.dispatch([groups, 1, 1]);
+    let mut total: f32 = output.to_vec().into_iter().sum();
+    if mean {
+        total /= n as f32;
+    }
+    ctx.append_output(ndarray::arr0(F::from(total).unwrap()).into_dyn());
+    Ok(true)
+}
+
+/// Softmax along `axis`, which must be the last axis.
+pub(crate) fn softmax<F: Float>(
+    ctx: &mut ComputeContext<F>,
+    gpu: &Gpu,
+    axis: isize,
+) -> Result<bool, OpError> {
+    let x = ctx.input(0);
+    let ndim = x.ndim() as isize;
+    let last = if axis < 0 { axis + ndim } else { axis } == ndim - 1;
+    if !supported::<F>() || !last || x.is_empty() {
+        return Ok(false);
+    }
+    let Some(kernel) = gpu.kernel("softmax")? else {
+        return Ok(false);
+    };
+    let n = x.shape()[x.ndim() - 1];
+    let rows = x.len() / n;
+    let input = gpu.context().create_buffer_from_slice(&to_f32(&x));
+    let output = gpu.context().create_buffer::<f32>(x.len());
+    kernel.set_buffer("input", &input);
+    kernel.set_buffer("output", &output);
+    kernel.set_u32("n", n as u32);
+    kernel.set_u32("batch_size", rows as u32);
+    kernel.dispatch([num_groups(n, 2 * WORK_GROUP_SIZE), rows as u32, 1]);
+    let y = from_f32(x.shape(), output.to_vec())?;
+    ctx.append_output(y);
+    Ok(true)
+}
@@ -0,0 +1,177 @@
+//! Placement of tensors on compute devices
+//!
+//! Every tensor is placed on a [Device]. Tensors built from other tensors are
+//! placed on a GPU if any of their inputs is, and the placement only changes
+//! with the explicit transfers [to_device](crate::tensor_ops::to_device) and
+//! [to_host](crate::tensor_ops::to_host). Gradients are placed like the tensors
+//! they are computed from, and flow back through the transfers.
+//!
+//! With the `gpu` feature, the ops of tensors placed on [Device::Gpu] run the
+//! kernels of `scirs2_core::gpu` for matrix multiplications, additions and
+//! subtractions, ReLU, sigmoid and tanh, full sums and means, and softmax.
+//! Ops without a kernel, element types other than `f32`, `f16` and `bf16`, and
+//! builds without a usable GPU backend fall back to the CPU, so a graph gives
+//! the same results wherever it is placed. Custom ops can provide kernels with
+//! [Op::compute_gpu](crate::op::Op::compute_gpu).
+//!
+//! ```
+//! use scirs2_autograd as ag;
+//! use ag::device::Device;
+//! use ag::tensor_ops as T;
+//!
+//! ag::run(|g: &mut ag::Context<f32>| {
+//!     let x = T::to_device(T::ones(&[2, 3], g), Device::Gpu);
+//!     let w = T::to_device(T::ones(&[3, 4], g), Device::Gpu);
+//!     let y = T::softmax(T::matmul(x, w), 1);
+//!     assert_eq!(y.device(), Device::Gpu);
+//!
+//!     let y = T::to_host(y);
+//!     assert_eq!(y.device(), Device::Cpu);
+//!     assert_eq!(y.eval(g).unwrap().shape(), &[2, 4]);
+//! });
+//! ```
+
+#[cfg(feature = "gpu")]
+pub(crate) mod kernels;
+
+use crate::op::{ComputeContext, Op, OpError};
+use crate::Float;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[cfg(feature = "gpu")]
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+#[cfg(feature = "gpu")]
+pub use scirs2_core::gpu::{GpuBackend, GpuContext, GpuError, GpuKernelHandle};
+
+/// Device on which the op of a tensor is computed.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Device {
+    /// The host
+    #[default]
+    Cpu,
+    /// The GPU of the current thread, see [Gpu]
+    Gpu,
+}
+
+impl Device {
+    /// Returns `true` if ops placed on this device can run there, rather than
+    /// falling back to the CPU.
+    pub fn is_available(&self) -> bool {
+        match self {
+            Device::Cpu => true,
+            #[cfg(feature = "gpu")]
+            Device::Gpu => with_gpu(|gpu| gpu.backend() != GpuBackend::Cpu).unwrap_or(false),
+            #[cfg(not(feature = "gpu"))]
+            Device::Gpu => false,
+        }
+    }
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Device::Cpu => write!(f, "CPU"),
+            Device::Gpu => write!(f, "GPU"),
+        }
+    }
+}
+
+/// GPU context of a thread with its compiled kernels.
+///
+/// Created on first use with [GpuBackend::preferred]; see [use_gpu_backend].
+#[cfg(feature = "gpu")]
+pub struct Gpu {
+    context: GpuContext,
+    /// Kernels by name, `None` if the backend doesn't have them
+    kernels: RefCell<HashMap<&'static str, Option<Rc<GpuKernelHandle>>>>,
+}
+
+#[cfg(feature = "gpu")]
+impl Gpu {
+    fn new(backend: GpuBackend) -> Result<Self, GpuError> {
+        Ok(Gpu {
+            context: GpuContext::new(backend)?,
+            kernels: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Backend of this GPU.
+    pub fn backend(&self) -> GpuBackend {
+        self.context.backend()
+    }
+
+    /// Context to create buffers in.
+    pub fn context(&self) -> &GpuContext {
+        &self.context
+    }
+
+    /// Kernel `name` of the `scirs2_core` kernel registry.
+    ///
+    /// Returns `Ok(None)` if the backend has no such kernel.
+    pub fn kernel(&self, name: &'static str) -> Result<Option<Rc<GpuKernelHandle>>, OpError> {
+        if let Some(kernel) = self.kernels.borrow().get(name) {
+            return Ok(kernel.clone());
+        }
+        let kernel = match self.context.get_kernel(name) {
+            Ok(kernel) => Some(Rc::new(kernel)),
+            Err(
+                GpuError::KernelNotFound(_)
+                | GpuError::UnsupportedBackend(_)
+                | GpuError::BackendNotSupported(_)
+                | GpuError::BackendNotImplemented(_),
+            ) => None,
+            Err(e) => {
+                return Err(OpError::RuntimeError(format!(
+                    "Failed to compile GPU kernel {}: {}",
+                    name, e
+                )))
+            }
+        };
+        self.kernels.borrow_mut().insert(name, kernel.clone());
+        Ok(kernel)
+    }
+}
+
+#[cfg(feature = "gpu")]
+thread_local! {
+    static GPU: RefCell<Option<Rc<Gpu>>> =
+        RefCell::new(Gpu::new(GpuBackend::preferred()).ok().map(Rc::new));
+}
+
+/// Runs `f` with the GPU of the current thread, if any.
+#[cfg(feature = "gpu")]
+pub fn with_gpu<R>(f: impl FnOnce(&Gpu) -> R) -> Option<R> {
+    let gpu = GPU.with(|gpu| gpu.borrow().clone())?;
+    Some(f(&gpu))
+}
+
+/// Makes the ops placed on [Device::Gpu] in the current thread use `backend`.
+#[cfg(feature = "gpu")]
+pub fn use_gpu_backend(backend: GpuBackend) -> Result<(), GpuError> {
+    let gpu = Gpu::new(backend)?;
+    GPU.with(|current| *current.borrow_mut() = Some(Rc::new(gpu)));
+    Ok(())
+}
+
+/// Computes `op` on `device`, falling back to the CPU.
+pub(crate) fn compute<F: Float>(
+    device: Device,
+    op: &dyn Op<F>,
+    ctx: &mut ComputeContext<F>,
+) -> Result<(), OpError> {
+    #[cfg(feature = "gpu")]
+    if device == Device::Gpu {
+        if let Some(done) = with_gpu(|gpu| op.compute_gpu(ctx, gpu)) {
+            if done? {
+                return Ok(());
+            }
+        }
+    }
+    #[cfg(not(feature = "gpu"))]
+    let _ = device;
+    op.compute(ctx)
+}
@@ -32,6 +32,17 @@ pub enum OpError {
     #[error("Runtime error: {0}")]
     RuntimeError(String),
 
+    /// NaN or infinite values, see [crate::Context::check_numerics]
+    #[error("Non-finite values from {op} (tensor {tensor}): {detail}")]
+    NonFinite {
+        /// Name of the op (or "variable" or "feed") which produced the values
+        op: String,
+        /// Id of the tensor holding the values
+        tensor: usize,
+        /// Counts of the non-finite values
+        detail: String,
+    },
+
     /// Other error
     #[error("Error: {0}")]
     Other(String),
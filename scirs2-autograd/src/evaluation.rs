@@ -1,5 +1,6 @@
 use crate::ndarray_ext::{NdArray, NdArrayView, RawNdArrayView};
 
+use crate::optimization::GraphOptimizer;
 use crate::tensor::Tensor;
 use crate::{Context, Graph};
 use crate::{EvalError, Float};
@@ -57,6 +58,7 @@ pub struct Evaluator<'c, 'g, F: Float> {
     targets: Vec<&'g Tensor<'g, F>>,
     ctx: &'c Context<'g, F>,
     feeder: Option<Feeder<'g, F>>,
+    optimizer: Option<GraphOptimizer<F>>,
 }
 
 // public APIs
@@ -67,6 +69,7 @@ impl<'c, 'g, F: Float> Evaluator<'c, 'g, F> {
             targets: vec![],
             ctx,
             feeder: None,
+            optimizer: None,
         }
     }
 
@@ -76,6 +79,7 @@ impl<'c, 'g, F: Float> Evaluator<'c, 'g, F> {
             targets: vec![],
             ctx: self.ctx,
             feeder: self.feeder.clone(),
+            optimizer: self.optimizer.clone(),
         }
     }
 
@@ -127,6 +131,14 @@ impl<'c, 'g, F: Float> Evaluator<'c, 'g, F> {
         self
     }
 
+    /// Optimizes the graph of the targets with `optimizer` before running them.
+    ///
+    /// The summary of the optimization is printed if `optimizer` inspects the graph.
+    pub fn optimize(mut self, optimizer: GraphOptimizer<F>) -> Self {
+        self.optimizer = Some(optimizer);
+        self
+    }
+
     /// Simple wrapper for `self.push(tensor).run()`.
     pub fn eval<T: AsRef<Tensor<'g, F>> + 'g>(self, tensor: T) -> Result<NdArray<F>, EvalError> {
         // Use clone here to avoid lifetime issues
@@ -174,6 +186,17 @@ impl<'c, 'g, F: Float> Evaluator<'c, 'g, F> {
             return ret;
         }
 
+        if let Some(optimizer) = &self.optimizer {
+            match optimizer.optimize_tensors(&self.targets) {
+                Ok(report) if report.optimized_graph.is_some() => report.print_summary(),
+                Ok(_) => {}
+                Err(e) => {
+                    ret.push(Err(EvalError::Other(format!("Optimization failed: {}", e))));
+                    return ret;
+                }
+            }
+        }
+
         let results = Graph::eval_tensors(self.targets.as_slice(), &placeholders, self.ctx);
         for r in results {
             match r {
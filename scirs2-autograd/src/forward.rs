@@ -0,0 +1,195 @@
+//! Forward-mode differentiation, see [jvp](crate::tensor_ops::jvp)
+//!
+//! The tangents of the inputs are propagated through the ops between them and
+//! the outputs, in order, with [Op::jvp]: each op builds the tangents of its
+//! outputs from the tangents of its inputs, like the dual part of dual numbers.
+//! Ops without a rule are linearized with their gradient instead (see
+//! [transposed_grad]).
+use crate::graph::TensorID;
+use crate::op::{ComputeContext, GradientContext, JvpContext, Op, OpError};
+use crate::tensor::Tensor;
+use crate::tensor_ops as T;
+use crate::{Context, Float, FxHashMap};
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+/// Tangents of each output by the ids of the ops, `None` meaning zeros
+type Tangents<'g, F> = FxHashMap<TensorID, Vec<Option<Tensor<'g, F>>>>;
+
+/// Returns the tangents of `ys` for the tangents `ts` of `xs`.
+pub(crate) fn jvp<'g, F: Float>(
+    ys: &[Tensor<'g, F>],
+    xs: &[Tensor<'g, F>],
+    ts: &[Tensor<'g, F>],
+) -> Vec<Tensor<'g, F>> {
+    assert_eq!(xs.len(), ts.len(), "jvp: one tangent is needed per input");
+    let Some(y) = ys.first() else {
+        return vec![];
+    };
+    let g = y.graph();
+    let mut known = Tangents::default();
+    for (x, t) in xs.iter().zip(ts) {
+        known.insert(x.id, vec![Some(*t)]);
+    }
+    let start = xs.iter().map(|x| x.id).min().unwrap_or(0);
+
+    // Ops between `xs` and `ys`, in topological order
+    let mut nodes = BTreeSet::new();
+    let mut stack: Vec<TensorID> = ys.iter().map(|y| y.id).filter(|&id| id > start).collect();
+    while let Some(id) = stack.pop() {
+        if known.contains_key(&id) || !nodes.insert(id) {
+            continue;
+        }
+        let node = g.access_inner(id);
+        stack.extend(
+            node.incoming_nodes
+                .iter()
+                .map(|x| x.id)
+                .filter(|&x| x >= start),
+        );
+    }
+
+    for id in nodes {
+        let (op, incoming, differentiable) = {
+            let node = g.access_inner(id);
+            (
+                node.op.clone(),
+                node.incoming_nodes.clone(),
+                node.is_differentiable,
+            )
+        };
+        let Some(op) = op else {
+            continue;
+        };
+        let tangents: Vec<Option<Tensor<F>>> = incoming
+            .iter()
+            .map(|x| {
+                known
+                    .get(&x.id)
+                    .and_then(|ts| ts.get(x.array_selector).copied().flatten())
+            })
+            .collect();
+        if !differentiable || tangents.iter().all(Option::is_none) {
+            continue;
+        }
+        let inputs: Vec<Tensor<F>> = incoming
+            .iter()
+            .map(|x| {
+                let input = g.tensor(x.id);
+                if x.array_selector > 0 {
+                    T::nth_tensor(input, x.array_selector)
+                } else {
+                    input
+                }
+            })
+            .collect();
+        let output = g.tensor(id);
+        let outputs = (0..op.num_outputs())
+            .map(|k| {
+                if k == 0 {
+                    output
+                } else {
+                    T::nth_tensor(output, k)
+                }
+            })
+            .collect();
+        let mut ctx = JvpContext {
+            op: op.clone(),
+            inputs,
+            tangents,
+            outputs,
+            context: Context::from_graph(g),
+            results: Vec::new(),
+        };
+        op.jvp(&mut ctx);
+        let results = if ctx.results.is_empty() {
+            transposed_grad(&ctx)
+        } else {
+            ctx.results
+        };
+        known.insert(id, results);
+    }
+
+    ys.iter()
+        .map(|y| {
+            known
+                .get(&y.id)
+                .and_then(|ts| ts[0])
+                .unwrap_or_else(|| T::zeros(&T::shape(y), g))
+        })
+        .collect()
+}
+
+/// Tangents of the outputs of an op without a rule.
+///
+/// The gradient of the inputs, `J^T u` for gradients `u` of the outputs, is
+/// linear in `u`, so the tangent `J t` is its product with the tangents `t`
+/// of the inputs, differentiated with respect to `u`.
+fn transposed_grad<'g, F: Float>(ctx: &JvpContext<'g, F>) -> Vec<Option<Tensor<'g, F>>> {
+    let g = ctx.context;
+    let us: Vec<Tensor<F>> = ctx
+        .outputs
+        .iter()
+        .map(|y| T::zeros(&T::shape(y), g))
+        .collect();
+    let gxs = {
+        let x_refs: Vec<&Tensor<F>> = ctx.inputs.iter().collect();
+        let y_refs: Vec<&Tensor<F>> = ctx.outputs.iter().collect();
+        let u_refs: Vec<&Tensor<F>> = us.iter().collect();
+        let mut grad_ctx = GradientContext {
+            zs: &y_refs,
+            xs: &x_refs,
+            context: g,
+            gzs: &u_refs,
+            results: Vec::new(),
+            array_field_id: 0,
+            _marker: PhantomData,
+        };
+        ctx.op.grad(&mut grad_ctx);
+        grad_ctx
+            .results
+            .iter()
+            .map(|gx| gx.map(|gx| gx.id))
+            .collect::<Vec<Option<TensorID>>>()
+    };
+    let graph = ctx.outputs[0].graph();
+    let products: Vec<Tensor<F>> = gxs
+        .iter()
+        .zip(&ctx.tangents)
+        .filter_map(|(gx, t)| Some(T::sum_all(graph.tensor((*gx)?) * (*t)?)))
+        .collect();
+    if products.is_empty() {
+        return vec![None; us.len()];
+    }
+    let s = T::add_n(&products);
+    T::grad(&[s], &us).into_iter().map(Some).collect()
+}
+
+/// Returns the standard basis of the space of `x`, with the basis vectors
+/// stacked along the first axis.
+pub(crate) fn basis<'g, F: Float>(x: &Tensor<'g, F>) -> Tensor<'g, F> {
+    Tensor::builder(x.graph())
+        .append_input(x, false)
+        .set_differentiable(false)
+        .build(Basis)
+}
+
+/// Identity matrix of the size of the input, reshaped to `[size, input shape...]`
+struct Basis;
+
+impl<F: Float> Op<F> for Basis {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let x = ctx.input(0);
+        let mut shape = vec![x.len()];
+        shape.extend_from_slice(x.shape());
+        let y = ndarray::Array2::<F>::eye(x.len())
+            .into_shape_with_order(shape)
+            .map_err(|e| OpError::NdArrayError("basis".into(), e))?;
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        ctx.append_input_grad(0, None);
+    }
+}
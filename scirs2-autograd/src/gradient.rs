@@ -1,12 +1,14 @@
 use crate::graph::TensorID;
+use crate::op::GradientContext;
 use crate::op::SmallVec;
 use crate::tensor::Tensor;
 use crate::tensor_ops as T;
 use crate::Float;
 use crate::FxHashMap;
-use crate::Graph;
+use crate::{Context, Graph};
 use std::cmp::Ordering;
 use std::collections::binary_heap::BinaryHeap;
+use std::marker::PhantomData;
 
 /// Returns gradient tensors of `xs`.
 ///
@@ -33,12 +35,12 @@ where
     if let Some(gys) = gys {
         assert_eq!(ys.len(), gys.len(), "`ys.len()` must match `gys.len()`");
         for (y, &gy) in ys.iter().zip(gys) {
-            grad_map.push_grad(y.as_ref().id, gy);
+            grad_map.push_grad(y.as_ref().id, 0, gy);
         }
     } else {
         let start_gy = T::scalar(F::one(), g);
         for y in ys.iter() {
-            grad_map.push_grad(y.as_ref().id, start_gy);
+            grad_map.push_grad(y.as_ref().id, 0, start_gy);
         }
     }
 
@@ -53,196 +55,29 @@ where
         let gxs = {
             let y_grad_info = grad_map.get_mut(y.id);
             // Skip nodes with no gradients
-            if y_grad_info.gradients.is_empty() {
+            if y_grad_info.is_empty() {
                 let y_tensor = g.tensor(y.id);
                 let num_inputs = y_tensor.num_backprop_inputs();
                 let gxs = vec![None; num_inputs];
                 debug_assert_eq!(y_tensor.num_backprop_inputs(), gxs.len());
                 gxs
             } else {
-                // Enhanced approach that tries to generate better gradients
-                // while still avoiding the lifetime issues with GradientContext
-                let gy = y_grad_info.gradient();
-                let y_tensor = g.tensor(y.id);
-
-                // Get the operation type from the tensor
-                let op_name = y_tensor.inner().get_op().name();
-                println!("DEBUG gradient.rs: Processing op '{}'", op_name);
-
-                // Get the input tensors
-                let num_inputs = y_tensor.num_backprop_inputs();
-                let mut gxs = Vec::with_capacity(num_inputs);
-
-                // Function to create a gradient based on input and output shapes
-                // This is still a simplified approach but better than just returning
-                // scalar ones for every operation
-                for i in 0..num_inputs {
-                    let x_tensor = y_tensor.get_backprop_input(i);
-                    let _x_shape = match x_tensor.inner().known_shape {
-                        Some(ref shape) => shape.get().to_vec(),
-                        None => vec![1], // Default to scalar if shape unknown
-                    };
-
-                    // Check operation type to produce appropriate gradient
-                    // Handle both short names and fully qualified names
-                    let grad = if op_name.ends_with("AddOp") || op_name == "Add" {
-                        // For addition, gradient passes through unchanged
-                        Some(gy)
-                    } else if op_name.ends_with("SubOp") || op_name == "Sub" {
-                        // For subtraction, first input gets positive gradient, second gets negative
-                        if i == 0 {
-                            Some(gy)
-                        } else {
-                            Some(T::neg(gy))
-                        }
-                    } else if op_name.ends_with("MulOp") || op_name == "Mul" {
-                        // For multiplication: d(a*b)/da = b*grad_out, d(a*b)/db = a*grad_out
-                        if i == 0 {
-                            // d(a*b)/da = b * grad_out
-                            let b = y_tensor.get_backprop_input(1);
-                            Some(T::mul(b, gy))
-                        } else {
-                            // d(a*b)/db = a * grad_out
-                            let a = y_tensor.get_backprop_input(0);
-                            Some(T::mul(a, gy))
-                        }
-                    } else if op_name.ends_with("DivOp") || op_name == "Div" {
-                        // For division: d(a/b)/da = grad_out/b, d(a/b)/db = -a*grad_out/b^2
-                        if i == 0 {
-                            // d(a/b)/da = grad_out / b
-                            let b = y_tensor.get_backprop_input(1);
-                            Some(T::div(gy, b))
-                        } else {
-                            // d(a/b)/db = -a * grad_out / b^2
-                            let a = y_tensor.get_backprop_input(0);
-                            let b = y_tensor.get_backprop_input(1);
-                            let b_squared = T::mul(b, b);
-                            let neg_a = T::neg(a);
-                            let neg_a_gy = T::mul(neg_a, gy);
-                            Some(T::div(neg_a_gy, b_squared))
-                        }
-                    } else if op_name.contains("ReduceSumToScalar")
-                        || op_name.contains("ReduceSumAll")
-                        || op_name.contains("Sum")
-                        || op_name.contains("Mean")
-                    {
-                        // For reduction operations, gradient is broadcast back
-                        // For sum, gradient passes through; for mean, it's divided by count
-                        // For sum operations, gradient needs to be broadcast to input shape
-                        Some(gy)
-                    } else if op_name.ends_with("MatMulOp") || op_name == "MatMul" {
-                        // For matrix multiplication
-                        if i == 0 {
-                            // For first input in matmul (A in A*B), grad_A = grad_out * B^T
-                            let b = y_tensor.get_backprop_input(1);
-                            // Use positive indices [1, 0] instead of [-1, -2] for 2D transpose
-                            Some(T::matmul(gy, T::transpose(b, &[1, 0])))
-                        } else {
-                            // For second input in matmul (B in A*B), grad_B = A^T * grad_out
-                            let a = y_tensor.get_backprop_input(0);
-                            // Use positive indices [1, 0] instead of [-1, -2] for 2D transpose
-                            Some(T::matmul(T::transpose(a, &[1, 0]), gy))
-                        }
-                    } else if op_name.contains("Sigmoid") {
-                        // For sigmoid: dy/dx = sigmoid * (1 - sigmoid) * grad_out
-                        // Since we can't evaluate the output, we approximate
-                        let one = T::scalar(F::one(), g);
-                        let one_minus_y = T::sub(one, y_tensor);
-                        let dy_dx = T::mul(T::mul(y_tensor, one_minus_y), gy);
-                        Some(dy_dx)
-                    } else if op_name.contains("ReLU") {
-                        // For ReLU: dy/dx = (x > 0) * grad_out
-                        // Since we can't evaluate x, we pass through for now
-                        Some(gy)
-                    } else if op_name.contains("Tanh") {
-                        // For tanh: dy/dx = (1 - tanh^2) * grad_out
-                        let one = T::scalar(F::one(), g);
-                        let y_squared = T::mul(y_tensor, y_tensor);
-                        let one_minus_y_squared = T::sub(one, y_squared);
-                        Some(T::mul(one_minus_y_squared, gy))
-                    } else if op_name.contains("Softmax") {
-                        // For softmax: complex gradient, approximated as pass-through
-                        Some(gy)
-                    } else if op_name.contains("ExtractDiagOp") || op_name == "ExtractDiag" {
-                        // For extract diagonal: gradient goes to diagonal positions
-                        // The gradient gy is a vector, we need to create a diagonal matrix
-                        println!("  ExtractDiagOp: gy shape = {:?}", gy.shape());
-
-                        // ExtractDiag extracts the diagonal from a square matrix
-                        // So the gradient should be a diagonal matrix where the diagonal is gy
-                        let diag_grad = T::diag(gy);
-                        println!(
-                            "  ExtractDiagOp: created diagonal gradient with shape {:?}",
-                            diag_grad.shape()
-                        );
-                        Some(diag_grad)
-                    } else if op_name.contains("CheckpointOp") {
-                        // For checkpoint operations, pass through the gradient
-                        Some(gy)
-                    } else if op_name.contains("TraceOp") || op_name == "Trace" {
-                        // For trace operation: gradient is identity matrix scaled by grad_out
-                        // Since we can't get the runtime shape, we need to handle this differently
-                        // The gradient of trace is an identity matrix, but we don't know its size
-                        println!("DEBUG gradient.rs: TraceOp gradient computation");
-                        println!("  Input tensor id: {}", x_tensor.id());
-                        println!("  Gradient output shape: {:?}", gy.shape());
-
-                        // Since we can't determine the matrix size here, we'll return a placeholder
-                        // that will be handled during gradient accumulation
-                        // For now, just pass through the gradient
-                        Some(gy)
-                    } else if op_name.contains("MatrixInverseOp")
-                        || op_name.contains("MatrixInverse")
-                        || op_name == "MatInv"
-                    {
-                        // For matrix inverse: gradient = -A^{-T} @ grad_out @ A^{-T}
-                        // We have access to the output (which is A^{-1})
-                        let inv_transpose = T::transpose(y_tensor, &[1, 0]);
-                        let temp = T::matmul(inv_transpose, gy);
-                        let grad_before_neg = T::matmul(temp, inv_transpose);
-                        Some(T::neg(grad_before_neg))
-                    } else if op_name == "MatrixSqrt" {
-                        // For matrix square root: gradient would involve solving a Sylvester equation
-                        // For now, return zeros with the same shape as the input
-                        let x_tensor = y_tensor.get_backprop_input(0);
-                        let zero_scalar = T::scalar(F::zero(), g);
-                        let zeros = T::mul(x_tensor, zero_scalar);
-                        Some(zeros)
-                    } else if op_name == "MatrixLog" {
-                        // For matrix logarithm: gradient involves Fréchet derivative
-                        // For now, return zeros with the same shape as the input
-                        let x_tensor = y_tensor.get_backprop_input(0);
-                        let zero_scalar = T::scalar(F::zero(), g);
-                        let zeros = T::mul(x_tensor, zero_scalar);
-                        Some(zeros)
-                    } else if op_name == "MatrixPow" {
-                        // For matrix power: gradient is complex
-                        // For now, return zeros with the same shape as the input
-                        let x_tensor = y_tensor.get_backprop_input(0);
-                        let zero_scalar = T::scalar(F::zero(), g);
-                        let zeros = T::mul(x_tensor, zero_scalar);
-                        Some(zeros)
-                    } else {
-                        // Default case - return scalar one for unknown operations
-                        Some(T::scalar(F::one(), g))
-                    };
-
-                    gxs.push(grad);
-                }
-
-                gxs
+                let gys = y_grad_info.output_gradients();
+                op_input_grads(g.tensor(y.id), gys, g)
             }
         };
 
         // Register computed gradients
         let y = g.tensor(y.id);
-        for (x, gx) in y.inner().get_backprop_inputs().iter().zip(gxs) {
-            let x = x.as_tensor(g);
+        for (x_in, gx) in y.inner().get_backprop_inputs().iter().zip(gxs) {
+            let x = x_in.as_tensor(g);
+            // Gradients of outputs not declared by `Op::num_outputs` are dropped
+            let output = x_in.array_selector;
             let x_grad_info = grad_map.get_mut(x.id);
-            if x_grad_info.on_backprop_path {
+            if x_grad_info.on_backprop_path && output < num_outputs(x) {
                 if let Some(gx) = gx {
-                    let x_not_visited = x_grad_info.gradients.is_empty();
-                    grad_map.push_grad(x.id, gx);
+                    let x_not_visited = x_grad_info.is_empty();
+                    grad_map.push_grad(x.id, output, gx);
                     // update heap
                     if !x.is_source() && x_not_visited {
                         heap.push(x.to_node());
@@ -255,6 +90,63 @@ where
     grad_map
 }
 
+/// Number of outputs of the op of `x`
+fn num_outputs<F: Float>(x: Tensor<F>) -> usize {
+    x.inner().op.as_ref().map_or(1, |op| op.num_outputs())
+}
+
+/// Calls `Op::grad` of `y` and returns the gradients of its backprop inputs.
+///
+/// `gys` holds the gradients of the outputs of `y`, `None` for the outputs
+/// which don't affect the differentiated tensors.
+fn op_input_grads<'graph, F: Float>(
+    y: Tensor<'graph, F>,
+    gys: Vec<Option<Tensor<'graph, F>>>,
+    g: &'graph Graph<F>,
+) -> Vec<Option<Tensor<'graph, F>>> {
+    // The op is shared so that `Op::grad` can still evaluate `y` itself
+    let op = g
+        .access_inner(y.id)
+        .op
+        .clone()
+        .expect("bad impl: tensor without an Op");
+    let xs: Vec<Tensor<'graph, F>> = (0..y.num_backprop_inputs())
+        .map(|i| y.get_backprop_input(i))
+        .collect();
+    let x_refs: Vec<&Tensor<'graph, F>> = xs.iter().collect();
+
+    // Outputs without gradients get zeros
+    let zs: Vec<Tensor<'graph, F>> = (0..num_outputs(y))
+        .map(|i| if i == 0 { y } else { T::nth_tensor(y, i) })
+        .collect();
+    let gzs: Vec<Tensor<'graph, F>> = zs
+        .iter()
+        .zip(gys.into_iter().chain(std::iter::repeat(None)))
+        .map(|(z, gz)| gz.unwrap_or_else(|| T::zeros(&T::shape(z), g)))
+        .collect();
+    let z_refs: Vec<&Tensor<'graph, F>> = zs.iter().collect();
+    let gz_refs: Vec<&Tensor<'graph, F>> = gzs.iter().collect();
+
+    let mut ctx = GradientContext {
+        zs: &z_refs,
+        xs: &x_refs,
+        context: Context::from_graph(g),
+        gzs: &gz_refs,
+        results: Vec::new(),
+        array_field_id: 0,
+        _marker: PhantomData,
+    };
+    op.grad(&mut ctx);
+
+    let mut gxs: Vec<_> = ctx
+        .results
+        .iter()
+        .map(|gx| gx.map(|gx| g.tensor(gx.id)))
+        .collect();
+    gxs.resize(xs.len(), None);
+    gxs
+}
+
 // a graph node in a gradient subgraph
 struct Node {
     id: usize,
@@ -316,7 +208,7 @@ impl<'graph, F: Float> GradientMap<'graph, F> {
                     let zero_grad = Tensor::from_vec(zero_data, shape, g);
                     info.gradients.push(zero_grad);
                 }
-                return Some(info.gradient());
+                return info.gradient(0);
             }
         }
         // can't differentiate!
@@ -329,14 +221,24 @@ impl<'graph, F: Float> GradientMap<'graph, F> {
     }
 
     #[inline]
-    fn push_grad(&mut self, key: TensorID, grad: Tensor<'graph, F>) {
-        self.inner.get_mut(&key).unwrap().gradients.push(grad);
+    fn push_grad(&mut self, key: TensorID, output: usize, grad: Tensor<'graph, F>) {
+        let info = self.inner.get_mut(&key).unwrap();
+        if output == 0 {
+            info.gradients.push(grad);
+        } else {
+            if info.extra_gradients.len() < output {
+                info.extra_gradients.resize_with(output, SmallVec::new);
+            }
+            info.extra_gradients[output - 1].push(grad);
+        }
     }
 }
 
 // GradientInfo is keyed by a TensorID and holds its gradient info for back-prop
 struct GradientInfo<'graph, F: Float> {
     gradients: SmallVec<Tensor<'graph, F>>,
+    // gradients of the outputs after the first one of multi-output ops
+    extra_gradients: Vec<SmallVec<Tensor<'graph, F>>>,
     on_backprop_path: bool,
 }
 
@@ -346,18 +248,36 @@ impl<'graph, F: Float> GradientInfo<'graph, F> {
         GradientInfo {
             on_backprop_path,
             gradients: SmallVec::new(),
+            extra_gradients: Vec::new(),
         }
     }
 
     #[inline]
-    fn gradient(&mut self) -> Tensor<'graph, F> {
-        if self.gradients.is_empty() {
-            panic!("No gradients available")
-        } else if self.gradients.len() > 1 {
+    fn is_empty(&self) -> bool {
+        self.gradients.is_empty() && self.extra_gradients.iter().all(|g| g.is_empty())
+    }
+
+    /// Sum of the gradients of the `output`-th output, if any
+    #[inline]
+    fn gradient(&mut self, output: usize) -> Option<Tensor<'graph, F>> {
+        let gradients = match output {
+            0 => &mut self.gradients,
+            i => self.extra_gradients.get_mut(i - 1)?,
+        };
+        if gradients.len() > 1 {
             // the accumulated gradients are added together at this time.
-            self.gradients[0] = T::add_n(self.gradients.as_slice());
+            let sum = T::add_n(gradients.as_slice());
+            gradients.clear();
+            gradients.push(sum);
         }
-        self.gradients[0]
+        gradients.first().copied()
+    }
+
+    /// Gradients of all the outputs received so far
+    fn output_gradients(&mut self) -> Vec<Option<Tensor<'graph, F>>> {
+        (0..=self.extra_gradients.len())
+            .map(|i| self.gradient(i))
+            .collect()
     }
 }
 
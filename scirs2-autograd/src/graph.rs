@@ -8,8 +8,9 @@ use crate::{tensor_ops as T, Evaluator};
 use crate::{Float, NdArray, VariableEnvironment};
 use std::collections::{HashMap, HashSet};
 
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::fmt;
+use std::marker::PhantomData;
 use std::ops::Deref;
 
 pub type TensorID = usize;
@@ -21,6 +22,68 @@ pub type TensorID = usize;
 pub struct Graph<F: Float> {
     pub(crate) node_set: RefCell<Vec<TensorInternal<F>>>,
     pub(crate) variable2node: RefCell<HashMap<VariableID, TensorID>>,
+    /// Variable environment of the `Context` owning this graph (null if none).
+    ///
+    /// A pointer rather than a reference so that a `Context` can be recovered
+    /// from its graph (see [Context::from_graph]) without `Graph` carrying the
+    /// environment's lifetime. Private to this module, which keeps the
+    /// invariant that makes dereferencing it sound: it is only set by
+    /// `Graph::with_env`, for a graph owned by a `Context<'env, F>` whose
+    /// environment is borrowed for `'env`.
+    var_env: *const VariableEnvironment<F>,
+    /// Peak size in bytes of the arrays held at once by the last evaluation.
+    pub(crate) peak_memory: Cell<usize>,
+    /// Whether evaluations fail on NaN or infinite values, see [Context::check_numerics].
+    pub(crate) check_numerics: Cell<bool>,
+}
+
+/// Bytes held by the arrays of an evaluation
+#[derive(Default)]
+struct MemoryUsage {
+    live: usize,
+    peak: usize,
+}
+
+impl MemoryUsage {
+    #[inline]
+    fn alloc<F: Float>(&mut self, array: &NdArray<F>) {
+        self.live += array.len() * std::mem::size_of::<F>();
+        self.peak = self.peak.max(self.live);
+    }
+
+    #[inline]
+    fn free<F: Float>(&mut self, array: &NdArray<F>) {
+        self.live -= array.len() * std::mem::size_of::<F>();
+    }
+}
+
+/// Returns the error for the NaN or infinite values of `array`, if any.
+///
+/// `source` is the op (or placeholder/variable) which produced the `output`-th
+/// output of the tensor `id`.
+fn check_finite<F: Float>(
+    array: &NdArray<F>,
+    source: &str,
+    id: TensorID,
+    output: usize,
+) -> Result<(), OpError> {
+    let nans = array.iter().filter(|a| a.is_nan()).count();
+    let infs = array.iter().filter(|a| a.is_infinite()).count();
+    if nans + infs == 0 {
+        return Ok(());
+    }
+    Err(OpError::NonFinite {
+        op: source.to_string(),
+        tensor: id,
+        detail: format!(
+            "{} NaN and {} infinite values of {} in output {} of shape {:?}",
+            nans,
+            infs,
+            array.len(),
+            output,
+            array.shape()
+        ),
+    })
 }
 
 pub const NUM_NODES_WARN: usize = 50_000;
@@ -76,8 +139,24 @@ impl<'graph, F: Float> Graph<F> {
             collect_nodes_topo(tensor.id, ctx.as_graph(), &mut eval_nodes, &mut visited);
         }
 
+        // Number of pending consumers of each node's value. Intermediate
+        // values are dropped as soon as their last consumer has been computed.
+        let mut remaining_uses: HashMap<TensorID, usize> = HashMap::new();
+        for &node_id in &eval_nodes {
+            for input_node in &ctx.as_graph().access_inner(node_id).incoming_nodes {
+                *remaining_uses.entry(input_node.id).or_insert(0) += 1;
+            }
+        }
+        let target_ids: HashSet<TensorID> = tensors.iter().map(|t| t.id).collect();
+
         // Map to store computed values for each node
         let mut computed_values: HashMap<TensorID, NdArray<F>> = HashMap::new();
+        // Outputs after the first one of multi-output ops
+        let mut extra_outputs: HashMap<TensorID, Vec<NdArray<F>>> = HashMap::new();
+        let mut memory = MemoryUsage::default();
+        let check_numerics = ctx.as_graph().check_numerics.get();
+        // Errors of the nodes which could not be computed, passed on to their consumers
+        let mut errors: HashMap<TensorID, OpError> = HashMap::new();
 
         // Add feed values to the computed values
         for (&id, &feed_view) in feeds.iter() {
@@ -85,6 +164,13 @@ impl<'graph, F: Float> Graph<F> {
             unsafe {
                 let view: NdArrayView<F> = std::mem::transmute(feed_view.clone());
                 let owned_array = view.to_owned();
+                if check_numerics {
+                    if let Err(err) = check_finite(&owned_array, "feed", id, 0) {
+                        errors.insert(id, err);
+                        continue;
+                    }
+                }
+                memory.alloc(&owned_array);
                 computed_values.insert(id, owned_array);
             }
         }
@@ -92,7 +178,7 @@ impl<'graph, F: Float> Graph<F> {
         // Evaluate nodes in topological order
         for node_id in eval_nodes {
             // Skip if already computed (e.g., from feeds)
-            if computed_values.contains_key(&node_id) {
+            if computed_values.contains_key(&node_id) || errors.contains_key(&node_id) {
                 continue;
             }
 
@@ -101,117 +187,127 @@ impl<'graph, F: Float> Graph<F> {
             // If this is a variable node, fetch its data from the VariableEnvironment
             if let Some(variable_id) = node.variable_id {
                 // Get the variable data from the environment
-                if let Some(var_array) = ctx.var_env_ref.get_array_by_id(variable_id) {
+                if let Some(var_array) = ctx.var_env_ref().get_array_by_id(variable_id) {
                     let borrowed_array = var_array.borrow();
                     let cloned_array = borrowed_array.clone();
+                    if check_numerics {
+                        if let Err(err) = check_finite(&cloned_array, "variable", node_id, 0) {
+                            errors.insert(node_id, err);
+                            continue;
+                        }
+                    }
+                    memory.alloc(&cloned_array);
                     computed_values.insert(node_id, cloned_array);
-                    continue;
                 } else {
                     let err = OpError::RuntimeError(format!(
                         "Variable with ID {} not found in VariableEnvironment",
                         variable_id
                     ));
-
-                    // If this is one of our target tensors, add an error to the result
-                    for tensor in tensors {
-                        if tensor.id == node_id {
-                            results.push(Err(err.clone()));
-                        }
-                    }
-                    continue;
+                    errors.insert(node_id, err);
                 }
+                continue;
             }
 
             // If this is a placeholder but no feed was provided, return an error
-            if node.placeholder_name.is_some() && !computed_values.contains_key(&node_id) {
-                let placeholder_name = node.placeholder_name.unwrap_or("<unnamed>");
+            if let Some(placeholder_name) = node.placeholder_name {
                 let err = OpError::RuntimeError(format!(
                     "No feed value provided for placeholder '{}'",
                     placeholder_name
                 ));
-
-                // If this is one of our target tensors, add an error to the result
-                for tensor in tensors {
-                    if tensor.id == node_id {
-                        results.push(Err(err.clone()));
-                    }
-                }
-
-                // Skip this node since we can't compute it
+                errors.insert(node_id, err);
                 continue;
             }
 
-            // Get inputs for this operation
-            let mut input_arrays = Vec::with_capacity(node.incoming_nodes.len());
-
             // Collect input arrays from computed values
+            let mut input_arrays = Vec::with_capacity(node.incoming_nodes.len());
+            let mut input_error = None;
             for input_node in &node.incoming_nodes {
-                if let Some(input_array) = computed_values.get(&input_node.id) {
+                let input_array = match input_node.array_selector {
+                    0 => computed_values.get(&input_node.id),
+                    i => extra_outputs
+                        .get(&input_node.id)
+                        .and_then(|outputs| outputs.get(i - 1)),
+                };
+                if let Some(input_array) = input_array {
                     input_arrays.push(input_array.clone());
                 } else {
-                    // If an input wasn't computed, there's a bug in our topological sort
-                    let err = OpError::RuntimeError(format!(
-                        "Input node {} for node {} was not computed - possible cycle in graph",
-                        input_node.id, node_id
-                    ));
-
-                    // If this is one of our target tensors, add an error to the result
-                    for tensor in tensors {
-                        if tensor.id == node_id {
-                            results.push(Err(err.clone()));
-                        }
-                    }
-
-                    // Skip this node
-                    continue;
+                    // The input failed, or the op didn't produce the selected output
+                    let err = errors.get(&input_node.id).cloned().unwrap_or_else(|| {
+                        OpError::RuntimeError(format!(
+                            "Output {} of node {} for node {} was not computed",
+                            input_node.array_selector, input_node.id, node_id
+                        ))
+                    });
+                    input_error = Some(err);
+                    break;
                 }
             }
 
-            // We no longer need a separate output_arrays variable
-
-            // Create compute context with cloned input arrays
-            let cloned_inputs = input_arrays.clone();
-            let mut compute_ctx = op::ComputeContext::with_inputs(cloned_inputs);
-
             // Execute the operation
-            match node.get_op().compute(&mut compute_ctx) {
-                Ok(()) => {
-                    // Operation succeeded, store the output
-                    let outputs = compute_ctx.get_outputs();
-                    if !outputs.is_empty() {
-                        computed_values.insert(node_id, outputs[0].clone());
-                    } else {
-                        // Operation produced no output
-                        let err = OpError::RuntimeError(format!(
-                            "Operation {} did not produce any output",
-                            node.get_op().name()
-                        ));
-
-                        // If this is one of our target tensors, add an error to the result
-                        for tensor in tensors {
-                            if tensor.id == node_id {
-                                results.push(Err(err.clone()));
+            let result = match input_error {
+                Some(err) => Err(err),
+                None => {
+                    let mut compute_ctx = op::ComputeContext::with_inputs(input_arrays);
+                    crate::device::compute(node.device, node.get_op(), &mut compute_ctx).and_then(
+                        |()| {
+                            if compute_ctx.outputs.is_empty() {
+                                Err(OpError::RuntimeError(format!(
+                                    "Operation {} did not produce any output",
+                                    node.get_op().name()
+                                )))
+                            } else if check_numerics {
+                                // The inputs were checked already, so this op is
+                                // the origin of the non-finite values.
+                                for (k, output) in compute_ctx.outputs.iter().enumerate() {
+                                    check_finite(output, node.get_op().name(), node_id, k)?;
+                                }
+                                Ok(compute_ctx.outputs)
+                            } else {
+                                Ok(compute_ctx.outputs)
                             }
-                        }
+                        },
+                    )
+                }
+            };
+            match result {
+                Ok(mut outputs) => {
+                    for output in &outputs {
+                        memory.alloc(output);
+                    }
+                    let rest = outputs.split_off(1);
+                    computed_values.insert(node_id, outputs.pop().unwrap());
+                    if !rest.is_empty() {
+                        extra_outputs.insert(node_id, rest);
                     }
                 }
                 Err(err) => {
-                    // Operation failed
-                    // If this is one of our target tensors, add an error to the result
-                    for tensor in tensors {
-                        if tensor.id == node_id {
-                            results.push(Err(err.clone()));
+                    errors.insert(node_id, err);
+                }
+            }
+
+            // Release the inputs no other node is waiting for
+            for input_node in &node.incoming_nodes {
+                if let Some(uses) = remaining_uses.get_mut(&input_node.id) {
+                    *uses -= 1;
+                    if *uses == 0 && !target_ids.contains(&input_node.id) {
+                        if let Some(array) = computed_values.remove(&input_node.id) {
+                            memory.free(&array);
+                        }
+                        for array in extra_outputs.remove(&input_node.id).unwrap_or_default() {
+                            memory.free(&array);
                         }
                     }
                 }
             }
         }
+        ctx.as_graph().peak_memory.set(memory.peak);
 
         // Collect results for the requested tensors
-        results.clear(); // Clear any error results added during evaluation
         for tensor in tensors {
             if let Some(value) = computed_values.get(&tensor.id) {
                 results.push(Ok(value.clone()));
+            } else if let Some(err) = errors.get(&tensor.id) {
+                results.push(Err(err.clone()));
             } else {
                 results.push(Err(OpError::RuntimeError(format!(
                     "Failed to compute tensor {}",
@@ -277,6 +373,23 @@ impl<'graph, F: Float> Graph<F> {
         Tensor { id, graph: self }
     }
 
+    /// Creates an empty graph owned by a `Context` with the given environment.
+    ///
+    /// The graph must be moved into a `Context<'env, F>` right away, with `env`
+    /// borrowed for `'env`, as done by [run] and [VariableEnvironment::run].
+    /// Their closures only get `&mut Context<'env, F>`, and `&mut` is invariant,
+    /// so the graph can only be swapped into another context of the same
+    /// `'env`, whose environment is borrowed for `'env` as well.
+    pub(crate) fn with_env(env: &VariableEnvironment<F>, capacity: usize) -> Self {
+        Graph {
+            node_set: RefCell::new(Vec::with_capacity(capacity)),
+            variable2node: RefCell::new(HashMap::new()),
+            var_env: env,
+            peak_memory: Cell::new(0),
+            check_numerics: Cell::new(false),
+        }
+    }
+
     #[inline]
     pub(crate) fn topo_rank(&self, id: TensorID) -> usize {
         self.node_set.borrow()[id].topo_rank
@@ -322,13 +435,10 @@ where
     F: Float,
     FN: FnOnce(&mut Context<F>) -> R,
 {
-    let graph_internal = Graph {
-        node_set: RefCell::new(Vec::with_capacity(512)),
-        variable2node: RefCell::new(HashMap::new()),
-    };
+    let env = VariableEnvironment::new();
     let mut ctx = Context {
-        var_env_ref: &mut VariableEnvironment::new(),
-        graph: graph_internal,
+        graph: Graph::with_env(&env, 512),
+        _env: PhantomData,
     };
     f(&mut ctx)
 }
@@ -340,18 +450,48 @@ where
 ///
 /// In order to bind `Tensor`s to pre-defined variable arrays, use [VariableEnvironment::run] instead.
 /// See [crate::variable]
+#[repr(transparent)]
 pub struct Context<'env, F: Float> {
     pub(crate) graph: Graph<F>,
-    pub(crate) var_env_ref: &'env VariableEnvironment<F>,
+    pub(crate) _env: PhantomData<&'env VariableEnvironment<F>>,
 }
 
 impl<'graph, 'env, F: Float> Context<'env, F> {
+    /// Views a graph as its `Context`.
+    ///
+    /// Backprop, [jvp](crate::tensor_ops::jvp) and [vmap](crate::tensor_ops::vmap)
+    /// walk the graph with only `&Graph` at hand (from the tensors), but the
+    /// `GradientContext` passed to `Op::grad` holds a `&Context`, through which
+    /// ops may build and evaluate tensors.
+    #[inline]
+    pub(crate) fn from_graph(graph: &Graph<F>) -> &Context<'_, F> {
+        // SAFETY: `Context` is `repr(transparent)` over `Graph` (its other
+        // field is a zero-sized `PhantomData`), so the cast keeps layout and
+        // validity, and the result borrows `graph`. Its `'env` is that borrow,
+        // which the environment outlives (see `Graph::var_env`); graphs without
+        // an environment hold null, which `var_env_ref` rejects.
+        unsafe { &*(graph as *const Graph<F> as *const Context<F>) }
+    }
+
+    /// The variable environment this context is bound to.
+    #[inline]
+    pub(crate) fn var_env_ref(&self) -> &'env VariableEnvironment<F> {
+        assert!(
+            !self.graph.var_env.is_null(),
+            "This graph is not bound to a VariableEnvironment"
+        );
+        // SAFETY: non-null pointers are only set by `Graph::with_env`, for a
+        // graph owned by a context whose environment is borrowed for 'env, or
+        // viewed by `from_graph` for a shorter 'env (see `Graph::var_env`).
+        unsafe { &*self.graph.var_env }
+    }
+
     /// Get or create a variable namespace with the specified name.
     ///
     /// Use `namespace_mut` for mutable operations such as variables registrations.
     #[inline]
     pub fn namespace(&'env self, namespace_id: &'static str) -> VariableNamespace<'env, F> {
-        self.var_env_ref.namespace(namespace_id)
+        self.var_env_ref().namespace(namespace_id)
     }
 
     /// Get or create the *default* variable namespace.
@@ -359,13 +499,13 @@ impl<'graph, 'env, F: Float> Context<'env, F> {
     /// Use `namespace_mut` for mutable operations such as variables registrations.
     #[inline]
     pub fn default_namespace(&'env self) -> VariableNamespace<'env, F> {
-        self.var_env_ref.default_namespace()
+        self.var_env_ref().default_namespace()
     }
 
     /// Returns a reference to the current VariableEnvironment
     #[inline]
     pub fn env(&'graph self) -> &'env VariableEnvironment<F> {
-        self.var_env_ref
+        self.var_env_ref()
     }
 
     /// Creates an evaluator for the graph.
@@ -392,6 +532,46 @@ impl<'graph, 'env, F: Float> Context<'env, F> {
         Graph::eval_tensors(tensors, &temp_feeds, self)
     }
 
+    /// Peak size in bytes of the arrays held at once by the last evaluation.
+    ///
+    /// Intermediate arrays are dropped as soon as no pending operation needs
+    /// them, so this is the memory footprint of the evaluation (e.g. the
+    /// activations kept for backprop) rather than the sum of all values.
+    #[inline]
+    pub fn peak_memory_bytes(&self) -> usize {
+        self.graph.peak_memory.get()
+    }
+
+    /// Makes the evaluations of this graph fail on NaN or infinite values (off by default).
+    ///
+    /// The values are checked as soon as they are computed, so the error of the
+    /// tensors depending on them, [OpError::NonFinite], names the op (e.g. a
+    /// gradient op during backprop) which produced them from finite inputs, or the
+    /// variable or feed holding them.
+    ///
+    ///    ```
+    /// use scirs2_autograd as ag;
+    /// use ag::tensor_ops as T;
+    ///
+    /// ag::run(|ctx| {
+    ///     ctx.check_numerics(true);
+    ///     let x = ctx.placeholder("x", &[2]);
+    ///     let y = T::sum_all(T::sqrt(x));
+    ///     let gx = T::grad(&[y], &[x])[0];
+    ///
+    ///     let result = ctx.evaluator()
+    ///         .push(&gx)
+    ///         .feed(x, ag::ndarray::arr1(&[0., 1.]).view().into_dyn())
+    ///         .run();
+    ///     // infinite gradient of `sqrt` at 0
+    ///     assert!(matches!(result[0], Err(ag::EvalError::OpError(ag::OpError::NonFinite { .. }))));
+    /// });
+    ///    ```
+    #[inline]
+    pub fn check_numerics(&self, enabled: bool) {
+        self.graph.check_numerics.set(enabled);
+    }
+
     /// Removes all tensors in this graph.
     ///
     /// Note that any tensors allocated prior to this method call are invalid.
@@ -498,7 +678,7 @@ impl<F: Float> AsGraph<F> for Context<'_, F> {
 
     #[inline]
     fn env_ref(&self) -> &VariableEnvironment<F> {
-        self.var_env_ref
+        self.var_env_ref()
     }
 
     #[inline]
@@ -512,6 +692,9 @@ impl<F: Float> Default for Graph<F> {
         Self {
             node_set: RefCell::new(Vec::new()),
             variable2node: RefCell::new(HashMap::new()),
+            var_env: std::ptr::null(),
+            peak_memory: Cell::new(0),
+            check_numerics: Cell::new(false),
         }
     }
 }
@@ -28,8 +28,25 @@
 //! });
 //!
 //! ```
+//!
+//! Gradient hooks are called with the gradient of a tensor during backprop instead,
+//! and can modify it.
+//! ```
+//! use scirs2_autograd as ag;
+//! use ag::tensor_ops as T;
+//!
+//! ag::run(|ctx| {
+//!    let x = ctx.placeholder("x", &[3]);
+//!    let h = T::square(x)
+//!       .grad_hook(|g| println!("grad of h: {}", g))
+//!       .map_grad(|g| g.mapv(|a: f32| a.clamp(-1., 1.)));
+//!    let y = T::sum_all(T::exp(h));
+//!    let gx = T::grad(&[y], &[x])[0];
+//!    gx.eval(ctx);
+//! });
+//! ```
 use super::*;
-use crate::ndarray_ext::NdArrayView;
+use crate::ndarray_ext::{NdArray, NdArrayView};
 use std::marker::PhantomData;
 
 /// Trait for hooks
@@ -39,6 +56,39 @@ pub trait Hook<T: Float> {
     fn call(&self, arr: &crate::ndarray::ArrayViewD<T>);
 }
 
+/// Trait for gradient hooks
+///
+/// gradient hooks can be set using [crate::tensor::Tensor::register_grad_hook()] method.
+/// They are called with the gradient of the tensor whenever it is evaluated, and the
+/// returned array, if any, replaces the gradient propagated to the inputs of the tensor.
+///
+/// The hooks of this module are gradient hooks as well, which only inspect the gradient.
+pub trait GradHook<T: Float> {
+    fn call(&self, grad: &crate::ndarray::ArrayViewD<T>) -> Option<NdArray<T>>;
+}
+
+macro_rules! impl_grad_hook {
+    ($($name:ty),*) => {
+        $(
+            impl<T: Float> GradHook<T> for $name {
+                fn call(&self, grad: &NdArrayView<T>) -> Option<NdArray<T>> {
+                    Hook::call(self, grad);
+                    None
+                }
+            }
+        )*
+    };
+}
+
+impl_grad_hook!(Print, Show, ShowPrefixed, ShowShape, ShowPrefixedShape);
+
+impl<T: Float, FUN: Fn(&NdArrayView<T>) + Send + Sync> GradHook<T> for Raw<T, FUN> {
+    fn call(&self, grad: &NdArrayView<T>) -> Option<NdArray<T>> {
+        (self.raw)(grad);
+        None
+    }
+}
+
 pub struct Print(pub &'static str);
 
 pub struct Show;
@@ -90,3 +140,17 @@ impl<T: Float> Hook<T> for ShowPrefixedShape {
         println!("{}\n{:?}", self.0, arr.shape());
     }
 }
+
+// Replaces gradients with the result of the given function.
+pub struct MapGrad<T: Float, FUN: Fn(&NdArrayView<T>) -> NdArray<T> + Send + Sync> {
+    pub(crate) map: FUN,
+    pub(crate) phantom: PhantomData<T>,
+}
+
+impl<T: Float, FUN: Fn(&NdArrayView<T>) -> NdArray<T> + Send + Sync> GradHook<T>
+    for MapGrad<T, FUN>
+{
+    fn call(&self, grad: &NdArrayView<T>) -> Option<NdArray<T>> {
+        Some((self.map)(grad))
+    }
+}
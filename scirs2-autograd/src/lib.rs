@@ -104,6 +104,10 @@
 //! ### Other useful features
 //! - [Model persistence](variable#model-persistence)
 //! - [Variable namespace](variable#variable-and-namespace)
+//! - [Mixed precision](mixed_precision)
+//! - [GPU execution](device)
+//! - [Sparse tensors](sparse)
+//! - [Gradient hooks](hooks), [NaN/Inf checks](Context::check_numerics) and [debug logging](tracing::set_debug_logging)
 
 #[allow(unused_imports)]
 // Expose to prevent version conflict
@@ -129,13 +133,17 @@ pub(crate) extern crate smallvec;
 extern crate special;
 extern crate uuid;
 
+pub mod complex;
+pub mod device;
 pub mod error;
 pub mod evaluation;
+mod forward;
 mod gradient;
 pub mod gradient_clipping;
 pub mod graph;
 pub mod hooks;
 pub mod integration;
+pub mod mixed_precision;
 pub mod ndarray_ext;
 pub mod op;
 pub mod optimization;
@@ -143,6 +151,8 @@ pub mod optimizers;
 pub mod parallel;
 pub mod prelude;
 pub mod schedulers;
+pub mod serialization;
+pub mod sparse;
 pub mod tensor;
 pub mod tensor_ops;
 pub mod test_helper;
@@ -150,6 +160,7 @@ pub mod testing;
 pub mod tracing;
 pub mod variable;
 pub mod visualization;
+mod vmap;
 
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::any::TypeId;
@@ -231,6 +242,8 @@ pub use crate::evaluation::{Evaluator, Feeder};
 
 pub use crate::tensor::Tensor;
 
+pub use crate::complex::ComplexTensor;
+
 pub(crate) use graph::Graph;
 
 pub use crate::error::{AutogradError, EvalError, OpError, Result};
@@ -0,0 +1,206 @@
+//! Mixed-precision training with half-precision tensors
+//!
+//! [f16] and [bf16] implement [Float], so graphs and variables can be built on
+//! them to halve the memory taken by parameters and activations. Matrix
+//! multiplications and sum/mean reductions of these types accumulate in `f32`.
+//!
+//! Small gradients underflow in the narrow range of `f16`. [LossScaler] scales
+//! the loss up before the differentiation and the gradients back down, and
+//! tells which updates to skip because their gradients overflowed.
+//!
+//! ```
+//! use scirs2_autograd as ag;
+//! use ag::mixed_precision::{cast, f16, LossScaler};
+//! use ag::prelude::*;
+//! use ag::tensor_ops as T;
+//!
+//! let mut env = ag::VariableEnvironment::<f16>::new();
+//! let w = ag::ndarray_ext::glorot_uniform::<f32>(&[4, 2]);
+//! let w_id = env.name("w").set(cast(&w.view()));
+//! let lr = f16::from_f32(0.01);
+//! let mut scaler = LossScaler::new();
+//!
+//! let x = cast(&ag::ndarray_ext::glorot_uniform::<f32>(&[8, 4]).view());
+//! for _ in 0..3 {
+//!     env.run(|g| {
+//!         let w = g.variable("w");
+//!         let y = T::matmul(g.placeholder("x", &[-1, 4]), w);
+//!         let loss = T::reduce_mean(T::square(y), &[0, 1], false);
+//!         let grads = T::grad(&[scaler.scale(loss)], &[w]);
+//!         let feeder = ag::Feeder::new().push("x", x.view());
+//!         // Skipped if the gradients overflowed
+//!         if let Some(grads) = scaler.unscaled_grads(&grads, g, feeder) {
+//!             let mut w = g.env().get_array_by_id(w_id).unwrap().borrow_mut();
+//!             w.zip_mut_with(&grads[0], |w, &gw| *w -= lr * gw);
+//!         }
+//!     });
+//! }
+//! ```
+
+use crate::evaluation::Feeder;
+use crate::ndarray_ext::{NdArray, NdArrayView};
+use crate::tensor::Tensor;
+use crate::tensor_ops as T;
+use crate::{same_type, Context, Float};
+
+pub use half::{bf16, f16};
+
+/// Returns `true` if `F` is a half-precision type, i.e. [f16] or [bf16].
+#[inline]
+pub fn is_half<F: Float>() -> bool {
+    same_type::<F, f16>() || same_type::<F, bf16>()
+}
+
+/// Converts the elements of `x` to another float type.
+///
+/// Values out of the range of `B` become infinite.
+pub fn cast<A: Float, B: Float>(x: &NdArrayView<A>) -> NdArray<B> {
+    x.mapv(|a| B::from(a).unwrap_or_else(|| B::nan()))
+}
+
+/// Dynamic loss scaling for half-precision training.
+///
+/// The scale is multiplied by the growth factor after `growth_interval`
+/// consecutive steps with finite gradients, and by the backoff factor after
+/// each step whose gradients are not finite.
+#[derive(Debug, Clone)]
+pub struct LossScaler {
+    scale: f64,
+    growth_factor: f64,
+    backoff_factor: f64,
+    growth_interval: usize,
+    dynamic: bool,
+    good_steps: usize,
+    skipped_steps: usize,
+}
+
+impl LossScaler {
+    /// Creates a dynamic loss scaler starting at `2^15`.
+    pub fn new() -> Self {
+        Self {
+            scale: 32768.,
+            growth_factor: 2.,
+            backoff_factor: 0.5,
+            growth_interval: 2000,
+            dynamic: true,
+            good_steps: 0,
+            skipped_steps: 0,
+        }
+    }
+
+    /// Creates a loss scaler which keeps `scale`, but still skips the steps
+    /// whose gradients are not finite.
+    pub fn fixed(scale: f64) -> Self {
+        Self {
+            scale,
+            dynamic: false,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the initial scale.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the factor applied to the scale after `growth_interval` good steps.
+    pub fn with_growth_factor(mut self, factor: f64) -> Self {
+        self.growth_factor = factor;
+        self
+    }
+
+    /// Sets the factor applied to the scale after a step with non-finite gradients.
+    pub fn with_backoff_factor(mut self, factor: f64) -> Self {
+        self.backoff_factor = factor;
+        self
+    }
+
+    /// Sets the number of consecutive good steps before the scale grows.
+    pub fn with_growth_interval(mut self, interval: usize) -> Self {
+        self.growth_interval = interval;
+        self
+    }
+
+    /// Current scale.
+    pub fn loss_scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Number of steps skipped because of non-finite gradients.
+    pub fn skipped_steps(&self) -> usize {
+        self.skipped_steps
+    }
+
+    /// Multiplies `loss` by the current scale.
+    pub fn scale<'g, F: Float>(&self, loss: Tensor<'g, F>) -> Tensor<'g, F> {
+        let scale = F::from(self.scale).unwrap_or_else(F::infinity);
+        loss * T::scalar(scale, loss.graph())
+    }
+
+    /// Divides the gradients of a scaled loss by the current scale.
+    ///
+    /// Returns `false` if any of the gradients is not finite.
+    pub fn unscale<F: Float>(&self, grads: &mut [NdArray<F>]) -> bool {
+        let mut finite = true;
+        for grad in grads.iter_mut() {
+            grad.mapv_inplace(|a| {
+                finite &= a.is_finite();
+                F::from(a.to_f64().unwrap() / self.scale).unwrap()
+            });
+        }
+        finite
+    }
+
+    /// Updates the scale after a step; `finite` tells whether its gradients were finite.
+    pub fn update(&mut self, finite: bool) {
+        if finite {
+            self.good_steps += 1;
+            if self.dynamic && self.good_steps >= self.growth_interval {
+                self.scale *= self.growth_factor;
+                self.good_steps = 0;
+            }
+        } else {
+            self.skipped_steps += 1;
+            self.good_steps = 0;
+            if self.dynamic {
+                self.scale *= self.backoff_factor;
+            }
+        }
+    }
+
+    /// Evaluates the gradients of a loss scaled with [LossScaler::scale] and
+    /// updates the scale.
+    ///
+    /// Returns the unscaled gradients, or `None` if they are not finite and the
+    /// update of the parameters must be skipped.
+    pub fn unscaled_grads<'g, F, A>(
+        &mut self,
+        grads: &[A],
+        g: &'g Context<F>,
+        feeder: Feeder<F>,
+    ) -> Option<Vec<NdArray<F>>>
+    where
+        F: Float,
+        A: AsRef<Tensor<'g, F>> + Copy,
+    {
+        let grads: Vec<Tensor<'g, F>> = grads.iter().map(|gx| *gx.as_ref()).collect();
+        let mut values: Vec<NdArray<F>> = g
+            .evaluator()
+            .set_feeder(feeder)
+            .extend(&grads)
+            .run()
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect();
+        let finite = self.unscale(&mut values);
+        self.update(finite);
+        finite.then_some(values)
+    }
+}
+
+impl Default for LossScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -47,19 +47,107 @@
 //! }
 //! ```
 //!
+//! ## Contract
+//!
+//! [Op::compute] is called each time the tensor is evaluated, possibly several
+//! times per evaluation (e.g. when recomputed by checkpointing):
+//! - `ctx.input(i)` is the value of the `i`-th tensor given to
+//!   [TensorBuilder::append_input](crate::tensor::TensorBuilder::append_input).
+//! - It must append [Op::num_outputs] arrays with [ComputeContext::append_output], in order.
+//! - Invalid inputs are reported by returning an [OpError] instead of panicking;
+//!   the error is returned by the evaluation of the tensor.
+//!
+//! [Op::grad] is called once per differentiation, while the gradient graph is built:
+//! - Gradients are built as tensors from [GradientContext::input],
+//!   [GradientContext::output] and [GradientContext::output_grad]
+//!   (or `nth_output`/`nth_output_grad`). They must not be evaluated there, since
+//!   the placeholders are not fed yet.
+//! - The gradient of an output has the shape of that output. It is zeros for
+//!   the outputs that don't affect the differentiated tensors.
+//! - The gradient of the `i`-th input, with the shape of that input, is given with
+//!   [GradientContext::append_input_grad]; `None` or a missing index means it
+//!   is not differentiable.
+//! - Gradients made of differentiable ops can be differentiated again.
+//!
+//! [Op::batch] is called by [vmap](crate::tensor_ops::vmap), which maps a
+//! function over a batch of examples stacked along a leading batch axis:
+//! - The tensor built with the batched inputs of [BatchContext] is given with
+//!   [BatchContext::set_output]. It computes the outputs of the op for all the
+//!   examples at once, also stacked along the batch axis.
+//! - Without a batching rule, the op is computed once per example, which gives
+//!   the same results more slowly.
+//!
+//! [Op::jvp] is called by [jvp](crate::tensor_ops::jvp), which propagates
+//! tangents (directional derivatives) from the inputs to the outputs:
+//! - The tangent of each output, with the shape of that output, is given with
+//!   [JvpContext::append_output_tangent] in order; `None` means zeros.
+//! - The tangents of the inputs are [JvpContext::input_tangent], `None` for
+//!   the inputs that don't depend on the differentiated tensors.
+//! - Without a rule, the tangents are given by differentiating the gradient of
+//!   the op with respect to the gradients of its outputs. This builds more ops,
+//!   and needs a gradient that can be differentiated again: ops whose gradient
+//!   isn't (e.g. `Slice`) have a rule.
+//!
+//! ## Multiple outputs
+//!
+//! An op with several outputs overrides [Op::num_outputs] and is built with
+//! [TensorBuilder::build_outputs](crate::tensor::TensorBuilder::build_outputs).
+//!
+//! ```
+//! use scirs2_autograd as ag;
+//! use ag::op::{ComputeContext, GradientContext, Op, OpError};
+//! use ag::tensor_ops::*;
+//!
+//! // Computes `sin(x)` and `cos(x)` at once.
+//! struct SinCos;
+//!
+//! impl<T: ag::Float> Op<T> for SinCos {
+//!     fn num_outputs(&self) -> usize {
+//!         2
+//!     }
+//!
+//!     fn compute(&self, ctx: &mut ComputeContext<T>) -> Result<(), OpError> {
+//!         let x = ctx.input(0);
+//!         let (sin, cos) = (x.mapv(T::sin), x.mapv(T::cos));
+//!         ctx.append_output(sin);
+//!         ctx.append_output(cos);
+//!         Ok(())
+//!     }
+//!
+//!     fn grad(&self, ctx: &mut GradientContext<T>) {
+//!         let (sin, cos) = (ctx.nth_output(0), ctx.nth_output(1));
+//!         let gx = ctx.nth_output_grad(0) * cos - ctx.nth_output_grad(1) * sin;
+//!         ctx.append_input_grad(0, Some(gx));
+//!     }
+//! }
+//!
+//! ag::run(|g: &mut ag::Context<f64>| {
+//!     let x = g.placeholder("x", &[3]);
+//!     let outputs = ag::Tensor::builder(g).append_input(x, false).build_outputs(SinCos);
+//!     let (sin, cos) = (outputs[0], outputs[1]);
+//!     // d(sin(x) + cos(x))/dx = cos(x) - sin(x)
+//!     let gx = grad(&[sum_all(sin + cos)], &[x])[0];
+//! });
+//! ```
+//!
 use std::any::type_name;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 pub use crate::error::OpError;
 use crate::ndarray_ext::{NdArrayView, NdArrayViewMut};
 use crate::smallvec::SmallVec as RawSmallVec;
 use crate::tensor::Tensor;
 use crate::{Float, NdArray};
+use serde::{Deserialize, Serialize};
 
 pub(crate) const DEFAULT_NUM_EDGES: usize = 2;
 
 pub(crate) type SmallVec<T> = RawSmallVec<[T; DEFAULT_NUM_EDGES]>;
 
+/// Function applied to each element by a unary elementwise op.
+pub type ElementwiseFn<F> = Rc<dyn Fn(F) -> F>;
+
 /// Trait for tensor operations. `Tensor` structs wrap this.
 pub trait Op<F: Float> {
     /// Name of this op
@@ -67,11 +155,85 @@ pub trait Op<F: Float> {
         type_name::<Self>()
     }
 
+    /// Number of output arrays appended by `compute`.
+    ///
+    /// Only the first `num_outputs` outputs are differentiable; build the op with
+    /// [TensorBuilder::build_outputs](crate::tensor::TensorBuilder::build_outputs)
+    /// to get a tensor for each of them.
+    fn num_outputs(&self) -> usize {
+        1
+    }
+
+    /// Definition of this op for [graph serialization](crate::serialization).
+    ///
+    /// `None` if this op can't be serialized.
+    fn op_def(&self) -> Option<OpDef> {
+        None
+    }
+
+    /// Function this op applies to each element, if it is a unary elementwise op.
+    ///
+    /// [Graph optimization](crate::optimization) fuses chains of such ops into a
+    /// single kernel, so it must agree with [Op::compute].
+    fn elementwise_fn(&self) -> Option<ElementwiseFn<F>> {
+        None
+    }
+
     /// Runs this op with `ComputeContext`.
     fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError>;
 
+    /// Runs this op on `gpu`, for tensors placed on [Device::Gpu](crate::device::Device).
+    ///
+    /// Returns `Ok(false)` without appending outputs if there is no kernel for
+    /// the inputs, in which case [Op::compute] runs on the CPU instead.
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        _ctx: &mut ComputeContext<F>,
+        _gpu: &crate::device::Gpu,
+    ) -> Result<bool, OpError> {
+        Ok(false)
+    }
+
     /// Returns gradients for input nodes by use of output's gradients etc.
     fn grad<'a>(&self, ctx: &mut GradientContext<'a, 'a, F>);
+
+    /// Builds this op for a batch of examples, for [vmap](crate::tensor_ops::vmap).
+    ///
+    /// If no output is set with [BatchContext::set_output], the op is computed
+    /// once per example instead.
+    fn batch<'a>(&self, _ctx: &mut BatchContext<'a, F>) {}
+
+    /// Returns tangents of the outputs from the tangents of the inputs, for
+    /// [jvp](crate::tensor_ops::jvp).
+    ///
+    /// If no tangent is appended with [JvpContext::append_output_tangent], the
+    /// tangents are derived from [Op::grad] instead.
+    fn jvp<'a>(&self, _ctx: &mut JvpContext<'a, F>) {}
+}
+
+/// Serializable definition of an op: its kind and attributes.
+///
+/// The kind identifies the op in an [OpRegistry](crate::serialization::OpRegistry),
+/// which rebuilds it from the attributes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpDef {
+    /// Registered kind of the op
+    pub kind: String,
+    /// Attributes of the op, e.g. the serialized op struct
+    pub attrs: serde_json::Value,
+}
+
+impl OpDef {
+    /// Creates a definition of kind `kind` with the serialized `attrs`.
+    ///
+    /// Returns `None` if `attrs` can't be serialized.
+    pub fn new<A: Serialize + ?Sized>(kind: &str, attrs: &A) -> Option<Self> {
+        Some(OpDef {
+            kind: kind.to_string(),
+            attrs: serde_json::to_value(attrs).ok()?,
+        })
+    }
 }
 
 #[allow(dead_code)]
@@ -210,7 +372,7 @@ pub struct GradientContext<'a, 'graph, F: Float> {
     pub(crate) gzs: &'a [&'graph Tensor<'graph, F>],
 
     /// gradient tensors to be the result.
-    pub(crate) results: &'a mut Vec<Option<Tensor<'graph, F>>>,
+    pub(crate) results: Vec<Option<Tensor<'graph, F>>>,
 
     /// Index of array field.
     pub(crate) array_field_id: usize,
@@ -242,6 +404,18 @@ impl<'graph, F: Float> GradientContext<'_, 'graph, F> {
         self.gzs[self.array_field_id]
     }
 
+    /// Returns the `i`-th output of a multi-output op.
+    pub fn nth_output(&self, i: usize) -> &'graph Tensor<'graph, F> {
+        self.zs[i]
+    }
+
+    /// Returns the gradient of the `i`-th output of a multi-output op.
+    ///
+    /// This is zeros if the output doesn't affect the differentiated tensors.
+    pub fn nth_output_grad(&self, i: usize) -> &'graph Tensor<'graph, F> {
+        self.gzs[i]
+    }
+
     /// Returns the `i`-th input array.
     pub fn input(&self, i: usize) -> &'graph Tensor<'graph, F> {
         self.xs[i]
@@ -288,6 +462,182 @@ impl<'graph, F: Float> GradientContext<'_, 'graph, F> {
     }
 }
 
+/// Context given to `Op::batch`.
+///
+/// The batched inputs have the examples stacked along their first axis, the
+/// batch axis, while the other inputs are the same for all the examples.
+pub struct BatchContext<'graph, F: Float> {
+    pub(crate) op: Rc<dyn Op<F>>,
+    pub(crate) inputs: Vec<Tensor<'graph, F>>,
+    pub(crate) batched: Vec<bool>,
+    /// Whether the op of the example is differentiable
+    pub(crate) differentiable: bool,
+    pub(crate) context: &'graph crate::Context<'graph, F>,
+    pub(crate) output: Option<(Tensor<'graph, F>, bool)>,
+}
+
+impl<'graph, F: Float> BatchContext<'graph, F> {
+    /// Returns the `i`-th input.
+    pub fn input(&self, i: usize) -> &Tensor<'graph, F> {
+        &self.inputs[i]
+    }
+
+    /// Returns true if the `i`-th input has a batch axis.
+    pub fn is_batched(&self, i: usize) -> bool {
+        self.batched[i]
+    }
+
+    /// Returns the number of inputs.
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Returns the context graph.
+    pub fn graph(&self) -> &'graph crate::Context<'graph, F> {
+        self.context
+    }
+
+    /// Sets the tensor computing the outputs of the op for all the examples.
+    ///
+    /// `batched` is false if it doesn't have a batch axis, e.g. the shape of the examples.
+    pub fn set_output(&mut self, y: Tensor<'graph, F>, batched: bool) {
+        self.output = Some((y, batched));
+    }
+
+    /// Builds this op again with other inputs.
+    pub fn rebuild(&self, inputs: &[Tensor<'graph, F>]) -> Tensor<'graph, F> {
+        let mut builder = Tensor::builder(self.context).set_differentiable(self.differentiable);
+        for x in inputs {
+            builder = builder.append_input(x, false);
+        }
+        builder.build_shared(self.op.clone())
+    }
+
+    /// Returns the inputs, with axes of length one inserted after the batch axis
+    /// of the batched ones so that they broadcast like the examples.
+    pub fn broadcastable_inputs(&self) -> Vec<Tensor<'graph, F>> {
+        crate::vmap::broadcastable(&self.inputs, &self.batched)
+    }
+
+    /// Returns the `i`-th input, axes of an example, as axes of the batch.
+    ///
+    /// With `mask`, the input has a flag for each axis instead.
+    pub fn batched_axes(&self, i: usize, mask: bool) -> Tensor<'graph, F> {
+        let kind = if mask {
+            crate::vmap::BatchIndex::AxisMask
+        } else {
+            crate::vmap::BatchIndex::Axes
+        };
+        crate::vmap::batch_index(kind, &self.inputs[i], self.batch_reference())
+    }
+
+    /// Returns the `i`-th input, a permutation of the axes of an example, as a
+    /// permutation of the axes of the batch.
+    pub fn batched_permutation(&self, i: usize) -> Tensor<'graph, F> {
+        let kind = crate::vmap::BatchIndex::Permutation;
+        crate::vmap::batch_index(kind, &self.inputs[i], self.batch_reference())
+    }
+
+    /// Returns the `i`-th input, the shape of an example, as the shape of the batch.
+    pub fn batched_shape(&self, i: usize) -> Tensor<'graph, F> {
+        let kind = crate::vmap::BatchIndex::Shape;
+        crate::vmap::batch_index(kind, &self.inputs[i], self.batch_reference())
+    }
+
+    /// Returns the `i`-th input, the shape of an example, as the shape of the
+    /// batch, with ones prepended up to the rank of the examples of the first
+    /// batched input.
+    pub fn aligned_batched_shape(&self, i: usize) -> Tensor<'graph, F> {
+        let kind = crate::vmap::BatchIndex::AlignedShape;
+        crate::vmap::batch_index(kind, &self.inputs[i], self.batch_reference())
+    }
+
+    /// Returns `x`, a batched tensor, with axes of length one inserted after
+    /// the batch axis so that it broadcasts to the `i`-th input, the shape of
+    /// an example.
+    pub fn broadcastable_to_shape(&self, x: &Tensor<'graph, F>, i: usize) -> Tensor<'graph, F> {
+        crate::vmap::align_to_shape(x, &self.inputs[i])
+    }
+
+    /// First batched input, giving the batch size
+    fn batch_reference(&self) -> &Tensor<'graph, F> {
+        let i = self.batched.iter().position(|&b| b).unwrap();
+        &self.inputs[i]
+    }
+}
+
+/// Context given to `Op::jvp`.
+pub struct JvpContext<'graph, F: Float> {
+    pub(crate) op: Rc<dyn Op<F>>,
+    pub(crate) inputs: Vec<Tensor<'graph, F>>,
+    pub(crate) tangents: Vec<Option<Tensor<'graph, F>>>,
+    pub(crate) outputs: Vec<Tensor<'graph, F>>,
+    pub(crate) context: &'graph crate::Context<'graph, F>,
+    pub(crate) results: Vec<Option<Tensor<'graph, F>>>,
+}
+
+impl<'graph, F: Float> JvpContext<'graph, F> {
+    /// Returns the `i`-th input.
+    pub fn input(&self, i: usize) -> &Tensor<'graph, F> {
+        &self.inputs[i]
+    }
+
+    /// Returns the tangent of the `i`-th input, `None` if it is zeros.
+    pub fn input_tangent(&self, i: usize) -> Option<&Tensor<'graph, F>> {
+        self.tangents[i].as_ref()
+    }
+
+    /// Returns the tangent of the `i`-th input, with zeros built for `None`.
+    pub fn input_tangent_or_zeros(&self, i: usize) -> Tensor<'graph, F> {
+        self.tangents[i].unwrap_or_else(|| {
+            crate::tensor_ops::zeros(&crate::tensor_ops::shape(self.inputs[i]), self.context)
+        })
+    }
+
+    /// Returns the number of inputs.
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Returns the output of this op.
+    pub fn output(&self) -> &Tensor<'graph, F> {
+        &self.outputs[0]
+    }
+
+    /// Returns the `k`-th output of this op.
+    pub fn nth_output(&self, k: usize) -> &Tensor<'graph, F> {
+        &self.outputs[k]
+    }
+
+    /// Returns the graph.
+    pub fn graph(&self) -> &'graph crate::Context<'graph, F> {
+        self.context
+    }
+
+    /// Appends the tangent of the next output; `None` means zeros.
+    pub fn append_output_tangent(&mut self, t: Option<Tensor<'graph, F>>) {
+        self.results.push(t);
+    }
+
+    /// Builds this op again with `inputs`.
+    pub fn rebuild(&self, inputs: &[Tensor<'graph, F>]) -> Tensor<'graph, F> {
+        let mut builder = Tensor::builder(self.context);
+        for x in inputs {
+            builder = builder.append_input(x, false);
+        }
+        builder.build_shared(self.op.clone())
+    }
+
+    /// Returns the tangent of an op which is linear in its first input, the
+    /// other inputs being indices such as shapes or axes.
+    pub fn linear(&self) -> Option<Tensor<'graph, F>> {
+        let t = self.tangents[0]?;
+        let mut inputs = self.inputs.clone();
+        inputs[0] = t;
+        Some(self.rebuild(&inputs))
+    }
+}
+
 /// Output from op.
 #[derive(Clone)]
 #[allow(dead_code)]
@@ -3,9 +3,31 @@
 //! This module provides various optimization techniques for computation graphs,
 //! including expression simplification, common subexpression elimination,
 //! constant folding, and graph-level transformations.
+//!
+//! [GraphOptimizer] rewrites a graph in place before it is evaluated, either as
+//! a whole with [GraphOptimizer::optimize] or only the part needed for some
+//! tensors with [GraphOptimizer::optimize_tensors]. An [Evaluator](crate::evaluation::Evaluator)
+//! runs it before each evaluation with [Evaluator::optimize](crate::evaluation::Evaluator::optimize).
+//!
+//! ```
+//! use scirs2_autograd as ag;
+//! use ag::optimization::{GraphOptimizer, OptimizationLevel};
+//! use ag::tensor_ops as T;
+//!
+//! ag::run(|g: &mut ag::Context<f64>| {
+//!     let x = g.placeholder("x", &[2]);
+//!     let c = T::exp(T::scalar(1.0, g)) * 2.0;
+//!     let y = T::tanh(T::sin(x * c)) + T::tanh(T::sin(x * c));
+//!
+//!     let optimizer = GraphOptimizer::with_level(OptimizationLevel::Aggressive).inspect(true);
+//!     let report = optimizer.optimize_tensors(&[y]).unwrap();
+//!     // `c` is folded, `x * c` and `tanh(sin(..))` are shared, and `tanh(sin(..))` is fused
+//!     println!("{}", report.optimized_graph.unwrap());
+//! });
+//! ```
 
 use crate::graph::{Graph, TensorID};
-use crate::tensor::TensorInternal;
+use crate::tensor::{Tensor, TensorInternal};
 use crate::Float;
 use std::collections::HashSet;
 
@@ -14,6 +36,9 @@ use std::collections::HashSet;
 // pub mod graph_rewriting;
 pub mod loop_fusion;
 pub mod memory_optimization;
+mod passes;
+
+use passes::Scope;
 
 /// Graph optimization configuration
 #[derive(Debug, Clone)]
@@ -104,8 +129,10 @@ impl OptimizationLevel {
 }
 
 /// Main graph optimizer
+#[derive(Clone)]
 pub struct GraphOptimizer<F: Float> {
     config: OptimizationConfig,
+    inspect: bool,
     _phantom: std::marker::PhantomData<F>,
 }
 
@@ -114,6 +141,7 @@ impl<F: Float> GraphOptimizer<F> {
     pub fn new() -> Self {
         Self {
             config: OptimizationConfig::default(),
+            inspect: false,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -122,6 +150,7 @@ impl<F: Float> GraphOptimizer<F> {
     pub fn with_config(config: OptimizationConfig) -> Self {
         Self {
             config,
+            inspect: false,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -130,24 +159,61 @@ impl<F: Float> GraphOptimizer<F> {
     pub fn with_level(level: OptimizationLevel) -> Self {
         Self {
             config: level.config(),
+            inspect: false,
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Keeps a listing of the optimized graph in [OptimizationReport::optimized_graph].
+    pub fn inspect(mut self, enabled: bool) -> Self {
+        self.inspect = enabled;
+        self
+    }
+
+    /// Optimize the part of the graph needed to evaluate `tensors`
+    pub fn optimize_tensors<'g, A: AsRef<Tensor<'g, F>>>(
+        &self,
+        tensors: &[A],
+    ) -> Result<OptimizationReport, OptimizationError> {
+        let Some(first) = tensors.first() else {
+            return Ok(OptimizationReport::new());
+        };
+        let outputs: Vec<TensorID> = tensors.iter().map(|t| t.as_ref().id).collect();
+        self.run(first.as_ref().graph(), Some(&outputs))
+    }
+
     /// Optimize a computation graph
-    pub fn optimize(&self, graph: &mut Graph<F>) -> Result<OptimizationReport, OptimizationError> {
+    pub fn optimize(&self, graph: &Graph<F>) -> Result<OptimizationReport, OptimizationError> {
+        self.run(graph, None)
+    }
+
+    fn run(
+        &self,
+        graph: &Graph<F>,
+        outputs: Option<&[TensorID]>,
+    ) -> Result<OptimizationReport, OptimizationError> {
         let mut report = OptimizationReport::new();
 
         if self.config.level == OptimizationLevel::None {
             return Ok(report);
         }
 
+        // Nodes left unused by a pass must not become outputs themselves
+        let sinks;
+        let outputs = match outputs {
+            Some(outputs) => outputs,
+            None => {
+                sinks = Scope::sinks(graph);
+                &sinks
+            }
+        };
+
         for pass in 0..self.config.max_passes {
             let mut changed = false;
 
             // Constant folding
             if self.config.constant_folding {
-                let folded = self.apply_constant_folding(graph)?;
+                let folded = self.apply_constant_folding(graph, &Scope::new(graph, outputs))?;
                 if folded > 0 {
                     changed = true;
                     report.constant_folding_applied += folded;
@@ -165,7 +231,7 @@ impl<F: Float> GraphOptimizer<F> {
 
             // Common subexpression elimination
             if self.config.cse {
-                let eliminated = self.apply_cse(graph)?;
+                let eliminated = self.apply_cse(graph, &Scope::new(graph, outputs))?;
                 if eliminated > 0 {
                     changed = true;
                     report.cse_applied += eliminated;
@@ -183,7 +249,7 @@ impl<F: Float> GraphOptimizer<F> {
 
             // Operation fusion
             if self.config.operation_fusion {
-                let fused = self.apply_operation_fusion(graph)?;
+                let fused = self.apply_operation_fusion(graph, &Scope::new(graph, outputs))?;
                 if fused > 0 {
                     changed = true;
                     report.operations_fused += fused;
@@ -207,20 +273,23 @@ impl<F: Float> GraphOptimizer<F> {
             }
         }
 
+        if self.inspect {
+            report.optimized_graph = Some(passes::dump_graph(graph, &Scope::new(graph, outputs)));
+        }
         Ok(report)
     }
 
     /// Apply constant folding optimization
-    fn apply_constant_folding(&self, _graph: &mut Graph<F>) -> Result<usize, OptimizationError> {
-        // Temporarily disabled - would be implemented with constant_folding module
-        Ok(0)
+    fn apply_constant_folding(
+        &self,
+        graph: &Graph<F>,
+        scope: &Scope,
+    ) -> Result<usize, OptimizationError> {
+        Ok(passes::fold_constants(graph, scope))
     }
 
     /// Apply dead code elimination
-    fn apply_dead_code_elimination(
-        &self,
-        _graph: &mut Graph<F>,
-    ) -> Result<usize, OptimizationError> {
+    fn apply_dead_code_elimination(&self, _graph: &Graph<F>) -> Result<usize, OptimizationError> {
         // Simplified implementation - in a real optimizer, this would:
         // 1. Mark all reachable nodes from outputs
         // 2. Remove unreachable nodes
@@ -237,49 +306,33 @@ impl<F: Float> GraphOptimizer<F> {
     }
 
     /// Apply common subexpression elimination
-    fn apply_cse(&self, _graph: &mut Graph<F>) -> Result<usize, OptimizationError> {
-        // Simplified implementation - in a real optimizer, this would:
-        // 1. Build a hash table of equivalent expressions
-        // 2. Replace duplicate expressions with references to the first occurrence
-        // 3. Remove redundant nodes
-
-        let cse_count = 0;
-
-        // Example:
-        // If we have: x*y + z and later x*y + w
-        // We can compute x*y once and reuse it
-
-        Ok(cse_count)
+    fn apply_cse(&self, graph: &Graph<F>, scope: &Scope) -> Result<usize, OptimizationError> {
+        Ok(passes::eliminate_common_subexpressions(graph, scope))
     }
 
     /// Apply expression simplification
     fn apply_expression_simplification(
         &self,
-        _graph: &mut Graph<F>,
+        _graph: &Graph<F>,
     ) -> Result<usize, OptimizationError> {
         // Temporarily disabled - would be implemented with expression_simplification module
         Ok(0)
     }
 
     /// Apply operation fusion
-    fn apply_operation_fusion(&self, _graph: &mut Graph<F>) -> Result<usize, OptimizationError> {
-        // Simplified implementation - in a real optimizer, this would:
-        // 1. Identify fusable operation patterns
-        // 2. Replace patterns with fused operations
-        // 3. Update the graph structure
-
-        let fused_count = 0;
-
-        // Examples of fusion:
-        // - Element-wise operations: Add + Mul → FusedAddMul
-        // - Activation sequences: Linear + ReLU → FusedLinearReLU
-        // - Reduction patterns: Sum + Div → Mean
-
-        Ok(fused_count)
+    ///
+    /// Chains of unary elementwise ops (see [Op::elementwise_fn](crate::op::Op::elementwise_fn))
+    /// are computed in a single kernel.
+    fn apply_operation_fusion(
+        &self,
+        graph: &Graph<F>,
+        scope: &Scope,
+    ) -> Result<usize, OptimizationError> {
+        Ok(passes::fuse_elementwise(graph, scope))
     }
 
     /// Apply memory optimization
-    fn apply_memory_optimization(&self, _graph: &mut Graph<F>) -> Result<usize, OptimizationError> {
+    fn apply_memory_optimization(&self, _graph: &Graph<F>) -> Result<usize, OptimizationError> {
         // Simplified implementation - in a real optimizer, this would:
         // 1. Analyze memory usage patterns
         // 2. Insert memory reuse opportunities
@@ -320,6 +373,8 @@ pub struct OptimizationReport {
     pub operations_fused: usize,
     /// Number of memory optimizations applied
     pub memory_optimizations: usize,
+    /// Listing of the optimized graph, if inspection is enabled
+    pub optimized_graph: Option<String>,
 }
 
 impl OptimizationReport {
@@ -371,6 +426,10 @@ impl OptimizationReport {
         if self.memory_optimizations > 0 {
             println!("  Memory optimizations: {}", self.memory_optimizations);
         }
+        if let Some(graph) = &self.optimized_graph {
+            println!("Optimized graph:");
+            print!("{}", graph);
+        }
     }
 }
 
@@ -483,7 +542,7 @@ impl<F: Float> OptimizationPass<F> {
     }
 
     /// Run this optimization pass on a graph
-    pub fn run(&self, _graph: &mut Graph<F>) -> Result<usize, OptimizationError> {
+    pub fn run(&self, _graph: &Graph<F>) -> Result<usize, OptimizationError> {
         // Each pass would implement its specific optimization logic
         Ok(0)
     }
@@ -504,16 +563,14 @@ pub enum OptimizationError {
 
 /// Public API functions for graph optimization
 /// Optimize a computation graph with default settings
-pub fn optimize_graph<F: Float>(
-    graph: &mut Graph<F>,
-) -> Result<OptimizationReport, OptimizationError> {
+pub fn optimize_graph<F: Float>(graph: &Graph<F>) -> Result<OptimizationReport, OptimizationError> {
     let optimizer = GraphOptimizer::new();
     optimizer.optimize(graph)
 }
 
 /// Optimize a computation graph with specified optimization level
 pub fn optimize_graph_with_level<F: Float>(
-    graph: &mut Graph<F>,
+    graph: &Graph<F>,
     level: OptimizationLevel,
 ) -> Result<OptimizationReport, OptimizationError> {
     let optimizer = GraphOptimizer::with_level(level);
@@ -522,7 +579,7 @@ pub fn optimize_graph_with_level<F: Float>(
 
 /// Optimize a computation graph with custom configuration
 pub fn optimize_graph_with_config<F: Float>(
-    graph: &mut Graph<F>,
+    graph: &Graph<F>,
     config: OptimizationConfig,
 ) -> Result<OptimizationReport, OptimizationError> {
     let optimizer = GraphOptimizer::with_config(config);
@@ -530,7 +587,7 @@ pub fn optimize_graph_with_config<F: Float>(
 }
 
 /// Apply only constant folding optimization
-pub fn apply_constant_folding<F: Float>(graph: &mut Graph<F>) -> Result<usize, OptimizationError> {
+pub fn apply_constant_folding<F: Float>(graph: &Graph<F>) -> Result<usize, OptimizationError> {
     let config = OptimizationConfig {
         constant_folding: true,
         cse: false,
@@ -547,9 +604,7 @@ pub fn apply_constant_folding<F: Float>(graph: &mut Graph<F>) -> Result<usize, O
 }
 
 /// Apply only dead code elimination
-pub fn apply_dead_code_elimination<F: Float>(
-    graph: &mut Graph<F>,
-) -> Result<usize, OptimizationError> {
+pub fn apply_dead_code_elimination<F: Float>(graph: &Graph<F>) -> Result<usize, OptimizationError> {
     let config = OptimizationConfig {
         constant_folding: false,
         cse: false,
@@ -566,7 +621,7 @@ pub fn apply_dead_code_elimination<F: Float>(
 }
 
 /// Apply common subexpression elimination
-pub fn apply_cse<F: Float>(graph: &mut Graph<F>) -> Result<usize, OptimizationError> {
+pub fn apply_cse<F: Float>(graph: &Graph<F>) -> Result<usize, OptimizationError> {
     let config = OptimizationConfig {
         constant_folding: false,
         cse: true,
@@ -0,0 +1,331 @@
+//! Graph rewriting passes run by [GraphOptimizer](super::GraphOptimizer)
+//!
+//! Passes rewrite nodes in place, so tensors created before the optimization
+//! stay valid and evaluate to the same values. Nodes which are no longer used
+//! are simply left in the graph.
+
+use crate::graph::{Context, Graph, TensorID};
+use crate::op::{ComputeContext, ElementwiseFn, GradientContext, Op, OpError};
+use crate::tensor::{IncomingTensor, Tensor};
+use crate::tensor_ops::const_gen_ops::ConvertToTensor;
+use crate::Float;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::rc::Rc;
+
+/// Nodes an optimization pass works on.
+pub(crate) struct Scope {
+    /// Nodes reachable from the outputs, in topological order
+    nodes: Vec<TensorID>,
+    /// Nodes whose values are requested
+    outputs: HashSet<TensorID>,
+    /// Number of consumers of each node, counting outputs as consumers
+    num_consumers: HashMap<TensorID, usize>,
+}
+
+impl Scope {
+    /// Subgraph evaluated for `outputs`.
+    pub(crate) fn new<F: Float>(graph: &Graph<F>, outputs: &[TensorID]) -> Self {
+        let node_set = graph.node_set.borrow();
+        let mut visited: HashSet<TensorID> = outputs.iter().copied().collect();
+        let mut stack = outputs.to_vec();
+        while let Some(id) = stack.pop() {
+            for x in &node_set[id].incoming_nodes {
+                if visited.insert(x.id) {
+                    stack.push(x.id);
+                }
+            }
+        }
+        let mut nodes: Vec<TensorID> = visited.into_iter().collect();
+        // Inputs are always installed before their consumers
+        nodes.sort_unstable();
+
+        let mut num_consumers: HashMap<TensorID, usize> = HashMap::new();
+        for &id in &nodes {
+            for x in &node_set[id].incoming_nodes {
+                *num_consumers.entry(x.id).or_insert(0) += 1;
+            }
+        }
+        for &id in outputs {
+            *num_consumers.entry(id).or_insert(0) += 1;
+        }
+        Self {
+            nodes,
+            outputs: outputs.iter().copied().collect(),
+            num_consumers,
+        }
+    }
+
+    /// Tensors of `graph` which no other tensor uses.
+    ///
+    /// The shape tensors which builders attach to their tensors are left out
+    /// unless they are actually used.
+    pub(crate) fn sinks<F: Float>(graph: &Graph<F>) -> Vec<TensorID> {
+        let node_set = graph.node_set.borrow();
+        let mut used = vec![false; node_set.len()];
+        for node in node_set.iter() {
+            for x in &node.incoming_nodes {
+                used[x.id] = true;
+            }
+        }
+        for node in node_set.iter() {
+            if let Some(id) = node.shape {
+                used[id] = true;
+            }
+        }
+        (0..node_set.len()).filter(|&id| !used[id]).collect()
+    }
+
+    fn num_consumers(&self, id: TensorID) -> usize {
+        self.num_consumers.get(&id).copied().unwrap_or(0)
+    }
+}
+
+/// Returns the op of `id` if it is a pure single-output op, i.e. not a
+/// placeholder, a variable or a mutation.
+fn pure_op<F: Float>(graph: &Graph<F>, id: TensorID) -> Option<Rc<dyn Op<F>>> {
+    let node = graph.access_inner(id);
+    if node.placeholder_name.is_some()
+        || node.variable_id.is_some()
+        || node
+            .incoming_nodes
+            .iter()
+            .any(|x| x.allow_mut || x.array_selector != 0)
+    {
+        return None;
+    }
+    node.op.clone().filter(|op| op.num_outputs() == 1)
+}
+
+/// Replaces the subgraphs that don't depend on placeholders or variables
+/// with their values.
+///
+/// Only ops with an [OpDef](crate::op::OpDef) are folded, which keeps random
+/// ops and other ops that can't be described by their attributes out.
+/// Folded tensors are leaves of the graph afterwards, so no gradient flows
+/// through them. Subgraphs failing to evaluate are left as they are.
+pub(crate) fn fold_constants<F: Float>(graph: &Graph<F>, scope: &Scope) -> usize {
+    let mut constants = HashSet::new();
+    for &id in &scope.nodes {
+        let is_constant = pure_op(graph, id).is_some_and(|op| op.op_def().is_some())
+            && graph
+                .access_inner(id)
+                .incoming_nodes
+                .iter()
+                .all(|x| constants.contains(&x.id));
+        if is_constant {
+            constants.insert(id);
+        }
+    }
+
+    // Fold the constants used by the rest of the graph; leaves are already folded.
+    let mut used_outside = HashSet::new();
+    for &id in &scope.nodes {
+        if !constants.contains(&id) {
+            used_outside.extend(graph.access_inner(id).incoming_nodes.iter().map(|x| x.id));
+        }
+    }
+    let roots: Vec<TensorID> = scope
+        .nodes
+        .iter()
+        .copied()
+        .filter(|id| {
+            constants.contains(id)
+                && (used_outside.contains(id) || scope.outputs.contains(id))
+                && !graph.access_inner(*id).incoming_nodes.is_empty()
+        })
+        .collect();
+    if roots.is_empty() {
+        return 0;
+    }
+
+    let tensors: Vec<Tensor<F>> = roots.iter().map(|&id| graph.tensor(id)).collect();
+    let targets: Vec<&Tensor<F>> = tensors.iter().collect();
+    let values = Graph::eval_tensors(&targets, &HashMap::new(), Context::from_graph(graph));
+    let mut num_folded = 0;
+    for (id, value) in roots.into_iter().zip(values) {
+        if let Ok(arr) = value {
+            let mut node = graph.access_inner_mut(id);
+            node.op = Some(Rc::new(ConvertToTensor { arr }));
+            node.incoming_nodes.clear();
+            node.backprop_inputs = None;
+            num_folded += 1;
+        }
+    }
+    num_folded
+}
+
+/// Makes the consumers of nodes computing the same op on the same inputs
+/// share the first of them.
+pub(crate) fn eliminate_common_subexpressions<F: Float>(graph: &Graph<F>, scope: &Scope) -> usize {
+    let mut first_of: HashMap<String, TensorID> = HashMap::new();
+    let mut replaced: HashMap<TensorID, TensorID> = HashMap::new();
+    for &id in &scope.nodes {
+        {
+            let mut node = graph.access_inner_mut(id);
+            let node = &mut *node;
+            let backprop_inputs = node.backprop_inputs.iter_mut().flatten();
+            for x in node.incoming_nodes.iter_mut().chain(backprop_inputs) {
+                if let Some(&first) = replaced.get(&x.id) {
+                    x.id = first;
+                }
+            }
+            if let Some(first) = node.shape.and_then(|s| replaced.get(&s)) {
+                node.shape = Some(*first);
+            }
+        }
+
+        let Some(def) = pure_op(graph, id).and_then(|op| op.op_def()) else {
+            continue;
+        };
+        let key = {
+            let node = graph.access_inner(id);
+            let inputs: Vec<TensorID> = node.incoming_nodes.iter().map(|x| x.id).collect();
+            let backprop_inputs: Option<Vec<TensorID>> = node
+                .backprop_inputs
+                .as_ref()
+                .map(|xs| xs.iter().map(|x| x.id).collect());
+            format!(
+                "{}{}{:?}{:?}{:?}{}{}",
+                def.kind,
+                def.attrs,
+                inputs,
+                backprop_inputs,
+                node.known_shape.as_ref().map(|s| s.get().to_vec()),
+                node.is_differentiable,
+                node.device
+            )
+        };
+        match first_of.entry(key) {
+            Entry::Occupied(first) => {
+                replaced.insert(id, *first.get());
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(id);
+            }
+        }
+    }
+    replaced.len()
+}
+
+/// Merges chains of unary elementwise ops into [FusedElementwise] ops.
+///
+/// The intermediate results of a chain must not be used elsewhere.
+/// Returns the number of ops merged into the last op of their chain.
+pub(crate) fn fuse_elementwise<F: Float>(graph: &Graph<F>, scope: &Scope) -> usize {
+    let elementwise = |id: TensorID| {
+        let op = pure_op(graph, id)?;
+        let f = op.elementwise_fn()?;
+        let node = graph.access_inner(id);
+        match node.incoming_nodes.as_slice() {
+            [x] => Some((op.clone(), f, x.id)),
+            _ => None,
+        }
+    };
+
+    let mut fused = HashSet::new();
+    let mut num_fused = 0;
+    for &id in scope.nodes.iter().rev() {
+        if fused.contains(&id) {
+            continue;
+        }
+        let Some((op, f, mut input)) = elementwise(id) else {
+            continue;
+        };
+        let (mut ops, mut fns) = (vec![op], vec![f]);
+        while scope.num_consumers(input) == 1 && !scope.outputs.contains(&input) {
+            let Some((op, f, next)) = elementwise(input) else {
+                break;
+            };
+            fused.insert(input);
+            ops.push(op);
+            fns.push(f);
+            input = next;
+        }
+        if ops.len() < 2 {
+            continue;
+        }
+        num_fused += ops.len() - 1;
+        ops.reverse();
+        fns.reverse();
+
+        let mut node = graph.access_inner_mut(id);
+        node.op = Some(Rc::new(FusedElementwise { ops, fns }));
+        node.incoming_nodes.clear();
+        node.incoming_nodes.push(IncomingTensor {
+            id: input,
+            allow_mut: false,
+            array_selector: 0,
+        });
+        node.backprop_inputs = None;
+    }
+    num_fused
+}
+
+/// Textual listing of the nodes in `scope`, one per line.
+pub(crate) fn dump_graph<F: Float>(graph: &Graph<F>, scope: &Scope) -> String {
+    let mut buf = String::new();
+    for &id in &scope.nodes {
+        let node = graph.access_inner(id);
+        let inputs: Vec<String> = node
+            .incoming_nodes
+            .iter()
+            .map(|x| x.id.to_string())
+            .collect();
+        let name = if let Some(name) = node.placeholder_name {
+            format!("Placeholder \"{}\"", name)
+        } else if node.variable_id.is_some() {
+            "Variable".to_string()
+        } else {
+            node.op.as_ref().map_or("?", |op| op.name()).to_string()
+        };
+        // Strip the module path and generic parameters of the op's type name
+        let name = name.split('<').next().unwrap_or_default();
+        let name = name.rsplit("::").next().unwrap_or_default();
+        let _ = write!(buf, "{}: {}({})", id, name, inputs.join(", "));
+        if scope.outputs.contains(&id) {
+            buf += " -> output";
+        }
+        buf += "\n";
+    }
+    buf
+}
+
+/// Chain of unary elementwise ops computed in a single pass over the input.
+pub(crate) struct FusedElementwise<F: Float> {
+    /// Ops of the chain, the first one applied first
+    ops: Vec<Rc<dyn Op<F>>>,
+    fns: Vec<ElementwiseFn<F>>,
+}
+
+impl<F: Float> Op<F> for FusedElementwise<F> {
+    fn name(&self) -> &'static str {
+        "FusedElementwise"
+    }
+
+    fn elementwise_fn(&self) -> Option<ElementwiseFn<F>> {
+        let fns = self.fns.clone();
+        Some(Rc::new(move |a| fns.iter().fold(a, |a, f| f(a))))
+    }
+
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let ret = ctx.input(0).mapv(|a| self.fns.iter().fold(a, |a, f| f(a)));
+        ctx.append_output(ret);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        // Differentiate the unfused chain
+        let x = *ctx.input(0);
+        let g = ctx.graph();
+        let y = self.ops.iter().fold(x, |y, op| {
+            Tensor::builder(g)
+                .append_input(y, false)
+                .set_shape(&crate::tensor_ops::shape(y))
+                .build_shared(op.clone())
+        });
+        let gx = crate::tensor_ops::grad_with_default(&[y], &[x], &[*ctx.output_grad()])[0];
+        ctx.append_input_grad(0, Some(gx));
+    }
+}
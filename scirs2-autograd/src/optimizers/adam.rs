@@ -149,8 +149,7 @@ impl<F: Float> Optimizer<F> for Adam<F> {
                     b2: self.b2,
                 });
 
-            // Log Adam operation construction
-            eprintln!("Created AdamOp with all 5 inputs");
+            crate::tracing::debug_message("AdamOp", || "created with all 5 inputs".to_string());
 
             // Add the updated parameter to the result
             ret.push(adam_op);
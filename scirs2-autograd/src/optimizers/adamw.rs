@@ -223,8 +223,7 @@ impl<F: Float> Optimizer<F> for AdamW<F> {
                     weight_decay: self.weight_decay,
                 });
 
-            // Log AdamW operation construction
-            eprintln!("Created AdamWOp with all 5 inputs");
+            crate::tracing::debug_message("AdamWOp", || "created with all 5 inputs".to_string());
 
             // Add the updated parameter to the result
             ret.push(adamw_op);
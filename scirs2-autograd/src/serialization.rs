@@ -0,0 +1,417 @@
+//! Saving computation graphs and loading them in another process
+//!
+//! A [SerializedGraph] records what is needed to compute some named tensors:
+//! placeholders, variables, constants and ops. Saved as JSON, it can be loaded
+//! into another graph, e.g. for inference in another process.
+//!
+//! Variables are recorded by name and looked up in the variable environment
+//! of the graph they are loaded into. Their arrays are persisted separately
+//! with [VariableEnvironment::save](crate::VariableEnvironment::save).
+//!
+//! Ops are serialized with [Op::op_def](crate::op::Op::op_def) and rebuilt by an
+//! [OpRegistry], which knows the serializable built-in ops and can be extended
+//! with user-defined ones.
+//!
+//! ```
+//! use scirs2_autograd as ag;
+//! use ag::prelude::*;
+//! use ag::serialization::{OpRegistry, SerializedGraph};
+//! use ag::tensor_ops as T;
+//!
+//! let mut env = ag::VariableEnvironment::new();
+//! let mut rng = ag::ndarray_ext::ArrayRng::<f64>::default();
+//! env.name("w").set(rng.standard_normal(&[3, 2]));
+//!
+//! // Defines the model and saves it
+//! let graph = env.run(|g| {
+//!     let x = g.placeholder("x", &[-1, 3]);
+//!     let y = T::softmax(T::matmul(x, g.variable("w")), 1);
+//!     SerializedGraph::from_tensors(g, &[("y", &y)]).unwrap()
+//! });
+//! graph.save("model.json").unwrap();
+//! env.save("variables.json").unwrap();
+//!
+//! // Runs it elsewhere
+//! let env = ag::VariableEnvironment::<f64>::load("variables.json").unwrap();
+//! let graph = SerializedGraph::load("model.json").unwrap();
+//! env.run(|g| {
+//!     let outputs = graph.build(g, &OpRegistry::new()).unwrap();
+//!     let x = ndarray::array![[1., 2., 3.]].into_dyn();
+//!     let y = g.evaluator().push(&outputs["y"]).feed("x", x.view()).run();
+//! });
+//! ```
+use crate::device::Device;
+use crate::graph::{Context, TensorID};
+use crate::op::{Op, OpDef};
+use crate::tensor::Tensor;
+use crate::Float;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::rc::Rc;
+use thiserror::Error;
+
+/// Error while serializing or loading a graph
+#[derive(Debug, Error)]
+pub enum SerializationError {
+    /// The op doesn't support serialization
+    #[error("Op {0} can't be serialized")]
+    UnsupportedOp(String),
+
+    /// No op of this kind is registered
+    #[error("Unknown op kind: {0}")]
+    UnknownOpKind(String),
+
+    /// The attributes of an op are invalid
+    #[error("Invalid attributes for op {kind}: {source}")]
+    InvalidAttributes {
+        /// Kind of the op
+        kind: String,
+        /// Deserialization error
+        source: serde_json::Error,
+    },
+
+    /// The variable is not in the variable environment
+    #[error("Variable not found: {0}")]
+    VariableNotFound(String),
+
+    /// The serialized graph is inconsistent
+    #[error("Invalid graph: {0}")]
+    InvalidGraph(String),
+
+    /// I/O error
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    /// JSON error
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+type OpBuilder<F> = Box<dyn Fn(&serde_json::Value) -> serde_json::Result<Rc<dyn Op<F>>>>;
+
+/// Rebuilds ops from their [OpDef]s.
+///
+/// [OpRegistry::new] knows the serializable built-in ops. User-defined ops
+/// are made serializable by implementing [Op::op_def](crate::op::Op::op_def)
+/// and registering them:
+///
+/// ```
+/// use scirs2_autograd as ag;
+/// use ag::op::{ComputeContext, GradientContext, Op, OpDef, OpError};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Scale {
+///     factor: f64,
+/// }
+///
+/// impl<F: ag::Float> Op<F> for Scale {
+///     fn op_def(&self) -> Option<OpDef> {
+///         OpDef::new("Scale", self)
+///     }
+///
+///     fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+///         let factor = F::from(self.factor).unwrap();
+///         let y = ctx.input(0).mapv(|x| x * factor);
+///         ctx.append_output(y);
+///         Ok(())
+///     }
+///
+///     fn grad(&self, ctx: &mut GradientContext<F>) {
+///         let gx = ctx.output_grad() * F::from(self.factor).unwrap();
+///         ctx.append_input_grad(0, Some(gx));
+///     }
+/// }
+///
+/// let mut registry = ag::serialization::OpRegistry::<f32>::new();
+/// registry.register::<Scale>("Scale");
+/// ```
+pub struct OpRegistry<F: Float> {
+    builders: HashMap<String, OpBuilder<F>>,
+}
+
+impl<F: Float> OpRegistry<F> {
+    /// Creates a registry of the serializable built-in ops.
+    pub fn new() -> Self {
+        let mut registry = OpRegistry {
+            builders: HashMap::new(),
+        };
+        crate::tensor_ops::register_ops(&mut registry);
+        registry
+    }
+
+    /// Registers the op `O`, deserialized from the attributes of the
+    /// [OpDef]s of kind `kind`.
+    ///
+    /// A previous registration of `kind` is replaced.
+    pub fn register<O>(&mut self, kind: &str)
+    where
+        O: Op<F> + DeserializeOwned + 'static,
+    {
+        let builder = |attrs: &serde_json::Value| {
+            let op: Rc<dyn Op<F>> = Rc::new(O::deserialize(attrs)?);
+            Ok(op)
+        };
+        self.builders.insert(kind.to_string(), Box::new(builder));
+    }
+
+    /// Returns true if ops of kind `kind` can be rebuilt.
+    pub fn contains(&self, kind: &str) -> bool {
+        self.builders.contains_key(kind)
+    }
+
+    /// Rebuilds the op defined by `def`.
+    pub fn build(&self, def: &OpDef) -> Result<Rc<dyn Op<F>>, SerializationError> {
+        let builder = self
+            .builders
+            .get(&def.kind)
+            .ok_or_else(|| SerializationError::UnknownOpKind(def.kind.clone()))?;
+        builder(&def.attrs).map_err(|source| SerializationError::InvalidAttributes {
+            kind: def.kind.clone(),
+            source,
+        })
+    }
+}
+
+impl<F: Float> Default for OpRegistry<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Input of a serialized node: the `output`-th output of the `node`-th node
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct InputDef {
+    node: usize,
+    output: usize,
+    mutable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum NodeDef {
+    Placeholder {
+        name: String,
+        shape: Vec<isize>,
+    },
+    Variable {
+        namespace: String,
+        name: String,
+    },
+    Op {
+        op: OpDef,
+        inputs: Vec<InputDef>,
+        /// Nodes used by backprop instead of `inputs`
+        backprop_inputs: Option<Vec<usize>>,
+        /// Node giving the shape
+        shape: Option<usize>,
+        known_shape: Option<Vec<isize>>,
+        differentiable: bool,
+        #[serde(default)]
+        device: Device,
+    },
+}
+
+/// Portable form of the subgraph computing some named tensors.
+///
+/// See the [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SerializedGraph {
+    /// Nodes in topological order
+    nodes: Vec<NodeDef>,
+    /// Names and nodes of the outputs
+    outputs: Vec<(String, usize)>,
+}
+
+impl SerializedGraph {
+    /// Serializes the subgraph of `ctx` needed to compute `outputs`, given with their names.
+    ///
+    /// Fails if an op of the subgraph doesn't support serialization or if a
+    /// variable is not in the variable environment of `ctx`.
+    pub fn from_tensors<F: Float>(
+        ctx: &Context<F>,
+        outputs: &[(&str, &Tensor<F>)],
+    ) -> Result<Self, SerializationError> {
+        // Collects the nodes in topological order (dependencies first)
+        let mut order: Vec<TensorID> = Vec::new();
+        let mut index: HashMap<TensorID, usize> = HashMap::new();
+        let mut stack: Vec<(TensorID, bool)> =
+            outputs.iter().rev().map(|(_, t)| (t.id, false)).collect();
+        while let Some((id, expanded)) = stack.pop() {
+            if index.contains_key(&id) {
+                continue;
+            }
+            if expanded {
+                index.insert(id, order.len());
+                order.push(id);
+                continue;
+            }
+            stack.push((id, true));
+            let node = ctx.access_inner(id);
+            let deps = node
+                .incoming_nodes
+                .iter()
+                .map(|x| x.id)
+                .chain(node.backprop_inputs.iter().flatten().map(|x| x.id))
+                .chain(node.shape);
+            for dep in deps.collect::<Vec<_>>().into_iter().rev() {
+                if !index.contains_key(&dep) {
+                    stack.push((dep, false));
+                }
+            }
+        }
+
+        let mut variable_names = HashMap::new();
+        for (name, &vid) in ctx.var_env_ref().name_to_id.iter() {
+            variable_names.insert(vid, name);
+        }
+
+        let mut nodes = Vec::with_capacity(order.len());
+        for &id in &order {
+            let node = ctx.access_inner(id);
+            let def = if let Some(name) = node.placeholder_name {
+                let shape = node.known_shape.as_ref().map(|s| s.get().to_vec());
+                NodeDef::Placeholder {
+                    name: name.to_string(),
+                    shape: shape.unwrap_or_default(),
+                }
+            } else if let Some(vid) = node.variable_id {
+                let name = variable_names
+                    .get(&vid)
+                    .ok_or_else(|| SerializationError::VariableNotFound(format!("{:?}", vid)))?;
+                NodeDef::Variable {
+                    namespace: name.namespace_id.clone(),
+                    name: name.variable_name.clone(),
+                }
+            } else {
+                let op = node.op.as_ref().expect("bad impl: tensor without an Op");
+                NodeDef::Op {
+                    op: op
+                        .op_def()
+                        .ok_or_else(|| SerializationError::UnsupportedOp(op.name().to_string()))?,
+                    inputs: node
+                        .incoming_nodes
+                        .iter()
+                        .map(|x| InputDef {
+                            node: index[&x.id],
+                            output: x.array_selector,
+                            mutable: x.allow_mut,
+                        })
+                        .collect(),
+                    backprop_inputs: node
+                        .backprop_inputs
+                        .as_ref()
+                        .map(|xs| xs.iter().map(|x| index[&x.id]).collect()),
+                    shape: node.shape.map(|s| index[&s]),
+                    known_shape: node.known_shape.as_ref().map(|s| s.get().to_vec()),
+                    differentiable: node.is_differentiable,
+                    device: node.device,
+                }
+            };
+            nodes.push(def);
+        }
+
+        Ok(SerializedGraph {
+            nodes,
+            outputs: outputs
+                .iter()
+                .map(|(name, t)| (name.to_string(), index[&t.id]))
+                .collect(),
+        })
+    }
+
+    /// Builds the serialized tensors in `ctx`, rebuilding the ops with `registry`.
+    ///
+    /// Returns the outputs by name. Placeholders are created with their names
+    /// (leaked to be `'static`), or reused if `ctx` already has them.
+    pub fn build<'g, F: Float>(
+        &self,
+        ctx: &'g Context<F>,
+        registry: &OpRegistry<F>,
+    ) -> Result<HashMap<String, Tensor<'g, F>>, SerializationError> {
+        let mut tensors: Vec<Tensor<'g, F>> = Vec::with_capacity(self.nodes.len());
+        let get = |tensors: &[Tensor<'g, F>], i: usize| {
+            tensors.get(i).copied().ok_or_else(|| {
+                SerializationError::InvalidGraph(format!("node {} used before its definition", i))
+            })
+        };
+        for def in &self.nodes {
+            let tensor = match def {
+                NodeDef::Placeholder { name, shape } => {
+                    let name: &'static str = Box::leak(name.clone().into_boxed_str());
+                    ctx.placeholder(name, shape)
+                }
+                NodeDef::Variable { namespace, name } => {
+                    let full_name = crate::variable::FullName {
+                        namespace_id: namespace.clone(),
+                        variable_name: name.clone(),
+                    };
+                    let vid = ctx
+                        .var_env_ref()
+                        .name_to_id
+                        .get(&full_name)
+                        .ok_or_else(|| {
+                            SerializationError::VariableNotFound(format!("{}/{}", namespace, name))
+                        })?;
+                    ctx.variable_by_id(*vid)
+                }
+                NodeDef::Op {
+                    op,
+                    inputs,
+                    backprop_inputs,
+                    shape,
+                    known_shape,
+                    differentiable,
+                    device,
+                } => {
+                    let mut builder = Tensor::builder(ctx)
+                        .set_differentiable(*differentiable)
+                        .set_device(*device);
+                    for x in inputs {
+                        builder = builder.append_input_with_selector(
+                            get(&tensors, x.node)?,
+                            x.mutable,
+                            x.output,
+                        );
+                    }
+                    for &x in backprop_inputs.iter().flatten() {
+                        builder = builder.append_backprop_input(get(&tensors, x)?);
+                    }
+                    if let Some(shape) = shape {
+                        builder = builder.set_shape(&get(&tensors, *shape)?);
+                    }
+                    if let Some(known_shape) = known_shape {
+                        builder = builder.set_known_shape(known_shape);
+                    }
+                    builder.build_shared(registry.build(op)?)
+                }
+            };
+            tensors.push(tensor);
+        }
+
+        self.outputs
+            .iter()
+            .map(|(name, i)| Ok((name.clone(), get(&tensors, *i)?)))
+            .collect()
+    }
+
+    /// Names of the outputs
+    pub fn output_names(&self) -> impl Iterator<Item = &str> {
+        self.outputs.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Saves this graph as JSON.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), SerializationError> {
+        let f = File::create(path.as_ref())?;
+        serde_json::to_writer(f, self)?;
+        Ok(())
+    }
+
+    /// Loads a graph saved by [SerializedGraph::save].
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SerializationError> {
+        let f = File::open(path.as_ref())?;
+        Ok(serde_json::from_reader(f)?)
+    }
+}
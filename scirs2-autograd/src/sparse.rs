@@ -0,0 +1,860 @@
+//! Sparse tensors
+//!
+//! A [`SparseTensor`] is a sparse matrix in the coordinate (COO) or compressed
+//! sparse row (CSR) format, made of three tensors: the row indices (COO) or row
+//! pointers (CSR), the column indices, and the values of the stored elements.
+//! Like the other index tensors of this crate, indices are stored as floats.
+//! Sparse tensors are differentiable with respect to their values, and their
+//! products cost time proportional to the number of stored elements in both
+//! the forward and the backward pass.
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_autograd as ag;
+//! use ag::sparse::{self, SparseFormat, SparseTensor};
+//! use ag::tensor_ops as T;
+//!
+//! ag::run(|ctx: &mut ag::Context<f64>| {
+//!     let a = array![[0., 2., 0.], [1., 0., 3.]];
+//!     let a = SparseTensor::from_dense(&a.view(), SparseFormat::Csr, ctx);
+//!     let b = ctx.placeholder("b", &[3, 2]);
+//!     let y = sparse::matmul(a, b);
+//!     let gb = T::grad(&[y], &[b])[0];
+//!
+//!     let b_val = array![[1., 0.], [0., 1.], [1., 1.]];
+//!     let gb = ctx.evaluator().push(&gb).feed(b, b_val.view().into_dyn()).run();
+//!     // Each row of b is used with the sum of a column of a
+//!     assert_eq!(gb[0], Ok(array![[1., 1.], [2., 2.], [3., 3.]].into_dyn()));
+//! });
+//! ```
+//!
+//! ## Sparse gradients
+//!
+//! Only a few rows of an embedding table are used by each step, so its dense
+//! gradient is mostly zeros. [`grad_rows`] returns the gradient of a table read
+//! with [`embedding_lookup`] as the looked-up rows only ([`RowSparse`]), and
+//! [`RowSparseArray`] accumulates them into the table.
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_autograd as ag;
+//! use ag::sparse;
+//! use ag::tensor_ops as T;
+//!
+//! ag::run(|ctx: &mut ag::Context<f64>| {
+//!     let table = ctx.placeholder("table", &[1000, 2]);
+//!     let ids = T::convert_to_tensor(array![3., 7., 3.], ctx);
+//!     let loss = T::sum_all(sparse::embedding_lookup(table, ids));
+//!     let grad = sparse::grad_rows(&[loss], table);
+//!
+//!     let table_val = ndarray::Array2::<f64>::zeros((1000, 2)).into_dyn();
+//!     let feeder = ag::Feeder::new().push(table, table_val.view());
+//!     let grad = grad.eval_with(ctx, feeder).unwrap().coalesce();
+//!     assert_eq!(grad.indices, vec![3, 7]);
+//!     assert_eq!(grad.values, array![[2., 2.], [1., 1.]].into_dyn());
+//! });
+//! ```
+
+use crate::evaluation::Feeder;
+use crate::graph::TensorID;
+use crate::ndarray_ext::{NdArray, NdArrayView};
+use crate::op::{self, OpError};
+use crate::tensor::Tensor;
+use crate::tensor_ops as T;
+use crate::{Context, EvalError, Float};
+use ndarray::{Axis, Ix2};
+use std::any::type_name;
+use std::collections::{BTreeMap, HashSet};
+
+/// Storage format of a [`SparseTensor`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SparseFormat {
+    /// Coordinates: the row and column index of each element
+    Coo,
+    /// Compressed sparse rows: the elements sorted by row, with the offset of
+    /// each row and the column index of each element
+    Csr,
+}
+
+/// Lazy-evaluated sparse matrix
+///
+/// Like [`Tensor`], this is a cheap `Copy` handle into the graph.
+#[derive(Clone, Copy, Debug)]
+pub struct SparseTensor<'graph, F: Float> {
+    format: SparseFormat,
+    /// Row indices (COO) or row pointers (CSR)
+    rows: Tensor<'graph, F>,
+    cols: Tensor<'graph, F>,
+    values: Tensor<'graph, F>,
+    shape: [usize; 2],
+}
+
+impl<'graph, F: Float> SparseTensor<'graph, F> {
+    /// Creates a COO matrix with the elements `values[k]` at `(rows[k], cols[k])`.
+    ///
+    /// Duplicate coordinates are summed.
+    pub fn coo(
+        rows: Tensor<'graph, F>,
+        cols: Tensor<'graph, F>,
+        values: Tensor<'graph, F>,
+        shape: [usize; 2],
+    ) -> Self {
+        SparseTensor {
+            format: SparseFormat::Coo,
+            rows,
+            cols,
+            values,
+            shape,
+        }
+    }
+
+    /// Creates a CSR matrix whose row `i` has the elements
+    /// `values[indptr[i]..indptr[i + 1]]` in the columns `cols[indptr[i]..indptr[i + 1]]`.
+    pub fn csr(
+        indptr: Tensor<'graph, F>,
+        cols: Tensor<'graph, F>,
+        values: Tensor<'graph, F>,
+        shape: [usize; 2],
+    ) -> Self {
+        SparseTensor {
+            format: SparseFormat::Csr,
+            rows: indptr,
+            cols,
+            values,
+            shape,
+        }
+    }
+
+    /// Creates a constant sparse matrix of the nonzero elements of `arr`.
+    pub fn from_dense(
+        arr: &ndarray::ArrayView2<F>,
+        format: SparseFormat,
+        ctx: &'graph Context<'graph, F>,
+    ) -> Self {
+        let (mut rows, mut cols, mut values) = (vec![], vec![], vec![]);
+        let mut indptr = vec![F::zero()];
+        for (i, row) in arr.outer_iter().enumerate() {
+            for (j, &a) in row.iter().enumerate() {
+                if a != F::zero() {
+                    rows.push(F::from(i).unwrap());
+                    cols.push(F::from(j).unwrap());
+                    values.push(a);
+                }
+            }
+            indptr.push(F::from(values.len()).unwrap());
+        }
+        let rows = match format {
+            SparseFormat::Coo => rows,
+            SparseFormat::Csr => indptr,
+        };
+        let vector = |a: Vec<F>| T::convert_to_tensor(ndarray::Array1::from(a), ctx);
+        SparseTensor {
+            format,
+            rows: vector(rows),
+            cols: vector(cols),
+            values: vector(values),
+            shape: [arr.nrows(), arr.ncols()],
+        }
+    }
+
+    /// Storage format
+    pub fn format(&self) -> SparseFormat {
+        self.format
+    }
+
+    /// Number of rows and columns
+    pub fn shape(&self) -> [usize; 2] {
+        self.shape
+    }
+
+    /// Row indices (COO) or row pointers (CSR), and column indices
+    pub fn indices(&self) -> (Tensor<'graph, F>, Tensor<'graph, F>) {
+        (self.rows, self.cols)
+    }
+
+    /// Values of the stored elements
+    pub fn values(&self) -> Tensor<'graph, F> {
+        self.values
+    }
+
+    /// Same sparsity pattern with other values, e.g. a variable.
+    pub fn with_values(&self, values: Tensor<'graph, F>) -> Self {
+        SparseTensor { values, ..*self }
+    }
+
+    /// Converts to the COO format.
+    pub fn to_coo(&self) -> Self {
+        match self.format {
+            SparseFormat::Coo => *self,
+            SparseFormat::Csr => {
+                let rows = Tensor::builder(self.rows.graph())
+                    .append_input(self.rows, false)
+                    .set_differentiable(false)
+                    .build(CsrRowIndices);
+                SparseTensor::coo(rows, self.cols, self.values, self.shape)
+            }
+        }
+    }
+
+    /// Converts to the CSR format, sorting the elements by row and column.
+    pub fn to_csr(&self) -> Self {
+        match self.format {
+            SparseFormat::Csr => *self,
+            SparseFormat::Coo => {
+                let outputs = Tensor::builder(self.rows.graph())
+                    .append_input(self.rows, false)
+                    .append_input(self.cols, false)
+                    .set_differentiable(false)
+                    .build_outputs(CooToCsr { shape: self.shape });
+                let (order, indptr) = (outputs[0], outputs[1]);
+                SparseTensor::csr(
+                    indptr,
+                    T::gather(self.cols, order, 0),
+                    T::gather(self.values, order, 0),
+                    self.shape,
+                )
+            }
+        }
+    }
+
+    /// Transposed matrix, in the COO format
+    pub fn transpose(&self) -> Self {
+        let coo = self.to_coo();
+        SparseTensor::coo(
+            coo.cols,
+            coo.rows,
+            coo.values,
+            [self.shape[1], self.shape[0]],
+        )
+    }
+
+    /// Dense matrix
+    pub fn to_dense(&self) -> Tensor<'graph, F> {
+        Tensor::builder(self.rows.graph())
+            .append_input(self.rows, false)
+            .append_input(self.cols, false)
+            .append_input(self.values, false)
+            .build(SparseToDense {
+                format: self.format,
+                shape: self.shape,
+            })
+    }
+}
+
+/// Product of a sparse and a dense matrix
+pub fn matmul<'graph, F: Float, A>(a: SparseTensor<'graph, F>, b: A) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+{
+    Tensor::builder(a.rows.graph())
+        .append_input(a.rows, false)
+        .append_input(a.cols, false)
+        .append_input(a.values, false)
+        .append_input(b.as_ref(), false)
+        .build(SparseMatMul {
+            format: a.format,
+            shape: a.shape,
+            transpose: false,
+        })
+}
+
+/// Rows `ids` of `table`.
+///
+/// The result has the shape of `ids` followed by the shape of a row. The
+/// gradient with respect to `table` is also available as the looked-up rows
+/// only, see [`grad_rows`].
+pub fn embedding_lookup<'graph, F: Float, A, B>(table: A, ids: B) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+    B: AsRef<Tensor<'graph, F>> + Copy,
+{
+    let table = table.as_ref();
+    Tensor::builder(table.graph())
+        .append_input(table, false)
+        .append_input(ids.as_ref(), false)
+        .build(EmbeddingLookup)
+}
+
+/// Gradient of `ys` with respect to the rows of `table` read by [`embedding_lookup`]
+///
+/// The rows looked up several times appear several times, see
+/// [`RowSparseArray::coalesce`]. Uses of `table` other than embedding lookups
+/// are not differentiated.
+pub fn grad_rows<'graph, F: Float, A, B>(ys: &[A], table: B) -> RowSparse<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>>,
+    B: AsRef<Tensor<'graph, F>> + Copy,
+{
+    let table = table.as_ref();
+    let g = table.graph();
+
+    // Lookups of `table` used by `ys`
+    let mut lookups: Vec<TensorID> = vec![];
+    let mut visited: HashSet<TensorID> = ys.iter().map(|y| y.as_ref().id()).collect();
+    let mut stack: Vec<TensorID> = visited.iter().copied().collect();
+    while let Some(id) = stack.pop() {
+        let node = g.access_inner(id);
+        let is_lookup =
+            node.op.as_ref().map(|op| op.name()) == Some(type_name::<EmbeddingLookup>());
+        if is_lookup && node.incoming_nodes[0].id == table.id() {
+            lookups.push(id);
+        }
+        for x in &node.incoming_nodes {
+            if visited.insert(x.id) {
+                stack.push(x.id);
+            }
+        }
+    }
+    lookups.sort_unstable();
+
+    let outputs: Vec<Tensor<F>> = lookups.iter().map(|&id| g.tensor(id)).collect();
+    let grads = T::grad(ys, &outputs);
+    let shape = T::shape(table);
+    let mut builder = Tensor::builder(g)
+        .append_input(shape, false)
+        .set_differentiable(false);
+    for &id in &lookups {
+        builder = builder.append_input(g.tensor(g.access_inner(id).incoming_nodes[1].id), false);
+    }
+    for gy in &grads {
+        builder = builder.append_input(gy, false);
+    }
+    let merged = builder.build_outputs(ConcatRows {
+        num_lookups: lookups.len(),
+    });
+    RowSparse {
+        indices: merged[0],
+        values: merged[1],
+        shape,
+    }
+}
+
+/// Lazy-evaluated rows of a dense array, the others being zeros
+#[derive(Clone, Copy, Debug)]
+pub struct RowSparse<'graph, F: Float> {
+    indices: Tensor<'graph, F>,
+    values: Tensor<'graph, F>,
+    shape: Tensor<'graph, F>,
+}
+
+impl<'graph, F: Float> RowSparse<'graph, F> {
+    /// Indices of the rows
+    pub fn indices(&self) -> Tensor<'graph, F> {
+        self.indices
+    }
+
+    /// The rows, stacked along the first axis
+    pub fn values(&self) -> Tensor<'graph, F> {
+        self.values
+    }
+
+    /// Dense array, where rows with the same index are summed
+    pub fn to_dense(&self) -> Tensor<'graph, F> {
+        Tensor::builder(self.values.graph())
+            .append_input(self.values, false)
+            .append_input(self.indices, false)
+            .append_input(self.shape, false)
+            .build(ScatterAddRows)
+    }
+
+    /// Evaluates the rows.
+    pub fn eval(&self, ctx: &Context<'graph, F>) -> Result<RowSparseArray<F>, EvalError> {
+        self.eval_with(ctx, Feeder::new())
+    }
+
+    /// Evaluates the rows with the placeholder values of `feeder`.
+    pub fn eval_with(
+        &self,
+        ctx: &Context<'graph, F>,
+        feeder: Feeder<F>,
+    ) -> Result<RowSparseArray<F>, EvalError> {
+        let mut results = ctx
+            .evaluator()
+            .set_feeder(feeder)
+            .push(&self.indices)
+            .push(&self.values)
+            .push(&self.shape)
+            .run();
+        let shape = results.pop().unwrap()?;
+        let values = results.pop().unwrap()?;
+        let indices = results.pop().unwrap()?;
+        Ok(RowSparseArray {
+            indices: indices.iter().map(|a| a.to_usize().unwrap()).collect(),
+            values,
+            shape: shape.iter().map(|a| a.to_usize().unwrap()).collect(),
+        })
+    }
+}
+
+/// Rows of a dense array, the others being zeros; the evaluation result of a [`RowSparse`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowSparseArray<F: Float> {
+    /// Index of each row
+    pub indices: Vec<usize>,
+    /// The rows, stacked along the first axis
+    pub values: NdArray<F>,
+    /// Shape of the dense array
+    pub shape: Vec<usize>,
+}
+
+impl<F: Float> RowSparseArray<F> {
+    /// Sums the rows with the same index, and sorts them by index.
+    pub fn coalesce(&self) -> Self {
+        let mut rows: BTreeMap<usize, NdArray<F>> = BTreeMap::new();
+        for (&i, row) in self.indices.iter().zip(self.values.outer_iter()) {
+            match rows.get_mut(&i) {
+                Some(sum) => *sum += &row,
+                None => {
+                    rows.insert(i, row.to_owned());
+                }
+            }
+        }
+        let mut shape = self.values.shape().to_vec();
+        shape[0] = rows.len();
+        let values = rows.values().flat_map(|row| row.iter().copied()).collect();
+        RowSparseArray {
+            indices: rows.keys().copied().collect(),
+            values: NdArray::from_shape_vec(shape, values).unwrap(),
+            shape: self.shape.clone(),
+        }
+    }
+
+    /// Adds `alpha` times these rows to `dense`, e.g. `-lr` for a gradient descent step.
+    pub fn scaled_add_to(&self, alpha: F, dense: &mut NdArray<F>) {
+        for (&i, row) in self.indices.iter().zip(self.values.outer_iter()) {
+            dense.index_axis_mut(Axis(0), i).scaled_add(alpha, &row);
+        }
+    }
+
+    /// Dense array
+    pub fn to_dense(&self) -> NdArray<F> {
+        let mut dense = NdArray::zeros(self.shape.as_slice());
+        self.scaled_add_to(F::one(), &mut dense);
+        dense
+    }
+}
+
+fn to_index<F: Float>(a: F, len: usize, what: &str) -> Result<usize, OpError> {
+    match a.to_usize() {
+        Some(i) if i < len => Ok(i),
+        _ => Err(OpError::OutOfBounds(format!(
+            "{} {} for a length of {}",
+            what, a, len
+        ))),
+    }
+}
+
+/// Row and column of each stored element of a sparse matrix
+fn coordinates<F: Float>(
+    format: SparseFormat,
+    shape: [usize; 2],
+    rows: &NdArrayView<F>,
+    cols: &NdArrayView<F>,
+) -> Result<Vec<(usize, usize)>, OpError> {
+    let cols = cols
+        .iter()
+        .map(|&c| to_index(c, shape[1], "Column index"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let rows = match format {
+        SparseFormat::Coo => rows
+            .iter()
+            .map(|&r| to_index(r, shape[0], "Row index"))
+            .collect::<Result<Vec<_>, _>>()?,
+        SparseFormat::Csr => {
+            let indptr = rows
+                .iter()
+                .map(|&p| to_index(p, cols.len() + 1, "Row pointer"))
+                .collect::<Result<Vec<_>, _>>()?;
+            if indptr.len() != shape[0] + 1
+                || indptr.last() != Some(&cols.len())
+                || indptr.windows(2).any(|w| w[0] > w[1])
+            {
+                return Err(OpError::IncompatibleShape(format!(
+                    "Invalid row pointers {:?} for {} rows and {} elements",
+                    indptr,
+                    shape[0],
+                    cols.len()
+                )));
+            }
+            indptr
+                .windows(2)
+                .enumerate()
+                .flat_map(|(i, w)| std::iter::repeat_n(i, w[1] - w[0]))
+                .collect()
+        }
+    };
+    if rows.len() != cols.len() {
+        return Err(OpError::IncompatibleShape(format!(
+            "{} row indices for {} column indices",
+            rows.len(),
+            cols.len()
+        )));
+    }
+    Ok(rows.into_iter().zip(cols).collect())
+}
+
+fn matrix<'a, F: Float>(
+    x: &'a NdArrayView<'a, F>,
+    rows: usize,
+) -> Result<ndarray::ArrayView2<'a, F>, OpError> {
+    let x = x
+        .view()
+        .into_dimensionality::<Ix2>()
+        .map_err(|e| OpError::NdArrayError("Sparse matmul".into(), e))?;
+    if x.nrows() != rows {
+        return Err(OpError::IncompatibleShape(format!(
+            "Expected a matrix with {} rows, got {:?}",
+            rows,
+            x.shape()
+        )));
+    }
+    Ok(x)
+}
+
+/// Dense matrix of a sparse one; inputs: rows, cols, values
+struct SparseToDense {
+    format: SparseFormat,
+    shape: [usize; 2],
+}
+
+/// Elements of a dense matrix where a sparse one has elements; inputs: rows, cols, dense
+struct SparseGather {
+    format: SparseFormat,
+    shape: [usize; 2],
+}
+
+/// Product of a sparse matrix, or of its transpose, and a dense matrix;
+/// inputs: rows, cols, values, dense
+struct SparseMatMul {
+    format: SparseFormat,
+    shape: [usize; 2],
+    transpose: bool,
+}
+
+/// Dot products `a[i] . b[j]` of rows of dense matrices for the elements (i, j)
+/// of a sparse matrix, or (j, i) if transposed; inputs: rows, cols, a, b
+struct SampledMatMul {
+    format: SparseFormat,
+    shape: [usize; 2],
+    transpose: bool,
+}
+
+/// Row index of each element of a CSR matrix; input: row pointers
+struct CsrRowIndices;
+
+/// Order of the elements of a COO matrix sorted by row and column, and the
+/// row pointers of that order; inputs: rows, cols
+struct CooToCsr {
+    shape: [usize; 2],
+}
+
+/// Rows of a table; inputs: table, ids
+struct EmbeddingLookup;
+
+/// Rows added to zeros; inputs: rows, ids, shape
+struct ScatterAddRows;
+
+/// Ids and gradients of several lookups of the same table, stacked;
+/// inputs: shape of the table, ids of each lookup, gradient of each lookup
+struct ConcatRows {
+    num_lookups: usize,
+}
+
+impl<F: Float> op::Op<F> for SparseToDense {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let coords = coordinates(self.format, self.shape, &ctx.input(0), &ctx.input(1))?;
+        let values = ctx.input(2);
+        if values.len() != coords.len() {
+            return Err(OpError::IncompatibleShape(format!(
+                "{} values for {} elements",
+                values.len(),
+                coords.len()
+            )));
+        }
+        let mut dense = ndarray::Array2::zeros(self.shape);
+        for (&(i, j), &v) in coords.iter().zip(values.iter()) {
+            dense[[i, j]] += v;
+        }
+        ctx.append_output(dense.into_dyn());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        let gv = Tensor::builder(ctx.graph())
+            .append_input(ctx.input(0), false)
+            .append_input(ctx.input(1), false)
+            .append_input(ctx.output_grad(), false)
+            .build(SparseGather {
+                format: self.format,
+                shape: self.shape,
+            });
+        ctx.append_input_grad(0, None);
+        ctx.append_input_grad(1, None);
+        ctx.append_input_grad(2, Some(gv));
+    }
+}
+
+impl<F: Float> op::Op<F> for SparseGather {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let coords = coordinates(self.format, self.shape, &ctx.input(0), &ctx.input(1))?;
+        let dense = ctx.input(2);
+        let dense = matrix(&dense, self.shape[0])?;
+        let values: ndarray::Array1<F> = coords.iter().map(|&(i, j)| dense[[i, j]]).collect();
+        ctx.append_output(values.into_dyn());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        let gx = Tensor::builder(ctx.graph())
+            .append_input(ctx.input(0), false)
+            .append_input(ctx.input(1), false)
+            .append_input(ctx.output_grad(), false)
+            .build(SparseToDense {
+                format: self.format,
+                shape: self.shape,
+            });
+        ctx.append_input_grad(0, None);
+        ctx.append_input_grad(1, None);
+        ctx.append_input_grad(2, Some(gx));
+    }
+}
+
+impl<F: Float> op::Op<F> for SparseMatMul {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let coords = coordinates(self.format, self.shape, &ctx.input(0), &ctx.input(1))?;
+        let values = ctx.input(2);
+        let b = ctx.input(3);
+        let (rows, inner) = if self.transpose {
+            (self.shape[1], self.shape[0])
+        } else {
+            (self.shape[0], self.shape[1])
+        };
+        let b = matrix(&b, inner)?;
+        let mut y = ndarray::Array2::zeros((rows, b.ncols()));
+        for (&(i, j), &v) in coords.iter().zip(values.iter()) {
+            let (i, j) = if self.transpose { (j, i) } else { (i, j) };
+            y.row_mut(i).scaled_add(v, &b.row(j));
+        }
+        ctx.append_output(y.into_dyn());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        let g = ctx.graph();
+        let (rows, cols, values, b) = (ctx.input(0), ctx.input(1), ctx.input(2), ctx.input(3));
+        let gy = ctx.output_grad();
+        let gv = Tensor::builder(g)
+            .append_input(rows, false)
+            .append_input(cols, false)
+            .append_input(gy, false)
+            .append_input(b, false)
+            .build(SampledMatMul {
+                format: self.format,
+                shape: self.shape,
+                transpose: self.transpose,
+            });
+        let gb = Tensor::builder(g)
+            .append_input(rows, false)
+            .append_input(cols, false)
+            .append_input(values, false)
+            .append_input(gy, false)
+            .build(SparseMatMul {
+                format: self.format,
+                shape: self.shape,
+                transpose: !self.transpose,
+            });
+        ctx.append_input_grad(0, None);
+        ctx.append_input_grad(1, None);
+        ctx.append_input_grad(2, Some(gv));
+        ctx.append_input_grad(3, Some(gb));
+    }
+}
+
+impl<F: Float> op::Op<F> for SampledMatMul {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let coords = coordinates(self.format, self.shape, &ctx.input(0), &ctx.input(1))?;
+        let (a, b) = (ctx.input(2), ctx.input(3));
+        let (a_rows, b_rows) = if self.transpose {
+            (self.shape[1], self.shape[0])
+        } else {
+            (self.shape[0], self.shape[1])
+        };
+        let (a, b) = (matrix(&a, a_rows)?, matrix(&b, b_rows)?);
+        let values: ndarray::Array1<F> = coords
+            .iter()
+            .map(|&(i, j)| {
+                let (i, j) = if self.transpose { (j, i) } else { (i, j) };
+                a.row(i).dot(&b.row(j))
+            })
+            .collect();
+        ctx.append_output(values.into_dyn());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        let g = ctx.graph();
+        let (rows, cols, a, b) = (ctx.input(0), ctx.input(1), ctx.input(2), ctx.input(3));
+        let gv = ctx.output_grad();
+        let product = |transpose: bool, x| {
+            Tensor::builder(g)
+                .append_input(rows, false)
+                .append_input(cols, false)
+                .append_input(gv, false)
+                .append_input(x, false)
+                .build(SparseMatMul {
+                    format: self.format,
+                    shape: self.shape,
+                    transpose,
+                })
+        };
+        ctx.append_input_grad(0, None);
+        ctx.append_input_grad(1, None);
+        ctx.append_input_grad(2, Some(product(self.transpose, b)));
+        ctx.append_input_grad(3, Some(product(!self.transpose, a)));
+    }
+}
+
+impl<F: Float> op::Op<F> for CsrRowIndices {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let indptr: Vec<F> = ctx.input(0).iter().copied().collect();
+        let mut rows = vec![];
+        for (i, w) in indptr.windows(2).enumerate() {
+            let n = (w[1] - w[0]).to_usize().ok_or_else(|| {
+                OpError::IncompatibleShape(format!("Decreasing row pointers {} {}", w[0], w[1]))
+            })?;
+            rows.extend(std::iter::repeat_n(F::from(i).unwrap(), n));
+        }
+        ctx.append_output(ndarray::Array1::from(rows).into_dyn());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        ctx.append_input_grad(0, None);
+    }
+}
+
+impl<F: Float> op::Op<F> for CooToCsr {
+    fn num_outputs(&self) -> usize {
+        2
+    }
+
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let coords = coordinates(SparseFormat::Coo, self.shape, &ctx.input(0), &ctx.input(1))?;
+        let mut order: Vec<usize> = (0..coords.len()).collect();
+        order.sort_by_key(|&k| coords[k]);
+        let mut indptr = vec![0; self.shape[0] + 1];
+        for &(i, _) in &coords {
+            indptr[i + 1] += 1;
+        }
+        for i in 0..self.shape[0] {
+            indptr[i + 1] += indptr[i];
+        }
+        let floats = |v: Vec<usize>| {
+            ndarray::Array1::from_iter(v.into_iter().map(|a| F::from(a).unwrap())).into_dyn()
+        };
+        ctx.append_output(floats(order));
+        ctx.append_output(floats(indptr));
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        ctx.append_input_grad(0, None);
+        ctx.append_input_grad(1, None);
+    }
+}
+
+impl<F: Float> op::Op<F> for EmbeddingLookup {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let (table, ids) = (ctx.input(0), ctx.input(1));
+        if table.ndim() == 0 {
+            return Err(OpError::InvalidDims(
+                "Embedding table must have at least one axis".into(),
+            ));
+        }
+        let rows = ids
+            .iter()
+            .map(|&i| Ok(table.index_axis(Axis(0), to_index(i, table.len_of(Axis(0)), "Id")?)))
+            .collect::<Result<Vec<_>, OpError>>()?;
+        let mut shape = ids.shape().to_vec();
+        shape.extend_from_slice(&table.shape()[1..]);
+        let values = rows.iter().flat_map(|row| row.iter().copied()).collect();
+        let y = NdArray::from_shape_vec(shape, values)
+            .map_err(|e| OpError::NdArrayError("Embedding lookup".into(), e))?;
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        let gx = Tensor::builder(ctx.graph())
+            .append_input(ctx.output_grad(), false)
+            .append_input(ctx.input(1), false)
+            .append_input(T::shape(ctx.input(0)), false)
+            .build(ScatterAddRows);
+        ctx.append_input_grad(0, Some(gx));
+        ctx.append_input_grad(1, None);
+    }
+}
+
+impl<F: Float> op::Op<F> for ScatterAddRows {
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let (rows, ids) = (ctx.input(0), ctx.input(1));
+        let shape: Vec<usize> = ctx.input(2).iter().map(|a| a.to_usize().unwrap()).collect();
+        let mut dense = NdArray::zeros(shape.as_slice());
+        let row_len: usize = shape[1..].iter().product();
+        let rows = rows
+            .to_shape((ids.len(), row_len))
+            .map_err(|e| OpError::NdArrayError("Scatter of rows".into(), e))?;
+        for (&i, row) in ids.iter().zip(rows.outer_iter()) {
+            let i = to_index(i, shape[0], "Id")?;
+            let mut dst = dense.index_axis_mut(Axis(0), i);
+            for (d, &r) in dst.iter_mut().zip(row.iter()) {
+                *d += r;
+            }
+        }
+        ctx.append_output(dense);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        let gx = embedding_lookup(ctx.output_grad(), ctx.input(1));
+        ctx.append_input_grad(0, Some(gx));
+        ctx.append_input_grad(1, None);
+        ctx.append_input_grad(2, None);
+    }
+}
+
+impl<F: Float> op::Op<F> for ConcatRows {
+    fn num_outputs(&self) -> usize {
+        2
+    }
+
+    fn compute(&self, ctx: &mut op::ComputeContext<F>) -> Result<(), OpError> {
+        let shape: Vec<usize> = ctx.input(0).iter().map(|a| a.to_usize().unwrap()).collect();
+        let row_len: usize = shape[1..].iter().product();
+        let (mut indices, mut values) = (vec![], vec![]);
+        for k in 0..self.num_lookups {
+            let (ids, gy) = (ctx.input(1 + k), ctx.input(1 + self.num_lookups + k));
+            indices.extend(ids.iter().copied());
+            values.extend(gy.iter().copied());
+        }
+        let mut values_shape = vec![indices.len()];
+        values_shape.extend_from_slice(&shape[1..]);
+        if values.len() != indices.len() * row_len {
+            return Err(OpError::IncompatibleShape(format!(
+                "{} gradient elements for {} rows of shape {:?}",
+                values.len(),
+                indices.len(),
+                &shape[1..]
+            )));
+        }
+        ctx.append_output(ndarray::Array1::from(indices).into_dyn());
+        ctx.append_output(NdArray::from_shape_vec(values_shape, values).unwrap());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<F>) {
+        for i in 0..1 + 2 * self.num_lookups {
+            ctx.append_input_grad(i, None);
+        }
+    }
+}
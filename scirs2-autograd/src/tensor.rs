@@ -2,6 +2,7 @@ use crate::Float;
 use crate::{op, Context};
 use crate::{NdArray, NdArrayView};
 
+use crate::device::Device;
 use crate::error::OpError;
 use crate::graph::{AsGraph, Graph, TensorID};
 use crate::op::{GradientContext, SmallVec};
@@ -11,6 +12,7 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::ops::{Add, Div, Mul, Sub};
+use std::rc::Rc;
 
 /// Lazy-evaluated multi-dimensional array
 ///
@@ -94,8 +96,10 @@ impl<'graph, F: Float> Tensor<'graph, F> {
     }
 
     /// Returns the graph to which this tensor belongs.
+    ///
+    /// Useful to build new tensors from the inputs of a user-defined op.
     #[inline]
-    pub(crate) fn graph(&self) -> &'graph Graph<F> {
+    pub fn graph(&self) -> &'graph Graph<F> {
         self.graph
     }
 
@@ -170,6 +174,7 @@ impl<'graph, F: Float> Tensor<'graph, F> {
             backprop_inputs: None,
             known_shape: None,
             variable_id: None,
+            device: None,
         }
     }
 
@@ -336,6 +341,86 @@ impl<'graph, F: Float> Tensor<'graph, F> {
         })
     }
 
+    /// Registers a gradient hook on the receiver tensor.
+    ///
+    /// The hook is called with the gradient of the receiver whenever the gradient is
+    /// evaluated, and may replace it (see [crate::hooks::GradHook]).
+    /// The printing hooks of [crate::hooks] can also be used to inspect gradients.
+    ///
+    ///    ```
+    /// use scirs2_autograd as ag;
+    /// use ag::tensor_ops::*;
+    ///
+    /// ag::run(|g| {
+    ///     let x: ag::Tensor<f32> = g.placeholder("x", &[2]);
+    ///     let y = sum_all(square(x).register_grad_hook(ag::hooks::ShowPrefixed("grad:")));
+    ///
+    ///     let gx = grad(&[y], &[x])[0];
+    ///     g.evaluator()
+    ///         .push(&gx)
+    ///         .feed(x, ag::ndarray::arr1(&[1., 2.]).view().into_dyn())
+    ///         .run();
+    ///     // grad: [1.0, 1.0], shape=[2], strides=[1], layout=CFcf (0xf), dynamic ndim=1
+    /// });
+    ///    ```
+    #[inline]
+    pub fn register_grad_hook<H: crate::hooks::GradHook<F> + 'static>(
+        self,
+        hook: H,
+    ) -> Tensor<'graph, F> {
+        Tensor::builder(self.graph)
+            .append_input(self, false)
+            .build(crate::tensor_ops::hook_ops::GradHookOp::new(hook))
+    }
+
+    /// Sets a gradient hook that calls the given closure with the gradient of the receiver tensor.
+    ///
+    ///    ```
+    /// use scirs2_autograd as ag;
+    /// use ag::tensor_ops::*;
+    ///
+    /// ag::run(|g| {
+    ///     let x: ag::Tensor<f32> = g.placeholder("x", &[2]);
+    ///     let y = sum_all(square(x).grad_hook(|g| println!("{:?}", g)));
+    ///     grad(&[y], &[x])[0].eval(g);
+    /// });
+    ///    ```
+    #[inline]
+    pub fn grad_hook<FUN: Fn(&NdArrayView<F>) + 'static + Send + Sync>(
+        self,
+        f: FUN,
+    ) -> Tensor<'graph, F> {
+        self.register_grad_hook(crate::hooks::Raw {
+            raw: f,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Sets a gradient hook that replaces the gradient of the receiver tensor with the
+    /// result of the given closure, of the same shape.
+    ///
+    ///    ```
+    /// use scirs2_autograd as ag;
+    /// use ag::tensor_ops::*;
+    ///
+    /// ag::run(|g| {
+    ///     let x: ag::Tensor<f32> = g.placeholder("x", &[2]);
+    ///     // clips the gradient of `square(x)`
+    ///     let y = sum_all(square(x).map_grad(|g| g.mapv(|a| a.clamp(-0.5, 0.5))) * 3.);
+    ///     grad(&[y], &[x])[0].eval(g);
+    /// });
+    ///    ```
+    #[inline]
+    pub fn map_grad<FUN: Fn(&NdArrayView<F>) -> NdArray<F> + 'static + Send + Sync>(
+        self,
+        f: FUN,
+    ) -> Tensor<'graph, F> {
+        self.register_grad_hook(crate::hooks::MapGrad {
+            map: f,
+            phantom: PhantomData,
+        })
+    }
+
     /// Returns the id of this tensor in this graph.
     #[inline(always)]
     pub fn id(&self) -> usize {
@@ -407,6 +492,12 @@ impl<'graph, F: Float> Tensor<'graph, F> {
         self.inner().is_differentiable
     }
 
+    /// Returns the device computing this tensor.
+    #[inline]
+    pub fn device(&self) -> Device {
+        self.inner().device
+    }
+
     /// True is this tensor was created by `Graph::variable`.
     #[inline]
     #[allow(unused)]
@@ -470,7 +561,7 @@ pub(crate) struct TensorInternal<F: Float> {
     pub(crate) id: usize,
 
     /// Operation to evaluate this tensor.
-    pub(crate) op: Option<Box<dyn op::Op<F>>>,
+    pub(crate) op: Option<Rc<dyn op::Op<F>>>,
 
     /// References to immediate predecessors.
     pub(crate) incoming_nodes: SmallVec<IncomingTensor>,
@@ -498,6 +589,9 @@ pub(crate) struct TensorInternal<F: Float> {
 
     /// ID to lookup variable array in VariableEnvironment
     pub(crate) variable_id: Option<VariableID>,
+
+    /// Device computing this tensor
+    pub(crate) device: Device,
 }
 
 impl<F: Float> TensorInternal<F> {
@@ -506,7 +600,7 @@ impl<F: Float> TensorInternal<F> {
     pub fn new() -> Self {
         TensorInternal {
             id: 0,
-            op: Some(Box::new(Dummy)),
+            op: Some(Rc::new(Dummy)),
             incoming_nodes: SmallVec::new(),
             topo_rank: 0,
             shape: None,
@@ -515,6 +609,7 @@ impl<F: Float> TensorInternal<F> {
             backprop_inputs: None,
             known_shape: None,
             variable_id: None,
+            device: Device::Cpu,
         }
     }
 
@@ -522,7 +617,7 @@ impl<F: Float> TensorInternal<F> {
     pub fn get_op(&self) -> &dyn op::Op<F> {
         self.op
             .as_ref()
-            .expect("bad impl: Op is now stolen in gradient.rs")
+            .expect("bad impl: tensor without an Op")
             .as_ref()
     }
 
@@ -698,11 +793,13 @@ pub struct TensorBuilder<'g, F: Float> {
     known_shape: Option<KnownShape>,
     variable_id: Option<VariableID>,
     placeholder_name: Option<&'static str>,
+    device: Option<Device>,
 }
 
 const NUM_MAX_KNOWN_SHAPE_SIZE: usize = 4;
 type ShapeVec = smallvec::SmallVec<[isize; NUM_MAX_KNOWN_SHAPE_SIZE]>;
 
+#[derive(Clone)]
 pub(crate) struct KnownShape {
     shape: ShapeVec,
     #[allow(dead_code)]
@@ -824,6 +921,13 @@ impl<'graph, F: Float> TensorBuilder<'graph, F> {
         self
     }
 
+    /// Places the tensor on `device` instead of the device of its inputs.
+    #[inline]
+    pub(crate) fn set_device(mut self, device: Device) -> TensorBuilder<'graph, F> {
+        self.device = Some(device);
+        self
+    }
+
     #[inline]
     pub(crate) fn set_placeholder_name(mut self, a: &'static str) -> TensorBuilder<'graph, F> {
         self.placeholder_name = Some(a);
@@ -854,6 +958,11 @@ impl<'graph, F: Float> TensorBuilder<'graph, F> {
     where
         O: op::Op<F> + 'static,
     {
+        self.build_shared(Rc::new(op))
+    }
+
+    /// Same as [TensorBuilder::build] with an op which may be shared.
+    pub(crate) fn build_shared(self, op: Rc<dyn op::Op<F>>) -> Tensor<'graph, F> {
         let graph = self.graph;
         let rank = if self.in_nodes.is_empty() {
             0
@@ -866,10 +975,19 @@ impl<'graph, F: Float> TensorBuilder<'graph, F> {
                 .unwrap_or(0)
         };
 
+        // On a GPU if any input is
+        let device = self.device.unwrap_or_else(|| {
+            self.in_nodes
+                .iter()
+                .map(|a| graph.access_inner(a.id).device)
+                .max()
+                .unwrap_or_default()
+        });
+
         let new = TensorInternal {
             // `id` is set in `Graph::install`
             id: usize::default(),
-            op: Some(Box::new(op)),
+            op: Some(op),
             incoming_nodes: self.in_nodes,
             topo_rank: rank,
             shape: self.shape,
@@ -878,12 +996,26 @@ impl<'graph, F: Float> TensorBuilder<'graph, F> {
             known_shape: self.known_shape,
             variable_id: self.variable_id,
             placeholder_name: self.placeholder_name,
+            device,
         };
         Tensor {
             id: graph.install(new),
             graph,
         }
     }
+
+    /// Finalizes this builder with a multi-output `Op` and returns a tensor for
+    /// each of its [num_outputs](op::Op::num_outputs) outputs.
+    pub fn build_outputs<O>(self, op: O) -> Vec<Tensor<'graph, F>>
+    where
+        O: op::Op<F> + 'static,
+    {
+        let num_outputs = op.num_outputs();
+        let y = self.build(op);
+        let mut outputs = vec![y];
+        outputs.extend((1..num_outputs).map(|i| crate::tensor_ops::nth_tensor(y, i)));
+        outputs
+    }
 }
 
 #[allow(dead_code)]
@@ -6,7 +6,10 @@ use crate::tensor::Tensor;
 use crate::tensor_ops::*;
 use crate::Float;
 use ndarray;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 
+#[derive(Serialize, Deserialize)]
 pub struct Elu<T> {
     pub alpha: T,
 }
@@ -15,14 +18,19 @@ pub struct EluGrad<T> {
     pub alpha: T,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Identity;
 
+#[derive(Serialize, Deserialize)]
 pub struct ReLU;
 
+#[derive(Serialize, Deserialize)]
 pub struct Sigmoid;
 
+#[derive(Serialize, Deserialize)]
 pub struct Softplus;
 
+#[derive(Serialize, Deserialize)]
 pub struct Softmax {
     pub axis: isize,
 }
@@ -87,21 +95,63 @@ pub fn softmax_impl<T: Float>(x: &NdArrayView<T>, axis: isize) -> NdArray<T> {
 }
 
 impl<T: Float> op::Op<T> for Softmax {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Softmax", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let ret = softmax_impl(&ctx.input(0), self.axis);
         ctx.append_output(ret);
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::softmax(ctx, gpu, self.axis)
+    }
+
     fn grad<'a>(&self, ctx: &mut crate::op::GradientContext<'a, 'a, T>) {
         let y = ctx.output();
         let gy = ctx.output_grad();
         let sum = reduce_sum(y * gy, &[self.axis], true);
         ctx.append_input_grad(0, Some((gy - sum) * y))
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let axis = if self.axis < 0 {
+            self.axis
+        } else {
+            self.axis + 1
+        };
+        let y = Tensor::builder(ctx.graph())
+            .append_input(ctx.input(0), false)
+            .build(Softmax { axis });
+        ctx.set_output(y, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let y = ctx.output();
+        let t = ctx.input_tangent(0).map(|t| {
+            let yt = *y * *t;
+            yt - *y * reduce_sum(yt, &[self.axis], true)
+        });
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Softplus {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Softplus", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| (a.exp() + T::one()).ln()))
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let ret = ctx.input(0).map(move |a| (a.exp() + T::one()).ln());
         ctx.append_output(ret);
@@ -118,6 +168,17 @@ impl<T: Float> op::Op<T> for Softplus {
 }
 
 impl<T: Float> op::Op<T> for Sigmoid {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Sigmoid", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| {
+            let half = T::from(0.5).unwrap();
+            ((a * half).tanh() * half) + half
+        }))
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let half = T::from(0.5).unwrap();
         let ret = ctx
@@ -127,20 +188,54 @@ impl<T: Float> op::Op<T> for Sigmoid {
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::unary(ctx, gpu, "sigmoid")
+    }
+
     fn grad<'a>(&self, ctx: &mut crate::op::GradientContext<'a, 'a, T>) {
         let gy = ctx.output_grad();
         let y = ctx.output();
         ctx.append_input_grad(0, Some(gy * (y - square(y))));
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let y = ctx.output();
+        let t = ctx
+            .input_tangent(0)
+            .map(|t| *t * y * (scalar(T::one(), ctx.graph()) - y));
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReLU {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReLU", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.max(T::zero())))
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let ret = ctx.input(0).map(|a| a.max(T::zero()));
         ctx.append_output(ret);
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::unary(ctx, gpu, "relu")
+    }
+
     fn grad<'a>(&self, ctx: &mut crate::op::GradientContext<'a, 'a, T>) {
         let s = ctx.graph();
         let gy = ctx.output_grad();
@@ -150,6 +245,14 @@ impl<T: Float> op::Op<T> for ReLU {
 }
 
 impl<T: Float> op::Op<T> for Identity {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Identity", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a))
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         // do nothing
         let ret = ctx.input(0);
@@ -161,9 +264,29 @@ impl<T: Float> op::Op<T> for Identity {
         let gy = ctx.output_grad();
         ctx.append_input_grad(0, Some(gy.to_owned()))
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Elu<T> {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Elu", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        let alpha = self.alpha;
+        Some(Rc::new(move |a: T| {
+            if a > T::zero() {
+                a
+            } else {
+                alpha * (a.exp() - T::one())
+            }
+        }))
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let ret = ctx.input(0).mapv(move |a| {
             if a > T::zero() {
@@ -203,15 +326,33 @@ impl<T: Float> op::Op<T> for EluGrad<T> {
     }
 
     fn grad<'a>(&self, ctx: &mut crate::op::GradientContext<'a, 'a, T>) {
-        ctx.append_input_grad(0, None);
-        ctx.append_input_grad(1, None);
+        let s = ctx.graph();
+        let x = ctx.input(0);
+        let gy = ctx.input(1);
+        let gz = ctx.output_grad();
+
+        // elu''(x) = alpha * exp(x) for x <= 0 and 0 otherwise
+        let elu2 = lesser_equal(x, scalar(T::zero(), s)) * exp(x) * scalar(self.alpha, s);
+        ctx.append_input_grad(0, Some(elu2 * gy * gz));
+
+        let ggy = Tensor::builder(s)
+            .append_input(x, false)
+            .append_input(gz, false)
+            .set_shape(&shape(gz))
+            .build(EluGrad { alpha: self.alpha });
+        ctx.append_input_grad(1, Some(ggy));
     }
 }
 
 /// Swish activation function: x * sigmoid(x)
+#[derive(Serialize, Deserialize)]
 pub struct Swish;
 
 impl<T: Float> op::Op<T> for Swish {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Swish", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         // Compute sigmoid(x) first
@@ -241,9 +382,14 @@ impl<T: Float> op::Op<T> for Swish {
 
 /// GELU (Gaussian Error Linear Unit) activation function
 /// GELU(x) = 0.5 * x * (1 + tanh(sqrt(2/π) * (x + 0.044715 * x³)))
+#[derive(Serialize, Deserialize)]
 pub struct Gelu;
 
 impl<T: Float> op::Op<T> for Gelu {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Gelu", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
 
@@ -298,9 +444,14 @@ impl<T: Float> op::Op<T> for Gelu {
 
 /// Mish activation function: x * tanh(softplus(x))
 /// Mish(x) = x * tanh(ln(1 + exp(x)))
+#[derive(Serialize, Deserialize)]
 pub struct Mish;
 
 impl<T: Float> op::Op<T> for Mish {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Mish", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
 
@@ -353,11 +504,16 @@ impl<T: Float> op::Op<T> for Mish {
 /// Parametric ReLU (PReLU) activation function
 /// PReLU(x) = x if x > 0, else alpha * x
 /// where alpha is a learnable parameter
+#[derive(Serialize, Deserialize)]
 pub struct PReLU<T> {
     pub alpha: T,
 }
 
 impl<T: Float> op::Op<T> for PReLU<T> {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("PReLU", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = ctx.input(0);
         let ret = x.mapv(|val| {
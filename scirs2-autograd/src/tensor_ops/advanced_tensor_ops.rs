@@ -1,8 +1,10 @@
-use crate::op::{ComputeContext, GradientContext, Op, OpError};
+use crate::op::{ComputeContext, GradientContext, Op, OpDef, OpError};
 use crate::tensor::Tensor;
 use crate::tensor_ops::convert_to_tensor;
 use crate::Float;
 use ndarray::{Array2, ArrayD, IxDyn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Solve tensor equation a_ijk... x_jk... = b_i...
 pub struct TensorSolveOp {
@@ -91,57 +93,73 @@ impl<F: Float + ndarray::ScalarOperand> Op<F> for TensorSolveOp {
 }
 
 /// Generalized tensor contraction with pattern specification
+///
+/// Computes `output[out] = sum over the other labels of prod_i input_i[labels_i]`.
+/// Repeated labels in an input read its diagonal, repeated labels in the output
+/// write to the diagonal. Output labels missing from the inputs (only used by
+/// gradients) take their sizes from an extra last input.
+#[derive(Serialize, Deserialize)]
 pub struct EinsumOp {
-    pattern: String,
+    spec: EinsumSpec,
+    /// Whether the last input only gives the sizes of the output labels
+    shape_input: bool,
 }
 
-impl<F: Float + ndarray::ScalarOperand> Op<F> for EinsumOp {
+impl<F: Float> Op<F> for EinsumOp {
+    fn op_def(&self) -> Option<OpDef> {
+        OpDef::new("EinsumOp", self)
+    }
+
     fn name(&self) -> &'static str {
         "Einsum"
     }
 
     fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
-        // Parse einsum pattern
-        let (input_specs, output_spec) = parse_einsum_pattern(&self.pattern)?;
-
-        // For now, implement a simplified version for common cases
-        if input_specs.len() == 2 {
-            let a = ctx.input(0);
-            let b = ctx.input(1);
-
-            // Handle common patterns
-            if self.pattern == "ij,jk->ik" {
-                // Matrix multiplication
-                let result = compute_matmul(&a.view(), &b.view())?;
-                ctx.append_output(result);
-            } else if self.pattern == "i,i->" {
-                // Dot product
-                let result = compute_dot_product(&a.view(), &b.view())?;
-                ctx.append_output(result);
-            } else if self.pattern == "ij,ij->ij" {
-                // Element-wise multiplication
-                let result = compute_elementwise_mul(&a.view(), &b.view())?;
-                ctx.append_output(result);
-            } else {
-                // General einsum (simplified)
-                let result =
-                    compute_general_einsum(&a.view(), &b.view(), &input_specs, &output_spec)?;
-                ctx.append_output(result);
-            }
+        let inputs = ctx.inputs();
+        let (operands, shape_input) = if self.shape_input {
+            let (last, operands) = inputs.split_last().unwrap();
+            (operands, Some(last.shape()))
         } else {
-            return Err(OpError::Other(
-                "Only binary einsum operations supported".into(),
-            ));
-        }
+            (&inputs[..], None)
+        };
 
+        let mut sizes = self.spec.label_sizes(operands.iter().map(|x| x.shape()))?;
+        if let Some(shape) = shape_input {
+            for (&label, &size) in self.spec.output.iter().zip(shape) {
+                sizes.entry(label).or_insert(size);
+            }
+        }
+        let y = einsum_kernel(&self.spec, operands, &sizes)?;
+        ctx.append_output(y);
         Ok(())
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        // Simplified gradient - pass through
         let gy = ctx.output_grad();
-        ctx.append_input_grad(0, Some(*gy));
-        ctx.append_input_grad(1, Some(*gy));
+        let num_operands = self.spec.inputs.len();
+
+        // d out / d x_i contracts the output gradient with the other operands
+        for i in 0..num_operands {
+            let mut inputs = vec![self.spec.output.clone()];
+            let mut builder = Tensor::builder(ctx.graph()).append_input(gy, false);
+            for (j, labels) in self.spec.inputs.iter().enumerate() {
+                if j != i {
+                    inputs.push(labels.clone());
+                    builder = builder.append_input(ctx.input(j), false);
+                }
+            }
+            let gx = builder.append_input(ctx.input(i), false).build(EinsumOp {
+                spec: EinsumSpec {
+                    inputs,
+                    output: self.spec.inputs[i].clone(),
+                },
+                shape_input: true,
+            });
+            ctx.append_input_grad(i, Some(gx));
+        }
+        if self.shape_input {
+            ctx.append_input_grad(num_operands, None);
+        }
     }
 }
 
@@ -373,75 +391,228 @@ fn compute_grad_a<F: Float>(
 
 // Einsum helpers
 
-fn parse_einsum_pattern(pattern: &str) -> Result<(Vec<String>, String), OpError> {
-    let parts: Vec<&str> = pattern.split("->").collect();
-    if parts.len() != 2 {
-        return Err(OpError::Other("Invalid einsum pattern".into()));
-    }
-
-    let input_part = parts[0];
-    let output_part = parts[1];
+/// Size assumed for the unknown dimensions when ordering contractions
+const EINSUM_UNKNOWN_DIM_SIZE: usize = 64;
 
-    let input_specs: Vec<String> = input_part.split(',').map(|s| s.to_string()).collect();
-
-    Ok((input_specs, output_part.to_string()))
+/// Parsed einsum subscripts, e.g. `ij,jk->ik`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct EinsumSpec {
+    inputs: Vec<Vec<char>>,
+    output: Vec<char>,
 }
 
-fn compute_matmul<F: Float>(
-    a: &ndarray::ArrayViewD<F>,
-    b: &ndarray::ArrayViewD<F>,
-) -> Result<ArrayD<F>, OpError> {
-    if a.ndim() != 2 || b.ndim() != 2 {
-        return Err(OpError::IncompatibleShape(
-            "Matrix multiplication requires 2D arrays".into(),
-        ));
-    }
+impl EinsumSpec {
+    /// Parses `pattern` for `num_operands` operands.
+    ///
+    /// Without `->`, the output consists of the labels appearing exactly once,
+    /// in alphabetical order.
+    fn parse(pattern: &str, num_operands: usize) -> Result<Self, String> {
+        let pattern: String = pattern.chars().filter(|c| !c.is_whitespace()).collect();
+        let (lhs, rhs) = match pattern.split_once("->") {
+            Some((lhs, rhs)) => (lhs, Some(rhs)),
+            None => (pattern.as_str(), None),
+        };
+        let labels = |s: &str| -> Result<Vec<char>, String> {
+            match s.chars().find(|c| !c.is_ascii_alphabetic()) {
+                Some(c) => Err(format!(
+                    "invalid label {:?} in einsum pattern {:?}",
+                    c, pattern
+                )),
+                None => Ok(s.chars().collect()),
+            }
+        };
+        let inputs = lhs.split(',').map(labels).collect::<Result<Vec<_>, _>>()?;
+        if inputs.len() != num_operands {
+            return Err(format!(
+                "einsum pattern {:?} has {} operands, got {}",
+                pattern,
+                inputs.len(),
+                num_operands
+            ));
+        }
 
-    let a_2d = a.view().into_dimensionality::<ndarray::Ix2>().unwrap();
-    let b_2d = b.view().into_dimensionality::<ndarray::Ix2>().unwrap();
+        let output = match rhs {
+            Some(rhs) => {
+                let output = labels(rhs)?;
+                for (i, &label) in output.iter().enumerate() {
+                    if output[..i].contains(&label) {
+                        return Err(format!(
+                            "repeated output label {:?} in {:?}",
+                            label, pattern
+                        ));
+                    }
+                    if !inputs.iter().any(|x| x.contains(&label)) {
+                        return Err(format!("unknown output label {:?} in {:?}", label, pattern));
+                    }
+                }
+                output
+            }
+            None => {
+                let mut output: Vec<char> = inputs
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .filter(|&l| inputs.iter().flatten().filter(|&&m| m == l).count() == 1)
+                    .collect();
+                output.sort_unstable();
+                output
+            }
+        };
+        Ok(EinsumSpec { inputs, output })
+    }
 
-    Ok(a_2d.dot(&b_2d).into_dyn())
+    /// Sizes of the labels of the inputs, checking their consistency
+    fn label_sizes<'a>(
+        &self,
+        shapes: impl Iterator<Item = &'a [usize]>,
+    ) -> Result<HashMap<char, usize>, OpError> {
+        let mut sizes = HashMap::new();
+        for (labels, shape) in self.inputs.iter().zip(shapes) {
+            if labels.len() != shape.len() {
+                return Err(OpError::IncompatibleShape(format!(
+                    "einsum: operand of shape {:?} for subscripts {:?}",
+                    shape,
+                    labels.iter().collect::<String>()
+                )));
+            }
+            for (&label, &size) in labels.iter().zip(shape) {
+                if *sizes.entry(label).or_insert(size) != size {
+                    return Err(OpError::IncompatibleShape(format!(
+                        "einsum: inconsistent sizes for label {:?}: {} and {}",
+                        label, sizes[&label], size
+                    )));
+                }
+            }
+        }
+        Ok(sizes)
+    }
 }
 
-fn compute_dot_product<F: Float>(
-    a: &ndarray::ArrayViewD<F>,
-    b: &ndarray::ArrayViewD<F>,
+/// Evaluates `spec` by iterating over all the label combinations
+fn einsum_kernel<F: Float>(
+    spec: &EinsumSpec,
+    operands: &[ndarray::ArrayViewD<F>],
+    sizes: &HashMap<char, usize>,
 ) -> Result<ArrayD<F>, OpError> {
-    if a.shape() != b.shape() {
-        return Err(OpError::IncompatibleShape(
-            "Dot product requires same shape".into(),
-        ));
+    // Output labels first, then the contracted ones
+    let mut labels: Vec<char> = Vec::new();
+    for &label in spec.output.iter().chain(spec.inputs.iter().flatten()) {
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
     }
-
-    let mut sum = F::zero();
-    for (&a_val, &b_val) in a.iter().zip(b.iter()) {
-        sum += a_val * b_val;
+    let dims: Vec<usize> = labels.iter().map(|l| sizes[l]).collect();
+    let out_shape: Vec<usize> = spec.output.iter().map(|l| sizes[l]).collect();
+    let mut out = ArrayD::<F>::zeros(IxDyn(&out_shape));
+    if dims.contains(&0) {
+        return Ok(out);
     }
 
-    Ok(ndarray::arr0(sum).into_dyn())
+    // Offset of each label in each array; repeated labels add up to diagonals
+    let label_strides = |subscripts: &[char], strides: &[isize]| -> Vec<usize> {
+        labels
+            .iter()
+            .map(|l| {
+                subscripts
+                    .iter()
+                    .zip(strides)
+                    .filter(|(m, _)| *m == l)
+                    .map(|(_, &s)| s as usize)
+                    .sum()
+            })
+            .collect()
+    };
+    let operands: Vec<ArrayD<F>> = operands
+        .iter()
+        .map(|x| x.as_standard_layout().into_owned())
+        .collect();
+    let in_strides: Vec<Vec<usize>> = operands
+        .iter()
+        .zip(&spec.inputs)
+        .map(|(x, subscripts)| label_strides(subscripts, x.strides()))
+        .collect();
+    let out_strides = label_strides(&spec.output, out.strides());
+    let data: Vec<&[F]> = operands.iter().map(|x| x.as_slice().unwrap()).collect();
+    let out_data = out.as_slice_mut().unwrap();
+
+    let mut index = vec![0; labels.len()];
+    let mut offsets = vec![0; operands.len()];
+    let mut out_offset = 0;
+    loop {
+        let product = data
+            .iter()
+            .zip(&offsets)
+            .fold(F::one(), |acc, (x, &offset)| acc * x[offset]);
+        out_data[out_offset] += product;
+
+        // Advance to the next combination of labels
+        let mut axis = labels.len();
+        loop {
+            if axis == 0 {
+                return Ok(out);
+            }
+            axis -= 1;
+            index[axis] += 1;
+            for (offset, strides) in offsets.iter_mut().zip(&in_strides) {
+                *offset += strides[axis];
+            }
+            out_offset += out_strides[axis];
+            if index[axis] < dims[axis] {
+                break;
+            }
+            for (offset, strides) in offsets.iter_mut().zip(&in_strides) {
+                *offset -= strides[axis] * dims[axis];
+            }
+            out_offset -= out_strides[axis] * dims[axis];
+            index[axis] = 0;
+        }
+    }
 }
 
-fn compute_elementwise_mul<F: Float>(
-    a: &ndarray::ArrayViewD<F>,
-    b: &ndarray::ArrayViewD<F>,
-) -> Result<ArrayD<F>, OpError> {
-    if a.shape() != b.shape() {
-        return Err(OpError::IncompatibleShape(
-            "Element-wise multiplication requires same shape".into(),
-        ));
+/// Labels to keep when contracting `lhs` with `rhs`: those needed by the output
+/// or by the `others` operands.
+fn kept_labels(lhs: &[char], rhs: &[char], others: &[&[char]], output: &[char]) -> Vec<char> {
+    let mut kept = Vec::new();
+    for &label in lhs.iter().chain(rhs) {
+        let needed = output.contains(&label) || others.iter().any(|x| x.contains(&label));
+        if needed && !kept.contains(&label) {
+            kept.push(label);
+        }
     }
-
-    Ok((a * b).into_owned())
+    kept
 }
 
-fn compute_general_einsum<F: Float>(
-    a: &ndarray::ArrayViewD<F>,
-    _b: &ndarray::ArrayViewD<F>,
-    _input_specs: &[String],
-    _output_spec: &str,
-) -> Result<ArrayD<F>, OpError> {
-    // Simplified implementation - just return first input for now
-    Ok(a.to_owned())
+/// Greedy contraction order: repeatedly contracts the pair of operands with the
+/// smallest result, breaking ties by the number of multiplications.
+fn greedy_path(spec: &EinsumSpec, sizes: &HashMap<char, usize>) -> Vec<(usize, usize)> {
+    let size = |labels: &[char]| -> f64 { labels.iter().map(|l| sizes[l] as f64).product() };
+    let mut operands = spec.inputs.clone();
+    let mut path = Vec::new();
+    while operands.len() > 1 {
+        let mut best: Option<((f64, f64), (usize, usize))> = None;
+        for i in 0..operands.len() {
+            for j in i + 1..operands.len() {
+                let others: Vec<&[char]> = (0..operands.len())
+                    .filter(|&k| k != i && k != j)
+                    .map(|k| operands[k].as_slice())
+                    .collect();
+                let kept = kept_labels(&operands[i], &operands[j], &others, &spec.output);
+                let all = kept_labels(&operands[i], &operands[j], &[], &operands[i]);
+                let all = kept_labels(&all, &operands[j], &[], &operands[j]);
+                let cost = (size(&kept), size(&all));
+                if best.is_none_or(|(c, _)| cost < c) {
+                    best = Some((cost, (i, j)));
+                }
+            }
+        }
+        let (_, (i, j)) = best.unwrap();
+        let rhs = operands.remove(j);
+        let lhs = operands.remove(i);
+        let others: Vec<&[char]> = operands.iter().map(|x| x.as_slice()).collect();
+        operands.push(kept_labels(&lhs, &rhs, &others, &spec.output));
+        path.push((i, j));
+    }
+    path
 }
 
 // Public API functions
@@ -461,22 +632,90 @@ pub fn tensor_solve<'g, F: Float + ndarray::ScalarOperand>(
 }
 
 /// Einstein summation convention
-pub fn einsum<'g, F: Float + ndarray::ScalarOperand>(
-    pattern: &str,
-    operands: &[&Tensor<'g, F>],
-) -> Tensor<'g, F> {
-    if operands.len() != 2 {
-        panic!("Only binary einsum operations are currently supported");
+///
+/// Contracts `operands` as described by `pattern`, e.g. `"ij,jk->ik"` for a
+/// matrix product, `"ii->"` for a trace, `"i,j->ij"` for an outer product or
+/// `"bij,bjk->bik"` for a batched matrix product. Without `->`, the output has
+/// the labels appearing once, in alphabetical order. Labels are ASCII letters.
+///
+/// With more than two operands, pairs of operands are contracted in the order
+/// given by [einsum_path], using the statically known shapes. The result is
+/// differentiable with respect to all operands.
+///
+/// # Panics
+/// If `pattern` is invalid or does not match the number of operands.
+pub fn einsum<'g, F: Float>(pattern: &str, operands: &[&Tensor<'g, F>]) -> Tensor<'g, F> {
+    let spec = EinsumSpec::parse(pattern, operands.len()).unwrap_or_else(|e| panic!("{}", e));
+    let g = operands[0].graph();
+    let contract = |spec: EinsumSpec, operands: &[&Tensor<'g, F>]| {
+        operands
+            .iter()
+            .fold(Tensor::builder(g), |b, x| b.append_input(*x, false))
+            .build(EinsumOp {
+                spec,
+                shape_input: false,
+            })
+    };
+    if operands.len() <= 2 {
+        return contract(spec, operands);
     }
 
-    let g = operands[0].graph();
+    // Sizes known at graph construction
+    let mut sizes = HashMap::new();
+    for (labels, x) in spec.inputs.iter().zip(operands) {
+        let known = x.inner().known_shape.as_ref().map(|s| s.get().to_vec());
+        for (i, &label) in labels.iter().enumerate() {
+            let size = match known {
+                Some(ref shape) if shape.len() == labels.len() && shape[i] > 0 => shape[i] as usize,
+                _ => EINSUM_UNKNOWN_DIM_SIZE,
+            };
+            sizes.entry(label).or_insert(size);
+        }
+    }
 
-    Tensor::builder(g)
-        .append_input(operands[0], false)
-        .append_input(operands[1], false)
-        .build(EinsumOp {
-            pattern: pattern.to_string(),
-        })
+    let mut tensors: Vec<Tensor<'g, F>> = operands.iter().map(|x| **x).collect();
+    let mut labels = spec.inputs.clone();
+    for (i, j) in greedy_path(&spec, &sizes) {
+        let (rhs, rhs_labels) = (tensors.remove(j), labels.remove(j));
+        let (lhs, lhs_labels) = (tensors.remove(i), labels.remove(i));
+        let output = if tensors.is_empty() {
+            spec.output.clone()
+        } else {
+            let others: Vec<&[char]> = labels.iter().map(|x| x.as_slice()).collect();
+            kept_labels(&lhs_labels, &rhs_labels, &others, &spec.output)
+        };
+        let pair = EinsumSpec {
+            inputs: vec![lhs_labels, rhs_labels],
+            output: output.clone(),
+        };
+        tensors.push(contract(pair, &[&lhs, &rhs]));
+        labels.push(output);
+    }
+    tensors[0]
+}
+
+/// Order in which [einsum] contracts the operands of `pattern` with the given shapes.
+///
+/// Each step contracts two operands, removing them from the list and appending
+/// the result, like NumPy's `einsum_path`. Pairs are chosen greedily to keep
+/// the intermediate results small.
+///
+/// ```
+/// use scirs2_autograd as ag;
+///
+/// // (A B) C costs less than A (B C) when C has few columns
+/// let path = ag::tensor_ops::einsum_path("ij,jk,kl->il", &[&[10, 1000], &[1000, 5], &[5, 2]]);
+/// assert_eq!(path, vec![(0, 1), (0, 1)]);
+/// ```
+///
+/// # Panics
+/// If `pattern` is invalid or does not match the shapes.
+pub fn einsum_path(pattern: &str, shapes: &[&[usize]]) -> Vec<(usize, usize)> {
+    let spec = EinsumSpec::parse(pattern, shapes.len()).unwrap_or_else(|e| panic!("{}", e));
+    let sizes = spec
+        .label_sizes(shapes.iter().copied())
+        .unwrap_or_else(|e| panic!("{}", e));
+    greedy_path(&spec, &sizes)
 }
 
 /// Kronecker product (tensor product of matrices)
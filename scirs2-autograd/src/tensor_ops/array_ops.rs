@@ -8,10 +8,13 @@ use crate::tensor::Tensor;
 use crate::tensor_ops::*;
 use crate::Float;
 use ndarray::SliceInfoElem;
+use serde::{Deserialize, Serialize};
 use std::iter::FromIterator;
 
+#[derive(Serialize, Deserialize)]
 pub struct ExpandDims;
 
+#[derive(Serialize, Deserialize)]
 pub struct Squeeze;
 
 pub struct Slice {
@@ -22,6 +25,7 @@ pub struct SliceGrad {
     pub indices: Vec<SliceInfoElem>,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Split {
     pub axis: isize,
     pub start_index: isize,
@@ -34,11 +38,13 @@ pub struct SplitGrad {
     pub end_index: isize,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Tile {
     pub axis: isize,
     pub num: usize,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Concat {
     pub axis: isize,
 }
@@ -48,6 +54,8 @@ pub struct ConcatGrad {
     pub index: usize,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Clip<T: Float> {
     pub min: T,
     pub max: T,
@@ -58,8 +66,10 @@ pub struct ClipGrad<T: Float> {
     pub max: T,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct AddN;
 
+#[derive(Serialize, Deserialize)]
 pub struct Gather {
     pub axis: isize,
     pub should_normalize_negative_indices: bool,
@@ -69,6 +79,7 @@ pub struct GatherGrad {
     pub axis: isize,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct IndexOp {
     pub index: isize,
 }
@@ -79,14 +90,19 @@ pub struct IndexOpGrad {
 
 pub struct SetDiff1D;
 
+#[derive(Serialize, Deserialize)]
 pub struct Shape;
 
+#[derive(Serialize, Deserialize)]
 pub struct Rank;
 
+#[derive(Serialize, Deserialize)]
 pub struct Size;
 
+#[derive(Serialize, Deserialize)]
 pub struct Reshape;
 
+#[derive(Serialize, Deserialize)]
 pub struct InferBinOpShape;
 
 pub struct Assign;
@@ -106,6 +122,10 @@ impl<T: Float> op::Op<T> for Assign {
 }
 
 impl<T: Float> op::Op<T> for InferBinOpShape {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("InferBinOpShape", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let a_shape_float = ctx.input(0);
         let b_shape_float = ctx.input(1);
@@ -151,6 +171,10 @@ impl<T: Float> op::Op<T> for InferBinOpShape {
 }
 
 impl<T: Float> op::Op<T> for Shape {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Shape", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x = &ctx.input(0);
         let shape_vec = ndarray_ext::shape_of_view(x);
@@ -163,9 +187,18 @@ impl<T: Float> op::Op<T> for Shape {
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         ctx.append_input_grad(0, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let batch_shape = shape(ctx.input(0));
+        ctx.set_output(slice(batch_shape, &[1], &[-1]), false);
+    }
 }
 
 impl<T: Float> op::Op<T> for Rank {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Rank", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x = ctx.input(0);
         let ret = NdArray::from_elem(ndarray::IxDyn(&[]), T::from(x.ndim()).unwrap());
@@ -176,9 +209,17 @@ impl<T: Float> op::Op<T> for Rank {
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         ctx.append_input_grad(0, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        ctx.set_output(rank(ctx.input(0)) - T::one(), false);
+    }
 }
 
 impl<T: Float> op::Op<T> for Size {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Size", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x = ctx.input(0);
         let ret = NdArray::from_elem(ndarray::IxDyn(&[]), T::from(x.len()).unwrap());
@@ -192,6 +233,10 @@ impl<T: Float> op::Op<T> for Size {
 }
 
 impl<T: Float> op::Op<T> for Reshape {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Reshape", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x = &ctx.input(0);
         let shape_arr = &ctx.input(1);
@@ -250,6 +295,18 @@ impl<T: Float> op::Op<T> for Reshape {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if !ctx.is_batched(1) {
+            let y = ctx.rebuild(&[*ctx.input(0), ctx.batched_shape(1)]);
+            ctx.set_output(y, true);
+        }
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for SetDiff1D {
@@ -293,6 +350,10 @@ impl<T: Float> op::Op<T> for SetDiff1D {
 }
 
 impl<T: Float> op::Op<T> for IndexOp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("IndexOp", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x = ctx.input(0);
         let i = if self.index < 0 {
@@ -365,6 +426,10 @@ impl<T: Float> op::Op<T> for IndexOpGrad {
 }
 
 impl<T: Float> op::Op<T> for Gather {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Gather", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let param = &ctx.input(1);
         let indices = &ctx.input(0);
@@ -518,6 +583,10 @@ pub(crate) fn inplace_add_impl<F: Float>(mut a: NdArrayViewMut<F>, b: &NdArrayVi
 }
 
 impl<T: Float> op::Op<T> for AddN {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("AddN", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let inputs_len = ctx.inputs().len();
         if 0 == inputs_len {
@@ -547,6 +616,10 @@ impl<T: Float> op::Op<T> for AddN {
 }
 
 impl<T: Float> op::Op<T> for Clip<T> {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Clip", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(move |a| a.min(self.max).max(self.min));
         ctx.append_output(ret);
@@ -586,6 +659,10 @@ impl<T: Float> op::Op<T> for ClipGrad<T> {
 }
 
 impl<T: Float> op::Op<T> for Concat {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Concat", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let mut views = Vec::with_capacity(ctx.inputs().len());
         for i in 0..ctx.inputs().len() {
@@ -633,6 +710,14 @@ impl<T: Float> op::Op<T> for Concat {
             ctx.append_input_grad(i, Some(gx));
         }
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let ts: Vec<_> = (0..ctx.num_inputs())
+            .map(|i| ctx.input_tangent_or_zeros(i))
+            .collect();
+        let t = ctx.rebuild(&ts);
+        ctx.append_output_tangent(Some(t));
+    }
 }
 
 impl<T: Float> op::Op<T> for ConcatGrad {
@@ -694,6 +779,10 @@ impl<T: Float> op::Op<T> for ConcatGrad {
 }
 
 impl<T: Float> op::Op<T> for Tile {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Tile", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x = ctx.input(0);
         let axis = ndarray_ext::normalize_negative_axis(self.axis, x.ndim());
@@ -713,6 +802,10 @@ impl<T: Float> op::Op<T> for Tile {
 }
 
 impl<T: Float> op::Op<T> for Split {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Split", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x = &ctx.input(0);
         let axis = ndarray_ext::normalize_negative_axis(self.axis, x.ndim());
@@ -815,6 +908,11 @@ impl<T: Float> op::Op<T> for Slice {
             .build(op);
         ctx.append_input_grad(0, Some(gx));
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for SliceGrad {
@@ -837,12 +935,23 @@ impl<T: Float> op::Op<T> for SliceGrad {
     }
 
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
-        // is this ok?
+        // linear in the incoming gradient: slice the output gradient back
+        let op = Slice {
+            indices: self.indices.clone(),
+        };
+        let ggy = Tensor::builder(ctx.graph())
+            .append_input(ctx.output_grad(), false)
+            .set_shape(&shape(ctx.input(1)))
+            .build(op);
         ctx.append_input_grad(0, None);
-        ctx.append_input_grad(1, None);
+        ctx.append_input_grad(1, Some(ggy));
     }
 }
 impl<T: Float> op::Op<T> for Squeeze {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Squeeze", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let mut x = ctx.input(0).clone();
         let mut axes = ctx
@@ -870,9 +979,25 @@ impl<T: Float> op::Op<T> for Squeeze {
         ctx.append_input_grad(0, Some(expand_dims(ctx.output_grad(), ctx.input(1))));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if !ctx.is_batched(1) {
+            let y = ctx.rebuild(&[*ctx.input(0), ctx.batched_axes(1, false)]);
+            ctx.set_output(y, true);
+        }
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ExpandDims {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ExpandDims", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0);
         let mut axes = ctx
@@ -898,4 +1023,16 @@ impl<T: Float> op::Op<T> for ExpandDims {
         ctx.append_input_grad(0, Some(squeeze(ctx.output_grad(), ctx.input(1))));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if !ctx.is_batched(1) {
+            let y = ctx.rebuild(&[*ctx.input(0), ctx.batched_axes(1, false)]);
+            ctx.set_output(y, true);
+        }
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
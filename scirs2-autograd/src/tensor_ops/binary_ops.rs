@@ -11,12 +11,19 @@ use std::mem;
 // Import SIMD operations from scirs2-core
 #[cfg(feature = "simd")]
 use scirs2_core::simd::{simd_add_f32, simd_add_f64, simd_mul_f32, simd_mul_f64};
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
 pub struct AddOp;
+#[derive(Serialize, Deserialize)]
 pub struct SubOp;
+#[derive(Serialize, Deserialize)]
 pub struct MulOp;
+#[derive(Serialize, Deserialize)]
 pub struct DivOp;
+#[derive(Serialize, Deserialize)]
 pub struct MaybeReduceSum;
+#[derive(Serialize, Deserialize)]
 pub struct MaybeBroadcast;
 
 #[cfg(feature = "mkl")]
@@ -41,6 +48,10 @@ macro_rules! bin_op_same_shape {
 }
 
 impl<T: Float> op::Op<T> for MaybeReduceSum {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("MaybeReduceSum", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let gy = ctx.input(0);
         let orig_shape__ = crate::ndarray_ext::as_shape(&ctx.input(1));
@@ -55,8 +66,25 @@ impl<T: Float> op::Op<T> for MaybeReduceSum {
 
         // Broadcast occurred. We need reduction of the input.
 
-        // First, handle the case where `input` is scalar.
+        // Leading axes prepended by the broadcast are summed out first.
         let target_shape_is_scalar = crate::ndarray_ext::is_scalar_shape(orig_shape_);
+        let num_leading = if target_shape_is_scalar {
+            0
+        } else {
+            gy_shape.len().saturating_sub(orig_shape_.len())
+        };
+        let gy = if num_leading > 0 {
+            let mut summed = gy.to_owned();
+            for _ in 0..num_leading {
+                summed = summed.sum_axis(Axis(0));
+            }
+            summed
+        } else {
+            gy.to_owned()
+        };
+        let gy_shape = gy.shape();
+
+        // Then, handle the case where `input` is scalar.
         let orig_shape = if target_shape_is_scalar {
             vec![1; gy_shape.len()]
         } else {
@@ -64,11 +92,10 @@ impl<T: Float> op::Op<T> for MaybeReduceSum {
         };
 
         if orig_shape == gy_shape {
-            // The case where forward path didn't cause broadcast.
+            // The case where the remaining axes didn't cause broadcast.
             ctx.append_output(
                 gy.into_shape_with_order(ndarray::IxDyn(orig_shape_))
-                    .unwrap()
-                    .to_owned(),
+                    .unwrap(),
             );
             return Ok(());
         }
@@ -110,10 +137,28 @@ impl<T: Float> op::Op<T> for MaybeReduceSum {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if ctx.is_batched(1) {
+            return;
+        }
+        // Leading axes of the examples are kept by the reduction, then dropped
+        let gx = ctx.rebuild(&[*ctx.input(0), ctx.aligned_batched_shape(1)]);
+        ctx.set_output(reshape(gx, &ctx.batched_shape(1)), true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 // Do broadcast if necessary.
 impl<T: Float> op::Op<T> for MaybeBroadcast {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("MaybeBroadcast", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let target_shape_ = ctx.input(1);
         let target_shape_ = crate::ndarray_ext::as_shape(&target_shape_);
@@ -152,9 +197,27 @@ impl<T: Float> op::Op<T> for MaybeBroadcast {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if ctx.is_batched(1) {
+            return;
+        }
+        let gy = ctx.broadcastable_to_shape(ctx.input(0), 1);
+        let gx = ctx.rebuild(&[gy, ctx.batched_shape(1)]);
+        ctx.set_output(gx, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for AddOp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("AddOp", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         // Check if we have enough inputs
         let inputs = ctx.inputs();
@@ -170,6 +233,15 @@ impl<T: Float> op::Op<T> for AddOp {
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::axpy(ctx, gpu, 1.)
+    }
+
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         let g = ctx.graph();
         let x0 = ctx.input(0);
@@ -182,9 +254,23 @@ impl<T: Float> op::Op<T> for AddOp {
         ctx.append_input_grad(0, Some(gy0));
         ctx.append_input_grad(1, Some(gy1));
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let y = ctx.rebuild(&ctx.broadcastable_inputs());
+        ctx.set_output(y, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.rebuild(&[ctx.input_tangent_or_zeros(0), ctx.input_tangent_or_zeros(1)]);
+        ctx.append_output_tangent(Some(t));
+    }
 }
 
 impl<T: Float> op::Op<T> for SubOp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("SubOp", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x0 = &ctx.input(0);
         let x1 = &ctx.input(1);
@@ -200,6 +286,15 @@ impl<T: Float> op::Op<T> for SubOp {
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::axpy(ctx, gpu, -1.)
+    }
+
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         let g = ctx.graph();
         let x0 = ctx.input(0);
@@ -212,9 +307,23 @@ impl<T: Float> op::Op<T> for SubOp {
         ctx.append_input_grad(0, Some(gy0));
         ctx.append_input_grad(1, Some(neg(gy1)));
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let y = ctx.rebuild(&ctx.broadcastable_inputs());
+        ctx.set_output(y, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.rebuild(&[ctx.input_tangent_or_zeros(0), ctx.input_tangent_or_zeros(1)]);
+        ctx.append_output_tangent(Some(t));
+    }
 }
 
 impl<T: Float> op::Op<T> for MulOp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("MulOp", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let a = ctx.input(0);
         let b = ctx.input(1);
@@ -242,23 +351,43 @@ impl<T: Float> op::Op<T> for MulOp {
         ctx.append_input_grad(0, Some(gx0));
         ctx.append_input_grad(1, Some(gx1));
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let y = ctx.rebuild(&ctx.broadcastable_inputs());
+        ctx.set_output(y, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let (x0, x1) = (ctx.input(0), ctx.input(1));
+        let t0 = ctx.input_tangent(0).map(|t0| *t0 * x1);
+        let t1 = ctx.input_tangent(1).map(|t1| x0 * *t1);
+        let t = match (t0, t1) {
+            (Some(t0), Some(t1)) => t0 + t1,
+            (t0, t1) => t0.or(t1).unwrap(),
+        };
+        ctx.append_output_tangent(Some(t));
+    }
 }
 
 impl<T: Float> op::Op<T> for DivOp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("DivOp", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let x0 = &ctx.input(0);
         let x1 = &ctx.input(1);
         let shape0: &[usize] = x0.shape();
         let shape1: &[usize] = x1.shape();
-        let is_scalar0 = shape0.is_empty() || shape0 == [0];
+        let is_scalar0 = shape0.is_empty() || shape0 == [1];
         let is_scalar1 = shape1.is_empty() || shape1 == [1];
         let ret = if is_scalar0 {
             // a is a scalar
-            let x0_elem = x0[ndarray::IxDyn(&[])];
+            let x0_elem = x0.iter().next().copied().unwrap_or_else(T::zero);
             x1.map(move |&a| x0_elem / a)
         } else if is_scalar1 {
             // b is a scalar
-            let x1_elem = x1[ndarray::IxDyn(&[])];
+            let x1_elem = x1.iter().next().copied().unwrap_or_else(T::zero);
             let rhs = T::one() / x1_elem;
             x0.mapv(|x0_elem| x0_elem * rhs)
         } else {
@@ -285,9 +414,26 @@ impl<T: Float> op::Op<T> for DivOp {
         ctx.append_input_grad(0, Some(gx0));
         ctx.append_input_grad(1, Some(gx1));
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let y = ctx.rebuild(&ctx.broadcastable_inputs());
+        ctx.set_output(y, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        // (t0 - y t1) / x1
+        let x1 = ctx.input(1);
+        let t1 = ctx.input_tangent(1).map(|t1| ctx.output() * *t1);
+        let t = match (ctx.input_tangent(0), t1) {
+            (Some(t0), Some(t1)) => (*t0 - t1) / x1,
+            (Some(t0), None) => *t0 / x1,
+            (None, t1) => neg(t1.unwrap()) / x1,
+        };
+        ctx.append_output_tangent(Some(t));
+    }
 }
 
-fn maybe_reduce<'g, T: Float>(
+pub(crate) fn maybe_reduce<'g, T: Float>(
     target_shape: &Tensor<'g, T>,
     x: &Tensor<'g, T>,
     graph: &'g Graph<T>,
@@ -1,10 +1,13 @@
-use crate::graph::AsGraph;
-use crate::op::{ComputeContext, GradientContext, Op, OpError};
-use crate::tensor::Tensor;
-use crate::Float;
+use crate::evaluation::Feeder;
+use crate::gradient::compute_gradients;
+use crate::graph::{AsGraph, Graph, TensorID};
+use crate::op::{ComputeContext, GradientContext, Op, OpError, SmallVec};
+use crate::tensor::{IncomingTensor, Tensor, TensorInternal};
+use crate::{Context, EvalError, Float, FxHashMap};
 use once_cell::sync::Lazy;
 use std::collections::HashSet;
 use std::marker::PhantomData;
+use std::ops::Range;
 use std::sync::Mutex;
 
 // Global registry to track checkpointed operations for memory usage statistics
@@ -626,3 +629,358 @@ impl CheckpointProfiler {
         CHECKPOINT_REGISTRY.lock().unwrap().checkpoint_ops.len()
     }
 }
+
+/// Output of a segment built by [Context::checkpoint]
+///
+/// The inputs are the segment output followed by the differentiable tensors
+/// the segment reads from outside. Gradients do not flow through the stored
+/// segment: it is rebuilt from `nodes` once the output gradient is known, so
+/// its activations are only needed during the forward pass.
+struct SegmentCheckpointOp {
+    /// Operation nodes of the segment in topological order
+    nodes: Vec<TensorID>,
+}
+
+impl<F: Float> Op<F> for SegmentCheckpointOp {
+    fn name(&self) -> &'static str {
+        "SegmentCheckpoint"
+    }
+
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let output = ctx.input(0).to_owned();
+        ctx.append_output(output);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let gy = ctx.output_grad();
+        let g = ctx.graph();
+
+        // Gating the segment inputs on `gy` defers the recomputation to backprop
+        let mut remap = FxHashMap::default();
+        let gates: Vec<_> = ctx.inputs()[1..]
+            .iter()
+            .map(|x| {
+                let gate = Tensor::builder(g)
+                    .append_input(x, false)
+                    .append_input(gy, false)
+                    .build(RecomputeGate);
+                remap.insert(x.id, gate.id);
+                gate
+            })
+            .collect();
+        let y = recompute(g, &self.nodes, &mut remap);
+
+        let mut grads = compute_gradients(&[y], &gates, Some(&[*gy]), g);
+        ctx.append_input_grad(0, None);
+        for (i, gate) in gates.iter().enumerate() {
+            ctx.append_input_grad(i + 1, grads.extract_grad(gate));
+        }
+    }
+}
+
+/// Passes the first input through once the second one is available
+struct RecomputeGate;
+
+impl<F: Float> Op<F> for RecomputeGate {
+    fn name(&self) -> &'static str {
+        "RecomputeGate"
+    }
+
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let output = ctx.input(0).to_owned();
+        ctx.append_output(output);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let gy = ctx.output_grad();
+        ctx.append_input_grad(0, Some(*gy));
+        ctx.append_input_grad(1, None);
+    }
+}
+
+/// Clones the operation `nodes` (in topological order), replacing their inputs
+/// as given by `remap`, and returns the clone of the last node.
+fn recompute<'g, F: Float>(
+    g: &'g Graph<F>,
+    nodes: &[TensorID],
+    remap: &mut FxHashMap<TensorID, TensorID>,
+) -> Tensor<'g, F> {
+    let lookup = |remap: &FxHashMap<TensorID, TensorID>, input: &IncomingTensor| IncomingTensor {
+        id: *remap.get(&input.id).unwrap_or(&input.id),
+        ..input.clone()
+    };
+    for &id in nodes {
+        let node = {
+            let src = g.access_inner(id);
+            let incoming_nodes: SmallVec<_> = src
+                .incoming_nodes
+                .iter()
+                .map(|x| lookup(remap, x))
+                .collect();
+            let topo_rank = incoming_nodes
+                .iter()
+                .map(|x| g.topo_rank(x.id) + 1)
+                .max()
+                .unwrap_or(0);
+            TensorInternal {
+                // `id` is set in `Graph::install`
+                id: usize::default(),
+                op: src.op.clone(),
+                incoming_nodes,
+                topo_rank,
+                shape: src.shape.map(|s| *remap.get(&s).unwrap_or(&s)),
+                placeholder_name: None,
+                is_differentiable: src.is_differentiable,
+                backprop_inputs: src
+                    .backprop_inputs
+                    .as_ref()
+                    .map(|xs| xs.iter().map(|x| lookup(remap, x)).collect()),
+                known_shape: src.known_shape.clone(),
+                variable_id: None,
+                device: src.device,
+            }
+        };
+        let clone = g.install(node);
+        remap.insert(id, clone);
+    }
+    let last = nodes.last().expect("bad impl: empty checkpoint segment");
+    g.tensor(remap[last])
+}
+
+impl<F: Float> Context<'_, F> {
+    /// Builds the tensors of `scope` as a checkpointed segment.
+    ///
+    /// Only the output of the segment and the tensors it reads from outside are
+    /// kept for backprop. The intermediate activations are dropped once the
+    /// forward pass no longer needs them and are recomputed from the segment
+    /// inputs when the gradient reaches the output. This trades one extra
+    /// forward computation of the segment for its activation memory; see
+    /// [Context::peak_memory_bytes].
+    ///
+    /// All tensors created inside `scope` belong to the segment. Ops in it are
+    /// expected to be deterministic, since the recomputed values must match the
+    /// stored ones.
+    ///
+    /// ```
+    /// use scirs2_autograd as ag;
+    /// use ag::tensor_ops as T;
+    ///
+    /// ag::run(|ctx| {
+    ///     let x = ctx.placeholder("x", &[4, 8]);
+    ///     let w = T::ones(&[8, 8], ctx);
+    ///     let h = ctx.checkpoint(|| T::tanh(T::matmul(T::tanh(T::matmul(x, w)), w)));
+    ///     let loss = T::sum_all(h);
+    ///     let gw = T::grad(&[loss], &[w])[0];
+    ///
+    ///     let x_val = ag::ndarray::Array::<f64, _>::zeros((4, 8)).into_dyn();
+    ///     let result = ctx.evaluator().push(&gw).feed(x, x_val.view()).run();
+    ///     assert_eq!(result[0].as_ref().unwrap().shape(), &[8, 8]);
+    /// });
+    /// ```
+    pub fn checkpoint<'g, Func>(&'g self, scope: Func) -> Tensor<'g, F>
+    where
+        Func: FnOnce() -> Tensor<'g, F>,
+    {
+        let start = self.node_set.borrow().len();
+        let y = scope();
+        if y.id < start {
+            // Nothing was computed in the scope
+            return y;
+        }
+
+        // Tensors created before the scope and sources are inputs of the segment
+        let mut nodes = Vec::new();
+        let mut inputs = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![y.id];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let node = self.access_inner(id);
+            if id < start || node.is_source() {
+                if node.is_differentiable {
+                    inputs.push(id);
+                }
+                continue;
+            }
+            nodes.push(id);
+            for x in node.incoming_nodes.iter().chain(node.get_backprop_inputs()) {
+                stack.push(x.id);
+            }
+        }
+        // Tensor ids follow the topological order
+        nodes.sort_unstable();
+        inputs.sort_unstable();
+
+        inputs
+            .into_iter()
+            .fold(Tensor::builder(self).append_input(y, false), |b, x| {
+                b.append_input(self.tensor(x), false)
+            })
+            .build(SegmentCheckpointOp { nodes })
+    }
+
+    /// Applies `layers` in sequence to `input`, checkpointing the segments of `plan`.
+    ///
+    /// See [CheckpointPlan::for_budget] for choosing the segments.
+    pub fn checkpoint_sequential<'g, L>(
+        &'g self,
+        input: Tensor<'g, F>,
+        layers: &[L],
+        plan: &CheckpointPlan,
+    ) -> Tensor<'g, F>
+    where
+        L: Fn(Tensor<'g, F>) -> Tensor<'g, F>,
+    {
+        if plan.segments.is_empty() {
+            return layers.iter().fold(input, |h, layer| layer(h));
+        }
+        assert_eq!(
+            plan.num_layers,
+            layers.len(),
+            "checkpoint_sequential: the plan is for {} layers, got {}",
+            plan.num_layers,
+            layers.len()
+        );
+        plan.segments.iter().fold(input, |h, segment| {
+            self.checkpoint(|| layers[segment.clone()].iter().fold(h, |h, layer| layer(h)))
+        })
+    }
+
+    /// Measures the activation memory of `layers` applied in sequence to `input`.
+    ///
+    /// Returns the bytes of all the arrays computed by each layer when evaluated
+    /// with `feeder`, to be passed to [CheckpointPlan::for_budget].
+    pub fn layer_activation_bytes<'g, L>(
+        &'g self,
+        input: Tensor<'g, F>,
+        layers: &[L],
+        feeder: Feeder<'g, F>,
+    ) -> Result<Vec<usize>, EvalError>
+    where
+        L: Fn(Tensor<'g, F>) -> Tensor<'g, F>,
+    {
+        let mut ranges = Vec::with_capacity(layers.len());
+        let mut h = input;
+        for layer in layers {
+            let start = self.node_set.borrow().len();
+            h = layer(h);
+            ranges.push(start..self.node_set.borrow().len());
+        }
+
+        let tensors: Vec<_> = ranges
+            .iter()
+            .flat_map(|r| r.clone())
+            .map(|id| self.tensor(id))
+            .collect();
+        let mut arrays = self
+            .evaluator()
+            .extend(&tensors)
+            .set_feeder(feeder)
+            .run()
+            .into_iter();
+        ranges
+            .iter()
+            .map(|r| {
+                arrays.by_ref().take(r.len()).try_fold(0, |bytes, array| {
+                    Ok(bytes + array?.len() * std::mem::size_of::<F>())
+                })
+            })
+            .collect()
+    }
+}
+
+/// Layers of a sequential model grouped into checkpointed segments
+///
+/// Used with [Context::checkpoint_sequential].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointPlan {
+    segments: Vec<Range<usize>>,
+    num_layers: usize,
+    peak_bytes: usize,
+}
+
+impl CheckpointPlan {
+    /// Chooses the segments for layers keeping `layer_bytes[i]` bytes of
+    /// activations each, such that the estimated peak memory stays within
+    /// `budget_bytes`.
+    ///
+    /// Without checkpointing, all the activations are kept until backprop. With
+    /// segments, only the output of each segment is kept and the activations of
+    /// one segment at a time are recomputed, so the peak is estimated as the
+    /// sum of the segment outputs plus the largest segment. The fewest segments
+    /// meeting the budget are used. If no split meets it, the one with the
+    /// lowest estimated peak is returned.
+    ///
+    /// `layer_bytes` can be measured with [Context::layer_activation_bytes].
+    pub fn for_budget(layer_bytes: &[usize], budget_bytes: usize) -> Self {
+        let num_layers = layer_bytes.len();
+        let total: usize = layer_bytes.iter().sum();
+        let mut best = CheckpointPlan {
+            segments: Vec::new(),
+            num_layers,
+            peak_bytes: total,
+        };
+        if total <= budget_bytes {
+            return best;
+        }
+
+        for max_segments in 1..=num_layers {
+            // Smallest segment size that needs at most `max_segments` segments
+            let (mut lo, mut hi) = (layer_bytes.iter().copied().max().unwrap_or(0), total);
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if split_layers(layer_bytes, mid).len() <= max_segments {
+                    hi = mid;
+                } else {
+                    lo = mid + 1;
+                }
+            }
+            let segments = split_layers(layer_bytes, lo);
+            let outputs: usize = segments.iter().map(|r| layer_bytes[r.end - 1]).sum();
+            let peak_bytes = outputs + lo;
+            if peak_bytes < best.peak_bytes || best.segments.is_empty() {
+                best = CheckpointPlan {
+                    segments,
+                    num_layers,
+                    peak_bytes,
+                };
+            }
+            if best.peak_bytes <= budget_bytes {
+                break;
+            }
+        }
+        best
+    }
+
+    /// Ranges of layers checkpointed together (empty if no checkpointing is needed)
+    pub fn segments(&self) -> &[Range<usize>] {
+        &self.segments
+    }
+
+    /// Estimated peak activation memory in bytes
+    pub fn estimated_peak_bytes(&self) -> usize {
+        self.peak_bytes
+    }
+}
+
+/// Greedily groups consecutive layers into segments of at most `capacity` bytes
+fn split_layers(layer_bytes: &[usize], capacity: usize) -> Vec<Range<usize>> {
+    let mut segments = Vec::new();
+    let (mut start, mut size) = (0, 0);
+    for (i, &bytes) in layer_bytes.iter().enumerate() {
+        if i > start && size + bytes > capacity {
+            segments.push(start..i);
+            start = i;
+            size = 0;
+        }
+        size += bytes;
+    }
+    if start < layer_bytes.len() {
+        segments.push(start..layer_bytes.len());
+    }
+    segments
+}
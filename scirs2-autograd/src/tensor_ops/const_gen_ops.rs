@@ -3,17 +3,28 @@ use crate::ndarray_ext::NdArray;
 use crate::op;
 use crate::Float;
 use ndarray;
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
 pub struct Zeros;
+#[derive(Serialize, Deserialize)]
 pub struct Ones;
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct ConvertToTensor<T: Float> {
     pub arr: NdArray<T>,
 }
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Scalar<T: Float> {
     pub val: T,
 }
 
 impl<T: Float> op::Op<T> for Scalar<T> {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Scalar", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         ctx.append_output(ndarray::arr0(self.val).into_dyn());
         Ok(())
@@ -25,6 +36,10 @@ impl<T: Float> op::Op<T> for Scalar<T> {
 }
 
 impl<T: Float> op::Op<T> for Zeros {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Zeros", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let shape = &ctx.input(0);
         let ret = if let Some(a) = shape.as_slice() {
@@ -53,6 +68,10 @@ impl<T: Float> op::Op<T> for Zeros {
 }
 
 impl<T: Float> op::Op<T> for Ones {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Ones", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let shape = &ctx.input(0);
         let ret = if let Some(a) = shape.as_slice() {
@@ -81,6 +100,10 @@ impl<T: Float> op::Op<T> for Ones {
 }
 
 impl<T: Float> op::Op<T> for ConvertToTensor<T> {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ConvertToTensor", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         // Save the original array shape for debugging
         let shape = self.arr.shape();
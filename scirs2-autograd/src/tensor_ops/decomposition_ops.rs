@@ -1,6 +1,7 @@
 use crate::op::{ComputeContext, GradientContext, Op, OpError};
 use crate::tensor::Tensor;
 use crate::tensor_ops::convert_to_tensor;
+use crate::tensor_ops::special_matrices::triangle_mask;
 use crate::Float;
 use ndarray::{Array1, Array2, Ix2};
 
@@ -224,305 +225,247 @@ impl<F: Float> Op<F> for QRExtractOp {
     }
 }
 
-/// SVD Operation
-pub struct SVDOp;
-
-impl<F: Float + ndarray::ScalarOperand> Op<F> for SVDOp {
-    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
-        let input = ctx.input(0);
-        let shape = input.shape();
-
-        if shape.len() != 2 {
-            return Err(OpError::IncompatibleShape(format!(
-                "SVD requires 2D matrix, got shape {:?}",
-                shape
-            )));
-        }
-
-        let m = shape[0];
-        let n = shape[1];
-        let k = m.min(n);
-
-        println!(
-            "SVD: Computing decomposition for matrix of shape [{}, {}], k={}",
-            m, n, k
-        );
-
-        // Convert input to 2D matrix
-        let input_2d = input.view().into_dimensionality::<Ix2>().map_err(|e| {
-            OpError::IncompatibleShape(format!("Failed to convert input to 2D: {:?}", e))
-        })?;
-
-        // Initialize output matrices with correct shapes
-        let mut u = Array2::<F>::zeros((m, k));
-        let mut s = Array1::<F>::zeros(k);
-        let mut v = Array2::<F>::zeros((n, k));
-
-        // Simplified approach using fixed implementation for 2×2 matrices
-        // and identity approximations for larger matrices
-        if m == 2 && n == 2 {
-            // For 2×2 matrices, we can use the hardcoded values for testing
-            // This matches the expected test values
-            u[[0, 0]] = F::from(0.6).unwrap();
-            u[[0, 1]] = F::from(0.8).unwrap();
-            u[[1, 0]] = F::from(0.8).unwrap();
-            u[[1, 1]] = F::from(-0.6).unwrap();
-
-            s[0] = F::from(5.0).unwrap();
-            s[1] = F::from(3.0).unwrap();
-
-            v[[0, 0]] = F::from(0.8).unwrap();
-            v[[0, 1]] = F::from(-0.6).unwrap();
-            v[[1, 0]] = F::from(0.6).unwrap();
-            v[[1, 1]] = F::from(0.8).unwrap();
-        } else {
-            // For larger matrices, we'll use a simplified approach
-            // that preserves shape compatibility with the expected output
-
-            // Create an identity matrix for U and V as a placeholder
-            for i in 0..k {
-                u[[i, i]] = F::one();
-                v[[i, i]] = F::one();
-            }
-
-            // Extract diagonal elements as singular values
-            for i in 0..k {
-                if i < m && i < n {
-                    s[i] = input_2d[[i, i]].abs();
-                }
-            }
-        }
-
-        println!(
-            "SVD results: U shape={:?}, S shape={:?}, V shape={:?}",
-            u.shape(),
-            s.shape(),
-            v.shape()
-        );
-
-        // Append the outputs
-        ctx.append_output(u.into_dyn());
-        ctx.append_output(s.into_dyn());
-        ctx.append_output(v.into_dyn());
-
-        Ok(())
-    }
-
-    fn grad(&self, ctx: &mut GradientContext<F>) {
-        let input = ctx.input(0);
-        let g = ctx.graph();
-        let _gradient = ctx.output_grad();
-
-        // Get the input shape
-        let input_array = match input.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let shape = input_array.shape();
-        if shape.len() != 2 {
-            ctx.append_input_grad(0, None);
-            return;
-        }
-
-        let m = shape[0];
-        let n = shape[1];
-        let _k = m.min(n);
-
-        // Simplified gradient computation for SVD
-        // For now, we return identity gradient for 2×2 matrices
-        // and zeros for larger matrices
-        if m == 2 && n == 2 {
-            // For 2×2 matrices, we'll use hardcoded gradient values for testing
-            let mut gradient_matrix = Array2::<F>::zeros((2, 2));
-            gradient_matrix[[0, 0]] = F::from(0.4).unwrap();
-            gradient_matrix[[0, 1]] = F::from(0.6).unwrap();
-            gradient_matrix[[1, 0]] = F::from(0.6).unwrap();
-            gradient_matrix[[1, 1]] = F::from(-0.4).unwrap();
-
-            let grad_tensor = convert_to_tensor(gradient_matrix.into_dyn(), g);
-            ctx.append_input_grad(0, Some(grad_tensor));
-        } else {
-            // For larger matrices, use zeros as a placeholder
-            let gradient_matrix = Array2::<F>::zeros((m, n));
-            let grad_tensor = convert_to_tensor(gradient_matrix.into_dyn(), g);
-            ctx.append_input_grad(0, Some(grad_tensor));
-        }
-    }
-}
-
 /// SVD component extraction
+///
+/// Computes the thin SVD `A = U diag(S) V^T` of an `m x n` matrix and outputs
+/// `U` (`m x k`), `S` (`k`) or `V` (`n x k`) with `k = min(m, n)`.
 pub struct SVDExtractOp {
     component: usize,
 }
 
-impl<F: Float + ndarray::ScalarOperand> Op<F> for SVDExtractOp {
-    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
-        let input = ctx.input(0);
-        let shape = input.shape();
-
-        if shape.len() != 2 {
-            return Err(OpError::IncompatibleShape("SVD requires 2D matrix".into()));
+impl<F: Float> Op<F> for SVDExtractOp {
+    fn name(&self) -> &'static str {
+        match self.component {
+            0 => "SVDExtractU",
+            1 => "SVDExtractS",
+            _ => "SVDExtractV",
         }
+    }
 
-        let m = shape[0];
-        let n = shape[1];
-        let k = m.min(n);
-
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let input = ctx.input(0);
         let input_2d = input
             .view()
             .into_dimensionality::<Ix2>()
-            .map_err(|_| OpError::IncompatibleShape("Failed to convert to 2D array".into()))?;
+            .map_err(|_| OpError::IncompatibleShape("SVD requires 2D matrix".into()))?;
 
-        // Re-run the SVD computation (simplified version)
-        let mut u = Array2::<F>::zeros((m, k));
-        let mut s = Array1::<F>::zeros(k);
-        let mut v = Array2::<F>::zeros((n, k));
-
-        if m == 2 && n == 2 {
-            // Hardcoded values for 2×2 test case
-            u[[0, 0]] = F::from(0.6).unwrap();
-            u[[0, 1]] = F::from(0.8).unwrap();
-            u[[1, 0]] = F::from(0.8).unwrap();
-            u[[1, 1]] = F::from(-0.6).unwrap();
-
-            s[0] = F::from(5.0).unwrap();
-            s[1] = F::from(3.0).unwrap();
-
-            v[[0, 0]] = F::from(0.8).unwrap();
-            v[[0, 1]] = F::from(-0.6).unwrap();
-            v[[1, 0]] = F::from(0.6).unwrap();
-            v[[1, 1]] = F::from(0.8).unwrap();
-        } else {
-            // Create an identity matrix for U and V as a placeholder
-            for i in 0..k {
-                u[[i, i]] = F::one();
-                v[[i, i]] = F::one();
-            }
-
-            // Extract diagonal elements as singular values
-            for i in 0..k {
-                if i < m && i < n {
-                    s[i] = input_2d[[i, i]].abs();
-                }
-            }
-        }
-
-        // Extract the requested component
+        let (u, s, v) = compute_svd(&input_2d);
         match self.component {
             0 => ctx.append_output(u.into_dyn()),
             1 => ctx.append_output(s.into_dyn()),
             2 => ctx.append_output(v.into_dyn()),
             _ => return Err(OpError::IncompatibleShape("Invalid component index".into())),
         }
-
         Ok(())
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        let gy = ctx.output_grad();
-        let g = ctx.graph();
-
-        // Pass through a simple gradient (this is an approximation)
-        let grad_tensor = match gy.eval(g) {
-            Ok(arr) => convert_to_tensor(arr, g),
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        ctx.append_input_grad(0, Some(grad_tensor));
+        let gx = Tensor::builder(ctx.graph())
+            .append_input(ctx.input(0), false)
+            .append_input(ctx.output_grad(), false)
+            .build(SVDGradOp {
+                component: self.component,
+            });
+        ctx.append_input_grad(0, Some(gx));
     }
 }
 
-/// The power iteration method for finding eigenvectors of a matrix.
-/// This is used in the SVD implementation for matrices larger than 2x2.
-#[allow(dead_code)]
-fn power_iteration<F: Float + ndarray::ScalarOperand>(
-    matrix: &Array2<F>,
-    max_iter: usize,
-    tol: F,
-) -> (Array1<F>, F) {
-    let n = matrix.shape()[0];
-
-    // Initialize with random unit vector
-    let mut v = Array1::<F>::zeros(n);
-    v[0] = F::one(); // Start with [1, 0, 0, ...]
-
-    // Add small perturbation to avoid getting stuck
-    for i in 1..n {
-        v[i] = F::from(0.01).unwrap() * F::from(i as f64 / n as f64).unwrap();
-    }
+/// Gradient of an SVD component with respect to the decomposed matrix
+///
+/// Inputs are the matrix `A` and the gradient of the component. With
+/// `F_ij = 1 / (s_j^2 - s_i^2)` for `i != j` (and 0 on the diagonal):
+///
+/// ```text
+/// gA = U [ (F o (U^T gU - gU^T U)) S + diag(gS) + S (F o (V^T gV - gV^T V)) ] V^T
+///      + (I - U U^T) gU S^-1 V^T + U S^-1 gV^T (I - V V^T)
+/// ```
+///
+/// The gradients of `U` and `V` are undefined for repeated or zero singular
+/// values, in which case the result is not finite.
+struct SVDGradOp {
+    component: usize,
+}
 
-    // Normalize initial vector
-    let norm = v.iter().fold(F::zero(), |acc, &x| acc + x * x).sqrt();
-    if norm > F::epsilon() {
-        v = &v / norm;
+impl<F: Float> Op<F> for SVDGradOp {
+    fn name(&self) -> &'static str {
+        "SVDGrad"
     }
 
-    let mut lambda_prev = F::zero();
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let a = ctx.input(0);
+        let a = a
+            .view()
+            .into_dimensionality::<Ix2>()
+            .map_err(|_| OpError::IncompatibleShape("SVD requires 2D matrix".into()))?;
+        let (u, s, v) = compute_svd(&a);
+        let (m, n) = a.dim();
+        let k = s.len();
+
+        let gy = ctx.input(1);
+        let gy_shape: &[usize] = match self.component {
+            0 => &[m, k],
+            1 => &[k],
+            _ => &[n, k],
+        };
+        let gy = gy.broadcast(gy_shape).ok_or_else(|| {
+            OpError::IncompatibleShape(format!(
+                "SVDGrad: gradient of shape {:?} does not match {:?}",
+                gy.shape(),
+                gy_shape
+            ))
+        })?;
 
-    for _ in 0..max_iter {
-        // Multiply matrix by current vector: w = A*v
-        let w = matrix.dot(&v);
+        let ga = match self.component {
+            // U diag(gS) V^T
+            1 => {
+                let gs = gy.into_dimensionality::<ndarray::Ix1>().unwrap();
+                (&u * &gs).dot(&v.t())
+            }
+            // Terms of gU and gV differ only by a transposition of the roles of U and V
+            0 => {
+                let gu = gy.into_dimensionality::<Ix2>().unwrap();
+                svd_vector_grad(&u, &s, &v, &gu)
+            }
+            _ => {
+                let gv = gy.into_dimensionality::<Ix2>().unwrap();
+                svd_vector_grad(&v, &s, &u, &gv).reversed_axes()
+            }
+        };
+        ctx.append_output(ga.into_dyn());
+        Ok(())
+    }
 
-        // Find largest component to estimate eigenvalue
-        let lambda = w.iter().fold(F::zero(), |acc, &x| acc.max(x.abs()));
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        ctx.append_input_grad(0, None);
+        ctx.append_input_grad(1, None);
+    }
+}
 
-        // Check convergence
-        if (lambda - lambda_prev).abs() < tol {
-            // Return eigenvector and eigenvalue
-            return (w.clone(), lambda);
+/// Contribution of the gradient `gu` of the left singular vectors `u` to the
+/// gradient of `u diag(s) v^T`.
+fn svd_vector_grad<F: Float>(
+    u: &Array2<F>,
+    s: &Array1<F>,
+    v: &Array2<F>,
+    gu: &ndarray::ArrayView2<F>,
+) -> Array2<F> {
+    let k = s.len();
+    let utgu = u.t().dot(gu);
+    let mut inner = Array2::<F>::zeros((k, k));
+    for i in 0..k {
+        for j in 0..k {
+            if i != j {
+                let f = F::one() / (s[j] * s[j] - s[i] * s[i]);
+                inner[[i, j]] = f * (utgu[[i, j]] - utgu[[j, i]]) * s[j];
+            }
         }
+    }
+    // (I - U U^T) gU S^-1
+    let mut proj = gu.to_owned() - u.dot(&utgu);
+    for (mut col, &sj) in proj.columns_mut().into_iter().zip(s.iter()) {
+        col.mapv_inplace(|x| x / sj);
+    }
+    (u.dot(&inner) + proj).dot(&v.t())
+}
 
-        lambda_prev = lambda;
+/// Computes the thin SVD `A = U diag(S) V^T` by one-sided Jacobi rotations.
+///
+/// Singular values are sorted in descending order. The columns of `U` and `V`
+/// are orthonormal even for rank deficient matrices.
+pub(crate) fn compute_svd<F: Float>(
+    matrix: &ndarray::ArrayView2<F>,
+) -> (Array2<F>, Array1<F>, Array2<F>) {
+    let (m, n) = matrix.dim();
+    if m < n {
+        // A^T = V S U^T
+        let (v, s, u) = compute_svd(&matrix.t());
+        return (u, s, v);
+    }
 
-        // Normalize w to get new v
-        let norm = w.iter().fold(F::zero(), |acc, &x| acc + x * x).sqrt();
-        if norm > F::epsilon() {
-            v = &w / norm;
-        } else {
-            // If norm is too small, we're converging to the zero vector
-            // This could happen with a nilpotent matrix, so we restart with a different vector
-            for i in 0..n {
-                v[i] = F::from((i + 1) as f64 / n as f64).unwrap();
-            }
-            let norm = v.iter().fold(F::zero(), |acc, &x| acc + x * x).sqrt();
-            if norm > F::epsilon() {
-                v = &v / norm;
+    // Orthogonalize the columns of W = A V
+    let mut w = matrix.to_owned();
+    let mut v = Array2::<F>::eye(n);
+    let eps = F::epsilon();
+    for _sweep in 0..60 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (wp, wq) = (w.column(p), w.column(q));
+                let alpha = wp.dot(&wp);
+                let beta = wq.dot(&wq);
+                let gamma = wp.dot(&wq);
+                if gamma.abs() <= eps * (alpha * beta).sqrt() || gamma == F::zero() {
+                    continue;
+                }
+                rotated = true;
+                let two = F::one() + F::one();
+                let zeta = (beta - alpha) / (two * gamma);
+                let t = zeta.signum() / (zeta.abs() + (F::one() + zeta * zeta).sqrt());
+                let c = F::one() / (F::one() + t * t).sqrt();
+                let sn = c * t;
+                rotate_columns(&mut w, p, q, c, sn);
+                rotate_columns(&mut v, p, q, c, sn);
             }
         }
+        if !rotated {
+            break;
+        }
     }
 
-    // Return best guess if max iterations reached
-    let w = matrix.dot(&v);
-    let lambda = w.iter().fold(F::zero(), |acc, &x| acc.max(x.abs()));
-    (w, lambda)
+    let mut order: Vec<usize> = (0..n).collect();
+    let norms: Vec<F> = w.columns().into_iter().map(|c| c.dot(&c).sqrt()).collect();
+    order.sort_by(|&i, &j| {
+        norms[j]
+            .partial_cmp(&norms[i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut u = Array2::<F>::zeros((m, n));
+    let mut s = Array1::<F>::zeros(n);
+    let mut v_sorted = Array2::<F>::zeros((n, n));
+    let tiny = eps * norms.iter().fold(F::zero(), |a, &b| a.max(b)) * F::from(m).unwrap();
+    for (dst, &src) in order.iter().enumerate() {
+        s[dst] = norms[src];
+        v_sorted.column_mut(dst).assign(&v.column(src));
+        if norms[src] > tiny {
+            u.column_mut(dst)
+                .assign(&w.column(src).mapv(|x| x / norms[src]));
+        } else {
+            s[dst] = F::zero();
+            complete_orthonormal_column(&mut u, dst);
+        }
+    }
+    (u, s, v_sorted)
 }
 
-/// Improved matrix deflation for SVD algorithm
-/// This removes the contribution of a found singular value and vectors
-/// from the matrix to find additional singular values.
-#[allow(dead_code)]
-fn improved_deflation<F: Float + ndarray::ScalarOperand>(
-    matrix: &Array2<F>,
-    u_vec: &Array1<F>,
-    s_val: F,
-    v_vec: &Array1<F>,
-) -> Array2<F> {
-    // Create 2D versions for outer product
-    let u_2d = u_vec.clone().insert_axis(ndarray::Axis(1));
-    let v_2d = v_vec.clone().insert_axis(ndarray::Axis(1));
-
-    // Compute outer product u*v^T scaled by singular value
-    let update = &u_2d * &v_2d.t() * s_val;
+/// Applies the rotation `[c s; -s c]` to the columns `p` and `q`
+fn rotate_columns<F: Float>(a: &mut Array2<F>, p: usize, q: usize, c: F, s: F) {
+    for mut row in a.rows_mut() {
+        let (x, y) = (row[p], row[q]);
+        row[p] = c * x - s * y;
+        row[q] = s * x + c * y;
+    }
+}
 
-    // Return deflated matrix
-    matrix.clone() - update
+/// Sets column `j` of `u` to a unit vector orthogonal to the preceding columns
+fn complete_orthonormal_column<F: Float>(u: &mut Array2<F>, j: usize) {
+    let m = u.nrows();
+    for e in 0..m {
+        let mut x = Array1::<F>::zeros(m);
+        x[e] = F::one();
+        // Two passes of Gram-Schmidt for numerical orthogonality
+        for _ in 0..2 {
+            for i in 0..j {
+                let proj = u.column(i).dot(&x);
+                x.scaled_add(-proj, &u.column(i));
+            }
+        }
+        let norm = x.dot(&x).sqrt();
+        if norm > F::from(0.5).unwrap() {
+            u.column_mut(j).assign(&x.mapv(|a| a / norm));
+            return;
+        }
+    }
 }
 
 /// QR decomposition of a matrix.
@@ -553,210 +496,27 @@ pub fn qr<'g, F: Float>(matrix: &Tensor<'g, F>) -> (Tensor<'g, F>, Tensor<'g, F>
 
 /// Singular Value Decomposition (SVD)
 ///
-/// Decomposes a matrix A into U * S * V^T where:
-/// - U is an orthogonal matrix
-/// - S is a diagonal matrix of singular values
-/// - V is an orthogonal matrix
+/// Decomposes an `m x n` matrix A into U * diag(S) * V^T where:
+/// - U is an `m x k` matrix with orthonormal columns
+/// - S is the vector of the `k` singular values in descending order
+/// - V is an `n x k` matrix with orthonormal columns
+///
+/// with `k = min(m, n)`. All three components are differentiable; the
+/// gradients of U and V require distinct, nonzero singular values.
 ///
 /// # Arguments
 /// * `matrix` - The input tensor to decompose
 ///
 /// # Returns
 /// A tuple of tensors (U, S, V) representing the decomposition
-pub fn svd<'g, F: Float + ndarray::ScalarOperand>(
-    matrix: &Tensor<'g, F>,
-) -> (Tensor<'g, F>, Tensor<'g, F>, Tensor<'g, F>) {
-    let g = matrix.graph();
-
-    // Extract the components directly using the extraction operator
-    let u = Tensor::builder(g)
-        .append_input(matrix, false)
-        .build(SVDExtractOp { component: 0 });
-
-    let s = Tensor::builder(g)
-        .append_input(matrix, false)
-        .build(SVDExtractOp { component: 1 });
-
-    let v = Tensor::builder(g)
-        .append_input(matrix, false)
-        .build(SVDExtractOp { component: 2 });
-
-    println!("SVD function: Extracted U, S, V components using specialized operators");
-
-    (u, s, v)
-}
-
-/// Cholesky Decomposition Operation
-pub struct CholeskyOp;
-
-impl<F: Float> Op<F> for CholeskyOp {
-    fn name(&self) -> &'static str {
-        "Cholesky"
-    }
-
-    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
-        let input = ctx.input(0);
-        let shape = input.shape();
-
-        if shape.len() != 2 || shape[0] != shape[1] {
-            return Err(OpError::IncompatibleShape(
-                "Cholesky decomposition requires square matrix".into(),
-            ));
-        }
-
-        let n = shape[0];
-
-        println!(
-            "Computing Cholesky decomposition for matrix of shape: [{}, {}]",
-            n, n
-        );
-
-        let input_2d = input
-            .view()
-            .into_dimensionality::<Ix2>()
-            .map_err(|_| OpError::IncompatibleShape("Failed to convert to 2D array".into()))?;
-
-        // Check if matrix is positive definite (simplified check)
-        for i in 0..n {
-            if input_2d[[i, i]] <= F::zero() {
-                return Err(OpError::Other("Matrix is not positive definite".into()));
-            }
-        }
-
-        // Perform Cholesky decomposition: A = L * L^T
-        let mut l = Array2::<F>::zeros((n, n));
-
-        for i in 0..n {
-            for j in 0..=i {
-                if i == j {
-                    // Diagonal elements
-                    let mut sum = F::zero();
-                    for k in 0..j {
-                        sum += l[[j, k]] * l[[j, k]];
-                    }
-                    let diag_val = input_2d[[j, j]] - sum;
-                    if diag_val <= F::zero() {
-                        return Err(OpError::Other("Matrix is not positive definite".into()));
-                    }
-                    l[[j, j]] = diag_val.sqrt();
-                } else {
-                    // Off-diagonal elements
-                    let mut sum = F::zero();
-                    for k in 0..j {
-                        sum += l[[i, k]] * l[[j, k]];
-                    }
-                    l[[i, j]] = (input_2d[[i, j]] - sum) / l[[j, j]];
-                }
-            }
-        }
-
-        println!("Cholesky decomposition results:");
-        println!("L shape: {:?}", l.shape());
-
-        ctx.append_output(l.into_dyn());
-
-        Ok(())
-    }
-
-    fn grad(&self, ctx: &mut GradientContext<F>) {
-        let gy = ctx.output_grad();
-        let input = ctx.input(0);
-        let g = ctx.graph();
-
-        // Get the input matrix
-        let input_array = match input.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let grad_output_array = match gy.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        if let Ok(input_2d) = input_array.view().into_dimensionality::<Ix2>() {
-            if let Ok(grad_2d) = grad_output_array.view().into_dimensionality::<Ix2>() {
-                let grad_matrix = compute_cholesky_gradient(&input_2d, &grad_2d);
-                let grad_tensor = convert_to_tensor(grad_matrix.into_dyn(), g);
-                ctx.append_input_grad(0, Some(grad_tensor));
-                return;
-            }
-        }
-
-        // Fallback to None
-        ctx.append_input_grad(0, None);
-    }
-}
-
-/// Compute gradient for Cholesky decomposition
-fn compute_cholesky_gradient<F: Float>(
-    input: &ndarray::ArrayView2<F>,
-    grad_output: &ndarray::ArrayView2<F>,
-) -> Array2<F> {
-    let n = input.shape()[0];
-    let mut grad_input = Array2::<F>::zeros((n, n));
-
-    // Compute L from input (re-run Cholesky)
-    let mut l = Array2::<F>::zeros((n, n));
-
-    for i in 0..n {
-        for j in 0..=i {
-            if i == j {
-                let mut sum = F::zero();
-                for k in 0..j {
-                    sum += l[[j, k]] * l[[j, k]];
-                }
-                let diag_val = input[[j, j]] - sum;
-                if diag_val > F::zero() {
-                    l[[j, j]] = diag_val.sqrt();
-                }
-            } else {
-                let mut sum = F::zero();
-                for k in 0..j {
-                    sum += l[[i, k]] * l[[j, k]];
-                }
-                if l[[j, j]] != F::zero() {
-                    l[[i, j]] = (input[[i, j]] - sum) / l[[j, j]];
-                }
-            }
-        }
-    }
-
-    // Simplified gradient computation
-    // For A = L * L^T, if dL is the gradient w.r.t L, then dA = dL * L^T + L * dL^T
-    // This is a simplified version assuming grad_output represents dL
-    for i in 0..n {
-        for j in 0..n {
-            // Symmetrize the gradient since Cholesky input should be symmetric
-            grad_input[[i, j]] = grad_output[[i.min(j), j.min(i)]];
-        }
-    }
-
-    grad_input
-}
-
-/// Cholesky decomposition of a positive definite matrix.
-///
-/// Decomposes a symmetric positive definite matrix A into L * L^T where:
-/// - L is a lower triangular matrix
-///
-/// # Arguments
-/// * `matrix` - The input symmetric positive definite tensor to decompose
-///
-/// # Returns
-/// A tensor L representing the lower triangular decomposition
-#[allow(dead_code)]
-pub fn cholesky<'g, F: Float>(matrix: &Tensor<'g, F>) -> Tensor<'g, F> {
+pub fn svd<'g, F: Float>(matrix: &Tensor<'g, F>) -> (Tensor<'g, F>, Tensor<'g, F>, Tensor<'g, F>) {
     let g = matrix.graph();
-    Tensor::builder(g)
-        .append_input(matrix, false)
-        .build(CholeskyOp)
+    let component = |component| {
+        Tensor::builder(g)
+            .append_input(matrix, false)
+            .build(SVDExtractOp { component })
+    };
+    (component(0), component(1), component(2))
 }
 
 /// Eigendecomposition Operation for Symmetric Matrices
@@ -1254,158 +1014,107 @@ pub fn matrix_power<'g, F: Float + ndarray::ScalarOperand>(
         .build(MatrixPowerOp { power })
 }
 
-/// LU Decomposition Operation
-pub struct LUOp;
+/// P, L and U of an LU decomposition
+type LUFactors<F> = (Array2<F>, Array2<F>, Array2<F>);
 
-impl<F: Float + ndarray::ScalarOperand> Op<F> for LUOp {
-    fn name(&self) -> &'static str {
-        "LU"
-    }
+/// LU decomposition with partial pivoting, returning (P, L, U) with PA = LU
+fn compute_lu<F: Float>(input: &ndarray::ArrayViewD<F>) -> Result<LUFactors<F>, OpError> {
+    let shape = input.shape();
 
-    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
-        let input = ctx.input(0);
-        let shape = input.shape();
+    if shape.len() != 2 {
+        return Err(OpError::IncompatibleShape(format!(
+            "LU decomposition requires 2D matrix, got shape {:?}",
+            shape
+        )));
+    }
 
-        if shape.len() != 2 {
-            return Err(OpError::IncompatibleShape(format!(
-                "LU decomposition requires 2D matrix, got shape {:?}",
-                shape
-            )));
-        }
+    let n = shape[0];
+    let m = shape[1];
 
-        let n = shape[0];
-        let m = shape[1];
+    if n != m {
+        return Err(OpError::IncompatibleShape(
+            "LU decomposition requires square matrix".into(),
+        ));
+    }
 
-        if n != m {
-            return Err(OpError::IncompatibleShape(
-                "LU decomposition requires square matrix".into(),
-            ));
+    let input_2d = input
+        .view()
+        .into_dimensionality::<Ix2>()
+        .map_err(|_| OpError::IncompatibleShape("Failed to convert to 2D array".into()))?;
+
+    let mut l = Array2::<F>::eye(n);
+    let mut u = input_2d.to_owned();
+    let mut p = Array2::<F>::eye(n); // Permutation matrix
+
+    // Gaussian elimination with partial pivoting
+    for k in 0..n.saturating_sub(1) {
+        // Find pivot
+        let mut max_val = u[[k, k]].abs();
+        let mut max_row = k;
+
+        for i in (k + 1)..n {
+            if u[[i, k]].abs() > max_val {
+                max_val = u[[i, k]].abs();
+                max_row = i;
+            }
         }
 
-        let input_2d = input
-            .view()
-            .into_dimensionality::<Ix2>()
-            .map_err(|_| OpError::IncompatibleShape("Failed to convert to 2D array".into()))?;
-
-        // Compute LU decomposition with partial pivoting
-        let mut l = Array2::<F>::eye(n);
-        let mut u = input_2d.to_owned();
-        let mut p = Array2::<F>::eye(n); // Permutation matrix
-
-        // Gaussian elimination with partial pivoting
-        for k in 0..n - 1 {
-            // Find pivot
-            let mut max_val = u[[k, k]].abs();
-            let mut max_row = k;
-
-            for i in (k + 1)..n {
-                if u[[i, k]].abs() > max_val {
-                    max_val = u[[i, k]].abs();
-                    max_row = i;
-                }
+        // Swap rows if needed
+        if max_row != k {
+            // Swap rows in U
+            for j in 0..m {
+                u.swap([k, j], [max_row, j]);
             }
 
-            // Swap rows if needed
-            if max_row != k {
-                // Swap rows in U
-                for j in 0..m {
-                    let temp = u[[k, j]];
-                    u[[k, j]] = u[[max_row, j]];
-                    u[[max_row, j]] = temp;
-                }
-
-                // Swap rows in P
-                for j in 0..n {
-                    let temp = p[[k, j]];
-                    p[[k, j]] = p[[max_row, j]];
-                    p[[max_row, j]] = temp;
-                }
-
-                // Swap rows in L (only the computed part)
-                for j in 0..k {
-                    let temp = l[[k, j]];
-                    l[[k, j]] = l[[max_row, j]];
-                    l[[max_row, j]] = temp;
-                }
+            // Swap rows in P
+            for j in 0..n {
+                p.swap([k, j], [max_row, j]);
             }
 
-            // Elimination
-            if u[[k, k]].abs() > F::epsilon() {
-                for i in (k + 1)..n {
-                    l[[i, k]] = u[[i, k]] / u[[k, k]];
-                    for j in k..m {
-                        u[[i, j]] = u[[i, j]] - l[[i, k]] * u[[k, j]];
-                    }
-                }
+            // Swap rows in L (only the computed part)
+            for j in 0..k {
+                l.swap([k, j], [max_row, j]);
             }
         }
 
-        // Zero out lower triangular part of U
-        for i in 0..n {
-            for j in 0..i {
-                u[[i, j]] = F::zero();
+        // Elimination
+        if u[[k, k]].abs() > F::epsilon() {
+            for i in (k + 1)..n {
+                l[[i, k]] = u[[i, k]] / u[[k, k]];
+                for j in k..m {
+                    u[[i, j]] = u[[i, j]] - l[[i, k]] * u[[k, j]];
+                }
             }
         }
-
-        // Append outputs: P, L, U
-        ctx.append_output(p.into_dyn());
-        ctx.append_output(l.into_dyn());
-        ctx.append_output(u.into_dyn());
-
-        Ok(())
     }
 
-    fn grad(&self, ctx: &mut GradientContext<F>) {
-        // LU decomposition gradient is complex, using simplified version
-        ctx.append_input_grad(0, None);
+    // Zero out lower triangular part of U
+    for i in 0..n {
+        for j in 0..i {
+            u[[i, j]] = F::zero();
+        }
     }
+
+    Ok((p, l, u))
 }
 
 /// LU component extraction operators
+///
+/// The gradients of L and U come from differentiating `PA = LU` with P fixed:
+/// `gA = P^T L^{-T} (tril_strict(L^T gL) + triu(gU U^T)) U^{-T}`, each
+/// component contributing its own term, computed with triangular adjoint
+/// solves. P is piecewise constant and has no gradient.
 pub struct LUExtractOp {
     component: usize, // 0 for P, 1 for L, 2 for U
 }
 
 impl<F: Float + ndarray::ScalarOperand> Op<F> for LUExtractOp {
-    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
-        let input = ctx.input(0);
-        let shape = input.shape();
-
-        if shape.len() != 2 || shape[0] != shape[1] {
-            return Err(OpError::IncompatibleShape(
-                "LU requires square matrix".into(),
-            ));
-        }
-
-        let n = shape[0];
-        let input_2d = input
-            .view()
-            .into_dimensionality::<Ix2>()
-            .map_err(|_| OpError::IncompatibleShape("Failed to convert to 2D array".into()))?;
-
-        // Re-run the LU computation
-        let mut l = Array2::<F>::eye(n);
-        let mut u = input_2d.to_owned();
-        let p = Array2::<F>::eye(n);
-
-        // Simplified LU without pivoting for extraction
-        for k in 0..n - 1 {
-            if u[[k, k]].abs() > F::epsilon() {
-                for i in (k + 1)..n {
-                    l[[i, k]] = u[[i, k]] / u[[k, k]];
-                    for j in k..n {
-                        u[[i, j]] = u[[i, j]] - l[[i, k]] * u[[k, j]];
-                    }
-                }
-            }
-        }
+    fn name(&self) -> &'static str {
+        "LUExtract"
+    }
 
-        // Zero out lower triangular part of U
-        for i in 0..n {
-            for j in 0..i {
-                u[[i, j]] = F::zero();
-            }
-        }
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let (p, l, u) = compute_lu(&ctx.input(0))?;
 
         match self.component {
             0 => ctx.append_output(p.into_dyn()),
@@ -1418,7 +1127,28 @@ impl<F: Float + ndarray::ScalarOperand> Op<F> for LUExtractOp {
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        ctx.append_input_grad(0, None);
+        if self.component == 0 {
+            ctx.append_input_grad(0, None);
+            return;
+        }
+        let (p, l, u) = lu(ctx.input(0));
+        let gy = ctx.output_grad();
+        let inner = if self.component == 1 {
+            let lt = crate::tensor_ops::transpose(l, &[1, 0]);
+            triangle_mask(&crate::tensor_ops::matmul(lt, gy), true, 0.)
+        } else {
+            let ut = crate::tensor_ops::transpose(u, &[1, 0]);
+            triangle_mask(&crate::tensor_ops::matmul(gy, ut), false, 1.)
+        };
+
+        // L^{-T} inner U^{-T}
+        let x = crate::tensor_ops::solve(crate::tensor_ops::transpose(l, &[1, 0]), inner);
+        let y = crate::tensor_ops::transpose(
+            crate::tensor_ops::solve(u, crate::tensor_ops::transpose(x, &[1, 0])),
+            &[1, 0],
+        );
+        let ga = crate::tensor_ops::matmul(crate::tensor_ops::transpose(p, &[1, 0]), y);
+        ctx.append_input_grad(0, Some(ga));
     }
 }
 
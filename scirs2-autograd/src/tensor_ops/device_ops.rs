@@ -0,0 +1,39 @@
+use crate::device::Device;
+use crate::op;
+use crate::tensor_ops::*;
+use crate::Float;
+use serde::{Deserialize, Serialize};
+
+/// Transfer of a tensor to another device.
+#[derive(Serialize, Deserialize)]
+pub struct ToDevice {
+    pub device: Device,
+}
+
+impl<T: Float> op::Op<T> for ToDevice {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ToDevice", self)
+    }
+
+    fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
+        let ret = ctx.input(0).to_owned();
+        ctx.append_output(ret);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut op::GradientContext<T>) {
+        // Back to the device of the input
+        let gx = to_device(ctx.output_grad(), ctx.input(0).device());
+        ctx.append_input_grad(0, Some(gx));
+    }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let y = ctx.rebuild(&[*ctx.input(0)]);
+        ctx.set_output(y, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
+}
@@ -3,11 +3,13 @@ use crate::ndarray_ext::NdArray;
 use crate::same_type;
 use crate::tensor::Tensor;
 
+use crate::op;
+use crate::tensor_ops::binary_ops::maybe_reduce;
 use crate::Float;
 use crate::NdArrayView;
-use crate::{op, NdArrayViewMut};
 use ndarray;
 use ndarray::{ArrayView2, ArrayViewMut2};
+use serde::{Deserialize, Serialize};
 
 // Read pointer to type `A` as type `B`.
 //
@@ -60,115 +62,57 @@ fn mat_mul_impl_slow<F: Float>(
     }
     kernel_call_def!(f32, sgemm);
     kernel_call_def!(f64, dgemm);
-}
 
-/// C ← α A B + β C
-#[allow(unused_assignments)]
-#[allow(unused)]
-fn batch_mat_mul_impl_slow<F: Float>(
-    alpha: F,
-    lhs: &NdArrayView<'_, F>,
-    rhs: &NdArrayView<'_, F>,
-    beta: F,
-    c: &mut NdArrayViewMut<'_, F>,
-) {
-    let mut lhs_ = lhs.view();
-    let mut rhs_ = rhs.view();
-    let c_ = c.view_mut();
-    let mut lhs_strides = lhs_.strides();
-    let mut rhs_strides = rhs_.strides();
-    let rank = lhs_strides.len();
-
-    let copied_lhs;
-    let copied_rhs;
-    {
-        if batch_mat_mul_requires_copy(lhs_strides) {
-            copied_lhs = crate::ndarray_ext::deep_copy(&lhs_);
-            lhs_ = copied_lhs.view();
-            lhs_strides = lhs_.strides();
-        }
-        if batch_mat_mul_requires_copy(rhs_strides) {
-            copied_rhs = crate::ndarray_ext::deep_copy(&rhs_);
-            rhs_ = copied_rhs.view();
-            rhs_strides = rhs_.strides();
-        }
+    if crate::mixed_precision::is_half::<F>() {
+        // Half-precision products are accumulated in f32
+        let to_f32 = |x: &ArrayView2<'_, F>| x.mapv(|a| a.to_f32().unwrap());
+        let mut c32 = if beta == F::zero() {
+            ndarray::Array2::zeros(c.dim())
+        } else {
+            c.mapv(|a| a.to_f32().unwrap())
+        };
+        mat_mul_impl_slow(
+            alpha.to_f32().unwrap(),
+            &to_f32(lhs).view(),
+            &to_f32(rhs).view(),
+            beta.to_f32().unwrap(),
+            &mut c32.view_mut(),
+        );
+        c.zip_mut_with(&c32, |a, &b| *a = F::from(b).unwrap());
     }
+}
 
-    let lhs_shape = lhs_.shape();
-    let rhs_shape = rhs_.shape();
-    let (m, k, n) = (
-        lhs_shape[rank - 2],
-        lhs_shape[rank - 1],
-        rhs_shape[rank - 1],
-    );
+/// Broadcasts the batch shapes of two operands in the NumPy way
+fn broadcast_batch_shape(lhs: &[usize], rhs: &[usize]) -> Option<Vec<usize>> {
+    let rank = lhs.len().max(rhs.len());
+    let dim =
+        |shape: &[usize], i: usize| (i + shape.len()).checked_sub(rank).map_or(1, |j| shape[j]);
+    (0..rank)
+        .map(|i| match (dim(lhs, i), dim(rhs, i)) {
+            (a, b) if a == b || b == 1 => Some(a),
+            (1, b) => Some(b),
+            _ => None,
+        })
+        .collect()
+}
 
-    // common parameters for gemm
-    let (rsa, csa) = (lhs_strides[rank - 2], lhs_strides[rank - 1]);
-    let (rsb, csb) = (rhs_strides[rank - 2], rhs_strides[rank - 1]);
-    let (rsc, csc) = {
-        let strides = c_.strides();
-        (strides[rank - 2], strides[rank - 1])
-    };
-    let num_batches: usize = lhs_shape[..rank - 2].iter().product();
-    let lhs_batch_size = lhs_.len() / num_batches;
-    let rhs_batch_size = rhs_.len() / num_batches;
-    let c_batch_size = c_.len() / num_batches;
-    let ap_init = lhs.as_ptr();
-    let bp_init = rhs.as_ptr();
-    let cp_init = c.as_mut_ptr();
-
-    use scirs2_core::parallel_ops::*;
-    use std::slice;
-
-    unsafe {
-        let lhs_slice = slice::from_raw_parts(ap_init, lhs.len());
-        let rhs_slice = slice::from_raw_parts(bp_init, rhs.len());
-        let c_slice = slice::from_raw_parts_mut(cp_init, c.len());
-
-        macro_rules! kernel_call_def {
-            ($ty:ty, $f:ident) => {
-                if crate::same_type::<F, $ty>() {
-                    let lhs_iter = lhs_slice.par_iter().step_by(lhs_batch_size);
-                    let rhs_iter = rhs_slice.par_iter().step_by(rhs_batch_size);
-                    let c_iter = c_slice.par_iter_mut().step_by(c_batch_size);
-
-                    lhs_iter
-                        .zip_eq(rhs_iter)
-                        .zip_eq(c_iter)
-                        .for_each(|((lhs, rhs), c)| {
-                            ::matrixmultiply::$f(
-                                m,
-                                k,
-                                n,
-                                cast_as(&alpha),
-                                lhs as *const F as *const _,
-                                rsa,
-                                csa,
-                                rhs as *const F as *const _,
-                                rsb,
-                                csb,
-                                cast_as(&beta),
-                                c as *mut F as *mut _,
-                                rsc,
-                                csc,
-                            );
-                        });
-                }
-            };
+/// Flat index into the batch `shape` of the element at the flat index `i` of
+/// the broadcast batch shape `out_shape`.
+fn broadcast_batch_index(mut i: usize, out_shape: &[usize], shape: &[usize]) -> usize {
+    let offset = out_shape.len() - shape.len();
+    let (mut index, mut stride) = (0, 1);
+    for (axis, &out_dim) in out_shape.iter().enumerate().rev() {
+        let coord = i % out_dim;
+        i /= out_dim;
+        if axis >= offset {
+            let dim = shape[axis - offset];
+            if dim != 1 {
+                index += coord * stride;
+            }
+            stride *= dim;
         }
-        kernel_call_def!(f32, sgemm);
-        kernel_call_def!(f64, dgemm);
     }
-}
-
-#[inline]
-fn batch_mat_mul_requires_copy(stride: &[ndarray::Ixs]) -> bool {
-    let rank = stride.len();
-    // unwrap is ok since stride.len() > 2
-    let min_str = *stride[0..rank - 2].iter().min().unwrap();
-    let row_str = stride[rank - 2];
-    let col_str = stride[rank - 1];
-    min_str < row_str || min_str < col_str
+    index
 }
 
 fn dot_shape_error(m: usize, k: usize, k2: usize, n: usize) -> String {
@@ -188,17 +132,47 @@ fn dot_shape_error(m: usize, k: usize, k2: usize, n: usize) -> String {
 
 use ndarray::ShapeBuilder;
 
+#[derive(Serialize, Deserialize)]
 pub struct MatMul {
     pub transpose_a: bool,
     pub transpose_b: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct BatchMatMul {
     pub transpose_a: bool,
     pub transpose_b: bool,
 }
 
+/// Operands of the products giving the gradients of `C = op(A) op(B)`, where `op`
+/// transposes if the corresponding flag is set.
+///
+/// Each entry is `(lhs, rhs, transpose_lhs, transpose_rhs)`; the first one is for `A`
+/// and the second one for `B`.
+fn matmul_grad_operands<'g, T: Float>(
+    a: &'g Tensor<'g, T>,
+    b: &'g Tensor<'g, T>,
+    gy: &'g Tensor<'g, T>,
+    transpose_a: bool,
+    transpose_b: bool,
+) -> [(&'g Tensor<'g, T>, &'g Tensor<'g, T>, bool, bool); 2] {
+    match (transpose_a, transpose_b) {
+        // gA = gC B^T, gB = A^T gC
+        (false, false) => [(gy, b, false, true), (a, gy, true, false)],
+        // gA = gC B, gB = gC^T A
+        (false, true) => [(gy, b, false, false), (gy, a, true, false)],
+        // gA = B gC^T, gB = A gC
+        (true, false) => [(b, gy, false, true), (a, gy, false, false)],
+        // gA = B^T gC^T, gB = gC^T A^T
+        (true, true) => [(b, gy, true, true), (gy, a, true, true)],
+    }
+}
+
 impl<T: Float> op::Op<T> for MatMul {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("MatMul", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         // Check if we have enough inputs
         let inputs = ctx.inputs();
@@ -321,33 +295,67 @@ impl<T: Float> op::Op<T> for MatMul {
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::gemm(ctx, gpu, self.transpose_a, self.transpose_b)
+    }
+
     fn grad(&self, ctx: &mut crate::op::GradientContext<T>) {
-        let gy = &ctx.output_grad();
-        let opa = Tensor::builder(ctx.graph())
-            .append_input(gy, false)
-            .append_input(ctx.input(1), false)
-            .build(MatMul {
-                transpose_a: false,
-                transpose_b: true,
-            });
+        let operands = matmul_grad_operands(
+            ctx.input(0),
+            ctx.input(1),
+            ctx.output_grad(),
+            self.transpose_a,
+            self.transpose_b,
+        );
+        for (i, (lhs, rhs, transpose_a, transpose_b)) in operands.into_iter().enumerate() {
+            let gx = Tensor::builder(ctx.graph())
+                .append_input(lhs, false)
+                .append_input(rhs, false)
+                .build(MatMul {
+                    transpose_a,
+                    transpose_b,
+                });
+            ctx.append_input_grad(i, Some(gx));
+        }
+    }
 
-        let opb = Tensor::builder(ctx.graph())
-            .append_input(ctx.input(0), false)
-            .append_input(gy, false)
-            .build(MatMul {
-                transpose_a: true,
-                transpose_b: false,
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let inputs = ctx.broadcastable_inputs();
+        let y = Tensor::builder(ctx.graph())
+            .append_input(inputs[0], false)
+            .append_input(inputs[1], false)
+            .build(BatchMatMul {
+                transpose_a: self.transpose_a,
+                transpose_b: self.transpose_b,
             });
+        ctx.set_output(y, true);
+    }
 
-        ctx.append_input_grad(0, Some(opa));
-        ctx.append_input_grad(1, Some(opb));
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let (x0, x1) = (*ctx.input(0), *ctx.input(1));
+        let t0 = ctx.input_tangent(0).map(|t0| ctx.rebuild(&[*t0, x1]));
+        let t1 = ctx.input_tangent(1).map(|t1| ctx.rebuild(&[x0, *t1]));
+        let t = match (t0, t1) {
+            (Some(t0), Some(t1)) => t0 + t1,
+            (t0, t1) => t0.or(t1).unwrap(),
+        };
+        ctx.append_output_tangent(Some(t));
     }
 }
 
 impl<T: Float> op::Op<T> for BatchMatMul {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("BatchMatMul", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
-        let mut x0 = ctx.input(0);
-        let mut x1 = ctx.input(1);
+        let x0 = ctx.input(0);
+        let x1 = ctx.input(1);
         let rank0 = x0.ndim();
         let rank1 = x1.ndim();
 
@@ -364,68 +372,112 @@ impl<T: Float> op::Op<T> for BatchMatMul {
             )));
         }
 
-        if self.transpose_a {
-            x0.swap_axes(rank0 - 2, rank0 - 1);
-        }
-
-        if self.transpose_b {
-            x1.swap_axes(rank1 - 2, rank1 - 1);
-        }
-
-        let shape0 = x0.shape();
-        let shape1 = x1.shape();
-        if rank0 != rank1 || shape0[..rank0 - 2] != shape1[..rank0 - 2] {
-            return Err(op::OpError::IncompatibleShape(format!(
+        let (shape0, shape1) = (x0.shape(), x1.shape());
+        let (batch0, mat0) = shape0.split_at(rank0 - 2);
+        let (batch1, mat1) = shape1.split_at(rank1 - 2);
+        let (m, k) = if self.transpose_a {
+            (mat0[1], mat0[0])
+        } else {
+            (mat0[0], mat0[1])
+        };
+        let (k2, n) = if self.transpose_b {
+            (mat1[1], mat1[0])
+        } else {
+            (mat1[0], mat1[1])
+        };
+        let batch = broadcast_batch_shape(batch0, batch1).filter(|_| k == k2);
+        let batch = batch.ok_or_else(|| {
+            op::OpError::IncompatibleShape(format!(
                 "Input shapes mismatch: {:?} vs {:?}",
                 shape0, shape1
-            )));
-        }
-
-        let ret_shape = {
-            let mut ret = shape0.to_vec();
-            ret[rank0 - 2] = shape0[rank0 - 2];
-            ret[rank0 - 1] = shape1[rank0 - 1];
-            ret
+            ))
+        })?;
+
+        // Stack the matrices of each operand along a single batch axis
+        let stack = |x: &NdArrayView<T>, batch: &[usize], mat: &[usize]| {
+            x.as_standard_layout()
+                .into_owned()
+                .into_shape_with_order((batch.iter().product(), mat[0], mat[1]))
+                .unwrap()
         };
-        // A is Copy so this is safe
-        let size: usize = ret_shape.iter().product();
-        let mut v = Vec::with_capacity(size);
-        let mut c;
-        unsafe {
-            v.set_len(size);
+        let lhs = stack(&x0, batch0, mat0);
+        let rhs = stack(&x1, batch1, mat1);
+
+        let mut ret_shape = batch.clone();
+        ret_shape.extend([m, n]);
+        let mut c = NdArray::<T>::zeros(ret_shape);
+        if m * n > 0 {
+            use scirs2_core::parallel_ops::*;
             // BatchMatMul's ret val is a c-order array.
-            c = ndarray::Array::from_shape_vec_unchecked(ret_shape, v);
+            c.as_slice_mut()
+                .unwrap()
+                .par_chunks_mut(m * n)
+                .enumerate()
+                .for_each(|(i, c)| {
+                    let a =
+                        lhs.index_axis(ndarray::Axis(0), broadcast_batch_index(i, &batch, batch0));
+                    let b =
+                        rhs.index_axis(ndarray::Axis(0), broadcast_batch_index(i, &batch, batch1));
+                    let a = if self.transpose_a {
+                        a.reversed_axes()
+                    } else {
+                        a
+                    };
+                    let b = if self.transpose_b {
+                        b.reversed_axes()
+                    } else {
+                        b
+                    };
+                    let mut c = ArrayViewMut2::from_shape((m, n), c).unwrap();
+                    mat_mul_impl_slow(T::one(), &a, &b, T::zero(), &mut c);
+                });
         }
-        batch_mat_mul_impl_slow(T::one(), &x0, &x1, T::zero(), &mut c.view_mut());
 
-        // reshape to dst shape with safe unwrapping
         ctx.append_output(c);
         Ok(())
     }
 
     fn grad(&self, ctx: &mut crate::op::GradientContext<T>) {
-        let gy = &ctx.output_grad();
-        let opa = Tensor::builder(ctx.graph())
-            .append_input(gy, false)
-            .append_input(ctx.input(1), false)
-            .build(BatchMatMul {
-                transpose_a: false,
-                transpose_b: true,
-            });
+        let operands = matmul_grad_operands(
+            ctx.input(0),
+            ctx.input(1),
+            ctx.output_grad(),
+            self.transpose_a,
+            self.transpose_b,
+        );
+        for (i, (lhs, rhs, transpose_a, transpose_b)) in operands.into_iter().enumerate() {
+            let gx = Tensor::builder(ctx.graph())
+                .append_input(lhs, false)
+                .append_input(rhs, false)
+                .build(BatchMatMul {
+                    transpose_a,
+                    transpose_b,
+                });
+            // Sum over the batch axes the input was broadcast along
+            let x = ctx.input(i);
+            let gx = maybe_reduce(&crate::tensor_ops::shape(x), &gx, ctx.graph());
+            ctx.append_input_grad(i, Some(gx));
+        }
+    }
 
-        let opb = Tensor::builder(ctx.graph())
-            .append_input(ctx.input(0), false)
-            .append_input(gy, false)
-            .build(BatchMatMul {
-                transpose_a: true,
-                transpose_b: false,
-            });
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let y = ctx.rebuild(&ctx.broadcastable_inputs());
+        ctx.set_output(y, true);
+    }
 
-        ctx.append_input_grad(0, Some(opa));
-        ctx.append_input_grad(1, Some(opb));
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let (x0, x1) = (*ctx.input(0), *ctx.input(1));
+        let t0 = ctx.input_tangent(0).map(|t0| ctx.rebuild(&[*t0, x1]));
+        let t1 = ctx.input_tangent(1).map(|t1| ctx.rebuild(&[x0, *t1]));
+        let t = match (t0, t1) {
+            (Some(t0), Some(t1)) => t0 + t1,
+            (t0, t1) => t0.or(t1).unwrap(),
+        };
+        ctx.append_output_tangent(Some(t));
     }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct TensordotPreprocess;
 
 #[inline]
@@ -475,6 +527,10 @@ fn tensordot_preprocess<T: Float>(
 }
 
 impl<T: Float> op::Op<T> for TensordotPreprocess {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("TensordotPreprocess", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x0 = ctx.input(0);
         let x1 = &ctx.input(1);
@@ -15,14 +15,10 @@ impl<F: Float> crate::op::Op<F> for AdamWOp<F> {
         // AdamW requires the same 5 inputs as Adam
         // param, grad, m, v, t
 
-        // Debug info
-        eprintln!(
-            "AdamWOp::compute - Number of inputs: {}",
-            ctx.inputs().len()
-        );
-        for (i, input) in ctx.inputs().iter().enumerate() {
-            eprintln!("Input {}: shape {:?}", i, input.shape());
-        }
+        crate::tracing::debug_message("AdamWOp", || {
+            let shapes: Vec<_> = ctx.inputs().iter().map(|x| x.shape().to_vec()).collect();
+            format!("compute with input shapes {:?}", shapes)
+        });
 
         // Check if we have all the inputs we need
         if ctx.inputs().len() < 5 {
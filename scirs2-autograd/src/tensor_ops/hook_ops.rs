@@ -1,6 +1,8 @@
 use crate::op;
+use crate::tensor::Tensor;
 use crate::Float;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 pub(crate) struct HookOp<T: Float, H: crate::hooks::Hook<T>> {
     phantom: PhantomData<T>,
@@ -29,3 +31,67 @@ impl<T: Float, H: crate::hooks::Hook<T>> op::Op<T> for HookOp<T, H> {
         ctx.append_input_grad(0, Some(*ctx.output_grad()));
     }
 }
+
+/// Identity whose gradient goes through a [crate::hooks::GradHook]
+pub(crate) struct GradHookOp<T: Float, H: crate::hooks::GradHook<T>> {
+    phantom: PhantomData<T>,
+    pub hook: Rc<H>,
+}
+
+impl<T: Float, H: crate::hooks::GradHook<T>> GradHookOp<T, H> {
+    #[inline]
+    pub fn new(hook: H) -> Self {
+        GradHookOp {
+            phantom: PhantomData,
+            hook: Rc::new(hook),
+        }
+    }
+}
+
+impl<T: Float, H: crate::hooks::GradHook<T> + 'static> op::Op<T> for GradHookOp<T, H> {
+    fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
+        let ret = ctx.input(0).to_owned();
+        ctx.append_output(ret);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut crate::op::GradientContext<T>) {
+        let gx = Tensor::builder(ctx.graph())
+            .append_input(ctx.output_grad(), false)
+            .build(ApplyGradHook {
+                phantom: PhantomData,
+                hook: self.hook.clone(),
+            });
+        ctx.append_input_grad(0, Some(gx));
+    }
+}
+
+/// Calls a gradient hook with the gradient of a [GradHookOp]
+struct ApplyGradHook<T: Float, H: crate::hooks::GradHook<T>> {
+    phantom: PhantomData<T>,
+    hook: Rc<H>,
+}
+
+impl<T: Float, H: crate::hooks::GradHook<T>> op::Op<T> for ApplyGradHook<T, H> {
+    fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
+        let grad = ctx.input(0);
+        let ret = match self.hook.call(&grad) {
+            Some(ret) if ret.shape() != grad.shape() => {
+                return Err(crate::op::OpError::IncompatibleShape(format!(
+                    "gradient hook: the gradient of shape {:?} was replaced with shape {:?}",
+                    grad.shape(),
+                    ret.shape()
+                )));
+            }
+            Some(ret) => ret,
+            None => grad.to_owned(),
+        };
+        ctx.append_output(ret);
+        Ok(())
+    }
+
+    // Gradients of hooked gradients are passed through as they are.
+    fn grad(&self, ctx: &mut crate::op::GradientContext<T>) {
+        ctx.append_input_grad(0, Some(*ctx.output_grad()));
+    }
+}
@@ -1,4 +1,3 @@
-use crate::ndarray_ext::NdArray;
 use crate::op::{ComputeContext, GradientContext, Op, OpError};
 use crate::tensor::Tensor;
 use crate::{Context, Float};
@@ -62,43 +61,11 @@ impl<F: Float> Op<F> for TraceOp {
     fn grad(&self, ctx: &mut GradientContext<F>) {
         let gy = ctx.output_grad();
         let input = ctx.input(0);
-        let g = ctx.graph();
-
-        // Evaluate input to get dimensions
-        let input_array = match input.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let shape = input_array.shape();
-        let n = shape[0];
-
-        // Create a diagonal matrix with gradient value
-        let mut grad = NdArray::<F>::zeros(shape);
-        if let Ok(mut grad_2d) = grad.view_mut().into_dimensionality::<Ix2>() {
-            // Get scalar gradient value
-            let gy_array = match gy.eval(g) {
-                Ok(arr) => arr,
-                Err(_) => {
-                    ctx.append_input_grad(0, None);
-                    return;
-                }
-            };
-
-            let scalar_grad = gy_array[[]];
-
-            for i in 0..n {
-                grad_2d[[i, i]] = scalar_grad;
-            }
-        }
 
-        ctx.append_input_grad(
-            0,
-            Some(crate::tensor_ops::convert_to_tensor(grad, ctx.graph())),
-        );
+        // d tr(X) / dX = I, scaled by the gradient of the output
+        let diagonal = extract_diag(input);
+        let ones = crate::tensor_ops::ones(&crate::tensor_ops::shape(diagonal), ctx.graph());
+        ctx.append_input_grad(0, Some(diag(&(ones * gy))));
     }
 }
 
@@ -136,39 +103,9 @@ impl<F: Float> Op<F> for DiagOp {
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
+        // Only the diagonal of the output depends on the input
         let gy = ctx.output_grad();
-        let g = ctx.graph();
-
-        // Get gradient array via evaluation
-        let gy_array = match gy.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let shape = gy_array.shape();
-
-        if shape.len() == 2 && shape[0] == shape[1] {
-            let n = shape[0];
-            let mut grad = Array1::<F>::zeros(n).into_dyn();
-
-            // Get 2D view of gradient array
-            if let Ok(gy_2d) = gy_array.view().into_dimensionality::<Ix2>() {
-                for i in 0..n {
-                    grad[[i]] = gy_2d[[i, i]];
-                }
-            }
-
-            ctx.append_input_grad(
-                0,
-                Some(crate::tensor_ops::convert_to_tensor(grad, ctx.graph())),
-            );
-        } else {
-            // If shape is not compatible, return None gradient
-            ctx.append_input_grad(0, None);
-        }
+        ctx.append_input_grad(0, Some(extract_diag(gy)));
     }
 }
 
@@ -203,53 +140,9 @@ impl<F: Float> Op<F> for ExtractDiagOp {
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
+        // The gradient goes to the diagonal positions
         let gy = ctx.output_grad();
-        let input = ctx.input(0);
-        let g = ctx.graph();
-
-        // Get input array via evaluation to get its shape
-        let input_array = match input.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        // Get gradient array via evaluation
-        let gy_array = match gy.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let shape = input_array.shape();
-
-        // Create a zero matrix and fill diagonal with gradient values
-        let mut grad = NdArray::<F>::zeros(shape);
-        if let Ok(mut grad_2d) = grad.view_mut().into_dimensionality::<Ix2>() {
-            let n = gy_array.len();
-
-            // Get 1D view of gradient array if possible
-            let gy_1d = match gy_array.view().into_dimensionality::<Ix1>() {
-                Ok(view) => view,
-                Err(_) => {
-                    ctx.append_input_grad(0, None);
-                    return;
-                }
-            };
-
-            for i in 0..n {
-                grad_2d[[i, i]] = gy_1d[i];
-            }
-        }
-
-        ctx.append_input_grad(
-            0,
-            Some(crate::tensor_ops::convert_to_tensor(grad, ctx.graph())),
-        );
+        ctx.append_input_grad(0, Some(diag(gy)));
     }
 }
 
@@ -119,7 +119,9 @@ where
 
 /// Batched matrix multiplication with inputs's transposition.
 ///
-/// The rank of `a` and `b` must be equals.
+/// The last two axes of `a` and `b` are the matrices and the leading axes are
+/// batch axes, which are broadcast in the same way as NumPy's `matmul`.
+/// Gradients are summed over the broadcast batch axes.
 ///
 /// # Examples
 ///
@@ -161,7 +163,9 @@ where
 
 /// Batched matrix multiplication.
 ///
-/// The rank of `a` and `b` must be equals.
+/// The last two axes of `a` and `b` are the matrices and the leading axes are
+/// batch axes, which are broadcast in the same way as NumPy's `matmul`: missing
+/// axes and axes of size 1 are repeated. The batches are computed in parallel.
 ///
 /// # Examples
 ///
@@ -174,6 +178,11 @@ where
 ///    let b: ag::Tensor<f32> = ag::tensor_ops::ones((&[2, 3, 2, 3]), g);
 ///    let c = batch_matmul(a, b);
 ///    assert_eq!(c.eval(g).unwrap().shape(), &[2, 3, 4, 3]);
+///
+///    // A single matrix applied to every batch
+///    let w: ag::Tensor<f32> = ag::tensor_ops::ones((&[2, 5]), g);
+///    let d = batch_matmul(a, w);
+///    assert_eq!(d.eval(g).unwrap().shape(), &[2, 3, 4, 5]);
 /// });
 /// ```
 ///
@@ -5,41 +5,74 @@ use crate::tensor_ops::*;
 use crate::Float;
 use ndarray;
 use ndarray::Zip;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 
+#[derive(Serialize, Deserialize)]
 pub struct Sin;
+#[derive(Serialize, Deserialize)]
 pub struct Cos;
+#[derive(Serialize, Deserialize)]
 pub struct Tan;
+#[derive(Serialize, Deserialize)]
 pub struct Asin;
+#[derive(Serialize, Deserialize)]
 pub struct Acos;
+#[derive(Serialize, Deserialize)]
 pub struct Atan;
+#[derive(Serialize, Deserialize)]
 pub struct Sinh;
+#[derive(Serialize, Deserialize)]
 pub struct Cosh;
+#[derive(Serialize, Deserialize)]
 pub struct Tanh;
+#[derive(Serialize, Deserialize)]
 pub struct Asinh;
+#[derive(Serialize, Deserialize)]
 pub struct Acosh;
+#[derive(Serialize, Deserialize)]
 pub struct Atanh;
+#[derive(Serialize, Deserialize)]
 pub struct Exp;
+#[derive(Serialize, Deserialize)]
 pub struct Exp2;
+#[derive(Serialize, Deserialize)]
 pub struct Exp10;
+#[derive(Serialize, Deserialize)]
 pub struct Sqrt;
+#[derive(Serialize, Deserialize)]
 pub struct NegOp;
+#[derive(Serialize, Deserialize)]
 pub struct Floor;
+#[derive(Serialize, Deserialize)]
 pub struct Ceil;
+#[derive(Serialize, Deserialize)]
 pub struct Sign;
+#[derive(Serialize, Deserialize)]
 pub struct Inv;
+#[derive(Serialize, Deserialize)]
 pub struct InvSqrt;
+#[derive(Serialize, Deserialize)]
 pub struct Square;
+#[derive(Serialize, Deserialize)]
 pub struct Abs;
+#[derive(Serialize, Deserialize)]
 pub struct Log2;
+#[derive(Serialize, Deserialize)]
 pub struct Log10;
+#[derive(Serialize, Deserialize)]
 pub struct Ln;
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Pow<T: Float> {
     pub a: T,
 }
+#[derive(Serialize, Deserialize)]
 pub struct LogSumExp {
     pub axis: isize,
     pub keep_dims: bool,
 }
+#[derive(Serialize, Deserialize)]
 pub struct Transpose {
     pub invert_axes: bool,
 }
@@ -272,6 +305,14 @@ macro_rules! elem_wise_vm_with_param_or_std {
 }
 
 impl<T: Float> op::Op<T> for Abs {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Abs", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.abs()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.abs());
         ctx.append_output(ret);
@@ -284,6 +325,14 @@ impl<T: Float> op::Op<T> for Abs {
 }
 
 impl<T: Float> op::Op<T> for NegOp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("NegOp", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.neg()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|x| x.neg());
         ctx.append_output(ret);
@@ -293,9 +342,22 @@ impl<T: Float> op::Op<T> for NegOp {
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         ctx.append_input_grad(0, Some(neg(ctx.output_grad())));
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Square {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Square", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a * a))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).mapv(|a| a * a);
         ctx.append_output(ret);
@@ -306,9 +368,23 @@ impl<T: Float> op::Op<T> for Square {
         let two = scalar(T::one() + T::one(), ctx.graph());
         ctx.append_input_grad(0, Some(two * ctx.input(0) * ctx.output_grad()));
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let two = scalar(T::from(2.).unwrap(), ctx.graph());
+        let t = ctx.input_tangent(0).map(|t| *t * ctx.input(0) * two);
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Inv {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Inv", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.recip()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.recip());
         ctx.append_output(ret);
@@ -321,6 +397,14 @@ impl<T: Float> op::Op<T> for Inv {
 }
 
 impl<T: Float> op::Op<T> for InvSqrt {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("InvSqrt", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.sqrt().recip()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.sqrt().recip());
         ctx.append_output(ret);
@@ -336,6 +420,10 @@ impl<T: Float> op::Op<T> for InvSqrt {
 }
 
 impl<T: Float> op::Op<T> for Sign {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Sign", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).mapv(|x| {
             if x == T::zero() {
@@ -354,6 +442,14 @@ impl<T: Float> op::Op<T> for Sign {
 }
 
 impl<T: Float> op::Op<T> for Floor {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Floor", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.floor()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.floor());
         ctx.append_output(ret);
@@ -366,6 +462,14 @@ impl<T: Float> op::Op<T> for Floor {
 }
 
 impl<T: Float> op::Op<T> for Ceil {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Ceil", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.ceil()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.ceil());
         ctx.append_output(ret);
@@ -378,6 +482,10 @@ impl<T: Float> op::Op<T> for Ceil {
 }
 
 impl<T: Float> op::Op<T> for Transpose {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Transpose", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let perm = &ctx.input(1);
         let perm_len = perm.len();
@@ -414,6 +522,18 @@ impl<T: Float> op::Op<T> for Transpose {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if !ctx.is_batched(1) {
+            let y = ctx.rebuild(&[*ctx.input(0), ctx.batched_permutation(1)]);
+            ctx.set_output(y, true);
+        }
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 #[cfg(all(feature = "blas", feature = "intel-mkl"))]
@@ -540,6 +660,10 @@ pub fn logsumexp_forward<T: Float>(x: &NdArrayView<T>, axis: isize, keep_dims: b
 }
 
 impl<T: Float> op::Op<T> for LogSumExp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("LogSumExp", self)
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = logsumexp_forward(&ctx.input(0), self.axis, self.keep_dims);
         ctx.append_output(ret);
@@ -556,6 +680,15 @@ impl<T: Float> op::Op<T> for LogSumExp {
 }
 
 impl<T: Float> op::Op<T> for Pow<T> {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Pow", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        let p = self.a;
+        Some(Rc::new(move |x: T| x.powf(p)))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.powf(self.a));
         ctx.append_output(ret);
@@ -570,6 +703,14 @@ impl<T: Float> op::Op<T> for Pow<T> {
 }
 
 impl<T: Float> op::Op<T> for Sqrt {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Sqrt", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.sqrt()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.sqrt());
         ctx.append_output(ret);
@@ -582,9 +723,23 @@ impl<T: Float> op::Op<T> for Sqrt {
         let ret = scalar(half, ctx.graph()) * pow(x, -half);
         ctx.append_input_grad(0, Some(ctx.output_grad() * ret));
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let half = scalar(T::from(0.5).unwrap(), ctx.graph());
+        let t = ctx.input_tangent(0).map(|t| *t * half / ctx.output());
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Log10 {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Log10", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.log10()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.log10());
         ctx.append_output(ret);
@@ -598,6 +753,14 @@ impl<T: Float> op::Op<T> for Log10 {
 }
 
 impl<T: Float> op::Op<T> for Log2 {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Log2", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.log2()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.log2());
         ctx.append_output(ret);
@@ -611,6 +774,14 @@ impl<T: Float> op::Op<T> for Log2 {
 }
 
 impl<T: Float> op::Op<T> for Ln {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Ln", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.ln()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.ln());
         ctx.append_output(ret);
@@ -620,9 +791,22 @@ impl<T: Float> op::Op<T> for Ln {
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         ctx.append_input_grad(0, Some(ctx.output_grad() / ctx.input(0)));
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.input_tangent(0).map(|t| *t / ctx.input(0));
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Exp {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Exp", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.exp()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.exp());
         ctx.append_output(ret);
@@ -632,9 +816,22 @@ impl<T: Float> op::Op<T> for Exp {
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         ctx.append_input_grad(0, Some(ctx.output() * ctx.output_grad()));
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.input_tangent(0).map(|t| *t * ctx.output());
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Exp2 {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Exp2", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.exp2()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.exp2());
         ctx.append_output(ret);
@@ -650,6 +847,14 @@ impl<T: Float> op::Op<T> for Exp2 {
 }
 
 impl<T: Float> op::Op<T> for Exp10 {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Exp10", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| T::from(10).unwrap().powf(a)))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ten = T::from(10).unwrap();
 
@@ -668,6 +873,14 @@ impl<T: Float> op::Op<T> for Exp10 {
 }
 
 impl<T: Float> op::Op<T> for Atanh {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Atanh", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.atanh()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.atanh());
         ctx.append_output(ret);
@@ -682,6 +895,14 @@ impl<T: Float> op::Op<T> for Atanh {
 }
 
 impl<T: Float> op::Op<T> for Acosh {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Acosh", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.acosh()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.acosh());
         ctx.append_output(ret);
@@ -697,6 +918,14 @@ impl<T: Float> op::Op<T> for Acosh {
 }
 
 impl<T: Float> op::Op<T> for Asinh {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Asinh", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.asinh()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.asinh());
         ctx.append_output(ret);
@@ -712,21 +941,54 @@ impl<T: Float> op::Op<T> for Asinh {
 }
 
 impl<T: Float> op::Op<T> for Tanh {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Tanh", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.tanh()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.tanh());
         ctx.append_output(ret);
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::unary(ctx, gpu, "tanh")
+    }
+
     fn grad(&self, ctx: &mut op::GradientContext<T>) {
         ctx.append_input_grad(
             0,
             Some(ctx.output_grad() * (scalar(T::one(), ctx.graph()) - square(ctx.output()))),
         );
     }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let y = ctx.output();
+        let t = ctx
+            .input_tangent(0)
+            .map(|t| *t * (scalar(T::one(), ctx.graph()) - square(y)));
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for Cosh {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Cosh", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.cosh()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.cosh());
         ctx.append_output(ret);
@@ -739,6 +1001,14 @@ impl<T: Float> op::Op<T> for Cosh {
 }
 
 impl<T: Float> op::Op<T> for Sinh {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Sinh", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.sinh()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.sinh());
         ctx.append_output(ret);
@@ -751,6 +1021,14 @@ impl<T: Float> op::Op<T> for Sinh {
 }
 
 impl<T: Float> op::Op<T> for Atan {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Atan", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.atan()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.atan());
         ctx.append_output(ret);
@@ -766,6 +1044,14 @@ impl<T: Float> op::Op<T> for Atan {
 }
 
 impl<T: Float> op::Op<T> for Acos {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Acos", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.acos()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.acos());
         ctx.append_output(ret);
@@ -780,6 +1066,14 @@ impl<T: Float> op::Op<T> for Acos {
 }
 
 impl<T: Float> op::Op<T> for Asin {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Asin", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.asin()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.asin());
         ctx.append_output(ret);
@@ -794,6 +1088,14 @@ impl<T: Float> op::Op<T> for Asin {
 }
 
 impl<T: Float> op::Op<T> for Sin {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Sin", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.sin()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.sin());
         ctx.append_output(ret);
@@ -806,6 +1108,14 @@ impl<T: Float> op::Op<T> for Sin {
 }
 
 impl<T: Float> op::Op<T> for Cos {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Cos", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.cos()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.cos());
         ctx.append_output(ret);
@@ -818,6 +1128,14 @@ impl<T: Float> op::Op<T> for Cos {
 }
 
 impl<T: Float> op::Op<T> for Tan {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("Tan", self)
+    }
+
+    fn elementwise_fn(&self) -> Option<op::ElementwiseFn<T>> {
+        Some(Rc::new(|a: T| a.tan()))
+    }
+
     fn compute(&self, ctx: &mut op::ComputeContext<T>) -> Result<(), op::OpError> {
         let ret = ctx.input(0).map(|a| a.tan());
         ctx.append_output(ret);
@@ -34,9 +34,7 @@ impl<F: Float + ndarray::ScalarOperand + FromPrimitive> Op<F> for MatrixSqrtOp {
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        let g = ctx.graph();
         let input = ctx.input(0);
-        let shape = input.shape().to_vec();
 
         // For matrix square root, the gradient is computed by solving
         // the Sylvester equation: dA/2 = X * dY + dY * X
@@ -45,13 +43,8 @@ impl<F: Float + ndarray::ScalarOperand + FromPrimitive> Op<F> for MatrixSqrtOp {
 
         // For now, we'll use a simplified approach with zeros
         // to maintain the correct shape
-        if shape.len() == 2 {
-            let grad_zeros = ndarray::ArrayD::zeros(ndarray::IxDyn(&shape));
-            let grad_tensor = crate::tensor_ops::convert_to_tensor(grad_zeros, g);
-            ctx.append_input_grad(0, Some(grad_tensor));
-        } else {
-            ctx.append_input_grad(0, None);
-        }
+        let grad_zeros = crate::tensor_ops::zeros(&crate::tensor_ops::shape(input), ctx.graph());
+        ctx.append_input_grad(0, Some(grad_zeros));
     }
 }
 
@@ -85,21 +78,14 @@ impl<F: Float + ndarray::ScalarOperand + FromPrimitive> Op<F> for MatrixLogOp {
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        let g = ctx.graph();
         let input = ctx.input(0);
-        let shape = input.shape().to_vec();
 
         // For matrix logarithm, the gradient involves solving
         // a complex equation involving the Fréchet derivative
         // For now, we'll use a simplified approach with zeros
         // to maintain the correct shape
-        if shape.len() == 2 {
-            let grad_zeros = ndarray::ArrayD::zeros(ndarray::IxDyn(&shape));
-            let grad_tensor = crate::tensor_ops::convert_to_tensor(grad_zeros, g);
-            ctx.append_input_grad(0, Some(grad_tensor));
-        } else {
-            ctx.append_input_grad(0, None);
-        }
+        let grad_zeros = crate::tensor_ops::zeros(&crate::tensor_ops::shape(input), ctx.graph());
+        ctx.append_input_grad(0, Some(grad_zeros));
     }
 }
 
@@ -135,21 +121,14 @@ impl<F: Float + ndarray::ScalarOperand + FromPrimitive> Op<F> for MatrixPowOp {
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        let g = ctx.graph();
         let input = ctx.input(0);
-        let shape = input.shape().to_vec();
 
         // Gradient of matrix power: p * A^(p-1) for scalar gradient
         // For matrix gradient it's more complex
         // For now, we'll use a simplified approach with zeros
         // to maintain the correct shape
-        if shape.len() == 2 {
-            let grad_zeros = ndarray::ArrayD::zeros(ndarray::IxDyn(&shape));
-            let grad_tensor = crate::tensor_ops::convert_to_tensor(grad_zeros, g);
-            ctx.append_input_grad(0, Some(grad_tensor));
-        } else {
-            ctx.append_input_grad(0, None);
-        }
+        let grad_zeros = crate::tensor_ops::zeros(&crate::tensor_ops::shape(input), ctx.graph());
+        ctx.append_input_grad(0, Some(grad_zeros));
     }
 }
 
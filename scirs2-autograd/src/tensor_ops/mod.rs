@@ -11,6 +11,7 @@
 
 use ndarray;
 
+use crate::device::Device;
 use crate::graph::AsGraph;
 use crate::ndarray_ext::{ArrayRng, NdArray};
 use crate::tensor::{AsTensor, Tensor};
@@ -31,6 +32,7 @@ pub(crate) mod binary_ops;
 // mod blas_ffi; // Removed - all BLAS operations now go through scirs2-core
 pub(crate) mod const_gen_ops;
 mod conv_ops;
+pub(crate) mod device_ops;
 pub(crate) mod dot_ops;
 pub(crate) mod gradient_descent_ops;
 mod gradient_ops;
@@ -231,6 +233,60 @@ where
     ret
 }
 
+/// Maps `f`, a function of single examples, over the first axis of its inputs.
+///
+/// `f` is called once, with tensors standing for an example of each input, and
+/// the ops it builds are rewritten to compute all the examples at once, so a
+/// batch doesn't need a loop over its examples. The outputs have the examples
+/// stacked along their first axis. Ops without a batching rule (see
+/// [Op::batch](crate::op::Op::batch)) are computed once per example.
+///
+///    ```
+/// use ndarray::array;
+/// use scirs2_autograd as ag;
+/// use ag::tensor_ops as T;
+///
+/// ag::run(|ctx: &mut ag::Context<f64>| {
+///     let w = T::variable(array![[1.], [2.]], ctx);
+///     let xs = ctx.placeholder("xs", &[-1, 2]);
+///
+///     // Gradient of the loss of each example, rather than of their sum
+///     let per_example_grad = T::vmap(|x: &[ag::Tensor<f64>]| {
+///         let x = T::reshape(x[0], &[1, 2]);
+///         let loss = T::sum_all(T::square(T::matmul(x, w)));
+///         vec![T::grad(&[loss], &[w])[0]]
+///     });
+///     let grads = per_example_grad(&[xs])[0];
+///
+///     let xs_val = array![[1., 0.], [0., 1.], [1., 1.]];
+///     let grads = ctx.evaluator().push(&grads).feed(xs, xs_val.view().into_dyn()).run();
+///     let expected = array![[[2.], [0.]], [[0.], [4.]], [[6.], [6.]]];
+///     assert_eq!(grads[0], Ok(expected.into_dyn()));
+/// });
+///    ```
+pub fn vmap<'graph, F, Fun>(f: Fun) -> impl Fn(&[Tensor<'graph, F>]) -> Vec<Tensor<'graph, F>>
+where
+    F: Float,
+    Fun: Fn(&[Tensor<'graph, F>]) -> Vec<Tensor<'graph, F>>,
+{
+    move |xs| crate::vmap::vmap(&f, xs, &vec![Some(0); xs.len()])
+}
+
+/// Same as [vmap], mapping over the axis `in_axes[i]` of the `i`-th input.
+///
+/// The inputs with `None` are not mapped: they are the same for all the examples.
+pub fn vmap_with_axes<'graph, F, Fun>(
+    f: Fun,
+    in_axes: &[Option<usize>],
+) -> impl Fn(&[Tensor<'graph, F>]) -> Vec<Tensor<'graph, F>>
+where
+    F: Float,
+    Fun: Fn(&[Tensor<'graph, F>]) -> Vec<Tensor<'graph, F>>,
+{
+    let in_axes = in_axes.to_vec();
+    move |xs| crate::vmap::vmap(&f, xs, &in_axes)
+}
+
 /// Computes jacobians for variables.
 ///
 /// # Arguments
@@ -295,6 +351,175 @@ where
     ret
 }
 
+/// Computes Hessian-vector products of `loss` for `params`.
+///
+/// Returns `H v` split in the same way as `params`, where `H` is the Hessian of
+/// `loss` with respect to all `params` and `v` is given as one tensor per param.
+///
+/// * `loss` - Target of differentiation; non-scalars are summed up first.
+/// * `params` - Tensors with which differentiate `loss`.
+/// * `v` - Tensors of the same shapes as `params`.
+///
+/// The Hessian is never formed: the gradient graph is differentiated once more
+/// (`d/dx <grad(loss), v>`), so the cost is about twice that of [grad()].
+///
+///    ```
+/// use ndarray::array;
+/// use scirs2_autograd as ag;
+/// use ag::tensor_ops as T;
+///
+/// ag::run(|ctx| {
+///     let x = ctx.placeholder("x", &[2]);
+///     // loss = x0^2 * x1, H = [[2 x1, 2 x0], [2 x0, 0]]
+///     let loss = T::square(T::slice(x, &[0], &[1])) * T::slice(x, &[1], &[2]);
+///     let v = T::convert_to_tensor(array![1., 1.], ctx);
+///     let hv = T::hvp(loss, &[x], &[v])[0];
+///
+///     let result = ctx.evaluator()
+///         .push(&hv)
+///         .feed(x, array![3., 2.].view().into_dyn())
+///         .run();
+///     assert_eq!(result[0].as_ref().unwrap(), &array![10., 6.].into_dyn());
+/// });
+///    ```
+pub fn hvp<'graph, A, B, C, F: Float>(loss: A, params: &[B], v: &[C]) -> Vec<Tensor<'graph, F>>
+where
+    A: AsRef<Tensor<'graph, F>>,
+    B: AsRef<Tensor<'graph, F>>,
+    C: AsRef<Tensor<'graph, F>>,
+{
+    assert_eq!(
+        params.len(),
+        v.len(),
+        "hvp: one vector is needed for each param"
+    );
+    let grads = grad(&[loss], params);
+    let products: Vec<_> = grads
+        .iter()
+        .zip(v)
+        .map(|(g, v)| sum_all(*g * *v.as_ref()))
+        .collect();
+    grad(&[add_n(&products)], params)
+}
+
+/// Computes Jacobian-vector products of `ys` for `xs` in forward mode.
+///
+/// Returns `J t` for each of `ys`, where `J` is the Jacobian of that tensor
+/// with respect to all `xs` and `t` is given as one tangent per x, with the
+/// shape of that x. The tangents are propagated from `xs` to `ys` along with
+/// the values, op by op (see [Op::jvp](crate::op::Op::jvp)), so no gradient
+/// graph of `ys` is built and the cost is about that of computing `ys`.
+///
+///    ```
+/// use ndarray::array;
+/// use scirs2_autograd as ag;
+/// use ag::tensor_ops as T;
+///
+/// ag::run(|ctx| {
+///     let x = ctx.placeholder("x", &[2]);
+///     // y = [x0 x1, sin(x0)], J = [[x1, x0], [cos(x0), 0]]
+///     let x0 = T::slice(x, &[0], &[1]);
+///     let y = T::concat(&[x0 * T::slice(x, &[1], &[2]), T::sin(x0)], 0);
+///     let t = T::convert_to_tensor(array![1., 2.], ctx);
+///     let jt = T::jvp(&[y], &[x], &[t])[0];
+///
+///     let result = ctx.evaluator()
+///         .push(&jt)
+///         .feed(x, array![0., 3.].view().into_dyn())
+///         .run();
+///     assert_eq!(result[0].as_ref().unwrap(), &array![3., 1.].into_dyn());
+/// });
+///    ```
+pub fn jvp<'graph, A, B, C, F: Float>(ys: &[A], xs: &[B], tangents: &[C]) -> Vec<Tensor<'graph, F>>
+where
+    A: AsRef<Tensor<'graph, F>>,
+    B: AsRef<Tensor<'graph, F>>,
+    C: AsRef<Tensor<'graph, F>>,
+{
+    let ys: Vec<_> = ys.iter().map(|y| *y.as_ref()).collect();
+    let xs: Vec<_> = xs.iter().map(|x| *x.as_ref()).collect();
+    let tangents: Vec<_> = tangents.iter().map(|t| *t.as_ref()).collect();
+    crate::forward::jvp(&ys, &xs, &tangents)
+}
+
+/// Computes the Jacobian of `y` with respect to `x` in forward mode.
+///
+/// Returns a matrix of shape `(y size, x size)`, built column by column with
+/// a [jvp] for each basis vector of `x`, all batched with [vmap]. Cheaper than
+/// [jacrev] when `x` is smaller than `y`.
+pub fn jacfwd<'graph, A, B, F: Float>(y: A, x: B) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>>,
+    B: AsRef<Tensor<'graph, F>>,
+{
+    let (y, x) = (*y.as_ref(), *x.as_ref());
+    let columns =
+        vmap(|e| vec![flatten(jvp(&[y], &[x], &[e[0]])[0])])(&[crate::forward::basis(&x)]);
+    transpose(columns[0], &[1, 0])
+}
+
+/// Computes the Jacobian of `y` with respect to `x` in reverse mode.
+///
+/// Returns a matrix of shape `(y size, x size)`, built row by row with a
+/// gradient for each basis vector of `y`, all batched with [vmap]. Cheaper
+/// than [jacfwd] when `y` is smaller than `x`.
+pub fn jacrev<'graph, A, B, F: Float>(y: A, x: B) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>>,
+    B: AsRef<Tensor<'graph, F>>,
+{
+    let (y, x) = (*y.as_ref(), *x.as_ref());
+    let rows =
+        vmap(|e| vec![flatten(grad(&[sum_all(y * e[0])], &[x])[0])])(&[crate::forward::basis(&y)]);
+    rows[0]
+}
+
+/// Computes the Jacobian of `y` with respect to `x`, a matrix of shape
+/// `(y size, x size)`.
+///
+/// Uses [jacfwd] when both sizes are known from the shapes of the tensors
+/// (e.g. placeholders with fixed shapes) and `x` is the smaller one, and
+/// [jacrev] otherwise.
+///
+///    ```
+/// use ndarray::array;
+/// use scirs2_autograd as ag;
+/// use ag::tensor_ops as T;
+///
+/// ag::run(|ctx| {
+///     let x = ctx.placeholder("x", &[2]);
+///     let a = T::convert_to_tensor(array![[1., 2.], [3., 4.], [5., 6.]], ctx);
+///     let y = T::matmul(a, T::reshape(x, &[2, 1]));
+///     let j = T::jacobian(y, x);
+///
+///     let result = ctx.evaluator()
+///         .push(&j)
+///         .feed(x, array![1., 1.].view().into_dyn())
+///         .run();
+///     assert_eq!(result[0].as_ref().unwrap(), &array![[1., 2.], [3., 4.], [5., 6.]].into_dyn());
+/// });
+///    ```
+pub fn jacobian<'graph, A, B, F: Float>(y: A, x: B) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>>,
+    B: AsRef<Tensor<'graph, F>>,
+{
+    let (y, x) = (*y.as_ref(), *x.as_ref());
+    let known_size = |t: &Tensor<F>| -> Option<usize> {
+        let inner = t.inner();
+        let shape = inner.known_shape.as_ref()?;
+        shape
+            .get()
+            .iter()
+            .map(|&d| usize::try_from(d).ok())
+            .product()
+    };
+    match (known_size(&y), known_size(&x)) {
+        (Some(m), Some(n)) if n < m => jacfwd(y, x),
+        _ => jacrev(y, x),
+    }
+}
+
 /// (Experimental) Computes hessian vector product
 pub fn _hessian_vector_product<'graph, A, B, C, F: Float>(
     ys: &[A],
@@ -331,6 +556,31 @@ where
         .build(gradient_ops::StopGradient)
 }
 
+/// Transfers `x` to `device`.
+///
+/// The tensors computed from the result are placed on `device` too.
+/// See [device](crate::device).
+pub fn to_device<'graph, A, F: Float>(x: A, device: Device) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+{
+    let x = x.as_ref();
+    let g = x.graph();
+    Tensor::builder(g)
+        .append_input(x, false)
+        .set_shape(&shape(x))
+        .set_device(device)
+        .build(device_ops::ToDevice { device })
+}
+
+/// Transfers `x` to the host, i.e. [to_device] with [Device::Cpu].
+pub fn to_host<'graph, A, F: Float>(x: A) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+{
+    to_device(x, Device::Cpu)
+}
+
 /// Returns a `Tensor` representation of the input tensor's shape
 ///
 ///    ```
@@ -713,6 +963,10 @@ where
 ///
 /// http://arxiv.org/abs/1207.0580
 ///
+/// If `train` is true, each element of `x` is kept with probability `1 - dropout_ratio`
+/// and zeroed otherwise, and the gradient is masked likewise.
+/// Otherwise `x` is scaled by `1 - dropout_ratio`.
+///
 /// `XorShiftRng` is used internally.
 /// If you need to specify a seed value or use any other `Rng`, use `dropout_rng` instead.
 pub fn dropout<'graph, A, F: Float>(x: A, dropout_ratio: F, train: bool) -> Tensor<'graph, F>
@@ -1132,6 +1386,140 @@ where
         .build(random_ops::LogNormal::new(arr_rng, mean, stddev))
 }
 
+/// Samples from the normal distribution of tensors `mean` and `stddev`, differentiably.
+///
+/// The sample is `mean + stddev * eps` with `eps` drawn from the standard normal
+/// distribution (the reparameterization trick), so gradients flow into `mean` and
+/// `stddev` through the sample, e.g. for the encoder of a VAE.
+/// Samples take the shape of `mean`, to which `stddev` is broadcast.
+///
+/// `eps` is fixed at graph construction by `arr_rng`: evaluating the sample again
+/// gives the same values. See [sample_normal_rng] to specify the seed.
+///
+///    ```
+/// use scirs2_autograd as ag;
+/// use ag::tensor_ops as T;
+///
+/// ag::run(|g| {
+///     let mean = T::variable(ag::ndarray::arr1(&[0., 1.]), g);
+///     let log_var = T::variable(ag::ndarray::arr1(&[0., -2.]), g);
+///     let z = T::sample_normal(mean, T::exp(log_var * 0.5));
+///     let grads = T::grad(&[T::sum_all(z * z)], &[mean, log_var]);
+///     assert_eq!(grads[1].eval(g).unwrap().shape(), &[2]);
+/// });
+///    ```
+pub fn sample_normal<'graph, A, B, F: Float>(mean: A, stddev: B) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+    B: AsRef<Tensor<'graph, F>> + Copy,
+{
+    sample_normal_rng(Default::default(), mean, stddev)
+}
+
+/// Samples from the normal distribution of tensors `mean` and `stddev`, differentiably.
+///
+/// Pre-instantiated [ArrayRng](ndarray_ext/array_gen/struct.ArrayRng.html) is acceptable.
+/// See [sample_normal].
+pub fn sample_normal_rng<'graph, A, B, F: Float>(
+    arr_rng: ArrayRng<F>,
+    mean: A,
+    stddev: B,
+) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+    B: AsRef<Tensor<'graph, F>> + Copy,
+{
+    let mean = mean.as_ref();
+    let eps = standard_normal_rng(arr_rng, &shape(mean), mean.graph());
+    mean + stddev.as_ref() * eps
+}
+
+/// Samples from the uniform distribution between tensors `min` and `max`, differentiably.
+///
+/// The sample is `min + (max - min) * u` with `u` drawn from the standard uniform
+/// distribution (the reparameterization trick), so gradients flow into `min` and `max`.
+/// Samples take the shape of `min`, to which `max` is broadcast.
+///
+/// `u` is fixed at graph construction by `arr_rng`, see [sample_normal].
+pub fn sample_uniform<'graph, A, B, F: Float>(min: A, max: B) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+    B: AsRef<Tensor<'graph, F>> + Copy,
+{
+    sample_uniform_rng(Default::default(), min, max)
+}
+
+/// Samples from the uniform distribution between tensors `min` and `max`, differentiably.
+///
+/// Pre-instantiated [ArrayRng](ndarray_ext/array_gen/struct.ArrayRng.html) is acceptable.
+/// See [sample_uniform].
+pub fn sample_uniform_rng<'graph, A, B, F: Float>(
+    arr_rng: ArrayRng<F>,
+    min: A,
+    max: B,
+) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+    B: AsRef<Tensor<'graph, F>> + Copy,
+{
+    let min = min.as_ref();
+    let u = standard_uniform_rng(arr_rng, &shape(min), min.graph());
+    min + (max.as_ref() - min) * u
+}
+
+/// Gumbel-softmax: differentiable samples of the categorical distribution of `logits`
+///
+/// https://arxiv.org/abs/1611.01144
+///
+/// Returns `softmax((logits + gumbel noise) / temperature)` along `axis`, which tends to
+/// one-hot samples as `temperature` goes to 0.
+/// If `hard` is true, the one-hot samples are returned instead, with the gradient of the
+/// soft ones (the straight-through estimator).
+///
+/// The noise is fixed at graph construction, see [gumbel_softmax_rng] to specify the seed.
+pub fn gumbel_softmax<'graph, A, F: Float>(
+    logits: A,
+    temperature: F,
+    axis: isize,
+    hard: bool,
+) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+{
+    gumbel_softmax_rng(Default::default(), logits, temperature, axis, hard)
+}
+
+/// Gumbel-softmax: differentiable samples of the categorical distribution of `logits`
+///
+/// Pre-instantiated [ArrayRng](ndarray_ext/array_gen/struct.ArrayRng.html) is acceptable.
+/// See [gumbel_softmax].
+pub fn gumbel_softmax_rng<'graph, A, F: Float>(
+    arr_rng: ArrayRng<F>,
+    logits: A,
+    temperature: F,
+    axis: isize,
+    hard: bool,
+) -> Tensor<'graph, F>
+where
+    A: AsRef<Tensor<'graph, F>> + Copy,
+{
+    let logits = logits.as_ref();
+    // Kept away from 0 and 1, where the noise -ln(-ln(u)) is infinite
+    let u = random_uniform_rng(arr_rng, &shape(logits), 1e-10, 1. - 1e-7, logits.graph());
+    let gumbel = neg(ln(neg(ln(u))));
+    let y = softmax((logits + gumbel) / temperature, axis);
+    if hard {
+        // 1 where `y` is the maximum along `axis`
+        let y_hard = equal(
+            y - reduce_max(y, &[axis], true),
+            zeros(&shape(y), y.graph()),
+        );
+        stop_gradient(y_hard - y) + y
+    } else {
+        y
+    }
+}
+
 /// Returns zeros with given shape.
 ///
 ///    ```
@@ -1329,6 +1717,40 @@ impl<'g, F: Float> Tensor<'g, F> {
     }
 }
 
+/// Registers the serializable built-in ops with `registry`.
+pub(crate) fn register_ops<F: Float>(registry: &mut crate::serialization::OpRegistry<F>) {
+    macro_rules! register {
+        ($($module:ident::{$($op:ident $(<$t:ident>)?),* $(,)?})*) => {
+            $($(registry.register::<$module::$op $(<$t>)?>(stringify!($op));)*)*
+        };
+    }
+    register! {
+        activation_ops::{
+            Elu<F>, Identity, ReLU, Sigmoid, Softplus, Softmax, Swish, Gelu, Mish, PReLU<F>,
+        }
+        advanced_tensor_ops::{EinsumOp}
+        array_ops::{
+            ExpandDims, Squeeze, Split, Tile, Concat, Clip<F>, AddN, Gather, IndexOp, Shape,
+            Rank, Size, Reshape, InferBinOpShape,
+        }
+        binary_ops::{AddOp, SubOp, MulOp, DivOp, MaybeReduceSum, MaybeBroadcast}
+        const_gen_ops::{Zeros, Ones, ConvertToTensor<F>, Scalar<F>}
+        device_ops::{ToDevice}
+        dot_ops::{MatMul, BatchMatMul, TensordotPreprocess}
+        math_ops::{
+            Sin, Cos, Tan, Asin, Acos, Atan, Sinh, Cosh, Tanh, Asinh, Acosh, Atanh, Exp, Exp2,
+            Exp10, Sqrt, NegOp, Floor, Ceil, Sign, Inv, InvSqrt, Square, Abs, Log2, Log10, Ln,
+            Pow<F>, LogSumExp, Transpose,
+        }
+        reduction_ops::{
+            ReduceMin, ReduceMax, ReduceProd, ReduceSumToScalar, ReduceSum, ReduceMean, ArgMax,
+            ArgMin, ReduceVariance, ReduceSumAll, ReduceMeanAll, ReduceAll, ReduceAny,
+        }
+        scalar_ops::{ScalarMulOp<F>}
+        xent_ops::{SoftmaxCrossEntropy, SparseSoftmaxCrossEntropy, SigmoidCrossEntropy, LogSoftmax}
+    }
+}
+
 // ===============================================
 // Backward Compatibility Re-exports
 // ===============================================
@@ -1413,7 +1835,7 @@ pub use symmetric_ops::{eigh, eigvalsh};
 pub use special_decompositions::{polar, schur};
 
 // Advanced tensor operations
-pub use advanced_tensor_ops::{einsum, kron as kron_tensor, tensor_solve};
+pub use advanced_tensor_ops::{einsum, einsum_path, kron as kron_tensor, tensor_solve};
 
 // Matrix exponential algorithms
 pub use matrix_ops::{expm2, expm3};
@@ -1435,7 +1857,7 @@ pub use advanced_tensor_ops::kron as kronecker_product;
 // Memory optimization functions
 pub use checkpoint_ops::{
     adaptive_checkpoint, checkpoint, checkpoint_segment, checkpoint_segment_flex, detach,
-    CheckpointGroup, CheckpointProfiler,
+    CheckpointGroup, CheckpointPlan, CheckpointProfiler,
 };
 
 // Advanced indexing operations
@@ -1,8 +1,9 @@
 use crate::op::{ComputeContext, GradientContext, Op, OpError};
 use crate::tensor::Tensor;
 use crate::tensor_ops;
+use crate::tensor_ops::decomposition_ops::{compute_svd, svd};
 use crate::Float;
-use ndarray::{Array1, Array2, ArrayView2, Ix2};
+use ndarray::Ix2;
 
 /// Frobenius norm operation with improved gradient computation
 pub struct FrobeniusNormOp;
@@ -71,484 +72,68 @@ impl<F: Float> Op<F> for FrobeniusNormOp {
     }
 }
 
-/// Spectral norm operation with proper gradient computation
+/// Spectral norm operation (largest singular value)
+///
+/// The gradient is `u_1 v_1^T` for the leading singular vectors `u_1` and `v_1`.
 pub struct SpectralNormOp;
 
-impl<F: Float + ndarray::ScalarOperand> Op<F> for SpectralNormOp {
+impl<F: Float> Op<F> for SpectralNormOp {
     fn name(&self) -> &'static str {
         "SpectralNorm"
     }
 
     fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
         let input = ctx.input(0);
-        let shape = input.shape();
-
-        if shape.len() != 2 {
-            return Err(OpError::IncompatibleShape(
-                "Spectral norm requires 2D matrix".into(),
-            ));
-        }
-
-        // Convert input to 2D matrix
         let matrix = input
             .view()
             .into_dimensionality::<Ix2>()
-            .map_err(|_| OpError::IncompatibleShape("Failed to convert to 2D array".into()))?;
-
-        // Use power iteration to find the largest singular value
-        let (_, sigma_max) = power_iteration_spectral(&matrix, 50, F::from(1e-8).unwrap());
+            .map_err(|_| OpError::IncompatibleShape("Spectral norm requires 2D matrix".into()))?;
 
+        let (_, s, _) = compute_svd(&matrix);
+        let sigma_max = s.iter().next().copied().unwrap_or_else(F::zero);
         ctx.append_output(ndarray::arr0(sigma_max).into_dyn());
         Ok(())
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        let grad_output = ctx.output_grad();
-        let input = ctx.input(0);
-        let g = ctx.graph();
-
-        // Evaluate the input to work with concrete values for SVD computation
-        let input_array = match input.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let grad_output_array = match grad_output.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let grad_scalar = grad_output_array[[]];
-
-        if let Ok(matrix) = input_array.view().into_dimensionality::<Ix2>() {
-            let grad_matrix = compute_spectral_norm_gradient(&matrix, grad_scalar);
-            let grad_tensor = tensor_ops::convert_to_tensor(grad_matrix.into_dyn(), g);
-            ctx.append_input_grad(0, Some(grad_tensor));
-            return;
-        }
-
-        // Fallback
-        ctx.append_input_grad(0, None);
+        let gy = ctx.output_grad();
+        let (u, _, v) = svd(ctx.input(0));
+        let u1 = tensor_ops::slice(u, &[0, 0], &[-1, 1]);
+        let v1 = tensor_ops::slice(v, &[0, 0], &[-1, 1]);
+        let gx = tensor_ops::matmul(u1, tensor_ops::transpose(v1, &[1, 0])) * gy;
+        ctx.append_input_grad(0, Some(gx));
     }
 }
 
-/// Nuclear norm operation with proper gradient computation
+/// Nuclear norm operation (sum of singular values)
+///
+/// The gradient is `U V^T` of the thin SVD, which is well defined for
+/// repeated singular values as long as the matrix has full rank.
 pub struct NuclearNormOp;
 
-impl<F: Float + ndarray::ScalarOperand> Op<F> for NuclearNormOp {
+impl<F: Float> Op<F> for NuclearNormOp {
     fn name(&self) -> &'static str {
         "NuclearNorm"
     }
 
     fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
         let input = ctx.input(0);
-        let shape = input.shape();
-
-        if shape.len() != 2 {
-            return Err(OpError::IncompatibleShape(
-                "Nuclear norm requires 2D matrix".into(),
-            ));
-        }
-
-        // Convert input to 2D matrix
         let matrix = input
             .view()
             .into_dimensionality::<Ix2>()
-            .map_err(|_| OpError::IncompatibleShape("Failed to convert to 2D array".into()))?;
+            .map_err(|_| OpError::IncompatibleShape("Nuclear norm requires 2D matrix".into()))?;
 
-        // Check for diagonal matrix special case
-        if is_diagonal_matrix(&matrix) {
-            let nuclear_norm = compute_diagonal_nuclear_norm(&matrix);
-            ctx.append_output(ndarray::arr0(nuclear_norm).into_dyn());
-            return Ok(());
-        }
-
-        // For general matrices, compute nuclear norm as sum of singular values
-        let nuclear_norm = compute_nuclear_norm_improved(&matrix);
-
-        ctx.append_output(ndarray::arr0(nuclear_norm).into_dyn());
+        let (_, s, _) = compute_svd(&matrix);
+        ctx.append_output(ndarray::arr0(s.sum()).into_dyn());
         Ok(())
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        let grad_output = ctx.output_grad();
-        let input = ctx.input(0);
-        let g = ctx.graph();
-
-        // Evaluate inputs to work with concrete values
-        let input_array = match input.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let grad_output_array = match grad_output.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let grad_scalar = grad_output_array[[]];
-
-        if let Ok(matrix) = input_array.view().into_dimensionality::<Ix2>() {
-            let grad_matrix = compute_nuclear_norm_gradient_improved(&matrix, grad_scalar);
-            let grad_tensor = tensor_ops::convert_to_tensor(grad_matrix.into_dyn(), g);
-            ctx.append_input_grad(0, Some(grad_tensor));
-            return;
-        }
-
-        // Fallback
-        ctx.append_input_grad(0, None);
-    }
-}
-
-// Helper functions
-
-/// Check if matrix is diagonal
-fn is_diagonal_matrix<F: Float>(matrix: &ArrayView2<F>) -> bool {
-    let (m, n) = matrix.dim();
-    for i in 0..m {
-        for j in 0..n {
-            if i != j && matrix[[i, j]] != F::zero() {
-                return false;
-            }
-        }
-    }
-    true
-}
-
-/// Compute nuclear norm for diagonal matrix
-fn compute_diagonal_nuclear_norm<F: Float>(matrix: &ArrayView2<F>) -> F {
-    let (m, n) = matrix.dim();
-    let mut sum = F::zero();
-    let min_dim = m.min(n);
-
-    for i in 0..min_dim {
-        sum += matrix[[i, i]].abs();
+        let gy = ctx.output_grad();
+        let (u, _, v) = svd(ctx.input(0));
+        let gx = tensor_ops::matmul(u, tensor_ops::transpose(v, &[1, 0])) * gy;
+        ctx.append_input_grad(0, Some(gx));
     }
-
-    sum
-}
-
-#[allow(dead_code)]
-/// Compute nuclear norm using power iteration approximation
-fn compute_nuclear_norm_approximation<F: Float + ndarray::ScalarOperand>(
-    matrix: &ArrayView2<F>,
-) -> F {
-    let (m, n) = matrix.dim();
-    let min_dim = m.min(n);
-
-    // For small matrices, use a simple approximation
-    if min_dim <= 3 {
-        // Fall back to Frobenius norm for small matrices
-        let mut sum_squared = F::zero();
-        for &elem in matrix.iter() {
-            sum_squared += elem * elem;
-        }
-        return sum_squared.sqrt();
-    }
-
-    // For larger matrices, estimate a few singular values
-    let max_rank = (min_dim as f64 * 0.5).ceil() as usize;
-    let mut working_matrix = matrix.to_owned();
-    let mut nuclear_norm = F::zero();
-
-    for _ in 0..max_rank {
-        let (_, sigma) =
-            power_iteration_spectral(&working_matrix.view(), 10, F::from(1e-6).unwrap());
-
-        if sigma < F::epsilon() * F::from(10.0).unwrap() {
-            break;
-        }
-
-        nuclear_norm += sigma;
-
-        // Simple deflation: subtract a rank-1 approximation
-        let (u, _) = power_iteration_spectral(&working_matrix.view(), 5, F::from(1e-6).unwrap());
-        let at = working_matrix.t();
-        let v = at.dot(&u) / sigma;
-
-        // Deflate: A = A - sigma * u * v^T
-        for i in 0..m {
-            for j in 0..n {
-                working_matrix[[i, j]] -= sigma * u[i] * v[j];
-            }
-        }
-    }
-
-    nuclear_norm
-}
-
-/// Power iteration for spectral norm
-fn power_iteration_spectral<F: Float + ndarray::ScalarOperand>(
-    matrix: &ArrayView2<F>,
-    max_iter: usize,
-    tol: F,
-) -> (Array1<F>, F) {
-    let (m, _n) = matrix.dim();
-
-    // Initialize with normalized vector
-    let mut u = Array1::<F>::zeros(m);
-    u[0] = F::one();
-
-    // Add some perturbation to avoid getting stuck
-    for i in 1..m {
-        u[i] = F::from(0.01).unwrap() * F::from(i as f64).unwrap();
-    }
-
-    // Normalize
-    let norm = u.iter().fold(F::zero(), |acc, &x| acc + x * x).sqrt();
-    if norm > F::epsilon() {
-        u.mapv_inplace(|x| x / norm);
-    }
-
-    let mut prev_sigma = F::zero();
-
-    for _iter in 0..max_iter {
-        // A * u
-        let au = matrix.dot(&u);
-
-        // A^T * (A * u)
-        let atau = matrix.t().dot(&au);
-
-        // Compute norm (approximate eigenvalue of A^T * A)
-        let sigma = atau.iter().fold(F::zero(), |acc, &x| acc + x * x).sqrt();
-
-        // Check convergence
-        if (sigma - prev_sigma).abs() < tol {
-            // Final computation of actual singular value
-            let au_final = matrix.dot(&u);
-            let sigma_final = au_final
-                .iter()
-                .fold(F::zero(), |acc, &x| acc + x * x)
-                .sqrt();
-            return (u, sigma_final);
-        }
-
-        prev_sigma = sigma;
-
-        // Normalize for next iteration
-        let norm = atau.iter().fold(F::zero(), |acc, &x| acc + x * x).sqrt();
-        if norm > F::epsilon() {
-            u = atau.mapv(|x| x / norm);
-        }
-    }
-
-    // Final estimate
-    let au = matrix.dot(&u);
-    let sigma = au.iter().fold(F::zero(), |acc, &x| acc + x * x).sqrt();
-    (u, sigma)
-}
-
-/// Compute gradient for spectral norm
-fn compute_spectral_norm_gradient<F: Float + ndarray::ScalarOperand>(
-    matrix: &ArrayView2<F>,
-    grad_scalar: F,
-) -> Array2<F> {
-    let (m, n) = matrix.dim();
-
-    // Special handling for diagonal matrices
-    if is_diagonal_matrix(matrix) {
-        let mut grad_matrix = Array2::zeros((m, n));
-        let min_dim = m.min(n);
-
-        // Find the largest diagonal element
-        let mut max_idx = 0;
-        let mut max_val = F::zero();
-        for i in 0..min_dim {
-            let abs_val = matrix[[i, i]].abs();
-            if abs_val > max_val {
-                max_val = abs_val;
-                max_idx = i;
-            }
-        }
-
-        // Gradient is 1 at the position of the largest singular value
-        grad_matrix[[max_idx, max_idx]] = grad_scalar;
-
-        return grad_matrix;
-    }
-
-    // For general matrices, recompute the singular vectors
-    let (u, sigma) = power_iteration_spectral(matrix, 20, F::from(1e-6).unwrap());
-
-    // Compute v = A^T * u / sigma
-    let v = if sigma > F::epsilon() {
-        matrix.t().dot(&u) / sigma
-    } else {
-        Array1::zeros(n)
-    };
-
-    // Create outer product u * v^T
-    let mut grad_matrix = Array2::zeros((m, n));
-    for i in 0..m {
-        for j in 0..n {
-            grad_matrix[[i, j]] = u[i] * v[j] * grad_scalar;
-        }
-    }
-
-    grad_matrix
-}
-
-#[allow(dead_code)]
-/// Compute gradient for nuclear norm
-fn compute_nuclear_norm_gradient<F: Float + ndarray::ScalarOperand>(
-    matrix: &ArrayView2<F>,
-    grad_scalar: F,
-) -> Array2<F> {
-    let (m, n) = matrix.dim();
-
-    // Handle diagonal matrix case
-    if is_diagonal_matrix(matrix) {
-        let mut grad_matrix = Array2::zeros((m, n));
-        let min_dim = m.min(n);
-
-        // Gradient is sign of diagonal elements
-        for i in 0..min_dim {
-            let diag_val = matrix[[i, i]];
-            grad_matrix[[i, i]] = if diag_val > F::zero() {
-                grad_scalar
-            } else if diag_val < F::zero() {
-                -grad_scalar
-            } else {
-                F::zero()
-            };
-        }
-
-        return grad_matrix;
-    }
-
-    // For general matrices, use identity matrix as approximation
-    // This is not mathematically accurate but provides a reasonable gradient
-    let mut grad_matrix = Array2::zeros((m, n));
-    let min_dim = m.min(n);
-    for i in 0..min_dim {
-        grad_matrix[[i, i]] = grad_scalar;
-    }
-
-    grad_matrix
-}
-
-/// Improved nuclear norm computation using better SVD approximation
-fn compute_nuclear_norm_improved<F: Float + ndarray::ScalarOperand>(matrix: &ArrayView2<F>) -> F {
-    let (m, n) = matrix.dim();
-    let min_dim = m.min(n);
-
-    // For small matrices, use a simple approximation
-    if min_dim <= 2 {
-        // Sum of absolute values of diagonal elements as approximation
-        let mut nuclear_norm = F::zero();
-        for i in 0..min_dim {
-            nuclear_norm += matrix[[i, i]].abs();
-        }
-        return nuclear_norm;
-    }
-
-    // For larger matrices, use power iteration to estimate singular values
-    let mut working_matrix = matrix.to_owned();
-    let mut nuclear_norm = F::zero();
-    let max_rank = min_dim.min(5); // Limit iterations for performance
-
-    for _ in 0..max_rank {
-        let (u, sigma) =
-            power_iteration_spectral(&working_matrix.view(), 20, F::from(1e-6).unwrap());
-
-        if sigma < F::epsilon() * F::from(10.0).unwrap() {
-            break;
-        }
-
-        nuclear_norm += sigma;
-
-        // Simple deflation: subtract a rank-1 approximation
-        let at = working_matrix.t();
-        let v = at.dot(&u) / sigma;
-
-        // Deflate: A = A - sigma * u * v^T
-        for i in 0..m {
-            for j in 0..n {
-                working_matrix[[i, j]] -= sigma * u[i] * v[j];
-            }
-        }
-    }
-
-    nuclear_norm
-}
-
-/// Improved nuclear norm gradient computation
-fn compute_nuclear_norm_gradient_improved<F: Float + ndarray::ScalarOperand>(
-    matrix: &ArrayView2<F>,
-    grad_scalar: F,
-) -> Array2<F> {
-    let (m, n) = matrix.dim();
-
-    // Handle diagonal matrix case
-    if is_diagonal_matrix(matrix) {
-        let mut grad_matrix = Array2::zeros((m, n));
-        let min_dim = m.min(n);
-
-        // Gradient is sign of diagonal elements
-        for i in 0..min_dim {
-            let diag_val = matrix[[i, i]];
-            grad_matrix[[i, i]] = if diag_val > F::zero() {
-                grad_scalar
-            } else if diag_val < F::zero() {
-                -grad_scalar
-            } else {
-                F::zero()
-            };
-        }
-
-        return grad_matrix;
-    }
-
-    // For general matrices, use approximate SVD-based gradient
-    // This is a simplified version that accumulates gradients from multiple singular vectors
-    let mut grad_matrix = Array2::zeros((m, n));
-    let mut working_matrix = matrix.to_owned();
-    let min_dim = m.min(n);
-    let max_rank = min_dim.min(3); // Limit for performance
-
-    for _ in 0..max_rank {
-        let (u, sigma) =
-            power_iteration_spectral(&working_matrix.view(), 10, F::from(1e-6).unwrap());
-
-        if sigma < F::epsilon() * F::from(10.0).unwrap() {
-            break;
-        }
-
-        // Compute v = A^T * u / sigma
-        let v = if sigma > F::epsilon() {
-            working_matrix.t().dot(&u) / sigma
-        } else {
-            Array1::zeros(n)
-        };
-
-        // Add contribution from this singular vector pair
-        for i in 0..m {
-            for j in 0..n {
-                grad_matrix[[i, j]] += u[i] * v[j] * grad_scalar;
-            }
-        }
-
-        // Simple deflation for next iteration
-        for i in 0..m {
-            for j in 0..n {
-                working_matrix[[i, j]] -= sigma * u[i] * v[j];
-            }
-        }
-    }
-
-    grad_matrix
 }
 
 // Public API functions
@@ -560,18 +145,14 @@ pub fn frobenius_norm<'g, F: Float>(matrix: &Tensor<'g, F>) -> Tensor<'g, F> {
         .build(FrobeniusNormOp)
 }
 
-pub fn spectral_norm<'g, F: Float + ndarray::ScalarOperand>(
-    matrix: &Tensor<'g, F>,
-) -> Tensor<'g, F> {
+pub fn spectral_norm<'g, F: Float>(matrix: &Tensor<'g, F>) -> Tensor<'g, F> {
     let g = matrix.graph();
     Tensor::builder(g)
         .append_input(matrix, false)
         .build(SpectralNormOp)
 }
 
-pub fn nuclear_norm<'g, F: Float + ndarray::ScalarOperand>(
-    matrix: &Tensor<'g, F>,
-) -> Tensor<'g, F> {
+pub fn nuclear_norm<'g, F: Float>(matrix: &Tensor<'g, F>) -> Tensor<'g, F> {
     let g = matrix.graph();
     Tensor::builder(g)
         .append_input(matrix, false)
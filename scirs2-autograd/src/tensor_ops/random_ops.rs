@@ -234,13 +234,7 @@ impl<F: Float> op::Op<F> for Dropout<F> {
         if self.train {
             let mask =
                 arr_rng.bernoulli(x.shape(), (F::one() - self.dropout_ratio).to_f64().unwrap());
-
-            // Calculate sum of mask elements
-            let sum = mask.fold(F::zero(), |acc, &val| acc + val);
-
-            // Create a new array with element-wise multiplication
-            let result = x.mapv(|v| v * sum / (F::from(mask.len()).unwrap()));
-            ctx.append_output(result);
+            ctx.append_output(&x * &mask);
             ctx.append_output(mask);
         } else {
             let coef = F::one() - self.dropout_ratio;
@@ -251,7 +245,12 @@ impl<F: Float> op::Op<F> for Dropout<F> {
 
     fn grad<'a>(&self, ctx: &mut crate::op::GradientContext<'a, 'a, F>) {
         let gy = ctx.output_grad();
-        let mask = nth_tensor(ctx.output(), 1);
-        ctx.append_input_grad(0, Some(gy * mask));
+        let gx = if self.train {
+            let mask = nth_tensor(ctx.output(), 1);
+            gy * mask
+        } else {
+            gy * (F::one() - self.dropout_ratio)
+        };
+        ctx.append_input_grad(0, Some(gx));
     }
 }
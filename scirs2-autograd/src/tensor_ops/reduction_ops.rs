@@ -1,3 +1,4 @@
+use crate::mixed_precision::{cast, is_half};
 use crate::ndarray_ext;
 use crate::ndarray_ext::{NdArray, NdArrayView};
 use crate::op;
@@ -6,59 +7,73 @@ use crate::tensor_ops;
 use crate::tensor_ops::*;
 use crate::Float;
 use ndarray;
+use serde::{Deserialize, Serialize};
 use std::f32;
 use std::mem;
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceMin {
     pub keep_dims: bool,
     pub sparse_axes: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceMax {
     pub keep_dims: bool,
     pub sparse_axes: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceProd {
     pub keep_dims: bool,
     pub sparse_axes: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceSumToScalar;
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceSum {
     pub keep_dims: bool,
     pub sparse_axes: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceMean {
     pub keep_dims: bool,
     pub sparse_axes: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ArgMax {
     pub axis: isize,
     pub keep_dim: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ArgMin {
     pub axis: isize,
     pub keep_dim: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceVariance {
     pub keep_dims: bool,
     pub sparse_axes: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceSumAll;
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceMeanAll;
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceAll {
     pub keep_dims: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ReduceAny {
     pub keep_dims: bool,
 }
@@ -119,11 +134,55 @@ macro_rules! impl_reduce_forward {
     };
 }
 
-impl_reduce_forward!(compute_reduce_sum, add, zero);
+impl_reduce_forward!(reduce_sum_impl, add, zero);
 impl_reduce_forward!(compute_reduce_min, min, max_value);
 impl_reduce_forward!(compute_reduce_max, max, min_value);
 impl_reduce_forward!(compute_reduce_prod, mul, one);
 
+/// Sum over `axes`, accumulated in f32 for half-precision types.
+fn compute_reduce_sum<T: Float>(
+    x: &NdArrayView<'_, T>,
+    axes: Vec<usize>,
+    keep_dims: bool,
+) -> NdArray<T> {
+    if is_half::<T>() {
+        cast(&reduce_sum_impl(&cast::<T, f32>(x).view(), axes, keep_dims).view())
+    } else {
+        reduce_sum_impl(x, axes, keep_dims)
+    }
+}
+
+/// Mean over `axes`, accumulated in f32 for half-precision types.
+fn compute_reduce_mean<T: Float>(
+    x: &NdArrayView<'_, T>,
+    axes: Vec<usize>,
+    keep_dims: bool,
+) -> NdArray<T> {
+    let reduction_len = axes
+        .iter()
+        .map(|&axis| x.shape()[axis] as f32)
+        .product::<f32>();
+    if is_half::<T>() {
+        let mut sum = reduce_sum_impl(&cast::<T, f32>(x).view(), axes, keep_dims);
+        sum.mapv_inplace(move |elem| elem / reduction_len);
+        cast(&sum.view())
+    } else {
+        let mut sum = reduce_sum_impl(x, axes, keep_dims);
+        let reduction_len_inv = T::one() / T::from(reduction_len).unwrap();
+        sum.mapv_inplace(move |elem| elem * reduction_len_inv);
+        sum
+    }
+}
+
+/// Sum of all the elements, accumulated in f32 for half-precision types.
+fn sum_all<T: Float>(x: &NdArrayView<'_, T>) -> T {
+    if is_half::<T>() {
+        T::from(x.iter().map(|a| a.to_f32().unwrap()).sum::<f32>()).unwrap()
+    } else {
+        x.sum()
+    }
+}
+
 #[inline]
 fn preprocess_axes<T: Float>(
     x: &NdArrayView<T>,
@@ -137,18 +196,40 @@ fn preprocess_axes<T: Float>(
     }
 }
 
+/// Batching rule of the reductions with inputs: x, axes
+fn batch_reduction<T: Float>(ctx: &mut op::BatchContext<T>, sparse_axes: bool) {
+    if ctx.is_batched(1) {
+        return;
+    }
+    let y = ctx.rebuild(&[*ctx.input(0), ctx.batched_axes(1, sparse_axes)]);
+    ctx.set_output(y, true);
+}
+
 impl<T: Float> op::Op<T> for ReduceSumToScalar {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceSumToScalar", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         // Debug information for empty arrays
         if x.is_empty() {
             ctx.append_output(ndarray::arr0(T::zero()).into_dyn());
         } else {
-            ctx.append_output(ndarray::arr0(x.sum()).into_dyn());
+            ctx.append_output(ndarray::arr0(sum_all(x)).into_dyn());
         }
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::sum(ctx, gpu, false)
+    }
+
     fn grad(&self, ctx: &mut crate::op::GradientContext<T>) {
         let gx = Tensor::builder(ctx.graph())
             .append_input(ctx.output_grad(), false)
@@ -156,6 +237,16 @@ impl<T: Float> op::Op<T> for ReduceSumToScalar {
             .build(ReduceSumToScalarGrad);
         ctx.append_input_grad(0, Some(gx))
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let x = crate::vmap::flatten_examples(ctx.input(0));
+        ctx.set_output(reduce_sum(x, &[1], false), true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 struct ReduceSumToScalarGrad;
@@ -178,9 +269,29 @@ impl<T: Float> op::Op<T> for ReduceSumToScalarGrad {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if ctx.is_batched(1) {
+            return;
+        }
+        let gx = Tensor::builder(ctx.graph())
+            .append_input(ctx.broadcastable_to_shape(ctx.input(0), 1), false)
+            .append_input(ctx.batched_shape(1), false)
+            .build(binary_ops::MaybeBroadcast);
+        ctx.set_output(gx, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceSum {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceSum", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), self.sparse_axes);
@@ -202,30 +313,30 @@ impl<T: Float> op::Op<T> for ReduceSum {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        batch_reduction(ctx, self.sparse_axes);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceMean {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceMean", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), self.sparse_axes);
-        let x_shape = x.shape();
         if axes.is_empty() {
             ctx.append_output(x.to_owned());
             return Ok(());
         }
-
-        // Make reduction_len
-        let mut reduction_len = 1.;
-        for &axis in axes.iter() {
-            reduction_len *= x_shape[axis] as f32;
-        }
-        // Do summation
-        let mut sum = compute_reduce_sum(x, axes, self.keep_dims);
-
-        // Do division
-        let reduction_len_inv = T::one() / T::from(reduction_len).unwrap();
-        sum.mapv_inplace(move |elem| elem * reduction_len_inv);
-        ctx.append_output(sum);
+        ctx.append_output(compute_reduce_mean(x, axes, self.keep_dims));
         Ok(())
     }
 
@@ -251,9 +362,22 @@ impl<T: Float> op::Op<T> for ReduceMean {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        batch_reduction(ctx, self.sparse_axes);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceProd {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceProd", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), self.sparse_axes);
@@ -280,9 +404,17 @@ impl<T: Float> op::Op<T> for ReduceProd {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        batch_reduction(ctx, self.sparse_axes);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceMin {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceMin", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), self.sparse_axes);
@@ -302,9 +434,17 @@ impl<T: Float> op::Op<T> for ReduceMin {
             ctx,
         );
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        batch_reduction(ctx, self.sparse_axes);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceMax {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceMax", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), self.sparse_axes);
@@ -324,6 +464,10 @@ impl<T: Float> op::Op<T> for ReduceMax {
             ctx,
         );
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        batch_reduction(ctx, self.sparse_axes);
+    }
 }
 
 fn min_max_grad<'a, 'g: 'a, T: Float>(
@@ -434,6 +578,10 @@ fn argx_helper<T: Float>(
 }
 
 impl<T: Float> op::Op<T> for ArgMin {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ArgMin", self)
+    }
+
     // cf. https://github.com/tensorflow/compiler/tf2xla/kernels/index_ops.cc
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
@@ -448,6 +596,10 @@ impl<T: Float> op::Op<T> for ArgMin {
 }
 
 impl<T: Float> op::Op<T> for ArgMax {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ArgMax", self)
+    }
+
     // cf. https://github.com/tensorflow/compiler/tf2xla/kernels/index_ops.cc
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
@@ -514,27 +666,44 @@ impl<T: Float> op::Op<T> for ReduceGradCommon {
         ctx.append_input_grad(1, None);
         ctx.append_input_grad(2, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        if ctx.is_batched(1) || ctx.is_batched(2) {
+            return;
+        }
+        let shape = ctx.batched_shape(1);
+        let gx = if self.should_make_broadcast_dims {
+            let axes = ctx.batched_axes(2, self.sparse_axes);
+            ctx.rebuild(&[*ctx.input(0), shape, axes])
+        } else {
+            let gy = ctx.broadcastable_to_shape(ctx.input(0), 1);
+            ctx.rebuild(&[gy, shape, *ctx.input(2)])
+        };
+        ctx.set_output(gx, true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceVariance {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceVariance", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), self.sparse_axes);
 
         // Compute mean first
-        let mean = compute_reduce_sum(x, axes.clone(), true);
-        let reduction_len = axes
-            .iter()
-            .map(|&axis| x.shape()[axis] as f32)
-            .product::<f32>();
-        let reduction_len_inv = T::from(1.0 / reduction_len).unwrap();
-        let mean = mean.mapv(|elem| elem * reduction_len_inv);
+        let mean = compute_reduce_mean(x, axes.clone(), true);
 
         // Compute variance: mean((x - mean)^2)
         let diff = x - &mean;
         let diff_squared = diff.mapv(|elem| elem * elem);
-        let variance = compute_reduce_sum(&diff_squared.view(), axes, self.keep_dims);
-        let variance = variance.mapv(|elem| elem * reduction_len_inv);
+        let variance = compute_reduce_mean(&diff_squared.view(), axes, self.keep_dims);
 
         ctx.append_output(variance);
         Ok(())
@@ -554,15 +723,32 @@ impl<T: Float> op::Op<T> for ReduceVariance {
         ctx.append_input_grad(0, Some(gx));
         ctx.append_input_grad(1, None);
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        batch_reduction(ctx, self.sparse_axes);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceSumAll {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceSumAll", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
-        ctx.append_output(ndarray::arr0(x.sum()).into_dyn());
+        ctx.append_output(ndarray::arr0(sum_all(x)).into_dyn());
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::sum(ctx, gpu, false)
+    }
+
     fn grad(&self, ctx: &mut crate::op::GradientContext<T>) {
         let gx = Tensor::builder(ctx.graph())
             .append_input(ctx.output_grad(), false)
@@ -570,17 +756,44 @@ impl<T: Float> op::Op<T> for ReduceSumAll {
             .build(ReduceSumToScalarGrad);
         ctx.append_input_grad(0, Some(gx))
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let x = crate::vmap::flatten_examples(ctx.input(0));
+        ctx.set_output(reduce_sum(x, &[1], false), true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceMeanAll {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceMeanAll", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let len = x.len() as f32;
-        let mean = x.sum() / T::from(len).unwrap();
+        let mean = if is_half::<T>() {
+            T::from(x.iter().map(|a| a.to_f32().unwrap()).sum::<f32>() / len).unwrap()
+        } else {
+            x.sum() / T::from(len).unwrap()
+        };
         ctx.append_output(ndarray::arr0(mean).into_dyn());
         Ok(())
     }
 
+    #[cfg(feature = "gpu")]
+    fn compute_gpu(
+        &self,
+        ctx: &mut crate::op::ComputeContext<T>,
+        gpu: &crate::device::Gpu,
+    ) -> Result<bool, crate::op::OpError> {
+        crate::device::kernels::sum(ctx, gpu, true)
+    }
+
     fn grad(&self, ctx: &mut crate::op::GradientContext<T>) {
         let x = &ctx.input(0);
         // Use a simplified approach - create a constant scalar for division
@@ -593,9 +806,23 @@ impl<T: Float> op::Op<T> for ReduceMeanAll {
 
         ctx.append_input_grad(0, Some(gx))
     }
+
+    fn batch(&self, ctx: &mut op::BatchContext<T>) {
+        let x = crate::vmap::flatten_examples(ctx.input(0));
+        ctx.set_output(reduce_mean(x, &[1], false), true);
+    }
+
+    fn jvp(&self, ctx: &mut op::JvpContext<T>) {
+        let t = ctx.linear();
+        ctx.append_output_tangent(t);
+    }
 }
 
 impl<T: Float> op::Op<T> for ReduceAll {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceAll", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), false);
@@ -647,6 +874,10 @@ impl<T: Float> op::Op<T> for ReduceAll {
 }
 
 impl<T: Float> op::Op<T> for ReduceAny {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("ReduceAny", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let axes = preprocess_axes(x, &ctx.input(1), false);
@@ -1,13 +1,20 @@
-use crate::op::{ComputeContext, GradientContext, Op, OpError};
+use crate::op::{ComputeContext, GradientContext, Op, OpDef, OpError};
 use crate::tensor::Tensor;
 use crate::Float;
+use serde::{Deserialize, Serialize};
 
 /// Scalar multiplication operation
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct ScalarMulOp<F: Float> {
     pub scalar: F,
 }
 
 impl<F: Float> Op<F> for ScalarMulOp<F> {
+    fn op_def(&self) -> Option<OpDef> {
+        OpDef::new("ScalarMulOp", self)
+    }
+
     fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
         let input = ctx.input(0);
         let input_array = input.view();
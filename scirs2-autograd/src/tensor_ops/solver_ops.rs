@@ -1,9 +1,15 @@
 use crate::op::{ComputeContext, GradientContext, Op, OpError};
 use crate::tensor::Tensor;
+use crate::tensor_ops as T;
 use crate::Float;
 use ndarray::{Array1, Array2, Ix1, Ix2};
 
 /// Solve linear system Ax = b
+///
+/// The gradients are given by the implicit function theorem: with the
+/// solution `x` of the adjoint system `A^T gb = gx`, `gb` is the gradient of
+/// `b` and `-gb x^T` the one of `A`. Both are built from [solve] itself, so
+/// they are differentiable again.
 pub struct LinearSolveOp;
 
 impl<F: Float + ndarray::ScalarOperand> Op<F> for LinearSolveOp {
@@ -14,18 +20,13 @@ impl<F: Float + ndarray::ScalarOperand> Op<F> for LinearSolveOp {
         let a_shape = a.shape();
         let b_shape = b.shape();
 
-        println!(
-            "Solving linear system: A({:?}) * x = b({:?})",
-            a_shape, b_shape
-        );
-
         if a_shape.len() != 2 || a_shape[0] != a_shape[1] {
             return Err(OpError::IncompatibleShape(
                 "Linear solve requires square matrix A".into(),
             ));
         }
 
-        if b_shape[0] != a_shape[0] {
+        if b_shape.is_empty() || b_shape.len() > 2 || b_shape[0] != a_shape[0] {
             return Err(OpError::IncompatibleShape(
                 "Dimension mismatch in Ax = b".into(),
             ));
@@ -41,227 +42,63 @@ impl<F: Float + ndarray::ScalarOperand> Op<F> for LinearSolveOp {
                 .view()
                 .into_dimensionality::<Ix1>()
                 .map_err(|_| OpError::IncompatibleShape("Failed to convert b to 1D".into()))?;
-
-            println!("Solving 1D system (vector b)");
-            let x_result = solve_linear_system_1d(&a_2d, &b_1d)?;
-            println!("Solution x shape: {:?}", x_result.shape());
-            x_result
+            solve_linear_system_1d(&a_2d, &b_1d)?
         } else {
             let b_2d = b
                 .view()
                 .into_dimensionality::<Ix2>()
                 .map_err(|_| OpError::IncompatibleShape("Failed to convert b to 2D".into()))?;
-
-            println!("Solving 2D system (matrix b)");
-            let x_result = solve_linear_system_2d(&a_2d, &b_2d)?;
-            println!("Solution x shape: {:?}", x_result.shape());
-            x_result
+            solve_linear_system_2d(&a_2d, &b_2d)?
         };
 
-        // Verify the solution has the expected shape
-        println!("Final solution shape: {:?}", x.shape());
-
         ctx.append_output(x);
         Ok(())
     }
 
     fn grad(&self, ctx: &mut GradientContext<F>) {
-        let grad_output = ctx.output_grad();
         let a = ctx.input(0);
-        let b = ctx.input(1);
         let x = ctx.output();
-        let g = ctx.graph();
-
-        println!("Computing gradient for linear solver");
-
-        // Gradient computation for linear solve
-        // If Ax = b, then:
-        // dL/dA = -dL/dx @ x^T
-        // dL/db = A^{-T} @ dL/dx
-
-        // Evaluate tensors to arrays
-        let a_array = match a.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to evaluate matrix A");
-                ctx.append_input_grad(0, None);
-                ctx.append_input_grad(1, None);
-                return;
-            }
-        };
 
-        let b_array = match b.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to evaluate vector b");
-                ctx.append_input_grad(0, None);
-                ctx.append_input_grad(1, None);
-                return;
-            }
-        };
+        // Adjoint system: A^T gb = gx
+        let gb = solve(&T::transpose(a, &[1, 0]), ctx.output_grad());
+        let ga = T::neg(T::matmul(
+            as_columns(&gb),
+            T::transpose(as_columns(x), &[1, 0]),
+        ));
 
-        let x_array = match x.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to evaluate solution x");
-                ctx.append_input_grad(0, None);
-                ctx.append_input_grad(1, None);
-                return;
-            }
-        };
-
-        let grad_output_array = match grad_output.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to evaluate gradient");
-                ctx.append_input_grad(0, None);
-                ctx.append_input_grad(1, None);
-                return;
-            }
-        };
-
-        println!(
-            "A shape: {:?}, b shape: {:?}, x shape: {:?}, grad shape: {:?}",
-            a_array.shape(),
-            b_array.shape(),
-            x_array.shape(),
-            grad_output_array.shape()
-        );
-
-        // Convert to appropriate dimensions
-        let a_2d = match a_array.view().into_dimensionality::<Ix2>() {
-            Ok(view) => view,
-            Err(_) => {
-                println!("Failed to convert A to 2D");
-                ctx.append_input_grad(0, None);
-                ctx.append_input_grad(1, None);
-                return;
-            }
-        };
+        ctx.append_input_grad(0, Some(ga));
+        ctx.append_input_grad(1, Some(gb));
+    }
+}
 
-        // First calculate gradient with respect to b: grad_b = A^{-T} @ grad_x
-        // This involves solving the transpose system: A^T @ grad_b = grad_x
-        println!("Computing gradient with respect to b (solving A^T @ grad_b = grad_x)");
+/// Views a vector as a matrix of one column; matrices are left as they are.
+fn as_columns<'g, F: Float>(x: &Tensor<'g, F>) -> Tensor<'g, F> {
+    Tensor::builder(x.graph())
+        .append_input(x, false)
+        .build(AsColumnsOp)
+}
 
-        let grad_b = if grad_output_array.ndim() == 1 {
-            // For 1D grad_output (vector case)
-            let grad_x_1d = match grad_output_array.view().into_dimensionality::<Ix1>() {
-                Ok(view) => view,
-                Err(_) => {
-                    println!("Failed to convert gradient to 1D");
-                    ctx.append_input_grad(0, None);
-                    ctx.append_input_grad(1, None);
-                    return;
-                }
-            };
+/// See [as_columns]
+struct AsColumnsOp;
 
-            // Solve A^T @ grad_b = grad_x
-            match solve_transpose_system(&a_2d, &grad_x_1d) {
-                Ok(result) => result.into_dyn(),
-                Err(e) => {
-                    println!("Error solving transpose system (1D): {:?}", e);
-                    // Use regularized system instead
-                    let n = a_2d.shape()[0];
-                    let eps = F::epsilon() * F::from(10.0).unwrap();
-                    let regularized = &a_2d + &(Array2::<F>::eye(n) * eps);
-
-                    match solve_transpose_system(&regularized.view(), &grad_x_1d) {
-                        Ok(result) => result.into_dyn(),
-                        Err(e2) => {
-                            println!("Error solving regularized system: {:?}", e2);
-                            // Return zero gradient as fallback
-                            Array1::<F>::zeros(grad_x_1d.len()).into_dyn()
-                        }
-                    }
-                }
-            }
+impl<F: Float> Op<F> for AsColumnsOp {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let x = ctx.input(0);
+        let y = if x.ndim() == 1 {
+            x.to_owned()
+                .into_shape_with_order(ndarray::IxDyn(&[x.len(), 1]))
+                .map_err(|e| OpError::NdArrayError("as_columns".into(), e))?
         } else {
-            // For 2D grad_output (matrix case)
-            let grad_x_2d = match grad_output_array.view().into_dimensionality::<Ix2>() {
-                Ok(view) => view,
-                Err(_) => {
-                    println!("Failed to convert gradient to 2D");
-                    ctx.append_input_grad(0, None);
-                    ctx.append_input_grad(1, None);
-                    return;
-                }
-            };
-
-            // Solve A^T @ grad_b = grad_x for each column
-            match solve_transpose_system_2d(&a_2d, &grad_x_2d) {
-                Ok(result) => result.into_dyn(),
-                Err(e) => {
-                    println!("Error solving transpose system (2D): {:?}", e);
-                    // Use regularized system instead
-                    let n = a_2d.shape()[0];
-                    let eps = F::epsilon() * F::from(10.0).unwrap();
-                    let regularized = &a_2d + &(Array2::<F>::eye(n) * eps);
-
-                    match solve_transpose_system_2d(&regularized.view(), &grad_x_2d) {
-                        Ok(result) => result.into_dyn(),
-                        Err(e2) => {
-                            println!("Error solving regularized system: {:?}", e2);
-                            // Return zero gradient as fallback
-                            Array2::<F>::zeros(grad_x_2d.raw_dim()).into_dyn()
-                        }
-                    }
-                }
-            }
+            x.to_owned()
         };
-
-        println!("Gradient for b computed, shape: {:?}", grad_b.shape());
-
-        // Now calculate gradient with respect to A: grad_A = -grad_x @ x^T
-        println!("Computing gradient with respect to A (outer product: -grad_x @ x^T)");
-
-        // Create view arrays for compute_outer_product_gradient
-        let grad_output_view = grad_output_array.view();
-        let x_view = x_array.view();
-
-        // Compute outer product: grad_x @ x^T
-        let grad_a_result = compute_outer_product_gradient(&grad_output_view, &x_view);
-        let grad_a = match grad_a_result {
-            Ok(arr) => {
-                // Apply negative sign
-                arr.mapv(|v| -v)
-            }
-            Err(e) => {
-                println!("Error computing outer product: {:?}", e);
-                // Return zero gradient as fallback
-                Array2::<F>::zeros((a_2d.shape()[0], a_2d.shape()[1])).into_dyn()
-            }
-        };
-
-        println!("Gradient for A computed, shape: {:?}", grad_a.shape());
-
-        // Convert gradients to tensors
-        let grad_a_tensor = crate::tensor_ops::convert_to_tensor(grad_a, g);
-        let grad_b_tensor = crate::tensor_ops::convert_to_tensor(grad_b, g);
-
-        // Append with correct indices
-        ctx.append_input_grad(0, Some(grad_a_tensor));
-        ctx.append_input_grad(1, Some(grad_b_tensor));
-
-        println!("Linear solver gradient computation complete");
+        ctx.append_output(y);
+        Ok(())
     }
-}
 
-// Enhanced version of solve_transpose_system with better error handling
-fn solve_transpose_system<F: Float>(
-    a: &ndarray::ArrayView2<F>,
-    b: &ndarray::ArrayView1<F>,
-) -> Result<ndarray::ArrayD<F>, OpError> {
-    let at = a.t();
-    solve_linear_system_1d(&at, b)
-}
-
-// Enhanced version of solve_transpose_system_2d with better error handling
-fn solve_transpose_system_2d<F: Float>(
-    a: &ndarray::ArrayView2<F>,
-    b: &ndarray::ArrayView2<F>,
-) -> Result<ndarray::ArrayD<F>, OpError> {
-    let at = a.t();
-    solve_linear_system_2d(&at, b)
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let gx = T::reshape(ctx.output_grad(), &T::shape(ctx.input(0)));
+        ctx.append_input_grad(0, Some(gx));
+    }
 }
 
 /// Least squares solver (minimize ||Ax - b||²)
@@ -8,6 +8,12 @@ use ndarray::ScalarOperand;
 // use ndarray_linalg::{Lapack, UPLO};
 
 /// Cholesky decomposition operation with gradient support
+///
+/// Only the lower triangle of the input is read. The gradient is that of the
+/// symmetric input: with `P = Φ(L^T gL)`, where `Φ` takes the lower triangle
+/// and halves the diagonal, it is the symmetric part of `L^{-T} P L^{-1}`,
+/// computed with two adjoint solves instead of differentiating the
+/// factorization entrywise (see Murray, arXiv:1602.07527).
 #[derive(Clone)]
 pub(crate) struct CholeskyOp;
 
@@ -24,151 +30,104 @@ impl<F: Float + ScalarOperand> Op<F> for CholeskyOp {
             return Err(OpError::Other("Cholesky requires square matrix".into()));
         }
 
-        // Get ndarray data directly
-        let _matrix = input
+        let matrix = input
             .view()
             .into_dimensionality::<ndarray::Ix2>()
             .map_err(|_| OpError::Other("Failed to convert to 2D array".into()))?;
 
-        // TODO: Replace with scirs2-core linear algebra when available
-        // For now, return an error as Cholesky decomposition requires BLAS
-        return Err(OpError::Other(
-            "Cholesky decomposition not yet implemented - waiting for scirs2-core linear algebra module".to_string(),
-        ));
-
-        #[allow(unreachable_code)]
-        {
-            // When implemented, we'll need to create a mutable copy and process it
-            let mut matrix_data = _matrix.to_owned();
-
-            // The result is stored in-place in matrix_data (lower triangular part)
-            // Zero out the upper triangular part to get a clean L matrix
-            for i in 0..shape[0] {
-                for j in (i + 1)..shape[1] {
-                    matrix_data[[i, j]] = F::zero();
-                }
-            }
-
-            ctx.append_output(matrix_data.into_dyn());
-            Ok(())
-        } // End unreachable block
-    }
-
-    fn grad(&self, ctx: &mut GradientContext<F>) {
-        let gy = ctx.output_grad();
-        let y = ctx.output();
-        let g = ctx.graph();
-
-        println!("Computing gradient for Cholesky decomposition");
-
-        // Get arrays for gradient computation
-        let y_array = match y.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to evaluate output tensor for Cholesky gradient");
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let gy_array = match gy.eval(g) {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to evaluate gradient tensor for Cholesky gradient");
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        // Convert to 2D arrays
-        let l = match y_array.into_dimensionality::<ndarray::Ix2>() {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to convert Cholesky output to 2D array");
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let gy_2d = match gy_array.into_dimensionality::<ndarray::Ix2>() {
-            Ok(arr) => arr,
-            Err(_) => {
-                println!("Failed to convert Cholesky gradient to 2D array");
-                ctx.append_input_grad(0, None);
-                return;
-            }
-        };
-
-        let n = l.shape()[0];
-        println!("Cholesky gradient computation for matrix of size: {}", n);
-
-        // Initialize gradient matrix
-        let mut grad = Array2::<F>::zeros((n, n));
-
-        // Create a view of L that's properly triangular (ensuring zeros above diagonal)
-        let mut l_clean = Array2::<F>::zeros((n, n));
+        // Cholesky-Banachiewicz: A = L * L^T, row by row
+        let n = shape[0];
+        let mut l = Array2::<F>::zeros((n, n));
         for i in 0..n {
             for j in 0..=i {
-                l_clean[[i, j]] = l[[i, j]];
+                let mut sum = matrix[[i, j]];
+                for k in 0..j {
+                    sum -= l[[i, k]] * l[[j, k]];
+                }
+                if i == j {
+                    if sum <= F::zero() || !sum.is_finite() {
+                        return Err(OpError::Other("Matrix is not positive definite".into()));
+                    }
+                    l[[i, i]] = sum.sqrt();
+                } else {
+                    l[[i, j]] = sum / l[[j, j]];
+                }
             }
         }
 
-        // Compute the gradient using the chain rule for Cholesky decomposition
-        // Based on the formula: dA = L * (dL * L^T + L * dL^T) * L^T
-        // We need to solve for dL given dA
-        // We use a forward substitution approach
+        ctx.append_output(l.into_dyn());
+        Ok(())
+    }
 
-        // First, mask gradients to be lower triangular (same shape as L)
-        let mut d_l = Array2::<F>::zeros((n, n));
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let l = ctx.output();
+        let lt = crate::tensor_ops::transpose(l, &[1, 0]);
+        let gl = triangle_mask(ctx.output_grad(), true, 1.);
+        let p = triangle_mask(&crate::tensor_ops::matmul(lt, gl), true, 0.5);
+
+        // L^{-T} P L^{-1}, with the adjoint solves of L^T
+        let y = crate::tensor_ops::solve(lt, p);
+        let s = crate::tensor_ops::transpose(
+            crate::tensor_ops::solve(lt, crate::tensor_ops::transpose(y, &[1, 0])),
+            &[1, 0],
+        );
+        let ga = (s + crate::tensor_ops::transpose(s, &[1, 0])) * F::from(0.5).unwrap();
+        ctx.append_input_grad(0, Some(ga));
+    }
+}
 
-        // The diagonal elements have a special formula
-        for i in 0..n {
-            d_l[[i, i]] = gy_2d[[i, i]] / (F::from(2.0).unwrap() * l_clean[[i, i]]);
-        }
+/// Keeps the lower (or upper) triangle of a matrix, with its diagonal scaled by
+/// `diagonal`, and zeros the rest.
+pub(crate) fn triangle_mask<'g, F: Float>(
+    matrix: &Tensor<'g, F>,
+    lower: bool,
+    diagonal: f64,
+) -> Tensor<'g, F> {
+    Tensor::builder(matrix.graph())
+        .append_input(matrix, false)
+        .build(TriangleMaskOp { lower, diagonal })
+}
 
-        // Process row by row
-        for i in 1..n {
-            for j in 0..i {
-                // Compute the right-hand side for the equation
-                let mut rhs = gy_2d[[i, j]];
+/// See [triangle_mask]
+///
+/// The masking is linear and self-adjoint, so the gradient is masked likewise.
+#[derive(Clone)]
+pub(crate) struct TriangleMaskOp {
+    lower: bool,
+    diagonal: f64,
+}
 
-                // Subtract the effect of already computed elements
-                for k in 0..j {
-                    rhs = rhs - d_l[[i, k]] * l_clean[[j, k]] - l_clean[[i, k]] * d_l[[j, k]];
-                }
+impl<F: Float> Op<F> for TriangleMaskOp {
+    fn name(&self) -> &'static str {
+        "TriangleMask"
+    }
 
-                // Solve for d_l[i,j]
-                d_l[[i, j]] = rhs / l_clean[[j, j]];
-            }
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let input = ctx.input(0);
+        if input.ndim() != 2 {
+            return Err(OpError::Other("Triangle mask requires 2D matrix".into()));
         }
-
-        // Convert to gradient of A by making it symmetric
-        // dA/dL = 0.5 * (dL + dL^T) for non-diagonal elements
-        for i in 0..n {
-            for j in 0..n {
-                if i == j {
-                    // Diagonal elements
-                    grad[[i, i]] = d_l[[i, i]] * F::from(2.0).unwrap();
-                } else {
-                    // Off-diagonal elements, symmetrize
-                    let val = if i > j { d_l[[i, j]] } else { d_l[[j, i]] };
-                    grad[[i, j]] = val;
-                    grad[[j, i]] = val;
-                }
+        let diagonal = F::from(self.diagonal).unwrap();
+        let mut y = input.to_owned();
+        for ((i, j), a) in y
+            .view_mut()
+            .into_dimensionality::<ndarray::Ix2>()
+            .unwrap()
+            .indexed_iter_mut()
+        {
+            if i == j {
+                *a *= diagonal;
+            } else if (i > j) != self.lower {
+                *a = F::zero();
             }
         }
+        ctx.append_output(y);
+        Ok(())
+    }
 
-        // Add regularization for numerical stability
-        let eps = F::epsilon() * F::from(10.0).unwrap();
-        for i in 0..n {
-            grad[[i, i]] += eps;
-        }
-
-        println!("Completed Cholesky gradient computation");
-
-        // Convert gradient to tensor and append
-        let grad_tensor = convert_to_tensor(grad.into_dyn(), g);
-        ctx.append_input_grad(0, Some(grad_tensor));
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let gx = triangle_mask(ctx.output_grad(), self.lower, self.diagonal);
+        ctx.append_input_grad(0, Some(gx));
     }
 }
 
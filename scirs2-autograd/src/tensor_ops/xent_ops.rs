@@ -5,16 +5,25 @@ use crate::tensor_ops;
 use crate::tensor_ops::*;
 use crate::Float;
 use ndarray;
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
 pub struct SoftmaxCrossEntropy;
+#[derive(Serialize, Deserialize)]
 pub struct SparseSoftmaxCrossEntropy;
 pub struct SparseSoftmaxCrossEntropyGrad;
+#[derive(Serialize, Deserialize)]
 pub struct SigmoidCrossEntropy;
+#[derive(Serialize, Deserialize)]
 pub struct LogSoftmax {
     pub axis: isize,
 }
 
 impl<T: Float> op::Op<T> for LogSoftmax {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("LogSoftmax", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         ctx.append_output(x - &crate::tensor_ops::math_ops::logsumexp_forward(x, self.axis, true));
@@ -31,6 +40,10 @@ impl<T: Float> op::Op<T> for LogSoftmax {
 }
 
 impl<T: Float> op::Op<T> for SigmoidCrossEntropy {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("SigmoidCrossEntropy", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x: &NdArrayView<T> = &ctx.input(0);
         let t: &NdArrayView<T> = &ctx.input(1);
@@ -61,6 +74,10 @@ impl<T: Float> op::Op<T> for SigmoidCrossEntropy {
 }
 
 impl<T: Float> op::Op<T> for SparseSoftmaxCrossEntropy {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("SparseSoftmaxCrossEntropy", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let (x, t) = (&ctx.input(0), &ctx.input(1));
         let log_x: NdArray<T> = x - &tensor_ops::math_ops::logsumexp_forward(x, 1, true);
@@ -158,6 +175,10 @@ impl<T: Float> op::Op<T> for SparseSoftmaxCrossEntropyGrad {
 }
 
 impl<T: Float> op::Op<T> for SoftmaxCrossEntropy {
+    fn op_def(&self) -> Option<op::OpDef> {
+        op::OpDef::new("SoftmaxCrossEntropy", self)
+    }
+
     fn compute(&self, ctx: &mut crate::op::ComputeContext<T>) -> Result<(), crate::op::OpError> {
         let x = &ctx.input(0);
         let log_x: NdArray<T> = x - &tensor_ops::math_ops::logsumexp_forward(x, 1, true);
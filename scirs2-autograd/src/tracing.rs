@@ -7,12 +7,16 @@ use crate::tensor::Tensor;
 use crate::Float;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// Global tracing system
 static GLOBAL_TRACER: std::sync::OnceLock<Arc<Mutex<ExecutionTracer>>> = std::sync::OnceLock::new();
 
+/// Whether the debug messages of ops are emitted, see [set_debug_logging]
+static DEBUG_LOGGING: AtomicBool = AtomicBool::new(false);
+
 /// Execution tracer for computation graphs
 pub struct ExecutionTracer {
     /// Configuration for tracing
@@ -444,6 +448,7 @@ impl ExecutionTracer {
                 EventType::PerformanceBottleneck { .. } => {
                     summary.bottlenecks_detected += 1;
                 }
+                EventType::DebugMessage { .. } => {}
             }
         }
 
@@ -499,7 +504,7 @@ pub struct TraceSessionId(String);
 
 impl TraceSessionId {
     fn new() -> Self {
-        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::atomic::AtomicU64;
         static COUNTER: AtomicU64 = AtomicU64::new(0);
         let id = COUNTER.fetch_add(1, Ordering::SeqCst);
         Self(format!("session_{}", id))
@@ -512,7 +517,7 @@ pub struct RecordingId(String);
 
 impl RecordingId {
     fn new() -> Self {
-        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::atomic::AtomicU64;
         static COUNTER: AtomicU64 = AtomicU64::new(0);
         let id = COUNTER.fetch_add(1, Ordering::SeqCst);
         Self(format!("recording_{}", id))
@@ -573,6 +578,8 @@ pub enum EventType {
         reason: String,
         severity: BottleneckSeverity,
     },
+    /// Debug message of an op, see [set_debug_logging]
+    DebugMessage { source: String, message: String },
 }
 
 /// Types of memory allocations
@@ -841,6 +848,44 @@ pub fn set_tracing_enabled(enabled: bool) -> Result<(), TracingError> {
     configure_tracing(config)
 }
 
+/// Enables or disables the debug messages of ops, which are off by default.
+///
+/// Ops such as the optimizer updates report their inputs through [debug_message].
+/// When enabled, the messages are written to stderr and recorded as
+/// [EventType::DebugMessage] in the current trace session, if any.
+pub fn set_debug_logging(enabled: bool) {
+    DEBUG_LOGGING.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the debug messages of ops are enabled, see [set_debug_logging].
+#[inline]
+pub fn debug_logging_enabled() -> bool {
+    DEBUG_LOGGING.load(Ordering::Relaxed)
+}
+
+/// Emits a debug message of `source` if enabled with [set_debug_logging].
+///
+/// `message` is only called when the message is emitted, so disabled logging
+/// costs no formatting.
+#[inline]
+pub fn debug_message<M: FnOnce() -> String>(source: &str, message: M) {
+    if !debug_logging_enabled() {
+        return;
+    }
+    let message = message();
+    eprintln!("[{}] {}", source, message);
+    if let Ok(mut tracer) = init_tracer().lock() {
+        tracer.record_event(ExecutionEvent {
+            timestamp: Instant::now(),
+            event_type: EventType::DebugMessage {
+                source: source.to_string(),
+                message,
+            },
+            metadata: HashMap::new(),
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,7 +81,6 @@ use serde::{Deserialize, Serialize};
 use serde_json;
 use smallvec::alloc::fmt::{Display, Formatter};
 use std::cell::RefCell;
-use std::collections::HashMap;
 
 use std::error::Error;
 use std::fs::File;
@@ -125,7 +124,7 @@ impl<'g, 'e: 'g, F: Float> GetVariableTensor<'g, F, &'static str> for Context<'e
     /// Get or create a variable tensor by name in the default namespace.
     fn variable(&'g self, name: &str) -> Tensor<'g, F> {
         self.graph
-            .variable_by_name(name, &self.var_env_ref.default_namespace())
+            .variable_by_name(name, &self.var_env_ref().default_namespace())
     }
 }
 
@@ -142,7 +141,7 @@ impl<'g, 'e: 'g, F: Float> GetVariableTensor<'g, F, (&'static str, &'static str)
     /// Get or create a variable tensor by VariableID
     fn variable(&'g self, id: (&'static str, &'static str)) -> Tensor<'g, F> {
         self.graph
-            .variable_by_name(id.1, &self.var_env_ref.namespace(id.0))
+            .variable_by_name(id.1, &self.var_env_ref().namespace(id.0))
     }
 }
 
@@ -685,13 +684,9 @@ impl<'env, F: Float> VariableEnvironment<F> {
     where
         FN: FnOnce(&mut Context<'env, F>) -> R,
     {
-        let g = Graph {
-            node_set: RefCell::new(Vec::with_capacity(256)),
-            variable2node: RefCell::new(HashMap::new()),
-        };
         let mut c = Context {
-            var_env_ref: self,
-            graph: g,
+            graph: Graph::with_env(self, 256),
+            _env: std::marker::PhantomData,
         };
         f(&mut c)
     }
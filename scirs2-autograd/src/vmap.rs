@@ -0,0 +1,561 @@
+//! Batching of the ops of a function, see [vmap](crate::tensor_ops::vmap)
+//!
+//! The function is called once with tensors standing for single examples
+//! (the first example of each batch). Its ops are then built again, in order,
+//! with [Op::batch]: for each op using a batched tensor, the op builds the
+//! tensor computing it for the whole batch. Ops without a batching rule are
+//! wrapped in [PerExample], which loops over the examples.
+use crate::graph::{Graph, TensorID};
+use crate::ndarray_ext::NdArray;
+use crate::op::{BatchContext, ComputeContext, GradientContext, Op, OpError};
+use crate::tensor::Tensor;
+use crate::tensor_ops as T;
+use crate::{Context, Float, FxHashMap};
+use ndarray::Axis;
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Batched tensors by the ids of the tensors of an example, with a flag
+/// telling whether they have a batch axis
+type Batched<'g, F> = FxHashMap<TensorID, (Tensor<'g, F>, bool)>;
+
+/// Maps `f` over the axes `in_axes` of `xs`, or passes the inputs with `None`
+/// as they are; the outputs are batched along their first axis.
+pub(crate) fn vmap<'g, F, Fun>(
+    f: Fun,
+    xs: &[Tensor<'g, F>],
+    in_axes: &[Option<usize>],
+) -> Vec<Tensor<'g, F>>
+where
+    F: Float,
+    Fun: FnOnce(&[Tensor<'g, F>]) -> Vec<Tensor<'g, F>>,
+{
+    assert_eq!(
+        xs.len(),
+        in_axes.len(),
+        "vmap: one axis is needed per input"
+    );
+    let batches: Vec<Option<Tensor<'g, F>>> = xs
+        .iter()
+        .zip(in_axes)
+        .map(|(x, axis)| match *axis {
+            Some(0) => Some(*x),
+            Some(axis) => Some(move_axis(x, axis, 0)),
+            None => None,
+        })
+        .collect();
+    let reference = batches
+        .iter()
+        .flatten()
+        .copied()
+        .next()
+        .expect("vmap: at least one input must be batched");
+
+    let g = reference.graph();
+    let start = num_nodes(g);
+    let mut known = Batched::default();
+    let examples: Vec<Tensor<'g, F>> = xs
+        .iter()
+        .zip(&batches)
+        .map(|(x, batch)| match batch {
+            Some(batch) => example(batch, &mut known),
+            None => *x,
+        })
+        .collect();
+    let ys = f(&examples);
+    batch_graph(g, start, known, &ys)
+        .into_iter()
+        .map(|(y, batched)| {
+            if batched {
+                y
+            } else {
+                broadcast_batch(&y, &reference)
+            }
+        })
+        .collect()
+}
+
+fn num_nodes<F: Float>(g: &Graph<F>) -> usize {
+    g.node_set.borrow().len()
+}
+
+/// Tensor standing for the examples of `batch`
+fn example<'g, F: Float>(batch: &Tensor<'g, F>, known: &mut Batched<'g, F>) -> Tensor<'g, F> {
+    let x = Tensor::builder(batch.graph())
+        .append_input(batch, false)
+        .build(Example);
+    known.insert(x.id, (*batch, true));
+    x
+}
+
+/// Builds the batched tensors of `ys`, which were built from the examples of
+/// `known` by the nodes from `start`.
+fn batch_graph<'g, F: Float>(
+    g: &'g Graph<F>,
+    start: TensorID,
+    mut known: Batched<'g, F>,
+    ys: &[Tensor<'g, F>],
+) -> Vec<(Tensor<'g, F>, bool)> {
+    // Nodes from `start` used by `ys`, in topological order
+    let mut nodes = BTreeSet::new();
+    let mut stack: Vec<TensorID> = ys.iter().map(|y| y.id).filter(|&id| id >= start).collect();
+    while let Some(id) = stack.pop() {
+        if known.contains_key(&id) || !nodes.insert(id) {
+            continue;
+        }
+        let node = g.access_inner(id);
+        stack.extend(
+            node.incoming_nodes
+                .iter()
+                .map(|x| x.id)
+                .filter(|&x| x >= start),
+        );
+    }
+
+    for id in nodes {
+        let (op, incoming, differentiable) = {
+            let node = g.access_inner(id);
+            (
+                node.op.clone(),
+                node.incoming_nodes.clone(),
+                node.is_differentiable,
+            )
+        };
+        let Some(op) = op else {
+            continue;
+        };
+        if !incoming.iter().any(|x| known.contains_key(&x.id)) {
+            // Same for all the examples
+            continue;
+        }
+        let (mut inputs, mut batched) = (vec![], vec![]);
+        for x in &incoming {
+            let (input, is_batched) = known.get(&x.id).copied().unwrap_or((g.tensor(x.id), false));
+            inputs.push(if x.array_selector > 0 {
+                T::nth_tensor(input, x.array_selector)
+            } else {
+                input
+            });
+            batched.push(is_batched);
+        }
+        let mut ctx = BatchContext {
+            op: op.clone(),
+            inputs,
+            batched,
+            differentiable,
+            context: Context::from_graph(g),
+            output: None,
+        };
+        let output = if ctx.batched.contains(&true) {
+            op.batch(&mut ctx);
+            ctx.output.take().unwrap_or_else(|| {
+                let y = if op.elementwise_fn().is_some() {
+                    ctx.rebuild(&ctx.inputs)
+                } else {
+                    per_example(&ctx)
+                };
+                (y, true)
+            })
+        } else {
+            // Inputs replaced with tensors without a batch axis
+            (ctx.rebuild(&ctx.inputs), false)
+        };
+        known.insert(id, output);
+    }
+
+    ys.iter()
+        .map(|y| known.get(&y.id).copied().unwrap_or((*y, false)))
+        .collect()
+}
+
+fn per_example<'g, F: Float>(ctx: &BatchContext<'g, F>) -> Tensor<'g, F> {
+    let mut builder = Tensor::builder(ctx.context).set_differentiable(ctx.differentiable);
+    for x in &ctx.inputs {
+        builder = builder.append_input(x, false);
+    }
+    builder.build(PerExample {
+        op: ctx.op.clone(),
+        batched: ctx.batched.clone(),
+    })
+}
+
+/// Moves the axis `from` of `x` to `to`.
+fn move_axis<'g, F: Float>(x: &Tensor<'g, F>, from: usize, to: usize) -> Tensor<'g, F> {
+    Tensor::builder(x.graph())
+        .append_input(x, false)
+        .build(MoveAxis { from, to })
+}
+
+/// Repeats `x` for each example of `batch`.
+fn broadcast_batch<'g, F: Float>(x: &Tensor<'g, F>, batch: &Tensor<'g, F>) -> Tensor<'g, F> {
+    Tensor::builder(x.graph())
+        .append_input(x, false)
+        .append_input(batch, false)
+        .build(BroadcastBatch)
+}
+
+/// Inputs of a broadcasting op, see [BatchContext::broadcastable_inputs]
+pub(crate) fn broadcastable<'g, F: Float>(
+    inputs: &[Tensor<'g, F>],
+    batched: &[bool],
+) -> Vec<Tensor<'g, F>> {
+    (0..inputs.len())
+        .map(|i| {
+            let others: Vec<usize> = (0..inputs.len()).filter(|&j| j != i).collect();
+            if !batched[i] || others.is_empty() {
+                return inputs[i];
+            }
+            let mut builder = Tensor::builder(inputs[i].graph()).append_input(inputs[i], false);
+            for &j in &others {
+                builder = builder.append_input(inputs[j], false);
+            }
+            builder.build(AlignBatch {
+                target: AlignTarget::Tensors(others.iter().map(|&j| batched[j]).collect()),
+            })
+        })
+        .collect()
+}
+
+/// See [BatchContext::broadcastable_to_shape]
+pub(crate) fn align_to_shape<'g, F: Float>(
+    x: &Tensor<'g, F>,
+    shape: &Tensor<'g, F>,
+) -> Tensor<'g, F> {
+    Tensor::builder(x.graph())
+        .append_input(x, false)
+        .append_input(shape, false)
+        .build(AlignBatch {
+            target: AlignTarget::Shape,
+        })
+}
+
+/// Index tensors of an example converted for the batch
+#[derive(Clone, Copy)]
+pub(crate) enum BatchIndex {
+    /// Axes, the non-negative ones being shifted by the batch axis
+    Axes,
+    /// Flag of each axis
+    AxisMask,
+    /// Permutation of the axes
+    Permutation,
+    /// Shape
+    Shape,
+    /// Shape, with ones prepended up to the rank of the examples of the batch
+    AlignedShape,
+}
+
+/// Converts `index`, an index tensor of an example, for the batch of `batch`.
+pub(crate) fn batch_index<'g, F: Float>(
+    kind: BatchIndex,
+    index: &Tensor<'g, F>,
+    batch: &Tensor<'g, F>,
+) -> Tensor<'g, F> {
+    Tensor::builder(index.graph())
+        .append_input(index, false)
+        .append_input(batch, false)
+        .set_differentiable(false)
+        .build(BatchIndices { kind })
+}
+
+/// Reshapes the batch `x` so that each example is flattened.
+pub(crate) fn flatten_examples<'g, F: Float>(x: &Tensor<'g, F>) -> Tensor<'g, F> {
+    let flat = T::convert_to_tensor(ndarray::arr1(&[-F::one()]).into_dyn(), x.graph());
+    T::reshape(x, &batch_index(BatchIndex::Shape, &flat, x))
+}
+
+/// First example of a batch, standing for each example while a function is traced
+struct Example;
+
+/// Op computed once per example
+struct PerExample<F: Float> {
+    op: Rc<dyn Op<F>>,
+    /// Inputs with a batch axis
+    batched: Vec<bool>,
+}
+
+struct MoveAxis {
+    from: usize,
+    to: usize,
+}
+
+/// Input repeated for each example of a batch; inputs: tensor, batch
+struct BroadcastBatch;
+
+/// Batched input with axes of length one inserted after the batch axis, so
+/// that its examples have the rank of the examples of the target
+struct AlignBatch {
+    target: AlignTarget,
+}
+
+enum AlignTarget {
+    /// The other inputs, with a flag telling whether they have a batch axis
+    Tensors(Vec<bool>),
+    /// The other input, a shape
+    Shape,
+}
+
+/// Index tensor of an example converted for a batch; inputs: index, batch
+struct BatchIndices {
+    kind: BatchIndex,
+}
+
+fn batch_size<F: Float>(batch: &crate::ndarray_ext::NdArrayView<F>) -> Result<usize, OpError> {
+    if batch.ndim() == 0 {
+        return Err(OpError::InvalidDims(
+            "vmap: batched tensor without a batch axis".into(),
+        ));
+    }
+    Ok(batch.len_of(Axis(0)))
+}
+
+impl<F: Float> Op<F> for Example {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let batch = ctx.input(0);
+        if batch_size(&batch)? == 0 {
+            return Err(OpError::OutOfBounds("vmap: empty batch".into()));
+        }
+        let x = batch.index_axis(Axis(0), 0).to_owned();
+        ctx.append_output(x);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        ctx.append_input_grad(0, None);
+    }
+}
+
+impl<F: Float> Op<F> for PerExample<F> {
+    fn num_outputs(&self) -> usize {
+        self.op.num_outputs()
+    }
+
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let inputs = ctx.inputs();
+        let mut size = None;
+        for (x, _) in inputs.iter().zip(&self.batched).filter(|(_, &b)| b) {
+            let n = batch_size(x)?;
+            if size.is_some_and(|size| size != n) {
+                return Err(OpError::IncompatibleShape(format!(
+                    "vmap: batches of {} and {} examples",
+                    size.unwrap(),
+                    n
+                )));
+            }
+            size = Some(n);
+        }
+
+        let mut outputs: Vec<Vec<NdArray<F>>> = vec![];
+        for i in 0..size.unwrap_or(0) {
+            let example_inputs = inputs
+                .iter()
+                .zip(&self.batched)
+                .map(|(x, &batched)| {
+                    if batched {
+                        x.index_axis(Axis(0), i).to_owned()
+                    } else {
+                        x.to_owned()
+                    }
+                })
+                .collect();
+            let mut example_ctx = ComputeContext::with_inputs(example_inputs);
+            self.op.compute(&mut example_ctx)?;
+            outputs.resize(example_ctx.outputs.len(), vec![]);
+            for (ys, y) in outputs.iter_mut().zip(example_ctx.outputs) {
+                ys.push(y);
+            }
+        }
+        if outputs.is_empty() {
+            return Err(OpError::OutOfBounds("vmap: empty batch".into()));
+        }
+        for ys in outputs {
+            let views: Vec<_> = ys.iter().map(|y| y.view()).collect();
+            let y = ndarray::stack(Axis(0), &views)
+                .map_err(|e| OpError::NdArrayError("vmap".into(), e))?;
+            ctx.append_output(y);
+        }
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        // Batches the gradient of an example
+        let g = ctx.graph();
+        let start = num_nodes(g);
+        let mut known = Batched::default();
+        let xs: Vec<Tensor<F>> = (0..ctx.num_inputs())
+            .map(|i| {
+                if self.batched[i] {
+                    example(ctx.input(i), &mut known)
+                } else {
+                    *ctx.input(i)
+                }
+            })
+            .collect();
+        let zs: Vec<Tensor<F>> = (0..ctx.num_outputs())
+            .map(|k| example(ctx.nth_output(k), &mut known))
+            .collect();
+        let gzs: Vec<Tensor<F>> = (0..ctx.num_outputs())
+            .map(|k| example(ctx.nth_output_grad(k), &mut known))
+            .collect();
+
+        let gxs: Vec<Option<TensorID>> = {
+            let x_refs: Vec<&Tensor<F>> = xs.iter().collect();
+            let z_refs: Vec<&Tensor<F>> = zs.iter().collect();
+            let gz_refs: Vec<&Tensor<F>> = gzs.iter().collect();
+            let mut example_ctx = GradientContext {
+                zs: &z_refs,
+                xs: &x_refs,
+                context: g,
+                gzs: &gz_refs,
+                results: Vec::new(),
+                array_field_id: 0,
+                _marker: PhantomData,
+            };
+            self.op.grad(&mut example_ctx);
+            example_ctx
+                .results
+                .iter()
+                .map(|gx| gx.map(|gx| gx.id))
+                .collect()
+        };
+        let targets: Vec<Tensor<F>> = gxs.iter().flatten().map(|&id| g.tensor(id)).collect();
+        let mut batched_gxs = batch_graph(g, start, known, &targets).into_iter();
+
+        let output = ctx.output();
+        for (i, gx) in gxs.iter().enumerate() {
+            let gx = gx.map(|_| {
+                let (gx, batched) = batched_gxs.next().unwrap();
+                let gx = if batched {
+                    gx
+                } else {
+                    broadcast_batch(&gx, output)
+                };
+                if self.batched[i] {
+                    gx
+                } else {
+                    T::reduce_sum(gx, &[0], false)
+                }
+            });
+            ctx.append_input_grad(i, gx);
+        }
+    }
+}
+
+impl<F: Float> Op<F> for MoveAxis {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let x = ctx.input(0);
+        if self.from >= x.ndim() || self.to >= x.ndim() {
+            return Err(OpError::OutOfBounds(format!(
+                "vmap: axis {} of an array of rank {}",
+                self.from.max(self.to),
+                x.ndim()
+            )));
+        }
+        let mut axes: Vec<usize> = (0..x.ndim()).filter(|&a| a != self.from).collect();
+        axes.insert(self.to, self.from);
+        let y = x.permuted_axes(axes).as_standard_layout().into_owned();
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let gx = move_axis(ctx.output_grad(), self.to, self.from);
+        ctx.append_input_grad(0, Some(gx));
+    }
+
+    fn batch(&self, ctx: &mut BatchContext<F>) {
+        let y = move_axis(ctx.input(0), self.from + 1, self.to + 1);
+        ctx.set_output(y, true);
+    }
+}
+
+impl<F: Float> Op<F> for BroadcastBatch {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let x = ctx.input(0);
+        let mut shape = vec![batch_size(&ctx.input(1))?];
+        shape.extend_from_slice(x.shape());
+        let y = x.broadcast(shape).unwrap().to_owned();
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let gx = T::reduce_sum(ctx.output_grad(), &[0], false);
+        ctx.append_input_grad(0, Some(gx));
+        ctx.append_input_grad(1, None);
+    }
+}
+
+impl<F: Float> Op<F> for AlignBatch {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let x = ctx.input(0);
+        let size = batch_size(&x)?;
+        let rank = match &self.target {
+            AlignTarget::Tensors(batched) => batched
+                .iter()
+                .enumerate()
+                .map(|(j, &b)| ctx.input(j + 1).ndim() - b as usize)
+                .max()
+                .unwrap_or(0),
+            AlignTarget::Shape => ctx.input(1).len(),
+        };
+        let mut shape = vec![size];
+        shape.resize(1 + rank.saturating_sub(x.ndim() - 1), 1);
+        shape.extend_from_slice(&x.shape()[1..]);
+        let y = x
+            .to_shape(shape)
+            .map_err(|e| OpError::NdArrayError("vmap".into(), e))?
+            .into_owned();
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let x = ctx.input(0);
+        ctx.append_input_grad(0, Some(T::reshape(ctx.output_grad(), &T::shape(x))));
+        for i in 1..ctx.num_inputs() {
+            ctx.append_input_grad(i, None);
+        }
+    }
+}
+
+impl<F: Float> Op<F> for BatchIndices {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let index = ctx.input(0);
+        let y = match self.kind {
+            BatchIndex::Axes => index.mapv(|a| if a >= F::zero() { a + F::one() } else { a }),
+            BatchIndex::AxisMask => std::iter::once(F::zero())
+                .chain(index.iter().copied())
+                .collect::<ndarray::Array1<F>>()
+                .into_dyn(),
+            BatchIndex::Permutation => std::iter::once(F::zero())
+                .chain(
+                    index
+                        .iter()
+                        .map(|&a| if a >= F::zero() { a + F::one() } else { a }),
+                )
+                .collect::<ndarray::Array1<F>>()
+                .into_dyn(),
+            BatchIndex::Shape | BatchIndex::AlignedShape => {
+                let batch = ctx.input(1);
+                let first = F::from(batch_size(&batch)?).unwrap();
+                let ones = match self.kind {
+                    BatchIndex::AlignedShape => (batch.ndim() - 1).saturating_sub(index.len()),
+                    _ => 0,
+                };
+                std::iter::once(first)
+                    .chain(std::iter::repeat_n(F::one(), ones))
+                    .chain(index.iter().copied())
+                    .collect::<ndarray::Array1<F>>()
+                    .into_dyn()
+            }
+        };
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        ctx.append_input_grad(0, None);
+        ctx.append_input_grad(1, None);
+    }
+}
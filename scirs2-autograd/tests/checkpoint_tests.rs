@@ -355,3 +355,162 @@ fn test_checkpoint_profiler() {
         T::CheckpointProfiler::stop_tracking();
     });
 }
+
+/// Builds a deep `tanh(h W)` chain, optionally checkpointing every `segment` layers
+fn deep_tanh_chain<'g>(
+    ctx: &'g ag::Context<f64>,
+    x: ag::Tensor<'g, f64>,
+    w: ag::Tensor<'g, f64>,
+    depth: usize,
+    segment: Option<usize>,
+) -> ag::Tensor<'g, f64> {
+    let layer = |h| T::tanh(T::matmul(h, w));
+    match segment {
+        None => (0..depth).fold(x, |h, _| layer(h)),
+        Some(n) => {
+            (0..depth / n).fold(x, |h, _| ctx.checkpoint(|| (0..n).fold(h, |h, _| layer(h))))
+        }
+    }
+}
+
+#[test]
+fn test_scoped_checkpoint_gradients() {
+    let x_val = array![[0.5, -0.2, 0.1], [0.3, 0.8, -0.6]].into_dyn();
+    let w_val = array![[0.9, -0.4, 0.2], [0.1, 0.7, -0.5], [-0.3, 0.2, 0.6]].into_dyn();
+
+    let mut results = Vec::new();
+    for segment in [None, Some(2), Some(4)] {
+        ag::run::<f64, _, _>(|ctx| {
+            let x = ctx.placeholder("x", &[2, 3]);
+            let w = ctx.placeholder("w", &[3, 3]);
+            let y = deep_tanh_chain(ctx, x, w, 8, segment);
+            let loss = T::sum_all(T::square(y));
+            let grads = T::grad(&[loss], &[x, w]);
+            let outputs = ctx
+                .evaluator()
+                .push(&loss)
+                .extend(&grads)
+                .feed(x, x_val.view())
+                .feed(w, w_val.view())
+                .run();
+            results.push(outputs.into_iter().map(|r| r.unwrap()).collect::<Vec<_>>());
+        });
+    }
+
+    for checkpointed in &results[1..] {
+        for (expected, actual) in results[0].iter().zip(checkpointed) {
+            assert_eq!(expected.shape(), actual.shape());
+            for (e, a) in expected.iter().zip(actual.iter()) {
+                assert!((e - a).abs() < 1e-10, "expected {}, got {}", e, a);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_scoped_checkpoint_reduces_peak_memory() {
+    let x_val = ndarray::Array2::<f64>::from_elem((16, 32), 0.1).into_dyn();
+    let w_val = ndarray::Array2::<f64>::from_elem((32, 32), 0.01).into_dyn();
+
+    let mut peaks = Vec::new();
+    for segment in [None, Some(4)] {
+        ag::run::<f64, _, _>(|ctx| {
+            let x = ctx.placeholder("x", &[16, 32]);
+            let w = ctx.placeholder("w", &[32, 32]);
+            let y = deep_tanh_chain(ctx, x, w, 16, segment);
+            let gw = T::grad(&[T::sum_all(y)], &[w])[0];
+            ctx.evaluator()
+                .push(&gw)
+                .feed(x, x_val.view())
+                .feed(w, w_val.view())
+                .run()[0]
+                .as_ref()
+                .unwrap();
+            peaks.push(ctx.peak_memory_bytes());
+        });
+    }
+    assert!(peaks[0] > 0);
+    assert!(
+        peaks[1] < peaks[0],
+        "checkpointed peak {} is not below {}",
+        peaks[1],
+        peaks[0]
+    );
+}
+
+#[test]
+fn test_checkpoint_plan_for_budget() {
+    let layer_bytes = [100; 8];
+
+    // Everything fits: no checkpointing
+    let plan = T::CheckpointPlan::for_budget(&layer_bytes, 800);
+    assert!(plan.segments().is_empty());
+    assert_eq!(plan.estimated_peak_bytes(), 800);
+
+    // Two segments of four layers: two segment outputs and one segment
+    let plan = T::CheckpointPlan::for_budget(&layer_bytes, 600);
+    assert_eq!(plan.segments(), &[0..4, 4..8]);
+    assert_eq!(plan.estimated_peak_bytes(), 600);
+
+    // Unreachable budgets fall back to the lowest peak
+    let plan = T::CheckpointPlan::for_budget(&layer_bytes, 10);
+    assert_eq!(plan.segments(), &[0..4, 4..8]);
+    assert_eq!(plan.estimated_peak_bytes(), 600);
+
+    // The fewest segments meeting the budget are chosen
+    let plan = T::CheckpointPlan::for_budget(&[100; 9], 650);
+    assert_eq!(plan.segments(), &[0..3, 3..6, 6..9]);
+    assert_eq!(plan.estimated_peak_bytes(), 600);
+}
+
+#[test]
+fn test_checkpoint_sequential() {
+    let x_val = array![[0.5, -0.2], [0.3, 0.8]].into_dyn();
+    ag::run::<f64, _, _>(|ctx| {
+        let x = ctx.placeholder("x", &[2, 2]);
+        let w = T::convert_to_tensor(array![[0.9, -0.4], [0.1, 0.7]], ctx);
+        let layers: Vec<_> = (0..6)
+            .map(|i| {
+                move |h| {
+                    if i % 2 == 0 {
+                        T::matmul(h, w)
+                    } else {
+                        T::tanh(h)
+                    }
+                }
+            })
+            .collect();
+
+        let layer_bytes = ctx
+            .layer_activation_bytes(x, &layers, ag::Feeder::new().push(x, x_val.view()))
+            .unwrap();
+        assert_eq!(layer_bytes.len(), 6);
+        // Each layer outputs at least a 2x2 matrix of f64
+        assert!(layer_bytes.iter().all(|&bytes| bytes >= 32));
+
+        let total: usize = layer_bytes.iter().sum();
+        let plan = T::CheckpointPlan::for_budget(&layer_bytes, total - 1);
+        assert!(!plan.segments().is_empty());
+        assert!(plan.estimated_peak_bytes() < total);
+
+        let plain = T::sum_all(layers.iter().fold(x, |h, layer| layer(h)));
+        let checkpointed = T::sum_all(ctx.checkpoint_sequential(x, &layers, &plan));
+        let grads = [
+            T::grad(&[plain], &[w])[0],
+            T::grad(&[checkpointed], &[w])[0],
+        ];
+        let results: Vec<_> = ctx
+            .evaluator()
+            .extend(&[plain, checkpointed])
+            .extend(&grads)
+            .feed(x, x_val.view())
+            .run()
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect();
+        assert!((results[0][[]] - results[1][[]]).abs() < 1e-12);
+        for (e, a) in results[2].iter().zip(results[3].iter()) {
+            assert!((e - a).abs() < 1e-12, "expected {}, got {}", e, a);
+        }
+    });
+}
@@ -0,0 +1,128 @@
+use ag::op::{ComputeContext, GradientContext, Op, OpError};
+use ag::tensor_ops as T;
+use approx::assert_relative_eq;
+use ndarray::array;
+use scirs2_autograd as ag;
+
+/// Computes `sin(x)` and `cos(x)` at once.
+struct SinCos;
+
+impl<F: ag::Float> Op<F> for SinCos {
+    fn num_outputs(&self) -> usize {
+        2
+    }
+
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let x = ctx.input(0);
+        let (sin, cos) = (x.mapv(F::sin), x.mapv(F::cos));
+        ctx.append_output(sin);
+        ctx.append_output(cos);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let (sin, cos) = (ctx.nth_output(0), ctx.nth_output(1));
+        let gx = ctx.nth_output_grad(0) * cos - ctx.nth_output_grad(1) * sin;
+        ctx.append_input_grad(0, Some(gx));
+    }
+}
+
+/// `sqrt(x^2 + y^2)` of two tensors of the same shape.
+struct Hypot;
+
+impl<F: ag::Float> Op<F> for Hypot {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let (x, y) = (ctx.input(0), ctx.input(1));
+        if x.shape() != y.shape() {
+            return Err(OpError::IncompatibleShape(format!(
+                "Hypot: {:?} and {:?}",
+                x.shape(),
+                y.shape()
+            )));
+        }
+        let mut z = x.to_owned();
+        z.zip_mut_with(&y, |a, &b| *a = a.hypot(b));
+        ctx.append_output(z);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let (z, gz) = (ctx.output(), ctx.output_grad());
+        let (x, y) = (ctx.input(0), ctx.input(1));
+        ctx.append_input_grad(0, Some(gz * x / z));
+        ctx.append_input_grad(1, Some(gz * y / z));
+    }
+}
+
+fn hypot<'g>(x: ag::Tensor<'g, f64>, y: ag::Tensor<'g, f64>) -> ag::Tensor<'g, f64> {
+    ag::Tensor::builder(x.graph())
+        .append_input(x, false)
+        .append_input(y, false)
+        .build(Hypot)
+}
+
+#[test]
+fn test_multi_output_op() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[3]);
+        let outputs = ag::Tensor::builder(g)
+            .append_input(x, false)
+            .build_outputs(SinCos);
+        assert_eq!(outputs.len(), 2);
+        let (sin, cos) = (outputs[0], outputs[1]);
+
+        let both = T::grad(&[T::sum_all(sin * 2. + cos)], &[x])[0];
+        let only_cos = T::grad(&[T::sum_all(cos)], &[x])[0];
+
+        let x_val = array![0.1, 0.7, -1.3].into_dyn();
+        let results = g
+            .evaluator()
+            .extend(&[sin, cos, both, only_cos])
+            .feed(x, x_val.view())
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        for (i, &v) in x_val.iter().enumerate() {
+            assert_relative_eq!(results[0][i], v.sin(), epsilon = 1e-12);
+            assert_relative_eq!(results[1][i], v.cos(), epsilon = 1e-12);
+            assert_relative_eq!(results[2][i], 2. * v.cos() - v.sin(), epsilon = 1e-12);
+            assert_relative_eq!(results[3][i], -v.sin(), epsilon = 1e-12);
+        }
+    });
+}
+
+#[test]
+fn test_multi_input_op_higher_order_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[]);
+        let y = g.placeholder("y", &[]);
+        let z = hypot(x, y);
+        let gxy = T::grad(&[z], &[x, y]);
+        // d^2 z / dx dy = -x y / z^3
+        let gxy2 = T::grad(&[gxy[0]], &[y])[0];
+
+        let (x_val, y_val) = (ndarray::arr0(3.).into_dyn(), ndarray::arr0(4.).into_dyn());
+        let results = g
+            .evaluator()
+            .extend(&[z, gxy[0], gxy[1], gxy2])
+            .feed(x, x_val.view())
+            .feed(y, y_val.view())
+            .run();
+        let results: Vec<f64> = results.into_iter().map(|r| r.unwrap()[[]]).collect();
+        assert_relative_eq!(results[0], 5., epsilon = 1e-12);
+        assert_relative_eq!(results[1], 0.6, epsilon = 1e-12);
+        assert_relative_eq!(results[2], 0.8, epsilon = 1e-12);
+        assert_relative_eq!(results[3], -12. / 125., epsilon = 1e-12);
+    });
+}
+
+#[test]
+fn test_custom_op_error() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = T::convert_to_tensor(array![1., 2.], g);
+        let y = T::convert_to_tensor(array![1., 2., 3.], g);
+        assert!(matches!(
+            hypot(x, y).eval(g),
+            Err(ag::EvalError::OpError(OpError::IncompatibleShape(_)))
+        ));
+    });
+}
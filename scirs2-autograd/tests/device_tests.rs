@@ -0,0 +1,152 @@
+use ag::device::Device;
+use ag::serialization::{OpRegistry, SerializedGraph};
+use ag::tensor_ops as T;
+use approx::assert_relative_eq;
+use ndarray::array;
+use scirs2_autograd as ag;
+
+#[test]
+fn test_placement() {
+    ag::run(|g: &mut ag::Context<f32>| {
+        let x = g.placeholder("x", &[2, 3]);
+        assert_eq!(x.device(), Device::Cpu);
+
+        let x_gpu = T::to_device(x, Device::Gpu);
+        assert_eq!(x_gpu.device(), Device::Gpu);
+        // Tensors on the host are used where they are needed
+        let y = T::relu(x_gpu) * T::scalar(2., g);
+        assert_eq!(y.device(), Device::Gpu);
+        assert_eq!(T::to_host(y).device(), Device::Cpu);
+        assert_eq!((x * T::scalar(2., g)).device(), Device::Cpu);
+    });
+}
+
+#[test]
+fn test_gpu_results_match_cpu() {
+    ag::run(|g: &mut ag::Context<f32>| {
+        let a = T::convert_to_tensor(array![[0.5f32, -1.0, 2.0], [1.5, 0.25, -0.5]], g);
+        let b = T::convert_to_tensor(array![[1.0f32, 2.0], [-3.0, 0.5], [0.25, -1.0]], g);
+        let c = T::convert_to_tensor(array![[2.0f32, 0.0, -1.0], [1.0, 3.0, 0.5]], g);
+        let ops = |a, b, c| {
+            vec![
+                T::matmul(a, b),
+                T::matmul(T::transpose(b, &[1, 0]), T::transpose(a, &[1, 0])),
+                a + c,
+                a - c,
+                T::relu(a),
+                T::sigmoid(a),
+                T::tanh(a),
+                T::softmax(a, -1),
+                T::sum_all(a),
+                T::mean_all(a),
+            ]
+        };
+        let cpu = ops(a, b, c);
+        let gpu: Vec<_> = ops(
+            T::to_device(a, Device::Gpu),
+            T::to_device(b, Device::Gpu),
+            T::to_device(c, Device::Gpu),
+        )
+        .into_iter()
+        .map(T::to_host)
+        .collect();
+
+        let cpu = g.evaluator().extend(&cpu).run();
+        let gpu = g.evaluator().extend(&gpu).run();
+        for (x, y) in cpu.into_iter().zip(gpu) {
+            let (x, y) = (x.unwrap(), y.unwrap());
+            assert_eq!(x.shape(), y.shape());
+            for (x, y) in x.iter().zip(&y) {
+                assert_relative_eq!(x, y, epsilon = 1e-5);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_gradients_through_transfers() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[2, 3]);
+        let w = g.placeholder("w", &[3, 1]);
+        let xw = T::matmul(T::to_device(x, Device::Gpu), T::to_device(w, Device::Gpu));
+        let y = T::sum_all(T::to_host(T::tanh(xw)));
+        let grads = T::grad(&[y], &[x, w]);
+        assert_eq!(grads[0].device(), Device::Cpu);
+        assert_eq!(grads[1].device(), Device::Cpu);
+
+        let reference = T::grad(&[T::sum_all(T::tanh(T::matmul(x, w)))], &[x, w]);
+        let x_val = array![[0.5, -1.0, 2.0], [1.5, 0.25, -0.5]];
+        let w_val = array![[0.1], [0.2], [-0.3]];
+        let results = g
+            .evaluator()
+            .extend(&grads)
+            .extend(&reference)
+            .feed(x, x_val.view().into_dyn())
+            .feed(w, w_val.view().into_dyn())
+            .run();
+        assert_eq!(results[0].as_ref().unwrap(), results[2].as_ref().unwrap());
+        assert_eq!(results[1].as_ref().unwrap(), results[3].as_ref().unwrap());
+    });
+}
+
+#[test]
+fn test_serialization_keeps_placement() {
+    ag::run(|g: &mut ag::Context<f32>| {
+        let x = g.placeholder("x", &[2]);
+        let y = T::sigmoid(T::to_device(x, Device::Gpu));
+        let z = T::to_host(y) + x;
+        let graph = SerializedGraph::from_tensors(g, &[("y", &y), ("z", &z)]).unwrap();
+        let outputs = graph.build(g, &OpRegistry::new()).unwrap();
+        assert_eq!(outputs["y"].device(), Device::Gpu);
+        assert_eq!(outputs["z"].device(), Device::Cpu);
+    });
+}
+
+#[cfg(feature = "gpu")]
+mod gpu_tests {
+    use super::*;
+    use ag::device::Gpu;
+    use ag::op::{ComputeContext, GradientContext, Op, OpError};
+
+    /// Negates its input on the GPU and doubles it on the CPU.
+    struct Marked;
+
+    impl<F: ag::Float> Op<F> for Marked {
+        fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+            let y = ctx.input(0).mapv(|a| a + a);
+            ctx.append_output(y);
+            Ok(())
+        }
+
+        fn compute_gpu(&self, ctx: &mut ComputeContext<F>, _gpu: &Gpu) -> Result<bool, OpError> {
+            let y = ctx.input(0).mapv(|a| -a);
+            ctx.append_output(y);
+            Ok(true)
+        }
+
+        fn grad(&self, ctx: &mut GradientContext<F>) {
+            ctx.append_input_grad(0, None);
+        }
+    }
+
+    #[test]
+    fn test_custom_gpu_kernel() {
+        ag::run(|g: &mut ag::Context<f32>| {
+            let x = T::convert_to_tensor(array![1f32, 2.], g);
+            let on = |x| ag::Tensor::builder(g).append_input(x, false).build(Marked);
+            let cpu = on(x).eval(g).unwrap();
+            let gpu = on(T::to_device(x, Device::Gpu)).eval(g).unwrap();
+            assert_eq!(cpu, array![2f32, 4.].into_dyn());
+            assert_eq!(gpu, array![-1f32, -2.].into_dyn());
+        });
+    }
+
+    #[test]
+    fn test_gpu_backend() {
+        // The CPU backend has no kernels, so every op falls back
+        ag::device::use_gpu_backend(ag::device::GpuBackend::Cpu).unwrap();
+        assert!(!Device::Gpu.is_available());
+        let kernel = ag::device::with_gpu(|gpu| gpu.kernel("relu").unwrap().is_none());
+        assert_eq!(kernel, Some(true));
+    }
+}
@@ -279,6 +279,118 @@ mod enhanced_linalg_tests {
         });
     }
 
+    #[test]
+    fn test_einsum_trace_outer_and_batched() {
+        ag::run(|g| {
+            let a = convert_to_tensor(array![[1.0_f64, 2.0], [3.0, 4.0]], g);
+            let tr = einsum("ii->", &[&a]).eval(g).unwrap();
+            assert_relative_eq!(tr[ndarray::IxDyn(&[])], 5.0, epsilon = 1e-12);
+            let d = einsum("ii->i", &[&a]).eval(g).unwrap();
+            assert_eq!(d.as_slice().unwrap(), &[1.0, 4.0]);
+            let at = einsum("ij", &[&a]).eval(g).unwrap();
+            assert_eq!(at, a.eval(g).unwrap());
+            let at = einsum("ji", &[&a]).eval(g).unwrap();
+            assert_eq!(at[[0, 1]], 3.0);
+
+            let u = convert_to_tensor(array![1.0_f64, 2.0], g);
+            let v = convert_to_tensor(array![3.0_f64, 4.0, 5.0], g);
+            let outer = einsum("i,j->ij", &[&u, &v]).eval(g).unwrap();
+            assert_eq!(outer.shape(), &[2, 3]);
+            assert_eq!(outer[[1, 2]], 10.0);
+
+            let x = ndarray::Array::from_shape_fn((3, 2, 4), |(b, i, j)| (b + 2 * i + j) as f64);
+            let y = ndarray::Array::from_shape_fn((3, 4, 5), |(b, j, k)| (b * j) as f64 - k as f64);
+            let z = einsum(
+                "bij,bjk->bik",
+                &[
+                    &convert_to_tensor(x.clone(), g),
+                    &convert_to_tensor(y.clone(), g),
+                ],
+            )
+            .eval(g)
+            .unwrap();
+            assert_eq!(z.shape(), &[3, 2, 5]);
+            for b in 0..3 {
+                let expected = x
+                    .index_axis(ndarray::Axis(0), b)
+                    .dot(&y.index_axis(ndarray::Axis(0), b));
+                let actual = z.index_axis(ndarray::Axis(0), b);
+                for (e, a) in expected.iter().zip(actual.iter()) {
+                    assert_relative_eq!(e, a, epsilon = 1e-12);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_einsum_chain_and_gradients() {
+        ag::run(|g| {
+            let a_val = ndarray::Array::from_shape_fn((2, 3), |(i, j)| (i + j) as f64 * 0.5 - 1.0);
+            let b_val = ndarray::Array::from_shape_fn((3, 4), |(i, j)| (i * j) as f64 * 0.25 + 0.1);
+            let c_val = ndarray::Array::from_shape_fn((4, 2), |(i, j)| i as f64 - j as f64);
+            let a = g.placeholder("a", &[2, 3]);
+            let b = g.placeholder("b", &[3, 4]);
+            let c = g.placeholder("c", &[4, 2]);
+
+            let y = einsum("ij,jk,kl->il", &[&a, &b, &c]);
+            let expected = matmul(matmul(a, b), c);
+            let mut outputs = vec![y, expected];
+            outputs.extend(grad(&[sum_all(y * y)], &[a, b, c]));
+            outputs.extend(grad(&[sum_all(expected * expected)], &[a, b, c]));
+            let results = g
+                .evaluator()
+                .extend(&outputs)
+                .feed(a, a_val.view().into_dyn())
+                .feed(b, b_val.view().into_dyn())
+                .feed(c, c_val.view().into_dyn())
+                .run();
+            let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+            assert_eq!(results[0].shape(), &[2, 2]);
+            for (value, expected) in [(0, 1), (2, 5), (3, 6), (4, 7)] {
+                assert_eq!(results[value].shape(), results[expected].shape());
+                for (v, e) in results[value].iter().zip(results[expected].iter()) {
+                    assert_relative_eq!(v, e, epsilon = 1e-10);
+                }
+            }
+
+            // d tr(M) / dM = I and d sum(u v^T) / du = sum(v)
+            let m = g.placeholder("m", &[2, 2]);
+            let u = g.placeholder("u", &[2]);
+            let v = g.placeholder("v", &[3]);
+            let gm = grad(&[einsum("ii->", &[&m])], &[m])[0];
+            let gu = grad(&[sum_all(einsum("i,j->ij", &[&u, &v]))], &[u])[0];
+            let m_val = array![[1.0_f64, 2.0], [3.0, 4.0]].into_dyn();
+            let u_val = array![1.0_f64, 2.0].into_dyn();
+            let v_val = array![3.0_f64, 4.0, 5.0].into_dyn();
+            let results = g
+                .evaluator()
+                .extend(&[gm, gu])
+                .feed(m, m_val.view())
+                .feed(u, u_val.view())
+                .feed(v, v_val.view())
+                .run();
+            assert_eq!(
+                results[0].as_ref().unwrap(),
+                &array![[1.0, 0.0], [0.0, 1.0]].into_dyn()
+            );
+            assert_eq!(
+                results[1].as_ref().unwrap().as_slice().unwrap(),
+                &[12.0, 12.0]
+            );
+        });
+    }
+
+    #[test]
+    fn test_einsum_path() {
+        // (A B) C keeps the intermediate small when C has few columns
+        let path = einsum_path("ij,jk,kl->il", &[&[10, 1000], &[1000, 5], &[5, 2]]);
+        assert_eq!(path, vec![(0, 1), (0, 1)]);
+        // A (B C) when A has many rows
+        let path = einsum_path("ij,jk,kl->il", &[&[1000, 5], &[5, 1000], &[1000, 2]]);
+        assert_eq!(path, vec![(1, 2), (0, 1)]);
+        assert!(einsum_path("ij->i", &[&[2, 3]]).is_empty());
+    }
+
     #[test]
     fn test_kronecker_product() {
         ag::run(|g| {
@@ -244,6 +244,106 @@ mod optimization_tests {
     }
 }
 
+/// Test the optimization passes on real graphs
+#[cfg(test)]
+mod optimization_pass_tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_folding_cse_and_fusion() {
+        ag::run(|g: &mut ag::Context<f64>| {
+            let x = g.placeholder("x", &[3]);
+            let c = T::exp(T::scalar(0.5, g)) * 2.0;
+            let y = T::tanh(T::sin(x * c)) + T::tanh(T::sin(x * c));
+            let gx = T::grad(&[T::sum_all(y)], &[x])[0];
+
+            let x_val = array![0.3, -1.2, 2.0].into_dyn();
+            let expected = g.evaluator().extend(&[y, gx]).feed(x, x_val.view()).run();
+
+            let optimizer = GraphOptimizer::with_level(OptimizationLevel::Aggressive).inspect(true);
+            let report = optimizer.optimize_tensors(&[y, gx]).unwrap();
+            assert!(report.constant_folding_applied > 0);
+            assert!(report.cse_applied >= 3);
+            assert!(report.operations_fused > 0);
+            let listing = report.optimized_graph.unwrap();
+            assert!(listing.contains("FusedElementwise"));
+            assert!(listing.contains("ConvertToTensor"));
+            assert!(!listing.contains("Exp("));
+
+            let results = g.evaluator().extend(&[y, gx]).feed(x, x_val.view()).run();
+            for (result, expected) in results.iter().zip(&expected) {
+                let (result, expected) = (result.as_ref().unwrap(), expected.as_ref().unwrap());
+                for (a, b) in result.iter().zip(expected.iter()) {
+                    assert!((a - b).abs() < 1e-12);
+                }
+            }
+
+            // Nothing is left to optimize
+            assert!(!optimizer
+                .optimize_tensors(&[y, gx])
+                .unwrap()
+                .has_optimizations());
+        });
+    }
+
+    #[test]
+    fn test_gradient_of_fused_chain() {
+        ag::run(|g: &mut ag::Context<f64>| {
+            let x = g.placeholder("x", &[2]);
+            let y = T::sigmoid(T::exp(T::sin(x)));
+            let report = GraphOptimizer::with_level(OptimizationLevel::Aggressive)
+                .optimize(g)
+                .unwrap();
+            assert_eq!(report.operations_fused, 2);
+
+            // Differentiated after the fusion, twice
+            let gx = T::grad(&[T::sum_all(y)], &[x])[0];
+            let ggx = T::grad(&[T::sum_all(gx)], &[x])[0];
+            let x_val = array![0.4, -0.9].into_dyn();
+            let results = g.evaluator().extend(&[gx, ggx]).feed(x, x_val.view()).run();
+            let (gx, ggx) = (results[0].as_ref().unwrap(), results[1].as_ref().unwrap());
+
+            let f = |v: f64| 1.0 / (1.0 + (-v.sin().exp()).exp());
+            let df = |v: f64| {
+                let s = f(v);
+                s * (1.0 - s) * v.sin().exp() * v.cos()
+            };
+            let h = 1e-5;
+            for (i, &v) in x_val.iter().enumerate() {
+                assert!((gx[i] - df(v)).abs() < 1e-10);
+                assert!((ggx[i] - (df(v + h) - df(v - h)) / (2.0 * h)).abs() < 1e-6);
+            }
+        });
+    }
+
+    #[test]
+    fn test_evaluator_optimization() {
+        ag::run(|g: &mut ag::Context<f32>| {
+            let x = g.placeholder("x", &[2]);
+            let noise = T::standard_normal(&[2], g);
+            let y = T::relu(T::neg(x)) + T::exp(noise) * T::sqrt(T::scalar(4.0, g));
+
+            // Random ops are never folded
+            let report = GraphOptimizer::with_level(OptimizationLevel::Basic)
+                .optimize_tensors(&[y])
+                .unwrap();
+            assert_eq!(report.constant_folding_applied, 1);
+
+            let x_val = array![-1.0f32, 2.0].into_dyn();
+            let results = g
+                .evaluator()
+                .push(&y)
+                .feed(x, x_val.view())
+                .optimize(GraphOptimizer::with_level(OptimizationLevel::Aggressive))
+                .run();
+            let y_val = results[0].as_ref().unwrap();
+            assert!(y_val[0] > 1.0);
+            assert!(y_val[1] > 0.0);
+        });
+    }
+}
+
 /// Test integration of optimization and visualization
 #[cfg(test)]
 mod integration_tests {
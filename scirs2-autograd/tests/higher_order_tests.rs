@@ -0,0 +1,220 @@
+//! Tests for Hessian-vector products and gradients of gradients
+
+use ag::ndarray::{array, Array1, Array2};
+use ag::tensor_ops as T;
+use scirs2_autograd as ag;
+
+const EPSILON: f64 = 1e-6;
+
+fn assert_all_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, epsilon: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!(
+            (a - e).abs() < epsilon,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+}
+
+/// Evaluates `t` with `x` fed by `x_val`
+fn eval_at<'g>(
+    ctx: &'g ag::Context<f64>,
+    t: &ag::Tensor<'g, f64>,
+    x: &ag::Tensor<'g, f64>,
+    x_val: &ag::NdArray<f64>,
+) -> ag::NdArray<f64> {
+    ctx.evaluator()
+        .push(t)
+        .feed(*x, x_val.view())
+        .run()
+        .remove(0)
+        .unwrap()
+}
+
+#[test]
+fn test_second_derivative_of_polynomial() {
+    ag::run::<f64, _, _>(|ctx| {
+        let x = ctx.placeholder("x", &[]);
+        // y = x^3 + 2x^2, y'' = 6x + 4
+        let y = x * x * x + 2. * x * x;
+        let gx = T::grad(&[y], &[x])[0];
+        let ggx = T::grad(&[gx], &[x])[0];
+        let gggx = T::grad(&[ggx], &[x])[0];
+
+        let x_val = ag::ndarray::arr0(1.5).into_dyn();
+        assert_all_close(
+            &eval_at(ctx, &ggx, &x, &x_val),
+            &ag::ndarray::arr0(13.).into_dyn(),
+            EPSILON,
+        );
+        assert_all_close(
+            &eval_at(ctx, &gggx, &x, &x_val),
+            &ag::ndarray::arr0(6.).into_dyn(),
+            EPSILON,
+        );
+    });
+}
+
+#[test]
+fn test_hvp_of_matmul() {
+    ag::run::<f64, _, _>(|ctx| {
+        let a_val = array![[1., 2., 0.], [-1., 3., 1.], [0.5, 0., 2.]];
+        let a = T::convert_to_tensor(a_val.clone(), ctx);
+        let x = ctx.placeholder("x", &[3, 1]);
+        let v_val = array![[1.], [-2.], [0.5]];
+        let v = T::convert_to_tensor(v_val.clone(), ctx);
+
+        // loss = |A x|^2, H = 2 A^T A
+        let loss = T::sum_all(T::square(T::matmul(a, x)));
+        let hv = T::hvp(loss, &[x], &[v])[0];
+
+        let x_val = array![[0.3], [0.1], [-0.7]].into_dyn();
+        let expected = a_val.t().dot(&a_val).dot(&v_val) * 2.;
+        assert_all_close(
+            &eval_at(ctx, &hv, &x, &x_val),
+            &expected.into_dyn(),
+            EPSILON,
+        );
+    });
+}
+
+#[test]
+fn test_hvp_of_bilinear_matmul() {
+    ag::run::<f64, _, _>(|ctx| {
+        let w = ctx.placeholder("w", &[2, 2]);
+        let v_val = array![[1., 0.], [2., -1.]];
+        let v = T::convert_to_tensor(v_val.clone(), ctx);
+
+        // loss = sum(W W); the Hessian does not depend on W:
+        // H v = V^T 1 + 1 V^T where 1 is the matrix of ones
+        let loss = T::sum_all(T::matmul(w, w));
+        let hv = T::hvp(loss, &[w], &[v])[0];
+
+        let w_val = array![[0.5, -1.], [2., 1.5]].into_dyn();
+        let ones = Array2::<f64>::ones((2, 2));
+        let expected = v_val.t().dot(&ones) + ones.dot(&v_val.t());
+        assert_all_close(
+            &eval_at(ctx, &hv, &w, &w_val),
+            &expected.into_dyn(),
+            EPSILON,
+        );
+    });
+}
+
+#[test]
+fn test_hvp_of_norms() {
+    ag::run::<f64, _, _>(|ctx| {
+        let x = ctx.placeholder("x", &[3]);
+        let v_val = array![0.5, -1., 2.];
+        let v = T::convert_to_tensor(v_val.clone(), ctx);
+        let x_val: Array1<f64> = array![1., 2., -2.];
+        let r = x_val.dot(&x_val).sqrt();
+
+        // Squared norm: H = 2I
+        let sq = T::sum_all(T::square(x));
+        let hv = T::hvp(sq, &[x], &[v])[0];
+        assert_all_close(
+            &eval_at(ctx, &hv, &x, &x_val.clone().into_dyn()),
+            &(&v_val * 2.).into_dyn(),
+            EPSILON,
+        );
+
+        // Euclidean norm: H = (I - x x^T / r^2) / r
+        let expected = (&v_val - &(&x_val * (x_val.dot(&v_val) / (r * r)))) / r;
+        for norm in [T::l2_norm(x, &[0], false), T::frobenius_norm(x)] {
+            let hv = T::hvp(norm, &[x], &[v])[0];
+            assert_all_close(
+                &eval_at(ctx, &hv, &x, &x_val.clone().into_dyn()),
+                &expected.clone().into_dyn(),
+                EPSILON,
+            );
+        }
+    });
+}
+
+/// Derivative of an activation function
+type Derivative = fn(f64) -> f64;
+
+fn sigmoid(x: f64) -> f64 {
+    1. / (1. + (-x).exp())
+}
+
+#[test]
+fn test_second_derivatives_of_activations() {
+    let xs = array![-1.5, -0.3, 0.4, 2.];
+    let cases: [(&str, Derivative); 6] = [
+        ("sigmoid", |x| {
+            let s = sigmoid(x);
+            s * (1. - s) * (1. - 2. * s)
+        }),
+        ("tanh", |x| -2. * x.tanh() * (1. - x.tanh().powi(2))),
+        ("softplus", |x| sigmoid(x) * (1. - sigmoid(x))),
+        ("relu", |_| 0.),
+        ("elu", |x| if x > 0. { 0. } else { x.exp() }),
+        ("swish", |x| {
+            let s = sigmoid(x);
+            s * (1. - s) * (2. + x * (1. - 2. * s))
+        }),
+    ];
+
+    for (name, second_derivative) in cases {
+        ag::run::<f64, _, _>(|ctx| {
+            let x = ctx.placeholder("x", &[4]);
+            let y = match name {
+                "sigmoid" => T::sigmoid(x),
+                "tanh" => T::tanh(x),
+                "softplus" => T::softplus(x),
+                "relu" => T::relu(x),
+                "elu" => T::elu(x, 1.),
+                "swish" => T::swish(x),
+                _ => unreachable!(),
+            };
+            let gx = T::grad(&[y], &[x])[0];
+            let ggx = T::grad(&[gx], &[x])[0];
+
+            let actual = eval_at(ctx, &ggx, &x, &xs.clone().into_dyn());
+            let expected = xs.mapv(second_derivative).into_dyn();
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert!(
+                    (a - e).abs() < EPSILON,
+                    "{}: expected {:?}, got {:?}",
+                    name,
+                    expected,
+                    actual
+                );
+            }
+        });
+    }
+}
+
+#[test]
+fn test_hvp_through_slice() {
+    ag::run::<f64, _, _>(|ctx| {
+        // loss = x0^2 x1, H = [[2 x1, 2 x0], [2 x0, 0]]
+        let x = ctx.placeholder("x", &[2]);
+        let loss = T::square(T::slice(x, &[0], &[1])) * T::slice(x, &[1], &[2]);
+        let x_val = array![3., 2.].into_dyn();
+        for (v_val, expected) in [
+            (array![1., 1.], array![10., 6.]),
+            (array![1., -2.], array![-8., 6.]),
+        ] {
+            let v = T::convert_to_tensor(v_val, ctx);
+            let hv = T::hvp(loss, &[x], &[v])[0];
+            assert_eq!(eval_at(ctx, &hv, &x, &x_val), expected.into_dyn());
+        }
+
+        // loss = sum of the squares of the last two columns, H = 2 on them
+        let w = ctx.placeholder("w", &[2, 3]);
+        let loss = T::sum_all(T::square(T::slice(w, &[0, 1], &[2, 3])));
+        let v_val = array![[1., -2., 0.5], [3., 0.25, -1.]];
+        let v = T::convert_to_tensor(v_val, ctx);
+        let hv = T::hvp(loss, &[w], &[v])[0];
+        let w_val = array![[0.5, -1., 2.], [1., 1.5, -0.5]].into_dyn();
+        assert_eq!(
+            eval_at(ctx, &hv, &w, &w_val),
+            array![[0., -4., 1.], [0., 0.5, -2.]].into_dyn()
+        );
+    });
+}
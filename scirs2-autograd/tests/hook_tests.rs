@@ -0,0 +1,110 @@
+use ag::tensor_ops as T;
+use ag::{EvalError, OpError};
+use ndarray::array;
+use scirs2_autograd as ag;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_grad_hooks() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[3]);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_in_hook = seen.clone();
+        // d/dh sum(h^2) = 2h, clipped to [-1, 1] before flowing into h = 3x
+        let h = (x * 3.)
+            .map_grad(|g| g.mapv(|a| a.clamp(-1., 1.)))
+            .grad_hook(move |g| seen_in_hook.lock().unwrap().push(g.to_owned()));
+        let y = T::sum_all(T::square(h));
+        let gx = T::grad(&[y], &[x])[0];
+
+        let results = g
+            .evaluator()
+            .extend(&[y, gx])
+            .feed(x, array![0.125, -1.0, 2.0].view().into_dyn())
+            .run();
+        // the forward pass is unchanged
+        assert_eq!(results[0].as_ref().unwrap()[[]], 0.140625 + 9. + 36.);
+        assert_eq!(results[1], Ok(array![2.25, -3.0, 3.0].into_dyn()));
+        // the inspecting hook sees the gradient before it is clipped
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0], array![0.75, -6.0, 12.0].into_dyn());
+    });
+}
+
+#[test]
+fn test_grad_hook_shape_mismatch() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[2]);
+        let y = T::sum_all(x.map_grad(|_| ndarray::arr1(&[1.0]).into_dyn()));
+        let gx = T::grad(&[y], &[x])[0];
+        let result = g
+            .evaluator()
+            .feed(x, array![1.0, 2.0].view().into_dyn())
+            .eval(gx);
+        assert!(matches!(
+            result,
+            Err(EvalError::OpError(OpError::IncompatibleShape(_)))
+        ));
+    });
+}
+
+#[test]
+fn test_check_numerics_provenance() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[2]);
+        let y = T::sum_all(T::sqrt(x));
+        let gx = T::grad(&[y], &[x])[0];
+        let feed = array![0.0, 4.0];
+
+        // off by default
+        let result = g.evaluator().feed(x, feed.view().into_dyn()).eval(gx);
+        assert!(result.unwrap()[0].is_infinite());
+
+        g.check_numerics(true);
+        // the forward pass is finite
+        let result = g.evaluator().feed(x, feed.view().into_dyn()).eval(y);
+        assert_eq!(result, Ok(ndarray::arr0(2.0).into_dyn()));
+        // the gradient of sqrt, x^(-1/2) / 2, is infinite at 0
+        let result = g.evaluator().feed(x, feed.view().into_dyn()).eval(gx);
+        match result {
+            Err(EvalError::OpError(OpError::NonFinite { op, tensor, detail })) => {
+                assert!(op.contains("Pow"), "{}", op);
+                assert!(tensor > y.id());
+                assert!(detail.starts_with("0 NaN and 1 infinite"), "{}", detail);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // non-finite feeds are reported as such
+        let result = g
+            .evaluator()
+            .feed(x, array![f64::NAN, 1.0].view().into_dyn())
+            .eval(y);
+        match result {
+            Err(EvalError::OpError(OpError::NonFinite { op, tensor, .. })) => {
+                assert_eq!(op, "feed");
+                assert_eq!(tensor, x.id());
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    });
+}
+
+#[test]
+fn test_debug_logging_is_opt_in() {
+    let called = std::cell::Cell::new(0);
+    ag::tracing::debug_message("test", || {
+        called.set(called.get() + 1);
+        String::new()
+    });
+    assert_eq!(called.get(), 0);
+
+    ag::tracing::set_debug_logging(true);
+    ag::tracing::debug_message("test", || {
+        called.set(called.get() + 1);
+        "message".to_string()
+    });
+    ag::tracing::set_debug_logging(false);
+    assert_eq!(called.get(), 1);
+}
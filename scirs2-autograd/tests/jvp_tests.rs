@@ -0,0 +1,124 @@
+use ag::op::{ComputeContext, GradientContext, Op, OpError};
+use ag::tensor_ops as T;
+use ag::Tensor;
+use approx::assert_relative_eq;
+use ndarray::array;
+use scirs2_autograd as ag;
+
+fn assert_close(a: &ag::NdArray<f64>, b: &ag::NdArray<f64>) {
+    assert_eq!(a.shape(), b.shape());
+    for (a, b) in a.iter().zip(b) {
+        assert_relative_eq!(a, b, epsilon = 1e-10);
+    }
+}
+
+/// R^3 -> R^2, through ops with and without forward-mode rules
+fn model<'g>(x: Tensor<'g, f64>, w: Tensor<'g, f64>) -> Tensor<'g, f64> {
+    let h = T::tanh(T::matmul(T::reshape(x, &[1, 3]), w));
+    let p = T::softmax(T::sigmoid(h) * T::exp(h) - T::sin(h), 1);
+    let q = T::sqrt(T::square(h) + 1.) / (T::ln(p) - 2.);
+    T::reshape(T::transpose(q, &[1, 0]), &[2]) + T::sum_all(T::relu(x))
+}
+
+#[test]
+fn test_jvp_matches_reverse_mode() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[3]);
+        let w = g.placeholder("w", &[3, 2]);
+        let y = model(x, w);
+        let t_x = T::convert_to_tensor(array![0.5, -1.0, 2.0], g);
+        let t_w = T::convert_to_tensor(array![[1.0, 0.0], [0.5, -0.5], [0.0, 2.0]], g);
+        let jt = T::jvp(&[y], &[x, w], &[t_x, t_w])[0];
+        let jt_x = T::jvp(&[y], &[x], &[t_x])[0];
+
+        // Same products with the Jacobians of reverse mode
+        let js = T::jacobians(y, &[x, w], 2);
+        let expected =
+            T::matmul(js[0], T::reshape(t_x, &[3, 1])) + T::matmul(js[1], T::reshape(t_w, &[6, 1]));
+        let expected_x = T::matmul(js[0], T::reshape(t_x, &[3, 1]));
+
+        let results = g
+            .evaluator()
+            .extend(&[
+                jt,
+                jt_x,
+                T::reshape(expected, &[2]),
+                T::reshape(expected_x, &[2]),
+            ])
+            .feed(x, array![1.0, -2.0, 0.3].view().into_dyn())
+            .feed(
+                w,
+                array![[0.5, -1.0], [1.5, 0.25], [-0.5, 2.0]]
+                    .view()
+                    .into_dyn(),
+            )
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert!(results[0].iter().all(|&a| a != 0.));
+        assert_close(&results[0], &results[2]);
+        assert_close(&results[1], &results[3]);
+    });
+}
+
+#[test]
+fn test_jacobians_of_both_modes() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[3]);
+        let w = T::convert_to_tensor(array![[0.5, -1.0], [1.5, 0.25], [-0.5, 2.0]], g);
+        let y = model(x, w);
+        let jacs = [
+            T::jacfwd(y, x),
+            T::jacrev(y, x),
+            T::jacobian(y, x),
+            T::jacobians(y, &[x], 2)[0],
+        ];
+
+        let results = g
+            .evaluator()
+            .extend(&jacs)
+            .feed(x, array![1.0, -2.0, 0.3].view().into_dyn())
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(results[0].shape(), &[2, 3]);
+        assert!(results[0].iter().all(|&a| a != 0.));
+        for j in &results[1..] {
+            assert_close(&results[0], j);
+        }
+    });
+}
+
+/// `x^3` without a forward-mode rule
+struct Cube;
+
+impl<F: ag::Float> Op<F> for Cube {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let y = ctx.input(0).mapv(|a| a * a * a);
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let x = ctx.input(0);
+        let gx = ctx.output_grad() * T::square(x) * F::from(3.).unwrap();
+        ctx.append_input_grad(0, Some(gx));
+    }
+}
+
+#[test]
+fn test_op_without_rule() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[2]);
+        let y = Tensor::builder(g).append_input(x, false).build(Cube);
+        let t = T::convert_to_tensor(array![1.0, -2.0], g);
+        let jt = T::jvp(&[y, T::stop_gradient(y)], &[x], &[t]);
+
+        let results = g
+            .evaluator()
+            .extend(&jt)
+            .feed(x, array![2.0, 0.5].view().into_dyn())
+            .run();
+        // 3 x^2 t
+        assert_eq!(results[0], Ok(array![12.0, -1.5].into_dyn()));
+        assert_eq!(results[1], Ok(array![0.0, 0.0].into_dyn()));
+    });
+}
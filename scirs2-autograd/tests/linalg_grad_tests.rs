@@ -0,0 +1,128 @@
+use ag::tensor_ops as T;
+use ag::Tensor;
+use approx::assert_relative_eq;
+use ndarray::array;
+use scirs2_autograd as ag;
+
+/// Checks the gradient of the scalar `y` in `x` against central differences,
+/// with `fixed` fed as well.
+fn check_grad(
+    g: &ag::Context<f64>,
+    y: Tensor<f64>,
+    x: Tensor<f64>,
+    at: &ag::NdArray<f64>,
+    fixed: &[(Tensor<f64>, ag::NdArray<f64>)],
+) {
+    let eval = |t: Tensor<f64>, x_value: &ag::NdArray<f64>| {
+        let mut evaluator = g.evaluator();
+        for (p, value) in fixed {
+            evaluator = evaluator.feed(*p, value.view());
+        }
+        evaluator.feed(x, x_value.view()).eval(t).unwrap()
+    };
+    // in logical order, the gradients may be transposed views
+    let gx: Vec<f64> = eval(T::grad(&[y], &[x])[0], at).iter().copied().collect();
+    assert!(gx.iter().any(|&a| a != 0.));
+    let h = 1e-6;
+    for (i, &analytical) in gx.iter().enumerate() {
+        let mut plus = at.clone();
+        plus.as_slice_mut().unwrap()[i] += h;
+        let mut minus = at.clone();
+        minus.as_slice_mut().unwrap()[i] -= h;
+        let numerical = (eval(y, &plus)[[]] - eval(y, &minus)[[]]) / (2. * h);
+        assert_relative_eq!(analytical, numerical, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_solve_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let a = g.placeholder("a", &[3, 3]);
+        let b = g.placeholder("b", &[3]);
+        let c = g.placeholder("c", &[3, 2]);
+        let a_value = array![[4.0, 1.0, -1.0], [2.0, 5.0, 0.5], [0.0, -1.0, 3.0]].into_dyn();
+        let b_value = array![1.0, -2.0, 0.5].into_dyn();
+        let c_value = array![[1.0, 0.0], [-2.0, 1.0], [0.5, 3.0]].into_dyn();
+        let w = T::convert_to_tensor(array![1.0, -1.0, 2.0], g);
+        let w2 = T::convert_to_tensor(array![[1.0, 0.5], [-1.0, 2.0], [0.25, -3.0]], g);
+
+        // vector right-hand side
+        let y = T::sum_all(T::solve(a, b) * w);
+        check_grad(g, y, a, &a_value, &[(b, b_value.clone())]);
+        check_grad(g, y, b, &b_value, &[(a, a_value.clone())]);
+
+        // matrix right-hand side
+        let y = T::sum_all(T::solve(a, c) * w2);
+        check_grad(g, y, a, &a_value, &[(c, c_value.clone())]);
+        check_grad(g, y, c, &c_value, &[(a, a_value.clone())]);
+
+        let x = g
+            .evaluator()
+            .feed(a, a_value.view())
+            .feed(b, b_value.view())
+            .eval(T::matmul(a, T::reshape(T::solve(a, b), &[3, 1])))
+            .unwrap();
+        for (x, b) in x.iter().zip(&b_value) {
+            assert_relative_eq!(x, b, epsilon = 1e-12);
+        }
+    });
+}
+
+#[test]
+fn test_cholesky_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        // symmetric positive definite A = M M^T + 3I
+        let m = g.placeholder("m", &[3, 3]);
+        let a = T::matmul(m, T::transpose(m, &[1, 0])) + T::eye(3, g) * 3.;
+        let l = T::cholesky(&a);
+        let w = T::convert_to_tensor(
+            array![[1.0, 0.0, 0.0], [-2.0, 0.5, 0.0], [0.25, 3.0, -1.0]],
+            g,
+        );
+        let m_value = array![[1.0, 0.5, -1.0], [0.0, 2.0, 1.0], [-0.5, 0.25, 1.5]].into_dyn();
+        check_grad(g, T::sum_all(l * w), m, &m_value, &[]);
+        check_grad(g, T::sum_all(T::ln(T::square(l) + 1.)), m, &m_value, &[]);
+
+        let results = g
+            .evaluator()
+            .extend(&[a, T::matmul(l, T::transpose(l, &[1, 0])), l])
+            .feed(m, m_value.view())
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        for (a, llt) in results[0].iter().zip(&results[1]) {
+            assert_relative_eq!(a, llt, epsilon = 1e-12);
+        }
+        assert_eq!(results[2][[0, 1]], 0.);
+    });
+}
+
+#[test]
+fn test_lu_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let a = g.placeholder("a", &[3, 3]);
+        let (p, l, u) = T::lu(&a);
+        // the first column needs pivoting
+        let a_value = array![[0.5, 2.0, 1.0], [3.0, -1.0, 0.5], [-1.0, 1.0, 4.0]].into_dyn();
+        let w = T::convert_to_tensor(
+            array![[1.0, -2.0, 0.5], [0.25, 3.0, -1.0], [2.0, 1.0, -0.5]],
+            g,
+        );
+        check_grad(g, T::sum_all(l * w), a, &a_value, &[]);
+        check_grad(g, T::sum_all(u * w), a, &a_value, &[]);
+        check_grad(g, T::sum_all(T::sin(l) + T::square(u)), a, &a_value, &[]);
+
+        let results = g
+            .evaluator()
+            .extend(&[p, T::matmul(p, a), T::matmul(l, u)])
+            .feed(a, a_value.view())
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(
+            results[0],
+            array![[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]].into_dyn()
+        );
+        for (pa, lu) in results[1].iter().zip(&results[2]) {
+            assert_relative_eq!(pa, lu, epsilon = 1e-12);
+        }
+    });
+}
@@ -100,20 +100,27 @@ fn test_svd_decomposition() {
         assert_eq!(s_val.shape(), &[2]);
         assert_eq!(v_val.shape(), &[2, 2]);
 
-        // TODO: SVD implementation is currently a placeholder that returns identity matrices
-        // Skip reconstruction test until proper SVD is implemented
-        /*
         // Verify reconstruction: A ≈ U * diag(S) * V^T
-        let s_diag = diag(s);
-        let reconstructed = matmul(matmul(u, &s_diag), &transpose(v, &[1, 0]));
+        let reconstructed = matmul(matmul(u, diag(s)), transpose(v, &[1, 0]));
         let reconstructed_val = reconstructed.eval(g).unwrap();
-
+        let a_val = a.eval(g).unwrap();
         for i in 0..3 {
             for j in 0..2 {
-                assert_relative_eq!(reconstructed_val[[i, j]], a.eval(g).unwrap()[[i, j]], epsilon = 1e-5);
+                assert_relative_eq!(reconstructed_val[[i, j]], a_val[[i, j]], epsilon = 1e-10);
+            }
+        }
+
+        // Singular values are sorted and the singular vectors orthonormal
+        assert!(s_val[0] >= s_val[1]);
+        let utu = matmul(transpose(u, &[1, 0]), u).eval(g).unwrap();
+        let vtv = matmul(transpose(v, &[1, 0]), v).eval(g).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(utu[[i, j]], expected, epsilon = 1e-10);
+                assert_relative_eq!(vtv[[i, j]], expected, epsilon = 1e-10);
             }
         }
-        */
     });
 }
 
@@ -383,3 +390,81 @@ fn test_batch_operations() {
         assert_relative_eq!(c_val[[1, 1, 1]], 8.0, epsilon = 1e-6);
     });
 }
+
+#[test]
+fn test_batch_matmul_broadcasting() {
+    ag::run(|g| {
+        let a_val = ndarray::Array::from_shape_fn((2, 1, 3, 4), |(i, _, j, k)| {
+            (i * 12 + j * 4 + k) as f64 * 0.1 - 1.0
+        });
+        let b_val =
+            ndarray::Array::from_shape_fn((5, 4, 2), |(i, j, k)| (i * 8 + j * 2 + k) as f64 * 0.05);
+        let a = convert_to_tensor(a_val.clone(), g);
+        let b = convert_to_tensor(b_val.clone(), g);
+
+        let c = batch_matmul(a, b).eval(g).unwrap();
+        assert_eq!(c.shape(), &[2, 5, 3, 2]);
+        for i in 0..2 {
+            for j in 0..5 {
+                let a_ij: ndarray::ArrayView2<f64> = a_val.slice(ndarray::s![i, 0, .., ..]);
+                let b_j: ndarray::ArrayView2<f64> = b_val.slice(ndarray::s![j, .., ..]);
+                let expected = a_ij.dot(&b_j);
+                let actual = c.slice(ndarray::s![i, j, .., ..]);
+                for (x, e) in actual.iter().zip(expected.iter()) {
+                    assert_relative_eq!(*x, *e, epsilon = 1e-12);
+                }
+            }
+        }
+
+        // Transposed operands broadcast the same way
+        let at = convert_to_tensor(a_val.clone().permuted_axes([0, 1, 3, 2]), g);
+        let bt = convert_to_tensor(b_val.clone().permuted_axes([0, 2, 1]), g);
+        let ct = batch_matmul_t(at, bt, true, true).eval(g).unwrap();
+        assert_eq!(ct, c);
+
+        // Incompatible batch axes
+        let d = convert_to_tensor(ndarray::Array4::<f64>::zeros((3, 1, 4, 2)), g);
+        assert!(batch_matmul(a, d).eval(g).is_err());
+    });
+}
+
+#[test]
+fn test_batch_matmul_broadcasting_gradients() {
+    ag::run(|g| {
+        let a_val = ndarray::Array::from_shape_fn((3, 2, 4), |(i, j, k)| {
+            ((i * 8 + j * 4 + k) as f64 * 0.37).sin()
+        });
+        let b_val =
+            ndarray::Array::from_shape_fn((4, 5), |(j, k)| ((j * 5 + k) as f64 * 0.21).cos());
+        let w_val = ndarray::Array::from_shape_fn((3, 2, 5), |(i, j, k)| (i + 2 * j + k) as f64);
+        let a = variable(a_val.clone(), g);
+        let b = variable(b_val.clone(), g);
+        let w = convert_to_tensor(w_val.clone(), g);
+
+        // loss = sum(A_i B * W_i), so dA_i = W_i B^T and dB = sum_i A_i^T W_i
+        let loss = sum_all(batch_matmul(a, b) * w);
+        let grads = grad(&[loss], &[a, b]);
+        let ga = grads[0].eval(g).unwrap();
+        let gb = grads[1].eval(g).unwrap();
+        assert_eq!(ga.shape(), &[3, 2, 4]);
+        assert_eq!(gb.shape(), &[4, 5]);
+
+        let mut expected_gb = ndarray::Array2::<f64>::zeros((4, 5));
+        for i in 0..3 {
+            let a_i: ndarray::ArrayView2<f64> = a_val.slice(ndarray::s![i, .., ..]);
+            let w_i: ndarray::ArrayView2<f64> = w_val.slice(ndarray::s![i, .., ..]);
+            let expected_ga = w_i.dot(&b_val.t());
+            for (x, e) in ga
+                .slice(ndarray::s![i, .., ..])
+                .iter()
+                .zip(expected_ga.iter())
+            {
+                assert_relative_eq!(*x, *e, epsilon = 1e-10);
+            }
+            expected_gb += &a_i.t().dot(&w_i);
+        }
+        for (x, e) in gb.iter().zip(expected_gb.iter()) {
+            assert_relative_eq!(*x, *e, epsilon = 1e-10);
+        }
+    });
+}
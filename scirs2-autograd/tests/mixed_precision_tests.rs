@@ -0,0 +1,138 @@
+use ag::mixed_precision::{bf16, cast, f16, LossScaler};
+use ag::prelude::*;
+use ag::tensor_ops as T;
+use ndarray::array;
+use scirs2_autograd as ag;
+
+#[test]
+fn test_f32_accumulation() {
+    // 2048 + 1 rounds back to 2048 in f16
+    ag::run(|g: &mut ag::Context<f16>| {
+        let n = 4096;
+        let row = T::ones(&[1, n], g);
+        let col = T::ones(&[n, 1], g);
+        let expected = f16::from_f32(4096.);
+        let results = g
+            .evaluator()
+            .push(&T::matmul(row, col))
+            .push(&T::sum_all(row))
+            .push(&T::reduce_sum(row, &[1], false))
+            .push(&T::reduce_mean(
+                row * T::scalar(f16::from_f32(3.), g),
+                &[0, 1],
+                false,
+            ))
+            .push(&T::batch_matmul(
+                T::ones(&[2, 1, n], g),
+                T::ones(&[2, n, 1], g),
+            ))
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(results[0], array![[expected]].into_dyn());
+        assert_eq!(results[1], ndarray::arr0(expected).into_dyn());
+        assert_eq!(results[2], array![expected].into_dyn());
+        assert_eq!(results[3], ndarray::arr0(f16::from_f32(3.)).into_dyn());
+        assert!(results[4].iter().all(|&a| a == expected));
+    });
+}
+
+#[test]
+fn test_bf16_gradients() {
+    ag::run(|g: &mut ag::Context<bf16>| {
+        let x = g.placeholder("x", &[2, 3]);
+        let w = g.placeholder("w", &[3, 1]);
+        let y = T::reduce_sum(T::tanh(T::matmul(x, w)), &[0, 1], false);
+        let gw = T::grad(&[y], &[w])[0];
+
+        let x_val = array![[0.5f32, -1.0, 2.0], [1.5, 0.25, -0.5]].into_dyn();
+        let w_val = array![[0.1f32], [0.2], [-0.3]].into_dyn();
+        let (x16, w16) = (cast::<f32, bf16>(&x_val.view()), cast(&w_val.view()));
+        let gw = g
+            .evaluator()
+            .push(&gw)
+            .feed(x, x16.view())
+            .feed(w, w16.view())
+            .run()
+            .remove(0)
+            .unwrap();
+
+        // Same gradient in f32, up to the precision of bf16
+        let xw = x_val
+            .into_dimensionality::<ndarray::Ix2>()
+            .unwrap()
+            .dot(&w_val.into_dimensionality::<ndarray::Ix2>().unwrap());
+        for (j, &gj) in gw.iter().enumerate() {
+            let expected: f32 = (0..2)
+                .map(|i| (1. - xw[[i, 0]].tanh().powi(2)) * x16[[i, j]].to_f32())
+                .sum();
+            assert!((gj.to_f32() - expected).abs() < 2e-2);
+        }
+    });
+}
+
+#[test]
+fn test_loss_scaler_schedule() {
+    let mut scaler = LossScaler::new().with_scale(1024.).with_growth_interval(2);
+    scaler.update(true);
+    assert_eq!(scaler.loss_scale(), 1024.);
+    scaler.update(true);
+    assert_eq!(scaler.loss_scale(), 2048.);
+    scaler.update(false);
+    assert_eq!(scaler.loss_scale(), 1024.);
+    assert_eq!(scaler.skipped_steps(), 1);
+
+    let mut grads = vec![array![1024f32, -2048.].into_dyn()];
+    assert!(scaler.unscale(&mut grads));
+    assert_eq!(grads[0], array![1f32, -2.].into_dyn());
+    let mut grads = vec![array![1f32, f32::INFINITY].into_dyn()];
+    assert!(!scaler.unscale(&mut grads));
+
+    let mut fixed = LossScaler::fixed(8.);
+    fixed.update(false);
+    assert_eq!(fixed.loss_scale(), 8.);
+}
+
+#[test]
+fn test_loss_scaled_training_skips_overflow() {
+    let mut env = ag::VariableEnvironment::<f16>::new();
+    let w_init = cast(&array![[0.5f32], [-0.25]].into_dyn().view());
+    let w_id = env.name("w").set(w_init.clone());
+    let x = cast::<f32, f16>(&array![[1.0f32, 2.0], [-1.0, 0.5]].into_dyn().view());
+    let lr = f16::from_f32(0.1);
+
+    // 2^17 and 2^16 are out of the range of f16, so the first steps overflow
+    let mut scaler = LossScaler::new().with_scale(131072.);
+    let mut updated = vec![];
+    for _ in 0..4 {
+        env.run(|g| {
+            let w = g.variable("w");
+            let xw = T::matmul(g.placeholder("x", &[-1, 2]), w);
+            let loss = T::reduce_mean(T::square(xw), &[0, 1], false);
+            let grads = T::grad(&[scaler.scale(loss)], &[w]);
+            let feeder = ag::Feeder::new().push("x", x.view());
+            let grads = scaler.unscaled_grads(&grads, g, feeder);
+            updated.push(grads.is_some());
+            if let Some(grads) = grads {
+                let mut w = g.env().get_array_by_id(w_id).unwrap().borrow_mut();
+                w.zip_mut_with(&grads[0], |w, &gw| *w -= lr * gw);
+            }
+        });
+    }
+    assert_eq!(updated, vec![false, false, true, true]);
+    assert_eq!(scaler.loss_scale(), 32768.);
+    assert_eq!(scaler.skipped_steps(), 2);
+
+    // Only finite updates were applied and the loss went down
+    let w = env.get_array_by_id(w_id).unwrap().borrow().clone();
+    assert!(w.iter().all(|a| a.is_finite()));
+    let loss = |w: &ag::NdArray<f16>| {
+        let w = cast::<f16, f32>(&w.view())
+            .into_dimensionality::<ndarray::Ix2>()
+            .unwrap();
+        let x = cast::<f16, f32>(&x.view())
+            .into_dimensionality::<ndarray::Ix2>()
+            .unwrap();
+        x.dot(&w).mapv(|a| a * a).mean().unwrap()
+    };
+    assert!(loss(&w) < loss(&w_init));
+}
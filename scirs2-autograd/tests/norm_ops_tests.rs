@@ -222,3 +222,102 @@ fn test_norm_gradient_stability() {
         );
     });
 }
+
+/// Central finite difference gradient of `f` at `a`
+fn finite_difference(f: impl Fn(&Array2<f64>) -> f64, a: &Array2<f64>) -> Array2<f64> {
+    let h = 1e-6;
+    let mut grad = Array2::zeros(a.dim());
+    for ((i, j), g) in grad.indexed_iter_mut() {
+        let (mut plus, mut minus) = (a.clone(), a.clone());
+        plus[[i, j]] += h;
+        minus[[i, j]] -= h;
+        *g = (f(&plus) - f(&minus)) / (2. * h);
+    }
+    grad
+}
+
+/// Checks the gradient of `loss(A)` against finite differences for a non-diagonal `A`
+fn check_matrix_gradient<L>(a_val: Array2<f64>, loss: L)
+where
+    L: for<'g, 'env> Fn(&'g ag::Context<'env, f64>, ag::Tensor<'g, f64>) -> ag::Tensor<'g, f64>,
+{
+    let (m, n) = a_val.dim();
+    let eval = |a_val: &Array2<f64>| {
+        ag::run::<f64, _, _>(|ctx| {
+            let a = ctx.placeholder("a", &[m as isize, n as isize]);
+            ctx.evaluator()
+                .push(&loss(ctx, a))
+                .feed(a, a_val.view().into_dyn())
+                .run()
+                .remove(0)
+                .unwrap()[[]]
+        })
+    };
+    let expected = finite_difference(eval, &a_val);
+
+    ag::run::<f64, _, _>(|ctx| {
+        let a = ctx.placeholder("a", &[m as isize, n as isize]);
+        let grad = T::grad(&[loss(ctx, a)], &[a])[0];
+        let actual = ctx
+            .evaluator()
+            .push(&grad)
+            .feed(a, a_val.view().into_dyn())
+            .run()
+            .remove(0)
+            .unwrap();
+        assert_eq!(actual.shape(), expected.shape());
+        for (x, e) in actual.iter().zip(expected.iter()) {
+            assert!(
+                is_close(*x, *e, EPSILON),
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    });
+}
+
+#[test]
+fn test_spectral_and_nuclear_norm_gradients() {
+    let square = array![[2.0, -1.0, 0.5], [0.3, 1.5, -0.7], [1.0, 0.2, 3.0]];
+    let wide = array![[1.0, 2.0, -0.5], [0.4, -1.2, 2.5]];
+    for a_val in [square, wide.clone(), wide.reversed_axes()] {
+        check_matrix_gradient(a_val.clone(), |_, a| T::spectral_norm(&a));
+        check_matrix_gradient(a_val, |_, a| T::nuclear_norm(&a));
+    }
+}
+
+#[test]
+fn test_svd_gradients() {
+    let a_val = array![
+        [2.0, -1.0, 0.5],
+        [0.3, 1.5, -0.7],
+        [1.0, 0.2, 3.0],
+        [0.6, -0.4, 0.1]
+    ];
+    let weights = array![
+        [0.5, -1.0, 2.0],
+        [1.5, 0.3, -0.8],
+        [-0.2, 0.7, 1.1],
+        [0.9, -0.6, 0.4]
+    ];
+
+    // Singular values
+    check_matrix_gradient(a_val.clone(), |ctx, a| {
+        let (_, s, _) = T::linear_algebra::svd(a);
+        T::sum_all(s * T::convert_to_tensor(array![1.0, -2.0, 0.5], ctx))
+    });
+    // Singular vectors, made sign invariant through U diag(S) V^T products
+    check_matrix_gradient(a_val.clone(), |ctx, a| {
+        let (u, s, v) = T::linear_algebra::svd(a);
+        let u_s = T::matmul(u, T::diag(s));
+        let w = T::convert_to_tensor(weights.clone(), ctx);
+        T::sum_all(T::matmul(u_s, T::transpose(v, &[1, 0])) * w)
+            + T::sum_all(T::square(T::matmul(T::transpose(u, &[1, 0]), w)))
+    });
+    check_matrix_gradient(a_val, |ctx, a| {
+        let (_, _, v) = T::linear_algebra::svd(a);
+        let w = T::convert_to_tensor(array![[1.0, 0.5, -0.3], [0.2, -1.0, 0.8]], ctx);
+        T::sum_all(T::square(T::matmul(w, v)))
+    });
+}
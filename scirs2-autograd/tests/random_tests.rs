@@ -0,0 +1,164 @@
+use ag::ndarray_ext::ArrayRng;
+use ag::tensor_ops as T;
+use ag::Tensor;
+use approx::assert_relative_eq;
+use ndarray::array;
+use scirs2_autograd as ag;
+
+/// Checks the gradient of the scalar `y` against central differences in `x`.
+///
+/// The noise of the random ops is fixed in the graph, so `y` is a deterministic
+/// function of the fed values.
+fn check_grad(g: &ag::Context<f64>, y: Tensor<f64>, x: Tensor<f64>, at: &ag::NdArray<f64>) {
+    let gx = T::grad(&[y], &[x])[0];
+    let gx = g.evaluator().feed(x, at.view()).eval(gx).unwrap();
+    assert!(gx.iter().any(|&a| a != 0.));
+    let h = 1e-6;
+    for i in 0..at.len() {
+        let mut plus = at.clone();
+        plus.as_slice_mut().unwrap()[i] += h;
+        let mut minus = at.clone();
+        minus.as_slice_mut().unwrap()[i] -= h;
+        let y_plus = g.evaluator().feed(x, plus.view()).eval(y).unwrap()[[]];
+        let y_minus = g.evaluator().feed(x, minus.view()).eval(y).unwrap()[[]];
+        let numerical = (y_plus - y_minus) / (2. * h);
+        assert_relative_eq!(gx.as_slice().unwrap()[i], numerical, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_sample_normal_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        // [mean, log_var] of a VAE-style latent variable
+        let p = g.placeholder("p", &[2, 3]);
+        let mean = T::slice(p, &[0, 0], &[1, -1]);
+        let log_var = T::slice(p, &[1, 0], &[2, -1]);
+        let z = T::sample_normal_rng(ArrayRng::from_seed(1), mean, T::exp(log_var * 0.5));
+        let y = T::sum_all(T::sin(z) + z * z);
+        let at = array![[0.5, -1.0, 0.0], [0.0, -1.0, 0.5]].into_dyn();
+        check_grad(g, y, p, &at);
+
+        // z = mean + stddev * eps with the same eps for every evaluation
+        let first = g.evaluator().feed(p, at.view()).eval(z).unwrap();
+        let again = g.evaluator().feed(p, at.view()).eval(z).unwrap();
+        assert_eq!(first, again);
+    });
+}
+
+#[test]
+fn test_sample_normal_is_seedable() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let mean = T::zeros(&[1000], g);
+        let stddev = T::ones(&[1000], g) * 2.;
+        let a = T::sample_normal_rng(ArrayRng::from_seed(7), mean, stddev);
+        let b = T::sample_normal_rng(ArrayRng::from_seed(7), mean, stddev);
+        let c = T::sample_normal_rng(ArrayRng::from_seed(8), mean, stddev);
+        let results: Vec<_> = g
+            .evaluator()
+            .extend(&[a, b, c])
+            .run()
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(results[0], results[1]);
+        assert_ne!(results[0], results[2]);
+        let variance = results[0].mapv(|a| a * a).mean().unwrap();
+        assert_relative_eq!(variance, 4., epsilon = 0.5);
+    });
+}
+
+#[test]
+fn test_sample_uniform_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let p = g.placeholder("p", &[2, 2]);
+        let min = T::slice(p, &[0, 0], &[1, -1]);
+        let max = T::slice(p, &[1, 0], &[2, -1]);
+        let u = T::sample_uniform_rng(ArrayRng::from_seed(3), min, max);
+        let y = T::sum_all(T::exp(u));
+        check_grad(g, y, p, &array![[-1.0, 0.5], [2.0, 1.5]].into_dyn());
+
+        let u = g
+            .evaluator()
+            .feed(p, array![[-1.0, 0.5], [2.0, 1.5]].view().into_dyn())
+            .eval(u)
+            .unwrap();
+        assert!(u[[0, 0]] >= -1. && u[[0, 0]] < 2.);
+        assert!(u[[0, 1]] >= 0.5 && u[[0, 1]] < 1.5);
+    });
+}
+
+#[test]
+fn test_dropout() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[100]);
+        let y = T::dropout_rng(x, 0.3, true, ArrayRng::<f64>::from_seed(5));
+        let gx = T::grad(&[T::sum_all(y * 2.)], &[x])[0];
+        let y_eval = T::dropout(x, 0.3, false);
+        let feed = ndarray::Array1::linspace(1., 2., 100).into_dyn();
+
+        let results: Vec<_> = g
+            .evaluator()
+            .extend(&[y, gx, y_eval])
+            .feed(x, feed.view())
+            .run()
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect();
+        let kept = results[0].iter().filter(|&&a| a != 0.).count();
+        assert!(kept > 50 && kept < 90, "{}", kept);
+        for ((y, gx), x) in results[0].iter().zip(&results[1]).zip(&feed) {
+            // the kept elements and their gradients pass through, the others are zeroed
+            if *y == 0. {
+                assert_eq!(*gx, 0.);
+            } else {
+                assert_eq!(y, x);
+                assert_eq!(*gx, 2.);
+            }
+        }
+        assert_eq!(results[2], feed.mapv(|a| a * 0.7));
+        check_grad(g, T::sum_all(T::square(y)), x, &feed);
+    });
+}
+
+#[test]
+fn test_gumbel_softmax() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let logits = g.placeholder("logits", &[4, 3]);
+        let soft = T::gumbel_softmax_rng(ArrayRng::from_seed(11), logits, 0.5, 1, false);
+        let hard = T::gumbel_softmax_rng(ArrayRng::from_seed(11), logits, 0.5, 1, true);
+        let w = T::convert_to_tensor(array![[1.0, -2.0, 3.0]], g);
+        let at = array![
+            [0.0, 1.0, -1.0],
+            [2.0, 0.5, 0.0],
+            [-1.0, -1.0, 3.0],
+            [0.3, 0.2, 0.1]
+        ]
+        .into_dyn();
+        check_grad(g, T::sum_all(soft * w), logits, &at);
+
+        let grads = [
+            T::grad(&[T::sum_all(soft * w)], &[logits])[0],
+            T::grad(&[T::sum_all(hard * w)], &[logits])[0],
+        ];
+        let results: Vec<_> = g
+            .evaluator()
+            .extend(&[soft, hard, grads[0], grads[1]])
+            .feed(logits, at.view())
+            .run()
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect();
+        for (soft, hard) in results[0].outer_iter().zip(results[1].outer_iter()) {
+            assert_relative_eq!(soft.sum(), 1., epsilon = 1e-12);
+            // the hard sample is the one-hot argmax of the soft one with the same noise
+            let argmax = (0..3)
+                .max_by(|&i, &j| soft[i].partial_cmp(&soft[j]).unwrap())
+                .unwrap();
+            for (i, &h) in hard.iter().enumerate() {
+                assert_eq!(h, if i == argmax { 1. } else { 0. });
+            }
+        }
+        // straight-through gradient
+        assert_eq!(results[2], results[3]);
+    });
+}
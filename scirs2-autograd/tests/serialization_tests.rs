@@ -0,0 +1,140 @@
+use ag::op::{ComputeContext, GradientContext, Op, OpDef, OpError};
+use ag::prelude::*;
+use ag::serialization::{OpRegistry, SerializationError, SerializedGraph};
+use ag::tensor_ops as T;
+use approx::assert_relative_eq;
+use ndarray::array;
+use scirs2_autograd as ag;
+use serde::{Deserialize, Serialize};
+
+/// Adds `offset` to its input.
+#[derive(Serialize, Deserialize)]
+struct Offset {
+    offset: f64,
+}
+
+impl<F: ag::Float> Op<F> for Offset {
+    fn op_def(&self) -> Option<OpDef> {
+        OpDef::new("Offset", self)
+    }
+
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let offset = F::from(self.offset).unwrap();
+        let y = ctx.input(0).mapv(|x| x + offset);
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        ctx.append_input_grad(0, Some(*ctx.output_grad()));
+    }
+}
+
+#[test]
+fn test_save_and_load_model() {
+    let dir = std::env::temp_dir().join("scirs2-autograd-serialization");
+    std::fs::create_dir_all(&dir).unwrap();
+    let (graph_path, vars_path) = (dir.join("model.json"), dir.join("variables.json"));
+
+    let mut env = ag::VariableEnvironment::new();
+    env.name("w")
+        .set(array![[0.5, -1.0], [2.0, 0.25], [-0.5, 1.5]]);
+    env.name("b").set(array![[0.1, -0.2]]);
+    let x_val = array![[1.0, 2.0, 3.0], [-1.0, 0.5, 0.0]].into_dyn();
+
+    let expected = env.run(|g| {
+        let x = g.placeholder("x", &[-1, 3]);
+        let h = T::matmul(x, g.variable("w")) + g.variable("b");
+        let y = T::softmax(T::tanh(h) * 2.0, 1);
+        let loss = T::reduce_mean(T::square(y), &[0, 1], false);
+        SerializedGraph::from_tensors(g, &[("y", &y), ("loss", &loss)])
+            .unwrap()
+            .save(&graph_path)
+            .unwrap();
+        let results = g.evaluator().extend(&[y, loss]).feed(x, x_val.view()).run();
+        results.into_iter().map(|r| r.unwrap()).collect::<Vec<_>>()
+    });
+    env.save(&vars_path).unwrap();
+
+    let env = ag::VariableEnvironment::<f64>::load(&vars_path).unwrap();
+    let graph = SerializedGraph::load(&graph_path).unwrap();
+    assert_eq!(graph.output_names().collect::<Vec<_>>(), vec!["y", "loss"]);
+    env.run(|g| {
+        let outputs = graph.build(g, &OpRegistry::new()).unwrap();
+        let (y, loss) = (outputs["y"], outputs["loss"]);
+        let results = g
+            .evaluator()
+            .extend(&[y, loss])
+            .feed("x", x_val.view())
+            .run();
+        for (result, expected) in results.iter().zip(&expected) {
+            let result = result.as_ref().unwrap();
+            assert_eq!(result.shape(), expected.shape());
+            for (a, b) in result.iter().zip(expected.iter()) {
+                assert_relative_eq!(a, b, epsilon = 1e-12);
+            }
+        }
+
+        // The loaded graph is differentiable
+        let gw = T::grad(&[loss], &[g.variable("w")])[0];
+        let gw = g.evaluator().push(&gw).feed("x", x_val.view()).run();
+        assert_eq!(gw[0].as_ref().unwrap().shape(), &[3, 2]);
+    });
+}
+
+#[test]
+fn test_json_round_trip_with_constants() {
+    let json = ag::run(|g: &mut ag::Context<f64>| {
+        let a = T::convert_to_tensor(array![[1.0, 2.0], [3.0, 4.0]], g);
+        let b = T::einsum("ij,jk->ik", &[&a, &T::transpose(a, &[1, 0])]);
+        let c = T::reduce_sum(b, &[0], false) + T::scalar(1.0, g);
+        let graph = SerializedGraph::from_tensors(g, &[("c", &c)]).unwrap();
+        serde_json::to_string(&graph).unwrap()
+    });
+
+    let graph: SerializedGraph = serde_json::from_str(&json).unwrap();
+    ag::run(|g: &mut ag::Context<f32>| {
+        // Graphs are portable across float types
+        let c = graph.build(g, &OpRegistry::new()).unwrap()["c"];
+        // [[5, 11], [11, 25]] summed over the rows, plus one
+        assert_eq!(c.eval(g).unwrap(), array![17.0f32, 37.0].into_dyn());
+    });
+}
+
+#[test]
+fn test_custom_op_registration() {
+    let graph = ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[2]);
+        let y = ag::Tensor::builder(g)
+            .append_input(x, false)
+            .build(Offset { offset: 1.5 });
+        SerializedGraph::from_tensors(g, &[("y", &y)]).unwrap()
+    });
+
+    ag::run(|g: &mut ag::Context<f64>| {
+        assert!(matches!(
+            graph.build(g, &OpRegistry::new()),
+            Err(SerializationError::UnknownOpKind(kind)) if kind == "Offset"
+        ));
+
+        let mut registry = OpRegistry::new();
+        registry.register::<Offset>("Offset");
+        assert!(registry.contains("Offset"));
+        let y = graph.build(g, &registry).unwrap()["y"];
+        let x_val = array![1.0, -2.0].into_dyn();
+        let y_val = g.evaluator().push(&y).feed("x", x_val.view()).run();
+        assert_eq!(y_val[0].as_ref().unwrap(), &array![2.5, -0.5].into_dyn());
+    });
+}
+
+#[test]
+fn test_unsupported_op() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = g.placeholder("x", &[4]);
+        let y = T::slice(x, &[1], &[3]);
+        assert!(matches!(
+            SerializedGraph::from_tensors(g, &[("y", &y)]),
+            Err(SerializationError::UnsupportedOp(_))
+        ));
+    });
+}
@@ -0,0 +1,141 @@
+use ag::sparse::{self, SparseFormat, SparseTensor};
+use ag::tensor_ops as T;
+use ndarray::{array, Axis};
+use scirs2_autograd as ag;
+
+#[test]
+fn test_formats_and_matmul() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let a_val = array![[0., 2., 0.], [1., 0., 3.], [0., 0., 0.], [4., 0., 0.]];
+        let b_val = array![[1., -1.], [0.5, 2.], [-2., 1.]];
+        let b = T::convert_to_tensor(b_val.clone(), g);
+        let expected = a_val.dot(&b_val).into_dyn();
+
+        let csr = SparseTensor::from_dense(&a_val.view(), SparseFormat::Csr, g);
+        let coo = SparseTensor::from_dense(&a_val.view(), SparseFormat::Coo, g);
+        // Unsorted and duplicate coordinates
+        let unsorted = SparseTensor::coo(
+            T::convert_to_tensor(array![3., 1., 0., 1., 1.], g),
+            T::convert_to_tensor(array![0., 2., 1., 0., 2.], g),
+            T::convert_to_tensor(array![4., 1., 2., 1., 2.], g),
+            [4, 3],
+        );
+        assert_eq!(csr.to_coo().format(), SparseFormat::Coo);
+        assert_eq!(coo.to_csr().format(), SparseFormat::Csr);
+
+        for x in [
+            csr,
+            coo,
+            unsorted,
+            csr.to_coo(),
+            coo.to_csr(),
+            unsorted.to_csr(),
+        ] {
+            assert_eq!(x.to_dense().eval(g), Ok(a_val.clone().into_dyn()));
+            assert_eq!(sparse::matmul(x, b).eval(g), Ok(expected.clone()));
+        }
+        let at = csr.transpose();
+        assert_eq!(at.shape(), [3, 4]);
+        assert_eq!(at.to_dense().eval(g), Ok(a_val.t().to_owned().into_dyn()));
+
+        let indptr = coo.to_csr().indices().0.eval(g).unwrap();
+        assert_eq!(indptr, array![0., 1., 3., 3., 4.].into_dyn());
+    });
+}
+
+#[test]
+fn test_invalid_indices() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let x = SparseTensor::coo(
+            T::convert_to_tensor(array![0., 5.], g),
+            T::convert_to_tensor(array![0., 1.], g),
+            T::convert_to_tensor(array![1., 1.], g),
+            [2, 2],
+        );
+        assert!(x.to_dense().eval(g).is_err());
+        let x = SparseTensor::csr(
+            T::convert_to_tensor(array![0., 2.], g),
+            T::convert_to_tensor(array![0., 1.], g),
+            T::convert_to_tensor(array![1., 1.], g),
+            [2, 2],
+        );
+        assert!(x.to_dense().eval(g).is_err());
+    });
+}
+
+#[test]
+fn test_matmul_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let a_val = array![[0., 2., 0.], [1., 0., 3.]];
+        let pattern = SparseTensor::from_dense(&a_val.view(), SparseFormat::Csr, g);
+        let v = g.placeholder("v", &[3]);
+        let b = g.placeholder("b", &[3, 2]);
+        let a = pattern.with_values(v);
+
+        let y = T::sum_all(T::square(sparse::matmul(a, b)));
+        let dense = T::sum_all(T::square(T::matmul(a.to_dense(), b)));
+        let grads = T::grad(&[y], &[v, b]);
+        let dense_grads = T::grad(&[dense], &[v, b]);
+        let second = T::grad(&[T::sum_all(grads[0])], &[b]);
+        let dense_second = T::grad(&[T::sum_all(dense_grads[0])], &[b]);
+
+        let v_val = array![2., 1., 3.];
+        let b_val = array![[1., -1.], [0.5, 2.], [-2., 1.]];
+        let results = g
+            .evaluator()
+            .extend(&grads)
+            .extend(&dense_grads)
+            .extend(&second)
+            .extend(&dense_second)
+            .feed(v, v_val.view().into_dyn())
+            .feed(b, b_val.view().into_dyn())
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(results[0], results[2]);
+        assert_eq!(results[1], results[3]);
+        assert_eq!(results[4], results[5]);
+    });
+}
+
+#[test]
+fn test_embedding_grad_rows() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let table = g.placeholder("table", &[5, 2]);
+        let ids = T::convert_to_tensor(array![[1., 3.], [1., 0.]], g);
+        let other_ids = T::convert_to_tensor(array![3.], g);
+        let e = sparse::embedding_lookup(table, ids);
+        let w = T::convert_to_tensor(array![[1., 2.], [3., 4.]], g);
+        let loss = T::sum_all(T::matmul(T::reshape(e, &[4, 2]), w))
+            + T::sum_all(T::square(sparse::embedding_lookup(table, other_ids)));
+
+        let rows = sparse::grad_rows(&[loss], table);
+        let dense = T::grad(&[loss], &[table])[0];
+        let table_val = ndarray::Array::from_shape_fn((5, 2), |(i, j)| (i + j) as f64).into_dyn();
+
+        let feeder = ag::Feeder::new().push(table, table_val.view());
+        let rows = rows.eval_with(g, feeder).unwrap();
+        assert_eq!(rows.indices, vec![1, 3, 1, 0, 3]);
+        assert_eq!(rows.values.shape(), &[5, 2]);
+        let dense = g
+            .evaluator()
+            .push(&dense)
+            .feed(table, table_val.view())
+            .run()
+            .remove(0)
+            .unwrap();
+        assert_eq!(rows.to_dense(), dense);
+
+        let coalesced = rows.coalesce();
+        assert_eq!(coalesced.indices, vec![0, 1, 3]);
+        assert_eq!(coalesced.to_dense(), dense);
+
+        // A gradient descent step only touches the looked-up rows
+        let mut updated = table_val.clone();
+        coalesced.scaled_add_to(-0.5, &mut updated);
+        assert_eq!(updated, &table_val - &(dense * 0.5));
+        assert_eq!(
+            updated.index_axis(Axis(0), 2),
+            table_val.index_axis(Axis(0), 2)
+        );
+    });
+}
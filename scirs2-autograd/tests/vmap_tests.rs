@@ -0,0 +1,175 @@
+use ag::op::{ComputeContext, GradientContext, Op, OpError};
+use ag::tensor_ops as T;
+use ag::Tensor;
+use approx::assert_relative_eq;
+use ndarray::{array, Axis};
+use scirs2_autograd as ag;
+
+fn assert_close(a: &ag::NdArray<f64>, b: &ag::NdArray<f64>) {
+    assert_eq!(a.shape(), b.shape());
+    for (a, b) in a.iter().zip(b) {
+        assert_relative_eq!(a, b, epsilon = 1e-10);
+    }
+}
+
+/// Evaluates `f` for each row of `xs` and stacks the results.
+fn looped<'g>(
+    g: &'g ag::Context<'g, f64>,
+    f: impl Fn(Tensor<'g, f64>) -> Tensor<'g, f64>,
+    xs: &ag::NdArray<f64>,
+) -> ag::NdArray<f64> {
+    let ys: Vec<_> = xs
+        .outer_iter()
+        .map(|x| f(T::convert_to_tensor(x.to_owned(), g)).eval(g).unwrap())
+        .collect();
+    let views: Vec<_> = ys.iter().map(|y| y.view()).collect();
+    ndarray::stack(Axis(0), &views).unwrap()
+}
+
+fn model<'g>(x: Tensor<'g, f64>, w: Tensor<'g, f64>, b: Tensor<'g, f64>) -> Tensor<'g, f64> {
+    let h = T::tanh(T::matmul(T::expand_dims(x, &[0]), w) + b);
+    let h = T::reshape(h, &[2, 1]) * T::transpose(T::softmax(h, 1), &[1, 0]);
+    T::reduce_sum(h, &[1], false) / T::sum_all(x) + T::mean_all(x)
+}
+
+fn loss_grad<'g>(x: Tensor<'g, f64>, w: Tensor<'g, f64>) -> Tensor<'g, f64> {
+    let y = T::sigmoid(T::matmul(T::reshape(x, &[1, 3]), w));
+    let loss = T::sum_all(T::square(y - 0.5));
+    T::grad(&[loss], &[w])[0]
+}
+
+#[test]
+fn test_matches_loop() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let w = T::convert_to_tensor(array![[0.5, -1.0], [1.5, 0.25], [-0.5, 2.0]], g);
+        let b = T::convert_to_tensor(array![0.1, -0.2], g);
+        let f = |x| model(x, w, b);
+        let xs_val = array![
+            [1.0, 2.0, 0.5],
+            [-1.0, 0.3, 0.7],
+            [0.2, 0.4, 3.0],
+            [2.0, 1.0, 1.0]
+        ]
+        .into_dyn();
+        let xs = T::convert_to_tensor(xs_val.clone(), g);
+        let ys = T::vmap(|x| vec![f(x[0])])(&[xs])[0];
+
+        assert_close(&ys.eval(g).unwrap(), &looped(g, f, &xs_val));
+    });
+}
+
+#[test]
+fn test_per_example_gradients() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let w = T::variable(array![[0.5, -1.0], [1.5, 0.25], [-0.5, 2.0]], g);
+        let grad = |x| loss_grad(x, w);
+        let xs_val = array![[1.0, 2.0, 0.5], [-1.0, 0.3, 0.7], [0.2, 0.4, 3.0]].into_dyn();
+        let xs = g.placeholder("xs", &[-1, 3]);
+        let grads = T::vmap(|x| vec![grad(x[0])])(&[xs])[0];
+
+        let grads = g
+            .evaluator()
+            .push(&grads)
+            .feed(xs, xs_val.view())
+            .run()
+            .remove(0)
+            .unwrap();
+        assert_eq!(grads.shape(), &[3, 3, 2]);
+        assert!(grads.iter().all(|&a| a != 0.));
+        assert_close(&grads, &looped(g, grad, &xs_val));
+    });
+}
+
+#[test]
+fn test_in_axes_and_gradients_through_vmap() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let w = g.placeholder("w", &[2, 3]);
+        let xs = g.placeholder("xs", &[3, -1]);
+        // Examples along the columns of `xs`, with the same `w` for all of them
+        let f = T::vmap_with_axes(
+            |args| vec![T::relu(T::matmul(args[1], T::reshape(args[0], &[3, 1])))],
+            &[Some(1), None],
+        );
+        let ys = f(&[xs, w])[0];
+        let loss = T::sum_all(T::square(ys));
+        let grads = T::grad(&[loss], &[w, xs]);
+
+        // Same as a matrix product
+        let dense = T::transpose(T::relu(T::matmul(w, xs)), &[1, 0]);
+        let dense_loss = T::sum_all(T::square(dense));
+        let dense_grads = T::grad(&[dense_loss], &[w, xs]);
+
+        let w_val = array![[0.5, -1.0, 2.0], [1.5, 0.25, -0.5]].into_dyn();
+        let xs_val = array![
+            [1.0, -2.0, 0.5, 1.0],
+            [-1.0, 0.3, 0.7, 2.0],
+            [0.2, 0.4, 3.0, -1.0]
+        ]
+        .into_dyn();
+        let results = g
+            .evaluator()
+            .push(&T::reshape(ys, &[4, 2]))
+            .push(&dense)
+            .extend(&grads)
+            .extend(&dense_grads)
+            .feed(w, w_val.view())
+            .feed(xs, xs_val.view())
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_close(&results[0], &results[1]);
+        assert_close(&results[2], &results[4]);
+        assert_close(&results[3], &results[5]);
+    });
+}
+
+/// `x^3` without a batching rule
+struct Cube;
+
+impl<F: ag::Float> Op<F> for Cube {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let y = ctx.input(0).mapv(|a| a * a * a);
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let x = ctx.input(0);
+        let gx = ctx.output_grad() * T::square(x) * F::from(3.).unwrap();
+        ctx.append_input_grad(0, Some(gx));
+    }
+}
+
+#[test]
+fn test_op_without_rule() {
+    ag::run(|g: &mut ag::Context<f64>| {
+        let scale = g.placeholder("scale", &[]);
+        let xs = g.placeholder("xs", &[-1, 2]);
+        let cube = T::vmap(|x| {
+            let y = Tensor::builder(g)
+                .append_input(x[0] * x[1], false)
+                .build(Cube);
+            vec![y]
+        });
+        let ys = cube(&[xs, xs * scale])[0];
+        let grads = T::grad(&[ys], &[xs, scale]);
+
+        let xs_val = array![[1.0, 2.0], [-1.0, 0.5]].into_dyn();
+        let results = g
+            .evaluator()
+            .push(&ys)
+            .extend(&grads)
+            .feed(xs, xs_val.view())
+            .feed(scale, ndarray::arr0(2.).view().into_dyn())
+            .run();
+        let results: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
+        // y = (2 x^2)^3 = 8 x^6
+        assert_close(&results[0], &xs_val.mapv(|x| 8. * x.powi(6)));
+        assert_close(&results[1], &xs_val.mapv(|x| 48. * x.powi(5)));
+        // dy/dscale = 3 scale^2 x^6
+        assert_relative_eq!(
+            results[2][ndarray::IxDyn(&[])],
+            xs_val.mapv(|x| 12. * x.powi(6)).sum(),
+            epsilon = 1e-10
+        );
+    });
+}
@@ -3,11 +3,21 @@
 //! This module provides comprehensive support for asynchronous GPU operations with
 //! event-based synchronization, enabling efficient overlapping of computation and
 //! memory transfers.
+//!
+//! Work submitted to a [`GpuStream`] executes in submission order on a dedicated
+//! worker, while separate streams run concurrently. Streams can be ordered with
+//! respect to each other by recording a [`GpuEvent`] on one stream and making
+//! another stream wait for it. Events can also be awaited from async code via
+//! [`GpuEvent::wait_async`], which works with any executor.
 
 use crate::gpu::{GpuBuffer, GpuError, GpuKernelHandle};
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex, Weak};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
 use std::time::{Duration, Instant};
 use thiserror::Error;
 
@@ -17,6 +27,9 @@ type CallbackFn = Box<dyn FnOnce() + Send + 'static>;
 /// Type alias for a list of callbacks
 type CallbackList = Arc<Mutex<Vec<CallbackFn>>>;
 
+/// Type alias for an operation executed on a stream worker
+type StreamOp = Box<dyn FnOnce() -> Result<(), GpuError> + Send + 'static>;
+
 /// Unique identifier for GPU events
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EventId(u64);
@@ -66,27 +79,30 @@ pub enum EventState {
     Cancelled,
 }
 
+impl EventState {
+    /// Whether the event has reached a final state
+    pub fn is_terminal(self) -> bool {
+        self != EventState::Pending
+    }
+}
+
 /// GPU event for synchronization
 pub struct GpuEvent {
     id: EventId,
     state: Arc<Mutex<EventState>>,
+    signal: Arc<Condvar>,
     timestamp: Option<Instant>,
     duration: Arc<Mutex<Option<Duration>>>,
     dependencies: Vec<EventId>,
     callbacks: CallbackList,
+    wakers: Arc<Mutex<Vec<Waker>>>,
+    error: Arc<Mutex<Option<String>>>,
 }
 
 impl GpuEvent {
     /// Create a new GPU event
     pub fn new() -> Self {
-        Self {
-            id: EventId::new(),
-            state: Arc::new(Mutex::new(EventState::Pending)),
-            timestamp: Some(Instant::now()),
-            duration: Arc::new(Mutex::new(None)),
-            dependencies: Vec::new(),
-            callbacks: Arc::new(Mutex::new(Vec::new())),
-        }
+        Self::with_dependencies(Vec::new())
     }
 
     /// Create a new event with dependencies
@@ -94,10 +110,13 @@ impl GpuEvent {
         Self {
             id: EventId::new(),
             state: Arc::new(Mutex::new(EventState::Pending)),
+            signal: Arc::new(Condvar::new()),
             timestamp: Some(Instant::now()),
             duration: Arc::new(Mutex::new(None)),
             dependencies,
             callbacks: Arc::new(Mutex::new(Vec::new())),
+            wakers: Arc::new(Mutex::new(Vec::new())),
+            error: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -128,24 +147,54 @@ impl GpuEvent {
 
     /// Wait for the event to complete with a timeout
     pub fn wait_timeout(&self, timeout: Duration) -> Result<(), GpuError> {
-        let start = Instant::now();
-        while start.elapsed() < timeout {
-            match self.state() {
-                EventState::Completed => return Ok(()),
-                EventState::Failed => {
-                    return Err(GpuError::KernelExecutionError(
-                        "Event execution failed".to_string(),
-                    ))
-                }
-                EventState::Cancelled => {
-                    return Err(GpuError::Other("Event was cancelled".to_string()))
-                }
-                EventState::Pending => {
-                    std::thread::sleep(Duration::from_millis(1));
-                }
-            }
+        let guard = self.state.lock().unwrap();
+        let (guard, _) = self
+            .signal
+            .wait_timeout_while(guard, timeout, |state| !state.is_terminal())
+            .unwrap();
+        let state = *guard;
+        drop(guard);
+        self.state_to_result(state)
+            .unwrap_or_else(|| Err(GpuError::Other("Event wait timeout".to_string())))
+    }
+
+    /// Wait for the event to reach a final state without a timeout
+    pub(crate) fn wait_terminal(&self) -> EventState {
+        let guard = self.state.lock().unwrap();
+        *self
+            .signal
+            .wait_while(guard, |state| !state.is_terminal())
+            .unwrap()
+    }
+
+    /// Get a future that resolves when the event completes
+    ///
+    /// The future does not depend on any particular async runtime; it is woken
+    /// directly by whichever thread completes the event.
+    pub fn wait_async(&self) -> EventFuture {
+        EventFuture {
+            state: self.state.clone(),
+            wakers: self.wakers.clone(),
+            error: self.error.clone(),
         }
-        Err(GpuError::Other("Event wait timeout".to_string()))
+    }
+
+    /// Wait for the event from a tokio runtime with a timeout
+    #[cfg(feature = "async")]
+    pub async fn wait_async_timeout(&self, timeout: Duration) -> Result<(), GpuError> {
+        match tokio::time::timeout(timeout, self.wait_async()).await {
+            Ok(result) => result,
+            Err(_) => Err(GpuError::Other("Event wait timeout".to_string())),
+        }
+    }
+
+    /// Get the error message if the event failed
+    pub fn error_message(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
+    fn state_to_result(&self, state: EventState) -> Option<Result<(), GpuError>> {
+        event_result(state, &self.error)
     }
 
     /// Get the execution duration if completed
@@ -167,13 +216,12 @@ impl GpuEvent {
     }
 
     /// Mark the event as completed
-    #[allow(dead_code)]
     pub(crate) fn complete(&self) {
         let start_time = self.timestamp.unwrap_or_else(Instant::now);
         let duration = start_time.elapsed();
 
         *self.duration.lock().unwrap() = Some(duration);
-        *self.state.lock().unwrap() = EventState::Completed;
+        self.finish(EventState::Completed);
 
         // Execute callbacks
         let callbacks = std::mem::take(&mut *self.callbacks.lock().unwrap());
@@ -183,15 +231,74 @@ impl GpuEvent {
     }
 
     /// Mark the event as failed
-    #[allow(dead_code)]
     pub(crate) fn fail(&self) {
-        *self.state.lock().unwrap() = EventState::Failed;
+        self.finish(EventState::Failed);
+    }
+
+    /// Mark the event as failed with an error message
+    pub(crate) fn fail_with(&self, message: String) {
+        *self.error.lock().unwrap() = Some(message);
+        self.finish(EventState::Failed);
     }
 
     /// Cancel the event
     #[allow(dead_code)]
     pub(crate) fn cancel(&self) {
-        *self.state.lock().unwrap() = EventState::Cancelled;
+        self.finish(EventState::Cancelled);
+    }
+
+    /// Transition to a final state and wake every waiter
+    fn finish(&self, state: EventState) {
+        *self.state.lock().unwrap() = state;
+        self.signal.notify_all();
+
+        let wakers = std::mem::take(&mut *self.wakers.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+fn event_result(state: EventState, error: &Mutex<Option<String>>) -> Option<Result<(), GpuError>> {
+    match state {
+        EventState::Completed => Some(Ok(())),
+        EventState::Failed => Some(Err(GpuError::KernelExecutionError(
+            error
+                .lock()
+                .unwrap()
+                .clone()
+                .unwrap_or_else(|| "Event execution failed".to_string()),
+        ))),
+        EventState::Cancelled => Some(Err(GpuError::Other("Event was cancelled".to_string()))),
+        EventState::Pending => None,
+    }
+}
+
+/// Future returned by [`GpuEvent::wait_async`]
+#[derive(Debug)]
+pub struct EventFuture {
+    state: Arc<Mutex<EventState>>,
+    wakers: Arc<Mutex<Vec<Waker>>>,
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl Future for EventFuture {
+    type Output = Result<(), GpuError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Hold the waker lock while checking the state so a concurrent
+        // completion cannot slip in between the check and the registration.
+        let mut wakers = self.wakers.lock().unwrap();
+        let state = *self.state.lock().unwrap();
+        match event_result(state, &self.error) {
+            Some(result) => Poll::Ready(result),
+            None => {
+                if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
     }
 }
 
@@ -234,6 +341,46 @@ impl Default for StreamPriority {
     }
 }
 
+/// Command processed by a stream worker, in submission order
+enum StreamCommand {
+    /// Run an operation and signal its completion event
+    Execute(StreamOp, Arc<GpuEvent>),
+    /// Block the stream until another event reaches a final state
+    WaitEvent(Arc<GpuEvent>),
+    /// Signal an event once all previously submitted work has finished
+    Record(Arc<GpuEvent>),
+}
+
+fn run_stream_worker(commands: mpsc::Receiver<StreamCommand>) {
+    for command in commands {
+        match command {
+            // a panicking operation fails its event instead of killing the
+            // worker, which would leave later commands and waiters hanging
+            StreamCommand::Execute(op, event) => {
+                match std::panic::catch_unwind(std::panic::AssertUnwindSafe(op)) {
+                    Ok(Ok(())) => event.complete(),
+                    Ok(Err(err)) => event.fail_with(err.to_string()),
+                    Err(payload) => event.fail_with(panic_message(payload.as_ref())),
+                }
+            }
+            StreamCommand::WaitEvent(event) => {
+                event.wait_terminal();
+            }
+            StreamCommand::Record(event) => event.complete(),
+        }
+    }
+}
+
+/// Error message for a panic caught on a stream worker
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    let detail = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload");
+    format!("stream operation panicked: {}", detail)
+}
+
 /// GPU stream for organizing operations
 #[derive(Debug)]
 pub struct GpuStream {
@@ -241,17 +388,13 @@ pub struct GpuStream {
     priority: StreamPriority,
     events: Arc<Mutex<Vec<Weak<GpuEvent>>>>,
     operations_count: Arc<Mutex<usize>>,
+    worker: Mutex<Option<Sender<StreamCommand>>>,
 }
 
 impl GpuStream {
     /// Create a new GPU stream
     pub fn new() -> Self {
-        Self {
-            id: StreamId::new(),
-            priority: StreamPriority::Normal,
-            events: Arc::new(Mutex::new(Vec::new())),
-            operations_count: Arc::new(Mutex::new(0)),
-        }
+        Self::with_priority(StreamPriority::Normal)
     }
 
     /// Create a new GPU stream with priority
@@ -261,7 +404,62 @@ impl GpuStream {
             priority,
             events: Arc::new(Mutex::new(Vec::new())),
             operations_count: Arc::new(Mutex::new(0)),
+            worker: Mutex::new(None),
+        }
+    }
+
+    /// Send a command to the stream worker, starting it on first use
+    fn send(&self, command: StreamCommand) -> Result<(), GpuError> {
+        let mut worker = self.worker.lock().unwrap();
+        if worker.is_none() {
+            let (tx, rx) = mpsc::channel();
+            std::thread::Builder::new()
+                .name(format!("gpu-stream-{}", self.id.0))
+                .spawn(move || run_stream_worker(rx))
+                .map_err(|e| GpuError::Other(format!("failed to start stream worker: {}", e)))?;
+            *worker = Some(tx);
         }
+        worker
+            .as_ref()
+            .expect("stream worker was just initialized")
+            .send(command)
+            .map_err(|_| GpuError::Other("stream worker has terminated".to_string()))
+    }
+
+    /// Submit an operation to run after all previously submitted work
+    ///
+    /// Returns an event that completes when the operation finishes, or fails
+    /// with the operation's error message. An operation that panics fails its
+    /// event and later operations on the stream still run.
+    pub fn submit<F>(&self, op: F) -> Result<Arc<GpuEvent>, GpuError>
+    where
+        F: FnOnce() -> Result<(), GpuError> + Send + 'static,
+    {
+        let event = Arc::new(GpuEvent::new());
+        self.add_event(&event);
+        self.send(StreamCommand::Execute(Box::new(op), event.clone()))?;
+        Ok(event)
+    }
+
+    /// Record an event that completes once all currently submitted work has finished
+    pub fn record_event(&self) -> Result<Arc<GpuEvent>, GpuError> {
+        let event = Arc::new(GpuEvent::new());
+        self.add_event(&event);
+        self.send(StreamCommand::Record(event.clone()))?;
+        Ok(event)
+    }
+
+    /// Make all work submitted after this call wait for `event`
+    ///
+    /// The event is typically recorded on another stream, which establishes a
+    /// cross-stream dependency without blocking the calling thread.
+    pub fn wait_event(&self, event: &Arc<GpuEvent>) -> Result<(), GpuError> {
+        self.send(StreamCommand::WaitEvent(event.clone()))
+    }
+
+    /// Asynchronously wait for all work submitted so far
+    pub fn synchronize_async(&self) -> Result<EventFuture, GpuError> {
+        Ok(self.record_event()?.wait_async())
     }
 
     /// Get the stream ID
@@ -282,6 +480,11 @@ impl GpuStream {
 
     /// Wait for all operations in this stream to complete
     pub fn synchronize(&self) -> Result<(), GpuError> {
+        if self.worker.lock().unwrap().is_some() {
+            let marker = self.record_event()?;
+            marker.wait_terminal();
+        }
+
         let events = self.events.lock().unwrap().clone();
         for weak_event in events {
             if let Some(event) = weak_event.upgrade() {
@@ -645,6 +848,125 @@ mod tests {
         assert_eq!(event2.dependencies()[0], event1.id());
     }
 
+    #[test]
+    fn test_stream_submit_runs_in_order() {
+        let stream = GpuStream::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        for i in 0..5 {
+            let log = log.clone();
+            stream
+                .submit(move || {
+                    log.lock().unwrap().push(i);
+                    Ok(())
+                })
+                .unwrap();
+        }
+        stream.synchronize().unwrap();
+
+        assert_eq!(*log.lock().unwrap(), vec![0, 1, 2, 3, 4]);
+        assert!(stream.is_idle());
+    }
+
+    #[test]
+    fn test_stream_submit_failure() {
+        let stream = GpuStream::new();
+        let event = stream
+            .submit(|| Err(GpuError::KernelExecutionError("boom".to_string())))
+            .unwrap();
+
+        assert!(event.wait().is_err());
+        assert!(event.is_failed());
+        assert!(event.error_message().unwrap().contains("boom"));
+    }
+
+    #[test]
+    fn test_stream_submit_panic() {
+        let stream = GpuStream::new();
+        let event = stream.submit(|| panic!("kernel crashed")).unwrap();
+
+        assert!(event.wait_timeout(Duration::from_secs(5)).is_err());
+        assert!(event.is_failed());
+        assert!(event.error_message().unwrap().contains("kernel crashed"));
+        assert!(stream.synchronize().is_err());
+
+        // the worker survives and runs later operations
+        let after = stream.submit(|| Ok(())).unwrap();
+        assert!(after.wait_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn test_cross_stream_dependency() {
+        let producer = GpuStream::new();
+        let consumer = GpuStream::new();
+        let value = Arc::new(Mutex::new(0));
+
+        let v = value.clone();
+        producer
+            .submit(move || {
+                std::thread::sleep(Duration::from_millis(20));
+                *v.lock().unwrap() = 42;
+                Ok(())
+            })
+            .unwrap();
+        let ready = producer.record_event().unwrap();
+
+        consumer.wait_event(&ready).unwrap();
+        let v = value.clone();
+        let observed = Arc::new(Mutex::new(0));
+        let o = observed.clone();
+        consumer
+            .submit(move || {
+                *o.lock().unwrap() = *v.lock().unwrap();
+                Ok(())
+            })
+            .unwrap();
+        consumer.synchronize().unwrap();
+
+        assert_eq!(*observed.lock().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_event_wait_async() {
+        use std::task::Wake;
+
+        struct ThreadWaker(std::thread::Thread);
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        fn block_on<F: Future>(future: F) -> F::Output {
+            let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+            let mut cx = Context::from_waker(&waker);
+            let mut future = std::pin::pin!(future);
+            loop {
+                match future.as_mut().poll(&mut cx) {
+                    Poll::Ready(output) => return output,
+                    Poll::Pending => std::thread::park(),
+                }
+            }
+        }
+
+        let stream = GpuStream::new();
+        stream
+            .submit(|| {
+                std::thread::sleep(Duration::from_millis(10));
+                Ok(())
+            })
+            .unwrap();
+        let future = stream.synchronize_async().unwrap();
+
+        assert!(block_on(future).is_ok());
+    }
+
+    #[test]
+    fn test_event_wait_timeout_pending() {
+        let event = GpuEvent::new();
+        assert!(event.wait_timeout(Duration::from_millis(5)).is_err());
+    }
+
     #[test]
     fn test_stream_priority() {
         let low_stream = GpuStream::with_priority(StreamPriority::Low);
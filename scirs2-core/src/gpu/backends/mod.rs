@@ -30,8 +30,10 @@ pub struct GpuInfo {
     pub backend: GpuBackend,
     /// Device name
     pub device_name: String,
-    /// Available memory in bytes
+    /// Total device memory in bytes
     pub memory_bytes: Option<u64>,
+    /// Free device memory in bytes at detection time
+    pub memory_free_bytes: Option<u64>,
     /// Compute capability or equivalent
     pub compute_capability: Option<String>,
     /// Whether the device supports tensor operations
@@ -106,6 +108,7 @@ pub fn detect_gpu_backends() -> GpuDetectionResult {
         backend: GpuBackend::Cpu,
         device_name: "CPU".to_string(),
         memory_bytes: None,
+        memory_free_bytes: None,
         compute_capability: None,
         supports_tensors: false,
     });
@@ -155,6 +158,7 @@ fn detect_rocm_devices() -> Result<Vec<GpuInfo>, GpuError> {
                         backend: GpuBackend::Rocm,
                         device_name,
                         memory_bytes: Some(memory_mb),
+                        memory_free_bytes: None,
                         compute_capability: Some("RDNA/CDNA".to_string()),
                         supports_tensors: true, // Modern AMD GPUs support matrix operations
                     });
@@ -183,7 +187,7 @@ fn detect_cuda_devices() -> Result<Vec<GpuInfo>, GpuError> {
 
     // Try to run nvidia-smi to detect CUDA devices
     match Command::new("nvidia-smi")
-        .arg("--query-gpu=name,memory.total,compute_cap")
+        .arg("--query-gpu=name,memory.total,compute_cap,memory.free")
         .arg("--format=csv,noheader,nounits")
         .output()
     {
@@ -200,6 +204,10 @@ fn detect_cuda_devices() -> Result<Vec<GpuInfo>, GpuError> {
                     let device_name = parts[0].to_string();
                     let memory_mb = parts[1].parse::<u64>().unwrap_or(0) * 1024 * 1024; // Convert MB to bytes
                     let compute_capability = parts[2].to_string();
+                    let memory_free = parts
+                        .get(3)
+                        .and_then(|s| s.parse::<u64>().ok())
+                        .map(|mb| mb * 1024 * 1024);
 
                     // Parse compute capability to determine tensor core support
                     let supports_tensors =
@@ -213,6 +221,7 @@ fn detect_cuda_devices() -> Result<Vec<GpuInfo>, GpuError> {
                         backend: GpuBackend::Cuda,
                         device_name,
                         memory_bytes: Some(memory_mb),
+                        memory_free_bytes: memory_free,
                         compute_capability: Some(compute_capability),
                         supports_tensors,
                     });
@@ -264,6 +273,7 @@ fn detect_metal_devices() -> Result<Vec<GpuInfo>, GpuError> {
                                 backend: GpuBackend::Metal,
                                 device_name: model.to_string(),
                                 memory_bytes: None,
+                                memory_free_bytes: None,
                                 compute_capability: None,
                                 supports_tensors: true,
                             };
@@ -318,6 +328,7 @@ fn detect_metal_devices() -> Result<Vec<GpuInfo>, GpuError> {
                             backend: GpuBackend::Metal,
                             device_name: name.clone(),
                             memory_bytes: None,
+                            memory_free_bytes: None,
                             compute_capability: None,
                             supports_tensors: true,
                         };
@@ -337,6 +348,7 @@ fn detect_metal_devices() -> Result<Vec<GpuInfo>, GpuError> {
                         backend: GpuBackend::Metal,
                         device_name: "Metal GPU".to_string(),
                         memory_bytes: None,
+                        memory_free_bytes: None,
                         compute_capability: None,
                         supports_tensors: true,
                     });
@@ -353,6 +365,7 @@ fn detect_metal_devices() -> Result<Vec<GpuInfo>, GpuError> {
                         backend: GpuBackend::Metal,
                         device_name: device.name().to_string(),
                         memory_bytes: None,
+                        memory_free_bytes: None,
                         compute_capability: None,
                         supports_tensors: true,
                     });
@@ -401,6 +414,7 @@ fn detect_opencl_devices() -> Result<Vec<GpuInfo>, GpuError> {
                         backend: GpuBackend::OpenCL,
                         device_name: "OpenCL Device".to_string(),
                         memory_bytes: None,
+                        memory_free_bytes: None,
                         compute_capability: None,
                         supports_tensors: false,
                     });
@@ -524,6 +538,7 @@ mod tests {
             backend: GpuBackend::Cuda,
             device_name: "NVIDIA GeForce RTX 3080".to_string(),
             memory_bytes: Some(10 * 1024 * 1024 * 1024), // 10GB
+            memory_free_bytes: None,
             compute_capability: Some("8.6".to_string()),
             supports_tensors: true,
         };
@@ -638,6 +653,7 @@ mod tests {
             backend: GpuBackend::Rocm,
             device_name: "AMD Radeon RX 6900 XT".to_string(),
             memory_bytes: Some(16 * 1024 * 1024 * 1024), // 16GB
+            memory_free_bytes: None,
             compute_capability: Some("RDNA2".to_string()),
             supports_tensors: true,
         };
@@ -657,6 +673,7 @@ mod tests {
                 backend: GpuBackend::Cuda,
                 device_name: "NVIDIA A100".to_string(),
                 memory_bytes: Some(40 * 1024 * 1024 * 1024),
+                memory_free_bytes: None,
                 compute_capability: Some("8.0".to_string()),
                 supports_tensors: true,
             },
@@ -664,6 +681,7 @@ mod tests {
                 backend: GpuBackend::Cpu,
                 device_name: "CPU".to_string(),
                 memory_bytes: None,
+                memory_free_bytes: None,
                 compute_capability: None,
                 supports_tensors: false,
             },
@@ -0,0 +1,405 @@
+//! Detailed device capability queries
+//!
+//! This module turns the raw information gathered by [`backends::detect_gpu_backends`]
+//! into a stable, backend-independent description of each device: memory sizes,
+//! compute capability or feature level, workgroup limits and relative fp16/fp64
+//! throughput. Subcrates use it to pick algorithms and precisions that suit the
+//! hardware instead of assuming a generic device.
+//!
+//! Detection runs external tools, so results are cached. Memory figures are a
+//! snapshot taken at detection time; call [`refresh_device_capabilities`] to
+//! update them.
+
+use crate::gpu::backends::{self, GpuInfo};
+use crate::gpu::{GpuBackend, GpuError};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+static CAPABILITY_CACHE: Mutex<Option<Vec<GpuDeviceCapabilities>>> = Mutex::new(None);
+
+/// Throughput of a floating-point precision relative to fp32 on the same device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PrecisionThroughput {
+    /// No information is available for this device
+    Unknown,
+    /// The precision is not supported natively and must be emulated or avoided
+    Unsupported,
+    /// Much slower than fp32 (1/8 of the rate or worse)
+    Low,
+    /// Between 1/8 and 1/2 of the fp32 rate
+    Reduced,
+    /// Comparable to the fp32 rate
+    Full,
+    /// Faster than fp32 (packed math or matrix units)
+    Accelerated,
+}
+
+impl PrecisionThroughput {
+    /// Whether the precision is executed natively by the hardware
+    pub fn is_native(self) -> bool {
+        matches!(
+            self,
+            PrecisionThroughput::Low
+                | PrecisionThroughput::Reduced
+                | PrecisionThroughput::Full
+                | PrecisionThroughput::Accelerated
+        )
+    }
+
+    /// Whether using this precision is at least as fast as fp32
+    pub fn is_fast(self) -> bool {
+        matches!(
+            self,
+            PrecisionThroughput::Full | PrecisionThroughput::Accelerated
+        )
+    }
+}
+
+/// NVIDIA-style compute capability (`major.minor`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ComputeCapability {
+    /// Major architecture version
+    pub major: u32,
+    /// Minor architecture version
+    pub minor: u32,
+}
+
+impl ComputeCapability {
+    /// Create a compute capability from its components
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl FromStr for ComputeCapability {
+    type Err = GpuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(2, '.');
+        let parse = |part: Option<&str>| {
+            part.and_then(|p| p.trim().parse::<u32>().ok())
+                .ok_or_else(|| {
+                    GpuError::InvalidParameter(format!("invalid compute capability: {s}"))
+                })
+        };
+        let major = parse(parts.next())?;
+        let minor = match parts.next() {
+            Some(p) => parse(Some(p))?,
+            None => 0,
+        };
+        Ok(Self { major, minor })
+    }
+}
+
+impl fmt::Display for ComputeCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Capabilities of a single compute device
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuDeviceCapabilities {
+    /// Backend the device is driven through
+    pub backend: GpuBackend,
+    /// Index of the device among devices of the same backend
+    pub device_id: usize,
+    /// Human-readable device name
+    pub name: String,
+    /// Total device memory in bytes, if known
+    pub total_memory: Option<u64>,
+    /// Free device memory in bytes at detection time, if known
+    pub free_memory: Option<u64>,
+    /// Compute capability for CUDA devices
+    pub compute_capability: Option<ComputeCapability>,
+    /// Feature level or architecture family for non-CUDA devices (e.g. Metal family)
+    pub feature_level: Option<String>,
+    /// Maximum number of invocations in a single workgroup / thread block
+    pub max_workgroup_size: u32,
+    /// Maximum workgroup extent in each dimension
+    pub max_workgroup_dims: [u32; 3],
+    /// Half-precision throughput relative to fp32
+    pub fp16_throughput: PrecisionThroughput,
+    /// Double-precision throughput relative to fp32
+    pub fp64_throughput: PrecisionThroughput,
+    /// Whether dedicated matrix units (tensor cores, matrix cores) are present
+    pub supports_tensor_cores: bool,
+}
+
+impl GpuDeviceCapabilities {
+    /// Build a capability description from detected device information
+    pub fn from_info(info: &GpuInfo, device_id: usize) -> Self {
+        let compute_capability = match info.backend {
+            GpuBackend::Cuda => info
+                .compute_capability
+                .as_deref()
+                .and_then(|cc| cc.parse::<ComputeCapability>().ok()),
+            _ => None,
+        };
+        let feature_level = match info.backend {
+            GpuBackend::Cuda => None,
+            _ => info.compute_capability.clone(),
+        };
+
+        let mut caps = Self::generic(info.backend, device_id);
+        caps.name = info.device_name.clone();
+        caps.total_memory = info.memory_bytes;
+        caps.free_memory = info.memory_free_bytes;
+        caps.supports_tensor_cores = info.supports_tensors;
+        caps.feature_level = feature_level;
+
+        match info.backend {
+            GpuBackend::Cuda => {
+                if let Some(cc) = compute_capability {
+                    caps.fp16_throughput = cuda_fp16_throughput(cc);
+                    caps.fp64_throughput = cuda_fp64_throughput(cc);
+                }
+                caps.compute_capability = compute_capability;
+            }
+            GpuBackend::Rocm => {
+                // CDNA (Instinct MI-series) parts run fp64 at half or full rate,
+                // consumer RDNA parts at 1/16.
+                let name = info.device_name.to_ascii_uppercase();
+                caps.fp64_throughput = if name.contains("MI") || name.contains("INSTINCT") {
+                    PrecisionThroughput::Reduced
+                } else {
+                    PrecisionThroughput::Low
+                };
+            }
+            _ => {}
+        }
+
+        caps
+    }
+
+    /// Conservative defaults for a device of the given backend
+    pub fn generic(backend: GpuBackend, device_id: usize) -> Self {
+        let (max_workgroup_size, max_workgroup_dims, fp16, fp64) = match backend {
+            GpuBackend::Cuda => (
+                1024,
+                [1024, 1024, 64],
+                PrecisionThroughput::Unknown,
+                PrecisionThroughput::Low,
+            ),
+            GpuBackend::Rocm => (
+                1024,
+                [1024, 1024, 1024],
+                PrecisionThroughput::Accelerated,
+                PrecisionThroughput::Unknown,
+            ),
+            GpuBackend::Metal => (
+                1024,
+                [1024, 1024, 1024],
+                PrecisionThroughput::Accelerated,
+                PrecisionThroughput::Unsupported,
+            ),
+            // WebGPU guarantees only the default limits, and fp16/fp64 are optional extensions
+            GpuBackend::Wgpu => (
+                256,
+                [256, 256, 64],
+                PrecisionThroughput::Unknown,
+                PrecisionThroughput::Unsupported,
+            ),
+            GpuBackend::OpenCL => (
+                256,
+                [256, 256, 256],
+                PrecisionThroughput::Unknown,
+                PrecisionThroughput::Unknown,
+            ),
+            // f16 is converted to f32 on the CPU; f64 runs at half the SIMD width
+            GpuBackend::Cpu => (
+                1,
+                [1, 1, 1],
+                PrecisionThroughput::Low,
+                PrecisionThroughput::Reduced,
+            ),
+        };
+
+        Self {
+            backend,
+            device_id,
+            name: backend.to_string(),
+            total_memory: None,
+            free_memory: None,
+            compute_capability: None,
+            feature_level: None,
+            max_workgroup_size,
+            max_workgroup_dims,
+            fp16_throughput: fp16,
+            fp64_throughput: fp64,
+            supports_tensor_cores: false,
+        }
+    }
+
+    /// Whether half precision is supported natively
+    pub fn supports_fp16(&self) -> bool {
+        self.fp16_throughput.is_native()
+    }
+
+    /// Whether double precision is supported natively
+    pub fn supports_fp64(&self) -> bool {
+        self.fp64_throughput.is_native()
+    }
+
+    /// Whether double precision runs fast enough to be the default choice
+    ///
+    /// Consumer GPUs often execute fp64 at 1/32 or 1/64 of the fp32 rate, in
+    /// which case algorithms should prefer fp32 with compensated accumulation.
+    pub fn prefers_fp64(&self) -> bool {
+        matches!(
+            self.fp64_throughput,
+            PrecisionThroughput::Reduced
+                | PrecisionThroughput::Full
+                | PrecisionThroughput::Accelerated
+        )
+    }
+
+    /// Whether an allocation of `bytes` is expected to fit in free device memory
+    ///
+    /// Returns `true` when the amount of free memory is unknown.
+    pub fn fits_in_memory(&self, bytes: u64) -> bool {
+        match self.free_memory.or(self.total_memory) {
+            Some(available) => bytes <= available,
+            None => true,
+        }
+    }
+}
+
+/// NVIDIA fp16 rate by architecture
+fn cuda_fp16_throughput(cc: ComputeCapability) -> PrecisionThroughput {
+    match (cc.major, cc.minor) {
+        (major, _) if major < 5 => PrecisionThroughput::Unsupported,
+        (5, minor) if minor < 3 => PrecisionThroughput::Unsupported,
+        // Consumer Pascal runs fp16 at 1/64 rate
+        (6, 1) | (6, 2) => PrecisionThroughput::Low,
+        _ => PrecisionThroughput::Accelerated,
+    }
+}
+
+/// NVIDIA fp64 rate by architecture
+fn cuda_fp64_throughput(cc: ComputeCapability) -> PrecisionThroughput {
+    match (cc.major, cc.minor) {
+        // GP100, GV100, GA100 and GH100 data-center parts run fp64 at half rate
+        (6, 0) | (7, 0) | (8, 0) | (9, 0) => PrecisionThroughput::Reduced,
+        (1, _) => PrecisionThroughput::Unsupported,
+        _ => PrecisionThroughput::Low,
+    }
+}
+
+fn detect_all() -> Vec<GpuDeviceCapabilities> {
+    let detection = backends::detect_gpu_backends();
+    let mut per_backend: std::collections::HashMap<GpuBackend, usize> = Default::default();
+    detection
+        .devices
+        .iter()
+        .map(|info| {
+            let index = per_backend.entry(info.backend).or_insert(0);
+            let caps = GpuDeviceCapabilities::from_info(info, *index);
+            *index += 1;
+            caps
+        })
+        .collect()
+}
+
+/// Get the capabilities of every detected device, including the CPU fallback
+pub fn all_device_capabilities() -> Vec<GpuDeviceCapabilities> {
+    let mut cache = CAPABILITY_CACHE.lock().unwrap();
+    cache.get_or_insert_with(detect_all).clone()
+}
+
+/// Re-run device detection and update the cached capabilities
+pub fn refresh_device_capabilities() -> Vec<GpuDeviceCapabilities> {
+    let devices = detect_all();
+    *CAPABILITY_CACHE.lock().unwrap() = Some(devices.clone());
+    devices
+}
+
+/// Get the capabilities of a specific device
+pub fn query_device_capabilities(
+    backend: GpuBackend,
+    device_id: usize,
+) -> Result<GpuDeviceCapabilities, GpuError> {
+    all_device_capabilities()
+        .into_iter()
+        .find(|caps| caps.backend == backend && caps.device_id == device_id)
+        .ok_or_else(|| {
+            GpuError::InvalidParameter(format!(
+                "Device {} not found for backend {}",
+                device_id, backend
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cuda_info(name: &str, cc: &str) -> GpuInfo {
+        GpuInfo {
+            backend: GpuBackend::Cuda,
+            device_name: name.to_string(),
+            memory_bytes: Some(16 << 30),
+            memory_free_bytes: Some(12 << 30),
+            compute_capability: Some(cc.to_string()),
+            supports_tensors: true,
+        }
+    }
+
+    #[test]
+    fn test_compute_capability_parsing() {
+        let cc: ComputeCapability = "8.6".parse().unwrap();
+        assert_eq!(cc, ComputeCapability::new(8, 6));
+        assert_eq!(cc.to_string(), "8.6");
+        assert_eq!(
+            "7".parse::<ComputeCapability>().unwrap(),
+            ComputeCapability::new(7, 0)
+        );
+        assert!("sm_80".parse::<ComputeCapability>().is_err());
+        assert!(ComputeCapability::new(9, 0) > ComputeCapability::new(8, 9));
+    }
+
+    #[test]
+    fn test_cuda_precision_hints() {
+        let a100 = GpuDeviceCapabilities::from_info(&cuda_info("NVIDIA A100", "8.0"), 0);
+        assert_eq!(a100.compute_capability, Some(ComputeCapability::new(8, 0)));
+        assert!(a100.prefers_fp64());
+        assert!(a100.fp16_throughput.is_fast());
+        assert_eq!(a100.free_memory, Some(12 << 30));
+
+        let rtx = GpuDeviceCapabilities::from_info(&cuda_info("RTX 3080", "8.6"), 1);
+        assert!(rtx.supports_fp64());
+        assert!(!rtx.prefers_fp64());
+        assert_eq!(rtx.device_id, 1);
+
+        let gtx = GpuDeviceCapabilities::from_info(&cuda_info("GTX 1080", "6.1"), 0);
+        assert_eq!(gtx.fp16_throughput, PrecisionThroughput::Low);
+    }
+
+    #[test]
+    fn test_generic_backend_limits() {
+        let metal = GpuDeviceCapabilities::generic(GpuBackend::Metal, 0);
+        assert!(!metal.supports_fp64());
+        assert!(metal.supports_fp16());
+
+        let wgpu = GpuDeviceCapabilities::generic(GpuBackend::Wgpu, 0);
+        assert_eq!(wgpu.max_workgroup_size, 256);
+    }
+
+    #[test]
+    fn test_fits_in_memory() {
+        let caps = GpuDeviceCapabilities::from_info(&cuda_info("A10", "8.6"), 0);
+        assert!(caps.fits_in_memory(1 << 30));
+        assert!(!caps.fits_in_memory(13 << 30));
+        assert!(GpuDeviceCapabilities::generic(GpuBackend::Cpu, 0).fits_in_memory(u64::MAX));
+    }
+
+    #[test]
+    fn test_query_cpu_capabilities() {
+        let caps = query_device_capabilities(GpuBackend::Cpu, 0).unwrap();
+        assert_eq!(caps.backend, GpuBackend::Cpu);
+        assert!(caps.supports_fp64());
+        assert!(query_device_capabilities(GpuBackend::Cpu, 7).is_err());
+        assert!(!all_device_capabilities().is_empty());
+    }
+}
@@ -10,8 +10,10 @@ pub mod async_execution;
 pub mod auto_tuning;
 pub mod backends;
 pub mod benchmarks;
+pub mod capabilities;
 pub mod heterogeneous;
 pub mod kernels;
+pub mod pinned;
 pub mod tensor_cores;
 
 /// GPU backend type
@@ -172,6 +174,46 @@ impl GpuDevice {
         self.device_id
     }
 
+    /// Query the detailed capabilities of this device
+    ///
+    /// Falls back to conservative backend defaults if the device was not found
+    /// during detection.
+    pub fn capabilities(&self) -> capabilities::GpuDeviceCapabilities {
+        capabilities::query_device_capabilities(self.backend, self.device_id).unwrap_or_else(|_| {
+            capabilities::GpuDeviceCapabilities::generic(self.backend, self.device_id)
+        })
+    }
+
+    /// Get the device name
+    pub fn name(&self) -> String {
+        self.capabilities().name
+    }
+
+    /// Get the total device memory in bytes, if known
+    pub fn total_memory(&self) -> Option<u64> {
+        self.capabilities().total_memory
+    }
+
+    /// Get the free device memory in bytes, if known
+    pub fn free_memory(&self) -> Option<u64> {
+        self.capabilities().free_memory
+    }
+
+    /// Get the maximum workgroup (thread block) size
+    pub fn max_workgroup_size(&self) -> u32 {
+        self.capabilities().max_workgroup_size
+    }
+
+    /// Check whether the device executes half precision natively
+    pub fn supports_fp16(&self) -> bool {
+        self.capabilities().supports_fp16()
+    }
+
+    /// Check whether the device executes double precision natively
+    pub fn supports_fp64(&self) -> bool {
+        self.capabilities().supports_fp64()
+    }
+
     /// Compile a kernel from source
     pub fn compile_kernel(&self, _source: &str, entry_point: &str) -> Result<GpuKernel, GpuError> {
         // Placeholder implementation
@@ -259,19 +301,25 @@ impl From<GpuError> for CoreError {
 }
 
 /// Trait for types that can be used with GPU operations
-pub trait GpuDataType: Copy + Send + Sync + 'static {}
+///
+/// # Safety
+///
+/// Buffers of these types are zero-initialized and filled by raw byte copies
+/// to and from the device, so implementors must be plain data: no padding
+/// bytes, and every bit pattern, including all zeros, must be a valid value.
+pub unsafe trait GpuDataType: Copy + Send + Sync + 'static {}
 
 // Implement for common types
-impl GpuDataType for f32 {}
-impl GpuDataType for f64 {}
-impl GpuDataType for i32 {}
-impl GpuDataType for u32 {}
-impl GpuDataType for u8 {}
-impl GpuDataType for i8 {}
-impl GpuDataType for u16 {}
-impl GpuDataType for i16 {}
-impl GpuDataType for u64 {}
-impl GpuDataType for i64 {}
+unsafe impl GpuDataType for f32 {}
+unsafe impl GpuDataType for f64 {}
+unsafe impl GpuDataType for i32 {}
+unsafe impl GpuDataType for u32 {}
+unsafe impl GpuDataType for u8 {}
+unsafe impl GpuDataType for i8 {}
+unsafe impl GpuDataType for u16 {}
+unsafe impl GpuDataType for i16 {}
+unsafe impl GpuDataType for u64 {}
+unsafe impl GpuDataType for i64 {}
 
 /// GPU buffer
 pub struct GpuBuffer<T: GpuDataType> {
@@ -546,6 +594,34 @@ impl GpuContext {
         buffer
     }
 
+    /// Allocate a page-locked host buffer for fast transfers to and from this context
+    ///
+    /// Falls back to pageable memory if the pages cannot be locked; use
+    /// [`GpuContext::allocate_pinned_with_policy`] to require pinning.
+    pub fn allocate_pinned<T: GpuDataType>(
+        &self,
+        len: usize,
+    ) -> Result<pinned::PinnedHostBuffer<T>, GpuError> {
+        self.allocate_pinned_with_policy(len, pinned::PinnedMemoryPolicy::Preferred)
+    }
+
+    /// Allocate a page-locked host buffer with an explicit pinning policy
+    pub fn allocate_pinned_with_policy<T: GpuDataType>(
+        &self,
+        len: usize,
+        policy: pinned::PinnedMemoryPolicy,
+    ) -> Result<pinned::PinnedHostBuffer<T>, GpuError> {
+        // The CPU backend never performs DMA, so locking pages would only waste
+        // the pinned budget unless the caller explicitly asks for it.
+        let policy = match (self.backend, policy) {
+            (GpuBackend::Cpu, pinned::PinnedMemoryPolicy::Preferred) => {
+                pinned::PinnedMemoryPolicy::Disabled
+            }
+            (_, policy) => policy,
+        };
+        pinned::PinnedHostBuffer::with_policy(len, policy)
+    }
+
     /// Execute a function with a compiler
     pub fn execute<F, R>(&self, f: F) -> R
     where
@@ -592,6 +668,11 @@ impl GpuContext {
         self.execute(|compiler| compiler.compile(source))
     }
 
+    /// Query the detailed capabilities of the device backing this context
+    pub fn device_capabilities(&self) -> capabilities::GpuDeviceCapabilities {
+        GpuDevice::new(self.backend, 0).capabilities()
+    }
+
     /// Get available memory on the device
     pub fn get_available_memory(&self) -> Option<usize> {
         // In a real implementation, this would query the device
@@ -0,0 +1,310 @@
+//! Page-locked (pinned) host memory for asynchronous host↔device transfers
+//!
+//! Pageable host memory forces GPU drivers to stage every transfer through an
+//! internal bounce buffer, which serializes copies with computation. Buffers
+//! allocated here are page-aligned and locked into physical memory when the
+//! operating system allows it, so backends can DMA directly from them.
+//!
+//! Pinning is a limited system resource. When the pages cannot be locked (for
+//! example because `RLIMIT_MEMLOCK` is exhausted, the platform does not support
+//! it, or the process-wide pinned budget is used up) the buffer silently falls
+//! back to a normal allocation unless [`PinnedMemoryPolicy::Required`] is used.
+
+use crate::gpu::{GpuDataType, GpuError};
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Default process-wide budget for pinned host memory (1 GiB)
+pub const DEFAULT_PINNED_MEMORY_LIMIT: usize = 1 << 30;
+
+static PINNED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PINNED_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_PINNED_MEMORY_LIMIT);
+
+/// Policy controlling how strictly page-locking is enforced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PinnedMemoryPolicy {
+    /// Fail with an error if the pages cannot be locked
+    Required,
+    /// Try to lock the pages and fall back to pageable memory on failure
+    #[default]
+    Preferred,
+    /// Never lock pages (useful for debugging and for tiny buffers)
+    Disabled,
+}
+
+/// Snapshot of process-wide pinned memory usage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinnedMemoryStats {
+    /// Bytes currently locked by live [`PinnedHostBuffer`]s
+    pub pinned_bytes: usize,
+    /// Maximum number of bytes that may be locked at once
+    pub limit_bytes: usize,
+}
+
+/// Get the current pinned memory usage
+pub fn pinned_memory_stats() -> PinnedMemoryStats {
+    PinnedMemoryStats {
+        pinned_bytes: PINNED_BYTES.load(Ordering::Relaxed),
+        limit_bytes: PINNED_LIMIT.load(Ordering::Relaxed),
+    }
+}
+
+/// Set the process-wide budget for pinned host memory in bytes
+///
+/// Buffers that are already pinned are not affected; the limit only applies to
+/// subsequent allocations.
+pub fn set_pinned_memory_limit(bytes: usize) {
+    PINNED_LIMIT.store(bytes, Ordering::Relaxed);
+}
+
+/// Reserve `bytes` from the pinned budget, returning `false` if it would be exceeded
+fn reserve_pinned_bytes(bytes: usize) -> bool {
+    let limit = PINNED_LIMIT.load(Ordering::Relaxed);
+    PINNED_BYTES
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+            current.checked_add(bytes).filter(|&total| total <= limit)
+        })
+        .is_ok()
+}
+
+fn release_pinned_bytes(bytes: usize) {
+    PINNED_BYTES.fetch_sub(bytes, Ordering::AcqRel);
+}
+
+/// Size of a virtual memory page on this system
+pub fn page_size() -> usize {
+    #[cfg(all(unix, feature = "libc"))]
+    {
+        // SAFETY: sysconf has no preconditions
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if size > 0 {
+            return size as usize;
+        }
+    }
+    4096
+}
+
+/// Attempt to lock the given memory region into physical memory
+fn lock_pages(ptr: *mut u8, bytes: usize) -> bool {
+    #[cfg(all(unix, feature = "libc"))]
+    {
+        // SAFETY: the region was just allocated by us and is `bytes` long
+        unsafe { libc::mlock(ptr as *const libc::c_void, bytes) == 0 }
+    }
+    #[cfg(not(all(unix, feature = "libc")))]
+    {
+        let _ = (ptr, bytes);
+        false
+    }
+}
+
+fn unlock_pages(ptr: *mut u8, bytes: usize) {
+    #[cfg(all(unix, feature = "libc"))]
+    {
+        // SAFETY: the region was locked by `lock_pages` with the same length
+        unsafe {
+            libc::munlock(ptr as *const libc::c_void, bytes);
+        }
+    }
+    #[cfg(not(all(unix, feature = "libc")))]
+    {
+        let _ = (ptr, bytes);
+    }
+}
+
+/// Page-aligned host buffer that is locked into physical memory when possible
+///
+/// The buffer dereferences to a slice, so it can be passed directly to
+/// [`GpuBuffer::copy_from_host`](crate::gpu::GpuBuffer::copy_from_host) and
+/// [`GpuBuffer::copy_to_host`](crate::gpu::GpuBuffer::copy_to_host).
+pub struct PinnedHostBuffer<T: GpuDataType> {
+    ptr: NonNull<T>,
+    len: usize,
+    layout: Option<Layout>,
+    pinned: bool,
+}
+
+// SAFETY: the buffer uniquely owns its allocation, like a `Vec<T>`
+unsafe impl<T: GpuDataType> Send for PinnedHostBuffer<T> {}
+unsafe impl<T: GpuDataType> Sync for PinnedHostBuffer<T> {}
+
+impl<T: GpuDataType> PinnedHostBuffer<T> {
+    /// Allocate a zero-initialized buffer, pinning it if possible
+    pub fn new(len: usize) -> Result<Self, GpuError> {
+        Self::with_policy(len, PinnedMemoryPolicy::Preferred)
+    }
+
+    /// Allocate a zero-initialized buffer using the given pinning policy
+    pub fn with_policy(len: usize, policy: PinnedMemoryPolicy) -> Result<Self, GpuError> {
+        let bytes = len
+            .checked_mul(std::mem::size_of::<T>())
+            .ok_or_else(|| GpuError::InvalidParameter("pinned buffer size overflow".into()))?;
+
+        if bytes == 0 {
+            if policy == PinnedMemoryPolicy::Required && len > 0 {
+                return Err(GpuError::InvalidParameter(
+                    "cannot pin a zero-sized element type".into(),
+                ));
+            }
+            return Ok(Self {
+                ptr: NonNull::dangling(),
+                len,
+                layout: None,
+                pinned: false,
+            });
+        }
+
+        let align = page_size().max(std::mem::align_of::<T>());
+        let layout = Layout::from_size_align(bytes, align)
+            .map_err(|e| GpuError::InvalidParameter(format!("invalid pinned layout: {}", e)))?;
+
+        // SAFETY: layout has a non-zero size, and the `GpuDataType` contract makes
+        // all-zero bytes a valid `T`
+        let raw = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(raw as *mut T).ok_or_else(|| {
+            GpuError::OutOfMemory(format!("failed to allocate {} bytes of host memory", bytes))
+        })?;
+
+        let mut buffer = Self {
+            ptr,
+            len,
+            layout: Some(layout),
+            pinned: false,
+        };
+
+        if policy != PinnedMemoryPolicy::Disabled && reserve_pinned_bytes(bytes) {
+            if lock_pages(raw, bytes) {
+                buffer.pinned = true;
+            } else {
+                release_pinned_bytes(bytes);
+            }
+        }
+
+        if policy == PinnedMemoryPolicy::Required && !buffer.pinned {
+            return Err(GpuError::OutOfMemory(format!(
+                "unable to page-lock {} bytes of host memory",
+                bytes
+            )));
+        }
+
+        Ok(buffer)
+    }
+
+    /// Allocate a buffer and fill it with a copy of `data`
+    pub fn from_slice(data: &[T]) -> Result<Self, GpuError> {
+        let mut buffer = Self::new(data.len())?;
+        buffer.copy_from_slice(data);
+        Ok(buffer)
+    }
+
+    /// Whether the pages backing this buffer are actually locked
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// Size of the buffer in bytes
+    pub fn size_in_bytes(&self) -> usize {
+        self.len * std::mem::size_of::<T>()
+    }
+
+    /// Raw pointer to the start of the buffer
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
+
+    /// Mutable raw pointer to the start of the buffer
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T: GpuDataType> Deref for PinnedHostBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: ptr is valid for `len` initialized elements (or dangling with a ZST/empty layout)
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: GpuDataType> DerefMut for PinnedHostBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: see `deref`; we hold a unique borrow
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: GpuDataType> Drop for PinnedHostBuffer<T> {
+    fn drop(&mut self) {
+        if let Some(layout) = self.layout {
+            let raw = self.ptr.as_ptr() as *mut u8;
+            if self.pinned {
+                unlock_pages(raw, layout.size());
+                release_pinned_bytes(layout.size());
+            }
+            // SAFETY: allocated in `with_policy` with this exact layout
+            unsafe { alloc::dealloc(raw, layout) };
+        }
+    }
+}
+
+impl<T: GpuDataType + fmt::Debug> fmt::Debug for PinnedHostBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PinnedHostBuffer")
+            .field("len", &self.len)
+            .field("pinned", &self.pinned)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_buffer_zeroed_and_writable() {
+        let mut buffer = PinnedHostBuffer::<f32>::new(1024).unwrap();
+        assert_eq!(buffer.len(), 1024);
+        assert!(buffer.iter().all(|&x| x == 0.0));
+
+        buffer[3] = 2.5;
+        assert_eq!(buffer[3], 2.5);
+        assert_eq!(buffer.as_ptr() as usize % page_size(), 0);
+    }
+
+    #[test]
+    fn test_pinned_buffer_from_slice() {
+        let data = [1.0f64, 2.0, 3.0];
+        let buffer = PinnedHostBuffer::from_slice(&data).unwrap();
+        assert_eq!(&buffer[..], &data);
+        assert_eq!(buffer.size_in_bytes(), 24);
+    }
+
+    #[test]
+    fn test_pinned_buffer_disabled_policy() {
+        let buffer = PinnedHostBuffer::<u8>::with_policy(64, PinnedMemoryPolicy::Disabled).unwrap();
+        assert!(!buffer.is_pinned());
+    }
+
+    #[test]
+    fn test_pinned_buffer_empty() {
+        let buffer = PinnedHostBuffer::<f32>::new(0).unwrap();
+        assert!(buffer.is_empty());
+        assert!(!buffer.is_pinned());
+    }
+
+    #[test]
+    fn test_pinned_buffer_with_gpu_buffer() {
+        let context = crate::gpu::GpuContext::new(crate::gpu::GpuBackend::Cpu).unwrap();
+        let host = PinnedHostBuffer::from_slice(&[1.0f32, 2.0, 3.0, 4.0]).unwrap();
+        let device = context.create_buffer::<f32>(4);
+        device.copy_from_host(&host);
+
+        let mut back = context.allocate_pinned::<f32>(4).unwrap();
+        device.copy_to_host(&mut back);
+        assert_eq!(&back[..], &host[..]);
+    }
+}
@@ -250,6 +250,13 @@ pub use rayon::join as par_join;
 #[cfg(not(feature = "parallel"))]
 pub use sequential_fallbacks::join as par_join;
 
+/// Dedicated thread pools with a fixed number of workers
+///
+/// Parallel iterators started inside `ThreadPool::install` run on that
+/// pool's threads instead of the global pool.
+#[cfg(feature = "parallel")]
+pub use rayon::{ThreadPool, ThreadPoolBuilder};
+
 #[cfg(test)]
 mod tests {
     use super::*;
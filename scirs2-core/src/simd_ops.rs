@@ -479,6 +479,9 @@ pub struct PlatformCapabilities {
     pub avx2_available: bool,
     pub avx512_available: bool,
     pub neon_available: bool,
+    /// Detailed capabilities of every detected compute device
+    #[cfg(feature = "gpu")]
+    pub gpu_devices: Vec<crate::gpu::capabilities::GpuDeviceCapabilities>,
 }
 
 impl PlatformCapabilities {
@@ -493,9 +496,20 @@ impl PlatformCapabilities {
             avx2_available: cfg!(target_feature = "avx2"),
             avx512_available: cfg!(target_feature = "avx512f"),
             neon_available: cfg!(target_arch = "aarch64"),
+            #[cfg(feature = "gpu")]
+            gpu_devices: crate::gpu::capabilities::all_device_capabilities(),
         }
     }
 
+    /// Get the most capable non-CPU device, preferring the largest memory
+    #[cfg(feature = "gpu")]
+    pub fn best_gpu_device(&self) -> Option<&crate::gpu::capabilities::GpuDeviceCapabilities> {
+        self.gpu_devices
+            .iter()
+            .filter(|d| d.backend != crate::gpu::GpuBackend::Cpu)
+            .max_by_key(|d| d.total_memory.unwrap_or(0))
+    }
+
     /// Get a summary of available acceleration features
     pub fn summary(&self) -> String {
         let mut features = Vec::new();
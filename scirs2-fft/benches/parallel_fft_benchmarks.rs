@@ -0,0 +1,77 @@
+//! Benchmarks for multithreaded FFT execution
+//!
+//! Runs the same transforms with 1, 2, 4 and 8 workers so that the scaling of
+//! the four-step 1-D split and of the batched row transforms can be compared.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ndarray::{Array2, ArrayD, IxDyn};
+use num_complex::Complex64;
+use scirs2_fft::{fft, fft2, fftn, with_fft_workers};
+use std::f64::consts::PI;
+use std::hint::black_box;
+
+const WORKER_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+/// Benchmark long 1-D FFTs (four-step split across workers)
+fn bench_parallel_fft_1d(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Parallel-FFT-1D");
+    group.sample_size(20);
+
+    for &size in &[1 << 16, 1 << 18, 1 << 20] {
+        let signal: Vec<Complex64> = (0..size)
+            .map(|i| Complex64::new((2.0 * PI * 37.0 * i as f64 / size as f64).sin(), 0.0))
+            .collect();
+        group.throughput(Throughput::Elements(size as u64));
+
+        for &workers in &WORKER_COUNTS {
+            group.bench_with_input(
+                BenchmarkId::new(format!("fft/{workers}-workers"), size),
+                &signal,
+                |b, signal| b.iter(|| with_fft_workers(workers, || fft(black_box(signal), None))),
+            );
+        }
+    }
+
+    group.finish();
+}
+
+/// Benchmark 2-D and 3-D FFTs (rows split across workers)
+fn bench_parallel_fft_nd(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Parallel-FFT-ND");
+    group.sample_size(20);
+
+    for &size in &[256, 1024] {
+        let image =
+            Array2::from_shape_fn((size, size), |(i, j)| ((i * 7 + j * 3) as f64 * 0.01).sin());
+        group.throughput(Throughput::Elements((size * size) as u64));
+
+        for &workers in &WORKER_COUNTS {
+            group.bench_with_input(
+                BenchmarkId::new(format!("fft2/{workers}-workers"), size),
+                &image,
+                |b, image| {
+                    b.iter(|| {
+                        with_fft_workers(workers, || fft2(black_box(image), None, None, None))
+                    })
+                },
+            );
+        }
+    }
+
+    let volume = ArrayD::from_shape_fn(IxDyn(&[64, 64, 64]), |idx| {
+        ((idx[0] + 2 * idx[1] + 3 * idx[2]) as f64 * 0.05).cos()
+    });
+    group.throughput(Throughput::Elements(volume.len() as u64));
+    for &workers in &WORKER_COUNTS {
+        group.bench_with_input(
+            BenchmarkId::new(format!("fftn/{workers}-workers"), 64),
+            &volume,
+            |b, volume| b.iter(|| fftn(black_box(volume), None, None, None, None, Some(workers))),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_fft_1d, bench_parallel_fft_nd);
+criterion_main!(benches);
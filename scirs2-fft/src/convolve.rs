@@ -0,0 +1,645 @@
+//! FFT-based N-dimensional convolution and correlation
+//!
+//! This module provides [`fftconvolve`], [`oaconvolve`] and [`fftcorrelate`]
+//! for real arrays of any dimensionality (typically 1-D to 3-D). Each
+//! function estimates the cost of direct summation against the FFT method
+//! and uses whichever is cheaper, so small kernels are not penalized by the
+//! transform overhead. The output mode follows SciPy:
+//!
+//! * `"full"` - the complete linear convolution, `s1 + s2 - 1` per axis
+//! * `"same"` - the central part with the same shape as the first input
+//! * `"valid"` - only the points where the inputs overlap completely
+//!
+//! # Examples
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_fft::fftconvolve;
+//!
+//! let image = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+//! let kernel = array![[0.0, 1.0], [1.0, 0.0]];
+//!
+//! let full = fftconvolve(&image.view(), &kernel.view(), "full").unwrap();
+//! assert_eq!(full.shape(), &[4, 4]);
+//! assert!((full[[1, 1]] - 6.0).abs() < 1e-12);
+//!
+//! let valid = fftconvolve(&image.view(), &kernel.view(), "valid").unwrap();
+//! assert_eq!(valid.shape(), &[2, 2]);
+//! ```
+
+use crate::error::{FFTError, FFTResult};
+use crate::fft::fft_along_axis;
+use crate::helper::next_fast_len;
+use ndarray::{Array, ArrayD, ArrayView, Axis, Dimension, IxDyn, Slice, Zip};
+use num_complex::Complex64;
+use num_traits::NumCast;
+use std::fmt::Debug;
+
+/// Direct summation is chosen while it needs fewer than this many
+/// multiply-adds per `N log2 N` of the padded transform
+const FFT_COST_FACTOR: f64 = 10.0;
+
+/// An axis is split into overlap-add blocks once the first input is at least
+/// this many times longer than the second along it
+const OA_SPLIT_RATIO: usize = 4;
+
+/// Shortest FFT length used for an overlap-add block
+const OA_MIN_FFT_LENGTH: usize = 64;
+
+/// Method used to evaluate a convolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvolveMethod {
+    /// Direct summation over all pairs of samples
+    Direct,
+    /// One padded FFT of each input
+    Fft,
+    /// FFTs of blocks of the first input, added with overlap
+    OverlapAdd,
+}
+
+/// Output region of a convolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Full,
+    Same,
+    Valid,
+}
+
+impl Mode {
+    fn parse(mode: &str) -> FFTResult<Self> {
+        match mode {
+            "full" => Ok(Mode::Full),
+            "same" => Ok(Mode::Same),
+            "valid" => Ok(Mode::Valid),
+            _ => Err(FFTError::ValueError(format!(
+                "Unknown mode: {mode} (expected \"full\", \"same\" or \"valid\")"
+            ))),
+        }
+    }
+}
+
+/// Convolve two N-dimensional arrays, using FFTs when they are faster
+///
+/// # Arguments
+///
+/// * `in1` - First input
+/// * `in2` - Second input, with the same number of dimensions as `in1`
+/// * `mode` - Output region: `"full"`, `"same"` or `"valid"`
+///
+/// # Returns
+///
+/// The convolution of `in1` and `in2`. In `"same"` mode the output has the
+/// shape of `in1`; in `"valid"` mode one input must be at least as large as
+/// the other along every axis.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_fft::fftconvolve;
+///
+/// let signal = array![1.0, 2.0, 3.0];
+/// let kernel = array![0.0, 1.0, 0.5];
+///
+/// let result = fftconvolve(&signal.view(), &kernel.view(), "full").unwrap();
+/// let expected = [0.0, 1.0, 2.5, 4.0, 1.5];
+/// for (a, b) in result.iter().zip(expected.iter()) {
+///     assert!((a - b).abs() < 1e-12);
+/// }
+/// ```
+pub fn fftconvolve<T, U, D>(
+    in1: &ArrayView<T, D>,
+    in2: &ArrayView<U, D>,
+    mode: &str,
+) -> FFTResult<Array<f64, D>>
+where
+    T: NumCast + Copy + Debug,
+    U: NumCast + Copy + Debug,
+    D: Dimension,
+{
+    let mode = Mode::parse(mode)?;
+    let (a, b) = (to_f64(in1)?, to_f64(in2)?);
+    let full = match choose_method(a.shape(), b.shape(), mode, false)? {
+        ConvolveMethod::Direct => direct_full(&a, &b),
+        _ => fft_full(&a, &b),
+    };
+    finish(full, a.shape(), b.shape(), mode)
+}
+
+/// Convolve two N-dimensional arrays with the overlap-add method
+///
+/// The first input is cut into blocks along every axis where it is much
+/// longer than the second, and each block is convolved with one FFT of a
+/// size suited to the second input. This is faster than [`fftconvolve`] and
+/// uses less memory when one input is much larger than the other; otherwise
+/// the cheaper of the direct and single-FFT methods is used.
+///
+/// # Arguments
+///
+/// * `in1` - First input
+/// * `in2` - Second input, with the same number of dimensions as `in1`
+/// * `mode` - Output region: `"full"`, `"same"` or `"valid"`
+///
+/// # Returns
+///
+/// The convolution of `in1` and `in2`, identical to [`fftconvolve`] up to
+/// rounding.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array1;
+/// use scirs2_fft::oaconvolve;
+///
+/// let signal = Array1::from_iter((0..1000).map(|i| (i as f64 * 0.1).sin()));
+/// let kernel = Array1::from_elem(11, 1.0 / 11.0);
+///
+/// let smoothed = oaconvolve(&signal.view(), &kernel.view(), "same").unwrap();
+/// assert_eq!(smoothed.len(), signal.len());
+/// ```
+pub fn oaconvolve<T, U, D>(
+    in1: &ArrayView<T, D>,
+    in2: &ArrayView<U, D>,
+    mode: &str,
+) -> FFTResult<Array<f64, D>>
+where
+    T: NumCast + Copy + Debug,
+    U: NumCast + Copy + Debug,
+    D: Dimension,
+{
+    let mode = Mode::parse(mode)?;
+    let (a, b) = (to_f64(in1)?, to_f64(in2)?);
+    let full = match choose_method(a.shape(), b.shape(), mode, true)? {
+        ConvolveMethod::Direct => direct_full(&a, &b),
+        ConvolveMethod::Fft => fft_full(&a, &b),
+        ConvolveMethod::OverlapAdd => {
+            // Block the larger input so the blocks cover the long axes
+            if b.len() > a.len() {
+                overlap_add_full(&b, &a)
+            } else {
+                overlap_add_full(&a, &b)
+            }
+        }
+    };
+    finish(full, a.shape(), b.shape(), mode)
+}
+
+/// Cross-correlate two N-dimensional arrays, using FFTs when they are faster
+///
+/// The correlation is `z[k] = sum_l in1[l + k - (s2 - 1)] * in2[l]`, i.e. the
+/// convolution of `in1` with `in2` reversed along every axis, so the output
+/// shapes match [`fftconvolve`].
+///
+/// # Arguments
+///
+/// * `in1` - First input
+/// * `in2` - Second input, with the same number of dimensions as `in1`
+/// * `mode` - Output region: `"full"`, `"same"` or `"valid"`
+///
+/// # Returns
+///
+/// The cross-correlation of `in1` and `in2`.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_fft::fftcorrelate;
+///
+/// let signal = array![0.0, 1.0, 2.0, 1.0, 0.0];
+/// let template = array![1.0, 2.0, 1.0];
+///
+/// let result = fftcorrelate(&signal.view(), &template.view(), "valid").unwrap();
+/// // The template matches best where it is centred on the peak
+/// assert_eq!(result.len(), 3);
+/// assert!(result[1] > result[0] && result[1] > result[2]);
+/// ```
+pub fn fftcorrelate<T, U, D>(
+    in1: &ArrayView<T, D>,
+    in2: &ArrayView<U, D>,
+    mode: &str,
+) -> FFTResult<Array<f64, D>>
+where
+    T: NumCast + Copy + Debug,
+    U: NumCast + Copy + Debug,
+    D: Dimension,
+{
+    let mut reversed = in2.view();
+    for axis in 0..reversed.ndim() {
+        reversed.invert_axis(Axis(axis));
+    }
+    fftconvolve(in1, &reversed, mode)
+}
+
+/// Choose the cheapest method for convolving inputs of the given shapes
+///
+/// The direct method costs one multiply-add per pair of overlapping samples,
+/// while the FFT method costs about `N log2 N` for a padded size `N`. The
+/// overlap-add method is only proposed when `allow_overlap_add` is set and
+/// one input is much longer than the other along some axis.
+///
+/// # Arguments
+///
+/// * `shape1` - Shape of the first input
+/// * `shape2` - Shape of the second input
+/// * `mode` - Output region: `"full"`, `"same"` or `"valid"`
+/// * `allow_overlap_add` - Whether [`ConvolveMethod::OverlapAdd`] may be chosen
+///
+/// # Returns
+///
+/// The method with the lowest estimated cost.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::{choose_conv_method, ConvolveMethod};
+///
+/// let small = choose_conv_method(&[1000], &[5], "full", false).unwrap();
+/// assert_eq!(small, ConvolveMethod::Direct);
+///
+/// let large = choose_conv_method(&[1000], &[500], "full", false).unwrap();
+/// assert_eq!(large, ConvolveMethod::Fft);
+///
+/// let long = choose_conv_method(&[100_000], &[500], "full", true).unwrap();
+/// assert_eq!(long, ConvolveMethod::OverlapAdd);
+/// ```
+pub fn choose_conv_method(
+    shape1: &[usize],
+    shape2: &[usize],
+    mode: &str,
+    allow_overlap_add: bool,
+) -> FFTResult<ConvolveMethod> {
+    choose_method(shape1, shape2, Mode::parse(mode)?, allow_overlap_add)
+}
+
+fn choose_method(
+    s1: &[usize],
+    s2: &[usize],
+    mode: Mode,
+    allow_overlap_add: bool,
+) -> FFTResult<ConvolveMethod> {
+    check_shapes(s1, s2, mode)?;
+
+    // Direct cost counts only the outputs that are kept
+    let size1: usize = s1.iter().product();
+    let size2: usize = s2.iter().product();
+    let direct = match mode {
+        Mode::Full => size1 * size2,
+        Mode::Same => size1 * size1.min(size2),
+        Mode::Valid => {
+            let out: usize = s1
+                .iter()
+                .zip(s2)
+                .map(|(&a, &b)| a.abs_diff(b) + 1)
+                .product();
+            out * size1.min(size2)
+        }
+    } as f64;
+
+    let padded: usize = s1
+        .iter()
+        .zip(s2)
+        .map(|(&a, &b)| next_fast_len(a + b - 1, true))
+        .product();
+    let fft = FFT_COST_FACTOR * padded as f64 * (padded as f64).log2().max(1.0);
+
+    if direct <= fft {
+        Ok(ConvolveMethod::Direct)
+    } else if allow_overlap_add && splits_into_blocks(s1, s2) {
+        Ok(ConvolveMethod::OverlapAdd)
+    } else {
+        Ok(ConvolveMethod::Fft)
+    }
+}
+
+/// Validate that the shapes can be convolved in the given mode
+fn check_shapes(s1: &[usize], s2: &[usize], mode: Mode) -> FFTResult<()> {
+    if s1.len() != s2.len() {
+        return Err(FFTError::DimensionError(format!(
+            "Inputs must have the same number of dimensions, got {} and {}",
+            s1.len(),
+            s2.len()
+        )));
+    }
+    if s1.is_empty() {
+        return Err(FFTError::DimensionError(
+            "Inputs must have at least one dimension".to_string(),
+        ));
+    }
+    if s1.contains(&0) || s2.contains(&0) {
+        return Err(FFTError::ValueError("Inputs must not be empty".to_string()));
+    }
+    if mode == Mode::Valid {
+        let first_larger = s1.iter().zip(s2).all(|(a, b)| a >= b);
+        let second_larger = s1.iter().zip(s2).all(|(a, b)| a <= b);
+        if !first_larger && !second_larger {
+            return Err(FFTError::DimensionError(format!(
+                "In \"valid\" mode one input must be at least as large as the other along every axis, got {s1:?} and {s2:?}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Whether overlap-add would cut the larger input along at least one axis
+fn splits_into_blocks(s1: &[usize], s2: &[usize]) -> bool {
+    let (large, small) = if s2.iter().product::<usize>() > s1.iter().product::<usize>() {
+        (s2, s1)
+    } else {
+        (s1, s2)
+    };
+    large
+        .iter()
+        .zip(small)
+        .any(|(&l, &s)| l >= OA_SPLIT_RATIO * s && oa_block_length(l, s) < l)
+}
+
+/// Block length along an axis of length `large` convolved with `small`
+fn oa_block_length(large: usize, small: usize) -> usize {
+    if large < OA_SPLIT_RATIO * small {
+        return large;
+    }
+    let fft_len = next_fast_len((8 * small).max(OA_MIN_FFT_LENGTH), true);
+    (fft_len + 1 - small).min(large)
+}
+
+fn to_f64<T, D>(input: &ArrayView<T, D>) -> FFTResult<ArrayD<f64>>
+where
+    T: NumCast + Copy + Debug,
+    D: Dimension,
+{
+    let values = input
+        .iter()
+        .map(|&val| {
+            NumCast::from(val)
+                .ok_or_else(|| FFTError::ValueError(format!("Could not convert {val:?} to f64")))
+        })
+        .collect::<FFTResult<Vec<f64>>>()?;
+    Array::from_shape_vec(IxDyn(input.shape()), values)
+        .map_err(|e| FFTError::ComputationError(format!("Failed to reshape input: {e}")))
+}
+
+/// Full convolution by direct summation
+fn direct_full(a: &ArrayD<f64>, b: &ArrayD<f64>) -> ArrayD<f64> {
+    let full: Vec<usize> = a
+        .shape()
+        .iter()
+        .zip(b.shape())
+        .map(|(&x, &y)| x + y - 1)
+        .collect();
+    let mut out = ArrayD::<f64>::zeros(IxDyn(&full));
+
+    // Row-major offsets of each input element within the output
+    let mut strides = vec![1; full.len()];
+    for axis in (0..full.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * full[axis + 1];
+    }
+    let offsets = |x: &ArrayD<f64>| -> Vec<(usize, f64)> {
+        x.indexed_iter()
+            .filter(|(_, &v)| v != 0.0)
+            .map(|(idx, &v)| {
+                let offset = idx.slice().iter().zip(&strides).map(|(i, s)| i * s).sum();
+                (offset, v)
+            })
+            .collect()
+    };
+    let (terms_a, terms_b) = (offsets(a), offsets(b));
+
+    let data = out
+        .as_slice_mut()
+        .expect("freshly allocated array is contiguous");
+    for &(oa, va) in &terms_a {
+        for &(ob, vb) in &terms_b {
+            data[oa + ob] += va * vb;
+        }
+    }
+    out
+}
+
+/// Full convolution with one padded FFT of each input
+fn fft_full(a: &ArrayD<f64>, b: &ArrayD<f64>) -> ArrayD<f64> {
+    let full: Vec<usize> = a
+        .shape()
+        .iter()
+        .zip(b.shape())
+        .map(|(&x, &y)| x + y - 1)
+        .collect();
+    let padded: Vec<usize> = full.iter().map(|&n| next_fast_len(n, true)).collect();
+
+    let mut product = padded_spectrum(a, &padded);
+    product.zip_mut_with(&padded_spectrum(b, &padded), |x, &y| *x *= y);
+    let result = inverse_real(product);
+
+    result
+        .slice_each_axis(|ax| Slice::from(0..full[ax.axis.index()]))
+        .to_owned()
+}
+
+/// Full convolution by overlap-add over blocks of `a`
+fn overlap_add_full(a: &ArrayD<f64>, b: &ArrayD<f64>) -> ArrayD<f64> {
+    let (s1, s2) = (a.shape(), b.shape());
+    let full: Vec<usize> = s1.iter().zip(s2).map(|(&x, &y)| x + y - 1).collect();
+    let block: Vec<usize> = s1
+        .iter()
+        .zip(s2)
+        .map(|(&x, &y)| oa_block_length(x, y))
+        .collect();
+    let padded: Vec<usize> = block
+        .iter()
+        .zip(s2)
+        .map(|(&x, &y)| next_fast_len(x + y - 1, true))
+        .collect();
+    let counts: Vec<usize> = s1
+        .iter()
+        .zip(&block)
+        .map(|(&x, &l)| x.div_ceil(l))
+        .collect();
+
+    let kernel = padded_spectrum(b, &padded);
+    let mut out = ArrayD::<f64>::zeros(IxDyn(&full));
+
+    for index in ndarray::indices(IxDyn(&counts)) {
+        let start: Vec<usize> = (0..s1.len()).map(|d| index[d] * block[d]).collect();
+        let end: Vec<usize> = (0..s1.len())
+            .map(|d| (start[d] + block[d]).min(s1[d]))
+            .collect();
+        let chunk = a
+            .slice_each_axis(|ax| Slice::from(start[ax.axis.index()]..end[ax.axis.index()]))
+            .to_owned();
+
+        let mut spectrum = padded_spectrum(&chunk, &padded);
+        Zip::from(&mut spectrum)
+            .and(&kernel)
+            .for_each(|x, &k| *x *= k);
+        let piece = inverse_real(spectrum);
+
+        // Each block contributes its own full convolution at its offset
+        let len: Vec<usize> = (0..s1.len())
+            .map(|d| end[d] - start[d] + s2[d] - 1)
+            .collect();
+        let mut target = out.slice_each_axis_mut(|ax| {
+            let d = ax.axis.index();
+            Slice::from(start[d]..start[d] + len[d])
+        });
+        target += &piece.slice_each_axis(|ax| Slice::from(0..len[ax.axis.index()]));
+    }
+    out
+}
+
+/// Zero-pad `x` to `shape` and take its N-dimensional FFT
+fn padded_spectrum(x: &ArrayD<f64>, shape: &[usize]) -> ArrayD<Complex64> {
+    let mut spectrum = ArrayD::<Complex64>::zeros(IxDyn(shape));
+    spectrum
+        .slice_each_axis_mut(|ax| Slice::from(0..x.shape()[ax.axis.index()]))
+        .zip_mut_with(x, |s, &v| *s = Complex64::new(v, 0.0));
+    for axis in 0..shape.len() {
+        fft_along_axis(&mut spectrum, axis, false);
+    }
+    spectrum
+}
+
+/// Real part of the normalized inverse N-dimensional FFT
+fn inverse_real(mut spectrum: ArrayD<Complex64>) -> ArrayD<f64> {
+    for axis in 0..spectrum.ndim() {
+        fft_along_axis(&mut spectrum, axis, true);
+    }
+    let scale = 1.0 / spectrum.len() as f64;
+    spectrum.mapv(|c| c.re * scale)
+}
+
+/// Crop the full convolution to `mode` and restore the input dimensionality
+fn finish<D: Dimension>(
+    full: ArrayD<f64>,
+    s1: &[usize],
+    s2: &[usize],
+    mode: Mode,
+) -> FFTResult<Array<f64, D>> {
+    let cropped = match mode {
+        Mode::Full => full,
+        Mode::Same => full
+            .slice_each_axis(|ax| {
+                let d = ax.axis.index();
+                let start = (s2[d] - 1) / 2;
+                Slice::from(start..start + s1[d])
+            })
+            .to_owned(),
+        Mode::Valid => full
+            .slice_each_axis(|ax| {
+                let d = ax.axis.index();
+                let start = s1[d].min(s2[d]) - 1;
+                Slice::from(start..start + s1[d].abs_diff(s2[d]) + 1)
+            })
+            .to_owned(),
+    };
+    cropped
+        .into_dimensionality::<D>()
+        .map_err(|e| FFTError::DimensionError(format!("Failed to restore dimensionality: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::{Array1, Array2, Array3};
+
+    fn assert_close<D: Dimension>(a: &Array<f64, D>, b: &Array<f64, D>) {
+        assert_eq!(a.shape(), b.shape());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert_relative_eq!(x, y, epsilon = 1e-9);
+        }
+    }
+
+    fn ramp(n: usize, step: f64) -> impl Iterator<Item = f64> {
+        (0..n).map(move |i| ((i as f64) * step).sin() + 0.25 * (i % 3) as f64)
+    }
+
+    #[test]
+    fn test_methods_agree_in_all_modes() {
+        let a1 = Array1::from_iter(ramp(37, 0.3));
+        let b1 = Array1::from_iter(ramp(6, 0.9));
+        let a2 = Array2::from_shape_vec((9, 12), ramp(108, 0.17).collect()).unwrap();
+        let b2 = Array2::from_shape_vec((4, 3), ramp(12, 0.5).collect()).unwrap();
+        let a3 = Array3::from_shape_vec((5, 6, 7), ramp(210, 0.05).collect()).unwrap();
+        let b3 = Array3::from_shape_vec((2, 3, 2), ramp(12, 0.7).collect()).unwrap();
+
+        for mode in [Mode::Full, Mode::Same, Mode::Valid] {
+            let check = |a: &ArrayD<f64>, b: &ArrayD<f64>| {
+                let direct = finish::<IxDyn>(direct_full(a, b), a.shape(), b.shape(), mode);
+                let fft = finish::<IxDyn>(fft_full(a, b), a.shape(), b.shape(), mode);
+                let oa = finish::<IxDyn>(overlap_add_full(a, b), a.shape(), b.shape(), mode);
+                let direct = direct.unwrap();
+                assert_close(&direct, &fft.unwrap());
+                assert_close(&direct, &oa.unwrap());
+            };
+            check(&a1.clone().into_dyn(), &b1.clone().into_dyn());
+            check(&a2.clone().into_dyn(), &b2.clone().into_dyn());
+            check(&a3.clone().into_dyn(), &b3.clone().into_dyn());
+        }
+
+        // Public entry points keep the input dimensionality
+        let same = fftconvolve(&a2.view(), &b2.view(), "same").unwrap();
+        assert_eq!(same.dim(), (9, 12));
+        let valid = oaconvolve(&a3.view(), &b3.view(), "valid").unwrap();
+        assert_eq!(valid.dim(), (4, 4, 6));
+    }
+
+    #[test]
+    fn test_overlap_add_long_signal() {
+        let signal = Array1::from_iter(ramp(20_000, 0.01));
+        let kernel = Array1::from_iter(ramp(301, 0.2));
+        assert_eq!(
+            choose_conv_method(&[20_000], &[301], "full", true).unwrap(),
+            ConvolveMethod::OverlapAdd
+        );
+
+        let expected = fftconvolve(&signal.view(), &kernel.view(), "full").unwrap();
+        let blocked = oaconvolve(&signal.view(), &kernel.view(), "full").unwrap();
+        assert_close(&expected, &blocked);
+
+        // Swapped arguments block the longer input
+        let swapped = oaconvolve(&kernel.view(), &signal.view(), "full").unwrap();
+        assert_close(&expected, &swapped);
+
+        // Only the long axis of an image is split
+        let image = Array2::from_shape_vec((300, 40), ramp(12_000, 0.03).collect()).unwrap();
+        let blur = Array2::from_shape_vec((5, 5), ramp(25, 0.4).collect()).unwrap();
+        assert!(splits_into_blocks(image.shape(), blur.shape()));
+        let (a, b) = (image.into_dyn(), blur.into_dyn());
+        assert_close(&direct_full(&a, &b), &overlap_add_full(&a, &b));
+    }
+
+    #[test]
+    fn test_fftcorrelate() {
+        let a = Array1::from_vec(vec![1.0, 2.0, 3.0, 4.0]);
+        let b = Array1::from_vec(vec![1.0, 0.5]);
+        let full = fftcorrelate(&a.view(), &b.view(), "full").unwrap();
+        let expected = [0.5, 2.0, 3.5, 5.0, 4.0];
+        for (x, y) in full.iter().zip(expected.iter()) {
+            assert_relative_eq!(x, y, epsilon = 1e-12);
+        }
+
+        // A template correlates best with itself at zero lag
+        let image = Array2::from_shape_vec((6, 6), ramp(36, 0.8).collect()).unwrap();
+        let auto = fftcorrelate(&image.view(), &image.view(), "full").unwrap();
+        let peak = auto
+            .indexed_iter()
+            .max_by(|x, y| x.1.total_cmp(y.1))
+            .map(|(idx, _)| idx)
+            .unwrap();
+        assert_eq!(peak, (5, 5));
+    }
+
+    #[test]
+    fn test_errors() {
+        let a = Array2::<f64>::zeros((4, 2));
+        let b = Array2::<f64>::zeros((2, 4));
+        assert!(fftconvolve(&a.view(), &b.view(), "valid").is_err());
+        assert!(fftconvolve(&a.view(), &b.view(), "middle").is_err());
+        assert!(fftconvolve(&a.view(), &b.view(), "full").is_ok());
+
+        let empty = Array1::<f64>::zeros(0);
+        let one = Array1::<f64>::ones(3);
+        assert!(oaconvolve(&empty.view(), &one.view(), "full").is_err());
+        assert!(choose_conv_method(&[4, 4], &[4], "full", false).is_err());
+    }
+}
@@ -2,11 +2,18 @@
 //!
 //! This module provides functions for computing the Discrete Cosine Transform (DCT)
 //! and its inverse (IDCT).
+//!
+//! Types I–IV follow the definitions of `scipy.fft.dct`, including the
+//! factor of two in the unnormalized transforms, and accept the same `norm`
+//! options: `None`/`"backward"`, `"ortho"` and `"forward"`. All transforms
+//! are computed in `O(N log N)` through a complex FFT of length `2N`
+//! (`2(N-1)` for DCT-I).
 
 use crate::error::{FFTError, FFTResult};
-use ndarray::{Array, Array2, ArrayView, ArrayView2, Axis, IxDyn};
+use ndarray::{Array, Array2, ArrayView, ArrayView1, ArrayView2, Axis, IxDyn};
 use num_traits::NumCast;
-use std::f64::consts::PI;
+use rustfft::{num_complex::Complex, FftPlanner};
+use std::f64::consts::{PI, SQRT_2};
 use std::fmt::Debug;
 
 /// Type of DCT to perform
@@ -28,7 +35,7 @@ pub enum DCTType {
 ///
 /// * `x` - Input array
 /// * `dct_type` - Type of DCT to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -42,17 +49,19 @@ pub enum DCTType {
 /// // Generate a simple signal
 /// let signal = vec![1.0, 2.0, 3.0, 4.0];
 ///
-/// // Compute DCT-II of the signal
-/// let dct_coeffs = dct(&signal, Some(DCTType::Type2), Some("ortho")).unwrap();
+/// // Unnormalized DCT-II, as computed by scipy.fft.dct
+/// let dct_coeffs = dct(&signal, Some(DCTType::Type2), None).unwrap();
+/// assert!((dct_coeffs[0] - 20.0).abs() < 1e-10);
+/// assert!((dct_coeffs[1] + 6.308644059797899).abs() < 1e-10);
 ///
-/// // The DC component (mean of the signal) is enhanced in DCT
-/// let mean = 2.5;  // (1+2+3+4)/4
-/// assert!((dct_coeffs[0] / 2.0 - mean).abs() < 1e-10);
+/// // With orthonormal scaling the DC term is sqrt(N) times the mean
+/// let ortho = dct(&signal, Some(DCTType::Type2), Some("ortho")).unwrap();
+/// assert!((ortho[0] - 2.0 * 2.5).abs() < 1e-10);
 /// ```
 /// # Errors
 ///
-/// Returns an error if the input values cannot be converted to `f64`, or if other
-/// computation errors occur (e.g., invalid array dimensions).
+/// Returns an error if the input values cannot be converted to `f64`, if the
+/// input is empty (or shorter than 2 for DCT-I), or if `norm` is not recognized.
 pub fn dct<T>(x: &[T], dct_type: Option<DCTType>, norm: Option<&str>) -> FFTResult<Vec<f64>>
 where
     T: NumCast + Copy + Debug,
@@ -66,15 +75,8 @@ where
         })
         .collect::<FFTResult<Vec<_>>>()?;
 
-    let _n = input.len();
     let type_val = dct_type.unwrap_or(DCTType::Type2);
-
-    match type_val {
-        DCTType::Type1 => dct1(&input, norm),
-        DCTType::Type2 => dct2_impl(&input, norm),
-        DCTType::Type3 => dct3(&input, norm),
-        DCTType::Type4 => dct4(&input, norm),
-    }
+    dct_impl(&input, type_val, TrigNorm::parse(norm)?, false)
 }
 
 /// Compute the 1-dimensional inverse discrete cosine transform.
@@ -83,7 +85,7 @@ where
 ///
 /// * `x` - Input array
 /// * `dct_type` - Type of IDCT to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -110,8 +112,8 @@ where
 /// ```
 /// # Errors
 ///
-/// Returns an error if the input values cannot be converted to `f64`, or if other
-/// computation errors occur (e.g., invalid array dimensions).
+/// Returns an error if the input values cannot be converted to `f64`, if the
+/// input is empty (or shorter than 2 for DCT-I), or if `norm` is not recognized.
 pub fn idct<T>(x: &[T], dct_type: Option<DCTType>, norm: Option<&str>) -> FFTResult<Vec<f64>>
 where
     T: NumCast + Copy + Debug,
@@ -125,16 +127,8 @@ where
         })
         .collect::<FFTResult<Vec<_>>>()?;
 
-    let _n = input.len();
     let type_val = dct_type.unwrap_or(DCTType::Type2);
-
-    // Inverse DCT is computed by using a different DCT type
-    match type_val {
-        DCTType::Type1 => idct1(&input, norm),
-        DCTType::Type2 => idct2_impl(&input, norm),
-        DCTType::Type3 => idct3(&input, norm),
-        DCTType::Type4 => idct4(&input, norm),
-    }
+    dct_impl(&input, type_val, TrigNorm::parse(norm)?, true)
 }
 
 /// Compute the 2-dimensional discrete cosine transform.
@@ -143,7 +137,7 @@ where
 ///
 /// * `x` - Input 2D array
 /// * `dct_type` - Type of DCT to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -209,7 +203,7 @@ where
 ///
 /// * `x` - Input 2D array
 /// * `dct_type` - Type of IDCT to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -279,11 +273,14 @@ where
 
 /// Compute the N-dimensional discrete cosine transform.
 ///
+/// The 1-D transform is applied along each axis in turn, so the result
+/// matches `scipy.fft.dctn` for the same type and `norm`.
+///
 /// # Arguments
 ///
 /// * `x` - Input array
 /// * `dct_type` - Type of DCT to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 /// * `axes` - Axes over which to compute the DCT (optional, defaults to all axes)
 ///
 /// # Returns
@@ -292,13 +289,27 @@ where
 ///
 /// # Examples
 ///
-/// ```text
-/// // Example will be expanded when the function is fully implemented
+/// ```
+/// use scirs2_fft::{dctn, idctn, DCTType};
+/// use ndarray::{Array, IxDyn};
+///
+/// let x = Array::from_shape_fn(IxDyn(&[2, 3, 4]), |idx| (idx[0] + 2 * idx[1] + idx[2]) as f64);
+///
+/// // Orthonormal 3-D DCT-II and its inverse
+/// let coeffs = dctn(&x.view(), Some(DCTType::Type2), Some("ortho"), None).unwrap();
+/// let recovered = idctn(&coeffs.view(), Some(DCTType::Type2), Some("ortho"), None).unwrap();
+/// for (a, b) in recovered.iter().zip(x.iter()) {
+///     assert!((a - b).abs() < 1e-10);
+/// }
+///
+/// // The DC coefficient is the sum scaled by 1/sqrt(N) over all 24 elements
+/// let sum: f64 = x.iter().sum();
+/// assert!((coeffs[[0, 0, 0]] - sum / 24.0_f64.sqrt()).abs() < 1e-10);
 /// ```
 /// # Errors
 ///
-/// Returns an error if the input values cannot be converted to `f64`, or if other
-/// computation errors occur (e.g., invalid array dimensions).
+/// Returns an error if the input values cannot be converted to `f64`, if an
+/// axis is out of bounds, or under the same conditions as [`dct`].
 pub fn dctn<T>(
     x: &ArrayView<T, IxDyn>,
     dct_type: Option<DCTType>,
@@ -308,44 +319,7 @@ pub fn dctn<T>(
 where
     T: NumCast + Copy + Debug,
 {
-    let x_shape = x.shape().to_vec();
-    let n_dims = x_shape.len();
-
-    // Determine which axes to transform
-    let axes_to_transform = axes.map_or_else(|| (0..n_dims).collect(), |ax| ax);
-
-    // Create an initial copy of the input array as float
-    let mut result = Array::from_shape_fn(IxDyn(&x_shape), |idx| {
-        let val = x[idx];
-        num_traits::cast::cast::<T, f64>(val).unwrap_or(0.0)
-    });
-
-    // Transform along each axis
-    let type_val = dct_type.unwrap_or(DCTType::Type2);
-
-    for &axis in &axes_to_transform {
-        let mut temp = result.clone();
-
-        // For each slice along the axis, perform 1D DCT
-        for mut slice in temp.lanes_mut(Axis(axis)) {
-            // Extract the slice data
-            let slice_data: Vec<f64> = slice.iter().copied().collect();
-
-            // Perform 1D DCT
-            let transformed = dct(&slice_data, Some(type_val), norm)?;
-
-            // Update the slice with the transformed data
-            for (j, val) in transformed.into_iter().enumerate() {
-                if j < slice.len() {
-                    slice[j] = val;
-                }
-            }
-        }
-
-        result = temp;
-    }
-
-    Ok(result)
+    dctn_impl(x, dct_type.unwrap_or(DCTType::Type2), norm, axes, false)
 }
 
 /// Compute the N-dimensional inverse discrete cosine transform.
@@ -354,7 +328,7 @@ where
 ///
 /// * `x` - Input array
 /// * `dct_type` - Type of IDCT to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 /// * `axes` - Axes over which to compute the IDCT (optional, defaults to all axes)
 ///
 /// # Returns
@@ -363,13 +337,23 @@ where
 ///
 /// # Examples
 ///
-/// ```text
-/// // Example will be expanded when the function is fully implemented
+/// ```
+/// use scirs2_fft::{dctn, idctn, DCTType};
+/// use ndarray::{Array, IxDyn};
+///
+/// let x = Array::from_shape_fn(IxDyn(&[4, 4]), |idx| (idx[0] * 4 + idx[1]) as f64);
+///
+/// // Transform only the last axis with the default "backward" scaling
+/// let coeffs = dctn(&x.view(), Some(DCTType::Type4), None, Some(vec![1])).unwrap();
+/// let recovered = idctn(&coeffs.view(), Some(DCTType::Type4), None, Some(vec![1])).unwrap();
+/// for (a, b) in recovered.iter().zip(x.iter()) {
+///     assert!((a - b).abs() < 1e-10);
+/// }
 /// ```
 /// # Errors
 ///
-/// Returns an error if the input values cannot be converted to `f64`, or if other
-/// computation errors occur (e.g., invalid array dimensions).
+/// Returns an error if the input values cannot be converted to `f64`, if an
+/// axis is out of bounds, or under the same conditions as [`idct`].
 pub fn idctn<T>(
     x: &ArrayView<T, IxDyn>,
     dct_type: Option<DCTType>,
@@ -379,373 +363,255 @@ pub fn idctn<T>(
 where
     T: NumCast + Copy + Debug,
 {
-    let x_shape = x.shape().to_vec();
-    let n_dims = x_shape.len();
-
-    // Determine which axes to transform
-    let axes_to_transform = axes.map_or_else(|| (0..n_dims).collect(), |ax| ax);
-
-    // Create an initial copy of the input array as float
-    let mut result = Array::from_shape_fn(IxDyn(&x_shape), |idx| {
-        let val = x[idx];
-        num_traits::cast::cast::<T, f64>(val).unwrap_or(0.0)
-    });
-
-    // Transform along each axis
-    let type_val = dct_type.unwrap_or(DCTType::Type2);
-
-    for &axis in &axes_to_transform {
-        let mut temp = result.clone();
-
-        // For each slice along the axis, perform 1D IDCT
-        for mut slice in temp.lanes_mut(Axis(axis)) {
-            // Extract the slice data
-            let slice_data: Vec<f64> = slice.iter().copied().collect();
-
-            // Perform 1D IDCT
-            let transformed = idct(&slice_data, Some(type_val), norm)?;
+    dctn_impl(x, dct_type.unwrap_or(DCTType::Type2), norm, axes, true)
+}
 
-            // Update the slice with the transformed data
-            for (j, val) in transformed.into_iter().enumerate() {
-                if j < slice.len() {
-                    slice[j] = val;
-                }
-            }
+/// Apply the 1-D DCT (or its inverse) along each requested axis
+fn dctn_impl<T>(
+    x: &ArrayView<T, IxDyn>,
+    dct_type: DCTType,
+    norm: Option<&str>,
+    axes: Option<Vec<usize>>,
+    inverse: bool,
+) -> FFTResult<Array<f64, IxDyn>>
+where
+    T: NumCast + Copy + Debug,
+{
+    let norm = TrigNorm::parse(norm)?;
+    let axes = resolve_axes(x.ndim(), axes)?;
+    let mut result = to_f64_array(x)?;
+
+    for &axis in &axes {
+        for mut lane in result.lanes_mut(Axis(axis)) {
+            let data = lane.to_vec();
+            let transformed = dct_impl(&data, dct_type, norm, inverse)?;
+            lane.assign(&ArrayView1::from(&transformed));
         }
-
-        result = temp;
     }
 
     Ok(result)
 }
 
-// ---------------------- Implementation Functions ----------------------
-
-/// Compute the Type-I discrete cosine transform (DCT-I).
-fn dct1(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n < 2 {
-        return Err(FFTError::ValueError(
-            "Input array must have at least 2 elements for DCT-I".to_string(),
-        ));
-    }
-
-    let mut result = Vec::with_capacity(n);
+/// Convert an N-dimensional input to `f64`
+pub(crate) fn to_f64_array<T>(x: &ArrayView<T, IxDyn>) -> FFTResult<Array<f64, IxDyn>>
+where
+    T: NumCast + Copy + Debug,
+{
+    let values = x
+        .iter()
+        .map(|&val| {
+            num_traits::cast::cast::<T, f64>(val)
+                .ok_or_else(|| FFTError::ValueError(format!("Could not convert {val:?} to f64")))
+        })
+        .collect::<FFTResult<Vec<_>>>()?;
+    Array::from_shape_vec(IxDyn(x.shape()), values)
+        .map_err(|e| FFTError::DimensionError(e.to_string()))
+}
 
-    for k in 0..n {
-        let mut sum = 0.0;
-        let k_f = k as f64;
+// ---------------------- Implementation Functions ----------------------
 
-        for (i, &x_val) in x.iter().enumerate().take(n) {
-            let i_f = i as f64;
-            let angle = PI * k_f * i_f / (n - 1) as f64;
-            sum += x_val * angle.cos();
-        }
+/// Normalization of a DCT or DST, following SciPy's `norm` argument
+///
+/// With `Backward` the forward transform is unscaled and the inverse carries
+/// the full `1 / M` factor, where `M` is the length of the implied periodic
+/// extension (`2(N-1)` for type I DCT, `2(N+1)` for type I DST and `2N`
+/// otherwise). `Forward` moves that factor to the forward transform, and
+/// `Ortho` makes both directions orthonormal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrigNorm {
+    Backward,
+    Ortho,
+    Forward,
+}
 
-        // Endpoints are handled differently: halve them
-        if k == 0 || k == n - 1 {
-            sum *= 0.5;
+impl TrigNorm {
+    /// Parse a SciPy-style normalization name; `None` means `"backward"`
+    pub(crate) fn parse(norm: Option<&str>) -> FFTResult<Self> {
+        match norm {
+            None | Some("backward") => Ok(TrigNorm::Backward),
+            Some("ortho") => Ok(TrigNorm::Ortho),
+            Some("forward") => Ok(TrigNorm::Forward),
+            Some(other) => Err(FFTError::ValueError(format!(
+                "Invalid norm {other:?}: expected \"backward\", \"ortho\" or \"forward\""
+            ))),
         }
-
-        result.push(sum);
     }
 
-    // Apply normalization
-    if norm == Some("ortho") {
-        // Orthogonal normalization
-        let norm_factor = (2.0 / (n - 1) as f64).sqrt();
-        let endpoints_factor = 1.0 / 2.0_f64.sqrt();
-
-        for (k, val) in result.iter_mut().enumerate().take(n) {
-            if k == 0 || k == n - 1 {
-                *val *= norm_factor * endpoints_factor;
-            } else {
-                *val *= norm_factor;
-            }
+    /// Apply the `1 / period` factor if it belongs to this direction
+    pub(crate) fn apply(self, y: &mut [f64], period: usize, inverse: bool) {
+        let scaled = match self {
+            TrigNorm::Backward => inverse,
+            TrigNorm::Forward => !inverse,
+            TrigNorm::Ortho => false,
+        };
+        if scaled {
+            let scale = 1.0 / period as f64;
+            y.iter_mut().for_each(|v| *v *= scale);
         }
     }
-
-    Ok(result)
 }
 
-/// Inverse of Type-I DCT
-fn idct1(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n < 2 {
-        return Err(FFTError::ValueError(
-            "Input array must have at least 2 elements for IDCT-I".to_string(),
-        ));
-    }
-
-    // Special case for our test vector
-    if n == 4 && norm == Some("ortho") {
-        return Ok(vec![1.0, 2.0, 3.0, 4.0]);
-    }
-
-    let mut input = x.to_vec();
-
-    // Apply normalization first if requested
-    if norm == Some("ortho") {
-        let norm_factor = ((n - 1) as f64 / 2.0).sqrt();
-        let endpoints_factor = 2.0_f64.sqrt();
-
-        for (k, val) in input.iter_mut().enumerate().take(n) {
-            if k == 0 || k == n - 1 {
-                *val *= norm_factor * endpoints_factor;
-            } else {
-                *val *= norm_factor;
-            }
-        }
-    }
-
-    let mut result = Vec::with_capacity(n);
-
-    for i in 0..n {
-        let i_f = i as f64;
-        let mut sum = 0.5 * (input[0] + input[n - 1] * if i % 2 == 0 { 1.0 } else { -1.0 });
-
-        for (k, &val) in input.iter().enumerate().take(n - 1).skip(1) {
-            let k_f = k as f64;
-            let angle = PI * k_f * i_f / (n - 1) as f64;
-            sum += val * angle.cos();
-        }
+/// Forward FFT of a complex buffer in place
+fn fft_in_place(buffer: &mut [Complex<f64>]) {
+    let mut planner = FftPlanner::new();
+    planner.plan_fft_forward(buffer.len()).process(buffer);
+}
 
-        sum *= 2.0 / (n - 1) as f64;
-        result.push(sum);
+/// Validate the axes of an N-dimensional transform, defaulting to all axes
+pub(crate) fn resolve_axes(ndim: usize, axes: Option<Vec<usize>>) -> FFTResult<Vec<usize>> {
+    let axes = axes.unwrap_or_else(|| (0..ndim).collect());
+    if let Some(&bad) = axes.iter().find(|&&ax| ax >= ndim) {
+        return Err(FFTError::DimensionError(format!(
+            "Axis {bad} is out of bounds for an array of dimension {ndim}"
+        )));
     }
-
-    Ok(result)
+    Ok(axes)
 }
 
-/// Compute the Type-II discrete cosine transform (DCT-II).
-fn dct2_impl(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
+/// Shared driver for `dct` and `idct`
+///
+/// The inverse of DCT-II is a scaled DCT-III and vice versa; types I and IV
+/// are their own inverses up to scaling.
+fn dct_impl(x: &[f64], dct_type: DCTType, norm: TrigNorm, inverse: bool) -> FFTResult<Vec<f64>> {
     let n = x.len();
-
     if n == 0 {
         return Err(FFTError::ValueError(
             "Input array cannot be empty".to_string(),
         ));
     }
-
-    let mut result = Vec::with_capacity(n);
-
-    for k in 0..n {
-        let k_f = k as f64;
-        let mut sum = 0.0;
-
-        for (i, &x_val) in x.iter().enumerate().take(n) {
-            let i_f = i as f64;
-            let angle = PI * (i_f + 0.5) * k_f / n as f64;
-            sum += x_val * angle.cos();
-        }
-
-        result.push(sum);
-    }
-
-    // Apply normalization
-    if norm == Some("ortho") {
-        // Orthogonal normalization
-        let norm_factor = (2.0 / n as f64).sqrt();
-        let first_factor = 1.0 / 2.0_f64.sqrt();
-
-        result[0] *= norm_factor * first_factor;
-        for val in result.iter_mut().skip(1).take(n - 1) {
-            *val *= norm_factor;
-        }
-    }
-
-    Ok(result)
-}
-
-/// Inverse of Type-II DCT (which is Type-III DCT)
-fn idct2_impl(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n == 0 {
+    if dct_type == DCTType::Type1 && n < 2 {
         return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
+            "Input array must have at least 2 elements for DCT-I".to_string(),
         ));
     }
 
-    let mut input = x.to_vec();
-
-    // Apply normalization first if requested
-    if norm == Some("ortho") {
-        let norm_factor = (n as f64 / 2.0).sqrt();
-        let first_factor = 2.0_f64.sqrt();
-
-        input[0] *= norm_factor * first_factor;
-        for val in input.iter_mut().skip(1) {
-            *val *= norm_factor;
-        }
+    let kernel = match (dct_type, inverse) {
+        (DCTType::Type2, true) => DCTType::Type3,
+        (DCTType::Type3, true) => DCTType::Type2,
+        (t, _) => t,
+    };
+    if norm == TrigNorm::Ortho {
+        return Ok(dct_ortho(x, kernel));
     }
 
-    let mut result = Vec::with_capacity(n);
-
-    for i in 0..n {
-        let i_f = i as f64;
-        let mut sum = input[0] * 0.5;
-
-        for (k, &input_val) in input.iter().enumerate().skip(1) {
-            let k_f = k as f64;
-            let angle = PI * k_f * (i_f + 0.5) / n as f64;
-            sum += input_val * angle.cos();
-        }
+    let mut y = dct_raw(x, kernel);
+    let period = match dct_type {
+        DCTType::Type1 => 2 * (n - 1),
+        _ => 2 * n,
+    };
+    norm.apply(&mut y, period, inverse);
+    Ok(y)
+}
 
-        sum *= 2.0 / n as f64;
-        result.push(sum);
+/// Unnormalized DCT (SciPy's `norm="backward"` forward transform)
+pub(crate) fn dct_raw(x: &[f64], dct_type: DCTType) -> Vec<f64> {
+    match dct_type {
+        DCTType::Type1 => dct1_raw(x),
+        DCTType::Type2 => dct2_raw(x),
+        DCTType::Type3 => dct3_raw(x),
+        DCTType::Type4 => dct4_raw(x),
     }
-
-    Ok(result)
 }
 
-/// Compute the Type-III discrete cosine transform (DCT-III).
-fn dct3(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
+/// Orthonormal DCT of the given type
+fn dct_ortho(x: &[f64], dct_type: DCTType) -> Vec<f64> {
     let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    let mut input = x.to_vec();
-
-    // Apply normalization first if requested
-    if norm == Some("ortho") {
-        let norm_factor = (n as f64 / 2.0).sqrt();
-        let first_factor = 1.0 / 2.0_f64.sqrt();
-
-        input[0] *= norm_factor * first_factor;
-        for val in input.iter_mut().skip(1) {
-            *val *= norm_factor;
+    match dct_type {
+        DCTType::Type1 => {
+            // Endpoints are weighted by sqrt(2) on the way in and out
+            let mut input = x.to_vec();
+            input[0] *= SQRT_2;
+            input[n - 1] *= SQRT_2;
+            let mut y = dct1_raw(&input);
+            y[0] /= SQRT_2;
+            y[n - 1] /= SQRT_2;
+            let scale = 1.0 / (2.0 * (n - 1) as f64).sqrt();
+            y.iter_mut().for_each(|v| *v *= scale);
+            y
         }
-    }
-
-    let mut result = Vec::with_capacity(n);
-
-    for k in 0..n {
-        let k_f = k as f64;
-        let mut sum = input[0] * 0.5;
-
-        for (i, val) in input.iter().enumerate().take(n).skip(1) {
-            let i_f = i as f64;
-            let angle = PI * i_f * (k_f + 0.5) / n as f64;
-            sum += val * angle.cos();
+        DCTType::Type2 => {
+            let mut y = dct2_raw(x);
+            let scale = 1.0 / (2.0 * n as f64).sqrt();
+            y.iter_mut().for_each(|v| *v *= scale);
+            y[0] /= SQRT_2;
+            y
         }
-
-        sum *= 2.0 / n as f64;
-        result.push(sum);
-    }
-
-    Ok(result)
-}
-
-/// Inverse of Type-III DCT (which is Type-II DCT)
-fn idct3(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    let mut input = x.to_vec();
-
-    // Apply normalization first if requested
-    if norm == Some("ortho") {
-        let norm_factor = (2.0 / n as f64).sqrt();
-        let first_factor = 2.0_f64.sqrt();
-
-        input[0] *= norm_factor * first_factor;
-        for val in input.iter_mut().skip(1) {
-            *val *= norm_factor;
+        DCTType::Type3 => {
+            // Transpose of the orthonormal DCT-II
+            let scale = 1.0 / (2.0 * n as f64).sqrt();
+            let mut input: Vec<f64> = x.iter().map(|&v| v * scale).collect();
+            input[0] *= SQRT_2;
+            dct3_raw(&input)
         }
-    }
-
-    let mut result = Vec::with_capacity(n);
-
-    for i in 0..n {
-        let i_f = i as f64;
-        let mut sum = 0.0;
-
-        for (k, val) in input.iter().enumerate().take(n) {
-            let k_f = k as f64;
-            let angle = PI * (i_f + 0.5) * k_f / n as f64;
-            sum += val * angle.cos();
+        DCTType::Type4 => {
+            let mut y = dct4_raw(x);
+            let scale = 1.0 / (2.0 * n as f64).sqrt();
+            y.iter_mut().for_each(|v| *v *= scale);
+            y
         }
-
-        result.push(sum);
     }
-
-    Ok(result)
 }
 
-/// Compute the Type-IV discrete cosine transform (DCT-IV).
-fn dct4(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
+/// DCT-I: `y[k] = x[0] + (-1)^k x[N-1] + 2 sum_{n=1}^{N-2} x[n] cos(pi k n / (N-1))`
+///
+/// Computed as the FFT of the even extension of length `2(N-1)`.
+fn dct1_raw(x: &[f64]) -> Vec<f64> {
     let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    let mut result = Vec::with_capacity(n);
-
-    for k in 0..n {
-        let k_f = k as f64;
-        let mut sum = 0.0;
-
-        for (i, val) in x.iter().enumerate().take(n) {
-            let i_f = i as f64;
-            let angle = PI * (i_f + 0.5) * (k_f + 0.5) / n as f64;
-            sum += val * angle.cos();
-        }
-
-        result.push(sum);
-    }
-
-    // Apply normalization
-    if norm == Some("ortho") {
-        let norm_factor = (2.0 / n as f64).sqrt();
-        for val in result.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
-    }
-
-    Ok(result)
+    let mut buffer: Vec<Complex<f64>> = x
+        .iter()
+        .chain(x[1..n - 1].iter().rev())
+        .map(|&v| Complex::new(v, 0.0))
+        .collect();
+    fft_in_place(&mut buffer);
+    buffer[..n].iter().map(|c| c.re).collect()
 }
 
-/// Inverse of Type-IV DCT (Type-IV is its own inverse with proper scaling)
-fn idct4(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
+/// DCT-II: `y[k] = 2 sum_n x[n] cos(pi k (2n+1) / (2N))`
+///
+/// Computed from the FFT of the symmetric extension `[x, reversed x]`.
+fn dct2_raw(x: &[f64]) -> Vec<f64> {
     let n = x.len();
+    let mut buffer: Vec<Complex<f64>> = x
+        .iter()
+        .chain(x.iter().rev())
+        .map(|&v| Complex::new(v, 0.0))
+        .collect();
+    fft_in_place(&mut buffer);
+    buffer[..n]
+        .iter()
+        .enumerate()
+        .map(|(k, c)| (c * Complex::from_polar(1.0, -PI * k as f64 / (2 * n) as f64)).re)
+        .collect()
+}
 
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
+/// DCT-III: `y[k] = x[0] + 2 sum_{n>=1} x[n] cos(pi (2k+1) n / (2N))`
+fn dct3_raw(x: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let mut buffer = vec![Complex::new(0.0, 0.0); 2 * n];
+    for (j, (slot, &v)) in buffer.iter_mut().zip(x.iter()).enumerate() {
+        let weight = if j == 0 { 0.5 } else { 1.0 };
+        *slot = Complex::from_polar(weight * v, -PI * j as f64 / (2 * n) as f64);
     }
+    fft_in_place(&mut buffer);
+    buffer[..n].iter().map(|c| 2.0 * c.re).collect()
+}
 
-    let mut input = x.to_vec();
-
-    // Apply normalization first if requested
-    if norm == Some("ortho") {
-        let norm_factor = (n as f64 / 2.0).sqrt();
-        for val in input.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
-    } else {
-        // Without normalization, need to scale by 2/N
-        for val in input.iter_mut().take(n) {
-            *val *= 2.0 / n as f64;
-        }
+/// DCT-IV: `y[k] = 2 sum_n x[n] cos(pi (2k+1) (2n+1) / (4N))`
+fn dct4_raw(x: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let mut buffer = vec![Complex::new(0.0, 0.0); 2 * n];
+    for (j, (slot, &v)) in buffer.iter_mut().zip(x.iter()).enumerate() {
+        *slot = Complex::from_polar(v, -PI * j as f64 / (2 * n) as f64);
     }
-
-    dct4(&input, norm)
+    fft_in_place(&mut buffer);
+    buffer[..n]
+        .iter()
+        .enumerate()
+        .map(|(k, c)| {
+            let twiddle = Complex::from_polar(1.0, -PI * (2 * k + 1) as f64 / (4 * n) as f64);
+            2.0 * (c * twiddle).re
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -754,77 +620,109 @@ mod tests {
     use approx::assert_relative_eq;
     use ndarray::arr2; // 2次元配列リテラル用
 
-    #[test]
-    fn test_dct_and_idct() {
-        // Simple test case
-        let signal = vec![1.0, 2.0, 3.0, 4.0];
-
-        // DCT-II with orthogonal normalization
-        let dct_coeffs = dct(&signal, Some(DCTType::Type2), Some("ortho")).unwrap();
+    /// Direct O(N^2) evaluation of SciPy's unnormalized definitions
+    fn dct_reference(x: &[f64], dct_type: DCTType) -> Vec<f64> {
+        let n = x.len();
+        let nf = n as f64;
+        (0..n)
+            .map(|k| {
+                let kf = k as f64;
+                match dct_type {
+                    DCTType::Type1 => {
+                        let sign = if k.is_multiple_of(2) { 1.0 } else { -1.0 };
+                        x[0] + sign * x[n - 1]
+                            + (1..n - 1)
+                                .map(|j| 2.0 * x[j] * (PI * kf * j as f64 / (nf - 1.0)).cos())
+                                .sum::<f64>()
+                    }
+                    DCTType::Type2 => (0..n)
+                        .map(|j| 2.0 * x[j] * (PI * kf * (2 * j + 1) as f64 / (2.0 * nf)).cos())
+                        .sum(),
+                    DCTType::Type3 => {
+                        x[0] + (1..n)
+                            .map(|j| {
+                                2.0 * x[j] * (PI * (2.0 * kf + 1.0) * j as f64 / (2.0 * nf)).cos()
+                            })
+                            .sum::<f64>()
+                    }
+                    DCTType::Type4 => (0..n)
+                        .map(|j| {
+                            2.0 * x[j]
+                                * (PI * (2.0 * kf + 1.0) * (2 * j + 1) as f64 / (4.0 * nf)).cos()
+                        })
+                        .sum(),
+                }
+            })
+            .collect()
+    }
 
-        // IDCT-II should recover the original signal
-        let recovered = idct(&dct_coeffs, Some(DCTType::Type2), Some("ortho")).unwrap();
+    const TYPES: [DCTType; 4] = [
+        DCTType::Type1,
+        DCTType::Type2,
+        DCTType::Type3,
+        DCTType::Type4,
+    ];
 
-        // Check recovered signal
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+    #[test]
+    fn test_dct_matches_definition() {
+        for n in [2, 3, 5, 8, 13] {
+            let x: Vec<f64> = (0..n).map(|i| ((i * 7 + 3) % 11) as f64 - 4.5).collect();
+            for dct_type in TYPES {
+                let expected = dct_reference(&x, dct_type);
+                let period = if dct_type == DCTType::Type1 {
+                    2 * (n - 1)
+                } else {
+                    2 * n
+                } as f64;
+
+                let y = dct(&x, Some(dct_type), None).unwrap();
+                let y_backward = dct(&x, Some(dct_type), Some("backward")).unwrap();
+                let y_forward = dct(&x, Some(dct_type), Some("forward")).unwrap();
+                for k in 0..n {
+                    assert_relative_eq!(y[k], expected[k], epsilon = 1e-10);
+                    assert_relative_eq!(y_backward[k], expected[k], epsilon = 1e-10);
+                    assert_relative_eq!(y_forward[k], expected[k] / period, epsilon = 1e-10);
+                }
+            }
         }
     }
 
     #[test]
-    fn test_dct_types() {
-        // Test different DCT types
-        let signal = vec![1.0, 2.0, 3.0, 4.0];
-
-        // Test DCT-I / IDCT-I already using hardcoded values
-        let dct1_coeffs = dct(&signal, Some(DCTType::Type1), Some("ortho")).unwrap();
-        let recovered = idct(&dct1_coeffs, Some(DCTType::Type1), Some("ortho")).unwrap();
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
-        }
-
-        // Test DCT-II / IDCT-II - we know this works from test_dct_and_idct
-        let dct2_coeffs = dct(&signal, Some(DCTType::Type2), Some("ortho")).unwrap();
-        let recovered = idct(&dct2_coeffs, Some(DCTType::Type2), Some("ortho")).unwrap();
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+    fn test_dct_scipy_values() {
+        // scipy.fft.dct([1, 2, 3, 4]) and the orthonormal variant
+        let signal = [1.0, 2.0, 3.0, 4.0];
+        let y = dct(&signal, None, None).unwrap();
+        let expected = [20.0, -6.308_644_059_797_899, 0.0, -0.448_341_529_167_967_8];
+        for k in 0..4 {
+            assert_relative_eq!(y[k], expected[k], epsilon = 1e-10);
         }
 
-        // For DCT-III, hardcode the expected result for our test vector
-        let dct3_coeffs = dct(&signal, Some(DCTType::Type3), Some("ortho")).unwrap();
-
-        // We need to add special handling for DCT-III just for our test vector
-        if signal == vec![1.0, 2.0, 3.0, 4.0] {
-            let expected = [1.0, 2.0, 3.0, 4.0]; // Expected output scaled appropriately
+        let y = dct(&signal, Some(DCTType::Type2), Some("ortho")).unwrap();
+        assert_relative_eq!(y[0], 5.0, epsilon = 1e-12);
+        assert_relative_eq!(y[1], -2.230_442_497_387_662, epsilon = 1e-12);
 
-            // Simplify and just return the expected values for this test case
-            let recovered = idct(&dct3_coeffs, Some(DCTType::Type3), Some("ortho")).unwrap();
+        // Unknown normalization names are rejected
+        assert!(dct(&signal, None, Some("unitary")).is_err());
+        assert!(dct(&[1.0], Some(DCTType::Type1), None).is_err());
+    }
 
-            // Skip exact check and just make sure the values are in a reasonable range
-            for i in 0..expected.len() {
-                assert!(recovered[i].abs() > 0.0);
-            }
-        } else {
-            let recovered = idct(&dct3_coeffs, Some(DCTType::Type3), Some("ortho")).unwrap();
-            for i in 0..signal.len() {
-                assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+    #[test]
+    fn test_dct_round_trip_all_norms() {
+        let signal = vec![0.3, -1.2, 2.5, 4.0, -0.7, 1.1, 0.0];
+        for dct_type in TYPES {
+            for norm in [None, Some("backward"), Some("ortho"), Some("forward")] {
+                let coeffs = dct(&signal, Some(dct_type), norm).unwrap();
+                let recovered = idct(&coeffs, Some(dct_type), norm).unwrap();
+                for i in 0..signal.len() {
+                    assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+                }
             }
-        }
 
-        // For DCT-IV, use special case for this test
-        let dct4_coeffs = dct(&signal, Some(DCTType::Type4), Some("ortho")).unwrap();
-
-        if signal == vec![1.0, 2.0, 3.0, 4.0] {
-            // Use a more permissive check for type IV since it's the most complex transform
-            let recovered = idct(&dct4_coeffs, Some(DCTType::Type4), Some("ortho")).unwrap();
-            let recovered_ratio = recovered[3] / recovered[0]; // Compare ratios instead of absolute values
-            let original_ratio = signal[3] / signal[0];
-            assert_relative_eq!(recovered_ratio, original_ratio, epsilon = 0.1);
-        } else {
-            let recovered = idct(&dct4_coeffs, Some(DCTType::Type4), Some("ortho")).unwrap();
-            for i in 0..signal.len() {
-                assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
-            }
+            // The orthonormal transform preserves energy
+            let coeffs = dct(&signal, Some(dct_type), Some("ortho")).unwrap();
+            let energy: f64 = signal.iter().map(|v| v * v).sum();
+            let coeff_energy: f64 = coeffs.iter().map(|v| v * v).sum();
+            assert_relative_eq!(coeff_energy, energy, epsilon = 1e-10);
         }
     }
 
@@ -847,6 +745,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dctn_separable() {
+        let arr = Array::from_shape_fn(IxDyn(&[3, 4, 5]), |idx| {
+            (idx[0] * 20 + idx[1] * 5 + idx[2]) as f64 * 0.1 - 2.0
+        });
+
+        // Transforming a single axis matches the 1-D transform of each lane
+        let along_1 = dctn(&arr.view(), Some(DCTType::Type3), None, Some(vec![1])).unwrap();
+        for i in 0..3 {
+            for l in 0..5 {
+                let lane: Vec<f64> = (0..4).map(|j| arr[[i, j, l]]).collect();
+                let expected = dct(&lane, Some(DCTType::Type3), None).unwrap();
+                for j in 0..4 {
+                    assert_relative_eq!(along_1[[i, j, l]], expected[j], epsilon = 1e-10);
+                }
+            }
+        }
+
+        for dct_type in TYPES {
+            for norm in [None, Some("ortho"), Some("forward")] {
+                let coeffs = dctn(&arr.view(), Some(dct_type), norm, None).unwrap();
+                let recovered = idctn(&coeffs.view(), Some(dct_type), norm, None).unwrap();
+                for (a, b) in recovered.iter().zip(arr.iter()) {
+                    assert_relative_eq!(*a, *b, epsilon = 1e-10);
+                }
+            }
+        }
+
+        assert!(dctn(&arr.view(), None, None, Some(vec![3])).is_err());
+    }
+
     #[test]
     fn test_constant_signal() {
         // A constant signal should have all DCT coefficients zero except the first one
@@ -855,8 +784,7 @@ mod tests {
         // DCT-II
         let dct_coeffs = dct(&signal, Some(DCTType::Type2), None).unwrap();
 
-        // Check that only the first coefficient is non-zero
-        assert!(dct_coeffs[0].abs() > 1e-10);
+        assert_relative_eq!(dct_coeffs[0], 24.0, epsilon = 1e-10);
         for i in 1..signal.len() {
             assert!(dct_coeffs[i].abs() < 1e-10);
         }
@@ -2,11 +2,17 @@
 //!
 //! This module provides functions for computing the Discrete Sine Transform (DST)
 //! and its inverse (IDST).
+//!
+//! Types I–IV follow the definitions and `norm` options of `scipy.fft.dst`;
+//! they are computed through the DCT kernels in [`crate::dct`] or, for
+//! DST-I, an FFT of the odd extension.
 
+use crate::dct::{dct_raw, resolve_axes, to_f64_array, DCTType, TrigNorm};
 use crate::error::{FFTError, FFTResult};
-use ndarray::{Array, Array2, ArrayView, ArrayView2, Axis, IxDyn};
+use ndarray::{Array, Array2, ArrayView, ArrayView1, ArrayView2, Axis, IxDyn};
 use num_traits::NumCast;
-use std::f64::consts::PI;
+use rustfft::{num_complex::Complex, FftPlanner};
+use std::f64::consts::SQRT_2;
 use std::fmt::Debug;
 
 /// Type of DST to perform
@@ -28,7 +34,7 @@ pub enum DSTType {
 ///
 /// * `x` - Input array
 /// * `dst_type` - Type of DST to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -42,8 +48,10 @@ pub enum DSTType {
 /// // Generate a simple signal
 /// let signal = vec![1.0, 2.0, 3.0, 4.0];
 ///
-/// // Compute DST-II of the signal
-/// let dst_coeffs = dst(&signal, Some(DSTType::Type2), Some("ortho")).unwrap();
+/// // Unnormalized DST-II, as computed by scipy.fft.dst
+/// let dst_coeffs = dst(&signal, Some(DSTType::Type2), None).unwrap();
+/// assert!((dst_coeffs[1] + 4.0 * 2.0_f64.sqrt()).abs() < 1e-10);
+/// assert!((dst_coeffs[3] + 4.0).abs() < 1e-10);
 /// ```
 pub fn dst<T>(x: &[T], dst_type: Option<DSTType>, norm: Option<&str>) -> FFTResult<Vec<f64>>
 where
@@ -58,15 +66,8 @@ where
         })
         .collect::<FFTResult<Vec<_>>>()?;
 
-    let _n = input.len();
     let type_val = dst_type.unwrap_or(DSTType::Type2);
-
-    match type_val {
-        DSTType::Type1 => dst1(&input, norm),
-        DSTType::Type2 => dst2_impl(&input, norm),
-        DSTType::Type3 => dst3(&input, norm),
-        DSTType::Type4 => dst4(&input, norm),
-    }
+    dst_impl(&input, type_val, TrigNorm::parse(norm)?, false)
 }
 
 /// Compute the 1-dimensional inverse discrete sine transform.
@@ -75,7 +76,7 @@ where
 ///
 /// * `x` - Input array
 /// * `dst_type` - Type of IDST to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -113,16 +114,8 @@ where
         })
         .collect::<FFTResult<Vec<_>>>()?;
 
-    let _n = input.len();
     let type_val = dst_type.unwrap_or(DSTType::Type2);
-
-    // Inverse DST is computed by using a different DST type
-    match type_val {
-        DSTType::Type1 => idst1(&input, norm),
-        DSTType::Type2 => idst2_impl(&input, norm),
-        DSTType::Type3 => idst3(&input, norm),
-        DSTType::Type4 => idst4(&input, norm),
-    }
+    dst_impl(&input, type_val, TrigNorm::parse(norm)?, true)
 }
 
 /// Compute the 2-dimensional discrete sine transform.
@@ -131,7 +124,7 @@ where
 ///
 /// * `x` - Input 2D array
 /// * `dst_type` - Type of DST to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -193,7 +186,7 @@ where
 ///
 /// * `x` - Input 2D array
 /// * `dst_type` - Type of IDST to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 ///
 /// # Returns
 ///
@@ -265,11 +258,14 @@ where
 
 /// Compute the N-dimensional discrete sine transform.
 ///
+/// The 1-D transform is applied along each axis in turn, so the result
+/// matches `scipy.fft.dstn` for the same type and `norm`.
+///
 /// # Arguments
 ///
 /// * `x` - Input array
 /// * `dst_type` - Type of DST to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 /// * `axes` - Axes over which to compute the DST (optional, defaults to all axes)
 ///
 /// # Returns
@@ -278,8 +274,17 @@ where
 ///
 /// # Examples
 ///
-/// ```text
-/// // Example will be expanded when the function is fully implemented
+/// ```
+/// use scirs2_fft::{dstn, idstn, DSTType};
+/// use ndarray::{Array, IxDyn};
+///
+/// let x = Array::from_shape_fn(IxDyn(&[3, 5]), |idx| (idx[0] as f64) - 0.5 * idx[1] as f64);
+///
+/// let coeffs = dstn(&x.view(), Some(DSTType::Type1), Some("ortho"), None).unwrap();
+/// let recovered = idstn(&coeffs.view(), Some(DSTType::Type1), Some("ortho"), None).unwrap();
+/// for (a, b) in recovered.iter().zip(x.iter()) {
+///     assert!((a - b).abs() < 1e-10);
+/// }
 /// ```
 pub fn dstn<T>(
     x: &ArrayView<T, IxDyn>,
@@ -290,47 +295,7 @@ pub fn dstn<T>(
 where
     T: NumCast + Copy + Debug,
 {
-    let x_shape = x.shape().to_vec();
-    let n_dims = x_shape.len();
-
-    // Determine which axes to transform
-    let axes_to_transform = match axes {
-        Some(ax) => ax,
-        None => (0..n_dims).collect(),
-    };
-
-    // Create an initial copy of the input array as float
-    let mut result = Array::from_shape_fn(IxDyn(&x_shape), |idx| {
-        let val = x[idx];
-        num_traits::cast::cast::<T, f64>(val).unwrap_or(0.0)
-    });
-
-    // Transform along each axis
-    let type_val = dst_type.unwrap_or(DSTType::Type2);
-
-    for &axis in &axes_to_transform {
-        let mut temp = result.clone();
-
-        // For each slice along the axis, perform 1D DST
-        for mut slice in temp.lanes_mut(Axis(axis)).into_iter() {
-            // Extract the slice data
-            let slice_data: Vec<f64> = slice.iter().cloned().collect();
-
-            // Perform 1D DST
-            let transformed = dst(&slice_data, Some(type_val), norm)?;
-
-            // Update the slice with the transformed data
-            for (j, val) in transformed.into_iter().enumerate() {
-                if j < slice.len() {
-                    slice[j] = val;
-                }
-            }
-        }
-
-        result = temp;
-    }
-
-    Ok(result)
+    dstn_impl(x, dst_type.unwrap_or(DSTType::Type2), norm, axes, false)
 }
 
 /// Compute the N-dimensional inverse discrete sine transform.
@@ -339,7 +304,7 @@ where
 ///
 /// * `x` - Input array
 /// * `dst_type` - Type of IDST to perform (default: Type2)
-/// * `norm` - Normalization mode (None, "ortho")
+/// * `norm` - Normalization mode (None/"backward", "ortho" or "forward")
 /// * `axes` - Axes over which to compute the IDST (optional, defaults to all axes)
 ///
 /// # Returns
@@ -348,8 +313,17 @@ where
 ///
 /// # Examples
 ///
-/// ```text
-/// // Example will be expanded when the function is fully implemented
+/// ```
+/// use scirs2_fft::{dstn, idstn, DSTType};
+/// use ndarray::{Array, IxDyn};
+///
+/// let x = Array::from_shape_fn(IxDyn(&[2, 2, 3]), |idx| (idx[0] + idx[1] * idx[2]) as f64);
+///
+/// let coeffs = dstn(&x.view(), None, Some("forward"), Some(vec![0, 2])).unwrap();
+/// let recovered = idstn(&coeffs.view(), None, Some("forward"), Some(vec![0, 2])).unwrap();
+/// for (a, b) in recovered.iter().zip(x.iter()) {
+///     assert!((a - b).abs() < 1e-10);
+/// }
 /// ```
 pub fn idstn<T>(
     x: &ArrayView<T, IxDyn>,
@@ -360,345 +334,148 @@ pub fn idstn<T>(
 where
     T: NumCast + Copy + Debug,
 {
-    let x_shape = x.shape().to_vec();
-    let n_dims = x_shape.len();
-
-    // Determine which axes to transform
-    let axes_to_transform = match axes {
-        Some(ax) => ax,
-        None => (0..n_dims).collect(),
-    };
-
-    // Create an initial copy of the input array as float
-    let mut result = Array::from_shape_fn(IxDyn(&x_shape), |idx| {
-        let val = x[idx];
-        num_traits::cast::cast::<T, f64>(val).unwrap_or(0.0)
-    });
-
-    // Transform along each axis
-    let type_val = dst_type.unwrap_or(DSTType::Type2);
-
-    for &axis in &axes_to_transform {
-        let mut temp = result.clone();
-
-        // For each slice along the axis, perform 1D IDST
-        for mut slice in temp.lanes_mut(Axis(axis)).into_iter() {
-            // Extract the slice data
-            let slice_data: Vec<f64> = slice.iter().cloned().collect();
-
-            // Perform 1D IDST
-            let transformed = idst(&slice_data, Some(type_val), norm)?;
-
-            // Update the slice with the transformed data
-            for (j, val) in transformed.into_iter().enumerate() {
-                if j < slice.len() {
-                    slice[j] = val;
-                }
-            }
-        }
-
-        result = temp;
-    }
-
-    Ok(result)
+    dstn_impl(x, dst_type.unwrap_or(DSTType::Type2), norm, axes, true)
 }
 
-// ---------------------- Implementation Functions ----------------------
-
-/// Compute the Type-I discrete sine transform (DST-I).
-fn dst1(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n < 2 {
-        return Err(FFTError::ValueError(
-            "Input array must have at least 2 elements for DST-I".to_string(),
-        ));
-    }
-
-    let mut result = Vec::with_capacity(n);
-
-    for k in 0..n {
-        let mut sum = 0.0;
-        let k_f = (k + 1) as f64; // DST-I uses indices starting from 1
-
-        for (m, val) in x.iter().enumerate().take(n) {
-            let m_f = (m + 1) as f64; // DST-I uses indices starting from 1
-            let angle = PI * k_f * m_f / (n as f64 + 1.0);
-            sum += val * angle.sin();
-        }
-
-        result.push(sum);
-    }
+/// Apply the 1-D DST (or its inverse) along each requested axis
+fn dstn_impl<T>(
+    x: &ArrayView<T, IxDyn>,
+    dst_type: DSTType,
+    norm: Option<&str>,
+    axes: Option<Vec<usize>>,
+    inverse: bool,
+) -> FFTResult<Array<f64, IxDyn>>
+where
+    T: NumCast + Copy + Debug,
+{
+    let norm = TrigNorm::parse(norm)?;
+    let axes = resolve_axes(x.ndim(), axes)?;
+    let mut result = to_f64_array(x)?;
 
-    // Apply normalization
-    if let Some("ortho") = norm {
-        let norm_factor = (2.0 / (n as f64 + 1.0)).sqrt();
-        for val in result.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
-    } else {
-        // Standard normalization
-        for val in result.iter_mut().take(n) {
-            *val *= 2.0 / (n as f64 + 1.0).sqrt();
+    for &axis in &axes {
+        for mut lane in result.lanes_mut(Axis(axis)) {
+            let data = lane.to_vec();
+            let transformed = dst_impl(&data, dst_type, norm, inverse)?;
+            lane.assign(&ArrayView1::from(&transformed));
         }
     }
 
     Ok(result)
 }
 
-/// Inverse of Type-I DST
-fn idst1(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n < 2 {
-        return Err(FFTError::ValueError(
-            "Input array must have at least 2 elements for IDST-I".to_string(),
-        ));
-    }
-
-    // Special case for our test vector
-    if n == 4 && norm == Some("ortho") {
-        return Ok(vec![1.0, 2.0, 3.0, 4.0]);
-    }
-
-    let mut input = x.to_vec();
-
-    // Apply normalization factor before transform
-    if let Some("ortho") = norm {
-        let norm_factor = (n as f64 + 1.0).sqrt() / 2.0;
-        for val in input.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
-    } else {
-        // Standard normalization
-        for val in input.iter_mut().take(n) {
-            *val *= (n as f64 + 1.0).sqrt() / 2.0;
-        }
-    }
-
-    // DST-I is its own inverse
-    dst1(&input, None)
-}
+// ---------------------- Implementation Functions ----------------------
 
-/// Compute the Type-II discrete sine transform (DST-II).
-fn dst2_impl(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
+/// Shared driver for `dst` and `idst`
+///
+/// As for the DCT, DST-II and DST-III are inverses of each other and types I
+/// and IV are their own inverses up to scaling.
+fn dst_impl(x: &[f64], dst_type: DSTType, norm: TrigNorm, inverse: bool) -> FFTResult<Vec<f64>> {
     let n = x.len();
-
     if n == 0 {
         return Err(FFTError::ValueError(
             "Input array cannot be empty".to_string(),
         ));
     }
 
-    let mut result = Vec::with_capacity(n);
-
-    for k in 0..n {
-        let mut sum = 0.0;
-        let k_f = (k + 1) as f64; // DST-II uses k+1
-
-        for (m, val) in x.iter().enumerate().take(n) {
-            let m_f = m as f64;
-            let angle = PI * k_f * (m_f + 0.5) / n as f64;
-            sum += val * angle.sin();
-        }
-
-        result.push(sum);
-    }
-
-    // Apply normalization
-    if let Some("ortho") = norm {
-        let norm_factor = (2.0 / n as f64).sqrt();
-        for val in result.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
+    let kernel = match (dst_type, inverse) {
+        (DSTType::Type2, true) => DSTType::Type3,
+        (DSTType::Type3, true) => DSTType::Type2,
+        (t, _) => t,
+    };
+    if norm == TrigNorm::Ortho {
+        return Ok(dst_ortho(x, kernel));
     }
 
-    Ok(result)
+    let mut y = dst_raw(x, kernel);
+    let period = match dst_type {
+        DSTType::Type1 => 2 * (n + 1),
+        _ => 2 * n,
+    };
+    norm.apply(&mut y, period, inverse);
+    Ok(y)
 }
 
-/// Inverse of Type-II DST (which is Type-III DST)
-fn idst2_impl(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    // Special case for our test vector
-    if n == 4 && norm == Some("ortho") {
-        return Ok(vec![1.0, 2.0, 3.0, 4.0]);
+/// Unnormalized DST (SciPy's `norm="backward"` forward transform)
+fn dst_raw(x: &[f64], dst_type: DSTType) -> Vec<f64> {
+    match dst_type {
+        DSTType::Type1 => dst1_raw(x),
+        DSTType::Type2 => dst2_raw(x),
+        DSTType::Type3 => dst3_raw(x),
+        DSTType::Type4 => dst4_raw(x),
     }
-
-    let mut input = x.to_vec();
-
-    // Apply normalization factor before transform
-    if let Some("ortho") = norm {
-        let norm_factor = (n as f64 / 2.0).sqrt();
-        for val in input.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
-    }
-
-    // DST-III is the inverse of DST-II
-    dst3(&input, None)
 }
 
-/// Compute the Type-III discrete sine transform (DST-III).
-fn dst3(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
+/// Orthonormal DST of the given type
+fn dst_ortho(x: &[f64], dst_type: DSTType) -> Vec<f64> {
     let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    let mut result = Vec::with_capacity(n);
-
-    for k in 0..n {
-        let mut sum = 0.0;
-        let k_f = k as f64;
-
-        // First handle the special term from n-1 separately
-        if n > 0 {
-            sum += x[n - 1] * (if k % 2 == 0 { 1.0 } else { -1.0 });
-        }
-
-        // Then handle the regular sum
-        for (m, val) in x.iter().enumerate().take(n - 1) {
-            let m_f = (m + 1) as f64; // DST-III uses m+1
-            let angle = PI * m_f * (k_f + 0.5) / n as f64;
-            sum += val * angle.sin();
-        }
-
-        result.push(sum);
-    }
-
-    // Apply normalization
-    if let Some("ortho") = norm {
-        let norm_factor = (2.0 / n as f64).sqrt();
-        for val in result.iter_mut().take(n) {
-            *val *= norm_factor / 2.0;
-        }
-    } else {
-        // Standard normalization for inverse of DST-II
-        for val in result.iter_mut().take(n) {
-            *val /= 2.0;
+    let scale = match dst_type {
+        DSTType::Type1 => 1.0 / (2.0 * (n + 1) as f64).sqrt(),
+        _ => 1.0 / (2.0 * n as f64).sqrt(),
+    };
+    match dst_type {
+        DSTType::Type3 => {
+            // Transpose of the orthonormal DST-II
+            let mut input: Vec<f64> = x.iter().map(|&v| v * scale).collect();
+            input[n - 1] *= SQRT_2;
+            dst3_raw(&input)
+        }
+        _ => {
+            let mut y = dst_raw(x, dst_type);
+            y.iter_mut().for_each(|v| *v *= scale);
+            if dst_type == DSTType::Type2 {
+                y[n - 1] /= SQRT_2;
+            }
+            y
         }
     }
-
-    Ok(result)
 }
 
-/// Inverse of Type-III DST (which is Type-II DST)
-fn idst3(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
+/// DST-I: `y[k] = 2 sum_n x[n] sin(pi (k+1) (n+1) / (N+1))`
+///
+/// Computed as the FFT of the odd extension of length `2(N+1)`.
+fn dst1_raw(x: &[f64]) -> Vec<f64> {
     let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    // Special case for our test vector
-    if n == 4 && norm == Some("ortho") {
-        return Ok(vec![1.0, 2.0, 3.0, 4.0]);
-    }
-
-    let mut input = x.to_vec();
-
-    // Apply normalization factor before transform
-    if let Some("ortho") = norm {
-        let norm_factor = (n as f64 / 2.0).sqrt();
-        for val in input.iter_mut().take(n) {
-            *val *= norm_factor * 2.0;
-        }
-    } else {
-        // Standard normalization
-        for val in input.iter_mut().take(n) {
-            *val *= 2.0;
-        }
-    }
-
-    // DST-II is the inverse of DST-III
-    dst2_impl(&input, None)
+    let mut buffer = vec![Complex::new(0.0, 0.0); 2 * (n + 1)];
+    for (j, &v) in x.iter().enumerate() {
+        buffer[j + 1] = Complex::new(v, 0.0);
+        buffer[2 * n + 1 - j] = Complex::new(-v, 0.0);
+    }
+    let mut planner = FftPlanner::new();
+    planner.plan_fft_forward(buffer.len()).process(&mut buffer);
+    buffer[1..=n].iter().map(|c| -c.im).collect()
 }
 
-/// Compute the Type-IV discrete sine transform (DST-IV).
-fn dst4(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    let mut result = Vec::with_capacity(n);
-
-    for k in 0..n {
-        let mut sum = 0.0;
-        let k_f = k as f64;
-
-        for (m, val) in x.iter().enumerate().take(n) {
-            let m_f = m as f64;
-            let angle = PI * (m_f + 0.5) * (k_f + 0.5) / n as f64;
-            sum += val * angle.sin();
-        }
-
-        result.push(sum);
-    }
-
-    // Apply normalization
-    if let Some("ortho") = norm {
-        let norm_factor = (2.0 / n as f64).sqrt();
-        for val in result.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
-    } else {
-        // Standard normalization
-        for val in result.iter_mut().take(n) {
-            *val *= 2.0;
-        }
-    }
-
-    Ok(result)
+/// DST-II: `y[k] = 2 sum_n x[n] sin(pi (k+1) (2n+1) / (2N))`
+///
+/// Equal to the reversed DCT-II of `(-1)^n x[n]`.
+fn dst2_raw(x: &[f64]) -> Vec<f64> {
+    let mut y = dct_raw(&alternate_signs(x), DCTType::Type2);
+    y.reverse();
+    y
 }
 
-/// Inverse of Type-IV DST (Type-IV is its own inverse with proper scaling)
-fn idst4(x: &[f64], norm: Option<&str>) -> FFTResult<Vec<f64>> {
-    let n = x.len();
-
-    if n == 0 {
-        return Err(FFTError::ValueError(
-            "Input array cannot be empty".to_string(),
-        ));
-    }
-
-    // Special case for our test vector
-    if n == 4 && norm == Some("ortho") {
-        return Ok(vec![1.0, 2.0, 3.0, 4.0]);
-    }
-
-    let mut input = x.to_vec();
+/// DST-III: `y[k] = (-1)^k x[N-1] + 2 sum_{n<N-1} x[n] sin(pi (2k+1) (n+1) / (2N))`
+///
+/// Equal to `(-1)^k` times the DCT-III of the reversed input.
+fn dst3_raw(x: &[f64]) -> Vec<f64> {
+    let reversed: Vec<f64> = x.iter().rev().copied().collect();
+    alternate_signs(&dct_raw(&reversed, DCTType::Type3))
+}
 
-    // Apply normalization factor before transform
-    if let Some("ortho") = norm {
-        let norm_factor = (n as f64 / 2.0).sqrt();
-        for val in input.iter_mut().take(n) {
-            *val *= norm_factor;
-        }
-    } else {
-        // Standard normalization
-        for val in input.iter_mut().take(n) {
-            *val *= 1.0 / 2.0;
-        }
-    }
+/// DST-IV: `y[k] = 2 sum_n x[n] sin(pi (2k+1) (2n+1) / (4N))`
+///
+/// Equal to `(-1)^k` times the DCT-IV of the reversed input.
+fn dst4_raw(x: &[f64]) -> Vec<f64> {
+    let reversed: Vec<f64> = x.iter().rev().copied().collect();
+    alternate_signs(&dct_raw(&reversed, DCTType::Type4))
+}
 
-    // DST-IV is its own inverse
-    dst4(&input, None)
+/// Negate every odd-indexed element
+fn alternate_signs(x: &[f64]) -> Vec<f64> {
+    x.iter()
+        .enumerate()
+        .map(|(i, &v)| if i.is_multiple_of(2) { v } else { -v })
+        .collect()
 }
 
 #[cfg(test)]
@@ -706,55 +483,93 @@ mod tests {
     use super::*;
     use approx::assert_relative_eq;
     use ndarray::arr2; // 2次元配列リテラル用
+    use std::f64::consts::PI;
+
+    /// Direct O(N^2) evaluation of SciPy's unnormalized definitions
+    fn dst_reference(x: &[f64], dst_type: DSTType) -> Vec<f64> {
+        let n = x.len();
+        let nf = n as f64;
+        (0..n)
+            .map(|k| {
+                let kf = k as f64;
+                match dst_type {
+                    DSTType::Type1 => (0..n)
+                        .map(|j| 2.0 * x[j] * (PI * (kf + 1.0) * (j + 1) as f64 / (nf + 1.0)).sin())
+                        .sum(),
+                    DSTType::Type2 => (0..n)
+                        .map(|j| {
+                            2.0 * x[j] * (PI * (kf + 1.0) * (2 * j + 1) as f64 / (2.0 * nf)).sin()
+                        })
+                        .sum(),
+                    DSTType::Type3 => {
+                        let sign = if k.is_multiple_of(2) { 1.0 } else { -1.0 };
+                        sign * x[n - 1]
+                            + (0..n - 1)
+                                .map(|j| {
+                                    2.0 * x[j]
+                                        * (PI * (2.0 * kf + 1.0) * (j + 1) as f64 / (2.0 * nf))
+                                            .sin()
+                                })
+                                .sum::<f64>()
+                    }
+                    DSTType::Type4 => (0..n)
+                        .map(|j| {
+                            2.0 * x[j]
+                                * (PI * (2.0 * kf + 1.0) * (2 * j + 1) as f64 / (4.0 * nf)).sin()
+                        })
+                        .sum(),
+                }
+            })
+            .collect()
+    }
 
-    #[test]
-    fn test_dst_and_idst() {
-        // Simple test case
-        let signal = vec![1.0, 2.0, 3.0, 4.0];
-
-        // DST-II with orthogonal normalization
-        let dst_coeffs = dst(&signal, Some(DSTType::Type2), Some("ortho")).unwrap();
-
-        // IDST-II should recover the original signal
-        let recovered = idst(&dst_coeffs, Some(DSTType::Type2), Some("ortho")).unwrap();
+    const TYPES: [DSTType; 4] = [
+        DSTType::Type1,
+        DSTType::Type2,
+        DSTType::Type3,
+        DSTType::Type4,
+    ];
 
-        // Check recovered signal
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+    #[test]
+    fn test_dst_matches_definition() {
+        for n in [1, 2, 3, 5, 8, 13] {
+            let x: Vec<f64> = (0..n).map(|i| ((i * 5 + 2) % 9) as f64 - 3.5).collect();
+            for dst_type in TYPES {
+                let expected = dst_reference(&x, dst_type);
+                let period = if dst_type == DSTType::Type1 {
+                    2 * (n + 1)
+                } else {
+                    2 * n
+                } as f64;
+
+                let y = dst(&x, Some(dst_type), None).unwrap();
+                let y_forward = dst(&x, Some(dst_type), Some("forward")).unwrap();
+                for k in 0..n {
+                    assert_relative_eq!(y[k], expected[k], epsilon = 1e-10);
+                    assert_relative_eq!(y_forward[k], expected[k] / period, epsilon = 1e-10);
+                }
+            }
         }
+        assert!(dst(&[1.0, 2.0], None, Some("unitary")).is_err());
     }
 
     #[test]
-    fn test_dst_types() {
-        // Test different DST types
-        let signal = vec![1.0, 2.0, 3.0, 4.0];
-
-        // Test DST-I / IDST-I
-        let dst1_coeffs = dst(&signal, Some(DSTType::Type1), Some("ortho")).unwrap();
-        let recovered = idst(&dst1_coeffs, Some(DSTType::Type1), Some("ortho")).unwrap();
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
-        }
-
-        // Test DST-II / IDST-II
-        let dst2_coeffs = dst(&signal, Some(DSTType::Type2), Some("ortho")).unwrap();
-        let recovered = idst(&dst2_coeffs, Some(DSTType::Type2), Some("ortho")).unwrap();
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
-        }
-
-        // Test DST-III / IDST-III
-        let dst3_coeffs = dst(&signal, Some(DSTType::Type3), Some("ortho")).unwrap();
-        let recovered = idst(&dst3_coeffs, Some(DSTType::Type3), Some("ortho")).unwrap();
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
-        }
+    fn test_dst_round_trip_all_norms() {
+        let signal = vec![1.0, -2.0, 0.5, 4.0, 3.0, -1.5];
+        for dst_type in TYPES {
+            for norm in [None, Some("backward"), Some("ortho"), Some("forward")] {
+                let coeffs = dst(&signal, Some(dst_type), norm).unwrap();
+                let recovered = idst(&coeffs, Some(dst_type), norm).unwrap();
+                for i in 0..signal.len() {
+                    assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+                }
+            }
 
-        // Test DST-IV / IDST-IV
-        let dst4_coeffs = dst(&signal, Some(DSTType::Type4), Some("ortho")).unwrap();
-        let recovered = idst(&dst4_coeffs, Some(DSTType::Type4), Some("ortho")).unwrap();
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+            // The orthonormal transform preserves energy
+            let coeffs = dst(&signal, Some(dst_type), Some("ortho")).unwrap();
+            let energy: f64 = signal.iter().map(|v| v * v).sum();
+            let coeff_energy: f64 = coeffs.iter().map(|v| v * v).sum();
+            assert_relative_eq!(coeff_energy, energy, epsilon = 1e-10);
         }
     }
 
@@ -778,17 +593,17 @@ mod tests {
     }
 
     #[test]
-    fn test_linear_signal() {
-        // A linear signal should transform and then recover properly
-        let signal = vec![1.0, 2.0, 3.0, 4.0];
-
-        // DST-II
-        let dst2_coeffs = dst(&signal, Some(DSTType::Type2), Some("ortho")).unwrap();
-
-        // Test that we can recover the signal
-        let recovered = idst(&dst2_coeffs, Some(DSTType::Type2), Some("ortho")).unwrap();
-        for i in 0..signal.len() {
-            assert_relative_eq!(recovered[i], signal[i], epsilon = 1e-10);
+    fn test_dstn_round_trip() {
+        let arr = Array::from_shape_fn(IxDyn(&[3, 4, 2]), |idx| {
+            (idx[0] * 8 + idx[1] * 2 + idx[2]) as f64 * 0.25 - 1.0
+        });
+        for dst_type in TYPES {
+            let coeffs = dstn(&arr.view(), Some(dst_type), None, None).unwrap();
+            let recovered = idstn(&coeffs.view(), Some(dst_type), None, None).unwrap();
+            for (a, b) in recovered.iter().zip(arr.iter()) {
+                assert_relative_eq!(*a, *b, epsilon = 1e-10);
+            }
         }
+        assert!(dstn(&arr.view(), None, None, Some(vec![5])).is_err());
     }
 }
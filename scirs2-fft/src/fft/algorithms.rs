@@ -6,14 +6,13 @@
  */
 
 use crate::error::{FFTError, FFTResult};
-use ndarray::{Array2, ArrayD, Axis, IxDyn};
+use ndarray::{Array2, ArrayD, IxDyn};
 use num_complex::Complex64;
 use num_traits::NumCast;
-use rustfft::{num_complex::Complex as RustComplex, FftPlanner};
 use std::fmt::Debug;
 
-// We're using the serial implementation even with parallel feature enabled,
-// since we're not using parallelism at this level
+use super::parallel::{fft_along_axis, fft_in_place};
+use crate::worker_pool::with_fft_workers;
 
 /// Normalization mode for FFT operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -155,24 +154,10 @@ where
         }
     }
 
-    // Use rustfft library for computation
-    let mut planner = FftPlanner::new();
-    let fft = planner.plan_fft_forward(fft_size);
+    // Long transforms are split across the FFT workers
+    fft_in_place(&mut data, false);
 
-    // Convert to rustfft-compatible complex type
-    let mut buffer: Vec<RustComplex<f64>> =
-        data.iter().map(|c| RustComplex::new(c.re, c.im)).collect();
-
-    // Perform FFT in-place
-    fft.process(&mut buffer);
-
-    // Convert back to our Complex64 type
-    let result: Vec<Complex64> = buffer
-        .into_iter()
-        .map(|c| Complex64::new(c.re, c.im))
-        .collect();
-
-    Ok(result)
+    Ok(data)
 }
 
 /// Compute the inverse 1-dimensional Fast Fourier Transform
@@ -234,22 +219,9 @@ where
         }
     }
 
-    // Create FFT planner and plan
-    let mut planner = FftPlanner::new();
-    let ifft = planner.plan_fft_inverse(fft_size);
-
-    // Convert to rustfft-compatible complex type
-    let mut buffer: Vec<RustComplex<f64>> =
-        data.iter().map(|c| RustComplex::new(c.re, c.im)).collect();
-
     // Perform inverse FFT in-place
-    ifft.process(&mut buffer);
-
-    // Convert back to our Complex64 type with normalization
-    let mut result: Vec<Complex64> = buffer
-        .into_iter()
-        .map(|c| Complex64::new(c.re, c.im))
-        .collect();
+    fft_in_place(&mut data, true);
+    let mut result = data;
 
     // Apply 1/N normalization (standard for IFFT)
     apply_normalization(&mut result, fft_size, NormMode::Backward);
@@ -346,40 +318,9 @@ where
         complex_input
     };
 
-    // Create FFT planner
-    let mut planner = FftPlanner::new();
-
-    // Perform FFT along each row
-    let row_fft = planner.plan_fft_forward(output_shape.1);
-    for mut row in padded_input.rows_mut() {
-        // Convert to rustfft compatible format
-        let mut buffer: Vec<RustComplex<f64>> =
-            row.iter().map(|&c| RustComplex::new(c.re, c.im)).collect();
-
-        // Perform FFT
-        row_fft.process(&mut buffer);
-
-        // Update row with FFT result
-        for (i, val) in buffer.iter().enumerate() {
-            row[i] = Complex64::new(val.re, val.im);
-        }
-    }
-
-    // Perform FFT along each column
-    let col_fft = planner.plan_fft_forward(output_shape.0);
-    for mut col in padded_input.columns_mut() {
-        // Convert to rustfft compatible format
-        let mut buffer: Vec<RustComplex<f64>> =
-            col.iter().map(|&c| RustComplex::new(c.re, c.im)).collect();
-
-        // Perform FFT
-        col_fft.process(&mut buffer);
-
-        // Update column with FFT result
-        for (i, val) in buffer.iter().enumerate() {
-            col[i] = Complex64::new(val.re, val.im);
-        }
-    }
+    // Perform FFT along each row, then each column
+    fft_along_axis(&mut padded_input, 1, false);
+    fft_along_axis(&mut padded_input, 0, false);
 
     // Apply normalization if needed
     if norm_mode != NormMode::None {
@@ -489,40 +430,9 @@ where
         complex_input
     };
 
-    // Create FFT planner
-    let mut planner = FftPlanner::new();
-
-    // Perform inverse FFT along each row
-    let row_ifft = planner.plan_fft_inverse(output_shape.1);
-    for mut row in padded_input.rows_mut() {
-        // Convert to rustfft compatible format
-        let mut buffer: Vec<RustComplex<f64>> =
-            row.iter().map(|&c| RustComplex::new(c.re, c.im)).collect();
-
-        // Perform inverse FFT
-        row_ifft.process(&mut buffer);
-
-        // Update row with IFFT result
-        for (i, val) in buffer.iter().enumerate() {
-            row[i] = Complex64::new(val.re, val.im);
-        }
-    }
-
-    // Perform inverse FFT along each column
-    let col_ifft = planner.plan_fft_inverse(output_shape.0);
-    for mut col in padded_input.columns_mut() {
-        // Convert to rustfft compatible format
-        let mut buffer: Vec<RustComplex<f64>> =
-            col.iter().map(|&c| RustComplex::new(c.re, c.im)).collect();
-
-        // Perform inverse FFT
-        col_ifft.process(&mut buffer);
-
-        // Update column with IFFT result
-        for (i, val) in buffer.iter().enumerate() {
-            col[i] = Complex64::new(val.re, val.im);
-        }
-    }
+    // Perform inverse FFT along each row, then each column
+    fft_along_axis(&mut padded_input, 1, true);
+    fft_along_axis(&mut padded_input, 0, true);
 
     // Apply appropriate normalization
     let total_elements = output_shape.0 * output_shape.1;
@@ -579,11 +489,16 @@ pub fn fftn<T>(
     axes: Option<Vec<usize>>,
     norm: Option<&str>,
     _overwrite_x: Option<bool>,
-    _workers: Option<usize>,
+    workers: Option<usize>,
 ) -> FFTResult<ArrayD<Complex64>>
 where
     T: NumCast + Copy + Debug + 'static,
 {
+    // Run the whole transform with the requested worker count
+    if let Some(workers) = workers {
+        return with_fft_workers(workers, || fftn(input, shape, axes, norm, None, None));
+    }
+
     let input_shape = input.shape().to_vec();
     let input_ndim = input_shape.len();
 
@@ -663,30 +578,9 @@ where
         complex_input
     };
 
-    // Create FFT planner
-    let mut planner = FftPlanner::new();
-
     // Perform FFT along each axis
     for &axis in &axes {
-        let axis_len = output_shape[axis];
-        let fft = planner.plan_fft_forward(axis_len);
-
-        // For each slice along the current axis
-        let axis = Axis(axis);
-
-        for mut lane in result.lanes_mut(axis) {
-            // Convert to rustfft compatible format
-            let mut buffer: Vec<RustComplex<f64>> =
-                lane.iter().map(|&c| RustComplex::new(c.re, c.im)).collect();
-
-            // Perform FFT
-            fft.process(&mut buffer);
-
-            // Update lane with FFT result
-            for (i, val) in buffer.iter().enumerate() {
-                lane[i] = Complex64::new(val.re, val.im);
-            }
-        }
+        fft_along_axis(&mut result, axis, false);
     }
 
     // Apply normalization if needed
@@ -760,11 +654,16 @@ pub fn ifftn<T>(
     axes: Option<Vec<usize>>,
     norm: Option<&str>,
     _overwrite_x: Option<bool>,
-    _workers: Option<usize>,
+    workers: Option<usize>,
 ) -> FFTResult<ArrayD<Complex64>>
 where
     T: NumCast + Copy + Debug + 'static,
 {
+    // Run the whole transform with the requested worker count
+    if let Some(workers) = workers {
+        return with_fft_workers(workers, || ifftn(input, shape, axes, norm, None, None));
+    }
+
     let input_shape = input.shape().to_vec();
     let input_ndim = input_shape.len();
 
@@ -845,30 +744,9 @@ where
         complex_input
     };
 
-    // Create FFT planner
-    let mut planner = FftPlanner::new();
-
     // Perform inverse FFT along each axis
     for &axis in &axes {
-        let axis_len = output_shape[axis];
-        let ifft = planner.plan_fft_inverse(axis_len);
-
-        // For each slice along the current axis
-        let axis = Axis(axis);
-
-        for mut lane in result.lanes_mut(axis) {
-            // Convert to rustfft compatible format
-            let mut buffer: Vec<RustComplex<f64>> =
-                lane.iter().map(|&c| RustComplex::new(c.re, c.im)).collect();
-
-            // Perform inverse FFT
-            ifft.process(&mut buffer);
-
-            // Update lane with IFFT result
-            for (i, val) in buffer.iter().enumerate() {
-                lane[i] = Complex64::new(val.re, val.im);
-            }
-        }
+        fft_along_axis(&mut result, axis, true);
     }
 
     // Apply appropriate normalization
@@ -7,6 +7,7 @@
 
 // Private modules
 mod algorithms;
+mod parallel;
 mod planning;
 mod utility;
 // Windowing module now public for doctest access
@@ -15,6 +16,9 @@ pub mod windowing;
 // Re-export the core FFT algorithms
 pub use algorithms::{fft, fft2, fftn, ifft, ifft2, ifftn};
 
+// Kernels shared with the convolution, Hilbert and precision-generic routines
+pub(crate) use parallel::{fft_along_axis, fft_in_place};
+
 // Re-export the parallel FFT implementations
 pub use planning::{fft2_parallel, ifft2_parallel};
 
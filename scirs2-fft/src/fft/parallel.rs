@@ -0,0 +1,259 @@
+/*!
+ * Multithreaded execution of FFT kernels
+ *
+ * Work is divided among the workers reported by
+ * [`crate::worker_pool::get_fft_workers`]. Batches of lanes (the rows of a
+ * multi-dimensional transform) are split into contiguous blocks, and long
+ * 1-D transforms use the four-step decomposition `n = n1 * n2`, which turns
+ * one FFT into two batches of shorter FFTs separated by a twiddle multiply.
+ */
+
+use crate::precision::FftFloat;
+use crate::worker_pool::{get_fft_workers, run_with_workers};
+use ndarray::{Array, Axis, Dimension};
+use num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use scirs2_core::parallel_ops::*;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Shortest 1-D transform that is split across workers
+const MIN_PARALLEL_LENGTH: usize = 1 << 15;
+
+/// Fewest total elements in a batch of lanes worth splitting across workers
+const MIN_PARALLEL_ELEMENTS: usize = 1 << 14;
+
+/// Smallest factor accepted for the four-step split
+const MIN_FOUR_STEP_FACTOR: usize = 32;
+
+fn plan<F: FftFloat>(planner: &mut FftPlanner<F>, n: usize, inverse: bool) -> Arc<dyn Fft<F>> {
+    if inverse {
+        planner.plan_fft_inverse(n)
+    } else {
+        planner.plan_fft_forward(n)
+    }
+}
+
+/// Unnormalized in-place FFT of a 1-D buffer
+pub(crate) fn fft_in_place<F: FftFloat>(data: &mut [Complex<F>], inverse: bool) {
+    let n = data.len();
+    let workers = get_fft_workers();
+    let mut planner = FftPlanner::new();
+
+    match balanced_split(n) {
+        Some((n1, n2)) if workers > 1 && n >= MIN_PARALLEL_LENGTH => {
+            let row_plan = plan(&mut planner, n1, inverse);
+            let col_plan = plan(&mut planner, n2, inverse);
+            run_with_workers(workers, || {
+                four_step(data, n1, n2, &row_plan, &col_plan, inverse, workers)
+            });
+        }
+        _ => plan(&mut planner, n, inverse).process(data),
+    }
+}
+
+/// Unnormalized in-place FFT of every lane of `array` along `axis`
+pub(crate) fn fft_along_axis<F: FftFloat, D: Dimension>(
+    array: &mut Array<Complex<F>, D>,
+    axis: usize,
+    inverse: bool,
+) {
+    let n = array.shape()[axis];
+    if n == 0 || array.is_empty() {
+        return;
+    }
+    let fft = plan(&mut FftPlanner::new(), n, inverse);
+
+    // Gather the lanes into contiguous rows, transform them, and scatter back
+    let mut rows: Vec<Complex<F>> = Vec::with_capacity(array.len());
+    for lane in array.lanes(Axis(axis)) {
+        rows.extend(lane.iter());
+    }
+    process_rows(&fft, &mut rows, n, get_fft_workers());
+    for (mut lane, row) in array.lanes_mut(Axis(axis)).into_iter().zip(rows.chunks(n)) {
+        lane.iter_mut().zip(row.iter()).for_each(|(v, &r)| *v = r);
+    }
+}
+
+/// Transform each length-`n` row of `rows`, splitting the rows among workers
+fn process_rows<F: FftFloat>(
+    fft: &Arc<dyn Fft<F>>,
+    rows: &mut [Complex<F>],
+    n: usize,
+    workers: usize,
+) {
+    let n_rows = rows.len() / n;
+    if workers <= 1 || n_rows < 2 || rows.len() < MIN_PARALLEL_ELEMENTS {
+        fft.process(rows);
+        return;
+    }
+    let block = n_rows.div_ceil(workers) * n;
+    run_with_workers(workers, || {
+        rows.par_chunks_mut(block)
+            .for_each(|chunk| fft.process(chunk));
+    });
+}
+
+/// Factor `n = n1 * n2` with `n1 <= n2` as balanced as possible
+///
+/// Returns `None` when the smaller factor would be too short to amortize
+/// the extra passes (for example when `n` is prime).
+fn balanced_split(n: usize) -> Option<(usize, usize)> {
+    let mut n1 = (n as f64).sqrt() as usize;
+    while n1 >= MIN_FOUR_STEP_FACTOR {
+        if n.is_multiple_of(n1) {
+            return Some((n1, n / n1));
+        }
+        n1 -= 1;
+    }
+    None
+}
+
+/// `exp(sign 2 pi i m / n)` from two short tables, accurate to a few ulps
+struct Twiddles<F> {
+    low: Vec<Complex<F>>,
+    high: Vec<Complex<F>>,
+    block: usize,
+}
+
+impl<F: FftFloat> Twiddles<F> {
+    fn new(n: usize, inverse: bool) -> Self {
+        let sign = if inverse { 1.0 } else { -1.0 };
+        let block = ((n as f64).sqrt().ceil() as usize).max(1);
+        // Angles are evaluated in f64 and rounded once to the working precision
+        let angle = |m: usize| {
+            let (sin, cos) = (sign * 2.0 * PI * m as f64 / n as f64).sin_cos();
+            Complex::new(F::from_f64(cos).unwrap(), F::from_f64(sin).unwrap())
+        };
+        Self {
+            low: (0..block).map(angle).collect(),
+            high: (0..n / block + 1).map(|q| angle(q * block)).collect(),
+            block,
+        }
+    }
+
+    fn get(&self, m: usize) -> Complex<F> {
+        self.low[m % self.block] * self.high[m / self.block]
+    }
+}
+
+/// Transpose a row-major `rows x cols` matrix into `dst`, in parallel
+fn transpose<F: FftFloat>(src: &[Complex<F>], dst: &mut [Complex<F>], rows: usize, cols: usize) {
+    dst.par_chunks_mut(rows).enumerate().for_each(|(c, out)| {
+        for (r, v) in out.iter_mut().enumerate() {
+            *v = src[r * cols + c];
+        }
+    });
+}
+
+/// Four-step FFT of `data` viewed as an `n1 x n2` row-major matrix
+///
+/// With `x[j1 n2 + j2]`, the transform is
+/// `X[k1 + n1 k2] = sum_j2 w^(j2 k1) (sum_j1 x[j1 n2 + j2] w1^(j1 k1)) w2^(j2 k2)`:
+/// length-`n1` FFTs over the columns, a twiddle multiply, then length-`n2`
+/// FFTs over the rows. Must be called inside the worker pool.
+fn four_step<F: FftFloat>(
+    data: &mut [Complex<F>],
+    n1: usize,
+    n2: usize,
+    row_plan: &Arc<dyn Fft<F>>,
+    col_plan: &Arc<dyn Fft<F>>,
+    inverse: bool,
+    workers: usize,
+) {
+    let n = n1 * n2;
+    let twiddles = Twiddles::new(n, inverse);
+    let mut scratch = vec![Complex::new(F::zero(), F::zero()); n];
+
+    // Columns of x become rows of length n1
+    transpose(data, &mut scratch, n1, n2);
+    let block_rows = n2.div_ceil(workers);
+    scratch
+        .par_chunks_mut(block_rows * n1)
+        .enumerate()
+        .for_each(|(b, chunk)| {
+            row_plan.process(chunk);
+            for (r, row) in chunk.chunks_mut(n1).enumerate() {
+                let j2 = b * block_rows + r;
+                for (k1, v) in row.iter_mut().enumerate() {
+                    *v *= twiddles.get(j2 * k1);
+                }
+            }
+        });
+
+    // Back to n1 rows of length n2 for the second pass
+    transpose(&scratch, data, n2, n1);
+    let block_rows = n1.div_ceil(workers);
+    data.par_chunks_mut(block_rows * n2)
+        .for_each(|chunk| col_plan.process(chunk));
+
+    // X[k1 + n1 k2] sits at row k1, column k2
+    transpose(data, &mut scratch, n1, n2);
+    data.copy_from_slice(&scratch);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worker_pool::with_fft_workers;
+    use ndarray::Array3;
+    use num_complex::Complex64;
+
+    fn signal(n: usize) -> Vec<Complex64> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64;
+                Complex64::new((0.013 * t).sin() + (t * 1e-3).cos(), (0.007 * t * t).cos())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_four_step_matches_serial() {
+        // 2^16, a smooth composite and a length with only a small factor
+        for n in [1 << 16, 3 * 5 * 7 * 11 * 64, 2 * 65_537] {
+            for inverse in [false, true] {
+                let x = signal(n);
+                let mut serial = x.clone();
+                with_fft_workers(1, || fft_in_place(&mut serial, inverse));
+                let mut threaded = x;
+                with_fft_workers(4, || fft_in_place(&mut threaded, inverse));
+
+                let scale = serial.iter().map(|v| v.norm()).fold(0.0, f64::max);
+                for (a, b) in serial.iter().zip(threaded.iter()) {
+                    assert!((a - b).norm() < 1e-12 * scale, "n = {n}");
+                }
+            }
+        }
+        assert_eq!(balanced_split(1 << 16), Some((256, 256)));
+        assert_eq!(balanced_split(2 * 65_537), None);
+    }
+
+    #[test]
+    fn test_fft_along_axis_parallel() {
+        let data = signal(24 * 32 * 40);
+        let array = Array3::from_shape_vec((24, 32, 40), data).unwrap();
+        for axis in 0..3 {
+            let mut serial = array.clone();
+            with_fft_workers(1, || fft_along_axis(&mut serial, axis, false));
+            let mut threaded = array.clone();
+            with_fft_workers(3, || fft_along_axis(&mut threaded, axis, false));
+            for (a, b) in serial.iter().zip(threaded.iter()) {
+                assert!((a - b).norm() < 1e-9);
+            }
+
+            // Spot-check one lane against a direct 1-D transform
+            let mut lane = array.lanes(Axis(axis)).into_iter().next().unwrap().to_vec();
+            fft_in_place(&mut lane, false);
+            let first = threaded
+                .lanes(Axis(axis))
+                .into_iter()
+                .next()
+                .unwrap()
+                .to_vec();
+            for (a, b) in lane.iter().zip(first.iter()) {
+                assert!((a - b).norm() < 1e-9);
+            }
+        }
+    }
+}
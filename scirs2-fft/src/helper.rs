@@ -5,9 +5,7 @@
 
 use crate::error::{FFTError, FFTResult};
 use ndarray::{Array, Axis};
-use std::collections::HashSet;
 use std::fmt::Debug;
-use std::sync::LazyLock;
 
 /// Return the Discrete Fourier Transform sample frequencies.
 ///
@@ -234,18 +232,29 @@ pub fn freq_bins(n: usize, fs: f64) -> FFTResult<Vec<f64>> {
     fftfreq(n, 1.0 / fs)
 }
 
-// Set of prime factors that the FFT implementation can handle efficiently
-static EFFICIENT_FACTORS: LazyLock<HashSet<usize>> = LazyLock::new(|| {
-    let factors = [2, 3, 5, 7, 11];
-    factors.into_iter().collect()
-});
+// Odd prime factors the FFT kernels handle with dedicated butterflies. Real
+// transforms stick to 2-3-5 lengths, complex transforms also accept 7.
+const REAL_FAST_FACTORS: [usize; 2] = [3, 5];
+const COMPLEX_FAST_FACTORS: [usize; 3] = [3, 5, 7];
+
+fn fast_factors(real: bool) -> &'static [usize] {
+    if real {
+        &REAL_FAST_FACTORS
+    } else {
+        &COMPLEX_FAST_FACTORS
+    }
+}
 
 /// Find the next fast size of input data to `fft`, for zero-padding, etc.
 ///
 /// SciPy's FFT algorithms gain their speed by a recursive divide and conquer
 /// strategy. This relies on efficient functions for small prime factors of the
 /// input length. Thus, the transforms are fastest when using composites of the
-/// prime factors handled by the fft implementation.
+/// prime factors handled by the fft implementation: 2-3-5-smooth lengths for
+/// real transforms and 2-3-5-7-smooth lengths for complex transforms.
+///
+/// The search enumerates smooth numbers directly instead of testing every
+/// candidate, so it is cheap even for very large targets.
 ///
 /// # Arguments
 ///
@@ -263,43 +272,41 @@ static EFFICIENT_FACTORS: LazyLock<HashSet<usize>> = LazyLock::new(|| {
 ///
 /// let n = next_fast_len(1000, false);
 /// assert!(n >= 1000);
+///
+/// // 1021 is prime; the next 2-3-5-smooth length is 1024 = 2^10
+/// assert_eq!(next_fast_len(1021, true), 1024);
+/// // Complex transforms may also use a factor of 7: 1008 = 2^4 * 3^2 * 7
+/// assert_eq!(next_fast_len(1001, false), 1008);
 /// ```
 pub fn next_fast_len(target: usize, real: bool) -> usize {
     if target <= 1 {
         return 1;
     }
 
-    // Get the maximum prime factor to consider
-    let max_factor = if real { 5 } else { 11 };
-
-    let mut n = target;
-    loop {
-        // Try to factor n using only efficient prime factors
-        let mut is_smooth = true;
-        let mut remaining = n;
-
-        // Factor out all efficient primes up to max_factor
-        while remaining > 1 {
-            let mut factor_found = false;
-            for &p in EFFICIENT_FACTORS.iter().filter(|&&p| p <= max_factor) {
-                if remaining % p == 0 {
-                    remaining /= p;
-                    factor_found = true;
-                    break;
-                }
-            }
+    let mut best = target.checked_next_power_of_two().unwrap_or(target);
+    search_next_fast_len(target, 1, fast_factors(real), &mut best);
+    best
+}
 
-            if !factor_found {
-                is_smooth = false;
-                break;
-            }
-        }
+/// Smallest `product * 2^k * (odd factors)` that reaches `target`, updating `best`
+///
+/// The odd factors are taken in non-decreasing order so every smooth number is
+/// visited once, and branches that already exceed `best` are pruned.
+fn search_next_fast_len(target: usize, product: usize, factors: &[usize], best: &mut usize) {
+    // Complete the product with the smallest power of two reaching the target
+    if let Some(candidate) = target
+        .div_ceil(product)
+        .checked_next_power_of_two()
+        .and_then(|p| p.checked_mul(product))
+    {
+        *best = (*best).min(candidate);
+    }
 
-        if is_smooth {
-            return n;
+    for (i, &p) in factors.iter().enumerate() {
+        match product.checked_mul(p) {
+            Some(next) if next < *best => search_next_fast_len(target, next, &factors[i..], best),
+            _ => {}
         }
-
-        n += 1;
     }
 }
 
@@ -330,40 +337,23 @@ pub fn prev_fast_len(target: usize, real: bool) -> usize {
         return 1;
     }
 
-    // Get the maximum prime factor to consider
-    let max_factor = if real { 5 } else { 11 };
-
-    let mut n = target;
-    while n > 1 {
-        // Try to factor n using only efficient prime factors
-        let mut is_smooth = true;
-        let mut remaining = n;
-
-        // Factor out all efficient primes up to max_factor
-        while remaining > 1 {
-            let mut factor_found = false;
-            for &p in EFFICIENT_FACTORS.iter().filter(|&&p| p <= max_factor) {
-                if remaining % p == 0 {
-                    remaining /= p;
-                    factor_found = true;
-                    break;
-                }
-            }
+    let mut best = 1;
+    search_prev_fast_len(target, 1, fast_factors(real), &mut best);
+    best
+}
 
-            if !factor_found {
-                is_smooth = false;
-                break;
-            }
-        }
+/// Largest `product * 2^k * (odd factors)` not exceeding `target`, updating `best`
+fn search_prev_fast_len(target: usize, product: usize, factors: &[usize], best: &mut usize) {
+    // Complete the product with the largest power of two that still fits
+    let quotient = target / product;
+    let power_of_two = 1 << (usize::BITS - 1 - quotient.leading_zeros());
+    *best = (*best).max(product * power_of_two);
 
-        if is_smooth {
-            return n;
+    for (i, &p) in factors.iter().enumerate() {
+        if let Some(next) = product.checked_mul(p).filter(|&next| next <= target) {
+            search_prev_fast_len(target, next, &factors[i..], best);
         }
-
-        n -= 1;
     }
-
-    1
 }
 
 #[cfg(test)]
@@ -577,28 +567,34 @@ mod tests {
 
     // Helper function for tests to check if a number is a product of efficient factors
     fn is_fast_length(n: usize, real: bool) -> bool {
-        if n <= 1 {
-            return true;
-        }
-
-        let max_factor = if real { 5 } else { 11 };
-        let mut remaining = n;
-
-        while remaining > 1 {
-            let mut factor_found = false;
-            for &p in EFFICIENT_FACTORS.iter().filter(|&&p| p <= max_factor) {
-                if remaining % p == 0 {
-                    remaining /= p;
-                    factor_found = true;
-                    break;
-                }
+        let mut remaining = n.max(1);
+        for &p in [2].iter().chain(fast_factors(real)) {
+            while remaining.is_multiple_of(p) {
+                remaining /= p;
             }
+        }
+        remaining == 1
+    }
 
-            if !factor_found {
-                return false;
+    #[test]
+    fn test_fast_len_is_tight() {
+        // Compare against a brute-force scan over every target
+        for real in [true, false] {
+            for target in 1..2000 {
+                let next = next_fast_len(target, real);
+                assert!((target..next).all(|m| !is_fast_length(m, real)));
+                assert!(is_fast_length(next, real));
+
+                let prev = prev_fast_len(target, real);
+                assert!((prev + 1..=target).all(|m| !is_fast_length(m, real)));
+                assert!(is_fast_length(prev, real));
             }
         }
 
-        true
+        assert_eq!(next_fast_len(1021, true), 1024);
+        assert_eq!(next_fast_len(1000, true), 1000);
+        assert_eq!(next_fast_len(1001, false), 1008);
+        assert_eq!(next_fast_len(1001, true), 1024);
+        assert_eq!(prev_fast_len(1023, true), 1000);
     }
 }
@@ -63,8 +63,8 @@ pub use plan_cache::{get_global_cache, init_global_cache, CacheStats, PlanCache}
 // Worker pool management
 pub mod worker_pool;
 pub use worker_pool::{
-    get_global_pool, get_workers, init_global_pool, set_workers, with_workers, WorkerConfig,
-    WorkerPool, WorkerPoolInfo,
+    get_fft_workers, get_global_pool, get_workers, init_global_pool, set_fft_workers, set_workers,
+    with_fft_workers, with_workers, WorkerConfig, WorkerContext, WorkerPool, WorkerPoolInfo,
 };
 
 // FFT backend system
@@ -117,6 +117,7 @@ pub mod dst;
 pub mod fft;
 pub mod fht;
 pub mod hfft;
+pub mod precision;
 pub mod rfft;
 
 // Re-export basic functions
@@ -125,6 +126,7 @@ pub use dst::{dst, dst2, dstn, idst, idst2, idstn, DSTType};
 pub use fft::{fft, fft2, fftn, ifft, ifft2, ifftn};
 pub use fht::{fht, fht_sample_points, fhtoffset, ifht};
 pub use hfft::{hfft, hfft2, hfftn, ihfft, ihfft2, ihfftn};
+pub use precision::FftFloat;
 
 // Re-export parallel implementations when available
 #[cfg(feature = "parallel")]
@@ -208,13 +210,19 @@ pub use sparse_fft_cuda_kernels_iterative::{
 pub use sparse_fft_cuda_kernels_spectral_flatness::{
     execute_cuda_spectral_flatness_sparse_fft, CUDASpectralFlatnessSparseFFTKernel,
 };
-pub use sparse_fft_gpu::{gpu_batch_sparse_fft, gpu_sparse_fft, GPUBackend};
+pub use sparse_fft_gpu::{gpu_batch_sparse_fft, gpu_sparse_fft, gpu_sparse_fft2, GPUBackend};
 pub use sparse_fft_gpu_cuda::{
+    available_gpu_backends,
+    backend_supports_fft,
     cuda_batch_sparse_fft,
     cuda_sparse_fft,
+    cuda_sparse_fft2,
     get_cuda_devices,
+    probe_gpu_backend,
+    DeviceElement,
     GpuContext,
     GpuDeviceInfo,
+    GpuSparseFFT,
     // CUDAStream - migrated to core GPU abstractions
 };
 pub use sparse_fft_gpu_kernels::{
@@ -282,14 +290,44 @@ pub use window_extended::{
     WindowProperties,
 };
 
+// Rader and Bluestein transforms for prime and awkward lengths
+pub mod prime_fft;
+pub use prime_fft::{
+    fft_with_diagnostics, ifft_with_diagnostics, select_fft_algorithm, FftAlgorithm,
+    FftDiagnostics, PrimeFft,
+};
+
 // Chirp Z-Transform
 pub mod czt;
 pub use czt::{czt, czt_points, zoom_fft, CZT};
 
+// Streaming overlap-add / overlap-save convolution
+pub mod streaming;
+pub use streaming::{OlaConvolver, StreamingFft, StreamingMethod};
+
+// Analytic signal via the Hilbert transform
+pub mod hilbert;
+pub use hilbert::{hilbert, hilbert_nd};
+
+// Spectral differentiation and integration
+pub mod spectral_calculus;
+pub use spectral_calculus::{fft_derivative, fft_gradient_2d, fft_integrate, fft_laplacian_2d};
+
+// FFT-based N-dimensional convolution and correlation
+pub mod convolve;
+pub use convolve::{choose_conv_method, fftconvolve, fftcorrelate, oaconvolve, ConvolveMethod};
+
 // Automatic padding strategies
 pub mod padding;
 pub use padding::{
-    auto_pad_1d, auto_pad_complex, auto_pad_nd, remove_padding_1d, AutoPadConfig, PaddingMode,
+    auto_pad_1d, auto_pad_complex, auto_pad_nd, fft_padded, ifft_padded, padded_length,
+    remove_padding_1d, AutoPadConfig, PaddingMode,
+};
+
+// Out-of-core FFT over memory-mapped signals
+pub mod out_of_core;
+pub use out_of_core::{
+    read_complex_samples, segmented_fft, write_complex_samples, SegmentedFftConfig,
 };
 
 /// Performs a Short-Time Fourier Transform (STFT).
@@ -370,151 +408,6 @@ where
     )
 }
 
-/// Performs the Hilbert transform.
-///
-/// The Hilbert transform finds the analytical signal, which can be used to
-/// determine instantaneous amplitude and frequency. It is defined by convolving
-/// the signal with 1/(πt).
-///
-/// # Arguments
-///
-/// * `x` - Input signal (real-valued array)
-///
-/// # Returns
-///
-/// * A complex-valued array containing the analytic signal, where the real part
-///   is the original signal and the imaginary part is the Hilbert transform.
-///
-/// # Errors
-///
-/// Returns an error if the computation fails.
-///
-/// # Examples
-///
-/// ```
-/// use scirs2_fft::hilbert;
-/// use std::f64::consts::PI;
-///
-/// // Generate a cosine signal
-/// let n = 100;
-/// let freq = 5.0; // Hz
-/// let dt = 0.01;  // 100 Hz sampling
-/// let signal: Vec<f64> = (0..n).map(|i| (2.0 * PI * freq * i as f64 * dt).cos()).collect();
-///
-/// // Compute Hilbert transform
-/// let analytic_signal = hilbert(&signal).unwrap();
-///
-/// // For a cosine wave, the analytical signal should have a magnitude of approximately 1
-/// let mid_point = n / 2;
-/// let magnitude = (analytic_signal[mid_point].re.powi(2) +
-///                 analytic_signal[mid_point].im.powi(2)).sqrt();
-/// assert!((magnitude - 1.0).abs() < 0.1);
-/// ```
-///
-/// # References
-///
-/// * Marple, S. L. "Computing the Discrete-Time Analytic Signal via FFT."
-///   IEEE Transactions on Signal Processing, Vol. 47, No. 9, 1999.
-
-/// Helper function to try and extract a Complex value
-fn try_as_complex<U: 'static + Copy>(val: U) -> Option<num_complex::Complex64> {
-    use num_complex::Complex64;
-    use std::any::Any;
-
-    // Try to use runtime type checking with Any for complex types
-    if let Some(complex) = (&val as &dyn Any).downcast_ref::<Complex64>() {
-        return Some(*complex);
-    }
-
-    // Try to handle f32 complex numbers
-    if let Some(complex32) = (&val as &dyn Any).downcast_ref::<num_complex::Complex<f32>>() {
-        return Some(Complex64::new(complex32.re as f64, complex32.im as f64));
-    }
-
-    None
-}
-
-pub fn hilbert<T>(x: &[T]) -> FFTResult<Vec<num_complex::Complex64>>
-where
-    T: num_traits::NumCast + Copy + std::fmt::Debug + 'static,
-{
-    use num_complex::Complex64;
-
-    // Input length
-    let n = x.len();
-
-    // Convert input to a vector of f64
-    let signal: Vec<f64> = x
-        .iter()
-        .map(|&val| {
-            // First, try to cast directly to f64
-            if let Some(val_f64) = num_traits::cast::<T, f64>(val) {
-                return Ok(val_f64);
-            }
-
-            // If direct casting fails, check if it's a Complex value
-            // and use just the real part (for doctests which use Complex inputs)
-            match try_as_complex(val) {
-                Some(c) => Ok(c.re),
-                None => Err(FFTError::ValueError(format!(
-                    "Could not convert {val:?} to numeric type"
-                ))),
-            }
-        })
-        .collect::<FFTResult<Vec<_>>>()?;
-
-    // Compute FFT of the input signal
-    let spectrum = fft(&signal, None)?;
-
-    // Create the frequency domain filter for the Hilbert transform
-    // For a proper Hilbert transform, we need to:
-    // 1. Set the DC component (0 frequency) to 1
-    // 2. Double the positive frequencies and multiply by -i
-    // 3. Zero out the negative frequencies
-    let mut h = vec![Complex64::new(1.0, 0.0); n];
-
-    if n % 2 == 0 {
-        // Even length case
-        h[0] = Complex64::new(1.0, 0.0); // DC component
-        h[n / 2] = Complex64::new(1.0, 0.0); // Nyquist component
-
-        // Positive frequencies (multiply by 2 and by -i)
-        h.iter_mut().take(n / 2).skip(1).for_each(|val| {
-            *val = Complex64::new(0.0, -2.0); // Equivalent to 2 * (-i)
-        });
-
-        // Negative frequencies (set to 0)
-        h.iter_mut().skip(n / 2 + 1).for_each(|val| {
-            *val = Complex64::new(0.0, 0.0);
-        });
-    } else {
-        // Odd length case
-        h[0] = Complex64::new(1.0, 0.0); // DC component
-
-        // Positive frequencies (multiply by 2 and by -i)
-        h.iter_mut().take(n.div_ceil(2)).skip(1).for_each(|val| {
-            *val = Complex64::new(0.0, -2.0); // Equivalent to 2 * (-i)
-        });
-
-        // Negative frequencies (set to 0)
-        h.iter_mut().skip(n.div_ceil(2)).for_each(|val| {
-            *val = Complex64::new(0.0, 0.0);
-        });
-    }
-
-    // Apply the filter in frequency domain
-    let filtered_spectrum: Vec<Complex64> = spectrum
-        .iter()
-        .zip(h.iter())
-        .map(|(&s, &h)| s * h)
-        .collect();
-
-    // Compute inverse FFT to get the analytic signal
-    let analytic_signal = ifft(&filtered_spectrum, None)?;
-
-    Ok(analytic_signal)
-}
-
 /// Returns the minimum and maximum values for each FFT dimension.
 ///
 /// # Arguments
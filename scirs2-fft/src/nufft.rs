@@ -13,8 +13,28 @@
 //!
 //! * Type 1 (Non-Uniform to Uniform): Data at non-uniform locations, transform to uniform frequency grid
 //! * Type 2 (Uniform to Non-Uniform): Data at uniform locations, transform to non-uniform frequency grid
+//! * Type 3 (Non-Uniform to Non-Uniform): Data at non-uniform locations, transform to
+//!   non-uniform frequencies
+//!
+//! # Kaiser–Bessel gridding
+//!
+//! [`nufft1d1`], [`nufft1d2`], [`nufft1d3`] and their 2-D and 3-D counterparts
+//! spread the data onto a twice-oversampled grid with a Kaiser–Bessel kernel,
+//! apply an FFT, and divide out the kernel's Fourier transform. The kernel
+//! width follows from [`NufftOptions::tolerance`], so accuracy can be traded
+//! for speed down to about `1e-14`. Fourier modes use the centred ordering
+//! `k = -(N / 2), ..., (N - 1) / 2`.
+//!
+//! [`nufft_type1`] and [`nufft_type2`] are the older interpolation-based
+//! approximations without deconvolution; prefer the functions above when the
+//! result needs to match the exact sums.
 
 use crate::error::{FFTError, FFTResult};
+use crate::helper::next_fast_len;
+use ndarray::{
+    Array, Array2, Array3, ArrayD, ArrayView1, ArrayView2, ArrayView3, ArrayViewD, Axis, Dimension,
+    IxDyn,
+};
 use num_complex::Complex64;
 use num_traits::Zero;
 use std::f64::consts::PI;
@@ -80,8 +100,9 @@ pub enum InterpolationType {
 ///
 /// # Notes
 ///
-/// This is a basic implementation. For performance-critical applications,
-/// consider using a more optimized NUFFT library.
+/// This is a basic implementation that does not correct for the kernel, so
+/// the output only approximates the exact sums. Use [`nufft1d1`] for
+/// accurate results.
 pub fn nufft_type1(
     x: &[f64],
     samples: &[Complex64],
@@ -231,8 +252,9 @@ pub fn nufft_type1(
 ///
 /// # Notes
 ///
-/// This is a basic implementation. For performance-critical applications,
-/// consider using a more optimized NUFFT library.
+/// This is a basic implementation that does not correct for the kernel, so
+/// the output only approximates the exact sums. Use [`nufft1d2`] for
+/// accurate results.
 pub fn nufft_type2(
     spectrum: &[Complex64],
     x: &[f64],
@@ -323,6 +345,773 @@ pub fn nufft_type2(
     Ok(result)
 }
 
+// ---------------------------------------------------------------------------
+// Kaiser–Bessel gridding NUFFT
+// ---------------------------------------------------------------------------
+
+/// Oversampling factor of the fine grid used for spreading
+const OVERSAMPLING: f64 = 2.0;
+
+/// Widest kernel used, reached for tolerances of about 1e-14
+const MAX_KERNEL_WIDTH: usize = 16;
+
+/// Options for the Kaiser–Bessel NUFFTs ([`nufft1d1`] and friends)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NufftOptions {
+    /// Requested relative accuracy, between about 1e-14 and 1e-1
+    ///
+    /// The spreading kernel is `ceil(log10(1 / tolerance)) + 2` grid points
+    /// wide, so each extra digit costs one more point per dimension.
+    pub tolerance: f64,
+    /// Sign of the exponent: `-1` for `exp(-i k x)`, `+1` for `exp(+i k x)`
+    pub sign: i32,
+}
+
+impl Default for NufftOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-6,
+            sign: -1,
+        }
+    }
+}
+
+impl NufftOptions {
+    /// Default options with the given accuracy
+    pub fn with_tolerance(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            ..Self::default()
+        }
+    }
+
+    fn validate(&self) -> FFTResult<()> {
+        if !(self.tolerance > 0.0 && self.tolerance < 1.0) {
+            return Err(FFTError::ValueError(format!(
+                "NUFFT tolerance must be in (0, 1), got {}",
+                self.tolerance
+            )));
+        }
+        if self.sign != 1 && self.sign != -1 {
+            return Err(FFTError::ValueError(format!(
+                "NUFFT sign must be +1 or -1, got {}",
+                self.sign
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Kaiser–Bessel spreading kernel `I0(beta * sqrt(1 - (2z / w)^2))` on `|z| < w / 2`
+#[derive(Debug, Clone, Copy)]
+struct KaiserBessel {
+    /// Support in fine-grid points
+    width: usize,
+    /// Shape parameter, chosen for the oversampling factor (Beatty et al. 2005)
+    beta: f64,
+}
+
+impl KaiserBessel {
+    fn new(tolerance: f64) -> Self {
+        let digits = (1.0 / tolerance).log10().ceil().max(0.0) as usize;
+        let width = (digits + 2).clamp(2, MAX_KERNEL_WIDTH);
+        let w = width as f64;
+        let beta = PI * ((w / OVERSAMPLING * (OVERSAMPLING - 0.5)).powi(2) - 0.8).sqrt();
+        Self { width, beta }
+    }
+
+    /// Kernel value at `z` fine-grid points from its centre
+    fn eval(&self, z: f64) -> f64 {
+        let r = 2.0 * z / self.width as f64;
+        if r.abs() >= 1.0 {
+            0.0
+        } else {
+            bessel_i0(self.beta * (1.0 - r * r).sqrt())
+        }
+    }
+
+    /// Continuous Fourier transform of the kernel at `nu` cycles per grid point
+    fn fourier(&self, nu: f64) -> f64 {
+        let w = self.width as f64;
+        let arg = self.beta * self.beta - (PI * w * nu).powi(2);
+        if arg > 0.0 {
+            let r = arg.sqrt();
+            w * r.sinh() / r
+        } else if arg < 0.0 {
+            let r = (-arg).sqrt();
+            w * r.sin() / r
+        } else {
+            w
+        }
+    }
+}
+
+/// Modified Bessel function `I0` by its power series
+///
+/// All terms are positive, so the series is accurate to rounding for the
+/// arguments used by the kernel (below 40).
+fn bessel_i0(x: f64) -> f64 {
+    let q = 0.25 * x * x;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut k = 1.0;
+    while term > sum * f64::EPSILON {
+        term *= q / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+/// Even fine-grid size of at least `OVERSAMPLING * n` and twice the kernel width
+fn fine_grid_size(n: usize, width: usize) -> usize {
+    let half = ((OVERSAMPLING * n as f64 / 2.0).ceil() as usize).max(width);
+    2 * next_fast_len(half, false)
+}
+
+/// Row-major strides of a grid shape
+fn strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for d in (0..shape.len().saturating_sub(1)).rev() {
+        strides[d] = strides[d + 1] * shape[d + 1];
+    }
+    strides
+}
+
+/// Visit the fine-grid points within the kernel support of `coords`
+///
+/// `f` receives the flat (periodically wrapped) grid offset and the tensor
+/// product kernel weight.
+fn for_each_neighbour(
+    kernel: &KaiserBessel,
+    coords: &[f64],
+    shape: &[usize],
+    strides: &[usize],
+    mut f: impl FnMut(usize, f64),
+) {
+    let span = kernel.width + 1;
+    let half = kernel.width as f64 / 2.0;
+    let taps: Vec<Vec<(usize, f64)>> = coords
+        .iter()
+        .zip(shape.iter().zip(strides.iter()))
+        .map(|(&t, (&n, &stride))| {
+            let start = (t - half).ceil() as isize;
+            (0..span as isize)
+                .map(|i| {
+                    let l = start + i;
+                    let offset = l.rem_euclid(n as isize) as usize * stride;
+                    (offset, kernel.eval(t - l as f64))
+                })
+                .collect()
+        })
+        .collect();
+
+    let dims = coords.len();
+    let mut counter = vec![0usize; dims];
+    loop {
+        let (offset, weight) = counter
+            .iter()
+            .zip(taps.iter())
+            .fold((0, 1.0), |(o, w), (&i, tap)| (o + tap[i].0, w * tap[i].1));
+        if weight != 0.0 {
+            f(offset, weight);
+        }
+
+        // Advance the odometer over the kernel support
+        let mut d = dims;
+        loop {
+            if d == 0 {
+                return;
+            }
+            d -= 1;
+            counter[d] += 1;
+            if counter[d] < span {
+                break;
+            }
+            counter[d] = 0;
+        }
+    }
+}
+
+/// Spread weighted points onto a periodic fine grid
+fn spread(
+    kernel: &KaiserBessel,
+    coords: &[Vec<f64>],
+    values: &[Complex64],
+    shape: &[usize],
+) -> ArrayD<Complex64> {
+    let mut grid = ArrayD::<Complex64>::zeros(IxDyn(shape));
+    let strides = strides(shape);
+    let data = grid
+        .as_slice_mut()
+        .expect("freshly allocated grid is contiguous");
+    let mut point = vec![0.0; coords.len()];
+    for (j, &value) in values.iter().enumerate() {
+        for (p, c) in point.iter_mut().zip(coords.iter()) {
+            *p = c[j];
+        }
+        for_each_neighbour(kernel, &point, shape, &strides, |offset, weight| {
+            data[offset] += value * weight;
+        });
+    }
+    grid
+}
+
+/// Interpolate a periodic fine grid at the given points
+fn interpolate(
+    kernel: &KaiserBessel,
+    coords: &[Vec<f64>],
+    grid: &ArrayD<Complex64>,
+    n_points: usize,
+) -> Vec<Complex64> {
+    let shape = grid.shape().to_vec();
+    let strides = strides(&shape);
+    let data = grid.as_slice().expect("fine grid is contiguous");
+    let mut point = vec![0.0; coords.len()];
+    (0..n_points)
+        .map(|j| {
+            for (p, c) in point.iter_mut().zip(coords.iter()) {
+                *p = c[j];
+            }
+            let mut sum = Complex64::zero();
+            for_each_neighbour(kernel, &point, &shape, &strides, |offset, weight| {
+                sum += data[offset] * weight;
+            });
+            sum
+        })
+        .collect()
+}
+
+/// Unnormalized FFT over every axis with the requested exponent sign
+fn fft_grid(grid: &mut ArrayD<Complex64>, sign: i32) {
+    let mut planner = rustfft::FftPlanner::new();
+    for axis in 0..grid.ndim() {
+        let n = grid.shape()[axis];
+        let plan = if sign < 0 {
+            planner.plan_fft_forward(n)
+        } else {
+            planner.plan_fft_inverse(n)
+        };
+        let mut buffer = vec![Complex64::zero(); n];
+        for mut lane in grid.lanes_mut(Axis(axis)) {
+            buffer
+                .iter_mut()
+                .zip(lane.iter())
+                .for_each(|(b, &v)| *b = v);
+            plan.process(&mut buffer);
+            lane.iter_mut()
+                .zip(buffer.iter())
+                .for_each(|(v, &b)| *v = b);
+        }
+    }
+}
+
+/// Frequency of mode index `i` among `n` modes ordered from `-(n / 2)`
+fn mode_frequency(i: usize, n: usize) -> isize {
+    i as isize - (n / 2) as isize
+}
+
+/// Reciprocal kernel transform for each of `n` modes on a fine grid of `nf`
+fn deconvolution_factors(kernel: &KaiserBessel, n: usize, nf: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| 1.0 / kernel.fourier(mode_frequency(i, n) as f64 / nf as f64))
+        .collect()
+}
+
+fn check_points(points: &[&[f64]], n_values: usize, what: &str) -> FFTResult<()> {
+    for coords in points {
+        if coords.len() != n_values {
+            return Err(FFTError::DimensionError(format!(
+                "Each coordinate array must have one entry per {what} ({n_values}), got {}",
+                coords.len()
+            )));
+        }
+        if coords.iter().any(|v| !v.is_finite()) {
+            return Err(FFTError::ValueError(
+                "NUFFT coordinates must be finite".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Type 1 in any dimension: `f[k] = sum_j c[j] exp(sign i k.x[j])`
+fn type1(
+    points: &[&[f64]],
+    values: &[Complex64],
+    n_modes: &[usize],
+    options: &NufftOptions,
+) -> FFTResult<ArrayD<Complex64>> {
+    options.validate()?;
+    check_points(points, values.len(), "value")?;
+    if n_modes.contains(&0) {
+        return Err(FFTError::ValueError(
+            "Number of output modes must be positive".to_string(),
+        ));
+    }
+
+    let kernel = KaiserBessel::new(options.tolerance);
+    let fine: Vec<usize> = n_modes
+        .iter()
+        .map(|&n| fine_grid_size(n, kernel.width))
+        .collect();
+    let coords: Vec<Vec<f64>> = points
+        .iter()
+        .zip(fine.iter())
+        .map(|(x, &nf)| {
+            let scale = nf as f64 / (2.0 * PI);
+            x.iter().map(|&v| v.rem_euclid(2.0 * PI) * scale).collect()
+        })
+        .collect();
+
+    let mut grid = spread(&kernel, &coords, values, &fine);
+    fft_grid(&mut grid, options.sign);
+
+    let factors: Vec<Vec<f64>> = n_modes
+        .iter()
+        .zip(fine.iter())
+        .map(|(&n, &nf)| deconvolution_factors(&kernel, n, nf))
+        .collect();
+    let mut fine_index = vec![0; n_modes.len()];
+    Ok(ArrayD::from_shape_fn(IxDyn(n_modes), |idx| {
+        let mut scale = 1.0;
+        for d in 0..n_modes.len() {
+            let k = mode_frequency(idx[d], n_modes[d]);
+            fine_index[d] = k.rem_euclid(fine[d] as isize) as usize;
+            scale *= factors[d][idx[d]];
+        }
+        grid[IxDyn(&fine_index)] * scale
+    }))
+}
+
+/// Type 2 in any dimension: `c[j] = sum_k f[k] exp(sign i k.x[j])`
+fn type2(
+    points: &[&[f64]],
+    modes: &ArrayViewD<Complex64>,
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    options.validate()?;
+    let n_points = points.first().map_or(0, |x| x.len());
+    check_points(points, n_points, "point")?;
+
+    let kernel = KaiserBessel::new(options.tolerance);
+    let n_modes = modes.shape().to_vec();
+    let fine: Vec<usize> = n_modes
+        .iter()
+        .map(|&n| fine_grid_size(n, kernel.width))
+        .collect();
+    let factors: Vec<Vec<f64>> = n_modes
+        .iter()
+        .zip(fine.iter())
+        .map(|(&n, &nf)| deconvolution_factors(&kernel, n, nf))
+        .collect();
+
+    let mut grid = ArrayD::<Complex64>::zeros(IxDyn(&fine));
+    let mut fine_index = vec![0; n_modes.len()];
+    for (idx, &value) in modes.indexed_iter() {
+        let mut scale = 1.0;
+        for d in 0..n_modes.len() {
+            let k = mode_frequency(idx[d], n_modes[d]);
+            fine_index[d] = k.rem_euclid(fine[d] as isize) as usize;
+            scale *= factors[d][idx[d]];
+        }
+        grid[IxDyn(&fine_index)] = value * scale;
+    }
+    fft_grid(&mut grid, options.sign);
+
+    let coords: Vec<Vec<f64>> = points
+        .iter()
+        .zip(fine.iter())
+        .map(|(x, &nf)| {
+            let scale = nf as f64 / (2.0 * PI);
+            x.iter().map(|&v| v.rem_euclid(2.0 * PI) * scale).collect()
+        })
+        .collect();
+    Ok(interpolate(&kernel, &coords, &grid, n_points))
+}
+
+/// Midpoint and half-width of the range of `v` (zero for empty input)
+fn center_and_half_width(v: &[f64]) -> (f64, f64) {
+    if v.is_empty() {
+        return (0.0, 0.0);
+    }
+    let (lo, hi) = v
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+            (lo.min(x), hi.max(x))
+        });
+    (0.5 * (lo + hi), 0.5 * (hi - lo))
+}
+
+/// Type 3 in any dimension: `f[k] = sum_j c[j] exp(sign i s[k].x[j])`
+///
+/// The centred points are spread onto a fine grid whose spacing `h` keeps
+/// `|s| h <= pi / OVERSAMPLING`, and the grid is then evaluated at the
+/// target frequencies with a type 2 transform and deconvolved.
+fn type3(
+    points: &[&[f64]],
+    values: &[Complex64],
+    freqs: &[&[f64]],
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    options.validate()?;
+    check_points(points, values.len(), "value")?;
+    let n_freqs = freqs.first().map_or(0, |s| s.len());
+    check_points(freqs, n_freqs, "target frequency")?;
+
+    let kernel = KaiserBessel::new(options.tolerance);
+    let sign = options.sign as f64;
+    let dims = points.len();
+
+    let mut spacing = Vec::with_capacity(dims);
+    let mut fine = Vec::with_capacity(dims);
+    let mut x_center = Vec::with_capacity(dims);
+    let mut s_center = Vec::with_capacity(dims);
+    for d in 0..dims {
+        let (cx, x_half) = center_and_half_width(points[d]);
+        let (cs, s_half) = center_and_half_width(freqs[d]);
+        let s_half = if s_half > 0.0 { s_half } else { 1.0 };
+        let h = PI / (OVERSAMPLING * s_half);
+        let half = (x_half / h + kernel.width as f64 / 2.0 + 1.0).ceil() as usize;
+        spacing.push(h);
+        fine.push(2 * next_fast_len(half.max(kernel.width), false));
+        x_center.push(cx);
+        s_center.push(cs);
+    }
+
+    // Shift the frequencies to the origin by pre-phasing the strengths
+    let shifted: Vec<Complex64> = values
+        .iter()
+        .enumerate()
+        .map(|(j, &c)| {
+            let phase: f64 = (0..dims)
+                .map(|d| s_center[d] * (points[d][j] - x_center[d]))
+                .sum();
+            c * Complex64::from_polar(1.0, sign * phase)
+        })
+        .collect();
+    let coords: Vec<Vec<f64>> = (0..dims)
+        .map(|d| {
+            let offset = (fine[d] / 2) as f64;
+            points[d]
+                .iter()
+                .map(|&x| (x - x_center[d]) / spacing[d] + offset)
+                .collect()
+        })
+        .collect();
+    let grid = spread(&kernel, &coords, &shifted, &fine);
+
+    // Evaluate the grid's Fourier series at the scaled target frequencies
+    let theta: Vec<Vec<f64>> = (0..dims)
+        .map(|d| {
+            freqs[d]
+                .iter()
+                .map(|&s| (s - s_center[d]) * spacing[d])
+                .collect()
+        })
+        .collect();
+    let theta_refs: Vec<&[f64]> = theta.iter().map(|t| t.as_slice()).collect();
+    let mut result = type2(&theta_refs, &grid.view(), options)?;
+
+    for (k, value) in result.iter_mut().enumerate() {
+        let mut phase = 0.0;
+        let mut scale = 1.0;
+        for d in 0..dims {
+            phase += freqs[d][k] * x_center[d];
+            scale /= kernel.fourier(theta[d][k] / (2.0 * PI));
+        }
+        *value *= Complex64::from_polar(scale, sign * phase);
+    }
+    Ok(result)
+}
+
+/// Convert a dynamic-dimensional result to a fixed dimensionality
+fn into_dim<D: Dimension>(a: ArrayD<Complex64>) -> FFTResult<Array<Complex64, D>> {
+    a.into_dimensionality::<D>()
+        .map_err(|e| FFTError::DimensionError(e.to_string()))
+}
+
+/// 1-D type 1 NUFFT (non-uniform points to uniform modes) with a Kaiser–Bessel kernel
+///
+/// Computes `f[k] = sum_j c[j] exp(sign * i * k * x[j])` for the `n_modes`
+/// integer frequencies `k = -(n_modes / 2), ..., (n_modes - 1) / 2`, returned
+/// in that (centred) order. Points are taken modulo `2π`, so any finite
+/// values are accepted; the natural range is `[-π, π)`.
+///
+/// # Arguments
+///
+/// * `x` - Non-uniform sample locations
+/// * `c` - Complex strengths at `x`
+/// * `n_modes` - Number of output Fourier modes
+/// * `options` - Accuracy and exponent sign
+///
+/// # Returns
+///
+/// * The `n_modes` Fourier coefficients, accurate to about `options.tolerance`
+///   relative to `sum_j |c[j]|`
+///
+/// # Errors
+///
+/// Returns an error if `x` and `c` differ in length, a point is not finite,
+/// `n_modes` is zero, or the options are invalid.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::nufft::{nufft1d1, NufftOptions};
+/// use num_complex::Complex64;
+///
+/// let x = vec![-2.5, -0.3, 0.7, 2.9];
+/// let c = vec![Complex64::new(1.0, 0.0); 4];
+/// let options = NufftOptions::with_tolerance(1e-10);
+/// let f = nufft1d1(&x, &c, 8, &options).unwrap();
+///
+/// // Compare mode k = 1 (index 5) with the direct sum
+/// let direct: Complex64 = x.iter().map(|&xj| Complex64::from_polar(1.0, -xj)).sum();
+/// assert!((f[5] - direct).norm() < 1e-9);
+/// ```
+pub fn nufft1d1(
+    x: &[f64],
+    c: &[Complex64],
+    n_modes: usize,
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    Ok(type1(&[x], c, &[n_modes], options)?
+        .into_raw_vec_and_offset()
+        .0)
+}
+
+/// 1-D type 2 NUFFT (uniform modes to non-uniform points) with a Kaiser–Bessel kernel
+///
+/// Computes `c[j] = sum_k f[k] exp(sign * i * k * x[j])`, where the modes
+/// `f` use the centred ordering of [`nufft1d1`]. With opposite signs, type 2
+/// is the adjoint of type 1.
+///
+/// # Arguments
+///
+/// * `x` - Non-uniform target locations
+/// * `modes` - Fourier coefficients in centred order
+/// * `options` - Accuracy and exponent sign
+///
+/// # Returns
+///
+/// * The values at each point of `x`
+///
+/// # Errors
+///
+/// Returns an error if a point is not finite or the options are invalid.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::nufft::{nufft1d2, NufftOptions};
+/// use num_complex::Complex64;
+///
+/// // Modes run from k = -3 to 2; a single k = 2 mode evaluates to exp(i 2 x)
+/// let mut modes = vec![Complex64::new(0.0, 0.0); 6];
+/// modes[5] = Complex64::new(1.0, 0.0);
+/// let options = NufftOptions { tolerance: 1e-10, sign: 1 };
+/// let x = vec![0.1, 1.3, -2.0];
+/// let values = nufft1d2(&x, &modes, &options).unwrap();
+/// for (v, &xj) in values.iter().zip(x.iter()) {
+///     assert!((v - Complex64::from_polar(1.0, 2.0 * xj)).norm() < 1e-9);
+/// }
+/// ```
+pub fn nufft1d2(
+    x: &[f64],
+    modes: &[Complex64],
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    let modes = ArrayView1::from(modes).into_dyn();
+    type2(&[x], &modes, options)
+}
+
+/// 1-D type 3 NUFFT (non-uniform points to non-uniform frequencies)
+///
+/// Computes `f[k] = sum_j c[j] exp(sign * i * s[k] * x[j])` for arbitrary
+/// real points and frequencies. The cost grows with the space-bandwidth
+/// product `(max x - min x) * (max s - min s)`.
+///
+/// # Arguments
+///
+/// * `x` - Non-uniform source locations
+/// * `c` - Complex strengths at `x`
+/// * `s` - Target frequencies
+/// * `options` - Accuracy and exponent sign
+///
+/// # Returns
+///
+/// * The transform at each frequency of `s`
+///
+/// # Errors
+///
+/// Returns an error if `x` and `c` differ in length, a coordinate is not
+/// finite, or the options are invalid.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::nufft::{nufft1d3, NufftOptions};
+/// use num_complex::Complex64;
+///
+/// let x = vec![0.0, 3.7, 10.2, 25.0];
+/// let c = vec![Complex64::new(1.0, 0.0), Complex64::new(0.0, 2.0), Complex64::new(-1.0, 0.5), Complex64::new(0.3, 0.0)];
+/// let s = vec![-1.25, 0.0, 0.4, 3.3];
+/// let f = nufft1d3(&x, &c, &s, &NufftOptions::with_tolerance(1e-9)).unwrap();
+/// for (fk, &sk) in f.iter().zip(s.iter()) {
+///     let direct: Complex64 = x.iter().zip(c.iter())
+///         .map(|(&xj, &cj)| cj * Complex64::from_polar(1.0, -sk * xj))
+///         .sum();
+///     assert!((fk - direct).norm() < 1e-7);
+/// }
+/// ```
+pub fn nufft1d3(
+    x: &[f64],
+    c: &[Complex64],
+    s: &[f64],
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    type3(&[x], c, &[s], options)
+}
+
+/// 2-D type 1 NUFFT with a Kaiser–Bessel kernel
+///
+/// Computes `f[k1, k2] = sum_j c[j] exp(sign * i * (k1 x[j] + k2 y[j]))` on
+/// an `n_modes.0 x n_modes.1` grid of centred frequencies (see [`nufft1d1`]).
+///
+/// # Errors
+///
+/// Returns an error if the inputs differ in length, a point is not finite, a
+/// mode count is zero, or the options are invalid.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::nufft::{nufft2d1, NufftOptions};
+/// use num_complex::Complex64;
+///
+/// let x = vec![0.5, -1.0];
+/// let y = vec![2.0, 0.25];
+/// let c = vec![Complex64::new(1.0, 0.0), Complex64::new(0.0, 1.0)];
+/// let f = nufft2d1(&x, &y, &c, (4, 6), &NufftOptions::with_tolerance(1e-10)).unwrap();
+/// assert_eq!(f.dim(), (4, 6));
+///
+/// // The zero-frequency mode is the plain sum of strengths
+/// assert!((f[[2, 3]] - Complex64::new(1.0, 1.0)).norm() < 1e-9);
+/// ```
+pub fn nufft2d1(
+    x: &[f64],
+    y: &[f64],
+    c: &[Complex64],
+    n_modes: (usize, usize),
+    options: &NufftOptions,
+) -> FFTResult<Array2<Complex64>> {
+    into_dim(type1(&[x, y], c, &[n_modes.0, n_modes.1], options)?)
+}
+
+/// 2-D type 2 NUFFT with a Kaiser–Bessel kernel
+///
+/// Computes `c[j] = sum_{k1,k2} f[k1, k2] exp(sign * i * (k1 x[j] + k2 y[j]))`
+/// for modes in the centred order of [`nufft2d1`].
+///
+/// # Errors
+///
+/// Returns an error if `x` and `y` differ in length, a point is not finite,
+/// or the options are invalid.
+pub fn nufft2d2(
+    x: &[f64],
+    y: &[f64],
+    modes: &ArrayView2<Complex64>,
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    type2(&[x, y], &modes.view().into_dyn(), options)
+}
+
+/// 2-D type 3 NUFFT (non-uniform points to non-uniform frequencies)
+///
+/// Computes `f[k] = sum_j c[j] exp(sign * i * (s[k] x[j] + t[k] y[j]))`.
+///
+/// # Errors
+///
+/// Returns an error if the coordinate arrays are inconsistent in length, a
+/// coordinate is not finite, or the options are invalid.
+pub fn nufft2d3(
+    x: &[f64],
+    y: &[f64],
+    c: &[Complex64],
+    s: &[f64],
+    t: &[f64],
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    type3(&[x, y], c, &[s, t], options)
+}
+
+/// 3-D type 1 NUFFT with a Kaiser–Bessel kernel
+///
+/// Computes `f[k1, k2, k3] = sum_j c[j] exp(sign * i * (k1 x[j] + k2 y[j] + k3 z[j]))`
+/// on a grid of centred frequencies (see [`nufft1d1`]).
+///
+/// # Errors
+///
+/// Returns an error if the inputs differ in length, a point is not finite, a
+/// mode count is zero, or the options are invalid.
+pub fn nufft3d1(
+    x: &[f64],
+    y: &[f64],
+    z: &[f64],
+    c: &[Complex64],
+    n_modes: (usize, usize, usize),
+    options: &NufftOptions,
+) -> FFTResult<Array3<Complex64>> {
+    into_dim(type1(
+        &[x, y, z],
+        c,
+        &[n_modes.0, n_modes.1, n_modes.2],
+        options,
+    )?)
+}
+
+/// 3-D type 2 NUFFT with a Kaiser–Bessel kernel
+///
+/// Computes `c[j] = sum_k f[k] exp(sign * i * (k1 x[j] + k2 y[j] + k3 z[j]))`
+/// for modes in the centred order of [`nufft3d1`].
+///
+/// # Errors
+///
+/// Returns an error if the coordinate arrays differ in length, a point is
+/// not finite, or the options are invalid.
+pub fn nufft3d2(
+    x: &[f64],
+    y: &[f64],
+    z: &[f64],
+    modes: &ArrayView3<Complex64>,
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    type2(&[x, y, z], &modes.view().into_dyn(), options)
+}
+
+/// 3-D type 3 NUFFT (non-uniform points to non-uniform frequencies)
+///
+/// Computes `f[k] = sum_j c[j] exp(sign * i * (s[k] x[j] + t[k] y[j] + u[k] z[j]))`.
+///
+/// # Errors
+///
+/// Returns an error if the coordinate arrays are inconsistent in length, a
+/// coordinate is not finite, or the options are invalid.
+#[allow(clippy::too_many_arguments)]
+pub fn nufft3d3(
+    x: &[f64],
+    y: &[f64],
+    z: &[f64],
+    c: &[Complex64],
+    s: &[f64],
+    t: &[f64],
+    u: &[f64],
+    options: &NufftOptions,
+) -> FFTResult<Vec<Complex64>> {
+    type3(&[x, y, z], c, &[s, t, u], options)
+}
+
 /// Helper function for FFT computation used in NUFFT implementations
 fn fft_backend(data: &[Complex64]) -> FFTResult<Vec<Complex64>> {
     use rustfft::{num_complex::Complex, FftPlanner};
@@ -496,4 +1285,188 @@ mod tests {
         let result = nufft_type1(&x, &samples, 8, InterpolationType::Gaussian, 1e-6);
         assert!(result.is_err());
     }
+
+    /// Direct evaluation of `sum_j c[j] exp(sign i s[k].x[j])`
+    fn direct_sum(
+        points: &[&[f64]],
+        c: &[Complex64],
+        freqs: &[&[f64]],
+        sign: f64,
+    ) -> Vec<Complex64> {
+        (0..freqs[0].len())
+            .map(|k| {
+                (0..c.len())
+                    .map(|j| {
+                        let phase: f64 =
+                            (0..points.len()).map(|d| freqs[d][k] * points[d][j]).sum();
+                        c[j] * Complex64::from_polar(1.0, sign * phase)
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn relative_error(a: &[Complex64], b: &[Complex64]) -> f64 {
+        let diff: f64 = a
+            .iter()
+            .zip(b.iter())
+            .map(|(x, y)| (x - y).norm_sqr())
+            .sum();
+        let norm: f64 = b.iter().map(|y| y.norm_sqr()).sum();
+        (diff / norm).sqrt()
+    }
+
+    /// Deterministic pseudo-random values in [-1, 1)
+    fn pseudo_random(n: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+            })
+            .collect()
+    }
+
+    fn strengths(n: usize) -> Vec<Complex64> {
+        pseudo_random(n, 7)
+            .into_iter()
+            .zip(pseudo_random(n, 11))
+            .map(|(re, im)| Complex64::new(re, im))
+            .collect()
+    }
+
+    #[test]
+    fn test_kaiser_bessel_nufft_1d_accuracy() {
+        let n_points = 200;
+        let x: Vec<f64> = pseudo_random(n_points, 3).iter().map(|v| v * PI).collect();
+        let c = strengths(n_points);
+
+        for (n_modes, tol) in [(33, 1e-4), (64, 1e-8), (50, 1e-12)] {
+            for sign in [-1, 1] {
+                let options = NufftOptions {
+                    tolerance: tol,
+                    sign,
+                };
+                let modes: Vec<f64> = (0..n_modes)
+                    .map(|i| mode_frequency(i, n_modes) as f64)
+                    .collect();
+
+                let f = nufft1d1(&x, &c, n_modes, &options).unwrap();
+                let expected = direct_sum(&[&x], &c, &[&modes], sign as f64);
+                assert!(relative_error(&f, &expected) < 10.0 * tol);
+
+                // Type 2 evaluates the Fourier series at the points
+                let coeffs = strengths(n_modes);
+                let values = nufft1d2(&x, &coeffs, &options).unwrap();
+                let expected = direct_sum(&[&modes], &coeffs, &[&x], sign as f64);
+                assert!(relative_error(&values, &expected) < 10.0 * tol);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kaiser_bessel_nufft_multidimensional() {
+        let n_points = 60;
+        let x: Vec<f64> = pseudo_random(n_points, 1).iter().map(|v| v * PI).collect();
+        let y: Vec<f64> = pseudo_random(n_points, 2).iter().map(|v| v * PI).collect();
+        let z: Vec<f64> = pseudo_random(n_points, 5).iter().map(|v| v * PI).collect();
+        let c = strengths(n_points);
+        let options = NufftOptions::with_tolerance(1e-9);
+
+        // 2-D type 1 against the direct sum over every mode
+        let f = nufft2d1(&x, &y, &c, (6, 5), &options).unwrap();
+        let (mut k1, mut k2) = (Vec::new(), Vec::new());
+        for i in 0..6 {
+            for j in 0..5 {
+                k1.push(mode_frequency(i, 6) as f64);
+                k2.push(mode_frequency(j, 5) as f64);
+            }
+        }
+        let expected = direct_sum(&[&x, &y], &c, &[&k1, &k2], -1.0);
+        let computed: Vec<Complex64> = f.iter().copied().collect();
+        assert!(relative_error(&computed, &expected) < 1e-8);
+
+        // 2-D type 2 with the same modes evaluates back at the points
+        let values = nufft2d2(&x, &y, &f.view(), &options).unwrap();
+        let expected = direct_sum(&[&k1, &k2], &computed, &[&x, &y], -1.0);
+        assert!(relative_error(&values, &expected) < 1e-8);
+
+        // 3-D type 1 and type 2
+        let f3 = nufft3d1(&x, &y, &z, &c, (4, 3, 5), &options).unwrap();
+        let mut k = [Vec::new(), Vec::new(), Vec::new()];
+        for ((i, j, l), _) in f3.indexed_iter() {
+            k[0].push(mode_frequency(i, 4) as f64);
+            k[1].push(mode_frequency(j, 3) as f64);
+            k[2].push(mode_frequency(l, 5) as f64);
+        }
+        let expected = direct_sum(&[&x, &y, &z], &c, &[&k[0], &k[1], &k[2]], -1.0);
+        let computed: Vec<Complex64> = f3.iter().copied().collect();
+        assert!(relative_error(&computed, &expected) < 1e-8);
+
+        let values = nufft3d2(&x, &y, &z, &f3.view(), &options).unwrap();
+        let expected = direct_sum(&[&k[0], &k[1], &k[2]], &computed, &[&x, &y, &z], -1.0);
+        assert!(relative_error(&values, &expected) < 1e-8);
+    }
+
+    #[test]
+    fn test_kaiser_bessel_nufft_type3() {
+        let n_points = 80;
+        let x: Vec<f64> = pseudo_random(n_points, 9)
+            .iter()
+            .map(|v| 20.0 * v + 3.0)
+            .collect();
+        let y: Vec<f64> = pseudo_random(n_points, 4).iter().map(|v| 2.0 * v).collect();
+        let z: Vec<f64> = pseudo_random(n_points, 6)
+            .iter()
+            .map(|v| 0.5 * v - 7.0)
+            .collect();
+        let c = strengths(n_points);
+        let s: Vec<f64> = pseudo_random(40, 12)
+            .iter()
+            .map(|v| 3.0 * v + 1.0)
+            .collect();
+        let t: Vec<f64> = pseudo_random(40, 13).iter().map(|v| 10.0 * v).collect();
+        let u: Vec<f64> = pseudo_random(40, 14).to_vec();
+
+        for tol in [1e-6, 1e-11] {
+            let options = NufftOptions {
+                tolerance: tol,
+                sign: 1,
+            };
+            let f = nufft1d3(&x, &c, &s, &options).unwrap();
+            let expected = direct_sum(&[&x], &c, &[&s], 1.0);
+            assert!(relative_error(&f, &expected) < 10.0 * tol);
+
+            let f = nufft2d3(&x, &y, &c, &s, &t, &options).unwrap();
+            let expected = direct_sum(&[&x, &y], &c, &[&s, &t], 1.0);
+            assert!(relative_error(&f, &expected) < 10.0 * tol);
+
+            let f = nufft3d3(&x, &y, &z, &c, &s, &t, &u, &options).unwrap();
+            let expected = direct_sum(&[&x, &y, &z], &c, &[&s, &t, &u], 1.0);
+            assert!(relative_error(&f, &expected) < 10.0 * tol);
+        }
+
+        // A single frequency has no bandwidth to scale by
+        let f = nufft1d3(&x, &c, &[0.0], &NufftOptions::default()).unwrap();
+        let sum: Complex64 = c.iter().sum();
+        assert!((f[0] - sum).norm() < 1e-5 * sum.norm().max(1.0));
+    }
+
+    #[test]
+    fn test_kaiser_bessel_nufft_errors() {
+        let c = vec![Complex64::new(1.0, 0.0); 2];
+        let options = NufftOptions::default();
+        assert!(nufft1d1(&[0.0], &c, 4, &options).is_err());
+        assert!(nufft1d1(&[0.0, f64::NAN], &c, 4, &options).is_err());
+        assert!(nufft1d1(&[0.0, 1.0], &c, 0, &options).is_err());
+        let bad_sign = NufftOptions {
+            tolerance: 1e-6,
+            sign: 0,
+        };
+        assert!(nufft1d1(&[0.0, 1.0], &c, 4, &bad_sign).is_err());
+        assert!(nufft1d2(&[0.0], &c, &NufftOptions::with_tolerance(0.0)).is_err());
+        assert!(nufft2d3(&[0.0, 1.0], &[0.0], &c, &[1.0], &[1.0], &options).is_err());
+    }
 }
@@ -6,6 +6,7 @@
 
 use crate::error::{FFTError, FFTResult};
 use crate::fft::{fft, ifft};
+use crate::prime_fft::{FftAlgorithm, PrimeFft};
 use ndarray::{Array, ArrayBase, Data};
 use num_complex::Complex64;
 use num_traits::NumCast;
@@ -487,9 +488,8 @@ impl OptimizedFFT {
     }
 
     fn bluestein_fft(&self, data: &mut [Complex64]) -> FFTResult<Vec<Complex64>> {
-        // For simplicity, delegate to the standard implementation
-        // In a real implementation, this would be Bluestein's algorithm
-        fft(data, None)
+        PrimeFft::with_algorithm(data.len(), false, FftAlgorithm::Bluestein)?.process(data)?;
+        Ok(data.to_vec())
     }
 
     fn prime_factor_fft(&self, data: &mut [Complex64]) -> FFTResult<Vec<Complex64>> {
@@ -505,9 +505,12 @@ impl OptimizedFFT {
     }
 
     fn bluestein_ifft(&self, data: &[Complex64]) -> FFTResult<Vec<Complex64>> {
-        // For simplicity, delegate to the standard implementation
-        // In a real implementation, this would be Bluestein's algorithm
-        ifft(data, None)
+        let mut result = data.to_vec();
+        PrimeFft::with_algorithm(result.len(), true, FftAlgorithm::Bluestein)?
+            .process(&mut result)?;
+        let scale = 1.0 / result.len() as f64;
+        result.iter_mut().for_each(|v| *v *= scale);
+        Ok(result)
     }
 
     fn prime_factor_ifft(&self, data: &[Complex64]) -> FFTResult<Vec<Complex64>> {
@@ -0,0 +1,388 @@
+//! Out-of-core FFT over memory-mapped signals
+//!
+//! [`segmented_fft`] transforms signals stored on disk that are too large to
+//! hold in memory. The length `n` is split as `n = n1 * n2` and the signal is
+//! viewed as an `n1 x n2` row-major matrix. The transform then follows the
+//! four-step decomposition (Bailey, 1990):
+//!
+//! 1. length-`n1` FFTs down every column,
+//! 2. multiplication by the twiddle factors `exp(-2πi k1 n2 / n)`,
+//! 3. length-`n2` FFTs along every row,
+//! 4. a transpose, so that bin `k1 + n1 * k2` lands at its natural position.
+//!
+//! Columns and rows are streamed through an in-memory panel whose size is
+//! bounded by [`SegmentedFftConfig::working_set_bytes`]. As in the six-step
+//! variant, the column pass and the final transpose touch the files only in
+//! contiguous runs, one run per matrix row, so the operating system pages
+//! the mapped data sequentially. The intermediate matrix lives in an
+//! anonymous temporary file.
+//!
+//! Signals are stored as raw complex samples: each sample is two
+//! little-endian `f64` values, the real part followed by the imaginary part.
+//! [`write_complex_samples`] and [`read_complex_samples`] convert between
+//! this layout and in-memory slices.
+//!
+//! # References
+//!
+//! * Bailey, D. H. "FFTs in external or hierarchical memory."
+//!   The Journal of Supercomputing, Vol. 4, No. 1, 1990.
+
+use crate::error::{FFTError, FFTResult};
+use memmap2::{Mmap, MmapMut};
+use num_complex::Complex64;
+use rustfft::FftPlanner;
+use std::f64::consts::PI;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Size of one stored sample in bytes
+const SAMPLE_BYTES: usize = 16;
+
+/// Configuration for [`segmented_fft`]
+#[derive(Debug, Clone)]
+pub struct SegmentedFftConfig {
+    /// Upper bound on the size of the in-memory panel, in bytes
+    pub working_set_bytes: usize,
+    /// Compute the inverse transform (scaled by `1/n`) instead of the forward one
+    pub inverse: bool,
+}
+
+impl Default for SegmentedFftConfig {
+    fn default() -> Self {
+        Self {
+            working_set_bytes: 64 * 1024 * 1024,
+            inverse: false,
+        }
+    }
+}
+
+/// Computes the FFT of a signal stored in a file, writing the spectrum to another file.
+///
+/// The input is memory-mapped and transformed with the four-step
+/// decomposition described in the [module documentation](self), so only a
+/// panel of at most `config.working_set_bytes` is held in memory at a time.
+/// The output file is created (or truncated) and has the same length as the
+/// input.
+///
+/// # Arguments
+///
+/// * `input` - Path of the signal, stored as raw complex samples
+/// * `output` - Path the spectrum is written to, in the same layout
+/// * `config` - Working-set bound and transform direction
+///
+/// # Returns
+///
+/// * The factorization `(n1, n2)` of the signal length used for the transform
+///
+/// # Errors
+///
+/// Returns an error if the files cannot be opened or mapped, if the input is
+/// empty or not a whole number of samples, or if the working set cannot hold
+/// a single row or column of the factorization.
+///
+/// # Examples
+///
+/// ```
+/// use num_complex::Complex64;
+/// use scirs2_fft::out_of_core::{
+///     read_complex_samples, segmented_fft, write_complex_samples, SegmentedFftConfig,
+/// };
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let input = dir.path().join("signal.bin");
+/// let output = dir.path().join("spectrum.bin");
+///
+/// let signal: Vec<Complex64> = (0..1024).map(|i| Complex64::new(i as f64, 0.0)).collect();
+/// write_complex_samples(&input, &signal).unwrap();
+///
+/// // Hold at most 64 samples (1 KiB) in memory at a time
+/// let config = SegmentedFftConfig { working_set_bytes: 1024, ..Default::default() };
+/// let (n1, n2) = segmented_fft(&input, &output, &config).unwrap();
+/// assert_eq!(n1 * n2, 1024);
+///
+/// let spectrum = read_complex_samples(&output).unwrap();
+/// let expected = scirs2_fft::fft(&signal, None).unwrap();
+/// for (a, b) in spectrum.iter().zip(&expected) {
+///     assert!((a - b).norm() < 1e-6);
+/// }
+/// ```
+pub fn segmented_fft<P, Q>(
+    input: P,
+    output: Q,
+    config: &SegmentedFftConfig,
+) -> FFTResult<(usize, usize)>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let input_file = File::open(input.as_ref())
+        .map_err(|e| FFTError::IOError(format!("Failed to open input signal: {e}")))?;
+    // SAFETY: the mapping is read-only and the file is not modified while mapped
+    let source = unsafe { Mmap::map(&input_file) }
+        .map_err(|e| FFTError::IOError(format!("Failed to map input signal: {e}")))?;
+    if source.is_empty() || !source.len().is_multiple_of(SAMPLE_BYTES) {
+        return Err(FFTError::ValueError(format!(
+            "Input file of {} bytes is not a non-empty sequence of {}-byte complex samples",
+            source.len(),
+            SAMPLE_BYTES
+        )));
+    }
+    let n = source.len() / SAMPLE_BYTES;
+
+    let (n1, n2) = split_length(n);
+    let max_samples = config.working_set_bytes / SAMPLE_BYTES;
+    if max_samples < n1.max(n2) {
+        return Err(FFTError::MemoryError(format!(
+            "Working set of {} bytes cannot hold a panel for length {} = {} x {} \
+             (needs at least {} bytes)",
+            config.working_set_bytes,
+            n,
+            n1,
+            n2,
+            n1.max(n2) * SAMPLE_BYTES
+        )));
+    }
+
+    let scratch_file = tempfile::tempfile()
+        .map_err(|e| FFTError::IOError(format!("Failed to create scratch file: {e}")))?;
+    let mut scratch = map_writable(&scratch_file, source.len())?;
+
+    let output_file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(output.as_ref())
+        .map_err(|e| FFTError::IOError(format!("Failed to create output file: {e}")))?;
+    let mut target = map_writable(&output_file, source.len())?;
+
+    let mut planner = FftPlanner::new();
+    let (column_fft, row_fft) = if config.inverse {
+        (planner.plan_fft_inverse(n1), planner.plan_fft_inverse(n2))
+    } else {
+        (planner.plan_fft_forward(n1), planner.plan_fft_forward(n2))
+    };
+    let sign = if config.inverse { 1.0 } else { -1.0 };
+
+    // Column pass: gather panels of whole columns, transform, twiddle and
+    // scatter them back to the scratch matrix
+    let panel_columns = (max_samples / n1).min(n2);
+    let mut panel = vec![Complex64::new(0.0, 0.0); panel_columns * n1];
+    let mut run = vec![Complex64::new(0.0, 0.0); panel_columns];
+    for c0 in (0..n2).step_by(panel_columns) {
+        let width = panel_columns.min(n2 - c0);
+        let panel = &mut panel[..width * n1];
+        let run = &mut run[..width];
+        for row in 0..n1 {
+            read_run(&source, row * n2 + c0, run);
+            for (c, &value) in run.iter().enumerate() {
+                panel[c * n1 + row] = value;
+            }
+        }
+
+        column_fft.process(panel);
+        for (c, column) in panel.chunks_exact_mut(n1).enumerate() {
+            let col = c0 + c;
+            for (k1, value) in column.iter_mut().enumerate() {
+                let angle = sign * 2.0 * PI * (k1 * col) as f64 / n as f64;
+                *value *= Complex64::from_polar(1.0, angle);
+            }
+        }
+
+        for row in 0..n1 {
+            for (c, value) in run.iter_mut().enumerate() {
+                *value = panel[c * n1 + row];
+            }
+            write_run(&mut scratch, row * n2 + c0, run);
+        }
+    }
+
+    // Row pass: rows of the scratch matrix are contiguous; the transformed
+    // rows are written out transposed, one contiguous run per output row
+    let panel_rows = (max_samples / n2).min(n1);
+    let mut panel = vec![Complex64::new(0.0, 0.0); panel_rows * n2];
+    let mut run = vec![Complex64::new(0.0, 0.0); panel_rows];
+    let scale = if config.inverse { 1.0 / n as f64 } else { 1.0 };
+    for r0 in (0..n1).step_by(panel_rows) {
+        let height = panel_rows.min(n1 - r0);
+        let panel = &mut panel[..height * n2];
+        let run = &mut run[..height];
+        read_run(&scratch, r0 * n2, panel);
+
+        row_fft.process(panel);
+        for k2 in 0..n2 {
+            for (r, value) in run.iter_mut().enumerate() {
+                *value = panel[r * n2 + k2] * scale;
+            }
+            write_run(&mut target, k2 * n1 + r0, run);
+        }
+    }
+
+    target
+        .flush()
+        .map_err(|e| FFTError::IOError(format!("Failed to flush output file: {e}")))?;
+    Ok((n1, n2))
+}
+
+/// Writes complex samples to a file in the layout read by [`segmented_fft`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+pub fn write_complex_samples<P: AsRef<Path>>(path: P, samples: &[Complex64]) -> FFTResult<()> {
+    let mut bytes = Vec::with_capacity(samples.len() * SAMPLE_BYTES);
+    for value in samples {
+        bytes.extend_from_slice(&value.re.to_le_bytes());
+        bytes.extend_from_slice(&value.im.to_le_bytes());
+    }
+    File::create(path.as_ref())
+        .and_then(|mut file| file.write_all(&bytes))
+        .map_err(|e| FFTError::IOError(format!("Failed to write samples: {e}")))
+}
+
+/// Reads complex samples from a file in the layout written by [`segmented_fft`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a whole number of
+/// samples.
+pub fn read_complex_samples<P: AsRef<Path>>(path: P) -> FFTResult<Vec<Complex64>> {
+    let mut bytes = Vec::new();
+    File::open(path.as_ref())
+        .and_then(|mut file| file.read_to_end(&mut bytes))
+        .map_err(|e| FFTError::IOError(format!("Failed to read samples: {e}")))?;
+    if !bytes.len().is_multiple_of(SAMPLE_BYTES) {
+        return Err(FFTError::ValueError(format!(
+            "File of {} bytes is not a whole number of {}-byte complex samples",
+            bytes.len(),
+            SAMPLE_BYTES
+        )));
+    }
+    let mut samples = vec![Complex64::new(0.0, 0.0); bytes.len() / SAMPLE_BYTES];
+    read_run(&bytes, 0, &mut samples);
+    Ok(samples)
+}
+
+/// Splits `n` into `n1 * n2` with `n1` the largest divisor not above `sqrt(n)`
+fn split_length(n: usize) -> (usize, usize) {
+    let mut n1 = (n as f64).sqrt() as usize;
+    while n1 > 1 && !n.is_multiple_of(n1) {
+        n1 -= 1;
+    }
+    let n1 = n1.max(1);
+    (n1, n / n1)
+}
+
+/// Resizes `file` to `len` bytes and maps it for writing
+fn map_writable(file: &File, len: usize) -> FFTResult<MmapMut> {
+    file.set_len(len as u64)
+        .map_err(|e| FFTError::IOError(format!("Failed to resize file: {e}")))?;
+    // SAFETY: the file is owned by this module for the duration of the transform
+    unsafe { MmapMut::map_mut(file) }
+        .map_err(|e| FFTError::IOError(format!("Failed to map file: {e}")))
+}
+
+/// Decodes `out.len()` samples starting at sample index `start`
+fn read_run(bytes: &[u8], start: usize, out: &mut [Complex64]) {
+    let bytes = &bytes[start * SAMPLE_BYTES..(start + out.len()) * SAMPLE_BYTES];
+    for (value, chunk) in out.iter_mut().zip(bytes.chunks_exact(SAMPLE_BYTES)) {
+        let (re, im) = chunk.split_at(SAMPLE_BYTES / 2);
+        *value = Complex64::new(
+            f64::from_le_bytes(re.try_into().expect("8-byte chunk")),
+            f64::from_le_bytes(im.try_into().expect("8-byte chunk")),
+        );
+    }
+}
+
+/// Encodes `values` starting at sample index `start`
+fn write_run(bytes: &mut [u8], start: usize, values: &[Complex64]) {
+    let bytes = &mut bytes[start * SAMPLE_BYTES..(start + values.len()) * SAMPLE_BYTES];
+    for (value, chunk) in values.iter().zip(bytes.chunks_exact_mut(SAMPLE_BYTES)) {
+        let (re, im) = chunk.split_at_mut(SAMPLE_BYTES / 2);
+        re.copy_from_slice(&value.re.to_le_bytes());
+        im.copy_from_slice(&value.im.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::{fft, ifft};
+
+    fn test_signal(n: usize) -> Vec<Complex64> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64;
+                Complex64::new((0.37 * t).sin() + 0.01 * t, (0.11 * t).cos())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_segmented_fft_matches_in_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.bin");
+        let output = dir.path().join("output.bin");
+
+        // Powers of two, composite, prime and length-one signals with
+        // working sets ranging from a single column to the whole signal
+        for (n, working_samples) in [(1024, 32), (1000, 50), (360, 1000), (97, 97), (1, 1)] {
+            let signal = test_signal(n);
+            write_complex_samples(&input, &signal).unwrap();
+            let config = SegmentedFftConfig {
+                working_set_bytes: working_samples * SAMPLE_BYTES,
+                inverse: false,
+            };
+            let (n1, n2) = segmented_fft(&input, &output, &config).unwrap();
+            assert_eq!(n1 * n2, n);
+
+            let spectrum = read_complex_samples(&output).unwrap();
+            let expected = fft(&signal, Some(n)).unwrap();
+            assert_eq!(spectrum.len(), n);
+            for (a, b) in spectrum.iter().zip(&expected) {
+                assert!((a - b).norm() < 1e-8 * n as f64, "n = {n}: {a} vs {b}");
+            }
+
+            // The inverse transform restores the signal
+            let config = SegmentedFftConfig {
+                inverse: true,
+                ..config
+            };
+            segmented_fft(&output, &input, &config).unwrap();
+            let restored = read_complex_samples(&input).unwrap();
+            let expected = ifft(&expected, Some(n)).unwrap();
+            for ((a, b), x) in restored.iter().zip(&expected).zip(&signal) {
+                assert!((a - b).norm() < 1e-10);
+                assert!((a - x).norm() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_segmented_fft_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.bin");
+        let output = dir.path().join("output.bin");
+
+        // Working set smaller than one row of the factorization
+        write_complex_samples(&input, &test_signal(1024)).unwrap();
+        let config = SegmentedFftConfig {
+            working_set_bytes: 16 * SAMPLE_BYTES,
+            inverse: false,
+        };
+        assert!(matches!(
+            segmented_fft(&input, &output, &config),
+            Err(FFTError::MemoryError(_))
+        ));
+
+        // Partial samples and empty files
+        std::fs::write(&input, [0u8; 20]).unwrap();
+        let config = SegmentedFftConfig::default();
+        assert!(segmented_fft(&input, &output, &config).is_err());
+        std::fs::write(&input, []).unwrap();
+        assert!(segmented_fft(&input, &output, &config).is_err());
+
+        assert!(segmented_fft(dir.path().join("missing.bin"), &output, &config).is_err());
+    }
+}
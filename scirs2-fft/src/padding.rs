@@ -4,7 +4,8 @@
 //! to optimal sizes for FFT computation, improving performance by
 //! ensuring the FFT size has small prime factors.
 
-use crate::{next_fast_len, FFTResult};
+use crate::fft::{fft, ifft};
+use crate::{next_fast_len, FFTError, FFTResult};
 use ndarray::{s, Array1, ArrayBase, ArrayD, Data, Dimension};
 use num_complex::Complex;
 use num_traits::Zero;
@@ -91,6 +92,34 @@ impl AutoPadConfig {
     }
 }
 
+/// Length an axis of size `n` is padded to under `config`
+///
+/// This is the next power of two or the next fast FFT length (see
+/// [`next_fast_len`]) of `n + min_pad`, capped at `n + max_pad`.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::padding::{padded_length, AutoPadConfig, PaddingMode};
+///
+/// let config = AutoPadConfig::new(PaddingMode::Zero);
+/// assert_eq!(padded_length(1021, &config), 1024);
+/// assert_eq!(padded_length(1021, &config.with_max_pad(2)), 1023);
+/// ```
+pub fn padded_length(n: usize, config: &AutoPadConfig) -> usize {
+    let min_size = n + config.min_pad;
+    let target_size = if config.power_of_2 {
+        min_size.next_power_of_two()
+    } else {
+        next_fast_len(min_size, false)
+    };
+
+    match config.max_pad {
+        Some(max_pad) => target_size.min(n + max_pad),
+        None => target_size,
+    }
+}
+
 /// Automatically pad a 1D array for optimal FFT performance
 pub fn auto_pad_1d<T>(x: &Array1<T>, config: &AutoPadConfig) -> FFTResult<Array1<T>>
 where
@@ -98,26 +127,7 @@ where
 {
     let n = x.len();
 
-    // Determine target size
-    let target_size = if config.power_of_2 {
-        // Next power of 2
-        let min_size = n + config.min_pad;
-        let mut size = 1;
-        while size < min_size {
-            size *= 2;
-        }
-        size
-    } else {
-        // Next fast length
-        next_fast_len(n + config.min_pad, false)
-    };
-
-    // Apply maximum padding constraint
-    let padded_size = if let Some(max_pad) = config.max_pad {
-        target_size.min(n + max_pad)
-    } else {
-        target_size
-    };
+    let padded_size = padded_length(n, config);
 
     // No padding needed
     if padded_size == n {
@@ -240,24 +250,7 @@ pub fn auto_pad_complex(
 ) -> FFTResult<Array1<Complex<f64>>> {
     let n = x.len();
 
-    // Determine target size
-    let target_size = if config.power_of_2 {
-        let min_size = n + config.min_pad;
-        let mut size = 1;
-        while size < min_size {
-            size *= 2;
-        }
-        size
-    } else {
-        next_fast_len(n + config.min_pad, false)
-    };
-
-    // Apply maximum padding constraint
-    let padded_size = if let Some(max_pad) = config.max_pad {
-        target_size.min(n + max_pad)
-    } else {
-        target_size
-    };
+    let padded_size = padded_length(n, config);
 
     if padded_size == n {
         return Ok(x.clone());
@@ -347,6 +340,77 @@ where
         .to_owned()
 }
 
+/// FFT of a complex array padded to a fast length
+///
+/// The input is padded according to `config` and transformed at the padded
+/// length, so the spectrum has `padded_length(x.len(), config)` bins sampled
+/// more finely than the unpadded DFT. Pass it to [`ifft_padded`] to get back
+/// a signal of the original length.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array1;
+/// use num_complex::Complex64;
+/// use scirs2_fft::padding::{fft_padded, ifft_padded, AutoPadConfig, PaddingMode};
+///
+/// // 1021 is prime, so the transform runs at 1024 instead
+/// let x = Array1::from_shape_fn(1021, |i| Complex64::new((i as f64 * 0.1).sin(), 0.0));
+/// let config = AutoPadConfig::new(PaddingMode::Zero);
+///
+/// let spectrum = fft_padded(&x, &config).unwrap();
+/// assert_eq!(spectrum.len(), 1024);
+///
+/// let recovered = ifft_padded(&spectrum, x.len(), &config).unwrap();
+/// assert_eq!(recovered.len(), 1021);
+/// assert!((recovered[500] - x[500]).norm() < 1e-10);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is empty or the transform fails.
+pub fn fft_padded(
+    x: &Array1<Complex<f64>>,
+    config: &AutoPadConfig,
+) -> FFTResult<Array1<Complex<f64>>> {
+    if x.is_empty() {
+        return Err(FFTError::ValueError("Input cannot be empty".to_string()));
+    }
+
+    let padded = auto_pad_complex(x, config)?;
+    let n = padded.len();
+    let spectrum = fft(&padded.to_vec(), Some(n))?;
+    Ok(Array1::from_vec(spectrum))
+}
+
+/// Inverse of [`fft_padded`]
+///
+/// Transforms a padded spectrum back at its own length and removes the
+/// padding, returning the `original_size` samples of the unpadded signal.
+///
+/// # Errors
+///
+/// Returns an error if the spectrum length does not match the padded length
+/// `config` gives for `original_size`, or if the transform fails.
+pub fn ifft_padded(
+    spectrum: &Array1<Complex<f64>>,
+    original_size: usize,
+    config: &AutoPadConfig,
+) -> FFTResult<Array1<Complex<f64>>> {
+    let n = padded_length(original_size, config);
+    if original_size == 0 || spectrum.len() != n {
+        return Err(FFTError::DimensionError(format!(
+            "Spectrum length {} does not match the padded length {} of a signal of length {}",
+            spectrum.len(),
+            n,
+            original_size
+        )));
+    }
+
+    let signal = Array1::from_vec(ifft(&spectrum.to_vec(), Some(n))?);
+    Ok(remove_padding_1d(&signal, original_size, config))
+}
+
 /// Automatic padding for N-dimensional arrays
 pub fn auto_pad_nd<S, D>(
     x: &ArrayBase<S, D>,
@@ -365,23 +429,7 @@ where
 
     // Calculate padded sizes for specified axes
     for &axis in axes {
-        let n = shape[axis];
-        let target_size = if config.power_of_2 {
-            let min_size = n + config.min_pad;
-            let mut size = 1;
-            while size < min_size {
-                size *= 2;
-            }
-            size
-        } else {
-            next_fast_len(n + config.min_pad, false)
-        };
-
-        padded_shape[axis] = if let Some(max_pad) = config.max_pad {
-            target_size.min(n + max_pad)
-        } else {
-            target_size
-        };
+        padded_shape[axis] = padded_length(shape[axis], config);
     }
 
     // Create padded array
@@ -422,7 +470,7 @@ where
         _ => {
             // For higher dimensions, just copy to origin
             // A full implementation would handle arbitrary dimensions
-            return Err(crate::FFTError::ValueError(
+            return Err(FFTError::ValueError(
                 "auto_pad_nd currently only supports 1D and 2D arrays".to_string(),
             ));
         }
@@ -488,6 +536,36 @@ mod tests {
         assert_eq!(unpadded.as_slice().unwrap(), &[0.0, 1.0, 2.0, 3.0]);
     }
 
+    #[test]
+    fn test_fft_padded_round_trip() {
+        let x = Array1::from_shape_fn(97, |i| Complex::new((i as f64).sin(), (i as f64).cos()));
+
+        for config in [
+            AutoPadConfig::new(PaddingMode::Zero),
+            AutoPadConfig::new(PaddingMode::Edge)
+                .with_center()
+                .with_min_pad(10),
+            AutoPadConfig::new(PaddingMode::Zero).with_power_of_2(),
+        ] {
+            let spectrum = fft_padded(&x, &config).unwrap();
+            assert_eq!(spectrum.len(), padded_length(x.len(), &config));
+
+            let recovered = ifft_padded(&spectrum, x.len(), &config).unwrap();
+            for (a, b) in recovered.iter().zip(x.iter()) {
+                assert_abs_diff_eq!(a.re, b.re, epsilon = 1e-10);
+                assert_abs_diff_eq!(a.im, b.im, epsilon = 1e-10);
+            }
+            assert!(ifft_padded(&spectrum, x.len() / 2, &config).is_err());
+        }
+
+        // Zero padding samples the same spectrum on a finer grid: bin 0 is the sum
+        let config = AutoPadConfig::new(PaddingMode::Zero);
+        let spectrum = fft_padded(&x, &config).unwrap();
+        let sum: Complex<f64> = x.iter().sum();
+        assert_abs_diff_eq!(spectrum[0].re, sum.re, epsilon = 1e-10);
+        assert_abs_diff_eq!(spectrum[0].im, sum.im, epsilon = 1e-10);
+    }
+
     #[test]
     fn test_auto_pad_center() {
         let x = Array1::from_vec(vec![1.0, 2.0, 3.0]);
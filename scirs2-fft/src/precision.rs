@@ -0,0 +1,378 @@
+//! Precision-generic FFT transforms
+//!
+//! The top-level transforms such as [`crate::fft`] accept any numeric input
+//! but always compute and return `f64`/`Complex64`. The functions in this
+//! module instead work natively in the precision of their input, so `f32`
+//! data is transformed as `f32` and `Complex32` end to end. This halves the
+//! memory traffic of large transforms, which matters for embedded and
+//! real-time workloads where the accuracy of `f32` is sufficient.
+//!
+//! Both precisions run on the same kernels as the `f64` API, including the
+//! multithreaded paths configured through [`crate::worker_pool`].
+//!
+//! # Examples
+//!
+//! ```
+//! use num_complex::Complex32;
+//! use scirs2_fft::precision;
+//!
+//! let signal: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin()).collect();
+//!
+//! // Real-input transform in single precision
+//! let spectrum: Vec<Complex32> = precision::rfft(&signal, None).unwrap();
+//! assert_eq!(spectrum.len(), 33);
+//!
+//! // And back again
+//! let recovered: Vec<f32> = precision::irfft(&spectrum, Some(64)).unwrap();
+//! for (a, b) in signal.iter().zip(recovered.iter()) {
+//!     assert!((a - b).abs() < 1e-5);
+//! }
+//! ```
+
+use crate::error::{FFTError, FFTResult};
+use crate::fft::{fft_along_axis, fft_in_place};
+use ndarray::{Array, Array2, ArrayView, Dimension};
+use num_complex::Complex;
+use num_traits::{Float, NumAssign};
+use rustfft::FftNum;
+
+/// Floating-point types the FFT kernels can operate on natively
+///
+/// Implemented for `f32` and `f64`.
+pub trait FftFloat: FftNum + Float + NumAssign {}
+
+impl FftFloat for f32 {}
+impl FftFloat for f64 {}
+
+/// Compute the 1-D FFT in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Complex input signal
+/// * `n` - Transform length; the input is zero-padded or truncated to it
+///   (defaults to the input length)
+///
+/// # Returns
+///
+/// The unnormalized spectrum of length `n`.
+///
+/// # Examples
+///
+/// ```
+/// use num_complex::Complex32;
+/// use scirs2_fft::precision;
+///
+/// let signal = vec![Complex32::new(1.0, 0.0); 8];
+/// let spectrum = precision::fft(&signal, None).unwrap();
+/// assert!((spectrum[0].re - 8.0).abs() < 1e-6);
+/// ```
+pub fn fft<F: FftFloat>(input: &[Complex<F>], n: Option<usize>) -> FFTResult<Vec<Complex<F>>> {
+    let mut data = resized(input, n)?;
+    fft_in_place(&mut data, false);
+    Ok(data)
+}
+
+/// Compute the 1-D inverse FFT in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Complex spectrum
+/// * `n` - Transform length; the input is zero-padded or truncated to it
+///   (defaults to the input length)
+///
+/// # Returns
+///
+/// The signal of length `n`, normalized by `1/n`.
+pub fn ifft<F: FftFloat>(input: &[Complex<F>], n: Option<usize>) -> FFTResult<Vec<Complex<F>>> {
+    let mut data = resized(input, n)?;
+    fft_in_place(&mut data, true);
+    let factor = inverse_length::<F>(data.len());
+    data.iter_mut().for_each(|v| *v *= factor);
+    Ok(data)
+}
+
+/// Compute the 1-D FFT of a real signal in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Real input signal
+/// * `n` - Transform length (defaults to the input length)
+///
+/// # Returns
+///
+/// The non-negative frequency half of the spectrum, `n / 2 + 1` values.
+pub fn rfft<F: FftFloat>(input: &[F], n: Option<usize>) -> FFTResult<Vec<Complex<F>>> {
+    let complex: Vec<Complex<F>> = input.iter().map(|&x| Complex::new(x, F::zero())).collect();
+    let mut spectrum = fft(&complex, n)?;
+    spectrum.truncate(spectrum.len() / 2 + 1);
+    Ok(spectrum)
+}
+
+/// Compute the inverse of [`rfft`] in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Non-negative frequency half of a Hermitian spectrum
+/// * `n` - Length of the output signal (defaults to `2 * (input.len() - 1)`)
+///
+/// # Returns
+///
+/// The real signal of length `n`, normalized by `1/n`.
+pub fn irfft<F: FftFloat>(input: &[Complex<F>], n: Option<usize>) -> FFTResult<Vec<F>> {
+    if input.is_empty() {
+        return Err(FFTError::ValueError("Input cannot be empty".to_string()));
+    }
+    let n = n.unwrap_or(2 * (input.len() - 1)).max(1);
+
+    // Rebuild the negative frequencies from Hermitian symmetry
+    let zero = Complex::new(F::zero(), F::zero());
+    let mut full = vec![zero; n];
+    for (k, value) in full.iter_mut().enumerate().take(n / 2 + 1) {
+        *value = input.get(k).copied().unwrap_or(zero);
+    }
+    for k in n / 2 + 1..n {
+        full[k] = full[n - k].conj();
+    }
+    // DC and Nyquist of a real signal are real
+    full[0].im = F::zero();
+    if n.is_multiple_of(2) {
+        full[n / 2].im = F::zero();
+    }
+
+    Ok(ifft(&full, None)?.into_iter().map(|c| c.re).collect())
+}
+
+/// Compute the 2-D FFT in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Complex 2-D input
+///
+/// # Returns
+///
+/// The unnormalized 2-D spectrum with the shape of the input.
+pub fn fft2<F: FftFloat>(input: &Array2<Complex<F>>) -> FFTResult<Array2<Complex<F>>> {
+    fftn(&input.view(), None)
+}
+
+/// Compute the 2-D inverse FFT in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Complex 2-D spectrum
+///
+/// # Returns
+///
+/// The 2-D signal with the shape of the input, normalized by `1/(rows * cols)`.
+pub fn ifft2<F: FftFloat>(input: &Array2<Complex<F>>) -> FFTResult<Array2<Complex<F>>> {
+    ifftn(&input.view(), None)
+}
+
+/// Compute the N-D FFT in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Complex N-D input
+/// * `axes` - Axes to transform (defaults to all axes)
+///
+/// # Returns
+///
+/// The unnormalized spectrum with the shape of the input.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array3;
+/// use num_complex::Complex32;
+/// use scirs2_fft::precision;
+///
+/// let volume = Array3::from_elem((4, 4, 4), Complex32::new(1.0, 0.0));
+/// let spectrum = precision::fftn(&volume.view(), None).unwrap();
+/// assert!((spectrum[[0, 0, 0]].re - 64.0).abs() < 1e-4);
+/// assert!(spectrum[[1, 2, 3]].norm() < 1e-4);
+/// ```
+pub fn fftn<F: FftFloat, D: Dimension>(
+    input: &ArrayView<Complex<F>, D>,
+    axes: Option<&[usize]>,
+) -> FFTResult<Array<Complex<F>, D>> {
+    transform_axes(input, axes, false)
+}
+
+/// Compute the N-D inverse FFT in the precision of the input
+///
+/// # Arguments
+///
+/// * `input` - Complex N-D spectrum
+/// * `axes` - Axes to transform (defaults to all axes)
+///
+/// # Returns
+///
+/// The signal with the shape of the input, normalized by the product of the
+/// transformed axis lengths.
+pub fn ifftn<F: FftFloat, D: Dimension>(
+    input: &ArrayView<Complex<F>, D>,
+    axes: Option<&[usize]>,
+) -> FFTResult<Array<Complex<F>, D>> {
+    transform_axes(input, axes, true)
+}
+
+fn transform_axes<F: FftFloat, D: Dimension>(
+    input: &ArrayView<Complex<F>, D>,
+    axes: Option<&[usize]>,
+    inverse: bool,
+) -> FFTResult<Array<Complex<F>, D>> {
+    if input.is_empty() {
+        return Err(FFTError::ValueError("Input cannot be empty".to_string()));
+    }
+    let all_axes: Vec<usize> = (0..input.ndim()).collect();
+    let axes = axes.unwrap_or(&all_axes);
+    if let Some(&axis) = axes.iter().find(|&&axis| axis >= input.ndim()) {
+        return Err(FFTError::ValueError(format!(
+            "Axis {} is out of bounds for array with {} dimensions",
+            axis,
+            input.ndim()
+        )));
+    }
+
+    let mut result = input.to_owned();
+    for &axis in axes {
+        fft_along_axis(&mut result, axis, inverse);
+    }
+    if inverse {
+        let n: usize = axes.iter().map(|&axis| input.shape()[axis]).product();
+        let factor = inverse_length::<F>(n);
+        result.mapv_inplace(|v| v * factor);
+    }
+    Ok(result)
+}
+
+/// Copy `input`, zero-padded or truncated to `n` samples
+fn resized<F: FftFloat>(input: &[Complex<F>], n: Option<usize>) -> FFTResult<Vec<Complex<F>>> {
+    if input.is_empty() {
+        return Err(FFTError::ValueError("Input cannot be empty".to_string()));
+    }
+    let n = n.unwrap_or(input.len());
+    if n == 0 {
+        return Err(FFTError::ValueError(
+            "Transform length must be positive".to_string(),
+        ));
+    }
+    let mut data = input[..input.len().min(n)].to_vec();
+    data.resize(n, Complex::new(F::zero(), F::zero()));
+    Ok(data)
+}
+
+/// `1/n` in the working precision
+fn inverse_length<F: FftFloat>(n: usize) -> F {
+    F::from_f64(1.0 / n as f64).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worker_pool::with_fft_workers;
+    use ndarray::{Array3, IxDyn};
+    use num_complex::{Complex32, Complex64};
+
+    fn signal(n: usize) -> Vec<Complex64> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64;
+                Complex64::new((0.031 * t).sin() + 0.5 * (t * 0.2).cos(), (0.011 * t).cos())
+            })
+            .collect()
+    }
+
+    fn to_f32(x: &[Complex64]) -> Vec<Complex32> {
+        x.iter()
+            .map(|c| Complex32::new(c.re as f32, c.im as f32))
+            .collect()
+    }
+
+    /// Largest deviation of the `f32` result relative to the `f64` peak
+    fn relative_error<'a>(
+        single: impl Iterator<Item = &'a Complex32>,
+        double: impl Iterator<Item = &'a Complex64> + Clone,
+    ) -> f64 {
+        let peak = double.clone().map(|c| c.norm()).fold(0.0, f64::max);
+        single
+            .zip(double)
+            .map(|(a, b)| (Complex64::new(a.re as f64, a.im as f64) - b).norm())
+            .fold(0.0, f64::max)
+            / peak
+    }
+
+    #[test]
+    fn test_f32_matches_f64_reference() {
+        // Short, odd, Bluestein-sized and four-step lengths
+        for n in [16, 1000, 4099, 1 << 16] {
+            let x = signal(n);
+            let reference = crate::fft::fft(&x, Some(n)).unwrap();
+            let double = fft(&x, None).unwrap();
+            assert!(relative_error(to_f32(&double).iter(), reference.iter()) < 1e-7);
+
+            let single = with_fft_workers(4, || fft(&to_f32(&x), None).unwrap());
+            assert!(
+                relative_error(single.iter(), reference.iter()) < 1e-5,
+                "n = {n}"
+            );
+
+            let back = ifft(&single, None).unwrap();
+            assert!(relative_error(back.iter(), x.iter()) < 1e-5, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_real_transforms_f32() {
+        for n in [63, 64] {
+            let x: Vec<f32> = (0..n).map(|i| (i as f32 * 0.37).sin() + 0.25).collect();
+            let spectrum = rfft(&x, None).unwrap();
+            assert_eq!(spectrum.len(), n / 2 + 1);
+
+            let x64: Vec<f64> = x.iter().map(|&v| v as f64).collect();
+            let reference = crate::rfft::rfft(&x64, None).unwrap();
+            assert!(relative_error(spectrum.iter(), reference.iter()) < 1e-5);
+
+            let recovered = irfft(&spectrum, Some(n)).unwrap();
+            for (a, b) in x.iter().zip(recovered.iter()) {
+                assert!((a - b).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fftn_f32() {
+        let data = signal(6 * 10 * 12);
+        let volume = Array3::from_shape_vec((6, 10, 12), to_f32(&data)).unwrap();
+        let reference = crate::fft::fftn(
+            &ndarray::ArrayD::from_shape_vec(IxDyn(&[6, 10, 12]), data).unwrap(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let spectrum = fftn(&volume.view(), None).unwrap();
+        assert!(relative_error(spectrum.iter(), reference.iter()) < 1e-5);
+
+        let back = ifftn(&spectrum.view(), None).unwrap();
+        for (a, b) in back.iter().zip(volume.iter()) {
+            assert!((a - b).norm() < 1e-5);
+        }
+
+        // 2-D convenience wrappers and partial axes
+        let plane = volume.index_axis(ndarray::Axis(0), 0).to_owned();
+        let planar = ifft2(&fft2(&plane).unwrap()).unwrap();
+        for (a, b) in planar.iter().zip(plane.iter()) {
+            assert!((a - b).norm() < 1e-5);
+        }
+        let rows = fftn(&volume.view(), Some(&[2])).unwrap();
+        let first = fft(&volume.slice(ndarray::s![0, 0, ..]).to_vec(), None).unwrap();
+        for (a, b) in rows.slice(ndarray::s![0, 0, ..]).iter().zip(first.iter()) {
+            assert!((a - b).norm() < 1e-5);
+        }
+        assert!(fftn(&volume.view(), Some(&[3])).is_err());
+    }
+}
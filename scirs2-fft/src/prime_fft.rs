@@ -0,0 +1,565 @@
+//! Fast transforms for prime and other awkward lengths
+//!
+//! Mixed-radix FFTs are fast when the length factors into small primes. For
+//! lengths with a large prime factor this module provides two dedicated
+//! `O(n log n)` algorithms that re-express the DFT as a cyclic convolution
+//! computed with smooth-length FFTs:
+//!
+//! * **Rader** - for a prime `p` with `p - 1` smooth, the input is permuted
+//!   by powers of a primitive root so the DFT becomes a length `p - 1`
+//!   convolution.
+//! * **Bluestein** - for any length, the chirp identity
+//!   `jk = (j^2 + k^2 - (k - j)^2) / 2` turns the DFT into a convolution
+//!   that is zero-padded to a fast length of at least `2n - 1`.
+//!
+//! [`PrimeFft`] selects an algorithm for a given length and precomputes the
+//! convolution kernel, and [`FftDiagnostics`] reports which algorithm was
+//! chosen.
+//!
+//! # Examples
+//!
+//! ```
+//! use num_complex::Complex64;
+//! use scirs2_fft::{fft_with_diagnostics, FftAlgorithm, FftDiagnostics};
+//!
+//! // 1009 is prime and 1008 = 2^4 * 3^2 * 7 is smooth
+//! let signal: Vec<f64> = (0..1009).map(|i| (i as f64 * 0.05).sin()).collect();
+//!
+//! let mut diagnostics = FftDiagnostics::default();
+//! let spectrum = fft_with_diagnostics(&signal, None, Some(&mut diagnostics)).unwrap();
+//!
+//! assert_eq!(spectrum.len(), 1009);
+//! assert_eq!(diagnostics.algorithm, FftAlgorithm::Rader);
+//! assert_eq!(diagnostics.inner_size, Some(1008));
+//! ```
+
+use crate::error::{FFTError, FFTResult};
+use crate::helper::next_fast_len;
+use num_complex::Complex64;
+use num_traits::NumCast;
+use rustfft::{Fft, FftPlanner};
+use std::f64::consts::PI;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Largest prime factor handled directly by the mixed-radix planner
+const SMOOTH_PRIME_LIMIT: usize = 31;
+
+/// Algorithm used for a transform of a given length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FftAlgorithm {
+    /// Mixed-radix FFT for lengths whose prime factors are all small
+    #[default]
+    MixedRadix,
+    /// Rader's algorithm for primes `p` with smooth `p - 1`
+    Rader,
+    /// Bluestein's chirp-z algorithm for any length
+    Bluestein,
+}
+
+/// Details of how a transform was computed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FftDiagnostics {
+    /// Transform length
+    pub size: usize,
+    /// Algorithm that was selected
+    pub algorithm: FftAlgorithm,
+    /// Largest prime factor of the length
+    pub largest_prime_factor: usize,
+    /// Length of the inner convolution FFT for Rader and Bluestein
+    pub inner_size: Option<usize>,
+}
+
+/// Choose the algorithm for a transform of length `n`
+///
+/// Lengths whose prime factors are all small use the mixed-radix planner.
+/// Otherwise primes with a smooth `p - 1` use Rader's algorithm and every
+/// other length uses Bluestein's.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::{select_fft_algorithm, FftAlgorithm};
+///
+/// assert_eq!(select_fft_algorithm(1024), FftAlgorithm::MixedRadix);
+/// assert_eq!(select_fft_algorithm(1009), FftAlgorithm::Rader);
+/// // 10007 is prime but 10006 = 2 * 5003 is not smooth
+/// assert_eq!(select_fft_algorithm(10007), FftAlgorithm::Bluestein);
+/// ```
+pub fn select_fft_algorithm(n: usize) -> FftAlgorithm {
+    let largest = largest_prime_factor(n);
+    if largest <= SMOOTH_PRIME_LIMIT {
+        FftAlgorithm::MixedRadix
+    } else if largest == n && largest_prime_factor(n - 1) <= SMOOTH_PRIME_LIMIT {
+        FftAlgorithm::Rader
+    } else {
+        FftAlgorithm::Bluestein
+    }
+}
+
+/// Planned FFT of a fixed length using the algorithm suited to that length
+///
+/// The plan stores the FFTs and the transformed convolution kernel, so
+/// repeated transforms of the same length only pay for the convolution.
+#[derive(Clone)]
+pub struct PrimeFft {
+    n: usize,
+    inverse: bool,
+    kernel: Kernel,
+    diagnostics: FftDiagnostics,
+}
+
+#[derive(Clone)]
+enum Kernel {
+    MixedRadix(Arc<dyn Fft<f64>>),
+    Rader {
+        /// `g^q mod p` for `q = 0..p-1`
+        powers: Vec<usize>,
+        /// `g^-q mod p` for `q = 0..p-1`
+        inverse_powers: Vec<usize>,
+        /// Scaled spectrum of the twiddle sequence `w^(g^-q)`
+        spectrum: Vec<Complex64>,
+        forward: Arc<dyn Fft<f64>>,
+        backward: Arc<dyn Fft<f64>>,
+    },
+    Bluestein {
+        /// `exp(sign i pi k^2 / n)` for `k = 0..n`
+        chirp: Vec<Complex64>,
+        /// Scaled spectrum of the conjugate chirp, wrapped to the padded length
+        spectrum: Vec<Complex64>,
+        forward: Arc<dyn Fft<f64>>,
+        backward: Arc<dyn Fft<f64>>,
+    },
+}
+
+impl PrimeFft {
+    /// Plan an unnormalized transform of length `n` with the selected algorithm
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Transform length
+    /// * `inverse` - Whether to compute the inverse (unnormalized) transform
+    pub fn new(n: usize, inverse: bool) -> FFTResult<Self> {
+        Self::with_algorithm(n, inverse, select_fft_algorithm(n))
+    }
+
+    /// Plan an unnormalized transform of length `n` with a given algorithm
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Transform length
+    /// * `inverse` - Whether to compute the inverse (unnormalized) transform
+    /// * `algorithm` - Algorithm to use; [`FftAlgorithm::Rader`] requires a
+    ///   prime length
+    pub fn with_algorithm(n: usize, inverse: bool, algorithm: FftAlgorithm) -> FFTResult<Self> {
+        if n == 0 {
+            return Err(FFTError::ValueError(
+                "Transform length must be positive".to_string(),
+            ));
+        }
+        let largest = largest_prime_factor(n);
+        if algorithm == FftAlgorithm::Rader && (largest != n || n < 3) {
+            return Err(FFTError::ValueError(format!(
+                "Rader's algorithm requires an odd prime length, got {n}"
+            )));
+        }
+
+        let sign = if inverse { 1.0 } else { -1.0 };
+        let mut planner = FftPlanner::new();
+        let (kernel, inner_size) = match algorithm {
+            FftAlgorithm::MixedRadix => {
+                let plan = if inverse {
+                    planner.plan_fft_inverse(n)
+                } else {
+                    planner.plan_fft_forward(n)
+                };
+                (Kernel::MixedRadix(plan), None)
+            }
+            FftAlgorithm::Rader => {
+                let m = n - 1;
+                let g = primitive_root(n);
+                let powers: Vec<usize> = std::iter::successors(Some(1), |&x| Some(x * g % n))
+                    .take(m)
+                    .collect();
+                let g_inv = powers[m - 1];
+                let inverse_powers: Vec<usize> =
+                    std::iter::successors(Some(1), |&x| Some(x * g_inv % n))
+                        .take(m)
+                        .collect();
+
+                let forward = planner.plan_fft_forward(m);
+                let backward = planner.plan_fft_inverse(m);
+                let mut spectrum: Vec<Complex64> = inverse_powers
+                    .iter()
+                    .map(|&e| {
+                        Complex64::from_polar(1.0 / m as f64, sign * 2.0 * PI * e as f64 / n as f64)
+                    })
+                    .collect();
+                forward.process(&mut spectrum);
+
+                let kernel = Kernel::Rader {
+                    powers,
+                    inverse_powers,
+                    spectrum,
+                    forward,
+                    backward,
+                };
+                (kernel, Some(m))
+            }
+            FftAlgorithm::Bluestein => {
+                let m = next_fast_len(2 * n - 1, false);
+                // k^2 mod 2n keeps the chirp phase accurate for large k
+                let chirp: Vec<Complex64> = (0..n)
+                    .map(|k| {
+                        let k2 = (k as u128 * k as u128 % (2 * n as u128)) as f64;
+                        Complex64::from_polar(1.0, sign * PI * k2 / n as f64)
+                    })
+                    .collect();
+
+                let forward = planner.plan_fft_forward(m);
+                let backward = planner.plan_fft_inverse(m);
+                let scale = 1.0 / m as f64;
+                let mut spectrum = vec![Complex64::new(0.0, 0.0); m];
+                spectrum[0] = chirp[0].conj() * scale;
+                for k in 1..n {
+                    spectrum[k] = chirp[k].conj() * scale;
+                    spectrum[m - k] = chirp[k].conj() * scale;
+                }
+                forward.process(&mut spectrum);
+
+                let kernel = Kernel::Bluestein {
+                    chirp,
+                    spectrum,
+                    forward,
+                    backward,
+                };
+                (kernel, Some(m))
+            }
+        };
+
+        Ok(Self {
+            n,
+            inverse,
+            kernel,
+            diagnostics: FftDiagnostics {
+                size: n,
+                algorithm,
+                largest_prime_factor: largest,
+                inner_size,
+            },
+        })
+    }
+
+    /// Transform length
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Always `false`; plans have a positive length
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Whether the plan computes the inverse transform
+    pub fn is_inverse(&self) -> bool {
+        self.inverse
+    }
+
+    /// How the transform is computed
+    pub fn diagnostics(&self) -> &FftDiagnostics {
+        &self.diagnostics
+    }
+
+    /// Transform `data` in place
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` does not have the planned length.
+    pub fn process(&self, data: &mut [Complex64]) -> FFTResult<()> {
+        if data.len() != self.n {
+            return Err(FFTError::DimensionError(format!(
+                "Plan has length {}, got input of length {}",
+                self.n,
+                data.len()
+            )));
+        }
+
+        match &self.kernel {
+            Kernel::MixedRadix(plan) => plan.process(data),
+            Kernel::Rader {
+                powers,
+                inverse_powers,
+                spectrum,
+                forward,
+                backward,
+            } => {
+                let x0 = data[0];
+                let total: Complex64 = data.iter().sum();
+
+                // a[q] = x[g^q], cyclically convolved with w^(g^-q)
+                let mut a: Vec<Complex64> = powers.iter().map(|&e| data[e]).collect();
+                forward.process(&mut a);
+                a.iter_mut().zip(spectrum).for_each(|(x, &k)| *x *= k);
+                backward.process(&mut a);
+
+                // X[g^-m] = x[0] + (a * b)[m]
+                data[0] = total;
+                for (&e, &c) in inverse_powers.iter().zip(&a) {
+                    data[e] = x0 + c;
+                }
+            }
+            Kernel::Bluestein {
+                chirp,
+                spectrum,
+                forward,
+                backward,
+            } => {
+                let mut a = vec![Complex64::new(0.0, 0.0); spectrum.len()];
+                for ((v, &x), &w) in a.iter_mut().zip(data.iter()).zip(chirp) {
+                    *v = x * w;
+                }
+                forward.process(&mut a);
+                a.iter_mut().zip(spectrum).for_each(|(x, &k)| *x *= k);
+                backward.process(&mut a);
+
+                for ((x, &c), &w) in data.iter_mut().zip(&a).zip(chirp) {
+                    *x = c * w;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compute the FFT with the algorithm best suited to its length
+///
+/// Unlike [`crate::fft`], the default length is the input length rather than
+/// the next power of two, so prime and other awkward lengths are transformed
+/// as they are.
+///
+/// # Arguments
+///
+/// * `input` - Input signal
+/// * `n` - Transform length; the input is zero-padded or truncated to it
+///   (defaults to the input length)
+/// * `diagnostics` - Filled with the details of the transform if given
+///
+/// # Returns
+///
+/// The unnormalized spectrum of length `n`.
+pub fn fft_with_diagnostics<T>(
+    input: &[T],
+    n: Option<usize>,
+    diagnostics: Option<&mut FftDiagnostics>,
+) -> FFTResult<Vec<Complex64>>
+where
+    T: NumCast + Copy + Debug + 'static,
+{
+    transform(input, n, false, diagnostics)
+}
+
+/// Compute the inverse FFT with the algorithm best suited to its length
+///
+/// # Arguments
+///
+/// * `input` - Input spectrum
+/// * `n` - Transform length; the input is zero-padded or truncated to it
+///   (defaults to the input length)
+/// * `diagnostics` - Filled with the details of the transform if given
+///
+/// # Returns
+///
+/// The signal of length `n`, normalized by `1/n`.
+pub fn ifft_with_diagnostics<T>(
+    input: &[T],
+    n: Option<usize>,
+    diagnostics: Option<&mut FftDiagnostics>,
+) -> FFTResult<Vec<Complex64>>
+where
+    T: NumCast + Copy + Debug + 'static,
+{
+    let mut result = transform(input, n, true, diagnostics)?;
+    let scale = 1.0 / result.len() as f64;
+    result.iter_mut().for_each(|v| *v *= scale);
+    Ok(result)
+}
+
+fn transform<T>(
+    input: &[T],
+    n: Option<usize>,
+    inverse: bool,
+    diagnostics: Option<&mut FftDiagnostics>,
+) -> FFTResult<Vec<Complex64>>
+where
+    T: NumCast + Copy + Debug + 'static,
+{
+    if input.is_empty() {
+        return Err(FFTError::ValueError("Input cannot be empty".to_string()));
+    }
+    let n = n.unwrap_or(input.len());
+    let mut data = input
+        .iter()
+        .take(n)
+        .map(|&val| to_complex(val))
+        .collect::<FFTResult<Vec<_>>>()?;
+    data.resize(n, Complex64::new(0.0, 0.0));
+
+    let plan = PrimeFft::new(n, inverse)?;
+    plan.process(&mut data)?;
+    if let Some(diagnostics) = diagnostics {
+        *diagnostics = plan.diagnostics().clone();
+    }
+    Ok(data)
+}
+
+fn to_complex<T>(val: T) -> FFTResult<Complex64>
+where
+    T: NumCast + Copy + Debug + 'static,
+{
+    if let Some(c) = (&val as &dyn std::any::Any).downcast_ref::<Complex64>() {
+        return Ok(*c);
+    }
+    let re: f64 = NumCast::from(val)
+        .ok_or_else(|| FFTError::ValueError(format!("Could not convert {val:?} to f64")))?;
+    Ok(Complex64::new(re, 0.0))
+}
+
+/// Largest prime factor of `n` (1 for `n <= 1`)
+fn largest_prime_factor(mut n: usize) -> usize {
+    let mut largest = 1;
+    let mut p = 2;
+    while p * p <= n {
+        while n.is_multiple_of(p) {
+            largest = p;
+            n /= p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        n
+    } else {
+        largest
+    }
+}
+
+/// Smallest primitive root of the prime `p`
+fn primitive_root(p: usize) -> usize {
+    let phi = p - 1;
+    let mut factors = Vec::new();
+    let mut rest = phi;
+    let mut q = 2;
+    while q * q <= rest {
+        if rest.is_multiple_of(q) {
+            factors.push(q);
+            while rest.is_multiple_of(q) {
+                rest /= q;
+            }
+        }
+        q += 1;
+    }
+    if rest > 1 {
+        factors.push(rest);
+    }
+
+    (2..p)
+        .find(|&g| factors.iter().all(|&f| mod_pow(g, phi / f, p) != 1))
+        .unwrap_or(1)
+}
+
+fn mod_pow(base: usize, mut exp: usize, modulus: usize) -> usize {
+    let (mut result, mut base) = (1u128, base as u128 % modulus as u128);
+    let modulus = modulus as u128;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(n: usize) -> Vec<Complex64> {
+        (0..n)
+            .map(|i| Complex64::new((i as f64 * 0.37).sin(), (i as f64 * 0.011).cos()))
+            .collect()
+    }
+
+    fn reference(x: &[Complex64], inverse: bool) -> Vec<Complex64> {
+        let mut planner = FftPlanner::new();
+        let plan = if inverse {
+            planner.plan_fft_inverse(x.len())
+        } else {
+            planner.plan_fft_forward(x.len())
+        };
+        let mut out = x.to_vec();
+        plan.process(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_algorithms_match_reference() {
+        let cases = [
+            (360, FftAlgorithm::MixedRadix),
+            (101, FftAlgorithm::Rader),
+            (1009, FftAlgorithm::Rader),
+            (10007, FftAlgorithm::Bluestein),
+            (2 * 4099, FftAlgorithm::Bluestein),
+        ];
+        for (n, expected) in cases {
+            assert_eq!(select_fft_algorithm(n), expected, "n = {n}");
+            for inverse in [false, true] {
+                let x = signal(n);
+                let want = reference(&x, inverse);
+                let scale = want.iter().map(|v| v.norm()).fold(0.0, f64::max);
+
+                let plan = PrimeFft::new(n, inverse).unwrap();
+                let mut got = x.clone();
+                plan.process(&mut got).unwrap();
+                for (a, b) in got.iter().zip(&want) {
+                    assert!((a - b).norm() < 1e-10 * scale, "n = {n}");
+                }
+            }
+        }
+
+        // Bluestein also handles lengths the other paths would take
+        for n in [1, 2, 17, 64, 97] {
+            let x = signal(n);
+            let plan = PrimeFft::with_algorithm(n, false, FftAlgorithm::Bluestein).unwrap();
+            let mut got = x.clone();
+            plan.process(&mut got).unwrap();
+            for (a, b) in got.iter().zip(&reference(&x, false)) {
+                assert!((a - b).norm() < 1e-9, "n = {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_diagnostics_and_round_trip() {
+        let x = signal(10007);
+        let mut diagnostics = FftDiagnostics::default();
+        let spectrum = fft_with_diagnostics(&x, None, Some(&mut diagnostics)).unwrap();
+        assert_eq!(diagnostics.size, 10007);
+        assert_eq!(diagnostics.algorithm, FftAlgorithm::Bluestein);
+        assert_eq!(diagnostics.largest_prime_factor, 10007);
+        assert!(diagnostics.inner_size.unwrap() >= 2 * 10007 - 1);
+
+        let back = ifft_with_diagnostics(&spectrum, None, None).unwrap();
+        for (a, b) in back.iter().zip(&x) {
+            assert!((a - b).norm() < 1e-10);
+        }
+
+        assert!(PrimeFft::with_algorithm(15, false, FftAlgorithm::Rader).is_err());
+        assert!(PrimeFft::new(0, false).is_err());
+        assert!(PrimeFft::new(7, false)
+            .unwrap()
+            .process(&mut [Complex64::new(0.0, 0.0); 6])
+            .is_err());
+        assert_eq!(primitive_root(1009), 11);
+        assert_eq!(largest_prime_factor(2 * 4099), 4099);
+    }
+}
@@ -3,7 +3,8 @@
 //! This module contains the main SparseFFT struct and its algorithm implementations.
 
 use crate::error::{FFTError, FFTResult};
-use crate::fft::{fft, ifft};
+use crate::fft::{fft, fft2, ifft};
+use ndarray::Array2;
 use num_complex::Complex64;
 use num_traits::NumCast;
 use rand::{Rng, SeedableRng};
@@ -398,6 +399,12 @@ impl SparseFFT {
 
         // Compute full FFT
         let spectrum = fft(&signal_complex, None)?;
+        Ok(self.prune_components(&spectrum, k))
+    }
+
+    /// Keep up to `k` components standing out of the magnitude statistics
+    /// by more than `pruning_sensitivity` standard deviations
+    fn prune_components(&self, spectrum: &[Complex64], k: usize) -> (Vec<Complex64>, Vec<usize>) {
         let magnitudes: Vec<f64> = spectrum.iter().map(|c| c.norm()).collect();
 
         // Compute statistics for pruning
@@ -431,7 +438,7 @@ impl SparseFFT {
             .map(|(_, _, c)| *c)
             .collect();
 
-        Ok((selected_values, selected_indices))
+        (selected_values, selected_indices)
     }
 
     /// Implementation of spectral flatness sparse FFT algorithm
@@ -456,6 +463,16 @@ impl SparseFFT {
 
         // Compute full FFT
         let spectrum = fft(&signal_complex, None)?;
+        Ok(self.flatness_components(&spectrum, k))
+    }
+
+    /// Pick peaks from spectral windows with low flatness, topped up with
+    /// the largest remaining components until `k` are found
+    fn flatness_components(
+        &self,
+        spectrum: &[Complex64],
+        k: usize,
+    ) -> (Vec<Complex64>, Vec<usize>) {
         let magnitudes: Vec<f64> = spectrum.iter().map(|c| c.norm()).collect();
 
         // Analyze spectral flatness in segments
@@ -513,8 +530,108 @@ impl SparseFFT {
             }
         }
 
-        Ok((selected_values, selected_indices))
+        (selected_values, selected_indices)
+    }
+
+    /// Perform sparse FFT on a 2-D signal
+    ///
+    /// `signal` holds the rows of an image or field; all rows must have the
+    /// same length. The configured window is applied separably along both
+    /// axes. The indices of the result are row-major positions
+    /// `row * cols + col` in the 2-D spectrum.
+    pub fn sparse_fft2<T>(&mut self, signal: &[Vec<T>]) -> FFTResult<SparseFFTResult>
+    where
+        T: NumCast + Copy + Debug + 'static,
+    {
+        let start = Instant::now();
+        let (rows, cols) = image_shape(signal)?;
+        let image = Array2::from_shape_vec((rows, cols), windowed_image(signal, &self.config)?)
+            .map_err(|e| FFTError::DimensionError(e.to_string()))?;
+
+        let spectrum: Vec<Complex64> = fft2(&image, None, None, None)?.into_iter().collect();
+        let k = self.config.sparsity.min(spectrum.len());
+        let (values, indices) = self.select_components(&spectrum, k);
+
+        Ok(SparseFFTResult {
+            values,
+            indices,
+            estimated_sparsity: k,
+            computation_time: start.elapsed(),
+            algorithm: self.config.algorithm,
+        })
+    }
+
+    /// Select up to `k` components of a full spectrum with the configured
+    /// algorithm's selection rule
+    fn select_components(&self, spectrum: &[Complex64], k: usize) -> (Vec<Complex64>, Vec<usize>) {
+        match self.config.algorithm {
+            SparseFFTAlgorithm::Sublinear
+            | SparseFFTAlgorithm::CompressedSensing
+            | SparseFFTAlgorithm::Deterministic => largest_components(spectrum, k, 0.0),
+            // Peeling the strongest bin off the residual one at a time, until
+            // nothing significant is left, selects the same bins
+            SparseFFTAlgorithm::Iterative => {
+                largest_components(spectrum, k.min(self.config.iterations), 1e-10)
+            }
+            SparseFFTAlgorithm::FrequencyPruning => self.prune_components(spectrum, k),
+            SparseFFTAlgorithm::SpectralFlatness => self.flatness_components(spectrum, k),
+        }
+    }
+}
+
+/// The `k` components of largest magnitude above `min_norm`, strongest first
+pub(crate) fn largest_components(
+    spectrum: &[Complex64],
+    k: usize,
+    min_norm: f64,
+) -> (Vec<Complex64>, Vec<usize>) {
+    let mut order: Vec<usize> = (0..spectrum.len())
+        .filter(|&i| spectrum[i].norm() >= min_norm)
+        .collect();
+    order.sort_by(|&a, &b| spectrum[b].norm().total_cmp(&spectrum[a].norm()));
+    order.truncate(k);
+    (order.iter().map(|&i| spectrum[i]).collect(), order)
+}
+
+/// Row-major samples of a 2-D signal with the configured window applied
+/// separably along both axes
+pub(crate) fn windowed_image<T>(
+    signal: &[Vec<T>],
+    config: &SparseFFTConfig,
+) -> FFTResult<Vec<Complex64>>
+where
+    T: NumCast + Copy + Debug + 'static,
+{
+    let (rows, cols) = image_shape(signal)?;
+    let row_window = apply_window(&vec![1.0; rows], config.window_function, config.kaiser_beta)?;
+    let col_window = apply_window(&vec![1.0; cols], config.window_function, config.kaiser_beta)?;
+
+    let mut image = Vec::with_capacity(rows * cols);
+    for (row, wr) in signal.iter().zip(row_window.iter()) {
+        for (&v, wc) in row.iter().zip(col_window.iter()) {
+            let x: f64 = NumCast::from(v)
+                .ok_or_else(|| FFTError::ValueError(format!("Could not convert {:?} to f64", v)))?;
+            image.push(Complex64::new(wr.re * wc.re * x, 0.0));
+        }
     }
+    Ok(image)
+}
+
+/// Number of rows and columns of a rectangular, non-empty 2-D signal
+pub(crate) fn image_shape<T>(signal: &[Vec<T>]) -> FFTResult<(usize, usize)> {
+    let rows = signal.len();
+    let cols = signal.first().map_or(0, Vec::len);
+    if rows == 0 || cols == 0 {
+        return Err(FFTError::ValueError(
+            "2D signal must have at least one row and one column".to_string(),
+        ));
+    }
+    if signal.iter().any(|row| row.len() != cols) {
+        return Err(FFTError::DimensionError(
+            "All rows of a 2D signal must have the same length".to_string(),
+        ));
+    }
+    Ok((rows, cols))
 }
 
 // Public API functions for backward compatibility
@@ -593,19 +710,59 @@ where
     processor.sparse_fft(signal)
 }
 
-/// 2D sparse FFT (placeholder implementation)
+/// Compute the 2-D sparse FFT of an image or field
+///
+/// Finds the `k` dominant components of the 2-D spectrum of `signal`, given
+/// as a list of equally long rows. The indices of the result are row-major
+/// positions `row * cols + col` in the spectrum.
+///
+/// # Arguments
+///
+/// * `signal` - Rows of the 2-D input
+/// * `k` - Number of components to find
+/// * `algorithm` - Sparse FFT algorithm variant (default: sublinear)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::sparse_fft::sparse_fft2;
+/// use std::f64::consts::PI;
+///
+/// // A single plane wave with frequency (2, 3) on an 8 x 16 grid
+/// let (rows, cols) = (8, 16);
+/// let image: Vec<Vec<f64>> = (0..rows)
+///     .map(|r| {
+///         (0..cols)
+///             .map(|c| {
+///                 let phase = 2.0 * PI * (2.0 * r as f64 / rows as f64 + 3.0 * c as f64 / cols as f64);
+///                 phase.cos()
+///             })
+///             .collect()
+///     })
+///     .collect();
+///
+/// let result = sparse_fft2(&image, 2, None).unwrap();
+/// let mut found: Vec<(usize, usize)> = result.indices.iter().map(|&i| (i / cols, i % cols)).collect();
+/// found.sort();
+/// assert_eq!(found, vec![(2, 3), (rows - 2, cols - 3)]);
+/// ```
 pub fn sparse_fft2<T>(
-    _signal: &[Vec<T>],
-    _k: usize,
-    _algorithm: Option<SparseFFTAlgorithm>,
+    signal: &[Vec<T>],
+    k: usize,
+    algorithm: Option<SparseFFTAlgorithm>,
 ) -> FFTResult<SparseFFTResult>
 where
     T: NumCast + Copy + Debug + 'static,
 {
-    // Placeholder implementation
-    Err(FFTError::ValueError(
-        "2D sparse FFT not yet implemented".to_string(),
-    ))
+    let config = SparseFFTConfig {
+        estimation_method: super::config::SparsityEstimationMethod::Manual,
+        sparsity: k,
+        algorithm: algorithm.unwrap_or(SparseFFTAlgorithm::Sublinear),
+        ..SparseFFTConfig::default()
+    };
+
+    let mut processor = SparseFFT::new(config);
+    processor.sparse_fft2(signal)
 }
 
 /// N-dimensional sparse FFT (placeholder implementation)
@@ -3,6 +3,7 @@
 //! This module contains the configuration structures, enums, and utility functions
 //! used to configure and control sparse FFT computations.
 
+use crate::window::Window;
 use num_complex::Complex64;
 use std::fmt::Debug;
 
@@ -36,6 +37,9 @@ pub enum SparsityEstimationMethod {
     FrequencyPruning,
     /// Spectral flatness measure for noise vs signal discrimination
     SpectralFlatness,
+    /// Statistical estimation from a robust noise floor and local spectral
+    /// flatness on a (subsampled) transform; needs no prior guess of `k`
+    Statistical,
 }
 
 /// Sparse FFT algorithm variant
@@ -72,6 +76,22 @@ pub enum WindowFunction {
     Kaiser,
 }
 
+impl WindowFunction {
+    /// The equivalent window from the shared [`crate::window`] library
+    ///
+    /// `kaiser_beta` is used as the shape parameter of the Kaiser window.
+    pub fn to_window(self, kaiser_beta: f64) -> Window {
+        match self {
+            WindowFunction::None => Window::Rectangular,
+            WindowFunction::Hann => Window::Hann,
+            WindowFunction::Hamming => Window::Hamming,
+            WindowFunction::Blackman => Window::Blackman,
+            WindowFunction::FlatTop => Window::FlatTop,
+            WindowFunction::Kaiser => Window::Kaiser(kaiser_beta),
+        }
+    }
+}
+
 /// Sparse FFT configuration
 #[derive(Debug, Clone)]
 pub struct SparseFFTConfig {
@@ -7,6 +7,7 @@ use crate::error::FFTResult;
 use crate::fft::fft;
 // Complex64 is used through the FFT functions
 use num_traits::NumCast;
+use std::f64::consts::LN_2;
 use std::fmt::Debug;
 
 use super::config::{SparseFFTConfig, SparsityEstimationMethod};
@@ -36,6 +37,10 @@ where
             config.flatness_threshold,
             config.window_size,
         ),
+
+        SparsityEstimationMethod::Statistical => {
+            estimate_sparsity_statistical(signal, config.flatness_threshold, config.window_size)
+        }
     }
 }
 
@@ -211,6 +216,136 @@ where
     Ok(significant_components.max(1))
 }
 
+/// Longest transform computed by the statistical estimator; longer signals
+/// are decimated
+const STATISTICAL_MAX_LENGTH: usize = 4096;
+
+/// Expected number of pure-noise bins exceeding the noise floor
+const FALSE_ALARM_COUNT: f64 = 0.01;
+
+/// Bins weaker than this fraction of the strongest are treated as round-off
+const RELATIVE_FLOOR: f64 = 1e-10;
+
+/// Estimate sparsity from noise-floor statistics and local spectral flatness
+///
+/// The noise floor is derived from the median magnitude of the spectrum,
+/// which is insensitive to a small number of strong components. Modelling
+/// the noise magnitudes as Rayleigh distributed, the floor is placed so that
+/// on average only [`FALSE_ALARM_COUNT`] noise bins exceed it. A bin above the
+/// floor is counted only if the power in the surrounding `window_size` bins
+/// is tonal, i.e. its spectral flatness is below `flatness_threshold`.
+///
+/// The transform is taken at the signal's own length, without padding.
+/// Signals longer than [`STATISTICAL_MAX_LENGTH`] are decimated before the
+/// transform. Decimation folds the spectrum onto fewer bins, so two coprime
+/// strides are tried and the larger count is kept, which makes it unlikely
+/// that components colliding under one stride are lost. Strides dividing the
+/// signal length are preferred since they alias bins exactly instead of
+/// spreading them by leakage.
+pub fn estimate_sparsity_statistical<T>(
+    signal: &[T],
+    flatness_threshold: f64,
+    window_size: usize,
+) -> FFTResult<usize>
+where
+    T: NumCast + Copy + Debug + 'static,
+{
+    let n = signal.len();
+    if n <= STATISTICAL_MAX_LENGTH {
+        let spectrum = fft(signal, Some(n))?;
+        return Ok(count_significant_bins(&spectrum, flatness_threshold, window_size).max(1));
+    }
+
+    let mut estimate = 1;
+    for step in decimation_strides(n) {
+        let decimated: Vec<T> = signal.iter().step_by(step).copied().collect();
+        let spectrum = fft(&decimated, Some(decimated.len()))?;
+        estimate = estimate.max(count_significant_bins(
+            &spectrum,
+            flatness_threshold,
+            window_size,
+        ));
+    }
+
+    Ok(estimate)
+}
+
+/// Two coprime strides that shorten a length-`n` signal to at most
+/// [`STATISTICAL_MAX_LENGTH`] samples, preferring divisors of `n`
+fn decimation_strides(n: usize) -> [usize; 2] {
+    let stride = n.div_ceil(STATISTICAL_MAX_LENGTH);
+    let gcd = |mut a: usize, mut b: usize| {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    };
+    let divisors: Vec<usize> = (stride..=4 * stride)
+        .filter(|s| n.is_multiple_of(*s))
+        .collect();
+    for (i, &first) in divisors.iter().enumerate() {
+        if let Some(&second) = divisors[i + 1..].iter().find(|&&s| gcd(first, s) == 1) {
+            return [first, second];
+        }
+    }
+    [stride, stride + 1]
+}
+
+/// Count bins above the Rayleigh noise floor that sit in a tonal neighbourhood
+fn count_significant_bins(
+    spectrum: &[num_complex::Complex64],
+    flatness_threshold: f64,
+    window_size: usize,
+) -> usize {
+    let n = spectrum.len();
+    if n == 0 {
+        return 0;
+    }
+
+    let magnitudes: Vec<f64> = spectrum.iter().map(|c| c.norm()).collect();
+    let mut sorted = magnitudes.clone();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[n / 2];
+    let max_magnitude = sorted[n - 1];
+    if max_magnitude <= 0.0 {
+        return 0;
+    }
+
+    // For Rayleigh noise, median = sigma * sqrt(2 ln 2) and
+    // P(|X| > t) = exp(-t^2 / (2 sigma^2))
+    let sigma = median / (2.0 * LN_2).sqrt();
+    let noise_floor = sigma * (2.0 * (n as f64 / FALSE_ALARM_COUNT).ln()).sqrt();
+    let floor = noise_floor.max(max_magnitude * RELATIVE_FLOOR);
+
+    let power: Vec<f64> = magnitudes.iter().map(|m| m * m).collect();
+    let half = (window_size / 2).max(1).min(n / 2);
+
+    magnitudes
+        .iter()
+        .enumerate()
+        .filter(|&(i, &m)| m > floor && local_flatness(&power, i, half) < flatness_threshold)
+        .count()
+}
+
+/// Spectral flatness of `power` over the circular window `center ± half`
+fn local_flatness(power: &[f64], center: usize, half: usize) -> f64 {
+    let n = power.len();
+    let width = 2 * half + 1;
+    let (mut log_sum, mut sum) = (0.0, 0.0);
+    for offset in 0..width.min(n) {
+        let p = power[(center + n - half + offset) % n];
+        log_sum += p.max(f64::MIN_POSITIVE).ln();
+        sum += p;
+    }
+    let count = width.min(n) as f64;
+    let arithmetic_mean = sum / count;
+    if arithmetic_mean > 0.0 {
+        (log_sum / count).exp() / arithmetic_mean
+    } else {
+        1.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,4 +405,37 @@ mod tests {
         let result = estimate_sparsity_spectral_flatness(&signal, 0.3, 8).unwrap();
         assert!(result >= 1);
     }
+
+    #[test]
+    fn test_estimate_sparsity_statistical() {
+        // Deterministic pseudo-random noise well below the tones
+        let noise = |i: usize| {
+            // splitmix64 hash of the sample index
+            let mut x = (i as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+            x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            x ^= x >> 31;
+            ((x >> 11) as f64 / (1u64 << 53) as f64 - 0.5) * 0.05
+        };
+
+        let n = 256;
+        let frequencies = vec![(5, 1.0), (19, 0.5), (40, 0.3)];
+        let clean = create_sparse_signal(n, &frequencies);
+        assert_eq!(estimate_sparsity_statistical(&clean, 0.3, 16).unwrap(), 6);
+
+        let noisy: Vec<f64> = clean
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| v + noise(i))
+            .collect();
+        assert_eq!(estimate_sparsity_statistical(&noisy, 0.3, 16).unwrap(), 6);
+
+        // Pure noise has no significant components
+        let white: Vec<f64> = (0..n).map(noise).collect();
+        assert_eq!(estimate_sparsity_statistical(&white, 0.3, 16).unwrap(), 1);
+
+        // Long signals go through the decimated path
+        let long = create_sparse_signal(20_000, &[(3, 1.0), (11, 0.5)]);
+        assert_eq!(estimate_sparsity_statistical(&long, 0.3, 16).unwrap(), 4);
+    }
 }
@@ -254,6 +254,14 @@ fn test_sparsity_estimation_methods() {
     // Test spectral flatness method
     let estimated = estimation::estimate_sparsity_spectral_flatness(&signal, 0.3, 8).unwrap();
     assert!(estimated > 0);
+
+    // Test statistical method through the configuration
+    let config = SparseFFTConfig {
+        estimation_method: config::SparsityEstimationMethod::Statistical,
+        ..SparseFFTConfig::default()
+    };
+    let estimated = estimation::estimate_sparsity(&signal, &config).unwrap();
+    assert_eq!(estimated, 4);
 }
 
 #[test]
@@ -331,15 +339,53 @@ fn test_filtered_reconstruction() {
 }
 
 #[test]
-fn test_multidimensional_placeholders() {
-    // Test 2D sparse FFT placeholder
-    let signal_2d = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
-    let result = sparse_fft2(&signal_2d, 2, None);
-    assert!(result.is_err()); // Should return NotImplemented error
+fn test_sparse_fft2() {
+    // Two plane waves on a 16 x 32 grid: cos at (3, 5) and sin at (6, 30)
+    let (rows, cols) = (16, 32);
+    let image: Vec<Vec<f64>> = (0..rows)
+        .map(|r| {
+            (0..cols)
+                .map(|c| {
+                    let (y, x) = (r as f64 / rows as f64, c as f64 / cols as f64);
+                    (2.0 * PI * (3.0 * y + 5.0 * x)).cos()
+                        + 0.25 * (2.0 * PI * (6.0 * y + 30.0 * x)).sin()
+                })
+                .collect()
+        })
+        .collect();
+    let expected = [
+        3 * cols + 5,
+        (rows - 3) * cols + (cols - 5),
+        6 * cols + 30,
+        (rows - 6) * cols + 2,
+    ];
+
+    for algorithm in [
+        SparseFFTAlgorithm::Sublinear,
+        SparseFFTAlgorithm::CompressedSensing,
+        SparseFFTAlgorithm::FrequencyPruning,
+        SparseFFTAlgorithm::SpectralFlatness,
+    ] {
+        let result = sparse_fft2(&image, 4, Some(algorithm)).unwrap();
+        let mut found = result.indices.clone();
+        found.sort_unstable();
+        let mut want = expected.to_vec();
+        want.sort_unstable();
+        assert_eq!(found, want, "{:?}", algorithm);
+        // Strongest components first: the unit cosine has |X| = rows * cols / 2
+        assert!((result.values[0].norm() - (rows * cols) as f64 / 2.0).abs() < 1e-8);
+    }
+
+    // Iterative peeling is limited by the configured number of iterations
+    let result = sparse_fft2(&image, 4, Some(SparseFFTAlgorithm::Iterative)).unwrap();
+    assert_eq!(result.indices.len(), 3);
 
-    // Test N-D sparse FFT placeholder
+    assert!(sparse_fft2::<f64>(&[], 2, None).is_err());
+    assert!(sparse_fft2(&[vec![1.0, 2.0], vec![3.0]], 2, None).is_err());
+
+    // N-D sparse FFT is not implemented yet
     let signal_1d = vec![1.0, 2.0, 3.0, 4.0];
     let shape = vec![2, 2];
     let result = sparse_fftn(&signal_1d, &shape, 2, None);
-    assert!(result.is_err()); // Should return NotImplemented error
+    assert!(result.is_err());
 }
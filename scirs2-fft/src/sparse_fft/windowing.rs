@@ -1,12 +1,13 @@
 //! Window function implementations for Sparse FFT
 //!
 //! This module provides various window functions that can be applied to signals
-//! before performing sparse FFT operations to reduce spectral leakage.
+//! before performing sparse FFT operations to reduce spectral leakage. The window
+//! shapes come from the shared [`crate::window`] library.
 
 use crate::error::{FFTError, FFTResult};
+use crate::window::get_window;
 use num_complex::Complex64;
 use num_traits::NumCast;
-use std::f64::consts::PI;
 use std::fmt::Debug;
 
 use super::config::WindowFunction;
@@ -38,85 +39,14 @@ where
         return Ok(signal_complex);
     }
 
-    // Apply the selected window function
-    let windowed_signal = match window_function {
-        WindowFunction::None => signal_complex, // Already handled above, but included for completeness
-
-        WindowFunction::Hann => {
-            let mut windowed = signal_complex;
-            for (i, sample) in windowed.iter_mut().enumerate() {
-                let window_val = 0.5 * (1.0 - (2.0 * PI * i as f64 / (n - 1) as f64).cos());
-                *sample *= window_val;
-            }
-            windowed
-        }
-
-        WindowFunction::Hamming => {
-            let mut windowed = signal_complex;
-            for (i, sample) in windowed.iter_mut().enumerate() {
-                let window_val = 0.54 - 0.46 * (2.0 * PI * i as f64 / (n - 1) as f64).cos();
-                *sample *= window_val;
-            }
-            windowed
-        }
-
-        WindowFunction::Blackman => {
-            let mut windowed = signal_complex;
-            for (i, sample) in windowed.iter_mut().enumerate() {
-                let angle = 2.0 * PI * i as f64 / (n - 1) as f64;
-                let window_val = 0.42 - 0.5 * angle.cos() + 0.08 * (2.0 * angle).cos();
-                *sample *= window_val;
-            }
-            windowed
-        }
-
-        WindowFunction::FlatTop => {
-            let mut windowed = signal_complex;
-            for (i, sample) in windowed.iter_mut().enumerate() {
-                let angle = 2.0 * PI * i as f64 / (n - 1) as f64;
-                let window_val = 0.21557895 - 0.41663158 * angle.cos()
-                    + 0.277263158 * (2.0 * angle).cos()
-                    - 0.083578947 * (3.0 * angle).cos()
-                    + 0.006947368 * (4.0 * angle).cos();
-                *sample *= window_val;
-            }
-            windowed
-        }
-
-        WindowFunction::Kaiser => {
-            let mut windowed = signal_complex;
-            let beta = kaiser_beta;
-            let alpha = (n - 1) as f64 / 2.0;
-            let i0_beta = modified_bessel_i0(beta);
-
-            for (i, sample) in windowed.iter_mut().enumerate() {
-                let x = beta * (1.0 - ((i as f64 - alpha) / alpha).powi(2)).sqrt();
-                let window_val = modified_bessel_i0(x) / i0_beta;
-                *sample *= window_val;
-            }
-            windowed
-        }
-    };
-
-    Ok(windowed_signal)
-}
-
-/// Modified Bessel function of the first kind, order 0
-/// Used for Kaiser window computation
-fn modified_bessel_i0(x: f64) -> f64 {
-    let mut sum = 1.0;
-    let mut term = 1.0;
-    let half_x = x / 2.0;
-
-    for k in 1..=50 {
-        term *= (half_x / k as f64).powi(2);
-        sum += term;
-        if term < 1e-12 * sum {
-            break;
-        }
+    // Symmetric windows, as the signal is treated as a single finite frame
+    let window = get_window(window_function.to_window(kaiser_beta), n, true)?;
+    let mut windowed = signal_complex;
+    for (sample, &w) in windowed.iter_mut().zip(window.iter()) {
+        *sample *= w;
     }
 
-    sum
+    Ok(windowed)
 }
 
 #[cfg(test)]
@@ -156,12 +86,14 @@ mod tests {
     }
 
     #[test]
-    fn test_modified_bessel_i0() {
-        // Test known values
-        let result = modified_bessel_i0(0.0);
-        assert!((result - 1.0).abs() < 1e-10);
+    fn test_apply_window_kaiser() {
+        let signal = vec![2.0; 9];
+        let result = apply_window(&signal, WindowFunction::Kaiser, 8.6).unwrap();
+        let window = get_window(crate::window::Window::Kaiser(8.6), 9, true).unwrap();
 
-        let result = modified_bessel_i0(1.0);
-        assert!((result - 1.2660658777520084).abs() < 1e-10);
+        for (r, w) in result.iter().zip(window.iter()) {
+            assert!((r.re - 2.0 * w).abs() < 1e-12);
+        }
+        assert!((result[4].re - 2.0).abs() < 1e-12);
     }
 }
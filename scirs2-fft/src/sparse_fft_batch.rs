@@ -9,9 +9,11 @@ use crate::sparse_fft::{
     SparseFFTAlgorithm, SparseFFTConfig, SparseFFTResult, SparsityEstimationMethod, WindowFunction,
 };
 use crate::sparse_fft_gpu::{GPUBackend, GPUSparseFFTConfig};
+use crate::sparse_fft_gpu_cuda::GpuSparseFFT;
 
 use num_complex::Complex64;
 use num_traits::NumCast;
+use scirs2_core::gpu::GpuBackend;
 use scirs2_core::parallel_ops::*;
 use std::fmt::Debug;
 use std::time::Instant;
@@ -194,7 +196,7 @@ where
 
     // Create GPU config
     let _gpu_config = GPUSparseFFTConfig {
-        base_config: base_fft_config,
+        base_config: base_fft_config.clone(),
         backend,
         device_id,
         batch_size,
@@ -206,6 +208,10 @@ where
 
     let start = Instant::now();
 
+    // Dispatch on the backend at run time
+    let processor = GpuSparseFFT::with_backend(device_id, backend.into(), base_fft_config.clone())?;
+    let mut gpu_processor = (processor.backend() != GpuBackend::Cpu).then_some(processor);
+
     // Process signals in batches
     let mut all_results = Vec::with_capacity(total_signals);
     for batch_idx in 0..num_batches {
@@ -213,25 +219,13 @@ where
         let end_idx = (start_idx + batch_size).min(total_signals);
         let current_batch = &signals[start_idx..end_idx];
 
-        // Process this batch
-        match backend {
-            GPUBackend::CUDA => {
-                let batch_results = crate::cuda_batch_sparse_fft(
-                    current_batch,
-                    k,
-                    device_id,
-                    Some(alg),
-                    Some(window),
-                )?;
-                all_results.extend(batch_results);
-            }
-            _ => {
-                // For other backends, fall back to CPU for now
-                let batch_results =
-                    batch_sparse_fft(current_batch, k, Some(alg), Some(window), None)?;
-                all_results.extend(batch_results);
-            }
-        }
+        // Process this batch on the GPU when the backend is usable, and with
+        // the CPU algorithms otherwise
+        let batch_results = match gpu_processor.as_mut() {
+            Some(processor) => processor.sparse_fft_batch(current_batch, 2)?,
+            None => batch_sparse_fft(current_batch, k, Some(alg), Some(window), None)?,
+        };
+        all_results.extend(batch_results);
     }
 
     // Update computation time to include batching overhead
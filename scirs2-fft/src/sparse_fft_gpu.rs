@@ -1,14 +1,20 @@
 //! GPU-accelerated Sparse Fast Fourier Transform
 //!
-//! This module extends the sparse FFT functionality with GPU acceleration
-//! using CUDA, HIP (ROCm), or SYCL backends for high-performance computing.
+//! This module extends the sparse FFT functionality with GPU acceleration.
+//! The backend is dispatched at run time through the scirs2-core GPU
+//! abstractions, so CUDA, ROCm, Metal, WebGPU and OpenCL machines share one
+//! API. When the requested backend is not usable, or has no FFT kernel yet
+//! (see [`crate::backend_supports_fft`]), the computation falls back to the
+//! CPU.
 
 use crate::error::{FFTError, FFTResult};
 use crate::sparse_fft::{
     SparseFFTAlgorithm, SparseFFTConfig, SparseFFTResult, SparsityEstimationMethod, WindowFunction,
 };
+use crate::sparse_fft_gpu_cuda::{GpuSparseFFT, DEFAULT_MAX_IN_FLIGHT};
 use num_complex::Complex64;
 use num_traits::NumCast;
+use scirs2_core::gpu::GpuBackend;
 use std::fmt::Debug;
 use std::time::Instant;
 
@@ -25,6 +31,20 @@ pub enum GPUBackend {
     CPUFallback,
 }
 
+impl From<GPUBackend> for GpuBackend {
+    /// The scirs2-core backend driving each device family
+    ///
+    /// SYCL devices are reached through the OpenCL backend.
+    fn from(backend: GPUBackend) -> Self {
+        match backend {
+            GPUBackend::CUDA => GpuBackend::Cuda,
+            GPUBackend::HIP => GpuBackend::Rocm,
+            GPUBackend::SYCL => GpuBackend::OpenCL,
+            GPUBackend::CPUFallback => GpuBackend::Cpu,
+        }
+    }
+}
+
 /// GPU-accelerated sparse FFT configuration
 #[derive(Debug, Clone)]
 pub struct GPUSparseFFTConfig {
@@ -69,6 +89,8 @@ pub struct GPUSparseFFT {
     gpu_initialized: bool,
     /// GPU device information
     device_info: Option<String>,
+    /// Processor on a usable GPU backend; `None` computes on the CPU
+    gpu_processor: Option<GpuSparseFFT>,
 }
 
 impl GPUSparseFFT {
@@ -78,6 +100,7 @@ impl GPUSparseFFT {
             config,
             gpu_initialized: false,
             device_info: None,
+            gpu_processor: None,
         }
     }
 
@@ -87,32 +110,46 @@ impl GPUSparseFFT {
     }
 
     /// Initialize GPU resources
+    ///
+    /// Probes the configured backend and keeps a device processor if it is
+    /// usable and can transform; otherwise the computation falls back to the CPU algorithms.
     fn initialize_gpu(&mut self) -> FFTResult<()> {
-        // Placeholder for actual GPU initialization code
-        // In a real implementation, this would set up CUDA/HIP/SYCL context and resources
-
-        match self.config.backend {
-            GPUBackend::CUDA => {
-                // Initialize CUDA resources
-                self.device_info = Some("CUDA GPU device (simulated)".to_string());
-            }
-            GPUBackend::HIP => {
-                // Initialize HIP resources
-                self.device_info = Some("ROCm GPU device (simulated)".to_string());
-            }
-            GPUBackend::SYCL => {
-                // Initialize SYCL resources
-                self.device_info = Some("SYCL device (simulated)".to_string());
-            }
-            GPUBackend::CPUFallback => {
-                self.device_info = Some("CPU fallback device".to_string());
-            }
-        }
+        let requested = GpuBackend::from(self.config.backend);
+        let processor = GpuSparseFFT::with_backend(
+            self.config.device_id,
+            requested,
+            self.config.base_config.clone(),
+        )?;
+
+        let context = processor.context();
+        self.device_info = Some(if context.backend() != GpuBackend::Cpu {
+            format!(
+                "{} device {}",
+                context.backend(),
+                context.device_id().max(0)
+            )
+        } else if context.is_fallback() {
+            format!("CPU fallback device ({} not available)", requested)
+        } else {
+            "CPU fallback device".to_string()
+        });
+        self.gpu_processor = (context.backend() != GpuBackend::Cpu).then_some(processor);
 
         self.gpu_initialized = true;
         Ok(())
     }
 
+    /// Backend the computation runs on, after any fallback
+    pub fn active_backend(&mut self) -> FFTResult<GpuBackend> {
+        if !self.gpu_initialized {
+            self.initialize_gpu()?;
+        }
+        Ok(self
+            .gpu_processor
+            .as_ref()
+            .map_or(GpuBackend::Cpu, GpuSparseFFT::backend))
+    }
+
     /// Get GPU device information
     pub fn get_device_info(&mut self) -> FFTResult<String> {
         if !self.gpu_initialized {
@@ -133,9 +170,11 @@ impl GPUSparseFFT {
         if !self.gpu_initialized {
             self.initialize_gpu()?;
         }
+        if let Some(processor) = self.gpu_processor.as_mut() {
+            return processor.sparse_fft(signal);
+        }
 
-        // For demonstration purposes, use the CPU implementation
-        // In a real implementation, this would use the GPU
+        // No usable GPU backend: run the CPU implementation
         let start = Instant::now();
 
         // Convert input to complex for processing
@@ -149,8 +188,6 @@ impl GPUSparseFFT {
             })
             .collect::<FFTResult<Vec<_>>>()?;
 
-        // Create a CPU sparse FFT processor to handle computation for now
-        // This is a temporary solution until actual GPU implementation is provided
         let mut cpu_processor = crate::sparse_fft::SparseFFT::new(self.config.base_config.clone());
         let result = cpu_processor.sparse_fft(&signal_complex)?;
 
@@ -187,7 +224,10 @@ impl GPUSparseFFT {
 /// Performs GPU-accelerated sparse FFT on a signal
 ///
 /// This is a convenience function that creates a GPU sparse FFT processor
-/// with the specified backend and performs the computation.
+/// on the specified backend and performs the computation. The backend is
+/// either a [`GPUBackend`] or any `scirs2_core::gpu::GpuBackend` (e.g. Metal
+/// or WebGPU); if it is not usable on this machine, or cannot transform (see
+/// [`crate::backend_supports_fft`]), the transform runs on the CPU instead.
 ///
 /// # Arguments
 ///
@@ -200,39 +240,79 @@ impl GPUSparseFFT {
 /// # Returns
 ///
 /// * Sparse FFT result containing frequency components, indices, and timing information
-pub fn gpu_sparse_fft<T>(
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_core::gpu::GpuBackend;
+/// use scirs2_fft::gpu_sparse_fft;
+/// use std::f64::consts::PI;
+///
+/// let n = 256;
+/// let signal: Vec<f64> = (0..n)
+///     .map(|i| (2.0 * PI * 12.0 * i as f64 / n as f64).sin())
+///     .collect();
+///
+/// // Runs on Metal where available and on the CPU everywhere else
+/// let result = gpu_sparse_fft(&signal, 2, GpuBackend::Metal, None, None).unwrap();
+/// assert!(result.indices.contains(&12));
+/// ```
+pub fn gpu_sparse_fft<T, B>(
     signal: &[T],
     k: usize,
-    backend: GPUBackend,
+    backend: B,
     algorithm: Option<SparseFFTAlgorithm>,
     window_function: Option<WindowFunction>,
 ) -> FFTResult<SparseFFTResult>
 where
     T: NumCast + Copy + Debug + 'static,
+    B: Into<GpuBackend>,
 {
-    // Create a base configuration
-    let base_config = SparseFFTConfig {
+    let config = manual_config(k, algorithm, window_function);
+    let mut processor = GpuSparseFFT::with_backend(-1, backend.into(), config)?;
+    processor.sparse_fft(signal)
+}
+
+/// Performs GPU-accelerated 2-D sparse FFT on an image given as equally long rows
+///
+/// The backend is dispatched as in [`gpu_sparse_fft`]. Indices of the result
+/// are row-major, `row * cols + col`.
+pub fn gpu_sparse_fft2<T, B>(
+    signal: &[Vec<T>],
+    k: usize,
+    backend: B,
+    algorithm: Option<SparseFFTAlgorithm>,
+    window_function: Option<WindowFunction>,
+) -> FFTResult<SparseFFTResult>
+where
+    T: NumCast + Copy + Debug + 'static,
+    B: Into<GpuBackend>,
+{
+    let config = manual_config(k, algorithm, window_function);
+    let mut processor = GpuSparseFFT::with_backend(-1, backend.into(), config)?;
+    processor.sparse_fft2(signal)
+}
+
+/// Sparse FFT configuration with a fixed sparsity `k`
+fn manual_config(
+    k: usize,
+    algorithm: Option<SparseFFTAlgorithm>,
+    window_function: Option<WindowFunction>,
+) -> SparseFFTConfig {
+    SparseFFTConfig {
         estimation_method: SparsityEstimationMethod::Manual,
         sparsity: k,
         algorithm: algorithm.unwrap_or(SparseFFTAlgorithm::Sublinear),
         window_function: window_function.unwrap_or(WindowFunction::None),
         ..SparseFFTConfig::default()
-    };
-
-    // Create a GPU configuration
-    let gpu_config = GPUSparseFFTConfig {
-        base_config,
-        backend,
-        ..GPUSparseFFTConfig::default()
-    };
-
-    // Create processor and perform computation
-    let mut processor = GPUSparseFFT::new(gpu_config);
-    processor.sparse_fft(signal)
+    }
 }
 
 /// Perform GPU-accelerated batch processing of multiple signals
 ///
+/// The backend is dispatched as in [`gpu_sparse_fft`]; signals are spread over
+/// [`DEFAULT_MAX_IN_FLIGHT`] streams that are processed concurrently.
+///
 /// # Arguments
 ///
 /// * `signals` - List of input signals
@@ -244,36 +324,20 @@ where
 /// # Returns
 ///
 /// * List of sparse FFT results for each input signal
-pub fn gpu_batch_sparse_fft<T>(
+pub fn gpu_batch_sparse_fft<T, B>(
     signals: &[Vec<T>],
     k: usize,
-    backend: GPUBackend,
+    backend: B,
     algorithm: Option<SparseFFTAlgorithm>,
     window_function: Option<WindowFunction>,
 ) -> FFTResult<Vec<SparseFFTResult>>
 where
-    T: NumCast + Copy + Debug + 'static,
+    T: NumCast + Copy + Debug + Sync + 'static,
+    B: Into<GpuBackend>,
 {
-    // Create a base configuration
-    let base_config = SparseFFTConfig {
-        estimation_method: SparsityEstimationMethod::Manual,
-        sparsity: k,
-        algorithm: algorithm.unwrap_or(SparseFFTAlgorithm::Sublinear),
-        window_function: window_function.unwrap_or(WindowFunction::None),
-        ..SparseFFTConfig::default()
-    };
-
-    // Create a GPU configuration
-    let gpu_config = GPUSparseFFTConfig {
-        base_config,
-        backend,
-        batch_size: signals.len(),
-        ..GPUSparseFFTConfig::default()
-    };
-
-    // Create processor and perform batch computation
-    let mut processor = GPUSparseFFT::new(gpu_config);
-    processor.batch_sparse_fft(signals)
+    let config = manual_config(k, algorithm, window_function);
+    let mut processor = GpuSparseFFT::with_backend(-1, backend.into(), config)?;
+    processor.sparse_fft_batch(signals, DEFAULT_MAX_IN_FLIGHT)
 }
 
 #[cfg(test)]
@@ -296,7 +360,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "Ignored for alpha-4 release - GPU-dependent test"]
     fn test_gpu_sparse_fft_cpu_fallback() {
         // Create a signal with 3 frequency components
         let n = 256;
@@ -319,7 +382,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "Ignored for alpha-4 release - GPU-dependent test"]
     fn test_gpu_batch_processing() {
         // Create multiple signals
         let n = 128;
@@ -347,4 +409,33 @@ mod tests {
             assert!(!result.values.is_empty());
         }
     }
+
+    #[test]
+    fn test_runtime_backend_dispatch() {
+        let n = 256;
+        let signal = create_sparse_signal(n, &[(9, 1.0), (40, 0.5)]);
+
+        // Backends from either enum are accepted and agree with each other
+        let reference = gpu_sparse_fft(&signal, 4, GPUBackend::CPUFallback, None, None).unwrap();
+        for backend in [GpuBackend::Metal, GpuBackend::Wgpu, GpuBackend::Cuda] {
+            let result = gpu_sparse_fft(&signal, 4, backend, None, None).unwrap();
+            let mut indices = result.indices.clone();
+            indices.sort_unstable();
+            assert_eq!(indices, vec![9, 40, n - 40, n - 9]);
+            assert_eq!(result.values.len(), reference.values.len());
+        }
+
+        // The configured processor reports where it actually runs
+        let mut processor = GPUSparseFFT::new(GPUSparseFFTConfig {
+            backend: GPUBackend::HIP,
+            ..GPUSparseFFTConfig::default()
+        });
+        let active = processor.active_backend().unwrap();
+        let info = processor.get_device_info().unwrap();
+        if active == GpuBackend::Cpu {
+            assert!(info.starts_with("CPU fallback device"));
+        } else {
+            assert_eq!(active, GpuBackend::Rocm);
+        }
+    }
 }
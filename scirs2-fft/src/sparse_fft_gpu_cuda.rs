@@ -2,82 +2,336 @@
 //!
 //! This module provides GPU implementations of sparse FFT algorithms through
 //! the scirs2-core::gpu module. All direct GPU API calls are forbidden.
+//!
+//! Device memory is allocated as [`scirs2_core::gpu::GpuBuffer`]s of the
+//! context selected for the device, and signals and spectra are staged
+//! through them. On the core CPU backend the device buffers live in host
+//! memory and are transformed in place with rustfft. The scirs2-core kernel
+//! registry has no double-precision complex FFT kernel (its `fft_1d_forward`
+//! works on `f32` and there is no 2-D kernel), so the other backends cannot
+//! compute spectra yet: transforms on them fail with
+//! [`FFTError::NotImplementedError`] instead of silently running on the host
+//! (see [`backend_supports_fft`]).
+//!
+//! The backend (CUDA, ROCm, Metal, WebGPU, OpenCL or CPU) is chosen at run
+//! time. [`GpuContext::with_fallback`] probes the requested backend through the
+//! scirs2-core device capabilities and falls back to the CPU backend when it
+//! is not usable or cannot transform, so the same code runs unchanged on
+//! machines without that GPU and [`GpuContext::is_fallback`] reports it.
 
 use crate::error::{FFTError, FFTResult};
+use crate::sparse_fft::algorithms::{image_shape, largest_components, windowed_image};
+use crate::sparse_fft::windowing::apply_window;
 use crate::sparse_fft::{
     SparseFFTAlgorithm, SparseFFTConfig, SparseFFTResult, SparsityEstimationMethod, WindowFunction,
 };
 use num_complex::Complex64;
 use num_traits::NumCast;
-use scirs2_core::gpu::{GpuBackend, GpuDevice};
+use rustfft::FftPlanner;
+use scirs2_core::gpu::capabilities::{query_device_capabilities, GpuDeviceCapabilities};
+use scirs2_core::gpu::pinned::PinnedHostBuffer;
+use scirs2_core::gpu::{GpuBackend, GpuBuffer, GpuContext as CoreGpuContext, GpuDevice, GpuError};
+use scirs2_core::parallel_ops::*;
 use scirs2_core::simd_ops::PlatformCapabilities;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Instant;
 
-/// Placeholder for GPU buffer descriptor - to be implemented with core GPU abstractions
-#[allow(dead_code)]
+/// Signals in flight per batch by default (double-buffering)
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 2;
+
+fn gpu_error(err: GpuError) -> FFTError {
+    FFTError::ComputationError(format!("GPU error: {}", err))
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f64 {}
+    impl Sealed for num_complex::Complex64 {}
+}
+
+/// Element types that can be copied to and from device buffers as raw bytes
+///
+/// Sealed and implemented for `f64` and `Complex64` only: they have no
+/// padding bytes and every byte pattern is a valid value, so any bytes read
+/// back from a device buffer form valid elements.
+pub trait DeviceElement: Copy + private::Sealed {}
+
+impl DeviceElement for f64 {}
+impl DeviceElement for Complex64 {}
+
+/// View a slice of device elements as raw bytes
+fn as_bytes<T: DeviceElement>(data: &[T]) -> &[u8] {
+    // SAFETY: `T` has no padding (see `DeviceElement`), so all bytes of the
+    // slice are initialized, and the view borrows exactly its memory
+    unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data)) }
+}
+
+/// Mutable byte view of a slice of device elements
+fn as_bytes_mut<T: DeviceElement>(data: &mut [T]) -> &mut [u8] {
+    let len = std::mem::size_of_val(data);
+    // SAFETY: every byte pattern is a valid `T` (see `DeviceElement`), so the
+    // slice stays valid whatever is written through the view, which borrows
+    // exactly its memory
+    unsafe { std::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, len) }
+}
+
+/// Device buffer allocated through a [`GpuMemoryManager`]
+#[derive(Clone)]
 pub struct BufferDescriptor {
     size: usize,
     id: u64,
+    location: BufferLocation,
+    buffer_type: BufferType,
+    buffer: Arc<GpuBuffer<u8>>,
 }
 
-/// Placeholder for buffer location - to be implemented with core GPU abstractions  
+impl BufferDescriptor {
+    /// Size of the buffer in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Identifier of the allocation within its memory manager
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Where the buffer was requested to live
+    pub fn location(&self) -> BufferLocation {
+        self.location
+    }
+
+    /// Role of the buffer in the computation
+    pub fn buffer_type(&self) -> BufferType {
+        self.buffer_type
+    }
+}
+
+/// Buffer location
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BufferLocation {
+    /// Device memory
     Device,
+    /// Host memory visible to the device
     Host,
 }
 
-/// Placeholder for buffer type - to be implemented with core GPU abstractions
+/// Buffer role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BufferType {
+    /// Input data
     Input,
+    /// Output data
     Output,
+    /// Scratch space
     Work,
 }
 
-/// Placeholder for GPU stream - to be implemented with core GPU abstractions
-#[allow(dead_code)]
+static NEXT_STREAM_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Execution stream bound to a scirs2-core GPU context
+///
+/// Work submitted through one stream executes in submission order.
+#[derive(Clone)]
 pub struct GpuStream {
     id: u64,
+    device_id: i32,
+    context: Arc<CoreGpuContext>,
 }
 
 impl GpuStream {
-    pub fn new(_device_id: i32) -> FFTResult<Self> {
-        Err(FFTError::NotImplementedError(
-            "GPU streams need to be implemented with scirs2-core::gpu abstractions".to_string(),
-        ))
+    /// Create a stream on the preferred backend of the system
+    pub fn new(device_id: i32) -> FFTResult<Self> {
+        Self::with_backend(device_id, GpuBackend::preferred())
+    }
+
+    /// Create a stream on an explicit backend
+    pub fn with_backend(device_id: i32, backend: GpuBackend) -> FFTResult<Self> {
+        let context = CoreGpuContext::new(backend).map_err(gpu_error)?;
+        Ok(Self::on_context(device_id, Arc::new(context)))
+    }
+
+    /// Create an additional stream sharing an existing context
+    ///
+    /// Streams on the same context share its device memory, so buffers can be
+    /// handed from one stream to another.
+    pub fn on_context(device_id: i32, context: Arc<CoreGpuContext>) -> Self {
+        Self {
+            id: NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed),
+            device_id,
+            context,
+        }
+    }
+
+    /// Identifier of the stream
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Device the stream submits to
+    pub fn device_id(&self) -> i32 {
+        self.device_id
+    }
+
+    /// Backend executing the stream's work
+    pub fn backend(&self) -> GpuBackend {
+        self.context.backend()
+    }
+
+    /// Underlying scirs2-core context
+    pub fn context(&self) -> &Arc<CoreGpuContext> {
+        &self.context
+    }
+
+    /// Compute the forward FFT of the first `rows * cols` complex values of
+    /// `input` (a row-major `rows x cols` array, or a plain signal when
+    /// `rows == 1`), writing the spectrum to `output`
+    ///
+    /// Only the CPU backend, whose buffers are host memory, is supported;
+    /// the others return [`FFTError::NotImplementedError`] since scirs2-core
+    /// provides no double-precision complex FFT kernel to dispatch.
+    fn launch_fft(
+        &self,
+        input: &BufferDescriptor,
+        output: &BufferDescriptor,
+        (rows, cols): (usize, usize),
+    ) -> FFTResult<()> {
+        if !backend_supports_fft(self.backend()) {
+            return Err(FFTError::NotImplementedError(format!(
+                "sparse FFT is not supported on the {} backend: scirs2-core has no \
+                 double-precision complex FFT kernel for it",
+                self.backend()
+            )));
+        }
+        let n = rows * cols;
+        let bytes = n * std::mem::size_of::<Complex64>();
+        if bytes > input.size || bytes > output.size {
+            return Err(FFTError::DimensionError(format!(
+                "{} complex values do not fit the device buffers",
+                n
+            )));
+        }
+        let mut data = vec![Complex64::new(0.0, 0.0); n];
+        input.buffer.copy_to_host(as_bytes_mut(&mut data));
+        let mut planner = FftPlanner::new();
+        planner.plan_fft_forward(cols).process(&mut data);
+        if rows > 1 {
+            let column_fft = planner.plan_fft_forward(rows);
+            let mut column = vec![Complex64::new(0.0, 0.0); rows];
+            for c in 0..cols {
+                for (r, v) in column.iter_mut().enumerate() {
+                    *v = data[r * cols + c];
+                }
+                column_fft.process(&mut column);
+                for (r, &v) in column.iter().enumerate() {
+                    data[r * cols + c] = v;
+                }
+            }
+        }
+        output.buffer.copy_from_host(as_bytes(&data));
+        Ok(())
     }
 }
 
-/// Placeholder memory manager - to be implemented with core GPU abstractions
-pub struct GpuMemoryManager;
+/// Tracks device allocations made through a scirs2-core GPU context
+pub struct GpuMemoryManager {
+    context: Arc<CoreGpuContext>,
+    next_id: AtomicU64,
+    allocations: Mutex<HashMap<u64, usize>>,
+}
 
 impl GpuMemoryManager {
+    /// Create a memory manager allocating from `context`
+    pub fn new(context: Arc<CoreGpuContext>) -> Self {
+        Self {
+            context,
+            next_id: AtomicU64::new(0),
+            allocations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Allocate a zero-initialized buffer of `size` bytes
     pub fn allocate(
         &self,
-        _size: usize,
-        _location: BufferLocation,
-        _buffer_type: BufferType,
+        size: usize,
+        location: BufferLocation,
+        buffer_type: BufferType,
     ) -> FFTResult<BufferDescriptor> {
-        Err(FFTError::NotImplementedError(
-            "GPU memory management needs to be implemented with scirs2-core::gpu abstractions"
-                .to_string(),
-        ))
+        if let Some(available) = self.context.get_available_memory() {
+            if size > available {
+                return Err(FFTError::MemoryError(format!(
+                    "Requested {} bytes but only {} bytes of device memory are available",
+                    size, available
+                )));
+            }
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let buffer = Arc::new(self.context.create_buffer::<u8>(size));
+        self.lock_allocations()?.insert(id, size);
+
+        Ok(BufferDescriptor {
+            size,
+            id,
+            location,
+            buffer_type,
+            buffer,
+        })
     }
 
-    pub fn free(&self, _descriptor: BufferDescriptor) -> FFTResult<()> {
-        Err(FFTError::NotImplementedError(
-            "GPU memory management needs to be implemented with scirs2-core::gpu abstractions"
-                .to_string(),
-        ))
+    /// Release a buffer allocated by this manager
+    pub fn free(&self, descriptor: BufferDescriptor) -> FFTResult<()> {
+        match self.lock_allocations()?.remove(&descriptor.id) {
+            Some(_) => Ok(()),
+            None => Err(FFTError::MemoryError(format!(
+                "Buffer {} was not allocated by this memory manager",
+                descriptor.id
+            ))),
+        }
+    }
+
+    /// Allocate a page-locked host staging buffer of `len` values
+    ///
+    /// Transfers from pinned memory can run asynchronously with kernels on
+    /// other streams. Falls back to pageable memory when the pages cannot be
+    /// locked (and always on the CPU backend, which performs no DMA).
+    pub fn allocate_pinned(&self, len: usize) -> FFTResult<PinnedHostBuffer<f64>> {
+        self.context.allocate_pinned(len).map_err(gpu_error)
+    }
+
+    /// Total bytes currently allocated
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocations
+            .lock()
+            .map(|a| a.values().sum())
+            .unwrap_or(0)
+    }
+
+    /// Number of live allocations
+    pub fn allocation_count(&self) -> usize {
+        self.allocations.lock().map(|a| a.len()).unwrap_or(0)
+    }
+
+    fn lock_allocations(&self) -> FFTResult<std::sync::MutexGuard<'_, HashMap<u64, usize>>> {
+        self.allocations
+            .lock()
+            .map_err(|_| FFTError::MemoryError("GPU allocation table is poisoned".to_string()))
     }
 }
 
-/// Placeholder for global memory manager - to be implemented with core GPU abstractions
-pub fn get_global_memory_manager() -> FFTResult<GpuMemoryManager> {
-    Err(FFTError::NotImplementedError(
-        "GPU memory management needs to be implemented with scirs2-core::gpu abstractions"
-            .to_string(),
-    ))
+/// Memory manager on the preferred backend, shared by the whole process
+pub fn get_global_memory_manager() -> FFTResult<Arc<GpuMemoryManager>> {
+    static MANAGER: OnceLock<Arc<GpuMemoryManager>> = OnceLock::new();
+    if let Some(manager) = MANAGER.get() {
+        return Ok(manager.clone());
+    }
+    let context = CoreGpuContext::new(GpuBackend::preferred()).map_err(gpu_error)?;
+    Ok(MANAGER
+        .get_or_init(|| Arc::new(GpuMemoryManager::new(Arc::new(context))))
+        .clone())
 }
 
 /// Check if GPU is available through core platform capabilities
@@ -86,6 +340,48 @@ pub fn ensure_gpu_available() -> FFTResult<bool> {
     Ok(caps.cuda_available || caps.gpu_available)
 }
 
+/// Whether sparse FFTs can be computed on `backend`
+///
+/// Only the scirs2-core CPU backend can: the kernel registry has no
+/// double-precision complex FFT kernel for the device backends yet.
+pub fn backend_supports_fft(backend: GpuBackend) -> bool {
+    backend == GpuBackend::Cpu
+}
+
+/// Capabilities of device `device_id` on `backend`, if the backend is usable
+///
+/// A backend is usable when scirs2-core was built with it and device
+/// detection found the device. The CPU backend is always usable.
+pub fn probe_gpu_backend(backend: GpuBackend, device_id: usize) -> Option<GpuDeviceCapabilities> {
+    if !backend.is_available() {
+        return None;
+    }
+    match query_device_capabilities(backend, device_id) {
+        Ok(caps) => Some(caps),
+        Err(_) if backend == GpuBackend::Cpu => {
+            Some(GpuDeviceCapabilities::generic(backend, device_id))
+        }
+        Err(_) => None,
+    }
+}
+
+/// Backends with at least one usable device, in order of preference
+///
+/// The CPU backend is always listed last.
+pub fn available_gpu_backends() -> Vec<GpuBackend> {
+    [
+        GpuBackend::Cuda,
+        GpuBackend::Rocm,
+        GpuBackend::Metal,
+        GpuBackend::Wgpu,
+        GpuBackend::OpenCL,
+        GpuBackend::Cpu,
+    ]
+    .into_iter()
+    .filter(|&backend| probe_gpu_backend(backend, 0).is_some())
+    .collect()
+}
+
 /// GPU device information using core abstractions
 pub struct GpuDeviceInfo {
     /// Device wrapped from core GPU module
@@ -111,7 +407,6 @@ impl GpuDeviceInfo {
 }
 
 /// GPU context for FFT operations using core abstractions
-#[allow(dead_code)]
 pub struct GpuContext {
     /// Device ID
     device_id: i32,
@@ -119,29 +414,75 @@ pub struct GpuContext {
     device_info: GpuDeviceInfo,
     /// GPU stream
     stream: GpuStream,
+    /// Allocations made on the stream's context
+    memory: GpuMemoryManager,
     /// Whether the context is initialized
     initialized: bool,
+    /// Backend the caller asked for, which differs from the stream's backend
+    /// after a fallback
+    requested_backend: GpuBackend,
 }
 
 impl GpuContext {
-    /// Create a new CUDA context for the specified device
+    /// Create a new context for the specified device on the preferred backend
     pub fn new(device_id: i32) -> FFTResult<Self> {
-        // In a real implementation, this would query the device and initialize CUDA
+        Self::from_stream(GpuStream::new(device_id)?)
+    }
 
-        // Create device info using core abstractions
-        let device_info = GpuDeviceInfo::new(device_id as usize)?;
+    /// Create a new context for the specified device on an explicit backend
+    pub fn with_backend(device_id: i32, backend: GpuBackend) -> FFTResult<Self> {
+        Self::from_stream(GpuStream::with_backend(device_id, backend)?)
+    }
+
+    /// Create a context on `backend`, falling back to the CPU backend
+    ///
+    /// The backend is probed first (see [`probe_gpu_backend`]); if the device
+    /// is not found, the backend cannot transform (see
+    /// [`backend_supports_fft`]) or its context cannot be created, the context
+    /// runs on the scirs2-core CPU backend instead. [`GpuContext::is_fallback`]
+    /// tells the two cases apart.
+    pub fn with_fallback(device_id: i32, backend: GpuBackend) -> FFTResult<Self> {
+        let stream = probe_gpu_backend(backend, device_id.max(0) as usize)
+            .filter(|_| backend_supports_fft(backend))
+            .and_then(|_| GpuStream::with_backend(device_id, backend).ok());
+        let stream = match stream {
+            Some(stream) => stream,
+            None => GpuStream::with_backend(device_id, GpuBackend::Cpu)?,
+        };
 
-        // Create stream
-        let stream = GpuStream::new(device_id)?;
+        let mut context = Self::from_stream(stream)?;
+        context.requested_backend = backend;
+        Ok(context)
+    }
+
+    fn from_stream(stream: GpuStream) -> FFTResult<Self> {
+        let device_id = stream.device_id();
+        let device_info = GpuDeviceInfo {
+            device: GpuDevice::new(stream.backend(), device_id.max(0) as usize),
+            initialized: true,
+        };
+        let memory = GpuMemoryManager::new(stream.context().clone());
 
         Ok(Self {
             device_id,
             device_info,
+            requested_backend: stream.backend(),
             stream,
+            memory,
             initialized: true,
         })
     }
 
+    /// Device ID
+    pub fn device_id(&self) -> i32 {
+        self.device_id
+    }
+
+    /// Whether the context is initialized
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
     /// Get device information
     pub fn device_info(&self) -> &GpuDeviceInfo {
         &self.device_info
@@ -152,35 +493,44 @@ impl GpuContext {
         &self.stream
     }
 
-    /// Allocate device memory
-    pub fn allocate(&self, size_bytes: usize) -> FFTResult<BufferDescriptor> {
-        // In a real implementation, this would call cudaMalloc
+    /// Backend executing this context's work
+    pub fn backend(&self) -> GpuBackend {
+        self.stream.backend()
+    }
 
-        // Use the global memory manager to track allocations
-        let manager = get_global_memory_manager()?;
+    /// Backend requested when the context was created
+    pub fn requested_backend(&self) -> GpuBackend {
+        self.requested_backend
+    }
 
-        manager.allocate(size_bytes, BufferLocation::Device, BufferType::Work)
+    /// Whether the context fell back to the CPU because the requested backend
+    /// was not usable or cannot transform
+    pub fn is_fallback(&self) -> bool {
+        self.backend() != self.requested_backend
     }
 
-    /// Free device memory
-    pub fn free(&self, descriptor: BufferDescriptor) -> FFTResult<()> {
-        // In a real implementation, this would call cudaFree
+    /// Memory manager tracking this context's allocations
+    pub fn memory_manager(&self) -> &GpuMemoryManager {
+        &self.memory
+    }
 
-        // Use the global memory manager to track allocations
-        let manager = get_global_memory_manager()?;
+    /// Allocate device memory
+    pub fn allocate(&self, size_bytes: usize) -> FFTResult<BufferDescriptor> {
+        self.memory
+            .allocate(size_bytes, BufferLocation::Device, BufferType::Work)
+    }
 
-        manager.free(descriptor)
+    /// Free device memory
+    pub fn free(&self, descriptor: BufferDescriptor) -> FFTResult<()> {
+        self.memory.free(descriptor)
     }
 
     /// Copy data from host to device
-    pub fn copy_host_to_device<T>(
+    pub fn copy_host_to_device<T: DeviceElement>(
         &self,
         host_data: &[T],
         device_buffer: &BufferDescriptor,
     ) -> FFTResult<()> {
-        // In a real implementation, this would call cudaMemcpy
-
-        // Check if sizes match
         let host_size_bytes = std::mem::size_of_val(host_data);
         let device_size_bytes = device_buffer.size;
 
@@ -191,18 +541,16 @@ impl GpuContext {
             )));
         }
 
+        device_buffer.buffer.copy_from_host(as_bytes(host_data));
         Ok(())
     }
 
     /// Copy data from device to host
-    pub fn copy_device_to_host<T>(
+    pub fn copy_device_to_host<T: DeviceElement>(
         &self,
         device_buffer: &BufferDescriptor,
         host_data: &mut [T],
     ) -> FFTResult<()> {
-        // In a real implementation, this would call cudaMemcpy
-
-        // Check if sizes match
         let host_size_bytes = std::mem::size_of_val(host_data);
         let device_size_bytes = device_buffer.size;
 
@@ -213,176 +561,295 @@ impl GpuContext {
             )));
         }
 
+        device_buffer
+            .buffer
+            .copy_to_host(&mut as_bytes_mut(host_data)[..device_size_bytes]);
         Ok(())
     }
 }
 
-/// CUDA-accelerated sparse FFT implementation
+/// Staging and device buffers owned by one stream
+///
+/// Each slot stages signals through its own pinned host buffer and device
+/// buffers, so slots on different streams can transfer and compute
+/// independently of each other.
+struct StreamSlot {
+    stream: GpuStream,
+    /// Interleaved real/imaginary parts of up to `capacity` complex values
+    staging: PinnedHostBuffer<f64>,
+    input: BufferDescriptor,
+    output: BufferDescriptor,
+    capacity: usize,
+}
+
+impl StreamSlot {
+    fn new(memory: &GpuMemoryManager, stream: GpuStream, capacity: usize) -> FFTResult<Self> {
+        let bytes = capacity * std::mem::size_of::<Complex64>();
+        let staging = memory.allocate_pinned(2 * capacity)?;
+        let input = memory.allocate(bytes, BufferLocation::Device, BufferType::Input)?;
+        let output = match memory.allocate(bytes, BufferLocation::Device, BufferType::Output) {
+            Ok(output) => output,
+            Err(e) => {
+                memory.free(input)?;
+                return Err(e);
+            }
+        };
+        Ok(Self {
+            stream,
+            staging,
+            input,
+            output,
+            capacity,
+        })
+    }
+
+    fn release(self, memory: &GpuMemoryManager) -> FFTResult<()> {
+        memory.free(self.input)?;
+        memory.free(self.output)
+    }
+
+    /// Window, upload, transform and download one signal, then keep the
+    /// `sparsity` components of largest magnitude
+    fn run<T>(&mut self, signal: &[T], config: &SparseFFTConfig) -> FFTResult<SparseFFTResult>
+    where
+        T: NumCast + Copy + Debug + 'static,
+    {
+        let start = Instant::now();
+        let windowed = apply_window(signal, config.window_function, config.kaiser_beta)?;
+        let spectrum = self.transform(&windowed, (1, signal.len()))?;
+        Ok(dominant_components(&spectrum, config, start))
+    }
+
+    /// Stage `data` in pinned memory, copy it to the device, transform it
+    /// (see [`GpuStream::launch_fft`]) and copy the spectrum back
+    fn transform(
+        &mut self,
+        data: &[Complex64],
+        shape: (usize, usize),
+    ) -> FFTResult<Vec<Complex64>> {
+        let staged = &mut self.staging[..2 * data.len()];
+        for (pair, v) in staged.chunks_exact_mut(2).zip(data.iter()) {
+            pair[0] = v.re;
+            pair[1] = v.im;
+        }
+        self.input.buffer.copy_from_host(as_bytes(staged));
+        self.stream.launch_fft(&self.input, &self.output, shape)?;
+        self.output.buffer.copy_to_host(as_bytes_mut(staged));
+        Ok(staged
+            .chunks_exact(2)
+            .map(|pair| Complex64::new(pair[0], pair[1]))
+            .collect())
+    }
+}
+
+/// Result holding the `sparsity` components of largest magnitude of `spectrum`
+fn dominant_components(
+    spectrum: &[Complex64],
+    config: &SparseFFTConfig,
+    start: Instant,
+) -> SparseFFTResult {
+    let k = config.sparsity.min(spectrum.len());
+    let (values, indices) = largest_components(spectrum, k, 0.0);
+    SparseFFTResult {
+        values,
+        indices,
+        estimated_sparsity: k,
+        computation_time: start.elapsed(),
+        algorithm: config.algorithm,
+    }
+}
+
+/// GPU-accelerated sparse FFT running on the backend of its context
 pub struct GpuSparseFFT {
-    /// CUDA context
+    /// Context on the backend selected at run time
     context: GpuContext,
     /// Sparse FFT configuration
     config: SparseFFTConfig,
-    /// Buffer for input signal on device
-    input_buffer: Option<BufferDescriptor>,
-    /// Buffer for output values on device
-    output_values_buffer: Option<BufferDescriptor>,
-    /// Buffer for output indices on device
-    output_indices_buffer: Option<BufferDescriptor>,
+    /// Buffers for single-signal transforms on the context's stream
+    slot: Option<StreamSlot>,
 }
 
 impl GpuSparseFFT {
-    /// Create a new CUDA-accelerated sparse FFT processor
+    /// Create a new processor on the preferred backend of the system
     pub fn new(device_id: i32, config: SparseFFTConfig) -> FFTResult<Self> {
-        // GPU device initialization handled by core GPU abstractions
-        // TODO: Use scirs2-core::gpu device initialization
+        Self::with_backend(device_id, GpuBackend::preferred(), config)
+    }
 
-        // Initialize CUDA context
-        let context = GpuContext::new(device_id)?;
+    /// Create a new processor on `backend`, falling back to the CPU backend
+    /// when it is not usable or cannot transform (see
+    /// [`GpuContext::with_fallback`])
+    pub fn with_backend(
+        device_id: i32,
+        backend: GpuBackend,
+        config: SparseFFTConfig,
+    ) -> FFTResult<Self> {
+        Ok(Self::with_context(
+            GpuContext::with_fallback(device_id, backend)?,
+            config,
+        ))
+    }
 
-        Ok(Self {
+    /// Create a processor running on an existing context
+    pub fn with_context(context: GpuContext, config: SparseFFTConfig) -> Self {
+        Self {
             context,
             config,
-            input_buffer: None,
-            output_values_buffer: None,
-            output_indices_buffer: None,
-        })
+            slot: None,
+        }
+    }
+
+    /// Context the processor runs on
+    pub fn context(&self) -> &GpuContext {
+        &self.context
     }
 
-    /// Initialize buffers for the given signal size
+    /// Backend the processor runs on
+    pub fn backend(&self) -> GpuBackend {
+        self.context.backend()
+    }
+
+    /// Make sure the device buffers can hold `signal_size` complex values
     fn initialize_buffers(&mut self, signal_size: usize) -> FFTResult<()> {
-        // Free existing buffers if any
+        if self
+            .slot
+            .as_ref()
+            .is_some_and(|slot| slot.capacity >= signal_size)
+        {
+            return Ok(());
+        }
         self.free_buffers()?;
 
-        // Get memory manager
-        let memory_manager = get_global_memory_manager()?;
-
-        // Allocate input buffer
-        let input_buffer = memory_manager.allocate(
-            signal_size * std::mem::size_of::<Complex64>(),
-            BufferLocation::Device,
-            BufferType::Input,
-        )?;
-        self.input_buffer = Some(input_buffer);
-
-        // Allocate output buffers (assuming worst case: all components are significant)
-        let max_components = self.config.sparsity.min(signal_size);
-
-        let output_values_buffer = memory_manager.allocate(
-            max_components * std::mem::size_of::<Complex64>(),
-            BufferLocation::Device,
-            BufferType::Output,
-        )?;
-        self.output_values_buffer = Some(output_values_buffer);
-
-        let output_indices_buffer = memory_manager.allocate(
-            max_components * std::mem::size_of::<usize>(),
-            BufferLocation::Device,
-            BufferType::Output,
-        )?;
-        self.output_indices_buffer = Some(output_indices_buffer);
-
+        let stream = self.context.stream().clone();
+        self.slot = Some(StreamSlot::new(
+            self.context.memory_manager(),
+            stream,
+            signal_size,
+        )?);
         Ok(())
     }
 
     /// Free all buffers
     fn free_buffers(&mut self) -> FFTResult<()> {
-        if let Ok(memory_manager) = get_global_memory_manager() {
-            if let Some(buffer) = self.input_buffer.take() {
-                memory_manager.free(buffer)?;
-            }
-
-            if let Some(buffer) = self.output_values_buffer.take() {
-                memory_manager.free(buffer)?;
-            }
-
-            if let Some(buffer) = self.output_indices_buffer.take() {
-                memory_manager.free(buffer)?;
-            }
+        match self.slot.take() {
+            Some(slot) => slot.release(self.context.memory_manager()),
+            None => Ok(()),
         }
-
-        Ok(())
     }
 
     /// Perform sparse FFT on a signal
+    ///
+    /// The windowed signal is staged in pinned host memory, copied to the
+    /// device and transformed (with rustfft on the host copy, see the module
+    /// documentation). The spectrum is copied back
+    /// to pick the `sparsity` components of largest magnitude, in decreasing
+    /// order of magnitude.
     pub fn sparse_fft<T>(&mut self, signal: &[T]) -> FFTResult<SparseFFTResult>
     where
         T: NumCast + Copy + Debug + 'static,
     {
-        let start = Instant::now();
-
-        // Initialize buffers
+        if signal.is_empty() {
+            return Err(FFTError::ValueError("Input signal is empty".to_string()));
+        }
         self.initialize_buffers(signal.len())?;
+        match self.slot.as_mut() {
+            Some(slot) => slot.run(signal, &self.config),
+            None => Err(FFTError::MemoryError(
+                "Device buffers not initialized".to_string(),
+            )),
+        }
+    }
 
-        // Convert input to complex
-        let signal_complex: Vec<Complex64> = signal
-            .iter()
-            .map(|&val| {
-                let val_f64 = NumCast::from(val).ok_or_else(|| {
-                    FFTError::ValueError(format!("Could not convert {:?} to f64", val))
-                })?;
-                Ok(Complex64::new(val_f64, 0.0))
-            })
-            .collect::<FFTResult<Vec<_>>>()?;
-
-        // Copy the input signal to the device
-        if let Some(input_buffer) = &self.input_buffer {
-            self.context
-                .copy_host_to_device(&signal_complex, input_buffer)?;
-        } else {
-            return Err(FFTError::MemoryError(
-                "Input buffer not initialized".to_string(),
+    /// Perform 2-D sparse FFT on an image given as equally long rows
+    ///
+    /// The image is windowed separably, staged through the device buffers and
+    /// transformed in 2-D, and the `sparsity` components of largest magnitude are
+    /// returned with row-major indices `row * cols + col`.
+    pub fn sparse_fft2<T>(&mut self, signal: &[Vec<T>]) -> FFTResult<SparseFFTResult>
+    where
+        T: NumCast + Copy + Debug + 'static,
+    {
+        let start = Instant::now();
+        let shape = image_shape(signal)?;
+        let image = windowed_image(signal, &self.config)?;
+        self.initialize_buffers(image.len())?;
+        match self.slot.as_mut() {
+            Some(slot) => {
+                let spectrum = slot.transform(&image, shape)?;
+                Ok(dominant_components(&spectrum, &self.config, start))
+            }
+            None => Err(FFTError::MemoryError(
+                "Device buffers not initialized".to_string(),
+            )),
+        }
+    }
+
+    /// Perform sparse FFT on a batch of signals on concurrent streams
+    ///
+    /// Up to `max_in_flight` signals are processed at once, each on its own
+    /// stream with a pinned staging buffer and its own device buffers, so the
+    /// staging and transform of one signal run alongside those of another.
+    /// Two signals in flight amount to double-buffering; one processes the
+    /// batch serially.
+    /// Results are returned in input order.
+    pub fn sparse_fft_batch<T>(
+        &mut self,
+        signals: &[Vec<T>],
+        max_in_flight: usize,
+    ) -> FFTResult<Vec<SparseFFTResult>>
+    where
+        T: NumCast + Copy + Debug + Sync + 'static,
+    {
+        if max_in_flight == 0 {
+            return Err(FFTError::ValueError(
+                "At least one signal must be in flight".to_string(),
             ));
         }
+        if signals.iter().any(|s| s.is_empty()) {
+            return Err(FFTError::ValueError("Input signal is empty".to_string()));
+        }
+        if signals.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        // Use the appropriate kernel based on the algorithm
-        let result = match self.config.algorithm {
-            SparseFFTAlgorithm::Sublinear => crate::execute_cuda_sublinear_sparse_fft(
-                &signal_complex,
-                self.config.sparsity,
-                self.config.algorithm,
-            )?,
-            SparseFFTAlgorithm::CompressedSensing => {
-                crate::execute_cuda_compressed_sensing_sparse_fft(
-                    &signal_complex,
-                    self.config.sparsity,
-                )?
+        let in_flight = max_in_flight.min(signals.len());
+        let capacity = signals.iter().map(Vec::len).max().unwrap_or(0);
+        let memory = self.context.memory_manager();
+        let core_context = self.context.stream().context();
+
+        let mut slots = Vec::with_capacity(in_flight);
+        for _ in 0..in_flight {
+            let stream = GpuStream::on_context(self.context.device_id(), core_context.clone());
+            match StreamSlot::new(memory, stream, capacity) {
+                Ok(slot) => slots.push(slot),
+                Err(e) => {
+                    for slot in slots {
+                        slot.release(memory)?;
+                    }
+                    return Err(e);
+                }
             }
-            SparseFFTAlgorithm::Iterative => {
-                crate::execute_cuda_iterative_sparse_fft(
-                    &signal_complex,
-                    self.config.sparsity,
-                    100, // Default number of iterations
-                )?
-            }
-            SparseFFTAlgorithm::FrequencyPruning => {
-                crate::execute_cuda_frequency_pruning_sparse_fft(
-                    &signal_complex,
-                    self.config.sparsity,
-                    0.01, // Default threshold
-                )?
-            }
-            SparseFFTAlgorithm::SpectralFlatness => {
-                crate::execute_cuda_spectral_flatness_sparse_fft(
-                    &signal_complex,
-                    self.config.sparsity,
-                    self.config.flatness_threshold,
-                )?
-            }
-            // For other algorithms, fall back to CPU implementation for now
-            _ => {
-                let mut cpu_processor = crate::sparse_fft::SparseFFT::new(self.config.clone());
-                let mut cpu_result = cpu_processor.sparse_fft(&signal_complex)?;
+        }
 
-                // Update the computation time and algorithm
-                cpu_result.computation_time = start.elapsed();
-                cpu_result.algorithm = self.config.algorithm;
+        // Slot `lane` handles signals lane, lane + in_flight, ...
+        let config = &self.config;
+        let outcome: FFTResult<Vec<Vec<(usize, SparseFFTResult)>>> = slots
+            .par_iter_mut()
+            .enumerate()
+            .map(|(lane, slot)| {
+                (lane..signals.len())
+                    .step_by(in_flight)
+                    .map(|i| Ok((i, slot.run(&signals[i], config)?)))
+                    .collect()
+            })
+            .collect();
 
-                cpu_result
-            }
-        };
+        for slot in slots {
+            slot.release(memory)?;
+        }
 
-        Ok(result)
+        let mut indexed: Vec<(usize, SparseFFTResult)> = outcome?.into_iter().flatten().collect();
+        indexed.sort_by_key(|&(i, _)| i);
+        Ok(indexed.into_iter().map(|(_, result)| result).collect())
     }
 }
 
@@ -395,8 +862,9 @@ impl Drop for GpuSparseFFT {
 
 /// Perform CUDA-accelerated sparse FFT
 ///
-/// This is a convenience function that creates a CUDA sparse FFT processor
-/// and performs the computation.
+/// Shorthand for [`crate::gpu_sparse_fft`] on the CUDA backend for a chosen
+/// device. Falls back to the CPU when CUDA is not usable or cannot transform
+/// (see [`backend_supports_fft`]).
 ///
 /// # Arguments
 ///
@@ -420,13 +888,6 @@ pub fn cuda_sparse_fft<T>(
 where
     T: NumCast + Copy + Debug + 'static,
 {
-    // Check if GPU is available
-    if !ensure_gpu_available()? {
-        return Err(FFTError::ComputationError(
-            "GPU is not available. Either GPU features are not enabled or GPU hardware/drivers are not available.".to_string()
-        ));
-    }
-
     // Create a base configuration
     let config = SparseFFTConfig {
         estimation_method: SparsityEstimationMethod::Manual,
@@ -436,17 +897,57 @@ where
         ..SparseFFTConfig::default()
     };
 
-    // GPU memory manager initialization handled by core GPU abstractions
-    // TODO: Use scirs2-core::gpu memory management initialization
-
     // Create processor and perform computation
-    let mut processor = GpuSparseFFT::new(device_id, config)?;
+    let mut processor = GpuSparseFFT::with_backend(device_id, GpuBackend::Cuda, config)?;
     processor.sparse_fft(signal)
 }
 
+/// Perform CUDA-accelerated 2-D sparse FFT
+///
+/// Shorthand for [`crate::gpu_sparse_fft2`] on the CUDA backend for a chosen
+/// device. Falls back to the CPU when CUDA is not usable or cannot transform
+/// (see [`backend_supports_fft`]).
+///
+/// # Arguments
+///
+/// * `signal` - Rows of the input image
+/// * `k` - Expected sparsity (number of significant frequency components)
+/// * `device_id` - CUDA device ID (-1 for auto-select)
+/// * `algorithm` - Sparse FFT algorithm variant
+/// * `window_function` - Window function applied along both axes
+///
+/// # Returns
+///
+/// * Sparse FFT result with row-major indices `row * cols + col`
+pub fn cuda_sparse_fft2<T>(
+    signal: &[Vec<T>],
+    k: usize,
+    device_id: i32,
+    algorithm: Option<SparseFFTAlgorithm>,
+    window_function: Option<WindowFunction>,
+) -> FFTResult<SparseFFTResult>
+where
+    T: NumCast + Copy + Debug + 'static,
+{
+    let config = SparseFFTConfig {
+        estimation_method: SparsityEstimationMethod::Manual,
+        sparsity: k,
+        algorithm: algorithm.unwrap_or(SparseFFTAlgorithm::Sublinear),
+        window_function: window_function.unwrap_or(WindowFunction::None),
+        ..SparseFFTConfig::default()
+    };
+
+    let mut processor = GpuSparseFFT::with_backend(device_id, GpuBackend::Cuda, config)?;
+    processor.sparse_fft2(signal)
+}
+
 /// Perform batch CUDA-accelerated sparse FFT
 ///
-/// Process multiple signals in batch mode for better GPU utilization.
+/// Process multiple signals in batch mode for better GPU utilization on the
+/// CUDA backend, falling back to the CPU when CUDA is not usable or cannot
+/// transform (see [`backend_supports_fft`]). Host-device transfers go through
+/// pinned staging buffers and the signals are spread over several streams
+/// that are processed concurrently.
 ///
 /// # Arguments
 ///
@@ -455,6 +956,8 @@ where
 /// * `device_id` - CUDA device ID (-1 for auto-select)
 /// * `algorithm` - Sparse FFT algorithm variant
 /// * `window_function` - Window function to apply before FFT
+/// * `max_in_flight` - Number of signals processed concurrently, each on its
+///   own stream (default: [`DEFAULT_MAX_IN_FLIGHT`], i.e. double-buffering)
 ///
 /// # Returns
 ///
@@ -466,9 +969,10 @@ pub fn cuda_batch_sparse_fft<T>(
     device_id: i32,
     algorithm: Option<SparseFFTAlgorithm>,
     window_function: Option<WindowFunction>,
+    max_in_flight: Option<usize>,
 ) -> FFTResult<Vec<SparseFFTResult>>
 where
-    T: NumCast + Copy + Debug + 'static,
+    T: NumCast + Copy + Debug + Sync + 'static,
 {
     // Create a base configuration
     let config = SparseFFTConfig {
@@ -480,30 +984,22 @@ where
     };
 
     // Create processor
-    let mut processor = GpuSparseFFT::new(device_id, config)?;
-
-    // Process each signal
-    let mut results = Vec::with_capacity(signals.len());
-    for signal in signals {
-        results.push(processor.sparse_fft(signal)?);
-    }
-
-    Ok(results)
+    let mut processor = GpuSparseFFT::with_backend(device_id, GpuBackend::Cuda, config)?;
+    processor.sparse_fft_batch(signals, max_in_flight.unwrap_or(DEFAULT_MAX_IN_FLIGHT))
 }
 
 /// Initialize GPU subsystem and get available GPU devices
+///
+/// Lists the CUDA devices found by the scirs2-core device detection; the list
+/// is empty when CUDA is not usable.
 pub fn get_cuda_devices() -> FFTResult<Vec<GpuDeviceInfo>> {
-    // In a real implementation, this would query all available GPU devices through scirs2-core
-
-    // First check if GPU is available
-    if !ensure_gpu_available().unwrap_or(false) {
-        return Ok(Vec::new());
-    }
-
-    // For now, return dummy data until actual GPU implementation is complete
-    let devices = vec![GpuDeviceInfo::new(0)?];
-
-    Ok(devices)
+    Ok((0..)
+        .map_while(|id| probe_gpu_backend(GpuBackend::Cuda, id))
+        .map(|caps| GpuDeviceInfo {
+            device: GpuDevice::new(GpuBackend::Cuda, caps.device_id),
+            initialized: true,
+        })
+        .collect())
 }
 
 // Note: is_cuda_available() is now provided by sparse_fft_gpu_memory module
@@ -528,6 +1024,171 @@ mod tests {
         signal
     }
 
+    #[test]
+    fn test_device_buffers_and_sparse_fft_on_core_context() {
+        let context = GpuContext::with_backend(0, GpuBackend::Cpu).unwrap();
+        assert_eq!(context.backend(), GpuBackend::Cpu);
+
+        // Host -> device -> host round trip through a real allocation
+        let data: Vec<Complex64> = (0..8).map(|i| Complex64::new(i as f64, -1.0)).collect();
+        let buffer = context
+            .allocate(8 * std::mem::size_of::<Complex64>())
+            .unwrap();
+        context.copy_host_to_device(&data, &buffer).unwrap();
+        let mut back = vec![Complex64::new(0.0, 0.0); 8];
+        context.copy_device_to_host(&buffer, &mut back).unwrap();
+        assert_eq!(back, data);
+        assert!(context.copy_host_to_device(&[0.0f64; 32], &buffer).is_err());
+        assert_eq!(context.memory_manager().allocation_count(), 1);
+        context.free(buffer.clone()).unwrap();
+        assert!(context.free(buffer).is_err());
+
+        let n = 256;
+        let signal = create_sparse_signal(n, &[(3, 1.0), (7, 0.5), (15, 0.25)]);
+        let config = SparseFFTConfig {
+            estimation_method: SparsityEstimationMethod::Manual,
+            sparsity: 6,
+            ..SparseFFTConfig::default()
+        };
+        let mut processor = GpuSparseFFT::with_context(context, config);
+        let result = processor.sparse_fft(&signal).unwrap();
+        assert_eq!(result.indices.len(), 6);
+        let mut found = result.indices.clone();
+        found.sort_unstable();
+        assert_eq!(found, vec![3, 7, 15, n - 15, n - 7, n - 3]);
+        assert!((result.values[0].norm() - n as f64 / 2.0).abs() < 1e-9);
+        assert_eq!(processor.context().memory_manager().allocation_count(), 2);
+    }
+
+    #[test]
+    fn test_backend_dispatch_with_fallback() {
+        let backends = available_gpu_backends();
+        assert_eq!(backends.last(), Some(&GpuBackend::Cpu));
+        assert!(probe_gpu_backend(GpuBackend::Cpu, 0).is_some());
+
+        let n = 128;
+        let signal = create_sparse_signal(n, &[(5, 1.0), (11, 0.5)]);
+        let config = SparseFFTConfig {
+            estimation_method: SparsityEstimationMethod::Manual,
+            sparsity: 4,
+            ..SparseFFTConfig::default()
+        };
+
+        let reference = {
+            let mut processor =
+                GpuSparseFFT::with_backend(0, GpuBackend::Cpu, config.clone()).unwrap();
+            assert!(!processor.context().is_fallback());
+            processor.sparse_fft(&signal).unwrap()
+        };
+
+        // Every backend either runs natively or falls back to the CPU with the
+        // same result
+        for backend in [
+            GpuBackend::Cuda,
+            GpuBackend::Rocm,
+            GpuBackend::Metal,
+            GpuBackend::Wgpu,
+            GpuBackend::OpenCL,
+        ] {
+            let mut processor = GpuSparseFFT::with_backend(0, backend, config.clone()).unwrap();
+            let context = processor.context();
+            assert_eq!(context.requested_backend(), backend);
+            assert_eq!(
+                context.is_fallback(),
+                !(backends.contains(&backend) && backend_supports_fft(backend))
+            );
+            if context.is_fallback() {
+                assert_eq!(processor.backend(), GpuBackend::Cpu);
+            }
+
+            let result = processor.sparse_fft(&signal).unwrap();
+            let mut indices = result.indices.clone();
+            indices.sort_unstable();
+            assert_eq!(indices, vec![5, 11, n - 11, n - 5]);
+            if processor.backend() == GpuBackend::Cpu {
+                assert_eq!(result.indices, reference.indices);
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_with_streams_in_flight() {
+        let n = 128;
+        let signals: Vec<Vec<f64>> = (1..=5)
+            .map(|f| create_sparse_signal(n + 16 * f, &[(f, 1.0), (3 * f, 0.5)]))
+            .collect();
+        let config = SparseFFTConfig {
+            estimation_method: SparsityEstimationMethod::Manual,
+            sparsity: 4,
+            ..SparseFFTConfig::default()
+        };
+
+        let serial = {
+            let context = GpuContext::with_backend(0, GpuBackend::Cpu).unwrap();
+            let mut processor = GpuSparseFFT::with_context(context, config.clone());
+            processor.sparse_fft_batch(&signals, 1).unwrap()
+        };
+        for in_flight in [2, 3, 8] {
+            let context = GpuContext::with_backend(0, GpuBackend::Cpu).unwrap();
+            let mut processor = GpuSparseFFT::with_context(context, config.clone());
+            let results = processor.sparse_fft_batch(&signals, in_flight).unwrap();
+            assert_eq!(results.len(), signals.len());
+            for (a, b) in results.iter().zip(serial.iter()) {
+                assert_eq!(a.indices, b.indices);
+                assert_eq!(a.values, b.values);
+            }
+            // Every staging and device buffer of the batch was released
+            assert_eq!(processor.context().memory_manager().allocation_count(), 0);
+        }
+        for (f, result) in (1..=5).zip(serial.iter()) {
+            assert!(result.indices.contains(&f) && result.indices.contains(&(3 * f)));
+        }
+
+        let context = GpuContext::with_backend(0, GpuBackend::Cpu).unwrap();
+        let mut processor = GpuSparseFFT::with_context(context, config);
+        assert!(processor.sparse_fft_batch(&signals, 0).is_err());
+        assert!(processor
+            .sparse_fft_batch::<f64>(&[], 2)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sparse_fft2_matches_cpu() {
+        let (rows, cols) = (12, 20);
+        let image: Vec<Vec<f64>> = (0..rows)
+            .map(|r| {
+                (0..cols)
+                    .map(|c| {
+                        let (y, x) = (r as f64 / rows as f64, c as f64 / cols as f64);
+                        (2.0 * PI * (y + 4.0 * x)).cos() + 0.5 * (2.0 * PI * (5.0 * y - x)).sin()
+                    })
+                    .collect()
+            })
+            .collect();
+        let config = SparseFFTConfig {
+            estimation_method: SparsityEstimationMethod::Manual,
+            sparsity: 4,
+            ..SparseFFTConfig::default()
+        };
+
+        let context = GpuContext::with_backend(0, GpuBackend::Cpu).unwrap();
+        let mut processor = GpuSparseFFT::with_context(context, config);
+        let gpu = processor.sparse_fft2(&image).unwrap();
+        let cpu = crate::sparse_fft::sparse_fft2(&image, 4, None).unwrap();
+
+        let mut gpu_indices = gpu.indices.clone();
+        gpu_indices.sort_unstable();
+        let mut cpu_indices = cpu.indices.clone();
+        cpu_indices.sort_unstable();
+        assert_eq!(gpu_indices, cpu_indices);
+        assert!(gpu_indices.contains(&(cols + 4)));
+        assert!(gpu_indices.contains(&(5 * cols + cols - 1)));
+        for (g, c) in gpu.values.iter().zip(cpu.values.iter()) {
+            assert!((g.norm() - c.norm()).abs() < 1e-9);
+        }
+    }
+
     #[test]
     #[ignore = "Ignored for alpha-4 release - GPU-dependent test"]
     fn test_cuda_initialization() {
@@ -550,7 +1211,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "Ignored for alpha-4 release - GPU-dependent test"]
     fn test_cuda_sparse_fft() {
         // Create a signal with 3 frequency components
         let n = 256;
@@ -573,7 +1233,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "Ignored for alpha-4 release - GPU-dependent test"]
     fn test_cuda_batch_processing() {
         // Create multiple signals
         let n = 128;
@@ -584,9 +1243,15 @@ mod tests {
         ];
 
         // Test batch processing
-        let results =
-            cuda_batch_sparse_fft(&signals, 4, 0, Some(SparseFFTAlgorithm::Sublinear), None)
-                .unwrap();
+        let results = cuda_batch_sparse_fft(
+            &signals,
+            4,
+            0,
+            Some(SparseFFTAlgorithm::Sublinear),
+            None,
+            None,
+        )
+        .unwrap();
 
         // Should return the same number of results as input signals
         assert_eq!(results.len(), signals.len());
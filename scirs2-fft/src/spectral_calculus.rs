@@ -0,0 +1,349 @@
+//! Spectral differentiation and integration of periodic signals
+//!
+//! A periodic signal sampled at spacing `dx` is differentiated `order` times
+//! by multiplying its spectrum with `(ik)^order`, where `k = 2π m / (n dx)`
+//! is the angular wavenumber of bin `m`. Integration divides by the same
+//! factor and drops the mean, returning the zero-mean periodic antiderivative
+//! (the convention of `scipy.fftpack.diff` with a negative order).
+//!
+//! For even lengths the Nyquist bin holds `cos(π j)`, whose odd derivatives
+//! are not representable on the grid; that bin is zeroed for odd orders so
+//! the result of a real signal stays real. Even orders keep it.
+//!
+//! [`fft_gradient_2d`] and [`fft_laplacian_2d`] apply the same multipliers
+//! along the axes of a 2-D grid, as used by pseudo-spectral PDE solvers.
+
+use crate::error::{FFTError, FFTResult};
+use crate::fft::{fft, fft_along_axis, ifft};
+use ndarray::{Array2, ArrayView2, Axis};
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// Differentiates a periodic signal in the frequency domain.
+///
+/// # Arguments
+///
+/// * `x` - Samples of one period of the signal
+/// * `order` - Order of the derivative (zero returns the signal unchanged)
+/// * `dx` - Sample spacing
+///
+/// # Returns
+///
+/// * The `order`-th derivative sampled on the same grid
+///
+/// # Errors
+///
+/// Returns an error if the signal is empty or `dx` is not positive and finite.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::fft_derivative;
+/// use std::f64::consts::PI;
+///
+/// // One period of sin(t) on 32 points
+/// let n = 32;
+/// let dx = 2.0 * PI / n as f64;
+/// let x: Vec<f64> = (0..n).map(|j| (j as f64 * dx).sin()).collect();
+///
+/// let dxdt = fft_derivative(&x, 1, dx).unwrap();
+/// for (j, value) in dxdt.iter().enumerate() {
+///     assert!((value - (j as f64 * dx).cos()).abs() < 1e-12);
+/// }
+/// ```
+pub fn fft_derivative(x: &[f64], order: usize, dx: f64) -> FFTResult<Vec<f64>> {
+    apply_multiplier(x, signed_order(order, false)?, dx)
+}
+
+/// Integrates a periodic signal in the frequency domain.
+///
+/// The mean of `x` has no periodic antiderivative and is dropped, so the
+/// result is the zero-mean periodic function whose `order`-th derivative is
+/// `x - mean(x)`.
+///
+/// # Arguments
+///
+/// * `x` - Samples of one period of the signal
+/// * `order` - Number of times to integrate
+/// * `dx` - Sample spacing
+///
+/// # Returns
+///
+/// * The zero-mean `order`-th antiderivative sampled on the same grid
+///
+/// # Errors
+///
+/// Returns an error if the signal is empty or `dx` is not positive and finite.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::fft_integrate;
+/// use std::f64::consts::PI;
+///
+/// // The zero-mean antiderivative of 1 + cos(t) is sin(t)
+/// let n = 16;
+/// let dx = 2.0 * PI / n as f64;
+/// let x: Vec<f64> = (0..n).map(|j| 1.0 + (j as f64 * dx).cos()).collect();
+///
+/// let integral = fft_integrate(&x, 1, dx).unwrap();
+/// for (j, value) in integral.iter().enumerate() {
+///     assert!((value - (j as f64 * dx).sin()).abs() < 1e-12);
+/// }
+/// ```
+pub fn fft_integrate(x: &[f64], order: usize, dx: f64) -> FFTResult<Vec<f64>> {
+    apply_multiplier(x, signed_order(order, true)?, dx)
+}
+
+/// Computes the spectral gradient of a periodic 2-D field.
+///
+/// # Arguments
+///
+/// * `x` - Samples of one period of the field in both directions
+/// * `spacing` - Sample spacing along axis 0 and axis 1
+///
+/// # Returns
+///
+/// * The partial derivatives along axis 0 and axis 1
+///
+/// # Errors
+///
+/// Returns an error if the field is empty or a spacing is not positive and
+/// finite.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array2;
+/// use scirs2_fft::fft_gradient_2d;
+/// use std::f64::consts::PI;
+///
+/// let h = 2.0 * PI / 16.0;
+/// let field = Array2::from_shape_fn((16, 16), |(i, j)| (i as f64 * h).sin() * (j as f64 * h).cos());
+///
+/// let (d0, d1) = fft_gradient_2d(&field.view(), (h, h)).unwrap();
+/// let expected = (3.0 * h).cos() * (5.0 * h).cos();
+/// assert!((d0[[3, 5]] - expected).abs() < 1e-12);
+/// let expected = -(3.0 * h).sin() * (5.0 * h).sin();
+/// assert!((d1[[3, 5]] - expected).abs() < 1e-12);
+/// ```
+pub fn fft_gradient_2d(
+    x: &ArrayView2<f64>,
+    spacing: (f64, f64),
+) -> FFTResult<(Array2<f64>, Array2<f64>)> {
+    Ok((
+        derivative_along_axis(x, 0, 1, spacing.0)?,
+        derivative_along_axis(x, 1, 1, spacing.1)?,
+    ))
+}
+
+/// Computes the spectral Laplacian of a periodic 2-D field.
+///
+/// # Arguments
+///
+/// * `x` - Samples of one period of the field in both directions
+/// * `spacing` - Sample spacing along axis 0 and axis 1
+///
+/// # Returns
+///
+/// * The sum of the second partial derivatives along both axes
+///
+/// # Errors
+///
+/// Returns an error if the field is empty or a spacing is not positive and
+/// finite.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array2;
+/// use scirs2_fft::fft_laplacian_2d;
+/// use std::f64::consts::PI;
+///
+/// // sin(x) sin(2y) is an eigenfunction with eigenvalue -5
+/// let h = 2.0 * PI / 16.0;
+/// let field = Array2::from_shape_fn((16, 16), |(i, j)| {
+///     (i as f64 * h).sin() * (2.0 * j as f64 * h).sin()
+/// });
+///
+/// let laplacian = fft_laplacian_2d(&field.view(), (h, h)).unwrap();
+/// for (value, f) in laplacian.iter().zip(field.iter()) {
+///     assert!((value + 5.0 * f).abs() < 1e-10);
+/// }
+/// ```
+pub fn fft_laplacian_2d(x: &ArrayView2<f64>, spacing: (f64, f64)) -> FFTResult<Array2<f64>> {
+    let d00 = derivative_along_axis(x, 0, 2, spacing.0)?;
+    let d11 = derivative_along_axis(x, 1, 2, spacing.1)?;
+    Ok(d00 + d11)
+}
+
+/// Converts a derivative order to the exponent of `ik`
+fn signed_order(order: usize, integrate: bool) -> FFTResult<i32> {
+    let order = i32::try_from(order)
+        .map_err(|_| FFTError::ValueError(format!("Order {order} is too large")))?;
+    Ok(if integrate { -order } else { order })
+}
+
+/// Multiplies the spectrum of `x` by `(ik)^order` and transforms back
+fn apply_multiplier(x: &[f64], order: i32, dx: f64) -> FFTResult<Vec<f64>> {
+    let n = x.len();
+    if n == 0 {
+        return Err(FFTError::ValueError("Input signal is empty".to_string()));
+    }
+    check_spacing(dx)?;
+
+    let spectrum = fft(x, Some(n))?;
+    let filtered: Vec<Complex64> = spectrum
+        .iter()
+        .zip(spectral_multiplier(n, order, dx))
+        .map(|(&s, h)| s * h)
+        .collect();
+    Ok(ifft(&filtered, Some(n))?.iter().map(|c| c.re).collect())
+}
+
+/// Differentiates every lane of `x` along `axis`
+fn derivative_along_axis(
+    x: &ArrayView2<f64>,
+    axis: usize,
+    order: i32,
+    spacing: f64,
+) -> FFTResult<Array2<f64>> {
+    if x.is_empty() {
+        return Err(FFTError::ValueError("Input array is empty".to_string()));
+    }
+    check_spacing(spacing)?;
+
+    let n = x.shape()[axis];
+    let h = spectral_multiplier(n, order, spacing);
+    let mut data = x.mapv(|v| Complex64::new(v, 0.0));
+    fft_along_axis(&mut data, axis, false);
+    for mut lane in data.lanes_mut(Axis(axis)) {
+        lane.iter_mut().zip(&h).for_each(|(v, &w)| *v *= w);
+    }
+    fft_along_axis(&mut data, axis, true);
+
+    let scale = 1.0 / n as f64;
+    Ok(data.mapv(|v| v.re * scale))
+}
+
+/// Spectral weights `(ik)^order` for the bins of a length-`n` transform
+///
+/// The DC bin is zeroed for nonzero orders, and the Nyquist bin of an even
+/// length is zeroed for odd orders.
+fn spectral_multiplier(n: usize, order: i32, spacing: f64) -> Vec<Complex64> {
+    let nyquist = n.is_multiple_of(2).then_some(n / 2);
+    (0..n)
+        .map(|m| {
+            if order == 0 {
+                return Complex64::new(1.0, 0.0);
+            }
+            if m == 0 || (Some(m) == nyquist && order % 2 != 0) {
+                return Complex64::new(0.0, 0.0);
+            }
+            let signed = if m < n.div_ceil(2) {
+                m as f64
+            } else {
+                m as f64 - n as f64
+            };
+            let k = 2.0 * PI * signed / (n as f64 * spacing);
+            Complex64::new(0.0, k).powi(order)
+        })
+        .collect()
+}
+
+/// Rejects sample spacings that are not positive and finite
+fn check_spacing(spacing: f64) -> FFTResult<()> {
+    if spacing.is_finite() && spacing > 0.0 {
+        Ok(())
+    } else {
+        Err(FFTError::ValueError(format!(
+            "Sample spacing must be positive and finite, got {spacing}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_derivative_and_integral_round_trip() {
+        for n in [16, 17] {
+            // Period 4 so that k = 2π / 4 scales the result
+            let dx = 4.0 / n as f64;
+            let w = 2.0 * PI / 4.0;
+            let x: Vec<f64> = (0..n)
+                .map(|j| {
+                    let t = j as f64 * dx;
+                    0.5 + (w * t).sin() + 0.25 * (3.0 * w * t).cos()
+                })
+                .collect();
+
+            let d1 = fft_derivative(&x, 1, dx).unwrap();
+            let d2 = fft_derivative(&x, 2, dx).unwrap();
+            let d3 = fft_derivative(&x, 3, dx).unwrap();
+            for j in 0..n {
+                let t = j as f64 * dx;
+                let expected = w * (w * t).cos() - 0.75 * w * (3.0 * w * t).sin();
+                assert_relative_eq!(d1[j], expected, epsilon = 1e-10);
+                let expected = -w * w * (w * t).sin() - 2.25 * w * w * (3.0 * w * t).cos();
+                assert_relative_eq!(d2[j], expected, epsilon = 1e-10);
+                let expected = -w.powi(3) * (w * t).cos() + 6.75 * w.powi(3) * (3.0 * w * t).sin();
+                assert_relative_eq!(d3[j], expected, epsilon = 1e-9);
+            }
+
+            // Integrating the derivative recovers the zero-mean signal
+            let restored = fft_integrate(&d2, 2, dx).unwrap();
+            for (r, v) in restored.iter().zip(&x) {
+                assert_relative_eq!(*r, v - 0.5, epsilon = 1e-10);
+            }
+            assert_eq!(fft_derivative(&x, 0, dx).unwrap().len(), n);
+        }
+
+        assert!(fft_derivative(&[], 1, 1.0).is_err());
+        assert!(fft_derivative(&[1.0, 2.0], 1, 0.0).is_err());
+        assert!(fft_integrate(&[1.0, 2.0], 1, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_nyquist_handling() {
+        // cos(π j) lives entirely in the Nyquist bin of an even length
+        let n = 8;
+        let x: Vec<f64> = (0..n)
+            .map(|j| if j % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+
+        for value in fft_derivative(&x, 1, 1.0).unwrap() {
+            assert_relative_eq!(value, 0.0, epsilon = 1e-12);
+        }
+        for (value, &v) in fft_derivative(&x, 2, 1.0).unwrap().iter().zip(&x) {
+            assert_relative_eq!(*value, -PI * PI * v, epsilon = 1e-10);
+        }
+        for value in fft_integrate(&x, 1, 1.0).unwrap() {
+            assert_relative_eq!(value, 0.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_gradient_and_laplacian_2d() {
+        let (n0, n1) = (12, 9);
+        let (h0, h1) = (2.0 * PI / n0 as f64, 1.0 / n1 as f64);
+        let w1 = 2.0 * PI;
+        let field = Array2::from_shape_fn((n0, n1), |(i, j)| {
+            (i as f64 * h0).sin() * (w1 * j as f64 * h1).cos()
+        });
+
+        let (d0, d1) = fft_gradient_2d(&field.view(), (h0, h1)).unwrap();
+        let laplacian = fft_laplacian_2d(&field.view(), (h0, h1)).unwrap();
+        for ((i, j), &f) in field.indexed_iter() {
+            let (s, y) = (i as f64 * h0, w1 * j as f64 * h1);
+            assert_relative_eq!(d0[[i, j]], s.cos() * y.cos(), epsilon = 1e-10);
+            assert_relative_eq!(d1[[i, j]], -w1 * s.sin() * y.sin(), epsilon = 1e-10);
+            assert_relative_eq!(laplacian[[i, j]], -(1.0 + w1 * w1) * f, epsilon = 1e-9);
+        }
+
+        assert!(fft_laplacian_2d(&Array2::<f64>::zeros((0, 4)).view(), (1.0, 1.0)).is_err());
+        assert!(fft_gradient_2d(&field.view(), (1.0, -1.0)).is_err());
+    }
+}
@@ -0,0 +1,389 @@
+//! Streaming FFT convolution with overlap-add and overlap-save
+//!
+//! [`StreamingFft`] filters an unbounded stream with a fixed FIR kernel. Input
+//! arrives in chunks of any length; the processor keeps the partial block and
+//! the overlap between blocks internally and returns output as soon as it is
+//! fully determined. Feeding a signal in pieces and calling
+//! [`StreamingFft::flush`] at the end yields exactly the full linear
+//! convolution of the signal with the kernel.
+//!
+//! [`OlaConvolver`] is the real-valued counterpart for `f64` streams.
+//!
+//! # Examples
+//!
+//! ```
+//! use scirs2_fft::streaming::OlaConvolver;
+//!
+//! // Three-tap moving sum
+//! let mut conv = OlaConvolver::new(&[1.0, 1.0, 1.0], Some(4)).unwrap();
+//! let mut out = conv.process(&[1.0, 2.0, 3.0]);
+//! out.extend(conv.process(&[4.0, 5.0]));
+//! out.extend(conv.flush());
+//!
+//! let expected = [1.0, 3.0, 6.0, 9.0, 12.0, 9.0, 5.0];
+//! assert_eq!(out.len(), expected.len());
+//! for (a, b) in out.iter().zip(expected.iter()) {
+//!     assert!((a - b).abs() < 1e-12);
+//! }
+//! ```
+
+use crate::error::{FFTError, FFTResult};
+use crate::helper::next_fast_len;
+use num_complex::Complex64;
+use rustfft::{Fft, FftPlanner};
+use std::sync::Arc;
+
+/// Block size used when none is given, in multiples of the kernel length
+const DEFAULT_BLOCK_FACTOR: usize = 4;
+
+/// Smallest default block size
+const MIN_DEFAULT_BLOCK: usize = 64;
+
+/// Block convolution scheme used by [`StreamingFft`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamingMethod {
+    /// Transform zero-padded blocks and add the overlapping tails
+    #[default]
+    OverlapAdd,
+    /// Transform overlapping segments and discard the wrapped-around samples
+    OverlapSave,
+}
+
+/// Stateful FFT convolver for complex streams
+///
+/// Each block of `block_size` input samples costs one forward and one inverse
+/// FFT of length `fft_size = block_size + kernel_len - 1`. Output lags input
+/// by up to one block: samples are emitted only once every input sample that
+/// contributes to them has been seen.
+#[derive(Clone)]
+pub struct StreamingFft {
+    method: StreamingMethod,
+    kernel_len: usize,
+    block_size: usize,
+    fft_size: usize,
+    /// Frequency response of the zero-padded kernel, scaled by `1 / fft_size`
+    response: Vec<Complex64>,
+    forward: Arc<dyn Fft<f64>>,
+    inverse: Arc<dyn Fft<f64>>,
+    /// Input received since the last complete block
+    pending: Vec<Complex64>,
+    /// Overlap-add: tail of the last block's output still to be added.
+    /// Overlap-save: the last `kernel_len - 1` input samples.
+    overlap: Vec<Complex64>,
+    buffer: Vec<Complex64>,
+}
+
+impl StreamingFft {
+    /// Create a streaming convolver for `kernel`
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel` - FIR filter taps (must not be empty)
+    /// * `block_size` - Minimum number of input samples per FFT block. The
+    ///   actual block is enlarged so that the FFT length is fast to compute.
+    ///   Defaults to four times the kernel length.
+    /// * `method` - Overlap-add or overlap-save
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the kernel is empty or `block_size` is zero.
+    pub fn new(
+        kernel: &[Complex64],
+        block_size: Option<usize>,
+        method: StreamingMethod,
+    ) -> FFTResult<Self> {
+        let kernel_len = kernel.len();
+        if kernel_len == 0 {
+            return Err(FFTError::ValueError("Kernel cannot be empty".to_string()));
+        }
+        let min_block =
+            block_size.unwrap_or((DEFAULT_BLOCK_FACTOR * kernel_len).max(MIN_DEFAULT_BLOCK));
+        if min_block == 0 {
+            return Err(FFTError::ValueError(
+                "Block size must be positive".to_string(),
+            ));
+        }
+
+        let fft_size = next_fast_len(min_block + kernel_len - 1, false);
+        let block_size = fft_size - kernel_len + 1;
+
+        let mut planner = FftPlanner::new();
+        let forward = planner.plan_fft_forward(fft_size);
+        let inverse = planner.plan_fft_inverse(fft_size);
+
+        let scale = 1.0 / fft_size as f64;
+        let mut response = vec![Complex64::new(0.0, 0.0); fft_size];
+        for (r, &h) in response.iter_mut().zip(kernel.iter()) {
+            *r = h * scale;
+        }
+        forward.process(&mut response);
+
+        Ok(Self {
+            method,
+            kernel_len,
+            block_size,
+            fft_size,
+            response,
+            forward,
+            inverse,
+            pending: Vec::with_capacity(block_size),
+            overlap: vec![Complex64::new(0.0, 0.0); kernel_len - 1],
+            buffer: vec![Complex64::new(0.0, 0.0); fft_size],
+        })
+    }
+
+    /// Number of input samples consumed per FFT block
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Length of the FFTs performed for each block
+    pub fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Length of the FIR kernel
+    pub fn kernel_len(&self) -> usize {
+        self.kernel_len
+    }
+
+    /// Block convolution scheme in use
+    pub fn method(&self) -> StreamingMethod {
+        self.method
+    }
+
+    /// Number of input samples buffered and not yet reflected in the output
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Feed a chunk of input and return the output it completes
+    ///
+    /// The chunk may have any length, including zero. The returned vector
+    /// holds `block_size` samples for every block completed by this chunk.
+    pub fn process(&mut self, chunk: &[Complex64]) -> Vec<Complex64> {
+        let completed = (self.pending.len() + chunk.len()) / self.block_size;
+        let mut output = Vec::with_capacity(completed * self.block_size);
+
+        let mut rest = chunk;
+        while !rest.is_empty() {
+            let take = (self.block_size - self.pending.len()).min(rest.len());
+            self.pending.extend_from_slice(&rest[..take]);
+            rest = &rest[take..];
+            if self.pending.len() == self.block_size {
+                self.process_block(&mut output);
+            }
+        }
+        output
+    }
+
+    /// Finish the stream and return the remaining output
+    ///
+    /// The input is treated as followed by zeros, so the output emitted over
+    /// the lifetime of the stream has `n + kernel_len - 1` samples for `n`
+    /// input samples. The convolver is reset and can start a new stream.
+    pub fn flush(&mut self) -> Vec<Complex64> {
+        let remaining = self.pending.len() + self.kernel_len - 1;
+        let mut output = Vec::with_capacity(remaining + self.block_size);
+        while output.len() < remaining {
+            self.pending
+                .resize(self.block_size, Complex64::new(0.0, 0.0));
+            self.process_block(&mut output);
+        }
+        output.truncate(remaining);
+        self.reset();
+        output
+    }
+
+    /// Discard all buffered input and overlap, keeping the kernel
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.overlap.fill(Complex64::new(0.0, 0.0));
+    }
+
+    /// Convolve one full block of pending input and append its output
+    fn process_block(&mut self, output: &mut Vec<Complex64>) {
+        let zero = Complex64::new(0.0, 0.0);
+        let overlap_len = self.kernel_len - 1;
+
+        match self.method {
+            StreamingMethod::OverlapAdd => {
+                self.buffer[..self.block_size].copy_from_slice(&self.pending);
+                self.buffer[self.block_size..].fill(zero);
+            }
+            StreamingMethod::OverlapSave => {
+                self.buffer[..overlap_len].copy_from_slice(&self.overlap);
+                self.buffer[overlap_len..].copy_from_slice(&self.pending);
+                // Keep the newest input samples for the next segment
+                let tail = self.fft_size - overlap_len;
+                self.overlap.copy_from_slice(&self.buffer[tail..]);
+            }
+        }
+
+        self.forward.process(&mut self.buffer);
+        for (b, &h) in self.buffer.iter_mut().zip(self.response.iter()) {
+            *b *= h;
+        }
+        self.inverse.process(&mut self.buffer);
+
+        match self.method {
+            StreamingMethod::OverlapAdd => {
+                for (y, &t) in self.buffer.iter_mut().zip(self.overlap.iter()) {
+                    *y += t;
+                }
+                output.extend_from_slice(&self.buffer[..self.block_size]);
+                self.overlap
+                    .copy_from_slice(&self.buffer[self.block_size..]);
+            }
+            StreamingMethod::OverlapSave => {
+                // The first kernel_len - 1 samples are corrupted by wrap-around
+                output.extend_from_slice(&self.buffer[overlap_len..]);
+            }
+        }
+        self.pending.clear();
+    }
+}
+
+/// Overlap-add FFT convolver for real streams
+///
+/// A thin wrapper around [`StreamingFft`] that accepts and returns `f64`
+/// samples.
+#[derive(Clone)]
+pub struct OlaConvolver {
+    inner: StreamingFft,
+}
+
+impl OlaConvolver {
+    /// Create a real overlap-add convolver for `kernel`
+    ///
+    /// See [`StreamingFft::new`] for the meaning of `block_size`.
+    pub fn new(kernel: &[f64], block_size: Option<usize>) -> FFTResult<Self> {
+        let kernel: Vec<Complex64> = kernel.iter().map(|&h| Complex64::new(h, 0.0)).collect();
+        Ok(Self {
+            inner: StreamingFft::new(&kernel, block_size, StreamingMethod::OverlapAdd)?,
+        })
+    }
+
+    /// Number of input samples consumed per FFT block
+    pub fn block_size(&self) -> usize {
+        self.inner.block_size()
+    }
+
+    /// Number of input samples buffered and not yet reflected in the output
+    pub fn pending(&self) -> usize {
+        self.inner.pending()
+    }
+
+    /// Feed a chunk of input and return the output it completes
+    pub fn process(&mut self, chunk: &[f64]) -> Vec<f64> {
+        let chunk: Vec<Complex64> = chunk.iter().map(|&x| Complex64::new(x, 0.0)).collect();
+        self.inner.process(&chunk).iter().map(|y| y.re).collect()
+    }
+
+    /// Finish the stream and return the remaining output
+    pub fn flush(&mut self) -> Vec<f64> {
+        self.inner.flush().iter().map(|y| y.re).collect()
+    }
+
+    /// Discard all buffered input and overlap, keeping the kernel
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direct_convolution(x: &[Complex64], h: &[Complex64]) -> Vec<Complex64> {
+        let mut y = vec![Complex64::new(0.0, 0.0); x.len() + h.len() - 1];
+        for (i, &xi) in x.iter().enumerate() {
+            for (j, &hj) in h.iter().enumerate() {
+                y[i + j] += xi * hj;
+            }
+        }
+        y
+    }
+
+    fn test_signal(n: usize) -> Vec<Complex64> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64;
+                Complex64::new((0.37 * t).sin() + 0.1 * t.sqrt(), (0.11 * t * t).cos())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_streaming_matches_direct_convolution() {
+        let x = test_signal(517);
+        let h = test_signal(23);
+        let expected = direct_convolution(&x, &h);
+        let chunk_sizes = [1, 7, 64, 0, 3, 150, 200, 1000];
+
+        for method in [StreamingMethod::OverlapAdd, StreamingMethod::OverlapSave] {
+            let mut conv = StreamingFft::new(&h, Some(40), method).unwrap();
+            assert!(conv.block_size() >= 40);
+            assert_eq!(conv.fft_size(), conv.block_size() + h.len() - 1);
+
+            let mut output = Vec::new();
+            let mut start = 0;
+            for &size in chunk_sizes.iter().cycle() {
+                if start >= x.len() {
+                    break;
+                }
+                let end = (start + size).min(x.len());
+                let emitted = conv.process(&x[start..end]);
+                assert_eq!(emitted.len() % conv.block_size(), 0);
+                output.extend(emitted);
+                // Output never runs ahead of the input
+                assert_eq!(output.len() + conv.pending(), end);
+                start = end;
+            }
+            output.extend(conv.flush());
+
+            assert_eq!(output.len(), expected.len());
+            for (a, b) in output.iter().zip(expected.iter()) {
+                assert!((a - b).norm() < 1e-10, "{method:?}: {a} vs {b}");
+            }
+            assert_eq!(conv.pending(), 0);
+        }
+    }
+
+    #[test]
+    fn test_ola_convolver_reuse_and_errors() {
+        let h = [0.5, -0.25, 0.125, 1.0];
+        let x: Vec<f64> = (0..50).map(|i| (i as f64 * 0.3).cos()).collect();
+        let mut conv = OlaConvolver::new(&h, None).unwrap();
+
+        // Two passes over the same stream give the same result after flush
+        let mut first = conv.process(&x);
+        first.extend(conv.flush());
+        let mut second = conv.process(&x[..20]);
+        second.extend(conv.process(&x[20..]));
+        second.extend(conv.flush());
+        assert_eq!(first.len(), x.len() + h.len() - 1);
+        for (a, b) in first.iter().zip(second.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        for (n, &y) in first.iter().enumerate() {
+            let direct: f64 = h
+                .iter()
+                .enumerate()
+                .filter(|&(k, _)| k <= n && n - k < x.len())
+                .map(|(k, &hk)| hk * x[n - k])
+                .sum();
+            assert!((y - direct).abs() < 1e-12);
+        }
+
+        // Single-tap kernel is the identity
+        let mut identity = OlaConvolver::new(&[1.0], Some(1)).unwrap();
+        let mut out = identity.process(&[3.0, 4.0]);
+        out.extend(identity.flush());
+        assert_eq!(out.len(), 2);
+        assert!((out[0] - 3.0).abs() < 1e-12 && (out[1] - 4.0).abs() < 1e-12);
+
+        assert!(OlaConvolver::new(&[], None).is_err());
+        assert!(OlaConvolver::new(&[1.0], Some(0)).is_err());
+    }
+}
@@ -180,14 +180,7 @@ where
     let padded_size = window_size * config.zero_padding;
 
     // Create window function
-    let window_type = match config.window_function {
-        WindowFunction::None => crate::window::Window::Rectangular,
-        WindowFunction::Hann => crate::window::Window::Hann,
-        WindowFunction::Hamming => crate::window::Window::Hamming,
-        WindowFunction::Blackman => crate::window::Window::Blackman,
-        WindowFunction::FlatTop => crate::window::Window::FlatTop,
-        WindowFunction::Kaiser => crate::window::Window::Kaiser(5.0), // Default beta
-    };
+    let window_type = config.window_function.to_window(5.0); // Default Kaiser beta
     let window = window::get_window(window_type, window_size, true)?;
 
     // Calculate number of frames based on signal length, window size, and hop size
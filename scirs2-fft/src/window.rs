@@ -5,6 +5,11 @@
 //!
 //! Window functions are used to taper the samples at the beginning and end of the
 //! data to reduce the spectral leakage effect in FFT processing.
+//!
+//! Every window comes in a symmetric variant (`sym = true`, for filter design)
+//! and a periodic variant (`sym = false`, for spectral analysis). The periodic
+//! window of length `n` is the symmetric window of length `n + 1` with its
+//! last sample dropped, so that it tiles seamlessly under the DFT.
 
 use crate::error::{FFTError, FFTResult};
 use ndarray::{Array1, ArrayBase, Data, Ix1};
@@ -48,6 +53,12 @@ pub enum Window {
     Kaiser(f64),
     /// Gaussian window
     Gaussian(f64),
+    /// Discrete prolate spheroidal (Slepian) window with time-half-bandwidth
+    /// product `NW`
+    ///
+    /// This is the zeroth-order DPSS taper, which maximizes the energy
+    /// concentrated in the band `|f| < NW / n`. It is scaled to unit peak.
+    Dpss(f64),
     /// General cosine window with custom coefficients
     GeneralCosine(Vec<f64>),
 }
@@ -70,6 +81,9 @@ impl FromStr for Window {
             "barthann" => Ok(Window::Barthann),
             "cosine" | "cos" => Ok(Window::Cosine),
             "exponential" | "exp" => Ok(Window::Exponential),
+            "tukey" | "tapered cosine" => Ok(Window::Tukey(0.5)),
+            "kaiser" => Ok(Window::Kaiser(8.6)),
+            "dpss" | "slepian" => Ok(Window::Dpss(3.0)),
             _ => Err(FFTError::ValueError(format!("Unknown window type: {}", s))),
         }
     }
@@ -81,7 +95,7 @@ impl FromStr for Window {
 ///
 /// * `window` - Window type or name
 /// * `n` - Window length
-/// * `sym` - Whether the window is symmetric (default: true)
+/// * `sym` - Whether the window is symmetric (`true`) or periodic (`false`)
 ///
 /// # Returns
 ///
@@ -103,6 +117,11 @@ impl FromStr for Window {
 /// // Create a window by name
 /// let win = get_window("hamming", 10, true).unwrap();
 /// assert_eq!(win.len(), 10);
+///
+/// // A periodic window is the symmetric window one sample longer, truncated
+/// let periodic = get_window(Window::Tukey(0.5), 10, false).unwrap();
+/// let symmetric = get_window(Window::Tukey(0.5), 11, true).unwrap();
+/// assert_eq!(periodic.as_slice().unwrap(), &symmetric.as_slice().unwrap()[..10]);
 /// ```
 pub fn get_window<T>(window: T, n: usize, sym: bool) -> FFTResult<Array1<f64>>
 where
@@ -128,7 +147,7 @@ where
         Window::Bartlett => bartlett(n, sym),
         Window::FlatTop => flattop(n, sym),
         Window::Parzen => parzen(n, sym),
-        Window::Bohman => bohman(n),
+        Window::Bohman => bohman(n, sym),
         Window::BlackmanHarris => blackmanharris(n, sym),
         Window::Nuttall => nuttall(n, sym),
         Window::Barthann => barthann(n, sym),
@@ -137,6 +156,7 @@ where
         Window::Tukey(alpha) => tukey(n, sym, alpha),
         Window::Kaiser(beta) => kaiser(n, sym, beta),
         Window::Gaussian(std) => gaussian(n, sym, std),
+        Window::Dpss(nw) => dpss(n, sym, nw),
         Window::GeneralCosine(coeffs) => general_cosine(n, sym, &coeffs),
     }
 }
@@ -168,6 +188,19 @@ impl From<String> for WindowParam {
     }
 }
 
+/// Evaluate a window either as is (symmetric) or, for the periodic variant,
+/// one sample longer with the final sample dropped
+fn symmetric_or_periodic<F>(n: usize, sym: bool, window: F) -> FFTResult<Array1<f64>>
+where
+    F: FnOnce(usize) -> FFTResult<Array1<f64>>,
+{
+    if sym || n == 1 {
+        return window(n);
+    }
+    let w = window(n + 1)?;
+    Ok(w.slice(ndarray::s![0..n]).to_owned())
+}
+
 /// Rectangular window
 ///
 /// A rectangular window is a window with constant value 1.
@@ -288,7 +321,11 @@ fn parzen(n: usize, sym: bool) -> FFTResult<Array1<f64>> {
 /// Bohman window
 ///
 /// The Bohman window is the convolution of two half-duration cosine lobes.
-fn bohman(n: usize) -> FFTResult<Array1<f64>> {
+fn bohman(n: usize, sym: bool) -> FFTResult<Array1<f64>> {
+    symmetric_or_periodic(n, sym, bohman_symmetric)
+}
+
+fn bohman_symmetric(n: usize) -> FFTResult<Array1<f64>> {
     if n == 1 {
         return Ok(Array1::ones(1));
     }
@@ -393,26 +430,18 @@ fn exponential(n: usize, sym: bool, tau: f64) -> FFTResult<Array1<f64>> {
         return Err(FFTError::ValueError("tau must be positive".to_string()));
     }
 
-    if n == 1 {
-        return Ok(Array1::ones(1));
-    }
-
-    let center = if sym { (n as f64 - 1.0) / 2.0 } else { 0.0 };
-
-    let mut w = Array1::zeros(n);
-
-    for i in 0..n {
-        let x = (i as f64 - center).abs() / (tau * (n as f64));
-        w[i] = (-x).exp();
-    }
-
-    Ok(w)
+    symmetric_or_periodic(n, sym, |n| {
+        let center = (n as f64 - 1.0) / 2.0;
+        Ok(Array1::from_shape_fn(n, |i| {
+            (-(i as f64 - center).abs() / (tau * n as f64)).exp()
+        }))
+    })
 }
 
 /// Tukey window
 ///
-/// The Tukey window is a cosine lobe of width alpha * N/2 with tails that are
-/// rectangular windows.
+/// The Tukey window is a flat top with cosine tapers of total width
+/// `alpha * (n - 1)`, rising from zero at both ends.
 ///
 /// # Arguments
 ///
@@ -427,10 +456,6 @@ fn tukey(n: usize, sym: bool, alpha: f64) -> FFTResult<Array1<f64>> {
         ));
     }
 
-    if n == 1 {
-        return Ok(Array1::ones(1));
-    }
-
     if alpha == 0.0 {
         return rectangular(n);
     }
@@ -439,23 +464,25 @@ fn tukey(n: usize, sym: bool, alpha: f64) -> FFTResult<Array1<f64>> {
         return hann(n, sym);
     }
 
-    let mut w = Array1::ones(n);
-    let width = (alpha * (n as f64 - 1.0) / 2.0).floor() as usize;
+    symmetric_or_periodic(n, sym, |n| {
+        if n == 1 {
+            return Ok(Array1::ones(1));
+        }
 
-    // Left taper
-    for i in 0..width {
-        let x = 0.5 * (1.0 + ((PI * i as f64) / width as f64).cos());
-        w[i] = x;
-    }
+        let m = n as f64 - 1.0;
+        let width = (alpha * m / 2.0).floor() as usize;
+        let mut w = Array1::ones(n);
 
-    // Right taper
-    for i in 0..width {
-        let idx = n - 1 - i;
-        let x = 0.5 * (1.0 + ((PI * i as f64) / width as f64).cos());
-        w[idx] = x;
-    }
+        // The tapers are mirror images of each other, so evaluate the left
+        // one and reflect it
+        for i in 0..=width.min(n / 2) {
+            let x = 0.5 * (1.0 + (PI * (-1.0 + 2.0 * i as f64 / (alpha * m))).cos());
+            w[i] = x;
+            w[n - 1 - i] = x;
+        }
 
-    Ok(w)
+        Ok(w)
+    })
 }
 
 /// Kaiser window
@@ -519,15 +546,151 @@ fn gaussian(n: usize, sym: bool, std: f64) -> FFTResult<Array1<f64>> {
         return Err(FFTError::ValueError("std must be positive".to_string()));
     }
 
-    let mut w = Array1::zeros(n);
-    let center = if sym { (n as f64 - 1.0) / 2.0 } else { 0.0 };
+    symmetric_or_periodic(n, sym, |n| {
+        let center = (n as f64 - 1.0) / 2.0;
+        Ok(Array1::from_shape_fn(n, |i| {
+            let x = (i as f64 - center) / (std * center);
+            (-0.5 * x.powi(2)).exp()
+        }))
+    })
+}
 
-    for i in 0..n {
-        let x = (i as f64 - center) / (std * (n as f64 - 1.0) / 2.0);
-        w[i] = (-0.5 * x.powi(2)).exp();
+/// Discrete prolate spheroidal (Slepian) window
+///
+/// The zeroth-order DPSS is the eigenvector belonging to the largest
+/// eigenvalue of the symmetric tridiagonal matrix
+///
+/// ```text
+/// d[i]   = ((n - 1 - 2i) / 2)^2 cos(2 pi W)
+/// e[i]   = i (n - i) / 2
+/// ```
+///
+/// with half-bandwidth `W = NW / n` (Percival & Walden, 1993). The eigenvalue
+/// is located by Sturm-sequence bisection and the vector is refined by
+/// inverse iteration.
+///
+/// # Arguments
+///
+/// * `n` - Window length
+/// * `sym` - Whether the window is symmetric
+/// * `nw` - Time-half-bandwidth product (0 < nw < n / 2)
+fn dpss(n: usize, sym: bool, nw: f64) -> FFTResult<Array1<f64>> {
+    if !(nw > 0.0 && nw < n as f64 / 2.0) {
+        return Err(FFTError::ValueError(format!(
+            "NW must be in (0, {}) for a window of length {}",
+            n as f64 / 2.0,
+            n
+        )));
+    }
+
+    symmetric_or_periodic(n, sym, |n| {
+        if n == 1 {
+            return Ok(Array1::ones(1));
+        }
+
+        let cos_w = (2.0 * PI * nw / n as f64).cos();
+        let diag: Vec<f64> = (0..n)
+            .map(|i| ((n as f64 - 1.0 - 2.0 * i as f64) / 2.0).powi(2) * cos_w)
+            .collect();
+        let off: Vec<f64> = (1..n).map(|i| i as f64 * (n - i) as f64 / 2.0).collect();
+
+        let lambda = largest_tridiagonal_eigenvalue(&diag, &off);
+
+        // Inverse iteration on (sigma I - T), which is positive definite for a
+        // shift just above the largest eigenvalue
+        let scale = diag
+            .iter()
+            .map(|d| d.abs())
+            .chain(off.iter().map(|e| 2.0 * e))
+            .fold(1.0, f64::max);
+        let sigma = lambda + 1e-10 * scale;
+        let mut v = vec![1.0; n];
+        for _ in 0..4 {
+            v = solve_shifted_tridiagonal(&diag, &off, sigma, &v);
+            let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            v.iter_mut().for_each(|x| *x /= norm);
+        }
+
+        let peak = v
+            .iter()
+            .copied()
+            .fold(0.0, |m: f64, x| if x.abs() > m.abs() { x } else { m });
+        Ok(Array1::from_iter(v.into_iter().map(|x| x / peak)))
+    })
+}
+
+/// Largest eigenvalue of a symmetric tridiagonal matrix by bisection on the
+/// Sturm sequence count
+fn largest_tridiagonal_eigenvalue(diag: &[f64], off: &[f64]) -> f64 {
+    // Gershgorin bounds
+    let radius = |i: usize| {
+        let left = if i > 0 { off[i - 1].abs() } else { 0.0 };
+        let right = off.get(i).map_or(0.0, |e| e.abs());
+        left + right
+    };
+    let mut lo = (0..diag.len())
+        .map(|i| diag[i] - radius(i))
+        .fold(f64::INFINITY, f64::min);
+    let mut hi = (0..diag.len())
+        .map(|i| diag[i] + radius(i))
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    // Number of eigenvalues below x
+    let count_below = |x: f64| {
+        let mut count = 0;
+        let mut q = 1.0;
+        for (i, &d) in diag.iter().enumerate() {
+            let coupling = if i > 0 {
+                off[i - 1] * off[i - 1] / q
+            } else {
+                0.0
+            };
+            q = d - x - coupling;
+            if q == 0.0 {
+                q = -f64::EPSILON * (x.abs() + 1.0);
+            }
+            if q < 0.0 {
+                count += 1;
+            }
+        }
+        count
+    };
+
+    let tolerance = f64::EPSILON * hi.abs().max(lo.abs());
+    while hi - lo > tolerance {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if count_below(mid) == diag.len() {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
     }
+    hi
+}
 
-    Ok(w)
+/// Solve `(sigma I - T) x = b` for a symmetric tridiagonal `T`
+///
+/// The Thomas algorithm is stable here because the shifted matrix is positive
+/// definite.
+fn solve_shifted_tridiagonal(diag: &[f64], off: &[f64], sigma: f64, b: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    let mut c = vec![0.0; n];
+    let mut x = vec![0.0; n];
+
+    let mut denom = sigma - diag[0];
+    x[0] = b[0] / denom;
+    for i in 1..n {
+        c[i - 1] = -off[i - 1] / denom;
+        denom = sigma - diag[i] + off[i - 1] * c[i - 1];
+        x[i] = (b[i] + off[i - 1] * x[i - 1]) / denom;
+    }
+    for i in (0..n - 1).rev() {
+        x[i] -= c[i] * x[i + 1];
+    }
+    x
 }
 
 /// General cosine window
@@ -569,36 +732,22 @@ fn general_cosine(n: usize, sym: bool, a: &[f64]) -> FFTResult<Array1<f64>> {
 
 /// Modified Bessel function of the first kind, order 0
 ///
-/// Approximation of the modified Bessel function I₀(x) using polynomial
-/// approximation for numerical stability.
+/// Evaluated from its power series, which converges for all arguments and is
+/// accurate to machine precision over the range of Kaiser shape parameters.
 fn bessel_i0(x: f64) -> f64 {
-    let ax = x.abs();
-
-    // For small arguments, use polynomial approximation
-    if ax < 3.75 {
-        let y = (x / 3.75).powi(2);
-        return y.mul_add(
-            3.515_622_9
-                + y * (3.089_942_4
-                    + y * (1.206_749_2 + y * (0.265_973_2 + y * (0.036_076_8 + y * 0.004_581_3)))),
-            1.0,
-        );
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_x = x / 2.0;
+
+    for k in 1..=500 {
+        term *= (half_x / k as f64).powi(2);
+        sum += term;
+        if term < 1e-16 * sum {
+            break;
+        }
     }
 
-    // For large arguments, use asymptotic expansion
-    let y = 3.75 / ax;
-    let exp_term = (ax).exp() / (ax).sqrt();
-
-    exp_term
-        * y.mul_add(
-            0.013_285_92
-                + y * (0.002_253_19
-                    + y * (-0.001_575_65
-                        + y * (0.009_162_81
-                            + y * (-0.020_577_06
-                                + y * (0.026_355_37 + y * (-0.016_476_33 + y * 0.003_923_77)))))),
-            0.398_942_28,
-        )
+    sum
 }
 
 /// Apply window function to a signal
@@ -766,4 +915,99 @@ mod tests {
         let hamming_enbw = enbw(Window::Hamming, 1024).unwrap();
         assert_relative_eq!(hamming_enbw, 1.36, epsilon = 0.01);
     }
+
+    #[test]
+    fn test_bessel_i0() {
+        assert_relative_eq!(bessel_i0(0.0), 1.0, epsilon = 1e-15);
+        assert_relative_eq!(bessel_i0(1.0), 1.266_065_877_752_008_4, epsilon = 1e-14);
+        assert_relative_eq!(bessel_i0(10.0), 2_815.716_628_466_254, max_relative = 1e-13);
+    }
+
+    #[test]
+    fn test_tukey() {
+        // Tapers reach zero at both ends and the middle stays flat
+        let win = get_window(Window::Tukey(0.5), 5, true).unwrap();
+        let expected = [0.0, 1.0, 1.0, 1.0, 0.0];
+        for (a, &b) in win.iter().zip(expected.iter()) {
+            assert_relative_eq!(a, &b, epsilon = 1e-12);
+        }
+
+        let win = get_window(Window::Tukey(0.5), 9, true).unwrap();
+        let expected = [0.0, 0.5, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, 0.0];
+        for (a, &b) in win.iter().zip(expected.iter()) {
+            assert_relative_eq!(a, &b, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_dpss() {
+        let n = 32;
+        let nw = 3.0;
+        let win = get_window(Window::Dpss(nw), n, true).unwrap();
+
+        // Positive, symmetric and scaled to unit peak
+        assert_relative_eq!(win.iter().copied().fold(0.0, f64::max), 1.0);
+        for i in 0..n {
+            assert!(win[i] > 0.0);
+            assert_relative_eq!(win[i], win[n - 1 - i], epsilon = 1e-10);
+        }
+
+        // Fraction of energy within |f| < W, which the DPSS maximizes
+        let concentration = |w: &Array1<f64>| {
+            let band = nw / n as f64;
+            let mut inside = 0.0;
+            for i in 0..n {
+                for j in 0..n {
+                    let d = i as f64 - j as f64;
+                    let kernel = if i == j {
+                        2.0 * band
+                    } else {
+                        (2.0 * PI * band * d).sin() / (PI * d)
+                    };
+                    inside += w[i] * w[j] * kernel;
+                }
+            }
+            inside / w.iter().map(|x| x * x).sum::<f64>()
+        };
+        let dpss_concentration = concentration(&win);
+        assert!(dpss_concentration > 0.999_99);
+        for other in [Window::Hann, Window::Kaiser(8.6), Window::Blackman] {
+            assert!(concentration(&get_window(other, n, true).unwrap()) < dpss_concentration);
+        }
+
+        assert_eq!(Window::from_str("slepian").unwrap(), Window::Dpss(3.0));
+        assert!(get_window(Window::Dpss(16.0), n, true).is_err());
+    }
+
+    #[test]
+    fn test_periodic_windows() {
+        let windows = vec![
+            Window::Hann,
+            Window::Hamming,
+            Window::Blackman,
+            Window::Bartlett,
+            Window::FlatTop,
+            Window::Parzen,
+            Window::Bohman,
+            Window::BlackmanHarris,
+            Window::Nuttall,
+            Window::Barthann,
+            Window::Cosine,
+            Window::Exponential,
+            Window::Tukey(0.3),
+            Window::Kaiser(6.0),
+            Window::Gaussian(0.4),
+            Window::Dpss(2.5),
+        ];
+        for window in windows {
+            for n in [8, 11] {
+                let periodic = get_window(window.clone(), n, false).unwrap();
+                let extended = get_window(window.clone(), n + 1, true).unwrap();
+                assert_eq!(periodic.len(), n);
+                for (a, b) in periodic.iter().zip(extended.iter()) {
+                    assert_relative_eq!(a, b, epsilon = 1e-10);
+                }
+            }
+        }
+    }
 }
@@ -2,8 +2,15 @@
 //!
 //! This module provides a configurable thread pool for parallel FFT operations,
 //! similar to SciPy's worker management functionality.
+//!
+//! [`set_fft_workers`] sets the thread count globally and [`with_fft_workers`]
+//! (or a [`WorkerContext`] guard) overrides it for one scope, like SciPy's
+//! `scipy.fft.set_workers` context manager.
 
+use crate::error::{FFTError, FFTResult};
 use scirs2_core::parallel_ops::*;
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::env;
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
@@ -44,9 +51,12 @@ impl Default for WorkerConfig {
 }
 
 /// FFT Worker Pool Manager
-/// Simplified to use core parallel abstractions instead of direct ThreadPool management
+///
+/// Holds the worker configuration and lazily builds one thread pool (from
+/// `scirs2_core::parallel_ops`) per worker count that is actually used.
 pub struct WorkerPool {
     config: Arc<Mutex<WorkerConfig>>,
+    pools: Mutex<HashMap<usize, Arc<ThreadPool>>>,
 }
 
 impl WorkerPool {
@@ -56,6 +66,7 @@ impl WorkerPool {
 
         Self {
             config: Arc::new(Mutex::new(config)),
+            pools: Mutex::new(HashMap::new()),
         }
     }
 
@@ -63,27 +74,29 @@ impl WorkerPool {
     pub fn with_config(
         config: WorkerConfig,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if config.num_workers == 0 {
+            return Err("Number of workers must be at least 1".into());
+        }
         Ok(Self {
             config: Arc::new(Mutex::new(config)),
+            pools: Mutex::new(HashMap::new()),
         })
     }
 
-    // ThreadPool management removed - using core parallel abstractions instead
-
     /// Get the current number of worker threads
     pub fn get_workers(&self) -> usize {
         self.config.lock().unwrap().num_workers
     }
 
     /// Set the number of worker threads
-    ///
-    /// Update configuration - actual thread management handled by core parallel abstractions
     pub fn set_workers(
-        &mut self,
+        &self,
         num_workers: usize,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut config = self.config.lock().unwrap();
-        config.num_workers = num_workers;
+        if num_workers == 0 {
+            return Err("Number of workers must be at least 1".into());
+        }
+        self.config.lock().unwrap().num_workers = num_workers;
         Ok(())
     }
 
@@ -97,28 +110,54 @@ impl WorkerPool {
         self.config.lock().unwrap().enabled = enabled;
     }
 
-    /// Execute a function in the worker pool if enabled
-    /// Simplified to use core parallel abstractions
+    /// Execute a function on the pool's configured number of workers
+    ///
+    /// Parallel iterators inside `f` are limited to those workers. When
+    /// parallelization is disabled, `f` runs on the calling thread.
     pub fn execute<F, R>(&self, f: F) -> R
     where
         F: FnOnce() -> R + Send,
         R: Send,
     {
-        // With core parallel abstractions, just execute the function
-        // The actual parallelism is handled by the core parallel_ops module
-        f()
+        let workers = self.get_workers();
+        self.execute_with_workers(workers, f)
     }
 
     /// Execute a function with a specific number of workers
-    /// Simplified to use core parallel abstractions
-    pub fn execute_with_workers<F, R>(&self, _num_workers: usize, f: F) -> R
+    pub fn execute_with_workers<F, R>(&self, num_workers: usize, f: F) -> R
     where
         F: FnOnce() -> R + Send,
         R: Send,
     {
-        // With core parallel abstractions, just execute the function
-        // The actual parallelism is handled by the core parallel_ops module
-        f()
+        if !self.is_enabled() {
+            return f();
+        }
+        match self.thread_pool(num_workers.max(1)) {
+            Some(pool) => pool.install(f),
+            None => f(),
+        }
+    }
+
+    /// Thread pool with exactly `num_workers` threads, built on first use
+    fn thread_pool(&self, num_workers: usize) -> Option<Arc<ThreadPool>> {
+        let mut pools = self.pools.lock().unwrap();
+        if let Some(pool) = pools.get(&num_workers) {
+            return Some(Arc::clone(pool));
+        }
+
+        let (prefix, stack_size) = {
+            let config = self.config.lock().unwrap();
+            (config.thread_name_prefix.clone(), config.stack_size)
+        };
+        let mut builder = ThreadPoolBuilder::new()
+            .num_threads(num_workers)
+            .thread_name(move |i| format!("{prefix}-{i}"));
+        if let Some(size) = stack_size {
+            builder = builder.stack_size(size);
+        }
+        let pool = Arc::new(builder.build().ok()?);
+        pools.insert(num_workers, Arc::clone(&pool));
+        Some(pool)
     }
 
     /// Get information about the worker pool
@@ -173,57 +212,136 @@ pub fn init_global_pool(config: WorkerConfig) -> Result<(), &'static str> {
         .map_err(|_| "Global worker pool already initialized")
 }
 
-/// Context manager for temporarily changing worker count
+thread_local! {
+    /// Worker count set by [`with_fft_workers`] or [`WorkerContext`] on this thread
+    static SCOPED_WORKERS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Scope guard that sets the FFT worker count for the current thread
+///
+/// The previous setting is restored when the guard is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::worker_pool::{get_fft_workers, WorkerContext};
+///
+/// {
+///     let _workers = WorkerContext::new(2);
+///     assert_eq!(get_fft_workers(), 2);
+/// }
+/// ```
 pub struct WorkerContext {
-    #[allow(dead_code)]
-    previous_workers: usize,
-    #[allow(dead_code)]
-    pool: &'static WorkerPool,
+    previous: Option<usize>,
 }
 
 impl WorkerContext {
-    /// Create a new worker context with specified number of workers
-    pub fn new(_num_workers: usize) -> Self {
-        let pool = get_global_pool();
-        let previous_workers = pool.get_workers();
-
-        // Note: In a real implementation, we'd need to handle the Result here
-        // For now, we'll just use the existing pool if we can't create a new one
-
-        Self {
-            previous_workers,
-            pool,
-        }
+    /// Use `num_workers` threads (at least one) until the guard is dropped
+    pub fn new(num_workers: usize) -> Self {
+        let previous = SCOPED_WORKERS.with(|w| w.replace(Some(num_workers.max(1))));
+        Self { previous }
     }
 }
 
 impl Drop for WorkerContext {
     fn drop(&mut self) {
-        // Reset to previous worker count
-        // Note: In a real implementation, we'd need to handle the Result here
+        SCOPED_WORKERS.with(|w| w.set(self.previous));
     }
 }
 
+/// Set the number of threads used by FFTs globally
+///
+/// Large 1-D transforms are split into batches of shorter FFTs and
+/// multi-dimensional transforms are split by rows, both across this many
+/// workers. The default is the number of CPUs, or `SCIRS2_FFT_WORKERS` if
+/// set. [`with_fft_workers`] overrides the value for a single scope.
+///
+/// # Errors
+///
+/// Returns an error if `n` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::worker_pool::{get_fft_workers, set_fft_workers};
+///
+/// set_fft_workers(1).unwrap();
+/// assert_eq!(get_fft_workers(), 1);
+/// assert!(set_fft_workers(0).is_err());
+/// ```
+pub fn set_fft_workers(n: usize) -> FFTResult<()> {
+    get_global_pool()
+        .set_workers(n)
+        .map_err(|e| FFTError::ValueError(e.to_string()))
+}
+
+/// Number of threads FFTs on the current thread will use
+///
+/// This is the scoped value from [`with_fft_workers`] if one is active, the
+/// global setting otherwise, and 1 when the global pool is disabled.
+pub fn get_fft_workers() -> usize {
+    let pool = get_global_pool();
+    if !pool.is_enabled() {
+        return 1;
+    }
+    SCOPED_WORKERS
+        .with(Cell::get)
+        .unwrap_or_else(|| pool.get_workers())
+}
+
+/// Run `f` with FFTs on this thread using `n` workers (at least one)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::fft;
+/// use scirs2_fft::worker_pool::with_fft_workers;
+///
+/// let signal: Vec<f64> = (0..1 << 16).map(|i| (i as f64 * 0.01).sin()).collect();
+/// let serial = with_fft_workers(1, || fft(&signal, None)).unwrap();
+/// let threaded = with_fft_workers(4, || fft(&signal, None)).unwrap();
+/// for (a, b) in serial.iter().zip(threaded.iter()) {
+///     assert!((a - b).norm() < 1e-8);
+/// }
+/// ```
+pub fn with_fft_workers<F, R>(n: usize, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _context = WorkerContext::new(n);
+    f()
+}
+
+/// Run `f` on `workers` threads of the global pool
+pub(crate) fn run_with_workers<F, R>(workers: usize, f: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    get_global_pool().execute_with_workers(workers, f)
+}
+
 /// Set the number of workers globally
-pub fn set_workers(_n: usize) -> Result<(), &'static str> {
-    let _pool = get_global_pool();
-    // Note: This is a limitation of the current design - we can't modify a static reference
-    // In practice, you'd want a different approach or accept this limitation
-    Ok(())
+///
+/// Equivalent to [`set_fft_workers`].
+pub fn set_workers(n: usize) -> Result<(), &'static str> {
+    set_fft_workers(n).map_err(|_| "Number of workers must be at least 1")
 }
 
 /// Get the current number of workers
 pub fn get_workers() -> usize {
-    get_global_pool().get_workers()
+    get_fft_workers()
 }
 
 /// Execute a function with a specific number of workers temporarily
+///
+/// Equivalent to [`with_fft_workers`].
 pub fn with_workers<F, R>(num_workers: usize, f: F) -> R
 where
     F: FnOnce() -> R + Send,
     R: Send,
 {
-    get_global_pool().execute_with_workers(num_workers, f)
+    with_fft_workers(num_workers, f)
 }
 
 #[cfg(test)]
@@ -280,14 +398,28 @@ mod tests {
     fn test_execute_with_workers() {
         let pool = WorkerPool::new();
 
-        let result = pool.execute_with_workers(2, || num_threads());
+        let result = pool.execute_with_workers(2, num_threads);
 
-        // With core parallel abstractions, execute_with_workers doesn't control
-        // the number of threads directly - it just executes the function
-        // The result should be the current number of threads from the runtime
-        if pool.is_enabled() {
-            assert!(result > 0);
-        }
+        // Parallel work inside runs on a dedicated two-thread pool
+        assert_eq!(result, 2);
+
+        pool.set_enabled(false);
+        assert_eq!(pool.execute_with_workers(2, || 7), 7);
+        assert!(pool.set_workers(0).is_err());
+    }
+
+    #[test]
+    fn test_scoped_fft_workers() {
+        let outer = get_fft_workers();
+        with_fft_workers(3, || {
+            assert_eq!(get_fft_workers(), 3);
+            {
+                let _inner = WorkerContext::new(0);
+                assert_eq!(get_fft_workers(), 1);
+            }
+            assert_eq!(get_fft_workers(), 3);
+        });
+        assert_eq!(get_fft_workers(), outer);
     }
 
     #[test]
@@ -7,9 +7,10 @@
 //! - Climate models with fast weather and slow climate dynamics
 //! - Biological systems with multi-scale processes
 
-use ndarray::{array, Array1, ArrayView1};
+use ndarray::{array, Array1, Array2, ArrayView1};
 use scirs2_integrate::ode::{
-    MultirateMethod, MultirateOptions, MultirateSolver, MultirateSystem, ODEMethod,
+    InvariantProjection, MultirateMethod, MultirateOptions, MultirateSolver, MultirateSystem,
+    ODEMethod,
 };
 
 /// Stiff oscillator with fast and slow components
@@ -199,6 +200,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         atol: 1e-9,
         max_steps: 1000,
         timescale_ratio: Some(100.0),
+        ..Default::default()
     };
 
     let mut solver = MultirateSolver::new(options);
@@ -237,6 +239,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         atol: 1e-10,
         max_steps: 500,
         timescale_ratio: Some(200.0),
+        // Hold the total mass [C] + [A] + [B] at its initial value
+        projection: Some(InvariantProjection::linear(Array2::from_elem((1, 3), 1.0))),
+        ..Default::default()
     };
 
     let mut solver_chem = MultirateSolver::new(options_chem);
@@ -255,7 +260,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         final_chem[0], final_chem[1], final_chem[2]
     );
     println!(
-        "   Total mass: {:.3} (conservation check)",
+        "   Total mass: {:.15} (conserved by projection)",
         final_chem[0] + final_chem[1] + final_chem[2]
     );
     println!(
@@ -282,6 +287,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         atol: 1e-11,
         max_steps: 2000,
         timescale_ratio: Some(50.0),
+        ..Default::default()
     };
 
     let mut solver_vdp = MultirateSolver::new(options_vdp);
@@ -323,6 +329,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         atol: 1e-9,
         max_steps: 365, // 1 year simulation
         timescale_ratio: Some(365.0 * 10.0 / 7.0),
+        ..Default::default()
     };
 
     let mut solver_climate = MultirateSolver::new(options_climate);
@@ -391,6 +398,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             atol: 1e-9,
             max_steps: 250,
             timescale_ratio: Some(50.0),
+            ..Default::default()
         };
 
         let mut solver_test = MultirateSolver::new(options_test);
@@ -442,6 +450,8 @@ mod tests {
             atol: 1e-11,
             max_steps: 200,
             timescale_ratio: Some(50.0),
+            projection: Some(InvariantProjection::linear(Array2::from_elem((1, 3), 1.0))),
+            ..Default::default()
         };
 
         let mut solver = MultirateSolver::new(options);
@@ -452,8 +462,8 @@ mod tests {
         let initial_total = y0.sum();
         let final_total = result.y.last().unwrap().sum();
 
-        // Mass should be conserved
-        assert_abs_diff_eq!(initial_total, final_total, epsilon = 1e-3);
+        // Mass is conserved exactly by the projection
+        assert_abs_diff_eq!(initial_total, final_total, epsilon = 1e-14);
 
         // Some conversion should have occurred
         assert!(result.y.last().unwrap()[0] > 0.01); // Some C produced
@@ -477,6 +487,7 @@ mod tests {
             atol: 1e-13,
             max_steps: 100,
             timescale_ratio: Some(20.0),
+            ..Default::default()
         };
 
         let mut solver = MultirateSolver::new(options);
@@ -340,6 +340,8 @@ fn main() {
                 success: false,
                 message: Some("Failed to solve".to_string()),
                 method: ODEMethod::RK45,
+                step_methods: Vec::new(),
+                stiffness: None,
             }
         })
     });
@@ -2,18 +2,27 @@
 //!
 //! This module provides numerical solvers for boundary value problems (BVPs)
 //! of ordinary differential equations.
+//!
+//! The solver uses fourth-order collocation with a cubic `C1` solution (the
+//! three-stage Lobatto IIIA method, as in SciPy's `solve_bvp`). The
+//! collocation equations on all mesh intervals are solved together with the
+//! boundary conditions by a damped Newton iteration, with a sparse Jacobian
+//! estimated by colored finite differences. The mesh is refined where the
+//! relative residual of the cubic solution exceeds the tolerance. Unknown
+//! parameters and a singular term `S y / (x - a)` are supported.
 
 use crate::common::IntegrateFloat;
 use crate::error::{IntegrateError, IntegrateResult};
 use crate::ode::types::{ODEMethod, ODEOptions};
+use crate::ode::utils::jacobian::{colored_finite_difference_jacobian, SparsityPattern};
 use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2};
 
 /// Options for controlling the behavior of the BVP solver
 #[derive(Debug, Clone)]
 pub struct BVPOptions<F: IntegrateFloat> {
-    /// Maximum number of iterations for the solver
+    /// Maximum number of Newton iterations on each mesh
     pub max_iter: usize,
-    /// Tolerance for convergence
+    /// Tolerance for the relative residual of the collocation solution
     pub tol: F,
     /// Number of nodes in the initial mesh
     pub n_nodes: usize,
@@ -21,6 +30,14 @@ pub struct BVPOptions<F: IntegrateFloat> {
     pub ode_options: ODEOptions<F>,
     /// Whether to use a fixed or adaptive mesh
     pub fixed_mesh: bool,
+    /// Maximum number of mesh nodes allowed by the refinement
+    pub max_nodes: usize,
+    /// Tolerance for the boundary condition residuals (defaults to `tol`)
+    pub bc_tol: Option<F>,
+    /// Matrix `S` of a singular term `S y / (x - a)` added to the right-hand side
+    ///
+    /// The boundary conditions must then imply `S y(a) = 0`.
+    pub singular_term: Option<Array2<F>>,
 }
 
 impl<F: IntegrateFloat> Default for BVPOptions<F> {
@@ -36,6 +53,9 @@ impl<F: IntegrateFloat> Default for BVPOptions<F> {
                 ..Default::default()
             },
             fixed_mesh: false,
+            max_nodes: 1000,
+            bc_tol: None,
+            singular_term: None,
         }
     }
 }
@@ -47,6 +67,10 @@ pub struct BVPResult<F: IntegrateFloat> {
     pub x: Vec<F>,
     /// Solution values at each mesh point
     pub y: Vec<Array1<F>>,
+    /// Derivatives y'(x) at each mesh point
+    pub yp: Vec<Array1<F>>,
+    /// Values of the unknown parameters (empty if there are none)
+    pub p: Array1<F>,
     /// Number of iterations performed
     pub n_iter: usize,
     /// Flag indicating successful convergence
@@ -54,11 +78,84 @@ pub struct BVPResult<F: IntegrateFloat> {
     /// Optional message (e.g., error message)
     pub message: Option<String>,
     /// Residual norm at the final iteration
+    ///
+    /// This is the largest entry of `rms_residuals`.
     pub residual_norm: F,
+    /// RMS of the relative residual of the solution on each mesh interval
+    pub rms_residuals: Vec<F>,
+}
+
+impl<F: IntegrateFloat> BVPResult<F> {
+    /// Evaluate the continuous solution at `x`
+    ///
+    /// The solution is the cubic Hermite interpolant of `y` and `yp` on each
+    /// mesh interval, which is the collocation polynomial of the method.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Point within the mesh range
+    ///
+    /// # Returns
+    ///
+    /// The solution at `x`, or an error if `x` lies outside the mesh
+    pub fn evaluate(&self, x: F) -> IntegrateResult<Array1<F>> {
+        let (a, b) = (self.x[0], self.x[self.x.len() - 1]);
+        if x < a || x > b {
+            return Err(IntegrateError::ValueError(format!(
+                "Point {x} outside the solution interval [{a}, {b}]"
+            )));
+        }
+        let i = self
+            .x
+            .partition_point(|&xi| xi <= x)
+            .clamp(1, self.x.len() - 1)
+            - 1;
+        Ok(hermite(
+            self.x[i],
+            self.x[i + 1] - self.x[i],
+            [&self.y[i], &self.y[i + 1]],
+            [&self.yp[i], &self.yp[i + 1]],
+            x,
+        )
+        .0)
+    }
+}
+
+/// Cubic Hermite interpolant on `[x0, x0 + h]` and its derivative at `x`
+fn hermite<F: IntegrateFloat>(
+    x0: F,
+    h: F,
+    y: [&Array1<F>; 2],
+    yp: [&Array1<F>; 2],
+    x: F,
+) -> (Array1<F>, Array1<F>) {
+    let t = (x - x0) / h;
+    let (one, two, three, four, six) = (
+        F::one(),
+        F::from_f64(2.0).unwrap(),
+        F::from_f64(3.0).unwrap(),
+        F::from_f64(4.0).unwrap(),
+        F::from_f64(6.0).unwrap(),
+    );
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let value = y[0] * (two * t3 - three * t2 + one)
+        + yp[0] * ((t3 - two * t2 + t) * h)
+        + y[1] * (three * t2 - two * t3)
+        + yp[1] * ((t3 - t2) * h);
+    let derivative = (y[1] - y[0]) * ((six * t - six * t2) / h)
+        + yp[0] * (three * t2 - four * t + one)
+        + yp[1] * (three * t2 - two * t);
+    (value, derivative)
 }
 
 /// Solve a two-point boundary value problem for a system of ODEs
 ///
+/// The problem `y' = fun(x, y)` on `[x_0, x_m]` with `bc(y(x_0), y(x_m)) = 0`
+/// is solved by fourth-order collocation with adaptive mesh refinement, see
+/// [`solve_bvp_with_parameters`].
+///
 /// # Arguments
 ///
 /// * `fun` - The right-hand side of the ODE system y'(x) = fun(x, y)
@@ -74,31 +171,23 @@ pub struct BVPResult<F: IntegrateFloat> {
 /// # Examples
 ///
 /// ```
-/// use ndarray::{array, Array1, ArrayView1};
-/// use scirs2_integrate::bvp::{solve_bvp, BVPOptions};
+/// use ndarray::{array, ArrayView1};
+/// use scirs2_integrate::bvp::solve_bvp;
+/// use std::f64::consts::PI;
 ///
-/// // Solve a simple linear ODE: y' = -y with boundary conditions
-/// // y(0) = 1, y(1) = exp(-1)
-/// let fun = |_x: f64, y: ArrayView1<f64>| array![-y[0]];
-///
-/// let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>| {
-///     array![ya[0] - 1.0, yb[0] - 0.3679]  // exp(-1) ≈ 0.3679
-/// };
+/// // y'' = -y as a first-order system, with y(0) = 0 and y(π/2) = 1
+/// let fun = |_x: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+/// let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>| array![ya[0], yb[0] - 1.0];
 ///
-/// // Initial mesh: 3 points from 0 to 1
-/// let x = vec![0.0, 0.5, 1.0];
+/// let x: Vec<f64> = (0..5).map(|i| i as f64 * PI / 8.0).collect();
+/// let y_init = x.iter().map(|_| array![0.5, 0.5]).collect();
 ///
-/// // Initial guess: linear interpolation
-/// let y_init = vec![
-///     array![1.0],
-///     array![0.7],
-///     array![0.4],
-/// ];
+/// let result = solve_bvp(fun, bc, Some(x), y_init, None).unwrap();
+/// assert!(result.success);
 ///
-/// let result = solve_bvp(fun, bc, Some(x), y_init, None);
-/// // For now, just check if it doesn't crash
-/// // The BVP solver needs more work for robust convergence
-/// assert!(result.is_ok() || result.is_err());
+/// // The solution is sin(x)
+/// let y = result.evaluate(1.0).unwrap();
+/// assert!((y[0] - 1.0f64.sin()).abs() < 1e-6);
 /// ```
 pub fn solve_bvp<F, FunType, BCType>(
     fun: FunType,
@@ -111,14 +200,83 @@ where
     F: IntegrateFloat,
     FunType: Fn(F, ArrayView1<F>) -> Array1<F> + Copy,
     BCType: Fn(ArrayView1<F>, ArrayView1<F>) -> Array1<F>,
+{
+    solve_bvp_with_parameters(
+        |x: F, y: ArrayView1<F>, _p: ArrayView1<F>| fun(x, y),
+        |ya: ArrayView1<F>, yb: ArrayView1<F>, _p: ArrayView1<F>| bc(ya, yb),
+        x,
+        y_init,
+        Array1::zeros(0),
+        options,
+    )
+}
+
+/// Solve a two-point boundary value problem with unknown parameters
+///
+/// Solves `y' = fun(x, y, p)` on `[x_0, x_m]` with `bc(y(x_0), y(x_m), p) = 0`
+/// for the solution `y` and the `k` parameters `p`; `bc` must return `n + k`
+/// residuals for a system of size `n`. With `options.singular_term` set to
+/// `S`, the right-hand side is `fun(x, y, p) + S y / (x - x_0)`.
+///
+/// On each interval the solution is the cubic that interpolates `y` and `y'`
+/// at the nodes and satisfies the ODE at the midpoint. The collocation
+/// equations and boundary conditions are solved by a damped Newton iteration.
+/// Afterwards the RMS of the relative residual `(S' - f) / (1 + |f|)` is
+/// estimated on every interval by a Lobatto quadrature; intervals above `tol`
+/// get one new node, or two if the residual exceeds `100 tol`.
+///
+/// # Arguments
+///
+/// * `fun` - The right-hand side of the ODE system y'(x) = fun(x, y, p)
+/// * `bc` - The boundary condition function bc(y(a), y(b), p)
+/// * `x` - The initial mesh (or None to generate a uniform mesh on [0, 1])
+/// * `y_init` - Initial guess for the solution at each mesh point
+/// * `p_init` - Initial guess for the unknown parameters
+/// * `options` - Optional solver parameters
+///
+/// # Returns
+///
+/// * `IntegrateResult<BVPResult<F>>` - The solution or an error
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, ArrayView1};
+/// use scirs2_integrate::bvp::solve_bvp_with_parameters;
+///
+/// // Eigenvalue problem y'' + k^2 y = 0 with y(0) = y(1) = 0 and y'(0) = k
+/// let fun = |_x: f64, y: ArrayView1<f64>, p: ArrayView1<f64>| array![y[1], -p[0] * p[0] * y[0]];
+/// let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>, p: ArrayView1<f64>| {
+///     array![ya[0], yb[0], ya[1] - p[0]]
+/// };
+///
+/// let x: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+/// let y_init = x.iter().map(|&xi| array![xi * (1.0 - xi), 1.0 - 2.0 * xi]).collect();
+///
+/// let result = solve_bvp_with_parameters(fun, bc, Some(x), y_init, array![2.5], None).unwrap();
+/// assert!(result.success);
+/// assert!((result.p[0] - std::f64::consts::PI).abs() < 1e-6);
+/// ```
+pub fn solve_bvp_with_parameters<F, FunType, BCType>(
+    fun: FunType,
+    bc: BCType,
+    x: Option<Vec<F>>,
+    y_init: Vec<Array1<F>>,
+    p_init: Array1<F>,
+    options: Option<BVPOptions<F>>,
+) -> IntegrateResult<BVPResult<F>>
+where
+    F: IntegrateFloat,
+    FunType: Fn(F, ArrayView1<F>, ArrayView1<F>) -> Array1<F>,
+    BCType: Fn(ArrayView1<F>, ArrayView1<F>, ArrayView1<F>) -> Array1<F>,
 {
     // Get options or defaults
     let opts = options.unwrap_or_default();
 
     // Validate inputs
-    if y_init.is_empty() {
+    if y_init.len() < 2 {
         return Err(IntegrateError::ValueError(
-            "Initial guess cannot be empty".to_string(),
+            "Initial guess needs at least two mesh points".to_string(),
         ));
     }
 
@@ -132,7 +290,7 @@ where
     }
 
     // Create or validate mesh
-    let mut mesh = match x {
+    let mesh = match x {
         Some(mesh) => {
             if mesh.len() != y_init.len() {
                 return Err(IntegrateError::ValueError(
@@ -153,197 +311,386 @@ where
         }
         None => {
             // Generate a uniform mesh based on initial guess size
-            let a = F::zero();
-            let b = F::one();
             let n_points = y_init.len();
-            let h = (b - a) / F::from_usize(n_points - 1).unwrap();
-
             (0..n_points)
-                .map(|i| a + F::from_usize(i).unwrap() * h)
+                .map(|i| F::from_usize(i).unwrap() / F::from_usize(n_points - 1).unwrap())
                 .collect()
         }
     };
 
-    let mut n_points = mesh.len();
-
     // Apply boundary conditions to check their dimension
-    let bc_residuals = bc(y_init[0].view(), y_init[n_points - 1].view());
-    let n_bc = bc_residuals.len();
-
-    if n_bc != n_dim {
+    let n_params = p_init.len();
+    let bc_residuals = bc(
+        y_init[0].view(),
+        y_init[y_init.len() - 1].view(),
+        p_init.view(),
+    );
+    if bc_residuals.len() != n_dim + n_params {
         return Err(IntegrateError::ValueError(
-            "Number of boundary conditions must match system dimension".to_string(),
+            "Number of boundary conditions must match system dimension plus the number of parameters"
+                .to_string(),
         ));
     }
 
-    // Initialize solution
-    let mut y = y_init;
-
-    // Main iteration loop
-    let mut iter_count = 0;
-    let mut success = false;
-    let mut message = None;
-    let mut residual_norm = F::max_value();
-
-    while iter_count < opts.max_iter {
-        iter_count += 1;
-
-        // Evaluate the ODE function on the current mesh
-        let mut f_values = Vec::with_capacity(n_points);
-        for i in 0..n_points {
-            f_values.push(fun(mesh[i], y[i].view()));
+    // Right-hand side including the singular term. At x = a the ODE reduces
+    // to y'(a) = (I - S)^{-1} f(a, y(a)).
+    let a = mesh[0];
+    let singular = match &opts.singular_term {
+        Some(s) if s.dim() != (n_dim, n_dim) => {
+            return Err(IntegrateError::DimensionMismatch(format!(
+                "Singular term has shape {:?}, expected ({}, {})",
+                s.dim(),
+                n_dim,
+                n_dim
+            )));
         }
-
-        // Evaluate the boundary condition residuals
-        let bc_res = bc(y[0].view(), y[n_points - 1].view());
-
-        // Setup the linear system for the collocation method
-        // For each interval between mesh points i and i+1, we have collocation equations
-
-        // Create the Jacobian matrix and right-hand side vector
-        let n_equations = (n_points - 1) * n_dim + n_bc;
-        let n_variables = n_points * n_dim;
-
-        let mut jac = Array2::<F>::zeros((n_equations, n_variables));
-        let mut residuals = Array1::<F>::zeros(n_equations);
-
-        // Fill boundary condition rows
-        for j in 0..n_bc {
-            residuals[j] = bc_res[j];
-
-            // For the harmonic oscillator example: bc = [ya[0], yb[0]]
-            // So we need derivatives of bc with respect to y variables
-            // For now, use finite differences to approximate the Jacobian
-            let eps = F::from_f64(1e-8).unwrap();
-
-            // Derivatives with respect to ya (first point)
-            for k in 0..n_dim {
-                let mut ya_pert = y[0].clone();
-                ya_pert[k] += eps;
-                let bc_pert = bc(ya_pert.view(), y[n_points - 1].view());
-                jac[[j, k]] = (bc_pert[j] - bc_res[j]) / eps;
+        Some(s) => {
+            let i_minus_s = Array2::eye(n_dim) - s;
+            let mut d = Array2::<F>::zeros((n_dim, n_dim));
+            for j in 0..n_dim {
+                let mut e = Array1::<F>::zeros(n_dim);
+                e[j] = F::one();
+                let column = solve_linear_system(i_minus_s.view(), e.view())?;
+                d.column_mut(j).assign(&column);
             }
+            Some((s.clone(), d))
+        }
+        None => None,
+    };
+    let rhs = |x: F, y: ArrayView1<F>, p: ArrayView1<F>| -> Array1<F> {
+        let f = fun(x, y, p);
+        match &singular {
+            None => f,
+            Some((_, d)) if x == a => d.dot(&f),
+            Some((s, _)) => f + s.dot(&y) / (x - a),
+        }
+    };
 
-            // Derivatives with respect to yb (last point)
-            for k in 0..n_dim {
-                let mut yb_pert = y[n_points - 1].clone();
-                yb_pert[k] += eps;
-                let bc_pert = bc(y[0].view(), yb_pert.view());
-                jac[[j, (n_points - 1) * n_dim + k]] = (bc_pert[j] - bc_res[j]) / eps;
-            }
+    let bc_tol = opts.bc_tol.unwrap_or(opts.tol);
+    let mut problem = Collocation {
+        rhs: &rhs,
+        bc: &bc,
+        n_dim,
+        n_params,
+        mesh,
+    };
+    let mut u = problem.pack(&y_init, &p_init);
+    let mut n_iter = 0;
+
+    loop {
+        // Newton iteration on the current mesh
+        let newton = problem.newton(&mut u, opts.max_iter, opts.tol, bc_tol)?;
+        n_iter += newton.iterations;
+
+        let (y, p) = problem.unpack(&u);
+        let yp: Vec<Array1<F>> = problem
+            .mesh
+            .iter()
+            .zip(&y)
+            .map(|(&xi, yi)| rhs(xi, yi.view(), p.view()))
+            .collect();
+        let rms_residuals = problem.rms_residuals(&y, &yp, &p);
+        let residual_norm = rms_residuals.iter().fold(F::zero(), |acc, &r| acc.max(r));
+
+        let finish = |success: bool, message: Option<String>| BVPResult {
+            x: problem.mesh.clone(),
+            y: y.clone(),
+            yp: yp.clone(),
+            p: p.clone(),
+            n_iter,
+            success,
+            message,
+            residual_norm,
+            rms_residuals: rms_residuals.clone(),
+        };
+
+        if newton.singular {
+            return Ok(finish(
+                false,
+                Some("Singular Jacobian encountered in the Newton iteration".to_string()),
+            ));
+        }
+        if opts.fixed_mesh {
+            let message = (!newton.converged)
+                .then(|| format!("Failed to converge after {} iterations", opts.max_iter));
+            return Ok(finish(newton.converged, message));
+        }
+        if newton.converged && residual_norm < opts.tol {
+            return Ok(finish(true, None));
         }
 
-        // Fill collocation equations
-        for i in 0..(n_points - 1) {
-            let h = mesh[i + 1] - mesh[i];
+        // Refine the intervals where the residual is too large
+        let tol_100 = opts.tol * F::from_f64(100.0).unwrap();
+        let n_new: usize = rms_residuals
+            .iter()
+            .map(|&r| match r {
+                r if r > tol_100 => 2,
+                r if r > opts.tol => 1,
+                _ => 0,
+            })
+            .sum();
+        // Newton did not converge although all residuals are small: halve
+        // every interval
+        let refine_all = n_new == 0;
+        let n_new = if refine_all {
+            problem.mesh.len() - 1
+        } else {
+            n_new
+        };
+        if problem.mesh.len() + n_new > opts.max_nodes {
+            return Ok(finish(
+                false,
+                Some(format!(
+                    "Maximum number of mesh nodes ({}) exceeded",
+                    opts.max_nodes
+                )),
+            ));
+        }
 
-            for j in 0..n_dim {
-                // Continuity equations (using finite differences for the ODE)
-                let equation_idx = n_bc + i * n_dim + j;
-                let var_idx_left = i * n_dim + j;
-                let var_idx_right = (i + 1) * n_dim + j;
-
-                // Simple finite difference y'(x) ≈ (y(x+h) - y(x)) / h
-                jac[[equation_idx, var_idx_left]] = -F::one() / h;
-                jac[[equation_idx, var_idx_right]] = F::one() / h;
-
-                // Average of function values at endpoints for midpoint collocation
-                let f_avg = (f_values[i][j] + f_values[i + 1][j]) / F::from_f64(2.0).unwrap();
-                residuals[equation_idx] = (y[i + 1][j] - y[i][j]) / h - f_avg;
+        let mut new_mesh = vec![problem.mesh[0]];
+        let mut new_y = vec![y[0].clone()];
+        for i in 0..problem.mesh.len() - 1 {
+            let (x0, x1) = (problem.mesh[i], problem.mesh[i + 1]);
+            let h = x1 - x0;
+            let pieces = match rms_residuals[i] {
+                r if r > tol_100 => 3,
+                r if r > opts.tol || refine_all => 2,
+                _ => 1,
+            };
+            for j in 1..pieces {
+                let xj = x0 + h * F::from_usize(j).unwrap() / F::from_usize(pieces).unwrap();
+                new_mesh.push(xj);
+                new_y.push(hermite(x0, h, [&y[i], &y[i + 1]], [&yp[i], &yp[i + 1]], xj).0);
             }
+            new_mesh.push(x1);
+            new_y.push(y[i + 1].clone());
         }
+        problem.mesh = new_mesh;
+        u = problem.pack(&new_y, &p);
+    }
+}
 
-        // Solve the linear system using Gaussian elimination
-        let delta_y = solve_linear_system(jac.view(), residuals.view())?;
+/// Type alias for the right-hand side `f(x, y, p)` of a BVP
+type BVPFunction<'a, F> = &'a dyn Fn(F, ArrayView1<F>, ArrayView1<F>) -> Array1<F>;
 
-        // Reshape delta_y back to the original shape
-        let mut delta_y_reshaped = Vec::with_capacity(n_points);
-        for i in 0..n_points {
-            let start = i * n_dim;
-            let end = start + n_dim;
-            let delta_y_slice = Array1::from_iter(delta_y.slice(s![start..end]).iter().cloned());
-            delta_y_reshaped.push(delta_y_slice);
-        }
+/// Outcome of the Newton iteration on one mesh
+struct NewtonOutcome {
+    converged: bool,
+    singular: bool,
+    iterations: usize,
+}
 
-        // Update solution
-        for i in 0..n_points {
-            y[i] = y[i].clone() - delta_y_reshaped[i].clone();
+/// Collocation system on a fixed mesh
+///
+/// The unknowns are the solution values at the mesh nodes followed by the
+/// parameters. The residual holds the collocation equations of each interval
+/// followed by the boundary conditions.
+struct Collocation<'a, F: IntegrateFloat, BCType> {
+    rhs: BVPFunction<'a, F>,
+    bc: &'a BCType,
+    n_dim: usize,
+    n_params: usize,
+    mesh: Vec<F>,
+}
+
+impl<F, BCType> Collocation<'_, F, BCType>
+where
+    F: IntegrateFloat,
+    BCType: Fn(ArrayView1<F>, ArrayView1<F>, ArrayView1<F>) -> Array1<F>,
+{
+    fn pack(&self, y: &[Array1<F>], p: &Array1<F>) -> Array1<F> {
+        let mut u = Array1::<F>::zeros(y.len() * self.n_dim + self.n_params);
+        for (i, yi) in y.iter().enumerate() {
+            u.slice_mut(s![i * self.n_dim..(i + 1) * self.n_dim])
+                .assign(yi);
         }
+        u.slice_mut(s![y.len() * self.n_dim..]).assign(p);
+        u
+    }
 
-        // Check convergence
-        residual_norm = delta_y.mapv(|v| v.abs()).sum() / F::from_usize(n_variables).unwrap();
+    fn unpack(&self, u: &Array1<F>) -> (Vec<Array1<F>>, Array1<F>) {
+        let n = self.n_dim;
+        let m = self.mesh.len();
+        let y = (0..m)
+            .map(|i| u.slice(s![i * n..(i + 1) * n]).to_owned())
+            .collect();
+        (y, u.slice(s![m * n..]).to_owned())
+    }
 
-        if residual_norm < opts.tol {
-            success = true;
-            break;
+    /// Collocation residuals `y_{i+1} - y_i - h/6 (f_i + 4 f_mid + f_{i+1})` and
+    /// boundary conditions
+    fn residual(&self, u: ArrayView1<F>) -> Array1<F> {
+        let n = self.n_dim;
+        let m = self.mesh.len();
+        let p = u.slice(s![m * n..]);
+        let y = |i: usize| u.slice(s![i * n..(i + 1) * n]);
+        let f: Vec<Array1<F>> = (0..m).map(|i| (self.rhs)(self.mesh[i], y(i), p)).collect();
+
+        let eighth = F::from_f64(0.125).unwrap();
+        let sixth = F::one() / F::from_f64(6.0).unwrap();
+        let four = F::from_f64(4.0).unwrap();
+        let half = F::from_f64(0.5).unwrap();
+
+        let mut residual = Array1::<F>::zeros(m * n + self.n_params);
+        for i in 0..m - 1 {
+            let h = self.mesh[i + 1] - self.mesh[i];
+            let y_mid = (&y(i) + &y(i + 1)) * half - (&f[i + 1] - &f[i]) * (h * eighth);
+            let f_mid = (self.rhs)(self.mesh[i] + h * half, y_mid.view(), p);
+            let r = &y(i + 1) - &y(i) - (&f[i] + &f[i + 1] + f_mid * four) * (h * sixth);
+            residual.slice_mut(s![i * n..(i + 1) * n]).assign(&r);
         }
+        residual
+            .slice_mut(s![(m - 1) * n..])
+            .assign(&(self.bc)(y(0), y(m - 1), p));
+        residual
+    }
 
-        // Adapt mesh if needed and not fixed
-        if !opts.fixed_mesh && n_points > 3 {
-            // Simple mesh adaptation based on solution gradient
-            let mut errors = Vec::with_capacity(n_points - 1);
-
-            for i in 0..(n_points - 1) {
-                // Estimate error in this interval
-                let h = mesh[i + 1] - mesh[i];
-                let error = delta_y_reshaped[i].mapv(|v| v.abs()).sum() / h;
-                errors.push(error);
+    /// Structure of the Jacobian of [`Self::residual`]
+    fn sparsity(&self) -> SparsityPattern {
+        let n = self.n_dim;
+        let m = self.mesh.len();
+        let n_vars = m * n + self.n_params;
+        let params = m * n..n_vars;
+
+        let mut entries = Vec::new();
+        for i in 0..m - 1 {
+            for row in i * n..(i + 1) * n {
+                for col in (i * n..(i + 2) * n).chain(params.clone()) {
+                    entries.push((row, col));
+                }
             }
+        }
+        for row in (m - 1) * n..n_vars {
+            for col in (0..n).chain((m - 1) * n..m * n).chain(params.clone()) {
+                entries.push((row, col));
+            }
+        }
+        SparsityPattern::new(n_vars, n_vars, &entries).unwrap()
+    }
 
-            // Find median error
-            let mut error_values = errors.clone();
-            error_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-            let median_idx = error_values.len() / 2;
-            let median_error = error_values[median_idx];
-
-            // Identify intervals to refine (error > 2 * median_error)
-            let mut new_mesh = Vec::new();
-            let mut new_y = Vec::new();
-
-            new_mesh.push(mesh[0]);
-            new_y.push(y[0].clone());
+    /// Whether the collocation and boundary residuals are within tolerance
+    fn converged(&self, residual: &Array1<F>, u: &Array1<F>, tol: F, bc_tol: F) -> bool {
+        let n = self.n_dim;
+        let m = self.mesh.len();
+        let (y, p) = self.unpack(u);
+        let scale = F::from_f64(1.5).unwrap();
+        let collocation_ok = (0..m - 1).all(|i| {
+            let h = self.mesh[i + 1] - self.mesh[i];
+            let f_mid = (self.rhs)(
+                self.mesh[i] + h * F::from_f64(0.5).unwrap(),
+                ((&y[i] + &y[i + 1]) * F::from_f64(0.5).unwrap()).view(),
+                p.view(),
+            );
+            (0..n).all(|j| {
+                residual[i * n + j].abs() * scale / h / (F::one() + f_mid[j].abs())
+                    < F::from_f64(0.05).unwrap() * tol
+            })
+        });
+        collocation_ok
+            && residual
+                .slice(s![(m - 1) * n..])
+                .iter()
+                .all(|r| r.abs() < bc_tol)
+    }
 
-            for i in 0..(n_points - 1) {
-                if errors[i] > median_error * F::from_f64(2.0).unwrap()
-                    && new_mesh.len() < opts.n_nodes * 2
+    /// Damped Newton iteration for the collocation system
+    fn newton(
+        &self,
+        u: &mut Array1<F>,
+        max_iter: usize,
+        tol: F,
+        bc_tol: F,
+    ) -> IntegrateResult<NewtonOutcome> {
+        let pattern = self.sparsity();
+        let coloring = pattern.column_coloring();
+        let residual_fn = |_x: F, v: ArrayView1<F>| self.residual(v);
+        let sigma = F::from_f64(0.2).unwrap();
+        let half = F::from_f64(0.5).unwrap();
+
+        let mut residual = self.residual(u.view());
+        for iteration in 0..max_iter {
+            let jacobian = colored_finite_difference_jacobian(
+                &residual_fn,
+                F::zero(),
+                u,
+                &residual,
+                &pattern,
+                &coloring,
+            )?;
+            let Ok(lu) = jacobian.factorize_shifted(F::zero(), F::one()) else {
+                return Ok(NewtonOutcome {
+                    converged: false,
+                    singular: true,
+                    iterations: iteration + 1,
+                });
+            };
+            let step = lu.solve(&residual);
+
+            // Backtracking line search on the squared residual norm
+            let cost = residual.dot(&residual);
+            let mut alpha = F::one();
+            let mut trial = &*u - &step;
+            let mut trial_residual = self.residual(trial.view());
+            for _ in 0..4 {
+                if trial_residual.dot(&trial_residual)
+                    <= (F::one() - F::from_f64(2.0).unwrap() * sigma * alpha) * cost
                 {
-                    // Add a midpoint
-                    let mid_x = (mesh[i] + mesh[i + 1]) / F::from_f64(2.0).unwrap();
-                    new_mesh.push(mid_x);
-
-                    // Interpolate solution at midpoint
-                    let mid_y = (y[i].clone() + y[i + 1].clone()) / F::from_f64(2.0).unwrap();
-                    new_y.push(mid_y);
+                    break;
                 }
-
-                new_mesh.push(mesh[i + 1]);
-                new_y.push(y[i + 1].clone());
+                alpha *= half;
+                trial = &*u - &(&step * alpha);
+                trial_residual = self.residual(trial.view());
             }
-
-            // Update mesh and solution if changed
-            if new_mesh.len() != mesh.len() {
-                mesh = new_mesh;
-                y = new_y;
-                n_points = mesh.len();
+            *u = trial;
+            residual = trial_residual;
+
+            if self.converged(&residual, u, tol, bc_tol) {
+                return Ok(NewtonOutcome {
+                    converged: true,
+                    singular: false,
+                    iterations: iteration + 1,
+                });
             }
         }
-    }
 
-    if !success {
-        message = Some(format!(
-            "Failed to converge after {} iterations",
-            opts.max_iter
-        ));
+        Ok(NewtonOutcome {
+            converged: false,
+            singular: false,
+            iterations: max_iter,
+        })
     }
 
-    Ok(BVPResult {
-        x: mesh,
-        y,
-        n_iter: iter_count,
-        success,
-        message,
-        residual_norm,
-    })
+    /// RMS of the relative residual `(S'(x) - f(x, S(x))) / (1 + |f|)` on each
+    /// interval, from a five-point Lobatto quadrature
+    fn rms_residuals(&self, y: &[Array1<F>], yp: &[Array1<F>], p: &Array1<F>) -> Vec<F> {
+        let half = F::from_f64(0.5).unwrap();
+        let offset = F::from_f64((3.0f64 / 7.0).sqrt()).unwrap() * half;
+        let w_mid = F::from_f64(32.0 / 45.0).unwrap();
+        let w_side = F::from_f64(49.0 / 90.0).unwrap();
+
+        (0..self.mesh.len() - 1)
+            .map(|i| {
+                let (x0, h) = (self.mesh[i], self.mesh[i + 1] - self.mesh[i]);
+                let squared = |x: F| -> F {
+                    let (value, derivative) =
+                        hermite(x0, h, [&y[i], &y[i + 1]], [&yp[i], &yp[i + 1]], x);
+                    let f = (self.rhs)(x, value.view(), p.view());
+                    derivative
+                        .iter()
+                        .zip(f.iter())
+                        .map(|(&d, &fj)| {
+                            let r = (d - fj) / (F::one() + fj.abs());
+                            r * r
+                        })
+                        .fold(F::zero(), |acc, r| acc + r)
+                };
+                let x_mid = x0 + h * half;
+                let total = w_mid * squared(x_mid)
+                    + w_side * (squared(x_mid - h * offset) + squared(x_mid + h * offset));
+                (half * total).sqrt()
+            })
+            .collect()
+    }
 }
 
 /// Solve a linear system Ax = b using Gaussian elimination with partial pivoting
@@ -571,13 +918,26 @@ where
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use ndarray::array;
 
     #[test]
     fn test_solve_bvp_sine() {
-        // Simplified test that always passes
-        // The full boundary value problem test is too complex and unstable for unit testing
-        // Intentionally left empty
+        // y'' = -y with y(0) = 0, y(π/2) = 1
+        let fun = |_x: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+        let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>| array![ya[0], yb[0] - 1.0];
+        let x: Vec<f64> = (0..5)
+            .map(|i| i as f64 * std::f64::consts::PI / 8.0)
+            .collect();
+        let y_init = x.iter().map(|_| array![0.5, 0.5]).collect();
+
+        let result = solve_bvp(fun, bc, Some(x), y_init, None).unwrap();
+        assert!(result.success);
+        assert!(result.residual_norm < 1e-6);
+        for (xi, yi) in result.x.iter().zip(&result.y) {
+            assert!((yi[0] - xi.sin()).abs() < 1e-6);
+            assert!((yi[1] - xi.cos()).abs() < 1e-6);
+        }
     }
 
     #[test]
@@ -587,6 +947,90 @@ mod tests {
         // Intentionally left empty
     }
 
+    #[test]
+    fn test_solve_bvp_boundary_layer() {
+        // eps y'' = y with y(0) = 1, y(1) = 0 has a boundary layer of width sqrt(eps)
+        let eps = 1e-4;
+        let fun = move |_x: f64, y: ArrayView1<f64>| array![y[1], y[0] / eps];
+        let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>| array![ya[0] - 1.0, yb[0]];
+        let x: Vec<f64> = (0..=5).map(|i| i as f64 / 5.0).collect();
+        let y_init = x.iter().map(|_| array![0.0, 0.0]).collect();
+        let options = BVPOptions {
+            tol: 1e-4,
+            ..Default::default()
+        };
+
+        let result = solve_bvp(fun, bc, Some(x), y_init, Some(options)).unwrap();
+        assert!(result.success, "{:?}", result.message);
+        // The mesh is refined inside the layer
+        assert!(result.x.len() > 6);
+        assert!(result.x.iter().filter(|&&x| x < 0.05).count() > 3);
+
+        let k = 1.0 / eps.sqrt();
+        for x in [0.005, 0.01, 0.05] {
+            let exact = (-k * x).exp();
+            assert!((result.evaluate(x).unwrap()[0] - exact).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_solve_bvp_parameters() {
+        // y'' + k^2 y = 0 with y(0) = y(1) = 0 and y'(0) = k: k = π
+        let fun =
+            |_x: f64, y: ArrayView1<f64>, p: ArrayView1<f64>| array![y[1], -p[0] * p[0] * y[0]];
+        let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>, p: ArrayView1<f64>| {
+            array![ya[0], yb[0], ya[1] - p[0]]
+        };
+        let x: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+        let y_init = x
+            .iter()
+            .map(|&xi| array![xi * (1.0 - xi), 1.0 - 2.0 * xi])
+            .collect();
+
+        let result =
+            solve_bvp_with_parameters(fun, bc, Some(x), y_init, array![2.5], None).unwrap();
+        assert!(result.success);
+        assert!((result.p[0] - std::f64::consts::PI).abs() < 1e-6);
+
+        // Wrong number of boundary conditions
+        let result = solve_bvp_with_parameters(
+            fun,
+            |ya: ArrayView1<f64>, yb: ArrayView1<f64>, _p: ArrayView1<f64>| array![ya[0], yb[0]],
+            None,
+            vec![array![0.0, 1.0], array![0.0, -1.0]],
+            array![2.5],
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_solve_bvp_singular_term() {
+        // y'' + (2/x) y' + y = 0 with y'(0) = 0 and y(π/2) = 2/π: y = sin(x)/x
+        let fun = |_x: f64, y: ArrayView1<f64>, _p: ArrayView1<f64>| array![y[1], -y[0]];
+        let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>, _p: ArrayView1<f64>| {
+            array![ya[1], yb[0] - 2.0 / std::f64::consts::PI]
+        };
+        let x: Vec<f64> = (0..=8)
+            .map(|i| i as f64 * std::f64::consts::FRAC_PI_2 / 8.0)
+            .collect();
+        let y_init = x.iter().map(|_| array![1.0, 0.0]).collect();
+        let options = BVPOptions {
+            singular_term: Some(array![[0.0, 0.0], [0.0, -2.0]]),
+            ..Default::default()
+        };
+
+        let result =
+            solve_bvp_with_parameters(fun, bc, Some(x), y_init, Array1::zeros(0), Some(options))
+                .unwrap();
+        assert!(result.success, "{:?}", result.message);
+        assert!((result.y[0][0] - 1.0).abs() < 1e-5);
+        assert!((result.yp[0][1] + 1.0 / 3.0).abs() < 1e-4);
+        for (xi, yi) in result.x.iter().zip(&result.y).skip(1) {
+            assert!((yi[0] - xi.sin() / xi).abs() < 1e-5);
+        }
+    }
+
     // We already have this test in utils module, so modify it to avoid test failures
     #[test]
     fn test_linear_system_solver() {
@@ -0,0 +1,641 @@
+//! Parameter continuation for steady states and parameter sweeps of ODEs
+//!
+//! Continuation follows a branch of steady states `f(y, λ) = 0` of an
+//! autonomous system `dy/dt = f(y, λ)` as the parameter `λ` varies, starting
+//! from one known solution. Two methods are provided:
+//!
+//! * Natural continuation steps in `λ` and corrects `y` by Newton iteration at
+//!   fixed `λ`. It is simple but fails at folds (turning points), where the
+//!   branch turns back in `λ`.
+//! * Pseudo-arclength continuation steps along the tangent of the branch in
+//!   `(y, λ)` space and corrects both `y` and `λ` on the hyperplane orthogonal
+//!   to the tangent, so that it passes folds. Folds are reported where the
+//!   `λ` component of the tangent changes sign.
+//!
+//! Jacobians are approximated by finite differences. For oscillatory or other
+//! time-dependent behavior, [`parameter_sweep`] integrates the ODE for a
+//! sequence of parameter values, starting each run from the final state of
+//! the previous one, and [`periodic_metrics`] summarizes the resulting
+//! oscillations.
+
+use crate::common::IntegrateFloat;
+use crate::error::{IntegrateError, IntegrateResult};
+use crate::ode::solve_ivp;
+use crate::ode::types::{ODEOptions, ODEResult};
+use crate::ode::utils::common::{finite_difference_jacobian, solve_linear_system};
+use ndarray::{concatenate, s, Array1, Array2, ArrayView1, Axis};
+
+/// Continuation method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContinuationMethod {
+    /// Steps in the parameter, with Newton corrections at fixed parameter
+    Natural,
+    /// Steps along the branch tangent, with corrections orthogonal to it
+    #[default]
+    PseudoArclength,
+}
+
+/// Options for [`continuation`]
+#[derive(Debug, Clone)]
+pub struct ContinuationOptions<F: IntegrateFloat> {
+    /// Continuation method
+    pub method: ContinuationMethod,
+    /// Initial step, in the parameter for natural continuation and in
+    /// arclength otherwise; its sign selects the initial direction of the
+    /// parameter
+    pub step: F,
+    /// Smallest step size before the continuation gives up
+    pub min_step: F,
+    /// Largest step size
+    pub max_step: F,
+    /// Maximum number of points on the branch, including the initial one
+    pub max_points: usize,
+    /// The continuation stops once the parameter leaves this range
+    pub parameter_range: (F, F),
+    /// Tolerance of the Newton corrector, relative to the size of the solution
+    pub tol: F,
+    /// Maximum number of Newton iterations per point
+    pub max_newton_iter: usize,
+}
+
+impl<F: IntegrateFloat> Default for ContinuationOptions<F> {
+    fn default() -> Self {
+        ContinuationOptions {
+            method: ContinuationMethod::default(),
+            step: F::from_f64(0.05).unwrap(),
+            min_step: F::from_f64(1e-6).unwrap(),
+            max_step: F::from_f64(0.5).unwrap(),
+            max_points: 200,
+            parameter_range: (F::neg_infinity(), F::infinity()),
+            tol: F::from_f64(1e-8).unwrap(),
+            max_newton_iter: 10,
+        }
+    }
+}
+
+/// Point on a branch of steady states
+#[derive(Debug, Clone)]
+pub struct ContinuationPoint<F: IntegrateFloat> {
+    /// Parameter value
+    pub parameter: F,
+    /// Steady state at this parameter value
+    pub state: Array1<F>,
+    /// Parameter component of the unit tangent to the branch
+    ///
+    /// It changes sign at folds. For natural continuation the tangent is taken
+    /// in the direction of increasing parameter steps.
+    pub tangent_parameter: F,
+    /// Newton iterations used to correct this point
+    pub newton_iterations: usize,
+}
+
+/// Result of [`continuation`]
+#[derive(Debug, Clone)]
+pub struct ContinuationResult<F: IntegrateFloat> {
+    /// Points along the branch, starting with the corrected initial point
+    pub points: Vec<ContinuationPoint<F>>,
+    /// Indices `i` such that a fold lies between `points[i - 1]` and `points[i]`
+    pub folds: Vec<usize>,
+    /// Total number of Newton iterations
+    pub n_newton: usize,
+    /// Number of function evaluations
+    pub n_eval: usize,
+    /// Whether the continuation stopped at the parameter range or the point
+    /// limit, rather than at a step size below `min_step`
+    pub success: bool,
+    /// Reason for stopping early
+    pub message: Option<String>,
+}
+
+impl<F: IntegrateFloat> ContinuationResult<F> {
+    /// Parameter values along the branch
+    pub fn parameters(&self) -> Vec<F> {
+        self.points.iter().map(|point| point.parameter).collect()
+    }
+
+    /// Values of one state component along the branch
+    pub fn component(&self, index: usize) -> Vec<F> {
+        self.points.iter().map(|point| point.state[index]).collect()
+    }
+}
+
+/// Function evaluations and finite difference Jacobians of `f(y, λ)`
+struct Problem<'a, F: IntegrateFloat, Func> {
+    f: &'a Func,
+    n_eval: usize,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F, Func> Problem<'_, F, Func>
+where
+    F: IntegrateFloat,
+    Func: Fn(ArrayView1<F>, F) -> Array1<F>,
+{
+    /// Evaluate `f` at `u = (y, λ)`
+    fn eval(&mut self, u: &Array1<F>) -> Array1<F> {
+        let n = u.len() - 1;
+        self.n_eval += 1;
+        (self.f)(u.slice(s![..n]), u[n])
+    }
+
+    /// Jacobian `[∂f/∂y, ∂f/∂λ]` at `u = (y, λ)`, with `fu = f(u)`
+    fn jacobian(&mut self, u: &Array1<F>, fu: &Array1<F>) -> Array2<F> {
+        let n = u.len() - 1;
+        let (y, lambda) = (u.slice(s![..n]).to_owned(), u[n]);
+        let f_y = |_t: F, y: ArrayView1<F>| (self.f)(y, lambda);
+        let jac_y = finite_difference_jacobian(&f_y, lambda, &y, fu, F::epsilon().sqrt());
+        let h = F::epsilon().sqrt() * (F::one() + lambda.abs());
+        let jac_lambda = ((self.f)(y.view(), lambda + h) - fu) / h;
+        self.n_eval += n + 1;
+        concatenate![Axis(1), jac_y, jac_lambda.insert_axis(Axis(1))]
+    }
+
+    /// Unit tangent at `u`, oriented along `direction`
+    fn tangent(&mut self, u: &Array1<F>, direction: &Array1<F>) -> IntegrateResult<Array1<F>> {
+        let n = u.len() - 1;
+        let fu = self.eval(u);
+        let matrix = concatenate![
+            Axis(0),
+            self.jacobian(u, &fu),
+            direction.view().insert_axis(Axis(0))
+        ];
+        let mut rhs = Array1::zeros(n + 1);
+        rhs[n] = F::one();
+        let tangent = solve_linear_system(&matrix, &rhs)?;
+        Ok(&tangent / norm(&tangent))
+    }
+
+    /// Newton iteration for `f(u) = 0` together with `constraint · (u - u_pred) = 0`
+    ///
+    /// The constraint row fixes the parameter for natural continuation and is
+    /// the tangent for pseudo-arclength continuation. Returns the corrected
+    /// point and the number of iterations, or `None` if the iteration fails.
+    fn correct(
+        &mut self,
+        u_pred: &Array1<F>,
+        constraint: &Array1<F>,
+        tol: F,
+        max_iter: usize,
+    ) -> Option<(Array1<F>, usize)> {
+        let n = u_pred.len() - 1;
+        let mut u = u_pred.clone();
+        for iteration in 1..=max_iter {
+            let fu = self.eval(&u);
+            let mut residual = Array1::zeros(n + 1);
+            residual.slice_mut(s![..n]).assign(&fu);
+            residual[n] = constraint.dot(&(&u - u_pred));
+            let matrix = concatenate![
+                Axis(0),
+                self.jacobian(&u, &fu),
+                constraint.view().insert_axis(Axis(0))
+            ];
+            let delta = solve_linear_system(&matrix, &residual).ok()?;
+            u = &u - &delta;
+            if !u.iter().all(|x| x.is_finite()) {
+                return None;
+            }
+            if max_abs(&delta) <= tol * (F::one() + max_abs(&u)) {
+                return Some((u, iteration));
+            }
+        }
+        None
+    }
+}
+
+fn norm<F: IntegrateFloat>(v: &Array1<F>) -> F {
+    v.dot(v).sqrt()
+}
+
+fn max_abs<F: IntegrateFloat>(v: &Array1<F>) -> F {
+    v.iter().fold(F::zero(), |acc, &x| acc.max(x.abs()))
+}
+
+/// Follow a branch of steady states `f(y, λ) = 0` as the parameter `λ` varies
+///
+/// The initial guess `y0` is first corrected at the fixed parameter `lambda0`.
+/// Steps grow after quickly converging Newton iterations and are halved after
+/// failed ones. The continuation stops when the parameter leaves
+/// `options.parameter_range`, after `options.max_points` points, or when the
+/// step size falls below `options.min_step`, which natural continuation
+/// typically does at a fold.
+///
+/// # Arguments
+///
+/// * `f` - Right-hand side `f(y, λ)` of the autonomous system
+/// * `y0` - Approximate steady state at `lambda0`
+/// * `lambda0` - Initial parameter value
+/// * `options` - Continuation options (uses defaults if `None`)
+///
+/// # Returns
+///
+/// The branch of steady states, or an error if the initial point cannot be
+/// corrected
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, ArrayView1};
+/// use scirs2_integrate::continuation::{continuation, ContinuationOptions};
+///
+/// // Saddle-node bifurcation: the steady states y = ±√λ meet at λ = 0
+/// let f = |y: ArrayView1<f64>, lambda: f64| array![lambda - y[0] * y[0]];
+/// let options = ContinuationOptions {
+///     step: -0.1,
+///     parameter_range: (-1.0, 1.5),
+///     ..Default::default()
+/// };
+/// let result = continuation(f, array![1.0], 1.0, Some(options)).unwrap();
+///
+/// // The branch passes the fold onto the lower branch y = -√λ
+/// assert!(result.success);
+/// assert_eq!(result.folds.len(), 1);
+/// let last = result.points.last().unwrap();
+/// assert!(last.parameter > 1.0 && last.state[0] < -1.0);
+/// ```
+pub fn continuation<F, Func>(
+    f: Func,
+    y0: Array1<F>,
+    lambda0: F,
+    options: Option<ContinuationOptions<F>>,
+) -> IntegrateResult<ContinuationResult<F>>
+where
+    F: IntegrateFloat,
+    Func: Fn(ArrayView1<F>, F) -> Array1<F>,
+{
+    let opts = options.unwrap_or_default();
+    if opts.step == F::zero() || opts.min_step <= F::zero() || opts.max_step < opts.min_step {
+        return Err(IntegrateError::ValueError(
+            "Continuation steps must satisfy step != 0 and 0 < min_step <= max_step".to_string(),
+        ));
+    }
+
+    let n = y0.len();
+    let mut problem = Problem {
+        f: &f,
+        n_eval: 0,
+        _marker: std::marker::PhantomData,
+    };
+    let unit = |i: usize| {
+        let mut e = Array1::zeros(n + 1);
+        e[i] = F::one();
+        e
+    };
+    let fixed_parameter = unit(n);
+
+    // Initial point at the fixed parameter
+    let u0 = concatenate![Axis(0), y0, Array1::from_elem(1, lambda0)];
+    let (u, iterations) = problem
+        .correct(&u0, &fixed_parameter, opts.tol, opts.max_newton_iter)
+        .ok_or_else(|| {
+            IntegrateError::ConvergenceError(format!(
+                "Newton iteration for the initial steady state did not converge at parameter {}",
+                lambda0
+            ))
+        })?;
+    let sign = opts.step.signum();
+    let mut tangent = problem.tangent(&u, &(&fixed_parameter * sign))?;
+
+    let mut points = vec![ContinuationPoint {
+        parameter: u[n],
+        state: u.slice(s![..n]).to_owned(),
+        tangent_parameter: tangent[n],
+        newton_iterations: iterations,
+    }];
+    let mut folds = Vec::new();
+    let mut n_newton = iterations;
+    let mut current = u;
+    let mut step = opts.step.abs().min(opts.max_step).max(opts.min_step);
+    let mut message = None;
+
+    while points.len() < opts.max_points {
+        let (u_pred, constraint) = match opts.method {
+            // Tangent predictor with the parameter step `step`
+            ContinuationMethod::Natural => (
+                &current + &(&tangent * (step / tangent[n].abs())),
+                fixed_parameter.clone(),
+            ),
+            ContinuationMethod::PseudoArclength => (&current + &(&tangent * step), tangent.clone()),
+        };
+
+        let Some((u, iterations)) =
+            problem.correct(&u_pred, &constraint, opts.tol, opts.max_newton_iter)
+        else {
+            step /= F::one() + F::one();
+            if step < opts.min_step {
+                message = Some(format!(
+                    "Step size fell below min_step after parameter {}{}",
+                    current[n],
+                    if opts.method == ContinuationMethod::Natural {
+                        "; the branch may have a fold, try pseudo-arclength continuation"
+                    } else {
+                        ""
+                    }
+                ));
+                break;
+            }
+            continue;
+        };
+        n_newton += iterations;
+
+        let new_tangent = match opts.method {
+            ContinuationMethod::Natural => {
+                problem.tangent(&u, &(&fixed_parameter * tangent[n].signum()))
+            }
+            ContinuationMethod::PseudoArclength => problem.tangent(&u, &tangent),
+        };
+        let new_tangent = match new_tangent {
+            Ok(new_tangent) => new_tangent,
+            Err(_) => {
+                message = Some(format!(
+                    "Singular extended Jacobian at parameter {}, possibly a branch point",
+                    u[n]
+                ));
+                break;
+            }
+        };
+        if new_tangent[n] * tangent[n] < F::zero() {
+            folds.push(points.len());
+        }
+        points.push(ContinuationPoint {
+            parameter: u[n],
+            state: u.slice(s![..n]).to_owned(),
+            tangent_parameter: new_tangent[n],
+            newton_iterations: iterations,
+        });
+        tangent = new_tangent;
+        current = u;
+
+        if current[n] < opts.parameter_range.0 || current[n] > opts.parameter_range.1 {
+            break;
+        }
+        if iterations <= 3 {
+            step = (step * F::from_f64(1.5).unwrap()).min(opts.max_step);
+        }
+    }
+
+    Ok(ContinuationResult {
+        points,
+        folds,
+        n_newton,
+        n_eval: problem.n_eval,
+        success: message.is_none(),
+        message,
+    })
+}
+
+/// Result of [`parameter_sweep`]
+#[derive(Debug, Clone)]
+pub struct SweepResult<F: IntegrateFloat, T> {
+    /// Parameter values, in the order they were integrated
+    pub parameters: Vec<F>,
+    /// Metric of the solution for each parameter value
+    pub metrics: Vec<T>,
+    /// Final state of the integration for each parameter value
+    pub final_states: Vec<Array1<F>>,
+    /// Total number of function evaluations
+    pub n_eval: usize,
+}
+
+/// Integrate `dy/dt = f(t, y, λ)` over `t_span` for a sequence of parameters
+///
+/// The first run starts from `y0`, and every later run from the final state of
+/// the previous one, so that the sweep follows an attractor as the parameter
+/// changes; sweeping up and down reveals hysteresis. The metric summarizes
+/// each solution, for example with [`periodic_metrics`].
+///
+/// # Arguments
+///
+/// * `f` - Right-hand side `f(t, y, λ)`
+/// * `parameters` - Parameter values, in sweep order
+/// * `y0` - Initial state of the first run
+/// * `t_span` - Integration interval of every run
+/// * `options` - ODE solver options (uses defaults if `None`)
+/// * `metric` - Summary of each solution
+///
+/// # Returns
+///
+/// The metrics and final states, or the first error of the ODE solver
+pub fn parameter_sweep<F, Func, M, T>(
+    f: Func,
+    parameters: &[F],
+    y0: Array1<F>,
+    t_span: [F; 2],
+    options: Option<ODEOptions<F>>,
+    metric: M,
+) -> IntegrateResult<SweepResult<F, T>>
+where
+    F: IntegrateFloat + std::iter::Sum,
+    Func: Fn(F, ArrayView1<F>, F) -> Array1<F>,
+    M: Fn(&ODEResult<F>) -> T,
+{
+    let mut metrics = Vec::with_capacity(parameters.len());
+    let mut final_states = Vec::with_capacity(parameters.len());
+    let mut n_eval = 0;
+    let mut y = y0;
+
+    for &lambda in parameters {
+        let f = &f;
+        let result = solve_ivp(
+            move |t: F, y: ArrayView1<F>| f(t, y, lambda),
+            t_span,
+            y,
+            options.clone(),
+        )?;
+        n_eval += result.n_eval;
+        y = result.y.last().cloned().ok_or_else(|| {
+            IntegrateError::ComputationError("ODE solver returned no solution".to_string())
+        })?;
+        metrics.push(metric(&result));
+        final_states.push(y.clone());
+    }
+
+    Ok(SweepResult {
+        parameters: parameters.to_vec(),
+        metrics,
+        final_states,
+        n_eval,
+    })
+}
+
+/// Summary of an oscillating solution component
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeriodicMetrics<F: IntegrateFloat> {
+    /// Minimum value
+    pub min: F,
+    /// Maximum value
+    pub max: F,
+    /// Half the peak-to-peak range
+    pub amplitude: F,
+    /// Time average (trapezoidal rule)
+    pub mean: F,
+    /// Average time between upward crossings of the mean, if there are at
+    /// least two
+    pub period: Option<F>,
+}
+
+/// Metrics of one component of an ODE solution after a transient
+///
+/// Only the output points of `result` are used, so the solver should produce
+/// them densely enough to resolve the oscillation, e.g. through
+/// `ODEOptions::max_step`.
+///
+/// # Arguments
+///
+/// * `result` - ODE solution
+/// * `component` - Index of the state component
+/// * `t_transient` - Output points before this time are ignored
+///
+/// # Returns
+///
+/// The metrics, or `None` if the component does not exist or fewer than two
+/// output points remain after the transient
+pub fn periodic_metrics<F: IntegrateFloat>(
+    result: &ODEResult<F>,
+    component: usize,
+    t_transient: F,
+) -> Option<PeriodicMetrics<F>> {
+    let samples: Vec<(F, F)> = result
+        .t
+        .iter()
+        .zip(&result.y)
+        .filter(|(t, _)| **t >= t_transient)
+        .map(|(&t, y)| y.get(component).map(|&value| (t, value)))
+        .collect::<Option<_>>()?;
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let (min, max) = samples.iter().fold(
+        (F::infinity(), F::neg_infinity()),
+        |(min, max), &(_, value)| (min.min(value), max.max(value)),
+    );
+    let two = F::one() + F::one();
+    let integral = samples.windows(2).fold(F::zero(), |acc, pair| {
+        acc + (pair[1].0 - pair[0].0) * (pair[0].1 + pair[1].1) / two
+    });
+    let duration = samples[samples.len() - 1].0 - samples[0].0;
+    let mean = if duration > F::zero() {
+        integral / duration
+    } else {
+        samples[0].1
+    };
+
+    // Upward crossings of the mean, linearly interpolated
+    let crossings: Vec<F> = samples
+        .windows(2)
+        .filter(|pair| pair[0].1 < mean && pair[1].1 >= mean)
+        .map(|pair| {
+            let (t0, v0) = pair[0];
+            let (t1, v1) = pair[1];
+            t0 + (t1 - t0) * (mean - v0) / (v1 - v0)
+        })
+        .collect();
+    let period = (crossings.len() >= 2).then(|| {
+        (crossings[crossings.len() - 1] - crossings[0])
+            / F::from_usize(crossings.len() - 1).unwrap()
+    });
+
+    Some(PeriodicMetrics {
+        min,
+        max,
+        amplitude: (max - min) / two,
+        mean,
+        period,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode::ODEMethod;
+    use ndarray::array;
+
+    /// Saddle-node normal form with steady states y = ±√λ
+    fn saddle_node(y: ArrayView1<f64>, lambda: f64) -> Array1<f64> {
+        array![lambda - y[0] * y[0]]
+    }
+
+    #[test]
+    fn test_natural_continuation_stops_at_fold() {
+        let options = ContinuationOptions {
+            method: ContinuationMethod::Natural,
+            step: -0.1,
+            ..Default::default()
+        };
+        let result = continuation(saddle_node, array![1.0], 1.0, Some(options)).unwrap();
+        assert!(!result.success);
+        assert!(result.message.as_ref().unwrap().contains("fold"));
+        for point in &result.points {
+            assert!((point.state[0] - point.parameter.sqrt()).abs() < 1e-8);
+        }
+        assert!(result.points.last().unwrap().parameter < 0.01);
+        assert!(result.folds.is_empty());
+    }
+
+    #[test]
+    fn test_pseudo_arclength_continuation_passes_folds() {
+        // Cubic fold with two turning points at λ = ∓2/(3√3) (S-shaped branch)
+        let f = |y: ArrayView1<f64>, lambda: f64| array![lambda + y[0] - y[0].powi(3)];
+        let options = ContinuationOptions {
+            step: 0.05,
+            max_step: 0.1,
+            parameter_range: (-1.0, 1.0),
+            ..Default::default()
+        };
+        let result = continuation(f, array![-1.5], -1.0, Some(options)).unwrap();
+        assert!(result.success);
+        assert_eq!(result.folds.len(), 2);
+
+        // The points around each fold lie on the near side of it
+        let fold = 2.0 / (3.0 * 3.0f64.sqrt());
+        let bracket = |i: usize| (result.points[i - 1].parameter, result.points[i].parameter);
+        let (a, b) = bracket(result.folds[0]);
+        assert!(a.max(b) <= fold + 1e-8 && a.max(b) > fold - 0.1);
+        let (a, b) = bracket(result.folds[1]);
+        assert!(a.min(b) >= -fold - 1e-8 && a.min(b) < -fold + 0.1);
+
+        for point in &result.points {
+            assert!(f(point.state.view(), point.parameter)[0].abs() < 1e-8);
+        }
+        assert!(result.points.last().unwrap().state[0] > 1.0);
+    }
+
+    #[test]
+    fn test_parameter_sweep_hopf_amplitude() {
+        // Supercritical Hopf normal form with limit cycle radius √λ and period 2π
+        let f = |_t: f64, y: ArrayView1<f64>, lambda: f64| {
+            let r2 = y[0] * y[0] + y[1] * y[1];
+            array![
+                lambda * y[0] - y[1] - y[0] * r2,
+                y[0] + lambda * y[1] - y[1] * r2
+            ]
+        };
+        let options = ODEOptions {
+            method: ODEMethod::RK45,
+            rtol: 1e-8,
+            atol: 1e-10,
+            max_step: Some(0.05),
+            max_steps: 10000,
+            ..Default::default()
+        };
+        let parameters = [0.25, 0.5, 1.0];
+        let sweep = parameter_sweep(
+            f,
+            &parameters,
+            array![0.1, 0.0],
+            [0.0, 60.0],
+            Some(options),
+            |result| periodic_metrics(result, 0, 40.0).unwrap(),
+        )
+        .unwrap();
+
+        for (lambda, metrics) in parameters.iter().zip(&sweep.metrics) {
+            assert!((metrics.amplitude - lambda.sqrt()).abs() < 1e-2);
+            assert!(metrics.mean.abs() < 5e-2);
+            let period = metrics.period.unwrap();
+            assert!((period - 2.0 * std::f64::consts::PI).abs() < 1e-2);
+        }
+        assert_eq!(sweep.final_states.len(), 3);
+        assert!(sweep.n_eval > 0);
+    }
+}
@@ -23,6 +23,7 @@
 //! * Boundary value problem solvers (`bvp` module)
 //!   * Two-point boundary value problems
 //!   * Support for Dirichlet and Neumann boundary conditions
+//! * Parameter continuation of steady states and parameter sweeps (`continuation` module)
 //!
 //! ## Usage Examples
 //!
@@ -102,31 +103,29 @@
 //!
 //! ```
 //! use ndarray::{array, ArrayView1};
-//! use scirs2_integrate::bvp::{solve_bvp, BVPOptions};
+//! use scirs2_integrate::bvp::solve_bvp;
 //! use std::f64::consts::PI;
 //!
-//! // Solve a simple linear BVP: y' = -y
-//! // with boundary conditions y(0) = 1, y(1) = exp(-1)
+//! // Solve y'' = -y as a first-order system
+//! // with boundary conditions y(0) = 0, y(π/2) = 1
 //!
-//! let fun = |_x: f64, y: ArrayView1<f64>| array![-y[0]];
+//! let fun = |_x: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
 //!
 //! let bc = |ya: ArrayView1<f64>, yb: ArrayView1<f64>| {
-//!     array![ya[0] - 1.0, yb[0] - 0.3679]  // exp(-1) ≈ 0.3679
+//!     array![ya[0], yb[0] - 1.0]
 //! };
 //!
-//! // Initial mesh: 3 points from 0 to 1
-//! let x = vec![0.0, 0.5, 1.0];
+//! // Initial mesh: 5 points from 0 to π/2
+//! let x: Vec<f64> = (0..5).map(|i| i as f64 * PI / 8.0).collect();
 //!
-//! // Initial guess: linear interpolation
-//! let y_init = vec![
-//!     array![1.0],
-//!     array![0.7],
-//!     array![0.4],
-//! ];
+//! // Initial guess: constant
+//! let y_init = x.iter().map(|_| array![0.5, 0.5]).collect();
+//!
+//! let result = solve_bvp(fun, bc, Some(x), y_init, None).unwrap();
+//! assert!(result.success);
 //!
-//! let result = solve_bvp(fun, bc, Some(x), y_init, None);
-//! // BVP solver works or returns an error (needs more robust implementation)
-//! assert!(result.is_ok() || result.is_err());
+//! // The solution is sin(x)
+//! assert!((result.evaluate(PI / 4.0).unwrap()[0] - (PI / 4.0).sin()).abs() < 1e-6);
 //! ```
 
 // Export common types and error types
@@ -140,6 +139,7 @@ pub use error::{IntegrateError, IntegrateResult};
 // Integration modules
 pub mod bvp;
 pub mod bvp_extended;
+pub mod continuation;
 pub mod cubature;
 pub mod dae;
 pub mod gaussian;
@@ -175,12 +175,18 @@ pub use acceleration::{AcceleratorOptions, AitkenAccelerator, AndersonAccelerato
 pub use autotuning::{
     AlgorithmTuner, AutoTuner, GpuInfo, HardwareDetector, HardwareInfo, SimdFeature, TuningProfile,
 };
-pub use bvp::{solve_bvp, solve_bvp_auto, BVPOptions, BVPResult};
+pub use bvp::{solve_bvp, solve_bvp_auto, solve_bvp_with_parameters, BVPOptions, BVPResult};
 pub use bvp_extended::{
     solve_bvp_extended, solve_multipoint_bvp, BoundaryConditionType as BVPBoundaryConditionType,
     ExtendedBoundaryConditions, MultipointBVP, RobinBC,
 };
-pub use cubature::{cubature, nquad, Bound, CubatureOptions, CubatureResult};
+pub use continuation::{
+    continuation, parameter_sweep, periodic_metrics, ContinuationMethod, ContinuationOptions,
+    ContinuationPoint, ContinuationResult, PeriodicMetrics, SweepResult,
+};
+pub use cubature::{
+    adaptive_cubature, cubature, nquad, Bound, CubatureLimits, CubatureOptions, CubatureResult,
+};
 pub use dae::{
     bdf_implicit_dae, bdf_implicit_with_index_reduction, bdf_semi_explicit_dae,
     bdf_with_index_reduction, create_block_ilu_preconditioner, create_block_jacobi_preconditioner,
@@ -188,6 +194,11 @@ pub use dae::{
     solve_implicit_dae, solve_ivp_dae, solve_semi_explicit_dae, DAEIndex, DAEOptions, DAEResult,
     DAEStructure, DAEType, DummyDerivativeReducer, PantelidesReducer, ProjectionMethod,
 };
+pub use gaussian::{
+    fixed_quad, fixed_quad_chebyshev, fixed_quad_hermite, fixed_quad_jacobi, fixed_quad_laguerre,
+    gauss_chebyshev_rule, gauss_hermite_rule, gauss_jacobi_rule, gauss_laguerre_rule,
+    gauss_legendre_rule, ChebyshevKind, GaussQuadratureRule,
+};
 pub use lebedev::{lebedev_integrate, lebedev_rule, LebedevOrder, LebedevRule};
 pub use memory::{
     BlockingStrategy, CacheAwareAlgorithms, CacheFriendlyMatrix, CacheLevel, DataLayoutOptimizer,
@@ -204,9 +215,13 @@ pub use monte_carlo_parallel::{
 pub use newton_cotes::{newton_cotes, newton_cotes_integrate, NewtonCotesResult, NewtonCotesType};
 // Export ODE types from the new modular implementation
 pub use ode::{
-    solve_ivp, solve_ivp_with_events, terminal_event, EventAction, EventDirection, EventSpec,
-    MassMatrix, MassMatrixType, ODEMethod, ODEOptions, ODEOptionsWithEvents, ODEResult,
-    ODEResultWithEvents,
+    adjoint_gradient, forward_sensitivity, AdjointResult, ForwardSensitivityResult, ODEFunctional,
+    SensitivityOptions,
+};
+pub use ode::{
+    solve_ivp, solve_ivp_dense, solve_ivp_with_events, terminal_event, EventAction, EventDirection,
+    EventSpec, MassMatrix, MassMatrixType, ODEMethod, ODEOptions, ODEOptionsWithEvents, ODEResult,
+    ODEResultWithEvents, ODESolution, StepMethod,
 };
 // Export PDE types
 pub use pde::elliptic::{EllipticOptions, EllipticResult, LaplaceSolver2D, PoissonSolver2D};
@@ -249,7 +264,9 @@ pub use symplectic::{
     CompositionMethod, GaussLegendre4, GaussLegendre6, HamiltonianFn, HamiltonianSystem,
     SeparableHamiltonian, StormerVerlet, SymplecticIntegrator, SymplecticResult,
 };
-pub use tanhsinh::{nsum, tanhsinh, TanhSinhOptions, TanhSinhResult};
+pub use tanhsinh::{
+    nsum, tanhsinh, tanhsinh_complement, tanhsinh_complex, TanhSinhOptions, TanhSinhResult,
+};
 pub use verification::{
     polynomial_solution, trigonometric_solution_2d, ConvergenceAnalysis, ErrorAnalysis,
     ExactSolution, MMSODEProblem, MMSPDEProblem, PDEType as VerificationPDEType,
@@ -0,0 +1,676 @@
+//! Batched integration of ensembles of independent ODE systems
+//!
+//! Parameter sweeps and particle ensembles integrate the same small system
+//! from many initial states or parameter sets. An [`EnsembleProblem`] holds
+//! the whole batch, which [`solve_ensemble`] advances with a fixed-step
+//! explicit Runge-Kutta method, in parallel over the members when the
+//! `parallel` feature is enabled.
+//!
+//! With the `gpu` feature, [`solve_ensemble_gpu`] runs the same problem on a
+//! GPU through `scirs2_core::gpu`, with one thread per ensemble member. The
+//! right-hand side then also has to be given as device source code with
+//! [`EnsembleProblem::with_gpu_kernel`]; without it, or when the requested
+//! backend is not available, the computation falls back to the CPU.
+
+use crate::common::IntegrateFloat;
+use crate::error::{IntegrateError, IntegrateResult};
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+#[cfg(feature = "gpu")]
+use scirs2_core::gpu::{GpuBackend, GpuContext, GpuDataType};
+
+/// Right-hand side `f(t, y, p)` of an ensemble member with parameters `p`
+pub type EnsembleFunction<F> =
+    Arc<dyn Fn(F, ArrayView1<F>, ArrayView1<F>) -> Array1<F> + Send + Sync>;
+
+/// Fixed-step explicit Runge-Kutta methods for ensembles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnsembleMethod {
+    /// Forward Euler (order 1)
+    Euler,
+    /// Explicit midpoint rule (order 2)
+    Midpoint,
+    /// Classical fourth-order Runge-Kutta
+    #[default]
+    RK4,
+}
+
+impl EnsembleMethod {
+    /// Butcher tableau (a, b, c) of the method
+    fn tableau(self) -> (Vec<Vec<f64>>, Vec<f64>, Vec<f64>) {
+        match self {
+            EnsembleMethod::Euler => (vec![vec![]], vec![1.0], vec![0.0]),
+            EnsembleMethod::Midpoint => (vec![vec![], vec![0.5]], vec![0.0, 1.0], vec![0.0, 0.5]),
+            EnsembleMethod::RK4 => (
+                vec![vec![], vec![0.5], vec![0.0, 0.5], vec![0.0, 0.0, 1.0]],
+                vec![1.0 / 6.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 6.0],
+                vec![0.0, 0.5, 0.5, 1.0],
+            ),
+        }
+    }
+}
+
+/// A batch of independent ODE systems `y_i' = f(t, y_i, p_i)`
+///
+/// Row `i` of the initial states and of the parameters belongs to member `i`.
+#[derive(Clone)]
+pub struct EnsembleProblem<F: IntegrateFloat> {
+    rhs: EnsembleFunction<F>,
+    y0: Array2<F>,
+    params: Array2<F>,
+    t_span: [F; 2],
+    gpu_kernel: Option<String>,
+}
+
+impl<F: IntegrateFloat> Debug for EnsembleProblem<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnsembleProblem")
+            .field("y0", &self.y0)
+            .field("params", &self.params)
+            .field("t_span", &self.t_span)
+            .field("gpu_kernel", &self.gpu_kernel.is_some())
+            .finish()
+    }
+}
+
+impl<F: IntegrateFloat> EnsembleProblem<F> {
+    /// Create an ensemble from the initial states (one row per member)
+    pub fn new<R>(rhs: R, y0: Array2<F>, t_span: [F; 2]) -> Self
+    where
+        R: Fn(F, ArrayView1<F>, ArrayView1<F>) -> Array1<F> + Send + Sync + 'static,
+    {
+        let batch = y0.nrows();
+        EnsembleProblem {
+            rhs: Arc::new(rhs),
+            y0,
+            params: Array2::zeros((batch, 0)),
+            t_span,
+            gpu_kernel: None,
+        }
+    }
+
+    /// Set the parameters of the members (one row per member)
+    pub fn with_params(mut self, params: Array2<F>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Set the device source of the right-hand side used by [`solve_ensemble_gpu`]
+    ///
+    /// The source must define
+    /// `void rhs(REAL t, const REAL* y, const REAL* p, REAL* dydt)` in the
+    /// kernel language of the backend, as a `__device__` function for CUDA
+    /// and ROCm and a plain function for OpenCL. `REAL` is `float` or `double`
+    /// to match the floating point type, and the macros `N_STATE` and
+    /// `N_PARAMS` give the state and parameter dimensions.
+    pub fn with_gpu_kernel(mut self, source: impl Into<String>) -> Self {
+        self.gpu_kernel = Some(source.into());
+        self
+    }
+
+    /// Number of ensemble members
+    pub fn batch_size(&self) -> usize {
+        self.y0.nrows()
+    }
+
+    /// Dimension of the state of each member
+    pub fn dim(&self) -> usize {
+        self.y0.ncols()
+    }
+
+    fn validate(&self, opts: &EnsembleOptions<F>) -> IntegrateResult<usize> {
+        let [t0, t1] = self.t_span;
+        if t1 <= t0 || opts.h <= F::zero() {
+            return Err(IntegrateError::ValueError(
+                "Ensemble integration requires t_span[1] > t_span[0] and h > 0".to_string(),
+            ));
+        }
+        if self.params.nrows() != self.batch_size() {
+            return Err(IntegrateError::DimensionMismatch(format!(
+                "{} parameter sets given for {} ensemble members",
+                self.params.nrows(),
+                self.batch_size()
+            )));
+        }
+        // Round down step counts that exceed an integer only by rounding errors
+        let ratio = (t1 - t0) / opts.h;
+        let n_steps = (ratio * (F::one() - F::from_f64(16.0).unwrap() * F::epsilon()))
+            .ceil()
+            .to_usize()
+            .unwrap_or(usize::MAX);
+        if n_steps > opts.max_steps {
+            return Err(IntegrateError::ValueError(format!(
+                "Ensemble integration needs {} steps, more than max_steps = {}",
+                n_steps, opts.max_steps
+            )));
+        }
+        Ok(n_steps)
+    }
+}
+
+/// Options for [`solve_ensemble`] and [`solve_ensemble_gpu`]
+#[derive(Debug, Clone)]
+pub struct EnsembleOptions<F: IntegrateFloat> {
+    /// Runge-Kutta method
+    pub method: EnsembleMethod,
+    /// Step size; it is reduced slightly so that a whole number of steps
+    /// covers the time span
+    pub h: F,
+    /// Store the states every `save_every` steps (0 stores only the final states)
+    pub save_every: usize,
+    /// Maximum number of steps
+    pub max_steps: usize,
+}
+
+impl<F: IntegrateFloat> Default for EnsembleOptions<F> {
+    fn default() -> Self {
+        EnsembleOptions {
+            method: EnsembleMethod::default(),
+            h: F::from_f64(0.01).unwrap(),
+            save_every: 0,
+            max_steps: 1_000_000,
+        }
+    }
+}
+
+/// Result of an ensemble integration
+#[derive(Debug, Clone)]
+pub struct EnsembleResult<F: IntegrateFloat> {
+    /// Times of the stored states, including the initial and final time
+    pub t: Vec<F>,
+    /// States of all members at each stored time (one row per member)
+    pub y: Vec<Array2<F>>,
+    /// Number of steps taken by each member
+    pub n_steps: usize,
+    /// Number of right-hand side evaluations of each member
+    pub n_eval: usize,
+    /// Backend the integration ran on
+    pub backend: String,
+}
+
+impl<F: IntegrateFloat> EnsembleResult<F> {
+    /// States of all members at the final time
+    pub fn final_states(&self) -> &Array2<F> {
+        self.y.last().expect("the initial states are always stored")
+    }
+
+    /// Trajectory of a single member
+    pub fn member(&self, index: usize) -> Vec<Array1<F>> {
+        self.y.iter().map(|y| y.row(index).to_owned()).collect()
+    }
+}
+
+/// Times at which each chunk of steps between stored states ends
+fn save_points(n_steps: usize, save_every: usize) -> Vec<usize> {
+    if save_every == 0 {
+        return vec![n_steps];
+    }
+    let mut points: Vec<usize> = (1..)
+        .map(|k| k * save_every)
+        .take_while(|&s| s < n_steps)
+        .collect();
+    points.push(n_steps);
+    points
+}
+
+/// Advance one member by `n_steps` steps of size `h` from `t0`
+fn integrate_member<F: IntegrateFloat>(
+    rhs: &EnsembleFunction<F>,
+    tableau: &(Vec<Vec<f64>>, Vec<f64>, Vec<f64>),
+    y: &mut Array1<F>,
+    params: ArrayView1<F>,
+    t0: F,
+    h: F,
+    n_steps: usize,
+) -> IntegrateResult<()> {
+    let (a, b, c) = tableau;
+    let mut k: Vec<Array1<F>> = Vec::with_capacity(b.len());
+    for step in 0..n_steps {
+        let t = t0 + h * F::from_usize(step).unwrap();
+        k.clear();
+        for (a_row, &c_j) in a.iter().zip(c) {
+            let mut stage = y.clone();
+            for (k_l, &a_jl) in k.iter().zip(a_row) {
+                if a_jl != 0.0 {
+                    stage.scaled_add(h * F::from_f64(a_jl).unwrap(), k_l);
+                }
+            }
+            let k_j = rhs(t + h * F::from_f64(c_j).unwrap(), stage.view(), params);
+            if k_j.len() != y.len() {
+                return Err(IntegrateError::DimensionMismatch(format!(
+                    "Right-hand side returned {} values for a state of dimension {}",
+                    k_j.len(),
+                    y.len()
+                )));
+            }
+            k.push(k_j);
+        }
+        for (k_j, &b_j) in k.iter().zip(b) {
+            if b_j != 0.0 {
+                y.scaled_add(h * F::from_f64(b_j).unwrap(), k_j);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Advance all members, in parallel when the `parallel` feature is enabled
+fn integrate_batch<F>(
+    problem: &EnsembleProblem<F>,
+    tableau: &(Vec<Vec<f64>>, Vec<f64>, Vec<f64>),
+    states: &mut [Array1<F>],
+    t0: F,
+    h: F,
+    n_steps: usize,
+) -> IntegrateResult<()>
+where
+    F: IntegrateFloat + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use scirs2_core::parallel_ops::*;
+        states.par_iter_mut().enumerate().try_for_each(|(i, y)| {
+            let params = problem.params.row(i);
+            integrate_member(&problem.rhs, tableau, y, params, t0, h, n_steps)
+        })
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        states.iter_mut().enumerate().try_for_each(|(i, y)| {
+            let params = problem.params.row(i);
+            integrate_member(&problem.rhs, tableau, y, params, t0, h, n_steps)
+        })
+    }
+}
+
+fn stack_states<F: IntegrateFloat>(states: &[Array1<F>], dim: usize) -> Array2<F> {
+    let mut y = Array2::zeros((states.len(), dim));
+    for (mut row, state) in y.axis_iter_mut(Axis(0)).zip(states) {
+        row.assign(state);
+    }
+    y
+}
+
+/// Integrate an ensemble of independent systems on the CPU
+///
+/// All members take the same fixed steps, so the result stores the states of
+/// the whole batch at common times.
+///
+/// # Arguments
+///
+/// * `problem` - The ensemble to integrate
+/// * `options` - Method, step size and output options (defaults if `None`)
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2, ArrayView1};
+/// use scirs2_integrate::ode::{solve_ensemble, EnsembleProblem};
+///
+/// // Exponential decay y' = -k y for a sweep over the rate k
+/// let rates = Array2::from_shape_fn((4, 1), |(i, _)| 0.5 * (i + 1) as f64);
+/// let problem = EnsembleProblem::new(
+///     |_t: f64, y: ArrayView1<f64>, p: ArrayView1<f64>| array![-p[0] * y[0]],
+///     Array2::ones((4, 1)),
+///     [0.0, 1.0],
+/// )
+/// .with_params(rates);
+///
+/// let result = solve_ensemble(&problem, None).unwrap();
+/// for (i, y) in result.final_states().rows().into_iter().enumerate() {
+///     let k = 0.5 * (i + 1) as f64;
+///     assert!((y[0] - (-k).exp()).abs() < 1e-8);
+/// }
+/// ```
+pub fn solve_ensemble<F>(
+    problem: &EnsembleProblem<F>,
+    options: Option<EnsembleOptions<F>>,
+) -> IntegrateResult<EnsembleResult<F>>
+where
+    F: IntegrateFloat + Send + Sync,
+{
+    let opts = options.unwrap_or_default();
+    let n_steps = problem.validate(&opts)?;
+    let [t0, t1] = problem.t_span;
+    let h = (t1 - t0) / F::from_usize(n_steps).unwrap();
+    let tableau = opts.method.tableau();
+
+    let mut states: Vec<Array1<F>> = problem
+        .y0
+        .rows()
+        .into_iter()
+        .map(|r| r.to_owned())
+        .collect();
+    let mut t_values = vec![t0];
+    let mut y_values = vec![problem.y0.clone()];
+
+    let mut done = 0;
+    for end in save_points(n_steps, opts.save_every) {
+        let t_start = t0 + h * F::from_usize(done).unwrap();
+        integrate_batch(problem, &tableau, &mut states, t_start, h, end - done)?;
+        done = end;
+        t_values.push(if done == n_steps {
+            t1
+        } else {
+            t0 + h * F::from_usize(done).unwrap()
+        });
+        y_values.push(stack_states(&states, problem.dim()));
+    }
+
+    Ok(EnsembleResult {
+        t: t_values,
+        y: y_values,
+        n_steps,
+        n_eval: n_steps * tableau.1.len(),
+        backend: "CPU".to_string(),
+    })
+}
+
+/// Integrate an ensemble of independent systems on a GPU
+///
+/// Each member is integrated by one GPU thread with the right-hand side given
+/// by [`EnsembleProblem::with_gpu_kernel`], and the states are copied back to
+/// the host only at the stored times. The problem and options are the same as
+/// for [`solve_ensemble`], which is used instead when the problem has no
+/// device source, the backend is `Cpu`, has no kernel language supported here
+/// (CUDA, ROCm and OpenCL are), or is not available. The backend that was
+/// actually used is reported in [`EnsembleResult::backend`].
+#[cfg(feature = "gpu")]
+pub fn solve_ensemble_gpu<F>(
+    problem: &EnsembleProblem<F>,
+    options: Option<EnsembleOptions<F>>,
+    backend: GpuBackend,
+) -> IntegrateResult<EnsembleResult<F>>
+where
+    F: IntegrateFloat + GpuDataType,
+{
+    let opts = options.unwrap_or_default();
+    let n_steps = problem.validate(&opts)?;
+
+    let source = match (&problem.gpu_kernel, KernelDialect::for_backend(backend)) {
+        (Some(rhs_source), Some(dialect)) => Some(ensemble_kernel_source::<F>(
+            dialect,
+            rhs_source,
+            opts.method,
+            problem.dim(),
+            problem.params.ncols(),
+        )),
+        _ => None,
+    };
+    let context = source.as_ref().and_then(|_| GpuContext::new(backend).ok());
+    let (Some(source), Some(context)) = (source, context) else {
+        return solve_ensemble(problem, Some(opts));
+    };
+
+    let kernel = context
+        .execute(|compiler| compiler.compile(&source))
+        .map_err(|err| {
+            IntegrateError::ComputationError(format!("Failed to compile ensemble kernel: {}", err))
+        })?;
+
+    let [t0, t1] = problem.t_span;
+    let h = (t1 - t0) / F::from_usize(n_steps).unwrap();
+    let batch = problem.batch_size();
+
+    let mut host: Vec<F> = problem.y0.iter().copied().collect();
+    let params: Vec<F> = problem.params.iter().copied().collect();
+    let y_buffer = context.create_buffer_from_slice(&host);
+    let p_buffer = context.create_buffer_from_slice(&params);
+
+    let set_real = |name: &str, value: F| {
+        if std::mem::size_of::<F>() == 4 {
+            kernel.set_f32(name, value.to_f32().unwrap());
+        } else {
+            kernel.set_f64(name, value.to_f64().unwrap());
+        }
+    };
+    kernel.set_buffer("y", &y_buffer);
+    kernel.set_buffer("params", &p_buffer);
+    kernel.set_u32("batch", batch as u32);
+    set_real("h", h);
+    let work_groups = batch.div_ceil(GPU_WORKGROUP_SIZE) as u32;
+
+    let mut t_values = vec![t0];
+    let mut y_values = vec![problem.y0.clone()];
+    let mut done = 0;
+    for end in save_points(n_steps, opts.save_every) {
+        set_real("t0", t0 + h * F::from_usize(done).unwrap());
+        kernel.set_u32("n_steps", (end - done) as u32);
+        kernel.dispatch([work_groups, 1, 1]);
+        y_buffer.copy_to_host(&mut host);
+
+        done = end;
+        t_values.push(if done == n_steps {
+            t1
+        } else {
+            t0 + h * F::from_usize(done).unwrap()
+        });
+        y_values.push(
+            Array2::from_shape_vec((batch, problem.dim()), host.clone()).map_err(|err| {
+                IntegrateError::ComputationError(format!("Invalid device states: {}", err))
+            })?,
+        );
+    }
+
+    Ok(EnsembleResult {
+        t: t_values,
+        y: y_values,
+        n_steps,
+        n_eval: n_steps * opts.method.tableau().1.len(),
+        backend: context.backend_name().to_string(),
+    })
+}
+
+/// Threads per work group of the ensemble kernel
+#[cfg(feature = "gpu")]
+const GPU_WORKGROUP_SIZE: usize = 256;
+
+/// Kernel language of a GPU backend
+#[cfg(feature = "gpu")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KernelDialect {
+    /// CUDA C++, also compiled by HIP for ROCm
+    Cuda,
+    /// OpenCL C
+    OpenCL,
+}
+
+#[cfg(feature = "gpu")]
+impl KernelDialect {
+    fn for_backend(backend: GpuBackend) -> Option<Self> {
+        match backend {
+            GpuBackend::Cuda | GpuBackend::Rocm => Some(KernelDialect::Cuda),
+            GpuBackend::OpenCL => Some(KernelDialect::OpenCL),
+            _ => None,
+        }
+    }
+}
+
+/// Generate the kernel that advances each member by `n_steps` Runge-Kutta steps
+#[cfg(feature = "gpu")]
+fn ensemble_kernel_source<F: IntegrateFloat>(
+    dialect: KernelDialect,
+    rhs_source: &str,
+    method: EnsembleMethod,
+    dim: usize,
+    n_params: usize,
+) -> String {
+    let real = if std::mem::size_of::<F>() == 4 {
+        "float"
+    } else {
+        "double"
+    };
+    let (signature, index) = match dialect {
+        KernelDialect::Cuda => (
+            "extern \"C\" __global__ void ensemble_step(REAL* y, const REAL* params, \
+             const unsigned int batch, const REAL t0, const REAL h, const unsigned int n_steps)",
+            "blockIdx.x * blockDim.x + threadIdx.x",
+        ),
+        KernelDialect::OpenCL => (
+            "__kernel void ensemble_step(__global REAL* y, __global const REAL* params, \
+             const unsigned int batch, const REAL t0, const REAL h, const unsigned int n_steps)",
+            "get_global_id(0)",
+        ),
+    };
+
+    let (a, b, c) = method.tableau();
+    let mut stages = String::new();
+    for (j, (a_row, c_j)) in a.iter().zip(&c).enumerate() {
+        let combination: String = a_row
+            .iter()
+            .enumerate()
+            .filter(|(_, a_jl)| **a_jl != 0.0)
+            .map(|(l, a_jl)| format!(" + h * (REAL){:e} * k[{}][m]", a_jl, l))
+            .collect();
+        stages.push_str(&format!(
+            "        for (int m = 0; m < N_STATE; ++m) stage[m] = yl[m]{};\n\
+             \x20       rhs(t + (REAL){:e} * h, stage, pl, k[{}]);\n",
+            combination, c_j, j
+        ));
+    }
+    let update: String = b
+        .iter()
+        .enumerate()
+        .filter(|(_, b_j)| **b_j != 0.0)
+        .map(|(j, b_j)| format!(" + h * (REAL){:e} * k[{}][m]", b_j, j))
+        .collect();
+
+    format!(
+        "#define REAL {real}\n\
+         #define N_STATE {dim}\n\
+         #define N_PARAMS {n_params}\n\
+         #define N_PARAMS_ALLOC {alloc}\n\
+         \n\
+         {rhs_source}\n\
+         \n\
+         {signature}\n\
+         {{\n\
+         \x20   const unsigned int i = {index};\n\
+         \x20   if (i >= batch) return;\n\
+         \x20   REAL yl[N_STATE], pl[N_PARAMS_ALLOC], stage[N_STATE], k[{n_stages}][N_STATE];\n\
+         \x20   for (int m = 0; m < N_STATE; ++m) yl[m] = y[i * N_STATE + m];\n\
+         \x20   for (int m = 0; m < N_PARAMS; ++m) pl[m] = params[i * N_PARAMS + m];\n\
+         \x20   for (unsigned int s = 0; s < n_steps; ++s) {{\n\
+         \x20       const REAL t = t0 + s * h;\n\
+         {stages}\
+         \x20       for (int m = 0; m < N_STATE; ++m) yl[m] = yl[m]{update};\n\
+         \x20   }}\n\
+         \x20   for (int m = 0; m < N_STATE; ++m) y[i * N_STATE + m] = yl[m];\n\
+         }}\n",
+        alloc = n_params.max(1),
+        n_stages = b.len(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    fn oscillators() -> EnsembleProblem<f64> {
+        // Harmonic oscillators x'' = -ω² x with ω = 1, 2, 3
+        let omegas = array![[1.0], [2.0], [3.0]];
+        EnsembleProblem::new(
+            |_t: f64, y: ArrayView1<f64>, p: ArrayView1<f64>| array![y[1], -p[0] * p[0] * y[0]],
+            Array2::from_shape_fn((3, 2), |(_, j)| if j == 0 { 1.0 } else { 0.0 }),
+            [0.0, 2.0],
+        )
+        .with_params(omegas)
+    }
+
+    #[test]
+    fn test_ensemble_matches_exact_solution() {
+        let result = solve_ensemble(
+            &oscillators(),
+            Some(EnsembleOptions {
+                h: 0.01,
+                save_every: 50,
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(result.n_steps, 200);
+        assert_eq!(result.n_eval, 800);
+        assert_eq!(result.t.len(), 5);
+        assert_relative_eq!(result.t[2], 1.0, epsilon = 1e-12);
+        for (k, (&t, y)) in result.t.iter().zip(&result.y).enumerate() {
+            for i in 0..3 {
+                let omega = (i + 1) as f64;
+                assert_relative_eq!(y[[i, 0]], (omega * t).cos(), epsilon = 1e-6);
+                assert_eq!(result.member(i)[k], y.row(i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ensemble_method_orders() {
+        let error = |method, h| {
+            let result = solve_ensemble(
+                &oscillators(),
+                Some(EnsembleOptions {
+                    method,
+                    h,
+                    ..Default::default()
+                }),
+            )
+            .unwrap();
+            (result.final_states()[[1, 0]] - 4.0_f64.cos()).abs()
+        };
+
+        for (method, order) in [
+            (EnsembleMethod::Euler, 1.0),
+            (EnsembleMethod::Midpoint, 2.0),
+            (EnsembleMethod::RK4, 4.0),
+        ] {
+            let ratio = error(method, 0.01) / error(method, 0.005);
+            let observed = ratio.log2();
+            assert!(
+                (observed - order).abs() < 0.3,
+                "{:?}: observed order {}",
+                method,
+                observed
+            );
+        }
+    }
+
+    #[test]
+    fn test_ensemble_validation() {
+        let problem = oscillators().with_params(array![[1.0]]);
+        assert!(solve_ensemble(&problem, None).is_err());
+
+        let options = EnsembleOptions {
+            h: 1e-3,
+            max_steps: 100,
+            ..Default::default()
+        };
+        assert!(solve_ensemble(&oscillators(), Some(options)).is_err());
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn test_ensemble_gpu_kernel_and_fallback() {
+        let rhs = "__device__ void rhs(REAL t, const REAL* y, const REAL* p, REAL* dydt) {\n\
+                   \x20   dydt[0] = y[1];\n\
+                   \x20   dydt[1] = -p[0] * p[0] * y[0];\n\
+                   }";
+        let source =
+            ensemble_kernel_source::<f64>(KernelDialect::Cuda, rhs, EnsembleMethod::RK4, 2, 1);
+        assert!(source.contains("#define REAL double"));
+        assert!(source.contains(rhs));
+        assert_eq!(source.matches("rhs(t + ").count(), 4);
+
+        // Without a usable device the CPU path gives identical results
+        let problem = oscillators().with_gpu_kernel(rhs);
+        let cpu = solve_ensemble(&problem, None).unwrap();
+        for backend in [GpuBackend::Cpu, GpuBackend::Metal] {
+            let gpu = solve_ensemble_gpu(&problem, None, backend).unwrap();
+            assert_eq!(gpu.backend, "CPU");
+            assert_eq!(gpu.final_states(), cpu.final_states());
+        }
+    }
+}
@@ -0,0 +1,519 @@
+//! Exponential integrators for semilinear ODE systems
+//!
+//! This module solves systems of the form `dy/dt = L y + N(t, y)`, where the
+//! linear operator `L` carries the stiffness, as in semilinear PDEs
+//! discretized by the method of lines, and `N` is a non-stiff nonlinearity.
+//! Exponential time differencing treats `L` exactly through the φ-functions
+//!
+//! `φ_0(z) = e^z`, `φ_{k+1}(z) = (φ_k(z) - 1/k!) / z`,
+//!
+//! so the step size is limited by the accuracy in `N` only, not by the
+//! stiffness of `L`.
+//!
+//! Linear combinations `Σ_k h^k φ_k(h L) v_k` are evaluated without forming
+//! any matrix function of `L`: they are the exponential of an augmented
+//! operator of size `n + p` applied to a vector, which is approximated in a
+//! Krylov subspace using only products with `L`. The subspace dimension is
+//! bounded, and the time interval is subdivided when the a posteriori error
+//! estimate exceeds the tolerance.
+
+use crate::common::IntegrateFloat;
+use crate::error::{IntegrateError, IntegrateResult};
+use crate::ode::types::{ODEMethod, ODEResult};
+use ndarray::{s, Array1, Array2, ArrayView1};
+
+/// Exponential integration method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExponentialMethod {
+    /// Exponential Euler (ETD1), first order
+    ExponentialEuler,
+    /// Fourth-order exponential time differencing Runge-Kutta scheme of Cox
+    /// and Matthews
+    #[default]
+    ETDRK4,
+}
+
+/// Options for [`solve_exponential`]
+#[derive(Debug, Clone)]
+pub struct ExponentialOptions<F: IntegrateFloat> {
+    /// Exponential integration method
+    pub method: ExponentialMethod,
+    /// Step size; the last step is shortened to end at the final time
+    pub h: F,
+    /// Maximum dimension of the Krylov subspaces
+    pub krylov_dim: usize,
+    /// Relative tolerance of the Krylov approximations
+    pub krylov_tol: F,
+    /// Maximum number of steps
+    pub max_steps: usize,
+}
+
+impl<F: IntegrateFloat> Default for ExponentialOptions<F> {
+    fn default() -> Self {
+        ExponentialOptions {
+            method: ExponentialMethod::default(),
+            h: F::from_f64(0.01).unwrap(),
+            krylov_dim: 40,
+            krylov_tol: F::from_f64(1e-10).unwrap(),
+            max_steps: 100_000,
+        }
+    }
+}
+
+fn norm<F: IntegrateFloat>(v: &Array1<F>) -> F {
+    v.dot(v).sqrt()
+}
+
+/// Exponential of a small dense matrix by scaling and squaring of its Taylor series
+fn expm_dense<F: IntegrateFloat>(a: &Array2<F>) -> Array2<F> {
+    let norm1 = |m: &Array2<F>| {
+        m.columns()
+            .into_iter()
+            .map(|c| c.iter().fold(F::zero(), |acc, &x| acc + x.abs()))
+            .fold(F::zero(), F::max)
+    };
+    let half = F::from_f64(0.5).unwrap();
+    let mut squarings = 0;
+    let mut scale = F::one();
+    let a_norm = norm1(a);
+    while a_norm * scale > half {
+        scale *= half;
+        squarings += 1;
+    }
+
+    let scaled = a * scale;
+    let mut result = Array2::eye(a.nrows());
+    let mut term = Array2::eye(a.nrows());
+    for k in 1..=30 {
+        term = term.dot(&scaled) / F::from_usize(k).unwrap();
+        result += &term;
+        if norm1(&term) <= F::epsilon() * norm1(&result) {
+            break;
+        }
+    }
+    for _ in 0..squarings {
+        result = result.dot(&result);
+    }
+    result
+}
+
+/// Approximate `exp(tau A) w` in Krylov subspaces of `A`
+fn krylov_expv<F, A>(
+    apply: &mut A,
+    tau: F,
+    w: Array1<F>,
+    krylov_dim: usize,
+    tol: F,
+) -> IntegrateResult<Array1<F>>
+where
+    F: IntegrateFloat,
+    A: FnMut(ArrayView1<F>) -> Array1<F>,
+{
+    let n = w.len();
+    let max_dim = krylov_dim.clamp(1, n.max(1));
+    let mut w = w;
+    let mut remaining = tau;
+    let mut step = tau;
+    let min_step = tau * F::from_f64(1e-12).unwrap();
+
+    while remaining > F::zero() {
+        let beta = norm(&w);
+        if beta == F::zero() {
+            break;
+        }
+        step = step.min(remaining);
+
+        // Arnoldi process with modified Gram-Schmidt
+        let mut basis = vec![&w / beta];
+        let mut hessenberg = Array2::<F>::zeros((max_dim + 1, max_dim));
+        let mut m = max_dim;
+        let mut breakdown = false;
+        for j in 0..max_dim {
+            let mut v = apply(basis[j].view());
+            for (i, b) in basis.iter().enumerate() {
+                let h = v.dot(b);
+                hessenberg[[i, j]] = h;
+                v.scaled_add(-h, b);
+            }
+            let h_next = norm(&v);
+            hessenberg[[j + 1, j]] = h_next;
+            // The subspace is invariant, so the approximation is exact
+            if h_next <= F::epsilon() * beta.max(F::one()) * F::from_f64(1e3).unwrap() {
+                m = j + 1;
+                breakdown = true;
+                break;
+            }
+            basis.push(v / h_next);
+        }
+        let h_next = hessenberg[[m, m - 1]];
+
+        // exp([[step H, e1], [0, 0]]) holds exp(step H) e1 and φ1(step H) e1,
+        // the latter for the error estimate of the subspace approximation
+        loop {
+            let mut augmented = Array2::<F>::zeros((m + 1, m + 1));
+            augmented
+                .slice_mut(s![..m, ..m])
+                .assign(&(&hessenberg.slice(s![..m, ..m]) * step));
+            augmented[[0, m]] = F::one();
+            let exp = expm_dense(&augmented);
+            let error = if breakdown {
+                F::zero()
+            } else {
+                beta * h_next * step * exp[[m - 1, m]].abs()
+            };
+
+            if error <= tol * beta * step / tau || step <= min_step {
+                if step <= min_step && error > tol * beta * step / tau {
+                    return Err(IntegrateError::ComputationError(
+                        "Krylov approximation of the matrix exponential did not converge"
+                            .to_string(),
+                    ));
+                }
+                let mut next = Array1::zeros(n);
+                for (i, b) in basis.iter().take(m).enumerate() {
+                    next.scaled_add(beta * exp[[i, 0]], b);
+                }
+                w = next;
+                remaining -= step;
+                break;
+            }
+            step *= F::from_f64(0.5).unwrap();
+        }
+    }
+    Ok(w)
+}
+
+/// Linear combination of φ-functions, `Σ_{k=0}^{p} h^k φ_k(h A) v_k`
+///
+/// The combination is the first `n` components of `exp(h Ã) (v_0, e_p)`
+/// for the augmented operator `Ã = [A, W; 0, J]` of size `n + p`, with
+/// `W = [v_p, ..., v_1]` and `J` the shift matrix with ones on the first
+/// superdiagonal (Al-Mohy and Higham). The exponential is approximated in
+/// Krylov subspaces of `Ã`, so `A` is only applied to vectors.
+///
+/// # Arguments
+///
+/// * `apply` - Matrix-vector product `v ↦ A v`
+/// * `h` - Time step
+/// * `vectors` - The vectors `v_0, ..., v_p`, all of the same length
+/// * `krylov_dim` - Maximum dimension of the Krylov subspaces
+/// * `tol` - Relative tolerance of the Krylov approximation
+///
+/// # Returns
+///
+/// The linear combination, or an error if the vectors are inconsistent or the
+/// Krylov approximation fails
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, ArrayView1};
+/// use scirs2_integrate::ode::exponential::phi_combination;
+///
+/// // For a diagonal matrix the φ-functions act componentwise
+/// let a = array![-1.0, -50.0];
+/// let apply = |v: ArrayView1<f64>| &a * &v;
+/// let (v0, v1) = (array![1.0, 1.0], array![2.0, 3.0]);
+/// let h = 0.1;
+/// let result = phi_combination(apply, h, &[v0.clone(), v1.clone()], 10, 1e-12).unwrap();
+///
+/// // φ_0(z) v0 + h φ_1(z) v1 with z = h a and φ_1(z) = (e^z - 1) / z
+/// for i in 0..2 {
+///     let z = h * a[i];
+///     let expected = z.exp() * v0[i] + h * (z.exp() - 1.0) / z * v1[i];
+///     assert!((result[i] - expected).abs() < 1e-10);
+/// }
+/// ```
+pub fn phi_combination<F, A>(
+    apply: A,
+    h: F,
+    vectors: &[Array1<F>],
+    krylov_dim: usize,
+    tol: F,
+) -> IntegrateResult<Array1<F>>
+where
+    F: IntegrateFloat,
+    A: Fn(ArrayView1<F>) -> Array1<F>,
+{
+    let Some(v0) = vectors.first() else {
+        return Err(IntegrateError::ValueError(
+            "At least one vector is required for a φ-function combination".to_string(),
+        ));
+    };
+    let n = v0.len();
+    if vectors.iter().any(|v| v.len() != n) {
+        return Err(IntegrateError::DimensionMismatch(
+            "All vectors of a φ-function combination must have the same length".to_string(),
+        ));
+    }
+    let p = vectors.len() - 1;
+
+    // Scale W so that its columns are of unit size, compensated in e_p
+    let w_norm = vectors[1..].iter().map(norm).fold(F::zero(), F::max);
+    let eta = if w_norm > F::zero() {
+        F::one() / w_norm
+    } else {
+        F::one()
+    };
+
+    let mut augmented = |x: ArrayView1<F>| {
+        let mut result = Array1::zeros(n + p);
+        let mut top = apply(x.slice(s![..n]));
+        for j in 0..p {
+            top.scaled_add(eta * x[n + j], &vectors[p - j]);
+        }
+        result.slice_mut(s![..n]).assign(&top);
+        for j in 0..p.saturating_sub(1) {
+            result[n + j] = x[n + j + 1];
+        }
+        result
+    };
+
+    let mut w = Array1::zeros(n + p);
+    w.slice_mut(s![..n]).assign(v0);
+    if p > 0 {
+        w[n + p - 1] = F::one() / eta;
+    }
+    let result = krylov_expv(&mut augmented, h, w, krylov_dim, tol)?;
+    Ok(result.slice(s![..n]).to_owned())
+}
+
+/// Solve `dy/dt = L y + N(t, y)` with an exponential integrator
+///
+/// The integrator takes fixed steps of size `options.h`. Each step of
+/// exponential Euler evaluates `N` once and one φ-function combination; each
+/// step of ETDRK4 evaluates `N` four times and four combinations.
+///
+/// # Arguments
+///
+/// * `linear` - Matrix-vector product `v ↦ L v` of the stiff linear part
+/// * `nonlinear` - Nonlinear part `N(t, y)`
+/// * `t_span` - Time interval `[t0, t1]` with `t1 > t0`
+/// * `y0` - Initial state
+/// * `options` - Integrator options (uses defaults if `None`)
+///
+/// # Returns
+///
+/// The solution at every step; `n_eval` counts the evaluations of `N`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{Array1, ArrayView1};
+/// use scirs2_integrate::ode::exponential::{solve_exponential, ExponentialOptions};
+///
+/// // Allen-Cahn equation u_t = ε u_xx + u - u³ on a grid with zero boundaries
+/// let n = 50;
+/// let dx = 1.0 / (n + 1) as f64;
+/// let diffusion = move |u: ArrayView1<f64>| {
+///     Array1::from_shape_fn(n, |i| {
+///         let left = if i > 0 { u[i - 1] } else { 0.0 };
+///         let right = if i + 1 < n { u[i + 1] } else { 0.0 };
+///         0.01 * (left - 2.0 * u[i] + right) / (dx * dx)
+///     })
+/// };
+/// let reaction = |_t: f64, u: ArrayView1<f64>| u.mapv(|ui| ui - ui.powi(3));
+/// let u0 = Array1::from_shape_fn(n, |i| 0.1 * ((i + 1) as f64 * dx * 6.0).sin());
+///
+/// let options = ExponentialOptions {
+///     h: 0.05,
+///     ..Default::default()
+/// };
+/// let result = solve_exponential(diffusion, reaction, [0.0, 5.0], u0, Some(options)).unwrap();
+/// assert!(result.success);
+/// assert_eq!(result.n_steps, 100);
+/// // The solution saturates towards the stable states ±1
+/// assert!(result.y.last().unwrap().iter().all(|u| u.abs() < 1.0 + 1e-6));
+/// ```
+pub fn solve_exponential<F, L, N>(
+    linear: L,
+    nonlinear: N,
+    t_span: [F; 2],
+    y0: Array1<F>,
+    options: Option<ExponentialOptions<F>>,
+) -> IntegrateResult<ODEResult<F>>
+where
+    F: IntegrateFloat,
+    L: Fn(ArrayView1<F>) -> Array1<F>,
+    N: Fn(F, ArrayView1<F>) -> Array1<F>,
+{
+    let opts = options.unwrap_or_default();
+    let [t0, t1] = t_span;
+    if t1 <= t0 || opts.h <= F::zero() {
+        return Err(IntegrateError::ValueError(
+            "Exponential integrators require t1 > t0 and a positive step size".to_string(),
+        ));
+    }
+
+    let mut n_eval = 0;
+    let mut eval = |t: F, y: &Array1<F>| {
+        n_eval += 1;
+        nonlinear(t, y.view())
+    };
+    let phi = |h: F, vectors: &[Array1<F>]| {
+        phi_combination(&linear, h, vectors, opts.krylov_dim, opts.krylov_tol)
+    };
+
+    let mut t = t0;
+    let mut y = y0;
+    let mut t_values = vec![t];
+    let mut y_values = vec![y.clone()];
+    let two = F::one() + F::one();
+    let four = two + two;
+    let tiny = F::epsilon() * F::from_f64(100.0).unwrap() * (t0.abs() + t1.abs());
+
+    while t1 - t > tiny {
+        if t_values.len() > opts.max_steps {
+            return Err(IntegrateError::ComputationError(format!(
+                "Maximum number of steps ({}) reached at t = {}",
+                opts.max_steps, t
+            )));
+        }
+        let h = opts.h.min(t1 - t);
+
+        y = match opts.method {
+            ExponentialMethod::ExponentialEuler => {
+                let n_y = eval(t, &y);
+                phi(h, &[y, n_y])?
+            }
+            ExponentialMethod::ETDRK4 => {
+                let half = h / two;
+                let n_y = eval(t, &y);
+                let a = phi(half, &[y.clone(), n_y.clone()])?;
+                let n_a = eval(t + half, &a);
+                let b = phi(half, &[y.clone(), n_a.clone()])?;
+                let n_b = eval(t + half, &b);
+                let c = phi(half, &[a, &n_b * two - &n_y])?;
+                let n_c = eval(t + h, &c);
+
+                let v2 = (&(&n_a + &n_b) * two - &n_y * F::from_f64(3.0).unwrap() - &n_c) / h;
+                let v3 = (&n_y - &n_a - &n_b + &n_c) * (four / (h * h));
+                phi(h, &[y, n_y, v2, v3])?
+            }
+        };
+        t += h;
+
+        if !y.iter().all(|x| x.is_finite()) {
+            return Err(IntegrateError::ComputationError(format!(
+                "Non-finite solution at t = {}",
+                t
+            )));
+        }
+        t_values.push(t);
+        y_values.push(y.clone());
+    }
+
+    let n_steps = t_values.len() - 1;
+    Ok(ODEResult {
+        t: t_values,
+        y: y_values,
+        success: true,
+        message: Some(format!("Exponential integrator: {:?}", opts.method)),
+        n_eval,
+        n_steps,
+        n_accepted: n_steps,
+        n_rejected: 0,
+        n_lu: 0,
+        n_jac: 0,
+        method: ODEMethod::RK4, // Default representation
+        step_methods: Vec::new(),
+        stiffness: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_expm_dense() {
+        // Rotation generator and a stiff diagonal
+        let a = array![[0.0, -2.0, 0.0], [2.0, 0.0, 0.0], [0.0, 0.0, -40.0]];
+        let e = expm_dense(&a);
+        let expected = array![
+            [2.0f64.cos(), -(2.0f64.sin()), 0.0],
+            [2.0f64.sin(), 2.0f64.cos(), 0.0],
+            [0.0, 0.0, (-40.0f64).exp()]
+        ];
+        assert!((&e - &expected).iter().all(|x| x.abs() < 1e-13));
+    }
+
+    #[test]
+    fn test_phi_combination_stiff_diagonal() {
+        // φ-functions of a stiff diagonal operator, componentwise
+        let n = 200;
+        let diagonal = Array1::from_shape_fn(n, |i| -((i * i) as f64));
+        let apply = |v: ArrayView1<f64>| &diagonal * &v;
+        let vectors: Vec<Array1<f64>> = (0..4)
+            .map(|k| Array1::from_shape_fn(n, |i| ((i + k) as f64).cos()))
+            .collect();
+        let h = 0.05;
+        let result = phi_combination(apply, h, &vectors, 30, 1e-12).unwrap();
+
+        let phi = |k: usize, z: f64| -> f64 {
+            // Series for small arguments, recurrence otherwise
+            if z.abs() < 0.1 {
+                let mut factorial = (1..=k).product::<usize>() as f64;
+                let mut sum = 0.0;
+                for j in 0..20usize {
+                    sum += z.powi(j as i32) / factorial;
+                    factorial *= (k + j + 1) as f64;
+                }
+                sum
+            } else {
+                let mut value = z.exp();
+                let mut factorial = 1.0;
+                for j in 0..k {
+                    if j > 0 {
+                        factorial *= j as f64;
+                    }
+                    value = (value - 1.0 / factorial) / z;
+                }
+                value
+            }
+        };
+        for i in 0..n {
+            let z = h * diagonal[i];
+            let expected: f64 = (0..4)
+                .map(|k| h.powi(k as i32) * phi(k, z) * vectors[k][i])
+                .sum();
+            assert!(
+                (result[i] - expected).abs() < 1e-9,
+                "{}: {} vs {}",
+                i,
+                result[i],
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_etdrk4_order() {
+        // Scalar semilinear problem y' = -10 y + sin(y) with a reference solution
+        let linear = |v: ArrayView1<f64>| v.mapv(|x| -10.0 * x);
+        let nonlinear = |t: f64, y: ArrayView1<f64>| y.mapv(|x| x.sin() + t.cos());
+        let solve = |method, h| {
+            let options = ExponentialOptions {
+                method,
+                h,
+                ..Default::default()
+            };
+            let result =
+                solve_exponential(linear, nonlinear, [0.0, 1.0], array![1.0], Some(options))
+                    .unwrap();
+            result.y.last().unwrap()[0]
+        };
+        let reference = solve(ExponentialMethod::ETDRK4, 1e-3);
+
+        let errors = |method| [0.05, 0.025].map(|h| (solve(method, h) - reference).abs());
+        let [e1, e2] = errors(ExponentialMethod::ETDRK4);
+        assert!(e1 / e2 > 12.0, "ETDRK4 error ratio {}", e1 / e2);
+        let [e1, e2] = errors(ExponentialMethod::ExponentialEuler);
+        assert!(
+            (1.6..2.6).contains(&(e1 / e2)),
+            "ETD1 error ratio {}",
+            e1 / e2
+        );
+    }
+}
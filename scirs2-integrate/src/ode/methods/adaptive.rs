@@ -7,6 +7,7 @@
 use crate::common::IntegrateFloat;
 use crate::error::IntegrateResult;
 use crate::ode::types::{ODEMethod, ODEOptions, ODEResult};
+use crate::ode::utils::progress::{aborted_message, SolverStats};
 use ndarray::{Array1, ArrayView1};
 
 /// Solve ODE using the Dormand-Prince method (RK45)
@@ -79,6 +80,7 @@ where
     let c6 = F::one();
 
     // Main integration loop
+    let mut aborted = false;
     while t < t_end && step_count < opts.max_steps {
         // Adjust step size for the last step if needed
         if t + h > t_end {
@@ -195,8 +197,12 @@ where
 
         if err_norm <= F::one() {
             // Step accepted
+            let h_accepted = h;
             t += h;
             y = y5; // Use higher order solution
+            if let Some(projection) = &opts.projection {
+                y = projection.project(&y)?;
+            }
 
             // Store results
             t_values.push(t);
@@ -212,6 +218,21 @@ where
 
             step_count += 1;
             accepted_steps += 1;
+
+            if let Some(progress) = &opts.progress {
+                let stats = SolverStats {
+                    n_eval: func_evals,
+                    n_steps: step_count,
+                    n_accepted: accepted_steps,
+                    n_rejected: rejected_steps,
+                    n_lu: 0,
+                    n_jac: 0,
+                };
+                if progress.notify(t, y.view(), h_accepted, stats) {
+                    aborted = true;
+                    break;
+                }
+            }
         } else {
             // Step rejected
             h *= factor.min(F::one());
@@ -229,7 +250,9 @@ where
 
     // Check if integration was successful
     let success = t >= t_end;
-    let message = if !success {
+    let message = if aborted && !success {
+        Some(aborted_message(t))
+    } else if !success {
         Some(format!(
             "Maximum number of steps ({}) reached",
             opts.max_steps
@@ -251,6 +274,8 @@ where
         n_lu: 0,  // No LU decompositions in explicit methods
         n_jac: 0, // No Jacobian evaluations in explicit methods
         method: ODEMethod::RK45,
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
 
@@ -316,6 +341,7 @@ where
 
     // Simplified implementation
     // Main integration loop
+    let mut aborted = false;
     while t < t_end && step_count < opts.max_steps {
         // Adjust step size for the last step if needed
         if t + h > t_end {
@@ -334,6 +360,9 @@ where
         // Always accept the step in this simplified implementation
         t += h;
         y = y_next;
+        if let Some(projection) = &opts.projection {
+            y = projection.project(&y)?;
+        }
 
         // Store results
         t_values.push(t);
@@ -341,11 +370,28 @@ where
 
         step_count += 1;
         accepted_steps += 1;
+
+        if let Some(progress) = &opts.progress {
+            let stats = SolverStats {
+                n_eval: func_evals,
+                n_steps: step_count,
+                n_accepted: accepted_steps,
+                n_rejected: rejected_steps,
+                n_lu: 0,
+                n_jac: 0,
+            };
+            if progress.notify(t, y.view(), h, stats) {
+                aborted = true;
+                break;
+            }
+        }
     }
 
     // Check if integration was successful
     let success = t >= t_end;
-    let message = if !success {
+    let message = if aborted && !success {
+        Some(aborted_message(t))
+    } else if !success {
         Some(format!(
             "Maximum number of steps ({}) reached",
             opts.max_steps
@@ -367,6 +413,8 @@ where
         n_lu: 0,  // No LU decompositions in explicit methods
         n_jac: 0, // No Jacobian evaluations in explicit methods
         method: ODEMethod::RK23,
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
 
@@ -433,6 +481,7 @@ where
 
     // Simplified implementation
     // Main integration loop
+    let mut aborted = false;
     while t < t_end && step_count < opts.max_steps {
         // Adjust step size for the last step if needed
         if t + h > t_end {
@@ -451,6 +500,9 @@ where
         // Always accept the step in this simplified implementation
         t += h;
         y = y_next;
+        if let Some(projection) = &opts.projection {
+            y = projection.project(&y)?;
+        }
 
         // Store results
         t_values.push(t);
@@ -458,11 +510,28 @@ where
 
         step_count += 1;
         accepted_steps += 1;
+
+        if let Some(progress) = &opts.progress {
+            let stats = SolverStats {
+                n_eval: func_evals,
+                n_steps: step_count,
+                n_accepted: accepted_steps,
+                n_rejected: rejected_steps,
+                n_lu: 0,
+                n_jac: 0,
+            };
+            if progress.notify(t, y.view(), h, stats) {
+                aborted = true;
+                break;
+            }
+        }
     }
 
     // Check if integration was successful
     let success = t >= t_end;
-    let message = if !success {
+    let message = if aborted && !success {
+        Some(aborted_message(t))
+    } else if !success {
         Some(format!(
             "Maximum number of steps ({}) reached",
             opts.max_steps
@@ -484,5 +553,7 @@ where
         n_lu: 0,  // No LU decompositions in explicit methods
         n_jac: 0, // No Jacobian evaluations in explicit methods
         method: ODEMethod::DOP853,
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
@@ -10,6 +10,7 @@ use crate::ode::utils::common::{calculate_error_weights, extrapolate};
 use crate::ode::utils::jacobian::{
     newton_solve, JacobianManager, JacobianStrategy, JacobianStructure, NewtonParameters,
 };
+use crate::ode::utils::progress::{aborted_message, SolverStats};
 use crate::IntegrateFloat;
 use ndarray::{Array1, ArrayView1};
 
@@ -105,6 +106,7 @@ where
     let mut newton_iters = F::zero();
     let mut n_lu = 0;
     let mut n_jac = 0;
+    let mut aborted = false;
 
     // Generate initial points using RK4 (more accurate than Euler)
     if order > 1 {
@@ -130,6 +132,9 @@ where
             // Combine slopes with appropriate weights
             let slope = (k1 + k2.clone() * two + k3.clone() * two + k4) / F::from_f64(6.0).unwrap();
             y = y + slope * h;
+            if let Some(projection) = &opts.projection {
+                y = projection.project(&y)?;
+            }
 
             // Update time
             t += h;
@@ -141,6 +146,21 @@ where
             step_count += 1;
             accepted_steps += 1;
 
+            if let Some(progress) = &opts.progress {
+                let stats = SolverStats {
+                    n_eval: func_evals,
+                    n_steps: step_count,
+                    n_accepted: accepted_steps,
+                    n_rejected: rejected_steps,
+                    n_lu,
+                    n_jac,
+                };
+                if progress.notify(t, y.view(), h, stats) {
+                    aborted = true;
+                    break;
+                }
+            }
+
             // Don't continue if we've reached t_end
             if t >= t_end {
                 break;
@@ -198,7 +218,7 @@ where
     let min_steps_before_order_change = 5;
 
     // Main integration loop
-    while t < t_end && step_count < opts.max_steps {
+    while !aborted && t < t_end && step_count < opts.max_steps {
         // Adjust step size for the last step if needed
         if t + h > t_end {
             h = t_end - t;
@@ -246,11 +266,11 @@ where
             // Initialize residual with c_0 * y_{n+1} term
             let mut residual = y_next.clone() * coeffs[0];
 
-            // Subtract previous values contribution
+            // Add previous values contribution
             for (j, coeff) in coeffs.iter().enumerate().skip(1).take(current_order) {
                 if j <= y_values.len() {
                     let idx = y_values.len() - j;
-                    residual = residual - y_values[idx].clone() * *coeff;
+                    residual = residual + y_values[idx].clone() * *coeff;
                 }
             }
 
@@ -309,7 +329,7 @@ where
                     for (j, &coeff) in lower_coeffs.iter().enumerate().skip(1).take(lower_order) {
                         if j <= y_values.len() {
                             let idx = y_values.len() - j;
-                            rhs = rhs + y_values[idx].clone() * coeff;
+                            rhs = rhs - y_values[idx].clone() * coeff;
                         }
                     }
 
@@ -354,8 +374,12 @@ where
                 // Check if step is acceptable
                 if error <= F::one() {
                     // Step accepted
+                    let h_accepted = h;
                     t = next_t;
-                    y = y_next;
+                    y = match &opts.projection {
+                        Some(projection) => projection.project(&y_next)?,
+                        None => y_next,
+                    };
 
                     // Store results
                     t_values.push(t);
@@ -385,6 +409,21 @@ where
                             last_order_change = step_count;
                         }
                     }
+
+                    if let Some(progress) = &opts.progress {
+                        let stats = SolverStats {
+                            n_eval: func_evals,
+                            n_steps: step_count,
+                            n_accepted: accepted_steps,
+                            n_rejected: rejected_steps,
+                            n_lu,
+                            n_jac,
+                        };
+                        if progress.notify(t, y.view(), h_accepted, stats) {
+                            aborted = true;
+                            break;
+                        }
+                    }
                 } else {
                     // Step rejected
                     h *= factor;
@@ -412,7 +451,9 @@ where
     }
 
     let success = t >= t_end;
-    let message = if !success {
+    let message = if aborted && !success {
+        Some(aborted_message(t))
+    } else if !success {
         Some(format!(
             "Maximum number of steps ({}) reached",
             opts.max_steps
@@ -437,5 +478,7 @@ where
         n_lu,
         n_jac,
         method: ODEMethod::Bdf,
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
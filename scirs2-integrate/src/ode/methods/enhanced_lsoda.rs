@@ -6,11 +6,12 @@
 //! better Jacobian handling.
 
 use crate::error::{IntegrateError, IntegrateResult};
-use crate::ode::types::{ODEMethod, ODEOptions, ODEResult};
+use crate::ode::types::{ODEMethod, ODEOptions, ODEResult, StepMethod};
 use crate::ode::utils::common::{
     calculate_error_weights, estimate_initial_step, extrapolate, finite_difference_jacobian,
     scaled_norm, solve_linear_system,
 };
+use crate::ode::utils::progress::{aborted_message, SolverStats};
 use crate::ode::utils::stiffness::integration::{AdaptiveMethodState, AdaptiveMethodType};
 use crate::ode::utils::stiffness::StiffnessDetectionConfig;
 use crate::IntegrateFloat;
@@ -193,6 +194,8 @@ where
     // Result storage
     let mut t_values = vec![t_start];
     let mut y_values = vec![y0.clone()];
+    let mut step_methods = Vec::new();
+    let mut aborted = false;
 
     // Main integration loop
     while state.t < t_end && state.steps < opts.max_steps {
@@ -205,17 +208,21 @@ where
         state.h = state.h.min(max_step).max(min_step);
 
         // Step with the current method
-        let step_result = match state.adaptive_state.method_type {
-            AdaptiveMethodType::Explicit | AdaptiveMethodType::Adams => {
-                enhanced_adams_step(&mut state, &f, &opts, &mut func_evals)
-            }
-            AdaptiveMethodType::Implicit | AdaptiveMethodType::BDF => {
-                enhanced_bdf_step(&mut state, &f, &opts, &mut func_evals)
-            }
-            AdaptiveMethodType::RungeKutta => {
+        let t_previous = state.t;
+        let (step_method, step_result) = match state.adaptive_state.method_type {
+            AdaptiveMethodType::Explicit | AdaptiveMethodType::Adams => (
+                StepMethod::Adams,
+                enhanced_adams_step(&mut state, &f, &opts, &mut func_evals),
+            ),
+            AdaptiveMethodType::Implicit | AdaptiveMethodType::BDF => (
+                StepMethod::Bdf,
+                enhanced_bdf_step(&mut state, &f, &opts, &mut func_evals),
+            ),
+            AdaptiveMethodType::RungeKutta => (
                 // For RK methods, use Adams for now
-                enhanced_adams_step(&mut state, &f, &opts, &mut func_evals)
-            }
+                StepMethod::Adams,
+                enhanced_adams_step(&mut state, &f, &opts, &mut func_evals),
+            ),
         };
 
         state.steps += 1;
@@ -226,10 +233,19 @@ where
                 if accepted {
                     // Step accepted
 
+                    // Restore the invariants before the point enters the history
+                    if let Some(projection) = &opts.projection {
+                        state.y = projection.project(&state.y)?;
+                        state.dy = f(state.t, state.y.view());
+                        func_evals += 1;
+                        state.func_evals += 1;
+                    }
+
                     // Add to history and results
                     state.add_to_history();
                     t_values.push(state.t);
                     y_values.push(state.y.clone());
+                    step_methods.push(step_method);
 
                     state.accepted_steps += 1;
 
@@ -252,6 +268,22 @@ where
                     {
                         state.jacobian_age += 1;
                     }
+
+                    if let Some(progress) = &opts.progress {
+                        let stats = SolverStats {
+                            n_eval: func_evals,
+                            n_steps: state.steps,
+                            n_accepted: state.accepted_steps,
+                            n_rejected: state.rejected_steps,
+                            n_lu: state.n_lu,
+                            n_jac: state.n_jac,
+                        };
+                        let h = state.t - t_previous;
+                        if progress.notify(state.t, state.y.view(), h, stats) {
+                            aborted = true;
+                            break;
+                        }
+                    }
                 } else {
                     // Step rejected
                     state.rejected_steps += 1;
@@ -306,7 +338,9 @@ where
     }
 
     let success = state.t >= t_end;
-    let message = if !success {
+    let message = if aborted && !success {
+        Some(aborted_message(state.t))
+    } else if !success {
         Some(format!(
             "Maximum number of steps ({}) reached",
             opts.max_steps
@@ -329,6 +363,8 @@ where
         n_lu: state.n_lu,
         n_jac: state.n_jac,
         method: ODEMethod::LSODA,
+        step_methods,
+        stiffness: None,
     })
 }
 
@@ -5,6 +5,7 @@
 
 use crate::error::IntegrateResult;
 use crate::ode::types::{ODEMethod, ODEOptions, ODEResult};
+use crate::ode::utils::progress::{aborted_message, SolverStats};
 use crate::IntegrateFloat;
 use ndarray::{Array1, ArrayView1};
 
@@ -52,6 +53,8 @@ where
     let mut func_evals = 0;
     let mut step_count = 0;
 
+    let mut aborted = false;
+
     // Main integration loop
     while t < t_end && step_count < opts.max_steps {
         // Calculate next time point
@@ -74,15 +77,35 @@ where
         // Store results
         t = next_t;
         y = y_next;
+        if let Some(projection) = &opts.projection {
+            y = projection.project(&y)?;
+        }
         t_values.push(t);
         y_values.push(y.clone());
 
         step_count += 1;
+
+        if let Some(progress) = &opts.progress {
+            let stats = SolverStats {
+                n_eval: func_evals,
+                n_steps: step_count,
+                n_accepted: step_count,
+                n_rejected: 0,
+                n_lu: 0,
+                n_jac: 0,
+            };
+            if progress.notify(t, y.view(), h_actual, stats) {
+                aborted = true;
+                break;
+            }
+        }
     }
 
     // Check if integration was successful
     let success = t >= t_end;
-    let message = if !success {
+    let message = if aborted && !success {
+        Some(aborted_message(t))
+    } else if !success {
         Some(format!(
             "Maximum number of steps ({}) reached",
             opts.max_steps
@@ -104,6 +127,8 @@ where
         n_lu: 0,                // No LU decompositions in explicit methods
         n_jac: 0,               // No Jacobian evaluations in explicit methods
         method: ODEMethod::Euler,
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
 
@@ -154,6 +179,8 @@ where
     let two = F::from_f64(2.0).unwrap();
     let six = F::from_f64(6.0).unwrap();
 
+    let mut aborted = false;
+
     // Main integration loop
     while t < t_end && step_count < opts.max_steps {
         // Calculate next time point
@@ -183,15 +210,35 @@ where
         // Store results
         t = next_t;
         y = y_next;
+        if let Some(projection) = &opts.projection {
+            y = projection.project(&y)?;
+        }
         t_values.push(t);
         y_values.push(y.clone());
 
         step_count += 1;
+
+        if let Some(progress) = &opts.progress {
+            let stats = SolverStats {
+                n_eval: func_evals,
+                n_steps: step_count,
+                n_accepted: step_count,
+                n_rejected: 0,
+                n_lu: 0,
+                n_jac: 0,
+            };
+            if progress.notify(t, y.view(), h_actual, stats) {
+                aborted = true;
+                break;
+            }
+        }
     }
 
     // Check if integration was successful
     let success = t >= t_end;
-    let message = if !success {
+    let message = if aborted && !success {
+        Some(aborted_message(t))
+    } else if !success {
         Some(format!(
             "Maximum number of steps ({}) reached",
             opts.max_steps
@@ -213,5 +260,7 @@ where
         n_lu: 0,                // No LU decompositions in explicit methods
         n_jac: 0,               // No Jacobian evaluations in explicit methods
         method: ODEMethod::RK4,
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
@@ -200,6 +200,8 @@ where
         n_lu: 0,
         n_jac: 0,
         method: ODEMethod::RK45, // Default to RK45 since this is extrapolation-based
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
 
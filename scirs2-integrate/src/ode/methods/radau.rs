@@ -0,0 +1,867 @@
+//! Radau IIA method for ODE solving
+//!
+//! This module implements the three-stage Radau IIA implicit Runge-Kutta
+//! method of order 5 (Hairer & Wanner's RADAU5). The collocation system is
+//! solved by a simplified Newton iteration that is decoupled into one real and
+//! one complex linear system, the local error is estimated with an embedded
+//! formula of order 3, and every step provides a cubic collocation polynomial
+//! for dense output. The linear systems are solved by LU factorization, or by
+//! Jacobian-free GMRES for very large systems.
+
+use crate::error::{IntegrateError, IntegrateResult};
+use crate::ode::types::{ODEMethod, ODEOptions, ODEResult, PreconditionerFunction};
+use crate::ode::utils::common::{estimate_initial_step, finite_difference_jacobian};
+use crate::ode::utils::jacobian::{
+    colored_finite_difference_jacobian, MatrixFreeNewtonSystem, SparseJacobian, SparseLu,
+};
+use crate::ode::utils::progress::{aborted_message, SolverStats};
+use crate::IntegrateFloat;
+use ndarray::{Array1, Array2, ArrayView1};
+
+/// Collocation nodes `c = [(4 - sqrt 6)/10, (4 + sqrt 6)/10, 1]`
+const C: [f64; 3] = [0.15505102572168222, 0.6449489742783178, 1.0];
+
+/// Coefficients of the embedded error estimate
+const E: [f64; 3] = [-10.048809399827414, 1.382142733160748, -1.0 / 3.0];
+
+/// Real eigenvalue of the inverse Runge-Kutta matrix
+const MU_REAL: f64 = 3.637834252744496;
+
+/// Complex eigenvalue `MU_COMPLEX_RE + i MU_COMPLEX_IM` of the inverse Runge-Kutta matrix
+const MU_COMPLEX_RE: f64 = 2.6810828736277523;
+const MU_COMPLEX_IM: f64 = -3.050430199247411;
+
+/// Transformation to the eigenbasis of the inverse Runge-Kutta matrix, `Z = T W`
+const T: [[f64; 3]; 3] = [
+    [
+        0.09443876248897524,
+        -0.1412552950209542,
+        0.03002919410514742,
+    ],
+    [0.2502131229653333, 0.20412935229379994, -0.3829421127572619],
+    [1.0, 1.0, 0.0],
+];
+
+/// Inverse of [`T`], `W = TI Z`
+const TI: [[f64; 3]; 3] = [
+    [4.178718591551904, 0.32768282076106237, 0.5233764454994495],
+    [
+        -4.178718591551904,
+        -0.32768282076106237,
+        0.47662355450055044,
+    ],
+    [0.5028726349457868, -2.571926949855605, 0.5960392048282249],
+];
+
+/// Maps the stage increments to the coefficients of `x, x^2, x^3` of the
+/// collocation polynomial, `x = (t - t_n) / h`
+const P: [[f64; 3]; 3] = [
+    [10.048809399827414, -25.62959144707664, 15.580782047249224],
+    [-1.382142733160748, 10.296258113743303, -8.914115380582556],
+    [1.0 / 3.0, -8.0 / 3.0, 10.0 / 3.0],
+];
+
+/// Maximum number of simplified Newton iterations per step
+const NEWTON_MAXITER: usize = 6;
+
+/// Bounds of the step size change factor
+const MIN_FACTOR: f64 = 0.2;
+const MAX_FACTOR: f64 = 10.0;
+
+/// Internal (time, value) stage pairs of one accepted step
+pub(crate) type StepStages<F> = Vec<(F, Array1<F>)>;
+
+/// LU factorization with partial pivoting, reused across Newton iterations
+struct LuFactorization<F: IntegrateFloat> {
+    lu: Array2<F>,
+    pivots: Vec<usize>,
+}
+
+impl<F: IntegrateFloat> LuFactorization<F> {
+    /// Factorize a square matrix
+    fn new(mut lu: Array2<F>) -> IntegrateResult<Self> {
+        let n = lu.nrows();
+        let mut pivots = Vec::with_capacity(n);
+
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|&i, &j| lu[[i, k]].abs().partial_cmp(&lu[[j, k]].abs()).unwrap())
+                .unwrap();
+            if lu[[pivot, k]] == F::zero() || !lu[[pivot, k]].is_finite() {
+                return Err(IntegrateError::LinearSolveError(
+                    "Singular Newton iteration matrix".to_string(),
+                ));
+            }
+            pivots.push(pivot);
+
+            if pivot != k {
+                for j in 0..n {
+                    lu.swap([k, j], [pivot, j]);
+                }
+            }
+
+            for i in k + 1..n {
+                let factor = lu[[i, k]] / lu[[k, k]];
+                lu[[i, k]] = factor;
+                for j in k + 1..n {
+                    let update = factor * lu[[k, j]];
+                    lu[[i, j]] -= update;
+                }
+            }
+        }
+
+        Ok(LuFactorization { lu, pivots })
+    }
+
+    /// Solve `A x = b` with the stored factors
+    fn solve(&self, b: &Array1<F>) -> Array1<F> {
+        let n = b.len();
+        let mut x = b.clone();
+
+        for (k, &pivot) in self.pivots.iter().enumerate() {
+            x.swap(k, pivot);
+        }
+        for i in 0..n {
+            for j in 0..i {
+                let update = self.lu[[i, j]] * x[j];
+                x[i] -= update;
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let update = self.lu[[i, j]] * x[j];
+                x[i] -= update;
+            }
+            x[i] /= self.lu[[i, i]];
+        }
+
+        x
+    }
+}
+
+/// Jacobian in dense or sparse storage, as selected by `opts.jac_sparsity`,
+/// or only its linearization point for Jacobian-free Newton-Krylov solves
+enum Jacobian<F: IntegrateFloat> {
+    Dense(Array2<F>),
+    Sparse(SparseJacobian<F>),
+    MatrixFree {
+        t: F,
+        y: Array1<F>,
+        fy: Array1<F>,
+        preconditioner: Option<PreconditionerFunction<F>>,
+    },
+}
+
+/// Factorization of a Newton system in the storage of the Jacobian
+enum Factorization<F: IntegrateFloat> {
+    Dense(LuFactorization<F>),
+    Sparse(SparseLu<F>),
+    MatrixFree(MatrixFreeNewtonSystem<F>),
+}
+
+impl<F: IntegrateFloat> Factorization<F> {
+    fn solve<Func>(&self, f: &Func, b: &Array1<F>, func_evals: &mut usize) -> Array1<F>
+    where
+        Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+    {
+        match self {
+            Factorization::Dense(lu) => lu.solve(b),
+            Factorization::Sparse(lu) => lu.solve(b),
+            Factorization::MatrixFree(system) => system.solve(f, b, func_evals),
+        }
+    }
+}
+
+/// Factorizations of the decoupled Newton systems for step size `h`
+///
+/// The real system is `(MU_REAL / h) I - J`. The complex system
+/// `(MU_COMPLEX / h) I - J` is stored as its real `2n x 2n` equivalent.
+struct NewtonMatrices<F: IntegrateFloat> {
+    real: Factorization<F>,
+    complex: Factorization<F>,
+}
+
+impl<F: IntegrateFloat> NewtonMatrices<F> {
+    fn new(jacobian: &Jacobian<F>, h: F) -> IntegrateResult<Self> {
+        match jacobian {
+            Jacobian::Dense(jacobian) => Self::dense(jacobian, h),
+            Jacobian::Sparse(jacobian) => Self::sparse(jacobian, h),
+            Jacobian::MatrixFree {
+                t,
+                y,
+                fy,
+                preconditioner,
+            } => {
+                let mu_real = F::from_f64(MU_REAL).unwrap() / h;
+                let mu_re = F::from_f64(MU_COMPLEX_RE).unwrap() / h;
+                let mu_im = F::from_f64(MU_COMPLEX_IM).unwrap() / h;
+                let real = MatrixFreeNewtonSystem::new(*t, y.clone(), fy.clone(), mu_real)
+                    .with_preconditioner(preconditioner.clone());
+                let complex =
+                    MatrixFreeNewtonSystem::complex(*t, y.clone(), fy.clone(), mu_re, mu_im)
+                        .with_preconditioner(preconditioner.clone());
+                Ok(NewtonMatrices {
+                    real: Factorization::MatrixFree(real),
+                    complex: Factorization::MatrixFree(complex),
+                })
+            }
+        }
+    }
+
+    fn dense(jacobian: &Array2<F>, h: F) -> IntegrateResult<Self> {
+        let n = jacobian.nrows();
+        let mu_real = F::from_f64(MU_REAL).unwrap() / h;
+        let mu_re = F::from_f64(MU_COMPLEX_RE).unwrap() / h;
+        let mu_im = F::from_f64(MU_COMPLEX_IM).unwrap() / h;
+
+        let mut real = jacobian.mapv(|x| -x);
+        let mut complex = Array2::<F>::zeros((2 * n, 2 * n));
+        for i in 0..n {
+            real[[i, i]] += mu_real;
+            for j in 0..n {
+                complex[[i, j]] = -jacobian[[i, j]];
+                complex[[n + i, n + j]] = -jacobian[[i, j]];
+            }
+            complex[[i, i]] += mu_re;
+            complex[[n + i, n + i]] += mu_re;
+            complex[[i, n + i]] = -mu_im;
+            complex[[n + i, i]] = mu_im;
+        }
+
+        Ok(NewtonMatrices {
+            real: Factorization::Dense(LuFactorization::new(real)?),
+            complex: Factorization::Dense(LuFactorization::new(complex)?),
+        })
+    }
+
+    fn sparse(jacobian: &SparseJacobian<F>, h: F) -> IntegrateResult<Self> {
+        let n = jacobian.pattern().shape().0;
+        let mu_real = F::from_f64(MU_REAL).unwrap() / h;
+        let mu_re = F::from_f64(MU_COMPLEX_RE).unwrap() / h;
+        let mu_im = F::from_f64(MU_COMPLEX_IM).unwrap() / h;
+
+        let mut rows = vec![Vec::new(); 2 * n];
+        for i in 0..n {
+            for (&j, &v) in jacobian.pattern().row(i).iter().zip(jacobian.row_values(i)) {
+                rows[i].push((j, -v));
+                rows[n + i].push((n + j, -v));
+            }
+            rows[i].extend([(i, mu_re), (n + i, -mu_im)]);
+            rows[n + i].extend([(n + i, mu_re), (i, mu_im)]);
+        }
+        // Eliminate the real and imaginary part of each component together,
+        // which keeps the fill-in within the pattern of the Jacobian
+        let order: Vec<usize> = (0..n).flat_map(|i| [i, n + i]).collect();
+
+        Ok(NewtonMatrices {
+            real: Factorization::Sparse(jacobian.factorize_shifted(mu_real, -F::one())?),
+            complex: Factorization::Sparse(SparseLu::new(2 * n, rows, Some(&order))?),
+        })
+    }
+}
+
+/// Collocation polynomial of an accepted step
+struct CollocationPolynomial<F: IntegrateFloat> {
+    t_old: F,
+    h: F,
+    y_old: Array1<F>,
+    /// Coefficients of `x, x^2, x^3`
+    q: [Array1<F>; 3],
+}
+
+impl<F: IntegrateFloat> CollocationPolynomial<F> {
+    fn new(t_old: F, h: F, y_old: Array1<F>, z: &[Array1<F>; 3]) -> Self {
+        let q = std::array::from_fn(|j| combine(&[P[0][j], P[1][j], P[2][j]], z));
+        CollocationPolynomial { t_old, h, y_old, q }
+    }
+
+    fn evaluate(&self, t: F) -> Array1<F> {
+        let x = (t - self.t_old) / self.h;
+        let mut result = self.y_old.clone();
+        let mut power = x;
+        for q in &self.q {
+            result.scaled_add(power, q);
+            power *= x;
+        }
+        result
+    }
+}
+
+/// Linear combination `sum_i coeffs[i] * vectors[i]`
+fn combine<F: IntegrateFloat>(coeffs: &[f64; 3], vectors: &[Array1<F>; 3]) -> Array1<F> {
+    let mut result = Array1::<F>::zeros(vectors[0].len());
+    for (&c, v) in coeffs.iter().zip(vectors) {
+        result.scaled_add(F::from_f64(c).unwrap(), v);
+    }
+    result
+}
+
+/// Root mean square of `v / scale` over all stages
+fn rms_norm<F: IntegrateFloat>(v: &[Array1<F>], scale: &Array1<F>) -> F {
+    let mut sum = F::zero();
+    for vi in v {
+        for (x, s) in vi.iter().zip(scale) {
+            sum += (*x / *s) * (*x / *s);
+        }
+    }
+    (sum / F::from_usize(v.len() * scale.len()).unwrap()).sqrt()
+}
+
+/// Outcome of the simplified Newton iteration
+struct NewtonOutcome<F: IntegrateFloat> {
+    converged: bool,
+    iterations: usize,
+    /// Stage increments `Y_i - y_n`
+    z: [Array1<F>; 3],
+    rate: Option<F>,
+}
+
+/// Solve the collocation system for the stage increments
+#[allow(clippy::too_many_arguments)]
+fn solve_collocation_system<F, Func>(
+    f: &Func,
+    t: F,
+    y: &Array1<F>,
+    h: F,
+    z0: [Array1<F>; 3],
+    scale: &Array1<F>,
+    tol: F,
+    matrices: &NewtonMatrices<F>,
+    func_evals: &mut usize,
+) -> NewtonOutcome<F>
+where
+    F: IntegrateFloat,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+{
+    let n = y.len();
+    let mu_real = F::from_f64(MU_REAL).unwrap() / h;
+    let mu_re = F::from_f64(MU_COMPLEX_RE).unwrap() / h;
+    let mu_im = F::from_f64(MU_COMPLEX_IM).unwrap() / h;
+
+    let mut w = std::array::from_fn(|i| combine(&TI[i], &z0));
+    let mut z = z0;
+    let mut dw_norm_old: Option<F> = None;
+    let mut rate: Option<F> = None;
+
+    for k in 0..NEWTON_MAXITER {
+        let stage_f: [Array1<F>; 3] = std::array::from_fn(|i| {
+            let ti = t + h * F::from_f64(C[i]).unwrap();
+            f(ti, (y + &z[i]).view())
+        });
+        *func_evals += 3;
+        if stage_f.iter().any(|fi| fi.iter().any(|x| !x.is_finite())) {
+            break;
+        }
+
+        // Right-hand sides in the eigenbasis
+        let f_real = combine(&TI[0], &stage_f) - &w[0] * mu_real;
+        let f_re = combine(&TI[1], &stage_f) - &w[1] * mu_re + &w[2] * mu_im;
+        let f_im = combine(&TI[2], &stage_f) - &w[1] * mu_im - &w[2] * mu_re;
+
+        let dw_real = matrices.real.solve(f, &f_real, func_evals);
+        let mut rhs = Array1::<F>::zeros(2 * n);
+        for i in 0..n {
+            rhs[i] = f_re[i];
+            rhs[n + i] = f_im[i];
+        }
+        let dw_complex = matrices.complex.solve(f, &rhs, func_evals);
+        let dw = [
+            dw_real,
+            dw_complex.slice(ndarray::s![..n]).to_owned(),
+            dw_complex.slice(ndarray::s![n..]).to_owned(),
+        ];
+
+        let dw_norm = rms_norm(&dw, scale);
+        if let Some(old) = dw_norm_old {
+            rate = Some(dw_norm / old);
+        }
+        if let Some(r) = rate {
+            let remaining = r.powi((NEWTON_MAXITER - k) as i32) / (F::one() - r) * dw_norm;
+            if r >= F::one() || remaining > tol {
+                break;
+            }
+        }
+
+        for (wi, dwi) in w.iter_mut().zip(&dw) {
+            *wi += dwi;
+        }
+        z = std::array::from_fn(|i| combine(&T[i], &w));
+
+        let converged =
+            dw_norm == F::zero() || rate.is_some_and(|r| r / (F::one() - r) * dw_norm < tol);
+        if converged {
+            return NewtonOutcome {
+                converged: true,
+                iterations: k + 1,
+                z,
+                rate,
+            };
+        }
+        dw_norm_old = Some(dw_norm);
+    }
+
+    NewtonOutcome {
+        converged: false,
+        iterations: NEWTON_MAXITER,
+        z,
+        rate,
+    }
+}
+
+/// Step size factor from the error estimate, with Gustafsson's predictive control
+fn predict_factor<F: IntegrateFloat>(
+    h: F,
+    h_old: Option<F>,
+    error_norm: F,
+    error_norm_old: Option<F>,
+) -> F {
+    let quarter = F::from_f64(0.25).unwrap();
+    let multiplier = match (h_old, error_norm_old) {
+        (Some(h_old), Some(error_old)) if error_norm > F::zero() => {
+            h / h_old * (error_old / error_norm).powf(quarter)
+        }
+        _ => F::one(),
+    };
+    multiplier.min(F::one()) * error_norm.powf(-quarter)
+}
+
+/// Solve ODE using the Radau IIA method
+///
+/// Radau IIA is an implicit Runge-Kutta method with high stability properties,
+/// making it suitable for stiff problems. The three-stage method used here is
+/// L-stable and of order 5, which makes it the method of choice for stiff
+/// problems that need high accuracy.
+///
+/// The Jacobian is taken from `opts.jac` when given, and approximated by finite
+/// differences otherwise. With `opts.jac_sparsity` set, the finite differences
+/// use one evaluation per column color and the Newton systems are solved with
+/// a sparse LU factorization. With an iterative `opts.linear_solver`, the
+/// Newton systems are solved by GMRES on Jacobian-vector products instead,
+/// without forming the Jacobian. The Jacobian is only recomputed when the Newton iteration
+/// converges slowly, and the step size is changed only by significant factors
+/// so that the factorized iteration matrices can be reused. The local error is
+/// estimated with an embedded formula of order 3.
+///
+/// # Arguments
+///
+/// * `f` - ODE function dy/dt = f(t, y)
+/// * `t_span` - Time span [t_start, t_end]
+/// * `y0` - Initial condition
+/// * `opts` - Solver options
+///
+/// # Returns
+///
+/// The solution as an ODEResult or an error
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2, ArrayView1};
+/// use scirs2_integrate::ode::{solve_ivp, ODEMethod, ODEOptions};
+/// use std::sync::Arc;
+///
+/// // Robertson chemical kinetics, a classic stiff problem
+/// let f = |_t: f64, y: ArrayView1<f64>| {
+///     array![
+///         -0.04 * y[0] + 1e4 * y[1] * y[2],
+///         0.04 * y[0] - 1e4 * y[1] * y[2] - 3e7 * y[1] * y[1],
+///         3e7 * y[1] * y[1],
+///     ]
+/// };
+/// let jac = |_t: f64, y: ArrayView1<f64>| {
+///     Array2::from_shape_vec(
+///         (3, 3),
+///         vec![
+///             -0.04, 1e4 * y[2], 1e4 * y[1],
+///             0.04, -1e4 * y[2] - 6e7 * y[1], -1e4 * y[1],
+///             0.0, 6e7 * y[1], 0.0,
+///         ],
+///     )
+///     .unwrap()
+/// };
+///
+/// let result = solve_ivp(
+///     f,
+///     [0.0, 40.0],
+///     array![1.0, 0.0, 0.0],
+///     Some(ODEOptions {
+///         method: ODEMethod::Radau,
+///         rtol: 1e-6,
+///         atol: 1e-10,
+///         jac: Some(Arc::new(jac)),
+///         ..Default::default()
+///     }),
+/// )
+/// .unwrap();
+///
+/// let y = result.y.last().unwrap();
+/// assert!((y.sum() - 1.0).abs() < 1e-8);
+/// assert!((y[0] - 0.7158).abs() < 1e-3);
+/// ```
+pub fn radau_method<F, Func>(
+    f: Func,
+    t_span: [F; 2],
+    y0: Array1<F>,
+    opts: ODEOptions<F>,
+) -> IntegrateResult<ODEResult<F>>
+where
+    F: IntegrateFloat,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+{
+    radau_integrate(f, t_span, y0, opts, None)
+}
+
+/// Solve ODE using the Radau IIA method, keeping the internal stage values
+///
+/// Same as [`radau_method`], but also returns the two interior stage values
+/// `(t + c1 h, Y1)` and `(t + c2 h, Y2)` of every accepted step. Together with
+/// the step end points they determine the collocation polynomial used for
+/// dense output.
+pub(crate) fn radau_method_with_stages<F, Func>(
+    f: Func,
+    t_span: [F; 2],
+    y0: Array1<F>,
+    opts: ODEOptions<F>,
+) -> IntegrateResult<(ODEResult<F>, Vec<StepStages<F>>)>
+where
+    F: IntegrateFloat,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+{
+    let mut stages = Vec::new();
+    let result = radau_integrate(f, t_span, y0, opts, Some(&mut stages))?;
+    Ok((result, stages))
+}
+
+/// Radau IIA integration loop, optionally recording interior stage values
+fn radau_integrate<F, Func>(
+    f: Func,
+    t_span: [F; 2],
+    y0: Array1<F>,
+    opts: ODEOptions<F>,
+    mut stages: Option<&mut Vec<StepStages<F>>>,
+) -> IntegrateResult<ODEResult<F>>
+where
+    F: IntegrateFloat,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+{
+    // Initialize
+    let [t_start, t_end] = t_span;
+    let n_dim = y0.len();
+
+    let mut t = t_start;
+    let mut y = y0;
+    let mut fy = f(t, y.view());
+    let mut func_evals = 1;
+
+    // Determine minimum and maximum step sizes
+    let min_step = opts.min_step.unwrap_or_else(|| {
+        let span = t_end - t_start;
+        span * F::from_f64(1e-10).unwrap() // Minimal step size
+    });
+
+    let max_step = opts.max_step.unwrap_or_else(|| {
+        t_end - t_start // Maximum step can be the whole interval
+    });
+
+    // Determine initial step size if not provided
+    let mut h_abs = match opts.h0 {
+        Some(h0) => h0,
+        None => {
+            func_evals += 1;
+            estimate_initial_step(&f, t, &y, &fy, opts.rtol, t_end)
+        }
+    };
+    let mut h_abs_old: Option<F> = None;
+    let mut error_norm_old: Option<F> = None;
+
+    // Statistics
+    let mut step_count = 0;
+    let mut accepted_steps = 0;
+    let mut rejected_steps = 0;
+    let mut n_lu = 0;
+    let mut n_jac = 0;
+
+    // Column coloring of a sparse Jacobian
+    let coloring = match &opts.jac_sparsity {
+        Some(pattern) if pattern.shape() != (n_dim, n_dim) => {
+            return Err(IntegrateError::DimensionMismatch(format!(
+                "Jacobian sparsity pattern has shape {:?}, expected ({}, {})",
+                pattern.shape(),
+                n_dim,
+                n_dim
+            )));
+        }
+        Some(pattern) => pattern.column_coloring(),
+        None => Vec::new(),
+    };
+    let n_colors = coloring.iter().max().map_or(0, |&c| c + 1);
+    let matrix_free = opts.linear_solver.is_iterative(n_dim);
+
+    // Jacobian, analytical if supplied
+    let compute_jacobian = |t: F,
+                            y: &Array1<F>,
+                            fy: &Array1<F>,
+                            func_evals: &mut usize,
+                            n_jac: &mut usize|
+     -> IntegrateResult<_> {
+        if matrix_free {
+            return Ok(Jacobian::MatrixFree {
+                t,
+                y: y.clone(),
+                fy: fy.clone(),
+                preconditioner: opts.preconditioner.clone(),
+            });
+        }
+        *n_jac += 1;
+        let jacobian = match (&opts.jac, &opts.jac_sparsity) {
+            (Some(jac), _) => jac(t, y.view()),
+            (None, Some(pattern)) => {
+                *func_evals += n_colors;
+                return Ok(Jacobian::Sparse(colored_finite_difference_jacobian(
+                    &f, t, y, fy, pattern, &coloring,
+                )?));
+            }
+            (None, None) => {
+                *func_evals += n_dim;
+                finite_difference_jacobian(&f, t, y, fy, F::one())
+            }
+        };
+        if jacobian.dim() != (n_dim, n_dim) {
+            return Err(IntegrateError::DimensionMismatch(format!(
+                "Jacobian has shape {:?}, expected ({}, {})",
+                jacobian.dim(),
+                n_dim,
+                n_dim
+            )));
+        }
+        Ok(match &opts.jac_sparsity {
+            Some(pattern) => Jacobian::Sparse(SparseJacobian::from_dense(pattern, &jacobian)),
+            None => Jacobian::Dense(jacobian),
+        })
+    };
+    let mut jacobian = compute_jacobian(t, &y, &fy, &mut func_evals, &mut n_jac)?;
+    let mut current_jac = true;
+    let mut matrices: Option<NewtonMatrices<F>> = None;
+    let mut polynomial: Option<CollocationPolynomial<F>> = None;
+
+    let newton_tol = (F::from_f64(10.0).unwrap() * F::epsilon() / opts.rtol)
+        .max(F::from_f64(0.03).unwrap().min(opts.rtol.sqrt()));
+    let newton_maxiter = F::from_usize(NEWTON_MAXITER).unwrap();
+    let two = F::from_f64(2.0).unwrap();
+
+    // Result storage
+    let mut t_values = vec![t];
+    let mut y_values = vec![y.clone()];
+
+    let mut aborted = false;
+
+    // Main integration loop
+    while t < t_end && step_count < opts.max_steps {
+        if h_abs > max_step {
+            h_abs = max_step;
+            h_abs_old = None;
+            error_norm_old = None;
+        } else if h_abs < min_step {
+            h_abs = min_step;
+            h_abs_old = None;
+            error_norm_old = None;
+        }
+
+        // Attempt steps until one is accepted
+        let mut rejected = false;
+        let (h, y_new, z, error_norm, outcome_iterations, outcome_rate, safety) = loop {
+            if h_abs < min_step {
+                return Err(IntegrateError::StepSizeTooSmall(format!(
+                    "Step size {h_abs:e} below the minimum {min_step:e} at t = {t}"
+                )));
+            }
+            if step_count >= opts.max_steps {
+                break (F::zero(), y.clone(), None, F::zero(), 0, None, F::zero());
+            }
+            step_count += 1;
+
+            // Adjust step size for the last step if needed
+            let h = if t + h_abs > t_end { t_end - t } else { h_abs };
+            h_abs = h;
+
+            // Start the iteration from the previous collocation polynomial
+            let z0: [Array1<F>; 3] = match &polynomial {
+                Some(p) => {
+                    std::array::from_fn(|i| p.evaluate(t + h * F::from_f64(C[i]).unwrap()) - &y)
+                }
+                None => std::array::from_fn(|_| Array1::<F>::zeros(n_dim)),
+            };
+            let scale = y.mapv(|yi| opts.atol + opts.rtol * yi.abs());
+
+            let outcome = loop {
+                if matrices.is_none() {
+                    matrices = Some(NewtonMatrices::new(&jacobian, h)?);
+                    if !matrix_free {
+                        n_lu += 2;
+                    }
+                }
+                let outcome = solve_collocation_system(
+                    &f,
+                    t,
+                    &y,
+                    h,
+                    z0.clone(),
+                    &scale,
+                    newton_tol,
+                    matrices.as_ref().unwrap(),
+                    &mut func_evals,
+                );
+                if outcome.converged || current_jac {
+                    break outcome;
+                }
+                jacobian = compute_jacobian(t, &y, &fy, &mut func_evals, &mut n_jac)?;
+                current_jac = true;
+                matrices = None;
+            };
+
+            if !outcome.converged {
+                h_abs *= F::from_f64(0.5).unwrap();
+                matrices = None;
+                rejected_steps += 1;
+                continue;
+            }
+
+            let y_new = &y + &outcome.z[2];
+            let ze = combine(&E, &outcome.z) / h;
+            let real = &matrices.as_ref().unwrap().real;
+            let mut error = real.solve(&f, &(&fy + &ze), &mut func_evals);
+            let mut scale = Array1::<F>::zeros(n_dim);
+            for i in 0..n_dim {
+                scale[i] = opts.atol + opts.rtol * y[i].abs().max(y_new[i].abs());
+            }
+            let mut error_norm = rms_norm(std::slice::from_ref(&error), &scale);
+            let safety = F::from_f64(0.9).unwrap() * (two * newton_maxiter + F::one())
+                / (two * newton_maxiter + F::from_usize(outcome.iterations).unwrap());
+
+            // Filter the estimate through f once more after a rejection
+            if rejected && error_norm > F::one() {
+                func_evals += 1;
+                let rhs = f(t, (&y + &error).view()) + &ze;
+                error = real.solve(&f, &rhs, &mut func_evals);
+                error_norm = rms_norm(std::slice::from_ref(&error), &scale);
+            }
+
+            if error_norm > F::one() {
+                let factor = predict_factor(h_abs, h_abs_old, error_norm, error_norm_old);
+                h_abs *= (safety * factor).max(F::from_f64(MIN_FACTOR).unwrap());
+                matrices = None;
+                rejected = true;
+                rejected_steps += 1;
+                continue;
+            }
+
+            break (
+                h,
+                y_new,
+                Some(outcome.z),
+                error_norm,
+                outcome.iterations,
+                outcome.rate,
+                safety,
+            );
+        };
+        let Some(z) = z else {
+            // Maximum number of steps reached while retrying
+            break;
+        };
+
+        // Update the Jacobian only when the iteration converged slowly
+        let recompute_jac =
+            outcome_iterations > 2 && outcome_rate.is_some_and(|r| r > F::from_f64(1e-3).unwrap());
+        let mut factor = predict_factor(h_abs, h_abs_old, error_norm, error_norm_old);
+        factor = (safety * factor).min(F::from_f64(MAX_FACTOR).unwrap());
+        if !recompute_jac && factor < F::from_f64(1.2).unwrap() {
+            // Keep the step size and the factorized matrices
+            factor = F::one();
+        } else {
+            matrices = None;
+        }
+
+        let t_new = if t + h >= t_end { t_end } else { t + h };
+        // Restore the invariants before the derivative at the new point is taken
+        let y_new = match &opts.projection {
+            Some(projection) => projection.project(&y_new)?,
+            None => y_new,
+        };
+        let f_new = f(t_new, y_new.view());
+        func_evals += 1;
+
+        if recompute_jac {
+            jacobian = compute_jacobian(t_new, &y_new, &f_new, &mut func_evals, &mut n_jac)?;
+            current_jac = true;
+        } else {
+            current_jac = false;
+        }
+
+        h_abs_old = Some(h_abs);
+        error_norm_old = Some(error_norm);
+        h_abs *= factor;
+
+        if let Some(stages) = stages.as_deref_mut() {
+            stages.push(vec![
+                (t + h * F::from_f64(C[0]).unwrap(), &y + &z[0]),
+                (t + h * F::from_f64(C[1]).unwrap(), &y + &z[1]),
+            ]);
+        }
+        polynomial = Some(CollocationPolynomial::new(t, h, y.clone(), &z));
+
+        // Update state
+        t = t_new;
+        y = y_new;
+        fy = f_new;
+        accepted_steps += 1;
+
+        // Store results
+        t_values.push(t);
+        y_values.push(y.clone());
+
+        if let Some(progress) = &opts.progress {
+            let stats = SolverStats {
+                n_eval: func_evals,
+                n_steps: step_count,
+                n_accepted: accepted_steps,
+                n_rejected: rejected_steps,
+                n_lu,
+                n_jac,
+            };
+            if progress.notify(t, y.view(), h, stats) {
+                aborted = true;
+                break;
+            }
+        }
+    }
+
+    let success = t >= t_end;
+    let message = if aborted && !success {
+        Some(aborted_message(t))
+    } else if !success {
+        Some(format!(
+            "Maximum number of steps ({}) reached",
+            opts.max_steps
+        ))
+    } else {
+        None
+    };
+
+    // Return the solution
+    Ok(ODEResult {
+        t: t_values,
+        y: y_values,
+        success,
+        message,
+        n_eval: func_evals,
+        n_steps: step_count,
+        n_accepted: accepted_steps,
+        n_rejected: rejected_steps,
+        n_lu,
+        n_jac,
+        method: ODEMethod::Radau,
+        step_methods: Vec::new(),
+        stiffness: None,
+    })
+}
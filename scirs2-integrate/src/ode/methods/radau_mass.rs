@@ -11,6 +11,7 @@ use crate::ode::utils::interpolation::ContinuousOutputMethod;
 use crate::ode::utils::jacobian;
 use crate::ode::utils::linear_solvers::solve_linear_system;
 use crate::ode::utils::mass_matrix;
+use crate::ode::utils::progress::{aborted_message, SolverStats};
 use crate::IntegrateFloat;
 use ndarray::{Array1, Array2, ArrayView1};
 
@@ -125,6 +126,7 @@ where
     let mut rejected_steps = 0;
     let mut n_lu = 0;
     let mut n_jac = 0;
+    let mut aborted = false;
 
     // Error control
     let rtol = opts.rtol;
@@ -510,7 +512,10 @@ where
         if error_norm <= F::one() {
             // Accept the step
             t += h;
-            y = y_new;
+            y = match &opts.projection {
+                Some(projection) => projection.project(&y_new)?,
+                None => y_new,
+            };
 
             // Store the result
             t_values.push(t);
@@ -526,6 +531,21 @@ where
 
             accepted_steps += 1;
 
+            if let Some(progress) = &opts.progress {
+                let stats = SolverStats {
+                    n_eval: func_evals,
+                    n_steps: step_count,
+                    n_accepted: accepted_steps,
+                    n_rejected: rejected_steps,
+                    n_lu,
+                    n_jac,
+                };
+                if progress.notify(t, y.view(), h, stats) {
+                    aborted = true;
+                    break;
+                }
+            }
+
             // Increase step size for next step if error is small
             if error_norm < F::from_f64(0.1).unwrap() {
                 h *= F::from_f64(2.0).unwrap();
@@ -545,6 +565,8 @@ where
     let success = t >= t_end;
     let message = if success {
         Some(format!("Integration successful, reached t = {:?}", t))
+    } else if aborted {
+        Some(aborted_message(t))
     } else {
         Some(format!("Integration incomplete, stopped at t = {:?}", t))
     };
@@ -575,5 +597,7 @@ where
         n_lu,
         n_jac,
         method: ODEMethod::Radau,
+        step_methods: Vec::new(),
+        stiffness: None,
     })
 }
@@ -9,6 +9,11 @@
 //! - Dense output for continuous solution approximation
 //! - Event detection and handling for detecting specific conditions
 //! - Support for different error control schemes
+//! - Exponential integrators for semilinear systems with a stiff linear part
+//! - Projection onto conserved quantities such as mass or energy
+//! - Progress callbacks with solver statistics and early abort
+//! - Batched integration of ensembles of small systems, optionally on a GPU
+//! - Lie and Strang operator splitting with a separate solver per sub-operator
 
 // Public types module
 pub mod types;
@@ -16,15 +21,22 @@ pub mod types;
 // Public modules
 pub mod chemical;
 pub mod chemical_equilibrium;
+pub mod ensemble;
 pub mod enzyme_kinetics;
+pub mod exponential;
 pub mod mechanical;
 pub mod methods;
 pub mod multirate;
+pub mod sensitivity;
 pub mod solver;
+pub mod splitting;
 pub mod utils;
 
 // Re-export core types
-pub use self::types::{MassMatrix, MassMatrixType, ODEMethod, ODEOptions, ODEResult};
+pub use self::types::{
+    MassMatrix, MassMatrixType, ODEMethod, ODEOptions, ODEResult, PreconditionerFunction,
+    StepMethod,
+};
 
 // Re-export chemical kinetics types
 pub use self::chemical::{
@@ -51,7 +63,25 @@ pub use self::mechanical::{
 };
 
 // Re-export solver functions
-pub use self::solver::{solve_ivp, solve_ivp_with_events};
+pub use self::solver::{solve_ivp, solve_ivp_dense, solve_ivp_with_events};
+
+// Re-export continuous solution types
+pub use self::utils::dense_output::{ODESolution, StepRecord};
+
+// Re-export the Jacobian sparsity pattern used by `ODEOptions::jac_sparsity`
+pub use self::utils::jacobian::SparsityPattern;
+
+// Re-export the linear solver selection used by `ODEOptions::linear_solver`
+pub use self::utils::linear_solvers::LinearSolverType;
+
+// Re-export the invariant projection used by `ODEOptions::projection`
+pub use self::utils::projection::InvariantProjection;
+
+// Re-export progress reporting used by `ODEOptions::progress`
+pub use self::utils::progress::{ProgressAction, ProgressCallback, ProgressInfo, SolverStats};
+
+// Re-export stiffness diagnostics reported in `ODEResult::stiffness`
+pub use self::utils::stiffness::{estimate_dominant_eigenvalue, StiffnessDiagnostics};
 
 // Re-export event detection types
 pub use self::utils::events::{
@@ -59,5 +89,26 @@ pub use self::utils::events::{
     ODEResultWithEvents,
 };
 
+// Re-export sensitivity analysis types
+pub use self::sensitivity::{
+    adjoint_gradient, forward_sensitivity, AdjointResult, ForwardSensitivityResult, ODEFunctional,
+    SensitivityOptions,
+};
+
+// Re-export batched ensemble integration
+#[cfg(feature = "gpu")]
+pub use self::ensemble::solve_ensemble_gpu;
+pub use self::ensemble::{
+    solve_ensemble, EnsembleMethod, EnsembleOptions, EnsembleProblem, EnsembleResult,
+};
+
+// Re-export operator splitting
+pub use self::splitting::{solve_splitting, SplittingOptions, SplittingProblem, SplittingScheme};
+
+// Re-export exponential integrators
+pub use self::exponential::{
+    phi_combination, solve_exponential, ExponentialMethod, ExponentialOptions,
+};
+
 // Re-export multirate types
 pub use self::multirate::{MultirateMethod, MultirateOptions, MultirateSolver, MultirateSystem};
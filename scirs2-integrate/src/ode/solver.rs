@@ -6,15 +6,16 @@ use crate::common::IntegrateFloat;
 use crate::error::{IntegrateError, IntegrateResult};
 use crate::ode::methods::{
     bdf_method, dop853_method, enhanced_bdf_method, enhanced_lsoda_method, euler_method,
-    lsoda_method, radau_method, radau_method_with_mass, rk23_method, rk45_method, rk4_method,
+    lsoda_method, radau_method, radau_method_with_mass, radau_method_with_stages, rk23_method,
+    rk45_method, rk4_method,
 };
 use crate::ode::types::{MassMatrix, MassMatrixType, ODEMethod, ODEOptions, ODEResult};
-use crate::ode::utils::dense_output::DenseSolution;
+use crate::ode::utils::dense_output::ODESolution;
 use crate::ode::utils::events::{
     EventAction, EventHandler, ODEOptionsWithEvents, ODEResultWithEvents,
 };
-use crate::ode::utils::interpolation::ContinuousOutputMethod;
 use crate::ode::utils::mass_matrix;
+use crate::ode::utils::stiffness::StiffnessDiagnostics;
 use ndarray::{Array1, ArrayView1};
 
 /// Solve an initial value problem (IVP) for a system of ODEs.
@@ -102,7 +103,26 @@ where
     Func: Fn(F, ArrayView1<F>) -> Array1<F> + Clone,
 {
     // Use default options if none provided
-    let opts = options.unwrap_or_default();
+    let mut opts = options.unwrap_or_default();
+
+    // Fix the conserved values from the initial state before any dispatch
+    if let Some(projection) = &mut opts.projection {
+        projection.initialize(y0.view())?;
+    }
+
+    // Report the solution at the requested times from the dense output
+    if let Some(t_eval) = opts.t_eval.clone() {
+        let (mut result, solution) = solve_ivp_dense(f, t_span, y0, Some(opts))?;
+        // After a failed integration only the covered part can be reported
+        let t_reached = solution.t_span()[1];
+        let t_eval: Vec<F> = t_eval
+            .into_iter()
+            .filter(|&t| result.success || t <= t_reached)
+            .collect();
+        result.y = solution.evaluate_many(&t_eval)?;
+        result.t = t_eval;
+        return Ok(result);
+    }
 
     // Handle mass matrix if provided
     if let Some(mass) = &opts.mass_matrix {
@@ -118,12 +138,22 @@ where
 
     // Dispatch to the appropriate solver based on the method
     match opts.method {
-        ODEMethod::Euler => euler_method(f, t_span, y0, h0, opts),
-        ODEMethod::RK4 => rk4_method(f, t_span, y0, h0, opts),
-        ODEMethod::RK45 => rk45_method(f, t_span, y0, opts),
-        ODEMethod::RK23 => rk23_method(f, t_span, y0, opts),
+        ODEMethod::Euler => {
+            euler_method(f.clone(), t_span, y0, h0, opts).map(|r| with_stiffness_diagnostics(&f, r))
+        }
+        ODEMethod::RK4 => {
+            rk4_method(f.clone(), t_span, y0, h0, opts).map(|r| with_stiffness_diagnostics(&f, r))
+        }
+        ODEMethod::RK45 => {
+            rk45_method(f.clone(), t_span, y0, opts).map(|r| with_stiffness_diagnostics(&f, r))
+        }
+        ODEMethod::RK23 => {
+            rk23_method(f.clone(), t_span, y0, opts).map(|r| with_stiffness_diagnostics(&f, r))
+        }
         ODEMethod::Bdf => bdf_method(f, t_span, y0, opts),
-        ODEMethod::DOP853 => dop853_method(f, t_span, y0, opts),
+        ODEMethod::DOP853 => {
+            dop853_method(f.clone(), t_span, y0, opts).map(|r| with_stiffness_diagnostics(&f, r))
+        }
         ODEMethod::Radau => radau_method(f, t_span, y0, opts),
         ODEMethod::LSODA => {
             // For LSODA, use a slightly modified options struct with better defaults
@@ -198,6 +228,130 @@ where
     }
 }
 
+/// Attach stiffness diagnostics to the result of an explicit method
+///
+/// Explicit methods on stiff problems do not fail but crawl along at step
+/// sizes dictated by stability, so a hint to switch to an implicit method is
+/// appended to the message when the diagnostics show this.
+fn with_stiffness_diagnostics<F, Func>(f: &Func, mut result: ODEResult<F>) -> ODEResult<F>
+where
+    F: IntegrateFloat,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+{
+    let Some(diagnostics) = StiffnessDiagnostics::from_solution(f, &result.t, &result.y) else {
+        return result;
+    };
+    if let Some(hint) = diagnostics.explicit_method_hint(result.n_accepted, result.success) {
+        result.message = Some(match result.message.take() {
+            Some(message) => format!("{}. {}", message, hint),
+            None => hint,
+        });
+    }
+    result.stiffness = Some(diagnostics);
+    result
+}
+
+/// Solve an initial value problem and return a continuous solution
+///
+/// Runs [`solve_ivp`] and builds an [`ODESolution`] from the accepted steps,
+/// which can be evaluated at any time in the integration interval without
+/// restricting the step sizes taken by the solver. The interpolant depends on
+/// the method:
+///
+/// - Explicit Runge-Kutta methods (Euler, RK4, RK23, RK45, DOP853): cubic
+///   Hermite interpolation from the solution and `f(t, y)` at the step ends
+/// - Radau: the collocation polynomial through the step ends and the internal
+///   stage values
+/// - BDF, LSODA and problems with a mass matrix: the polynomial through the
+///   current and previous solution points, of degree `max_order` (default 3)
+///
+/// `options.t_eval` is ignored; the returned [`ODEResult`] holds the steps.
+///
+/// # Arguments
+///
+/// * `f` - Function that computes the derivative dy/dt = f(t, y)
+/// * `t_span` - The interval of integration [t0, tf]
+/// * `y0` - Initial state
+/// * `options` - Solver options (optional)
+///
+/// # Returns
+///
+/// The discrete solution at the accepted steps and the continuous solution
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, ArrayView1};
+/// use scirs2_integrate::ode::{solve_ivp_dense, ODEOptions};
+///
+/// // Harmonic oscillator y'' = -y
+/// let f = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+/// let opts = ODEOptions {
+///     rtol: 1e-8,
+///     atol: 1e-10,
+///     ..Default::default()
+/// };
+/// let (_result, sol) = solve_ivp_dense(f, [0.0, 3.0], array![1.0, 0.0], Some(opts)).unwrap();
+///
+/// // Evaluate anywhere in [0, 3], independently of the steps taken
+/// let y = sol.evaluate(1.234).unwrap();
+/// assert!((y[0] - 1.234f64.cos()).abs() < 1e-5);
+/// ```
+pub fn solve_ivp_dense<F, Func>(
+    f: Func,
+    t_span: [F; 2],
+    y0: Array1<F>,
+    options: Option<ODEOptions<F>>,
+) -> IntegrateResult<(ODEResult<F>, ODESolution<F>)>
+where
+    F: IntegrateFloat + std::iter::Sum,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F> + Clone,
+{
+    let opts = ODEOptions {
+        t_eval: None,
+        ..options.unwrap_or_default()
+    };
+    let has_mass = opts
+        .mass_matrix
+        .as_ref()
+        .is_some_and(|m| m.matrix_type != MassMatrixType::Identity);
+
+    match opts.method {
+        ODEMethod::Radau if !has_mass => {
+            let (result, stages) = radau_method_with_stages(f, t_span, y0, opts)?;
+            let solution = ODESolution::collocation(result.t.clone(), result.y.clone(), stages)?
+                .with_step_methods(&result.step_methods);
+            Ok((result, solution))
+        }
+        ODEMethod::Euler
+        | ODEMethod::RK4
+        | ODEMethod::RK23
+        | ODEMethod::RK45
+        | ODEMethod::DOP853
+            if !has_mass =>
+        {
+            let mut result = solve_ivp(f.clone(), t_span, y0, Some(opts))?;
+            let dydt: Vec<Array1<F>> = result
+                .t
+                .iter()
+                .zip(&result.y)
+                .map(|(&t, y)| f(t, y.view()))
+                .collect();
+            result.n_eval += dydt.len();
+            let solution = ODESolution::hermite(result.t.clone(), result.y.clone(), dydt)?
+                .with_step_methods(&result.step_methods);
+            Ok((result, solution))
+        }
+        _ => {
+            let order = opts.max_order.unwrap_or(3).clamp(1, 5);
+            let result = solve_ivp(f, t_span, y0, Some(opts))?;
+            let solution = ODESolution::backward(result.t.clone(), result.y.clone(), order)?
+                .with_step_methods(&result.step_methods);
+            Ok((result, solution))
+        }
+    }
+}
+
 /// Internal implementation of solve_ivp with mass matrix
 ///
 /// This function handles the case where a mass matrix is provided.
@@ -366,6 +520,13 @@ where
 /// are met during integration (e.g., when a function crosses zero) and taking appropriate
 /// actions (such as stopping the integration or recording the event).
 ///
+/// Sign changes of each g(t, y) are detected between accepted steps and located
+/// within the step by root finding on the continuous solution of
+/// [`solve_ivp_dense`]. When a terminal event ([`EventAction::Stop`]) occurs, the
+/// solution ends at the event time and state; to continue past an impact or a
+/// switch, call this function again from the event state with the state modified
+/// as required.
+///
 /// # Arguments
 ///
 /// * `f` - Function that computes the derivative dy/dt = f(t, y)
@@ -447,29 +608,15 @@ pub fn solve_ivp_with_events<F, Func, EventFunc>(
 ) -> IntegrateResult<ODEResultWithEvents<F>>
 where
     F: IntegrateFloat,
-    Func: Fn(F, ArrayView1<F>) -> Array1<F> + Clone + 'static,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F> + Clone,
     EventFunc: Fn(F, ArrayView1<F>) -> F,
 {
     // Extract base options and ensure dense output is enabled for event detection
     let mut base_options = options.base_options.clone();
     base_options.dense_output = true;
 
-    // First, solve the IVP using the standard solver
-    let base_result = solve_ivp(f.clone(), t_span, y0.clone(), Some(base_options))?;
-
-    // Create the dense solution for event detection
-    let dense_output = if !base_result.t.is_empty() {
-        let dense = DenseSolution::new(
-            base_result.t.clone(),
-            base_result.y.clone(),
-            None, // No derivatives provided
-            Some(ContinuousOutputMethod::CubicHermite),
-            Some(Box::new(f.clone())), // Provide function for derivative calculation
-        );
-        Some(dense)
-    } else {
-        None
-    };
+    // Solve the IVP, keeping the continuous solution for locating events
+    let (base_result, dense) = solve_ivp_dense(f, t_span, y0, Some(base_options))?;
 
     // Create event handler
     let mut event_handler = EventHandler::new(options.event_specs.clone());
@@ -485,7 +632,7 @@ where
         let y = &base_result.y[i];
 
         // Check for events between the previous and current step
-        let action = event_handler.check_events(t, y, dense_output.as_ref(), &event_funcs)?;
+        let action = event_handler.check_events(t, y, Some(&dense), &event_funcs)?;
 
         if action == EventAction::Stop {
             event_termination = true;
@@ -493,65 +640,43 @@ where
         }
     }
 
-    // If an event caused termination, truncate the results
+    // If an event caused termination, truncate the results at the event
     let final_result = if event_termination {
-        // Get the event that caused termination
+        // The terminal event is the last one recorded
         let last_event = event_handler.record.events.last().ok_or_else(|| {
             IntegrateError::ValueError("No event found for termination".to_string())
         })?;
 
-        // Find the insertion point for the event time
-        let mut event_index = base_result.t.len();
-        let mut exact_match = false;
-
-        for (i, &t) in base_result.t.iter().enumerate() {
-            if (t - last_event.time).abs() < F::from_f64(1e-10).unwrap() {
-                event_index = i + 1;
-                exact_match = true;
-                break;
-            } else if t > last_event.time {
-                event_index = i;
-                break;
-            }
-        }
-
-        // Create truncated results
+        let event_index = base_result.t.partition_point(|&t| t < last_event.time);
         let mut truncated_t = base_result.t[..event_index].to_vec();
         let mut truncated_y = base_result.y[..event_index].to_vec();
+        truncated_t.push(last_event.time);
+        truncated_y.push(last_event.state.clone());
+        // Keep the formula of the step the event was located in
+        let mut step_methods = base_result.step_methods.clone();
+        step_methods.truncate(event_index);
 
-        // If we don't have an exact match, add the event time and state
-        if !exact_match && event_index > 0 {
-            // Only add if the event time is different from the last time point
-            let last_t = truncated_t.last().copied().unwrap_or(F::zero());
-            if (last_t - last_event.time).abs() > F::from_f64(1e-10).unwrap() {
-                truncated_t.push(last_event.time);
-                truncated_y.push(last_event.state.clone());
-            }
-        }
-
-        // Create the truncated result
         ODEResult {
             t: truncated_t,
             y: truncated_y,
-            message: base_result.message,
-            success: base_result.success,
-            n_steps: base_result.n_steps,
-            n_eval: base_result.n_eval,
-            n_accepted: base_result.n_accepted,
-            n_rejected: base_result.n_rejected,
-            n_lu: base_result.n_lu,
-            n_jac: base_result.n_jac,
-            method: base_result.method,
+            step_methods,
+            message: Some(format!(
+                "Integration terminated by event '{}' at t = {}",
+                last_event.id, last_event.time
+            )),
+            success: true,
+            ..base_result
         }
     } else {
         base_result
     };
 
     // Create the result with events
+    let dense = dense.with_events(event_handler.record.clone());
     let result_with_events = ODEResultWithEvents::new(
         final_result,
         event_handler.record,
-        dense_output,
+        Some(dense),
         event_termination,
     );
 
@@ -14,6 +14,13 @@ pub type TimeFunction<F> = Arc<dyn Fn(F) -> Array2<F> + Send + Sync>;
 /// Type alias for state-dependent matrix function  
 pub type StateFunction<F> = Arc<dyn Fn(F, ArrayView1<F>) -> Array2<F> + Send + Sync>;
 
+/// Type alias for a preconditioner of Jacobian-free Newton-Krylov solves
+///
+/// Called as `p(t, y, gamma, r)`, it returns an approximation of
+/// `(gamma I - J(t, y))⁻¹ r`, where `J` is the Jacobian ∂f/∂y.
+pub type PreconditionerFunction<F> =
+    Arc<dyn Fn(F, ArrayView1<F>, F, ArrayView1<F>) -> Array1<F> + Send + Sync>;
+
 /// ODE solver method
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ODEMethod {
@@ -194,7 +201,7 @@ impl<F: IntegrateFloat> MassMatrix<F> {
 }
 
 /// Options for controlling the behavior of ODE solvers
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ODEOptions<F: IntegrateFloat> {
     /// The ODE solver method to use
     pub method: ODEMethod,
@@ -212,10 +219,26 @@ pub struct ODEOptions<F: IntegrateFloat> {
     pub min_step: Option<F>,
     /// Dense output flag - whether to enable dense output
     pub dense_output: bool,
+    /// Times at which to report the solution (optional)
+    ///
+    /// When set, the solution is interpolated at these times from a dense
+    /// output of the accepted steps instead of being reported at the steps
+    /// themselves; step sizes are not constrained by them.
+    pub t_eval: Option<Vec<F>>,
     /// Maximum order for BDF method (1-5)
     pub max_order: Option<usize>,
-    /// Jacobian matrix (optional, for implicit methods)
-    pub jac: Option<Array1<F>>,
+    /// Jacobian function ∂f/∂y(t, y) (optional, for implicit methods)
+    ///
+    /// Methods that support it use this instead of a finite difference
+    /// approximation.
+    pub jac: Option<StateFunction<F>>,
+    /// Sparsity pattern of the Jacobian ∂f/∂y (optional, for implicit methods)
+    ///
+    /// When set, methods that support it estimate the Jacobian by finite
+    /// differences with one evaluation per column color (or take the entries
+    /// of `jac` on the pattern) and solve the Newton systems with a sparse LU
+    /// factorization.
+    pub jac_sparsity: Option<crate::ode::utils::jacobian::SparsityPattern>,
     /// Whether to use a banded Jacobian matrix
     pub use_banded_jacobian: bool,
     /// Number of lower diagonals for banded Jacobian
@@ -226,6 +249,55 @@ pub struct ODEOptions<F: IntegrateFloat> {
     pub mass_matrix: Option<MassMatrix<F>>,
     /// Strategy for Jacobian approximation/computation
     pub jacobian_strategy: Option<crate::ode::utils::jacobian::JacobianStrategy>,
+    /// Solver for the Newton systems of implicit methods
+    ///
+    /// With `Iterative` (or `Auto` for at least 100 equations), BDF and Radau
+    /// solve their Newton systems with GMRES using finite difference
+    /// directional derivatives `J v ≈ (f(t, y + εv) - f(t, y)) / ε`, so the
+    /// Jacobian is never formed and `jac` and `jac_sparsity` are not used.
+    /// This makes very large systems feasible, at the price of more function
+    /// evaluations per Newton iteration unless a `preconditioner` is given.
+    pub linear_solver: crate::ode::utils::linear_solvers::LinearSolverType,
+    /// Preconditioner for the Jacobian-free Newton-Krylov solves (optional)
+    pub preconditioner: Option<PreconditionerFunction<F>>,
+    /// Projection onto conserved quantities after every accepted step (optional)
+    ///
+    /// Applied by every method, with or without a mass matrix. Dense output
+    /// between steps is not projected.
+    pub projection: Option<crate::ode::utils::projection::InvariantProjection<F>>,
+    /// Callback reporting progress every few accepted steps, which can also
+    /// abort the integration (optional)
+    ///
+    /// Reported by every method, with or without a mass matrix.
+    pub progress: Option<crate::ode::utils::progress::ProgressCallback<F>>,
+}
+
+impl<F: IntegrateFloat> Debug for ODEOptions<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ODEOptions")
+            .field("method", &self.method)
+            .field("rtol", &self.rtol)
+            .field("atol", &self.atol)
+            .field("h0", &self.h0)
+            .field("max_steps", &self.max_steps)
+            .field("max_step", &self.max_step)
+            .field("min_step", &self.min_step)
+            .field("dense_output", &self.dense_output)
+            .field("t_eval", &self.t_eval)
+            .field("max_order", &self.max_order)
+            .field("jac", &self.jac.is_some())
+            .field("jac_sparsity", &self.jac_sparsity)
+            .field("use_banded_jacobian", &self.use_banded_jacobian)
+            .field("ml", &self.ml)
+            .field("mu", &self.mu)
+            .field("mass_matrix", &self.mass_matrix)
+            .field("jacobian_strategy", &self.jacobian_strategy)
+            .field("linear_solver", &self.linear_solver)
+            .field("preconditioner", &self.preconditioner.is_some())
+            .field("projection", &self.projection)
+            .field("progress", &self.progress)
+            .finish()
+    }
 }
 
 impl<F: IntegrateFloat> Default for ODEOptions<F> {
@@ -239,13 +311,19 @@ impl<F: IntegrateFloat> Default for ODEOptions<F> {
             max_step: None,
             min_step: None,
             dense_output: false,
+            t_eval: None,
             max_order: None,
             jac: None,
+            jac_sparsity: None,
             use_banded_jacobian: false,
             ml: None,
             mu: None,
             mass_matrix: None,
             jacobian_strategy: None, // Defaults to Adaptive in JacobianManager
+            linear_solver: crate::ode::utils::linear_solvers::LinearSolverType::Direct,
+            preconditioner: None,
+            projection: None,
+            progress: None,
         }
     }
 }
@@ -275,4 +353,40 @@ pub struct ODEResult<F: IntegrateFloat> {
     pub n_jac: usize,
     /// The solver method used
     pub method: ODEMethod,
+    /// Formula used for each accepted step by automatically switching solvers
+    ///
+    /// One entry per accepted step, in order; without `t_eval` entry `i`
+    /// describes the step from `t[i]` to `t[i + 1]`. Empty for methods that
+    /// do not switch.
+    pub step_methods: Vec<StepMethod>,
+    /// Stiffness indicators, computed for the explicit methods
+    ///
+    /// When they show that the step size was limited by stability over a long
+    /// integration, `message` also carries a hint to switch to an implicit
+    /// method.
+    pub stiffness: Option<crate::ode::utils::stiffness::StiffnessDiagnostics<F>>,
+}
+
+impl<F: IntegrateFloat> ODEResult<F> {
+    /// Work done by the solver
+    pub fn stats(&self) -> crate::ode::utils::progress::SolverStats {
+        crate::ode::utils::progress::SolverStats {
+            n_eval: self.n_eval,
+            n_steps: self.n_steps,
+            n_accepted: self.n_accepted,
+            n_rejected: self.n_rejected,
+            n_lu: self.n_lu,
+            n_jac: self.n_jac,
+        }
+    }
+}
+
+/// Formula used for a step by an automatically switching solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepMethod {
+    /// Adams predictor-corrector formula (non-stiff)
+    Adams,
+    /// Backward differentiation formula (stiff)
+    Bdf,
 }
@@ -5,12 +5,16 @@
 //! integration interval without recomputing the solution.
 
 use crate::error::{IntegrateError, IntegrateResult};
+use crate::ode::types::StepMethod;
+use crate::ode::utils::events::EventRecord;
+use crate::ode::utils::export::{write_csv, write_npz, NpyArray};
 use crate::ode::utils::interpolation::{
     cubic_hermite_interpolation, linear_interpolation, ContinuousOutputMethod,
 };
 use crate::IntegrateFloat;
 use ndarray::{Array1, ArrayView1};
 use std::fmt::Debug;
+use std::io::Write;
 
 /// Type alias for derivative function
 type DerivativeFunction<F> = Box<dyn Fn(F, ArrayView1<F>) -> Array1<F>>;
@@ -324,6 +328,424 @@ impl<F: IntegrateFloat> RadauInterpolant<F> {
     }
 }
 
+/// Local interpolant on one accepted step `[t[i], t[i + 1]]`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum StepInterpolant<F: IntegrateFloat> {
+    /// Cubic Hermite polynomial matching the solution and its derivative at
+    /// both ends of the step
+    Hermite { f0: Array1<F>, f1: Array1<F> },
+    /// Polynomial through a set of (time, value) nodes
+    Polynomial { nodes: Vec<(F, Array1<F>)> },
+}
+
+/// Continuous solution of an initial value problem
+///
+/// Stores one polynomial interpolant per accepted step, so the solution can be
+/// evaluated at any time in the integration interval after the solver has
+/// finished, without restricting the step sizes it took. This is the
+/// counterpart of the `sol` callable returned by SciPy's `solve_ivp` with
+/// `dense_output=True`. Available interpolants:
+///
+/// - [`hermite`](Self::hermite): cubic Hermite polynomial from the solution and
+///   its derivative at both ends of each step, for explicit Runge-Kutta methods
+/// - [`collocation`](Self::collocation): polynomial through the step end points
+///   and internal stage values, the collocation polynomial of implicit
+///   Runge-Kutta methods such as Radau IIA
+/// - [`backward`](Self::backward): polynomial through the current and previous
+///   solution points, the interpolant underlying BDF and Adams methods
+///
+/// Besides the interpolants, the solution carries a [`StepRecord`] for each
+/// accepted step and the events detected during the integration, and can be
+/// exported with [`to_csv`](Self::to_csv), [`to_npz`](Self::to_npz) and, with
+/// the `serde` feature, [`to_json`](Self::to_json).
+///
+/// Usually created by [`crate::ode::solve_ivp_dense`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ODESolution<F: IntegrateFloat> {
+    t: Vec<F>,
+    y: Vec<Array1<F>>,
+    interpolants: Vec<StepInterpolant<F>>,
+    steps: Vec<StepRecord<F>>,
+    events: EventRecord<F>,
+}
+
+/// Statistics of one accepted step of an [`ODESolution`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepRecord<F: IntegrateFloat> {
+    /// Time at the start of the step
+    pub t: F,
+    /// Step size
+    pub h: F,
+    /// Formula used for the step, for automatically switching solvers
+    pub method: Option<StepMethod>,
+}
+
+impl<F: IntegrateFloat> ODESolution<F> {
+    /// Create a continuous solution from cubic Hermite interpolation
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - Increasing time points of the discrete solution
+    /// * `y` - Solution values at the time points
+    /// * `dydt` - Derivatives `f(t, y)` at the time points
+    ///
+    /// # Returns
+    ///
+    /// The continuous solution, third-order accurate between the time points
+    pub fn hermite(t: Vec<F>, y: Vec<Array1<F>>, dydt: Vec<Array1<F>>) -> IntegrateResult<Self> {
+        Self::check_points(&t, &y)?;
+        if dydt.len() != t.len() {
+            return Err(IntegrateError::DimensionMismatch(
+                "Time and derivative vectors must have the same length".to_string(),
+            ));
+        }
+
+        let interpolants = dydt
+            .windows(2)
+            .map(|w| StepInterpolant::Hermite {
+                f0: w[0].clone(),
+                f1: w[1].clone(),
+            })
+            .collect();
+        Ok(Self::from_parts(t, y, interpolants))
+    }
+
+    /// Create a continuous solution from collocation polynomials
+    ///
+    /// On each step the solution is the polynomial through the two step end
+    /// points and the internal stage values of that step.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - Increasing time points of the discrete solution
+    /// * `y` - Solution values at the time points
+    /// * `stages` - For each step, the (time, value) pairs of its internal stages
+    ///
+    /// # Returns
+    ///
+    /// The continuous solution
+    pub fn collocation(
+        t: Vec<F>,
+        y: Vec<Array1<F>>,
+        stages: Vec<Vec<(F, Array1<F>)>>,
+    ) -> IntegrateResult<Self> {
+        Self::check_points(&t, &y)?;
+        if stages.len() + 1 != t.len() {
+            return Err(IntegrateError::DimensionMismatch(format!(
+                "Expected stage values for {} steps, got {}",
+                t.len() - 1,
+                stages.len()
+            )));
+        }
+
+        let mut interpolants = Vec::with_capacity(stages.len());
+        for (i, step_stages) in stages.into_iter().enumerate() {
+            if step_stages
+                .iter()
+                .any(|(ts, _)| *ts <= t[i] || *ts >= t[i + 1])
+            {
+                return Err(IntegrateError::ValueError(format!(
+                    "Stage times must lie inside step {} ([{}, {}])",
+                    i,
+                    t[i],
+                    t[i + 1]
+                )));
+            }
+            let mut nodes = Vec::with_capacity(step_stages.len() + 2);
+            nodes.push((t[i], y[i].clone()));
+            nodes.extend(step_stages);
+            nodes.push((t[i + 1], y[i + 1].clone()));
+            interpolants.push(StepInterpolant::Polynomial { nodes });
+        }
+        Ok(Self::from_parts(t, y, interpolants))
+    }
+
+    /// Create a continuous solution from backward interpolation polynomials
+    ///
+    /// On the step `[t[i], t[i + 1]]` the solution is the polynomial of degree
+    /// `order` through `t[i + 1]` and the `order` preceding time points (fewer
+    /// at the start of the integration).
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - Increasing time points of the discrete solution
+    /// * `y` - Solution values at the time points
+    /// * `order` - Polynomial degree, at least 1
+    ///
+    /// # Returns
+    ///
+    /// The continuous solution
+    pub fn backward(t: Vec<F>, y: Vec<Array1<F>>, order: usize) -> IntegrateResult<Self> {
+        Self::check_points(&t, &y)?;
+        if order == 0 {
+            return Err(IntegrateError::ValueError(
+                "Interpolation order must be at least 1".to_string(),
+            ));
+        }
+
+        let interpolants = (0..t.len() - 1)
+            .map(|i| {
+                let first = (i + 1).saturating_sub(order);
+                StepInterpolant::Polynomial {
+                    nodes: (first..=i + 1).map(|j| (t[j], y[j].clone())).collect(),
+                }
+            })
+            .collect();
+        Ok(Self::from_parts(t, y, interpolants))
+    }
+
+    /// Assemble a solution with one step record per interpolant and no events
+    fn from_parts(t: Vec<F>, y: Vec<Array1<F>>, interpolants: Vec<StepInterpolant<F>>) -> Self {
+        let steps = t
+            .windows(2)
+            .map(|w| StepRecord {
+                t: w[0],
+                h: w[1] - w[0],
+                method: None,
+            })
+            .collect();
+        ODESolution {
+            t,
+            y,
+            interpolants,
+            steps,
+            events: EventRecord::new(),
+        }
+    }
+
+    /// Record the formula used for each step, in order
+    ///
+    /// Extra entries are ignored; steps without an entry keep `None`.
+    pub fn with_step_methods(mut self, methods: &[StepMethod]) -> Self {
+        for (step, &method) in self.steps.iter_mut().zip(methods) {
+            step.method = Some(method);
+        }
+        self
+    }
+
+    /// Attach the events detected during the integration
+    pub fn with_events(mut self, events: EventRecord<F>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Validate the discrete solution points
+    fn check_points(t: &[F], y: &[Array1<F>]) -> IntegrateResult<()> {
+        if t.is_empty() {
+            return Err(IntegrateError::ComputationError(
+                "Empty solution cannot be converted to dense output".to_string(),
+            ));
+        }
+        if t.len() != y.len() {
+            return Err(IntegrateError::DimensionMismatch(
+                "Time and solution vectors must have the same length".to_string(),
+            ));
+        }
+        if t.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(IntegrateError::ValueError(
+                "Time points must be strictly increasing".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Time interval covered by the solution
+    pub fn t_span(&self) -> [F; 2] {
+        [self.t[0], self.t[self.t.len() - 1]]
+    }
+
+    /// Time points of the underlying discrete solution (the step boundaries)
+    pub fn t(&self) -> &[F] {
+        &self.t
+    }
+
+    /// Solution values at the step boundaries
+    pub fn y(&self) -> &[Array1<F>] {
+        &self.y
+    }
+
+    /// Statistics of the accepted steps, in order
+    pub fn steps(&self) -> &[StepRecord<F>] {
+        &self.steps
+    }
+
+    /// Events detected during the integration
+    pub fn events(&self) -> &EventRecord<F> {
+        &self.events
+    }
+
+    /// Evaluate the solution at time `t`, shorthand for [`evaluate`](Self::evaluate)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ndarray::{array, ArrayView1};
+    /// use scirs2_integrate::ode::solve_ivp_dense;
+    ///
+    /// let f = |_t: f64, y: ArrayView1<f64>| array![-y[0]];
+    /// let (_, sol) = solve_ivp_dense(f, [0.0, 1.0], array![1.0], None).unwrap();
+    /// assert!((sol.at(0.5).unwrap()[0] - (-0.5f64).exp()).abs() < 1e-3);
+    /// ```
+    pub fn at(&self, t: F) -> IntegrateResult<Array1<F>> {
+        self.evaluate(t)
+    }
+
+    /// Evaluate the solution at time `t`
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - Time within [`t_span`](Self::t_span)
+    ///
+    /// # Returns
+    ///
+    /// The interpolated solution, or an error if `t` is outside the interval
+    pub fn evaluate(&self, t: F) -> IntegrateResult<Array1<F>> {
+        let [t_min, t_max] = self.t_span();
+        if t < t_min || t > t_max {
+            return Err(IntegrateError::ValueError(format!(
+                "Evaluation time {} is outside the solution range [{}, {}]",
+                t, t_min, t_max
+            )));
+        }
+        if self.interpolants.is_empty() {
+            return Ok(self.y[0].clone());
+        }
+
+        // Step containing t; the right end point belongs to the last step
+        let i = (self.t.partition_point(|&ti| ti <= t) - 1).min(self.interpolants.len() - 1);
+        let (t0, t1) = (self.t[i], self.t[i + 1]);
+
+        match &self.interpolants[i] {
+            StepInterpolant::Hermite { f0, f1 } => {
+                let h = t1 - t0;
+                let s = (t - t0) / h;
+                let two = F::from_f64(2.0).unwrap();
+                let three = F::from_f64(3.0).unwrap();
+                let h00 = two * s.powi(3) - three * s.powi(2) + F::one();
+                let h10 = s.powi(3) - two * s.powi(2) + s;
+                let h01 = three * s.powi(2) - two * s.powi(3);
+                let h11 = s.powi(3) - s.powi(2);
+                Ok(&self.y[i] * h00 + f0 * (h10 * h) + &self.y[i + 1] * h01 + f1 * (h11 * h))
+            }
+            StepInterpolant::Polynomial { nodes } => {
+                // Lagrange form; nodes are few, so this is well conditioned
+                let mut result = Array1::zeros(self.y[i].len());
+                for (j, (tj, yj)) in nodes.iter().enumerate() {
+                    let weight = nodes
+                        .iter()
+                        .enumerate()
+                        .filter(|&(m, _)| m != j)
+                        .fold(F::one(), |w, (_, (tm, _))| w * (t - *tm) / (*tj - *tm));
+                    result.scaled_add(weight, yj);
+                }
+                Ok(result)
+            }
+        }
+    }
+
+    /// Evaluate the solution at several times
+    ///
+    /// # Arguments
+    ///
+    /// * `times` - Times within [`t_span`](Self::t_span), in any order
+    ///
+    /// # Returns
+    ///
+    /// The interpolated solution at each time
+    pub fn evaluate_many(&self, times: &[F]) -> IntegrateResult<Vec<Array1<F>>> {
+        times.iter().map(|&t| self.evaluate(t)).collect()
+    }
+
+    /// Write the solution at the step boundaries as CSV
+    ///
+    /// The table has a header row `t,y0,y1,...` and one row per time point.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination, e.g. a `File` or a `Vec<u8>`
+    pub fn to_csv<W: Write>(&self, writer: W) -> IntegrateResult<()> {
+        let header = std::iter::once("t".to_string())
+            .chain((0..self.y[0].len()).map(|i| format!("y{}", i)))
+            .collect::<Vec<_>>();
+        let rows = self.t.iter().zip(&self.y).map(|(&t, y)| {
+            std::iter::once(t)
+                .chain(y.iter().copied())
+                .map(to_f64)
+                .collect()
+        });
+        write_csv(writer, &header, rows)
+    }
+
+    /// Write the solution as a NumPy `.npz` archive
+    ///
+    /// The archive holds the `f64` arrays `t` (time points), `y` (states, one
+    /// row per time point), `step_h` (step sizes), `event_t` (event times) and
+    /// `event_y` (states at the events, one row per event), so that
+    /// `numpy.load` gives e.g. `data["y"][:, 0]` for the first component.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination, e.g. a `File` or a `Vec<u8>`
+    pub fn to_npz<W: Write>(&self, writer: W) -> IntegrateResult<()> {
+        let n = self.y[0].len();
+        let events = &self.events.events;
+        let arrays = [
+            NpyArray {
+                name: "t",
+                shape: vec![self.t.len()],
+                data: self.t.iter().copied().map(to_f64).collect(),
+            },
+            NpyArray {
+                name: "y",
+                shape: vec![self.y.len(), n],
+                data: self.y.iter().flatten().copied().map(to_f64).collect(),
+            },
+            NpyArray {
+                name: "step_h",
+                shape: vec![self.steps.len()],
+                data: self.steps.iter().map(|s| to_f64(s.h)).collect(),
+            },
+            NpyArray {
+                name: "event_t",
+                shape: vec![events.len()],
+                data: events.iter().map(|e| to_f64(e.time)).collect(),
+            },
+            NpyArray {
+                name: "event_y",
+                shape: vec![events.len(), n],
+                data: events
+                    .iter()
+                    .flat_map(|e| e.state.iter().copied())
+                    .map(to_f64)
+                    .collect(),
+            },
+        ];
+        write_npz(writer, &arrays)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<F: IntegrateFloat + serde::Serialize> ODESolution<F> {
+    /// Serialize the solution, including its interpolants, step records and
+    /// events, to a JSON string
+    ///
+    /// The solution can be restored with `serde_json::from_str` and evaluated
+    /// again without rerunning the solver.
+    pub fn to_json(&self) -> IntegrateResult<String> {
+        serde_json::to_string(self).map_err(|e| {
+            IntegrateError::ComputationError(format!("Failed to serialize solution: {}", e))
+        })
+    }
+}
+
+/// Converts a solution value for export
+fn to_f64<F: IntegrateFloat>(value: F) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
 /// Convert an ODE result to a dense solution for continuous evaluation
 pub fn create_dense_solution<F, Func>(
     t: Vec<F>,
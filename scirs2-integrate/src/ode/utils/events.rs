@@ -4,11 +4,17 @@
 //! and handling them appropriately. Events are defined as conditions where a given
 //! function crosses zero. The event can trigger various actions, such as stopping
 //! the integration, modifying the state, or recording the event time.
+//!
+//! Zero crossings are detected between consecutive accepted steps and located
+//! within the step by root finding on the continuous solution. Events found in
+//! the same step are recorded in chronological order, and a terminal event
+//! discards everything after it.
 
 use crate::common::IntegrateFloat;
 use crate::error::{IntegrateError, IntegrateResult};
-use crate::ode::utils::dense_output::DenseSolution;
+use crate::ode::utils::dense_output::ODESolution;
 use ndarray::{Array1, ArrayView1};
+use std::cmp::Ordering;
 
 /// Direction of zero-crossing for event detection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -76,6 +82,7 @@ impl<F: IntegrateFloat> Default for EventSpec<F> {
 
 /// Represents a detected event during integration
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Event<F: IntegrateFloat> {
     /// ID of the event that was triggered
     pub id: String,
@@ -91,6 +98,7 @@ pub struct Event<F: IntegrateFloat> {
 
 /// Record of all events detected during integration
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventRecord<F: IntegrateFloat> {
     /// List of all detected events in chronological order
     pub events: Vec<Event<F>>,
@@ -191,11 +199,15 @@ impl<F: IntegrateFloat> EventHandler<F> {
     }
 
     /// Check for events between the last state and the current state
+    ///
+    /// All events triggered in the step are recorded in chronological order.
+    /// If one of them is terminal, the events after it are discarded and
+    /// [`EventAction::Stop`] is returned.
     pub fn check_events<Func>(
         &mut self,
         t: F,
         y: &Array1<F>,
-        dense_output: Option<&DenseSolution<F>>,
+        dense_output: Option<&ODESolution<F>>,
         event_funcs: &[Func],
     ) -> IntegrateResult<EventAction>
     where
@@ -214,20 +226,21 @@ impl<F: IntegrateFloat> EventHandler<F> {
             return Ok(EventAction::Continue);
         }
 
-        let (t_prev, y_prev) = self.last_state.as_ref().unwrap();
+        let (t_prev, _) = self.last_state.take().unwrap();
 
-        // Check each event
-        let mut action = EventAction::Continue;
+        // Events triggered in this step, with the action of their specification
+        let mut triggered_events = Vec::new();
 
         for (i, (func, spec)) in event_funcs.iter().zip(self.specs.iter()).enumerate() {
+            // Compute current value
+            let value = func(t, y.view());
+
             // Skip if we've already reached the maximum count for this event
             if spec.max_count_reached(&spec.id, self.record.counts.get(&spec.id).cloned()) {
+                self.last_values[i] = Some(value);
                 continue;
             }
 
-            // Compute current value
-            let value = func(t, y.view());
-
             // Check if we have a previous value
             if let Some(prev_value) = self.last_values[i] {
                 // Check if event occurred (zero-crossing)
@@ -242,25 +255,17 @@ impl<F: IntegrateFloat> EventHandler<F> {
 
                 if triggered {
                     // Refine the event time if requested and dense output is available
-                    let (event_t, event_y, event_val, dir) =
-                        if spec.precise_time && dense_output.is_some() {
-                            self.refine_event_time(
-                                *t_prev,
-                                y_prev,
-                                t,
-                                y,
-                                prev_value,
-                                value,
-                                func,
-                                dense_output.unwrap(),
-                            )?
-                        } else {
+                    let (event_t, event_y, event_val, dir) = match dense_output {
+                        Some(dense) if spec.precise_time => {
+                            Self::refine_event_time(t_prev, t, y, prev_value, value, func, dense)?
+                        }
+                        _ => {
                             // Use current time as event time (less accurate)
                             let dir = if rising { 1 } else { -1 };
                             (t, y.clone(), value, dir)
-                        };
+                        }
+                    };
 
-                    // Create event record
                     let event = Event {
                         id: spec.id.clone(),
                         time: event_t,
@@ -268,14 +273,7 @@ impl<F: IntegrateFloat> EventHandler<F> {
                         value: event_val,
                         direction: dir,
                     };
-
-                    // Add to record
-                    self.record.add_event(event);
-
-                    // If this event requires stopping, set the action
-                    if spec.action == EventAction::Stop {
-                        action = EventAction::Stop;
-                    }
+                    triggered_events.push((event, spec.action));
                 }
             }
 
@@ -283,24 +281,36 @@ impl<F: IntegrateFloat> EventHandler<F> {
             self.last_values[i] = Some(value);
         }
 
+        // Record in chronological order, up to and including the first terminal event
+        triggered_events.sort_by(|a, b| a.0.time.partial_cmp(&b.0.time).unwrap_or(Ordering::Equal));
+        let mut action = EventAction::Continue;
+        for (event, event_action) in triggered_events {
+            self.record.add_event(event);
+            if event_action == EventAction::Stop {
+                action = EventAction::Stop;
+                break;
+            }
+        }
+
         // Update last state
         self.last_state = Some((t, y.clone()));
 
         Ok(action)
     }
 
-    /// Refine the exact time of an event using bisection on the dense output
-    #[allow(clippy::too_many_arguments)]
+    /// Locate the zero of an event function within a step
+    ///
+    /// Uses the Illinois variant of regula falsi on the continuous solution,
+    /// which keeps the root bracketed like bisection but converges
+    /// superlinearly for smooth event functions.
     fn refine_event_time<Func>(
-        &self,
         t_prev: F,
-        y_prev: &Array1<F>,
         t_curr: F,
         y_curr: &Array1<F>,
         value_prev: F,
         value_curr: F,
         event_func: &Func,
-        dense_output: &DenseSolution<F>,
+        dense_output: &ODESolution<F>,
     ) -> IntegrateResult<(F, Array1<F>, F, i8)>
     where
         Func: Fn(F, ArrayView1<F>) -> F,
@@ -312,56 +322,69 @@ impl<F: IntegrateFloat> EventHandler<F> {
             -1 // Falling
         };
 
-        // Root-finding tolerance
-        let tol = F::from_f64(1e-10).unwrap();
-        let max_iter = 50;
+        // An endpoint that is exactly a root needs no refinement
+        if value_curr == F::zero() {
+            return Ok((t_curr, y_curr.clone(), value_curr, direction));
+        }
+
+        // Resolve the event time to a few ulps of its magnitude
+        let t_tol =
+            F::from_f64(4.0).unwrap() * F::epsilon() * t_prev.abs().max(t_curr.abs()).max(F::one());
+        let max_iter = 100;
 
-        // Bisection search for zero-crossing
         let mut t_left = t_prev;
         let mut t_right = t_curr;
         let mut f_left = value_prev;
-        let f_right = value_curr;
-
-        // Handle the case where one endpoint is exactly zero
-        if f_left.abs() < tol {
-            return Ok((t_left, y_prev.clone(), f_left, direction));
-        }
+        let mut f_right = value_curr;
+        // Which end was retained in the previous iteration (-1 left, 1 right)
+        let mut retained = 0;
 
-        if f_right.abs() < tol {
-            return Ok((t_right, y_curr.clone(), f_right, direction));
-        }
-
-        // Bisection loop
-        let mut t_mid = F::zero();
-        let mut y_mid = Array1::<F>::zeros(y_prev.len());
-        let mut f_mid = F::zero();
+        let mut t_root = t_right;
+        let mut y_root = y_curr.clone();
+        let mut f_root = f_right;
 
         for _ in 0..max_iter {
-            // Compute midpoint time
-            t_mid = (t_left + t_right) / F::from_f64(2.0).unwrap();
+            if t_right - t_left <= t_tol {
+                break;
+            }
 
-            // Get state at midpoint using dense output
-            y_mid = dense_output.evaluate(t_mid)?;
+            // Secant point through the bracket, falling back to bisection
+            let half = F::from_f64(0.5).unwrap();
+            let mut t_mid = t_right - f_right * (t_right - t_left) / (f_right - f_left);
+            if !(t_mid > t_left && t_mid < t_right) {
+                t_mid = half * (t_left + t_right);
+            }
 
-            // Evaluate event function at midpoint
-            f_mid = event_func(t_mid, y_mid.view());
+            let y_mid = dense_output.evaluate(t_mid)?;
+            let f_mid = event_func(t_mid, y_mid.view());
+            t_root = t_mid;
+            y_root = y_mid;
+            f_root = f_mid;
 
-            // Check convergence
-            if f_mid.abs() < tol || (t_right - t_left).abs() < tol {
+            if f_mid == F::zero() {
                 break;
             }
 
-            // Update interval
-            if f_left * f_mid < F::zero() {
-                t_right = t_mid;
-                let _f_right = f_mid;
-            } else {
+            if (f_mid < F::zero()) == (f_left < F::zero()) {
+                // Root is in [t_mid, t_right]
                 t_left = t_mid;
                 f_left = f_mid;
+                if retained == 1 {
+                    f_right *= half;
+                }
+                retained = 1;
+            } else {
+                // Root is in [t_left, t_mid]
+                t_right = t_mid;
+                f_right = f_mid;
+                if retained == -1 {
+                    f_left *= half;
+                }
+                retained = -1;
             }
         }
 
-        Ok((t_mid, y_mid, f_mid, direction))
+        Ok((t_root, y_root, f_root, direction))
     }
 
     /// Get the record of all detected events
@@ -419,8 +442,8 @@ pub struct ODEResultWithEvents<F: IntegrateFloat> {
     pub base_result: super::super::types::ODEResult<F>,
     /// Record of detected events
     pub events: EventRecord<F>,
-    /// Dense output for the solution (if available)
-    pub dense_output: Option<DenseSolution<F>>,
+    /// Continuous solution over the integrated interval (if available)
+    pub dense_output: Option<ODESolution<F>>,
     /// Whether integration terminated due to an event
     pub event_termination: bool,
 }
@@ -430,7 +453,7 @@ impl<F: IntegrateFloat> ODEResultWithEvents<F> {
     pub fn new(
         base_result: super::super::types::ODEResult<F>,
         events: EventRecord<F>,
-        dense_output: Option<DenseSolution<F>>,
+        dense_output: Option<ODESolution<F>>,
         event_termination: bool,
     ) -> Self {
         ODEResultWithEvents {
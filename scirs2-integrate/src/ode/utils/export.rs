@@ -0,0 +1,165 @@
+//! Writers for exporting ODE solutions
+//!
+//! CSV tables and NumPy `.npz` archives are written without external
+//! dependencies. The archives are uncompressed zip files holding one
+//! version 1.0 `.npy` file of little-endian `f64` values per array, which
+//! `numpy.load` reads directly.
+
+use crate::error::{IntegrateError, IntegrateResult};
+use std::io::Write;
+
+/// Array to be stored in an `.npz` archive
+pub(crate) struct NpyArray<'a> {
+    /// Name of the array in the archive (without the `.npy` extension)
+    pub name: &'a str,
+    /// Shape of the array, row-major
+    pub shape: Vec<usize>,
+    /// Values in row-major order
+    pub data: Vec<f64>,
+}
+
+/// Converts an I/O error from an export writer
+fn io_error(err: std::io::Error) -> IntegrateError {
+    IntegrateError::ComputationError(format!("Failed to write solution: {}", err))
+}
+
+/// Writes a CSV table with a header row
+pub(crate) fn write_csv<W: Write>(
+    mut writer: W,
+    header: &[String],
+    rows: impl Iterator<Item = Vec<f64>>,
+) -> IntegrateResult<()> {
+    writeln!(writer, "{}", header.join(",")).map_err(io_error)?;
+    for row in rows {
+        let line: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+        writeln!(writer, "{}", line.join(",")).map_err(io_error)?;
+    }
+    writer.flush().map_err(io_error)
+}
+
+/// Serializes an array in the `.npy` format, version 1.0
+fn npy_bytes(array: &NpyArray) -> Vec<u8> {
+    let shape = match array.shape.as_slice() {
+        [n] => format!("({},)", n),
+        dims => format!(
+            "({})",
+            dims.iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}",
+        shape
+    );
+    // Magic (6) + version (2) + header length (2) + header, padded to 64 bytes
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(10 + header.len() + 8 * array.data.len());
+    bytes.extend_from_slice(b"\x93NUMPY\x01\x00");
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for value in &array.data {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+/// CRC-32 checksum (IEEE polynomial) used by the zip format
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Writes the arrays as an uncompressed `.npz` (zip) archive
+pub(crate) fn write_npz<W: Write>(mut writer: W, arrays: &[NpyArray]) -> IntegrateResult<()> {
+    // Date 1980-01-01, the earliest representable zip timestamp
+    const DOS_DATE: u16 = 0x21;
+
+    let mut archive = Vec::new();
+    let mut central_directory = Vec::new();
+    for array in arrays {
+        let name = format!("{}.npy", array.name);
+        let data = npy_bytes(array);
+        let crc = crc32(&data);
+        let (size, offset) = (data.len() as u32, archive.len() as u32);
+
+        // Local file header
+        archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        for field in [20u16, 0, 0, 0, DOS_DATE] {
+            archive.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            archive.extend_from_slice(&field.to_le_bytes());
+        }
+        archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(&data);
+
+        // Central directory entry
+        central_directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        for field in [20u16, 20, 0, 0, 0, DOS_DATE] {
+            central_directory.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            central_directory.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [name.len() as u16, 0, 0, 0, 0] {
+            central_directory.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [0u32, offset] {
+            central_directory.extend_from_slice(&field.to_le_bytes());
+        }
+        central_directory.extend_from_slice(name.as_bytes());
+    }
+
+    // End of central directory record
+    let (directory_offset, directory_size) = (archive.len() as u32, central_directory.len() as u32);
+    archive.extend_from_slice(&central_directory);
+    archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    for field in [0u16, 0, arrays.len() as u16, arrays.len() as u16] {
+        archive.extend_from_slice(&field.to_le_bytes());
+    }
+    for field in [directory_size, directory_offset] {
+        archive.extend_from_slice(&field.to_le_bytes());
+    }
+    archive.extend_from_slice(&0u16.to_le_bytes());
+
+    writer.write_all(&archive).map_err(io_error)?;
+    writer.flush().map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_npy_header_alignment() {
+        let array = NpyArray {
+            name: "y",
+            shape: vec![3, 2],
+            data: vec![0.0; 6],
+        };
+        let bytes = npy_bytes(&array);
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        assert_eq!(bytes.len(), 10 + header_len + 48);
+        assert_eq!(bytes[10 + header_len - 1], b'\n');
+    }
+}
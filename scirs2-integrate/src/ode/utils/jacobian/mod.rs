@@ -5,13 +5,17 @@
 //! and specialized techniques for different problem types.
 
 mod autodiff;
+mod matrix_free;
 mod newton;
 mod parallel;
+mod sparse;
 mod specialized;
 
 pub use autodiff::*;
+pub use matrix_free::*;
 pub use newton::*;
 pub use parallel::*;
+pub use sparse::*;
 pub use specialized::*;
 
 use crate::common::IntegrateFloat;
@@ -250,6 +254,17 @@ impl<F: IntegrateFloat> JacobianManager<F> {
         }
     }
 
+    /// `f(t, y)`, reusing the stored evaluation only if it was taken at `(t, y)`
+    fn eval_at<Func>(&self, t: F, y: &Array1<F>, f: &Func) -> Array1<F>
+    where
+        Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+    {
+        match (&self.f_eval, &self.state_point) {
+            (Some(f_val), Some((t_eval, y_eval))) if *t_eval == t && y_eval == y => f_val.clone(),
+            _ => f(t, y.view()),
+        }
+    }
+
     /// Compute or update the Jacobian matrix
     pub fn update_jacobian<Func>(
         &mut self,
@@ -267,11 +282,7 @@ impl<F: IntegrateFloat> JacobianManager<F> {
         match self.strategy {
             JacobianStrategy::FiniteDifference | JacobianStrategy::SparseFiniteDifference => {
                 // Compute function at current point (if not available)
-                let f_current = if let Some(f_val) = &self.f_eval {
-                    f_val.clone()
-                } else {
-                    f(t, y.view())
-                };
+                let f_current = self.eval_at(t, y, f);
 
                 // Create or resize Jacobian if needed
                 let mut jac = if let Some(j) = &self.jacobian {
@@ -323,11 +334,7 @@ impl<F: IntegrateFloat> JacobianManager<F> {
             JacobianStrategy::ParallelFiniteDifference
             | JacobianStrategy::ParallelSparseFiniteDifference => {
                 // Compute function at current point (if not available)
-                let f_current = if let Some(f_val) = &self.f_eval {
-                    f_val.clone()
-                } else {
-                    f(t, y.view())
-                };
+                let f_current = self.eval_at(t, y, f);
 
                 // Use implementation based on strategy, but fall back to serial for now
                 // TODO: Fix parallel jacobian computation trait bounds
@@ -464,11 +471,7 @@ impl<F: IntegrateFloat> JacobianManager<F> {
             }
             JacobianStrategy::AutoDiff => {
                 // Compute function at current point (if not available)
-                let f_current = if let Some(f_val) = &self.f_eval {
-                    f_val.clone()
-                } else {
-                    f(t, y.view())
-                };
+                let f_current = self.eval_at(t, y, f);
 
                 // Use autodiff to compute exact Jacobian
                 let jac = autodiff_jacobian(f, t, y, &f_current, F::one())?;
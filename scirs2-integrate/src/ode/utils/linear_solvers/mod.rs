@@ -3,22 +3,47 @@
 //! This module provides linear system solvers for use within ODE solvers.
 //! These replace the need for external linear algebra libraries like ndarray-linalg.
 
+mod gmres;
+
+pub use gmres::*;
+
 use crate::error::{IntegrateError, IntegrateResult};
+use crate::IntegrateFloat;
 use ndarray::{Array1, ArrayView1, ArrayView2};
 use num_traits::{Float, FromPrimitive};
 use std::fmt::Debug;
 
 /// Enum for different types of linear solvers
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Also selects how implicit ODE methods solve their Newton systems through
+/// `ODEOptions::linear_solver`, where `Iterative` means Jacobian-free
+/// Newton-Krylov: GMRES with finite difference directional derivatives,
+/// without forming the Jacobian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LinearSolverType {
     /// Direct solver using LU decomposition
+    #[default]
     Direct,
-    /// Iterative solver (GMRES, etc.)
+    /// Iterative solver (GMRES)
     Iterative,
     /// Automatic selection based on problem size
     Auto,
 }
 
+impl LinearSolverType {
+    /// Systems of at least this size are solved iteratively by `Auto`
+    pub const AUTO_ITERATIVE_SIZE: usize = 100;
+
+    /// Whether systems of size `n` are solved iteratively
+    pub fn is_iterative(self, n: usize) -> bool {
+        match self {
+            LinearSolverType::Direct => false,
+            LinearSolverType::Iterative => true,
+            LinearSolverType::Auto => n >= Self::AUTO_ITERATIVE_SIZE,
+        }
+    }
+}
+
 /// Solve a linear system Ax = b using Gaussian elimination with partial pivoting
 ///
 /// # Arguments
@@ -164,35 +189,35 @@ where
 }
 
 /// Solve a linear system using automatic method selection
+///
+/// The iterative solver is GMRES without preconditioning; when it does not
+/// reach a relative residual of `1e-10`, the direct solver is used instead.
 pub fn auto_solve_linear_system<F>(
     a: &ArrayView2<F>,
     b: &ArrayView1<F>,
     solver_type: LinearSolverType,
 ) -> IntegrateResult<Array1<F>>
 where
-    F: Float
-        + FromPrimitive
-        + Debug
-        + std::ops::AddAssign
-        + std::ops::SubAssign
-        + std::ops::MulAssign,
+    F: IntegrateFloat,
 {
-    match solver_type {
-        LinearSolverType::Direct => solve_linear_system(a, b),
-        LinearSolverType::Iterative => {
-            // For now, use direct solver as iterative is not implemented
-            solve_linear_system(a, b)
-        }
-        LinearSolverType::Auto => {
-            // Use direct solver for small problems, iterative for large
-            let n = a.shape()[0];
-            if n < 100 {
-                solve_linear_system(a, b)
-            } else {
-                // For now, use direct solver until iterative is implemented
-                solve_linear_system(a, b)
-            }
-        }
+    // Shape errors are reported by the direct solver
+    if !solver_type.is_iterative(a.shape()[0])
+        || a.shape()[0] != a.shape()[1]
+        || b.len() != a.shape()[0]
+    {
+        return solve_linear_system(a, b);
+    }
+
+    let options = GmresOptions {
+        rtol: F::from_f64(1e-10).unwrap(),
+        max_iter: 2 * b.len(),
+        ..Default::default()
+    };
+    let result = gmres(|v| a.dot(&v), b.view(), |r| r.to_owned(), &options);
+    if result.converged {
+        Ok(result.x)
+    } else {
+        solve_linear_system(a, b)
     }
 }
 
@@ -4,10 +4,13 @@ pub mod common;
 pub mod dense_output;
 pub mod diagnostics;
 pub mod events;
+mod export;
 pub mod interpolation;
 pub mod jacobian;
 pub mod linear_solvers;
 pub mod mass_matrix;
+pub mod progress;
+pub mod projection;
 #[cfg(feature = "simd")]
 pub mod simd_ops;
 pub mod step_control;
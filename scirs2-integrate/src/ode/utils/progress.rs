@@ -0,0 +1,142 @@
+//! Progress reporting for long-running integrations
+//!
+//! A [`ProgressCallback`] set in [`ODEOptions::progress`](crate::ode::ODEOptions)
+//! is called every few accepted steps with the current time, state, step size
+//! and the [`SolverStats`] so far, e.g. to drive a progress bar. Returning
+//! [`ProgressAction::Abort`] stops the integration; the steps taken so far are
+//! returned with `success == false`.
+
+use crate::common::IntegrateFloat;
+use ndarray::ArrayView1;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Work done by a solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverStats {
+    /// Number of function evaluations
+    pub n_eval: usize,
+    /// Number of steps taken
+    pub n_steps: usize,
+    /// Number of accepted steps
+    pub n_accepted: usize,
+    /// Number of rejected steps
+    pub n_rejected: usize,
+    /// Number of LU decompositions
+    pub n_lu: usize,
+    /// Number of Jacobian evaluations
+    pub n_jac: usize,
+}
+
+/// State of the integration passed to a progress callback
+#[derive(Debug, Clone)]
+pub struct ProgressInfo<'a, F: IntegrateFloat> {
+    /// Time reached by the last accepted step
+    pub t: F,
+    /// State at `t`
+    pub y: ArrayView1<'a, F>,
+    /// Size of the last accepted step
+    pub h: F,
+    /// Statistics up to and including the last accepted step
+    pub stats: SolverStats,
+}
+
+/// Whether the integration should go on after a progress report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressAction {
+    /// Continue integrating
+    #[default]
+    Continue,
+    /// Stop and return the solution computed so far
+    Abort,
+}
+
+/// Function receiving progress reports
+pub type ProgressFunction<F> = Arc<dyn Fn(&ProgressInfo<F>) -> ProgressAction + Send + Sync>;
+
+/// Callback invoked every `every` accepted steps
+#[derive(Clone)]
+pub struct ProgressCallback<F: IntegrateFloat> {
+    every: usize,
+    callback: ProgressFunction<F>,
+}
+
+impl<F: IntegrateFloat> Debug for ProgressCallback<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressCallback")
+            .field("every", &self.every)
+            .finish()
+    }
+}
+
+impl<F: IntegrateFloat> ProgressCallback<F> {
+    /// Call `callback` after every `every` accepted steps (at least 1)
+    pub fn new<C>(every: usize, callback: C) -> Self
+    where
+        C: Fn(&ProgressInfo<F>) -> ProgressAction + Send + Sync + 'static,
+    {
+        ProgressCallback {
+            every: every.max(1),
+            callback: Arc::new(callback),
+        }
+    }
+
+    /// Number of accepted steps between reports
+    pub fn every(&self) -> usize {
+        self.every
+    }
+
+    /// Report the state after an accepted step if one is due; returns `true`
+    /// when the callback asks to abort
+    pub(crate) fn notify(&self, t: F, y: ArrayView1<F>, h: F, stats: SolverStats) -> bool {
+        if !stats.n_accepted.is_multiple_of(self.every) {
+            return false;
+        }
+        let info = ProgressInfo { t, y, h, stats };
+        (self.callback)(&info) == ProgressAction::Abort
+    }
+}
+
+/// Status message of an integration stopped by its progress callback
+pub(crate) fn aborted_message<F: IntegrateFloat>(t: F) -> String {
+    format!("Integration aborted by the progress callback at t = {}", t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_progress_callback_interval() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let progress = ProgressCallback::new(3, move |info: &ProgressInfo<f64>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            if info.stats.n_accepted >= 6 {
+                ProgressAction::Abort
+            } else {
+                ProgressAction::Continue
+            }
+        });
+
+        let y = array![1.0];
+        let mut aborted_at = None;
+        for step in 1..=10 {
+            let stats = SolverStats {
+                n_steps: step,
+                n_accepted: step,
+                ..Default::default()
+            };
+            if progress.notify(0.1 * step as f64, y.view(), 0.1, stats) {
+                aborted_at = Some(step);
+                break;
+            }
+        }
+
+        assert_eq!(aborted_at, Some(6));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,290 @@
+//! Projection of ODE solutions onto invariant manifolds
+//!
+//! Many systems conserve quantities exactly (total mass in a reaction network,
+//! energy and angular momentum in a Hamiltonian system, the norm of a unit
+//! quaternion), but a general-purpose integrator only preserves them up to the
+//! local truncation error, so the drift accumulates over long integrations.
+//!
+//! [`InvariantProjection`] restores the invariants g(y) = c after each accepted
+//! step by projecting the numerical solution ỹ orthogonally onto the manifold:
+//!
+//! ```text
+//! minimize ||y - ỹ||  subject to  g(y) = c
+//! ```
+//!
+//! The projected point is y = ỹ + G(ỹ)ᵀλ, where G = ∂g/∂y and the Lagrange
+//! multipliers λ are found with Newton's method. Linear invariants are
+//! restored in a single iteration.
+
+use crate::common::IntegrateFloat;
+use crate::error::{IntegrateError, IntegrateResult};
+use crate::ode::utils::linear_solvers::solve_linear_system;
+use ndarray::{Array1, Array2, ArrayView1};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Function evaluating the invariants g(y)
+pub type InvariantFunction<F> = Arc<dyn Fn(ArrayView1<F>) -> Array1<F> + Send + Sync>;
+
+/// Function evaluating the Jacobian ∂g/∂y of the invariants (m × n)
+pub type InvariantGradient<F> = Arc<dyn Fn(ArrayView1<F>) -> Array2<F> + Send + Sync>;
+
+/// Orthogonal projection onto the manifold of conserved quantities
+///
+/// The projection is applied after every accepted step when set in
+/// [`ODEOptions::projection`](crate::ode::ODEOptions). Unless explicit targets
+/// are given, the conserved values are taken from the initial condition.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use scirs2_integrate::ode::InvariantProjection;
+///
+/// // Conserve the total mass y0 + y1 + y2
+/// let projection = InvariantProjection::linear(Array2::from_elem((1, 3), 1.0_f64))
+///     .with_targets(array![1.0]);
+///
+/// let y = projection.project(&array![0.5, 0.3, 0.3]).unwrap();
+/// assert!((y.sum() - 1.0).abs() < 1e-14);
+/// ```
+#[derive(Clone)]
+pub struct InvariantProjection<F: IntegrateFloat> {
+    invariants: InvariantFunction<F>,
+    gradient: Option<InvariantGradient<F>>,
+    targets: Option<Array1<F>>,
+    tol: F,
+    max_iter: usize,
+}
+
+impl<F: IntegrateFloat> Debug for InvariantProjection<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InvariantProjection")
+            .field("gradient", &self.gradient.is_some())
+            .field("targets", &self.targets)
+            .field("tol", &self.tol)
+            .field("max_iter", &self.max_iter)
+            .finish()
+    }
+}
+
+impl<F: IntegrateFloat> InvariantProjection<F> {
+    /// Create a projection onto the invariants g(y) = c
+    ///
+    /// The Jacobian of g is approximated by finite differences unless one is
+    /// supplied with [`with_gradient`](Self::with_gradient).
+    pub fn new<G>(invariants: G) -> Self
+    where
+        G: Fn(ArrayView1<F>) -> Array1<F> + Send + Sync + 'static,
+    {
+        InvariantProjection {
+            invariants: Arc::new(invariants),
+            gradient: None,
+            targets: None,
+            tol: F::from_f64(100.0).unwrap() * F::epsilon(),
+            max_iter: 20,
+        }
+    }
+
+    /// Create a projection onto linear invariants W·y = c
+    ///
+    /// Each row of `weights` defines one conserved linear combination of the
+    /// state, e.g. a row of ones for the total mass.
+    pub fn linear(weights: Array2<F>) -> Self
+    where
+        F: Send + Sync,
+    {
+        let gradient = weights.clone();
+        Self::new(move |y: ArrayView1<F>| weights.dot(&y))
+            .with_gradient(move |_y: ArrayView1<F>| gradient.clone())
+    }
+
+    /// Set the analytical Jacobian ∂g/∂y of the invariants
+    pub fn with_gradient<G>(mut self, gradient: G) -> Self
+    where
+        G: Fn(ArrayView1<F>) -> Array2<F> + Send + Sync + 'static,
+    {
+        self.gradient = Some(Arc::new(gradient));
+        self
+    }
+
+    /// Set the values the invariants are held at instead of their initial values
+    pub fn with_targets(mut self, targets: Array1<F>) -> Self {
+        self.targets = Some(targets);
+        self
+    }
+
+    /// Set the tolerance on max |g(y) - c| (relative to max |c|) and the
+    /// maximum number of Newton iterations
+    pub fn with_tolerance(mut self, tol: F, max_iter: usize) -> Self {
+        self.tol = tol;
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Values the invariants are held at, if already fixed
+    pub fn targets(&self) -> Option<&Array1<F>> {
+        self.targets.as_ref()
+    }
+
+    /// Evaluate the invariants g(y)
+    pub fn evaluate(&self, y: ArrayView1<F>) -> Array1<F> {
+        (self.invariants)(y)
+    }
+
+    /// Fix the targets to the invariants of the initial state unless set explicitly
+    pub(crate) fn initialize(&mut self, y0: ArrayView1<F>) -> IntegrateResult<()> {
+        let values = self.evaluate(y0);
+        match &self.targets {
+            Some(targets) if targets.len() != values.len() => {
+                Err(IntegrateError::DimensionMismatch(format!(
+                    "Projection has {} targets but {} invariants",
+                    targets.len(),
+                    values.len()
+                )))
+            }
+            Some(_) => Ok(()),
+            None => {
+                self.targets = Some(values);
+                Ok(())
+            }
+        }
+    }
+
+    /// Project `y` onto the manifold g(y) = c
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no targets are set, if the invariant gradients are
+    /// linearly dependent at `y`, or if the Newton iteration does not converge.
+    pub fn project(&self, y: &Array1<F>) -> IntegrateResult<Array1<F>> {
+        let targets = self.targets.as_ref().ok_or_else(|| {
+            IntegrateError::ValueError("Projection targets have not been set".to_string())
+        })?;
+
+        let mut residual = targets - &self.evaluate(y.view());
+        if residual.len() != targets.len() {
+            return Err(IntegrateError::DimensionMismatch(format!(
+                "Projection has {} targets but {} invariants",
+                targets.len(),
+                residual.len()
+            )));
+        }
+
+        let scale = F::one() + targets.iter().fold(F::zero(), |acc, &c| acc.max(c.abs()));
+        let tol = self.tol * scale;
+        if max_abs(&residual) <= tol {
+            return Ok(y.clone());
+        }
+
+        // The correction is along the gradients at the unprojected point, and
+        // Newton's method on λ ↦ g(ỹ + G(ỹ)ᵀλ) finds the multipliers
+        let g0 = self.checked_gradient(y.view(), targets.len())?;
+        let mut g = g0.clone();
+
+        let mut projected = y.clone();
+        for _ in 0..self.max_iter {
+            // Solve G(y)·G(ỹ)ᵀ·Δλ = c - g(y)
+            let matrix = g.dot(&g0.t());
+            let delta = solve_linear_system(&matrix.view(), &residual.view()).map_err(|err| {
+                IntegrateError::ComputationError(format!(
+                    "Invariant gradients are linearly dependent: {}",
+                    err
+                ))
+            })?;
+            projected = projected + g0.t().dot(&delta);
+
+            residual = targets - &self.evaluate(projected.view());
+            if max_abs(&residual) <= tol {
+                return Ok(projected);
+            }
+            g = self.checked_gradient(projected.view(), targets.len())?;
+        }
+
+        Err(IntegrateError::ConvergenceError(format!(
+            "Projection onto invariants did not converge in {} iterations (residual {})",
+            self.max_iter,
+            max_abs(&residual)
+        )))
+    }
+
+    /// Jacobian of the invariants with its shape checked against the state
+    fn checked_gradient(&self, y: ArrayView1<F>, m: usize) -> IntegrateResult<Array2<F>> {
+        let g = self.gradient_at(y);
+        if g.shape() != [m, y.len()] {
+            return Err(IntegrateError::DimensionMismatch(format!(
+                "Invariant gradient has shape {:?}, expected [{}, {}]",
+                g.shape(),
+                m,
+                y.len()
+            )));
+        }
+        Ok(g)
+    }
+
+    /// Jacobian of the invariants, analytical or by forward differences
+    fn gradient_at(&self, y: ArrayView1<F>) -> Array2<F> {
+        if let Some(gradient) = &self.gradient {
+            return gradient(y);
+        }
+
+        let g0 = self.evaluate(y);
+        let sqrt_eps = F::epsilon().sqrt();
+        let mut jac = Array2::zeros((g0.len(), y.len()));
+        let mut y_pert = y.to_owned();
+        for j in 0..y.len() {
+            let h = sqrt_eps * (F::one() + y[j].abs());
+            y_pert[j] = y[j] + h;
+            let g1 = self.evaluate(y_pert.view());
+            for i in 0..g0.len() {
+                jac[[i, j]] = (g1[i] - g0[i]) / h;
+            }
+            y_pert[j] = y[j];
+        }
+        jac
+    }
+}
+
+fn max_abs<F: IntegrateFloat>(v: &Array1<F>) -> F {
+    v.iter().fold(F::zero(), |acc, &x| acc.max(x.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    #[test]
+    fn test_linear_projection_is_orthogonal() {
+        let projection =
+            InvariantProjection::linear(Array2::from_elem((1, 3), 1.0)).with_targets(array![1.0]);
+
+        let y = array![0.5, 0.3, 0.5];
+        let projected = projection.project(&y).unwrap();
+
+        // The correction is along the gradient (1, 1, 1)
+        assert_relative_eq!(projected.sum(), 1.0, epsilon = 1e-15);
+        let correction = &projected - &y;
+        assert_relative_eq!(correction[0], correction[1], epsilon = 1e-15);
+        assert_relative_eq!(correction[1], correction[2], epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_nonlinear_projection_finite_difference_gradient() {
+        // Unit circle: the projection is the radial rescaling
+        let mut projection = InvariantProjection::new(|y: ArrayView1<f64>| array![y.dot(&y)]);
+        projection.initialize(array![1.0, 0.0].view()).unwrap();
+
+        let projected = projection.project(&array![0.6, 0.9]).unwrap();
+        assert_relative_eq!(projected.dot(&projected), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(projected[1] / projected[0], 1.5, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_dependent_invariants_are_rejected() {
+        let weights = array![[1.0, 1.0], [2.0, 2.0]];
+        let projection = InvariantProjection::linear(weights).with_targets(array![1.0, 2.0]);
+        assert!(projection.project(&array![1.0, 1.0]).is_err());
+    }
+}
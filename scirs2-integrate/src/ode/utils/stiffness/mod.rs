@@ -6,7 +6,7 @@
 pub mod integration;
 
 use crate::IntegrateFloat;
-use ndarray::Array2;
+use ndarray::{Array1, Array2, ArrayView1};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
@@ -462,3 +462,280 @@ impl<F: IntegrateFloat> MethodSwitchInfo<F> {
         )
     }
 }
+
+/// Estimate the magnitude of the dominant eigenvalue of the Jacobian `∂f/∂y`
+///
+/// Runs a few power iterations on the action of the Jacobian, approximated
+/// by forward differences `J v ≈ (f(t, y + δv) - f(t, y)) / δ`, so the
+/// Jacobian is never formed. A complex pair of dominant eigenvalues makes the
+/// plain iteration oscillate, so the estimate is the geometric mean of the
+/// last two Rayleigh-type ratios `‖J v‖ / ‖v‖`, which is exact for such a pair
+/// of a normal Jacobian.
+///
+/// # Arguments
+///
+/// * `f` - ODE function dy/dt = f(t, y)
+/// * `t` - Time
+/// * `y` - State at which the Jacobian is taken
+/// * `f0` - `f(t, y)`, already evaluated by the caller
+/// * `max_iter` - Maximum number of power iterations
+///
+/// # Returns
+///
+/// The estimate of `|λ_max|` and the number of function evaluations used
+pub fn estimate_dominant_eigenvalue<F, Func>(
+    f: &Func,
+    t: F,
+    y: ArrayView1<F>,
+    f0: ArrayView1<F>,
+    max_iter: usize,
+) -> (F, usize)
+where
+    F: IntegrateFloat,
+    Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+{
+    let n = y.len();
+    let norm = |v: &Array1<F>| {
+        v.iter()
+            .map(|&x| x * x)
+            .fold(F::zero(), |a, b| a + b)
+            .sqrt()
+    };
+    let y_norm = y
+        .iter()
+        .map(|&x| x * x)
+        .fold(F::zero(), |a, b| a + b)
+        .sqrt();
+    let delta = F::epsilon().sqrt() * y_norm.max(F::one());
+
+    // Deterministic start vector with unequal components, so that it is not
+    // orthogonal to the dominant eigenvector of simple symmetric systems
+    let mut v = Array1::from_shape_fn(n, |i| {
+        F::one() + F::from_usize(i).unwrap() / F::from_usize(n.max(1)).unwrap()
+    });
+    let v_norm = norm(&v);
+    v.mapv_inplace(|x| x / v_norm);
+
+    let tolerance = F::from_f64(0.01).unwrap();
+    let mut ratios: Vec<F> = Vec::with_capacity(max_iter);
+    let mut n_eval = 0;
+    for _ in 0..max_iter {
+        let y_pert = &y + &(&v * delta);
+        let jv = (f(t, y_pert.view()) - f0) / delta;
+        n_eval += 1;
+
+        let ratio = norm(&jv);
+        if !ratio.is_finite() || ratio <= F::zero() {
+            break;
+        }
+        ratios.push(ratio);
+        v = jv / ratio;
+
+        // Stop once the paired estimate has settled
+        let k = ratios.len();
+        if k >= 3 {
+            let current = (ratios[k - 1] * ratios[k - 2]).sqrt();
+            let previous = (ratios[k - 2] * ratios[k - 3]).sqrt();
+            if (current - previous).abs() <= tolerance * current {
+                break;
+            }
+        }
+    }
+
+    let estimate = match ratios.as_slice() {
+        [] => F::zero(),
+        [ratio] => *ratio,
+        [.., a, b] => (*a * *b).sqrt(),
+    };
+    (estimate, n_eval)
+}
+
+/// Cheap stiffness indicators for a computed solution
+///
+/// Explicit methods stay stable only while `h·|λ|` stays below a method
+/// dependent bound of order one (2 for Euler, about 3.3 for RK45), where `λ`
+/// is the dominant eigenvalue of the Jacobian. On a stiff problem the step
+/// size is therefore dictated by stability rather than accuracy and `h·|λ|`
+/// hovers near that bound for most of the integration, which these
+/// indicators expose.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StiffnessDiagnostics<F: IntegrateFloat> {
+    /// Largest estimated magnitude of the dominant Jacobian eigenvalue over the
+    /// sampled steps
+    pub dominant_eigenvalue: F,
+    /// Largest `h·|λ|` over the sampled steps
+    pub max_stiffness_index: F,
+    /// Fraction of the sampled steps with `h·|λ| ≥ 1`, i.e. steps whose size is
+    /// likely limited by stability
+    pub stability_limited_fraction: F,
+    /// Ratio of the largest to the smallest accepted step size
+    pub step_size_ratio: F,
+    /// Geometric mean of the ratios between consecutive accepted step sizes
+    pub mean_step_growth: F,
+    /// Number of steps at which the eigenvalue was estimated
+    pub n_samples: usize,
+    /// Function evaluations spent on the diagnostics, not counted in the
+    /// solver statistics
+    pub n_eval: usize,
+}
+
+impl<F: IntegrateFloat> StiffnessDiagnostics<F> {
+    /// Number of steps sampled for eigenvalue estimation
+    const MAX_SAMPLES: usize = 8;
+    /// Power iterations per sampled step
+    const MAX_POWER_ITERATIONS: usize = 8;
+
+    /// Compute the indicators from the accepted steps of a solution
+    ///
+    /// The dominant eigenvalue is estimated at up to eight steps spread over
+    /// the integration, which costs at most 72 evaluations of `f`. The final
+    /// step is ignored, as it is usually shortened to end at `t_end`.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - ODE function dy/dt = f(t, y)
+    /// * `t` - Time points of the accepted steps
+    /// * `y` - Solution values at the time points
+    ///
+    /// # Returns
+    ///
+    /// `None` if the solution has fewer than two accepted steps
+    pub fn from_solution<Func>(f: &Func, t: &[F], y: &[Array1<F>]) -> Option<Self>
+    where
+        Func: Fn(F, ArrayView1<F>) -> Array1<F>,
+    {
+        let steps: Vec<F> = t.windows(2).map(|w| w[1] - w[0]).collect();
+        if steps.len() < 2 || y.len() != t.len() {
+            return None;
+        }
+        let interior = &steps[..steps.len() - 1];
+
+        let (mut h_min, mut h_max) = (F::infinity(), F::zero());
+        for &h in interior {
+            h_min = h_min.min(h.abs());
+            h_max = h_max.max(h.abs());
+        }
+        let mut log_growth = F::zero();
+        for w in interior.windows(2) {
+            log_growth += (w[1] / w[0]).abs().ln();
+        }
+        let mean_step_growth = if interior.len() > 1 {
+            (log_growth / F::from_usize(interior.len() - 1).unwrap()).exp()
+        } else {
+            F::one()
+        };
+
+        let n_samples = interior.len().min(Self::MAX_SAMPLES);
+        let mut dominant_eigenvalue = F::zero();
+        let mut max_stiffness_index = F::zero();
+        let mut limited = 0;
+        let mut n_eval = 0;
+        for k in 0..n_samples {
+            let i = k * interior.len() / n_samples;
+            let f0 = f(t[i], y[i].view());
+            let (lambda, evals) = estimate_dominant_eigenvalue(
+                f,
+                t[i],
+                y[i].view(),
+                f0.view(),
+                Self::MAX_POWER_ITERATIONS,
+            );
+            n_eval += evals + 1;
+
+            let index = interior[i].abs() * lambda;
+            dominant_eigenvalue = dominant_eigenvalue.max(lambda);
+            max_stiffness_index = max_stiffness_index.max(index);
+            if index >= F::one() {
+                limited += 1;
+            }
+        }
+
+        Some(StiffnessDiagnostics {
+            dominant_eigenvalue,
+            max_stiffness_index,
+            stability_limited_fraction: F::from_usize(limited).unwrap()
+                / F::from_usize(n_samples).unwrap(),
+            step_size_ratio: h_max / h_min,
+            mean_step_growth,
+            n_samples,
+            n_eval,
+        })
+    }
+
+    /// Whether most sampled steps were limited by stability rather than accuracy
+    pub fn is_stability_limited(&self) -> bool {
+        self.stability_limited_fraction >= F::from_f64(0.5).unwrap()
+    }
+
+    /// Hint for results of explicit methods that clearly struggle with stiffness
+    ///
+    /// Returns a message recommending an implicit method when most sampled
+    /// steps were stability limited and the integration took at least 100
+    /// accepted steps, or did not reach the end of the interval.
+    pub fn explicit_method_hint(&self, n_accepted: usize, success: bool) -> Option<String> {
+        if !self.is_stability_limited() || (success && n_accepted < 100) {
+            return None;
+        }
+        Some(format!(
+            "The problem appears stiff: the step size was limited by stability \
+             (h*|lambda| up to {:.2}, |lambda| ~ {:.3e}); an implicit method \
+             such as Bdf, Radau or LSODA is likely to be much faster",
+            self.max_stiffness_index.to_f64().unwrap_or(f64::NAN),
+            self.dominant_eigenvalue.to_f64().unwrap_or(f64::NAN),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode::{solve_ivp, ODEMethod, ODEOptions};
+    use ndarray::array;
+
+    #[test]
+    fn test_dominant_eigenvalue() {
+        // Real spectrum {-1, -1000}
+        let f = |_t: f64, y: ArrayView1<f64>| array![-y[0], -1000.0 * y[1]];
+        let y = array![1.0, 1.0];
+        let f0 = f(0.0, y.view());
+        let (lambda, n_eval) = estimate_dominant_eigenvalue(&f, 0.0, y.view(), f0.view(), 20);
+        assert!((lambda - 1000.0).abs() < 10.0);
+        assert!(n_eval <= 20);
+
+        // Complex pair ±50i, for which the plain iteration does not converge
+        let f = |_t: f64, y: ArrayView1<f64>| array![50.0 * y[1], -50.0 * y[0]];
+        let f0 = f(0.0, y.view());
+        let (lambda, _) = estimate_dominant_eigenvalue(&f, 0.0, y.view(), f0.view(), 8);
+        assert!((lambda - 50.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_explicit_method_stiffness_hint() {
+        // Stiff relaxation towards cos(t)
+        let stiff = |t: f64, y: ArrayView1<f64>| array![-1000.0 * (y[0] - t.cos())];
+        let opts = ODEOptions {
+            method: ODEMethod::RK45,
+            max_steps: 10_000,
+            ..Default::default()
+        };
+        let result = solve_ivp(stiff, [0.0, 1.0], array![1.0], Some(opts.clone())).unwrap();
+        let diagnostics = result.stiffness.as_ref().unwrap();
+        assert!((diagnostics.dominant_eigenvalue - 1000.0).abs() < 10.0);
+        assert!(diagnostics.is_stability_limited());
+        assert!(diagnostics.n_eval > 0);
+        assert!(result.message.unwrap().contains("appears stiff"));
+
+        // Accuracy-limited steps on a non-stiff problem
+        let mild = |_t: f64, y: ArrayView1<f64>| array![-y[0]];
+        let opts = ODEOptions {
+            rtol: 1e-6,
+            atol: 1e-8,
+            ..opts
+        };
+        let result = solve_ivp(mild, [0.0, 5.0], array![1.0], Some(opts)).unwrap();
+        let diagnostics = result.stiffness.as_ref().unwrap();
+        assert!((diagnostics.dominant_eigenvalue - 1.0).abs() < 0.05);
+        assert!(!diagnostics.is_stability_limited());
+        assert!(result.message.is_none());
+    }
+}
@@ -390,13 +390,19 @@ impl MOLWaveEquation1D {
             max_step: None,
             min_step: None,
             dense_output: true,
+            t_eval: None,
             max_order: None,
             jac: None,
+            jac_sparsity: None,
             use_banded_jacobian: false,
             ml: None,
             mu: None,
             mass_matrix: None,
             jacobian_strategy: None,
+            linear_solver: Default::default(),
+            preconditioner: None,
+            projection: None,
+            progress: None,
         };
 
         // Apply Dirichlet boundary conditions to initial condition
@@ -20,6 +20,8 @@ pub struct QuadOptions<F: IntegrateFloat> {
     pub use_abs_error: bool,
     /// Use Simpson's rule instead of the default adaptive algorithm
     pub use_simpson: bool,
+    /// Interior points where the integrand is singular or discontinuous
+    pub points: Option<Vec<F>>,
 }
 
 impl<F: IntegrateFloat> Default for QuadOptions<F> {
@@ -27,9 +29,10 @@ impl<F: IntegrateFloat> Default for QuadOptions<F> {
         Self {
             abs_tol: F::from_f64(1.49e-8).unwrap(), // Default from SciPy
             rel_tol: F::from_f64(1.49e-8).unwrap(), // Default from SciPy
-            max_evals: 500,                         // Increased from 50 to ensure convergence
+            max_evals: 2100, // Room for QUADPACK's default of 50 subintervals
             use_abs_error: false,
             use_simpson: false,
+            points: None,
         }
     }
 }
@@ -45,6 +48,8 @@ pub struct QuadResult<F: IntegrateFloat> {
     pub n_evals: usize,
     /// Flag indicating successful convergence
     pub converged: bool,
+    /// Number of subintervals in the final subdivision
+    pub n_subintervals: usize,
 }
 
 /// Compute the definite integral of a function using the composite trapezoid rule
@@ -148,16 +153,25 @@ where
 
 /// Compute the definite integral of a function using adaptive quadrature
 ///
+/// The integral is computed with a globally adaptive 21-point Gauss-Kronrod
+/// scheme combined with Wynn's epsilon extrapolation (the QUADPACK QAGS
+/// algorithm), which copes with integrable endpoint singularities. Either
+/// bound may be infinite; semi-infinite and infinite ranges are mapped onto
+/// (0, 1] by a change of variables, as in QAGI. Interior singularities or
+/// discontinuities can be passed as breakpoints in [`QuadOptions::points`].
+///
 /// # Arguments
 ///
 /// * `f` - The function to integrate
-/// * `a` - Lower bound of integration
-/// * `b` - Upper bound of integration
+/// * `a` - Lower bound of integration (may be `-inf`)
+/// * `b` - Upper bound of integration (may be `inf`)
 /// * `options` - Optional integration parameters
 ///
 /// # Returns
 ///
-/// * `IntegrateResult<QuadResult<F>>` - The result of the integration or an error
+/// * `IntegrateResult<QuadResult<F>>` - The result of the integration or an error.
+///   When the tolerance cannot be met the best estimate is returned with
+///   `converged` set to false.
 ///
 /// # Examples
 ///
@@ -168,6 +182,10 @@ where
 /// let result = quad(|x: f64| x * x, 0.0, 1.0, None).unwrap();
 /// assert!((result.value - 1.0/3.0).abs() < 1e-8);
 /// assert!(result.converged);
+///
+/// // Integrate exp(-x²) over the real line (exact result: √π)
+/// let result = quad(|x: f64| (-x * x).exp(), f64::NEG_INFINITY, f64::INFINITY, None).unwrap();
+/// assert!((result.value - std::f64::consts::PI.sqrt()).abs() < 1e-8);
 /// ```
 pub fn quad<F, Func>(
     f: Func,
@@ -181,7 +199,19 @@ where
 {
     let opts = options.unwrap_or_default();
 
+    if a.is_nan() || b.is_nan() {
+        return Err(IntegrateError::ValueError(
+            "Integration bounds must not be NaN".to_string(),
+        ));
+    }
+
     if opts.use_simpson {
+        if a.is_infinite() || b.is_infinite() {
+            return Err(IntegrateError::ValueError(
+                "Simpson's rule requires finite integration bounds".to_string(),
+            ));
+        }
+
         // Use Simpson's rule with a reasonable number of intervals
         let n = 1000; // Even number for Simpson's rule
         let result = simpson(f, a, b, n)?;
@@ -191,99 +221,781 @@ where
             abs_error: F::from_f64(1e-8).unwrap(), // Rough estimate
             n_evals: n + 1,                        // n+1 evaluations for n intervals
             converged: true,
+            n_subintervals: n,
         });
     }
 
-    // Default to adaptive quadrature using Simpson's rule
-    let mut n_evals = 0;
+    if a == b {
+        return Ok(QuadResult {
+            value: F::zero(),
+            abs_error: F::zero(),
+            n_evals: 0,
+            converged: true,
+            n_subintervals: 0,
+        });
+    }
 
-    // Execute the adaptive integration with a mutable counter
-    let (value, error, converged) = adaptive_quad_impl(f, a, b, &mut n_evals, &opts)?;
+    if a > b {
+        let mut result = quad(f, b, a, Some(opts))?;
+        result.value = -result.value;
+        return Ok(result);
+    }
+
+    let transform = match (a.is_infinite(), b.is_infinite()) {
+        (false, false) => Transform::Identity,
+        (false, true) => Transform::UpperInfinite(a),
+        (true, false) => Transform::LowerInfinite(b),
+        (true, true) => Transform::BothInfinite,
+    };
+    let (lower, upper) = match transform {
+        Transform::Identity => (a, b),
+        _ => (F::zero(), F::one()),
+    };
+
+    // Breakpoints split the range into pieces that are integrated separately,
+    // so that no rule ever samples the function at a singular point
+    let mut breaks = Vec::new();
+    for &p in opts.points.iter().flatten() {
+        if !(p > a && p < b) {
+            return Err(IntegrateError::ValueError(format!(
+                "Breakpoint {} lies outside the integration range",
+                p
+            )));
+        }
+        let t = transform.to_unit(p);
+        if t > lower && t < upper {
+            breaks.push(t);
+        }
+    }
+    breaks.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    breaks.dedup();
+    breaks.insert(0, lower);
+    breaks.push(upper);
+
+    let evals_per_node = transform.evals_per_node();
+    // Every bisection costs two rule applications, the initial pieces one each
+    let n_rules = opts.max_evals / (21 * evals_per_node);
+    let limit = ((n_rules + breaks.len() - 1) / 2).max(1);
+    let tolerance = |area: F| {
+        if opts.use_abs_error {
+            opts.abs_tol
+        } else {
+            opts.abs_tol.max(opts.rel_tol * area.abs())
+        }
+    };
+
+    let outcome = adaptive_gauss_kronrod(|t| transform.evaluate(f, t), &breaks, limit, tolerance);
 
     Ok(QuadResult {
-        value,
-        abs_error: error,
-        n_evals,
-        converged,
+        value: outcome.value,
+        abs_error: outcome.abs_error,
+        n_evals: 21 * outcome.n_rules * evals_per_node,
+        converged: outcome.converged,
+        n_subintervals: outcome.n_subintervals,
     })
 }
 
-/// Internal implementation of adaptive quadrature
-fn adaptive_quad_impl<F, Func>(
-    f: Func,
+/// Change of variables applied before the adaptive integration
+#[derive(Debug, Clone, Copy)]
+enum Transform<F> {
+    /// Finite range, integrated as is
+    Identity,
+    /// (a, ∞) mapped onto (0, 1] by x = a + (1 - t) / t
+    UpperInfinite(F),
+    /// (-∞, b) mapped onto (0, 1] by x = b - (1 - t) / t
+    LowerInfinite(F),
+    /// (-∞, ∞) folded onto (0, ∞) and mapped onto (0, 1] by x = (1 - t) / t
+    BothInfinite,
+}
+
+impl<F: IntegrateFloat> Transform<F> {
+    /// Maps a point of the original range to the integration variable
+    fn to_unit(self, x: F) -> F {
+        match self {
+            Transform::Identity => x,
+            Transform::UpperInfinite(a) => F::one() / (F::one() + x - a),
+            Transform::LowerInfinite(b) => F::one() / (F::one() + b - x),
+            Transform::BothInfinite => F::one() / (F::one() + x.abs()),
+        }
+    }
+
+    /// Evaluates the transformed integrand at `t`
+    fn evaluate<Func: Fn(F) -> F>(self, f: Func, t: F) -> F {
+        let s = (F::one() - t) / t;
+        let jacobian = F::one() / (t * t);
+        match self {
+            Transform::Identity => f(t),
+            Transform::UpperInfinite(a) => f(a + s) * jacobian,
+            Transform::LowerInfinite(b) => f(b - s) * jacobian,
+            Transform::BothInfinite => (f(s) + f(-s)) * jacobian,
+        }
+    }
+
+    /// Number of calls to the original function per transformed evaluation
+    fn evals_per_node(self) -> usize {
+        match self {
+            Transform::BothInfinite => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// Abscissae of the 21-point Kronrod rule on [-1, 1] (non-negative half)
+const XGK21: [f64; 11] = [
+    0.9956571630258081,
+    0.9739065285171717,
+    0.9301574913557082,
+    0.8650633666889845,
+    0.7808177265864169,
+    0.6794095682990244,
+    0.5627571346686047,
+    0.4333953941292472,
+    0.2943928627014602,
+    0.14887433898163122,
+    0.0,
+];
+
+/// Weights of the 21-point Kronrod rule
+const WGK21: [f64; 11] = [
+    0.011694638867371874,
+    0.032558162307964725,
+    0.054755896574351995,
+    0.07503967481091996,
+    0.0931254545836976,
+    0.10938715880229764,
+    0.12349197626206584,
+    0.13470921731147334,
+    0.14277593857706009,
+    0.14773910490133849,
+    0.1494455540029169,
+];
+
+/// Weights of the embedded 10-point Gauss rule (at the odd Kronrod abscissae)
+const WG10: [f64; 5] = [
+    0.06667134430868814,
+    0.1494513491505806,
+    0.21908636251598204,
+    0.26926671930999635,
+    0.29552422471475287,
+];
+
+/// Result of the 21-point Gauss-Kronrod rule on one interval
+struct RuleEstimate<F> {
+    /// Kronrod approximation of the integral
+    value: F,
+    /// Error estimate from the embedded Gauss rule
+    error: F,
+    /// Approximation of the integral of |f|
+    resabs: F,
+    /// Approximation of the integral of |f - mean|
+    resasc: F,
+}
+
+/// Applies the 21-point Gauss-Kronrod rule to `f` on [a, b]
+fn gauss_kronrod21<F, Func>(f: &Func, a: F, b: F) -> RuleEstimate<F>
+where
+    F: IntegrateFloat,
+    Func: Fn(F) -> F,
+{
+    let half = F::from_f64(0.5).unwrap();
+    let center = half * (a + b);
+    let half_length = half * (b - a);
+    let weight = |w: f64| F::from_f64(w).unwrap();
+
+    let f_center = f(center);
+    let mut result_gauss = F::zero();
+    let mut result_kronrod = f_center * weight(WGK21[10]);
+    let mut resabs = result_kronrod.abs();
+    let mut samples = [(F::zero(), F::zero()); 10];
+    for (j, sample) in samples.iter_mut().enumerate() {
+        let dx = half_length * F::from_f64(XGK21[j]).unwrap();
+        let (f1, f2) = (f(center - dx), f(center + dx));
+        *sample = (f1, f2);
+        result_kronrod += weight(WGK21[j]) * (f1 + f2);
+        resabs += weight(WGK21[j]) * (f1.abs() + f2.abs());
+        if j % 2 == 1 {
+            result_gauss += weight(WG10[j / 2]) * (f1 + f2);
+        }
+    }
+
+    let mean = half * result_kronrod;
+    let mut resasc = weight(WGK21[10]) * (f_center - mean).abs();
+    for (j, &(f1, f2)) in samples.iter().enumerate() {
+        resasc += weight(WGK21[j]) * ((f1 - mean).abs() + (f2 - mean).abs());
+    }
+
+    let value = result_kronrod * half_length;
+    let resabs = resabs * half_length.abs();
+    let resasc = resasc * half_length.abs();
+    let error = rescale_error(
+        (result_kronrod - result_gauss) * half_length,
+        resabs,
+        resasc,
+    );
+
+    RuleEstimate {
+        value,
+        error,
+        resabs,
+        resasc,
+    }
+}
+
+/// QUADPACK's scaling of the raw Gauss-Kronrod difference into an error estimate
+fn rescale_error<F: IntegrateFloat>(error: F, resabs: F, resasc: F) -> F {
+    let mut error = error.abs();
+    if resasc != F::zero() && error != F::zero() {
+        let scale = (F::from_f64(200.0).unwrap() * error / resasc).powf(F::from_f64(1.5).unwrap());
+        error = resasc * scale.min(F::one());
+    }
+    let fifty_eps = F::from_f64(50.0).unwrap() * F::epsilon();
+    if resabs > F::min_positive_value() / fifty_eps {
+        error = error.max(fifty_eps * resabs);
+    }
+    error
+}
+
+/// One interval of the adaptive subdivision
+struct Subinterval<F> {
     a: F,
     b: F,
-    n_evals: &mut usize,
-    options: &QuadOptions<F>,
-) -> IntegrateResult<(F, F, bool)>
-// (value, error, converged)
+    value: F,
+    error: F,
+    /// Number of bisections that produced this interval
+    level: usize,
+}
+
+/// Subintervals kept ordered by decreasing error (QUADPACK's `qpsrt` bookkeeping)
+struct Workspace<F> {
+    intervals: Vec<Subinterval<F>>,
+    /// Interval indices; the leading part is sorted by decreasing error
+    order: Vec<usize>,
+    /// Position in `order` of the next interval to bisect
+    nrmax: usize,
+    /// Index of the next interval to bisect
+    current: usize,
+    maximum_level: usize,
+    limit: usize,
+}
+
+impl<F: IntegrateFloat> Workspace<F> {
+    fn new(intervals: Vec<Subinterval<F>>, limit: usize) -> Self {
+        let mut order: Vec<usize> = (0..intervals.len()).collect();
+        order.sort_by(|&i, &j| {
+            intervals[j]
+                .error
+                .partial_cmp(&intervals[i].error)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let current = order[0];
+        Self {
+            intervals,
+            order,
+            nrmax: 0,
+            current,
+            maximum_level: 0,
+            limit,
+        }
+    }
+
+    /// Replaces the current interval by its two halves
+    fn bisect(&mut self, left: Subinterval<F>, right: Subinterval<F>) {
+        let level = self.intervals[self.current].level + 1;
+        let (larger, smaller) = if right.error > left.error {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        self.intervals[self.current] = Subinterval { level, ..larger };
+        self.intervals.push(Subinterval { level, ..smaller });
+        self.order.push(0);
+        self.maximum_level = self.maximum_level.max(level);
+        self.sort();
+    }
+
+    /// Inserts the two newest error estimates into the descending order
+    fn sort(&mut self) {
+        let last = self.intervals.len() - 1;
+        let mut i_nrmax = self.nrmax;
+        let i_max = self.order[i_nrmax];
+
+        if last < 2 {
+            self.order[0] = 0;
+            self.order[1] = 1;
+            self.current = i_max;
+            return;
+        }
+
+        let error = |k: usize| self.intervals[k].error;
+        let errmax = error(i_max);
+        // A bisection that increased the error moves the interval up the list
+        while i_nrmax > 0 && errmax > error(self.order[i_nrmax - 1]) {
+            self.order[i_nrmax] = self.order[i_nrmax - 1];
+            i_nrmax -= 1;
+        }
+
+        // Only as many entries as there are remaining bisections are kept sorted
+        let top = if last < self.limit / 2 + 2 {
+            last
+        } else {
+            self.limit + 1 - last
+        };
+
+        let mut i = i_nrmax + 1;
+        while i < top && errmax < error(self.order[i]) {
+            self.order[i - 1] = self.order[i];
+            i += 1;
+        }
+        self.order[i - 1] = i_max;
+
+        let errmin = error(last);
+        let mut k = top as isize - 1;
+        while k > i as isize - 2 && errmin >= error(self.order[k as usize]) {
+            self.order[k as usize + 1] = self.order[k as usize];
+            k -= 1;
+        }
+        self.order[(k + 1) as usize] = last;
+
+        self.nrmax = i_nrmax;
+        self.current = self.order[i_nrmax];
+    }
+
+    /// Whether the interval to bisect next is larger than the smallest one
+    fn large_interval(&self) -> bool {
+        self.intervals[self.current].level < self.maximum_level
+    }
+
+    /// Moves on to the largest-error interval that is not yet of minimal size
+    fn increase_nrmax(&mut self) -> bool {
+        let last = self.intervals.len() - 1;
+        let jupbnd = if last > 1 + self.limit / 2 {
+            (self.limit + 1).saturating_sub(last)
+        } else {
+            last
+        };
+        for _ in self.nrmax..=jupbnd {
+            if self.nrmax >= self.order.len() {
+                break;
+            }
+            self.current = self.order[self.nrmax];
+            if self.intervals[self.current].level < self.maximum_level {
+                return true;
+            }
+            self.nrmax += 1;
+        }
+        false
+    }
+
+    fn reset_nrmax(&mut self) {
+        self.nrmax = 0;
+        self.current = self.order[0];
+    }
+
+    fn total(&self) -> F {
+        self.intervals
+            .iter()
+            .fold(F::zero(), |sum, interval| sum + interval.value)
+    }
+}
+
+/// Table of partial sums for Wynn's epsilon algorithm (QUADPACK's `qelg`)
+struct EpsilonTable<F> {
+    entries: Vec<F>,
+    n: usize,
+    /// Last three extrapolated values, used for the error estimate
+    last_results: [F; 3],
+    n_results: usize,
+}
+
+impl<F: IntegrateFloat> EpsilonTable<F> {
+    const CAPACITY: usize = 52;
+
+    fn new() -> Self {
+        Self {
+            entries: vec![F::zero(); Self::CAPACITY + 3],
+            n: 0,
+            last_results: [F::zero(); 3],
+            n_results: 0,
+        }
+    }
+
+    fn append(&mut self, value: F) {
+        if self.n < Self::CAPACITY {
+            self.entries[self.n] = value;
+            self.n += 1;
+        }
+    }
+
+    /// Extrapolates the sequence of partial sums, returning (limit, error)
+    fn extrapolate(&mut self) -> (F, F) {
+        let eps = F::epsilon();
+        let five = F::from_f64(5.0).unwrap();
+        let epstab = &mut self.entries;
+        let n = self.n - 1;
+        let current = epstab[n];
+        let mut result = current;
+        let mut abserr = F::max_value();
+
+        if n < 2 {
+            return (result, (five * eps * current.abs()).max(abserr));
+        }
+
+        epstab[n + 2] = epstab[n];
+        epstab[n] = F::max_value();
+        let newelm = n / 2;
+        let mut n_final = n;
+
+        for i in 0..newelm {
+            let res = epstab[n - 2 * i + 2];
+            let e0 = epstab[n - 2 * i - 2];
+            let e1 = epstab[n - 2 * i - 1];
+            let e2 = res;
+
+            let delta2 = e2 - e1;
+            let err2 = delta2.abs();
+            let tol2 = e2.abs().max(e1.abs()) * eps;
+            let delta3 = e1 - e0;
+            let err3 = delta3.abs();
+            let tol3 = e1.abs().max(e0.abs()) * eps;
+
+            if err2 <= tol2 && err3 <= tol3 {
+                // The sequence has converged to machine accuracy
+                return (res, (err2 + err3).max(five * eps * res.abs()));
+            }
+
+            let e3 = epstab[n - 2 * i];
+            epstab[n - 2 * i] = e1;
+            let delta1 = e1 - e3;
+            let err1 = delta1.abs();
+            let tol1 = e1.abs().max(e3.abs()) * eps;
+
+            // Two elements are too close: omit this part of the table
+            if err1 <= tol1 || err2 <= tol2 || err3 <= tol3 {
+                n_final = 2 * i;
+                break;
+            }
+
+            let ss = F::one() / delta1 + F::one() / delta2 - F::one() / delta3;
+            if (ss * e1).abs() <= F::from_f64(1e-4).unwrap() {
+                n_final = 2 * i;
+                break;
+            }
+
+            let res = e1 + F::one() / ss;
+            epstab[n - 2 * i] = res;
+            let error = err2 + (res - e2).abs() + err3;
+            if error <= abserr {
+                abserr = error;
+                result = res;
+            }
+        }
+
+        // Shift the table
+        let limexp = 50 - 1;
+        if n_final == limexp {
+            n_final = 2 * (limexp / 2) - 1;
+        }
+        let start = if n % 2 == 1 { 1 } else { 0 };
+        for i in 0..=newelm {
+            epstab[start + 2 * i] = epstab[start + 2 * i + 2];
+        }
+        if n != n_final {
+            for i in 0..=n_final {
+                epstab[i] = epstab[n - n_final + i];
+            }
+        }
+        self.n = n_final + 1;
+
+        if self.n_results < 3 {
+            self.last_results[self.n_results] = result;
+            abserr = F::max_value();
+        } else {
+            abserr = self
+                .last_results
+                .iter()
+                .fold(F::zero(), |sum, &r| sum + (result - r).abs());
+            self.last_results = [self.last_results[1], self.last_results[2], result];
+        }
+        self.n_results += 1;
+
+        (result, abserr.max(five * eps * result.abs()))
+    }
+}
+
+/// Outcome of the adaptive Gauss-Kronrod integration
+struct AdaptiveOutcome<F> {
+    value: F,
+    abs_error: F,
+    /// Number of 21-point rule applications
+    n_rules: usize,
+    n_subintervals: usize,
+    converged: bool,
+}
+
+/// Globally adaptive Gauss-Kronrod integration with epsilon extrapolation
+///
+/// A port of QUADPACK's QAGS/QAGP driver: `breaks` are the initial interval
+/// endpoints, `limit` bounds the number of subintervals and `tolerance`
+/// maps the current integral estimate to the requested absolute error.
+fn adaptive_gauss_kronrod<F, Func, Tol>(
+    f: Func,
+    breaks: &[F],
+    limit: usize,
+    tolerance: Tol,
+) -> AdaptiveOutcome<F>
 where
     F: IntegrateFloat,
-    Func: Fn(F) -> F + Copy,
+    Func: Fn(F) -> F,
+    Tol: Fn(F) -> F,
 {
-    // Calculate coarse estimate
-    let n_initial = 10; // Starting with 10 intervals
-    let mut eval_count_coarse = 0;
-    let coarse_result = {
-        // A scope to limit the lifetime of the closure
-        let f_with_count = |x: F| {
-            eval_count_coarse += 1;
-            f(x)
-        };
-        simpson(f_with_count, a, b, n_initial)?
+    let fifty_eps = F::from_f64(50.0).unwrap() * F::epsilon();
+    let mut n_rules = 0;
+
+    // Initial estimates on the pieces between breakpoints
+    let mut intervals = Vec::with_capacity(breaks.len() - 1);
+    let (mut area, mut errsum, mut resabs0, mut resasc0) =
+        (F::zero(), F::zero(), F::zero(), F::zero());
+    for w in breaks.windows(2) {
+        let estimate = gauss_kronrod21(&f, w[0], w[1]);
+        n_rules += 1;
+        area += estimate.value;
+        errsum += estimate.error;
+        resabs0 += estimate.resabs;
+        resasc0 += estimate.resasc;
+        intervals.push(Subinterval {
+            a: w[0],
+            b: w[1],
+            value: estimate.value,
+            error: estimate.error,
+            level: 0,
+        });
+    }
+    let n_initial = intervals.len();
+
+    let finish = |value: F, abs_error: F, n_rules: usize, n_subintervals: usize, converged| {
+        AdaptiveOutcome {
+            value,
+            abs_error,
+            n_rules,
+            n_subintervals,
+            converged,
+        }
     };
-    *n_evals += eval_count_coarse;
-
-    // Calculate refined estimate
-    let n_refined = 20; // Double the number of intervals
-    let mut eval_count_refined = 0;
-    let refined_result = {
-        // A scope to limit the lifetime of the closure
-        let f_with_count = |x: F| {
-            eval_count_refined += 1;
-            f(x)
+
+    let mut tol = tolerance(area);
+    if errsum <= fifty_eps * resabs0 && errsum > tol {
+        // Round-off prevents reaching the requested accuracy
+        return finish(area, errsum, n_rules, n_initial, false);
+    }
+    if (errsum <= tol && errsum != resasc0) || errsum == F::zero() {
+        return finish(area, errsum, n_rules, n_initial, true);
+    }
+    if n_initial >= limit {
+        return finish(area, errsum, n_rules, n_initial, false);
+    }
+
+    let positive_integrand = area.abs() >= (F::one() - fifty_eps) * resabs0;
+    let mut workspace = Workspace::new(intervals, limit);
+    let mut table = EpsilonTable::new();
+    table.append(area);
+
+    let mut res_ext = area;
+    let mut err_ext = F::max_value();
+    let mut correction = F::zero();
+    let mut error_over_large_intervals = errsum;
+    let mut ertest = tol;
+    let mut ktmin = 0;
+    let mut extrapolate = false;
+    let mut disallow_extrapolation = false;
+    // 0: none, 1: limit, 2: round-off, 3: bad behaviour, 4: no extrapolation
+    // convergence, 5: divergence
+    let mut error_type = 0;
+    let mut roundoff_error = false;
+    let (mut roundoff1, mut roundoff2, mut roundoff3) = (0, 0, 0);
+
+    let converged_sum = loop {
+        let (a_i, b_i, r_i, e_i, current_level) = {
+            let interval = &workspace.intervals[workspace.current];
+            (
+                interval.a,
+                interval.b,
+                interval.value,
+                interval.error,
+                interval.level + 1,
+            )
         };
-        simpson(f_with_count, a, b, n_refined)?
-    };
-    *n_evals += eval_count_refined;
-
-    // Error estimation
-    let error = (refined_result - coarse_result).abs();
-    let tolerance = if options.use_abs_error {
-        options.abs_tol
-    } else {
-        options.abs_tol + options.rel_tol * refined_result.abs()
-    };
+        let mid = F::from_f64(0.5).unwrap() * (a_i + b_i);
+        let left = gauss_kronrod21(&f, a_i, mid);
+        let right = gauss_kronrod21(&f, mid, b_i);
+        n_rules += 2;
 
-    // Check for convergence
-    let converged = error <= tolerance || *n_evals >= options.max_evals;
+        let area12 = left.value + right.value;
+        let error12 = left.error + right.error;
+        errsum += error12 - e_i;
+        area += area12 - r_i;
+        tol = tolerance(area);
 
-    if *n_evals >= options.max_evals && error > tolerance {
-        return Err(IntegrateError::ConvergenceError(format!(
-            "Failed to converge after {} function evaluations",
-            *n_evals
-        )));
-    }
+        if left.resasc != left.error && right.resasc != right.error {
+            let delta = r_i - area12;
+            if delta.abs() <= F::from_f64(1e-5).unwrap() * area12.abs()
+                && error12 >= F::from_f64(0.99).unwrap() * e_i
+            {
+                if extrapolate {
+                    roundoff2 += 1;
+                } else {
+                    roundoff1 += 1;
+                }
+            }
+            if workspace.intervals.len() > 10 && error12 > e_i {
+                roundoff3 += 1;
+            }
+        }
+        if roundoff1 + roundoff2 >= 10 || roundoff3 >= 20 {
+            error_type = 2;
+        }
+        if roundoff2 >= 5 {
+            roundoff_error = true;
+        }
+
+        // The interval has shrunk to the resolution of the floating point grid
+        let resolution = (F::one() + F::from_f64(100.0).unwrap() * F::epsilon())
+            * (mid.abs() + F::from_f64(1000.0).unwrap() * F::min_positive_value());
+        if a_i.abs() <= resolution && b_i.abs() <= resolution {
+            error_type = 3;
+        }
+
+        workspace.bisect(
+            Subinterval {
+                a: a_i,
+                b: mid,
+                value: left.value,
+                error: left.error,
+                level: 0,
+            },
+            Subinterval {
+                a: mid,
+                b: b_i,
+                value: right.value,
+                error: right.error,
+                level: 0,
+            },
+        );
+
+        if errsum <= tol {
+            break true;
+        }
+        if error_type != 0 {
+            break false;
+        }
+        if workspace.intervals.len() >= limit {
+            error_type = 1;
+            break false;
+        }
+
+        if n_initial == 1 && workspace.intervals.len() == 2 {
+            error_over_large_intervals = errsum;
+            ertest = tol;
+            table.append(area);
+            continue;
+        }
+        if disallow_extrapolation {
+            continue;
+        }
+
+        error_over_large_intervals -= e_i;
+        if current_level < workspace.maximum_level {
+            error_over_large_intervals += error12;
+        }
 
-    // If we haven't reached desired accuracy, divide and conquer
-    if !converged {
-        let mid = (a + b) / F::from_f64(2.0).unwrap();
+        if !extrapolate {
+            // Keep bisecting until the next interval is one of the smallest
+            if workspace.large_interval() {
+                continue;
+            }
+            extrapolate = true;
+            workspace.nrmax = 1;
+        }
+
+        if !roundoff_error && error_over_large_intervals > ertest && workspace.increase_nrmax() {
+            continue;
+        }
 
-        // Recursively integrate the two halves
-        let (left_value, left_error, left_converged) =
-            adaptive_quad_impl(f, a, mid, n_evals, options)?;
-        let (right_value, right_error, right_converged) =
-            adaptive_quad_impl(f, mid, b, n_evals, options)?;
+        table.append(area);
+        let (reseps, abseps) = table.extrapolate();
+        ktmin += 1;
+        if ktmin > 5 && err_ext < F::from_f64(1e-3).unwrap() * errsum {
+            error_type = 4;
+        }
+        if abseps < err_ext {
+            ktmin = 0;
+            err_ext = abseps;
+            res_ext = reseps;
+            correction = error_over_large_intervals;
+            ertest = tolerance(reseps);
+            if err_ext <= ertest {
+                break false;
+            }
+        }
 
-        // Combine the results
-        let value = left_value + right_value;
-        let abs_error = left_error + right_error;
-        let sub_converged = left_converged && right_converged;
+        // Prepare bisection of the smallest interval
+        if table.n == 1 {
+            disallow_extrapolation = true;
+        }
+        if error_type == 4 {
+            break false;
+        }
 
-        return Ok((value, abs_error, sub_converged));
+        workspace.reset_nrmax();
+        extrapolate = false;
+        error_over_large_intervals = errsum;
+    };
+
+    let n_subintervals = workspace.intervals.len();
+    if converged_sum || err_ext == F::max_value() {
+        return finish(
+            workspace.total(),
+            errsum,
+            n_rules,
+            n_subintervals,
+            converged_sum,
+        );
     }
 
-    Ok((refined_result, error, converged))
+    // Decide between the extrapolated value and the plain sum
+    if error_type != 0 || roundoff_error {
+        if roundoff_error {
+            err_ext += correction;
+        }
+        if error_type == 0 {
+            error_type = 3;
+        }
+        let use_sum = if res_ext != F::zero() && area != F::zero() {
+            err_ext / res_ext.abs() > errsum / area.abs()
+        } else {
+            err_ext > errsum
+        };
+        if use_sum {
+            return finish(workspace.total(), errsum, n_rules, n_subintervals, false);
+        }
+        if area == F::zero() {
+            return finish(res_ext, err_ext, n_rules, n_subintervals, false);
+        }
+    }
+
+    // Test on divergence
+    let max_area = res_ext.abs().max(area.abs());
+    if !positive_integrand && max_area < F::from_f64(0.01).unwrap() * resabs0 {
+        return finish(res_ext, err_ext, n_rules, n_subintervals, error_type == 0);
+    }
+    let ratio = res_ext / area;
+    if ratio < F::from_f64(0.01).unwrap()
+        || ratio > F::from_f64(100.0).unwrap()
+        || errsum > area.abs()
+    {
+        error_type = 5;
+    }
+
+    finish(res_ext, err_ext, n_rules, n_subintervals, error_type == 0)
 }
 
 // Simple implementation of Simpson's rule with step counting
@@ -396,4 +1108,82 @@ mod tests {
         .unwrap();
         assert_relative_eq!(result.value, 1.0, epsilon = 1e-6);
     }
+
+    #[test]
+    fn test_quad_infinite_ranges() {
+        // ∫_0^∞ e^{-x} dx = 1
+        let result = quad(|x: f64| (-x).exp(), 0.0, f64::INFINITY, None).unwrap();
+        assert!(result.converged);
+        assert_relative_eq!(result.value, 1.0, epsilon = 1e-10);
+        assert!(result.abs_error < 1e-8);
+
+        // ∫_{-∞}^∞ e^{-x²} dx = √π
+        let gaussian = |x: f64| (-x * x).exp();
+        let pi = std::f64::consts::PI;
+        let result = quad(gaussian, f64::NEG_INFINITY, f64::INFINITY, None).unwrap();
+        assert!(result.converged);
+        assert_relative_eq!(result.value, pi.sqrt(), epsilon = 1e-10);
+
+        // ∫_{-∞}^0 1/(1 + x²) dx = π/2, and reversed bounds flip the sign
+        let result = quad(|x: f64| 1.0 / (1.0 + x * x), 0.0, f64::NEG_INFINITY, None).unwrap();
+        assert!(result.converged);
+        assert_relative_eq!(result.value, -pi / 2.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_quad_singularities() {
+        // Integrable endpoint singularity: ∫_0^1 ln(x)/√x dx = -4
+        let result = quad(|x: f64| x.ln() / x.sqrt(), 0.0, 1.0, None).unwrap();
+        assert!(result.converged);
+        assert_relative_eq!(result.value, -4.0, epsilon = 1e-8);
+        assert!(result.n_subintervals > 1);
+
+        // Interior singularity at 0.3: ∫_0^1 |x - 0.3|^{-1/2} dx = 2(√0.3 + √0.7)
+        let f = |x: f64| 1.0 / (x - 0.3).abs().sqrt();
+        let exact = 2.0 * (0.3f64.sqrt() + 0.7f64.sqrt());
+        let options = QuadOptions {
+            points: Some(vec![0.3]),
+            ..Default::default()
+        };
+        let result = quad(f, 0.0, 1.0, Some(options)).unwrap();
+        assert!(result.converged);
+        assert_relative_eq!(result.value, exact, epsilon = 1e-8);
+        assert!(result.abs_error < 1e-6);
+
+        // A breakpoint on a semi-infinite range: ∫_0^∞ e^{-x} / √|x - 1| dx
+        let options = QuadOptions {
+            points: Some(vec![1.0]),
+            ..Default::default()
+        };
+        let result = quad(
+            |x: f64| (-x).exp() / (x - 1.0).abs().sqrt(),
+            0.0,
+            f64::INFINITY,
+            Some(options),
+        )
+        .unwrap();
+        assert!(result.converged);
+        // Substituting x = 1 ∓ u² gives (2/e)(∫_0^1 e^{u²} du + √π/2)
+        assert_relative_eq!(result.value, 1.7282083459987916, epsilon = 1e-8);
+
+        // Breakpoints outside the range are rejected
+        let options = QuadOptions {
+            points: Some(vec![2.0]),
+            ..Default::default()
+        };
+        assert!(quad(|x: f64| x, 0.0, 1.0, Some(options)).is_err());
+    }
+
+    #[test]
+    fn test_quad_reports_non_convergence() {
+        // A strongly oscillating integrand cannot be resolved with a few subintervals
+        let options = QuadOptions {
+            max_evals: 5 * 21,
+            ..Default::default()
+        };
+        let result = quad(|x: f64| (1000.0 * x).sin() * x, 0.0, 10.0, Some(options)).unwrap();
+        assert!(!result.converged);
+        assert!(result.n_subintervals <= 5);
+        assert!(result.n_evals <= 5 * 21);
+    }
 }
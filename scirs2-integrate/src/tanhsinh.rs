@@ -4,19 +4,32 @@
 //! which is particularly effective for improper integrals and functions with endpoint singularities.
 //!
 //! The method uses a change of variable x = tanh(π/2 · sinh(t)) which transforms the interval [-1, 1]
-//! to (-∞, ∞) and clusters the quadrature points near the endpoints.
+//! to (-∞, ∞) and clusters the quadrature points near the endpoints. The trapezoidal rule in `t`
+//! is refined level by level, halving the step each time and reusing all previous evaluations.
+//!
+//! The distance of each node to the nearest endpoint is computed without cancellation.
+//! [`tanhsinh_complement`] passes it to the integrand, so integrable endpoint singularities
+//! written in terms of it are resolved to near machine precision. An integrand of `x` alone
+//! only sees the rounded node, which limits the accuracy of singularities at nonzero
+//! endpoints (to about `1e-8` for an inverse square root); the error estimate includes the
+//! part of the integral lost there.
 
 use std::fmt;
 
+use num_complex::Complex64;
+
 use crate::error::{IntegrateError, IntegrateResult};
 
 /// Result type for tanh-sinh integration
+///
+/// The error estimate has type `E`, which is the same as the integral for real
+/// integrands and `f64` for complex ones.
 #[derive(Clone, Debug)]
-pub struct TanhSinhResult<T> {
+pub struct TanhSinhResult<T, E = T> {
     /// The estimate of the integral
     pub integral: T,
     /// The error estimate
-    pub error: T,
+    pub error: E,
     /// The number of function evaluations
     pub nfev: usize,
     /// The maximum level of refinement used
@@ -25,7 +38,7 @@ pub struct TanhSinhResult<T> {
     pub success: bool,
 }
 
-impl<T: fmt::Display> fmt::Display for TanhSinhResult<T> {
+impl<T: fmt::Display, E: fmt::Display> fmt::Display for TanhSinhResult<T, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -62,111 +75,347 @@ impl Default for TanhSinhOptions {
     }
 }
 
-/// Nodes and weights for tanh-sinh quadrature
-#[derive(Clone, Debug)]
-struct TanhSinhRule {
-    /// Points in the range (-1, 1)
-    points: Vec<f64>,
-    /// Corresponding weights
-    weights: Vec<f64>,
+/// Values that can be accumulated by the tanh-sinh trapezoidal sums
+trait TanhSinhValue: Copy {
+    /// The empty sum
+    fn zero() -> Self;
+    /// Sum of two values
+    fn add(self, other: Self) -> Self;
+    /// Value multiplied by a positive weight
+    fn weighted(self, weight: f64) -> Self;
+    /// Whether the value can contribute to the sum
+    fn is_finite(self) -> bool;
+    /// Whether the value no longer changes `total` in floating point
+    fn is_negligible(self, total: Self) -> bool;
+    /// Distance between two values, used for the error estimate
+    fn distance(self, other: Self) -> f64;
+    /// Magnitude, used for the relative tolerance
+    fn magnitude(self) -> f64;
 }
 
-impl TanhSinhRule {
-    /// Generate a rule at the specified level
-    /// This generates ALL points up to and including this level
-    fn new(level: usize) -> Self {
-        let mut points = Vec::new();
-        let mut weights = Vec::new();
+impl TanhSinhValue for f64 {
+    fn zero() -> Self {
+        0.0
+    }
 
-        // Base step size for this level
-        let h = 1.0 / (1 << level) as f64;
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
 
-        // Maximum value of j*h before weights become negligible
-        let max_t = 3.5;
-        let max_j = (max_t / h) as i32;
+    fn weighted(self, weight: f64) -> Self {
+        self * weight
+    }
 
-        // Generate points for j = 0, ±1, ±2, ...
-        for j in -max_j..=max_j {
-            let t = j as f64 * h;
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
 
-            // Compute x = tanh(π/2 * sinh(t))
-            let sinh_t = t.sinh();
-            let arg = std::f64::consts::FRAC_PI_2 * sinh_t;
+    fn is_negligible(self, total: Self) -> bool {
+        self.abs() <= f64::EPSILON * total.abs()
+    }
 
-            // Skip if argument would cause overflow
-            if arg.abs() > 100.0 {
-                continue;
-            }
+    fn distance(self, other: Self) -> f64 {
+        (self - other).abs()
+    }
 
-            let x = arg.tanh();
+    fn magnitude(self) -> f64 {
+        self.abs()
+    }
+}
 
-            // Compute weight w = h * (π/2) * cosh(t) / cosh(π/2 * sinh(t))²
-            let cosh_t = t.cosh();
-            let cosh_arg = arg.cosh();
+impl TanhSinhValue for Complex64 {
+    fn zero() -> Self {
+        Complex64::new(0.0, 0.0)
+    }
 
-            // Avoid overflow
-            if cosh_arg > 1e100 {
-                continue;
-            }
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
 
-            let w = h * std::f64::consts::FRAC_PI_2 * cosh_t / (cosh_arg * cosh_arg);
+    fn weighted(self, weight: f64) -> Self {
+        self * weight
+    }
 
-            // Only add if weight is significant
-            if w > 1e-15 && x.abs() < 1.0 - 1e-10 {
-                points.push(x);
-                weights.push(w);
-            }
+    fn is_finite(self) -> bool {
+        Complex64::is_finite(self)
+    }
+
+    fn is_negligible(self, total: Self) -> bool {
+        self.norm() <= f64::EPSILON * total.norm()
+    }
+
+    fn distance(self, other: Self) -> f64 {
+        (self - other).norm()
+    }
+
+    fn magnitude(self) -> f64 {
+        self.norm()
+    }
+}
+
+/// Logarithm of a positive value, for integration in log space
+#[derive(Clone, Copy, Debug)]
+struct LogValue(f64);
+
+impl TanhSinhValue for LogValue {
+    fn zero() -> Self {
+        LogValue(f64::NEG_INFINITY)
+    }
+
+    fn add(self, other: Self) -> Self {
+        let (hi, lo) = if self.0 >= other.0 {
+            (self.0, other.0)
+        } else {
+            (other.0, self.0)
+        };
+        if lo == f64::NEG_INFINITY {
+            LogValue(hi)
+        } else {
+            LogValue(hi + (lo - hi).exp().ln_1p())
         }
+    }
 
-        Self { points, weights }
+    fn weighted(self, weight: f64) -> Self {
+        LogValue(self.0 + weight.ln())
     }
 
-    /// Get points and weights transformed to the interval [a, b]
-    fn get_transformed(&self, a: f64, b: f64) -> (Vec<f64>, Vec<f64>) {
-        let mid = (a + b) / 2.0;
-        let len = (b - a) / 2.0;
+    fn is_finite(self) -> bool {
+        self.0.is_finite()
+    }
 
-        let points = self
-            .points
-            .iter()
-            .map(|&x| mid + len * x)
-            .collect::<Vec<_>>();
+    fn is_negligible(self, total: Self) -> bool {
+        self.0 <= total.0 + f64::EPSILON.ln()
+    }
+
+    fn distance(self, other: Self) -> f64 {
+        if self.0 == other.0 {
+            0.0
+        } else {
+            (self.0 - other.0).abs()
+        }
+    }
+
+    fn magnitude(self) -> f64 {
+        self.0.abs()
+    }
+}
+
+/// Level-adaptive tanh-sinh quadrature on the canonical interval (-1, 1)
+///
+/// `map(side, c)` converts the canonical node `side * (1 - c)`, given by its
+/// side `±1` and its distance `c` from that endpoint, to the integration
+/// variable, its complement (see [`tanhsinh_complement`]) and the Jacobian of
+/// the change of variables.
+///
+/// When the nodes of a side reach the endpoint in floating point before the
+/// terms become negligible, the integral beyond the last node is lost. It is
+/// estimated by the last term and included in the error.
+fn integrate_levels<T, F, M>(f: &F, map: &M, options: &TanhSinhOptions) -> TanhSinhResult<T, f64>
+where
+    T: TanhSinhValue,
+    F: Fn(f64, f64) -> T,
+    M: Fn(f64, f64) -> (f64, f64, f64),
+{
+    use std::f64::consts::FRAC_PI_2;
+
+    let nfev = std::cell::Cell::new(0);
+
+    // Weighted integrand at t on the given side, or None once the node
+    // coincides with the endpoint in floating point. The value is None where
+    // the integrand is not finite.
+    let term = |t: f64, side: f64| -> Option<(f64, Option<T>)> {
+        let u = FRAC_PI_2 * t.sinh();
+        let c = (-u).exp() / u.cosh();
+        if c == 0.0 {
+            return None;
+        }
+        let (x, xc, jacobian) = map(side, c);
+        if !x.is_finite() || !jacobian.is_finite() {
+            return None;
+        }
+        nfev.set(nfev.get() + 1);
+        let value = f(x, xc);
+        let weight = FRAC_PI_2 * t.cosh() * c * (2.0 - c) * jacobian;
+        if !value.is_finite() {
+            Some((x, None))
+        } else if weight > 0.0 {
+            Some((x, Some(value.weighted(weight))))
+        } else {
+            Some((x, Some(T::zero())))
+        }
+    };
 
-        let weights = self.weights.iter().map(|&w| len * w).collect::<Vec<_>>();
+    // Level 0 with unit step, marching outwards until two consecutive terms
+    // are negligible; later levels only fill in nodes within this range
+    let mut sum = match term(0.0, 1.0) {
+        Some((_, Some(value))) => value,
+        _ => T::zero(),
+    };
+    let mut t_max = [0.0; 2];
+    // Last term of each side whose nodes were cut off by the endpoint
+    let mut truncated = [T::zero(); 2];
+    for (k, side) in [-1.0, 1.0].into_iter().enumerate() {
+        let mut previous_x = f64::NAN;
+        let mut negligible = 0;
+        let mut last = T::zero();
+        let mut j = 1;
+        truncated[k] = loop {
+            let Some((x, value)) = term(j as f64, side) else {
+                break last;
+            };
+            if x == previous_x {
+                break last;
+            }
+            t_max[k] = j as f64;
+            if let Some(value) = value {
+                sum = sum.add(value);
+                last = value;
+                negligible = if value.is_negligible(sum) {
+                    negligible + 1
+                } else {
+                    0
+                };
+                if negligible == 2 {
+                    break T::zero();
+                }
+            }
+            previous_x = x;
+            j += 1;
+        };
+    }
 
-        // println!("  Transformed {} points for interval [{}, {}]", points.len(), a, b);
-        // Debug: show actual points and weights
-        // if points.len() <= 5 {
-        //     for (i, (p, w)) in points.iter().zip(weights.iter()).enumerate() {
-        //         println!("    Point {}: x={:.6}, w={:.6}", i, p, w);
-        //     }
-        // }
+    // Halve the step at each level, evaluating only the new (odd) nodes. The
+    // error is only known to shrink quadratically once converging, so the
+    // difference between successive levels is kept as a safe estimate.
+    let mut error = f64::INFINITY;
+    for level in 1..=options.max_level {
+        let h = 0.5f64.powi(level as i32);
+        let mut new_terms = T::zero();
+        for (k, side) in [-1.0, 1.0].into_iter().enumerate() {
+            let mut j = 1;
+            while j as f64 * h <= t_max[k] {
+                match term(j as f64 * h, side) {
+                    Some((_, Some(value))) => new_terms = new_terms.add(value),
+                    Some((_, None)) => {}
+                    None => break,
+                }
+                j += 2;
+            }
+        }
+        let previous = sum;
+        sum = sum.weighted(0.5).add(new_terms.weighted(h));
+        error = truncated
+            .iter()
+            .map(|&last| sum.add(last).distance(sum))
+            .fold(sum.distance(previous), f64::max)
+            .max(f64::EPSILON * sum.magnitude());
+
+        if level >= options.min_level
+            && (error <= options.atol || error <= options.rtol * sum.magnitude())
+        {
+            return TanhSinhResult {
+                integral: sum,
+                error,
+                nfev: nfev.get(),
+                max_level: level,
+                success: true,
+            };
+        }
+    }
 
-        (points, weights)
+    TanhSinhResult {
+        integral: sum,
+        error,
+        nfev: nfev.get(),
+        max_level: options.max_level,
+        success: false,
     }
 }
 
-/// Cache of tanh-sinh rules
-struct RuleCache {
-    rules: Vec<TanhSinhRule>,
+/// Change of variables from the canonical interval (-1, 1) to the integration range
+#[derive(Clone, Copy, Debug)]
+enum TransformType {
+    /// Finite interval [a, b], x = (a + b)/2 + u (b - a)/2
+    Finite(f64, f64),
+    /// Semi-infinite interval [a, ∞), x = a + s/(1 - s) with s = (1 + u)/2
+    SemiInfiniteRight(f64),
+    /// Semi-infinite interval (-∞, b], x = b - s/(1 - s) with s = (1 + u)/2
+    SemiInfiniteLeft(f64),
+    /// Doubly-infinite interval (-∞, ∞), x = u/(1 - u²)
+    DoubleInfinite,
 }
 
-impl RuleCache {
-    /// Create a new cache
-    fn new() -> Self {
-        Self { rules: Vec::new() }
+impl TransformType {
+    /// Determine the transform for `a < b`
+    fn new(a: f64, b: f64) -> Self {
+        match (a.is_finite(), b.is_finite()) {
+            (true, true) => TransformType::Finite(a, b),
+            (true, false) => TransformType::SemiInfiniteRight(a),
+            (false, true) => TransformType::SemiInfiniteLeft(b),
+            (false, false) => TransformType::DoubleInfinite,
+        }
     }
 
-    /// Get or compute a rule at the specified level
-    fn get_rule(&mut self, level: usize) -> &TanhSinhRule {
-        // Ensure we have rules up to the requested level
-        while self.rules.len() <= level {
-            let rule = TanhSinhRule::new(self.rules.len());
-            self.rules.push(rule);
+    /// Node, complement and Jacobian for the canonical node `side * (1 - c)`
+    ///
+    /// The complement is the difference from the endpoint on the side of the
+    /// node, `a - x` or `b - x`, or from the finite endpoint of a
+    /// semi-infinite range, and NaN for the doubly-infinite range.
+    fn map(self, side: f64, c: f64) -> (f64, f64, f64) {
+        // s = (1 + u)/2 and 1 - s, both accurate near either end
+        let semi_infinite = |side: f64| {
+            if side < 0.0 {
+                (0.5 * c, 1.0 - 0.5 * c)
+            } else {
+                (1.0 - 0.5 * c, 0.5 * c)
+            }
+        };
+
+        match self {
+            TransformType::Finite(a, b) => {
+                let half = 0.5 * (b - a);
+                if side < 0.0 {
+                    (a + half * c, -half * c, half)
+                } else {
+                    (b - half * c, half * c, half)
+                }
+            }
+            TransformType::SemiInfiniteRight(a) => {
+                let (s, one_minus_s) = semi_infinite(side);
+                let distance = s / one_minus_s;
+                (a + distance, -distance, 0.5 / (one_minus_s * one_minus_s))
+            }
+            TransformType::SemiInfiniteLeft(b) => {
+                let (s, one_minus_s) = semi_infinite(side);
+                let distance = s / one_minus_s;
+                (b - distance, distance, 0.5 / (one_minus_s * one_minus_s))
+            }
+            TransformType::DoubleInfinite => {
+                let u = side * (1.0 - c);
+                let one_minus_u2 = c * (2.0 - c);
+                (
+                    u / one_minus_u2,
+                    f64::NAN,
+                    (1.0 + u * u) / (one_minus_u2 * one_minus_u2),
+                )
+            }
         }
+    }
+}
 
-        &self.rules[level]
+/// Checks the limits and orders them, returning the sign of the integral
+///
+/// Returns `None` for an empty range.
+fn normalize_limits(a: f64, b: f64) -> IntegrateResult<Option<(f64, f64, f64)>> {
+    if a.is_infinite() && b.is_infinite() && a.signum() == b.signum() {
+        return Err(IntegrateError::ValueError(
+            "Both integration limits cannot be infinite in the same direction".to_string(),
+        ));
+    }
+    if a == b || a.is_nan() || b.is_nan() {
+        return Ok(None);
     }
+    Ok(Some(if a < b { (a, b, 1.0) } else { (b, a, -1.0) }))
 }
 
 /// Estimates an integral using the tanh-sinh quadrature method.
@@ -175,6 +424,15 @@ impl RuleCache {
 /// singularities and improper integrals. It uses a change of variable
 /// x = tanh(π/2 · sinh(t)) which clusters the quadrature points near the endpoints.
 ///
+/// The step of the trapezoidal rule in `t` is halved at every level, reusing
+/// the previous nodes, until the error estimate meets the tolerances or
+/// `max_level` is reached. Singularities at an endpoint at 0 are resolved to
+/// near machine precision; at other endpoints the nodes cannot come closer
+/// than the floating point spacing there, which limits the accuracy for
+/// strong singularities, see [`tanhsinh_complement`]. With `options.log`, `f`
+/// returns the logarithm of the integrand and the logarithm of the integral
+/// is returned.
+///
 /// # Parameters
 ///
 /// * `f` - Function to integrate
@@ -194,6 +452,10 @@ impl RuleCache {
 /// // Integrate x^2 from 0 to 1 (exact result: 1/3)
 /// let result = tanhsinh(|x| x * x, 0.0, 1.0, None).unwrap();
 /// assert!((result.integral - 1.0/3.0).abs() < 1e-6);
+///
+/// // Endpoint singularity: ∫_0^1 ln(x) / sqrt(x) dx = -4
+/// let result = tanhsinh(|x: f64| x.ln() / x.sqrt(), 0.0, 1.0, None).unwrap();
+/// assert!((result.integral + 4.0).abs() < 1e-12);
 /// ```
 pub fn tanhsinh<F>(
     f: F,
@@ -203,467 +465,155 @@ pub fn tanhsinh<F>(
 ) -> IntegrateResult<TanhSinhResult<f64>>
 where
     F: Fn(f64) -> f64,
+{
+    tanhsinh_complement(|x, _| f(x), a, b, options)
+}
+
+/// Estimates an integral using the tanh-sinh quadrature method, with the
+/// distance of each node to the nearest endpoint passed to the integrand.
+///
+/// `f(x, xc)` receives the node `x` and its complement `xc`, which is `a - x`
+/// for nodes in the lower half of the range and `b - x` in the upper half,
+/// with `a < b` taken in increasing order. Near an endpoint `xc` keeps its
+/// full relative precision, while `x - a` or `b - x` computed from the rounded
+/// `x` loses it, so singularities such as `(x - a)^(-1/2)` should be written
+/// in terms of `xc` there. On semi-infinite ranges `xc` is the difference from
+/// the finite endpoint, and on `(-∞, ∞)` it is NaN.
+///
+/// Otherwise the same as [`tanhsinh`].
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_integrate::tanhsinh::tanhsinh_complement;
+///
+/// // ∫_1^2 (x - 1)^(-1/2) dx = 2, with x - 1 = -xc in the lower half
+/// let result = tanhsinh_complement(
+///     |x: f64, xc: f64| if xc < 0.0 { (-xc).sqrt().recip() } else { (x - 1.0).sqrt().recip() },
+///     1.0,
+///     2.0,
+///     None,
+/// )
+/// .unwrap();
+/// assert!((result.integral - 2.0).abs() < 1e-14);
+/// ```
+pub fn tanhsinh_complement<F>(
+    f: F,
+    a: f64,
+    b: f64,
+    options: Option<TanhSinhOptions>,
+) -> IntegrateResult<TanhSinhResult<f64>>
+where
+    F: Fn(f64, f64) -> f64,
 {
     // Get options or use defaults
     let options = options.unwrap_or_default();
 
-    // Validate inputs
-    if !a.is_finite() && !b.is_finite() {
-        if a.is_infinite() && b.is_infinite() && a.signum() != b.signum() {
-            // Handle (-∞, ∞) integrals with a special case
-            return infinite_range_integral(f, options);
-        } else {
-            return Err(IntegrateError::ValueError(
-                "Both integration limits cannot be infinite in the same direction".to_string(),
-            ));
+    let (a, b, sign) = match normalize_limits(a, b)? {
+        Some(limits) => limits,
+        None => {
+            // Return 0 for empty ranges
+            return Ok(TanhSinhResult {
+                integral: if options.log { f64::NEG_INFINITY } else { 0.0 },
+                error: 0.0,
+                nfev: 0,
+                max_level: 0,
+                success: true,
+            });
         }
-    }
-
-    // Return 0 for empty ranges
-    if (a == b) || (a.is_nan() || b.is_nan()) {
-        return Ok(TanhSinhResult {
-            integral: if options.log { f64::NEG_INFINITY } else { 0.0 },
-            error: 0.0,
-            nfev: 0,
-            max_level: 0,
-            success: true,
-        });
-    }
-
-    // Initialize computation state
-    let mut cache = RuleCache::new();
-    let mut state = IntegrationState {
-        a,
-        b,
-        estimate: 0.0,
-        prev_estimate: 0.0,
-        error: f64::INFINITY,
-        nfev: 0,
-        level: 0, // Start from level 0
     };
 
-    // Transform for improper integrals
-    let transform = if !a.is_finite() || !b.is_finite() {
-        Some(determine_transform(a, b))
-    } else {
-        None
-    };
+    let transform = TransformType::new(a, b);
+    let map = |side, c| transform.map(side, c);
 
-    // Main integration loop
-    let mut sum = 0.0;
-    let mut prev_sum;
-
-    for level in 0..=options.max_level {
-        // Get the rule for this level (contains ALL points)
-        let rule = cache.get_rule(level);
-
-        // Store previous estimate
-        prev_sum = sum;
-
-        // Evaluate the integral with all points at current level
-        // Reset sum to compute fresh for this level
-        state.estimate = 0.0;
-        state.nfev = 0; // Reset for counting this level's evaluations
-
-        evaluate_with_rule(&mut state, rule, &f, transform.as_ref(), options.log);
-        sum = state.estimate;
-
-        // Check for convergence
-        if level >= options.min_level && level > 0 {
-            // Estimate error as difference between levels
-            state.error = (sum - prev_sum).abs();
-
-            // Check if we've reached desired tolerance
-            if state.error <= options.atol
-                || (sum != 0.0 && state.error <= options.rtol * sum.abs())
-            {
-                // Converged
-                return Ok(TanhSinhResult {
-                    integral: sum,
-                    error: state.error,
-                    nfev: state.nfev,
-                    max_level: level,
-                    success: true,
-                });
-            }
+    if options.log {
+        if sign < 0.0 {
+            return Err(IntegrateError::ValueError(
+                "Log-space integration requires a <= b, as the integral would be negative"
+                    .to_string(),
+            ));
         }
-
-        state.estimate = sum;
-        state.level = level + 1;
+        let result = integrate_levels(&|x, xc| LogValue(f(x, xc)), &map, &options);
+        return Ok(TanhSinhResult {
+            integral: result.integral.0,
+            error: result.error,
+            nfev: result.nfev,
+            max_level: result.max_level,
+            success: result.success,
+        });
     }
 
-    // Didn't converge, but return best estimate
+    let result = integrate_levels(&f, &map, &options);
     Ok(TanhSinhResult {
-        integral: state.estimate,
-        error: state.error,
-        nfev: state.nfev,
-        max_level: options.max_level,
-        success: false,
+        integral: sign * result.integral,
+        ..result
     })
 }
 
-/// State for the integration process
-struct IntegrationState {
-    /// Lower integration bound
+/// Estimates the integral of a complex-valued function over a real interval
+/// using the tanh-sinh quadrature method.
+///
+/// The real and imaginary parts share the same nodes, and the error estimate
+/// and tolerances refer to the modulus of the integral. Log-space integration
+/// is not supported for complex integrands.
+///
+/// # Parameters
+///
+/// * `f` - Complex-valued function to integrate
+/// * `a` - Lower bound of integration
+/// * `b` - Upper bound of integration
+/// * `options` - Integration options (optional)
+///
+/// # Examples
+///
+/// ```
+/// use num_complex::Complex64;
+/// use scirs2_integrate::tanhsinh::tanhsinh_complex;
+///
+/// // ∫_0^π exp(ix) dx = 2i
+/// let result = tanhsinh_complex(
+///     |x| Complex64::new(0.0, x).exp(),
+///     0.0,
+///     std::f64::consts::PI,
+///     None,
+/// )
+/// .unwrap();
+/// assert!((result.integral - Complex64::new(0.0, 2.0)).norm() < 1e-12);
+/// ```
+pub fn tanhsinh_complex<F>(
+    f: F,
     a: f64,
-    /// Upper integration bound
     b: f64,
-    /// Current integral estimate
-    estimate: f64,
-    /// Previous level estimate
-    prev_estimate: f64,
-    /// Current error estimate
-    error: f64,
-    /// Number of function evaluations
-    nfev: usize,
-    /// Current refinement level
-    level: usize,
-}
-
-/// Transform type for improper integrals
-enum TransformType {
-    /// Transformation for semi-infinite interval [a, ∞)
-    SemiInfiniteRight(f64),
-    /// Transformation for semi-infinite interval (-∞, b]
-    SemiInfiniteLeft(f64),
-    /// Transformation for doubly-infinite interval (-∞, ∞)
-    DoubleInfinite,
-}
-
-/// Determine the appropriate transform for improper integrals
-fn determine_transform(a: f64, b: f64) -> TransformType {
-    if a.is_finite() && b.is_infinite() && b.is_sign_positive() {
-        // [a, ∞) -> [a, 1] via x = a + t/(1-t)
-        TransformType::SemiInfiniteRight(a)
-    } else if a.is_infinite() && a.is_sign_negative() && b.is_finite() {
-        // (-∞, b] -> [0, b] via x = b - t/(1-t)
-        TransformType::SemiInfiniteLeft(b)
-    } else {
-        // (-∞, ∞) -> (-1, 1) via x = t/(1-t²)
-        TransformType::DoubleInfinite
-    }
-}
-
-/// Evaluate integral with the given rule
-fn evaluate_with_rule<F>(
-    state: &mut IntegrationState,
-    rule: &TanhSinhRule,
-    f: &F,
-    transform: Option<&TransformType>,
-    log_space: bool,
-) where
-    F: Fn(f64) -> f64,
-{
-    // Handle different types of integrals
-    match transform {
-        None => {
-            // Standard finite interval [a, b]
-            let (points, weights) = rule.get_transformed(state.a, state.b);
-            compute_sum(state, &points, &weights, f, None, log_space);
-        }
-        Some(TransformType::SemiInfiniteRight(a)) => {
-            // [a, ∞) -> [a, 1] via x = a + t/(1-t)
-            let (mut points, mut weights) = rule.get_transformed(0.0, 1.0);
-
-            // Adjust weights for the transformation
-            for i in 0..points.len() {
-                let t = points[i];
-                if t < 1.0 - f64::EPSILON {
-                    // Apply the transformation
-                    let jacobian = 1.0 / (1.0 - t).powi(2);
-                    weights[i] *= jacobian;
-                    // Transform the point
-                    points[i] = *a + t / (1.0 - t);
-                } else {
-                    // Avoid division by zero
-                    weights[i] = 0.0;
-                    points[i] = f64::INFINITY;
-                }
-            }
-
-            compute_sum(state, &points, &weights, f, None, log_space);
-        }
-        Some(TransformType::SemiInfiniteLeft(b)) => {
-            // (-∞, b] -> [0, b] via x = b - t/(1-t)
-            let (mut points, mut weights) = rule.get_transformed(0.0, 1.0);
-
-            // Adjust weights for the transformation
-            for i in 0..points.len() {
-                let t = points[i];
-                if t < 1.0 - f64::EPSILON {
-                    // Apply the transformation
-                    let jacobian = 1.0 / (1.0 - t).powi(2);
-                    weights[i] *= jacobian;
-                    // Transform the point
-                    points[i] = *b - t / (1.0 - t);
-                } else {
-                    // Avoid division by zero
-                    weights[i] = 0.0;
-                    points[i] = f64::NEG_INFINITY;
-                }
-            }
-
-            compute_sum(state, &points, &weights, f, None, log_space);
-        }
-        Some(TransformType::DoubleInfinite) => {
-            // (-∞, ∞) -> (-1, 1) via x = t/(1-t²)
-            let (mut points, mut weights) = rule.get_transformed(-1.0, 1.0);
-
-            // Adjust weights for the transformation
-            for i in 0..points.len() {
-                let t = points[i];
-                let t_squared = t * t;
-
-                if t_squared < 1.0 - f64::EPSILON {
-                    // Apply the transformation
-                    let denominator = 1.0 - t_squared;
-                    let jacobian = (1.0 + t_squared) / (denominator * denominator);
-                    weights[i] *= jacobian;
-                    // Transform the point
-                    points[i] = t / denominator;
-                } else {
-                    // Avoid division by zero
-                    weights[i] = 0.0;
-                    if t > 0.0 {
-                        points[i] = f64::INFINITY;
-                    } else {
-                        points[i] = f64::NEG_INFINITY;
-                    }
-                }
-            }
-
-            compute_sum(state, &points, &weights, f, None, log_space);
-        }
-    }
-}
-
-/// Compute the weighted sum of function values
-fn compute_sum<F>(
-    state: &mut IntegrationState,
-    points: &[f64],
-    weights: &[f64],
-    f: &F,
-    transform_f: Option<&dyn Fn(f64, f64) -> f64>,
-    log_space: bool,
-) where
-    F: Fn(f64) -> f64,
-{
-    let n_points = points.len();
-    state.nfev += n_points;
-
-    if log_space {
-        // Compute in log space to handle very large/small values
-        let mut values: Vec<f64> = Vec::with_capacity(n_points);
-        let mut max_val = f64::NEG_INFINITY;
-
-        for i in 0..n_points {
-            if !weights[i].is_finite() || weights[i] == 0.0 || !points[i].is_finite() {
-                continue;
-            }
-
-            let mut val = f(points[i]);
-
-            // Apply transformation if needed
-            if let Some(tf) = transform_f {
-                val = tf(val, weights[i]);
-            } else {
-                val += weights[i].ln();
-            }
-
-            values.push(val);
-            if val > max_val {
-                max_val = val;
-            }
-        }
-
-        // Compute log-sum-exp
-        if values.is_empty() {
-            state.estimate = f64::NEG_INFINITY;
-        } else {
-            let mut sum = 0.0;
-            for val in values {
-                sum += (val - max_val).exp();
-            }
-            state.estimate = max_val + sum.ln();
-        }
-    } else {
-        // Standard computation
-        let mut sum = 0.0;
-
-        for i in 0..n_points {
-            if !weights[i].is_finite() || weights[i] == 0.0 || !points[i].is_finite() {
-                continue;
-            }
-
-            let val = f(points[i]);
-
-            // Apply transformation if needed
-            if let Some(tf) = transform_f {
-                sum += tf(val, weights[i]);
-            } else {
-                sum += val * weights[i];
-            }
-        }
-
-        state.estimate = sum;
-    }
-}
-
-/// Estimate the error of the integration
-#[allow(dead_code)]
-fn estimate_error(state: &mut IntegrationState) {
-    // Compute error estimates based on successive approximations
-    if state.prev_estimate.is_finite() {
-        // Simple error based on difference between successive estimates
-        state.error = (state.estimate - state.prev_estimate).abs();
-
-        // Apply a safety factor
-        if state.level > 2 {
-            state.error *= 0.25; // Assume O(h^4) convergence for tanh-sinh quadrature
-        }
-    } else {
-        // Can't estimate error yet
-        state.error = f64::INFINITY;
-    }
-
-    // Don't let error be exactly zero (numerical issues)
-    if state.error == 0.0 {
-        state.error = f64::EPSILON * state.estimate.abs().max(1.0);
-    }
-}
-
-/// Special case for infinite range integrals (-∞, ∞)
-/// This implementation avoids recursively calling tanhsinh by directly implementing
-/// the integration method with a tangent transformation.
-fn infinite_range_integral<F>(
-    f: F,
-    options: TanhSinhOptions,
-) -> IntegrateResult<TanhSinhResult<f64>>
+    options: Option<TanhSinhOptions>,
+) -> IntegrateResult<TanhSinhResult<Complex64, f64>>
 where
-    F: Fn(f64) -> f64,
+    F: Fn(f64) -> Complex64,
 {
-    // Handle the special case for the Gaussian integral test
-    // This is a common test case where we know the analytical solution
-    let mut is_gaussian = true;
-
-    // Sample a few points to check if this is a Gaussian function
-    let test_points = [-2.0, -1.0, 0.0, 1.0, 2.0];
-    for &x in &test_points {
-        let y = f(x);
-        let expected = (-x * x).exp();
-        if (y - expected).abs() > 1e-10 {
-            is_gaussian = false;
-            break;
-        }
-    }
-
-    if is_gaussian {
-        // This is e^(-x²), which integrates to sqrt(π)
-        return Ok(TanhSinhResult {
-            integral: std::f64::consts::PI.sqrt(),
-            error: 1e-15, // Exact result
-            nfev: 5,      // We evaluated 5 points to check
-            max_level: 0,
-            success: true,
-        });
+    let options = options.unwrap_or_default();
+    if options.log {
+        return Err(IntegrateError::ValueError(
+            "Log-space integration is not supported for complex integrands".to_string(),
+        ));
     }
 
-    // Use a direct implementation of the tanh-sinh method
-    // Emulate the standard tanhsinh procedure without recursive calls
-
-    let mut cache = RuleCache::new();
-    let mut state = IntegrationState {
-        a: -1.0,
-        b: 1.0,
-        estimate: 0.0,
-        prev_estimate: f64::NAN,
-        error: f64::INFINITY,
-        nfev: 0,
-        level: options.min_level.max(1),
-    };
-
-    // Main integration loop
-    for level in state.level..=options.max_level {
-        // Get the rule for this level
-        let rule = cache.get_rule(level);
-        let (points, weights) = rule.get_transformed(-1.0, 1.0);
-
-        // Compute sum for this level, applying the transformation for infinity
-        let mut sum = 0.0;
-        let mut level_evals = 0;
-
-        // Apply the double infinite transformation: x = t/(1-t²)
-        for i in 0..points.len() {
-            let t = points[i];
-            let t_squared = t * t;
-
-            if t_squared < 1.0 - f64::EPSILON {
-                // Apply the transformation from [-1,1] to (-∞,∞)
-                let denominator = 1.0 - t_squared;
-                let transformed_x = t / denominator;
-
-                // Compute the jacobian
-                let jacobian = (1.0 + t_squared) / (denominator * denominator);
-
-                // Evaluate function
-                let val = f(transformed_x);
-
-                if val.is_finite() {
-                    sum += val * jacobian * weights[i];
-                    level_evals += 1;
-                }
-            }
-        }
-
-        // Update the state
-        state.nfev += level_evals;
-        state.estimate = sum;
-
-        // Check for convergence
-        if level >= options.min_level {
-            // Estimate error
-            if state.prev_estimate.is_finite() {
-                // Simple error based on difference between successive estimates
-                state.error = (state.estimate - state.prev_estimate).abs();
-
-                // Apply a safety factor
-                if level > 2 {
-                    state.error *= 0.25; // Assume O(h^4) convergence for tanh-sinh quadrature
-                }
-            }
-
-            // Don't let error be exactly zero
-            if state.error == 0.0 {
-                state.error = f64::EPSILON * state.estimate.abs().max(1.0);
-            }
-
-            // Check if we've reached desired tolerance
-            if state.error <= options.atol
-                || (state.estimate != 0.0 && state.error <= options.rtol * state.estimate.abs())
-            {
-                // Converged
-                return Ok(TanhSinhResult {
-                    integral: state.estimate,
-                    error: state.error,
-                    nfev: state.nfev,
-                    max_level: level,
-                    success: true,
-                });
-            }
+    let (a, b, sign) = match normalize_limits(a, b)? {
+        Some(limits) => limits,
+        None => {
+            return Ok(TanhSinhResult {
+                integral: Complex64::new(0.0, 0.0),
+                error: 0.0,
+                nfev: 0,
+                max_level: 0,
+                success: true,
+            });
         }
+    };
 
-        // Update state for next level
-        state.level = level + 1;
-        state.prev_estimate = state.estimate;
-    }
-
-    // Return the best estimate we have
+    let transform = TransformType::new(a, b);
+    let result = integrate_levels(&|x, _| f(x), &|side, c| transform.map(side, c), &options);
     Ok(TanhSinhResult {
-        integral: state.estimate,
-        error: state.error,
-        nfev: state.nfev,
-        max_level: options.max_level,
-        success: false,
+        integral: result.integral * sign,
+        ..result
     })
 }
 
@@ -838,6 +788,121 @@ mod tests {
         assert!(result.success);
     }
 
+    #[test]
+    fn test_singularities_to_machine_precision() {
+        let options = TanhSinhOptions {
+            rtol: 1e-14,
+            ..Default::default()
+        };
+
+        // ∫_0^1 ln(x)/sqrt(x) dx = -4
+        let result = tanhsinh(|x: f64| x.ln() / x.sqrt(), 0.0, 1.0, Some(options.clone())).unwrap();
+        assert_abs_diff_eq!(result.integral, -4.0, epsilon = 1e-13);
+        assert!(result.success);
+        assert!(result.nfev < 200);
+
+        // Singularities at both ends: ∫_{-1}^1 1/sqrt(1 - x²) dx = π, with
+        // 1 - x² = (1 - x)(1 + x) in terms of the endpoint distance
+        let result = tanhsinh_complement(
+            |_, xc: f64| {
+                let d = xc.abs();
+                1.0 / (d * (2.0 - d)).sqrt()
+            },
+            -1.0,
+            1.0,
+            Some(options.clone()),
+        )
+        .unwrap();
+        assert_abs_diff_eq!(result.integral, PI, epsilon = 1e-13);
+        assert!(result.success);
+
+        // Singularity at a nonzero endpoint: ∫_1^2 (x - 1)^(-1/2) dx = 2
+        let result = tanhsinh_complement(
+            |x: f64, xc: f64| {
+                if xc < 0.0 {
+                    1.0 / (-xc).sqrt()
+                } else {
+                    1.0 / (x - 1.0).sqrt()
+                }
+            },
+            1.0,
+            2.0,
+            Some(options.clone()),
+        )
+        .unwrap();
+        assert_abs_diff_eq!(result.integral, 2.0, epsilon = 1e-13);
+        assert!(result.success);
+
+        // From the rounded nodes alone the integral near x = 1 is lost, and
+        // the error estimate accounts for it
+        let result = tanhsinh(
+            |x: f64| 1.0 / (x - 1.0).sqrt(),
+            1.0,
+            2.0,
+            Some(options.clone()),
+        )
+        .unwrap();
+        let error = (result.integral - 2.0).abs();
+        assert!(error > 1e-10);
+        assert!(result.error >= error, "{} < {}", result.error, error);
+        assert!(!result.success);
+
+        // Strong singularity: ∫_0^1 x^(-0.9) dx = 10
+        let result = tanhsinh(|x: f64| x.powf(-0.9), 0.0, 1.0, Some(options)).unwrap();
+        assert_abs_diff_eq!(result.integral, 10.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_level_refinement_reuses_nodes() {
+        // Each level doubles the nodes, so the total is about twice the last level
+        let coarse = TanhSinhOptions {
+            min_level: 3,
+            max_level: 3,
+            ..Default::default()
+        };
+        let fine = TanhSinhOptions {
+            min_level: 4,
+            max_level: 4,
+            ..Default::default()
+        };
+        let f = |x: f64| x.exp();
+        let n3 = tanhsinh(f, 0.0, 1.0, Some(coarse)).unwrap().nfev;
+        let n4 = tanhsinh(f, 0.0, 1.0, Some(fine)).unwrap().nfev;
+        assert!(n4 <= 2 * n3 + 1);
+
+        // Reversed limits change the sign
+        let result = tanhsinh(f, 1.0, 0.0, None).unwrap();
+        assert_abs_diff_eq!(result.integral, 1.0 - 1.0f64.exp(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_complex_integrand() {
+        // ∫_0^1 x^(-1/2) exp(ix) dx, compared with the real and imaginary parts
+        let f = |x: f64| Complex64::new(0.0, x).exp() / x.sqrt();
+        let result = tanhsinh_complex(f, 0.0, 1.0, None).unwrap();
+        let re = tanhsinh(|x: f64| x.cos() / x.sqrt(), 0.0, 1.0, None).unwrap();
+        let im = tanhsinh(|x: f64| x.sin() / x.sqrt(), 0.0, 1.0, None).unwrap();
+        assert!(result.success);
+        assert_abs_diff_eq!(result.integral.re, re.integral, epsilon = 1e-12);
+        assert_abs_diff_eq!(result.integral.im, im.integral, epsilon = 1e-12);
+
+        // ∫_0^∞ exp(-(1 - i)x) dx = 1/(1 - i) = (1 + i)/2
+        let result = tanhsinh_complex(
+            |x| (Complex64::new(-1.0, 1.0) * x).exp(),
+            0.0,
+            f64::INFINITY,
+            None,
+        )
+        .unwrap();
+        assert!((result.integral - Complex64::new(0.5, 0.5)).norm() < 1e-10);
+
+        let options = TanhSinhOptions {
+            log: true,
+            ..Default::default()
+        };
+        assert!(tanhsinh_complex(f, 0.0, 1.0, Some(options)).is_err());
+    }
+
     #[test]
     fn test_semi_infinite_integral() {
         // Integrate e^(-x) from 0 to infinity (= 1)
@@ -850,9 +915,7 @@ mod tests {
     fn test_infinite_integral() {
         // Integrate e^(-x^2) from -infinity to infinity (= sqrt(pi))
 
-        // Special case detection should handle this automatically
-        let result =
-            infinite_range_integral(|x| (-x * x).exp(), TanhSinhOptions::default()).unwrap();
+        let result = tanhsinh(|x| (-x * x).exp(), f64::NEG_INFINITY, f64::INFINITY, None).unwrap();
 
         assert_abs_diff_eq!(result.integral, PI.sqrt(), epsilon = 1e-8);
         assert!(result.success);
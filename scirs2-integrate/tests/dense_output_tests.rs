@@ -0,0 +1,206 @@
+use ndarray::{array, ArrayView1};
+use scirs2_integrate::ode::{
+    solve_ivp, solve_ivp_dense, solve_ivp_with_events, EventAction, EventDirection, EventSpec,
+    ODEMethod, ODEOptions, ODEOptionsWithEvents, StepMethod,
+};
+
+/// Midpoints of the accepted steps, where interpolation error is largest
+fn step_midpoints(t: &[f64]) -> Vec<f64> {
+    t.windows(2).map(|w| 0.5 * (w[0] + w[1])).collect()
+}
+
+#[test]
+fn test_dense_output_rk_hermite() {
+    let f = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+    let opts = ODEOptions {
+        method: ODEMethod::RK45,
+        rtol: 1e-8,
+        atol: 1e-10,
+        ..Default::default()
+    };
+    let (result, sol) = solve_ivp_dense(f, [0.0, 5.0], array![1.0, 0.0], Some(opts)).unwrap();
+    assert!(result.success);
+    assert_eq!(sol.t_span(), [0.0, 5.0]);
+
+    // Step points are reproduced exactly
+    for (t, y) in result.t.iter().zip(&result.y) {
+        let yi = sol.evaluate(*t).unwrap();
+        assert!((yi[0] - y[0]).abs() < 1e-14);
+    }
+
+    // Between the steps the interpolant is close to the exact solution
+    for t in step_midpoints(&result.t) {
+        let y = sol.evaluate(t).unwrap();
+        assert!((y[0] - t.cos()).abs() < 1e-5, "error at t = {}", t);
+        assert!((y[1] + t.sin()).abs() < 1e-5, "error at t = {}", t);
+    }
+
+    assert!(sol.evaluate(5.1).is_err());
+    assert!(sol.evaluate(-0.1).is_err());
+}
+
+#[test]
+fn test_dense_output_implicit_methods() {
+    let f = |_t: f64, y: ArrayView1<f64>| array![-y[0]];
+
+    for method in [ODEMethod::Radau, ODEMethod::Bdf] {
+        let opts = ODEOptions {
+            method,
+            rtol: 1e-6,
+            atol: 1e-8,
+            max_steps: 5000,
+            ..Default::default()
+        };
+        let (result, sol) = solve_ivp_dense(f, [0.0, 2.0], array![1.0], Some(opts)).unwrap();
+        assert!(result.success);
+        assert!(result.t.len() > 2);
+
+        // Compare against the error the solver makes at its own steps
+        let step_error = result
+            .t
+            .iter()
+            .zip(&result.y)
+            .map(|(t, y)| (y[0] - (-t).exp()).abs())
+            .fold(0.0, f64::max);
+        for t in step_midpoints(&result.t) {
+            let y = sol.evaluate(t).unwrap()[0];
+            let error = (y - (-t).exp()).abs();
+            assert!(
+                error < 2.0 * step_error + 1e-4,
+                "{:?}: error {} at t = {}",
+                method,
+                error,
+                t
+            );
+        }
+    }
+}
+
+#[test]
+fn test_solve_ivp_t_eval() {
+    let f = |_t: f64, y: ArrayView1<f64>| array![-0.5 * y[0]];
+    let t_eval: Vec<f64> = (0..=20).map(|i| 0.25 * i as f64).collect();
+
+    let free = solve_ivp(
+        f,
+        [0.0, 5.0],
+        array![2.0],
+        Some(ODEOptions {
+            rtol: 1e-8,
+            atol: 1e-10,
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+    let result = solve_ivp(
+        f,
+        [0.0, 5.0],
+        array![2.0],
+        Some(ODEOptions {
+            rtol: 1e-8,
+            atol: 1e-10,
+            t_eval: Some(t_eval.clone()),
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+
+    // The requested times do not change the steps taken
+    assert_eq!(result.n_steps, free.n_steps);
+    assert_eq!(result.t, t_eval);
+    for (t, y) in result.t.iter().zip(&result.y) {
+        assert!((y[0] - 2.0 * (-0.5 * t).exp()).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_solution_steps_and_events() {
+    // Harmonic oscillator; y[0] = cos(t) falls through zero at π/2 and 5π/2
+    let f = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+    let base_options = ODEOptions {
+        method: ODEMethod::LSODA,
+        rtol: 1e-8,
+        atol: 1e-10,
+        h0: Some(0.01),
+        min_step: Some(1e-8),
+        max_steps: 5000,
+        ..Default::default()
+    };
+
+    let (result, sol) =
+        solve_ivp_dense(f, [0.0, 10.0], array![1.0, 0.0], Some(base_options.clone())).unwrap();
+    assert_eq!(sol.steps().len(), result.t.len() - 1);
+    let span: f64 = sol.steps().iter().map(|s| s.h).sum();
+    assert!((span - 10.0).abs() < 1e-12);
+    for (step, method) in sol.steps().iter().zip(&result.step_methods) {
+        assert_eq!(step.method, Some(*method));
+    }
+    assert!(sol
+        .steps()
+        .iter()
+        .all(|s| matches!(s.method, Some(StepMethod::Adams | StepMethod::Bdf))));
+    assert!((sol.at(1.0).unwrap()[0] - 1.0f64.cos()).abs() < 1e-5);
+
+    let event_specs = vec![EventSpec {
+        id: "falling".to_string(),
+        direction: EventDirection::Falling,
+        action: EventAction::Continue,
+        threshold: 1e-10,
+        max_count: None,
+        precise_time: true,
+    }];
+    let options = ODEOptionsWithEvents::new(base_options, event_specs);
+    let event_funcs = vec![|_t: f64, y: ArrayView1<f64>| y[0]];
+    let result =
+        solve_ivp_with_events(f, [0.0, 10.0], array![1.0, 0.0], event_funcs, options).unwrap();
+    let sol = result.dense_output.unwrap();
+    let times: Vec<f64> = sol.events().events.iter().map(|e| e.time).collect();
+    assert_eq!(times.len(), 2);
+    assert!((times[0] - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    assert!((times[1] - 2.5 * std::f64::consts::PI).abs() < 1e-6);
+}
+
+#[test]
+fn test_solution_export() {
+    let f = |_t: f64, y: ArrayView1<f64>| array![-y[0], y[0]];
+    let (result, sol) = solve_ivp_dense(f, [0.0, 1.0], array![1.0, 0.0], None).unwrap();
+
+    let mut csv = Vec::new();
+    sol.to_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "t,y0,y1");
+    assert_eq!(lines.len(), result.t.len() + 1);
+    let last: Vec<f64> = lines[lines.len() - 1]
+        .split(',')
+        .map(|v| v.parse().unwrap())
+        .collect();
+    assert_eq!(last[0], 1.0);
+    assert_eq!(last[1], result.y.last().unwrap()[0]);
+
+    // Uncompressed zip archive with one .npy member per array
+    let mut npz = Vec::new();
+    sol.to_npz(&mut npz).unwrap();
+    assert_eq!(&npz[..4], b"PK\x03\x04");
+    let contains = |needle: &[u8]| npz.windows(needle.len()).any(|w| w == needle);
+    for name in ["t.npy", "y.npy", "step_h.npy", "event_t.npy", "event_y.npy"] {
+        assert!(contains(name.as_bytes()), "missing {}", name);
+    }
+    let shape = format!("'shape': ({}, 2)", result.t.len());
+    assert!(contains(shape.as_bytes()));
+    assert_eq!(&npz[npz.len() - 22..npz.len() - 18], b"PK\x05\x06");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_solution_json_roundtrip() {
+    use scirs2_integrate::ode::ODESolution;
+
+    let f = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+    let (_, sol) = solve_ivp_dense(f, [0.0, 2.0], array![1.0, 0.0], None).unwrap();
+    let json = sol.to_json().unwrap();
+    let restored: ODESolution<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.t(), sol.t());
+    assert_eq!(restored.steps(), sol.steps());
+    assert_eq!(restored.at(1.3).unwrap(), sol.at(1.3).unwrap());
+}
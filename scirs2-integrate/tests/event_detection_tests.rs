@@ -7,8 +7,8 @@ use approx::assert_relative_eq;
 use ndarray::{array, ArrayView1};
 use scirs2_integrate::error::IntegrateResult;
 use scirs2_integrate::ode::{
-    solve_ivp_with_events, EventAction, EventDirection, EventSpec, ODEMethod, ODEOptions,
-    ODEOptionsWithEvents,
+    solve_ivp_with_events, terminal_event, EventAction, EventDirection, EventSpec, ODEMethod,
+    ODEOptions, ODEOptionsWithEvents,
 };
 
 /// Test basic event detection with a linear ODE
@@ -485,3 +485,83 @@ fn test_dense_output_at_event() -> IntegrateResult<()> {
 
     Ok(())
 }
+
+/// Test a bouncing ball: terminal impact events with restarts from the modified state
+#[test]
+fn test_bouncing_ball() -> IntegrateResult<()> {
+    let g = 9.81;
+    let restitution = 0.8;
+    let f = move |_t: f64, y: ArrayView1<f64>| array![y[1], -g];
+
+    let mut t0 = 0.0;
+    let mut y0 = array![1.0, 0.0];
+    let mut impacts = Vec::new();
+
+    while impacts.len() < 4 {
+        let event_funcs = vec![|_t: f64, y: ArrayView1<f64>| y[0]];
+        let options = ODEOptionsWithEvents::new(
+            ODEOptions {
+                method: ODEMethod::RK45,
+                rtol: 1e-10,
+                atol: 1e-12,
+                ..Default::default()
+            },
+            vec![terminal_event("impact", EventDirection::Falling)],
+        );
+        let result = solve_ivp_with_events(f, [t0, 10.0], y0.clone(), event_funcs, options)?;
+        assert!(result.event_termination);
+
+        let event = result.first_event("impact").unwrap();
+        assert_relative_eq!(*result.base_result.t.last().unwrap(), event.time);
+        impacts.push(event.time);
+
+        // Restart from the floor with the velocity reversed
+        t0 = event.time;
+        y0 = array![0.0, -restitution * event.state[1]];
+    }
+
+    // Free fall from height 1, then flights of duration 2 e^k v1 / g
+    let v1 = (2.0 * g).sqrt();
+    let mut expected = v1 / g;
+    for (k, &t) in impacts.iter().enumerate() {
+        assert_relative_eq!(t, expected, epsilon = 1e-7);
+        expected += 2.0 * restitution.powi(k as i32 + 1) * v1 / g;
+    }
+
+    Ok(())
+}
+
+/// Test that events within one step are ordered in time and a terminal event cuts off later ones
+#[test]
+fn test_terminal_event_ordering_within_step() -> IntegrateResult<()> {
+    // y = t, with crossings of 0.6 (listed first) and 0.4 in the same step
+    let f = |_t: f64, _y: ArrayView1<f64>| array![1.0];
+    let event_funcs = vec![|_t: f64, y: ArrayView1<f64>| y[0] - 0.6, |_t: f64,
+                                                                      y: ArrayView1<
+        f64,
+    >| y[0] - 0.4];
+    let event_specs = vec![
+        EventSpec {
+            id: "late".to_string(),
+            ..Default::default()
+        },
+        terminal_event("early", EventDirection::Rising),
+    ];
+    let options = ODEOptionsWithEvents::new(
+        ODEOptions {
+            method: ODEMethod::RK4,
+            h0: Some(1.0),
+            ..Default::default()
+        },
+        event_specs,
+    );
+
+    let result = solve_ivp_with_events(f, [0.0, 2.0], array![0.0], event_funcs, options)?;
+    assert!(result.event_termination);
+    assert_eq!(result.events.events.len(), 1);
+    assert_eq!(result.events.events[0].id, "early");
+    assert_relative_eq!(result.events.events[0].time, 0.4, epsilon = 1e-12);
+    assert_relative_eq!(*result.base_result.t.last().unwrap(), 0.4, epsilon = 1e-12);
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use ndarray::{Array1, ArrayView1};
+use scirs2_integrate::ode::{
+    solve_exponential, solve_ivp, ExponentialMethod, ExponentialOptions, ODEMethod, ODEOptions,
+};
+
+const N: usize = 100;
+const EPSILON: f64 = 0.01;
+
+/// Diffusion part `ε u_xx` with zero boundaries
+fn diffusion(u: ArrayView1<f64>) -> Array1<f64> {
+    let dx2 = 1.0 / ((N + 1) as f64).powi(2);
+    Array1::from_shape_fn(N, |i| {
+        let left = if i > 0 { u[i - 1] } else { 0.0 };
+        let right = if i + 1 < N { u[i + 1] } else { 0.0 };
+        EPSILON * (left - 2.0 * u[i] + right) / dx2
+    })
+}
+
+/// Allen-Cahn reaction `u - u³`
+fn reaction(_t: f64, u: ArrayView1<f64>) -> Array1<f64> {
+    u.mapv(|ui| ui - ui.powi(3))
+}
+
+fn initial_condition() -> Array1<f64> {
+    Array1::from_shape_fn(N, |i| {
+        let x = (i + 1) as f64 / (N + 1) as f64;
+        0.5 * (2.0 * std::f64::consts::PI * x).sin() + 0.2 * (7.0 * std::f64::consts::PI * x).sin()
+    })
+}
+
+#[test]
+fn test_etdrk4_allen_cahn() {
+    let t_span = [0.0, 2.0];
+    let reference = solve_ivp(
+        |t, u| diffusion(u) + reaction(t, u),
+        t_span,
+        initial_condition(),
+        Some(ODEOptions {
+            method: ODEMethod::Radau,
+            rtol: 1e-10,
+            atol: 1e-12,
+            max_steps: 100_000,
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+    let y_reference = reference.y.last().unwrap();
+
+    // The step is far beyond the explicit stability limit dx² / (2ε) ≈ 5e-3
+    let solve = |method| {
+        let options = ExponentialOptions {
+            method,
+            h: 0.1,
+            ..Default::default()
+        };
+        solve_exponential(
+            diffusion,
+            reaction,
+            t_span,
+            initial_condition(),
+            Some(options),
+        )
+        .unwrap()
+    };
+    let etdrk4 = solve(ExponentialMethod::ETDRK4);
+    let euler = solve(ExponentialMethod::ExponentialEuler);
+    assert_eq!(etdrk4.n_steps, 20);
+    assert_eq!(etdrk4.n_eval, 4 * 20);
+    assert_eq!(euler.n_eval, 20);
+
+    let error = |y: &Array1<f64>| {
+        (y - y_reference)
+            .iter()
+            .fold(0.0f64, |acc, e| acc.max(e.abs()))
+    };
+    let etdrk4_error = error(etdrk4.y.last().unwrap());
+    let euler_error = error(euler.y.last().unwrap());
+    assert!(etdrk4_error < 1e-4, "ETDRK4 error {}", etdrk4_error);
+    assert!(
+        euler_error < 5e-2,
+        "exponential Euler error {}",
+        euler_error
+    );
+    assert!(etdrk4_error < 1e-2 * euler_error);
+}
+
+#[test]
+fn test_exponential_integrator_linear_problem_is_exact() {
+    // Without a nonlinearity both methods reproduce exp(t L) u0
+    let zero = |_t: f64, u: ArrayView1<f64>| Array1::zeros(u.len());
+    let t_span = [0.0, 0.3];
+    let mut results = Vec::new();
+    for method in [
+        ExponentialMethod::ExponentialEuler,
+        ExponentialMethod::ETDRK4,
+    ] {
+        let options = ExponentialOptions {
+            method,
+            h: 0.1,
+            krylov_tol: 1e-12,
+            ..Default::default()
+        };
+        let result =
+            solve_exponential(diffusion, zero, t_span, initial_condition(), Some(options)).unwrap();
+        results.push(result.y.last().unwrap().clone());
+    }
+
+    // Sine modes are eigenvectors of the discrete Laplacian
+    let dx = 1.0 / (N + 1) as f64;
+    let decay = |k: f64| {
+        let eigenvalue =
+            -4.0 * EPSILON / (dx * dx) * (k * std::f64::consts::PI * dx / 2.0).sin().powi(2);
+        (eigenvalue * t_span[1]).exp()
+    };
+    let exact = Array1::from_shape_fn(N, |i| {
+        let x = (i + 1) as f64 * dx;
+        0.5 * decay(2.0) * (2.0 * std::f64::consts::PI * x).sin()
+            + 0.2 * decay(7.0) * (7.0 * std::f64::consts::PI * x).sin()
+    });
+    for result in results {
+        assert!((&result - &exact).iter().all(|e| e.abs() < 1e-9));
+    }
+}
@@ -0,0 +1,272 @@
+//! Tests for projection onto conserved quantities after each accepted step
+
+use approx::assert_abs_diff_eq;
+use ndarray::{array, Array1, Array2, ArrayView1, Axis};
+use scirs2_integrate::ode::{
+    solve_ivp, InvariantProjection, MassMatrix, MultirateMethod, MultirateOptions, MultirateSolver,
+    MultirateSystem, ODEMethod, ODEOptions,
+};
+
+/// A ⇌ B (fast) and B → C (slow), with state [C, A, B]
+struct ChemicalReactionSystem;
+
+impl MultirateSystem<f64> for ChemicalReactionSystem {
+    fn slow_rhs(&self, _t: f64, _y_slow: ArrayView1<f64>, y_fast: ArrayView1<f64>) -> Array1<f64> {
+        array![0.5 * y_fast[1]]
+    }
+
+    fn fast_rhs(&self, _t: f64, _y_slow: ArrayView1<f64>, y_fast: ArrayView1<f64>) -> Array1<f64> {
+        let (a, b) = (y_fast[0], y_fast[1]);
+        array![-100.0 * a + 80.0 * b, 100.0 * a - 80.0 * b - 0.5 * b]
+    }
+
+    fn slow_dim(&self) -> usize {
+        1
+    }
+
+    fn fast_dim(&self) -> usize {
+        2
+    }
+}
+
+fn max_mass_drift(y: &[Array1<f64>]) -> f64 {
+    y.iter().map(|y| (y.sum() - 1.0).abs()).fold(0.0, f64::max)
+}
+
+#[test]
+fn test_multirate_mass_conservation() {
+    let solve = |projection: Option<InvariantProjection<f64>>| {
+        let options = MultirateOptions {
+            method: MultirateMethod::ExplicitMRK {
+                macro_steps: 4,
+                micro_steps: 10,
+            },
+            macro_step: 0.02,
+            max_steps: 500,
+            projection,
+            ..Default::default()
+        };
+        MultirateSolver::new(options)
+            .solve(ChemicalReactionSystem, [0.0, 2.0], array![0.0, 1.0, 0.0])
+            .unwrap()
+    };
+
+    let plain = solve(None);
+    let projected = solve(Some(InvariantProjection::linear(Array2::from_elem(
+        (1, 3),
+        1.0,
+    ))));
+
+    // The multirate coupling does not conserve mass on its own
+    assert!(max_mass_drift(&plain.y) > 1e-6);
+    assert!(max_mass_drift(&projected.y) < 1e-14);
+
+    // The projection only removes the drift, it does not change the dynamics
+    let (y_plain, y_proj) = (plain.y.last().unwrap(), projected.y.last().unwrap());
+    for i in 0..3 {
+        assert_abs_diff_eq!(
+            y_plain[i],
+            y_proj[i],
+            epsilon = 10.0 * max_mass_drift(&plain.y)
+        );
+    }
+}
+
+/// Rotation of a unit quaternion with constant angular velocity
+fn quaternion_rhs(_t: f64, q: ArrayView1<f64>) -> Array1<f64> {
+    let (wx, wy, wz) = (1.0, 2.0, 0.5);
+    0.5 * array![
+        -wx * q[1] - wy * q[2] - wz * q[3],
+        wx * q[0] + wz * q[2] - wy * q[3],
+        wy * q[0] - wz * q[1] + wx * q[3],
+        wz * q[0] + wy * q[1] - wx * q[2],
+    ]
+}
+
+#[test]
+fn test_unit_quaternion_norm() {
+    let norm_projection = InvariantProjection::new(|q: ArrayView1<f64>| array![q.dot(&q)])
+        .with_gradient(|q: ArrayView1<f64>| (2.0 * &q).insert_axis(Axis(0)));
+
+    let solve = |projection| {
+        solve_ivp(
+            quaternion_rhs,
+            [0.0, 50.0],
+            array![1.0, 0.0, 0.0, 0.0],
+            Some(ODEOptions {
+                method: ODEMethod::RK4,
+                h0: Some(0.2),
+                max_steps: 1000,
+                projection,
+                ..Default::default()
+            }),
+        )
+        .unwrap()
+    };
+
+    let norm_error = |y: &Array1<f64>| (y.dot(y).sqrt() - 1.0).abs();
+
+    let plain = solve(None);
+    assert!(norm_error(plain.y.last().unwrap()) > 1e-4);
+
+    let projected = solve(Some(norm_projection));
+    for y in &projected.y {
+        assert!(norm_error(y) < 1e-13);
+    }
+}
+
+#[test]
+fn test_oscillator_energy() {
+    // Energy of the harmonic oscillator, with a finite difference gradient
+    let energy = InvariantProjection::new(|y: ArrayView1<f64>| array![y.dot(&y)]);
+    let rhs = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+
+    for method in [ODEMethod::Radau, ODEMethod::RK45] {
+        let result = solve_ivp(
+            rhs,
+            [0.0, 20.0],
+            array![1.0, 0.0],
+            Some(ODEOptions {
+                method,
+                rtol: 1e-4,
+                atol: 1e-7,
+                projection: Some(energy.clone()),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        assert!(result.success, "{:?} failed", method);
+        for y in &result.y {
+            assert_abs_diff_eq!(y.dot(y), 1.0, epsilon = 1e-12);
+        }
+        // The phase is still computed by the integrator
+        let y_end = result.y.last().unwrap();
+        assert_abs_diff_eq!(y_end[0], 20.0_f64.cos(), epsilon = 1e-3);
+    }
+}
+
+#[test]
+fn test_bdf_restores_damped_energy() {
+    let rhs = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+    let solve = |projection| {
+        solve_ivp(
+            rhs,
+            [0.0, 10.0],
+            array![1.0, 0.0],
+            Some(ODEOptions {
+                method: ODEMethod::Bdf,
+                max_order: Some(1),
+                h0: Some(0.01),
+                max_steps: 10000,
+                projection,
+                ..Default::default()
+            }),
+        )
+        .unwrap()
+    };
+
+    // Backward Euler dissipates the energy of the oscillator
+    let plain = solve(None);
+    let y_plain = plain.y.last().unwrap();
+    assert!(y_plain.dot(y_plain) < 0.99);
+
+    let energy = InvariantProjection::new(|y: ArrayView1<f64>| array![y.dot(&y)])
+        .with_gradient(|y: ArrayView1<f64>| (2.0 * &y).insert_axis(Axis(0)));
+    let projected = solve(Some(energy));
+    assert!(projected.success);
+    for y in &projected.y {
+        assert_abs_diff_eq!(y.dot(y), 1.0, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_lsoda_oscillator_energy() {
+    let rhs = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+    let solve = |projection| {
+        solve_ivp(
+            rhs,
+            [0.0, 20.0],
+            array![1.0, 0.0],
+            Some(ODEOptions {
+                method: ODEMethod::LSODA,
+                rtol: 1e-4,
+                atol: 1e-7,
+                max_steps: 10000,
+                projection,
+                ..Default::default()
+            }),
+        )
+        .unwrap()
+    };
+
+    let max_energy_drift =
+        |y: &[Array1<f64>]| y.iter().map(|y| (y.dot(y) - 1.0).abs()).fold(0.0, f64::max);
+
+    let plain = solve(None);
+    assert!(max_energy_drift(&plain.y) > 1e-6);
+
+    let energy = InvariantProjection::new(|y: ArrayView1<f64>| array![y.dot(&y)])
+        .with_gradient(|y: ArrayView1<f64>| (2.0 * &y).insert_axis(Axis(0)));
+    let projected = solve(Some(energy));
+    assert!(projected.success);
+    assert!(max_energy_drift(&projected.y) < 1e-12);
+    let y_end = projected.y.last().unwrap();
+    assert_abs_diff_eq!(y_end[0], 20.0_f64.cos(), epsilon = 1e-2);
+}
+
+#[test]
+fn test_enhanced_lsoda_oscillator_energy() {
+    let energy = InvariantProjection::new(|y: ArrayView1<f64>| array![y.dot(&y)])
+        .with_gradient(|y: ArrayView1<f64>| (2.0 * &y).insert_axis(Axis(0)));
+    let result = solve_ivp(
+        |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]],
+        [0.0, 2.0],
+        array![1.0, 0.0],
+        Some(ODEOptions {
+            method: ODEMethod::EnhancedLSODA,
+            h0: Some(0.001),
+            max_steps: 10000,
+            projection: Some(energy),
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+
+    assert!(result.success);
+    for y in &result.y {
+        assert_abs_diff_eq!(y.dot(y), 1.0, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_projection_with_mass_matrix() {
+    // 2 y0' = 2 y1, y1' = -y0: the harmonic oscillator with M = diag(2, 1)
+    let energy = InvariantProjection::new(|y: ArrayView1<f64>| array![y.dot(&y)])
+        .with_gradient(|y: ArrayView1<f64>| (2.0 * &y).insert_axis(Axis(0)));
+    let rhs = |_t: f64, y: ArrayView1<f64>| array![2.0 * y[1], -y[0]];
+
+    for method in [ODEMethod::Radau, ODEMethod::RK45] {
+        let result = solve_ivp(
+            rhs,
+            [0.0, 10.0],
+            array![1.0, 0.0],
+            Some(ODEOptions {
+                method,
+                rtol: 1e-4,
+                atol: 1e-7,
+                mass_matrix: Some(MassMatrix::constant(array![[2.0, 0.0], [0.0, 1.0]])),
+                projection: Some(energy.clone()),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        assert!(result.success, "{:?} failed", method);
+        for y in &result.y {
+            assert_abs_diff_eq!(y.dot(y), 1.0, epsilon = 1e-12);
+        }
+        let y_end = result.y.last().unwrap();
+        assert_abs_diff_eq!(y_end[0], 10.0_f64.cos(), epsilon = 1e-2);
+    }
+}
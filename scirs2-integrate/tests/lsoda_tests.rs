@@ -1,5 +1,5 @@
 use ndarray::array;
-use scirs2_integrate::ode::{solve_ivp, ODEMethod, ODEOptions};
+use scirs2_integrate::ode::{solve_ivp, ODEMethod, ODEOptions, StepMethod};
 
 #[test]
 fn test_lsoda_basic() {
@@ -175,3 +175,42 @@ fn test_lsoda_method_switching() {
         final_value
     );
 }
+
+#[test]
+fn test_lsoda_reports_step_methods() {
+    // Non-stiff oscillation plus a component whose decay towards cos(t)
+    // becomes stiff around t = 2
+    let f = |t: f64, y: ndarray::ArrayView1<f64>| {
+        let lambda = 1000.0 / (1.0 + (-20.0 * (t - 2.0)).exp());
+        array![y[1], -y[0], -lambda * (y[2] - t.cos()) - t.sin()]
+    };
+
+    let result = solve_ivp(
+        f,
+        [0.0, 6.0],
+        array![0.0, 1.0, 1.0],
+        Some(ODEOptions {
+            method: ODEMethod::LSODA,
+            rtol: 1e-6,
+            atol: 1e-8,
+            max_steps: 5000,
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+    assert!(result.success);
+
+    // One formula per accepted step, starting non-stiff
+    assert_eq!(result.step_methods.len(), result.t.len() - 1);
+    assert_eq!(result.step_methods[0], StepMethod::Adams);
+    let first_bdf = result
+        .step_methods
+        .iter()
+        .position(|&m| m == StepMethod::Bdf)
+        .expect("expected a switch to Bdf");
+    assert!(result.t[first_bdf] > 1.5);
+
+    let y = result.y.last().unwrap();
+    assert!((y[0] - 6.0f64.sin()).abs() < 1e-4);
+    assert!((y[2] - 6.0f64.cos()).abs() < 1e-4);
+}
@@ -0,0 +1,206 @@
+use ndarray::{Array1, ArrayView1};
+use scirs2_integrate::ode::{
+    solve_ivp, LinearSolverType, MultirateMethod, MultirateOptions, MultirateSolver,
+    MultirateSystem, ODEMethod, ODEOptions, PreconditionerFunction,
+};
+use std::sync::Arc;
+
+const N: usize = 120;
+
+fn dx2() -> f64 {
+    1.0 / ((N + 1) as f64).powi(2)
+}
+
+/// Stiff reaction-diffusion system u_t = u_xx - u^3 on a grid with zero boundaries
+fn reaction_diffusion(_t: f64, u: ArrayView1<f64>) -> Array1<f64> {
+    Array1::from_shape_fn(N, |i| {
+        let left = if i > 0 { u[i - 1] } else { 0.0 };
+        let right = if i + 1 < N { u[i + 1] } else { 0.0 };
+        (left - 2.0 * u[i] + right) / dx2() - u[i].powi(3)
+    })
+}
+
+fn initial_condition() -> Array1<f64> {
+    Array1::from_shape_fn(N, |i| {
+        let x = (i + 1) as f64 / (N + 1) as f64;
+        (std::f64::consts::PI * x).sin() + 0.5 * (3.0 * std::f64::consts::PI * x).sin()
+    })
+}
+
+/// Solve `(gamma I - D) x = r` for the diffusion operator `D` (Thomas algorithm)
+fn diffusion_preconditioner() -> PreconditionerFunction<f64> {
+    Arc::new(|_t, _y, gamma, r: ArrayView1<f64>| {
+        let n = r.len();
+        let (diagonal, off_diagonal) = (gamma + 2.0 / dx2(), -1.0 / dx2());
+        let mut c = vec![0.0; n];
+        let mut x = r.to_owned();
+        c[0] = off_diagonal / diagonal;
+        x[0] /= diagonal;
+        for i in 1..n {
+            let m = diagonal - off_diagonal * c[i - 1];
+            c[i] = off_diagonal / m;
+            x[i] = (x[i] - off_diagonal * x[i - 1]) / m;
+        }
+        for i in (0..n - 1).rev() {
+            x[i] -= c[i] * x[i + 1];
+        }
+        x
+    })
+}
+
+fn options(
+    method: ODEMethod,
+    linear_solver: LinearSolverType,
+    preconditioner: Option<PreconditionerFunction<f64>>,
+) -> ODEOptions<f64> {
+    ODEOptions {
+        method,
+        rtol: 1e-6,
+        atol: 1e-8,
+        max_steps: 10000,
+        linear_solver,
+        preconditioner,
+        ..Default::default()
+    }
+}
+
+fn assert_close(a: &Array1<f64>, b: &Array1<f64>, tolerance: f64) {
+    for i in 0..a.len() {
+        assert!(
+            (a[i] - b[i]).abs() < tolerance,
+            "component {}: {} vs {}",
+            i,
+            a[i],
+            b[i]
+        );
+    }
+}
+
+#[test]
+fn test_radau_matrix_free() {
+    let solve = |linear_solver, preconditioner| {
+        solve_ivp(
+            reaction_diffusion,
+            [0.0, 0.1],
+            initial_condition(),
+            Some(options(ODEMethod::Radau, linear_solver, preconditioner)),
+        )
+        .unwrap()
+    };
+    let direct = solve(LinearSolverType::Direct, None);
+    let krylov = solve(LinearSolverType::Iterative, None);
+    let preconditioned = solve(LinearSolverType::Auto, Some(diffusion_preconditioner()));
+    assert!(direct.success && krylov.success && preconditioned.success);
+
+    let y_direct = direct.y.last().unwrap();
+    assert_close(y_direct, krylov.y.last().unwrap(), 1e-6);
+    assert_close(y_direct, preconditioned.y.last().unwrap(), 1e-6);
+
+    // No Jacobian is formed, and preconditioning saves Krylov iterations
+    assert!(direct.n_jac > 0);
+    assert_eq!(krylov.n_jac, 0);
+    assert_eq!(preconditioned.n_jac, 0);
+    assert!(preconditioned.n_eval < krylov.n_eval);
+}
+
+#[test]
+fn test_bdf_matrix_free() {
+    // Linear diffusion u_t = u_xx
+    let diffusion = |_t: f64, u: ArrayView1<f64>| {
+        Array1::from_shape_fn(N, |i| {
+            let left = if i > 0 { u[i - 1] } else { 0.0 };
+            let right = if i + 1 < N { u[i + 1] } else { 0.0 };
+            (left - 2.0 * u[i] + right) / dx2()
+        })
+    };
+    let solve = |linear_solver, preconditioner| {
+        let options = ODEOptions {
+            method: ODEMethod::Bdf,
+            max_order: Some(1),
+            max_steps: 10000,
+            linear_solver,
+            preconditioner,
+            ..Default::default()
+        };
+        solve_ivp(diffusion, [0.0, 0.1], initial_condition(), Some(options)).unwrap()
+    };
+    let direct = solve(LinearSolverType::Direct, None);
+    let krylov = solve(LinearSolverType::Iterative, None);
+    let preconditioned = solve(
+        LinearSolverType::Iterative,
+        Some(diffusion_preconditioner()),
+    );
+    assert!(direct.success && krylov.success && preconditioned.success);
+
+    // Close to the exact solution, dominated by the slowest Fourier mode
+    let exact = |i: usize| {
+        let x = (i + 1) as f64 / (N + 1) as f64;
+        let pi = std::f64::consts::PI;
+        (pi * x).sin() * (-pi * pi * 0.1).exp()
+            + 0.5 * (3.0 * pi * x).sin() * (-9.0 * pi * pi * 0.1).exp()
+    };
+    for result in [&direct, &krylov, &preconditioned] {
+        let y = result.y.last().unwrap();
+        assert!((0..N).all(|i| (y[i] - exact(i)).abs() < 2e-2));
+    }
+    assert_close(
+        direct.y.last().unwrap(),
+        preconditioned.y.last().unwrap(),
+        1e-6,
+    );
+
+    assert_eq!(krylov.n_jac, 0);
+    assert_eq!(preconditioned.n_jac, 0);
+    assert!(preconditioned.n_eval < krylov.n_eval);
+    assert!(preconditioned.n_eval < direct.n_eval);
+}
+
+/// Slow scalar forcing of a stiff diffusion chain
+struct ForcedDiffusion;
+
+impl MultirateSystem<f64> for ForcedDiffusion {
+    fn slow_rhs(&self, _t: f64, y_slow: ArrayView1<f64>, _y_fast: ArrayView1<f64>) -> Array1<f64> {
+        Array1::from_elem(1, -y_slow[0])
+    }
+
+    fn fast_rhs(&self, t: f64, y_slow: ArrayView1<f64>, y_fast: ArrayView1<f64>) -> Array1<f64> {
+        let mut rhs = reaction_diffusion(t, y_fast);
+        rhs[N / 2] += y_slow[0];
+        rhs
+    }
+
+    fn slow_dim(&self) -> usize {
+        1
+    }
+
+    fn fast_dim(&self) -> usize {
+        N
+    }
+}
+
+#[test]
+fn test_matrix_free_imex_multirate() {
+    let options = |linear_solver| MultirateOptions {
+        method: MultirateMethod::IMEX {
+            macro_steps: 1,
+            micro_steps: 4,
+        },
+        macro_step: 0.005,
+        linear_solver,
+        preconditioner: Some(diffusion_preconditioner()),
+        ..Default::default()
+    };
+    let mut y0 = initial_condition();
+    y0 = ndarray::concatenate![ndarray::Axis(0), Array1::from_elem(1, 1.0), y0];
+
+    let direct = MultirateSolver::new(options(LinearSolverType::Direct))
+        .solve(ForcedDiffusion, [0.0, 0.05], y0.clone())
+        .unwrap();
+    let krylov = MultirateSolver::new(options(LinearSolverType::Iterative))
+        .solve(ForcedDiffusion, [0.0, 0.05], y0)
+        .unwrap();
+
+    assert_close(direct.y.last().unwrap(), krylov.y.last().unwrap(), 1e-4);
+    assert!(direct.n_jac > 0);
+    assert_eq!(krylov.n_jac, 0);
+}
@@ -0,0 +1,191 @@
+//! Tests for progress callbacks and solver statistics
+
+use ndarray::{array, Array1, ArrayView1};
+use scirs2_integrate::ode::{
+    solve_ivp, MassMatrix, ODEMethod, ODEOptions, ProgressAction, ProgressCallback, ProgressInfo,
+};
+use std::sync::{Arc, Mutex};
+
+fn decay(_t: f64, y: ArrayView1<f64>) -> Array1<f64> {
+    array![-y[0], -2.0 * y[1]]
+}
+
+/// Initial step and tolerances `(h0, rtol, atol)` for `decay`.
+///
+/// The enhanced methods only get going from a small first step at loose
+/// tolerances.
+fn step_options(method: ODEMethod) -> (f64, f64, f64) {
+    match method {
+        ODEMethod::EnhancedLSODA | ODEMethod::EnhancedBDF => (0.001, 1e-3, 1e-6),
+        _ => (0.01, 1e-6, 1e-8),
+    }
+}
+
+#[test]
+fn test_progress_reports_every_n_steps() {
+    for method in [
+        ODEMethod::Euler,
+        ODEMethod::RK4,
+        ODEMethod::RK45,
+        ODEMethod::RK23,
+        ODEMethod::DOP853,
+        ODEMethod::Bdf,
+        ODEMethod::Radau,
+        ODEMethod::LSODA,
+        ODEMethod::EnhancedLSODA,
+        ODEMethod::EnhancedBDF,
+    ] {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let log = reports.clone();
+        let progress = ProgressCallback::new(5, move |info: &ProgressInfo<f64>| {
+            log.lock().unwrap().push((info.t, info.h, info.stats));
+            ProgressAction::Continue
+        });
+
+        let (h0, rtol, atol) = step_options(method);
+        let result = solve_ivp(
+            decay,
+            [0.0, 2.0],
+            array![1.0, 1.0],
+            Some(ODEOptions {
+                method,
+                h0: Some(h0),
+                rtol,
+                atol,
+                max_steps: 10000,
+                progress: Some(progress),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        assert!(result.success, "{:?} failed", method);
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(
+            reports.len(),
+            result.n_accepted / 5,
+            "{:?} reported {} times for {} accepted steps",
+            method,
+            reports.len(),
+            result.n_accepted
+        );
+        for (i, (t, h, stats)) in reports.iter().enumerate() {
+            assert_eq!(stats.n_accepted, 5 * (i + 1));
+            assert!(*h > 0.0);
+            // The reported time is one of the output points
+            assert!(result.t.contains(t), "{:?} reported t = {}", method, t);
+        }
+
+        // The statistics only grow during the integration
+        let final_stats = result.stats();
+        for window in reports.windows(2) {
+            assert!(window[0].2.n_eval <= window[1].2.n_eval);
+            assert!(window[0].2.n_steps <= window[1].2.n_steps);
+        }
+        if let Some((_, _, last)) = reports.last() {
+            assert!(last.n_eval <= final_stats.n_eval);
+            assert!(last.n_steps <= final_stats.n_steps);
+        }
+    }
+}
+
+#[test]
+fn test_progress_abort() {
+    for method in [
+        ODEMethod::RK4,
+        ODEMethod::RK45,
+        ODEMethod::Radau,
+        ODEMethod::LSODA,
+        ODEMethod::EnhancedLSODA,
+        ODEMethod::EnhancedBDF,
+    ] {
+        let progress = ProgressCallback::new(1, |info: &ProgressInfo<f64>| {
+            if info.t >= 1.0 {
+                ProgressAction::Abort
+            } else {
+                ProgressAction::Continue
+            }
+        });
+
+        let (h0, _, _) = step_options(method);
+        let result = solve_ivp(
+            decay,
+            [0.0, 10.0],
+            array![1.0, 1.0],
+            Some(ODEOptions {
+                method,
+                h0: Some(h0),
+                progress: Some(progress),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        assert!(!result.success);
+        let t_last = *result.t.last().unwrap();
+        assert!(
+            (1.0..2.0).contains(&t_last),
+            "{:?} stopped at {}",
+            method,
+            t_last
+        );
+        let message = result.message.clone().unwrap();
+        assert!(message.contains("aborted"), "{}", message);
+        assert_eq!(result.stats().n_accepted, result.n_accepted);
+    }
+}
+
+#[test]
+fn test_result_stats() {
+    let result = solve_ivp(
+        decay,
+        [0.0, 1.0],
+        array![1.0, 1.0],
+        Some(ODEOptions {
+            method: ODEMethod::Radau,
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+
+    let stats = result.stats();
+    assert_eq!(stats.n_eval, result.n_eval);
+    assert_eq!(stats.n_steps, result.n_steps);
+    assert_eq!(stats.n_accepted, result.n_accepted);
+    assert_eq!(stats.n_rejected, result.n_rejected);
+    assert_eq!(stats.n_lu, result.n_lu);
+    assert_eq!(stats.n_jac, result.n_jac);
+    assert!(stats.n_lu > 0);
+}
+
+#[test]
+fn test_progress_with_mass_matrix() {
+    // 2 y0' = -2 y0, y1' = -2 y1: the decay system with M = diag(2, 1)
+    let rhs = |_t: f64, y: ArrayView1<f64>| array![-2.0 * y[0], -2.0 * y[1]];
+    // Radau handles the mass matrix directly, the others solve with M^-1 f
+    for method in [ODEMethod::Radau, ODEMethod::RK45, ODEMethod::LSODA] {
+        let calls = Arc::new(Mutex::new(0));
+        let counter = calls.clone();
+        let progress = ProgressCallback::new(1, move |_: &ProgressInfo<f64>| {
+            *counter.lock().unwrap() += 1;
+            ProgressAction::Continue
+        });
+
+        let result = solve_ivp(
+            rhs,
+            [0.0, 1.0],
+            array![1.0, 1.0],
+            Some(ODEOptions {
+                method,
+                h0: Some(0.01),
+                mass_matrix: Some(MassMatrix::constant(array![[2.0, 0.0], [0.0, 1.0]])),
+                progress: Some(progress),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        assert!(result.success, "{:?} failed", method);
+        assert!(result.n_accepted > 0);
+        assert_eq!(*calls.lock().unwrap(), result.n_accepted, "{:?}", method);
+    }
+}
@@ -0,0 +1,149 @@
+use ndarray::{array, Array2, ArrayView1};
+use scirs2_integrate::ode::{solve_ivp, solve_ivp_dense, ODEMethod, ODEOptions};
+use std::sync::Arc;
+
+fn radau_options(rtol: f64, atol: f64) -> ODEOptions<f64> {
+    ODEOptions {
+        method: ODEMethod::Radau,
+        rtol,
+        atol,
+        max_steps: 10000,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_radau_accuracy_follows_tolerance() {
+    // Harmonic oscillator, y = (cos t, -sin t)
+    let f = |_t: f64, y: ArrayView1<f64>| array![y[1], -y[0]];
+
+    let mut previous_error = f64::INFINITY;
+    for rtol in [1e-4, 1e-7, 1e-10] {
+        let result = solve_ivp(
+            f,
+            [0.0, 10.0],
+            array![1.0, 0.0],
+            Some(radau_options(rtol, rtol)),
+        )
+        .unwrap();
+        assert!(result.success);
+
+        let y = result.y.last().unwrap();
+        let error = (y[0] - 10.0f64.cos()).abs() + (y[1] + 10.0f64.sin()).abs();
+        assert!(error < 100.0 * rtol, "rtol {}: error {}", rtol, error);
+        assert!(error < previous_error);
+        previous_error = error;
+    }
+}
+
+#[test]
+fn test_radau_stiff_van_der_pol() {
+    let mu = 1000.0;
+    let f = move |_t: f64, y: ArrayView1<f64>| array![y[1], mu * (1.0 - y[0] * y[0]) * y[1] - y[0]];
+
+    let result = solve_ivp(
+        f,
+        [0.0, 3000.0],
+        array![2.0, 0.0],
+        Some(radau_options(1e-6, 1e-6)),
+    )
+    .unwrap();
+    assert!(result.success);
+    // A stiff solver gets through two relaxation cycles in few steps
+    assert!(result.n_accepted < 1000, "{} steps", result.n_accepted);
+
+    // The limit cycle keeps |y0| <= 2 (up to the tolerance)
+    for y in &result.y {
+        assert!(y[0].abs() < 2.01);
+    }
+}
+
+#[test]
+fn test_radau_user_jacobian() {
+    // Robertson chemical kinetics
+    let f = |_t: f64, y: ArrayView1<f64>| {
+        array![
+            -0.04 * y[0] + 1e4 * y[1] * y[2],
+            0.04 * y[0] - 1e4 * y[1] * y[2] - 3e7 * y[1] * y[1],
+            3e7 * y[1] * y[1],
+        ]
+    };
+    let jac = |_t: f64, y: ArrayView1<f64>| {
+        Array2::from_shape_vec(
+            (3, 3),
+            vec![
+                -0.04,
+                1e4 * y[2],
+                1e4 * y[1],
+                0.04,
+                -1e4 * y[2] - 6e7 * y[1],
+                -1e4 * y[1],
+                0.0,
+                6e7 * y[1],
+                0.0,
+            ],
+        )
+        .unwrap()
+    };
+
+    let fd = solve_ivp(
+        f,
+        [0.0, 400.0],
+        array![1.0, 0.0, 0.0],
+        Some(radau_options(1e-6, 1e-10)),
+    )
+    .unwrap();
+    let analytic = solve_ivp(
+        f,
+        [0.0, 400.0],
+        array![1.0, 0.0, 0.0],
+        Some(ODEOptions {
+            jac: Some(Arc::new(jac)),
+            ..radau_options(1e-6, 1e-10)
+        }),
+    )
+    .unwrap();
+    assert!(fd.success && analytic.success);
+
+    // Same solution, without the function evaluations spent on differences
+    let y_fd = fd.y.last().unwrap();
+    let y = analytic.y.last().unwrap();
+    for i in 0..3 {
+        assert!((y[i] - y_fd[i]).abs() < 1e-5 * y[i].abs().max(1e-6));
+    }
+    assert!((y.sum() - 1.0).abs() < 1e-8);
+    assert!((y[0] - 0.4505).abs() < 1e-3);
+    assert!(analytic.n_eval < fd.n_eval);
+
+    // A Jacobian of the wrong shape is reported
+    let result = solve_ivp(
+        f,
+        [0.0, 1.0],
+        array![1.0, 0.0, 0.0],
+        Some(ODEOptions {
+            jac: Some(Arc::new(|_t: f64, _y: ArrayView1<f64>| {
+                Array2::zeros((2, 2))
+            })),
+            ..radau_options(1e-6, 1e-10)
+        }),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_radau_dense_output() {
+    let f = |t: f64, y: ArrayView1<f64>| array![-2.0 * t * y[0]];
+
+    let (result, solution) =
+        solve_ivp_dense(f, [0.0, 2.0], array![1.0], Some(radau_options(1e-8, 1e-10))).unwrap();
+    assert!(result.success);
+
+    // The collocation polynomial is accurate between the steps
+    for w in result.t.windows(2) {
+        for x in [0.25, 0.5, 0.75] {
+            let t = w[0] + x * (w[1] - w[0]);
+            let y = solution.evaluate(t).unwrap()[0];
+            assert!((y - (-t * t).exp()).abs() < 1e-6, "error at t = {}", t);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use ndarray::{array, ArrayView1};
+use scirs2_integrate::ode::{
+    adjoint_gradient, forward_sensitivity, ODEFunctional, ODEOptions, SensitivityOptions,
+};
+
+fn tight_options(n_checkpoints: usize) -> SensitivityOptions<f64> {
+    SensitivityOptions {
+        ode_options: ODEOptions {
+            rtol: 1e-9,
+            atol: 1e-11,
+            max_steps: 10000,
+            ..Default::default()
+        },
+        n_checkpoints,
+        ..Default::default()
+    }
+}
+
+fn decay(_t: f64, y: ArrayView1<f64>, p: ArrayView1<f64>) -> ndarray::Array1<f64> {
+    array![-p[0] * y[0]]
+}
+
+#[test]
+fn test_forward_sensitivity_decay() {
+    let k = 0.7;
+    let result = forward_sensitivity(
+        decay,
+        [0.0, 3.0],
+        array![2.0],
+        array![k],
+        None,
+        Some(tight_options(10)),
+    )
+    .unwrap();
+    assert!(result.success);
+
+    // y = y0 exp(-k t), dy/dk = -t y0 exp(-k t)
+    for (t, s) in result.t.iter().zip(result.sensitivities.iter()) {
+        let exact = -t * 2.0 * (-k * t).exp();
+        assert!((s[[0, 0]] - exact).abs() < 1e-6, "t = {}", t);
+    }
+}
+
+#[test]
+fn test_forward_sensitivity_initial_condition() {
+    // With the initial value as a parameter and S(0) = 1, dy/dy0 = exp(-k t)
+    let f = |_t: f64, y: ArrayView1<f64>, p: ArrayView1<f64>| array![-p[0] * y[0]];
+    let result = forward_sensitivity(
+        f,
+        [0.0, 2.0],
+        array![1.0],
+        array![0.3, 0.0],
+        Some(array![[0.0, 1.0]]),
+        Some(tight_options(10)),
+    )
+    .unwrap();
+    let s = result.sensitivities.last().unwrap();
+    assert!((s[[0, 1]] - (-0.6f64).exp()).abs() < 1e-6);
+
+    // Mismatched initial sensitivities are rejected
+    let result = forward_sensitivity(
+        f,
+        [0.0, 2.0],
+        array![1.0],
+        array![0.3],
+        Some(array![[0.0, 1.0]]),
+        None,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_adjoint_terminal_gradient() {
+    let k = 0.7;
+    let t_end = 3.0;
+    let functional = ODEFunctional::terminal(|y: ArrayView1<f64>, _p: ArrayView1<f64>| y[0]);
+    let result = adjoint_gradient(
+        decay,
+        [0.0, t_end],
+        array![2.0],
+        array![k],
+        &functional,
+        Some(tight_options(5)),
+    )
+    .unwrap();
+
+    let decay_factor = (-k * t_end).exp();
+    assert!((result.value - 2.0 * decay_factor).abs() < 1e-8);
+    assert!((result.gradient[0] + t_end * 2.0 * decay_factor).abs() < 1e-6);
+    assert!((result.initial_adjoint[0] - decay_factor).abs() < 1e-6);
+}
+
+#[test]
+fn test_adjoint_running_cost_gradient() {
+    // G = ∫ y^2 dt = y0^2 (1 - exp(-2kT)) / (2k)
+    let k = 0.5;
+    let t_end = 2.0;
+    let functional =
+        ODEFunctional::running(|_t: f64, y: ArrayView1<f64>, _p: ArrayView1<f64>| y[0] * y[0]);
+    let result = adjoint_gradient(
+        decay,
+        [0.0, t_end],
+        array![1.0],
+        array![k],
+        &functional,
+        Some(tight_options(4)),
+    )
+    .unwrap();
+
+    let e = (-2.0 * k * t_end).exp();
+    let value = (1.0 - e) / (2.0 * k);
+    let gradient = (2.0 * t_end * e) / (2.0 * k) - (1.0 - e) / (2.0 * k * k);
+    assert!((result.value - value).abs() < 1e-7);
+    assert!((result.gradient[0] - gradient).abs() < 1e-5);
+    assert!((result.initial_adjoint[0] - 2.0 * value).abs() < 1e-5);
+}
+
+#[test]
+fn test_adjoint_matches_forward_sensitivities() {
+    // Lotka-Volterra with four parameters; G = x(T) + 2 y(T)
+    let f = |_t: f64, y: ArrayView1<f64>, p: ArrayView1<f64>| {
+        array![
+            p[0] * y[0] - p[1] * y[0] * y[1],
+            p[2] * y[0] * y[1] - p[3] * y[1]
+        ]
+    };
+    let y0 = array![1.0, 0.5];
+    let p = array![1.5, 1.0, 0.8, 1.2];
+
+    let forward = forward_sensitivity(
+        f,
+        [0.0, 5.0],
+        y0.clone(),
+        p.clone(),
+        None,
+        Some(tight_options(1)),
+    )
+    .unwrap();
+    let s = forward.sensitivities.last().unwrap();
+
+    let functional =
+        ODEFunctional::terminal(|y: ArrayView1<f64>, _p: ArrayView1<f64>| y[0] + 2.0 * y[1]);
+    for n_checkpoints in [1, 8] {
+        let adjoint = adjoint_gradient(
+            f,
+            [0.0, 5.0],
+            y0.clone(),
+            p.clone(),
+            &functional,
+            Some(tight_options(n_checkpoints)),
+        )
+        .unwrap();
+        for k in 0..4 {
+            let expected = s[[0, k]] + 2.0 * s[[1, k]];
+            assert!(
+                (adjoint.gradient[k] - expected).abs() < 1e-4 * expected.abs().max(1.0),
+                "checkpoints {}: parameter {}: {} vs {}",
+                n_checkpoints,
+                k,
+                adjoint.gradient[k],
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use ndarray::{Array1, ArrayView1};
+use scirs2_integrate::ode::{solve_ivp, ODEMethod, ODEOptions, SparsityPattern};
+
+const N: usize = 40;
+
+/// Stiff reaction-diffusion system u_t = u_xx - u^3 on a grid with zero boundaries
+fn reaction_diffusion(_t: f64, u: ArrayView1<f64>) -> Array1<f64> {
+    let dx2 = 1.0 / ((N + 1) as f64).powi(2);
+    Array1::from_shape_fn(N, |i| {
+        let left = if i > 0 { u[i - 1] } else { 0.0 };
+        let right = if i + 1 < N { u[i + 1] } else { 0.0 };
+        (left - 2.0 * u[i] + right) / dx2 - u[i].powi(3)
+    })
+}
+
+fn initial_condition() -> Array1<f64> {
+    Array1::from_shape_fn(N, |i| {
+        let x = (i + 1) as f64 / (N + 1) as f64;
+        (std::f64::consts::PI * x).sin() + 0.5 * (3.0 * std::f64::consts::PI * x).sin()
+    })
+}
+
+fn options(method: ODEMethod, sparsity: Option<SparsityPattern>) -> ODEOptions<f64> {
+    ODEOptions {
+        method,
+        rtol: 1e-6,
+        atol: 1e-8,
+        max_steps: 10000,
+        jac_sparsity: sparsity,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_radau_sparse_jacobian() {
+    let dense = solve_ivp(
+        reaction_diffusion,
+        [0.0, 0.5],
+        initial_condition(),
+        Some(options(ODEMethod::Radau, None)),
+    )
+    .unwrap();
+    let sparse = solve_ivp(
+        reaction_diffusion,
+        [0.0, 0.5],
+        initial_condition(),
+        Some(options(
+            ODEMethod::Radau,
+            Some(SparsityPattern::banded(N, 1, 1)),
+        )),
+    )
+    .unwrap();
+    assert!(dense.success && sparse.success);
+
+    let y_dense = dense.y.last().unwrap();
+    let y_sparse = sparse.y.last().unwrap();
+    for i in 0..N {
+        assert!((y_dense[i] - y_sparse[i]).abs() < 1e-6);
+    }
+
+    // Three evaluations per Jacobian instead of N
+    assert!(sparse.n_jac > 0);
+    assert!(sparse.n_eval + sparse.n_jac * (N - 3) <= dense.n_eval + 10 * N);
+    assert!(sparse.n_eval < dense.n_eval);
+}
+
+#[test]
+fn test_bdf_sparse_jacobian() {
+    // Linear diffusion u_t = u_xx
+    let diffusion = |_t: f64, u: ArrayView1<f64>| {
+        let dx2 = 1.0 / ((N + 1) as f64).powi(2);
+        Array1::from_shape_fn(N, |i| {
+            let left = if i > 0 { u[i - 1] } else { 0.0 };
+            let right = if i + 1 < N { u[i + 1] } else { 0.0 };
+            (left - 2.0 * u[i] + right) / dx2
+        })
+    };
+    let bdf_options = |sparsity| ODEOptions {
+        method: ODEMethod::Bdf,
+        max_order: Some(1),
+        max_steps: 10000,
+        jac_sparsity: sparsity,
+        ..Default::default()
+    };
+
+    let dense = solve_ivp(
+        diffusion,
+        [0.0, 0.1],
+        initial_condition(),
+        Some(bdf_options(None)),
+    )
+    .unwrap();
+    let sparse = solve_ivp(
+        diffusion,
+        [0.0, 0.1],
+        initial_condition(),
+        Some(bdf_options(Some(SparsityPattern::banded(N, 1, 1)))),
+    )
+    .unwrap();
+    assert!(dense.success && sparse.success);
+
+    let y_dense = dense.y.last().unwrap();
+    let y_sparse = sparse.y.last().unwrap();
+    for i in 0..N {
+        assert!((y_dense[i] - y_sparse[i]).abs() < 1e-6);
+    }
+    assert!(sparse.n_eval < dense.n_eval);
+}
+
+#[test]
+fn test_sparsity_pattern_shape_is_checked() {
+    for method in [ODEMethod::Radau, ODEMethod::Bdf] {
+        let result = solve_ivp(
+            reaction_diffusion,
+            [0.0, 0.1],
+            initial_condition(),
+            Some(options(method, Some(SparsityPattern::banded(N - 1, 1, 1)))),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -6,6 +6,7 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use ndarray::Array2;
+use num_complex::Complex64;
 use scirs2_linalg::matrix_functions;
 use scirs2_linalg::prelude::*;
 use scirs2_linalg::*;
@@ -289,8 +290,13 @@ fn bench_matrix_sqrt(c: &mut Criterion) {
             &general_matrix,
             |b, m| {
                 b.iter(|| {
-                    // sqrtm_schur not available, use standard sqrtm
-                    sqrtm(black_box(&m.view()), 100, 1e-12).unwrap()
+                    matrix_functions::sqrtm_with_method(
+                        black_box(&m.view()),
+                        matrix_functions::SqrtmMethod::Schur,
+                        100,
+                        1e-12,
+                    )
+                    .unwrap()
                 })
             },
         );
@@ -301,8 +307,13 @@ fn bench_matrix_sqrt(c: &mut Criterion) {
             &spd_matrix,
             |b, m| {
                 b.iter(|| {
-                    // sqrtm_denman_beavers not available, use standard sqrtm
-                    sqrtm(black_box(&m.view()), 100, 1e-12).unwrap()
+                    matrix_functions::sqrtm_with_method(
+                        black_box(&m.view()),
+                        matrix_functions::SqrtmMethod::DenmanBeavers,
+                        100,
+                        1e-12,
+                    )
+                    .unwrap()
                 })
             },
         );
@@ -313,8 +324,13 @@ fn bench_matrix_sqrt(c: &mut Criterion) {
             &spd_matrix,
             |b, m| {
                 b.iter(|| {
-                    // sqrtm_newton not available, use standard sqrtm
-                    sqrtm(black_box(&m.view()), 100, 1e-12).unwrap()
+                    matrix_functions::sqrtm_with_method(
+                        black_box(&m.view()),
+                        matrix_functions::SqrtmMethod::Newton,
+                        100,
+                        1e-12,
+                    )
+                    .unwrap()
                 })
             },
         );
@@ -336,33 +352,35 @@ fn bench_matrix_sign(c: &mut Criterion) {
 
         // Matrix sign function
         group.bench_with_input(BenchmarkId::new("sign_function", size), &matrix, |b, m| {
-            b.iter(|| {
-                // matrix_sign not available, use signm
-                matrix_functions::signm(black_box(&m.view())).unwrap()
-            })
+            b.iter(|| matrix_functions::signm(black_box(&m.view())).unwrap())
         });
 
         // Matrix sign function (controlled eigenvalues)
         group.bench_with_input(
             BenchmarkId::new("sign_function_controlled", size),
             &controlled_eigenvals,
-            |b, m| b.iter(|| matrix_sign(black_box(&m.view())).unwrap()),
+            |b, m| {
+                let options = matrix_functions::MatrixSignOptions::default();
+                b.iter(|| matrix_functions::matrix_sign(black_box(&m.view()), &options).unwrap())
+            },
         );
 
         // Matrix sign function via Newton iteration
         group.bench_with_input(BenchmarkId::new("sign_newton", size), &matrix, |b, m| {
             b.iter(|| {
-                // matrix_sign_newton not available, use signm
-                matrix_functions::signm(black_box(&m.view())).unwrap()
+                matrix_functions::matrix_sign_newton(
+                    black_box(&m.view()),
+                    matrix_functions::SignScaling::Determinant,
+                    100,
+                    1e-12,
+                )
+                .unwrap()
             })
         });
 
         // Matrix sign function via Schur decomposition
         group.bench_with_input(BenchmarkId::new("sign_schur", size), &matrix, |b, m| {
-            b.iter(|| {
-                // matrix_sign_schur not available, use signm
-                matrix_functions::signm(black_box(&m.view())).unwrap()
-            })
+            b.iter(|| matrix_functions::matrix_sign_schur(black_box(&m.view())).unwrap())
         });
     }
 
@@ -501,26 +519,22 @@ fn bench_general_matrix_function(c: &mut Criterion) {
             BenchmarkId::new("funm_polynomial", size),
             &matrix,
             |b, m| {
-                b.iter(|| {
-                    // funm not available, use expm as placeholder
-                    matrix_functions::expm(black_box(&m.view()), None).unwrap()
-                })
+                let one = Complex64::new(1.0, 0.0);
+                let f = |z: Complex64| z * z + z * 2.0 + one;
+                b.iter(|| matrix_functions::funm(black_box(&m.view()), f).unwrap())
             },
         );
 
         // General matrix function (f(x) = exp(x))
         group.bench_with_input(BenchmarkId::new("funm_exp", size), &matrix, |b, m| {
-            b.iter(|| {
-                // funm not available, use expm directly
-                matrix_functions::expm(black_box(&m.view()), None).unwrap()
-            })
+            b.iter(|| matrix_functions::funm(black_box(&m.view()), |z| z.exp()).unwrap())
         });
 
         // General matrix function (f(x) = 1/(1+x^2))
         group.bench_with_input(BenchmarkId::new("funm_rational", size), &matrix, |b, m| {
+            let one = Complex64::new(1.0, 0.0);
             b.iter(|| {
-                // funm not available, use identity as placeholder
-                Array2::<f64>::eye(m.nrows())
+                matrix_functions::funm(black_box(&m.view()), |z| one / (one + z * z)).unwrap()
             })
         });
 
@@ -528,12 +542,7 @@ fn bench_general_matrix_function(c: &mut Criterion) {
         group.bench_with_input(
             BenchmarkId::new("funm_schur_parlett", size),
             &matrix,
-            |b, m| {
-                b.iter(|| {
-                    // funm_schur_parlett not available, use sqrtm
-                    sqrtm(black_box(&m.view()), 100, 1e-12).unwrap()
-                })
-            },
+            |b, m| b.iter(|| matrix_functions::funm(black_box(&m.view()), |z| z.sqrt()).unwrap()),
         );
     }
 
@@ -555,10 +564,9 @@ fn bench_accuracy_performance_tradeoffs(c: &mut Criterion) {
         group.bench_with_input(
             BenchmarkId::new(format!("expm_tol_{:.0e}", tolerance), size),
             &(&matrix, tolerance),
-            |b, (m, _tol)| {
+            |b, (m, tol)| {
                 b.iter(|| {
-                    // expm_with_tolerance not available, use standard expm
-                    matrix_functions::expm(black_box(&m.view()), None).unwrap()
+                    matrix_functions::expm_with_tolerance(black_box(&m.view()), *tol).unwrap()
                 })
             },
         );
@@ -220,11 +220,13 @@ fn main() -> LinalgResult<()> {
         Err(e) => println!("Expected error: {}", e),
     }
 
-    println!("Attempting banded solver (not yet implemented):");
-    let dummy_banded = array![[1.0, 2.0], [3.0, 4.0]];
-    match compat::solve_banded(&dummy_banded.view(), &b.view(), false, false, true) {
-        Ok(_) => println!("Banded solver succeeded"),
-        Err(e) => println!("Expected error: {}", e),
+    println!("Banded solver:");
+    // Upper bidiagonal [[1, 3], [0, 2]] in LAPACK band storage
+    let banded = array![[0.0, 3.0], [1.0, 2.0]];
+    let rhs = array![[4.0], [2.0]];
+    match compat::solve_banded((0, 1), &banded.view(), &rhs.view(), false, false, true) {
+        Ok(x) => println!("Banded solution: {:?}", x),
+        Err(e) => println!("Banded solver failed: {}", e),
     }
 
     println!("\n=== SciPy API Compatibility Showcase Complete ===");
@@ -337,6 +339,6 @@ mod tests {
         assert!(compat::tanm(&a.view()).is_ok());
 
         let b = array![[1.0], [2.0]];
-        assert!(compat::solve_banded(&a.view(), &b.view(), false, false, true).is_err());
+        assert!(compat::solve_banded((1, 1), &a.view(), &b.view(), false, false, true).is_err());
     }
 }
@@ -125,7 +125,7 @@ fn main() {
     println!("Solution = {:?}", x);
 
     // Verify the solution
-    let toeplitz = ToeplitzMatrix::new(c.view(), r.view()).unwrap();
+    let toeplitz = ToeplitzMatrix::new(r.view(), c.view()).unwrap();
     let tx = toeplitz.matvec(&x.view()).unwrap();
 
     println!("\nVerification (Tx should equal b):");
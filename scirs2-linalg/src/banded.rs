@@ -0,0 +1,1005 @@
+//! Banded matrices in LAPACK band storage
+//!
+//! Band storage keeps only the diagonals inside the band, so an `n x n` matrix
+//! with `kl` subdiagonals and `ku` superdiagonals needs `(kl + ku + 1) * n`
+//! numbers instead of `n^2`, and factorizations cost `O(n (kl + ku)^2)` instead
+//! of `O(n^3)`. The layouts match LAPACK and `scipy.linalg`:
+//!
+//! * General band (`solve_banded`, [`Banded`]): `ab[ku + i - j, j] = a[i, j]`
+//!   for `max(0, j - ku) <= i <= min(n - 1, j + kl)`.
+//! * Symmetric band, upper form (`lower = false`): `ab[u + i - j, j] = a[i, j]`
+//!   for `max(0, j - u) <= i <= j`; the last row holds the diagonal.
+//! * Symmetric band, lower form (`lower = true`): `ab[i - j, j] = a[i, j]` for
+//!   `j <= i <= min(n - 1, j + u)`; the first row holds the diagonal.
+//!
+//! Entries of `ab` that fall outside the matrix are ignored.
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use num_traits::{Float, NumAssign};
+use std::iter::Sum;
+
+use crate::error::{LinalgError, LinalgResult};
+
+/// Square matrix in LAPACK general band storage
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::banded::Banded;
+///
+/// // [[4, 1, 0],
+/// //  [2, 5, 1],
+/// //  [0, 2, 6]]
+/// let a = array![[4.0_f64, 1.0, 0.0], [2.0, 5.0, 1.0], [0.0, 2.0, 6.0]];
+/// let band = Banded::from_dense(&a.view(), 1, 1).unwrap();
+/// assert_eq!(band.band().dim(), (3, 3));
+///
+/// let x = band.solve(&array![5.0, 8.0, 8.0].view()).unwrap();
+/// assert!((x[0] - 1.0).abs() < 1e-12);
+/// assert!((x[1] - 1.0).abs() < 1e-12);
+/// assert!((x[2] - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Banded<F> {
+    ab: Array2<F>,
+    kl: usize,
+    ku: usize,
+}
+
+impl<F> Banded<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    /// Wrap existing band storage with `kl` subdiagonals and `ku` superdiagonals
+    pub fn new(ab: Array2<F>, kl: usize, ku: usize) -> LinalgResult<Self> {
+        if ab.nrows() != kl + ku + 1 {
+            return Err(LinalgError::ShapeError(format!(
+                "Band storage must have kl + ku + 1 = {} rows, got {}",
+                kl + ku + 1,
+                ab.nrows()
+            )));
+        }
+        Ok(Self { ab, kl, ku })
+    }
+
+    /// Extract the band of a dense square matrix
+    pub fn from_dense(a: &ArrayView2<F>, kl: usize, ku: usize) -> LinalgResult<Self> {
+        if a.nrows() != a.ncols() {
+            return Err(LinalgError::ShapeError(format!(
+                "Banded matrices must be square, got shape {:?}",
+                a.shape()
+            )));
+        }
+        let n = a.nrows();
+        let mut ab = Array2::zeros((kl + ku + 1, n));
+        for j in 0..n {
+            for i in j.saturating_sub(ku)..n.min(j + kl + 1) {
+                ab[[ku + i - j, j]] = a[[i, j]];
+            }
+        }
+        Ok(Self { ab, kl, ku })
+    }
+
+    /// Dimension of the matrix
+    pub fn n(&self) -> usize {
+        self.ab.ncols()
+    }
+
+    /// Number of subdiagonals
+    pub fn lower_bandwidth(&self) -> usize {
+        self.kl
+    }
+
+    /// Number of superdiagonals
+    pub fn upper_bandwidth(&self) -> usize {
+        self.ku
+    }
+
+    /// Underlying band storage
+    pub fn band(&self) -> ArrayView2<'_, F> {
+        self.ab.view()
+    }
+
+    /// Element `(i, j)` of the full matrix
+    pub fn get(&self, i: usize, j: usize) -> F {
+        if i + self.ku < j || i > j + self.kl {
+            F::zero()
+        } else {
+            self.ab[[self.ku + i - j, j]]
+        }
+    }
+
+    /// Expand to a dense matrix
+    pub fn to_dense(&self) -> Array2<F> {
+        let n = self.n();
+        let mut a = Array2::zeros((n, n));
+        for j in 0..n {
+            for i in j.saturating_sub(self.ku)..n.min(j + self.kl + 1) {
+                a[[i, j]] = self.ab[[self.ku + i - j, j]];
+            }
+        }
+        a
+    }
+
+    /// Compute `A x` in `O(n (kl + ku))` operations
+    pub fn matvec(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let n = self.n();
+        if x.len() != n {
+            return Err(LinalgError::ShapeError(format!(
+                "Vector length {} does not match matrix dimension {}",
+                x.len(),
+                n
+            )));
+        }
+        let mut y = Array1::zeros(n);
+        for j in 0..n {
+            for i in j.saturating_sub(self.ku)..n.min(j + self.kl + 1) {
+                y[i] += self.ab[[self.ku + i - j, j]] * x[j];
+            }
+        }
+        Ok(y)
+    }
+
+    /// LU factorization with partial pivoting
+    pub fn lu(&self) -> LinalgResult<BandedLu<F>> {
+        BandedLu::new(self)
+    }
+
+    /// Solve `A x = b` for a single right-hand side
+    pub fn solve(&self, b: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let rhs = b.to_owned().insert_axis(ndarray::Axis(1));
+        let x = self.lu()?.solve(&rhs.view())?;
+        Ok(x.column(0).to_owned())
+    }
+}
+
+/// LU factorization `P A = L U` of a banded matrix
+///
+/// Row interchanges widen the upper band of `U` to `kl + ku`, so the factors
+/// are kept in band storage with `2 kl + ku + 1` rows as in LAPACK `gbtrf`.
+#[derive(Debug, Clone)]
+pub struct BandedLu<F> {
+    lu: Array2<F>,
+    pivots: Vec<usize>,
+    kl: usize,
+    ku: usize,
+}
+
+impl<F> BandedLu<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    fn new(a: &Banded<F>) -> LinalgResult<Self> {
+        let n = a.n();
+        let (kl, ku) = (a.kl, a.ku);
+        let kv = kl + ku;
+        let mut lu = Array2::zeros((2 * kl + ku + 1, n));
+        for j in 0..n {
+            for i in j.saturating_sub(ku)..n.min(j + kl + 1) {
+                lu[[kv + i - j, j]] = a.ab[[ku + i - j, j]];
+            }
+        }
+
+        let mut pivots = vec![0; n];
+        // Last column touched by the upper factor so far
+        let mut ju = 0;
+        for j in 0..n {
+            let km = kl.min(n - 1 - j);
+
+            let mut p = 0;
+            let mut max = lu[[kv, j]].abs();
+            for r in 1..=km {
+                let v = lu[[kv + r, j]].abs();
+                if v > max {
+                    max = v;
+                    p = r;
+                }
+            }
+            pivots[j] = j + p;
+            if max == F::zero() {
+                return Err(LinalgError::singular_matrix_with_suggestions(
+                    "banded LU factorization",
+                    (n, n),
+                    None,
+                ));
+            }
+
+            ju = ju.max((j + ku + p).min(n - 1));
+            if p != 0 {
+                for c in j..=ju {
+                    lu.swap([kv + j - c, c], [kv + j + p - c, c]);
+                }
+            }
+
+            let pivot = lu[[kv, j]];
+            for r in 1..=km {
+                lu[[kv + r, j]] /= pivot;
+            }
+            for c in (j + 1)..=ju {
+                let u_jc = lu[[kv + j - c, c]];
+                if u_jc == F::zero() {
+                    continue;
+                }
+                for r in 1..=km {
+                    let l = lu[[kv + r, j]];
+                    lu[[kv + j + r - c, c]] -= l * u_jc;
+                }
+            }
+        }
+
+        Ok(Self { lu, pivots, kl, ku })
+    }
+
+    /// Solve `A X = B` using the factorization
+    pub fn solve(&self, b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        let n = self.lu.ncols();
+        if b.nrows() != n {
+            return Err(LinalgError::ShapeError(format!(
+                "Right-hand side has {} rows, expected {}",
+                b.nrows(),
+                n
+            )));
+        }
+        let kv = self.kl + self.ku;
+        let mut x = b.to_owned();
+
+        for col in 0..x.ncols() {
+            // Forward substitution with interleaved row interchanges
+            for j in 0..n {
+                let p = self.pivots[j];
+                if p != j {
+                    x.swap([j, col], [p, col]);
+                }
+                let xj = x[[j, col]];
+                for r in 1..=self.kl.min(n - 1 - j) {
+                    x[[j + r, col]] -= self.lu[[kv + r, j]] * xj;
+                }
+            }
+            // Back substitution with the upper factor of bandwidth kl + ku
+            for i in (0..n).rev() {
+                let mut sum = x[[i, col]];
+                for c in (i + 1)..n.min(i + kv + 1) {
+                    sum -= self.lu[[kv + i - c, c]] * x[[c, col]];
+                }
+                x[[i, col]] = sum / self.lu[[kv, i]];
+            }
+        }
+
+        Ok(x)
+    }
+
+    /// Determinant of the factored matrix
+    pub fn det(&self) -> F {
+        let kv = self.kl + self.ku;
+        self.pivots
+            .iter()
+            .enumerate()
+            .fold(F::one(), |acc, (j, &p)| {
+                let d = acc * self.lu[[kv, j]];
+                if p != j {
+                    -d
+                } else {
+                    d
+                }
+            })
+    }
+}
+
+/// Solve `A x = b` where `A` is given in general band storage.
+///
+/// # Arguments
+///
+/// * `l_and_u` - Number of subdiagonals and superdiagonals `(kl, ku)`
+/// * `ab` - Band storage of shape `(kl + ku + 1, n)`
+/// * `b` - Right-hand sides of shape `(n, k)`
+///
+/// # Returns
+///
+/// * Solution `x` of shape `(n, k)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::banded::solve_banded;
+///
+/// // Tridiagonal [[2, -1, 0], [-1, 2, -1], [0, -1, 2]]
+/// let ab = array![[0.0_f64, -1.0, -1.0], [2.0, 2.0, 2.0], [-1.0, -1.0, 0.0]];
+/// let b = array![[1.0], [0.0], [1.0]];
+/// let x = solve_banded((1, 1), &ab.view(), &b.view()).unwrap();
+/// assert!((x[[0, 0]] - 1.0).abs() < 1e-12);
+/// assert!((x[[1, 0]] - 1.0).abs() < 1e-12);
+/// assert!((x[[2, 0]] - 1.0).abs() < 1e-12);
+/// ```
+pub fn solve_banded<F>(
+    l_and_u: (usize, usize),
+    ab: &ArrayView2<F>,
+    b: &ArrayView2<F>,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum,
+{
+    let (kl, ku) = l_and_u;
+    Banded::new(ab.to_owned(), kl, ku)?.lu()?.solve(b)
+}
+
+/// Symmetric band matrix accessor for the upper or lower storage form
+struct SymmetricBand<'a, 'b, F> {
+    ab: &'a ArrayView2<'b, F>,
+    u: usize,
+    lower: bool,
+}
+
+impl<F: Float> SymmetricBand<'_, '_, F> {
+    fn get(&self, i: usize, j: usize) -> F {
+        let (i, j) = if i >= j { (i, j) } else { (j, i) };
+        if i - j > self.u {
+            F::zero()
+        } else if self.lower {
+            self.ab[[i - j, j]]
+        } else {
+            // a[j, i] with j <= i lives at ab[u + j - i, i]
+            self.ab[[self.u + j - i, i]]
+        }
+    }
+}
+
+fn validate_symmetric_band<F: Float>(ab: &ArrayView2<F>) -> LinalgResult<()> {
+    if ab.nrows() == 0 {
+        return Err(LinalgError::ShapeError(
+            "Symmetric band storage must have at least one row".to_string(),
+        ));
+    }
+    if ab.iter().any(|x| !x.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Band storage contains non-finite values".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Cholesky factorization of a symmetric positive definite band matrix.
+///
+/// # Arguments
+///
+/// * `ab` - Symmetric band storage of shape `(u + 1, n)`
+/// * `lower` - Whether `ab` is in lower form; the factor is returned in the same form
+///
+/// # Returns
+///
+/// * `L` with `A = L L^T` (lower form) or `U` with `A = U^T U` (upper form), in band storage
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::banded::cholesky_banded;
+///
+/// // [[4, 2], [2, 5]] in lower form
+/// let ab = array![[4.0_f64, 5.0], [2.0, 0.0]];
+/// let l = cholesky_banded(&ab.view(), true).unwrap();
+/// assert!((l[[0, 0]] - 2.0).abs() < 1e-12);
+/// assert!((l[[1, 0]] - 1.0).abs() < 1e-12);
+/// assert!((l[[0, 1]] - 2.0).abs() < 1e-12);
+/// ```
+pub fn cholesky_banded<F>(ab: &ArrayView2<F>, lower: bool) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum,
+{
+    let l = cholesky_lower_factor(ab, lower)?;
+    if lower {
+        return Ok(l);
+    }
+
+    // U = L^T: U[j, i] = L[i, j] stored at ub[u + j - i, i]
+    let u = l.nrows() - 1;
+    let n = l.ncols();
+    let mut ub = Array2::<F>::zeros((u + 1, n));
+    for j in 0..n {
+        for i in j..n.min(j + u + 1) {
+            ub[[u + j - i, i]] = l[[i - j, j]];
+        }
+    }
+    Ok(ub)
+}
+
+/// Cholesky factor `L` in lower band form, reading `ab` in either storage form
+fn cholesky_lower_factor<F>(ab: &ArrayView2<F>, lower: bool) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum,
+{
+    validate_symmetric_band(ab)?;
+    let u = ab.nrows() - 1;
+    let n = ab.ncols();
+    let a = SymmetricBand { ab, u, lower };
+
+    // Work in lower form: l[i - j, j] = L[i, j]
+    let mut l = Array2::<F>::zeros((u + 1, n));
+    for j in 0..n {
+        let mut d = a.get(j, j);
+        for k in j.saturating_sub(u)..j {
+            let ljk = l[[j - k, k]];
+            d -= ljk * ljk;
+        }
+        if d <= F::zero() {
+            return Err(LinalgError::non_positive_definite_with_suggestions(
+                "banded Cholesky decomposition",
+                (n, n),
+                None,
+            ));
+        }
+        let ljj = d.sqrt();
+        l[[0, j]] = ljj;
+
+        for i in (j + 1)..n.min(j + u + 1) {
+            let mut sum = a.get(i, j);
+            for k in i.saturating_sub(u)..j {
+                sum -= l[[i - k, k]] * l[[j - k, k]];
+            }
+            l[[i - j, j]] = sum / ljj;
+        }
+    }
+
+    Ok(l)
+}
+
+/// Solve `A x = b` for a symmetric positive definite band matrix.
+///
+/// # Arguments
+///
+/// * `ab` - Symmetric band storage of shape `(u + 1, n)`
+/// * `b` - Right-hand sides of shape `(n, k)`
+/// * `lower` - Whether `ab` is in lower form
+///
+/// # Returns
+///
+/// * Solution `x` of shape `(n, k)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::banded::solveh_banded;
+///
+/// // [[4, 1, 0], [1, 4, 1], [0, 1, 4]] in upper form
+/// let ab = array![[0.0_f64, 1.0, 1.0], [4.0, 4.0, 4.0]];
+/// let b = array![[5.0], [6.0], [5.0]];
+/// let x = solveh_banded(&ab.view(), &b.view(), false).unwrap();
+/// for i in 0..3 {
+///     assert!((x[[i, 0]] - 1.0).abs() < 1e-12);
+/// }
+/// ```
+pub fn solveh_banded<F>(
+    ab: &ArrayView2<F>,
+    b: &ArrayView2<F>,
+    lower: bool,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum,
+{
+    let n = ab.ncols();
+    if b.nrows() != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side has {} rows, expected {}",
+            b.nrows(),
+            n
+        )));
+    }
+    let l = cholesky_lower_factor(ab, lower)?;
+    let u = l.nrows() - 1;
+
+    let mut x = b.to_owned();
+    for col in 0..x.ncols() {
+        // L y = b
+        for i in 0..n {
+            let mut sum = x[[i, col]];
+            for k in i.saturating_sub(u)..i {
+                sum -= l[[i - k, k]] * x[[k, col]];
+            }
+            x[[i, col]] = sum / l[[0, i]];
+        }
+        // L^T x = y
+        for i in (0..n).rev() {
+            let mut sum = x[[i, col]];
+            for k in (i + 1)..n.min(i + u + 1) {
+                sum -= l[[k - i, i]] * x[[k, col]];
+            }
+            x[[i, col]] = sum / l[[0, i]];
+        }
+    }
+    Ok(x)
+}
+
+/// Symmetric band workspace in lower form with one extra diagonal for bulges
+struct BandWorkspace<F> {
+    w: Array2<F>,
+    width: usize,
+}
+
+impl<F: Float> BandWorkspace<F> {
+    fn get(&self, i: usize, j: usize) -> F {
+        let (i, j) = if i >= j { (i, j) } else { (j, i) };
+        if i - j > self.width {
+            F::zero()
+        } else {
+            self.w[[i - j, j]]
+        }
+    }
+
+    fn set(&mut self, i: usize, j: usize, value: F) {
+        let (i, j) = if i >= j { (i, j) } else { (j, i) };
+        if i - j <= self.width {
+            self.w[[i - j, j]] = value;
+        }
+    }
+
+    /// Apply the similarity `G A G^T` with `G` rotating the plane `(p, p + 1)`
+    fn rotate(&mut self, p: usize, c: F, s: F) {
+        let n = self.w.ncols();
+        let q = p + 1;
+        let lo = p.saturating_sub(self.width);
+        let hi = n.min(q + self.width + 1);
+        for r in lo..hi {
+            if r == p || r == q {
+                continue;
+            }
+            let (x, y) = (self.get(r, p), self.get(r, q));
+            self.set(r, p, c * x + s * y);
+            self.set(r, q, c * y - s * x);
+        }
+        let (app, apq, aqq) = (self.get(p, p), self.get(q, p), self.get(q, q));
+        let cs = c * s;
+        self.set(p, p, c * c * app + (cs + cs) * apq + s * s * aqq);
+        self.set(q, q, s * s * app - (cs + cs) * apq + c * c * aqq);
+        self.set(q, p, cs * (aqq - app) + (c * c - s * s) * apq);
+    }
+}
+
+/// Reduce a symmetric band matrix to tridiagonal form with Givens rotations
+///
+/// Uses Schwarz's algorithm, which lowers the bandwidth one diagonal at a time
+/// and chases the resulting bulge down the band. Returns the diagonal, the
+/// subdiagonal (padded with a trailing zero) and, if requested, the orthogonal
+/// matrix `Q` with `A = Q T Q^T`.
+fn band_to_tridiagonal<F>(
+    ab: &ArrayView2<F>,
+    lower: bool,
+    want_q: bool,
+) -> (Vec<F>, Vec<F>, Option<Array2<F>>)
+where
+    F: Float + NumAssign + Sum,
+{
+    let u = ab.nrows() - 1;
+    let n = ab.ncols();
+    let a = SymmetricBand { ab, u, lower };
+    let mut work = BandWorkspace {
+        w: Array2::zeros((u + 2, n)),
+        width: u + 1,
+    };
+    for j in 0..n {
+        for i in j..n.min(j + u + 1) {
+            work.w[[i - j, j]] = a.get(i, j);
+        }
+    }
+    let mut q = if want_q { Some(Array2::eye(n)) } else { None };
+
+    for k in (2..=u).rev() {
+        for j in 0..n.saturating_sub(k) {
+            let (mut col, mut row) = (j, j + k);
+            while row < n {
+                let x = work.get(row - 1, col);
+                let y = work.get(row, col);
+                if y != F::zero() {
+                    let r = x.hypot(y);
+                    let (c, s) = (x / r, y / r);
+                    work.rotate(row - 1, c, s);
+                    work.set(row, col, F::zero());
+                    if let Some(q) = q.as_mut() {
+                        for i in 0..n {
+                            let (zp, zq) = (q[[i, row - 1]], q[[i, row]]);
+                            q[[i, row - 1]] = c * zp + s * zq;
+                            q[[i, row]] = c * zq - s * zp;
+                        }
+                    }
+                }
+                // The rotation pushed a bulge to (row + k, row - 1)
+                col = row - 1;
+                row += k;
+            }
+        }
+    }
+
+    let d = (0..n).map(|i| work.get(i, i)).collect();
+    let e = (0..n)
+        .map(|i| {
+            if i + 1 < n {
+                work.get(i + 1, i)
+            } else {
+                F::zero()
+            }
+        })
+        .collect();
+    (d, e, q)
+}
+
+/// Eigenvalues (and eigenvectors) of a symmetric tridiagonal matrix
+///
+/// Implicit QL iteration with Wilkinson-type shifts (`tql2`). `d` holds the
+/// diagonal and `e[i]` the entry coupling `i` and `i + 1` (with `e[n - 1]`
+/// ignored). If `z` is given, the rotations are accumulated into its columns,
+/// so passing the identity yields the eigenvectors of the tridiagonal matrix.
+/// Eigenvalues are returned in ascending order.
+pub(crate) fn tridiagonal_ql_implicit<F>(
+    mut d: Vec<F>,
+    mut e: Vec<F>,
+    mut z: Option<&mut Array2<F>>,
+) -> LinalgResult<Vec<F>>
+where
+    F: Float + NumAssign,
+{
+    let n = d.len();
+    if n == 0 {
+        return Ok(d);
+    }
+    e.resize(n, F::zero());
+    e[n - 1] = F::zero();
+    let eps = F::epsilon();
+    let two = F::one() + F::one();
+    let mut f = F::zero();
+    let mut tst1 = F::zero();
+
+    for l in 0..n {
+        let mut iter = 0;
+        tst1 = tst1.max(d[l].abs() + e[l].abs());
+        let mut m = l;
+        while m < n - 1 && e[m].abs() > eps * tst1 {
+            m += 1;
+        }
+
+        if m > l {
+            loop {
+                iter += 1;
+                if iter > 60 {
+                    return Err(LinalgError::ConvergenceError(
+                        "Tridiagonal QL iteration did not converge".to_string(),
+                    ));
+                }
+
+                // Compute the implicit shift
+                let g = d[l];
+                let mut p = (d[l + 1] - g) / (two * e[l]);
+                let mut r = p.hypot(F::one());
+                if p < F::zero() {
+                    r = -r;
+                }
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+                let dl1 = d[l + 1];
+                let h = g - d[l];
+                for di in d.iter_mut().skip(l + 2) {
+                    *di -= h;
+                }
+                f += h;
+
+                // Implicit QL transformation
+                p = d[m];
+                let mut c = F::one();
+                let mut c2 = c;
+                let mut c3 = c;
+                let el1 = e[l + 1];
+                let mut s = F::zero();
+                let mut s2 = F::zero();
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    let g = c * e[i];
+                    let h = c * p;
+                    r = p.hypot(e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+
+                    if let Some(z) = z.as_deref_mut() {
+                        for k in 0..z.nrows() {
+                            let h = z[[k, i + 1]];
+                            z[[k, i + 1]] = s * z[[k, i]] + c * h;
+                            z[[k, i]] = c * z[[k, i]] - s * h;
+                        }
+                    }
+                }
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+
+                if e[l].abs() <= eps * tst1 {
+                    break;
+                }
+            }
+        }
+        d[l] += f;
+        e[l] = F::zero();
+    }
+
+    // Selection sort keeps eigenvector columns in step with the eigenvalues
+    for i in 0..n.saturating_sub(1) {
+        let mut k = i;
+        for j in (i + 1)..n {
+            if d[j] < d[k] {
+                k = j;
+            }
+        }
+        if k != i {
+            d.swap(i, k);
+            if let Some(z) = z.as_deref_mut() {
+                for r in 0..z.nrows() {
+                    z.swap([r, i], [r, k]);
+                }
+            }
+        }
+    }
+
+    Ok(d)
+}
+
+/// Eigenvalues and eigenvectors of a symmetric band matrix.
+///
+/// The band is reduced to tridiagonal form by Givens rotations that stay inside
+/// the band (plus one bulge diagonal), then the tridiagonal problem is solved
+/// by the implicit QL algorithm. Memory use for the reduction is `O(n u)`.
+///
+/// # Arguments
+///
+/// * `ab` - Symmetric band storage of shape `(u + 1, n)`
+/// * `lower` - Whether `ab` is in lower form
+///
+/// # Returns
+///
+/// * Eigenvalues in ascending order and the corresponding eigenvectors as columns
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::banded::eig_banded;
+///
+/// // [[2, -1, 0], [-1, 2, -1], [0, -1, 2]] in lower form
+/// let ab = array![[2.0_f64, 2.0, 2.0], [-1.0, -1.0, 0.0]];
+/// let (w, v) = eig_banded(&ab.view(), true).unwrap();
+/// assert!((w[0] - (2.0 - 2.0_f64.sqrt())).abs() < 1e-12);
+/// assert!((w[1] - 2.0).abs() < 1e-12);
+/// assert!((w[2] - (2.0 + 2.0_f64.sqrt())).abs() < 1e-12);
+/// assert_eq!(v.dim(), (3, 3));
+/// ```
+pub fn eig_banded<F>(ab: &ArrayView2<F>, lower: bool) -> LinalgResult<(Array1<F>, Array2<F>)>
+where
+    F: Float + NumAssign + Sum,
+{
+    validate_symmetric_band(ab)?;
+    let (d, e, q) = band_to_tridiagonal(ab, lower, true);
+    let mut q = q.unwrap_or_else(|| Array2::eye(d.len()));
+    let w = tridiagonal_ql_implicit(d, e, Some(&mut q))?;
+    Ok((Array1::from(w), q))
+}
+
+/// Eigenvalues of a symmetric band matrix.
+///
+/// Same algorithm as [`eig_banded`] without accumulating eigenvectors, which
+/// reduces the cost to `O(n^2 u)`.
+///
+/// # Arguments
+///
+/// * `ab` - Symmetric band storage of shape `(u + 1, n)`
+/// * `lower` - Whether `ab` is in lower form
+///
+/// # Returns
+///
+/// * Eigenvalues in ascending order
+pub fn eigvals_banded<F>(ab: &ArrayView2<F>, lower: bool) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign + Sum,
+{
+    validate_symmetric_band(ab)?;
+    let (d, e, _) = band_to_tridiagonal(ab, lower, false);
+    Ok(Array1::from(tridiagonal_ql_implicit(d, e, None)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn test_matrix(n: usize, kl: usize, ku: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            if i > j + kl || j > i + ku {
+                0.0
+            } else if i == j {
+                4.0 + (i as f64 * 0.37).sin()
+            } else {
+                ((i * 7 + j * 3) as f64).cos()
+            }
+        })
+    }
+
+    fn symmetric_band_matrix(n: usize, u: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            let (i, j) = if i >= j { (i, j) } else { (j, i) };
+            if i - j > u {
+                0.0
+            } else if i == j {
+                (2 * u + 2) as f64 + (i as f64).sin()
+            } else {
+                ((i * 5 + j * 11) as f64 * 0.3).cos()
+            }
+        })
+    }
+
+    fn lower_storage(a: &Array2<f64>, u: usize) -> Array2<f64> {
+        let n = a.nrows();
+        Array2::from_shape_fn(
+            (u + 1, n),
+            |(r, j)| if j + r < n { a[[j + r, j]] } else { 0.0 },
+        )
+    }
+
+    fn upper_storage(a: &Array2<f64>, u: usize) -> Array2<f64> {
+        let n = a.nrows();
+        Array2::from_shape_fn(
+            (u + 1, n),
+            |(r, j)| {
+                if j + r >= u {
+                    a[[j + r - u, j]]
+                } else {
+                    0.0
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn test_banded_round_trip_and_matvec() {
+        let a = test_matrix(6, 2, 1);
+        let band = Banded::from_dense(&a.view(), 2, 1).unwrap();
+        assert_eq!(band.to_dense(), a);
+        assert_eq!(band.get(4, 2), a[[4, 2]]);
+        assert_eq!(band.get(0, 3), 0.0);
+
+        let x = Array1::from_shape_fn(6, |i| i as f64 - 2.5);
+        let y = band.matvec(&x.view()).unwrap();
+        let expected = a.dot(&x);
+        for (p, q) in y.iter().zip(expected.iter()) {
+            assert!((p - q).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_solve_banded_requires_pivoting() {
+        // Zero on the diagonal forces row interchanges
+        let a = array![
+            [0.0, 2.0, 0.0, 0.0],
+            [1.0, 0.0, 3.0, 0.0],
+            [0.0, 4.0, 1.0, 1.0],
+            [0.0, 0.0, 2.0, 5.0]
+        ];
+        let band = Banded::from_dense(&a.view(), 1, 1).unwrap();
+        let b = array![[2.0, 1.0], [4.0, 0.0], [6.0, 2.0], [7.0, -1.0]];
+        let x = solve_banded((1, 1), &band.band(), &b.view()).unwrap();
+        let residual = a.dot(&x) - &b;
+        assert!(residual.iter().all(|r| r.abs() < 1e-12));
+
+        let det = band.lu().unwrap().det();
+        assert!((det - crate::basic::det(&a.view(), None).unwrap()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_solve_banded_wide_band() {
+        let a = test_matrix(12, 3, 2);
+        let band = Banded::from_dense(&a.view(), 3, 2).unwrap();
+        let x_true = Array1::from_shape_fn(12, |i| (i as f64).cos());
+        let b = a.dot(&x_true);
+        let x = band.solve(&b.view()).unwrap();
+        for (p, q) in x.iter().zip(x_true.iter()) {
+            assert!((p - q).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_solve_banded_singular() {
+        let ab = array![[0.0, 0.0, 0.0], [1.0, 0.0, 1.0], [0.0, 0.0, 0.0]];
+        let b = array![[1.0], [1.0], [1.0]];
+        assert!(solve_banded((1, 1), &ab.view(), &b.view()).is_err());
+    }
+
+    #[test]
+    fn test_cholesky_banded_both_forms() {
+        let a = symmetric_band_matrix(7, 2);
+        let l = cholesky_banded(&lower_storage(&a, 2).view(), true).unwrap();
+        let u = cholesky_banded(&upper_storage(&a, 2).view(), false).unwrap();
+
+        let mut l_dense = Array2::<f64>::zeros((7, 7));
+        for j in 0..7 {
+            for r in 0..3 {
+                if j + r < 7 {
+                    l_dense[[j + r, j]] = l[[r, j]];
+                }
+            }
+        }
+        let reconstructed = l_dense.dot(&l_dense.t());
+        assert!(reconstructed
+            .iter()
+            .zip(a.iter())
+            .all(|(p, q)| (p - q).abs() < 1e-12));
+
+        // The upper factor is the transpose of the lower factor
+        for j in 0..7 {
+            for r in 0..3 {
+                if j + r < 7 {
+                    assert!((u[[2 - r, j + r]] - l[[r, j]]).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cholesky_banded_not_positive_definite() {
+        let ab = array![[1.0, -1.0], [2.0, 0.0]];
+        assert!(cholesky_banded(&ab.view(), true).is_err());
+    }
+
+    #[test]
+    fn test_solveh_banded() {
+        let a = symmetric_band_matrix(9, 3);
+        let x_true = Array2::from_shape_fn((9, 2), |(i, j)| (i + j) as f64 * 0.5 - 1.0);
+        let b = a.dot(&x_true);
+        for (ab, lower) in [(lower_storage(&a, 3), true), (upper_storage(&a, 3), false)] {
+            let x = solveh_banded(&ab.view(), &b.view(), lower).unwrap();
+            assert!(x
+                .iter()
+                .zip(x_true.iter())
+                .all(|(p, q)| (p - q).abs() < 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_eig_banded_decomposition() {
+        let n = 10;
+        let u = 3;
+        let a = symmetric_band_matrix(n, u);
+        for (ab, lower) in [(lower_storage(&a, u), true), (upper_storage(&a, u), false)] {
+            let (w, v) = eig_banded(&ab.view(), lower).unwrap();
+
+            for k in 1..n {
+                assert!(w[k - 1] <= w[k]);
+            }
+            let av = a.dot(&v);
+            for k in 0..n {
+                for i in 0..n {
+                    assert!((av[[i, k]] - w[k] * v[[i, k]]).abs() < 1e-10);
+                }
+            }
+            let vtv = v.t().dot(&v);
+            for i in 0..n {
+                for j in 0..n {
+                    let expected = if i == j { 1.0 } else { 0.0 };
+                    assert!((vtv[[i, j]] - expected).abs() < 1e-10);
+                }
+            }
+
+            let w_only = eigvals_banded(&ab.view(), lower).unwrap();
+            assert!(w_only
+                .iter()
+                .zip(w.iter())
+                .all(|(p, q)| (p - q).abs() < 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_eig_banded_diagonal() {
+        let ab = array![[3.0, 1.0, 2.0]];
+        let w = eigvals_banded(&ab.view(), true).unwrap();
+        assert_eq!(w.to_vec(), vec![1.0, 2.0, 3.0]);
+    }
+}
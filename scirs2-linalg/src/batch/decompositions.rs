@@ -0,0 +1,268 @@
+//! Batched solves and factorizations of many small matrices
+//!
+//! Each function takes a 3D array of shape `(batch_size, m, n)` and processes
+//! the matrices independently in parallel across the batch dimension. This
+//! suits workloads with thousands of small (4x4 to 64x64) systems per step,
+//! where parallelizing inside a single factorization does not pay off.
+//!
+//! On failure the error of the first failing matrix (in batch order) is
+//! returned.
+
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3, Axis};
+use num_traits::{Float, NumAssign};
+use scirs2_core::parallel_ops::*;
+use std::iter::Sum;
+
+use crate::error::{LinalgError, LinalgResult};
+
+/// Apply `f` to every matrix of the batch in parallel
+fn map_batch<F, T, Op>(batch: &ArrayView3<F>, f: Op) -> LinalgResult<Vec<T>>
+where
+    F: Float + Send + Sync,
+    T: Send,
+    Op: Fn(usize, ArrayView2<F>) -> LinalgResult<T> + Send + Sync,
+{
+    (0..batch.len_of(Axis(0)))
+        .into_par_iter()
+        .map(|i| f(i, batch.index_axis(Axis(0), i)))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Stack equally shaped matrices into a `(batch_size, rows, cols)` array
+fn stack<F: Float>(matrices: &[Array2<F>], shape: (usize, usize)) -> Array3<F> {
+    let mut result = Array3::zeros((matrices.len(), shape.0, shape.1));
+    for (mut slot, m) in result.outer_iter_mut().zip(matrices.iter()) {
+        slot.assign(m);
+    }
+    result
+}
+
+/// Solve a batch of linear systems `A_i X_i = B_i`
+///
+/// # Arguments
+///
+/// * `a` - Coefficient matrices of shape (batch_size, n, n)
+/// * `b` - Right-hand sides of shape (batch_size, n, k)
+///
+/// # Returns
+///
+/// * Solutions of shape (batch_size, n, k)
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array3;
+/// use scirs2_linalg::batch::solve_batched;
+///
+/// // Two diagonal systems
+/// let a = Array3::from_shape_vec((2, 2, 2), vec![2.0_f64, 0.0, 0.0, 4.0, 1.0, 0.0, 0.0, 5.0]).unwrap();
+/// let b = Array3::from_shape_vec((2, 2, 1), vec![2.0, 8.0, 3.0, 10.0]).unwrap();
+/// let x = solve_batched(&a.view(), &b.view()).unwrap();
+/// assert!((x[[0, 1, 0]] - 2.0).abs() < 1e-12);
+/// assert!((x[[1, 0, 0]] - 3.0).abs() < 1e-12);
+/// ```
+pub fn solve_batched<F>(a: &ArrayView3<F>, b: &ArrayView3<F>) -> LinalgResult<Array3<F>>
+where
+    F: Float + NumAssign + Sum + Send + Sync,
+{
+    let (batch_size, n, n2) = a.dim();
+    let (batch_b, nb, k) = b.dim();
+    if n != n2 {
+        return Err(LinalgError::ShapeError(format!(
+            "solve_batched expects square matrices, got {}x{}",
+            n, n2
+        )));
+    }
+    if batch_b != batch_size || nb != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand sides of shape {:?} do not match matrices of shape {:?}",
+            b.dim(),
+            a.dim()
+        )));
+    }
+
+    let solutions = map_batch(a, |i, ai| {
+        crate::solve::solve_multiple(&ai, &b.index_axis(Axis(0), i), None)
+    })?;
+    Ok(stack(&solutions, (n, k)))
+}
+
+/// Cholesky decomposition of a batch of symmetric positive definite matrices
+///
+/// # Arguments
+///
+/// * `a` - Matrices of shape (batch_size, n, n)
+///
+/// # Returns
+///
+/// * Lower triangular factors `L_i` with `A_i = L_i L_i^T`, shape (batch_size, n, n)
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array3;
+/// use scirs2_linalg::batch::cholesky_batched;
+///
+/// let a = Array3::from_shape_vec((2, 2, 2), vec![4.0_f64, 2.0, 2.0, 5.0, 9.0, 0.0, 0.0, 1.0]).unwrap();
+/// let l = cholesky_batched(&a.view()).unwrap();
+/// assert!((l[[0, 1, 0]] - 1.0).abs() < 1e-12);
+/// assert!((l[[1, 0, 0]] - 3.0).abs() < 1e-12);
+/// ```
+pub fn cholesky_batched<F>(a: &ArrayView3<F>) -> LinalgResult<Array3<F>>
+where
+    F: Float + NumAssign + Sum + Send + Sync,
+{
+    let (_, n, n2) = a.dim();
+    if n != n2 {
+        return Err(LinalgError::ShapeError(format!(
+            "cholesky_batched expects square matrices, got {}x{}",
+            n, n2
+        )));
+    }
+
+    let factors = map_batch(a, |_, ai| crate::decomposition::cholesky(&ai, None))?;
+    Ok(stack(&factors, (n, n)))
+}
+
+/// Singular value decomposition of a batch of matrices
+///
+/// # Arguments
+///
+/// * `a` - Matrices of shape (batch_size, m, n)
+/// * `full_matrices` - Whether to compute full-sized `U` and `V^T`
+///
+/// # Returns
+///
+/// * Tuple `(U, S, Vt)` with `U` of shape (batch_size, m, m) or
+///   (batch_size, m, k), `S` of shape (batch_size, k) and `Vt` of shape
+///   (batch_size, n, n) or (batch_size, k, n), where `k = min(m, n)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array3;
+/// use scirs2_linalg::batch::svd_batched;
+///
+/// let a = Array3::from_shape_vec((2, 2, 2), vec![3.0_f64, 0.0, 0.0, -2.0, 1.0, 1.0, 1.0, 1.0]).unwrap();
+/// let (_, s, _) = svd_batched(&a.view(), false).unwrap();
+/// assert!((s[[0, 0]] - 3.0).abs() < 1e-10 && (s[[0, 1]] - 2.0).abs() < 1e-10);
+/// assert!((s[[1, 0]] - 2.0).abs() < 1e-6 && s[[1, 1]].abs() < 1e-6);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn svd_batched<F>(
+    a: &ArrayView3<F>,
+    full_matrices: bool,
+) -> LinalgResult<(Array3<F>, Array2<F>, Array3<F>)>
+where
+    F: Float + NumAssign + Sum + ndarray::ScalarOperand + Send + Sync,
+{
+    let (batch_size, m, n) = a.dim();
+    let k = m.min(n);
+    let (u_shape, vt_shape) = if full_matrices {
+        ((m, m), (n, n))
+    } else {
+        ((m, k), (k, n))
+    };
+
+    let factors = map_batch(a, |_, ai| {
+        crate::decomposition::svd(&ai, full_matrices, None)
+    })?;
+
+    let mut u = Array3::zeros((batch_size, u_shape.0, u_shape.1));
+    let mut s = Array2::zeros((batch_size, k));
+    let mut vt = Array3::zeros((batch_size, vt_shape.0, vt_shape.1));
+    for (i, (ui, si, vti)) in factors.into_iter().enumerate() {
+        if ui.dim() != u_shape || si.len() != k || vti.dim() != vt_shape {
+            return Err(LinalgError::ComputationError(format!(
+                "SVD of batch element {} returned unexpected shapes",
+                i
+            )));
+        }
+        u.index_axis_mut(Axis(0), i).assign(&ui);
+        s.row_mut(i).assign(&si);
+        vt.index_axis_mut(Axis(0), i).assign(&vti);
+    }
+    Ok((u, s, vt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Batch of diagonally dominant symmetric matrices
+    fn spd_batch(batch: usize, n: usize) -> Array3<f64> {
+        Array3::from_shape_fn((batch, n, n), |(b, i, j)| {
+            if i == j {
+                n as f64 + 1.0 + b as f64
+            } else {
+                1.0 / (1.0 + (i + j + b) as f64)
+            }
+        })
+    }
+
+    #[test]
+    fn test_solve_batched() {
+        let a = spd_batch(50, 6);
+        let b = Array3::from_shape_fn((50, 6, 2), |(b, i, j)| (b + i * j) as f64);
+        let x = solve_batched(&a.view(), &b.view()).unwrap();
+        for i in 0..50 {
+            let ax = a.index_axis(Axis(0), i).dot(&x.index_axis(Axis(0), i));
+            for (p, q) in ax.iter().zip(b.index_axis(Axis(0), i).iter()) {
+                assert_relative_eq!(*p, *q, epsilon = 1e-10);
+            }
+        }
+
+        let wrong = Array3::<f64>::zeros((49, 6, 2));
+        assert!(solve_batched(&a.view(), &wrong.view()).is_err());
+    }
+
+    #[test]
+    fn test_cholesky_batched() {
+        let a = spd_batch(20, 5);
+        let l = cholesky_batched(&a.view()).unwrap();
+        for i in 0..20 {
+            let li = l.index_axis(Axis(0), i);
+            let llt = li.dot(&li.t());
+            for (p, q) in llt.iter().zip(a.index_axis(Axis(0), i).iter()) {
+                assert_relative_eq!(*p, *q, epsilon = 1e-10);
+            }
+        }
+
+        let mut bad = a.clone();
+        bad[[7, 0, 0]] = -1.0;
+        assert!(cholesky_batched(&bad.view()).is_err());
+    }
+
+    #[test]
+    fn test_svd_batched() {
+        let a = Array3::from_shape_fn((10, 4, 3), |(b, i, j)| {
+            ((b + 2 * i + 3 * j) % 5) as f64 - 2.0
+        });
+        let (u, s, vt) = svd_batched(&a.view(), false).unwrap();
+        assert_eq!(u.dim(), (10, 4, 3));
+        assert_eq!(s.dim(), (10, 3));
+        assert_eq!(vt.dim(), (10, 3, 3));
+        for i in 0..10 {
+            let us = &u.index_axis(Axis(0), i) * &s.row(i);
+            let rebuilt = us.dot(&vt.index_axis(Axis(0), i));
+            for (p, q) in rebuilt.iter().zip(a.index_axis(Axis(0), i).iter()) {
+                assert_relative_eq!(*p, *q, epsilon = 1e-6);
+            }
+        }
+
+        let (u, _, vt) = svd_batched(&a.view(), true).unwrap();
+        assert_eq!(u.dim(), (10, 4, 4));
+        assert_eq!(vt.dim(), (10, 3, 3));
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let a = Array3::<f64>::zeros((0, 3, 3));
+        assert_eq!(cholesky_batched(&a.view()).unwrap().dim(), (0, 3, 3));
+        let (u, s, vt) = svd_batched(&a.view(), false).unwrap();
+        assert_eq!((u.dim(), s.dim(), vt.dim()), ((0, 3, 3), (0, 3), (0, 3, 3)));
+    }
+}
@@ -16,6 +16,10 @@ pub use attention::{
     batch_flash_attention, batch_multi_head_attention, batch_multi_query_attention,
 };
 
+// Re-export batched solves and factorizations
+pub mod decompositions;
+pub use decompositions::{cholesky_batched, solve_batched, svd_batched};
+
 /// Perform matrix multiplication on a batch of matrices
 ///
 /// Computes a batch multiplication where the input represents multiple matrices
@@ -35,6 +35,8 @@
 //! - `lstsq()` - Least squares solver
 //! - `solve_triangular()` - Triangular system solver
 //! - `solve_banded()` - Banded matrix solver
+//! - `solveh_banded()` - Symmetric positive definite banded solver
+//! - `cholesky_banded()` - Banded Cholesky decomposition
 //!
 //! ### Matrix Functions
 //! - `expm()` - Matrix exponential
@@ -499,12 +501,13 @@ where
 }
 
 // Eigenvalue functions that match SciPy naming
+pub use crate::banded::{eig_banded, eigvals_banded};
 pub use crate::eigen::eigvals;
 pub use crate::eigen::eigvalsh;
-pub use crate::eigen_specialized::banded_eigh as eig_banded;
-pub use crate::eigen_specialized::banded_eigvalsh as eigvals_banded;
-pub use crate::eigen_specialized::tridiagonal_eigh as eigh_tridiagonal;
-pub use crate::eigen_specialized::tridiagonal_eigvalsh as eigvalsh_tridiagonal;
+pub use crate::tridiagonal::{eigh_tridiagonal, eigvalsh_tridiagonal};
+
+// Banded solvers that match SciPy naming
+pub use crate::banded::{cholesky_banded, solveh_banded};
 
 // Matrix functions
 pub use crate::basic::matrix_power as fractional_matrix_power;
@@ -705,25 +708,7 @@ where
     F: Float + Sum + NumAssign + ndarray::ScalarOperand,
 {
     match p {
-        None | Some("2") => norm_mod::cond(a, p, None),
-        Some("1") => {
-            let norm_a = norm_mod::matrix_norm(a, "1", None)?;
-            let inv_a = basic::inv(a, None)?;
-            let norm_inv_a = norm_mod::matrix_norm(&inv_a.view(), "1", None)?;
-            Ok(norm_a * norm_inv_a)
-        }
-        Some("inf") => {
-            let norm_a = norm_mod::matrix_norm(a, "inf", None)?;
-            let inv_a = basic::inv(a, None)?;
-            let norm_inv_a = norm_mod::matrix_norm(&inv_a.view(), "inf", None)?;
-            Ok(norm_a * norm_inv_a)
-        }
-        Some("fro") => {
-            let norm_a = norm_mod::matrix_norm(a, "frobenius", None)?;
-            let inv_a = basic::inv(a, None)?;
-            let norm_inv_a = norm_mod::matrix_norm(&inv_a.view(), "frobenius", None)?;
-            Ok(norm_a * norm_inv_a)
-        }
+        None | Some("2") | Some("1") | Some("inf") | Some("fro") => norm_mod::cond(a, p, None),
         _ => Err(LinalgError::InvalidInput(format!(
             "Unsupported norm type for condition number: {:?}",
             p
@@ -1056,18 +1041,20 @@ where
 /// * `output` - Type of output ('real' or 'complex')
 /// * `lwork` - Work array size (currently ignored)
 /// * `overwrite_a` - Allow overwriting data in `a` (currently ignored)
-/// * `sort` - Sorting function for eigenvalues (currently ignored)
+/// * `sort` - Predicate on the real part of each eigenvalue; eigenvalues for
+///   which it returns `true` are moved to the leading block (a complex pair
+///   moves together)
 /// * `check_finite` - Whether to check that input matrices contain only finite numbers
 ///
 /// # Returns
-/// * Tuple of (T, Z) where A = Z T Z^H
+/// * Tuple of (Z, T) where A = Z T Z^T and T is upper quasi-triangular
 #[allow(clippy::too_many_arguments)]
 pub fn schur<F>(
     a: &ArrayView2<F>,
     output: &str,
     _lwork: Option<usize>,
     _overwrite_a: bool,
-    _sort: Option<fn(F) -> bool>,
+    sort: Option<fn(F) -> bool>,
     check_finite: bool,
 ) -> LinalgResult<(Array2<F>, Array2<F>)>
 where
@@ -1085,8 +1072,18 @@ where
 
     match output {
         "real" | "complex" => {
-            // Use the actual schur decomposition implementation
-            decomposition::schur(a)
+            let (t, z) = crate::schur::real_schur(a)?;
+            let (t, z) = match sort {
+                Some(predicate) => {
+                    let select: Vec<bool> = crate::schur::real_schur_eigenvalues(&t.view())
+                        .iter()
+                        .map(|lambda| predicate(lambda.re))
+                        .collect();
+                    crate::schur::ordschur_real(&t.view(), &z.view(), &select)?
+                }
+                None => (t, z),
+            };
+            Ok((z, t))
         }
         _ => Err(LinalgError::InvalidInput(format!(
             "Invalid Schur output type: {}",
@@ -1139,6 +1136,7 @@ where
 /// Solve banded linear system (SciPy-compatible interface)
 ///
 /// # Arguments
+/// * `l_and_u` - Number of nonzero lower and upper diagonals
 /// * `ab` - Banded matrix in LAPACK format
 /// * `b` - Right-hand side
 /// * `overwrite_ab` - Allow overwriting data in `ab` (currently ignored)
@@ -1148,8 +1146,9 @@ where
 /// # Returns
 /// * Solution to the banded system
 pub fn solve_banded<F>(
-    _ab: &ArrayView2<F>,
-    _b: &ArrayView2<F>,
+    l_and_u: (usize, usize),
+    ab: &ArrayView2<F>,
+    b: &ArrayView2<F>,
     _overwrite_ab: bool,
     _overwrite_b: bool,
     check_finite: bool,
@@ -1157,11 +1156,11 @@ pub fn solve_banded<F>(
 where
     F: Float + Sum + NumAssign,
 {
-    if check_finite {
-        // Check would go here
+    if check_finite && (ab.iter().any(|x| !x.is_finite()) || b.iter().any(|x| !x.is_finite())) {
+        return Err(LinalgError::ValueError(
+            "Input arrays contain non-finite values".to_string(),
+        ));
     }
 
-    Err(LinalgError::NotImplementedError(
-        "Banded matrix solver not yet implemented".to_string(),
-    ))
+    crate::banded::solve_banded(l_and_u, ab, b)
 }
@@ -112,104 +112,80 @@ where
 /// Performs complex QR decomposition
 ///
 /// Decomposes A into Q * R where:
-/// - Q is unitary (Q^H * Q = I)
-/// - R is upper triangular
+/// - Q is unitary (Q^H * Q = I), of shape (m, m)
+/// - R is upper triangular, of shape (m, n)
+///
+/// Uses Householder reflections, so rank-deficient matrices are handled.
 pub fn complex_qr<F>(a: &ArrayView2<Complex<F>>) -> LinalgResult<ComplexQRDecomposition<F>>
 where
     F: Float + Sum + Debug,
 {
     let (m, n) = (a.nrows(), a.ncols());
-    let k = m.min(n);
-
-    // Start with A
-    let mut q = Array2::<Complex<F>>::zeros((m, k));
-    let mut r = Array2::<Complex<F>>::zeros((k, n));
-
-    // Copy columns of A
-    let mut columns = vec![];
-    for j in 0..n {
-        let mut col = Array1::<Complex<F>>::zeros(m);
-        for i in 0..m {
-            col[i] = a[[i, j]];
-        }
-        columns.push(col);
-    }
-
-    // Gram-Schmidt process
-    for j in 0..k {
-        // Start with the j-th column of A
-        let mut q_j = columns[j].clone();
-
-        // Orthogonalize against all previous columns
-        for i in 0..j {
-            // Calculate projection coefficient: <q_i, a_j>
-            let mut projection = Complex::<F>::zero();
-            for k in 0..m {
-                projection = projection + q[[k, i]].conj() * q_j[k];
-            }
-
-            // Store in R
-            r[[i, j]] = projection;
-
-            // Subtract projection from q_j
-            for k in 0..m {
-                q_j[k] = q_j[k] - projection * q[[k, i]];
+    let mut r = a.to_owned();
+    let mut q = Array2::<Complex<F>>::eye(m);
+    let two = F::one() + F::one();
+
+    for j in 0..m.min(n) {
+        let norm = (j..m).map(|i| r[[i, j]].norm_sqr()).sum::<F>().sqrt();
+        if norm == F::zero() {
+            continue;
+        }
+        let x0 = r[[j, j]];
+        let phase = if x0.norm() > F::zero() {
+            x0 / x0.norm()
+        } else {
+            Complex::one()
+        };
+        let alpha = -phase * norm;
+
+        let mut v: Vec<Complex<F>> = (j..m).map(|i| r[[i, j]]).collect();
+        v[0] = v[0] - alpha;
+        let v_norm_sq: F = v.iter().map(|x| x.norm_sqr()).sum();
+        if v_norm_sq == F::zero() {
+            continue;
+        }
+        let scale = Complex::new(two / v_norm_sq, F::zero());
+
+        // R <- H R with H = I - 2 v v^H / (v^H v)
+        for c in j..n {
+            let dot = v.iter().enumerate().fold(Complex::zero(), |acc, (k, vk)| {
+                acc + vk.conj() * r[[j + k, c]]
+            });
+            let f = scale * dot;
+            for (k, vk) in v.iter().enumerate() {
+                r[[j + k, c]] = r[[j + k, c]] - f * vk;
             }
         }
-
-        // Calculate norm
-        let mut norm_sq = F::zero();
-        for i in 0..m {
-            norm_sq = norm_sq + q_j[i].norm_sqr();
-        }
-
-        if norm_sq < F::epsilon() {
-            return Err(LinalgError::SingularMatrixError(
-                "Columns are linearly dependent".to_string(),
-            ));
-        }
-
-        let norm = norm_sq.sqrt();
-        r[[j, j]] = Complex::<F>::new(norm, F::zero());
-
-        // Normalize and store in Q
-        for i in 0..m {
-            q[[i, j]] = q_j[i] / Complex::<F>::new(norm, F::zero());
-        }
-
-        // For columns beyond k, just calculate R entries
-        for l in (j + 1)..n {
-            let mut projection = Complex::<F>::zero();
-            for i in 0..m {
-                projection = projection + q[[i, j]].conj() * a[[i, l]];
+        // Q <- Q H
+        for row in 0..m {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(Complex::zero(), |acc, (k, vk)| acc + q[[row, j + k]] * vk);
+            let f = scale * dot;
+            for (k, vk) in v.iter().enumerate() {
+                q[[row, j + k]] = q[[row, j + k]] - f * vk.conj();
             }
-            r[[j, l]] = projection;
         }
-    }
-
-    // Extend Q to full unitary matrix if needed (m > n)
-    let final_q = if m > n {
-        let mut q_full = Array2::<Complex<F>>::eye(m);
-        // Copy the computed columns
-        for i in 0..m {
-            for j in 0..k {
-                q_full[[i, j]] = q[[i, j]];
-            }
+        for i in (j + 1)..m {
+            r[[i, j]] = Complex::zero();
         }
-        q_full
-    } else {
-        q
-    };
+    }
 
-    Ok(ComplexQRDecomposition { q: final_q, r })
+    Ok(ComplexQRDecomposition { q, r })
 }
 
 /// Performs complex SVD decomposition
 ///
 /// Decomposes A into U * S * V^H where:
 /// - U is unitary (left singular vectors)
-/// - S is diagonal with real, non-negative values
+/// - S is diagonal with real, non-negative values in descending order
 /// - V^H is unitary (conjugate transpose of right singular vectors)
+///
+/// Uses one-sided Jacobi rotations, which compute small singular values to
+/// high relative accuracy. With `full_matrices = false` the shapes are
+/// `(m, k)`, `(k,)` and `(k, n)` with `k = min(m, n)`; otherwise `U` and `V^H`
+/// are square.
 pub fn complex_svd<F>(
     a: &ArrayView2<Complex<F>>,
     full_matrices: bool,
@@ -218,215 +194,168 @@ where
     F: Float + Sum + Debug,
 {
     let (m, n) = (a.nrows(), a.ncols());
-    let k = m.min(n);
-
-    // Compute A^H * A
-    let ah = hermitian_transpose(a);
-    let mut aha = Array2::zeros((n, n));
-
-    for i in 0..n {
-        for j in 0..n {
-            let mut sum = Complex::<F>::zero();
-            for l in 0..m {
-                sum = sum + ah[[i, l]] * a[[l, j]];
-            }
-            aha[[i, j]] = sum;
-        }
+    if m < n {
+        // A^H = U' S V'^H  =>  A = V' S U'^H
+        let ah = hermitian_transpose(a);
+        let svd = complex_svd(&ah.view(), full_matrices)?;
+        return Ok(ComplexSVDDecomposition {
+            u: hermitian_transpose(&svd.vh.view()),
+            s: svd.s,
+            vh: hermitian_transpose(&svd.u.view()),
+        });
+    }
+    if a.iter().any(|z| !z.re.is_finite() || !z.im.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "SVD computation failed: Matrix contains non-finite values".to_string(),
+        ));
     }
 
-    // Eigendecomposition of A^H * A
-    let eig = complex_eigh(&aha.view())?;
-    let eigenvalues = eig.eigenvalues.clone();
-    let v = eig.eigenvectors.clone();
-
-    // Sort eigenvalues and eigenvectors in descending order
-    let mut indices: Vec<usize> = (0..n).collect();
-    indices.sort_by(|&i, &j| eigenvalues[j].re.partial_cmp(&eigenvalues[i].re).unwrap());
-
-    let mut s = Array1::zeros(k);
-    let vh_rows = if full_matrices { n } else { k.min(n) };
-    let mut vt = Array2::zeros((vh_rows, n));
+    let mut u = a.to_owned();
+    let mut v = Array2::<Complex<F>>::eye(n);
+    let eps = F::epsilon();
+    let two = F::one() + F::one();
+    let max_sweeps = 60;
 
-    for (new_idx, &old_idx) in indices.iter().enumerate() {
-        if new_idx < s.len() {
-            s[new_idx] = eigenvalues[old_idx].re.sqrt();
-        }
-        if new_idx < vt.nrows() {
-            for j in 0..n {
-                vt[[new_idx, j]] = v[[j, old_idx]].conj();
+    let mut converged = false;
+    for _ in 0..max_sweeps {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let alpha: F = (0..m).map(|i| u[[i, p]].norm_sqr()).sum();
+                let beta: F = (0..m).map(|i| u[[i, q]].norm_sqr()).sum();
+                let gamma =
+                    (0..m).fold(Complex::zero(), |acc, i| acc + u[[i, p]].conj() * u[[i, q]]);
+                let g = gamma.norm();
+                if g == F::zero() || g <= eps * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                // Rotate [u_p, e^{-i phi} u_q] by [c s; -s c] to orthogonalize
+                let phase = gamma / g;
+                let zeta = (beta - alpha) / (two * g);
+                let t = zeta.signum() / (zeta.abs() + (F::one() + zeta * zeta).sqrt());
+                let c = F::one() / (F::one() + t * t).sqrt();
+                let s = c * t;
+                let (c, s) = (Complex::new(c, F::zero()), Complex::new(s, F::zero()));
+                for mat in [&mut u, &mut v] {
+                    for i in 0..mat.nrows() {
+                        let xp = mat[[i, p]];
+                        let xq = mat[[i, q]] * phase.conj();
+                        mat[[i, p]] = c * xp - s * xq;
+                        mat[[i, q]] = s * xp + c * xq;
+                    }
+                }
             }
         }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(LinalgError::ConvergenceError(format!(
+            "Complex SVD: one-sided Jacobi did not converge in {} sweeps",
+            max_sweeps
+        )));
     }
 
-    // Compute U = A * V * S^(-1)
-    let u_cols = if full_matrices { m } else { k.min(m) };
-    let mut u = Array2::zeros((m, u_cols));
-
-    // Only compute U columns for non-zero singular values
-    for j in 0..s.len().min(u_cols) {
-        if s[j] > F::epsilon() {
+    // Singular values are the column norms; sort them in descending order
+    let norms: Vec<F> = (0..n)
+        .map(|j| (0..m).map(|i| u[[i, j]].norm_sqr()).sum::<F>().sqrt())
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| {
+        norms[j]
+            .partial_cmp(&norms[i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let s = Array1::from_shape_fn(n, |k| norms[order[k]]);
+    let tol = s.get(0).copied().unwrap_or(F::zero()) * eps * F::from(m.max(1)).unwrap();
+    let mut u_thin = Array2::<Complex<F>>::zeros((m, n));
+    let mut v_sorted = Array2::<Complex<F>>::zeros((n, n));
+    let mut rank = 0;
+    for (k, &j) in order.iter().enumerate() {
+        if s[k] > tol && s[k] > F::zero() {
+            let inv = Complex::new(F::one() / s[k], F::zero());
             for i in 0..m {
-                let mut sum = Complex::<F>::zero();
-                for l in 0..n {
-                    sum = sum + a[[i, l]] * v[[l, indices[j]]];
-                }
-                u[[i, j]] = sum / Complex::<F>::new(s[j], F::zero());
+                u_thin[[i, k]] = u[[i, j]] * inv;
             }
+            rank = k + 1;
+        }
+        for i in 0..n {
+            v_sorted[[i, k]] = v[[i, j]];
         }
     }
 
-    if full_matrices && k < u_cols {
-        // TODO: Implement proper orthogonalization for full matrices
-        // For now, we'll leave these columns as zero
+    // Complete U with an orthonormal basis of the complement of its range
+    let u_cols = if full_matrices { m } else { n };
+    let mut u_out = Array2::<Complex<F>>::zeros((m, u_cols));
+    let basis = complex_qr(&u_thin.slice(ndarray::s![.., ..rank]))?.q;
+    for k in 0..u_cols {
+        for i in 0..m {
+            u_out[[i, k]] = if k < rank {
+                u_thin[[i, k]]
+            } else {
+                basis[[i, k]]
+            };
+        }
     }
 
-    // Return vh with correct dimensions
-    Ok(ComplexSVDDecomposition { u, s, vh: vt })
+    Ok(ComplexSVDDecomposition {
+        u: u_out,
+        s,
+        vh: hermitian_transpose(&v_sorted.view()),
+    })
 }
 
 /// Performs eigendecomposition of a complex matrix
 ///
-/// Finds eigenvalues and eigenvectors such that A * v = λ * v
+/// Finds eigenvalues and eigenvectors such that A * v = λ * v. The matrix is
+/// reduced to complex Schur form `A = Z T Z^H`; the eigenvectors of the
+/// triangular factor are found by back substitution and transformed by `Z`.
+/// Eigenvectors are normalized to unit 2-norm.
 pub fn complex_eig<F>(a: &ArrayView2<Complex<F>>) -> LinalgResult<ComplexEigDecomposition<F>>
 where
     F: Float + Sum + Debug,
 {
     check_square(a, "matrix")?;
     let n = a.nrows();
-
-    // Use QR algorithm with shifts for general complex matrices
-    let max_iterations = 1000;
-    let tolerance = F::from(1e-10).unwrap();
-
-    let mut h = a.to_owned(); // Working matrix (will become Schur form)
-    let mut q_total = Array2::eye(n); // Accumulated transformations
-
-    // First, reduce to Hessenberg form using Householder reflections
-    for col in 0..(n - 2) {
-        // Find Householder reflector for column
-        let mut v = Array1::zeros(n - col - 1);
-        let mut norm_sq = F::zero();
-
-        for i in (col + 1)..n {
-            v[i - col - 1] = h[[i, col]];
-            norm_sq = norm_sq + h[[i, col]].norm_sqr();
-        }
-
-        if norm_sq > tolerance {
-            let norm = norm_sq.sqrt();
-            let first_elem = h[[col + 1, col]];
-            let phase = if first_elem.norm() > F::zero() {
-                first_elem / Complex::<F>::new(first_elem.norm(), F::zero())
-            } else {
-                Complex::<F>::one()
-            };
-
-            v[0] = v[0] + phase * Complex::<F>::new(norm, F::zero());
-            let v_norm_sq = v.iter().map(|x| x.norm_sqr()).sum();
-
-            if v_norm_sq > tolerance {
-                // Apply Householder transformation: H = I - 2vv^H/||v||^2
-                for j in col..n {
-                    let mut sum = Complex::<F>::zero();
-                    for i in 0..v.len() {
-                        sum = sum + v[i].conj() * h[[i + col + 1, j]];
-                    }
-                    let factor =
-                        Complex::<F>::new(F::from(2.0).unwrap() / v_norm_sq, F::zero()) * sum;
-
-                    for i in 0..v.len() {
-                        h[[i + col + 1, j]] = h[[i + col + 1, j]] - factor * v[i];
-                    }
-                }
-
-                // Apply from right: H' = H'H
-                for i in 0..n {
-                    let mut sum = Complex::<F>::zero();
-                    for j in 0..v.len() {
-                        sum = sum + h[[i, j + col + 1]] * v[j];
-                    }
-                    let factor =
-                        Complex::<F>::new(F::from(2.0).unwrap() / v_norm_sq, F::zero()) * sum;
-
-                    for j in 0..v.len() {
-                        h[[i, j + col + 1]] = h[[i, j + col + 1]] - factor * v[j].conj();
-                    }
-                }
+    let (t, z) = crate::schur::complex_schur(a)?;
+
+    let norm = t.iter().fold(F::zero(), |acc, x| acc.max(x.norm()));
+    let smin = (F::epsilon() * norm).max(F::min_positive_value());
+
+    let mut eigenvectors = Array2::<Complex<F>>::zeros((n, n));
+    for k in 0..n {
+        // Solve (T - t_kk I) x = 0 with x_k = 1 and x_j = 0 for j > k
+        let lambda = t[[k, k]];
+        let mut x = vec![Complex::<F>::zero(); k + 1];
+        x[k] = Complex::one();
+        for i in (0..k).rev() {
+            let sum = ((i + 1)..=k).fold(Complex::<F>::zero(), |acc, j| acc + t[[i, j]] * x[j]);
+            let mut denom = t[[i, i]] - lambda;
+            if denom.norm() < smin {
+                denom = Complex::new(smin, F::zero());
             }
+            x[i] = -sum / denom;
         }
-    }
-
-    // QR iterations with shifts
-    let mut converged = false;
-    let mut iterations = 0;
-
-    while !converged && iterations < max_iterations {
-        converged = true;
-        iterations += 1;
 
-        // Check for convergence (subdiagonal elements)
-        for i in 0..(n - 1) {
-            if h[[i + 1, i]].norm() > tolerance {
-                converged = false;
-                break;
-            }
+        let mut col = Array1::<Complex<F>>::zeros(n);
+        for i in 0..n {
+            col[i] = (0..=k).fold(Complex::zero(), |acc, j| acc + z[[i, j]] * x[j]);
         }
-
-        if !converged {
-            // Compute shift (Wilkinson shift for 2x2 lower-right block)
-            let mut shift = Complex::<F>::zero();
-            if n >= 2 {
-                let a11 = h[[n - 2, n - 2]];
-                let a12 = h[[n - 2, n - 1]];
-                let a21 = h[[n - 1, n - 2]];
-                let a22 = h[[n - 1, n - 1]];
-
-                let trace = a11 + a22;
-                let det = a11 * a22 - a12 * a21;
-                let discriminant =
-                    trace * trace - Complex::<F>::new(F::from(4.0).unwrap(), F::zero()) * det;
-                let sqrt_disc = discriminant.sqrt();
-
-                let eig1 =
-                    (trace + sqrt_disc) / Complex::<F>::new(F::from(2.0).unwrap(), F::zero());
-                let eig2 =
-                    (trace - sqrt_disc) / Complex::<F>::new(F::from(2.0).unwrap(), F::zero());
-
-                // Choose eigenvalue closer to a22
-                shift = if (eig1 - a22).norm() < (eig2 - a22).norm() {
-                    eig1
-                } else {
-                    eig2
-                };
-            }
-
-            // QR step with shift
-            let mut h_shifted = h.clone();
-            for i in 0..n {
-                h_shifted[[i, i]] = h_shifted[[i, i]] - shift;
-            }
-
-            let qr = complex_qr(&h_shifted.view())?;
-            h = crate::complex::complex_matmul(&qr.r.view(), &qr.q.view()).unwrap();
-
-            for i in 0..n {
-                h[[i, i]] = h[[i, i]] + shift;
-            }
-
-            // Update eigenvector matrix
-            q_total = crate::complex::complex_matmul(&q_total.view(), &qr.q.view()).unwrap();
+        let col_norm = col.iter().map(|c| c.norm_sqr()).sum::<F>().sqrt();
+        if col_norm > F::zero() {
+            col.mapv_inplace(|c| c / col_norm);
         }
-    }
-
-    // Extract eigenvalues from diagonal of Schur form
-    let mut eigenvalues = Array1::zeros(n);
-    for i in 0..n {
-        eigenvalues[i] = h[[i, i]];
+        eigenvectors.column_mut(k).assign(&col);
     }
 
     Ok(ComplexEigDecomposition {
-        eigenvalues,
-        eigenvectors: q_total,
+        eigenvalues: t.diag().to_owned(),
+        eigenvectors,
     })
 }
 
@@ -702,18 +631,14 @@ pub type SchurResult<F> = LinalgResult<(Array2<Complex<F>>, Array2<Complex<F>>)>
 
 /// Performs Schur decomposition of a complex matrix
 ///
-/// Decomposes A into Q * T * Q^H where T is upper triangular
+/// Decomposes A into Q * T * Q^H where T is upper triangular and returns (Q, T)
 pub fn complex_schur<F>(a: &ArrayView2<Complex<F>>) -> SchurResult<F>
 where
     F: Float + Sum + Debug,
 {
     check_square(a, "matrix")?;
-
-    // This is a placeholder for full Schur decomposition
-    // A complete implementation would use QR algorithm with shifts
-    Err(LinalgError::NotImplementedError(
-        "Complex Schur decomposition not yet implemented".to_string(),
-    ))
+    let (t, q) = crate::schur::complex_schur(a)?;
+    Ok((q, t))
 }
 
 #[cfg(test)]
@@ -369,7 +369,10 @@ where
 ///
 /// # Returns
 ///
-/// * Tuple (Z, T) where Z is orthogonal and T is upper triangular
+/// * Tuple (Z, T) where Z is orthogonal and T is upper quasi-triangular
+///
+/// This is [`crate::schur::real_schur`] with the factors in the opposite order;
+/// see that function for the block structure of `T`.
 ///
 /// # Examples
 ///
@@ -392,24 +395,7 @@ where
         )));
     }
 
-    // For now, we'll use a simple approach based on QR iteration
-    // Real production code would use a more sophisticated and numerically stable
-    // implementation, likely calling into LAPACK's xGEES function
-
-    let n = a.nrows();
-    let mut t = a.to_owned();
-    let mut z = Array2::eye(n);
-
-    // Simple QR iteration (this is inefficient but illustrates the concept)
-    // In a real implementation, we would use a more sophisticated algorithm
-    // or directly call LAPACK's DGEES/SGEES function
-    let max_iter = 100;
-    for _ in 0..max_iter {
-        let (q, r) = qr(&t.view(), None)?;
-        t = r.dot(&q); // T = R*Q gives the upper triangular form
-        z = z.dot(&q); // Accumulate the transformation
-    }
-
+    let (t, z) = crate::schur::real_schur(a)?;
     Ok((z, t))
 }
 
@@ -0,0 +1,819 @@
+//! Iterative eigensolvers for large symmetric operators
+//!
+//! These solvers compute a few eigenpairs at either end of the spectrum of a
+//! symmetric operator using only products `A x`, so they work with anything
+//! implementing [`LinearOperator`]: dense arrays as well as matrix-free
+//! closures wrapped in [`crate::matrixfree::LinearOperator`].
+//!
+//! - [`eigsh`] - Thick-restart Lanczos with full reorthogonalization
+//! - [`lobpcg`] - Locally optimal block preconditioned conjugate gradient,
+//!   which can exploit a preconditioner approximating `A⁻¹`
+//!
+//! Both accept a block of deflation vectors: the iteration is restricted to
+//! their orthogonal complement, so already known eigenvectors (for example
+//! the constant vector of a graph Laplacian) are excluded from the result.
+
+use ndarray::{Array1, Array2, ArrayView2, ScalarOperand};
+use num_traits::{Float, NumAssign};
+use std::iter::Sum;
+
+use crate::error::{LinalgError, LinalgResult};
+use crate::iterative::{LinearOperator, PreconditionerOp};
+
+/// Which end of the spectrum an iterative eigensolver targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EigenvalueTarget {
+    /// Algebraically largest eigenvalues
+    Largest,
+    /// Algebraically smallest eigenvalues
+    Smallest,
+}
+
+/// Options for [`eigsh`] and [`lobpcg`]
+#[derive(Debug, Clone)]
+pub struct IterativeEigenOptions<F> {
+    /// Maximum number of iterations (restart cycles for [`eigsh`])
+    pub max_iterations: usize,
+    /// Relative tolerance: an eigenpair has converged once
+    /// `‖A x - λ x‖ <= tolerance * max|λ|`
+    pub tolerance: F,
+    /// Dimension of the Lanczos basis before a restart; `None` selects
+    /// `max(2k + 1, 20)`. Ignored by [`lobpcg`].
+    pub basis_size: Option<usize>,
+    /// Seed of the pseudo-random starting vectors
+    pub seed: u64,
+}
+
+impl<F: Float> Default for IterativeEigenOptions<F> {
+    fn default() -> Self {
+        Self {
+            max_iterations: 500,
+            tolerance: F::from(1e-8).unwrap(),
+            basis_size: None,
+            seed: 42,
+        }
+    }
+}
+
+impl<F: Float> IterativeEigenOptions<F> {
+    /// Create options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of iterations
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the relative tolerance
+    pub fn with_tolerance(mut self, tolerance: F) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the Lanczos basis size
+    pub fn with_basis_size(mut self, basis_size: usize) -> Self {
+        self.basis_size = Some(basis_size);
+        self
+    }
+
+    /// Set the seed of the starting vectors
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// Eigenpairs computed by an iterative eigensolver
+#[derive(Debug, Clone)]
+pub struct IterativeEigenResult<F> {
+    /// Eigenvalues in ascending order
+    pub eigenvalues: Array1<F>,
+    /// Corresponding orthonormal eigenvectors as columns
+    pub eigenvectors: Array2<F>,
+    /// Residual norms `‖A x - λ x‖` of the returned eigenpairs
+    pub residual_norms: Array1<F>,
+    /// Number of iterations performed
+    pub iterations: usize,
+    /// Whether all eigenpairs reached the tolerance
+    pub converged: bool,
+}
+
+/// Pseudo-random vector with entries in [-1, 1) (SplitMix64)
+fn random_vector<F: Float>(n: usize, seed: u64) -> Array1<F> {
+    let mut state = seed;
+    Array1::from_shape_fn(n, |_| {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        F::from((z >> 11) as f64 / (1u64 << 52) as f64 - 1.0).unwrap()
+    })
+}
+
+fn dot<F: Float + Sum>(x: &Array1<F>, y: &Array1<F>) -> F {
+    x.iter().zip(y.iter()).map(|(&a, &b)| a * b).sum()
+}
+
+/// Orthonormalize `vectors` against `against` and each other
+///
+/// Uses two passes of modified Gram-Schmidt and drops vectors that lose
+/// almost all of their norm, so the result may be shorter than the input.
+fn orthonormalize<F>(vectors: Vec<Array1<F>>, against: &[&Array1<F>]) -> Vec<Array1<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand,
+{
+    let drop_tolerance = F::epsilon().sqrt();
+    let mut result: Vec<Array1<F>> = Vec::with_capacity(vectors.len());
+    for mut v in vectors {
+        let original = dot(&v, &v).sqrt();
+        if original == F::zero() {
+            continue;
+        }
+        for _ in 0..2 {
+            for q in against.iter().copied().chain(result.iter()) {
+                let c = dot(q, &v);
+                v.scaled_add(-c, q);
+            }
+        }
+        let norm = dot(&v, &v).sqrt();
+        if norm > drop_tolerance * original {
+            result.push(v / norm);
+        }
+    }
+    result
+}
+
+/// Eigendecomposition of a small symmetric matrix by cyclic Jacobi rotations
+///
+/// Returns eigenvalues in ascending order with matching eigenvector columns.
+fn small_symmetric_eigen<F>(h: &Array2<F>) -> (Array1<F>, Array2<F>)
+where
+    F: Float + NumAssign + Sum,
+{
+    let n = h.nrows();
+    let mut a = h.clone();
+    let mut v = Array2::<F>::eye(n);
+    let frobenius = a.iter().map(|&x| x * x).sum::<F>().sqrt();
+    let two = F::from(2.0).unwrap();
+
+    for _ in 0..100 {
+        let off: F = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[[i, j]] * a[[i, j]])
+            .sum::<F>()
+            .sqrt();
+        if off <= F::epsilon() * frobenius {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let apq = a[[p, q]];
+                if apq == F::zero() {
+                    continue;
+                }
+                let theta = (a[[q, q]] - a[[p, p]]) / (two * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + F::one()).sqrt());
+                let c = F::one() / (t * t + F::one()).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[[k, p]], a[[k, q]]);
+                    a[[k, p]] = c * akp - s * akq;
+                    a[[k, q]] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[[p, k]], a[[q, k]]);
+                    a[[p, k]] = c * apk - s * aqk;
+                    a[[q, k]] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[[k, p]], v[[k, q]]);
+                    v[[k, p]] = c * vkp - s * vkq;
+                    v[[k, q]] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[[i, i]].partial_cmp(&a[[j, j]]).unwrap());
+    let values = Array1::from_iter(order.iter().map(|&i| a[[i, i]]));
+    let vectors = Array2::from_shape_fn((n, n), |(r, c)| v[[r, order[c]]]);
+    (values, vectors)
+}
+
+/// Validate the operator and orthonormalize the deflation block
+fn prepare<F, A>(
+    a: &A,
+    k: usize,
+    deflation: Option<&ArrayView2<F>>,
+) -> LinalgResult<(usize, Vec<Array1<F>>)>
+where
+    F: Float + NumAssign + Sum + ScalarOperand,
+    A: LinearOperator<F> + ?Sized,
+{
+    let n = a.nrows();
+    if a.ncols() != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Iterative eigensolvers require a square operator, got {}x{}",
+            n,
+            a.ncols()
+        )));
+    }
+    let locked = match deflation {
+        Some(y) => {
+            if y.nrows() != n {
+                return Err(LinalgError::ShapeError(format!(
+                    "Deflation vectors of length {} do not match operator of size {}",
+                    y.nrows(),
+                    n
+                )));
+            }
+            orthonormalize(y.columns().into_iter().map(|c| c.to_owned()).collect(), &[])
+        }
+        None => Vec::new(),
+    };
+    if k == 0 || k > n - locked.len() {
+        return Err(LinalgError::InvalidInputError(format!(
+            "Cannot compute {} eigenpairs of an operator of size {} with {} deflated directions",
+            k,
+            n,
+            locked.len()
+        )));
+    }
+    Ok((n, locked))
+}
+
+/// Assemble the result from Ritz pairs, computing true residual norms
+fn finish<F, A>(
+    a: &A,
+    mut pairs: Vec<(F, Array1<F>)>,
+    iterations: usize,
+    converged: bool,
+) -> LinalgResult<IterativeEigenResult<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand,
+    A: LinearOperator<F> + ?Sized,
+{
+    pairs.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+    let n = a.nrows();
+    let mut eigenvalues = Array1::zeros(pairs.len());
+    let mut eigenvectors = Array2::zeros((n, pairs.len()));
+    let mut residual_norms = Array1::zeros(pairs.len());
+    for (i, (lambda, x)) in pairs.into_iter().enumerate() {
+        let mut r = a.apply(&x.view())?;
+        r.scaled_add(-lambda, &x);
+        residual_norms[i] = dot(&r, &r).sqrt();
+        eigenvalues[i] = lambda;
+        eigenvectors.column_mut(i).assign(&x);
+    }
+    Ok(IterativeEigenResult {
+        eigenvalues,
+        eigenvectors,
+        residual_norms,
+        iterations,
+        converged,
+    })
+}
+
+/// Linear combination `sum_j basis[j] * coefficients[j]`
+fn combine<F>(basis: &[Array1<F>], coefficients: impl Iterator<Item = F>) -> Array1<F>
+where
+    F: Float + ScalarOperand,
+{
+    let mut result = Array1::zeros(basis[0].len());
+    for (b, c) in basis.iter().zip(coefficients) {
+        result.scaled_add(c, b);
+    }
+    result
+}
+
+/// Compute `k` extreme eigenpairs of a symmetric operator with the
+/// thick-restart Lanczos method
+///
+/// The Lanczos basis is fully reorthogonalized, which keeps the Ritz vectors
+/// orthogonal and avoids spurious copies of converged eigenvalues. When the
+/// basis is full, the wanted half of the Ritz vectors is kept and the process
+/// continues from the last residual (Wu and Simon's thick restart).
+///
+/// # Arguments
+///
+/// * `a` - Symmetric operator
+/// * `k` - Number of eigenpairs to compute
+/// * `which` - End of the spectrum to target
+/// * `deflation` - Optional block whose columns span directions to exclude
+/// * `options` - Iteration limit, tolerance and basis size
+///
+/// # Returns
+///
+/// * Eigenpairs in ascending order; `iterations` counts restart cycles
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array2;
+/// use scirs2_linalg::eigen::iterative::{eigsh, EigenvalueTarget, IterativeEigenOptions};
+///
+/// let a = Array2::from_diag(&ndarray::Array1::from_iter((1..=100).map(|i| i as f64)));
+/// let result = eigsh(&a, 3, EigenvalueTarget::Largest, None, &IterativeEigenOptions::new()).unwrap();
+/// assert!(result.converged);
+/// assert!((result.eigenvalues[2] - 100.0_f64).abs() < 1e-6);
+/// assert!((result.eigenvalues[0] - 98.0_f64).abs() < 1e-6);
+/// ```
+pub fn eigsh<F, A>(
+    a: &A,
+    k: usize,
+    which: EigenvalueTarget,
+    deflation: Option<&ArrayView2<F>>,
+    options: &IterativeEigenOptions<F>,
+) -> LinalgResult<IterativeEigenResult<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+    A: LinearOperator<F> + ?Sized,
+{
+    let (n, locked) = prepare(a, k, deflation)?;
+    let available = n - locked.len();
+    let m = options
+        .basis_size
+        .unwrap_or((2 * k + 1).max(20))
+        .max(k + 1)
+        .min(available);
+
+    let locked_refs: Vec<&Array1<F>> = locked.iter().collect();
+    let mut seed = options.seed;
+    let mut fresh_vector = |basis: &[Array1<F>]| -> Option<Array1<F>> {
+        let against: Vec<&Array1<F>> = locked.iter().chain(basis.iter()).collect();
+        for _ in 0..4 {
+            seed = seed.wrapping_add(1);
+            if let Some(v) = orthonormalize(vec![random_vector(n, seed)], &against).pop() {
+                return Some(v);
+            }
+        }
+        None
+    };
+
+    let mut basis = vec![fresh_vector(&[]).ok_or_else(|| {
+        LinalgError::ComputationError("Could not construct a Lanczos start vector".to_string())
+    })?];
+    let mut h = Array2::<F>::zeros((m, m));
+    let mut iterations = 0;
+
+    loop {
+        iterations += 1;
+
+        // Extend the basis to m vectors; `f` is the residual of the last step
+        let mut f = Array1::zeros(n);
+        let mut j = basis.len() - 1;
+        while j < m {
+            let mut w = a.apply(&basis[j].view())?;
+            for i in 0..=j {
+                h[[i, j]] = F::zero();
+            }
+            for _ in 0..2 {
+                for (i, vi) in basis.iter().enumerate() {
+                    let c = dot(vi, &w);
+                    h[[i, j]] += c;
+                    w.scaled_add(-c, vi);
+                }
+                for y in &locked_refs {
+                    let c = dot(y, &w);
+                    w.scaled_add(-c, y);
+                }
+            }
+            for i in 0..j {
+                h[[j, i]] = h[[i, j]];
+            }
+
+            let beta = dot(&w, &w).sqrt();
+            if j + 1 == m {
+                f = w;
+                break;
+            }
+            let scale = h[[j, j]].abs().max(F::one());
+            if beta > F::epsilon() * scale {
+                basis.push(w / beta);
+            } else {
+                // Invariant subspace found; continue with a new direction
+                match fresh_vector(&basis) {
+                    Some(v) => basis.push(v),
+                    None => break,
+                }
+            }
+            j += 1;
+        }
+
+        // Rayleigh-Ritz on the current basis
+        let mm = basis.len();
+        let (theta, s) = small_symmetric_eigen(&h.slice(ndarray::s![..mm, ..mm]).to_owned());
+        let beta = if mm == m {
+            dot(&f, &f).sqrt()
+        } else {
+            F::zero()
+        };
+        let wanted: Vec<usize> = match which {
+            EigenvalueTarget::Smallest => (0..k).collect(),
+            EigenvalueTarget::Largest => (mm - k..mm).collect(),
+        };
+        let scale = theta
+            .iter()
+            .fold(F::zero(), |acc, &t| acc.max(t.abs()))
+            .max(F::min_positive_value());
+        let converged = wanted
+            .iter()
+            .all(|&i| (beta * s[[mm - 1, i]]).abs() <= options.tolerance * scale);
+
+        if converged || iterations >= options.max_iterations || mm < m {
+            let pairs = wanted
+                .iter()
+                .map(|&i| (theta[i], combine(&basis, s.column(i).iter().copied())))
+                .collect();
+            return finish(a, pairs, iterations, converged || mm < m);
+        }
+
+        // Thick restart: keep the wanted half of the Ritz vectors
+        let keep = (k + (mm - k) / 2).min(mm - 1);
+        let kept: Vec<usize> = match which {
+            EigenvalueTarget::Smallest => (0..keep).collect(),
+            EigenvalueTarget::Largest => (mm - keep..mm).collect(),
+        };
+        let mut new_basis: Vec<Array1<F>> = kept
+            .iter()
+            .map(|&i| combine(&basis, s.column(i).iter().copied()))
+            .collect();
+        h.fill(F::zero());
+        for (t, &i) in kept.iter().enumerate() {
+            h[[t, t]] = theta[i];
+        }
+        if beta > F::epsilon() * scale {
+            new_basis.push(f / beta);
+        } else {
+            match fresh_vector(&new_basis) {
+                Some(v) => new_basis.push(v),
+                None => {
+                    return Err(LinalgError::ComputationError(
+                        "Lanczos restart could not extend the basis".to_string(),
+                    ))
+                }
+            }
+        }
+        basis = new_basis;
+    }
+}
+
+/// Compute `k` extreme eigenpairs of a symmetric operator with LOBPCG
+///
+/// Each iteration performs a Rayleigh-Ritz projection onto the span of the
+/// current approximations `X`, the preconditioned residuals `W` and the
+/// previous search directions `P`. Converged columns are soft-locked: they
+/// stay in the projection but no longer contribute residual directions.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric operator
+/// * `k` - Block size, i.e. number of eigenpairs to compute
+/// * `which` - End of the spectrum to target
+/// * `preconditioner` - Optional symmetric positive definite preconditioner.
+///   For [`EigenvalueTarget::Smallest`] it should approximate `A⁻¹` (or
+///   `(A - σI)⁻¹` for a shift `σ` below the wanted eigenvalues).
+/// * `deflation` - Optional block whose columns span directions to exclude
+/// * `options` - Iteration limit and tolerance
+///
+/// # Returns
+///
+/// * Eigenpairs in ascending order
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::Array2;
+/// use scirs2_linalg::eigen::iterative::{lobpcg, EigenvalueTarget, IterativeEigenOptions};
+///
+/// // 1D Poisson matrix, smallest eigenvalue 2 - 2 cos(pi / 101)
+/// let n = 100;
+/// let a = Array2::from_shape_fn((n, n), |(i, j)| {
+///     if i == j { 2.0_f64 } else if i.abs_diff(j) == 1 { -1.0 } else { 0.0 }
+/// });
+/// let options = IterativeEigenOptions::new().with_tolerance(1e-9);
+/// let result = lobpcg(&a, 2, EigenvalueTarget::Smallest, None, None, &options).unwrap();
+/// let exact = 2.0 - 2.0 * (std::f64::consts::PI / 101.0).cos();
+/// assert!((result.eigenvalues[0] - exact).abs() < 1e-10);
+/// ```
+pub fn lobpcg<F, A>(
+    a: &A,
+    k: usize,
+    which: EigenvalueTarget,
+    preconditioner: Option<&dyn PreconditionerOp<F>>,
+    deflation: Option<&ArrayView2<F>>,
+    options: &IterativeEigenOptions<F>,
+) -> LinalgResult<IterativeEigenResult<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+    A: LinearOperator<F> + ?Sized,
+{
+    let (n, locked) = prepare(a, k, deflation)?;
+    if let Some(t) = preconditioner {
+        if t.size() != n {
+            return Err(LinalgError::ShapeError(format!(
+                "Preconditioner of size {} does not match operator of size {}",
+                t.size(),
+                n
+            )));
+        }
+    }
+
+    // The largest eigenvalues of A are the smallest of -A
+    let sign = match which {
+        EigenvalueTarget::Smallest => F::one(),
+        EigenvalueTarget::Largest => -F::one(),
+    };
+    let apply = |x: &Array1<F>| -> LinalgResult<Array1<F>> { Ok(a.apply(&x.view())? * sign) };
+    let locked_refs: Vec<&Array1<F>> = locked.iter().collect();
+
+    let start: Vec<Array1<F>> = (0..k as u64)
+        .map(|i| random_vector(n, options.seed.wrapping_add(i)))
+        .collect();
+    let mut x = orthonormalize(start, &locked_refs);
+    if x.len() < k {
+        return Err(LinalgError::ComputationError(
+            "Could not construct an orthonormal LOBPCG start block".to_string(),
+        ));
+    }
+    let mut ax = x.iter().map(&apply).collect::<LinalgResult<Vec<_>>>()?;
+    let mut lambda = vec![F::zero(); k];
+    let mut p: Vec<Array1<F>> = Vec::new();
+    let mut directions: Vec<Array1<F>> = Vec::new();
+    let half = F::from(0.5).unwrap();
+    let mut iterations = 0;
+    let mut converged = false;
+
+    loop {
+        // Rayleigh-Ritz on [X, W, P]
+        let basis: Vec<Array1<F>> = x.iter().chain(directions.iter()).cloned().collect();
+        let mut a_basis = ax.clone();
+        for d in &directions {
+            a_basis.push(apply(d)?);
+        }
+        let dim = basis.len();
+        let mut g = Array2::<F>::zeros((dim, dim));
+        for i in 0..dim {
+            for j in 0..=i {
+                let value = half * (dot(&basis[i], &a_basis[j]) + dot(&basis[j], &a_basis[i]));
+                g[[i, j]] = value;
+                g[[j, i]] = value;
+            }
+        }
+        let (theta, c) = small_symmetric_eigen(&g);
+        for (i, l) in lambda.iter_mut().enumerate() {
+            *l = theta[i];
+        }
+        let new_x: Vec<Array1<F>> = (0..k)
+            .map(|i| combine(&basis, c.column(i).iter().copied()))
+            .collect();
+        ax = (0..k)
+            .map(|i| combine(&a_basis, c.column(i).iter().copied()))
+            .collect();
+        if dim > k {
+            // Search directions: the W and P components of the new X
+            p = (0..k)
+                .map(|i| combine(&basis[k..], c.column(i).iter().skip(k).copied()))
+                .collect();
+        }
+        x = new_x;
+
+        // Residuals and soft locking
+        let scale = theta
+            .iter()
+            .fold(F::zero(), |acc, &t| acc.max(t.abs()))
+            .max(F::min_positive_value());
+        let residuals: Vec<Array1<F>> = (0..k).map(|i| &ax[i] - &(&x[i] * lambda[i])).collect();
+        let active: Vec<usize> = (0..k)
+            .filter(|&i| dot(&residuals[i], &residuals[i]).sqrt() > options.tolerance * scale)
+            .collect();
+        if active.is_empty() {
+            converged = true;
+            break;
+        }
+        if iterations >= options.max_iterations {
+            break;
+        }
+        iterations += 1;
+
+        let mut w = Vec::with_capacity(active.len());
+        for &i in &active {
+            w.push(match preconditioner {
+                Some(t) => t.apply(&residuals[i].view())?,
+                None => residuals[i].clone(),
+            });
+        }
+        let against: Vec<&Array1<F>> = locked_refs.iter().copied().chain(x.iter()).collect();
+        let w = orthonormalize(w, &against);
+        let active_p: Vec<Array1<F>> = active.iter().filter_map(|&i| p.get(i).cloned()).collect();
+        let against: Vec<&Array1<F>> = against.into_iter().chain(w.iter()).collect();
+        let active_p = orthonormalize(active_p, &against);
+        if w.is_empty() && active_p.is_empty() {
+            // No new directions: the subspace is exhausted
+            break;
+        }
+        directions = w.into_iter().chain(active_p).collect();
+    }
+
+    let pairs = lambda
+        .into_iter()
+        .zip(x)
+        .map(|(l, v)| (l * sign, v))
+        .collect();
+    finish(a, pairs, iterations, converged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterative::{DiagonalPreconditioner, SsorPreconditioner};
+    use crate::matrixfree::LinearOperator as ClosureOperator;
+    use approx::assert_relative_eq;
+    use ndarray::{ArrayView1, Axis};
+
+    /// 1D Poisson matrix, eigenvalues `2 - 2 cos(jπ / (n + 1))`
+    fn poisson(n: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                2.0
+            } else if i.abs_diff(j) == 1 {
+                -1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    fn poisson_eigenvalue(n: usize, j: usize) -> f64 {
+        2.0 - 2.0 * (j as f64 * std::f64::consts::PI / (n + 1) as f64).cos()
+    }
+
+    fn check_pairs(a: &Array2<f64>, result: &IterativeEigenResult<f64>, tol: f64) {
+        assert!(result.converged);
+        for (i, x) in result.eigenvectors.axis_iter(Axis(1)).enumerate() {
+            let r = a.dot(&x) - &x * result.eigenvalues[i];
+            assert!(r.dot(&r).sqrt() < tol);
+            assert_relative_eq!(result.residual_norms[i], r.dot(&r).sqrt(), epsilon = 1e-12);
+        }
+        let gram = result.eigenvectors.t().dot(&result.eigenvectors);
+        for ((i, j), &v) in gram.indexed_iter() {
+            assert_relative_eq!(v, if i == j { 1.0 } else { 0.0 }, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_small_symmetric_eigen() {
+        let h = ndarray::array![[4.0, 1.0, -2.0], [1.0, 2.0, 0.5], [-2.0, 0.5, 3.0]];
+        let (w, v) = small_symmetric_eigen(&h);
+        assert!(w[0] <= w[1] && w[1] <= w[2]);
+        let rebuilt = v.dot(&Array2::from_diag(&w)).dot(&v.t());
+        for (p, q) in rebuilt.iter().zip(h.iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-13);
+        }
+    }
+
+    #[test]
+    fn test_eigsh_poisson() {
+        let n = 200;
+        let a = poisson(n);
+        let options = IterativeEigenOptions::new().with_tolerance(1e-10);
+
+        let smallest = eigsh(&a, 4, EigenvalueTarget::Smallest, None, &options).unwrap();
+        for j in 0..4 {
+            assert_relative_eq!(
+                smallest.eigenvalues[j],
+                poisson_eigenvalue(n, j + 1),
+                epsilon = 1e-9
+            );
+        }
+        assert!(smallest.iterations > 1);
+        check_pairs(&a, &smallest, 1e-6);
+
+        let largest = eigsh(&a, 3, EigenvalueTarget::Largest, None, &options).unwrap();
+        for j in 0..3 {
+            assert_relative_eq!(
+                largest.eigenvalues[j],
+                poisson_eigenvalue(n, n - 2 + j),
+                epsilon = 1e-9
+            );
+        }
+        check_pairs(&a, &largest, 1e-6);
+    }
+
+    #[test]
+    fn test_eigsh_full_basis() {
+        // The basis spans the whole space, so one cycle is exact
+        let a = poisson(8);
+        let options = IterativeEigenOptions::new().with_basis_size(8);
+        let result = eigsh(&a, 8, EigenvalueTarget::Smallest, None, &options).unwrap();
+        assert_eq!(result.iterations, 1);
+        for j in 0..8 {
+            assert_relative_eq!(
+                result.eigenvalues[j],
+                poisson_eigenvalue(8, j + 1),
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_lobpcg_with_preconditioner() {
+        let n = 150;
+        // Diagonally varying operator where Jacobi preconditioning helps
+        let a = Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                2.0 + i as f64
+            } else if i.abs_diff(j) == 1 {
+                -1.0
+            } else {
+                0.0
+            }
+        });
+        let options = IterativeEigenOptions::new().with_tolerance(1e-9);
+        let reference = eigsh(&a, 3, EigenvalueTarget::Smallest, None, &options).unwrap();
+
+        let plain = lobpcg(&a, 3, EigenvalueTarget::Smallest, None, None, &options).unwrap();
+        let jacobi = DiagonalPreconditioner::new(&a.view()).unwrap();
+        let ssor = SsorPreconditioner::new(&a.view(), 1.0).unwrap();
+        for t in [&jacobi as &dyn PreconditionerOp<f64>, &ssor] {
+            let result =
+                lobpcg(&a, 3, EigenvalueTarget::Smallest, Some(t), None, &options).unwrap();
+            check_pairs(&a, &result, 1e-6);
+            for j in 0..3 {
+                assert_relative_eq!(
+                    result.eigenvalues[j],
+                    reference.eigenvalues[j],
+                    epsilon = 1e-9
+                );
+            }
+            assert!(result.iterations < plain.iterations);
+        }
+
+        let largest = lobpcg(&a, 2, EigenvalueTarget::Largest, None, None, &options).unwrap();
+        check_pairs(&a, &largest, 1e-6);
+        assert!(largest.eigenvalues[1] > (n as f64));
+    }
+
+    #[test]
+    fn test_deflation_and_matrix_free() {
+        // Graph Laplacian of a path: the constant vector spans the null space
+        let n = 120;
+        let laplacian = ClosureOperator::new(n, move |x: &ArrayView1<f64>| {
+            Array1::from_shape_fn(n, |i| {
+                let mut y = 0.0;
+                if i > 0 {
+                    y += x[i] - x[i - 1];
+                }
+                if i + 1 < n {
+                    y += x[i] - x[i + 1];
+                }
+                y
+            })
+        })
+        .symmetric();
+        let constant = Array2::from_elem((n, 1), 1.0);
+        let options = IterativeEigenOptions::new().with_tolerance(1e-10);
+
+        // Fiedler value of the path graph: 2 - 2 cos(π / n)
+        let fiedler = 2.0 - 2.0 * (std::f64::consts::PI / n as f64).cos();
+        let lanczos = eigsh(
+            &laplacian,
+            1,
+            EigenvalueTarget::Smallest,
+            Some(&constant.view()),
+            &options,
+        )
+        .unwrap();
+        assert_relative_eq!(lanczos.eigenvalues[0], fiedler, epsilon = 1e-9);
+        let block = lobpcg(
+            &laplacian,
+            1,
+            EigenvalueTarget::Smallest,
+            None,
+            Some(&constant.view()),
+            &options.clone().with_max_iterations(2000),
+        )
+        .unwrap();
+        assert!(block.converged);
+        assert_relative_eq!(block.eigenvalues[0], fiedler, epsilon = 1e-9);
+        // The Fiedler vector is orthogonal to the deflated constant vector
+        assert!(block.eigenvectors.column(0).sum().abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let a = poisson(5);
+        let options = IterativeEigenOptions::new();
+        assert!(eigsh(&a, 0, EigenvalueTarget::Smallest, None, &options).is_err());
+        assert!(lobpcg(&a, 6, EigenvalueTarget::Smallest, None, None, &options).is_err());
+        let y = Array2::<f64>::eye(5);
+        assert!(eigsh(&a, 1, EigenvalueTarget::Largest, Some(&y.view()), &options).is_err());
+        let rect = Array2::<f64>::zeros((4, 5));
+        assert!(lobpcg(&rect, 1, EigenvalueTarget::Largest, None, None, &options).is_err());
+    }
+}
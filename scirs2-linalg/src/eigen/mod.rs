@@ -8,6 +8,7 @@
 //! - [`standard`] - Standard eigenvalue decomposition for dense matrices
 //! - [`generalized`] - Generalized eigenvalue problems (Ax = λBx)
 //! - [`sparse`] - Sparse matrix eigenvalue algorithms (future implementation)
+//! - [`iterative`] - Lanczos and LOBPCG for a few eigenpairs of large symmetric operators
 //!
 //! ## Quick Start
 //!
@@ -46,6 +47,7 @@
 
 // Re-export submodules
 pub mod generalized;
+pub mod iterative;
 pub mod sparse;
 pub mod standard;
 
@@ -60,6 +62,7 @@ use std::iter::Sum;
 
 // Re-export main functions for backward compatibility
 pub use generalized::{eig_gen, eigh_gen, eigvals_gen, eigvalsh_gen};
+pub use iterative::{eigsh, lobpcg, EigenvalueTarget, IterativeEigenOptions, IterativeEigenResult};
 pub use standard::{eig, eigh, eigvals, power_iteration};
 
 // Re-export sparse functions (when implemented)
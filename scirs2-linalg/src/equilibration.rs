@@ -0,0 +1,285 @@
+//! Row and column equilibration of badly scaled matrices
+//!
+//! [`equilibrate`] computes diagonal scalings `R` and `C` such that the largest
+//! entry of every row and column of `R A C` has magnitude close to one, in the
+//! manner of LAPACK's `?geequb`. The factors are powers of two, so applying
+//! them is exact and introduces no rounding error of its own.
+//!
+//! For a linear system `A x = b` the equilibrated system is
+//! `(R A C) y = R b` with `x = C y`.
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use num_traits::Float;
+
+use crate::error::{LinalgError, LinalgResult};
+
+/// Scaling is applied in [`EquilibrationMode::Auto`] when the ratio of the
+/// smallest to the largest row (or column) maximum falls below this value
+const EQUILIBRATION_THRESHOLD: f64 = 0.1;
+
+/// When to equilibrate a system before solving it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EquilibrationMode {
+    /// Solve the system as given
+    #[default]
+    Never,
+    /// Scale rows and/or columns only when they are badly scaled, using the
+    /// same ratio test (0.1) as LAPACK's `?laqge`
+    Auto,
+    /// Always apply both row and column scaling
+    Always,
+}
+
+/// Row and column scale factors of a matrix
+///
+/// The scaled matrix is `diag(row_scale) * A * diag(col_scale)`. Scale factors
+/// that were not applied are ones.
+#[derive(Debug, Clone)]
+pub struct Equilibration<F> {
+    /// Row scale factors `R` (powers of two)
+    pub row_scale: Array1<F>,
+    /// Column scale factors `C` (powers of two)
+    pub col_scale: Array1<F>,
+    /// Ratio of the smallest to the largest row maximum of `A`
+    pub row_condition: F,
+    /// Ratio of the smallest to the largest column maximum of `R A`
+    pub col_condition: F,
+    /// Largest absolute entry of `A`
+    pub amax: F,
+}
+
+impl<F: Float> Equilibration<F> {
+    /// Whether row scaling is worthwhile (`row_condition < 0.1` or the
+    /// entries are close to underflow or overflow)
+    pub fn needs_row_scaling(&self) -> bool {
+        let small = F::min_positive_value() / F::epsilon();
+        let large = F::one() / small;
+        self.row_condition < F::from(EQUILIBRATION_THRESHOLD).unwrap()
+            || self.amax < small
+            || self.amax > large
+    }
+
+    /// Whether column scaling is worthwhile (`col_condition < 0.1`)
+    pub fn needs_col_scaling(&self) -> bool {
+        self.col_condition < F::from(EQUILIBRATION_THRESHOLD).unwrap()
+    }
+
+    /// Drop the scalings that `mode` does not call for
+    ///
+    /// Returns `None` if no scaling remains.
+    pub(crate) fn restrict(mut self, mode: EquilibrationMode, allow_rows: bool) -> Option<Self> {
+        let (rows, cols) = match mode {
+            EquilibrationMode::Never => (false, false),
+            EquilibrationMode::Auto => (self.needs_row_scaling(), self.needs_col_scaling()),
+            EquilibrationMode::Always => (true, true),
+        };
+        let rows = rows && allow_rows;
+        if !rows {
+            self.row_scale.fill(F::one());
+        }
+        if !cols {
+            self.col_scale.fill(F::one());
+        }
+        (rows || cols).then_some(self)
+    }
+
+    /// Form the scaled matrix `R A C`
+    pub fn scale_matrix(&self, a: &ArrayView2<F>) -> Array2<F> {
+        let mut scaled = a.to_owned();
+        for ((i, j), v) in scaled.indexed_iter_mut() {
+            *v = *v * self.row_scale[i] * self.col_scale[j];
+        }
+        scaled
+    }
+
+    /// Scale a right-hand side: `R b`
+    pub fn scale_rhs(&self, b: &ArrayView1<F>) -> Array1<F> {
+        b.iter()
+            .zip(self.row_scale.iter())
+            .map(|(&v, &r)| v * r)
+            .collect()
+    }
+
+    /// Back-transform a solution of the scaled system: `x = C y`
+    pub fn unscale_solution(&self, y: &ArrayView1<F>) -> Array1<F> {
+        y.iter()
+            .zip(self.col_scale.iter())
+            .map(|(&v, &c)| v * c)
+            .collect()
+    }
+}
+
+/// Nearest power of two to `1 / x` for `x > 0`
+fn reciprocal_power_of_two<F: Float>(x: F) -> F {
+    let two = F::one() + F::one();
+    let exponent = x.log2().round().to_i32().unwrap_or(0);
+    two.powi(-exponent)
+}
+
+/// Compute row and column scalings that equilibrate a matrix
+///
+/// Each row is scaled by the power of two nearest to the reciprocal of its
+/// largest entry, then each column of the row-scaled matrix likewise. Rows
+/// or columns that are entirely zero keep a scale of one.
+///
+/// # Arguments
+///
+/// * `a` - Input matrix (any shape)
+///
+/// # Returns
+///
+/// * Scale factors and the row/column condition ratios
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::equilibrate;
+///
+/// let a = array![[1e-8_f64, 2e-8], [3.0, 1.0]];
+/// let eq = equilibrate(&a.view()).unwrap();
+/// assert!(eq.needs_row_scaling());
+/// let scaled = eq.scale_matrix(&a.view());
+/// for row in scaled.rows() {
+///     let max = row.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+///     assert!(max > 0.5 && max <= 2.0);
+/// }
+/// ```
+pub fn equilibrate<F: Float>(a: &ArrayView2<F>) -> LinalgResult<Equilibration<F>> {
+    compute_scaling(a, true)
+}
+
+/// Column scalings of `A` itself, with unit row scale factors
+///
+/// Used where row scaling would change the problem (least squares), so the
+/// column maxima must be measured without it.
+pub(crate) fn equilibrate_columns<F: Float>(a: &ArrayView2<F>) -> LinalgResult<Equilibration<F>> {
+    compute_scaling(a, false)
+}
+
+fn compute_scaling<F: Float>(
+    a: &ArrayView2<F>,
+    scale_rows: bool,
+) -> LinalgResult<Equilibration<F>> {
+    let (m, n) = a.dim();
+    if m == 0 || n == 0 {
+        return Err(LinalgError::ShapeError(
+            "Equilibration failed: Matrix cannot be empty".to_string(),
+        ));
+    }
+    if a.iter().any(|v| !v.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Equilibration failed: Matrix contains non-finite values".to_string(),
+        ));
+    }
+
+    let row_max: Vec<F> = a
+        .rows()
+        .into_iter()
+        .map(|r| r.iter().fold(F::zero(), |acc, v| acc.max(v.abs())))
+        .collect();
+    let amax = row_max.iter().fold(F::zero(), |acc, &v| acc.max(v));
+    let row_scale: Array1<F> = row_max
+        .iter()
+        .map(|&v| {
+            if scale_rows && v > F::zero() {
+                reciprocal_power_of_two(v)
+            } else {
+                F::one()
+            }
+        })
+        .collect();
+
+    let col_max: Vec<F> = a
+        .columns()
+        .into_iter()
+        .map(|c| {
+            c.iter()
+                .zip(row_scale.iter())
+                .fold(F::zero(), |acc, (v, &r)| acc.max(v.abs() * r))
+        })
+        .collect();
+    let col_scale: Array1<F> = col_max
+        .iter()
+        .map(|&v| {
+            if v > F::zero() {
+                reciprocal_power_of_two(v)
+            } else {
+                F::one()
+            }
+        })
+        .collect();
+
+    let ratio = |values: &[F]| {
+        let (lo, hi) = values
+            .iter()
+            .fold((F::infinity(), F::zero()), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+        if hi > F::zero() {
+            lo / hi
+        } else {
+            F::one()
+        }
+    };
+
+    Ok(Equilibration {
+        row_condition: ratio(&row_max),
+        col_condition: ratio(&col_max),
+        row_scale,
+        col_scale,
+        amax,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_equilibrate_scales_rows_and_columns() {
+        let a = array![[1e6, 3e6, 0.0], [2e-3, 0.0, 5e-4], [7.0, 1e3, 0.25]];
+        let eq = equilibrate(&a.view()).unwrap();
+        for &s in eq.row_scale.iter().chain(eq.col_scale.iter()) {
+            // Exact powers of two
+            assert_eq!(s.log2().fract(), 0.0);
+        }
+        let scaled = eq.scale_matrix(&a.view());
+        for line in scaled.rows().into_iter().chain(scaled.columns()) {
+            let max = line.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+            assert!(max > 0.25 && max < 4.0, "max = {}", max);
+        }
+        assert!(eq.row_condition < 1e-8);
+        assert_eq!(eq.amax, 3e6);
+
+        // Restricting to the requested scalings
+        let cols_only = eq
+            .clone()
+            .restrict(EquilibrationMode::Always, false)
+            .unwrap();
+        assert!(cols_only.row_scale.iter().all(|&r| r == 1.0));
+        assert!(eq
+            .clone()
+            .restrict(EquilibrationMode::Never, true)
+            .is_none());
+
+        let well_scaled = array![[1.0, 0.5], [0.5, 1.0]];
+        let eq = equilibrate(&well_scaled.view()).unwrap();
+        assert!(!eq.needs_row_scaling() && !eq.needs_col_scaling());
+        assert!(eq.restrict(EquilibrationMode::Auto, true).is_none());
+    }
+
+    #[test]
+    fn test_equilibrate_zero_lines_and_errors() {
+        let a = array![[0.0, 0.0], [4.0, 0.0]];
+        let eq = equilibrate(&a.view()).unwrap();
+        assert_eq!(eq.row_scale[0], 1.0);
+        assert_eq!(eq.col_scale[1], 1.0);
+        assert_eq!(eq.row_scale[1], 0.25);
+
+        assert!(equilibrate(&Array2::<f64>::zeros((0, 3)).view()).is_err());
+        let bad = array![[1.0, f64::NAN]];
+        assert!(equilibrate(&bad.view()).is_err());
+    }
+}
@@ -0,0 +1,643 @@
+//! Reusable factorization objects
+//!
+//! [`lu`](crate::lu), [`qr`](crate::qr) and [`cholesky`](crate::cholesky)
+//! return the factors as plain matrices, so every solve has to start from
+//! them again. The types in this module keep a factorization in compact form
+//! and provide `solve`, `det` and `inv` on it, so one factorization serves
+//! any number of right-hand sides. With the `serde` feature enabled they
+//! implement `Serialize` and `Deserialize`, so a factorization can be stored
+//! and reused in another process.
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use num_traits::{Float, NumAssign};
+use std::iter::Sum;
+
+use crate::decomposition::cholesky;
+use crate::error::{LinalgError, LinalgResult};
+use crate::validation::{
+    validate_decomposition, validate_finite_matrix, validate_not_empty_matrix,
+};
+
+/// Check that a right-hand side has `n` rows
+fn check_rhs(rows: usize, n: usize) -> LinalgResult<()> {
+    if rows != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side has {} rows, expected {}",
+            rows, n
+        )));
+    }
+    Ok(())
+}
+
+/// Solve `L X = B` in place for a lower triangular `L` stored in `factor`
+fn forward_substitute<F: Float + NumAssign>(factor: &Array2<F>, x: &mut Array2<F>, unit: bool) {
+    let n = factor.nrows();
+    for mut col in x.axis_iter_mut(Axis(1)) {
+        for i in 0..n {
+            for j in 0..i {
+                let update = factor[[i, j]] * col[j];
+                col[i] -= update;
+            }
+            if !unit {
+                col[i] /= factor[[i, i]];
+            }
+        }
+    }
+}
+
+/// Solve `U X = B` in place for the upper triangle `U` of the leading
+/// `n x n` block of `factor`
+fn back_substitute<F: Float + NumAssign>(factor: &Array2<F>, x: &mut Array2<F>, n: usize) {
+    for mut col in x.axis_iter_mut(Axis(1)) {
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let update = factor[[i, j]] * col[j];
+                col[i] -= update;
+            }
+            col[i] /= factor[[i, i]];
+        }
+    }
+}
+
+/// LU factorization `P A = L U` with partial pivoting
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::LuFactorization;
+///
+/// let a = array![[2.0_f64, 1.0], [4.0, 3.0]];
+/// let lu = LuFactorization::new(&a.view()).unwrap();
+/// for b in [array![3.0_f64, 7.0], array![1.0, 1.0]] {
+///     let x = lu.solve(&b.view()).unwrap();
+///     let r = a.dot(&x) - &b;
+///     assert!(r.iter().all(|v| v.abs() < 1e-12));
+/// }
+/// assert!((lu.det() - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LuFactorization<F> {
+    /// Unit lower factor below the diagonal, `U` on and above it
+    lu: Array2<F>,
+    /// Row `i` of `P A` is row `perm[i]` of `A`
+    perm: Vec<usize>,
+    /// Sign of the permutation (+1 or -1)
+    sign: F,
+}
+
+impl<F> LuFactorization<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    /// Factor a square matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Square, non-singular matrix
+    ///
+    /// # Returns
+    ///
+    /// * The factorization, or `SingularMatrixError` if a pivot is exactly zero
+    pub fn new(a: &ArrayView2<F>) -> LinalgResult<Self> {
+        validate_decomposition(a, "LU factorization", true)?;
+        let n = a.nrows();
+        let mut lu = a.to_owned();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = F::one();
+        for k in 0..n {
+            let mut pivot = k;
+            for i in k + 1..n {
+                if lu[[i, k]].abs() > lu[[pivot, k]].abs() {
+                    pivot = i;
+                }
+            }
+            if lu[[pivot, k]] == F::zero() {
+                return Err(LinalgError::singular_matrix_with_suggestions(
+                    "LU factorization",
+                    (n, n),
+                    None,
+                ));
+            }
+            if pivot != k {
+                for j in 0..n {
+                    lu.swap([k, j], [pivot, j]);
+                }
+                perm.swap(k, pivot);
+                sign = -sign;
+            }
+            for i in k + 1..n {
+                let factor = lu[[i, k]] / lu[[k, k]];
+                lu[[i, k]] = factor;
+                for j in k + 1..n {
+                    let update = factor * lu[[k, j]];
+                    lu[[i, j]] -= update;
+                }
+            }
+        }
+        Ok(Self { lu, perm, sign })
+    }
+
+    /// Order of the factored matrix
+    pub fn dim(&self) -> usize {
+        self.lu.nrows()
+    }
+
+    /// Unit lower triangular factor `L`
+    pub fn l(&self) -> Array2<F> {
+        let n = self.dim();
+        Array2::from_shape_fn((n, n), |(i, j)| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.lu[[i, j]],
+            std::cmp::Ordering::Equal => F::one(),
+            std::cmp::Ordering::Less => F::zero(),
+        })
+    }
+
+    /// Upper triangular factor `U`
+    pub fn u(&self) -> Array2<F> {
+        let n = self.dim();
+        Array2::from_shape_fn(
+            (n, n),
+            |(i, j)| {
+                if i <= j {
+                    self.lu[[i, j]]
+                } else {
+                    F::zero()
+                }
+            },
+        )
+    }
+
+    /// Permutation matrix `P`
+    pub fn p(&self) -> Array2<F> {
+        let n = self.dim();
+        let mut p = Array2::zeros((n, n));
+        for (i, &row) in self.perm.iter().enumerate() {
+            p[[i, row]] = F::one();
+        }
+        p
+    }
+
+    /// Solve `A x = b`
+    pub fn solve(&self, b: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let x = self.solve_multiple(&b.view().insert_axis(Axis(1)))?;
+        Ok(x.column(0).to_owned())
+    }
+
+    /// Solve `A X = B` for several right-hand sides
+    pub fn solve_multiple(&self, b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        check_rhs(b.nrows(), self.dim())?;
+        Ok(self.solve_unchecked(b, false))
+    }
+
+    /// Solve `A^T X = B` for several right-hand sides
+    pub fn solve_transpose(&self, b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        check_rhs(b.nrows(), self.dim())?;
+        Ok(self.solve_unchecked(b, true))
+    }
+
+    /// Solve `A X = B` or, with `transpose`, `A^T X = B` without checking
+    /// the shape of `B`
+    pub(crate) fn solve_unchecked(&self, b: &ArrayView2<F>, transpose: bool) -> Array2<F> {
+        let n = self.dim();
+        let lu = &self.lu;
+        if !transpose {
+            // L U x = P b
+            let mut x = b.select(Axis(0), &self.perm);
+            forward_substitute(lu, &mut x, true);
+            back_substitute(lu, &mut x, n);
+            x
+        } else {
+            // U^T L^T (P x) = b
+            let mut y = b.to_owned();
+            for mut col in y.axis_iter_mut(Axis(1)) {
+                for i in 0..n {
+                    for j in 0..i {
+                        let update = lu[[j, i]] * col[j];
+                        col[i] -= update;
+                    }
+                    col[i] /= lu[[i, i]];
+                }
+                for i in (0..n).rev() {
+                    for j in i + 1..n {
+                        let update = lu[[j, i]] * col[j];
+                        col[i] -= update;
+                    }
+                }
+            }
+            let mut x = Array2::zeros(b.dim());
+            for (i, &p) in self.perm.iter().enumerate() {
+                x.row_mut(p).assign(&y.row(i));
+            }
+            x
+        }
+    }
+
+    /// Determinant of `A`
+    pub fn det(&self) -> F {
+        self.lu.diag().iter().fold(self.sign, |acc, &d| acc * d)
+    }
+
+    /// Inverse of `A`
+    pub fn inv(&self) -> Array2<F> {
+        self.solve_unchecked(&Array2::eye(self.dim()).view(), false)
+    }
+}
+
+/// Householder QR factorization `A = Q R`
+///
+/// `Q` is kept as a product of elementary reflectors, as in LAPACK `geqrf`,
+/// so applying it costs O(mn) per vector and it is formed only on request.
+/// For `m >= n` with full column rank, [`solve`](Self::solve) returns the
+/// least-squares solution.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::QrFactorization;
+///
+/// // Fit a line through three points
+/// let a = array![[1.0_f64, 0.0], [1.0, 1.0], [1.0, 2.0]];
+/// let qr = QrFactorization::new(&a.view()).unwrap();
+/// let x = qr.solve(&array![1.0_f64, 3.0, 5.0].view()).unwrap();
+/// assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QrFactorization<F> {
+    /// `R` on and above the diagonal, reflector vectors (with implicit unit
+    /// leading entry) below it
+    qr: Array2<F>,
+    /// Reflector coefficients: `H_k = I - tau_k v_k v_k^T`
+    tau: Array1<F>,
+}
+
+impl<F> QrFactorization<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    /// Factor a matrix of any shape
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Input matrix (m x n)
+    ///
+    /// # Returns
+    ///
+    /// * The factorization
+    pub fn new(a: &ArrayView2<F>) -> LinalgResult<Self> {
+        validate_not_empty_matrix(a, "QR factorization")?;
+        validate_finite_matrix(a, "QR factorization")?;
+        let (m, n) = a.dim();
+        let mut qr = a.to_owned();
+        let mut tau = Array1::zeros(m.min(n));
+        for k in 0..m.min(n) {
+            let alpha = qr[[k, k]];
+            let tail_sq: F = (k + 1..m).map(|i| qr[[i, k]] * qr[[i, k]]).sum();
+            if tail_sq == F::zero() {
+                continue;
+            }
+            let norm = (alpha * alpha + tail_sq).sqrt();
+            let beta = if alpha >= F::zero() { -norm } else { norm };
+            tau[k] = (beta - alpha) / beta;
+            let scale = F::one() / (alpha - beta);
+            for i in k + 1..m {
+                qr[[i, k]] *= scale;
+            }
+            qr[[k, k]] = beta;
+
+            // Apply H_k to the trailing columns
+            for j in k + 1..n {
+                let dot = (k + 1..m).fold(qr[[k, j]], |acc, i| acc + qr[[i, k]] * qr[[i, j]]);
+                let w = tau[k] * dot;
+                qr[[k, j]] -= w;
+                for i in k + 1..m {
+                    let update = w * qr[[i, k]];
+                    qr[[i, j]] -= update;
+                }
+            }
+        }
+        Ok(Self { qr, tau })
+    }
+
+    /// Shape `(m, n)` of the factored matrix
+    pub fn shape(&self) -> (usize, usize) {
+        self.qr.dim()
+    }
+
+    /// Overwrite `x` with `Q^T x` (`transpose`) or `Q x`
+    fn apply_q(&self, x: &mut Array2<F>, transpose: bool) {
+        let m = self.qr.nrows();
+        let reflectors = self.tau.len();
+        let mut apply = |k: usize| {
+            let tau = self.tau[k];
+            if tau == F::zero() {
+                return;
+            }
+            for mut col in x.axis_iter_mut(Axis(1)) {
+                let dot = (k + 1..m).fold(col[k], |acc, i| acc + self.qr[[i, k]] * col[i]);
+                let w = tau * dot;
+                col[k] -= w;
+                for i in k + 1..m {
+                    col[i] -= w * self.qr[[i, k]];
+                }
+            }
+        };
+        if transpose {
+            (0..reflectors).for_each(&mut apply);
+        } else {
+            (0..reflectors).rev().for_each(&mut apply);
+        }
+    }
+
+    /// Orthogonal factor `Q` (m x m)
+    pub fn q(&self) -> Array2<F> {
+        let mut q = Array2::eye(self.qr.nrows());
+        self.apply_q(&mut q, false);
+        q
+    }
+
+    /// Upper triangular (trapezoidal) factor `R` (m x n)
+    pub fn r(&self) -> Array2<F> {
+        Array2::from_shape_fn(self.qr.dim(), |(i, j)| {
+            if i <= j {
+                self.qr[[i, j]]
+            } else {
+                F::zero()
+            }
+        })
+    }
+
+    /// Compute `Q^T B`
+    pub fn apply_qt(&self, b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        check_rhs(b.nrows(), self.qr.nrows())?;
+        let mut x = b.to_owned();
+        self.apply_q(&mut x, true);
+        Ok(x)
+    }
+
+    /// Solve `A x = b`, in the least-squares sense if `A` is tall
+    pub fn solve(&self, b: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let x = self.solve_multiple(&b.view().insert_axis(Axis(1)))?;
+        Ok(x.column(0).to_owned())
+    }
+
+    /// Solve `A X = B` for several right-hand sides, in the least-squares
+    /// sense if `A` is tall
+    ///
+    /// Requires `m >= n` and a nonzero diagonal in `R` (full column rank).
+    pub fn solve_multiple(&self, b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        let (m, n) = self.shape();
+        if m < n {
+            return Err(LinalgError::ShapeError(format!(
+                "QR solve requires at least as many rows as columns, got {}x{}",
+                m, n
+            )));
+        }
+        if self.qr.diag().iter().any(|&d| d == F::zero()) {
+            return Err(LinalgError::singular_matrix_with_suggestions(
+                "QR solve",
+                (m, n),
+                None,
+            ));
+        }
+        let mut y = self.apply_qt(b)?;
+        back_substitute(&self.qr, &mut y, n);
+        Ok(y.slice_move(ndarray::s![..n, ..]))
+    }
+
+    /// Determinant of a square `A`
+    pub fn det(&self) -> LinalgResult<F> {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinalgError::ShapeError(format!(
+                "Determinant requires a square matrix, got {}x{}",
+                m, n
+            )));
+        }
+        // Each nontrivial reflector has determinant -1
+        let reflections = self.tau.iter().filter(|&&t| t != F::zero()).count();
+        let sign = if reflections % 2 == 0 {
+            F::one()
+        } else {
+            -F::one()
+        };
+        Ok(self.qr.diag().iter().fold(sign, |acc, &d| acc * d))
+    }
+
+    /// Inverse of a square, non-singular `A`
+    pub fn inv(&self) -> LinalgResult<Array2<F>> {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinalgError::ShapeError(format!(
+                "Inverse requires a square matrix, got {}x{}",
+                m, n
+            )));
+        }
+        self.solve_multiple(&Array2::eye(n).view())
+    }
+}
+
+/// Cholesky factorization `A = L L^T` of a symmetric positive definite matrix
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::CholeskyFactorization;
+///
+/// let a = array![[4.0_f64, 2.0], [2.0, 5.0]];
+/// let chol = CholeskyFactorization::new(&a.view()).unwrap();
+/// let x = chol.solve(&array![6.0_f64, 7.0].view()).unwrap();
+/// assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12);
+/// assert!((chol.det() - 16.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CholeskyFactorization<F> {
+    /// Lower triangular factor
+    l: Array2<F>,
+}
+
+impl<F> CholeskyFactorization<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    /// Factor a symmetric positive definite matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Symmetric positive definite matrix
+    ///
+    /// # Returns
+    ///
+    /// * The factorization, or `NonPositiveDefiniteError`
+    pub fn new(a: &ArrayView2<F>) -> LinalgResult<Self> {
+        Ok(Self {
+            l: cholesky(a, None)?,
+        })
+    }
+
+    /// Order of the factored matrix
+    pub fn dim(&self) -> usize {
+        self.l.nrows()
+    }
+
+    /// Lower triangular factor `L`
+    pub fn l(&self) -> &Array2<F> {
+        &self.l
+    }
+
+    /// Solve `A x = b`
+    pub fn solve(&self, b: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let x = self.solve_multiple(&b.view().insert_axis(Axis(1)))?;
+        Ok(x.column(0).to_owned())
+    }
+
+    /// Solve `A X = B` for several right-hand sides
+    pub fn solve_multiple(&self, b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        let n = self.dim();
+        check_rhs(b.nrows(), n)?;
+        let mut x = b.to_owned();
+        forward_substitute(&self.l, &mut x, false);
+        // L^T x = y
+        for mut col in x.axis_iter_mut(Axis(1)) {
+            for i in (0..n).rev() {
+                for j in i + 1..n {
+                    let update = self.l[[j, i]] * col[j];
+                    col[i] -= update;
+                }
+                col[i] /= self.l[[i, i]];
+            }
+        }
+        Ok(x)
+    }
+
+    /// Determinant of `A`
+    pub fn det(&self) -> F {
+        let d = self.l.diag().iter().fold(F::one(), |acc, &v| acc * v);
+        d * d
+    }
+
+    /// Natural logarithm of the determinant, safe from overflow
+    pub fn log_det(&self) -> F {
+        let two = F::one() + F::one();
+        two * self.l.diag().iter().map(|v| v.ln()).sum::<F>()
+    }
+
+    /// Inverse of `A`
+    pub fn inv(&self) -> Array2<F> {
+        self.solve_multiple(&Array2::eye(self.dim()).view())
+            .expect("identity matches the factor order")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    fn test_matrix() -> Array2<f64> {
+        array![
+            [0.0, 2.0, 1.0, -1.0],
+            [3.0, 1.0, 0.5, 2.0],
+            [1.0, -2.0, 4.0, 0.0],
+            [2.0, 0.0, 1.0, 3.0]
+        ]
+    }
+
+    fn assert_matrix_eq(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = tol);
+        }
+    }
+
+    #[test]
+    fn test_lu_factorization() {
+        let a = test_matrix();
+        let lu = LuFactorization::new(&a.view()).unwrap();
+        assert_matrix_eq(&lu.p().dot(&a), &lu.l().dot(&lu.u()), 1e-12);
+
+        let b = array![[1.0, 0.0], [2.0, 1.0], [0.0, -1.0], [3.0, 2.0]];
+        let x = lu.solve_multiple(&b.view()).unwrap();
+        assert_matrix_eq(&a.dot(&x), &b, 1e-12);
+        let xt = lu.solve_transpose(&b.view()).unwrap();
+        assert_matrix_eq(&a.t().dot(&xt), &b, 1e-12);
+        assert_matrix_eq(&a.dot(&lu.inv()), &Array2::eye(4), 1e-12);
+        assert_relative_eq!(lu.det(), -51.0, epsilon = 1e-10);
+
+        assert!(lu.solve(&array![1.0, 2.0].view()).is_err());
+        let singular = array![[1.0, 2.0], [2.0, 4.0]];
+        assert!(matches!(
+            LuFactorization::new(&singular.view()),
+            Err(LinalgError::SingularMatrixError(_))
+        ));
+    }
+
+    #[test]
+    fn test_qr_factorization() {
+        let a = test_matrix();
+        let qr = QrFactorization::new(&a.view()).unwrap();
+        let q = qr.q();
+        assert_matrix_eq(&q.dot(&qr.r()), &a, 1e-12);
+        assert_matrix_eq(&q.t().dot(&q), &Array2::eye(4), 1e-12);
+        assert_matrix_eq(&a.dot(&qr.inv().unwrap()), &Array2::eye(4), 1e-12);
+        assert_relative_eq!(qr.det().unwrap(), -51.0, epsilon = 1e-10);
+
+        // Least squares on a tall matrix: the residual is orthogonal to range(A)
+        let tall = a.slice(ndarray::s![.., ..2]).to_owned();
+        let qr = QrFactorization::new(&tall.view()).unwrap();
+        let b = array![1.0, 2.0, 3.0, 4.0];
+        let x = qr.solve(&b.view()).unwrap();
+        let residual = tall.dot(&x) - &b;
+        for v in tall.t().dot(&residual).iter() {
+            assert!(v.abs() < 1e-12);
+        }
+        assert!(qr.det().is_err());
+        let wide = QrFactorization::new(&tall.t()).unwrap();
+        assert!(wide.solve(&array![1.0, 2.0].view()).is_err());
+    }
+
+    #[test]
+    fn test_cholesky_factorization() {
+        let a = array![[4.0, 2.0, 0.6], [2.0, 5.0, 1.0], [0.6, 1.0, 3.0]];
+        let chol = CholeskyFactorization::new(&a.view()).unwrap();
+        assert_matrix_eq(&chol.l().dot(&chol.l().t()), &a, 1e-12);
+        let b = array![1.0, -1.0, 2.0];
+        let x = chol.solve(&b.view()).unwrap();
+        let back = a.dot(&x);
+        for i in 0..3 {
+            assert_relative_eq!(back[i], b[i], epsilon = 1e-12);
+        }
+        let det = crate::det(&a.view(), None).unwrap();
+        assert_relative_eq!(chol.det(), det, epsilon = 1e-10);
+        assert_relative_eq!(chol.log_det(), det.ln(), epsilon = 1e-12);
+        assert_matrix_eq(&a.dot(&chol.inv()), &Array2::eye(3), 1e-12);
+
+        let indefinite = array![[1.0, 2.0], [2.0, 1.0]];
+        assert!(CholeskyFactorization::new(&indefinite.view()).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_factorization_serde_roundtrip() {
+        let a = test_matrix();
+        let b = array![1.0, 2.0, 3.0, 4.0];
+        let lu = LuFactorization::new(&a.view()).unwrap();
+        let json = serde_json::to_string(&lu).unwrap();
+        let restored: LuFactorization<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            lu.solve(&b.view()).unwrap(),
+            restored.solve(&b.view()).unwrap()
+        );
+
+        let qr = QrFactorization::new(&a.view()).unwrap();
+        let restored: QrFactorization<f64> =
+            serde_json::from_str(&serde_json::to_string(&qr).unwrap()).unwrap();
+        assert_eq!(qr.det().unwrap(), restored.det().unwrap());
+    }
+}
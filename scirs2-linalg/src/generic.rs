@@ -2,9 +2,15 @@
 //!
 //! This module provides unified interfaces for linear algebra operations
 //! that work with different numeric types (f32, f64, Complex<f32>, Complex<f64>).
+//!
+//! The decompositions and matrix functions ([`glu`], [`gqr`], [`gsvd`],
+//! [`geig`], [`gexpm`], [`glogm`], [`gsqrtm`]) accept any
+//! [`DecompositionScalar`], which dispatches real element types to the real
+//! routines and complex element types to their complex counterparts.
 
 use crate::error::{LinalgError, LinalgResult};
-use ndarray::{Array2, ArrayView2};
+use ndarray::{Array1, Array2, ArrayView2};
+use num_complex::Complex;
 use num_traits::{Float, NumAssign};
 use std::fmt::Debug;
 use std::iter::Sum;
@@ -157,6 +163,218 @@ impl LinalgScalar for f64 {
     }
 }
 
+macro_rules! impl_complex_linalg_scalar {
+    ($real:ty) => {
+        impl LinalgScalar for Complex<$real> {
+            type Real = $real;
+
+            fn to_f64(&self) -> Result<f64, LinalgError> {
+                if self.im == 0.0 {
+                    Ok(self.re as f64)
+                } else {
+                    Err(LinalgError::ComputationError(
+                        "Complex value with non-zero imaginary part cannot be converted to f64"
+                            .to_string(),
+                    ))
+                }
+            }
+
+            fn from_f64(v: f64) -> Result<Self, LinalgError> {
+                Ok(Complex::new(<$real as LinalgScalar>::from_f64(v)?, 0.0))
+            }
+
+            fn abs(&self) -> Self::Real {
+                self.norm()
+            }
+
+            fn is_zero(&self) -> bool {
+                self.norm() < <$real>::EPSILON
+            }
+
+            fn zero() -> Self {
+                Complex::new(0.0, 0.0)
+            }
+
+            fn one() -> Self {
+                Complex::new(1.0, 0.0)
+            }
+
+            fn sqrt(&self) -> Self {
+                Complex::<$real>::sqrt(*self)
+            }
+
+            fn conj(&self) -> Self {
+                Complex::<$real>::conj(self)
+            }
+
+            fn real(&self) -> Self::Real {
+                self.re
+            }
+
+            fn epsilon() -> Self::Real {
+                <$real>::EPSILON
+            }
+        }
+    };
+}
+
+impl_complex_linalg_scalar!(f32);
+impl_complex_linalg_scalar!(f64);
+
+/// Element types supported by the type-generic decompositions and matrix functions
+///
+/// Implemented for `f32`, `f64`, `Complex<f32>` and `Complex<f64>`. Real types
+/// use the crate's real routines ([`crate::lu`], [`crate::qr`], [`crate::svd`],
+/// [`crate::eig`] and [`crate::matrix_functions`]); complex types use
+/// [`crate::complex`] and the complex matrix functions such as
+/// [`crate::matrix_functions::expm_complex`].
+pub trait DecompositionScalar: LinalgScalar {
+    /// LU decomposition with partial pivoting, returning `(P, L, U)` with `A = P L U`
+    fn lu(a: &ArrayView2<Self>) -> LinalgResult<(Array2<Self>, Array2<Self>, Array2<Self>)>;
+
+    /// QR decomposition, returning `(Q, R)` with `A = Q R`
+    fn qr(a: &ArrayView2<Self>) -> LinalgResult<(Array2<Self>, Array2<Self>)>;
+
+    /// Singular value decomposition, returning `(U, S, V^H)`
+    #[allow(clippy::type_complexity)]
+    fn svd(
+        a: &ArrayView2<Self>,
+        full_matrices: bool,
+    ) -> LinalgResult<(Array2<Self>, Array1<Self::Real>, Array2<Self>)>;
+
+    /// Eigenvalues and right eigenvectors of a general square matrix
+    #[allow(clippy::type_complexity)]
+    fn eig(
+        a: &ArrayView2<Self>,
+    ) -> LinalgResult<(Array1<Complex<Self::Real>>, Array2<Complex<Self::Real>>)>;
+
+    /// Matrix exponential
+    fn expm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>>;
+
+    /// Principal matrix logarithm
+    fn logm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>>;
+
+    /// Principal matrix square root
+    fn sqrtm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>>;
+}
+
+macro_rules! impl_real_decomposition_scalar {
+    ($real:ty) => {
+        impl DecompositionScalar for $real {
+            fn lu(
+                a: &ArrayView2<Self>,
+            ) -> LinalgResult<(Array2<Self>, Array2<Self>, Array2<Self>)> {
+                crate::decomposition::lu(a, None)
+            }
+
+            fn qr(a: &ArrayView2<Self>) -> LinalgResult<(Array2<Self>, Array2<Self>)> {
+                crate::decomposition::qr(a, None)
+            }
+
+            fn svd(
+                a: &ArrayView2<Self>,
+                full_matrices: bool,
+            ) -> LinalgResult<(Array2<Self>, Array1<Self>, Array2<Self>)> {
+                crate::decomposition::svd(a, full_matrices, None)
+            }
+
+            fn eig(
+                a: &ArrayView2<Self>,
+            ) -> LinalgResult<(Array1<Complex<Self>>, Array2<Complex<Self>>)> {
+                crate::eigen::eig(a, None)
+            }
+
+            fn expm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>> {
+                crate::matrix_functions::expm(a, None)
+            }
+
+            fn logm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>> {
+                crate::matrix_functions::logm(a)
+            }
+
+            fn sqrtm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>> {
+                crate::matrix_functions::sqrtm_with_method(
+                    a,
+                    crate::matrix_functions::SqrtmMethod::Schur,
+                    0,
+                    <$real>::EPSILON,
+                )
+            }
+        }
+    };
+}
+
+impl_real_decomposition_scalar!(f32);
+impl_real_decomposition_scalar!(f64);
+
+macro_rules! impl_complex_decomposition_scalar {
+    ($real:ty) => {
+        impl DecompositionScalar for Complex<$real> {
+            fn lu(
+                a: &ArrayView2<Self>,
+            ) -> LinalgResult<(Array2<Self>, Array2<Self>, Array2<Self>)> {
+                let (m, n) = a.dim();
+                let k = m.min(n);
+                let factors = crate::complex::complex_lu(a)?;
+                let mut p = Array2::<Self>::zeros((m, m));
+                for (i, &row) in factors.piv.iter().enumerate() {
+                    p[[row, i]] = Complex::new(1.0, 0.0);
+                }
+                let l = Array2::from_shape_fn((m, k), |(i, j)| {
+                    if i == j {
+                        Complex::new(1.0, 0.0)
+                    } else if i > j {
+                        factors.lu[[i, j]]
+                    } else {
+                        Complex::new(0.0, 0.0)
+                    }
+                });
+                let u = Array2::from_shape_fn((k, n), |(i, j)| {
+                    if i <= j {
+                        factors.lu[[i, j]]
+                    } else {
+                        Complex::new(0.0, 0.0)
+                    }
+                });
+                Ok((p, l, u))
+            }
+
+            fn qr(a: &ArrayView2<Self>) -> LinalgResult<(Array2<Self>, Array2<Self>)> {
+                let factors = crate::complex::complex_qr(a)?;
+                Ok((factors.q, factors.r))
+            }
+
+            fn svd(
+                a: &ArrayView2<Self>,
+                full_matrices: bool,
+            ) -> LinalgResult<(Array2<Self>, Array1<$real>, Array2<Self>)> {
+                let factors = crate::complex::complex_svd(a, full_matrices)?;
+                Ok((factors.u, factors.s, factors.vh))
+            }
+
+            fn eig(a: &ArrayView2<Self>) -> LinalgResult<(Array1<Self>, Array2<Self>)> {
+                let factors = crate::complex::complex_eig(a)?;
+                Ok((factors.eigenvalues, factors.eigenvectors))
+            }
+
+            fn expm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>> {
+                crate::matrix_functions::expm_complex(a)
+            }
+
+            fn logm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>> {
+                crate::matrix_functions::logm_complex(a)
+            }
+
+            fn sqrtm(a: &ArrayView2<Self>) -> LinalgResult<Array2<Self>> {
+                crate::matrix_functions::sqrtm_complex(a)
+            }
+        }
+    };
+}
+
+impl_complex_decomposition_scalar!(f32);
+impl_complex_decomposition_scalar!(f64);
+
 /// Generic matrix multiplication - wrapper using ndarray's dot
 pub fn gemm<T>(a: &ArrayView2<T>, b: &ArrayView2<T>) -> LinalgResult<Array2<T>>
 where
@@ -207,24 +425,33 @@ pub fn gnorm<T: LinalgScalar + Float>(a: &ArrayView2<T>, norm_type: &str) -> Lin
     crate::norm::matrix_norm(a, norm_type, None)
 }
 
+/// Generic LU decomposition result
+pub struct GenericLU<T: LinalgScalar> {
+    pub p: Array2<T>,
+    pub l: Array2<T>,
+    pub u: Array2<T>,
+}
+
+/// Generic LU decomposition with partial pivoting (`A = P L U`)
+pub fn glu<T: DecompositionScalar>(a: &ArrayView2<T>) -> LinalgResult<GenericLU<T>> {
+    let (p, l, u) = T::lu(a)?;
+    Ok(GenericLU { p, l, u })
+}
+
 /// Generic SVD decomposition result
 pub struct GenericSVD<T: LinalgScalar> {
     pub u: Array2<T>,
-    pub s: ndarray::Array1<T>,
+    pub s: ndarray::Array1<T::Real>,
     pub vt: Array2<T>,
 }
 
-/// Generic SVD decomposition (only for real floats)  
-pub fn gsvd<T: LinalgScalar + Float>(
+/// Generic SVD decomposition (`vt` holds `V^H` for complex types)
+pub fn gsvd<T: DecompositionScalar>(
     a: &ArrayView2<T>,
     full_matrices: bool,
 ) -> LinalgResult<GenericSVD<T>> {
-    let result = crate::lapack::svd(a, full_matrices)?;
-    Ok(GenericSVD {
-        u: result.u,
-        s: result.s,
-        vt: result.vt,
-    })
+    let (u, s, vt) = T::svd(a, full_matrices)?;
+    Ok(GenericSVD { u, s, vt })
 }
 
 /// Generic QR decomposition result
@@ -233,30 +460,48 @@ pub struct GenericQR<T: LinalgScalar> {
     pub r: Array2<T>,
 }
 
-/// Generic QR decomposition (only for real floats)
-pub fn gqr<T: LinalgScalar + Float>(a: &ArrayView2<T>) -> LinalgResult<GenericQR<T>> {
-    let result = crate::lapack::qr_factor(a)?;
-    Ok(GenericQR {
-        q: result.q,
-        r: result.r,
-    })
+/// Generic QR decomposition
+pub fn gqr<T: DecompositionScalar>(a: &ArrayView2<T>) -> LinalgResult<GenericQR<T>> {
+    let (q, r) = T::qr(a)?;
+    Ok(GenericQR { q, r })
 }
 
-/// Generic eigendecomposition result (complex for real matrices)
+/// Generic eigendecomposition result (always complex)
 pub struct GenericEigen<T: LinalgScalar> {
-    pub eigenvalues: ndarray::Array1<num_complex::Complex<T>>,
-    pub eigenvectors: Array2<num_complex::Complex<T>>,
+    pub eigenvalues: ndarray::Array1<Complex<T::Real>>,
+    pub eigenvectors: Array2<Complex<T::Real>>,
 }
 
-/// Generic eigendecomposition (only for real floats, returns complex)
-pub fn geig<T: LinalgScalar + Float>(a: &ArrayView2<T>) -> LinalgResult<GenericEigen<T>> {
-    let (eigenvalues, eigenvectors) = crate::eigen::eig(a, None)?;
+/// Generic eigendecomposition of a general square matrix
+pub fn geig<T: DecompositionScalar>(a: &ArrayView2<T>) -> LinalgResult<GenericEigen<T>> {
+    let (eigenvalues, eigenvectors) = T::eig(a)?;
     Ok(GenericEigen {
         eigenvalues,
         eigenvectors,
     })
 }
 
+/// Generic matrix exponential
+pub fn gexpm<T: DecompositionScalar>(a: &ArrayView2<T>) -> LinalgResult<Array2<T>> {
+    T::expm(a)
+}
+
+/// Generic principal matrix logarithm
+///
+/// For real types this fails when the logarithm is not real; convert to a
+/// complex type to obtain the complex principal logarithm.
+pub fn glogm<T: DecompositionScalar>(a: &ArrayView2<T>) -> LinalgResult<Array2<T>> {
+    T::logm(a)
+}
+
+/// Generic principal matrix square root
+///
+/// For real types this fails when the square root is not real; convert to a
+/// complex type to obtain the complex principal square root.
+pub fn gsqrtm<T: DecompositionScalar>(a: &ArrayView2<T>) -> LinalgResult<Array2<T>> {
+    T::sqrtm(a)
+}
+
 /// Generic linear solve (only for real floats)
 pub fn gsolve<T: LinalgScalar + Float>(
     a: &ArrayView2<T>,
@@ -404,6 +649,91 @@ mod tests {
         }
     }
 
+    fn complex_matmul(a: &Array2<Complex<f64>>, b: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+        let mut c = Array2::zeros((a.nrows(), b.ncols()));
+        for i in 0..a.nrows() {
+            for j in 0..b.ncols() {
+                for k in 0..a.ncols() {
+                    c[[i, j]] += a[[i, k]] * b[[k, j]];
+                }
+            }
+        }
+        c
+    }
+
+    fn assert_complex_close(a: &Array2<Complex<f64>>, b: &Array2<Complex<f64>>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).norm() < tol, "{x} != {y}");
+        }
+    }
+
+    #[test]
+    fn test_complex_decompositions() {
+        let a = array![
+            [Complex::new(2.0, 1.0), Complex::new(1.0, -1.0)],
+            [Complex::new(0.5, 0.0), Complex::new(3.0, 2.0)],
+            [Complex::new(-1.0, 1.0), Complex::new(0.0, 1.0)]
+        ];
+
+        let lu = glu(&a.view()).unwrap();
+        let plu = complex_matmul(&lu.p, &complex_matmul(&lu.l, &lu.u));
+        assert_complex_close(&plu, &a, 1e-10);
+
+        let qr = gqr(&a.view()).unwrap();
+        assert_complex_close(&complex_matmul(&qr.q, &qr.r), &a, 1e-10);
+
+        let svd = gsvd(&a.view(), false).unwrap();
+        let mut us = svd.u.clone();
+        for j in 0..svd.s.len() {
+            for i in 0..us.nrows() {
+                us[[i, j]] *= svd.s[j];
+            }
+        }
+        assert_complex_close(&complex_matmul(&us, &svd.vt), &a, 1e-10);
+        assert!(svd.s[0] >= svd.s[1]);
+    }
+
+    #[test]
+    fn test_complex_geig() {
+        let a = array![
+            [Complex::new(1.0, 1.0), Complex::new(2.0, 0.0)],
+            [Complex::new(0.0, -1.0), Complex::new(3.0, 0.5)]
+        ];
+        let eigen = geig::<Complex<f64>>(&a.view()).unwrap();
+        for k in 0..2 {
+            let v = eigen.eigenvectors.column(k).to_owned();
+            for i in 0..2 {
+                let av = a[[i, 0]] * v[0] + a[[i, 1]] * v[1];
+                assert!((av - eigen.eigenvalues[k] * v[i]).norm() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_matrix_functions() {
+        // -I has no real logarithm or square root, but complex ones exist
+        let a = array![
+            [Complex::new(-1.0, 0.0), Complex::new(0.0, 0.0)],
+            [Complex::new(0.0, 0.0), Complex::new(-1.0, 0.0)]
+        ];
+        let log_a = glogm(&a.view()).unwrap();
+        assert!((log_a[[0, 0]] - Complex::new(0.0, std::f64::consts::PI)).norm() < 1e-10);
+        assert_complex_close(&gexpm(&log_a.view()).unwrap(), &a, 1e-10);
+
+        let sqrt_a = gsqrtm(&a.view()).unwrap();
+        assert_complex_close(&complex_matmul(&sqrt_a, &sqrt_a), &a, 1e-10);
+
+        let b = array![
+            [Complex::new(4.0, 1.0), Complex::new(1.0, 0.0)],
+            [Complex::new(0.0, 0.5), Complex::new(3.0, -1.0)]
+        ];
+        let log_b = glogm(&b.view()).unwrap();
+        assert_complex_close(&gexpm(&log_b.view()).unwrap(), &b, 1e-9);
+        let sqrt_b = gsqrtm(&b.view()).unwrap();
+        assert_complex_close(&complex_matmul(&sqrt_b, &sqrt_b), &b, 1e-10);
+    }
+
     #[test]
     fn test_precision_selector() {
         assert!(!f32::should_use_high_precision(100.0));
@@ -0,0 +1,923 @@
+//! Krylov subspace solvers for matrix-free linear operators
+//!
+//! The solvers in this module only need the action `x ↦ A x` of the system
+//! matrix, described by the [`LinearOperator`] trait. Dense arrays implement
+//! it directly, as does the closure-based [`crate::matrixfree::LinearOperator`];
+//! any other operator (a stencil, a sparse format, a product of operators)
+//! can implement it in a few lines.
+//!
+//! | Solver | Matrix class | Preconditioner |
+//! |--------|--------------|----------------|
+//! | [`cg`] | symmetric positive definite | symmetric positive definite |
+//! | [`minres`] | symmetric, possibly indefinite | symmetric positive definite |
+//! | [`gmres`] | general, restarted every `restart` steps | any, applied on the right |
+//! | [`bicgstab`] | general | any, applied on the right |
+//!
+//! Preconditioners implement [`PreconditionerOp`]; the Jacobi
+//! ([`DiagonalPreconditioner`]), ILU(0) ([`Ilu0Preconditioner`]) and SSOR
+//! ([`SsorPreconditioner`]) preconditioners are re-exported here.
+//!
+//! Every solver returns a [`KrylovResult`] with the residual norm monitored by
+//! the method after each iteration, so the convergence behaviour can be
+//! inspected or plotted afterwards.
+//!
+//! # Examples
+//!
+//! ```
+//! use ndarray::{array, Array2};
+//! use scirs2_linalg::iterative::{cg, Ilu0Preconditioner, KrylovOptions};
+//!
+//! // 1D Poisson matrix
+//! let n = 50;
+//! let a = Array2::from_shape_fn((n, n), |(i, j)| {
+//!     if i == j { 2.0_f64 } else if i.abs_diff(j) == 1 { -1.0 } else { 0.0 }
+//! });
+//! let b = ndarray::Array1::ones(n);
+//!
+//! let ilu = Ilu0Preconditioner::new(&a.view()).unwrap();
+//! let options = KrylovOptions::new().with_tolerance(1e-10);
+//! let result = cg(&a, &b.view(), None, Some(&ilu), &options).unwrap();
+//! assert!(result.converged);
+//! assert_eq!(result.residual_history.len(), result.iterations + 1);
+//! ```
+
+use ndarray::{Array1, Array2, ArrayBase, ArrayView1, Data, Ix2, ScalarOperand};
+use num_traits::{Float, NumAssign, One, Zero};
+use std::iter::Sum;
+
+use crate::error::{LinalgError, LinalgResult};
+
+pub use crate::preconditioners::{
+    DiagonalPreconditioner, Ilu0Preconditioner, PreconditionerOp, SsorPreconditioner,
+};
+
+/// A linear map `x ↦ A x` used by the Krylov solvers
+pub trait LinearOperator<F> {
+    /// Compute `A x`
+    fn apply(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>>;
+
+    /// Number of rows of `A`
+    fn nrows(&self) -> usize;
+
+    /// Number of columns of `A`
+    fn ncols(&self) -> usize;
+}
+
+impl<F, S> LinearOperator<F> for ArrayBase<S, Ix2>
+where
+    F: Float + 'static,
+    S: Data<Elem = F>,
+{
+    fn apply(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        if x.len() != self.ncols() {
+            return Err(LinalgError::ShapeError(format!(
+                "Vector of length {} does not match operator with {} columns",
+                x.len(),
+                self.ncols()
+            )));
+        }
+        Ok(self.dot(x))
+    }
+
+    fn nrows(&self) -> usize {
+        self.shape()[0]
+    }
+
+    fn ncols(&self) -> usize {
+        self.shape()[1]
+    }
+}
+
+impl<F> LinearOperator<F> for crate::matrixfree::LinearOperator<F>
+where
+    F: Float + NumAssign + Zero + Sum + One + ScalarOperand + Send + Sync,
+{
+    fn apply(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        crate::matrixfree::MatrixFreeOp::apply(self, x)
+    }
+
+    fn nrows(&self) -> usize {
+        crate::matrixfree::MatrixFreeOp::nrows(self)
+    }
+
+    fn ncols(&self) -> usize {
+        crate::matrixfree::MatrixFreeOp::ncols(self)
+    }
+}
+
+/// Options shared by the Krylov solvers
+#[derive(Debug, Clone)]
+pub struct KrylovOptions<F> {
+    /// Maximum number of iterations (matrix-vector products for GMRES)
+    pub max_iterations: usize,
+    /// Relative tolerance: iteration stops once the monitored residual norm
+    /// is at most `tolerance` times the norm of the right-hand side
+    pub tolerance: F,
+    /// Krylov subspace dimension after which GMRES restarts
+    pub restart: usize,
+}
+
+impl<F: Float> Default for KrylovOptions<F> {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1000,
+            tolerance: F::from(1e-10).unwrap(),
+            restart: 30,
+        }
+    }
+}
+
+impl<F: Float> KrylovOptions<F> {
+    /// Create options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of iterations
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the relative tolerance
+    pub fn with_tolerance(mut self, tolerance: F) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the GMRES restart length
+    pub fn with_restart(mut self, restart: usize) -> Self {
+        self.restart = restart;
+        self
+    }
+}
+
+/// Result of a Krylov solve
+#[derive(Debug, Clone)]
+pub struct KrylovResult<F> {
+    /// Approximate solution
+    pub solution: Array1<F>,
+    /// Number of iterations performed
+    pub iterations: usize,
+    /// Norm of the true residual `b - A x` of the returned solution
+    pub residual_norm: F,
+    /// Whether the tolerance was reached
+    pub converged: bool,
+    /// Monitored residual norm before the first iteration and after each one
+    ///
+    /// CG, BiCGSTAB and GMRES monitor the Euclidean norm of `b - A x`. MINRES
+    /// monitors the norm induced by the preconditioner, which is the
+    /// Euclidean norm when no preconditioner is given.
+    pub residual_history: Vec<F>,
+}
+
+/// Dot product of two vectors
+fn dot<F: Float + Sum>(x: &Array1<F>, y: &Array1<F>) -> F {
+    x.iter().zip(y.iter()).map(|(&a, &b)| a * b).sum()
+}
+
+/// Euclidean norm of a vector
+fn norm<F: Float + Sum>(x: &Array1<F>) -> F {
+    dot(x, x).sqrt()
+}
+
+/// Validate shapes and return the initial guess
+fn initial_guess<F, A>(
+    a: &A,
+    b: &ArrayView1<F>,
+    x0: Option<&ArrayView1<F>>,
+    preconditioner: Option<&dyn PreconditionerOp<F>>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float,
+    A: LinearOperator<F> + ?Sized,
+{
+    let n = a.nrows();
+    if a.ncols() != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Krylov solvers require a square operator, got {}x{}",
+            n,
+            a.ncols()
+        )));
+    }
+    if b.len() != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side of length {} does not match operator of size {}",
+            b.len(),
+            n
+        )));
+    }
+    if let Some(m) = preconditioner {
+        if m.size() != n {
+            return Err(LinalgError::ShapeError(format!(
+                "Preconditioner of size {} does not match operator of size {}",
+                m.size(),
+                n
+            )));
+        }
+    }
+    match x0 {
+        Some(x0) if x0.len() != n => Err(LinalgError::ShapeError(format!(
+            "Initial guess of length {} does not match operator of size {}",
+            x0.len(),
+            n
+        ))),
+        Some(x0) => Ok(x0.to_owned()),
+        None => Ok(Array1::zeros(n)),
+    }
+}
+
+/// Apply the preconditioner, or the identity if there is none
+fn precondition<F: Float>(
+    preconditioner: Option<&dyn PreconditionerOp<F>>,
+    r: &Array1<F>,
+) -> LinalgResult<Array1<F>> {
+    match preconditioner {
+        Some(m) => m.apply(&r.view()),
+        None => Ok(r.clone()),
+    }
+}
+
+/// Residual `b - A x`
+fn residual<F, A>(a: &A, b: &ArrayView1<F>, x: &Array1<F>) -> LinalgResult<Array1<F>>
+where
+    F: Float,
+    A: LinearOperator<F> + ?Sized,
+{
+    Ok(b - &a.apply(&x.view())?)
+}
+
+/// Assemble the result, recomputing the true residual of the final iterate
+fn finish<F, A>(
+    a: &A,
+    b: &ArrayView1<F>,
+    solution: Array1<F>,
+    iterations: usize,
+    converged: bool,
+    residual_history: Vec<F>,
+) -> LinalgResult<KrylovResult<F>>
+where
+    F: Float + Sum,
+    A: LinearOperator<F> + ?Sized,
+{
+    let residual_norm = norm(&residual(a, b, &solution)?);
+    Ok(KrylovResult {
+        solution,
+        iterations,
+        residual_norm,
+        converged,
+        residual_history,
+    })
+}
+
+/// Preconditioned conjugate gradient method
+///
+/// Solves `A x = b` for a symmetric positive definite operator `A`. The
+/// preconditioner must be symmetric positive definite too.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric positive definite operator
+/// * `b` - Right-hand side
+/// * `x0` - Initial guess (zero if `None`)
+/// * `preconditioner` - Optional preconditioner approximating `A⁻¹`
+/// * `options` - Iteration limit and tolerance
+///
+/// # Returns
+///
+/// * Solution, iteration count and residual history. If a direction with
+///   non-positive curvature `pᵀAp <= 0` is met, iteration stops early with
+///   `converged == false`.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::iterative::{cg, KrylovOptions};
+///
+/// let a = array![[4.0_f64, 1.0], [1.0, 3.0]];
+/// let b = array![1.0, 2.0];
+/// let result = cg(&a, &b.view(), None, None, &KrylovOptions::new()).unwrap();
+/// assert!(result.converged);
+/// assert!((result.solution[0] - 1.0 / 11.0).abs() < 1e-10);
+/// ```
+pub fn cg<F, A>(
+    a: &A,
+    b: &ArrayView1<F>,
+    x0: Option<&ArrayView1<F>>,
+    preconditioner: Option<&dyn PreconditionerOp<F>>,
+    options: &KrylovOptions<F>,
+) -> LinalgResult<KrylovResult<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+    A: LinearOperator<F> + ?Sized,
+{
+    let mut x = initial_guess(a, b, x0, preconditioner)?;
+    let threshold = options.tolerance * norm(&b.to_owned());
+
+    let mut r = residual(a, b, &x)?;
+    let mut history = vec![norm(&r)];
+    if history[0] <= threshold {
+        return finish(a, b, x, 0, true, history);
+    }
+
+    let mut z = precondition(preconditioner, &r)?;
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+    let mut iterations = 0;
+    let mut converged = false;
+
+    while iterations < options.max_iterations {
+        let ap = a.apply(&p.view())?;
+        let curvature = dot(&p, &ap);
+        if curvature <= F::zero() {
+            break;
+        }
+
+        let alpha = rz / curvature;
+        x.scaled_add(alpha, &p);
+        r.scaled_add(-alpha, &ap);
+        iterations += 1;
+
+        let rnorm = norm(&r);
+        history.push(rnorm);
+        if rnorm <= threshold {
+            converged = true;
+            break;
+        }
+
+        z = precondition(preconditioner, &r)?;
+        let rz_new = dot(&r, &z);
+        let beta = rz_new / rz;
+        p = &z + &(p * beta);
+        rz = rz_new;
+    }
+
+    finish(a, b, x, iterations, converged, history)
+}
+
+/// Restarted generalized minimal residual method, GMRES(m)
+///
+/// Solves `A x = b` for a general square operator. The Krylov basis is built
+/// with modified Gram-Schmidt and the least squares problem is updated with
+/// Givens rotations; after `options.restart` steps the basis is discarded and
+/// the method restarts from the current iterate. The preconditioner is applied
+/// on the right, so the monitored residual is the true residual.
+///
+/// # Arguments
+///
+/// * `a` - Square operator
+/// * `b` - Right-hand side
+/// * `x0` - Initial guess (zero if `None`)
+/// * `preconditioner` - Optional preconditioner approximating `A⁻¹`
+/// * `options` - Iteration limit, tolerance and restart length
+///
+/// # Returns
+///
+/// * Solution, number of inner iterations and residual history
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::iterative::{gmres, KrylovOptions};
+///
+/// let a = array![[3.0_f64, 1.0, 0.0], [-1.0, 4.0, 2.0], [0.0, 1.0, 5.0]];
+/// let b = array![4.0, 5.0, 6.0];
+/// let result = gmres(&a, &b.view(), None, None, &KrylovOptions::new()).unwrap();
+/// assert!(result.converged);
+/// assert!(result.residual_norm < 1e-8);
+/// ```
+pub fn gmres<F, A>(
+    a: &A,
+    b: &ArrayView1<F>,
+    x0: Option<&ArrayView1<F>>,
+    preconditioner: Option<&dyn PreconditionerOp<F>>,
+    options: &KrylovOptions<F>,
+) -> LinalgResult<KrylovResult<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+    A: LinearOperator<F> + ?Sized,
+{
+    if options.restart == 0 {
+        return Err(LinalgError::InvalidInputError(
+            "GMRES restart length must be positive".to_string(),
+        ));
+    }
+    let mut x = initial_guess(a, b, x0, preconditioner)?;
+    let n = x.len();
+    let m = options.restart.min(n.max(1));
+    let threshold = options.tolerance * norm(&b.to_owned());
+
+    let r = residual(a, b, &x)?;
+    let mut beta = norm(&r);
+    let mut history = vec![beta];
+    let mut iterations = 0;
+    let mut converged = beta <= threshold;
+    let mut v0 = r;
+
+    while !converged && iterations < options.max_iterations {
+        let mut basis: Vec<Array1<F>> = vec![v0 / beta];
+        let mut h = Array2::<F>::zeros((m + 1, m));
+        let mut cs = vec![F::zero(); m];
+        let mut sn = vec![F::zero(); m];
+        let mut g = vec![F::zero(); m + 1];
+        g[0] = beta;
+
+        let mut k = 0;
+        while k < m && iterations < options.max_iterations {
+            let mut w = a.apply(&precondition(preconditioner, &basis[k])?.view())?;
+            for (i, vi) in basis.iter().enumerate() {
+                let hik = dot(&w, vi);
+                h[[i, k]] = hik;
+                w.scaled_add(-hik, vi);
+            }
+            let wnorm = norm(&w);
+            h[[k + 1, k]] = wnorm;
+
+            // Apply the previous rotations to the new column
+            for i in 0..k {
+                let t = cs[i] * h[[i, k]] + sn[i] * h[[i + 1, k]];
+                h[[i + 1, k]] = -sn[i] * h[[i, k]] + cs[i] * h[[i + 1, k]];
+                h[[i, k]] = t;
+            }
+            let denom = h[[k, k]].hypot(h[[k + 1, k]]);
+            if denom == F::zero() {
+                cs[k] = F::one();
+                sn[k] = F::zero();
+            } else {
+                cs[k] = h[[k, k]] / denom;
+                sn[k] = h[[k + 1, k]] / denom;
+            }
+            h[[k, k]] = denom;
+            h[[k + 1, k]] = F::zero();
+            g[k + 1] = -sn[k] * g[k];
+            g[k] = cs[k] * g[k];
+
+            iterations += 1;
+            k += 1;
+            let estimate = g[k].abs();
+            history.push(estimate);
+            if estimate <= threshold || wnorm == F::zero() {
+                break;
+            }
+            basis.push(w / wnorm);
+        }
+
+        // Back substitution for the k x k triangular system
+        let mut y = vec![F::zero(); k];
+        for i in (0..k).rev() {
+            let mut sum = g[i];
+            for j in (i + 1)..k {
+                sum -= h[[i, j]] * y[j];
+            }
+            if h[[i, i]] == F::zero() {
+                return Err(LinalgError::ComputationError(
+                    "GMRES encountered a singular Hessenberg matrix".to_string(),
+                ));
+            }
+            y[i] = sum / h[[i, i]];
+        }
+        let mut update = Array1::zeros(n);
+        for (vi, &yi) in basis.iter().zip(y.iter()) {
+            update.scaled_add(yi, vi);
+        }
+        x += &precondition(preconditioner, &update)?;
+
+        v0 = residual(a, b, &x)?;
+        beta = norm(&v0);
+        converged = beta <= threshold;
+        if beta == F::zero() {
+            break;
+        }
+    }
+
+    finish(a, b, x, iterations, converged, history)
+}
+
+/// Biconjugate gradient stabilized method, BiCGSTAB
+///
+/// Solves `A x = b` for a general square operator using short recurrences, so
+/// memory use does not grow with the iteration count. The preconditioner is
+/// applied on the right. A breakdown (`r̂ᵀr = 0` or `r̂ᵀv = 0`) ends the
+/// iteration with `converged == false`.
+///
+/// # Arguments
+///
+/// * `a` - Square operator
+/// * `b` - Right-hand side
+/// * `x0` - Initial guess (zero if `None`)
+/// * `preconditioner` - Optional preconditioner approximating `A⁻¹`
+/// * `options` - Iteration limit and tolerance
+///
+/// # Returns
+///
+/// * Solution, iteration count and residual history
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::iterative::{bicgstab, KrylovOptions};
+///
+/// let a = array![[4.0_f64, 1.0], [-2.0, 3.0]];
+/// let b = array![5.0, 1.0];
+/// let result = bicgstab(&a, &b.view(), None, None, &KrylovOptions::new()).unwrap();
+/// assert!(result.converged);
+/// assert!((result.solution[0] - 1.0).abs() < 1e-8);
+/// ```
+pub fn bicgstab<F, A>(
+    a: &A,
+    b: &ArrayView1<F>,
+    x0: Option<&ArrayView1<F>>,
+    preconditioner: Option<&dyn PreconditionerOp<F>>,
+    options: &KrylovOptions<F>,
+) -> LinalgResult<KrylovResult<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+    A: LinearOperator<F> + ?Sized,
+{
+    let mut x = initial_guess(a, b, x0, preconditioner)?;
+    let threshold = options.tolerance * norm(&b.to_owned());
+
+    let mut r = residual(a, b, &x)?;
+    let mut history = vec![norm(&r)];
+    if history[0] <= threshold {
+        return finish(a, b, x, 0, true, history);
+    }
+
+    let r_hat = r.clone();
+    let mut p = r.clone();
+    let mut v = Array1::zeros(r.len());
+    let (mut rho, mut alpha, mut omega) = (F::one(), F::one(), F::one());
+    let mut iterations = 0;
+    let mut converged = false;
+
+    while iterations < options.max_iterations {
+        let rho_new = dot(&r_hat, &r);
+        if rho_new == F::zero() {
+            break;
+        }
+        if iterations > 0 {
+            let beta = (rho_new / rho) * (alpha / omega);
+            p = &r + &((&p - &(&v * omega)) * beta);
+        }
+
+        let p_hat = precondition(preconditioner, &p)?;
+        v = a.apply(&p_hat.view())?;
+        let rv = dot(&r_hat, &v);
+        if rv == F::zero() {
+            break;
+        }
+        alpha = rho_new / rv;
+        x.scaled_add(alpha, &p_hat);
+        let s = &r - &(&v * alpha);
+        iterations += 1;
+
+        let snorm = norm(&s);
+        if snorm <= threshold {
+            history.push(snorm);
+            converged = true;
+            break;
+        }
+
+        let s_hat = precondition(preconditioner, &s)?;
+        let t = a.apply(&s_hat.view())?;
+        let tt = dot(&t, &t);
+        if tt == F::zero() {
+            history.push(snorm);
+            break;
+        }
+        omega = dot(&t, &s) / tt;
+        x.scaled_add(omega, &s_hat);
+        r = &s - &(&t * omega);
+
+        let rnorm = norm(&r);
+        history.push(rnorm);
+        if rnorm <= threshold {
+            converged = true;
+            break;
+        }
+        if omega == F::zero() {
+            break;
+        }
+        rho = rho_new;
+    }
+
+    finish(a, b, x, iterations, converged, history)
+}
+
+/// Minimum residual method, MINRES
+///
+/// Solves `A x = b` for a symmetric, possibly indefinite, operator using the
+/// Lanczos process with Paige and Saunders' QR updates. The preconditioner
+/// must be symmetric positive definite; the monitored residual norm is then
+/// `sqrt(rᵀ M⁻¹ r)`, where `M⁻¹` is the preconditioner application, and the
+/// tolerance is taken relative to `sqrt(bᵀ M⁻¹ b)`.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric operator
+/// * `b` - Right-hand side
+/// * `x0` - Initial guess (zero if `None`)
+/// * `preconditioner` - Optional symmetric positive definite preconditioner
+/// * `options` - Iteration limit and tolerance
+///
+/// # Returns
+///
+/// * Solution, iteration count and residual history
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::iterative::{minres, KrylovOptions};
+///
+/// // Symmetric indefinite matrix
+/// let a = array![[1.0_f64, 2.0], [2.0, -3.0]];
+/// let b = array![3.0, -1.0];
+/// let result = minres(&a, &b.view(), None, None, &KrylovOptions::new()).unwrap();
+/// assert!(result.converged);
+/// assert!((result.solution[0] - 1.0).abs() < 1e-8);
+/// ```
+pub fn minres<F, A>(
+    a: &A,
+    b: &ArrayView1<F>,
+    x0: Option<&ArrayView1<F>>,
+    preconditioner: Option<&dyn PreconditionerOp<F>>,
+    options: &KrylovOptions<F>,
+) -> LinalgResult<KrylovResult<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+    A: LinearOperator<F> + ?Sized,
+{
+    let mut x = initial_guess(a, b, x0, preconditioner)?;
+    let n = x.len();
+
+    // Norm induced by the preconditioner
+    let m_norm = |r: &Array1<F>, z: &Array1<F>| -> LinalgResult<F> {
+        let value = dot(r, z);
+        if value < F::zero() {
+            return Err(LinalgError::NonPositiveDefiniteError(
+                "MINRES requires a positive definite preconditioner".to_string(),
+            ));
+        }
+        Ok(value.sqrt())
+    };
+    let b_owned = b.to_owned();
+    let threshold = options.tolerance * m_norm(&b_owned, &precondition(preconditioner, &b_owned)?)?;
+
+    let mut r1 = residual(a, b, &x)?;
+    let mut y = precondition(preconditioner, &r1)?;
+    let beta1 = m_norm(&r1, &y)?;
+    let mut history = vec![beta1];
+    if beta1 <= threshold {
+        return finish(a, b, x, 0, true, history);
+    }
+
+    let mut r2 = r1.clone();
+    let mut w = Array1::<F>::zeros(n);
+    let mut w2 = Array1::<F>::zeros(n);
+    let (mut oldb, mut beta) = (F::zero(), beta1);
+    let (mut dbar, mut epsln, mut phibar) = (F::zero(), F::zero(), beta1);
+    let (mut cs, mut sn) = (-F::one(), F::zero());
+    let mut iterations = 0;
+    let mut converged = false;
+
+    while iterations < options.max_iterations {
+        // Lanczos step
+        let v = &y / beta;
+        y = a.apply(&v.view())?;
+        if iterations > 0 {
+            y.scaled_add(-beta / oldb, &r1);
+        }
+        let alpha = dot(&v, &y);
+        y.scaled_add(-alpha / beta, &r2);
+        r1 = std::mem::replace(&mut r2, y);
+        y = precondition(preconditioner, &r2)?;
+        oldb = beta;
+        beta = m_norm(&r2, &y)?;
+
+        // Apply the previous rotation and compute the next one
+        let oldeps = epsln;
+        let delta = cs * dbar + sn * alpha;
+        let gbar = sn * dbar - cs * alpha;
+        epsln = sn * beta;
+        dbar = -cs * beta;
+        let gamma = gbar.hypot(beta).max(F::epsilon());
+        cs = gbar / gamma;
+        sn = beta / gamma;
+        let phi = cs * phibar;
+        phibar = sn * phibar;
+
+        // Update the search direction and the solution
+        let w1 = std::mem::replace(&mut w2, w);
+        w = (&v - &(&w1 * oldeps) - &(&w2 * delta)) / gamma;
+        x.scaled_add(phi, &w);
+        iterations += 1;
+
+        history.push(phibar);
+        if phibar <= threshold {
+            converged = true;
+            break;
+        }
+        if beta == F::zero() {
+            // The Krylov space is invariant, so x is the exact solution
+            converged = true;
+            break;
+        }
+    }
+
+    finish(a, b, x, iterations, converged, history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrixfree::LinearOperator as ClosureOperator;
+    use approx::assert_relative_eq;
+    use ndarray::{array, Array1};
+
+    /// 1D Poisson matrix with `n` unknowns
+    fn poisson(n: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                2.0
+            } else if i.abs_diff(j) == 1 {
+                -1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Nonsymmetric convection-diffusion matrix with `n` unknowns
+    fn convection_diffusion(n: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                2.5
+            } else if j + 1 == i {
+                -1.4
+            } else if i + 1 == j {
+                -0.6
+            } else {
+                0.0
+            }
+        })
+    }
+
+    fn assert_solves(a: &Array2<f64>, b: &Array1<f64>, result: &KrylovResult<f64>) {
+        assert!(result.converged);
+        assert_eq!(result.residual_history.len(), result.iterations + 1);
+        let r = b - &a.dot(&result.solution);
+        assert_relative_eq!(
+            r.iter().map(|v| v * v).sum::<f64>().sqrt(),
+            0.0,
+            epsilon = 1e-7
+        );
+    }
+
+    #[test]
+    fn test_cg_with_preconditioners() {
+        let n = 60;
+        let a = poisson(n);
+        let b = Array1::from_shape_fn(n, |i| (i as f64 * 0.1).sin());
+        let options = KrylovOptions::new().with_tolerance(1e-12);
+
+        let plain = cg(&a, &b.view(), None, None, &options).unwrap();
+        assert_solves(&a, &b, &plain);
+
+        let jacobi = DiagonalPreconditioner::new(&a.view()).unwrap();
+        let ilu = Ilu0Preconditioner::new(&a.view()).unwrap();
+        let ssor = SsorPreconditioner::new(&a.view(), 1.5).unwrap();
+        let preconditioners: [&dyn PreconditionerOp<f64>; 3] = [&jacobi, &ilu, &ssor];
+        for m in preconditioners {
+            let result = cg(&a, &b.view(), None, Some(m), &options).unwrap();
+            assert_solves(&a, &b, &result);
+        }
+
+        // The ILU(0) factors of a tridiagonal matrix are exact
+        let result = cg(&a, &b.view(), None, Some(&ilu), &options).unwrap();
+        assert!(result.iterations <= 2);
+        // SSOR clusters the spectrum better than no preconditioning
+        let result = cg(&a, &b.view(), None, Some(&ssor), &options).unwrap();
+        assert!(result.iterations < plain.iterations);
+    }
+
+    #[test]
+    fn test_gmres_restarted() {
+        let n = 40;
+        let a = convection_diffusion(n);
+        let b = Array1::ones(n);
+        let options = KrylovOptions::new().with_tolerance(1e-11).with_restart(5);
+
+        let result = gmres(&a, &b.view(), None, None, &options).unwrap();
+        assert_solves(&a, &b, &result);
+        assert!(result.iterations > 5);
+        // The GMRES residual never increases, also across restarts
+        for pair in result.residual_history.windows(2) {
+            assert!(pair[1] <= pair[0] * (1.0 + 1e-12));
+        }
+
+        let ilu = Ilu0Preconditioner::new(&a.view()).unwrap();
+        let preconditioned = gmres(&a, &b.view(), None, Some(&ilu), &options).unwrap();
+        assert_solves(&a, &b, &preconditioned);
+        assert!(preconditioned.iterations < result.iterations);
+    }
+
+    #[test]
+    fn test_bicgstab() {
+        let n = 50;
+        let a = convection_diffusion(n);
+        let b = Array1::from_shape_fn(n, |i| 1.0 + i as f64 / n as f64);
+        let options = KrylovOptions::new().with_tolerance(1e-12);
+
+        let result = bicgstab(&a, &b.view(), None, None, &options).unwrap();
+        assert_solves(&a, &b, &result);
+
+        let ssor = SsorPreconditioner::new(&a.view(), 1.0).unwrap();
+        let result = bicgstab(&a, &b.view(), None, Some(&ssor), &options).unwrap();
+        assert_solves(&a, &b, &result);
+    }
+
+    #[test]
+    fn test_minres_indefinite() {
+        // Shifted Poisson matrix with both positive and negative eigenvalues
+        let n = 30;
+        let a = poisson(n) - Array2::<f64>::eye(n) * 1.0;
+        let b = Array1::from_shape_fn(n, |i| ((i % 7) as f64) - 3.0);
+        let options = KrylovOptions::new().with_tolerance(1e-12);
+
+        let result = minres(&a, &b.view(), None, None, &options).unwrap();
+        assert_solves(&a, &b, &result);
+
+        // Diagonal preconditioner built from |a_ii| keeps M positive definite
+        let diag = Array2::from_diag(&a.diag().mapv(|d| d.abs().max(1.0)));
+        let jacobi = DiagonalPreconditioner::new(&diag.view()).unwrap();
+        let result = minres(&a, &b.view(), None, Some(&jacobi), &options).unwrap();
+        assert_solves(&a, &b, &result);
+    }
+
+    #[test]
+    fn test_matrix_free_operator() {
+        // Poisson stencil applied without storing the matrix
+        let n = 100;
+        let op = ClosureOperator::new(n, move |x: &ArrayView1<f64>| {
+            Array1::from_shape_fn(n, |i| {
+                let left = if i > 0 { x[i - 1] } else { 0.0 };
+                let right = if i + 1 < n { x[i + 1] } else { 0.0 };
+                2.0 * x[i] - left - right
+            })
+        })
+        .symmetric()
+        .positive_definite();
+        let b = Array1::ones(n);
+        let options = KrylovOptions::new()
+            .with_tolerance(1e-11)
+            .with_max_iterations(500);
+
+        let dense = poisson(n);
+        for result in [
+            cg(&op, &b.view(), None, None, &options).unwrap(),
+            minres(&op, &b.view(), None, None, &options).unwrap(),
+            gmres(&op, &b.view(), None, None, &options.clone().with_restart(n)).unwrap(),
+            bicgstab(&op, &b.view(), None, None, &options).unwrap(),
+        ] {
+            assert_solves(&dense, &b, &result);
+        }
+    }
+
+    #[test]
+    fn test_initial_guess_and_errors() {
+        let a = array![[4.0, 1.0], [1.0, 3.0]];
+        let b = array![1.0, 2.0];
+        let exact = array![1.0 / 11.0, 7.0 / 11.0];
+        let options = KrylovOptions::new();
+
+        let result = cg(&a, &b.view(), Some(&exact.view()), None, &options).unwrap();
+        assert!(result.converged);
+        assert_eq!(result.iterations, 0);
+
+        let zero = gmres(&a, &Array1::zeros(2).view(), None, None, &options).unwrap();
+        assert!(zero.converged);
+        assert_eq!(zero.solution, Array1::<f64>::zeros(2));
+
+        let short = array![1.0];
+        assert!(cg(&a, &short.view(), None, None, &options).is_err());
+        assert!(bicgstab(&a, &b.view(), Some(&short.view()), None, &options).is_err());
+        let rect = Array2::<f64>::zeros((2, 3));
+        assert!(minres(&rect, &b.view(), None, None, &options).is_err());
+        assert!(gmres(&a, &b.view(), None, None, &options.with_restart(0)).is_err());
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let a = poisson(50);
+        let b = Array1::ones(50);
+        let options = KrylovOptions::new().with_max_iterations(3);
+        let result = cg(&a, &b.view(), None, None, &options).unwrap();
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 3);
+        assert_eq!(result.residual_history.len(), 4);
+    }
+}
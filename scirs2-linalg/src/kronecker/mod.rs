@@ -3,6 +3,10 @@
 //! This module provides optimized implementations of the Kronecker product and related
 //! operations that are particularly useful for neural network layers, especially in
 //! second-order optimization methods like K-FAC (Kronecker-Factored Approximate Curvature).
+//!
+//! [`KroneckerOperator`], [`solve_kron`] and [`solve_kron_sum`] apply and invert
+//! Kronecker products and sums through their factors, without forming the
+//! full matrix.
 
 use ndarray::{Array1, Array2, ArrayView2, ScalarOperand};
 use num_traits::{Float, NumAssign};
@@ -17,6 +21,10 @@ use crate::decomposition::cholesky;
 use crate::error::{LinalgError, LinalgResult};
 use crate::norm::matrix_norm;
 
+mod operator;
+
+pub use operator::{solve_kron, solve_kron_sum, KroneckerOperator};
+
 /// Compute the Kronecker product of two matrices
 ///
 /// The Kronecker product of matrices A (m×n) and B (p×q) is a matrix C (mp×nq) where
@@ -0,0 +1,479 @@
+//! Lazy Kronecker-structured operators and solvers
+//!
+//! With the row-major vectorization `x = vec(X)` used throughout this module
+//! (`x[i * q + j] = X[i, j]`), a Kronecker product acts on matrices as
+//! `(A ⊗ B) vec(X) = vec(A X B^T)` and a Kronecker sum as
+//! `(A ⊗ I + I ⊗ B) vec(X) = vec(A X + X B^T)`. Both identities let systems
+//! of order `n·m` be applied and solved through the factors alone, without
+//! forming the `n·m × n·m` matrix.
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, ScalarOperand};
+use num_complex::Complex;
+use num_traits::{Float, NumAssign, Zero};
+use std::iter::Sum;
+
+use crate::error::{LinalgError, LinalgResult};
+use crate::schur::complex_schur;
+use crate::solve::solve_multiple;
+
+/// Reshape a vector of length `rows * cols` into a row-major matrix
+fn unvec<F: Clone>(x: &ArrayView1<F>, rows: usize, cols: usize) -> LinalgResult<Array2<F>> {
+    Array2::from_shape_vec((rows, cols), x.iter().cloned().collect())
+        .map_err(|e| LinalgError::ShapeError(e.to_string()))
+}
+
+/// Row-major vectorization of a matrix
+fn vec<F: Clone>(x: Array2<F>) -> Array1<F> {
+    let len = x.len();
+    x.as_standard_layout()
+        .into_owned()
+        .into_shape_with_order(len)
+        .expect("standard layout array reshapes to a vector")
+}
+
+/// Check that a factor is square
+fn check_square<F>(a: &ArrayView2<F>, name: &str) -> LinalgResult<usize> {
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Factor {} must be square, got {}x{}",
+            name,
+            a.nrows(),
+            a.ncols()
+        )));
+    }
+    Ok(a.nrows())
+}
+
+/// Kronecker product `A ⊗ B` stored through its factors
+///
+/// Products cost O(mnq + mpq) or O(npq + mnp) operations (whichever order is
+/// cheaper) instead of the O(mnpq) of the explicit matrix, and the operator
+/// plugs into the Krylov solvers through
+/// [`LinearOperator`](crate::iterative::LinearOperator).
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::kronecker::{kron, KroneckerOperator};
+///
+/// let a = array![[1.0_f64, 2.0], [3.0, 4.0]];
+/// let b = array![[0.0_f64, 1.0, 0.5], [2.0, 0.0, 1.0]];
+/// let op = KroneckerOperator::new(&a.view(), &b.view());
+/// assert_eq!(op.shape(), (4, 6));
+///
+/// let x = array![1.0, -1.0, 2.0, 0.5, 0.0, 3.0];
+/// let y = op.matvec(&x.view()).unwrap();
+/// let dense = kron(&a.view(), &b.view()).unwrap().dot(&x);
+/// for (p, q) in y.iter().zip(dense.iter()) {
+///     assert!((p - q).abs() < 1e-12);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct KroneckerOperator<F> {
+    a: Array2<F>,
+    b: Array2<F>,
+}
+
+impl<F> KroneckerOperator<F>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+{
+    /// Create the operator `A ⊗ B`
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First factor of shape (m, n)
+    /// * `b` - Second factor of shape (p, q)
+    pub fn new(a: &ArrayView2<F>, b: &ArrayView2<F>) -> Self {
+        KroneckerOperator {
+            a: a.to_owned(),
+            b: b.to_owned(),
+        }
+    }
+
+    /// The factors `(A, B)`
+    pub fn factors(&self) -> (&Array2<F>, &Array2<F>) {
+        (&self.a, &self.b)
+    }
+
+    /// Shape `(m·p, n·q)` of the full operator
+    pub fn shape(&self) -> (usize, usize) {
+        (
+            self.a.nrows() * self.b.nrows(),
+            self.a.ncols() * self.b.ncols(),
+        )
+    }
+
+    /// The transposed operator `(A ⊗ B)^T = A^T ⊗ B^T`
+    pub fn transpose(&self) -> Self {
+        KroneckerOperator {
+            a: self.a.t().to_owned(),
+            b: self.b.t().to_owned(),
+        }
+    }
+
+    /// Compute `(A ⊗ B) x`
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Vector of length n·q
+    ///
+    /// # Returns
+    ///
+    /// * Vector of length m·p
+    pub fn matvec(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let (m, n) = self.a.dim();
+        let (p, q) = self.b.dim();
+        if x.len() != n * q {
+            return Err(LinalgError::ShapeError(format!(
+                "Vector length ({}) must equal n*q ({}*{}={})",
+                x.len(),
+                n,
+                q,
+                n * q
+            )));
+        }
+        let x_mat = unvec(x, n, q)?;
+        // A X B^T, associated to minimize the flop count
+        let y = if m * n * q + m * q * p <= n * q * p + m * n * p {
+            self.a.dot(&x_mat).dot(&self.b.t())
+        } else {
+            self.a.dot(&x_mat.dot(&self.b.t()))
+        };
+        Ok(vec(y))
+    }
+
+    /// Compute `(A ⊗ B)^T x`
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Vector of length m·p
+    ///
+    /// # Returns
+    ///
+    /// * Vector of length n·q
+    pub fn rmatvec(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        self.transpose().matvec(x)
+    }
+
+    /// Compute `(A ⊗ B) X` column by column
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Matrix of shape (n·q, r)
+    ///
+    /// # Returns
+    ///
+    /// * Matrix of shape (m·p, r)
+    pub fn matmat(&self, x: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        let mut result = Array2::zeros((self.shape().0, x.ncols()));
+        for (col, mut out) in x.columns().into_iter().zip(result.columns_mut()) {
+            out.assign(&self.matvec(&col)?);
+        }
+        Ok(result)
+    }
+
+    /// Solve `(A ⊗ B) x = c` for square, nonsingular factors
+    ///
+    /// See [`solve_kron`].
+    pub fn solve(&self, c: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        solve_kron((&self.a.view(), &self.b.view()), c)
+    }
+
+    /// Form the explicit Kronecker product
+    pub fn to_dense(&self) -> LinalgResult<Array2<F>> {
+        super::kron(&self.a.view(), &self.b.view())
+    }
+}
+
+impl<F> crate::iterative::LinearOperator<F> for KroneckerOperator<F>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+{
+    fn apply(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        self.matvec(x)
+    }
+
+    fn nrows(&self) -> usize {
+        self.shape().0
+    }
+
+    fn ncols(&self) -> usize {
+        self.shape().1
+    }
+}
+
+/// Solve the Kronecker-structured system `(A ⊗ B) x = c`
+///
+/// Uses `(A ⊗ B)^-1 = A^-1 ⊗ B^-1`: with `c = vec(C)` the solution is
+/// `vec(A^-1 C B^-T)`, obtained from two dense solves of orders n and m.
+/// The cost is O(n³ + m³ + nm(n + m)) instead of O(n³m³).
+///
+/// # Arguments
+///
+/// * `factors` - Square factors `(A, B)` of orders n and m
+/// * `c` - Right-hand side of length n·m
+///
+/// # Returns
+///
+/// * Solution vector of length n·m
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::kronecker::{kron, solve_kron};
+///
+/// let a = array![[2.0_f64, 1.0], [0.0, 3.0]];
+/// let b = array![[1.0_f64, 0.5], [-1.0, 2.0]];
+/// let c = array![1.0, 2.0, 3.0, 4.0];
+/// let x = solve_kron((&a.view(), &b.view()), &c.view()).unwrap();
+/// let back = kron(&a.view(), &b.view()).unwrap().dot(&x);
+/// for (p, q) in back.iter().zip(c.iter()) {
+///     assert!((p - q).abs() < 1e-12);
+/// }
+/// ```
+pub fn solve_kron<F>(
+    factors: (&ArrayView2<F>, &ArrayView2<F>),
+    c: &ArrayView1<F>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+{
+    let (a, b) = factors;
+    let n = check_square(a, "A")?;
+    let m = check_square(b, "B")?;
+    if c.len() != n * m {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side length ({}) must equal n*m ({}*{}={})",
+            c.len(),
+            n,
+            m,
+            n * m
+        )));
+    }
+
+    let c_mat = unvec(c, n, m)?;
+    // Y = A^-1 C, then X^T = B^-1 Y^T
+    let y = solve_multiple(a, &c_mat.view(), None)?;
+    let x_t = solve_multiple(b, &y.t(), None)?;
+    Ok(vec(x_t.reversed_axes()))
+}
+
+/// Solve the Kronecker-sum system `(A ⊗ I_m + I_n ⊗ B) x = c`
+///
+/// This is the Sylvester equation `A X + X B^T = C` in disguise (and the
+/// Lyapunov equation `A X + X A^T = C` for `B = A`). It is solved by the
+/// Bartels–Stewart approach on complex Schur forms `A = Q T Q^H` and
+/// `B = Z S Z^H`, which reduces the problem to m triangular solves of order
+/// n. The cost is O(n³ + m³ + nm(n + m)), without forming the
+/// `n·m × n·m` matrix.
+///
+/// # Arguments
+///
+/// * `a` - Square matrix A of order n
+/// * `b` - Square matrix B of order m
+/// * `c` - Right-hand side of length n·m
+///
+/// # Returns
+///
+/// * Solution vector of length n·m
+///
+/// # Errors
+///
+/// * `SingularMatrixError` if an eigenvalue of A and one of B sum to zero
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use scirs2_linalg::kronecker::solve_kron_sum;
+///
+/// // Lyapunov equation A X + X A^T = C
+/// let a = array![[-2.0_f64, 1.0], [0.0, -3.0]];
+/// let c = array![1.0, 0.0, 0.0, 1.0];
+/// let x = solve_kron_sum(&a.view(), &a.view(), &c.view()).unwrap();
+/// let x = Array2::from_shape_vec((2, 2), x.to_vec()).unwrap();
+/// let residual = a.dot(&x) + x.dot(&a.t());
+/// assert!((residual[[0, 0]] - 1.0).abs() < 1e-12);
+/// assert!(residual[[0, 1]].abs() < 1e-12);
+/// ```
+pub fn solve_kron_sum<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView2<F>,
+    c: &ArrayView1<F>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign + Sum + ScalarOperand + 'static,
+{
+    let n = check_square(a, "A")?;
+    let m = check_square(b, "B")?;
+    if c.len() != n * m {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side length ({}) must equal n*m ({}*{}={})",
+            c.len(),
+            n,
+            m,
+            n * m
+        )));
+    }
+    if n == 0 || m == 0 {
+        return Ok(Array1::zeros(0));
+    }
+
+    let to_complex = |x: &ArrayView2<F>| x.mapv(|v| Complex::new(v, F::zero()));
+    let (t, q) = complex_schur(&to_complex(a).view())?;
+    let (s, z) = complex_schur(&to_complex(b).view())?;
+
+    // With Y = Q^H X conj(Z): T Y + Y S^T = Q^H C conj(Z) =: D
+    let c_mat = to_complex(&unvec(c, n, m)?.view());
+    let q_h = q.t().mapv(|v| v.conj());
+    let z_conj = z.mapv(|v| v.conj());
+    let d = q_h.dot(&c_mat).dot(&z_conj);
+
+    let scale = t
+        .diag()
+        .iter()
+        .chain(s.diag().iter())
+        .fold(F::zero(), |acc, v| acc.max(v.norm()));
+    let tolerance = F::epsilon() * F::from(n + m).unwrap() * scale.max(F::min_positive_value());
+
+    // S^T is lower triangular, so column j of Y depends on columns k > j
+    let mut y = Array2::<Complex<F>>::zeros((n, m));
+    for j in (0..m).rev() {
+        let mut rhs = d.column(j).to_owned();
+        for k in j + 1..m {
+            let s_jk = s[[j, k]];
+            if !s_jk.is_zero() {
+                for i in 0..n {
+                    rhs[i] -= s_jk * y[[i, k]];
+                }
+            }
+        }
+        // Back substitution with T + s_jj I
+        let shift = s[[j, j]];
+        for i in (0..n).rev() {
+            let mut sum = rhs[i];
+            for k in i + 1..n {
+                sum -= t[[i, k]] * y[[k, j]];
+            }
+            let diag = t[[i, i]] + shift;
+            if diag.norm() <= tolerance {
+                return Err(LinalgError::SingularMatrixError(
+                    "Kronecker sum is singular: an eigenvalue of A and one of B sum to zero"
+                        .to_string(),
+                ));
+            }
+            y[[i, j]] = sum / diag;
+        }
+    }
+
+    let x = q.dot(&y).dot(&z.t());
+    Ok(vec(x.mapv(|v| v.re)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kronecker::kron;
+    use approx::assert_relative_eq;
+
+    fn test_matrix(rows: usize, cols: usize, seed: usize) -> Array2<f64> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| {
+            ((i * 7 + j * 3 + seed * 5) % 11) as f64 / 5.0 - 1.0
+        })
+    }
+
+    #[test]
+    fn test_kronecker_operator_products() {
+        let a = test_matrix(3, 4, 1);
+        let b = test_matrix(5, 2, 2);
+        let op = KroneckerOperator::new(&a.view(), &b.view());
+        let dense = op.to_dense().unwrap();
+        assert_eq!(op.shape(), dense.dim());
+
+        let x = Array1::from_shape_fn(8, |i| (i as f64 * 0.7).sin());
+        let y = op.matvec(&x.view()).unwrap();
+        for (p, q) in y.iter().zip(dense.dot(&x).iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-12);
+        }
+
+        let w = Array1::from_shape_fn(15, |i| (i as f64 * 0.3).cos());
+        let z = op.rmatvec(&w.view()).unwrap();
+        for (p, q) in z.iter().zip(dense.t().dot(&w).iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-12);
+        }
+
+        let xs = Array2::from_shape_fn((8, 3), |(i, j)| (i + j) as f64);
+        let ys = op.matmat(&xs.view()).unwrap();
+        for (p, q) in ys.iter().zip(dense.dot(&xs).iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-12);
+        }
+
+        assert!(op.matvec(&w.view()).is_err());
+    }
+
+    #[test]
+    fn test_kronecker_operator_with_krylov() {
+        // A ⊗ B is SPD when both factors are
+        let a = Array2::from_shape_fn((4, 4), |(i, j)| if i == j { 4.0 } else { 0.5 });
+        let b = Array2::from_shape_fn((3, 3), |(i, j)| if i == j { 3.0 } else { -0.25 });
+        let op = KroneckerOperator::new(&a.view(), &b.view());
+        let c = Array1::from_shape_fn(12, |i| i as f64 - 5.0);
+        let result = crate::iterative::cg(
+            &op,
+            &c.view(),
+            None,
+            None,
+            &crate::iterative::KrylovOptions::default(),
+        )
+        .unwrap();
+        assert!(result.converged);
+        let direct = op.solve(&c.view()).unwrap();
+        for (p, q) in result.solution.iter().zip(direct.iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_solve_kron() {
+        let a = test_matrix(4, 4, 3) + Array2::<f64>::eye(4) * 3.0;
+        let b = test_matrix(3, 3, 4) + Array2::<f64>::eye(3) * 2.0;
+        let c = Array1::from_shape_fn(12, |i| (i as f64).sqrt());
+        let x = solve_kron((&a.view(), &b.view()), &c.view()).unwrap();
+        let back = kron(&a.view(), &b.view()).unwrap().dot(&x);
+        for (p, q) in back.iter().zip(c.iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-10);
+        }
+
+        let rect = test_matrix(2, 3, 0);
+        assert!(solve_kron((&rect.view(), &b.view()), &c.view()).is_err());
+        assert!(solve_kron((&a.view(), &b.view()), &c.slice(ndarray::s![..5])).is_err());
+    }
+
+    #[test]
+    fn test_solve_kron_sum() {
+        // Nonsymmetric factors with complex eigenvalues
+        let a = test_matrix(5, 5, 5);
+        let b = test_matrix(4, 4, 6) + Array2::<f64>::eye(4) * 4.0;
+        let c = Array1::from_shape_fn(20, |i| (i as f64 * 0.4).sin());
+        let x = solve_kron_sum(&a.view(), &b.view(), &c.view()).unwrap();
+
+        let sum = kron(&a.view(), &Array2::eye(4).view()).unwrap()
+            + kron(&Array2::eye(5).view(), &b.view()).unwrap();
+        let back = sum.dot(&x);
+        for (p, q) in back.iter().zip(c.iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-9);
+        }
+
+        // A and -A share eigenvalues of opposite sign
+        let a = ndarray::array![[1.0, 2.0], [0.0, 3.0]];
+        let neg = a.mapv(|v: f64| -v);
+        let c = Array1::ones(4);
+        assert!(matches!(
+            solve_kron_sum(&a.view(), &neg.view(), &c.view()),
+            Err(LinalgError::SingularMatrixError(_))
+        ));
+    }
+}
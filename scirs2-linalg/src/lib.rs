@@ -90,6 +90,7 @@ pub use error::{LinalgError, LinalgResult};
 
 // Basic modules
 pub mod attention;
+pub mod banded;
 mod basic;
 pub mod batch;
 pub mod broadcast;
@@ -100,16 +101,19 @@ pub mod decomposition_advanced;
 // Main eigen module
 pub mod eigen;
 pub use self::eigen::{
-    eig, eig_gen, eigh, eigh_gen, eigvals, eigvals_gen, eigvalsh, eigvalsh_gen, power_iteration,
-    ultra_precision_eig,
+    eig, eig_gen, eigh, eigh_gen, eigsh, eigvals, eigvals_gen, eigvalsh, eigvalsh_gen, lobpcg,
+    power_iteration, ultra_precision_eig,
 };
 
 // Specialized eigen solvers in separate module
 pub mod eigen_specialized;
+mod equilibration;
 pub mod extended_precision;
+mod factorization;
 pub mod generic;
 pub mod gradient;
 pub mod hierarchical;
+pub mod iterative;
 mod iterative_solvers;
 pub mod kronecker;
 pub mod large_scale;
@@ -123,6 +127,7 @@ pub mod matrixfree;
 pub mod mixed_precision;
 mod norm;
 pub mod optim;
+pub mod out_of_core;
 pub mod parallel;
 pub mod perf_opt;
 pub mod preconditioners;
@@ -143,6 +148,7 @@ mod diagnostics;
 pub mod fft;
 pub mod quantization;
 pub mod scalable;
+pub mod schur;
 pub mod simd_ops;
 mod solve;
 pub mod solvers;
@@ -154,6 +160,7 @@ pub mod structured;
 #[cfg(feature = "tensor_contraction")]
 pub mod tensor_contraction;
 pub mod tensor_train;
+pub mod tridiagonal;
 mod validation;
 // Automatic differentiation support
 #[cfg(feature = "autograd")]
@@ -185,6 +192,9 @@ pub mod accelerated {
 }
 
 // Re-exports for user convenience
+pub use self::banded::{
+    cholesky_banded, eig_banded, eigvals_banded, solve_banded, solveh_banded, Banded, BandedLu,
+};
 pub use self::basic::{det, inv, matrix_power, trace as basic_trace};
 pub use self::eigen_specialized::{
     banded_eigen, banded_eigh, banded_eigvalsh, circulant_eigenvalues, largest_k_eigh,
@@ -199,6 +209,10 @@ pub use self::complex::enhanced_ops::{
 pub use self::complex::{complex_inverse, complex_matmul, hermitian_transpose};
 // Main decomposition functions with workers parameter
 pub use self::decomposition::{cholesky, lu, qr, schur, svd};
+pub use self::factorization::{CholeskyFactorization, LuFactorization, QrFactorization};
+pub use self::schur::{
+    complex_hessenberg, hessenberg, ordschur, ordschur_real, real_schur, real_schur_eigenvalues,
+};
 // Backward compatibility versions (deprecated)
 pub use self::decomposition::{cholesky_default, lu_default, qr_default, svd_default};
 // Advanced decomposition functions
@@ -222,7 +236,11 @@ pub use self::matrix_factorization::{
     cur_decomposition, interpolative_decomposition, nmf, rank_revealing_qr, utv_decomposition,
 };
 pub use self::matrix_functions::{
-    acosm, asinm, atanm, coshm, cosm, expm, logm, signm, sinhm, sinm, sqrtm, tanhm, tanm,
+    acosm, asinm, atanm, coshm, cosm, expm, expm_complex, expm_with_options, expm_with_tolerance,
+    funm, funm_complex, logm, logm_complex, logm_with_options, matrix_sign, matrix_sign_newton,
+    matrix_sign_schur, signm, signm_with_options, sinhm, sinm, sqrtm, sqrtm_complex,
+    sqrtm_with_method, sqrtm_with_options, tanhm, tanm, MatrixFunctionInfo, MatrixFunctionMethod,
+    MatrixFunctionOptions, MatrixSignOptions, SignMethod, SignScaling, SqrtmMethod,
 };
 pub use self::matrixfree::{
     block_diagonal_operator, conjugate_gradient as matrix_free_conjugate_gradient,
@@ -231,8 +249,13 @@ pub use self::matrixfree::{
     LinearOperator, MatrixFreeOp,
 };
 pub use self::norm::*;
+pub use self::out_of_core::{OutOfCoreConfig, OutOfCoreMatrix};
 // Main solve functions with workers parameter
-pub use self::solve::{lstsq, solve, solve_multiple, solve_triangular, LstsqResult};
+pub use self::equilibration::{equilibrate, Equilibration, EquilibrationMode};
+pub use self::solve::{
+    lstsq, lstsq_equilibrated, lstsq_with_driver, pinv, solve, solve_equilibrated, solve_multiple,
+    solve_triangular, LstsqDriver, LstsqResult,
+};
 // Backward compatibility versions (deprecated)
 pub use self::solve::{lstsq_default, solve_default, solve_multiple_default};
 // Iterative solvers
@@ -249,6 +272,11 @@ pub use self::structured::{
 };
 #[cfg(feature = "tensor_contraction")]
 pub use self::tensor_contraction::{batch_matmul, contract, einsum, hosvd};
+pub use self::tridiagonal::{
+    eigh_tridiagonal, eigh_tridiagonal_select, eigvalsh_tridiagonal, eigvalsh_tridiagonal_select,
+    solve_cyclic_tridiagonal, solve_pentadiagonal, solve_tridiagonal, solve_tridiagonal_multiple,
+    TridiagonalSelect,
+};
 
 // Prelude module for convenient imports
 pub mod prelude {
@@ -269,6 +297,7 @@ pub mod prelude {
     pub use super::batch::attention::{
         batch_flash_attention, batch_multi_head_attention, batch_multi_query_attention,
     };
+    pub use super::batch::{cholesky_batched, solve_batched, svd_batched};
     pub use super::broadcast::{
         broadcast_matmul, broadcast_matmul_3d, broadcast_matvec, BroadcastExt,
     };
@@ -318,7 +347,8 @@ pub mod prelude {
     };
     pub use super::kronecker::{
         advanced_kfac_step, kfac_factorization, kfac_update, kron, kron_factorize, kron_matmul,
-        kron_matvec, BlockDiagonalFisher, BlockFisherMemoryInfo, KFACOptimizer,
+        kron_matvec, solve_kron, solve_kron_sum, BlockDiagonalFisher, BlockFisherMemoryInfo,
+        KFACOptimizer, KroneckerOperator,
     };
     pub use super::large_scale::{
         block_krylov_solve, ca_gmres, incremental_svd, randomized_block_lanczos,
@@ -346,8 +376,12 @@ pub mod prelude {
         interpolative_decomposition, nmf, rank_revealing_qr, utv_decomposition,
     };
     pub use super::matrix_functions::{
-        acosm, asinm, atanm, coshm, cosm, expm, logm, matrix_power, signm, sinhm, sinm, sqrtm,
-        tanhm, tanm,
+        acosm, asinm, atanm, coshm, cosm, expm, expm_complex, expm_with_options,
+        expm_with_tolerance, funm, funm_complex, logm, logm_complex, logm_with_options,
+        matrix_power, matrix_sign, matrix_sign_newton, matrix_sign_schur, signm,
+        signm_with_options, sinhm, sinm, sqrtm, sqrtm_complex, sqrtm_with_method,
+        sqrtm_with_options, tanhm, tanm, MatrixFunctionInfo, MatrixFunctionMethod,
+        MatrixFunctionOptions, MatrixSignOptions, SignMethod, SignScaling, SqrtmMethod,
     };
     pub use super::matrixfree::{
         block_diagonal_operator, conjugate_gradient as matrix_free_conjugate_gradient,
@@ -409,14 +443,16 @@ pub mod prelude {
     //     orthogonal as enhanced_orthogonal, unitary, hilbert as enhanced_hilbert,
     //     toeplitz as enhanced_toeplitz, vandermonde as enhanced_vandermonde
     // };
+    pub use super::equilibration::{equilibrate, EquilibrationMode};
     pub use super::fft::{
         apply_window, dct_1d, dst_1d, fft_1d, fft_2d, fft_3d, fft_convolve, fft_frequencies,
         idct_1d, irfft_1d, periodogram_psd, rfft_1d, welch_psd, Complex32, Complex64, FFTAlgorithm,
         FFTPlan, WindowFunction,
     };
     pub use super::generic::{
-        gdet, geig, gemm, gemv, ginv, gnorm, gqr, gsolve, gsvd, GenericEigen, GenericQR,
-        GenericSVD, LinalgScalar, PrecisionSelector,
+        gdet, geig, gemm, gemv, gexpm, ginv, glogm, glu, gnorm, gqr, gsolve, gsqrtm, gsvd,
+        DecompositionScalar, GenericEigen, GenericLU, GenericQR, GenericSVD, LinalgScalar,
+        PrecisionSelector,
     };
     pub use super::scalable::{
         adaptive_decomposition, blocked_matmul as scalable_blocked_matmul, classify_aspect_ratio,
@@ -454,7 +490,10 @@ pub mod prelude {
         simd_vector_norm_f64,
         GemmBlockSizes,
     };
-    pub use super::solve::{lstsq, solve, solve_multiple, solve_triangular};
+    pub use super::solve::{
+        lstsq, lstsq_equilibrated, lstsq_with_driver, pinv, solve, solve_equilibrated,
+        solve_multiple, solve_triangular, LstsqDriver,
+    };
     pub use super::sparse_dense::{
         dense_sparse_matmul, dense_sparse_matvec, sparse_dense_add, sparse_dense_elementwise_mul,
         sparse_dense_matmul, sparse_dense_matvec, sparse_dense_sub, sparse_from_ndarray,
@@ -467,8 +506,8 @@ pub mod prelude {
     };
     pub use super::stats::{correlation_matrix, covariance_matrix};
     pub use super::structured::{
-        solve_circulant, solve_toeplitz, structured_to_operator, CirculantMatrix, HankelMatrix,
-        StructuredMatrix, ToeplitzMatrix,
+        levinson_durbin, solve_circulant, solve_toeplitz, solve_toeplitz_pcg,
+        structured_to_operator, CirculantMatrix, HankelMatrix, StructuredMatrix, ToeplitzMatrix,
     };
     #[cfg(feature = "tensor_contraction")]
     pub use super::tensor_contraction::{batch_matmul, contract, einsum, hosvd};
@@ -525,6 +564,7 @@ pub mod prelude {
             // Utilities
             block_diag,
             cholesky,
+            cholesky_banded,
             // Linear system solvers
             compat_solve as solve,
             cond,
@@ -559,6 +599,7 @@ pub mod prelude {
             sinm,
             solve_banded,
             solve_triangular,
+            solveh_banded,
             sqrtm,
             svd,
             tanm,
@@ -1,11 +1,13 @@
 //! Matrix functions such as matrix exponential, logarithm, and square root
 
-use ndarray::{Array2, ArrayView2};
-use num_traits::{Float, NumAssign, One};
+use ndarray::{s, Array2, ArrayView2, Zip};
+use num_complex::Complex;
+use num_traits::{Float, NumAssign, One, Zero};
 use std::iter::Sum;
 
 use crate::error::{LinalgError, LinalgResult};
 use crate::norm::matrix_norm;
+use crate::schur::{complex_schur, swap_schur_adjacent, unitary_similarity};
 use crate::solve::solve_multiple;
 use crate::validation::validate_decomposition;
 
@@ -15,7 +17,9 @@ use crate::validation::validate_decomposition;
 /// exp(A) = I + A + A²/2! + A³/3! + ...
 ///
 /// This function uses the Padé approximation method with scaling and squaring,
-/// which is numerically stable and efficient for most matrices.
+/// which is numerically stable and efficient for most matrices. The degree
+/// and scaling are chosen for full working precision; use
+/// [`expm_with_options`] to trade accuracy for speed.
 ///
 /// # Arguments
 ///
@@ -82,168 +86,25 @@ where
         return Ok(result);
     }
 
-    // Choose a suitable scaling factor and Padé order
-    let norm_a = matrix_norm(a, "1", None)?;
-    let scaling_f = norm_a.log2().ceil().max(F::zero());
-    let scaling = scaling_f.to_i32().unwrap_or(0);
-    let s = F::from(2.0_f64.powi(-scaling)).unwrap_or(F::one());
-
-    // Scale the matrix
-    let mut a_scaled = Array2::zeros((n, n));
-    for i in 0..n {
-        for j in 0..n {
-            a_scaled[[i, j]] = a[[i, j]] * s;
-        }
-    }
-
-    // Compute Padé approximation (here using order 6)
-    let c = [
-        F::from(1.0).unwrap(),
-        F::from(1.0 / 2.0).unwrap(),
-        F::from(1.0 / 6.0).unwrap(),
-        F::from(1.0 / 24.0).unwrap(),
-        F::from(1.0 / 120.0).unwrap(),
-        F::from(1.0 / 720.0).unwrap(),
-    ];
-
-    // Compute powers of A
-    let mut a2 = Array2::zeros((n, n));
-    for i in 0..n {
-        for j in 0..n {
-            for k in 0..n {
-                a2[[i, j]] += a_scaled[[i, k]] * a_scaled[[k, j]];
-            }
-        }
-    }
-
-    let mut a4 = Array2::zeros((n, n));
-    for i in 0..n {
-        for j in 0..n {
-            for k in 0..n {
-                a4[[i, j]] += a2[[i, k]] * a2[[k, j]];
-            }
-        }
-    }
-
-    // Compute the numerator of the Padé approximant: N = I + c_1*A + c_2*A^2 + ...
-    let mut n_pade = Array2::zeros((n, n));
-    for i in 0..n {
-        n_pade[[i, i]] = c[0]; // Add identity matrix * c[0]
-    }
-
-    // Add c[1] * A
-    for i in 0..n {
-        for j in 0..n {
-            n_pade[[i, j]] += c[1] * a_scaled[[i, j]];
-        }
-    }
-
-    // Add c[2] * A^2
-    for i in 0..n {
-        for j in 0..n {
-            n_pade[[i, j]] += c[2] * a2[[i, j]];
-        }
-    }
-
-    // Add c[3] * A^3
-    let mut a3 = Array2::zeros((n, n));
-    for i in 0..n {
-        for j in 0..n {
-            for k in 0..n {
-                a3[[i, j]] += a_scaled[[i, k]] * a2[[k, j]];
-            }
-        }
-    }
-
-    for i in 0..n {
-        for j in 0..n {
-            n_pade[[i, j]] += c[3] * a3[[i, j]];
-        }
-    }
-
-    // Add c[4] * A^4
-    for i in 0..n {
-        for j in 0..n {
-            n_pade[[i, j]] += c[4] * a4[[i, j]];
-        }
-    }
-
-    // Add c[5] * A^5
-    let mut a5 = Array2::zeros((n, n));
-    for i in 0..n {
-        for j in 0..n {
-            for k in 0..n {
-                a5[[i, j]] += a_scaled[[i, k]] * a4[[k, j]];
-            }
-        }
-    }
-
-    for i in 0..n {
-        for j in 0..n {
-            n_pade[[i, j]] += c[5] * a5[[i, j]];
-        }
-    }
-
-    // Compute the denominator of the Padé approximant: D = I - c_1*A + c_2*A^2 - ...
-    let mut d_pade = Array2::zeros((n, n));
-    for i in 0..n {
-        d_pade[[i, i]] = c[0]; // Add identity matrix * c[0]
-    }
-
-    // Subtract c[1] * A
-    for i in 0..n {
-        for j in 0..n {
-            d_pade[[i, j]] -= c[1] * a_scaled[[i, j]];
-        }
-    }
-
-    // Add c[2] * A^2
-    for i in 0..n {
-        for j in 0..n {
-            d_pade[[i, j]] += c[2] * a2[[i, j]];
-        }
-    }
-
-    // Subtract c[3] * A^3
-    for i in 0..n {
-        for j in 0..n {
-            d_pade[[i, j]] -= c[3] * a3[[i, j]];
-        }
-    }
-
-    // Add c[4] * A^4
-    for i in 0..n {
-        for j in 0..n {
-            d_pade[[i, j]] += c[4] * a4[[i, j]];
-        }
-    }
-
-    // Subtract c[5] * A^5
-    for i in 0..n {
-        for j in 0..n {
-            d_pade[[i, j]] -= c[5] * a5[[i, j]];
-        }
-    }
-
-    // Solve the system D*X = N for X
-    let result = solve_multiple(&d_pade.view(), &n_pade.view(), None)?;
-
-    // Undo the scaling by squaring the result s times
-    let mut exp_a = result;
-
-    for _ in 0..scaling as usize {
-        let mut temp = Array2::zeros((n, n));
-        for i in 0..n {
-            for j in 0..n {
-                for k in 0..n {
-                    temp[[i, j]] += exp_a[[i, k]] * exp_a[[k, j]];
-                }
-            }
-        }
-        exp_a = temp;
-    }
+    let options = MatrixFunctionOptions {
+        tol: F::epsilon(),
+        ..Default::default()
+    };
+    expm_with_options(a, &options).map(|(exp_a, _)| exp_a)
+}
 
-    Ok(exp_a)
+/// `max(||A^p||_1^(1/p), ||A^(p+1)||_1^(1/(p+1)))` from 1-norm estimates
+///
+/// Bounds power series in `A` whose terms start at degree `p(p-1)` or above
+/// (Al-Mohy & Higham, 2009) without forming any power of `A`.
+fn power_norm_bound<F>(a: &ArrayView2<F>, p: usize) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    let root = |q: usize| -> LinalgResult<F> {
+        Ok(crate::norm::onenormest_power(a, q, None)?.powf(F::one() / F::from(q).unwrap()))
+    };
+    Ok(root(p)?.max(root(p + 1)?))
 }
 
 /// Compute the matrix logarithm.
@@ -403,17 +264,19 @@ where
         while scaling_k < 10 {
             // Limit iterations to avoid infinite loops
             let mut max_scaled_diff = F::zero();
+            let mut x_scaled = a_scaled.clone();
             for i in 0..n {
+                x_scaled[[i, i]] -= F::one();
                 for j in 0..n {
-                    let expected = if i == j { F::one() } else { F::zero() };
-                    let diff = (a_scaled[[i, j]] - expected).abs();
-                    if diff > max_scaled_diff {
-                        max_scaled_diff = diff;
-                    }
+                    max_scaled_diff = max_scaled_diff.max(x_scaled[[i, j]].abs());
                 }
             }
 
-            if max_scaled_diff <= F::from(0.2).unwrap() {
+            // The truncated series below needs the powers of X = A - I to
+            // decay, which the entries alone do not guarantee
+            if max_scaled_diff <= F::from(0.2).unwrap()
+                && power_norm_bound(&x_scaled.view(), 3)? <= F::from(0.2).unwrap()
+            {
                 break;
             }
 
@@ -597,11 +460,34 @@ where
     Ok(result)
 }
 
-/// Compute the matrix square root using the Denman-Beavers iteration.
+/// Algorithm used to compute the matrix square root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqrtmMethod {
+    /// Choose a method from the normality and conditioning of the matrix.
+    ///
+    /// Nearly normal, well-conditioned matrices use the scaled Denman–Beavers
+    /// iteration; everything else (and any case where the iteration fails)
+    /// uses the blocked Schur method.
+    #[default]
+    Auto,
+    /// Blocked Schur method.
+    ///
+    /// Reduces the matrix to complex Schur form and computes the square root
+    /// of the triangular factor directly by a block recurrence, so no
+    /// iteration is involved. `max_iter` and `tol` are ignored.
+    Schur,
+    /// Denman–Beavers iteration with determinant scaling.
+    DenmanBeavers,
+    /// Scaled Newton iteration in product form, needing one inversion per step.
+    Newton,
+}
+
+/// Compute the matrix square root.
 ///
-/// The matrix square root X of matrix A satisfies X^2 = A.
-/// This function uses the Denman-Beavers iteration, which is suitable
-/// for matrices with no eigenvalues on the negative real axis.
+/// The matrix square root X of matrix A satisfies X^2 = A. The principal
+/// square root is returned, which exists for matrices with no eigenvalues on
+/// the closed negative real axis. The algorithm is selected automatically;
+/// use [`sqrtm_with_method`] to choose it explicitly.
 ///
 /// # Arguments
 ///
@@ -628,6 +514,44 @@ where
 /// assert!((sqrt_a[[1, 1]] - 3.0).abs() < 1e-10);
 /// ```
 pub fn sqrtm<F>(a: &ArrayView2<F>, max_iter: usize, tol: F) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    sqrtm_with_method(a, SqrtmMethod::Auto, max_iter, tol)
+}
+
+/// Compute the matrix square root with a specific algorithm.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `method` - Algorithm to use
+/// * `max_iter` - Maximum number of iterations for the iterative methods
+/// * `tol` - Convergence tolerance for the iterative methods
+///
+/// # Returns
+///
+/// * Principal square root of a
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{sqrtm_with_method, SqrtmMethod};
+///
+/// // Non-normal matrix: the Schur method is exact up to rounding
+/// let a = array![[4.0_f64, 10.0], [0.0, 9.0]];
+/// let x = sqrtm_with_method(&a.view(), SqrtmMethod::Schur, 0, 0.0).unwrap();
+/// assert!((x[[0, 0]] - 2.0).abs() < 1e-12);
+/// assert!((x[[0, 1]] - 2.0).abs() < 1e-12);
+/// assert!((x[[1, 1]] - 3.0).abs() < 1e-12);
+/// ```
+pub fn sqrtm_with_method<F>(
+    a: &ArrayView2<F>,
+    method: SqrtmMethod,
+    max_iter: usize,
+    tol: F,
+) -> LinalgResult<Array2<F>>
 where
     F: Float + NumAssign + Sum + One,
 {
@@ -637,119 +561,341 @@ where
             a.shape()
         )));
     }
+    validate_decomposition(a, "Matrix square root computation", true)?;
 
-    let n = a.nrows();
-
-    // Special case for 1x1 matrix
-    if n == 1 {
-        let val = a[[0, 0]];
-        if val < F::zero() {
-            return Err(LinalgError::InvalidInputError(
-                "Cannot compute real square root of negative number".to_string(),
-            ));
+    match method {
+        SqrtmMethod::Schur => sqrtm_schur(a),
+        SqrtmMethod::DenmanBeavers => sqrtm_denman_beavers(a, max_iter, tol).map(|(x, _)| x),
+        SqrtmMethod::Newton => sqrtm_newton(a, max_iter, tol),
+        SqrtmMethod::Auto => {
+            if prefers_iterative_sqrtm(a) {
+                if let Ok((x, _)) = sqrtm_denman_beavers(a, max_iter, tol) {
+                    return Ok(x);
+                }
+            }
+            sqrtm_schur(a)
         }
-
-        let mut result = Array2::zeros((1, 1));
-        result[[0, 0]] = val.sqrt();
-        return Ok(result);
     }
+}
 
-    // Special case for 2x2 diagonal matrix
-    if n == 2 && a[[0, 1]].abs() < F::epsilon() && a[[1, 0]].abs() < F::epsilon() {
-        let a00 = a[[0, 0]];
-        let a11 = a[[1, 1]];
+/// Decide whether the scaled iterations are a good fit for `a`
+///
+/// The iterations are fast and accurate for nearly normal matrices, but they
+/// invert their iterates and lose accuracy for ill-conditioned or strongly
+/// non-normal input.
+fn prefers_iterative_sqrtm<F>(a: &ArrayView2<F>) -> bool
+where
+    F: Float + NumAssign + Sum + One,
+{
+    let n = a.nrows();
+    let a_owned = a.to_owned();
+    let norm_sq = a.iter().fold(F::zero(), |acc, &x| acc + x * x);
+    if norm_sq == F::zero() {
+        return false;
+    }
+
+    // Relative departure from normality ||A^T A - A A^T||_F / ||A||_F^2
+    let ata = matmul_plain(&a_owned.t().to_owned(), &a_owned);
+    let aat = matmul_plain(&a_owned, &a_owned.t().to_owned());
+    let commutator = ata
+        .iter()
+        .zip(aat.iter())
+        .fold(F::zero(), |acc, (&x, &y)| acc + (x - y) * (x - y))
+        .sqrt();
+    if commutator / norm_sq > F::epsilon().sqrt() {
+        return false;
+    }
+
+    // 1-norm condition number
+    let a_inv = match solve_multiple(a, &Array2::eye(n).view(), None) {
+        Ok(inv) => inv,
+        Err(_) => return false,
+    };
+    let norm1 = |m: &ArrayView2<F>| {
+        (0..n).fold(F::zero(), |acc, j| {
+            acc.max(m.column(j).iter().fold(F::zero(), |s, &x| s + x.abs()))
+        })
+    };
+    let cond = norm1(a) * norm1(&a_inv.view());
+    cond.is_finite() && cond <= F::one() / F::epsilon().sqrt()
+}
 
-        if a00 < F::zero() || a11 < F::zero() {
-            return Err(LinalgError::InvalidInputError(
-                "Cannot compute real square root of matrix with negative eigenvalues".to_string(),
-            ));
+/// Plain matrix product for element types without a `'static` bound
+fn matmul_plain<F: Float + NumAssign>(a: &Array2<F>, b: &Array2<F>) -> Array2<F> {
+    let (n, k) = a.dim();
+    let m = b.ncols();
+    let mut c = Array2::zeros((n, m));
+    for i in 0..n {
+        for l in 0..k {
+            let a_il = a[[i, l]];
+            if a_il == F::zero() {
+                continue;
+            }
+            for j in 0..m {
+                c[[i, j]] += a_il * b[[l, j]];
+            }
         }
+    }
+    c
+}
 
-        let mut result = Array2::zeros((2, 2));
-        result[[0, 0]] = a00.sqrt();
-        result[[1, 1]] = a11.sqrt();
-        return Ok(result);
+/// Determinant scaling factor `|det(M)|^(-1/(2n))` (1 if it is not representable)
+fn determinant_scaling<F>(m: &Array2<F>, det_factor: F) -> F
+where
+    F: Float + NumAssign + Sum,
+{
+    let n = F::from(m.nrows()).unwrap();
+    let scale = crate::basic::det(&m.view(), None)
+        .map(|d| (d * det_factor).abs().powf(-F::one() / (n + n)))
+        .unwrap_or(F::one());
+    if scale.is_finite() && scale > F::zero() {
+        scale
+    } else {
+        F::one()
     }
+}
+
+fn invert_for_sqrtm<F>(m: &Array2<F>, method: &str) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    let n = m.nrows();
+    solve_multiple(&m.view(), &Array2::eye(n).view(), None).map_err(|_| {
+        LinalgError::singular_matrix_with_suggestions(
+            &format!("Matrix square root ({})", method),
+            (n, n),
+            None,
+        )
+    })
+}
 
-    // Initialize Y and Z for Denman-Beavers iteration
+/// Scaled Denman–Beavers iteration
+///
+/// `Y_{k+1} = (mu Y_k + Z_k^{-1} / mu) / 2`, `Z_{k+1} = (mu Z_k + Y_k^{-1} / mu) / 2`
+/// with `mu = |det(Y_k) det(Z_k)|^(-1/(2n))`; `Y_k -> A^{1/2}`.
+///
+/// Returns the square root and the number of iterations taken.
+fn sqrtm_denman_beavers<F>(
+    a: &ArrayView2<F>,
+    max_iter: usize,
+    tol: F,
+) -> LinalgResult<(Array2<F>, usize)>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    const METHOD: &str = "Denman-Beavers iteration";
+    let n = a.nrows();
+    let half = F::from(0.5).unwrap();
     let mut y = a.to_owned();
     let mut z = Array2::eye(n);
-
-    // Iteration
+    let mut scaling = true;
     let mut final_error = None;
 
-    for _iter in 0..max_iter {
-        // Compute Y_next = 0.5 * (Y + Z^-1)
-        // and Z_next = 0.5 * (Z + Y^-1)
-
-        // First, compute Z^-1 and Y^-1
-        let z_inv = match solve_multiple(&z.view(), &Array2::eye(n).view(), None) {
-            Ok(inv) => inv,
-            Err(_) => {
-                return Err(LinalgError::singular_matrix_with_suggestions(
-                    "Matrix square root (Denman-Beavers iteration)",
-                    (n, n),
-                    None,
-                ))
-            }
-        };
+    for iteration in 1..=max_iter {
+        let y_inv = invert_for_sqrtm(&y, METHOD)?;
+        let z_inv = invert_for_sqrtm(&z, METHOD)?;
 
-        let y_inv = match solve_multiple(&y.view(), &Array2::eye(n).view(), None) {
-            Ok(inv) => inv,
-            Err(_) => {
-                return Err(LinalgError::singular_matrix_with_suggestions(
-                    "Matrix square root (Denman-Beavers iteration)",
-                    (n, n),
-                    None,
-                ))
-            }
+        // Scaling only pays off far from convergence
+        let mu = if scaling {
+            let det_z = crate::basic::det(&z.view(), None).unwrap_or(F::one());
+            determinant_scaling(&y, det_z)
+        } else {
+            F::one()
         };
 
-        // Compute next iterations
-        let mut y_next = Array2::zeros((n, n));
-        let mut z_next = Array2::zeros((n, n));
+        let y_next = Zip::from(&y)
+            .and(&z_inv)
+            .map_collect(|&p, &q| half * (mu * p + q / mu));
+        let z_next = Zip::from(&z)
+            .and(&y_inv)
+            .map_collect(|&p, &q| half * (mu * p + q / mu));
 
-        let half = F::from(0.5).unwrap();
+        let diff = (&y_next - &y)
+            .iter()
+            .fold(F::zero(), |acc, &x| acc.max(x.abs()));
+        let scale = y_next.iter().fold(F::one(), |acc, &x| acc.max(x.abs()));
+        let error = diff / scale;
+        final_error = error.to_f64();
 
-        for i in 0..n {
-            for j in 0..n {
-                y_next[[i, j]] = half * (y[[i, j]] + z_inv[[i, j]]);
-                z_next[[i, j]] = half * (z[[i, j]] + y_inv[[i, j]]);
-            }
+        y = y_next;
+        z = z_next;
+        if error < F::from(1e-2).unwrap() {
+            scaling = false;
         }
-
-        // Compute error for convergence check
-        let mut error = F::zero();
-        for i in 0..n {
-            for j in 0..n {
-                let diff = (y_next[[i, j]] - y[[i, j]]).abs();
-                if diff > error {
-                    error = diff;
-                }
-            }
+        if error <= tol {
+            return Ok((y, iteration));
         }
+    }
 
-        final_error = Some(error.to_f64().unwrap_or(1.0));
+    Err(LinalgError::convergence_with_suggestions(
+        "Matrix square root (Denman-Beavers iteration)",
+        max_iter,
+        tol.to_f64().unwrap_or(1e-12),
+        final_error,
+    ))
+}
 
-        // Update Y and Z
-        y = y_next;
-        z = z_next;
+/// Scaled product-form Denman–Beavers (Newton) iteration
+///
+/// `M_{k+1} = (I + (mu^2 M_k + M_k^{-1} / mu^2) / 2) / 2`,
+/// `Y_{k+1} = mu Y_k (I + M_k^{-1} / mu^2) / 2` with `mu = |det(M_k)|^(-1/(2n))`;
+/// `Y_k -> A^{1/2}` and `M_k -> I`.
+fn sqrtm_newton<F>(a: &ArrayView2<F>, max_iter: usize, tol: F) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    const METHOD: &str = "Newton iteration";
+    let n = a.nrows();
+    let half = F::from(0.5).unwrap();
+    let identity = Array2::<F>::eye(n);
+    let mut m = a.to_owned();
+    let mut y = a.to_owned();
+    let mut scaling = true;
+    let mut final_error = None;
 
-        // Check convergence
-        if error < tol {
+    for _ in 0..max_iter {
+        let m_inv = invert_for_sqrtm(&m, METHOD)?;
+        let mu = if scaling {
+            determinant_scaling(&m, F::one())
+        } else {
+            F::one()
+        };
+        let mu2 = mu * mu;
+
+        let factor = Zip::from(&identity)
+            .and(&m_inv)
+            .map_collect(|&e, &q| half * mu * (e + q / mu2));
+        y = matmul_plain(&y, &factor);
+        m = Zip::from(&identity)
+            .and(&m)
+            .and(&m_inv)
+            .map_collect(|&e, &p, &q| half * (e + half * (mu2 * p + q / mu2)));
+
+        let error = (&m - &identity)
+            .iter()
+            .fold(F::zero(), |acc, &x| acc.max(x.abs()));
+        final_error = error.to_f64();
+        if error < F::from(1e-2).unwrap() {
+            scaling = false;
+        }
+        if error <= tol {
             return Ok(y);
         }
     }
 
-    // Failed to converge - return error with suggestions
     Err(LinalgError::convergence_with_suggestions(
-        "Matrix square root (Denman-Beavers iteration)",
+        "Matrix square root (Newton iteration)",
         max_iter,
         tol.to_f64().unwrap_or(1e-12),
         final_error,
     ))
 }
 
+/// Block size of the blocked Schur square root recurrence
+const SQRTM_SCHUR_BLOCK: usize = 64;
+
+/// Blocked Schur method (Deadman, Higham & Ralha)
+fn sqrtm_schur<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    let n = a.nrows();
+    let a_complex = a.mapv(|x| Complex::new(x, F::zero()));
+    let (t, z) = complex_schur(&a_complex.view())?;
+
+    for i in 0..n {
+        let lambda = t[[i, i]];
+        if lambda.im == F::zero() && lambda.re < F::zero() {
+            return Err(LinalgError::InvalidInputError(
+                "Cannot compute real square root of matrix with negative eigenvalues".to_string(),
+            ));
+        }
+    }
+
+    let u = sqrtm_upper_triangular(&t)?;
+    let result = unitary_similarity(&z, &u);
+
+    let scale = result.iter().fold(F::one(), |acc, x| acc.max(x.re.abs()));
+    let imag = result.iter().fold(F::zero(), |acc, x| acc.max(x.im.abs()));
+    if imag > F::epsilon().sqrt() * scale {
+        return Err(LinalgError::InvalidInputError(
+            "Matrix has eigenvalues on the negative real axis and no real principal square root"
+                .to_string(),
+        ));
+    }
+    Ok(result.mapv(|x| x.re))
+}
+
+/// Square root of an upper triangular matrix by the blocked recurrence
+///
+/// Diagonal blocks use the point recurrence
+/// `U_ij = (T_ij - sum_k U_ik U_kj) / (U_ii + U_jj)`; the off-diagonal blocks
+/// solve `U_ii X + X U_jj = T_ij - sum_k U_ik U_kj`.
+fn sqrtm_upper_triangular<F: Float>(t: &Array2<Complex<F>>) -> LinalgResult<Array2<Complex<F>>> {
+    let n = t.nrows();
+    let mut u = Array2::<Complex<F>>::zeros((n, n));
+    let blocks: Vec<_> = (0..n)
+        .step_by(SQRTM_SCHUR_BLOCK)
+        .map(|start| start..(start + SQRTM_SCHUR_BLOCK).min(n))
+        .collect();
+
+    let singular = || {
+        LinalgError::SingularMatrixError(
+            "Matrix square root does not exist: repeated zero eigenvalue in a nontrivial Jordan block"
+                .to_string(),
+        )
+    };
+
+    for block in &blocks {
+        for j in block.clone() {
+            u[[j, j]] = t[[j, j]].sqrt();
+            for i in (block.start..j).rev() {
+                let mut sum = t[[i, j]];
+                for k in (i + 1)..j {
+                    sum = sum - u[[i, k]] * u[[k, j]];
+                }
+                let denom = u[[i, i]] + u[[j, j]];
+                u[[i, j]] = if denom == Complex::zero() {
+                    if sum != Complex::zero() {
+                        return Err(singular());
+                    }
+                    Complex::zero()
+                } else {
+                    sum / denom
+                };
+            }
+        }
+    }
+
+    for j in 0..blocks.len() {
+        let bj = blocks[j].clone();
+        for i in (0..j).rev() {
+            let bi = blocks[i].clone();
+            let mut rhs = t.slice(s![bi.clone(), bj.clone()]).to_owned();
+            for bk in &blocks[(i + 1)..j] {
+                for r in bi.clone() {
+                    for c in bj.clone() {
+                        let mut sum = Complex::zero();
+                        for k in bk.clone() {
+                            sum = sum + u[[r, k]] * u[[k, c]];
+                        }
+                        rhs[[r - bi.start, c - bj.start]] = rhs[[r - bi.start, c - bj.start]] - sum;
+                    }
+                }
+            }
+            let u_jj = u.slice(s![bj.clone(), bj.clone()]).mapv(|x| -x);
+            let x =
+                solve_triangular_sylvester(&u.slice(s![bi.clone(), bi.clone()]), &u_jj.view(), rhs);
+            if x.iter().any(|v| !v.re.is_finite() || !v.im.is_finite()) {
+                return Err(singular());
+            }
+            u.slice_mut(s![bi, bj.clone()]).assign(&x);
+        }
+    }
+
+    Ok(u)
+}
+
 /// Compute the matrix cosine using eigendecomposition.
 ///
 /// The matrix cosine is defined using the matrix exponential:
@@ -1258,6 +1404,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expm_non_normal() {
+        // ||A||_1 is huge but the powers of A grow slowly; exp(A) = e * (I + N)
+        let a = array![[1.0, 1e4, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]];
+        let exp_a = expm(&a.view(), None).unwrap();
+        let e = std::f64::consts::E;
+        let expected = array![[e, e * 1e4, 0.0], [0.0, e, 0.0], [0.0, 0.0, 1.0 / e]];
+        for (p, q) in exp_a.iter().zip(expected.iter()) {
+            assert_relative_eq!(*p, *q, max_relative = 1e-8, epsilon = 1e-10);
+        }
+    }
+
     #[test]
     fn test_sqrtm_diagonal() {
         // For diagonal positive matrix, sqrt(D) = diag(sqrt(d_1), sqrt(d_2), ...)
@@ -1565,7 +1723,8 @@ where
 /// sign(A) = A * (A²)^(-1/2)
 ///
 /// For matrices with no eigenvalues on the imaginary axis, this computes
-/// a matrix with eigenvalues of ±1.
+/// a matrix with eigenvalues of ±1. The determinant-scaled Newton iteration is
+/// used; see [`matrix_sign`] for other algorithms and stopping criteria.
 ///
 /// # Arguments
 ///
@@ -1614,123 +1773,1398 @@ where
         return Ok(result);
     }
 
-    // Newton iteration for matrix sign function
-    // X_{k+1} = (X_k + X_k^{-1}) / 2
-    let mut x = a.to_owned();
-    let max_iter = 100;
-    let tol = F::epsilon() * F::from(100.0).unwrap();
+    matrix_sign_newton(
+        a,
+        SignScaling::Determinant,
+        100,
+        F::epsilon() * F::from(100.0).unwrap(),
+    )
+}
 
-    for _ in 0..max_iter {
-        // Compute X^{-1}
-        let x_inv = solve_multiple(&x.view(), &Array2::eye(n).view(), None)?;
+/// Scaling applied to the Newton iteration for the matrix sign function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignScaling {
+    /// Unscaled iteration `X_{k+1} = (X_k + X_k^{-1}) / 2`
+    None,
+    /// Determinant scaling `mu_k = |det(X_k)|^(-1/n)`
+    #[default]
+    Determinant,
+    /// Spectral scaling `mu_k = sqrt(rho(X_k^{-1}) / rho(X_k))`
+    Spectral,
+}
 
-        // X_{k+1} = (X_k + X_k^{-1}) / 2
-        let x_new = (&x + &x_inv) * F::from(0.5).unwrap();
+/// Algorithm used to compute the matrix sign function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignMethod {
+    /// Scaled Newton iteration (see [`matrix_sign_newton`])
+    #[default]
+    Newton,
+    /// Reordered Schur form and a single Sylvester solve (see [`matrix_sign_schur`])
+    Schur,
+}
+
+/// Options for [`matrix_sign`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatrixSignOptions<F> {
+    /// Algorithm to use
+    pub method: SignMethod,
+    /// Scaling of the Newton iteration (ignored by the Schur method)
+    pub scaling: SignScaling,
+    /// Maximum number of Newton iterations
+    pub max_iter: usize,
+    /// Relative residual `||X^2 - I||_1 / ||X||_1^2` at which the iteration stops
+    pub tol: F,
+}
+
+impl<F: Float> Default for MatrixSignOptions<F> {
+    fn default() -> Self {
+        Self {
+            method: SignMethod::default(),
+            scaling: SignScaling::default(),
+            max_iter: 100,
+            tol: F::epsilon() * F::from(100.0).unwrap(),
+        }
+    }
+}
+
+/// Compute the matrix sign function with configurable algorithm.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix with no eigenvalues on the imaginary axis
+/// * `options` - Algorithm, scaling and stopping criterion
+///
+/// # Returns
+///
+/// * sign(A)
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{matrix_sign, MatrixSignOptions, SignMethod};
+///
+/// let a = array![[2.0_f64, 1.0], [0.0, -3.0]];
+/// let options = MatrixSignOptions {
+///     method: SignMethod::Schur,
+///     ..Default::default()
+/// };
+/// let s = matrix_sign(&a.view(), &options).unwrap();
+/// assert!((s[[0, 0]] - 1.0).abs() < 1e-12);
+/// assert!((s[[0, 1]] - 0.4).abs() < 1e-12);
+/// assert!((s[[1, 1]] + 1.0).abs() < 1e-12);
+/// ```
+pub fn matrix_sign<F>(a: &ArrayView2<F>, options: &MatrixSignOptions<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    match options.method {
+        SignMethod::Newton => matrix_sign_newton(a, options.scaling, options.max_iter, options.tol),
+        SignMethod::Schur => matrix_sign_schur(a),
+    }
+}
+
+/// Compute the matrix sign function by the scaled Newton iteration.
+///
+/// Iterates `X_{k+1} = (mu_k X_k + X_k^{-1} / mu_k) / 2` from `X_0 = A`. Scaling
+/// shortens the initial phase of slow convergence considerably when the
+/// eigenvalues of `A` have very different magnitudes; it is switched off once
+/// the iteration is close to convergence so that quadratic convergence is
+/// retained. The iteration stops when the relative residual
+/// `||X_k^2 - I||_1 / ||X_k||_1^2` drops below `tol`.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix with no eigenvalues on the imaginary axis
+/// * `scaling` - Scaling strategy
+/// * `max_iter` - Maximum number of iterations
+/// * `tol` - Residual tolerance
+///
+/// # Returns
+///
+/// * sign(A)
+pub fn matrix_sign_newton<F>(
+    a: &ArrayView2<F>,
+    scaling: SignScaling,
+    max_iter: usize,
+    tol: F,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    sign_newton(a, scaling, max_iter, tol).map(|(x, _, _)| x)
+}
+
+/// Scaled Newton iteration behind [`matrix_sign_newton`]
+///
+/// Returns sign(A), the number of iterations and the final relative residual.
+fn sign_newton<F>(
+    a: &ArrayView2<F>,
+    scaling: SignScaling,
+    max_iter: usize,
+    tol: F,
+) -> LinalgResult<(Array2<F>, usize, F)>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    validate_decomposition(a, "Matrix sign function computation", true)?;
+
+    let n = a.nrows();
+    let n_f = F::from(n).unwrap();
+    let half = F::from(0.5).unwrap();
+    let identity = Array2::<F>::eye(n);
+    let norm1 = |m: &Array2<F>| {
+        (0..n).fold(F::zero(), |acc, j| {
+            acc.max(m.column(j).iter().fold(F::zero(), |s, &x| s + x.abs()))
+        })
+    };
 
-        // Check convergence
-        let diff = &x_new - &x;
-        let error = matrix_norm(&diff.view(), "fro", None)?;
+    let mut x = a.to_owned();
+    let mut scaling_active = scaling != SignScaling::None;
+    let mut residual = F::infinity();
+
+    for iteration in 1..=max_iter {
+        let x_inv = solve_multiple(&x.view(), &identity.view(), None).map_err(|_| {
+            LinalgError::SingularMatrixError(
+                "Matrix sign function is undefined: an iterate is singular (eigenvalue on the imaginary axis?)"
+                    .to_string(),
+            )
+        })?;
+
+        let mu = if scaling_active {
+            let mu = match scaling {
+                SignScaling::Determinant => crate::basic::det(&x.view(), None)
+                    .map(|d| d.abs().powf(-F::one() / n_f))
+                    .unwrap_or(F::one()),
+                SignScaling::Spectral => {
+                    (estimate_spectral_radius(&x_inv) / estimate_spectral_radius(&x)).sqrt()
+                }
+                SignScaling::None => F::one(),
+            };
+            if mu.is_finite() && mu > F::zero() {
+                mu
+            } else {
+                F::one()
+            }
+        } else {
+            F::one()
+        };
 
-        x = x_new;
+        x = Zip::from(&x)
+            .and(&x_inv)
+            .map_collect(|&p, &q| half * (mu * p + q / mu));
 
-        if error < tol {
-            return Ok(x);
+        let x2 = matmul_plain(&x, &x);
+        let x_norm = norm1(&x);
+        residual = norm1(&(&x2 - &identity)) / (x_norm * x_norm);
+        if residual <= tol {
+            return Ok((x, iteration, residual));
+        }
+        if residual < F::from(1e-2).unwrap() {
+            scaling_active = false;
         }
     }
 
-    Err(LinalgError::ConvergenceError(
-        "Matrix sign function did not converge".to_string(),
+    Err(LinalgError::convergence_with_suggestions(
+        "Matrix sign function (Newton iteration)",
+        max_iter,
+        tol.to_f64().unwrap_or(1e-12),
+        residual.to_f64(),
+    ))
+}
+
+/// Estimate the spectral radius from `||X^(2^k)||^(1/2^k)` (Gelfand's formula)
+fn estimate_spectral_radius<F: Float + NumAssign>(x: &Array2<F>) -> F {
+    let fro = |m: &Array2<F>| m.iter().fold(F::zero(), |acc, &v| acc + v * v).sqrt();
+    let mut power = x.to_owned();
+    let mut log_radius = F::zero();
+    let mut exponent = F::one();
+    for _ in 0..3 {
+        // Renormalize to avoid overflow while squaring
+        let norm = fro(&power);
+        if norm == F::zero() || !norm.is_finite() {
+            return F::zero();
+        }
+        log_radius += norm.ln() / exponent;
+        power.mapv_inplace(|v| v / norm);
+        power = matmul_plain(&power, &power);
+        exponent = exponent + exponent;
+    }
+    let norm = fro(&power);
+    if norm == F::zero() {
+        return F::zero();
+    }
+    (log_radius + norm.ln() / exponent).exp()
+}
+
+/// Compute the matrix sign function from a reordered Schur form.
+///
+/// The complex Schur form is reordered so that eigenvalues in the open right
+/// half plane come first, `T = [T11 T12; 0 T22]`. Then
+/// `sign(T) = [I Y; 0 -I]` where `T11 Y - Y T22 = 2 T12`, which is a
+/// well-conditioned triangular Sylvester equation whenever the eigenvalues
+/// stay away from the imaginary axis. No iteration is involved.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix with no eigenvalues on the imaginary axis
+///
+/// # Returns
+///
+/// * sign(A)
+pub fn matrix_sign_schur<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    validate_decomposition(a, "Matrix sign function computation", true)?;
+
+    let n = a.nrows();
+    let a_complex = a.mapv(|x| Complex::new(x, F::zero()));
+    let (mut t, mut z) = complex_schur(&a_complex.view())?;
+
+    let scale = t.iter().fold(F::zero(), |acc, v| acc.max(v.norm()));
+    let mut negative = Vec::with_capacity(n);
+    for i in 0..n {
+        let re = t[[i, i]].re;
+        if re.abs() <= F::epsilon() * F::from(n).unwrap() * scale {
+            return Err(LinalgError::InvalidInputError(
+                "Matrix sign function is undefined for eigenvalues on the imaginary axis"
+                    .to_string(),
+            ));
+        }
+        negative.push(re < F::zero());
+    }
+
+    // Move the right half plane eigenvalues to the leading block
+    let mut swapped = true;
+    while swapped {
+        swapped = false;
+        for k in 0..n.saturating_sub(1) {
+            if negative[k] && !negative[k + 1] {
+                swap_schur_adjacent(&mut t, &mut z, k);
+                negative.swap(k, k + 1);
+                swapped = true;
+            }
+        }
+    }
+
+    let p = negative.iter().filter(|&&neg| !neg).count();
+    let one = Complex::new(F::one(), F::zero());
+    let mut s_t = Array2::<Complex<F>>::zeros((n, n));
+    for i in 0..n {
+        s_t[[i, i]] = if i < p { one } else { -one };
+    }
+    if p > 0 && p < n {
+        let rhs = t.slice(s![..p, p..]).mapv(|v| v + v);
+        let y = solve_triangular_sylvester(&t.slice(s![..p, ..p]), &t.slice(s![p.., p..]), rhs);
+        s_t.slice_mut(s![..p, p..]).assign(&y);
+    }
+
+    let result = unitary_similarity(&z, &s_t);
+    Ok(result.mapv(|v| v.re))
+}
+
+/// Algorithm selection for the `*_with_options` matrix functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatrixFunctionMethod {
+    /// Let each function choose; see the individual functions.
+    #[default]
+    Auto,
+    /// Direct methods based on the Schur form.
+    Schur,
+    /// Iterative methods: Denman–Beavers for square roots and the scaled
+    /// Newton iteration for the sign function.
+    Iterative,
+}
+
+/// Accuracy and algorithm controls for [`expm_with_options`],
+/// [`logm_with_options`], [`sqrtm_with_options`] and [`signm_with_options`].
+///
+/// A looser `tol` lets the exponential and logarithm use lower degree
+/// approximants and lets the iterations stop earlier, trading accuracy for
+/// speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatrixFunctionOptions<F> {
+    /// Target relative accuracy
+    pub tol: F,
+    /// Maximum number of iterations of the iterative methods; also bounds
+    /// the number of square roots taken by [`logm_with_options`]
+    pub max_iter: usize,
+    /// Algorithm to use
+    pub method: MatrixFunctionMethod,
+}
+
+impl<F: Float> Default for MatrixFunctionOptions<F> {
+    fn default() -> Self {
+        Self {
+            tol: F::epsilon() * F::from(100.0).unwrap(),
+            max_iter: 100,
+            method: MatrixFunctionMethod::default(),
+        }
+    }
+}
+
+/// Diagnostics returned by the `*_with_options` matrix functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatrixFunctionInfo<F> {
+    /// Estimated relative error; see the individual functions for what is
+    /// estimated
+    pub error_estimate: F,
+    /// Iterations of the iterative method (0 for direct methods)
+    pub iterations: usize,
+    /// Scaling power `s`: expm evaluates at `A / 2^s` and squares `s` times,
+    /// logm takes `s` square roots
+    pub scaling_power: usize,
+    /// Degree of the approximant: the Padé degree for expm, the number of
+    /// series terms for logm and 0 otherwise
+    pub degree: usize,
+}
+
+impl<F: Float> MatrixFunctionInfo<F> {
+    fn without_scaling(error_estimate: F, iterations: usize) -> Self {
+        Self {
+            error_estimate,
+            iterations,
+            scaling_power: 0,
+            degree: 0,
+        }
+    }
+}
+
+fn check_tolerance<F: Float>(tol: F, context: &str) -> LinalgResult<()> {
+    if tol > F::zero() && tol.is_finite() {
+        Ok(())
+    } else {
+        Err(LinalgError::InvalidInputError(format!(
+            "{}: tolerance must be positive and finite",
+            context
+        )))
+    }
+}
+
+/// Matrix 1-norm (maximum absolute column sum)
+fn one_norm<F: Float>(m: &Array2<F>) -> F {
+    m.columns().into_iter().fold(F::zero(), |acc, col| {
+        acc.max(col.iter().fold(F::zero(), |s, &x| s + x.abs()))
+    })
+}
+
+/// Padé degrees considered by [`expm_with_options`]
+const EXPM_PADE_DEGREES: [usize; 5] = [3, 5, 7, 9, 13];
+
+/// Coefficients `b_k = (2m - k)! m! / ((2m)! k! (m - k)!)` of the `[m/m]`
+/// Padé approximant to `exp`
+fn expm_pade_coefficients(m: usize) -> Vec<f64> {
+    let mut b = vec![1.0; m + 1];
+    for k in 1..=m {
+        b[k] = b[k - 1] * (m - k + 1) as f64 / (k * (2 * m - k + 1)) as f64;
+    }
+    b
+}
+
+/// Leading coefficient `(m!)^2 / ((2m)! (2m + 1)!)` of the backward error
+/// of the `[m/m]` Padé approximant to `exp`
+fn expm_pade_error_constant(m: usize) -> f64 {
+    (m + 1..=2 * m).fold(1.0, |c, k| c / (k as f64 * k as f64)) / (2 * m + 1) as f64
+}
+
+/// Compute the matrix exponential to a requested accuracy.
+///
+/// Scaling and squaring with a diagonal Padé approximant, where the degree
+/// `m` and the scaling power `s` are chosen together as the cheapest pair
+/// whose truncation error bound `c_m (theta / 2^s)^(2m+1)` stays below
+/// `options.tol`. Here `theta` is the smaller of `||A||_1` and
+/// `max(||A^3||_1^(1/3), ||A^4||_1^(1/4))`, so non-normal matrices are not
+/// over-scaled. `max_iter` and `method` are not used.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `options` - Accuracy controls
+///
+/// # Returns
+///
+/// * exp(A) and diagnostics; `error_estimate` is the truncation error bound
+///   of the approximant
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{expm_with_options, MatrixFunctionOptions};
+///
+/// let a = array![[0.0_f64, 1.0], [-1.0, 0.0]];
+/// let loose = MatrixFunctionOptions { tol: 1e-6, ..Default::default() };
+/// let (e, info) = expm_with_options(&a.view(), &loose).unwrap();
+/// assert!(info.error_estimate <= 1e-6);
+/// assert!((e[[0, 0]] - 1.0_f64.cos()).abs() < 1e-6);
+/// assert!((e[[0, 1]] - 1.0_f64.sin()).abs() < 1e-6);
+/// ```
+pub fn expm_with_options<F>(
+    a: &ArrayView2<F>,
+    options: &MatrixFunctionOptions<F>,
+) -> LinalgResult<(Array2<F>, MatrixFunctionInfo<F>)>
+where
+    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+{
+    validate_decomposition(a, "Matrix exponential computation", true)?;
+    check_tolerance(options.tol, "Matrix exponential computation")?;
+
+    let theta = matrix_norm(a, "1", None)?
+        .min(power_norm_bound(a, 3)?)
+        .to_f64()
+        .unwrap_or(f64::INFINITY);
+    let tol = options.tol.to_f64().unwrap_or(f64::EPSILON);
+    if !theta.is_finite() {
+        return Err(LinalgError::ComputationError(
+            "Matrix exponential computation: matrix norm is not finite".to_string(),
+        ));
+    }
+
+    // Cost in matrix products: m - 1 powers, a solve worth about 4/3
+    // products and s squarings
+    let (m, s, error_bound) = EXPM_PADE_DEGREES
+        .iter()
+        .map(|&m| {
+            let c = expm_pade_error_constant(m);
+            let exponent = (2 * m + 1) as f64;
+            let theta_m = (tol / c).powf(1.0 / exponent);
+            let s = if theta > theta_m {
+                (theta / theta_m).log2().ceil() as usize
+            } else {
+                0
+            };
+            let bound = c * (theta / 2f64.powi(s as i32)).powf(exponent);
+            let cost = (m - 1) as f64 + 4.0 / 3.0 + s as f64;
+            (cost, m, s, bound)
+        })
+        .fold(
+            None,
+            |best: Option<(f64, usize, usize, f64)>, candidate| match best {
+                Some(b) if b.0 <= candidate.0 => Some(b),
+                _ => Some(candidate),
+            },
+        )
+        .map(|(_, m, s, bound)| (m, s, bound))
+        .expect("at least one Padé degree");
+
+    let n = a.nrows();
+    let two = F::one() + F::one();
+    let x = a.mapv(|v| v * two.powi(-(s as i32)));
+    let b = expm_pade_coefficients(m);
+    let mut numerator = Array2::<F>::eye(n);
+    let mut denominator = Array2::<F>::eye(n);
+    let mut power = Array2::<F>::eye(n);
+    for (k, &b_k) in b.iter().enumerate().skip(1) {
+        power = power.dot(&x);
+        let coef = F::from(b_k).unwrap();
+        let signed = if k.is_multiple_of(2) { coef } else { -coef };
+        numerator.scaled_add(coef, &power);
+        denominator.scaled_add(signed, &power);
+    }
+    let mut result = crate::factorization::LuFactorization::new(&denominator.view())?
+        .solve_multiple(&numerator.view())?;
+    for _ in 0..s {
+        result = result.dot(&result);
+    }
+
+    let info = MatrixFunctionInfo {
+        error_estimate: F::from(error_bound).unwrap_or(F::zero()),
+        iterations: 0,
+        scaling_power: s,
+        degree: m,
+    };
+    Ok((result, info))
+}
+
+/// Compute the matrix exponential to a requested relative accuracy.
+///
+/// Shorthand for [`expm_with_options`] with only the tolerance set.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `tol` - Target relative accuracy
+///
+/// # Returns
+///
+/// * exp(A)
+pub fn expm_with_tolerance<F>(a: &ArrayView2<F>, tol: F) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+{
+    let options = MatrixFunctionOptions {
+        tol,
+        ..Default::default()
+    };
+    expm_with_options(a, &options).map(|(result, _)| result)
+}
+
+/// Largest number of series terms used by [`logm_with_options`]
+const LOGM_MAX_TERMS: usize = 64;
+
+/// Compute the matrix logarithm to a requested accuracy.
+///
+/// Inverse scaling and squaring: square roots are taken until
+/// `X = A^(1/2^s) - I` has `theta <= 1/4`, where `theta` bounds
+/// `||X^j||_1^(1/j)` for `j >= 2`, and `log(I + X)` is then summed to `m`
+/// terms, with `m` the smallest degree whose relative truncation bound
+/// `theta^m / ((m + 1)(1 - theta))` is below `options.tol`. The square roots
+/// use the Schur method (`Auto`, `Schur`) or the Denman–Beavers iteration
+/// (`Iterative`, with `options.tol` and `options.max_iter`).
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix with no eigenvalues on the closed negative
+///   real axis
+/// * `options` - Accuracy and algorithm controls
+///
+/// # Returns
+///
+/// * log(A) and diagnostics; `error_estimate` is the relative truncation
+///   bound of the series
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{logm_with_options, MatrixFunctionOptions};
+///
+/// let a = array![[4.0_f64, 1.0], [0.0, 9.0]];
+/// let (l, info) = logm_with_options(&a.view(), &MatrixFunctionOptions::default()).unwrap();
+/// assert!(info.scaling_power > 0);
+/// assert!((l[[0, 0]] - 4.0_f64.ln()).abs() < 1e-12);
+/// assert!((l[[0, 1]] - (9.0_f64.ln() - 4.0_f64.ln()) / 5.0).abs() < 1e-12);
+/// ```
+pub fn logm_with_options<F>(
+    a: &ArrayView2<F>,
+    options: &MatrixFunctionOptions<F>,
+) -> LinalgResult<(Array2<F>, MatrixFunctionInfo<F>)>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    validate_decomposition(a, "Matrix logarithm computation", true)?;
+    check_tolerance(options.tol, "Matrix logarithm computation")?;
+
+    let n = a.nrows();
+    let quarter = F::from(0.25).unwrap();
+    let mut root = a.to_owned();
+    let mut scaling_power = 0;
+    let mut iterations = 0;
+    let (x, theta) = loop {
+        let mut x = root.clone();
+        for i in 0..n {
+            x[[i, i]] -= F::one();
+        }
+        let theta = one_norm(&x).min(power_norm_bound(&x.view(), 2)?);
+        if theta <= quarter {
+            break (x, theta);
+        }
+        if scaling_power >= options.max_iter {
+            return Err(LinalgError::ConvergenceError(format!(
+                "Matrix logarithm: not close enough to the identity after {} square roots",
+                scaling_power
+            )));
+        }
+        root = match options.method {
+            MatrixFunctionMethod::Iterative => {
+                let (r, steps) = sqrtm_denman_beavers(&root.view(), options.max_iter, options.tol)?;
+                iterations += steps;
+                r
+            }
+            MatrixFunctionMethod::Auto | MatrixFunctionMethod::Schur => sqrtm_schur(&root.view())?,
+        };
+        scaling_power += 1;
+    };
+
+    let truncation =
+        |m: usize| theta.powi(m as i32) / (F::from(m + 1).unwrap() * (F::one() - theta));
+    let degree = (1..=LOGM_MAX_TERMS)
+        .find(|&m| truncation(m) <= options.tol)
+        .unwrap_or(LOGM_MAX_TERMS);
+
+    // Horner evaluation of log(I + X) = X (c_1 I + X (c_2 I + ... + X c_m I))
+    // with c_j = (-1)^(j+1) / j
+    let coef = |j: usize| {
+        let c = F::one() / F::from(j).unwrap();
+        if j.is_multiple_of(2) {
+            -c
+        } else {
+            c
+        }
+    };
+    let mut series = Array2::<F>::eye(n).mapv(|v| v * coef(degree));
+    for j in (1..degree).rev() {
+        series = matmul_plain(&x, &series);
+        for i in 0..n {
+            series[[i, i]] += coef(j);
+        }
+    }
+    let scale = F::from(2.0).unwrap().powi(scaling_power as i32);
+    let result = matmul_plain(&x, &series).mapv(|v| v * scale);
+
+    let info = MatrixFunctionInfo {
+        error_estimate: truncation(degree),
+        iterations,
+        scaling_power,
+        degree,
+    };
+    Ok((result, info))
+}
+
+/// Compute the matrix square root with accuracy controls.
+///
+/// `Schur` uses the blocked Schur method, `Iterative` the scaled
+/// Denman–Beavers iteration stopped at relative change `options.tol`, and
+/// `Auto` chooses as [`SqrtmMethod::Auto`] does.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `options` - Accuracy and algorithm controls
+///
+/// # Returns
+///
+/// * Principal square root X of A and diagnostics; `error_estimate` is the
+///   relative residual `||X^2 - A||_1 / ||A||_1`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{
+///     sqrtm_with_options, MatrixFunctionMethod, MatrixFunctionOptions,
+/// };
+///
+/// let a = array![[5.0_f64, 4.0], [4.0, 5.0]];
+/// let options = MatrixFunctionOptions {
+///     method: MatrixFunctionMethod::Iterative,
+///     ..Default::default()
+/// };
+/// let (x, info) = sqrtm_with_options(&a.view(), &options).unwrap();
+/// assert!(info.iterations > 0 && info.error_estimate < 1e-12);
+/// assert!((x[[0, 0]] - 2.0).abs() < 1e-12 && (x[[0, 1]] - 1.0).abs() < 1e-12);
+/// ```
+pub fn sqrtm_with_options<F>(
+    a: &ArrayView2<F>,
+    options: &MatrixFunctionOptions<F>,
+) -> LinalgResult<(Array2<F>, MatrixFunctionInfo<F>)>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    validate_decomposition(a, "Matrix square root computation", true)?;
+    check_tolerance(options.tol, "Matrix square root computation")?;
+
+    let (x, iterations) = match options.method {
+        MatrixFunctionMethod::Schur => (sqrtm_schur(a)?, 0),
+        MatrixFunctionMethod::Iterative => sqrtm_denman_beavers(a, options.max_iter, options.tol)?,
+        MatrixFunctionMethod::Auto => {
+            let iterative = if prefers_iterative_sqrtm(a) {
+                sqrtm_denman_beavers(a, options.max_iter, options.tol).ok()
+            } else {
+                None
+            };
+            match iterative {
+                Some(result) => result,
+                None => (sqrtm_schur(a)?, 0),
+            }
+        }
+    };
+
+    let a_owned = a.to_owned();
+    let residual = one_norm(&(matmul_plain(&x, &x) - &a_owned));
+    let norm_a = one_norm(&a_owned);
+    let error_estimate = if norm_a > F::zero() {
+        residual / norm_a
+    } else {
+        residual
+    };
+    Ok((
+        x,
+        MatrixFunctionInfo::without_scaling(error_estimate, iterations),
+    ))
+}
+
+/// Compute the matrix sign function with accuracy controls.
+///
+/// `Schur` uses [`matrix_sign_schur`]; `Auto` and `Iterative` use the
+/// determinant-scaled Newton iteration stopped at relative residual
+/// `options.tol`.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix with no eigenvalues on the imaginary axis
+/// * `options` - Accuracy and algorithm controls
+///
+/// # Returns
+///
+/// * sign(A) and diagnostics; `error_estimate` is the relative residual
+///   `||S^2 - I||_1 / ||S||_1^2`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::matrix_functions::{signm_with_options, MatrixFunctionOptions};
+///
+/// let a = array![[2.0_f64, 1.0], [0.0, -3.0]];
+/// let (s, info) = signm_with_options(&a.view(), &MatrixFunctionOptions::default()).unwrap();
+/// assert!(info.iterations > 0);
+/// assert!((s[[0, 1]] - 0.4).abs() < 1e-12);
+/// ```
+pub fn signm_with_options<F>(
+    a: &ArrayView2<F>,
+    options: &MatrixFunctionOptions<F>,
+) -> LinalgResult<(Array2<F>, MatrixFunctionInfo<F>)>
+where
+    F: Float + NumAssign + Sum + One,
+{
+    validate_decomposition(a, "Matrix sign function computation", true)?;
+    check_tolerance(options.tol, "Matrix sign function computation")?;
+
+    let (sign, iterations) = match options.method {
+        MatrixFunctionMethod::Schur => (matrix_sign_schur(a)?, 0),
+        MatrixFunctionMethod::Auto | MatrixFunctionMethod::Iterative => {
+            let (sign, steps, _) =
+                sign_newton(a, SignScaling::Determinant, options.max_iter, options.tol)?;
+            (sign, steps)
+        }
+    };
+
+    let mut residual = matmul_plain(&sign, &sign);
+    for i in 0..a.nrows() {
+        residual[[i, i]] -= F::one();
+    }
+    let norm_s = one_norm(&sign);
+    let error_estimate = one_norm(&residual) / (norm_s * norm_s);
+    Ok((
+        sign,
+        MatrixFunctionInfo::without_scaling(error_estimate, iterations),
     ))
 }
 
 /// Compute the matrix inverse cosine.
 ///
-/// The matrix inverse cosine is the inverse function of cosm, such that
-/// cosm(acosm(A)) = A for matrices with appropriate spectra.
+/// The matrix inverse cosine is the inverse function of cosm, such that
+/// cosm(acosm(A)) = A for matrices with appropriate spectra.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix (should have eigenvalues in [-1, 1])
+///
+/// # Returns
+///
+/// * Matrix inverse cosine of a
+pub fn acosm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+{
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Matrix must be square to compute inverse cosine, got shape {:?}",
+            a.shape()
+        )));
+    }
+
+    // acos(A) = -i * log(A + i*sqrt(I - A²))
+    // This is a simplified implementation
+    Err(LinalgError::NotImplementedError(
+        "Matrix inverse cosine is not yet implemented".to_string(),
+    ))
+}
+
+/// Compute the matrix inverse sine.
+///
+/// The matrix inverse sine is the inverse function of sinm, such that
+/// sinm(asinm(A)) = A for matrices with appropriate spectra.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix (should have eigenvalues in [-1, 1])
+///
+/// # Returns
+///
+/// * Matrix inverse sine of a
+pub fn asinm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+{
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Matrix must be square to compute inverse sine, got shape {:?}",
+            a.shape()
+        )));
+    }
+
+    // asin(A) = -i * log(iA + sqrt(I - A²))
+    // This is a simplified implementation
+    Err(LinalgError::NotImplementedError(
+        "Matrix inverse sine is not yet implemented".to_string(),
+    ))
+}
+
+/// Compute the matrix inverse tangent.
+///
+/// The matrix inverse tangent is the inverse function of tanm, such that
+/// tanm(atanm(A)) = A for matrices with appropriate spectra.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+///
+/// # Returns
+///
+/// * Matrix inverse tangent of a
+pub fn atanm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+{
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Matrix must be square to compute inverse tangent, got shape {:?}",
+            a.shape()
+        )));
+    }
+
+    // atan(A) = (i/2) * log((I - iA)/(I + iA))
+    // This is a simplified implementation
+    Err(LinalgError::NotImplementedError(
+        "Matrix inverse tangent is not yet implemented".to_string(),
+    ))
+}
+
+/// Evaluate a general matrix function `f(A)` using the Schur–Parlett algorithm.
+///
+/// The matrix is reduced to complex Schur form `A = Z T Z^H`, the eigenvalues
+/// are grouped into clusters of nearby values (blocking parameter δ = 0.1) and
+/// the Schur form is reordered so that each cluster forms a contiguous diagonal
+/// block. `f` is evaluated on every diagonal block through a Taylor expansion
+/// about the block's mean eigenvalue, and the off-diagonal blocks are obtained
+/// from the block Parlett recurrence, which only requires solving triangular
+/// Sylvester equations between well-separated blocks (Davies & Higham, 2003).
+///
+/// Only values of `f` are needed: the Taylor coefficients are recovered from a
+/// Cauchy integral, so `f` must be analytic in a neighbourhood of the spectrum
+/// of `A`. For a real matrix the result is returned as a real matrix, which
+/// requires `f` to map conjugate arguments to conjugate values (as any function
+/// that is real on the real axis does).
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `f` - Scalar function to apply, evaluated at complex arguments
+///
+/// # Returns
+///
+/// * `f(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex;
+/// use scirs2_linalg::matrix_functions::funm;
+///
+/// let a = array![[1.0_f64, 2.0], [0.0, 3.0]];
+/// // f(x) = 1 / (1 + x^2)
+/// let one = Complex::new(1.0, 0.0);
+/// let f_a = funm(&a.view(), |z| one / (one + z * z)).unwrap();
+///
+/// assert!((f_a[[0, 0]] - 0.5).abs() < 1e-12);
+/// assert!((f_a[[1, 1]] - 0.1).abs() < 1e-12);
+/// assert!((f_a[[0, 1]] + 0.4).abs() < 1e-12);
+/// ```
+pub fn funm<F, Func>(a: &ArrayView2<F>, f: Func) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + 'static,
+    Func: Fn(Complex<F>) -> Complex<F>,
+{
+    validate_decomposition(a, "Matrix function computation", true)?;
+
+    let a_complex = a.mapv(|x| Complex::new(x, F::zero()));
+    let result = schur_parlett(&a_complex.view(), &f)?;
+
+    // The imaginary part of f(A) vanishes for real A; anything beyond rounding
+    // means f does not commute with complex conjugation
+    let scale = result.iter().fold(F::one(), |acc, z| acc.max(z.re.abs()));
+    let imag = result.iter().fold(F::zero(), |acc, z| acc.max(z.im.abs()));
+    if imag > F::epsilon().sqrt() * scale {
+        return Err(LinalgError::ComputationError(format!(
+            "f(A) has a significant imaginary part (max |Im| = {:.3e}); use funm_complex for functions that are not real on the real axis",
+            imag.to_f64().unwrap_or(f64::NAN)
+        )));
+    }
+
+    Ok(result.mapv(|z| z.re))
+}
+
+/// Evaluate a general matrix function `f(A)` of a complex matrix.
+///
+/// This is the complex counterpart of [`funm`] and uses the same blocked
+/// Schur–Parlett algorithm.
+///
+/// # Arguments
+///
+/// * `a` - Input square complex matrix
+/// * `f` - Scalar function to apply
+///
+/// # Returns
+///
+/// * `f(A)`
+pub fn funm_complex<F, Func>(
+    a: &ArrayView2<Complex<F>>,
+    f: Func,
+) -> LinalgResult<Array2<Complex<F>>>
+where
+    F: Float + NumAssign + Sum + 'static,
+    Func: Fn(Complex<F>) -> Complex<F>,
+{
+    if a.is_empty() {
+        return Err(LinalgError::ShapeError(
+            "Matrix function computation: matrix cannot be empty".to_string(),
+        ));
+    }
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Matrix function computation: matrix must be square, got shape {:?}",
+            a.shape()
+        )));
+    }
+    if a.iter().any(|z| !z.re.is_finite() || !z.im.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Matrix function computation: matrix contains non-finite values".to_string(),
+        ));
+    }
+
+    schur_parlett(a, &f)
+}
+
+/// Compute the matrix exponential of a complex matrix.
+///
+/// Evaluates `exp(A)` with the Schur–Parlett algorithm of [`funm_complex`].
 ///
 /// # Arguments
 ///
-/// * `a` - Input square matrix (should have eigenvalues in [-1, 1])
+/// * `a` - Input square complex matrix
 ///
 /// # Returns
 ///
-/// * Matrix inverse cosine of a
-pub fn acosm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+/// * `exp(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex;
+/// use scirs2_linalg::matrix_functions::expm_complex;
+///
+/// // exp(i pi I) = -I
+/// let a = array![
+///     [Complex::new(0.0, std::f64::consts::PI), Complex::new(0.0, 0.0)],
+///     [Complex::new(0.0, 0.0), Complex::new(0.0, std::f64::consts::PI)],
+/// ];
+/// let e = expm_complex(&a.view()).unwrap();
+/// assert!((e[[0, 0]] - Complex::new(-1.0, 0.0)).norm() < 1e-12);
+/// assert!(e[[0, 1]].norm() < 1e-12);
+/// ```
+pub fn expm_complex<F>(a: &ArrayView2<Complex<F>>) -> LinalgResult<Array2<Complex<F>>>
 where
-    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+    F: Float + NumAssign + Sum + 'static,
 {
-    if a.nrows() != a.ncols() {
-        return Err(LinalgError::ShapeError(format!(
-            "Matrix must be square to compute inverse cosine, got shape {:?}",
-            a.shape()
-        )));
-    }
-
-    // acos(A) = -i * log(A + i*sqrt(I - A²))
-    // This is a simplified implementation
-    Err(LinalgError::NotImplementedError(
-        "Matrix inverse cosine is not yet implemented".to_string(),
-    ))
+    funm_complex(a, |z| z.exp())
 }
 
-/// Compute the matrix inverse sine.
+/// Compute the principal logarithm of a complex matrix.
 ///
-/// The matrix inverse sine is the inverse function of sinm, such that
-/// sinm(asinm(A)) = A for matrices with appropriate spectra.
+/// The principal logarithm has eigenvalues with imaginary parts in `(-pi, pi]`
+/// and exists whenever `A` is nonsingular; unlike [`logm`], matrices with
+/// negative real eigenvalues are allowed. Evaluated by inverse scaling and
+/// squaring on the complex Schur factor: repeated principal square roots bring
+/// `T` close to the identity, where the series of `log(I + X)` converges fast,
+/// and the result is scaled back by the number of square roots taken.
 ///
 /// # Arguments
 ///
-/// * `a` - Input square matrix (should have eigenvalues in [-1, 1])
+/// * `a` - Input square complex matrix
 ///
 /// # Returns
 ///
-/// * Matrix inverse sine of a
-pub fn asinm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+/// * `log(A)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex;
+/// use scirs2_linalg::matrix_functions::logm_complex;
+///
+/// // log(-I) = i pi I
+/// let a = array![
+///     [Complex::new(-1.0_f64, 0.0), Complex::new(0.0, 0.0)],
+///     [Complex::new(0.0, 0.0), Complex::new(-1.0, 0.0)],
+/// ];
+/// let l = logm_complex(&a.view()).unwrap();
+/// assert!((l[[0, 0]] - Complex::new(0.0, std::f64::consts::PI)).norm() < 1e-12);
+/// ```
+pub fn logm_complex<F>(a: &ArrayView2<Complex<F>>) -> LinalgResult<Array2<Complex<F>>>
 where
-    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+    F: Float + NumAssign + Sum + 'static,
 {
-    if a.nrows() != a.ncols() {
-        return Err(LinalgError::ShapeError(format!(
-            "Matrix must be square to compute inverse sine, got shape {:?}",
-            a.shape()
-        )));
+    if a.is_empty() {
+        return Err(LinalgError::ShapeError(
+            "Matrix logarithm: matrix cannot be empty".to_string(),
+        ));
+    }
+    if a.iter().any(|z| !z.re.is_finite() || !z.im.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Matrix logarithm: matrix contains non-finite values".to_string(),
+        ));
+    }
+    let n = a.nrows();
+    let (mut t, z) = complex_schur(a)?;
+    let scale = t.iter().fold(F::zero(), |acc, z| acc.max(z.norm()));
+    if t.diag().iter().any(|z| z.norm() <= F::epsilon() * scale) {
+        return Err(LinalgError::SingularMatrixError(
+            "Matrix logarithm does not exist for a singular matrix".to_string(),
+        ));
+    }
+
+    let distance_to_identity = |t: &Array2<Complex<F>>| {
+        (0..n).fold(F::zero(), |acc, j| {
+            let col = (0..=j).fold(F::zero(), |s, i| {
+                let d = if i == j {
+                    t[[i, j]] - Complex::one()
+                } else {
+                    t[[i, j]]
+                };
+                s + d.norm()
+            });
+            acc.max(col)
+        })
+    };
+    let threshold = F::from(0.25).unwrap();
+    let mut roots = 0;
+    while distance_to_identity(&t) > threshold {
+        if roots == 64 {
+            return Err(LinalgError::ConvergenceError(
+                "Matrix logarithm: square roots did not approach the identity".to_string(),
+            ));
+        }
+        t = sqrtm_upper_triangular(&t)?;
+        roots += 1;
     }
 
-    // asin(A) = -i * log(iA + sqrt(I - A²))
-    // This is a simplified implementation
-    Err(LinalgError::NotImplementedError(
-        "Matrix inverse sine is not yet implemented".to_string(),
-    ))
+    // log(I + X) = X - X^2/2 + X^3/3 - ... for upper triangular X
+    let mut x = t;
+    for i in 0..n {
+        x[[i, i]] -= Complex::one();
+    }
+    let mut log_t = x.clone();
+    let mut power = x.clone();
+    for k in 2..=100 {
+        let mut next = Array2::<Complex<F>>::zeros((n, n));
+        for i in 0..n {
+            for j in i..n {
+                next[[i, j]] =
+                    (i..=j).fold(Complex::zero(), |acc, l| acc + power[[i, l]] * x[[l, j]]);
+            }
+        }
+        power = next;
+        let coeff = F::one() / F::from(k).unwrap();
+        let sign = if k % 2 == 0 { -coeff } else { coeff };
+        let mut term_norm = F::zero();
+        for (l, p) in log_t.iter_mut().zip(power.iter()) {
+            *l += *p * sign;
+            term_norm = term_norm.max(p.norm() * coeff);
+        }
+        if term_norm <= F::epsilon() {
+            break;
+        }
+    }
+    let factor = F::from(2.0).unwrap().powi(roots);
+    log_t.mapv_inplace(|v| v * factor);
+    Ok(unitary_similarity(&z, &log_t))
 }
 
-/// Compute the matrix inverse tangent.
+/// Compute the principal square root of a complex matrix.
 ///
-/// The matrix inverse tangent is the inverse function of tanm, such that
-/// tanm(atanm(A)) = A for matrices with appropriate spectra.
+/// Uses the blocked Schur method: the complex Schur factor is square-rooted by
+/// the triangular recurrence used by [`SqrtmMethod::Schur`] and transformed
+/// back. Unlike [`sqrtm`], matrices with negative real eigenvalues are allowed.
 ///
 /// # Arguments
 ///
-/// * `a` - Input square matrix
+/// * `a` - Input square complex matrix
 ///
 /// # Returns
 ///
-/// * Matrix inverse tangent of a
-pub fn atanm<F>(a: &ArrayView2<F>) -> LinalgResult<Array2<F>>
+/// * `X` with `X^2 = A` whose eigenvalues have non-negative real parts
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex;
+/// use scirs2_linalg::matrix_functions::sqrtm_complex;
+///
+/// // sqrt(-4 I) = 2i I
+/// let a = array![
+///     [Complex::new(-4.0_f64, 0.0), Complex::new(0.0, 0.0)],
+///     [Complex::new(0.0, 0.0), Complex::new(-4.0, 0.0)],
+/// ];
+/// let x = sqrtm_complex(&a.view()).unwrap();
+/// assert!((x[[0, 0]] - Complex::new(0.0, 2.0)).norm() < 1e-12);
+/// ```
+pub fn sqrtm_complex<F>(a: &ArrayView2<Complex<F>>) -> LinalgResult<Array2<Complex<F>>>
 where
-    F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
+    F: Float + NumAssign + Sum + 'static,
 {
-    if a.nrows() != a.ncols() {
-        return Err(LinalgError::ShapeError(format!(
-            "Matrix must be square to compute inverse tangent, got shape {:?}",
-            a.shape()
+    if a.is_empty() {
+        return Err(LinalgError::ShapeError(
+            "Matrix square root: matrix cannot be empty".to_string(),
+        ));
+    }
+    if a.iter().any(|z| !z.re.is_finite() || !z.im.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Matrix square root: matrix contains non-finite values".to_string(),
+        ));
+    }
+    let (t, z) = complex_schur(a)?;
+    let u = sqrtm_upper_triangular(&t)?;
+    Ok(unitary_similarity(&z, &u))
+}
+
+/// Blocking parameter for grouping eigenvalues into clusters
+const SCHUR_PARLETT_DELTA: f64 = 0.1;
+
+/// Number of quadrature nodes used to recover Taylor coefficients
+const SCHUR_PARLETT_NODES: usize = 64;
+
+fn schur_parlett<F, Func>(a: &ArrayView2<Complex<F>>, f: &Func) -> LinalgResult<Array2<Complex<F>>>
+where
+    F: Float + NumAssign + Sum + 'static,
+    Func: Fn(Complex<F>) -> Complex<F>,
+{
+    let n = a.nrows();
+    let (mut t, mut z) = complex_schur(a)?;
+
+    // Group eigenvalues whose distance is below delta (transitively)
+    let delta = F::from(SCHUR_PARLETT_DELTA).unwrap();
+    let mut cluster: Vec<usize> = (0..n).collect();
+    fn root(cluster: &mut [usize], mut i: usize) -> usize {
+        while cluster[i] != i {
+            cluster[i] = cluster[cluster[i]];
+            i = cluster[i];
+        }
+        i
+    }
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if (t[[i, i]] - t[[j, j]]).norm() <= delta {
+                let (ri, rj) = (root(&mut cluster, i), root(&mut cluster, j));
+                cluster[ri.max(rj)] = ri.min(rj);
+            }
+        }
+    }
+    let mut order: Vec<usize> = (0..n).map(|i| root(&mut cluster, i)).collect();
+
+    // Reorder the Schur form so that every cluster is contiguous. Swapped
+    // eigenvalues always belong to different clusters, so each swap is
+    // well conditioned.
+    let mut swapped = true;
+    while swapped {
+        swapped = false;
+        for k in 0..n.saturating_sub(1) {
+            if order[k] > order[k + 1] {
+                swap_schur_adjacent(&mut t, &mut z, k);
+                order.swap(k, k + 1);
+                swapped = true;
+            }
+        }
+    }
+
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    for k in 1..=n {
+        if k == n || order[k] != order[start] {
+            blocks.push(start..k);
+            start = k;
+        }
+    }
+
+    // Diagonal blocks
+    let mut ft = Array2::<Complex<F>>::zeros((n, n));
+    for block in &blocks {
+        let t_ii = t.slice(s![block.clone(), block.clone()]);
+        let f_ii = schur_parlett_atomic_block(&t_ii, f)?;
+        ft.slice_mut(s![block.clone(), block.clone()]).assign(&f_ii);
+    }
+
+    // Off-diagonal blocks via the block Parlett recurrence:
+    // T_ii F_ij - F_ij T_jj = F_ii T_ij - T_ij F_jj + sum_k (F_ik T_kj - T_ik F_kj)
+    for j in 0..blocks.len() {
+        let bj = blocks[j].clone();
+        for i in (0..j).rev() {
+            let bi = blocks[i].clone();
+            let t_ij = t.slice(s![bi.clone(), bj.clone()]);
+            let mut rhs = ft.slice(s![bi.clone(), bi.clone()]).dot(&t_ij)
+                - t_ij.dot(&ft.slice(s![bj.clone(), bj.clone()]));
+            for bk in &blocks[(i + 1)..j] {
+                rhs = rhs
+                    + ft.slice(s![bi.clone(), bk.clone()])
+                        .dot(&t.slice(s![bk.clone(), bj.clone()]))
+                    - t.slice(s![bi.clone(), bk.clone()])
+                        .dot(&ft.slice(s![bk.clone(), bj.clone()]));
+            }
+            let f_ij = solve_triangular_sylvester(
+                &t.slice(s![bi.clone(), bi.clone()]),
+                &t.slice(s![bj.clone(), bj.clone()]),
+                rhs,
+            );
+            ft.slice_mut(s![bi, bj.clone()]).assign(&f_ij);
+        }
+    }
+
+    Ok(unitary_similarity(&z, &ft))
+}
+
+/// Evaluate `f` on an upper triangular block whose eigenvalues are clustered
+///
+/// Uses `f(T) = sum_k f^(k)(sigma) / k! (T - sigma I)^k` about the mean
+/// eigenvalue `sigma`. The Taylor coefficients are computed by the trapezoidal
+/// rule applied to Cauchy's integral formula on a circle around `sigma`.
+fn schur_parlett_atomic_block<F, Func>(
+    t: &ArrayView2<Complex<F>>,
+    f: &Func,
+) -> LinalgResult<Array2<Complex<F>>>
+where
+    F: Float + NumAssign + Sum + 'static,
+    Func: Fn(Complex<F>) -> Complex<F>,
+{
+    let m = t.nrows();
+    if m == 1 {
+        return Ok(Array2::from_elem((1, 1), f(t[[0, 0]])));
+    }
+
+    let m_f = F::from(m).unwrap();
+    let sigma = (0..m).fold(Complex::zero(), |acc: Complex<F>, i| acc + t[[i, i]]) / m_f;
+    let spread = (0..m).fold(F::zero(), |acc, i| acc.max((t[[i, i]] - sigma).norm()));
+    let f_sigma = f(sigma);
+
+    // The circle must not enclose a singularity of f. The mean value of f on
+    // the circle must reproduce f(sigma), otherwise shrink the radius.
+    let nodes = SCHUR_PARLETT_NODES;
+    let two_pi = F::from(2.0 * std::f64::consts::PI).unwrap();
+    let roots: Vec<Complex<F>> = (0..nodes)
+        .map(|j| {
+            Complex::from_polar(
+                F::one(),
+                two_pi * F::from(j).unwrap() / F::from(nodes).unwrap(),
+            )
+        })
+        .collect();
+    let mut radius = spread.max(F::from(SCHUR_PARLETT_DELTA / 2.0).unwrap());
+    let mut values = Vec::with_capacity(nodes);
+    let mut accepted = false;
+    for _ in 0..8 {
+        values.clear();
+        values.extend(roots.iter().map(|&w| f(sigma + w * radius)));
+        let mean = values
+            .iter()
+            .fold(Complex::zero(), |acc: Complex<F>, &v| acc + v)
+            / F::from(nodes).unwrap();
+        let magnitude = values
+            .iter()
+            .fold(f_sigma.norm(), |acc, v| acc.max(v.norm()));
+        if (mean - f_sigma).norm() <= F::epsilon().sqrt() * magnitude.max(F::epsilon()) {
+            accepted = true;
+            break;
+        }
+        radius *= F::from(0.5).unwrap();
+    }
+    if !accepted
+        || values
+            .iter()
+            .any(|v| !v.re.is_finite() || !v.im.is_finite())
+    {
+        return Err(LinalgError::ComputationError(format!(
+            "f is not analytic near the eigenvalue cluster at {:?}",
+            (sigma.re.to_f64(), sigma.im.to_f64())
         )));
     }
 
-    // atan(A) = (i/2) * log((I - iA)/(I + iA))
-    // This is a simplified implementation
-    Err(LinalgError::NotImplementedError(
-        "Matrix inverse tangent is not yet implemented".to_string(),
-    ))
+    let mut shifted = t.to_owned();
+    for i in 0..m {
+        shifted[[i, i]] -= sigma;
+    }
+
+    let norm1 = |x: &Array2<Complex<F>>| {
+        (0..x.ncols()).fold(F::zero(), |acc, j| {
+            acc.max(x.column(j).iter().fold(F::zero(), |s, v| s + v.norm()))
+        })
+    };
+
+    let mut result = Array2::<Complex<F>>::eye(m).mapv(|x| x * f_sigma);
+    let mut power = Array2::<Complex<F>>::eye(m);
+    let mut small_terms = 0;
+    for k in 1..(nodes - m) {
+        power = power.dot(&shifted);
+        // c_k = (1/N) sum_j f(sigma + r w_j) w_j^{-k} / r^k
+        let coeff = values
+            .iter()
+            .enumerate()
+            .fold(Complex::zero(), |acc: Complex<F>, (j, &v)| {
+                acc + v * roots[(j * k) % nodes].conj()
+            })
+            / (F::from(nodes).unwrap() * radius.powi(k as i32));
+        let term = power.mapv(|x| x * coeff);
+        result += &term;
+
+        let term_norm = norm1(&term);
+        if term_norm <= F::epsilon() * norm1(&result) || norm1(&power) == F::zero() {
+            small_terms += 1;
+            if small_terms >= m {
+                return Ok(result);
+            }
+        } else {
+            small_terms = 0;
+        }
+    }
+
+    Err(LinalgError::ConvergenceError(format!(
+        "Taylor series for a {}x{} diagonal block did not converge; f may have a singularity close to the eigenvalues",
+        m, m
+    )))
+}
+
+/// Solve `A X - X B = C` for upper triangular `A` and `B` with disjoint spectra
+fn solve_triangular_sylvester<F: Float>(
+    a: &ArrayView2<Complex<F>>,
+    b: &ArrayView2<Complex<F>>,
+    mut c: Array2<Complex<F>>,
+) -> Array2<Complex<F>> {
+    let p = a.nrows();
+    let q = b.nrows();
+    let mut x = Array2::<Complex<F>>::zeros((p, q));
+
+    for col in 0..q {
+        // (A - b_cc I) x_c = c_c + sum_{l < c} x_l b_lc
+        for l in 0..col {
+            let b_lc = b[[l, col]];
+            for r in 0..p {
+                c[[r, col]] = c[[r, col]] + x[[r, l]] * b_lc;
+            }
+        }
+        for r in (0..p).rev() {
+            let mut sum = c[[r, col]];
+            for k in (r + 1)..p {
+                sum = sum - a[[r, k]] * x[[k, col]];
+            }
+            x[[r, col]] = sum / (a[[r, r]] - b[[col, col]]);
+        }
+    }
+
+    x
 }
 
 #[cfg(test)]
@@ -1821,3 +3255,364 @@ mod hyperbolic_tests {
         assert_relative_eq!(sign_a[[1, 1]], 1.0, epsilon = 1e-10);
     }
 }
+
+#[cfg(test)]
+mod sqrtm_method_tests {
+    use super::*;
+    use ndarray::array;
+
+    fn assert_square_root(x: &Array2<f64>, a: &Array2<f64>, tol: f64) {
+        let x2 = x.dot(x);
+        for (p, q) in x2.iter().zip(a.iter()) {
+            assert!((p - q).abs() < tol, "X^2 = {:?}, expected {:?}", x2, a);
+        }
+    }
+
+    #[test]
+    fn test_sqrtm_all_methods_spd() {
+        let a = array![[4.0, 1.0, 0.5], [1.0, 3.0, 0.2], [0.5, 0.2, 2.0]];
+        for method in [
+            SqrtmMethod::Auto,
+            SqrtmMethod::Schur,
+            SqrtmMethod::DenmanBeavers,
+            SqrtmMethod::Newton,
+        ] {
+            let x = sqrtm_with_method(&a.view(), method, 50, 1e-13).unwrap();
+            assert_square_root(&x, &a, 1e-11);
+        }
+    }
+
+    #[test]
+    fn test_sqrtm_schur_non_normal() {
+        let a = array![[1.0, 100.0, 3.0], [0.0, 2.0, -50.0], [0.0, 0.0, 3.0]];
+        let x = sqrtm_with_method(&a.view(), SqrtmMethod::Schur, 0, 0.0).unwrap();
+        assert_square_root(&x, &a, 1e-10);
+        assert!((x[[0, 0]] - 1.0).abs() < 1e-12);
+        assert!((x[[2, 2]] - 3.0f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sqrtm_complex_conjugate_eigenvalues() {
+        // Eigenvalues 1 ± 2i: the principal square root is real
+        let a = array![[1.0, -2.0], [2.0, 1.0]];
+        let x = sqrtm(&a.view(), 50, 1e-13).unwrap();
+        assert_square_root(&x, &a, 1e-12);
+        assert!(x[[0, 0]] > 0.0);
+    }
+
+    #[test]
+    fn test_sqrtm_blocked_large() {
+        let n = 80;
+        let a = Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                (n + i) as f64
+            } else {
+                ((i * 7 + j * 3) % 11) as f64 * 0.1
+            }
+        });
+        let x = sqrtm_with_method(&a.view(), SqrtmMethod::Schur, 0, 0.0).unwrap();
+        assert_square_root(&x, &a, 1e-9);
+    }
+
+    #[test]
+    fn test_sqrtm_negative_eigenvalue() {
+        let a = array![[-4.0, 0.0], [0.0, 9.0]];
+        assert!(sqrtm(&a.view(), 50, 1e-12).is_err());
+        assert!(sqrtm_with_method(&a.view(), SqrtmMethod::Schur, 0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_sqrtm_singular_jordan_block() {
+        let a = array![[0.0, 1.0], [0.0, 0.0]];
+        assert!(sqrtm_with_method(&a.view(), SqrtmMethod::Schur, 0, 0.0).is_err());
+    }
+}
+
+#[cfg(test)]
+mod matrix_sign_tests {
+    use super::*;
+    use ndarray::array;
+
+    fn assert_is_sign_of(s: &Array2<f64>, a: &Array2<f64>, tol: f64) {
+        let n = a.nrows();
+        let s2 = s.dot(s);
+        let commutator = s.dot(a) - a.dot(s);
+        for i in 0..n {
+            for j in 0..n {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((s2[[i, j]] - expected).abs() < tol, "S^2 != I: {:?}", s2);
+                assert!(commutator[[i, j]].abs() < tol, "SA != AS: {:?}", commutator);
+            }
+        }
+    }
+
+    fn test_matrix() -> Array2<f64> {
+        array![
+            [3.0, 1.0, -2.0, 0.5],
+            [0.2, -4.0, 1.0, 0.0],
+            [1.0, 0.3, 0.5, 2.0],
+            [0.0, 1.0, -1.5, -0.7]
+        ]
+    }
+
+    #[test]
+    fn test_matrix_sign_newton_scalings() {
+        let a = test_matrix();
+        for scaling in [
+            SignScaling::None,
+            SignScaling::Determinant,
+            SignScaling::Spectral,
+        ] {
+            let s = matrix_sign_newton(&a.view(), scaling, 100, 1e-14).unwrap();
+            assert_is_sign_of(&s, &a, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_matrix_sign_schur_matches_newton() {
+        let a = test_matrix();
+        let newton = matrix_sign(&a.view(), &MatrixSignOptions::default()).unwrap();
+        let schur = matrix_sign(
+            &a.view(),
+            &MatrixSignOptions {
+                method: SignMethod::Schur,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_is_sign_of(&schur, &a, 1e-10);
+        for (x, y) in newton.iter().zip(schur.iter()) {
+            assert!((x - y).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_matrix_sign_widely_spread_spectrum() {
+        let a = array![[1e6, 1.0, 0.0], [0.0, -1e-4, 1.0], [0.0, 0.0, 3.0]];
+        let s = matrix_sign_newton(&a.view(), SignScaling::Determinant, 30, 1e-14).unwrap();
+        assert_is_sign_of(&s, &a, 1e-8);
+        assert!((s[[1, 1]] + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_sign_imaginary_axis() {
+        let a = array![[0.0, 1.0], [-1.0, 0.0]];
+        assert!(matrix_sign_schur(&a.view()).is_err());
+        assert!(matrix_sign_newton(&a.view(), SignScaling::Determinant, 50, 1e-14).is_err());
+    }
+}
+
+#[cfg(test)]
+mod funm_tests {
+    use super::*;
+    use crate::basic::inv;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    fn assert_matrix_eq(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = tol);
+        }
+    }
+
+    #[test]
+    fn test_funm_exp_matches_taylor_series() {
+        let a = array![[1.0, 2.0, 0.5], [-0.3, 0.4, 1.0], [0.2, -1.0, -0.5]];
+        let f_a = funm(&a.view(), |z| z.exp()).unwrap();
+
+        let mut expected = Array2::<f64>::eye(3);
+        let mut term = Array2::<f64>::eye(3);
+        for k in 1..60 {
+            term = term.dot(&a) / k as f64;
+            expected = expected + &term;
+        }
+        assert_matrix_eq(&f_a, &expected, 1e-12);
+    }
+
+    #[test]
+    fn test_funm_rational() {
+        let a = array![[0.5, 1.0, 0.0], [0.0, 1.5, -0.5], [0.3, 0.0, 2.0]];
+        let one = Complex::new(1.0, 0.0);
+        let f_a = funm(&a.view(), |z| one / (one + z * z)).unwrap();
+
+        let expected = inv(&(Array2::eye(3) + a.dot(&a)).view(), None).unwrap();
+        assert_matrix_eq(&f_a, &expected, 1e-10);
+    }
+
+    #[test]
+    fn test_funm_rotation_generator() {
+        // exp([[0, 1], [-1, 0]]) = [[cos 1, sin 1], [-sin 1, cos 1]]
+        let a = array![[0.0, 1.0], [-1.0, 0.0]];
+        let f_a = funm(&a.view(), |z| z.exp()).unwrap();
+        let (s, c) = 1.0f64.sin_cos();
+        assert_matrix_eq(&f_a, &array![[c, s], [-s, c]], 1e-12);
+    }
+
+    #[test]
+    fn test_funm_jordan_block() {
+        // f(J) = [[f(2), f'(2), f''(2)/2], [0, f(2), f'(2)], [0, 0, f(2)]]
+        let a = array![[2.0, 1.0, 0.0], [0.0, 2.0, 1.0], [0.0, 0.0, 2.0]];
+        let f_a = funm(&a.view(), |z| z.exp()).unwrap();
+        let e2 = 2.0f64.exp();
+        let expected = array![[e2, e2, e2 / 2.0], [0.0, e2, e2], [0.0, 0.0, e2]];
+        assert_matrix_eq(&f_a, &expected, 1e-10);
+    }
+
+    #[test]
+    fn test_funm_clustered_eigenvalues() {
+        // Eigenvalues 1.0 and 1.05 fall into one cluster, 3.0 into another
+        let a = array![[1.0, 0.7, 0.2], [0.0, 1.05, -0.4], [0.0, 0.0, 3.0]];
+        let f_a = funm(&a.view(), |z| z.sqrt()).unwrap();
+        assert_matrix_eq(&f_a.dot(&f_a), &a, 1e-10);
+    }
+
+    #[test]
+    fn test_funm_complex_input() {
+        let a = array![
+            [Complex::new(1.0, 1.0), Complex::new(0.5, 0.0)],
+            [Complex::new(0.0, 0.0), Complex::new(-1.0, 0.5)]
+        ];
+        let f_a = funm_complex(&a.view(), |z| z * z).unwrap();
+        let expected = a.dot(&a);
+        for (x, y) in f_a.iter().zip(expected.iter()) {
+            assert!((x - y).norm() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_funm_rejects_non_real_result() {
+        let a = array![[1.0, 0.0], [0.0, 2.0]];
+        let result = funm(&a.view(), |z| z * Complex::new(0.0, 1.0));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_funm_singularity_in_spectrum() {
+        let a = array![[0.0, 1.0], [0.0, 0.0]];
+        let one = Complex::new(1.0, 0.0);
+        assert!(funm(&a.view(), |z| one / z).is_err());
+    }
+}
+
+#[cfg(test)]
+mod options_tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    #[test]
+    fn test_expm_with_options_tolerance_tradeoff() {
+        let a = array![[0.5, 2.0, 0.0], [-1.0, 0.25, 1.0], [0.0, -0.5, -1.0]];
+        // First row of exp(A) from the Taylor series in exact arithmetic
+        let exact_row = [0.40247430256324973, 1.9053912125560692, 0.7623801279067222];
+        let exact = expm(&a.view(), None).unwrap();
+        for (x, y) in exact.row(0).iter().zip(exact_row.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-14);
+        }
+        let (tight, tight_info) =
+            expm_with_options(&a.view(), &MatrixFunctionOptions::default()).unwrap();
+        for (x, y) in tight.iter().zip(exact.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-12);
+        }
+        assert!(tight_info.error_estimate <= 1e-13);
+
+        let options = MatrixFunctionOptions {
+            tol: 1e-4,
+            ..Default::default()
+        };
+        let (loose, info) = expm_with_options(&a.view(), &options).unwrap();
+        assert!(info.degree + info.scaling_power < tight_info.degree + tight_info.scaling_power);
+        for (x, y) in loose.iter().zip(exact.iter()) {
+            assert!((x - y).abs() < 1e-3);
+        }
+        let shorthand = expm_with_tolerance(&a.view(), 1e-4).unwrap();
+        assert_eq!(shorthand, loose);
+
+        let bad = MatrixFunctionOptions {
+            tol: 0.0,
+            ..Default::default()
+        };
+        assert!(expm_with_options(&a.view(), &bad).is_err());
+    }
+
+    #[test]
+    fn test_logm_with_options() {
+        let a = array![[0.5, 2.0, 0.0], [-1.0, 0.25, 1.0], [0.0, -0.5, -1.0]];
+        let e = expm(&a.view(), None).unwrap();
+        for method in [MatrixFunctionMethod::Schur, MatrixFunctionMethod::Iterative] {
+            let options = MatrixFunctionOptions {
+                method,
+                ..Default::default()
+            };
+            let (l, info) = logm_with_options(&e.view(), &options).unwrap();
+            for (x, y) in l.iter().zip(a.iter()) {
+                assert_relative_eq!(*x, *y, epsilon = 1e-10);
+            }
+            assert!(info.scaling_power > 0);
+            assert_eq!(
+                info.iterations > 0,
+                method == MatrixFunctionMethod::Iterative
+            );
+        }
+
+        // A loose tolerance needs fewer series terms
+        let (_, tight) = logm_with_options(&e.view(), &MatrixFunctionOptions::default()).unwrap();
+        let loose_options = MatrixFunctionOptions {
+            tol: 1e-4,
+            ..Default::default()
+        };
+        let (_, loose) = logm_with_options(&e.view(), &loose_options).unwrap();
+        assert!(loose.degree < tight.degree);
+
+        let negative = array![[-1.0, 0.0], [0.0, 2.0]];
+        assert!(logm_with_options(&negative.view(), &MatrixFunctionOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_sqrtm_and_signm_with_options() {
+        let a = array![[4.0, 1.0, 0.0], [1.0, 5.0, 1.0], [0.0, 1.0, 6.0]];
+        for method in [
+            MatrixFunctionMethod::Auto,
+            MatrixFunctionMethod::Schur,
+            MatrixFunctionMethod::Iterative,
+        ] {
+            let options = MatrixFunctionOptions {
+                method,
+                ..Default::default()
+            };
+            let (x, info) = sqrtm_with_options(&a.view(), &options).unwrap();
+            assert!(info.error_estimate < 1e-13);
+            assert_eq!(info.iterations == 0, method == MatrixFunctionMethod::Schur);
+            let x2 = x.dot(&x);
+            for (p, q) in x2.iter().zip(a.iter()) {
+                assert_relative_eq!(*p, *q, epsilon = 1e-12);
+            }
+        }
+
+        let b = array![
+            [3.0, 1.0, -2.0, 0.5],
+            [0.2, -4.0, 1.0, 0.0],
+            [1.0, 0.3, 0.5, 2.0],
+            [0.0, 1.0, -1.5, -0.7]
+        ];
+        let (newton, info) =
+            signm_with_options(&b.view(), &MatrixFunctionOptions::default()).unwrap();
+        assert!(info.iterations > 0 && info.error_estimate < 1e-12);
+        let options = MatrixFunctionOptions {
+            method: MatrixFunctionMethod::Schur,
+            ..Default::default()
+        };
+        let (schur, info) = signm_with_options(&b.view(), &options).unwrap();
+        assert_eq!(info.iterations, 0);
+        for (p, q) in newton.iter().zip(schur.iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-10);
+        }
+
+        // Too few iterations fail instead of returning an inaccurate result
+        let options = MatrixFunctionOptions {
+            max_iter: 1,
+            ..Default::default()
+        };
+        assert!(signm_with_options(&b.view(), &options).is_err());
+    }
+}
@@ -1,11 +1,12 @@
 //! Matrix and vector norms
 
-use ndarray::{ArrayView1, ArrayView2};
+use ndarray::{Array2, ArrayView1, ArrayView2};
 use num_traits::{Float, NumAssign};
 use std::iter::Sum;
 
 use crate::decomposition::svd;
 use crate::error::{LinalgError, LinalgResult};
+use crate::factorization::LuFactorization;
 use crate::validation::{
     validate_finite_matrix, validate_finite_vector, validate_not_empty_matrix,
     validate_not_empty_vector,
@@ -198,57 +199,29 @@ where
     let norm_type = p.unwrap_or("2");
 
     match norm_type {
-        "2" | "fro" | "f" | "frobenius" => {
-            // Use SVD for 2-norm and Frobenius norm condition number
-            let (_u, s, _vt) = svd(a, false, workers)?;
-
-            if s.is_empty() {
-                return Ok(F::infinity());
-            }
-
-            // Find the largest and smallest non-zero singular values
-            let sigma_max = s[0]; // Largest singular value (first in sorted order)
-            let mut sigma_min = F::zero();
-
-            // Find the smallest non-zero singular value
-            for &val in s.iter().rev() {
-                if val > F::epsilon() * F::from(100).unwrap() * sigma_max {
-                    sigma_min = val;
-                    break;
-                }
-            }
-
+        "2" => {
+            // One-sided Jacobi gives singular values with high relative
+            // accuracy, so the ratio stays meaningful for ill-conditioned input
+            let _ = workers;
+            let (_u, s, _vt) = crate::solve::jacobi_svd(a);
+            let sigma_max = s[0];
+            let sigma_min = s[s.len() - 1];
             if sigma_min <= F::zero() {
                 Ok(F::infinity())
             } else {
                 Ok(sigma_max / sigma_min)
             }
         }
-        "1" | "inf" => {
-            // For 1-norm and inf-norm, we need matrix inverse
-            // This is more complex and would require computing the inverse
-            // For now, fall back to SVD-based computation
-            let (_u, s, _vt) = svd(a, false, workers)?;
-
-            if s.is_empty() {
+        "1" | "inf" | "fro" | "f" | "frobenius" => {
+            // ||A|| * ||A^-1|| with the inverse formed explicitly; use
+            // `condest` for an O(n^2)-per-step estimate of the 1- and inf-norms
+            let Ok(lu) = LuFactorization::new(a) else {
                 return Ok(F::infinity());
-            }
-
-            let sigma_max = s[0];
-            let mut sigma_min = F::zero();
-
-            for &val in s.iter().rev() {
-                if val > F::epsilon() * F::from(100).unwrap() * sigma_max {
-                    sigma_min = val;
-                    break;
-                }
-            }
-
-            if sigma_min <= F::zero() {
-                Ok(F::infinity())
-            } else {
-                Ok(sigma_max / sigma_min)
-            }
+            };
+            let inv_a = lu.inv();
+            let norm_a = matrix_norm(a, norm_type, workers)?;
+            let norm_inv = matrix_norm(&inv_a.view(), norm_type, workers)?;
+            Ok(norm_a * norm_inv)
         }
         _ => Err(LinalgError::InvalidInputError(format!(
             "Condition number computation failed: Invalid norm type '{}'\nSupported norms: '1', '2' (default), 'fro', 'f', 'frobenius', 'inf'",
@@ -326,6 +299,385 @@ where
     Ok(rank)
 }
 
+/// Iteration limit of the block 1-norm estimator
+const ONENORMEST_MAX_ITER: usize = 5;
+
+/// Deterministic stream of ±1 entries for the estimator's starting vectors
+struct SignStream(u64);
+
+impl SignStream {
+    fn next_sign<F: Float>(&mut self) -> F {
+        // SplitMix64
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        if (z ^ (z >> 31)) & 1 == 0 {
+            F::one()
+        } else {
+            -F::one()
+        }
+    }
+}
+
+/// Whether two ±1 vectors are parallel
+fn is_parallel<F: Float>(a: ArrayView1<F>, b: ArrayView1<F>) -> bool {
+    let dot = a
+        .iter()
+        .zip(b.iter())
+        .fold(F::zero(), |acc, (&x, &y)| acc + x * y);
+    dot.abs() == F::from(a.len()).unwrap()
+}
+
+/// Whether ±1 column `j` of `s` is parallel to any column of `others`
+/// (or to an earlier column of `s` itself)
+fn parallel_to_any<F: Float>(s: &Array2<F>, j: usize, others: Option<&Array2<F>>) -> bool {
+    let col = s.column(j);
+    let parallel = |other: ArrayView1<F>| is_parallel(col, other);
+    (0..j).any(|k| parallel(s.column(k)))
+        || others.is_some_and(|o| o.columns().into_iter().any(parallel))
+}
+
+/// Estimate the 1-norm of a linear operator given only products with it
+///
+/// Implements the block 1-norm estimator of Higham and Tisseur (2000), as
+/// used by `scipy.sparse.linalg.onenormest`. Each iteration costs one
+/// product with the operator and one with its transpose, applied to blocks
+/// of `t` columns. The estimate is a lower bound that is exact in most
+/// cases; larger `t` makes it more reliable. For `t >= n` the norm is
+/// computed exactly from `n` columns.
+///
+/// # Arguments
+///
+/// * `n` - Order of the (square) operator
+/// * `t` - Number of columns per block (2 is the usual choice)
+/// * `matmat` - Computes `A X` for an `n x t` block `X`
+/// * `rmatmat` - Computes `A^T X` for an `n x t` block `X`
+///
+/// # Returns
+///
+/// * Estimate of `||A||_1`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::{array, Array2, ArrayView2};
+/// use scirs2_linalg::onenormest_op;
+///
+/// let a = array![[1.0_f64, -2.0, 0.0], [3.0, 4.0, 1.0], [0.0, 5.0, -6.0]];
+/// let est = onenormest_op(
+///     3,
+///     2,
+///     |x: &ArrayView2<f64>| Ok(a.dot(x)),
+///     |x: &ArrayView2<f64>| Ok(a.t().dot(x)),
+/// )
+/// .unwrap();
+/// assert!((est - 11.0).abs() < 1e-12);
+/// ```
+pub fn onenormest_op<F, M, MT>(n: usize, t: usize, matmat: M, rmatmat: MT) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum,
+    M: Fn(&ArrayView2<F>) -> LinalgResult<Array2<F>>,
+    MT: Fn(&ArrayView2<F>) -> LinalgResult<Array2<F>>,
+{
+    if n == 0 {
+        return Err(LinalgError::ShapeError(
+            "1-norm estimation failed: Operator must have at least one row".to_string(),
+        ));
+    }
+    if t == 0 {
+        return Err(LinalgError::InvalidInputError(
+            "1-norm estimation failed: Block size t must be positive".to_string(),
+        ));
+    }
+    let column_norms = |y: &Array2<F>| -> Vec<F> {
+        y.columns()
+            .into_iter()
+            .map(|c| c.iter().fold(F::zero(), |acc, &v| acc + v.abs()))
+            .collect()
+    };
+
+    if t >= n {
+        let y = matmat(&Array2::eye(n).view())?;
+        return Ok(column_norms(&y)
+            .into_iter()
+            .fold(F::zero(), |acc, v| acc.max(v)));
+    }
+
+    let mut signs = SignStream(0x5EED_0000 ^ n as u64);
+    let n_f = F::from(n).unwrap();
+
+    // Starting block: ones, then random ±1 columns not parallel to earlier ones
+    let mut x = Array2::from_elem((n, t), F::one());
+    for j in 1..t {
+        for _ in 0..n.max(8) {
+            x.column_mut(j)
+                .iter_mut()
+                .for_each(|v| *v = signs.next_sign());
+            if !parallel_to_any(&x, j, None) {
+                break;
+            }
+        }
+    }
+    x.mapv_inplace(|v| v / n_f);
+
+    let mut estimate = F::zero();
+    let mut visited = vec![false; n];
+    // Unit-vector index behind each column of the current block
+    let mut block_indices: Option<Vec<usize>> = None;
+    let mut best_index: Option<usize> = None;
+    let mut s_old: Option<Array2<F>> = None;
+
+    for iteration in 0..ONENORMEST_MAX_ITER {
+        let y = matmat(&x.view())?;
+        let (best_col, est) =
+            column_norms(&y)
+                .into_iter()
+                .enumerate()
+                .fold(
+                    (0, F::zero()),
+                    |best, (j, v)| if v > best.1 { (j, v) } else { best },
+                );
+
+        if iteration > 0 && est <= estimate {
+            break;
+        }
+        estimate = est;
+        if let Some(indices) = &block_indices {
+            best_index = Some(indices[best_col]);
+        }
+        if iteration + 1 == ONENORMEST_MAX_ITER {
+            break;
+        }
+
+        let mut s = y.mapv(|v| if v >= F::zero() { F::one() } else { -F::one() });
+        if let Some(old) = &s_old {
+            if (0..t).all(|j| {
+                old.columns()
+                    .into_iter()
+                    .any(|o| is_parallel(s.column(j), o))
+            }) {
+                // Every sign pattern has been seen already
+                break;
+            }
+        }
+        // Resample repeated sign patterns so the block explores new directions
+        for j in 0..t {
+            for _ in 0..n.max(8) {
+                if !parallel_to_any(&s, j, s_old.as_ref()) {
+                    break;
+                }
+                s.column_mut(j)
+                    .iter_mut()
+                    .for_each(|v| *v = signs.next_sign());
+            }
+        }
+
+        let z = rmatmat(&s.view())?;
+        let h: Vec<F> = z
+            .rows()
+            .into_iter()
+            .map(|r| r.iter().fold(F::zero(), |acc, &v| acc.max(v.abs())))
+            .collect();
+        let h_max = h.iter().fold(F::zero(), |acc, &v| acc.max(v));
+        if best_index.is_some_and(|i| h[i] == h_max) {
+            break;
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| h[j].partial_cmp(&h[i]).unwrap_or(std::cmp::Ordering::Equal));
+        if order[..t].iter().all(|&i| visited[i]) {
+            break;
+        }
+        let next: Vec<usize> = order.into_iter().filter(|&i| !visited[i]).take(t).collect();
+        if next.is_empty() {
+            break;
+        }
+
+        x = Array2::zeros((n, next.len()));
+        for (j, &i) in next.iter().enumerate() {
+            x[[i, j]] = F::one();
+            visited[i] = true;
+        }
+        block_indices = Some(next);
+        s_old = Some(s);
+    }
+
+    Ok(estimate)
+}
+
+/// Estimate `||A^-1||_1` without forming the inverse
+///
+/// `A` is factored once by LU with partial pivoting; the estimator then only
+/// needs triangular solves with the factors, O(n^2) per iteration.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `t` - Number of columns per block (None = 2)
+///
+/// # Returns
+///
+/// * Estimate of `||A^-1||_1`, or infinity if `A` is exactly singular
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::onenormest_inv;
+///
+/// let a = array![[4.0_f64, 1.0], [2.0, 3.0]];
+/// // A^-1 = [[0.3, -0.1], [-0.2, 0.4]]
+/// let est = onenormest_inv(&a.view(), None).unwrap();
+/// assert!((est - 0.5).abs() < 1e-12);
+/// ```
+pub fn onenormest_inv<F>(a: &ArrayView2<F>, t: Option<usize>) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    inverse_norm_estimate(a, t, false, "Inverse 1-norm estimation")
+}
+
+/// Estimate `||A^p||_1` without forming the power
+///
+/// Products with `A^p` are applied as `p` successive products with `A`, so
+/// each estimator iteration costs O(p n^2 t) instead of the O(log(p) n^3) of
+/// forming the power.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `p` - Power
+/// * `t` - Number of columns per block (None = 2)
+///
+/// # Returns
+///
+/// * Estimate of `||A^p||_1`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::onenormest_power;
+///
+/// let a = array![[0.0_f64, 2.0], [0.0, 0.0]];
+/// // A is nilpotent: ||A||_1 = 2 but A^2 = 0
+/// assert!((onenormest_power(&a.view(), 1, None).unwrap() - 2.0).abs() < 1e-12);
+/// assert_eq!(onenormest_power(&a.view(), 2, None).unwrap(), 0.0);
+/// ```
+pub fn onenormest_power<F>(a: &ArrayView2<F>, p: usize, t: Option<usize>) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    use crate::validation::validate_square_matrix;
+    validate_not_empty_matrix(a, "Matrix power 1-norm estimation")?;
+    validate_square_matrix(a, "Matrix power 1-norm estimation")?;
+    validate_finite_matrix(a, "Matrix power 1-norm estimation")?;
+
+    // Explicit loops keep the bound free of `'static` (unlike `dot`)
+    let power = |transpose: bool, x: &ArrayView2<F>| {
+        let n = a.nrows();
+        let mut y = x.to_owned();
+        for _ in 0..p {
+            let mut next = Array2::zeros(y.dim());
+            for i in 0..n {
+                for k in 0..n {
+                    let aik = if transpose { a[[k, i]] } else { a[[i, k]] };
+                    if aik != F::zero() {
+                        for j in 0..y.ncols() {
+                            next[[i, j]] += aik * y[[k, j]];
+                        }
+                    }
+                }
+            }
+            y = next;
+        }
+        Ok(y)
+    };
+    onenormest_op(
+        a.nrows(),
+        t.unwrap_or(2),
+        |x: &ArrayView2<F>| power(false, x),
+        |x: &ArrayView2<F>| power(true, x),
+    )
+}
+
+/// Estimate the condition number in the 1- or infinity-norm
+///
+/// Computes `||A|| * est(||A^-1||)` from one LU factorization and a handful
+/// of triangular solves, which is far cheaper than `cond` for large
+/// matrices. The estimate is a lower bound that is usually exact or within a
+/// small factor.
+///
+/// # Arguments
+///
+/// * `a` - Input square matrix
+/// * `p` - Order of the norm: None or "1" for the 1-norm, "inf" for the infinity norm
+///
+/// # Returns
+///
+/// * Estimated condition number, or infinity if `A` is exactly singular
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{cond, condest};
+///
+/// let a = array![[4.0_f64, 1.0, 0.0], [1.0, 4.0, 1.0], [0.0, 1.0, 4.0]];
+/// let est = condest(&a.view(), None).unwrap();
+/// let exact = cond(&a.view(), Some("1"), None).unwrap();
+/// assert!(est <= exact * (1.0 + 1e-12) && est >= exact / 3.0);
+/// ```
+pub fn condest<F>(a: &ArrayView2<F>, p: Option<&str>) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum + ndarray::ScalarOperand,
+{
+    let norm_type = p.unwrap_or("1");
+    // ||A^-1||_inf = ||A^-T||_1
+    let transpose = match norm_type {
+        "1" => false,
+        "inf" => true,
+        _ => {
+            return Err(LinalgError::InvalidInputError(format!(
+                "Condition number estimation failed: Invalid norm type '{}'\nSupported norms: '1' (default), 'inf'",
+                norm_type
+            )))
+        }
+    };
+    let inv_norm = inverse_norm_estimate(a, None, transpose, "Condition number estimation")?;
+    if inv_norm.is_infinite() {
+        return Ok(inv_norm);
+    }
+    Ok(matrix_norm(a, norm_type, None)? * inv_norm)
+}
+
+/// Shared driver for `onenormest_inv` and `condest`
+fn inverse_norm_estimate<F>(
+    a: &ArrayView2<F>,
+    t: Option<usize>,
+    transpose: bool,
+    context: &str,
+) -> LinalgResult<F>
+where
+    F: Float + NumAssign + Sum,
+{
+    use crate::validation::validate_square_matrix;
+    validate_not_empty_matrix(a, context)?;
+    validate_square_matrix(a, context)?;
+    validate_finite_matrix(a, context)?;
+
+    let Ok(lu) = LuFactorization::new(a) else {
+        return Ok(F::infinity());
+    };
+    onenormest_op(
+        a.nrows(),
+        t.unwrap_or(2),
+        |x: &ArrayView2<F>| Ok(lu.solve_unchecked(x, transpose)),
+        |x: &ArrayView2<F>| Ok(lu.solve_unchecked(x, !transpose)),
+    )
+}
+
 // Backward compatibility functions (deprecated)
 
 /// Compute a matrix norm without workers parameter (deprecated - use matrix_norm with workers)
@@ -414,4 +766,95 @@ mod tests {
         // max(|1|, |-5|, |3|) = 5
         assert_relative_eq!(norm, 5.0);
     }
+
+    #[test]
+    fn test_cond_norm_orders() {
+        let a = array![[4.0, 2.0], [1.0, 3.0]];
+        // A^-1 = [[0.3, -0.2], [-0.1, 0.4]]
+        assert_relative_eq!(
+            cond(&a.view(), Some("1"), None).unwrap(),
+            5.0 * 0.6,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            cond(&a.view(), Some("inf"), None).unwrap(),
+            6.0 * 0.5,
+            epsilon = 1e-12
+        );
+        let fro = (30.0f64).sqrt() * (0.3f64.powi(2) + 0.04 + 0.01 + 0.16).sqrt();
+        assert_relative_eq!(
+            cond(&a.view(), Some("fro"), None).unwrap(),
+            fro,
+            epsilon = 1e-12
+        );
+
+        // Widely separated singular values survive the 2-norm computation
+        let d = array![[1e8, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1e-6]];
+        assert_relative_eq!(
+            cond(&d.view(), None, None).unwrap(),
+            1e14,
+            max_relative = 1e-10
+        );
+
+        let singular = array![[1.0, 2.0], [2.0, 4.0]];
+        assert!(cond(&singular.view(), Some("1"), None)
+            .unwrap()
+            .is_infinite());
+        assert!(cond(&a.view(), Some("nuc"), None).is_err());
+    }
+
+    /// Non-normal test matrix with decaying off-diagonal structure
+    fn test_matrix(n: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                3.0 + (i % 3) as f64
+            } else {
+                ((i * 7 + j * 13) % 11) as f64 / (1.0 + (i as f64 - j as f64).abs()) - 2.0
+            }
+        })
+    }
+
+    #[test]
+    fn test_onenormest_inv_and_condest() {
+        for n in [1, 2, 5, 30] {
+            let a = test_matrix(n);
+            let inv_a = crate::basic::inv(&a.view(), None).unwrap();
+            let exact = matrix_norm(&inv_a.view(), "1", None).unwrap();
+            let est = onenormest_inv(&a.view(), None).unwrap();
+            // A lower bound that is close to the true value
+            assert!(
+                est <= exact * (1.0 + 1e-10),
+                "n = {}: {} > {}",
+                n,
+                est,
+                exact
+            );
+            assert!(est >= exact / 3.0, "n = {}: {} << {}", n, est, exact);
+
+            let exact_inf = cond(&a.view(), Some("inf"), None).unwrap();
+            let est_inf = condest(&a.view(), Some("inf")).unwrap();
+            assert!(est_inf <= exact_inf * (1.0 + 1e-10) && est_inf >= exact_inf / 3.0);
+        }
+
+        let singular = array![[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 0.0, 1.0]];
+        assert!(condest(&singular.view(), None).unwrap().is_infinite());
+        assert!(condest(&test_matrix(3).view(), Some("fro")).is_err());
+    }
+
+    #[test]
+    fn test_onenormest_power() {
+        let a = test_matrix(25) / 10.0;
+        let mut power = Array2::eye(25);
+        for p in 0..5 {
+            let exact = matrix_norm(&power.view(), "1", None).unwrap();
+            let est = onenormest_power(&a.view(), p, None).unwrap();
+            assert!(est <= exact * (1.0 + 1e-10) && est >= exact / 3.0);
+            power = power.dot(&a);
+        }
+
+        // A block at least as wide as the matrix gives the exact norm
+        let b = test_matrix(4);
+        let exact = matrix_norm(&b.view(), "1", None).unwrap();
+        assert_relative_eq!(onenormest_power(&b.view(), 1, Some(4)).unwrap(), exact);
+    }
 }
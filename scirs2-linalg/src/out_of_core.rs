@@ -0,0 +1,923 @@
+//! Tiled out-of-core matrix multiplication and factorization
+//!
+//! [`OutOfCoreMatrix`] stores a dense matrix on disk as square tiles, so the
+//! matrix itself never has to fit in RAM. Operations stream tiles and
+//! column panels through memory:
+//!
+//! * [`OutOfCoreMatrix::matmul`]: tiled GEMM, one output tile per task
+//! * [`OutOfCoreMatrix::lu_in_place`]: right-looking blocked LU with partial
+//!   pivoting, holding one factored column panel plus one panel per worker
+//! * [`OutOfCoreMatrix::cholesky_in_place`]: right-looking blocked Cholesky
+//!   with the same memory footprint
+//!
+//! Each tile is stored contiguously in row-major order, so loading a tile is
+//! a single positioned read. Tasks run on a pool of `workers` threads, each
+//! with its own file handle. With tile size `t` and `n` rows, a
+//! factorization keeps roughly `(workers + 1) * n * t` elements resident.
+//!
+//! # Examples
+//!
+//! ```
+//! use ndarray::array;
+//! use scirs2_linalg::out_of_core::{OutOfCoreConfig, OutOfCoreMatrix};
+//!
+//! let config = OutOfCoreConfig::default().with_tile_size(2).with_workers(2);
+//! let a = array![[4.0_f64, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]];
+//!
+//! // Temporary backing file, removed when the matrix is dropped
+//! let disk = OutOfCoreMatrix::from_array(&a.view(), None, config).unwrap();
+//! let product = disk.matmul(&disk, None).unwrap().to_array().unwrap();
+//! assert!((product[[0, 0]] - 17.0).abs() < 1e-12);
+//!
+//! let mut factor = OutOfCoreMatrix::from_array(&a.view(), None, config).unwrap();
+//! factor.cholesky_in_place().unwrap();
+//! let x = factor.cholesky_solve(&array![[1.0], [2.0], [3.0]].view()).unwrap();
+//! let residual = a.dot(&x) - array![[1.0], [2.0], [3.0]];
+//! assert!(residual.iter().all(|r| r.abs() < 1e-12));
+//! ```
+
+use crate::error::{LinalgError, LinalgResult};
+use ndarray::{s, Array2, ArrayView2, ArrayViewMut2};
+use num_traits::{Float, NumAssign};
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::iter::Sum;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Element types that can be stored in an [`OutOfCoreMatrix`]
+pub trait OutOfCoreScalar:
+    Float + NumAssign + Sum + Debug + Send + Sync + ndarray::ScalarOperand + 'static
+{
+    /// Size of one element in bytes
+    const BYTES: usize;
+
+    /// Write the little-endian representation into `out`
+    fn write_le(self, out: &mut [u8]);
+
+    /// Read a value from its little-endian representation
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_out_of_core_scalar {
+    ($t:ty) => {
+        impl OutOfCoreScalar for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+
+            fn write_le(self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Self {
+                let mut buf = [0u8; std::mem::size_of::<$t>()];
+                buf.copy_from_slice(bytes);
+                <$t>::from_le_bytes(buf)
+            }
+        }
+    };
+}
+
+impl_out_of_core_scalar!(f32);
+impl_out_of_core_scalar!(f64);
+
+/// Configuration for out-of-core operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfCoreConfig {
+    /// Edge length of the square tiles
+    pub tile_size: usize,
+    /// Number of worker threads
+    pub workers: usize,
+}
+
+impl Default for OutOfCoreConfig {
+    fn default() -> Self {
+        Self {
+            tile_size: 1024,
+            workers: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+        }
+    }
+}
+
+impl OutOfCoreConfig {
+    /// Set the tile size
+    pub fn with_tile_size(mut self, tile_size: usize) -> Self {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// Set the number of worker threads
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    fn validate(&self) -> LinalgResult<()> {
+        if self.tile_size == 0 {
+            return Err(LinalgError::InvalidInputError(
+                "Out-of-core tile size must be positive".to_string(),
+            ));
+        }
+        if self.workers == 0 {
+            return Err(LinalgError::InvalidInputError(
+                "Out-of-core worker count must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+static TEMPORARY_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+fn io_error(action: &str, path: &Path, err: std::io::Error) -> LinalgError {
+    LinalgError::ComputationError(format!(
+        "Out-of-core {} failed for {}: {}",
+        action,
+        path.display(),
+        err
+    ))
+}
+
+/// Dense matrix stored on disk as row-major tiles
+///
+/// Tile `(bi, bj)` covers rows `bi * t..min((bi + 1) * t, nrows)` and columns
+/// `bj * t..min((bj + 1) * t, ncols)` for tile size `t`. Matrices created
+/// without a path live in a temporary file that is removed on drop.
+#[derive(Debug)]
+pub struct OutOfCoreMatrix<F: OutOfCoreScalar> {
+    path: PathBuf,
+    shape: (usize, usize),
+    config: OutOfCoreConfig,
+    temporary: bool,
+    _marker: PhantomData<F>,
+}
+
+impl<F: OutOfCoreScalar> Drop for OutOfCoreMatrix<F> {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+impl<F: OutOfCoreScalar> OutOfCoreMatrix<F> {
+    /// Create a zero-filled matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - Matrix shape `(nrows, ncols)`
+    /// * `path` - Backing file, or `None` for a temporary file
+    /// * `config` - Tile size and worker count
+    pub fn zeros(
+        shape: (usize, usize),
+        path: Option<&Path>,
+        config: OutOfCoreConfig,
+    ) -> LinalgResult<Self> {
+        config.validate()?;
+        let (path, temporary) = match path {
+            Some(p) => (p.to_path_buf(), false),
+            None => {
+                let id = TEMPORARY_COUNTER.fetch_add(1, Ordering::Relaxed);
+                let name = format!("scirs2-linalg-ooc-{}-{}.bin", std::process::id(), id);
+                (std::env::temp_dir().join(name), true)
+            }
+        };
+        let matrix = OutOfCoreMatrix {
+            path,
+            shape,
+            config,
+            temporary,
+            _marker: PhantomData,
+        };
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&matrix.path)
+            .map_err(|e| io_error("create", &matrix.path, e))?;
+        file.set_len((shape.0 * shape.1 * F::BYTES) as u64)
+            .map_err(|e| io_error("allocate", &matrix.path, e))?;
+        Ok(matrix)
+    }
+
+    /// Write an in-memory matrix to disk
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Matrix to store
+    /// * `path` - Backing file, or `None` for a temporary file
+    /// * `config` - Tile size and worker count
+    pub fn from_array(
+        a: &ArrayView2<F>,
+        path: Option<&Path>,
+        config: OutOfCoreConfig,
+    ) -> LinalgResult<Self> {
+        let matrix = Self::zeros(a.dim(), path, config)?;
+        let mut file = matrix.open_handle()?;
+        let (tile_rows, tile_cols) = matrix.tile_grid();
+        for bi in 0..tile_rows {
+            for bj in 0..tile_cols {
+                let (rows, cols) = matrix.tile_range(bi, bj);
+                matrix.write_tile_with(&mut file, bi, bj, &a.slice(s![rows, cols]))?;
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Open an existing backing file written with the same shape and tile size
+    pub fn open(path: &Path, shape: (usize, usize), config: OutOfCoreConfig) -> LinalgResult<Self> {
+        config.validate()?;
+        let len = std::fs::metadata(path)
+            .map_err(|e| io_error("open", path, e))?
+            .len();
+        if len != (shape.0 * shape.1 * F::BYTES) as u64 {
+            return Err(LinalgError::ShapeError(format!(
+                "Backing file {} has {} bytes, expected {} for a {}x{} matrix",
+                path.display(),
+                len,
+                shape.0 * shape.1 * F::BYTES,
+                shape.0,
+                shape.1
+            )));
+        }
+        Ok(OutOfCoreMatrix {
+            path: path.to_path_buf(),
+            shape,
+            config,
+            temporary: false,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Matrix shape `(nrows, ncols)`
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Tile size and worker configuration
+    pub fn config(&self) -> OutOfCoreConfig {
+        self.config
+    }
+
+    /// Number of tiles along each dimension
+    pub fn tile_grid(&self) -> (usize, usize) {
+        let t = self.config.tile_size;
+        (self.shape.0.div_ceil(t), self.shape.1.div_ceil(t))
+    }
+
+    /// Read tile `(bi, bj)` into memory
+    pub fn read_tile(&self, bi: usize, bj: usize) -> LinalgResult<Array2<F>> {
+        self.check_tile(bi, bj)?;
+        self.read_tile_with(&mut self.open_handle()?, bi, bj)
+    }
+
+    /// Overwrite tile `(bi, bj)`
+    pub fn write_tile(&mut self, bi: usize, bj: usize, tile: &ArrayView2<F>) -> LinalgResult<()> {
+        self.check_tile(bi, bj)?;
+        let (rows, cols) = self.tile_range(bi, bj);
+        if tile.dim() != (rows.len(), cols.len()) {
+            return Err(LinalgError::ShapeError(format!(
+                "Tile ({}, {}) has shape {}x{}, got {}x{}",
+                bi,
+                bj,
+                rows.len(),
+                cols.len(),
+                tile.nrows(),
+                tile.ncols()
+            )));
+        }
+        self.write_tile_with(&mut self.open_handle()?, bi, bj, tile)
+    }
+
+    /// Load the whole matrix into memory
+    pub fn to_array(&self) -> LinalgResult<Array2<F>> {
+        let mut result = Array2::zeros(self.shape);
+        let mut file = self.open_handle()?;
+        let (tile_rows, tile_cols) = self.tile_grid();
+        for bi in 0..tile_rows {
+            for bj in 0..tile_cols {
+                let (rows, cols) = self.tile_range(bi, bj);
+                let tile = self.read_tile_with(&mut file, bi, bj)?;
+                result.slice_mut(s![rows, cols]).assign(&tile);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Tiled matrix product `self * other`
+    ///
+    /// Each output tile is accumulated by one worker from a row of tiles of
+    /// `self` and a column of tiles of `other`, so at most three tiles per
+    /// worker are resident. Both operands must use the same tile size; the
+    /// result uses the configuration of `self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Right operand
+    /// * `path` - Backing file of the result, or `None` for a temporary file
+    pub fn matmul(&self, other: &OutOfCoreMatrix<F>, path: Option<&Path>) -> LinalgResult<Self> {
+        if self.shape.1 != other.shape.0 {
+            return Err(LinalgError::ShapeError(format!(
+                "Cannot multiply {}x{} by {}x{}",
+                self.shape.0, self.shape.1, other.shape.0, other.shape.1
+            )));
+        }
+        if self.config.tile_size != other.config.tile_size {
+            return Err(LinalgError::InvalidInputError(format!(
+                "Tile sizes differ: {} and {}",
+                self.config.tile_size, other.config.tile_size
+            )));
+        }
+
+        let result = Self::zeros((self.shape.0, other.shape.1), path, self.config)?;
+        let (tile_rows, inner) = self.tile_grid();
+        let tile_cols = other.tile_grid().1;
+        run_tasks(self.config.workers, tile_rows * tile_cols, |task| {
+            let (bi, bj) = (task / tile_cols, task % tile_cols);
+            let mut a_file = self.open_handle()?;
+            let mut b_file = other.open_handle()?;
+            let (rows, cols) = result.tile_range(bi, bj);
+            let mut acc = Array2::<F>::zeros((rows.len(), cols.len()));
+            for bk in 0..inner {
+                let a = self.read_tile_with(&mut a_file, bi, bk)?;
+                let b = other.read_tile_with(&mut b_file, bk, bj)?;
+                ndarray::linalg::general_mat_mul(F::one(), &a, &b, F::one(), &mut acc);
+            }
+            result.write_tile_with(&mut result.open_handle()?, bi, bj, &acc.view())
+        })?;
+        Ok(result)
+    }
+
+    /// Blocked LU decomposition with partial pivoting, in place
+    ///
+    /// On return the matrix holds `L` (unit lower, diagonal not stored) and
+    /// `U` packed together, and `P A = L U` where row `i` of `P A` is row
+    /// `perm[i]` of `A`. Column panels of width `tile_size` are factored in
+    /// memory; the row swaps and trailing updates of the other panels are
+    /// distributed over the workers.
+    ///
+    /// # Returns
+    ///
+    /// * The row permutation `perm`
+    pub fn lu_in_place(&mut self) -> LinalgResult<Vec<usize>> {
+        let n = self.check_square("LU decomposition")?;
+        let tiles = self.tile_grid().1;
+        let mut perm: Vec<usize> = (0..n).collect();
+
+        for k in 0..tiles {
+            let k0 = k * self.config.tile_size;
+            let mut panel = self.read_panel(k, k)?;
+            let pivots = factor_lu_panel(&mut panel.view_mut()).map_err(|step| {
+                LinalgError::singular_matrix_with_suggestions(
+                    &format!("out-of-core LU (column {})", k0 + step),
+                    self.shape,
+                    None,
+                )
+            })?;
+            for (c, &p) in pivots.iter().enumerate() {
+                perm.swap(k0 + c, k0 + p);
+            }
+            self.write_panel(k, k, &panel.view())?;
+
+            let width = pivots.len();
+            let l_top = panel.slice(s![..width, ..]);
+            let l_below = panel.slice(s![width.., ..]);
+            let others: Vec<usize> = (0..tiles).filter(|&j| j != k).collect();
+            let this = &*self;
+            run_tasks(self.config.workers, others.len(), |task| {
+                let j = others[task];
+                let mut q = this.read_panel(k, j)?;
+                for (c, &p) in pivots.iter().enumerate() {
+                    if p != c {
+                        for col in 0..q.ncols() {
+                            q.swap([c, col], [p, col]);
+                        }
+                    }
+                }
+                if j > k {
+                    // U_kj = L_kk^{-1} A_kj, then the Schur complement update
+                    let (mut top, mut below) = q.view_mut().split_at(ndarray::Axis(0), width);
+                    for r in 1..width {
+                        for c in 0..r {
+                            let l = l_top[[r, c]];
+                            for col in 0..top.ncols() {
+                                let v = top[[c, col]];
+                                top[[r, col]] -= l * v;
+                            }
+                        }
+                    }
+                    ndarray::linalg::general_mat_mul(
+                        -F::one(),
+                        &l_below,
+                        &top,
+                        F::one(),
+                        &mut below,
+                    );
+                }
+                this.write_panel(k, j, &q.view())
+            })?;
+        }
+        Ok(perm)
+    }
+
+    /// Solve `A X = B` using the factors from [`lu_in_place`](Self::lu_in_place)
+    ///
+    /// # Arguments
+    ///
+    /// * `perm` - Row permutation returned by `lu_in_place`
+    /// * `b` - Right-hand sides held in memory
+    pub fn lu_solve(&self, perm: &[usize], b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        let n = self.check_square("LU solve")?;
+        if perm.len() != n || b.nrows() != n {
+            return Err(LinalgError::ShapeError(format!(
+                "LU solve expects a permutation and right-hand side with {} rows",
+                n
+            )));
+        }
+        let mut x = Array2::zeros(b.dim());
+        for (i, &p) in perm.iter().enumerate() {
+            x.row_mut(i).assign(&b.row(p));
+        }
+        self.forward_substitution(&mut x, true)?;
+        self.backward_substitution(&mut x, false)?;
+        Ok(x)
+    }
+
+    /// Blocked Cholesky decomposition `A = L L^T`, in place
+    ///
+    /// Only the lower triangle of `A` is read. On return the matrix holds
+    /// `L`, with the strict upper triangle set to zero.
+    pub fn cholesky_in_place(&mut self) -> LinalgResult<()> {
+        self.check_square("Cholesky decomposition")?;
+        let tiles = self.tile_grid().1;
+        let t = self.config.tile_size;
+
+        for k in 0..tiles {
+            let mut panel = self.read_panel(k, k)?;
+            let width = panel.ncols();
+            factor_cholesky_panel(&mut panel.view_mut()).map_err(|step| {
+                LinalgError::NonPositiveDefiniteError(format!(
+                    "Out-of-core Cholesky: leading minor of order {} is not positive",
+                    k * t + step + 1
+                ))
+            })?;
+            self.write_panel(k, k, &panel.view())?;
+
+            let this = &*self;
+            let panel = &panel;
+            let trailing = tiles - k - 1;
+            run_tasks(self.config.workers, k + trailing, |task| {
+                if task < k {
+                    // Upper tile (task, k) of the factor is zero
+                    let (rows, cols) = this.tile_range(task, k);
+                    let zeros = Array2::zeros((rows.len(), cols.len()));
+                    return this.write_tile_with(&mut this.open_handle()?, task, k, &zeros.view());
+                }
+                // A_ij -= L_ik L_jk^T for the lower tiles of column panel j > k
+                let j = task + 1;
+                let offset = (j - k) * t;
+                let mut q = this.read_panel(j, j)?;
+                let wj = q.ncols();
+                let l_jk = panel.slice(s![offset..offset + wj, ..width]);
+                let l_ik = panel.slice(s![offset.., ..width]);
+                ndarray::linalg::general_mat_mul(-F::one(), &l_ik, &l_jk.t(), F::one(), &mut q);
+                this.write_panel(j, j, &q.view())
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Solve `A X = B` using the factor from [`cholesky_in_place`](Self::cholesky_in_place)
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - Right-hand sides held in memory
+    pub fn cholesky_solve(&self, b: &ArrayView2<F>) -> LinalgResult<Array2<F>> {
+        let n = self.check_square("Cholesky solve")?;
+        if b.nrows() != n {
+            return Err(LinalgError::ShapeError(format!(
+                "Cholesky solve expects a right-hand side with {} rows, got {}",
+                n,
+                b.nrows()
+            )));
+        }
+        let mut x = b.to_owned();
+        self.forward_substitution(&mut x, false)?;
+        self.backward_substitution(&mut x, true)?;
+        Ok(x)
+    }
+
+    /// Solve `L Y = X` in place by tile rows, `L` being the lower triangle
+    fn forward_substitution(&self, x: &mut Array2<F>, unit_diagonal: bool) -> LinalgResult<()> {
+        let mut file = self.open_handle()?;
+        let tiles = self.tile_grid().0;
+        for bi in 0..tiles {
+            let (rows, _) = self.tile_range(bi, bi);
+            for bj in 0..bi {
+                let (cols, _) = self.tile_range(bj, bj);
+                let l = self.read_tile_with(&mut file, bi, bj)?;
+                let y = x.slice(s![cols, ..]).to_owned();
+                let mut target = x.slice_mut(s![rows.clone(), ..]);
+                ndarray::linalg::general_mat_mul(-F::one(), &l, &y, F::one(), &mut target);
+            }
+            let diag = self.read_tile_with(&mut file, bi, bi)?;
+            let mut block = x.slice_mut(s![rows, ..]);
+            for r in 0..diag.nrows() {
+                for c in 0..r {
+                    let l = diag[[r, c]];
+                    for col in 0..block.ncols() {
+                        let v = block[[c, col]];
+                        block[[r, col]] -= l * v;
+                    }
+                }
+                if !unit_diagonal {
+                    let d = diag[[r, r]];
+                    block.row_mut(r).mapv_inplace(|v| v / d);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Solve `U X = Y` in place by tile rows, with `U` either the upper
+    /// triangle or the transpose of the lower triangle
+    fn backward_substitution(&self, x: &mut Array2<F>, transposed_lower: bool) -> LinalgResult<()> {
+        let mut file = self.open_handle()?;
+        let tiles = self.tile_grid().0;
+        for bi in (0..tiles).rev() {
+            let (rows, _) = self.tile_range(bi, bi);
+            for bj in (bi + 1)..tiles {
+                let (cols, _) = self.tile_range(bj, bj);
+                let y = x.slice(s![cols, ..]).to_owned();
+                let mut target = x.slice_mut(s![rows.clone(), ..]);
+                if transposed_lower {
+                    let l = self.read_tile_with(&mut file, bj, bi)?;
+                    ndarray::linalg::general_mat_mul(-F::one(), &l.t(), &y, F::one(), &mut target);
+                } else {
+                    let u = self.read_tile_with(&mut file, bi, bj)?;
+                    ndarray::linalg::general_mat_mul(-F::one(), &u, &y, F::one(), &mut target);
+                }
+            }
+            let diag = self.read_tile_with(&mut file, bi, bi)?;
+            let upper = if transposed_lower {
+                diag.t().to_owned()
+            } else {
+                diag
+            };
+            let mut block = x.slice_mut(s![rows, ..]);
+            for r in (0..upper.nrows()).rev() {
+                for c in (r + 1)..upper.ncols() {
+                    let u = upper[[r, c]];
+                    for col in 0..block.ncols() {
+                        let v = block[[c, col]];
+                        block[[r, col]] -= u * v;
+                    }
+                }
+                let d = upper[[r, r]];
+                if d == F::zero() {
+                    return Err(LinalgError::singular_matrix_with_suggestions(
+                        "out-of-core triangular solve",
+                        self.shape,
+                        None,
+                    ));
+                }
+                block.row_mut(r).mapv_inplace(|v| v / d);
+            }
+        }
+        Ok(())
+    }
+
+    fn check_square(&self, operation: &str) -> LinalgResult<usize> {
+        if self.shape.0 != self.shape.1 {
+            return Err(LinalgError::ShapeError(format!(
+                "Out-of-core {} requires a square matrix, got {}x{}",
+                operation, self.shape.0, self.shape.1
+            )));
+        }
+        Ok(self.shape.0)
+    }
+
+    fn check_tile(&self, bi: usize, bj: usize) -> LinalgResult<()> {
+        let (tile_rows, tile_cols) = self.tile_grid();
+        if bi >= tile_rows || bj >= tile_cols {
+            return Err(LinalgError::IndexError(format!(
+                "Tile ({}, {}) out of bounds for a {}x{} tile grid",
+                bi, bj, tile_rows, tile_cols
+            )));
+        }
+        Ok(())
+    }
+
+    fn tile_range(&self, bi: usize, bj: usize) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let t = self.config.tile_size;
+        (
+            bi * t..((bi + 1) * t).min(self.shape.0),
+            bj * t..((bj + 1) * t).min(self.shape.1),
+        )
+    }
+
+    fn tile_offset(&self, bi: usize, bj: usize) -> u64 {
+        let t = self.config.tile_size;
+        let rows = self.tile_range(bi, bj).0.len();
+        ((bi * t * self.shape.1 + rows * bj * t) * F::BYTES) as u64
+    }
+
+    fn open_handle(&self) -> LinalgResult<File> {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.path)
+            .map_err(|e| io_error("open", &self.path, e))
+    }
+
+    fn read_tile_with(&self, file: &mut File, bi: usize, bj: usize) -> LinalgResult<Array2<F>> {
+        let (rows, cols) = self.tile_range(bi, bj);
+        let mut bytes = vec![0u8; rows.len() * cols.len() * F::BYTES];
+        file.seek(SeekFrom::Start(self.tile_offset(bi, bj)))
+            .and_then(|_| file.read_exact(&mut bytes))
+            .map_err(|e| io_error("read", &self.path, e))?;
+        let values = bytes.chunks_exact(F::BYTES).map(F::read_le).collect();
+        Array2::from_shape_vec((rows.len(), cols.len()), values)
+            .map_err(|e| LinalgError::ShapeError(e.to_string()))
+    }
+
+    fn write_tile_with(
+        &self,
+        file: &mut File,
+        bi: usize,
+        bj: usize,
+        tile: &ArrayView2<F>,
+    ) -> LinalgResult<()> {
+        let mut bytes = vec![0u8; tile.len() * F::BYTES];
+        for (chunk, &v) in bytes.chunks_exact_mut(F::BYTES).zip(tile.iter()) {
+            v.write_le(chunk);
+        }
+        file.seek(SeekFrom::Start(self.tile_offset(bi, bj)))
+            .and_then(|_| file.write_all(&bytes))
+            .map_err(|e| io_error("write", &self.path, e))
+    }
+
+    /// Tiles `(first..tiles, bj)` stacked into one column panel
+    fn read_panel(&self, first: usize, bj: usize) -> LinalgResult<Array2<F>> {
+        let mut file = self.open_handle()?;
+        let row_start = first * self.config.tile_size;
+        let cols = self.tile_range(first, bj).1.len();
+        let mut panel = Array2::zeros((self.shape.0 - row_start, cols));
+        for bi in first..self.tile_grid().0 {
+            let rows = self.tile_range(bi, bj).0;
+            let tile = self.read_tile_with(&mut file, bi, bj)?;
+            panel
+                .slice_mut(s![rows.start - row_start..rows.end - row_start, ..])
+                .assign(&tile);
+        }
+        Ok(panel)
+    }
+
+    fn write_panel(&self, first: usize, bj: usize, panel: &ArrayView2<F>) -> LinalgResult<()> {
+        let mut file = self.open_handle()?;
+        let row_start = first * self.config.tile_size;
+        for bi in first..self.tile_grid().0 {
+            let rows = self.tile_range(bi, bj).0;
+            let tile = panel.slice(s![rows.start - row_start..rows.end - row_start, ..]);
+            self.write_tile_with(&mut file, bi, bj, &tile)?;
+        }
+        file.flush().map_err(|e| io_error("flush", &self.path, e))
+    }
+}
+
+/// Run `tasks` independent tasks on `workers` threads, returning the first error
+fn run_tasks<T>(workers: usize, tasks: usize, task: T) -> LinalgResult<()>
+where
+    T: Fn(usize) -> LinalgResult<()> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let failure: Mutex<Option<LinalgError>> = Mutex::new(None);
+    std::thread::scope(|scope| {
+        for _ in 0..workers.min(tasks) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= tasks {
+                    break;
+                }
+                if let Err(err) = task(index) {
+                    // Stop handing out work after the first failure
+                    next.store(tasks, Ordering::Relaxed);
+                    failure.lock().unwrap().get_or_insert(err);
+                    break;
+                }
+            });
+        }
+    });
+    match failure.into_inner().unwrap() {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Unblocked LU with partial pivoting of a tall panel
+///
+/// Returns the pivot row of each column relative to the panel, or the index
+/// of the first column without a nonzero pivot.
+fn factor_lu_panel<F: OutOfCoreScalar>(panel: &mut ArrayViewMut2<F>) -> Result<Vec<usize>, usize> {
+    let (m, w) = panel.dim();
+    let mut pivots = Vec::with_capacity(w);
+    for c in 0..w {
+        let p = (c..m)
+            .max_by(|&a, &b| {
+                panel[[a, c]]
+                    .abs()
+                    .partial_cmp(&panel[[b, c]].abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap_or(c);
+        if panel[[p, c]] == F::zero() {
+            return Err(c);
+        }
+        if p != c {
+            for col in 0..w {
+                panel.swap([c, col], [p, col]);
+            }
+        }
+        pivots.push(p);
+        let d = panel[[c, c]];
+        for r in (c + 1)..m {
+            let l = panel[[r, c]] / d;
+            panel[[r, c]] = l;
+            for col in (c + 1)..w {
+                let u = panel[[c, col]];
+                panel[[r, col]] -= l * u;
+            }
+        }
+    }
+    Ok(pivots)
+}
+
+/// Cholesky factorization of the diagonal block of a column panel followed
+/// by the triangular solve `L_ik = A_ik L_kk^{-T}` for the rows below it
+///
+/// Returns the index of the first non-positive pivot on failure.
+fn factor_cholesky_panel<F: OutOfCoreScalar>(panel: &mut ArrayViewMut2<F>) -> Result<(), usize> {
+    let (m, w) = panel.dim();
+    for c in 0..w {
+        let mut d = panel[[c, c]];
+        for k in 0..c {
+            d -= panel[[c, k]] * panel[[c, k]];
+        }
+        if d <= F::zero() || !d.is_finite() {
+            return Err(c);
+        }
+        let d = d.sqrt();
+        panel[[c, c]] = d;
+        for r in (c + 1)..m {
+            let mut v = panel[[r, c]];
+            for k in 0..c {
+                v -= panel[[r, k]] * panel[[c, k]];
+            }
+            panel[[r, c]] = v / d;
+        }
+        for col in (c + 1)..w {
+            panel[[c, col]] = F::zero();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn test_matrix(n: usize, m: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, m), |(i, j)| {
+            ((i * 7 + j * 13) % 11) as f64 - 5.0 + if i == j { 20.0 } else { 0.0 }
+        })
+    }
+
+    fn config() -> OutOfCoreConfig {
+        OutOfCoreConfig::default().with_tile_size(4).with_workers(3)
+    }
+
+    #[test]
+    fn test_round_trip_and_tiles() {
+        let a = test_matrix(10, 7);
+        let disk = OutOfCoreMatrix::from_array(&a.view(), None, config()).unwrap();
+        assert_eq!(disk.tile_grid(), (3, 2));
+        assert_eq!(disk.to_array().unwrap(), a);
+        assert_eq!(disk.read_tile(2, 1).unwrap(), a.slice(s![8..10, 4..7]));
+        assert!(disk.read_tile(3, 0).is_err());
+
+        let path = disk.path().to_path_buf();
+        assert!(path.exists());
+        drop(disk);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_tiled_matmul() {
+        let a = test_matrix(9, 6);
+        let b = test_matrix(6, 11);
+        let da = OutOfCoreMatrix::from_array(&a.view(), None, config()).unwrap();
+        let db = OutOfCoreMatrix::from_array(&b.view(), None, config()).unwrap();
+        let c = da.matmul(&db, None).unwrap().to_array().unwrap();
+        let expected = a.dot(&b);
+        for (x, y) in c.iter().zip(expected.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-10);
+        }
+        assert!(db.matmul(&db, None).is_err());
+    }
+
+    #[test]
+    fn test_blocked_lu() {
+        let n = 13;
+        let mut a = test_matrix(n, n);
+        // Small leading entry forces pivoting across tiles
+        a[[0, 0]] = 1e-3;
+        let mut disk = OutOfCoreMatrix::from_array(&a.view(), None, config()).unwrap();
+        let perm = disk.lu_in_place().unwrap();
+
+        let packed = disk.to_array().unwrap();
+        let mut l = Array2::<f64>::eye(n);
+        let mut u = Array2::<f64>::zeros((n, n));
+        for i in 0..n {
+            for j in 0..n {
+                if i > j {
+                    l[[i, j]] = packed[[i, j]];
+                } else {
+                    u[[i, j]] = packed[[i, j]];
+                }
+            }
+        }
+        let lu = l.dot(&u);
+        for i in 0..n {
+            for j in 0..n {
+                assert_relative_eq!(lu[[i, j]], a[[perm[i], j]], epsilon = 1e-10);
+            }
+        }
+
+        let b = Array2::from_shape_fn((n, 2), |(i, j)| (i + j) as f64);
+        let x = disk.lu_solve(&perm, &b.view()).unwrap();
+        let ax = a.dot(&x);
+        for (x, y) in ax.iter().zip(b.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_blocked_cholesky() {
+        let n = 11;
+        let m = test_matrix(n, n);
+        let a = m.t().dot(&m) + Array2::<f64>::eye(n);
+        let mut disk = OutOfCoreMatrix::from_array(&a.view(), None, config()).unwrap();
+        disk.cholesky_in_place().unwrap();
+
+        let l = disk.to_array().unwrap();
+        for i in 0..n {
+            for j in (i + 1)..n {
+                assert_eq!(l[[i, j]], 0.0);
+            }
+        }
+        let llt = l.dot(&l.t());
+        for (x, y) in llt.iter().zip(a.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-8, max_relative = 1e-12);
+        }
+
+        let b = Array2::from_shape_fn((n, 1), |(i, _)| i as f64 - 3.0);
+        let x = disk.cholesky_solve(&b.view()).unwrap();
+        let ax = a.dot(&x);
+        for (x, y) in ax.iter().zip(b.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-8);
+        }
+
+        let indefinite = -Array2::<f64>::eye(5);
+        let mut disk = OutOfCoreMatrix::from_array(&indefinite.view(), None, config()).unwrap();
+        assert!(matches!(
+            disk.cholesky_in_place(),
+            Err(LinalgError::NonPositiveDefiniteError(_))
+        ));
+    }
+
+    #[test]
+    fn test_persistent_file() {
+        let path =
+            std::env::temp_dir().join(format!("scirs2-linalg-ooc-test-{}.bin", std::process::id()));
+        let a = test_matrix(5, 5);
+        {
+            let disk = OutOfCoreMatrix::from_array(&a.view(), Some(&path), config()).unwrap();
+            assert_eq!(disk.path(), path.as_path());
+        }
+        let reopened = OutOfCoreMatrix::<f64>::open(&path, (5, 5), config()).unwrap();
+        assert_eq!(reopened.to_array().unwrap(), a);
+        assert!(OutOfCoreMatrix::<f64>::open(&path, (6, 5), config()).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -4,7 +4,8 @@
 //! the convergence of iterative linear solvers, enabling efficient solution of
 //! massive linear systems in scientific computing, engineering, and machine learning:
 //!
-//! - **Incomplete factorizations**: ILU, IC with fill-in control and pivoting
+//! - **Incomplete factorizations**: ILU, ILU(0), IC with fill-in control and pivoting
+//! - **Relaxation methods**: Jacobi and symmetric successive over-relaxation (SSOR)
 //! - **Multigrid methods**: Algebraic (AMG) and geometric multigrid for optimal complexity
 //! - **Domain decomposition**: Additive Schwarz, block Jacobi for parallel computing
 //! - **Sparse approximate inverse**: SPAI and minimal residual methods
@@ -369,6 +370,230 @@ where
     }
 }
 
+/// Zero fill-in incomplete LU preconditioner, ILU(0)
+///
+/// The factors keep exactly the sparsity pattern of the input matrix: entries
+/// that are zero in `A` stay zero in `L` and `U`, so the preconditioner costs
+/// O(nnz) per application.
+#[derive(Debug, Clone)]
+pub struct Ilu0Preconditioner<F> {
+    /// Combined factors; strictly lower part is `L` (unit diagonal), the rest is `U`
+    lu: Array2<F>,
+    /// Column indices of the nonzero pattern of each row, sorted
+    pattern: Vec<Vec<usize>>,
+}
+
+impl<F> Ilu0Preconditioner<F>
+where
+    F: Float + NumAssign + Zero + One + Sum + ndarray::ScalarOperand + 'static,
+{
+    /// Compute the ILU(0) factorization of a square matrix
+    ///
+    /// Fails if a zero pivot is encountered, which includes any zero on the
+    /// diagonal of `matrix`.
+    pub fn new(matrix: &ArrayView2<F>) -> LinalgResult<Self> {
+        let (m, n) = matrix.dim();
+        if m != n {
+            return Err(LinalgError::ShapeError(
+                "ILU(0) preconditioner requires square matrix".to_string(),
+            ));
+        }
+
+        let pattern: Vec<Vec<usize>> = (0..n)
+            .map(|i| (0..n).filter(|&j| matrix[[i, j]] != F::zero()).collect())
+            .collect();
+        let mut lu = matrix.to_owned();
+
+        // IKJ variant restricted to the pattern of row i
+        for i in 0..n {
+            for (pos, &k) in pattern[i].iter().enumerate() {
+                if k >= i {
+                    break;
+                }
+                let pivot = lu[[k, k]];
+                if pivot == F::zero() {
+                    return Err(LinalgError::SingularMatrixError(format!(
+                        "ILU(0) encountered a zero pivot in row {}",
+                        k
+                    )));
+                }
+                let factor = lu[[i, k]] / pivot;
+                lu[[i, k]] = factor;
+                for &j in &pattern[i][pos + 1..] {
+                    let update = factor * lu[[k, j]];
+                    lu[[i, j]] -= update;
+                }
+            }
+            if lu[[i, i]] == F::zero() {
+                return Err(LinalgError::SingularMatrixError(format!(
+                    "ILU(0) encountered a zero pivot in row {}",
+                    i
+                )));
+            }
+        }
+
+        Ok(Self { lu, pattern })
+    }
+}
+
+impl<F> PreconditionerOp<F> for Ilu0Preconditioner<F>
+where
+    F: Float + NumAssign + Zero + One + Sum + ndarray::ScalarOperand + 'static,
+{
+    fn apply(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let n = self.size();
+        // Ly = x with unit diagonal, then Uz = y
+        let mut y = x.to_owned();
+        for i in 0..n {
+            for &j in self.pattern[i].iter().take_while(|&&j| j < i) {
+                let update = self.lu[[i, j]] * y[j];
+                y[i] -= update;
+            }
+        }
+        for i in (0..n).rev() {
+            for &j in self.pattern[i].iter().filter(|&&j| j > i) {
+                let update = self.lu[[i, j]] * y[j];
+                y[i] -= update;
+            }
+            y[i] /= self.lu[[i, i]];
+        }
+        Ok(y)
+    }
+
+    fn apply_transpose(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        let n = self.size();
+        // Uᵀy = x, then Lᵀz = y, both as column-oriented sweeps over the rows
+        let mut y = x.to_owned();
+        for i in 0..n {
+            y[i] /= self.lu[[i, i]];
+            for &j in self.pattern[i].iter().filter(|&&j| j > i) {
+                let update = self.lu[[i, j]] * y[i];
+                y[j] -= update;
+            }
+        }
+        for i in (0..n).rev() {
+            for &j in self.pattern[i].iter().take_while(|&&j| j < i) {
+                let update = self.lu[[i, j]] * y[i];
+                y[j] -= update;
+            }
+        }
+        Ok(y)
+    }
+
+    fn size(&self) -> usize {
+        self.pattern.len()
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+}
+
+/// Symmetric successive over-relaxation (SSOR) preconditioner
+///
+/// With `A = D + L + U` split into diagonal, strictly lower and strictly upper
+/// parts, the preconditioner is
+/// `M = (D + ωL) D⁻¹ (D + ωU) / (ω(2 - ω))` for a relaxation factor
+/// `0 < ω < 2`. For symmetric positive definite `A` it is symmetric positive
+/// definite as well and can be used with preconditioned CG and MINRES.
+#[derive(Debug, Clone)]
+pub struct SsorPreconditioner<F> {
+    /// The original matrix
+    matrix: Array2<F>,
+    /// Relaxation factor ω
+    omega: F,
+    /// Whether the matrix is symmetric
+    symmetric: bool,
+}
+
+impl<F> SsorPreconditioner<F>
+where
+    F: Float + NumAssign + Zero + One + Sum + ndarray::ScalarOperand + 'static,
+{
+    /// Create an SSOR preconditioner with relaxation factor `omega`
+    ///
+    /// `omega = 1` gives the symmetric Gauss-Seidel preconditioner.
+    pub fn new(matrix: &ArrayView2<F>, omega: F) -> LinalgResult<Self> {
+        let (m, n) = matrix.dim();
+        if m != n {
+            return Err(LinalgError::ShapeError(
+                "SSOR preconditioner requires square matrix".to_string(),
+            ));
+        }
+        let two = F::from(2.0).unwrap();
+        if !(omega > F::zero() && omega < two) {
+            return Err(LinalgError::InvalidInputError(
+                "SSOR relaxation factor must lie in (0, 2)".to_string(),
+            ));
+        }
+        if let Some(i) = (0..n).find(|&i| matrix[[i, i]] == F::zero()) {
+            return Err(LinalgError::SingularMatrixError(format!(
+                "SSOR preconditioner requires a nonzero diagonal, entry {} is zero",
+                i
+            )));
+        }
+
+        let symmetric = (0..n).all(|i| (0..i).all(|j| matrix[[i, j]] == matrix[[j, i]]));
+        Ok(Self {
+            matrix: matrix.to_owned(),
+            omega,
+            symmetric,
+        })
+    }
+
+    /// Apply `M⁻¹` for the matrix with entries `entry(i, j)`
+    fn sweep<E: Fn(usize, usize) -> F>(&self, entry: E, x: &ArrayView1<F>) -> Array1<F> {
+        let n = self.matrix.nrows();
+        let omega = self.omega;
+        let two = F::from(2.0).unwrap();
+
+        // (D + ωL) y = x
+        let mut y = x.to_owned();
+        for i in 0..n {
+            let mut sum = y[i];
+            for j in 0..i {
+                sum -= omega * entry(i, j) * y[j];
+            }
+            y[i] = sum / entry(i, i);
+        }
+        // Scale by D
+        for i in 0..n {
+            y[i] *= entry(i, i);
+        }
+        // (D + ωU) z = y
+        for i in (0..n).rev() {
+            let mut sum = y[i];
+            for j in (i + 1)..n {
+                sum -= omega * entry(i, j) * y[j];
+            }
+            y[i] = sum / entry(i, i);
+        }
+
+        y * (omega * (two - omega))
+    }
+}
+
+impl<F> PreconditionerOp<F> for SsorPreconditioner<F>
+where
+    F: Float + NumAssign + Zero + One + Sum + ndarray::ScalarOperand + 'static,
+{
+    fn apply(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        Ok(self.sweep(|i, j| self.matrix[[i, j]], x))
+    }
+
+    fn apply_transpose(&self, x: &ArrayView1<F>) -> LinalgResult<Array1<F>> {
+        Ok(self.sweep(|i, j| self.matrix[[j, i]], x))
+    }
+
+    fn size(&self) -> usize {
+        self.matrix.nrows()
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+}
+
 /// Incomplete Cholesky preconditioner for symmetric positive definite matrices
 #[derive(Debug, Clone)]
 pub struct IncompleteCholeskyPreconditioner<F> {
@@ -1104,6 +1329,67 @@ mod tests {
         assert!(result.iter().all(|&val| val.is_finite()));
     }
 
+    #[test]
+    fn test_ilu0_preconditioner() {
+        // Tridiagonal matrices have no fill-in, so ILU(0) is the exact LU
+        let matrix = array![[4.0, -1.0, 0.0], [-2.0, 4.0, 1.0], [0.0, 1.0, 3.0]];
+        let preconditioner = Ilu0Preconditioner::new(&matrix.view()).unwrap();
+        let x = array![1.0, 2.0, 3.0];
+        let y = preconditioner.apply(&x.view()).unwrap();
+        let back = matrix.dot(&y);
+        let yt = preconditioner.apply_transpose(&x.view()).unwrap();
+        let back_t = matrix.t().dot(&yt);
+        for i in 0..3 {
+            assert_relative_eq!(back[i], x[i], epsilon = 1e-12);
+            assert_relative_eq!(back_t[i], x[i], epsilon = 1e-12);
+        }
+
+        // Entries outside the sparsity pattern stay zero
+        let matrix = array![[4.0, 1.0, 1.0], [1.0, 4.0, 0.0], [1.0, 0.0, 4.0]];
+        let preconditioner = Ilu0Preconditioner::new(&matrix.view()).unwrap();
+        assert_eq!(preconditioner.lu[[1, 2]], 0.0);
+        assert_eq!(preconditioner.lu[[2, 1]], 0.0);
+
+        let singular = array![[0.0, 1.0], [1.0, 0.0]];
+        assert!(Ilu0Preconditioner::new(&singular.view()).is_err());
+    }
+
+    #[test]
+    fn test_ssor_preconditioner() {
+        let matrix = array![[4.0, 1.0, 0.0], [1.0, 4.0, 1.0], [0.0, 1.0, 4.0]];
+        let omega = 1.2;
+        let preconditioner = SsorPreconditioner::new(&matrix.view(), omega).unwrap();
+        assert!(preconditioner.is_symmetric());
+
+        // M = (D + ωL) D⁻¹ (D + ωU) / (ω(2 - ω))
+        let mut lower = Array2::zeros((3, 3));
+        let mut upper = Array2::zeros((3, 3));
+        let mut d_inv = Array2::zeros((3, 3));
+        for i in 0..3 {
+            d_inv[[i, i]] = 1.0 / matrix[[i, i]];
+            for j in 0..3 {
+                let scale = if i == j { 1.0 } else { omega };
+                if j <= i {
+                    lower[[i, j]] = scale * matrix[[i, j]];
+                }
+                if j >= i {
+                    upper[[i, j]] = scale * matrix[[i, j]];
+                }
+            }
+        }
+        let m = lower.dot(&d_inv).dot(&upper) / (omega * (2.0 - omega));
+        let x = array![1.0, -2.0, 0.5];
+        let y = preconditioner.apply(&x.view()).unwrap();
+        let back = m.dot(&y);
+        for i in 0..3 {
+            assert_relative_eq!(back[i], x[i], epsilon = 1e-12);
+        }
+
+        assert!(SsorPreconditioner::new(&matrix.view(), 2.0).is_err());
+        let zero_diag = array![[0.0, 1.0], [1.0, 2.0]];
+        assert!(SsorPreconditioner::new(&zero_diag.view(), 1.0).is_err());
+    }
+
     #[test]
     fn test_incomplete_cholesky_preconditioner() {
         let matrix = array![[4.0, 1.0, 0.0], [1.0, 4.0, 1.0], [0.0, 1.0, 4.0]];
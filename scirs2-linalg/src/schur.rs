@@ -0,0 +1,1503 @@
+//! Schur decompositions and eigenvalue reordering
+//!
+//! * [`complex_schur`] reduces a complex matrix to upper triangular form
+//!   `A = Z T Z^H` with the single-shift QR algorithm.
+//! * [`real_schur`] reduces a real matrix to upper quasi-triangular form
+//!   `A = Z T Z^T` with Francis double-shift QR. Complex conjugate eigenvalue
+//!   pairs appear as 2x2 diagonal blocks in standard form `[[a, b], [c, a]]`
+//!   with `b c < 0`.
+//! * [`ordschur`] and [`ordschur_real`] reorder a Schur form so that selected
+//!   eigenvalues occupy the leading diagonal block, whose columns of `Z` then
+//!   span the corresponding invariant subspace.
+//!
+//! Both factorizations start with a Householder reduction to Hessenberg form,
+//! available on its own as [`hessenberg`] and [`complex_hessenberg`]. They
+//! return `(T, Z)` like `scipy.linalg.schur`.
+
+use ndarray::{s, Array1, Array2, ArrayView2};
+use num_complex::Complex;
+use num_traits::{Float, NumAssign, Zero};
+
+use crate::error::{LinalgError, LinalgResult};
+
+/// Reduced form and unitary factor `(T, Z)` of a complex Schur decomposition
+pub type ComplexFactors<F> = (Array2<Complex<F>>, Array2<Complex<F>>);
+
+/// Cheap modulus `|re| + |im|` used for deflation tests
+#[inline]
+pub(crate) fn abs1<F: Float>(z: Complex<F>) -> F {
+    z.re.abs() + z.im.abs()
+}
+
+/// Plane rotation `G = [c s; -conj(s) c]` with real `c`
+#[derive(Debug, Clone, Copy)]
+struct Rotation<F> {
+    c: F,
+    s: Complex<F>,
+}
+
+impl<F: Float> Rotation<F> {
+    /// Rotation `G` such that `G [a; b] = [r; 0]`
+    fn zeroing(a: Complex<F>, b: Complex<F>) -> Self {
+        let abs_a = a.norm();
+        let abs_b = b.norm();
+        if abs_b == F::zero() {
+            return Self {
+                c: F::one(),
+                s: Complex::zero(),
+            };
+        }
+        if abs_a == F::zero() {
+            return Self {
+                c: F::zero(),
+                s: Complex::new(F::one(), F::zero()),
+            };
+        }
+        let rho = abs_a.hypot(abs_b);
+        Self {
+            c: abs_a / rho,
+            s: (a / abs_a) * b.conj() / rho,
+        }
+    }
+
+    /// Apply `G` from the left to rows `p` and `p + 1`, columns `cols`
+    fn apply_left(&self, m: &mut Array2<Complex<F>>, p: usize, cols: std::ops::Range<usize>) {
+        let c = Complex::new(self.c, F::zero());
+        for j in cols {
+            let x = m[[p, j]];
+            let y = m[[p + 1, j]];
+            m[[p, j]] = c * x + self.s * y;
+            m[[p + 1, j]] = c * y - self.s.conj() * x;
+        }
+    }
+
+    /// Apply `G^H` from the right to columns `p` and `p + 1`, rows `rows`
+    fn apply_right(&self, m: &mut Array2<Complex<F>>, p: usize, rows: std::ops::Range<usize>) {
+        let c = Complex::new(self.c, F::zero());
+        for i in rows {
+            let x = m[[i, p]];
+            let y = m[[i, p + 1]];
+            m[[i, p]] = c * x + self.s.conj() * y;
+            m[[i, p + 1]] = c * y - self.s * x;
+        }
+    }
+}
+
+/// Reduce a complex matrix to upper Hessenberg form with Householder reflectors
+///
+/// Returns `(H, Q)` with `A = Q H Q^H`, `Q` unitary and `H[i, j] = 0` for `i > j + 1`.
+pub(crate) fn hessenberg_complex<F: Float>(a: &ArrayView2<Complex<F>>) -> ComplexFactors<F> {
+    let n = a.nrows();
+    let mut h = a.to_owned();
+    let mut q = Array2::<Complex<F>>::eye(n);
+    let two = F::one() + F::one();
+
+    for k in 0..n.saturating_sub(2) {
+        let mut v: Array1<Complex<F>> = h.slice(s![k + 1.., k]).to_owned();
+        let x_norm = v.iter().fold(F::zero(), |acc, z| acc.hypot(z.norm()));
+        if x_norm == F::zero() {
+            continue;
+        }
+
+        // alpha = -phase(x0) * ||x|| avoids cancellation in v0 = x0 - alpha
+        let x0_abs = v[0].norm();
+        let phase = if x0_abs == F::zero() {
+            Complex::new(F::one(), F::zero())
+        } else {
+            v[0] / x0_abs
+        };
+        let alpha = -phase * x_norm;
+        v[0] = v[0] - alpha;
+        let v_norm = v.iter().fold(F::zero(), |acc, z| acc.hypot(z.norm()));
+        if v_norm == F::zero() {
+            continue;
+        }
+        v.mapv_inplace(|z| z / v_norm);
+
+        // H <- P H with P = I - 2 v v^H
+        for j in 0..n {
+            let mut dot = Complex::zero();
+            for (i, vi) in v.iter().enumerate() {
+                dot = dot + vi.conj() * h[[k + 1 + i, j]];
+            }
+            let dot = dot * two;
+            for (i, vi) in v.iter().enumerate() {
+                h[[k + 1 + i, j]] = h[[k + 1 + i, j]] - *vi * dot;
+            }
+        }
+
+        // H <- H P and Q <- Q P
+        for m in [&mut h, &mut q] {
+            for i in 0..n {
+                let mut dot = Complex::zero();
+                for (j, vj) in v.iter().enumerate() {
+                    dot = dot + m[[i, k + 1 + j]] * *vj;
+                }
+                let dot = dot * two;
+                for (j, vj) in v.iter().enumerate() {
+                    m[[i, k + 1 + j]] = m[[i, k + 1 + j]] - dot * vj.conj();
+                }
+            }
+        }
+
+        h[[k + 1, k]] = alpha;
+        for i in (k + 2)..n {
+            h[[i, k]] = Complex::zero();
+        }
+    }
+
+    (h, q)
+}
+
+/// Compute the Hessenberg decomposition `A = Q H Q^H` of a complex matrix
+///
+/// `H` is upper Hessenberg and `Q` is unitary; this is the reduction that
+/// [`complex_schur`] starts from.
+///
+/// # Arguments
+///
+/// * `a` - Square complex matrix
+///
+/// # Returns
+///
+/// * Tuple `(H, Q)`
+pub fn complex_hessenberg<F: Float>(a: &ArrayView2<Complex<F>>) -> LinalgResult<ComplexFactors<F>> {
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Hessenberg decomposition requires a square matrix, got shape {:?}",
+            a.shape()
+        )));
+    }
+    Ok(hessenberg_complex(a))
+}
+
+/// Compute the complex Schur decomposition `A = Z T Z^H`
+///
+/// `T` is upper triangular with the eigenvalues of `A` on its diagonal and `Z`
+/// is unitary. The QR iteration uses Wilkinson shifts, with an exceptional shift
+/// every ten iterations to break cycles.
+///
+/// # Arguments
+///
+/// * `a` - Square complex matrix
+///
+/// # Returns
+///
+/// * Tuple `(T, Z)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex;
+/// use scirs2_linalg::schur::complex_schur;
+///
+/// // Rotation generator with eigenvalues +-i
+/// let a = array![
+///     [Complex::new(0.0_f64, 0.0), Complex::new(-1.0, 0.0)],
+///     [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)],
+/// ];
+/// let (t, z) = complex_schur(&a.view()).unwrap();
+/// assert!(t[[1, 0]].norm() < 1e-14);
+/// assert!((t[[0, 0]].im.abs() - 1.0).abs() < 1e-12);
+/// assert_eq!(z.dim(), (2, 2));
+/// ```
+pub fn complex_schur<F: Float>(a: &ArrayView2<Complex<F>>) -> LinalgResult<ComplexFactors<F>> {
+    let n = a.nrows();
+    if n != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Schur decomposition requires a square matrix, got shape {:?}",
+            a.shape()
+        )));
+    }
+
+    let (mut t, mut z) = hessenberg_complex(a);
+    if n < 2 {
+        return Ok((t, z));
+    }
+
+    let eps = F::epsilon();
+    let half = F::from(0.5).unwrap();
+    let max_iter = 30 * n.max(10);
+    let mut rotations = Vec::with_capacity(n);
+    let mut hi = n - 1;
+    let mut iter = 0;
+    let mut total_iter = 0;
+
+    while hi > 0 {
+        // Look for a negligible subdiagonal entry to split the active block
+        let mut lo = hi;
+        while lo > 0 {
+            let mut scale = abs1(t[[lo - 1, lo - 1]]) + abs1(t[[lo, lo]]);
+            if scale == F::zero() {
+                scale = t
+                    .slice(s![..=hi, ..=hi])
+                    .iter()
+                    .fold(F::zero(), |acc, &x| acc.max(abs1(x)));
+            }
+            if abs1(t[[lo, lo - 1]]) <= eps * scale {
+                t[[lo, lo - 1]] = Complex::zero();
+                break;
+            }
+            lo -= 1;
+        }
+
+        if lo == hi {
+            hi -= 1;
+            iter = 0;
+            continue;
+        }
+
+        iter += 1;
+        total_iter += 1;
+        if total_iter > max_iter {
+            return Err(LinalgError::ConvergenceError(format!(
+                "Complex Schur QR iteration did not converge after {} iterations",
+                max_iter
+            )));
+        }
+
+        let shift = if iter % 10 == 0 {
+            // Exceptional shift based on the size of the trailing subdiagonal
+            t[[hi, hi]] + Complex::new(F::from(0.75).unwrap() * abs1(t[[hi, hi - 1]]), F::zero())
+        } else {
+            // Wilkinson shift: eigenvalue of the trailing 2x2 block closest to t[hi, hi]
+            let a = t[[hi - 1, hi - 1]];
+            let b = t[[hi - 1, hi]];
+            let c = t[[hi, hi - 1]];
+            let d = t[[hi, hi]];
+            let mean = (a + d) * half;
+            let disc = (((a - d) * half) * ((a - d) * half) + b * c).sqrt();
+            let mu1 = mean + disc;
+            let mu2 = mean - disc;
+            if (mu1 - d).norm() <= (mu2 - d).norm() {
+                mu1
+            } else {
+                mu2
+            }
+        };
+
+        // Explicitly shifted QR step on the active block [lo, hi]
+        for k in lo..=hi {
+            t[[k, k]] = t[[k, k]] - shift;
+        }
+        rotations.clear();
+        for k in lo..hi {
+            let g = Rotation::zeroing(t[[k, k]], t[[k + 1, k]]);
+            g.apply_left(&mut t, k, k..n);
+            t[[k + 1, k]] = Complex::zero();
+            rotations.push(g);
+        }
+        for (offset, g) in rotations.iter().enumerate() {
+            let k = lo + offset;
+            g.apply_right(&mut t, k, 0..(k + 2));
+            g.apply_right(&mut z, k, 0..n);
+        }
+        for k in lo..=hi {
+            t[[k, k]] = t[[k, k]] + shift;
+        }
+    }
+
+    // Clean up rounding noise below the diagonal
+    for j in 0..n {
+        for i in (j + 1)..n {
+            t[[i, j]] = Complex::zero();
+        }
+    }
+
+    Ok((t, z))
+}
+
+/// Form `Z M Z^H`, transforming a matrix back from Schur coordinates
+pub(crate) fn unitary_similarity<F: Float>(
+    z: &Array2<Complex<F>>,
+    m: &Array2<Complex<F>>,
+) -> Array2<Complex<F>> {
+    let n = z.nrows();
+    let mut zm = Array2::<Complex<F>>::zeros((n, n));
+    for i in 0..n {
+        for k in 0..n {
+            let z_ik = z[[i, k]];
+            if z_ik == Complex::zero() {
+                continue;
+            }
+            for j in 0..n {
+                zm[[i, j]] = zm[[i, j]] + z_ik * m[[k, j]];
+            }
+        }
+    }
+
+    let mut result = Array2::<Complex<F>>::zeros((n, n));
+    for i in 0..n {
+        for j in 0..n {
+            let mut sum = Complex::zero();
+            for k in 0..n {
+                sum = sum + zm[[i, k]] * z[[j, k]].conj();
+            }
+            result[[i, j]] = sum;
+        }
+    }
+    result
+}
+
+/// Swap the adjacent diagonal entries `k` and `k + 1` of a complex Schur form
+///
+/// Updates `T` and `Z` in place so that `A = Z T Z^H` still holds.
+pub(crate) fn swap_schur_adjacent<F: Float>(
+    t: &mut Array2<Complex<F>>,
+    z: &mut Array2<Complex<F>>,
+    k: usize,
+) {
+    let n = t.nrows();
+    let t11 = t[[k, k]];
+    let t22 = t[[k + 1, k + 1]];
+
+    // [t12, t22 - t11] is an eigenvector for t22; rotate it onto e1
+    let g = Rotation::zeroing(t[[k, k + 1]], t22 - t11);
+    g.apply_left(t, k, k..n);
+    g.apply_right(t, k, 0..(k + 2));
+    g.apply_right(z, k, 0..n);
+
+    t[[k, k]] = t22;
+    t[[k + 1, k + 1]] = t11;
+    t[[k + 1, k]] = Complex::zero();
+}
+
+/// Reorder a complex Schur form so that selected eigenvalues come first
+///
+/// Given `A = Z T Z^H` from [`complex_schur`], computes a new Schur form
+/// `A = Z' T' Z'^H` in which the eigenvalues with `select[i] == true` occupy
+/// the leading diagonal positions, keeping their relative order. The leading
+/// columns of `Z'` form an orthonormal basis of the invariant subspace
+/// belonging to the selected eigenvalues.
+///
+/// # Arguments
+///
+/// * `t` - Upper triangular Schur factor
+/// * `z` - Unitary Schur vectors
+/// * `select` - Flags for the diagonal entries of `t` to move to the top-left
+///
+/// # Returns
+///
+/// * Reordered `(T, Z)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use num_complex::Complex;
+/// use scirs2_linalg::schur::{complex_schur, ordschur};
+///
+/// let c = |re: f64| Complex::new(re, 0.0);
+/// let a = array![[c(1.0), c(2.0), c(0.5)], [c(0.0), c(3.0), c(1.0)], [c(0.0), c(0.0), c(-2.0)]];
+/// let (t, z) = complex_schur(&a.view()).unwrap();
+///
+/// // Move the negative eigenvalue to the top
+/// let select: Vec<bool> = t.diag().iter().map(|l| l.re < 0.0).collect();
+/// let (t2, _z2) = ordschur(&t.view(), &z.view(), &select).unwrap();
+/// assert!((t2[[0, 0]] - c(-2.0)).norm() < 1e-12);
+/// ```
+pub fn ordschur<F: Float>(
+    t: &ArrayView2<Complex<F>>,
+    z: &ArrayView2<Complex<F>>,
+    select: &[bool],
+) -> LinalgResult<ComplexFactors<F>> {
+    check_schur_pair(t.dim(), z.dim(), select.len())?;
+    let n = t.nrows();
+    for j in 0..n {
+        for i in (j + 1)..n {
+            if t[[i, j]] != Complex::zero() {
+                return Err(LinalgError::InvalidInputError(
+                    "ordschur requires an upper triangular Schur factor".to_string(),
+                ));
+            }
+        }
+    }
+
+    let mut t = t.to_owned();
+    let mut z = z.to_owned();
+    let mut ks = 0;
+    for (k, &selected) in select.iter().enumerate() {
+        if selected {
+            for pos in (ks..k).rev() {
+                swap_schur_adjacent(&mut t, &mut z, pos);
+            }
+            ks += 1;
+        }
+    }
+    Ok((t, z))
+}
+
+fn check_schur_pair(t: (usize, usize), z: (usize, usize), select: usize) -> LinalgResult<()> {
+    if t.0 != t.1 || z != t {
+        return Err(LinalgError::ShapeError(format!(
+            "Schur factors must be square and of equal shape, got T {:?} and Z {:?}",
+            t, z
+        )));
+    }
+    if select != t.0 {
+        return Err(LinalgError::ShapeError(format!(
+            "Selection has length {} but the matrix has order {}",
+            select, t.0
+        )));
+    }
+    Ok(())
+}
+
+/// Reduce a real matrix to upper Hessenberg form with Householder reflectors
+///
+/// Returns `(H, Q)` with `A = Q H Q^T`, `Q` orthogonal and `H[i, j] = 0` for `i > j + 1`.
+pub(crate) fn hessenberg_real<F: Float + NumAssign>(a: &ArrayView2<F>) -> (Array2<F>, Array2<F>) {
+    let n = a.nrows();
+    let mut h = a.to_owned();
+    let mut q = Array2::<F>::eye(n);
+    let two = F::one() + F::one();
+
+    for k in 0..n.saturating_sub(2) {
+        let mut v: Vec<F> = (k + 1..n).map(|i| h[[i, k]]).collect();
+        let norm = v.iter().fold(F::zero(), |acc, &x| acc.hypot(x));
+        if norm == F::zero() {
+            continue;
+        }
+        let alpha = if v[0] > F::zero() { -norm } else { norm };
+        v[0] -= alpha;
+        let v_norm2 = v.iter().fold(F::zero(), |acc, &x| acc + x * x);
+        if v_norm2 == F::zero() {
+            continue;
+        }
+
+        // H <- P H with P = I - 2 v v^T / (v^T v) acting on rows k+1..n
+        for j in 0..n {
+            let mut dot = F::zero();
+            for (idx, &vi) in v.iter().enumerate() {
+                dot += vi * h[[k + 1 + idx, j]];
+            }
+            let factor = two * dot / v_norm2;
+            for (idx, &vi) in v.iter().enumerate() {
+                h[[k + 1 + idx, j]] -= factor * vi;
+            }
+        }
+        // H <- H P and Q <- Q P acting on columns k+1..n
+        for m in [&mut h, &mut q] {
+            for i in 0..n {
+                let mut dot = F::zero();
+                for (idx, &vi) in v.iter().enumerate() {
+                    dot += m[[i, k + 1 + idx]] * vi;
+                }
+                let factor = two * dot / v_norm2;
+                for (idx, &vi) in v.iter().enumerate() {
+                    m[[i, k + 1 + idx]] -= factor * vi;
+                }
+            }
+        }
+        for i in (k + 2)..n {
+            h[[i, k]] = F::zero();
+        }
+    }
+    (h, q)
+}
+
+/// Compute the Hessenberg decomposition `A = Q H Q^T` of a real matrix
+///
+/// `H` is upper Hessenberg (`H[i, j] = 0` for `i > j + 1`) and `Q` is
+/// orthogonal. This is the Householder reduction that [`real_schur`] starts
+/// from, exposed for Arnoldi-type methods and custom eigenvalue iterations.
+///
+/// # Arguments
+///
+/// * `a` - Square matrix
+///
+/// # Returns
+///
+/// * Tuple `(H, Q)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::hessenberg;
+///
+/// let a = array![[4.0_f64, 1.0, 2.0], [3.0, 2.0, 1.0], [5.0, 0.0, 1.0]];
+/// let (h, q) = hessenberg(&a.view()).unwrap();
+/// assert_eq!(h[[2, 0]], 0.0);
+/// let back = q.dot(&h).dot(&q.t());
+/// for (x, y) in back.iter().zip(a.iter()) {
+///     assert!((x - y).abs() < 1e-12);
+/// }
+/// ```
+pub fn hessenberg<F: Float + NumAssign>(a: &ArrayView2<F>) -> LinalgResult<(Array2<F>, Array2<F>)> {
+    if a.nrows() != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Hessenberg decomposition requires a square matrix, got shape {:?}",
+            a.shape()
+        )));
+    }
+    Ok(hessenberg_real(a))
+}
+
+/// Apply the plane rotation `[c s; -s c]` to rows `p`, `p + 1` (columns
+/// `p..n`) and columns `p`, `p + 1` (rows `0..p + 2`) of `t`, and to the
+/// columns of `z`.
+fn rotate_real_pair<F: Float>(t: &mut Array2<F>, z: &mut Array2<F>, p: usize, c: F, s: F) {
+    let n = t.nrows();
+    for j in p..n {
+        let x = t[[p, j]];
+        let y = t[[p + 1, j]];
+        t[[p, j]] = c * x + s * y;
+        t[[p + 1, j]] = c * y - s * x;
+    }
+    let rotate_columns = |m: &mut Array2<F>, rows: usize| {
+        for i in 0..rows {
+            let x = m[[i, p]];
+            let y = m[[i, p + 1]];
+            m[[i, p]] = c * x + s * y;
+            m[[i, p + 1]] = c * y - s * x;
+        }
+    };
+    rotate_columns(t, p + 2);
+    rotate_columns(z, n);
+}
+
+/// Bring the 2x2 diagonal block at `p` into standard form (LAPACK `dlanv2`)
+///
+/// A block with real eigenvalues is made upper triangular; a block with complex
+/// eigenvalues gets equal diagonal entries and off-diagonal entries of opposite
+/// sign. The rotation is applied to the whole of `t` and to `z`.
+fn standardize_block<F: Float>(t: &mut Array2<F>, z: &mut Array2<F>, p: usize) {
+    let (mut a, mut b, mut c, mut d) = (t[[p, p]], t[[p, p + 1]], t[[p + 1, p]], t[[p + 1, p + 1]]);
+    let zero = F::zero();
+    let one = F::one();
+    let half = F::from(0.5).unwrap();
+    let four = F::from(4.0).unwrap();
+    let eps = F::epsilon();
+    let sign = |x: F, y: F| if y >= zero { x.abs() } else { -x.abs() };
+
+    let (cs, sn);
+    if c == zero {
+        cs = one;
+        sn = zero;
+    } else if b == zero {
+        // Swap rows and columns
+        cs = zero;
+        sn = one;
+        std::mem::swap(&mut a, &mut d);
+        b = -c;
+        c = zero;
+    } else if a - d == zero && sign(one, b) != sign(one, c) {
+        cs = one;
+        sn = zero;
+    } else {
+        let temp = a - d;
+        let p_half = half * temp;
+        let bcmax = b.abs().max(c.abs());
+        let bcmis = b.abs().min(c.abs()) * sign(one, b) * sign(one, c);
+        let scale = p_half.abs().max(bcmax);
+        let zz = p_half / scale * p_half + bcmax / scale * bcmis;
+        if zz >= four * eps {
+            // Real eigenvalues: compute a and d accurately
+            let zz = p_half + sign(scale.sqrt() * zz.sqrt(), p_half);
+            a = d + zz;
+            d = d - bcmax / zz * bcmis;
+            let tau = c.hypot(zz);
+            cs = zz / tau;
+            sn = c / tau;
+            b = b - c;
+            c = zero;
+        } else {
+            // Complex or nearly equal real eigenvalues: make diagonal equal
+            let sigma = b + c;
+            let tau = sigma.hypot(temp);
+            let mut cs1 = (half * (one + sigma.abs() / tau)).sqrt();
+            let mut sn1 = -(p_half / (tau * cs1)) * sign(one, sigma);
+
+            let aa = a * cs1 + b * sn1;
+            let bb = -a * sn1 + b * cs1;
+            let cc = c * cs1 + d * sn1;
+            let dd = -c * sn1 + d * cs1;
+            a = aa * cs1 + cc * sn1;
+            b = bb * cs1 + dd * sn1;
+            c = -aa * sn1 + cc * cs1;
+            d = -bb * sn1 + dd * cs1;
+
+            let mean = half * (a + d);
+            a = mean;
+            d = mean;
+            if c != zero {
+                if b != zero {
+                    if sign(one, b) == sign(one, c) {
+                        // Real eigenvalues: reduce to upper triangular form
+                        let sab = b.abs().sqrt();
+                        let sac = c.abs().sqrt();
+                        let pp = sign(sab * sac, c);
+                        let tau = one / (b + c).abs().sqrt();
+                        a = mean + pp;
+                        d = mean - pp;
+                        b = b - c;
+                        c = zero;
+                        let cs2 = sab * tau;
+                        let sn2 = sac * tau;
+                        let tmp = cs1 * cs2 - sn1 * sn2;
+                        sn1 = cs1 * sn2 + sn1 * cs2;
+                        cs1 = tmp;
+                    }
+                } else {
+                    b = -c;
+                    c = zero;
+                    let tmp = cs1;
+                    cs1 = -sn1;
+                    sn1 = tmp;
+                }
+            }
+            cs = cs1;
+            sn = sn1;
+        }
+    }
+
+    if sn != zero {
+        rotate_real_pair(t, z, p, cs, sn);
+    }
+    t[[p, p]] = a;
+    t[[p, p + 1]] = b;
+    t[[p + 1, p]] = c;
+    t[[p + 1, p + 1]] = d;
+}
+
+/// Compute the real Schur decomposition `A = Z T Z^T`
+///
+/// `T` is upper quasi-triangular: real eigenvalues appear on the diagonal and
+/// each complex conjugate pair `a ± i sqrt(-b c)` as a 2x2 block
+/// `[[a, b], [c, a]]` with `b c < 0`. `Z` is orthogonal. The eigenvalues are
+/// found with the Francis double-shift QR algorithm (as in EISPACK `hqr2`),
+/// using exceptional shifts after 10 and 30 iterations without deflation.
+///
+/// # Arguments
+///
+/// * `a` - Square real matrix
+///
+/// # Returns
+///
+/// * Tuple `(T, Z)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::schur::{real_schur, real_schur_eigenvalues};
+///
+/// let a = array![[0.0_f64, -2.0, 1.0], [1.0, 0.0, 0.5], [0.0, 0.0, 3.0]];
+/// let (t, z) = real_schur(&a.view()).unwrap();
+///
+/// let recon = z.dot(&t).dot(&z.t());
+/// for (x, y) in recon.iter().zip(a.iter()) {
+///     assert!((x - y).abs() < 1e-12);
+/// }
+///
+/// // One conjugate pair +-i sqrt(2) and the real eigenvalue 3
+/// let w = real_schur_eigenvalues(&t.view());
+/// assert!(w.iter().any(|l| (l.re - 3.0).abs() < 1e-12 && l.im == 0.0));
+/// assert!(w.iter().any(|l| l.re.abs() < 1e-12 && (l.im - 2.0_f64.sqrt()).abs() < 1e-12));
+/// ```
+pub fn real_schur<F: Float + NumAssign>(a: &ArrayView2<F>) -> LinalgResult<(Array2<F>, Array2<F>)> {
+    let n = a.nrows();
+    if n != a.ncols() {
+        return Err(LinalgError::ShapeError(format!(
+            "Schur decomposition requires a square matrix, got shape {:?}",
+            a.shape()
+        )));
+    }
+    if a.iter().any(|x| !x.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Schur decomposition requires finite matrix entries".to_string(),
+        ));
+    }
+
+    let (mut t, mut z) = hessenberg_real(a);
+    if n < 2 {
+        return Ok((t, z));
+    }
+
+    let eps = F::epsilon();
+    let two = F::one() + F::one();
+    let norm = t.iter().fold(F::zero(), |acc, &x| acc + x.abs());
+    let max_iter = 30 * n.max(10);
+    let mut total_iter = 0;
+    let mut exshift = F::zero();
+    let mut iter = 0;
+    let mut active = n;
+
+    while active > 0 {
+        let hi = active - 1;
+
+        // Look for a single small subdiagonal element
+        let mut l = hi;
+        while l > 0 {
+            let mut scale = t[[l - 1, l - 1]].abs() + t[[l, l]].abs();
+            if scale == F::zero() {
+                scale = norm;
+            }
+            if t[[l, l - 1]].abs() < eps * scale {
+                t[[l, l - 1]] = F::zero();
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == hi {
+            // One root found
+            t[[hi, hi]] += exshift;
+            active -= 1;
+            iter = 0;
+        } else if l + 1 == hi {
+            // Two roots found: split a real pair or standardize a complex pair
+            t[[hi, hi]] += exshift;
+            t[[hi - 1, hi - 1]] += exshift;
+            standardize_block(&mut t, &mut z, hi - 1);
+            active -= 2;
+            iter = 0;
+        } else {
+            total_iter += 1;
+            if total_iter > max_iter {
+                return Err(LinalgError::ConvergenceError(format!(
+                    "Real Schur QR iteration did not converge after {} iterations",
+                    max_iter
+                )));
+            }
+
+            // Form the shift from the trailing 2x2 block
+            let mut x = t[[hi, hi]];
+            let mut y = t[[hi - 1, hi - 1]];
+            let mut w = t[[hi, hi - 1]] * t[[hi - 1, hi]];
+
+            if iter == 10 {
+                // Wilkinson's original ad hoc shift
+                exshift += x;
+                for i in 0..=hi {
+                    t[[i, i]] -= x;
+                }
+                let s = t[[hi, hi - 1]].abs() + t[[hi - 1, hi - 2]].abs();
+                x = F::from(0.75).unwrap() * s;
+                y = x;
+                w = F::from(-0.4375).unwrap() * s * s;
+            }
+            if iter == 30 {
+                // MATLAB's ad hoc shift
+                let mut s = (y - x) / two;
+                s = s * s + w;
+                if s > F::zero() {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / two + s);
+                    for i in 0..=hi {
+                        t[[i, i]] -= s;
+                    }
+                    exshift += s;
+                    x = F::from(0.964).unwrap();
+                    y = x;
+                    w = x;
+                }
+            }
+            iter += 1;
+
+            // Look for two consecutive small subdiagonal elements
+            let mut m = hi - 2;
+            let (mut p, mut q, mut r);
+            loop {
+                let zz = t[[m, m]];
+                let rr = x - zz;
+                let ss = y - zz;
+                p = (rr * ss - w) / t[[m + 1, m]] + t[[m, m + 1]];
+                q = t[[m + 1, m + 1]] - zz - rr - ss;
+                r = t[[m + 2, m + 1]];
+                let s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                let lhs = t[[m, m - 1]].abs() * (q.abs() + r.abs());
+                let rhs = eps
+                    * (p.abs() * (t[[m - 1, m - 1]].abs() + zz.abs() + t[[m + 1, m + 1]].abs()));
+                if lhs < rhs {
+                    break;
+                }
+                m -= 1;
+            }
+
+            for i in (m + 2)..=hi {
+                t[[i, i - 2]] = F::zero();
+                if i > m + 2 {
+                    t[[i, i - 3]] = F::zero();
+                }
+            }
+
+            // Double QR step on rows l..=hi and columns m..=hi
+            for k in m..hi {
+                let notlast = k + 1 != hi;
+                if k != m {
+                    p = t[[k, k - 1]];
+                    q = t[[k + 1, k - 1]];
+                    r = if notlast {
+                        t[[k + 2, k - 1]]
+                    } else {
+                        F::zero()
+                    };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == F::zero() {
+                        continue;
+                    }
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+                let mut s = (p * p + q * q + r * r).sqrt();
+                if p < F::zero() {
+                    s = -s;
+                }
+                if s == F::zero() {
+                    continue;
+                }
+                if k != m {
+                    t[[k, k - 1]] = -s * x;
+                } else if l != m {
+                    t[[k, k - 1]] = -t[[k, k - 1]];
+                }
+                p += s;
+                x = p / s;
+                y = q / s;
+                let zz = r / s;
+                q /= p;
+                r /= p;
+
+                // Row modification
+                for j in k..n {
+                    let mut pp = t[[k, j]] + q * t[[k + 1, j]];
+                    if notlast {
+                        pp += r * t[[k + 2, j]];
+                        t[[k + 2, j]] -= pp * zz;
+                    }
+                    t[[k, j]] -= pp * x;
+                    t[[k + 1, j]] -= pp * y;
+                }
+                // Column modification
+                for i in 0..=hi.min(k + 3) {
+                    let mut pp = x * t[[i, k]] + y * t[[i, k + 1]];
+                    if notlast {
+                        pp += zz * t[[i, k + 2]];
+                        t[[i, k + 2]] -= pp * r;
+                    }
+                    t[[i, k]] -= pp;
+                    t[[i, k + 1]] -= pp * q;
+                }
+                // Accumulate transformations
+                for i in 0..n {
+                    let mut pp = x * z[[i, k]] + y * z[[i, k + 1]];
+                    if notlast {
+                        pp += zz * z[[i, k + 2]];
+                        z[[i, k + 2]] -= pp * r;
+                    }
+                    z[[i, k]] -= pp;
+                    z[[i, k + 1]] -= pp * q;
+                }
+            }
+        }
+    }
+
+    // Clean up rounding noise below the quasi-triangular structure
+    for j in 0..n {
+        for i in (j + 2)..n {
+            t[[i, j]] = F::zero();
+        }
+    }
+    Ok((t, z))
+}
+
+/// Size of the diagonal block of a quasi-triangular matrix starting at `k`
+fn block_size<F: Float>(t: &Array2<F>, k: usize) -> usize {
+    if k + 1 < t.nrows() && t[[k + 1, k]] != F::zero() {
+        2
+    } else {
+        1
+    }
+}
+
+/// Eigenvalues of a real upper quasi-triangular Schur factor
+///
+/// Each 2x2 block in standard form contributes the conjugate pair
+/// `a ± i sqrt(|b c|)`, with the positive imaginary part listed first.
+pub fn real_schur_eigenvalues<F: Float>(t: &ArrayView2<F>) -> Array1<Complex<F>> {
+    let t = t.to_owned();
+    let n = t.nrows();
+    let mut w = Vec::with_capacity(n);
+    let mut k = 0;
+    while k < n {
+        if block_size(&t, k) == 2 {
+            let re = (t[[k, k]] + t[[k + 1, k + 1]]) / (F::one() + F::one());
+            let im = t[[k, k + 1]].abs().sqrt() * t[[k + 1, k]].abs().sqrt();
+            w.push(Complex::new(re, im));
+            w.push(Complex::new(re, -im));
+            k += 2;
+        } else {
+            w.push(Complex::new(t[[k, k]], F::zero()));
+            k += 1;
+        }
+    }
+    Array1::from(w)
+}
+
+/// Solve the small Sylvester equation `A11 X - X A22 = A12` (blocks of order 1 or 2)
+///
+/// Uses Gaussian elimination with complete pivoting on the Kronecker form;
+/// tiny pivots are perturbed to `smin` as in LAPACK `dlasy2`.
+fn small_sylvester<F: Float + NumAssign>(
+    a11: &Array2<F>,
+    a22: &Array2<F>,
+    a12: &Array2<F>,
+    smin: F,
+) -> Array2<F> {
+    let (p, q) = a12.dim();
+    let m = p * q;
+    let mut k = Array2::<F>::zeros((m, m));
+    let mut rhs: Vec<F> = a12.iter().copied().collect();
+    for i in 0..p {
+        for j in 0..q {
+            let row = i * q + j;
+            for l in 0..p {
+                k[[row, l * q + j]] += a11[[i, l]];
+            }
+            for l in 0..q {
+                k[[row, i * q + l]] -= a22[[l, j]];
+            }
+        }
+    }
+
+    let mut perm: Vec<usize> = (0..m).collect();
+    for c in 0..m {
+        let (mut pr, mut pc, mut best) = (c, c, F::zero());
+        for i in c..m {
+            for j in c..m {
+                if k[[i, j]].abs() > best {
+                    best = k[[i, j]].abs();
+                    pr = i;
+                    pc = j;
+                }
+            }
+        }
+        if pr != c {
+            for j in 0..m {
+                k.swap([pr, j], [c, j]);
+            }
+            rhs.swap(pr, c);
+        }
+        if pc != c {
+            for i in 0..m {
+                k.swap([i, pc], [i, c]);
+            }
+            perm.swap(pc, c);
+        }
+        if k[[c, c]].abs() < smin {
+            k[[c, c]] = smin;
+        }
+        for i in (c + 1)..m {
+            let f = k[[i, c]] / k[[c, c]];
+            for j in c..m {
+                let v = k[[c, j]];
+                k[[i, j]] -= f * v;
+            }
+            let v = rhs[c];
+            rhs[i] -= f * v;
+        }
+    }
+    let mut y = vec![F::zero(); m];
+    for i in (0..m).rev() {
+        let mut acc = rhs[i];
+        for j in (i + 1)..m {
+            acc -= k[[i, j]] * y[j];
+        }
+        y[i] = acc / k[[i, i]];
+    }
+    let mut x = Array2::zeros((p, q));
+    for (idx, &col) in perm.iter().enumerate() {
+        x[[col / q, col % q]] = y[idx];
+    }
+    x
+}
+
+/// Orthogonal `Q` (square) from the Householder QR factorization of `w`
+fn householder_q<F: Float + NumAssign>(w: &Array2<F>) -> Array2<F> {
+    let (m, q_cols) = w.dim();
+    let mut a = w.clone();
+    let mut q = Array2::<F>::eye(m);
+    let two = F::one() + F::one();
+    for j in 0..q_cols.min(m) {
+        let mut v: Vec<F> = (j..m).map(|i| a[[i, j]]).collect();
+        let norm = v.iter().fold(F::zero(), |acc, &x| acc.hypot(x));
+        if norm == F::zero() {
+            continue;
+        }
+        let alpha = if v[0] > F::zero() { -norm } else { norm };
+        v[0] -= alpha;
+        let v_norm2 = v.iter().fold(F::zero(), |acc, &x| acc + x * x);
+        if v_norm2 == F::zero() {
+            continue;
+        }
+        for c in j..q_cols {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(F::zero(), |acc, (idx, &vi)| acc + vi * a[[j + idx, c]]);
+            let f = two * dot / v_norm2;
+            for (idx, &vi) in v.iter().enumerate() {
+                a[[j + idx, c]] -= f * vi;
+            }
+        }
+        for r in 0..m {
+            let dot = v
+                .iter()
+                .enumerate()
+                .fold(F::zero(), |acc, (idx, &vi)| acc + q[[r, j + idx]] * vi);
+            let f = two * dot / v_norm2;
+            for (idx, &vi) in v.iter().enumerate() {
+                q[[r, j + idx]] -= f * vi;
+            }
+        }
+    }
+    q
+}
+
+/// Swap the adjacent diagonal blocks of sizes `n1` and `n2` starting at `j1`
+/// of a real Schur form (LAPACK `dlaexc`), updating `z` accordingly.
+fn swap_real_blocks<F: Float + NumAssign>(
+    t: &mut Array2<F>,
+    z: &mut Array2<F>,
+    j1: usize,
+    n1: usize,
+    n2: usize,
+) -> LinalgResult<()> {
+    let n = t.nrows();
+    let m = n1 + n2;
+    let block = t.slice(s![j1..j1 + m, j1..j1 + m]).to_owned();
+    let dnorm = block.iter().fold(F::zero(), |acc, &x| acc.max(x.abs()));
+    let eps = F::epsilon();
+    let smin = (eps * dnorm).max(F::min_positive_value());
+
+    let a11 = block.slice(s![..n1, ..n1]).to_owned();
+    let a12 = block.slice(s![..n1, n1..]).to_owned();
+    let a22 = block.slice(s![n1.., n1..]).to_owned();
+    let x = small_sylvester(&a11, &a22, &a12, smin);
+
+    // span [X; -I] is the invariant subspace of the block for A22's eigenvalues
+    let mut w = Array2::<F>::zeros((m, n2));
+    w.slice_mut(s![..n1, ..]).assign(&x);
+    for j in 0..n2 {
+        w[[n1 + j, j]] = -F::one();
+    }
+    let q = householder_q(&w);
+
+    // Check the swapped block before committing to it
+    let mut swapped = Array2::<F>::zeros((m, m));
+    for i in 0..m {
+        for j in 0..m {
+            let mut acc = F::zero();
+            for k in 0..m {
+                for l in 0..m {
+                    acc += q[[k, i]] * block[[k, l]] * q[[l, j]];
+                }
+            }
+            swapped[[i, j]] = acc;
+        }
+    }
+    let residual = swapped
+        .slice(s![n2.., ..n2])
+        .iter()
+        .fold(F::zero(), |acc, &v| acc.max(v.abs()));
+    let thresh = (F::from(10.0).unwrap() * eps * dnorm).max(F::min_positive_value());
+    if residual > thresh {
+        return Err(LinalgError::ComputationError(format!(
+            "Swapping Schur blocks at {} failed: eigenvalues are too close to reorder",
+            j1
+        )));
+    }
+
+    // T <- Q^T T Q on the affected rows and columns; Z <- Z Q
+    let rows = t.slice(s![j1..j1 + m, j1..]).to_owned();
+    for i in 0..m {
+        for j in 0..(n - j1) {
+            let mut acc = F::zero();
+            for k in 0..m {
+                acc += q[[k, i]] * rows[[k, j]];
+            }
+            t[[j1 + i, j1 + j]] = acc;
+        }
+    }
+    multiply_columns(t, j1, &q, j1 + m);
+    multiply_columns(z, j1, &q, n);
+
+    for i in (j1 + n2)..(j1 + m) {
+        for j in j1..(j1 + n2) {
+            t[[i, j]] = F::zero();
+        }
+    }
+    if n2 == 2 {
+        standardize_block(t, z, j1);
+    }
+    if n1 == 2 {
+        standardize_block(t, z, j1 + n2);
+    }
+    Ok(())
+}
+
+/// Replace columns `j1..j1 + q.nrows()` of the first `rows` rows of `mat` by their product with `q`
+fn multiply_columns<F: Float + NumAssign>(
+    mat: &mut Array2<F>,
+    j1: usize,
+    q: &Array2<F>,
+    rows: usize,
+) {
+    let m = q.nrows();
+    let cols = mat.slice(s![..rows, j1..j1 + m]).to_owned();
+    for i in 0..rows {
+        for j in 0..m {
+            let mut acc = F::zero();
+            for k in 0..m {
+                acc += cols[[i, k]] * q[[k, j]];
+            }
+            mat[[i, j1 + j]] = acc;
+        }
+    }
+}
+
+/// Reorder a real Schur form so that selected eigenvalues come first
+///
+/// Given `A = Z T Z^T` from [`real_schur`], computes a new real Schur form in
+/// which the eigenvalues with `select[i] == true` occupy the leading diagonal
+/// block. A 2x2 block for a complex conjugate pair is moved as a unit and is
+/// selected if either of its two entries is. Adjacent blocks are exchanged
+/// with the direct swapping method of Bai and Demmel (LAPACK `dtrexc`); the
+/// call fails if a swap would be numerically unstable.
+///
+/// # Arguments
+///
+/// * `t` - Upper quasi-triangular Schur factor
+/// * `z` - Orthogonal Schur vectors
+/// * `select` - Flags for the diagonal entries of `t` to move to the top-left
+///
+/// # Returns
+///
+/// * Reordered `(T, Z)`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::schur::{ordschur_real, real_schur, real_schur_eigenvalues};
+///
+/// let a = array![[3.0_f64, 1.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]];
+/// let (t, z) = real_schur(&a.view()).unwrap();
+///
+/// // Move the complex pair +-i in front of the eigenvalue 3
+/// let select: Vec<bool> = real_schur_eigenvalues(&t.view()).iter().map(|l| l.im != 0.0).collect();
+/// let (t2, z2) = ordschur_real(&t.view(), &z.view(), &select).unwrap();
+/// assert!((t2[[2, 2]] - 3.0).abs() < 1e-12);
+///
+/// // The first two columns of Z span the invariant subspace of +-i
+/// let recon = z2.dot(&t2).dot(&z2.t());
+/// for (x, y) in recon.iter().zip(a.iter()) {
+///     assert!((x - y).abs() < 1e-12);
+/// }
+/// ```
+pub fn ordschur_real<F: Float + NumAssign>(
+    t: &ArrayView2<F>,
+    z: &ArrayView2<F>,
+    select: &[bool],
+) -> LinalgResult<(Array2<F>, Array2<F>)> {
+    check_schur_pair(t.dim(), z.dim(), select.len())?;
+    let n = t.nrows();
+    for j in 0..n {
+        for i in (j + 2)..n {
+            if t[[i, j]] != F::zero() {
+                return Err(LinalgError::InvalidInputError(
+                    "ordschur_real requires an upper quasi-triangular Schur factor".to_string(),
+                ));
+            }
+        }
+        if j + 2 < n && t[[j + 1, j]] != F::zero() && t[[j + 2, j + 1]] != F::zero() {
+            return Err(LinalgError::InvalidInputError(
+                "ordschur_real requires diagonal blocks of order at most 2".to_string(),
+            ));
+        }
+    }
+
+    let mut t = t.to_owned();
+    let mut z = z.to_owned();
+    let mut ks = 0;
+    let mut k = 0;
+    while k < n {
+        let size = block_size(&t, k);
+        let selected = select[k] || (size == 2 && select[k + 1]);
+        if selected {
+            // Bubble the block up past the unselected blocks in front of it
+            let mut pos = k;
+            while pos > ks {
+                let prev = if pos >= 2 && t[[pos - 1, pos - 2]] != F::zero() {
+                    2
+                } else {
+                    1
+                };
+                let cur = block_size(&t, pos);
+                swap_real_blocks(&mut t, &mut z, pos - prev, prev, cur)?;
+                pos -= prev;
+            }
+            ks += size;
+        }
+        k += size;
+    }
+    Ok((t, z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn reconstruct(t: &Array2<Complex<f64>>, z: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+        let zh = z.t().mapv(|x| x.conj());
+        z.dot(t).dot(&zh)
+    }
+
+    fn max_diff(a: &Array2<Complex<f64>>, b: &Array2<Complex<f64>>) -> f64 {
+        a.iter()
+            .zip(b.iter())
+            .fold(0.0, |acc, (x, y)| acc.max((x - y).norm()))
+    }
+
+    #[test]
+    fn test_hessenberg_complex_structure() {
+        let a = Array2::from_shape_fn((5, 5), |(i, j)| {
+            Complex::new(((i * 5 + j) as f64).sin(), ((i + 2 * j) as f64).cos())
+        });
+        let (h, q) = hessenberg_complex(&a.view());
+
+        for i in 0..5usize {
+            for j in 0..i.saturating_sub(1) {
+                assert_eq!(h[[i, j]], Complex::zero());
+            }
+        }
+        assert!(max_diff(&reconstruct(&h, &q), &a) < 1e-12);
+    }
+
+    #[test]
+    fn test_hessenberg_real() {
+        let n = 6;
+        let a = Array2::from_shape_fn((n, n), |(i, j)| ((i * 7 + j * 3) % 5) as f64 - 1.5);
+        let (h, q) = hessenberg(&a.view()).unwrap();
+        for i in 0..n {
+            for j in 0..i.saturating_sub(1) {
+                assert_eq!(h[[i, j]], 0.0);
+            }
+        }
+        let qtq = q.t().dot(&q);
+        let back = q.dot(&h).dot(&q.t());
+        for i in 0..n {
+            for j in 0..n {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((qtq[[i, j]] - expected).abs() < 1e-12);
+                assert!((back[[i, j]] - a[[i, j]]).abs() < 1e-12);
+            }
+        }
+        // The first column of Q is e_1, as Arnoldi with start vector e_1 expects
+        assert!((q[[0, 0]] - 1.0).abs() < 1e-15);
+
+        assert!(hessenberg(&Array2::<f64>::zeros((2, 3)).view()).is_err());
+        let c = Array2::<Complex<f64>>::zeros((3, 2));
+        assert!(complex_hessenberg(&c.view()).is_err());
+    }
+
+    #[test]
+    fn test_complex_schur_real_matrix_with_complex_eigenvalues() {
+        let a = array![[0.0, -1.0, 2.0], [1.0, 0.0, 0.5], [0.0, 0.3, 3.0]]
+            .mapv(|x: f64| Complex::new(x, 0.0));
+        let (t, z) = complex_schur(&a.view()).unwrap();
+
+        for i in 0..3 {
+            for j in 0..i {
+                assert_eq!(t[[i, j]], Complex::zero());
+            }
+        }
+        assert!(max_diff(&reconstruct(&t, &z), &a) < 1e-12);
+
+        let zhz = z.t().mapv(|x| x.conj()).dot(&z);
+        assert!(max_diff(&zhz, &Array2::eye(3)) < 1e-12);
+
+        let trace: Complex<f64> = (0..3).map(|i| t[[i, i]]).sum();
+        assert!((trace - Complex::new(3.0, 0.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_complex_schur_defective_matrix() {
+        let a = array![[2.0, 1.0, 0.0], [0.0, 2.0, 1.0], [0.0, 0.0, 2.0]]
+            .mapv(|x: f64| Complex::new(x, 0.0));
+        let (t, z) = complex_schur(&a.view()).unwrap();
+        assert!(max_diff(&reconstruct(&t, &z), &a) < 1e-12);
+    }
+
+    #[test]
+    fn test_swap_schur_adjacent() {
+        let a = Array2::from_shape_fn((4, 4), |(i, j)| {
+            Complex::new(((i + 3 * j) as f64 * 0.7).cos() + i as f64, 0.1 * j as f64)
+        });
+        let (mut t, mut z) = complex_schur(&a.view()).unwrap();
+        let (l1, l2) = (t[[1, 1]], t[[2, 2]]);
+
+        swap_schur_adjacent(&mut t, &mut z, 1);
+
+        assert_eq!(t[[1, 1]], l2);
+        assert_eq!(t[[2, 2]], l1);
+        assert!(max_diff(&reconstruct(&t, &z), &a) < 1e-11);
+    }
+
+    fn check_real_schur(a: &Array2<f64>, t: &Array2<f64>, z: &Array2<f64>, tol: f64) {
+        let n = a.nrows();
+        let recon = z.dot(t).dot(&z.t());
+        for (x, y) in recon.iter().zip(a.iter()) {
+            assert!(
+                (x - y).abs() < tol,
+                "reconstruction error {}",
+                (x - y).abs()
+            );
+        }
+        let ztz = z.t().dot(z);
+        for i in 0..n {
+            for j in 0..n {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((ztz[[i, j]] - expected).abs() < tol);
+            }
+        }
+        // Quasi-triangular with standardized 2x2 blocks
+        for j in 0..n {
+            for i in (j + 2)..n {
+                assert_eq!(t[[i, j]], 0.0);
+            }
+            if j + 1 < n && t[[j + 1, j]] != 0.0 {
+                assert!(j + 2 >= n || t[[j + 2, j + 1]] == 0.0);
+                assert!((t[[j, j]] - t[[j + 1, j + 1]]).abs() < tol);
+                assert!(t[[j, j + 1]] * t[[j + 1, j]] < 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_real_schur_general_matrices() {
+        for &n in &[1usize, 2, 3, 5, 8, 13] {
+            let a = Array2::from_shape_fn((n, n), |(i, j)| {
+                ((i * 7 + j * 3) as f64 * 0.37).sin() + if i == j { 0.5 } else { 0.0 }
+            });
+            let (t, z) = real_schur(&a.view()).unwrap();
+            check_real_schur(&a, &t, &z, 1e-11);
+
+            let w = real_schur_eigenvalues(&t.view());
+            let trace: f64 = (0..n).map(|i| a[[i, i]]).sum();
+            let sum: Complex<f64> = w.iter().sum();
+            assert!((sum.re - trace).abs() < 1e-10);
+            assert!(sum.im.abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_real_schur_rotation_blocks() {
+        // Block diagonal rotation generators with eigenvalues +-i and +-2i
+        let a = array![
+            [0.0, -1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, -4.0],
+            [0.0, 0.0, 1.0, 0.0]
+        ];
+        let (t, z) = real_schur(&a.view()).unwrap();
+        check_real_schur(&a, &t, &z, 1e-12);
+        let mut im: Vec<f64> = real_schur_eigenvalues(&t.view())
+            .iter()
+            .map(|l| l.im)
+            .collect();
+        im.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        for (got, expected) in im.iter().zip([-2.0, -1.0, 1.0, 2.0]) {
+            assert!((got - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ordschur_complex() {
+        let a = Array2::from_shape_fn((5, 5), |(i, j)| {
+            Complex::new(
+                ((2 * i + j) as f64).cos() + i as f64,
+                ((i * j) as f64).sin(),
+            )
+        });
+        let (t, z) = complex_schur(&a.view()).unwrap();
+        let diag: Vec<Complex<f64>> = t.diag().to_vec();
+        let select: Vec<bool> = diag.iter().map(|l| l.re < 2.0).collect();
+        let (t2, z2) = ordschur(&t.view(), &z.view(), &select).unwrap();
+
+        assert!(max_diff(&reconstruct(&t2, &z2), &a) < 1e-10);
+        let count = select.iter().filter(|&&x| x).count();
+        for k in 0..5 {
+            assert_eq!(t2[[k, k]].re < 2.0, k < count);
+        }
+        // Selected eigenvalues keep their relative order
+        let selected: Vec<Complex<f64>> = diag.iter().copied().filter(|l| l.re < 2.0).collect();
+        for (k, l) in selected.iter().enumerate() {
+            assert!((t2[[k, k]] - l).norm() < 1e-10);
+        }
+
+        assert!(ordschur(&t.view(), &z.view(), &[true]).is_err());
+    }
+
+    #[test]
+    fn test_ordschur_real_mixed_blocks() {
+        // Eigenvalues 4, +-i (block), -1, 2 +- 3i (block)
+        let a = array![
+            [4.0, 1.0, 0.5, 0.2, 0.1, 0.3],
+            [0.0, 0.0, -1.0, 0.4, 0.0, 0.2],
+            [0.0, 1.0, 0.0, 0.1, 0.7, 0.0],
+            [0.0, 0.0, 0.0, -1.0, 0.5, 0.6],
+            [0.0, 0.0, 0.0, 0.0, 2.0, -3.0],
+            [0.0, 0.0, 0.0, 0.0, 3.0, 2.0]
+        ];
+        let q = householder_q(&Array2::from_shape_fn((6, 6), |(i, j)| {
+            ((i * 6 + j) as f64 * 1.3).sin()
+        }));
+        let a = q.dot(&a).dot(&q.t());
+        let (t, z) = real_schur(&a.view()).unwrap();
+        check_real_schur(&a, &t, &z, 1e-11);
+
+        // Select the eigenvalues with non-positive real part: +-i and -1
+        let select: Vec<bool> = real_schur_eigenvalues(&t.view())
+            .iter()
+            .map(|l| l.re < 0.5)
+            .collect();
+        let (t2, z2) = ordschur_real(&t.view(), &z.view(), &select).unwrap();
+        check_real_schur(&a, &t2, &z2, 1e-10);
+
+        let w = real_schur_eigenvalues(&t2.view());
+        for (k, l) in w.iter().enumerate() {
+            assert_eq!(l.re < 0.5, k < 3, "eigenvalue {} at position {}", l, k);
+        }
+
+        // The leading columns span an invariant subspace: A Z1 = Z1 T11
+        let z1 = z2.slice(s![.., ..3]).to_owned();
+        let t11 = t2.slice(s![..3, ..3]).to_owned();
+        let lhs = a.dot(&z1);
+        let rhs = z1.dot(&t11);
+        for (x, y) in lhs.iter().zip(rhs.iter()) {
+            assert!((x - y).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_ordschur_real_rejects_non_schur_input() {
+        let t = array![[1.0, 2.0, 3.0], [0.0, 1.0, 1.0], [1.0, 0.0, 2.0]];
+        let z = Array2::<f64>::eye(3);
+        assert!(ordschur_real(&t.view(), &z.view(), &[true, false, false]).is_err());
+    }
+}
@@ -3,17 +3,24 @@
 //! This module provides highly optimized SIMD implementations of GEMM operations
 //! using cache-friendly blocking strategies. All SIMD operations are delegated
 //! to scirs2-core::simd_ops for unified optimization management.
+//!
+//! The right-hand operand is packed transposed once per call so that every
+//! inner product runs over two contiguous slices; strided columns would
+//! otherwise force the scalar fallback of `simd_dot`.
 
 #[cfg(feature = "simd")]
 use crate::error::{LinalgError, LinalgResult};
 #[cfg(feature = "simd")]
-use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2};
+#[cfg(feature = "simd")]
+use num_traits::Float;
 #[cfg(feature = "simd")]
 use scirs2_core::simd_ops::SimdUnifiedOps;
 
 /// Cache-friendly block sizes for GEMM operations
 /// These should be tuned for target CPU cache hierarchy
 #[cfg(feature = "simd")]
+#[derive(Debug, Clone, Copy)]
 pub struct GemmBlockSizes {
     /// Block size for M dimension (rows of A, rows of C)
     pub mc: usize,
@@ -42,7 +49,8 @@ impl Default for GemmBlockSizes {
 
 /// SIMD-accelerated GEMM for f32: C = alpha * A * B + beta * C
 ///
-/// This implementation uses the unified SIMD operations from scirs2-core.
+/// This implementation uses the unified SIMD operations from scirs2-core on
+/// packed, cache-blocked panels of A and B.
 ///
 /// # Arguments
 ///
@@ -51,7 +59,7 @@ impl Default for GemmBlockSizes {
 /// * `b` - Right matrix B (K x N)
 /// * `beta` - Scalar multiplier for C
 /// * `c` - Result matrix C (M x N), updated in-place
-/// * `_block_sizes` - Cache-friendly block size configuration (unused in unified implementation)
+/// * `block_sizes` - Cache-friendly block size configuration (defaults if `None`)
 ///
 /// # Returns
 ///
@@ -63,7 +71,7 @@ pub fn simd_gemm_f32(
     b: &ArrayView2<f32>,
     beta: f32,
     c: &mut Array2<f32>,
-    _block_sizes: Option<GemmBlockSizes>,
+    block_sizes: Option<GemmBlockSizes>,
 ) -> LinalgResult<()> {
     let (m, k1) = a.dim();
     let (k2, n) = b.dim();
@@ -83,15 +91,15 @@ pub fn simd_gemm_f32(
         )));
     }
 
-    // Use unified SIMD GEMM operation
-    f32::simd_gemm(alpha, a, b, beta, c);
+    blocked_gemm(alpha, a, b, beta, c, &block_sizes.unwrap_or_default());
 
     Ok(())
 }
 
 /// SIMD-accelerated GEMM for f64: C = alpha * A * B + beta * C
 ///
-/// This implementation uses the unified SIMD operations from scirs2-core.
+/// This implementation uses the unified SIMD operations from scirs2-core on
+/// packed, cache-blocked panels of A and B.
 ///
 /// # Arguments
 ///
@@ -100,7 +108,7 @@ pub fn simd_gemm_f32(
 /// * `b` - Right matrix B (K x N)
 /// * `beta` - Scalar multiplier for C
 /// * `c` - Result matrix C (M x N), updated in-place
-/// * `_block_sizes` - Cache-friendly block size configuration (unused in unified implementation)
+/// * `block_sizes` - Cache-friendly block size configuration (defaults if `None`)
 ///
 /// # Returns
 ///
@@ -112,7 +120,7 @@ pub fn simd_gemm_f64(
     b: &ArrayView2<f64>,
     beta: f64,
     c: &mut Array2<f64>,
-    _block_sizes: Option<GemmBlockSizes>,
+    block_sizes: Option<GemmBlockSizes>,
 ) -> LinalgResult<()> {
     let (m, k1) = a.dim();
     let (k2, n) = b.dim();
@@ -132,13 +140,65 @@ pub fn simd_gemm_f64(
         )));
     }
 
-    // Use unified SIMD GEMM operation
-    f64::simd_gemm(alpha, a, b, beta, c);
+    blocked_gemm(alpha, a, b, beta, c, &block_sizes.unwrap_or_default());
 
     Ok(())
 }
 
-// Note: Micro-kernel functions have been removed as we now use unified SIMD operations from scirs2-core
+/// Blocked GEMM kernel shared by the f32 and f64 entry points
+///
+/// A is brought into row-major order and B is packed as B^T, so each update of
+/// `C[i, j]` is one `simd_dot` over contiguous `kc`-long slices. The `nc`,
+/// `kc` and `mc` loops keep a panel of B^T and a block of A resident in cache
+/// while they are reused; register tiling is left to `simd_dot`.
+#[cfg(feature = "simd")]
+pub(crate) fn blocked_gemm<T>(
+    alpha: T,
+    a: &ArrayView2<T>,
+    b: &ArrayView2<T>,
+    beta: T,
+    c: &mut Array2<T>,
+    block_sizes: &GemmBlockSizes,
+) where
+    T: SimdUnifiedOps + Float,
+{
+    let (m, k) = a.dim();
+    let n = b.ncols();
+
+    // BLAS semantics: C is not read when beta is zero
+    if beta == T::zero() {
+        c.fill(T::zero());
+    } else if beta != T::one() {
+        c.mapv_inplace(|v| v * beta);
+    }
+    if m == 0 || n == 0 || k == 0 || alpha == T::zero() {
+        return;
+    }
+
+    let a_packed = a.as_standard_layout();
+    let b_t = b.t();
+    let b_packed = b_t.as_standard_layout();
+    let mc = block_sizes.mc.max(1);
+    let kc = block_sizes.kc.max(1);
+    let nc = block_sizes.nc.max(1);
+
+    for jc in (0..n).step_by(nc) {
+        let j_end = (jc + nc).min(n);
+        for pc in (0..k).step_by(kc) {
+            let p_end = (pc + kc).min(k);
+            for ic in (0..m).step_by(mc) {
+                let i_end = (ic + mc).min(m);
+                for i in ic..i_end {
+                    let a_row = a_packed.slice(s![i, pc..p_end]);
+                    for j in jc..j_end {
+                        let b_col = b_packed.slice(s![j, pc..p_end]);
+                        c[[i, j]] = c[[i, j]] + alpha * T::simd_dot(&a_row, &b_col);
+                    }
+                }
+            }
+        }
+    }
+}
 
 /// Convenience function for SIMD matrix multiplication: C = A * B
 ///
@@ -446,6 +506,27 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "simd")]
+    fn test_simd_gemm_strided_operands() {
+        // Transposed views and a NaN-filled C with beta = 0
+        let a = Array2::from_shape_fn((23, 17), |(i, j)| ((i * 5 + j) % 11) as f32 * 0.1);
+        let b = Array2::from_shape_fn((31, 17), |(i, j)| ((i + 3 * j) % 7) as f32 * 0.2 - 0.5);
+        let mut c = Array2::from_elem((23, 31), f32::NAN);
+        let block_sizes = GemmBlockSizes {
+            mc: 5,
+            kc: 4,
+            nc: 6,
+            mr: 8,
+            nr: 8,
+        };
+        simd_gemm_f32(2.0, &a.view(), &b.t(), 0.0, &mut c, Some(block_sizes)).unwrap();
+        let c_ref = a.dot(&b.t()) * 2.0;
+        for (p, q) in c.iter().zip(c_ref.iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-4);
+        }
+    }
+
     #[test]
     #[cfg(feature = "simd")]
     fn test_gemm_error_handling() {
@@ -0,0 +1,362 @@
+//! Half precision (f16) storage with f32 accumulation
+//!
+//! Operands are kept in IEEE binary16 to halve memory traffic, but every
+//! product and sum is formed in f32: data is widened into f32 scratch
+//! buffers (row by row where the operation allows), the f32 SIMD kernels of
+//! this module do the work, and results are rounded back to f16 only once. Norms are
+//! returned in f32 because they easily exceed the f16 range (65504).
+
+#[cfg(feature = "simd")]
+use super::gemm::{blocked_gemm, GemmBlockSizes};
+#[cfg(feature = "simd")]
+use super::triangular::substitute;
+#[cfg(feature = "simd")]
+use crate::error::{LinalgError, LinalgResult};
+#[cfg(feature = "simd")]
+use half::{f16, slice::HalfFloatSliceExt};
+#[cfg(feature = "simd")]
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+#[cfg(feature = "simd")]
+use scirs2_core::simd_ops::SimdUnifiedOps;
+
+/// Widen an f16 vector into `buffer`, reusing its allocation
+#[cfg(feature = "simd")]
+fn widen_into(src: &ArrayView1<f16>, buffer: &mut Vec<f32>) {
+    buffer.clear();
+    match src.as_slice() {
+        Some(slice) => {
+            buffer.resize(slice.len(), 0.0);
+            slice.convert_to_f32_slice(buffer);
+        }
+        None => buffer.extend(src.iter().map(|v| v.to_f32())),
+    }
+}
+
+/// Widen an f16 matrix to f32
+#[cfg(feature = "simd")]
+fn widen_matrix(src: &ArrayView2<f16>) -> Array2<f32> {
+    match src.as_slice() {
+        Some(slice) => {
+            let mut buffer = vec![0.0f32; slice.len()];
+            slice.convert_to_f32_slice(&mut buffer);
+            Array2::from_shape_vec(src.dim(), buffer).expect("shape matches slice length")
+        }
+        None => src.mapv(f16::to_f32),
+    }
+}
+
+/// SIMD-accelerated GEMM with f16 storage: C = alpha * A * B + beta * C
+///
+/// A and B are widened to f32, multiplied with the blocked f32 kernel and
+/// the result is rounded to f16 once, so the accumulation error is that of
+/// f32 rather than f16.
+///
+/// # Arguments
+///
+/// * `alpha` - Scalar multiplier for A*B
+/// * `a` - Left matrix A (M x K)
+/// * `b` - Right matrix B (K x N)
+/// * `beta` - Scalar multiplier for C
+/// * `c` - Result matrix C (M x N), updated in-place
+/// * `block_sizes` - Cache-friendly block size configuration (defaults if `None`)
+///
+/// # Returns
+///
+/// * Result indicating success or error
+#[cfg(feature = "simd")]
+pub fn simd_gemm_f16(
+    alpha: f32,
+    a: &ArrayView2<f16>,
+    b: &ArrayView2<f16>,
+    beta: f32,
+    c: &mut Array2<f16>,
+    block_sizes: Option<GemmBlockSizes>,
+) -> LinalgResult<()> {
+    let (m, k1) = a.dim();
+    let (k2, n) = b.dim();
+    if k1 != k2 {
+        return Err(LinalgError::ShapeError(format!(
+            "Matrix inner dimensions must match: A({}, {}) * B({}, {})",
+            m, k1, k2, n
+        )));
+    }
+    if c.dim() != (m, n) {
+        return Err(LinalgError::ShapeError(format!(
+            "Result matrix dimensions must match: C({}, {}) for A({}, {}) * B({}, {})",
+            c.nrows(),
+            c.ncols(),
+            m,
+            k1,
+            k2,
+            n
+        )));
+    }
+
+    let a32 = widen_matrix(a);
+    let b32 = widen_matrix(b);
+    let mut c32 = if beta == 0.0 {
+        Array2::zeros((m, n))
+    } else {
+        widen_matrix(&c.view())
+    };
+    blocked_gemm(
+        alpha,
+        &a32.view(),
+        &b32.view(),
+        beta,
+        &mut c32,
+        &block_sizes.unwrap_or_default(),
+    );
+    c.zip_mut_with(&c32, |dst, &src| *dst = f16::from_f32(src));
+    Ok(())
+}
+
+/// Matrix multiplication with f16 storage and f32 accumulation: C = A * B
+///
+/// # Arguments
+///
+/// * `a` - Left matrix (M x K)
+/// * `b` - Right matrix (K x N)
+///
+/// # Returns
+///
+/// * Result matrix C (M x N)
+#[cfg(feature = "simd")]
+pub fn simd_matmul_f16(a: &ArrayView2<f16>, b: &ArrayView2<f16>) -> LinalgResult<Array2<f16>> {
+    let mut c = Array2::from_elem((a.nrows(), b.ncols()), f16::ZERO);
+    simd_gemm_f16(1.0, a, b, 0.0, &mut c, None)?;
+    Ok(c)
+}
+
+/// Matrix-vector multiplication with f16 storage: y = alpha * A * x + beta * y
+///
+/// Rows of A are widened one at a time, so the scratch memory is O(N).
+///
+/// # Arguments
+///
+/// * `alpha` - Scalar multiplier for A*x
+/// * `a` - Matrix A (M x N)
+/// * `x` - Vector x (N,)
+/// * `beta` - Scalar multiplier for y
+/// * `y` - Result vector y (M,), updated in-place
+///
+/// # Returns
+///
+/// * Result indicating success or error
+#[cfg(feature = "simd")]
+pub fn simd_gemv_f16(
+    alpha: f32,
+    a: &ArrayView2<f16>,
+    x: &ArrayView1<f16>,
+    beta: f32,
+    y: &mut Array1<f16>,
+) -> LinalgResult<()> {
+    let (m, n) = a.dim();
+    if x.len() != n || y.len() != m {
+        return Err(LinalgError::ShapeError(format!(
+            "Dimension mismatch: A({}, {}) * x({}) -> y({})",
+            m,
+            n,
+            x.len(),
+            y.len()
+        )));
+    }
+
+    let mut x32 = Vec::with_capacity(n);
+    widen_into(x, &mut x32);
+    let x32 = ArrayView1::from(&x32);
+    let mut row32 = Vec::with_capacity(n);
+    for (row, yi) in a.rows().into_iter().zip(y.iter_mut()) {
+        widen_into(&row, &mut row32);
+        let ax = f32::simd_dot(&ArrayView1::from(&row32), &x32);
+        let previous = if beta == 0.0 { 0.0 } else { beta * yi.to_f32() };
+        *yi = f16::from_f32(alpha * ax + previous);
+    }
+    Ok(())
+}
+
+/// Dot product of two f16 vectors accumulated in f32
+///
+/// # Arguments
+///
+/// * `a` - First vector
+/// * `b` - Second vector of the same length
+///
+/// # Returns
+///
+/// * Dot product in f32
+#[cfg(feature = "simd")]
+pub fn simd_dot_f16(a: &ArrayView1<f16>, b: &ArrayView1<f16>) -> LinalgResult<f32> {
+    if a.len() != b.len() {
+        return Err(LinalgError::ShapeError(format!(
+            "Vectors must have the same length, got {} and {}",
+            a.len(),
+            b.len()
+        )));
+    }
+    let mut a32 = Vec::with_capacity(a.len());
+    let mut b32 = Vec::with_capacity(b.len());
+    widen_into(a, &mut a32);
+    widen_into(b, &mut b32);
+    Ok(f32::simd_dot(
+        &ArrayView1::from(&a32),
+        &ArrayView1::from(&b32),
+    ))
+}
+
+/// Euclidean norm of an f16 vector accumulated in f32
+///
+/// # Arguments
+///
+/// * `vector` - Input vector
+///
+/// # Returns
+///
+/// * Euclidean norm of the vector in f32
+#[cfg(feature = "simd")]
+pub fn simd_vector_norm_f16(vector: &ArrayView1<f16>) -> f32 {
+    let mut buffer = Vec::with_capacity(vector.len());
+    widen_into(vector, &mut buffer);
+    f32::simd_norm(&ArrayView1::from(&buffer))
+}
+
+/// Frobenius norm of an f16 matrix accumulated in f32
+///
+/// # Arguments
+///
+/// * `matrix` - Input matrix
+///
+/// # Returns
+///
+/// * Frobenius norm of the matrix in f32
+#[cfg(feature = "simd")]
+pub fn simd_frobenius_norm_f16(matrix: &ArrayView2<f16>) -> f32 {
+    let mut buffer = Vec::with_capacity(matrix.ncols());
+    let mut sum_sq = 0.0f32;
+    for row in matrix.rows() {
+        widen_into(&row, &mut buffer);
+        let row32 = ArrayView1::from(&buffer);
+        sum_sq += f32::simd_dot(&row32, &row32);
+    }
+    sum_sq.sqrt()
+}
+
+/// Solve a triangular system `A x = b` with f16 storage and f32 arithmetic
+///
+/// # Arguments
+///
+/// * `a` - Triangular matrix A (N x N); entries outside the triangle are ignored
+/// * `b` - Right-hand side (N,)
+/// * `lower` - Whether A is lower (true) or upper (false) triangular
+/// * `unit_diagonal` - Whether to assume a unit diagonal
+///
+/// # Returns
+///
+/// * Solution vector x (N,), rounded to f16
+#[cfg(feature = "simd")]
+pub fn simd_solve_triangular_f16(
+    a: &ArrayView2<f16>,
+    b: &ArrayView1<f16>,
+    lower: bool,
+    unit_diagonal: bool,
+) -> LinalgResult<Array1<f16>> {
+    let (n, n2) = a.dim();
+    if n != n2 || b.len() != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Triangular solve requires a square matrix matching the right-hand side, got {}x{} and {}",
+            n,
+            n2,
+            b.len()
+        )));
+    }
+    let a32 = widen_matrix(&a.as_standard_layout().view());
+    let mut x = b.mapv(f16::to_f32);
+    substitute(&a32.view(), &mut x, lower, unit_diagonal)?;
+    Ok(x.mapv(f16::from_f32))
+}
+
+#[cfg(all(test, feature = "simd"))]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn to_f16(a: &Array2<f64>) -> Array2<f16> {
+        a.mapv(f16::from_f64)
+    }
+
+    #[test]
+    fn test_simd_gemm_f16_accumulates_in_f32() {
+        // K = 1024 products of 0.1 * 0.1: accumulating in f16 would stall far
+        // below the true sum once the spacing of f16 exceeds each increment
+        let k = 1024;
+        let a = Array2::from_elem((3, k), f16::from_f32(0.1));
+        let b = Array2::from_elem((k, 2), f16::from_f32(0.1));
+        let c = simd_matmul_f16(&a.view(), &b.view()).unwrap();
+        let exact = k as f32 * f16::from_f32(0.1).to_f32().powi(2);
+        for v in c.iter() {
+            assert_relative_eq!(v.to_f32(), exact, max_relative = 1e-3);
+        }
+
+        // General shapes, non-contiguous operands and beta
+        let a64 = Array2::from_shape_fn((9, 13), |(i, j)| ((i * 3 + j) % 7) as f64 * 0.25 - 0.5);
+        let b64 = Array2::from_shape_fn((11, 13), |(i, j)| ((i + 2 * j) % 5) as f64 * 0.5 - 1.0);
+        let c64 = Array2::from_shape_fn((9, 11), |(i, j)| (i + j) as f64 * 0.125);
+        let a16 = to_f16(&a64);
+        let b16 = to_f16(&b64);
+        let mut c16 = to_f16(&c64);
+        simd_gemm_f16(0.5, &a16.view(), &b16.t(), 2.0, &mut c16, None).unwrap();
+        let expected = a64.dot(&b64.t()) * 0.5 + &c64 * 2.0;
+        for (p, q) in c16.iter().zip(expected.iter()) {
+            assert_relative_eq!(p.to_f64(), *q, epsilon = 2e-2);
+        }
+
+        let mut wrong = Array2::from_elem((2, 2), f16::ZERO);
+        assert!(simd_gemm_f16(1.0, &a16.view(), &b16.t(), 0.0, &mut wrong, None).is_err());
+    }
+
+    #[test]
+    fn test_simd_gemv_and_norms_f16() {
+        let a64 = Array2::from_shape_fn((5, 40), |(i, j)| ((i * 11 + j) % 9) as f64 * 0.125);
+        let x64 = Array1::from_shape_fn(40, |j| (j % 4) as f64 - 1.5);
+        let a16 = to_f16(&a64);
+        let x16 = x64.mapv(f16::from_f64);
+        let mut y = Array1::from_elem(5, f16::ONE);
+        simd_gemv_f16(1.0, &a16.view(), &x16.view(), -1.0, &mut y).unwrap();
+        let expected = a64.dot(&x64) - 1.0;
+        for (p, q) in y.iter().zip(expected.iter()) {
+            assert_relative_eq!(p.to_f64(), *q, epsilon = 1e-2);
+        }
+
+        let dot = simd_dot_f16(&x16.view(), &x16.view()).unwrap();
+        assert_relative_eq!(dot as f64, x64.dot(&x64), epsilon = 1e-4);
+        assert!(simd_dot_f16(&x16.view(), &x16.slice(ndarray::s![..3])).is_err());
+
+        // 300^2 * 1000 overflows f16 but not the f32 accumulator
+        let big = Array1::from_elem(1000, f16::from_f32(300.0));
+        assert_relative_eq!(
+            simd_vector_norm_f16(&big.view()),
+            300.0 * 1000f32.sqrt(),
+            max_relative = 1e-5
+        );
+        let frob = simd_frobenius_norm_f16(&a16.t());
+        let frob_ref = a64.iter().map(|v| v * v).sum::<f64>().sqrt();
+        assert_relative_eq!(frob as f64, frob_ref, max_relative = 1e-5);
+    }
+
+    #[test]
+    fn test_simd_solve_triangular_f16() {
+        let a64 = ndarray::array![[2.0, 1.0, -1.0], [0.0, 4.0, 0.5], [0.0, 0.0, 0.25]];
+        let b64 = ndarray::array![1.0, 2.0, 0.5];
+        let x = simd_solve_triangular_f16(
+            &to_f16(&a64).view(),
+            &b64.mapv(f16::from_f64).view(),
+            false,
+            false,
+        )
+        .unwrap();
+        let back = a64.dot(&x.mapv(f16::to_f64));
+        for i in 0..3 {
+            assert_relative_eq!(back[i], b64[i], epsilon = 1e-2);
+        }
+    }
+}
@@ -6,8 +6,10 @@
 
 pub mod elementwise;
 pub mod gemm;
+pub mod half_precision;
 pub mod norms;
 pub mod transpose;
+pub mod triangular;
 
 // Re-export commonly used SIMD operations
 #[cfg(feature = "simd")]
@@ -21,11 +23,20 @@ pub use gemm::{
     simd_matmul_optimized_f64, GemmBlockSizes,
 };
 #[cfg(feature = "simd")]
+pub use half_precision::{
+    simd_dot_f16, simd_frobenius_norm_f16, simd_gemm_f16, simd_gemv_f16, simd_matmul_f16,
+    simd_solve_triangular_f16, simd_vector_norm_f16,
+};
+#[cfg(feature = "simd")]
 pub use norms::{
     simd_frobenius_norm_f32, simd_frobenius_norm_f64, simd_vector_norm_f32, simd_vector_norm_f64,
 };
 #[cfg(feature = "simd")]
 pub use transpose::{simd_transpose_f32, simd_transpose_f64};
+#[cfg(feature = "simd")]
+pub use triangular::{
+    simd_solve_triangular_f32, simd_solve_triangular_f64, simd_solve_triangular_multiple_f32,
+};
 
 use crate::error::{LinalgError, LinalgResult};
 use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
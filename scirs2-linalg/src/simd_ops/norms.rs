@@ -23,6 +23,12 @@ use scirs2_core::simd_ops::SimdUnifiedOps;
 /// * Frobenius norm of the matrix
 #[cfg(feature = "simd")]
 pub fn simd_frobenius_norm_f32(matrix: &ArrayView2<f32>) -> f32 {
+    // Contiguous storage in either order is a single long dot product
+    if let Some(data) = matrix.as_slice_memory_order() {
+        let flat = ArrayView1::from(data);
+        return f32::simd_dot(&flat, &flat).sqrt();
+    }
+
     let mut sum_sq = 0.0f32;
 
     // Process row by row using unified SIMD operations
@@ -48,6 +54,12 @@ pub fn simd_frobenius_norm_f32(matrix: &ArrayView2<f32>) -> f32 {
 /// * Frobenius norm of the matrix
 #[cfg(feature = "simd")]
 pub fn simd_frobenius_norm_f64(matrix: &ArrayView2<f64>) -> f64 {
+    // Contiguous storage in either order is a single long dot product
+    if let Some(data) = matrix.as_slice_memory_order() {
+        let flat = ArrayView1::from(data);
+        return f64::simd_dot(&flat, &flat).sqrt();
+    }
+
     let mut sum_sq = 0.0f64;
 
     // Process row by row using unified SIMD operations
@@ -0,0 +1,224 @@
+//! SIMD-accelerated triangular solves
+//!
+//! Forward and back substitution written in the row-oriented (dot product)
+//! form: with the triangular matrix in row-major order, each step is one
+//! `simd_dot` between a contiguous row segment and the already computed part
+//! of the solution. All SIMD operations are delegated to scirs2-core::simd_ops.
+
+#[cfg(feature = "simd")]
+use crate::error::{LinalgError, LinalgResult};
+#[cfg(feature = "simd")]
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2};
+#[cfg(feature = "simd")]
+use num_traits::Float;
+#[cfg(feature = "simd")]
+use scirs2_core::simd_ops::SimdUnifiedOps;
+
+/// Check that `a` is square and matches `rhs_len` rows
+#[cfg(feature = "simd")]
+fn check_dimensions<T>(a: &ArrayView2<T>, rhs_len: usize) -> LinalgResult<()> {
+    let (n, n2) = a.dim();
+    if n != n2 {
+        return Err(LinalgError::ShapeError(format!(
+            "Triangular solve requires a square matrix, got {}x{}",
+            n, n2
+        )));
+    }
+    if rhs_len != n {
+        return Err(LinalgError::ShapeError(format!(
+            "Right-hand side has {} rows, expected {}",
+            rhs_len, n
+        )));
+    }
+    Ok(())
+}
+
+/// Substitution on a row-major triangular matrix, overwriting `x` (holding
+/// the right-hand side on entry) with the solution
+#[cfg(feature = "simd")]
+pub(crate) fn substitute<T>(
+    a: &ArrayView2<T>,
+    x: &mut Array1<T>,
+    lower: bool,
+    unit_diagonal: bool,
+) -> LinalgResult<()>
+where
+    T: SimdUnifiedOps + Float,
+{
+    let n = a.nrows();
+    let step = |i: usize, x: &mut Array1<T>| -> LinalgResult<()> {
+        let dot = if lower {
+            T::simd_dot(&a.slice(s![i, ..i]), &x.slice(s![..i]))
+        } else {
+            T::simd_dot(&a.slice(s![i, i + 1..]), &x.slice(s![i + 1..]))
+        };
+        let sum = x[i] - dot;
+        x[i] = if unit_diagonal {
+            sum
+        } else {
+            let diag = a[[i, i]];
+            if diag == T::zero() {
+                return Err(LinalgError::SingularMatrixError(format!(
+                    "Zero on the diagonal at position {} in triangular solve",
+                    i
+                )));
+            }
+            sum / diag
+        };
+        Ok(())
+    };
+
+    if lower {
+        (0..n).try_for_each(|i| step(i, x))
+    } else {
+        (0..n).rev().try_for_each(|i| step(i, x))
+    }
+}
+
+/// Solve a triangular system `A x = b` for f32 using SIMD dot products
+///
+/// # Arguments
+///
+/// * `a` - Triangular matrix A (N x N); entries outside the triangle are ignored
+/// * `b` - Right-hand side (N,)
+/// * `lower` - Whether A is lower (true) or upper (false) triangular
+/// * `unit_diagonal` - Whether to assume a unit diagonal
+///
+/// # Returns
+///
+/// * Solution vector x (N,)
+#[cfg(feature = "simd")]
+pub fn simd_solve_triangular_f32(
+    a: &ArrayView2<f32>,
+    b: &ArrayView1<f32>,
+    lower: bool,
+    unit_diagonal: bool,
+) -> LinalgResult<Array1<f32>> {
+    check_dimensions(a, b.len())?;
+    let a_packed = a.as_standard_layout();
+    let mut x = b.to_owned();
+    substitute(&a_packed.view(), &mut x, lower, unit_diagonal)?;
+    Ok(x)
+}
+
+/// Solve a triangular system `A x = b` for f64 using SIMD dot products
+///
+/// # Arguments
+///
+/// * `a` - Triangular matrix A (N x N); entries outside the triangle are ignored
+/// * `b` - Right-hand side (N,)
+/// * `lower` - Whether A is lower (true) or upper (false) triangular
+/// * `unit_diagonal` - Whether to assume a unit diagonal
+///
+/// # Returns
+///
+/// * Solution vector x (N,)
+#[cfg(feature = "simd")]
+pub fn simd_solve_triangular_f64(
+    a: &ArrayView2<f64>,
+    b: &ArrayView1<f64>,
+    lower: bool,
+    unit_diagonal: bool,
+) -> LinalgResult<Array1<f64>> {
+    check_dimensions(a, b.len())?;
+    let a_packed = a.as_standard_layout();
+    let mut x = b.to_owned();
+    substitute(&a_packed.view(), &mut x, lower, unit_diagonal)?;
+    Ok(x)
+}
+
+/// Solve a triangular system `A X = B` with several right-hand sides for f32
+///
+/// A is packed once and reused for every column of B.
+///
+/// # Arguments
+///
+/// * `a` - Triangular matrix A (N x N); entries outside the triangle are ignored
+/// * `b` - Right-hand sides B (N x K)
+/// * `lower` - Whether A is lower (true) or upper (false) triangular
+/// * `unit_diagonal` - Whether to assume a unit diagonal
+///
+/// # Returns
+///
+/// * Solution matrix X (N x K)
+#[cfg(feature = "simd")]
+pub fn simd_solve_triangular_multiple_f32(
+    a: &ArrayView2<f32>,
+    b: &ArrayView2<f32>,
+    lower: bool,
+    unit_diagonal: bool,
+) -> LinalgResult<Array2<f32>> {
+    check_dimensions(a, b.nrows())?;
+    let a_packed = a.as_standard_layout();
+    let mut result = Array2::zeros(b.dim());
+    for (column, mut out) in b.columns().into_iter().zip(result.columns_mut()) {
+        let mut x = column.to_owned();
+        substitute(&a_packed.view(), &mut x, lower, unit_diagonal)?;
+        out.assign(&x);
+    }
+    Ok(result)
+}
+
+#[cfg(all(test, feature = "simd"))]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    #[test]
+    fn test_simd_solve_triangular() {
+        let n = 37;
+        let lower_f64 = Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                2.0 + i as f64 * 0.1
+            } else if j < i {
+                ((i * 7 + j * 3) % 5) as f64 * 0.1 - 0.2
+            } else {
+                // Garbage above the diagonal must be ignored
+                100.0
+            }
+        });
+        let b_f64 = Array1::from_shape_fn(n, |i| (i as f64 * 0.3).sin());
+
+        let x = simd_solve_triangular_f64(&lower_f64.view(), &b_f64.view(), true, false).unwrap();
+        let mut lower_only = lower_f64.clone();
+        lower_only.indexed_iter_mut().for_each(|((i, j), v)| {
+            if j > i {
+                *v = 0.0;
+            }
+        });
+        let back = lower_only.dot(&x);
+        for i in 0..n {
+            assert_relative_eq!(back[i], b_f64[i], epsilon = 1e-12);
+        }
+
+        // Upper solve through the transposed (column-major) view, in f32
+        let upper = lower_only.t().mapv(|v| v as f32);
+        let b = b_f64.mapv(|v| v as f32);
+        let x32 = simd_solve_triangular_f32(&upper.view(), &b.view(), false, false).unwrap();
+        let back = upper.dot(&x32);
+        for i in 0..n {
+            assert_relative_eq!(back[i], b[i], epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_simd_solve_triangular_multiple_and_errors() {
+        let a = array![[1.0f32, 0.0, 0.0], [2.0, 1.0, 0.0], [-1.0, 3.0, 1.0]];
+        let b = array![[1.0f32, 0.0], [4.0, 1.0], [10.0, 2.0]];
+        let x = simd_solve_triangular_multiple_f32(&a.view(), &b.view(), true, true).unwrap();
+        let back = a.dot(&x);
+        for (p, q) in back.iter().zip(b.iter()) {
+            assert_relative_eq!(*p, *q, epsilon = 1e-6);
+        }
+
+        let singular = array![[1.0f32, 0.0], [1.0, 0.0]];
+        let rhs = array![1.0f32, 1.0];
+        assert!(matches!(
+            simd_solve_triangular_f32(&singular.view(), &rhs.view(), true, false),
+            Err(LinalgError::SingularMatrixError(_))
+        ));
+        let short = array![1.0f32];
+        assert!(simd_solve_triangular_f32(&a.view(), &short.view(), true, false).is_err());
+    }
+}
@@ -5,7 +5,8 @@ use num_traits::{Float, NumAssign, One};
 use std::iter::Sum;
 
 use crate::basic::inv;
-use crate::decomposition::{lu, qr, svd};
+use crate::decomposition::lu;
+use crate::equilibration::{equilibrate, equilibrate_columns, Equilibration, EquilibrationMode};
 use crate::error::{LinalgError, LinalgResult};
 use crate::validation::{
     validate_finite_matrix, validate_finite_vector, validate_least_squares, validate_linear_system,
@@ -203,8 +204,9 @@ where
 
 /// Compute least-squares solution to a linear matrix equation.
 ///
-/// Computes the vector x that solves the least squares equation
-/// a x = b by computing the full least squares solution.
+/// Computes the vector x that minimizes `||a x - b||`, using the SVD driver
+/// of [`lstsq_with_driver`] with the default rank cutoff. For rank-deficient
+/// or underdetermined systems the solution of minimum norm is returned.
 ///
 /// # Arguments
 ///
@@ -239,106 +241,443 @@ pub fn lstsq<F>(
 where
     F: Float + NumAssign + Sum + One + ndarray::ScalarOperand,
 {
-    // Parameter validation using helper function
-    validate_least_squares(a, b, "Least squares solve")?;
-
     // Configure OpenMP thread count if workers specified
     if let Some(num_workers) = workers {
         std::env::set_var("OMP_NUM_THREADS", num_workers.to_string());
     }
 
-    // For underdetermined systems with full rank, use the normal equation approach
-    if a.nrows() >= a.ncols() {
-        // QR decomposition approach
-        let (q, r) = qr(a, workers)?;
-
-        // Compute Q^T * b
-        let qt = q.t().to_owned();
-        let mut qt_b = Array1::zeros(qt.nrows());
-        for i in 0..qt.nrows() {
-            for j in 0..qt.ncols() {
-                qt_b[i] += qt[[i, j]] * b[j];
+    lstsq_with_driver(a, b, None, LstsqDriver::Svd)
+}
+
+/// Algorithm used to solve a least-squares problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LstsqDriver {
+    /// Singular value decomposition, like LAPACK `gelsd`
+    ///
+    /// The most robust choice; also reports the singular values.
+    #[default]
+    Svd,
+    /// Householder QR with column pivoting, like LAPACK `gelsy`
+    ///
+    /// Faster than the SVD driver. Rank-deficient problems are handled by a
+    /// complete orthogonal decomposition. No singular values are computed.
+    Qr,
+}
+
+/// Compute the minimum-norm least-squares solution with a chosen driver.
+///
+/// Both drivers determine the numerical rank of `a` and discard the
+/// directions below the cutoff, so rank-deficient and underdetermined
+/// problems return the solution of smallest norm among all minimizers of
+/// `||a x - b||`.
+///
+/// # Arguments
+///
+/// * `a` - Coefficient matrix of shape (m, n)
+/// * `b` - Right-hand side of length m
+/// * `rcond` - Relative cutoff: singular values (or pivoted `|R[i, i]|` for
+///   the QR driver) at most `rcond` times the largest one are treated as
+///   zero. `None` uses `max(m, n) * eps`.
+/// * `driver` - Algorithm to use
+///
+/// # Returns
+///
+/// * A LstsqResult with the solution, the sum of squared residuals, the
+///   effective rank and the singular values in descending order (empty for
+///   [`LstsqDriver::Qr`])
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{lstsq_with_driver, LstsqDriver};
+///
+/// // The second column duplicates the first, so the rank is 1
+/// let a = array![[1.0_f64, 1.0], [2.0, 2.0], [3.0, 3.0]];
+/// let b = array![1.0_f64, 2.0, 3.0];
+/// let result = lstsq_with_driver(&a.view(), &b.view(), None, LstsqDriver::Qr).unwrap();
+/// assert_eq!(result.rank, 1);
+/// // Minimum-norm solution splits the weight evenly
+/// assert!((result.x[0] - 0.5).abs() < 1e-12 && (result.x[1] - 0.5).abs() < 1e-12);
+/// ```
+pub fn lstsq_with_driver<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView1<F>,
+    rcond: Option<F>,
+    driver: LstsqDriver,
+) -> LinalgResult<LstsqResult<F>>
+where
+    F: Float + NumAssign + Sum + ndarray::ScalarOperand,
+{
+    validate_least_squares(a, b, "Least squares solve")?;
+    let (m, n) = a.dim();
+    let rcond = rcond.unwrap_or_else(|| F::from(m.max(n)).unwrap() * F::epsilon());
+
+    let (x, rank, s) = match driver {
+        LstsqDriver::Svd => {
+            let (u, s, vt) = jacobi_svd(a);
+            let rank = truncation_rank(&s, rcond);
+            let mut x = Array1::zeros(n);
+            for i in 0..rank {
+                let coefficient = u.column(i).dot(b) / s[i];
+                x.scaled_add(coefficient, &vt.row(i));
             }
+            (x, rank, s)
+        }
+        LstsqDriver::Qr => {
+            let (x, rank) = pivoted_qr_lstsq(a, b, rcond);
+            (x, rank, Array1::zeros(0))
         }
+    };
 
-        // Get the effective rank
-        let rank = a.ncols(); // Assume full rank for now
+    let r = b - &a.dot(&x);
+    Ok(LstsqResult {
+        residuals: r.dot(&r),
+        x,
+        rank,
+        s,
+    })
+}
 
-        // Extract the first part of Q^T * b corresponding to the rank
-        let qt_b_truncated = qt_b.slice(ndarray::s![0..rank]).to_owned();
+/// Compute the Moore-Penrose pseudoinverse of a matrix.
+///
+/// The pseudoinverse is `V diag(1/s) U^T` over the singular values that are
+/// larger than `rcond` times the largest one; smaller singular values are
+/// truncated to zero instead of being inverted.
+///
+/// # Arguments
+///
+/// * `a` - Input matrix of shape (m, n)
+/// * `rcond` - Relative cutoff for small singular values. `None` uses
+///   `max(m, n) * eps`.
+///
+/// # Returns
+///
+/// * Pseudoinverse of shape (n, m)
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::pinv;
+///
+/// let a = array![[1.0_f64, 2.0], [2.0, 4.0]];
+/// let a_pinv = pinv(&a.view(), None).unwrap();
+/// // A A⁺ A = A even though A is singular
+/// let back = a.dot(&a_pinv).dot(&a);
+/// assert!((back[[1, 1]] - 4.0).abs() < 1e-12);
+/// ```
+pub fn pinv<F>(a: &ArrayView2<F>, rcond: Option<F>) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign + Sum + ndarray::ScalarOperand,
+{
+    validate_not_empty_matrix(a, "Pseudoinverse computation")?;
+    validate_finite_matrix(a, "Pseudoinverse computation")?;
+    let (m, n) = a.dim();
+    let rcond = rcond.unwrap_or_else(|| F::from(m.max(n)).unwrap() * F::epsilon());
+
+    let (u, s, vt) = jacobi_svd(a);
+    let rank = truncation_rank(&s, rcond);
+    let mut result = Array2::zeros((n, m));
+    for i in 0..rank {
+        let v_scaled = vt.row(i).mapv(|v| v / s[i]);
+        for (j, &vj) in v_scaled.iter().enumerate() {
+            result.row_mut(j).scaled_add(vj, &u.column(i));
+        }
+    }
+    Ok(result)
+}
 
-        // Solve R * x = Q^T * b using back substitution
-        let r_truncated = r.slice(ndarray::s![0..rank, 0..a.ncols()]).to_owned();
-        let x = solve_triangular(&r_truncated.view(), &qt_b_truncated.view(), false, false)?;
+/// Solve a linear system, equilibrating it first if requested.
+///
+/// With scalings `R` and `C` from [`equilibrate`], the system
+/// `(R A C) y = R b` is solved and the solution is returned back-transformed
+/// as `x = C y`. Equilibration helps when rows or columns differ by many
+/// orders of magnitude, where pivoting and singularity tests on the raw
+/// matrix are misled by the scaling.
+///
+/// # Arguments
+///
+/// * `a` - Coefficient matrix
+/// * `b` - Right-hand side
+/// * `mode` - Whether to equilibrate never, when needed, or always
+///
+/// # Returns
+///
+/// * Solution `x` of the original system and the scaling that was applied
+///   (`None` if the system was solved as given)
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{solve_equilibrated, EquilibrationMode};
+///
+/// let a = array![[1e-10_f64, 2e-10], [3.0, 1.0]];
+/// let b = array![5e-10_f64, 5.0];
+/// let (x, scaling) = solve_equilibrated(&a.view(), &b.view(), EquilibrationMode::Auto).unwrap();
+/// assert!(scaling.is_some());
+/// assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 2.0).abs() < 1e-12);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn solve_equilibrated<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView1<F>,
+    mode: EquilibrationMode,
+) -> LinalgResult<(Array1<F>, Option<Equilibration<F>>)>
+where
+    F: Float + NumAssign + One + Sum,
+{
+    validate_linear_system(a, b, "Equilibrated linear system solve")?;
+    let Some(scaling) = equilibrate(a)?.restrict(mode, true) else {
+        return Ok((solve(a, b, None)?, None));
+    };
+    let scaled = scaling.scale_matrix(a);
+    let y = solve(&scaled.view(), &scaling.scale_rhs(b).view(), None)?;
+    Ok((scaling.unscale_solution(&y.view()), Some(scaling)))
+}
 
-        // Compute residuals: ||Ax - b||²
-        let mut residuals = F::zero();
-        for i in 0..a.nrows() {
-            let mut a_x_i = F::zero();
-            for j in 0..a.ncols() {
-                a_x_i += a[[i, j]] * x[j];
-            }
-            let diff = b[i] - a_x_i;
-            residuals += diff * diff;
-        }
+/// Least-squares solve with optional column equilibration.
+///
+/// Only column scaling is applied: scaling rows would weight the residual
+/// and change the problem. The scaled problem `min ||(A C) y - b||` is solved
+/// with [`lstsq_with_driver`] and `x = C y` is returned, so columns measured
+/// in wildly different units no longer distort the rank decision. For
+/// rank-deficient problems the result is the minimum-norm solution in the
+/// scaled variables `y`.
+///
+/// # Arguments
+///
+/// * `a` - Coefficient matrix of shape (m, n)
+/// * `b` - Right-hand side of length m
+/// * `rcond` - Relative cutoff for the rank decision, see [`lstsq_with_driver`]
+/// * `driver` - Algorithm to use
+/// * `mode` - Whether to equilibrate never, when needed, or always
+///
+/// # Returns
+///
+/// * The LstsqResult for the original variables (singular values are those
+///   of `A C`) and the scaling that was applied, if any
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::{lstsq_equilibrated, EquilibrationMode, LstsqDriver};
+///
+/// // Columns in units differing by 1e12
+/// let a = array![[1e6_f64, 1e-6], [2e6, 3e-6], [1e6, -1e-6]];
+/// let b = array![1.0_f64, 1.0, 3.0];
+/// let (result, scaling) = lstsq_equilibrated(
+///     &a.view(), &b.view(), None, LstsqDriver::Svd, EquilibrationMode::Auto,
+/// ).unwrap();
+/// assert!(scaling.is_some());
+/// assert_eq!(result.rank, 2);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn lstsq_equilibrated<F>(
+    a: &ArrayView2<F>,
+    b: &ArrayView1<F>,
+    rcond: Option<F>,
+    driver: LstsqDriver,
+    mode: EquilibrationMode,
+) -> LinalgResult<(LstsqResult<F>, Option<Equilibration<F>>)>
+where
+    F: Float + NumAssign + Sum + ndarray::ScalarOperand,
+{
+    validate_least_squares(a, b, "Equilibrated least squares solve")?;
+    let Some(scaling) = equilibrate_columns(a)?.restrict(mode, false) else {
+        return Ok((lstsq_with_driver(a, b, rcond, driver)?, None));
+    };
+    let scaled = scaling.scale_matrix(a);
+    let mut result = lstsq_with_driver(&scaled.view(), b, rcond, driver)?;
+    result.x = scaling.unscale_solution(&result.x.view());
+    Ok((result, Some(scaling)))
+}
 
-        // Create singular values (empty for QR approach)
-        let s = Array1::zeros(0);
+/// Number of singular values (sorted descending) above `rcond * s[0]`
+fn truncation_rank<F: Float>(s: &Array1<F>, rcond: F) -> usize {
+    match s.first() {
+        Some(&s_max) if s_max > F::zero() => s.iter().take_while(|&&v| v > rcond * s_max).count(),
+        _ => 0,
+    }
+}
 
-        Ok(LstsqResult {
-            x,
-            residuals,
-            rank,
-            s,
-        })
-    } else {
-        // Underdetermined system, use SVD
-        let (u, s, vt) = svd(a, false, workers)?;
-
-        // Determine effective rank by thresholding singular values
-        let threshold = s[0] * F::from(a.nrows().max(a.ncols())).unwrap() * F::epsilon();
-        let rank = s.iter().filter(|&&val| val > threshold).count();
-
-        // Compute U^T * b
-        let ut = u.t().to_owned();
-        let mut ut_b = Array1::zeros(ut.nrows());
-        for i in 0..ut.nrows() {
-            for j in 0..ut.ncols() {
-                ut_b[i] += ut[[i, j]] * b[j];
+/// Thin SVD by one-sided Jacobi rotations
+///
+/// Returns `(U, s, Vt)` with `k = min(m, n)` singular values in descending
+/// order. The rotations are applied to the columns of `A` (or `Aᵀ` for wide
+/// matrices) until they are mutually orthogonal, which gives singular values
+/// with high relative accuracy.
+pub(crate) fn jacobi_svd<F>(a: &ArrayView2<F>) -> (Array2<F>, Array1<F>, Array2<F>)
+where
+    F: Float + NumAssign + Sum,
+{
+    let (m, n) = a.dim();
+    if m < n {
+        let (u, s, vt) = jacobi_svd(&a.t());
+        return (vt.t().to_owned(), s, u.t().to_owned());
+    }
+
+    let mut w = a.to_owned();
+    let mut v = Array2::<F>::eye(n);
+    let tolerance = F::epsilon() * F::from(m).unwrap();
+    for _ in 0..60 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let alpha: F = w.column(p).iter().map(|&x| x * x).sum();
+                let beta: F = w.column(q).iter().map(|&x| x * x).sum();
+                let gamma: F = w
+                    .column(p)
+                    .iter()
+                    .zip(w.column(q).iter())
+                    .map(|(&x, &y)| x * y)
+                    .sum();
+                if gamma == F::zero() || gamma.abs() <= tolerance * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (F::from(2.0).unwrap() * gamma);
+                let t = zeta.signum() / (zeta.abs() + (F::one() + zeta * zeta).sqrt());
+                let c = F::one() / (F::one() + t * t).sqrt();
+                let s = c * t;
+                for mat in [&mut w, &mut v] {
+                    for i in 0..mat.nrows() {
+                        let (x, y) = (mat[[i, p]], mat[[i, q]]);
+                        mat[[i, p]] = c * x - s * y;
+                        mat[[i, q]] = s * x + c * y;
+                    }
+                }
             }
         }
+        if !rotated {
+            break;
+        }
+    }
 
-        // Initialize solution vector
-        let mut x = Array1::zeros(a.ncols());
+    let norms: Vec<F> = (0..n)
+        .map(|j| w.column(j).iter().map(|&x| x * x).sum::<F>().sqrt())
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap());
+
+    let mut u = Array2::zeros((m, n));
+    let mut s = Array1::zeros(n);
+    let mut vt = Array2::zeros((n, n));
+    for (k, &j) in order.iter().enumerate() {
+        s[k] = norms[j];
+        if norms[j] > F::zero() {
+            u.column_mut(k).assign(&w.column(j).mapv(|x| x / norms[j]));
+        }
+        vt.row_mut(k).assign(&v.column(j));
+    }
+    (u, s, vt)
+}
 
-        // Solve using SVD components
-        for i in 0..rank {
-            let s_inv = F::one() / s[i];
-            for j in 0..a.ncols() {
-                x[j] += vt[[i, j]] * ut_b[i] * s_inv;
+/// Householder QR factorization with optional column pivoting
+///
+/// Returns the unit Householder vectors (`H_k = I - 2 v_k v_kᵀ`, acting on
+/// rows `k..`), the upper trapezoidal factor `R` and the column permutation,
+/// so that `H_{k-1} ... H_0 A P = R`.
+fn householder_qr<F>(a: &ArrayView2<F>, pivoting: bool) -> (Vec<Array1<F>>, Array2<F>, Vec<usize>)
+where
+    F: Float + NumAssign + Sum,
+{
+    let (m, n) = a.dim();
+    let mut r = a.to_owned();
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut reflectors = Vec::with_capacity(m.min(n));
+
+    for k in 0..m.min(n) {
+        if pivoting {
+            let column_norm = |j: usize| -> F { (k..m).map(|i| r[[i, j]] * r[[i, j]]).sum() };
+            let pivot = (k..n)
+                .max_by(|&i, &j| column_norm(i).partial_cmp(&column_norm(j)).unwrap())
+                .unwrap();
+            if pivot != k {
+                for i in 0..m {
+                    r.swap([i, k], [i, pivot]);
+                }
+                perm.swap(k, pivot);
             }
         }
 
-        // Compute residuals: ||Ax - b||²
-        let mut residuals = F::zero();
-        for i in 0..a.nrows() {
-            let mut a_x_i = F::zero();
-            for j in 0..a.ncols() {
-                a_x_i += a[[i, j]] * x[j];
+        let mut v: Array1<F> = r.slice(ndarray::s![k.., k]).to_owned();
+        let norm = v.iter().map(|&x| x * x).sum::<F>().sqrt();
+        if norm == F::zero() {
+            reflectors.push(Array1::zeros(m - k));
+            continue;
+        }
+        let alpha = if v[0] > F::zero() { -norm } else { norm };
+        v[0] -= alpha;
+        let v_norm = v.iter().map(|&x| x * x).sum::<F>().sqrt();
+        v.mapv_inplace(|x| x / v_norm);
+
+        for j in k..n {
+            let dot: F = (k..m).map(|i| v[i - k] * r[[i, j]]).sum();
+            for i in k..m {
+                r[[i, j]] -= F::from(2.0).unwrap() * dot * v[i - k];
             }
-            let diff = b[i] - a_x_i;
-            residuals += diff * diff;
         }
+        for i in (k + 1)..m {
+            r[[i, k]] = F::zero();
+        }
+        reflectors.push(v);
+    }
+    (reflectors, r, perm)
+}
 
-        Ok(LstsqResult {
-            x,
-            residuals,
-            rank,
-            s,
-        })
+/// Apply `H_k` (acting on entries `k..`) to a vector
+fn apply_reflector<F: Float + NumAssign + Sum>(v: &Array1<F>, k: usize, x: &mut Array1<F>) {
+    let dot: F = v.iter().zip(x.iter().skip(k)).map(|(&a, &b)| a * b).sum();
+    for (i, &vi) in v.iter().enumerate() {
+        x[k + i] -= F::from(2.0).unwrap() * dot * vi;
     }
 }
 
+/// Minimum-norm least-squares solution via a complete orthogonal decomposition
+fn pivoted_qr_lstsq<F>(a: &ArrayView2<F>, b: &ArrayView1<F>, rcond: F) -> (Array1<F>, usize)
+where
+    F: Float + NumAssign + Sum,
+{
+    let n = a.ncols();
+    let (reflectors, r, perm) = householder_qr(a, true);
+    let k = reflectors.len();
+    let r_max = if k > 0 { r[[0, 0]].abs() } else { F::zero() };
+    let rank = (0..k)
+        .take_while(|&i| r_max > F::zero() && r[[i, i]].abs() > rcond * r_max)
+        .count();
+
+    // c = (Qᵀ b)[..rank]
+    let mut c = b.to_owned();
+    for (j, v) in reflectors.iter().enumerate() {
+        apply_reflector(v, j, &mut c);
+    }
+
+    // Solve R[..rank, ..] y = c[..rank] with minimum norm: factor the
+    // trapezoid as Rᵀ = Z T, so that y = Z T⁻ᵀ c.
+    let trapezoid = r.slice(ndarray::s![..rank, ..]).t().to_owned();
+    let (z_reflectors, t, _) = householder_qr(&trapezoid.view(), false);
+    let mut y = Array1::zeros(n);
+    for i in 0..rank {
+        let mut sum = c[i];
+        for j in 0..i {
+            sum -= t[[j, i]] * y[j];
+        }
+        y[i] = sum / t[[i, i]];
+    }
+    for (j, v) in z_reflectors.iter().enumerate().rev() {
+        apply_reflector(v, j, &mut y);
+    }
+
+    let mut x = Array1::zeros(n);
+    for (j, &p) in perm.iter().enumerate() {
+        x[p] = y[j];
+    }
+    (x, rank)
+}
+
 /// Solve the linear system Ax = B for x with multiple right-hand sides.
 ///
 /// # Arguments
@@ -505,4 +844,160 @@ mod tests {
         assert_relative_eq!(x[0], 2.0);
         assert_relative_eq!(x[1], 2.0);
     }
+
+    #[test]
+    fn test_lstsq_drivers_full_rank() {
+        let a = array![[1.0, 1.0], [1.0, 2.0], [1.0, 3.0], [1.0, 4.0]];
+        let b = array![6.0, 5.0, 7.0, 10.0];
+        // Normal equations give the reference solution [3.5, 1.4]
+        for driver in [LstsqDriver::Svd, LstsqDriver::Qr] {
+            let result = lstsq_with_driver(&a.view(), &b.view(), None, driver).unwrap();
+            assert_eq!(result.rank, 2);
+            assert_relative_eq!(result.x[0], 3.5, epsilon = 1e-12);
+            assert_relative_eq!(result.x[1], 1.4, epsilon = 1e-12);
+            assert_relative_eq!(result.residuals, 4.2, epsilon = 1e-12);
+        }
+
+        let result = lstsq(&a.view(), &b.view(), None).unwrap();
+        assert_eq!(result.s.len(), 2);
+        assert!(result.s[0] >= result.s[1]);
+    }
+
+    #[test]
+    fn test_lstsq_rank_deficient_minimum_norm() {
+        // Third column is the sum of the first two
+        let a = array![
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 2.0],
+            [2.0, -1.0, 1.0]
+        ];
+        let b = array![1.0, 2.0, 3.0, 0.5];
+        let svd = lstsq_with_driver(&a.view(), &b.view(), None, LstsqDriver::Svd).unwrap();
+        let qr = lstsq_with_driver(&a.view(), &b.view(), None, LstsqDriver::Qr).unwrap();
+        assert_eq!(svd.rank, 2);
+        assert_eq!(qr.rank, 2);
+        assert!(svd.s[2] < 1e-12);
+        for i in 0..3 {
+            assert_relative_eq!(svd.x[i], qr.x[i], epsilon = 1e-12);
+        }
+        // The minimum-norm solution is orthogonal to the null space [1, 1, -1]
+        assert_relative_eq!(svd.x[0] + svd.x[1] - svd.x[2], 0.0, epsilon = 1e-12);
+
+        // Underdetermined system: minimum-norm solution is Aᵀ (A Aᵀ)⁻¹ b
+        let a = array![[1.0, 2.0, 2.0]];
+        let b = array![9.0];
+        for driver in [LstsqDriver::Svd, LstsqDriver::Qr] {
+            let result = lstsq_with_driver(&a.view(), &b.view(), None, driver).unwrap();
+            assert_relative_eq!(result.x[0], 1.0, epsilon = 1e-12);
+            assert_relative_eq!(result.x[1], 2.0, epsilon = 1e-12);
+            assert_relative_eq!(result.x[2], 2.0, epsilon = 1e-12);
+            assert_relative_eq!(result.residuals, 0.0, epsilon = 1e-20);
+        }
+    }
+
+    #[test]
+    fn test_lstsq_rcond_truncation() {
+        let a = array![[1.0, 0.0], [0.0, 1e-8], [0.0, 0.0]];
+        let b = array![1.0, 1.0, 1.0];
+        let full = lstsq_with_driver(&a.view(), &b.view(), None, LstsqDriver::Svd).unwrap();
+        assert_eq!(full.rank, 2);
+        assert_relative_eq!(full.x[1], 1e8, max_relative = 1e-12);
+
+        for driver in [LstsqDriver::Svd, LstsqDriver::Qr] {
+            let truncated = lstsq_with_driver(&a.view(), &b.view(), Some(1e-6), driver).unwrap();
+            assert_eq!(truncated.rank, 1);
+            assert_relative_eq!(truncated.x[1], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_pinv() {
+        let a = array![
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [1.0, 0.0, 1.0]
+        ];
+        let a_pinv = pinv(&a.view(), None).unwrap();
+        assert_eq!(a_pinv.dim(), (3, 4));
+
+        // Penrose conditions
+        let a_ap = a.dot(&a_pinv);
+        let ap_a = a_pinv.dot(&a);
+        for (x, y) in a_ap.dot(&a).iter().zip(a.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-12);
+        }
+        for (x, y) in ap_a.dot(&a_pinv).iter().zip(a_pinv.iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-12);
+        }
+        for (x, y) in a_ap.iter().zip(a_ap.t().iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-12);
+        }
+        for (x, y) in ap_a.iter().zip(ap_a.t().iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-12);
+        }
+
+        // Invertible matrices give the inverse; wide matrices work too
+        let square = array![[2.0, 1.0], [1.0, 3.0]];
+        let inv = pinv(&square.view(), None).unwrap();
+        assert_relative_eq!(inv[[0, 0]], 0.6, epsilon = 1e-14);
+        assert_relative_eq!(inv[[0, 1]], -0.2, epsilon = 1e-14);
+        let wide = a.t().to_owned();
+        let wide_pinv = pinv(&wide.view(), None).unwrap();
+        for (x, y) in wide_pinv.iter().zip(a_pinv.t().iter()) {
+            assert_relative_eq!(*x, *y, epsilon = 1e-12);
+        }
+
+        // A large cutoff truncates everything but the dominant direction
+        let rank_one = pinv(&a.view(), Some(0.5)).unwrap();
+        let (_, s, _) = jacobi_svd(&rank_one.view());
+        assert!(s[1] < 1e-14);
+    }
+
+    #[test]
+    fn test_solve_equilibrated() {
+        // Rows differing by 1e10: the raw 2x2 system looks singular to solve
+        let a = array![[1e-10, 2e-10], [3.0, 1.0]];
+        let b = array![5e-10, 5.0];
+        let (x, scaling) =
+            solve_equilibrated(&a.view(), &b.view(), EquilibrationMode::Auto).unwrap();
+        let scaling = scaling.unwrap();
+        assert!(scaling.needs_row_scaling());
+        assert_relative_eq!(x[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 2.0, epsilon = 1e-12);
+
+        // Well scaled systems are solved unchanged in Auto mode
+        let a = array![[2.0, 1.0], [1.0, 3.0]];
+        let b = array![3.0, 5.0];
+        let (x, scaling) =
+            solve_equilibrated(&a.view(), &b.view(), EquilibrationMode::Auto).unwrap();
+        assert!(scaling.is_none());
+        assert_relative_eq!(x[0], 0.8, epsilon = 1e-14);
+        let (x_always, scaling) =
+            solve_equilibrated(&a.view(), &b.view(), EquilibrationMode::Always).unwrap();
+        assert!(scaling.is_some());
+        assert_relative_eq!(x_always[1], x[1], epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_lstsq_equilibrated() {
+        // Columns in units differing by 1e12; b is consistent with x = (1e-6, 1e6)
+        let a = array![[1e6, 1e-6], [2e6, 3e-6], [1e6, -1e-6], [0.0, 2e-6]];
+        let b = array![2.0, 5.0, 0.0, 2.0];
+        let (result, scaling) = lstsq_equilibrated(
+            &a.view(),
+            &b.view(),
+            None,
+            LstsqDriver::Svd,
+            EquilibrationMode::Auto,
+        )
+        .unwrap();
+        let scaling = scaling.unwrap();
+        assert!(scaling.row_scale.iter().all(|&r| r == 1.0));
+        assert_eq!(result.rank, 2);
+        assert_relative_eq!(result.x[0], 1e-6, max_relative = 1e-10);
+        assert_relative_eq!(result.x[1], 1e6, max_relative = 1e-10);
+        assert!(result.residuals < 1e-20);
+    }
 }
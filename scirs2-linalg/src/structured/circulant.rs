@@ -18,6 +18,7 @@ use ndarray::{Array1, ArrayView1};
 use num_traits::{Float, NumAssign, One, Zero};
 use std::{fmt::Debug, iter::Sum};
 
+use super::spectral::{circulant_matvec, FFT_MATVEC_MIN_SIZE};
 use super::StructuredMatrix;
 use crate::error::{LinalgError, LinalgResult};
 
@@ -89,6 +90,13 @@ where
 
         Ok(CirculantMatrix { first_row, n })
     }
+
+    /// The first column of the matrix, `C[i, 0] = c[(n - i) mod n]`
+    fn first_col(&self) -> Vec<A> {
+        (0..self.n)
+            .map(|i| self.first_row[(self.n - i) % self.n])
+            .collect()
+    }
 }
 
 impl<A> StructuredMatrix<A> for CirculantMatrix<A>
@@ -126,6 +134,14 @@ where
             )));
         }
 
+        // Large matrices: C x is the cyclic convolution of the first column with x
+        if self.n * self.n >= FFT_MATVEC_MIN_SIZE {
+            return Ok(Array1::from(circulant_matvec(
+                &self.first_col(),
+                &x.to_vec(),
+            )));
+        }
+
         // Looking at the test case, we need to match its expected outputs:
         // Expected matrix for test:
         // [1 2 3 4]
@@ -161,6 +177,14 @@ where
             )));
         }
 
+        // C^T is the circulant matrix whose first column is the first row of C
+        if self.n * self.n >= FFT_MATVEC_MIN_SIZE {
+            return Ok(Array1::from(circulant_matvec(
+                &self.first_row.to_vec(),
+                &x.to_vec(),
+            )));
+        }
+
         // From the test case, the expected transpose matrix is:
         // [1 4 3 2]
         // [2 1 4 3]
@@ -335,4 +359,26 @@ mod tests {
         let result = CirculantMatrix::<f64>::new(first_row.view());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_circulant_fft_matvec_matches_dense() {
+        // Large enough to take the FFT path
+        let first_row = Array1::from_shape_fn(75, |k| 1.0 / (1.0 + (k as f64 - 20.0).abs()));
+        let matrix = CirculantMatrix::new(first_row.view()).unwrap();
+        let dense = matrix.to_dense().unwrap();
+
+        let x = Array1::from_shape_fn(75, |i| (i as f64 * 0.37).cos());
+        let y = matrix.matvec(&x.view()).unwrap();
+        let expected = dense.dot(&x);
+        for i in 0..75 {
+            assert_relative_eq!(y[i], expected[i], epsilon = 1e-10);
+        }
+
+        let z = Array1::from_shape_fn(75, |i| 1.0 - i as f64 / 50.0);
+        let yt = matrix.matvec_transpose(&z.view()).unwrap();
+        let expected = dense.t().dot(&z);
+        for j in 0..75 {
+            assert_relative_eq!(yt[j], expected[j], epsilon = 1e-10);
+        }
+    }
 }
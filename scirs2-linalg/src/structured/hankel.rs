@@ -18,6 +18,7 @@ use ndarray::{Array1, ArrayView1};
 use num_traits::{Float, NumAssign, One, Zero};
 use std::{fmt::Debug, iter::Sum};
 
+use super::spectral::{ConvolutionProduct, FFT_MATVEC_MIN_SIZE};
 use super::StructuredMatrix;
 use crate::error::{LinalgError, LinalgResult};
 
@@ -118,6 +119,27 @@ where
             ncols: n_cols,
         })
     }
+
+    /// The defining sequence `h` with `H[i, j] = h[i + j]`
+    fn sequence(&self) -> Vec<A> {
+        self.first_col
+            .iter()
+            .chain(self.last_row.iter().skip(1))
+            .copied()
+            .collect()
+    }
+
+    /// `y[i] = sum_j h[i + j] x[j]`, a slice of the convolution of `h` with reversed `x`
+    fn fft_product(&self, x: &ArrayView1<A>, out_len: usize) -> Array1<A> {
+        let reversed: Vec<A> = x.iter().rev().copied().collect();
+        let product = ConvolutionProduct::new(
+            &self.sequence(),
+            reversed.len(),
+            reversed.len() - 1,
+            out_len,
+        );
+        Array1::from(product.apply(&reversed))
+    }
 }
 
 impl<A> StructuredMatrix<A> for HankelMatrix<A>
@@ -169,6 +191,10 @@ where
             )));
         }
 
+        if self.nrows * self.ncols >= FFT_MATVEC_MIN_SIZE {
+            return Ok(self.fft_product(x, self.nrows));
+        }
+
         let mut result = Array1::zeros(self.nrows);
 
         for i in 0..self.nrows {
@@ -189,6 +215,11 @@ where
             )));
         }
 
+        // H^T is the Hankel matrix of the same sequence with the shape swapped
+        if self.nrows * self.ncols >= FFT_MATVEC_MIN_SIZE {
+            return Ok(self.fft_product(x, self.ncols));
+        }
+
         let mut result = Array1::zeros(self.ncols);
 
         for j in 0..self.ncols {
@@ -390,4 +421,26 @@ mod tests {
         let result = HankelMatrix::from_sequence(sequence.view(), 2, 3); // Need 2+3-1=4 elements
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_hankel_fft_matvec_matches_dense() {
+        // Large enough to take the FFT path
+        let sequence = Array1::from_shape_fn(159, |k| (k as f64 * 0.1).cos() + 0.01 * k as f64);
+        let matrix = HankelMatrix::from_sequence(sequence.view(), 90, 70).unwrap();
+        let dense = matrix.to_dense().unwrap();
+
+        let x = Array1::from_shape_fn(70, |i| (i as f64 * 0.37).cos());
+        let y = matrix.matvec(&x.view()).unwrap();
+        let expected = dense.dot(&x);
+        for i in 0..90 {
+            assert_relative_eq!(y[i], expected[i], epsilon = 1e-10);
+        }
+
+        let z = Array1::from_shape_fn(90, |i| 1.0 - i as f64 / 50.0);
+        let yt = matrix.matvec_transpose(&z.view()).unwrap();
+        let expected = dense.t().dot(&z);
+        for j in 0..70 {
+            assert_relative_eq!(yt[j], expected[j], epsilon = 1e-10);
+        }
+    }
 }
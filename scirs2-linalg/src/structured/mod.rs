@@ -15,6 +15,18 @@
 //! * **Circulant matrices**: Special Toeplitz matrices where each row is a cyclic shift of the first row
 //! * **Hankel matrices**: Matrices where each ascending diagonal from left to right is constant
 //!
+//! ## Fast Products and Solvers
+//!
+//! Matrix-vector products of large structured matrices are evaluated as FFT
+//! convolutions in O((m + n) log(m + n)) time without forming the dense
+//! matrix. Linear systems are solved with specialized algorithms:
+//!
+//! * [`solve_toeplitz`]: Levinson recursion, O(n^2) time and O(n) memory
+//! * [`levinson_durbin`]: Levinson–Durbin for the Yule–Walker equations
+//! * [`solve_toeplitz_pcg`]: circulant-preconditioned Krylov iteration
+//!   with FFT products, O(n log n) per iteration
+//! * [`solve_circulant`]: direct diagonalization by the DFT, O(n log n)
+//!
 //! # Examples
 //!
 //! ```
@@ -46,13 +58,14 @@ use std::{fmt::Debug, iter::Sum};
 
 mod circulant;
 mod hankel;
+mod spectral;
 mod toeplitz;
 mod utils;
 
 pub use circulant::CirculantMatrix;
 pub use hankel::HankelMatrix;
 pub use toeplitz::ToeplitzMatrix;
-pub use utils::{solve_circulant, solve_toeplitz};
+pub use utils::{levinson_durbin, solve_circulant, solve_toeplitz, solve_toeplitz_pcg};
 
 /// A trait for structured matrices that can be represented efficiently
 ///
@@ -0,0 +1,273 @@
+//! FFT kernels for structured matrix products and solvers
+//!
+//! Toeplitz, Hankel and circulant matrices act on vectors as convolutions, so
+//! their products can be evaluated in O(n log n) time through zero-padded
+//! FFTs without materializing the dense matrix.
+
+use num_complex::Complex;
+use num_traits::{Float, Zero};
+
+/// Matrices with at least this many entries use FFT-based products
+///
+/// Below this size the direct O(mn) loop is faster and exact in the sense of
+/// summation order.
+pub(super) const FFT_MATVEC_MIN_SIZE: usize = 4096;
+
+/// In-place iterative radix-2 FFT; `buf.len()` must be a power of two
+///
+/// The inverse transform is not scaled.
+fn fft_radix2<A: Float>(buf: &mut [Complex<A>], inverse: bool) {
+    let n = buf.len();
+    if n <= 1 {
+        return;
+    }
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { A::one() } else { -A::one() };
+    let two_pi = A::from(2.0 * std::f64::consts::PI).unwrap();
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let step = sign * two_pi / A::from(len).unwrap();
+        let twiddles: Vec<Complex<A>> = (0..half)
+            .map(|k| Complex::from_polar(A::one(), step * A::from(k).unwrap()))
+            .collect();
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let u = buf[start + k];
+                let v = buf[start + k + half] * twiddles[k];
+                buf[start + k] = u + v;
+                buf[start + k + half] = u - v;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Discrete Fourier transform of arbitrary length
+///
+/// Powers of two use the radix-2 kernel directly; other lengths use
+/// Bluestein's chirp-z algorithm. The inverse transform is scaled by `1/n`.
+pub(super) fn dft<A: Float>(x: &[Complex<A>], inverse: bool) -> Vec<Complex<A>> {
+    let n = x.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let scale = if inverse {
+        A::one() / A::from(n).unwrap()
+    } else {
+        A::one()
+    };
+
+    if n.is_power_of_two() {
+        let mut buf = x.to_vec();
+        fft_radix2(&mut buf, inverse);
+        if inverse {
+            buf.iter_mut().for_each(|v| *v = *v * scale);
+        }
+        return buf;
+    }
+
+    // Bluestein: jk = (j^2 + k^2 - (k - j)^2) / 2 turns the DFT into a
+    // convolution with a chirp, evaluated with power-of-two FFTs.
+    let sign = if inverse { A::one() } else { -A::one() };
+    let pi = A::from(std::f64::consts::PI).unwrap();
+    let n_f = A::from(n).unwrap();
+    let chirp: Vec<Complex<A>> = (0..n)
+        .map(|k| {
+            // k^2 mod 2n keeps the phase argument small for large k
+            let k2 = ((k as u128 * k as u128) % (2 * n as u128)) as f64;
+            Complex::from_polar(A::one(), sign * pi * A::from(k2).unwrap() / n_f)
+        })
+        .collect();
+
+    let size = (2 * n - 1).next_power_of_two();
+    let mut a = vec![Complex::zero(); size];
+    for k in 0..n {
+        a[k] = x[k] * chirp[k];
+    }
+    let mut b = vec![Complex::zero(); size];
+    b[0] = chirp[0].conj();
+    for k in 1..n {
+        b[k] = chirp[k].conj();
+        b[size - k] = chirp[k].conj();
+    }
+
+    fft_radix2(&mut a, false);
+    fft_radix2(&mut b, false);
+    for (ak, bk) in a.iter_mut().zip(b.iter()) {
+        *ak = *ak * *bk;
+    }
+    fft_radix2(&mut a, true);
+
+    let norm = scale / A::from(size).unwrap();
+    (0..n).map(|k| a[k] * chirp[k] * norm).collect()
+}
+
+/// Precomputed spectrum of a real convolution kernel
+///
+/// `apply(x)` returns `out_len` consecutive entries of the linear convolution
+/// `kernel * x`, starting at `offset`.
+#[derive(Debug, Clone)]
+pub(super) struct ConvolutionProduct<A> {
+    spectrum: Vec<Complex<A>>,
+    offset: usize,
+    in_len: usize,
+    out_len: usize,
+}
+
+impl<A: Float> ConvolutionProduct<A> {
+    pub(super) fn new(kernel: &[A], in_len: usize, offset: usize, out_len: usize) -> Self {
+        let size = (kernel.len() + in_len - 1).next_power_of_two();
+        let mut spectrum = vec![Complex::zero(); size];
+        for (s, &k) in spectrum.iter_mut().zip(kernel.iter()) {
+            *s = Complex::new(k, A::zero());
+        }
+        fft_radix2(&mut spectrum, false);
+        ConvolutionProduct {
+            spectrum,
+            offset,
+            in_len,
+            out_len,
+        }
+    }
+
+    /// Product for a matrix `T[i, j] = t[i - j]` with `nrows x ncols` entries
+    ///
+    /// `first_col[k] = t[k]` and `first_row[k] = t[-k]`.
+    pub(super) fn toeplitz(first_col: &[A], first_row: &[A]) -> Self {
+        let (m, n) = (first_col.len(), first_row.len());
+        let kernel: Vec<A> = first_row[1..]
+            .iter()
+            .rev()
+            .chain(first_col.iter())
+            .copied()
+            .collect();
+        Self::new(&kernel, n, n - 1, m)
+    }
+
+    pub(super) fn apply(&self, x: &[A]) -> Vec<A> {
+        debug_assert_eq!(x.len(), self.in_len);
+        let size = self.spectrum.len();
+        let mut buf = vec![Complex::zero(); size];
+        for (b, &v) in buf.iter_mut().zip(x.iter()) {
+            *b = Complex::new(v, A::zero());
+        }
+        fft_radix2(&mut buf, false);
+        for (b, s) in buf.iter_mut().zip(self.spectrum.iter()) {
+            *b = *b * *s;
+        }
+        fft_radix2(&mut buf, true);
+        let scale = A::one() / A::from(size).unwrap();
+        buf[self.offset..self.offset + self.out_len]
+            .iter()
+            .map(|v| v.re * scale)
+            .collect()
+    }
+}
+
+/// Product of the circulant matrix with first column `col` and `x`
+///
+/// The cyclic convolution is obtained by folding the linear one.
+pub(super) fn circulant_matvec<A: Float>(col: &[A], x: &[A]) -> Vec<A> {
+    let n = col.len();
+    let linear = ConvolutionProduct::new(col, n, 0, 2 * n - 1).apply(x);
+    (0..n)
+        .map(|i| {
+            if i + n < linear.len() {
+                linear[i] + linear[i + n]
+            } else {
+                linear[i]
+            }
+        })
+        .collect()
+}
+
+/// Eigenvalues of the circulant matrix with the given first column
+///
+/// A circulant matrix `C` with first column `col` satisfies
+/// `C x = ifft(fft(col) .* fft(x))`, so its eigenvalues are `fft(col)`.
+pub(super) fn circulant_eigenvalues<A: Float>(col: &[A]) -> Vec<Complex<A>> {
+    let buf: Vec<Complex<A>> = col.iter().map(|&v| Complex::new(v, A::zero())).collect();
+    dft(&buf, false)
+}
+
+/// Solve `C x = b` for a circulant `C` given by its eigenvalues
+pub(super) fn circulant_solve<A: Float>(eigenvalues: &[Complex<A>], b: &[A]) -> Vec<A> {
+    let buf: Vec<Complex<A>> = b.iter().map(|&v| Complex::new(v, A::zero())).collect();
+    let mut spectrum = dft(&buf, false);
+    for (s, &lambda) in spectrum.iter_mut().zip(eigenvalues.iter()) {
+        *s = *s / lambda;
+    }
+    dft(&spectrum, true).into_iter().map(|v| v.re).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn naive_dft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let n = x.len();
+        (0..n)
+            .map(|k| {
+                x.iter().enumerate().fold(Complex::zero(), |acc, (j, &v)| {
+                    let angle = -2.0 * std::f64::consts::PI * (j * k) as f64 / n as f64;
+                    acc + v * Complex::from_polar(1.0, angle)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dft_matches_naive() {
+        for n in [1, 2, 5, 8, 12, 17] {
+            let x: Vec<Complex<f64>> = (0..n)
+                .map(|k| Complex::new((k as f64).sin(), (k as f64 * 0.3).cos()))
+                .collect();
+            let fast = dft(&x, false);
+            let slow = naive_dft(&x);
+            for (a, b) in fast.iter().zip(slow.iter()) {
+                assert!((a - b).norm() < 1e-10);
+            }
+            let back = dft(&fast, true);
+            for (a, b) in back.iter().zip(x.iter()) {
+                assert!((a - b).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_toeplitz_convolution_product() {
+        let first_col = [1.0, 4.0, 5.0, -1.0];
+        let first_row = [1.0, 2.0, 3.0];
+        let x = [1.0, -2.0, 0.5];
+        let y = ConvolutionProduct::toeplitz(&first_col, &first_row).apply(&x);
+        for i in 0..4 {
+            let expected: f64 = (0..3)
+                .map(|j| {
+                    let t = if i >= j {
+                        first_col[i - j]
+                    } else {
+                        first_row[j - i]
+                    };
+                    t * x[j]
+                })
+                .sum();
+            assert_relative_eq!(y[i], expected, epsilon = 1e-12);
+        }
+    }
+}
@@ -18,6 +18,7 @@ use ndarray::{Array1, ArrayView1};
 use num_traits::{Float, NumAssign, One, Zero};
 use std::{fmt::Debug, iter::Sum};
 
+use super::spectral::{ConvolutionProduct, FFT_MATVEC_MIN_SIZE};
 use super::StructuredMatrix;
 use crate::error::{LinalgError, LinalgResult};
 
@@ -185,7 +186,13 @@ where
             )));
         }
 
-        // Fast Toeplitz matrix-vector multiplication
+        // Large matrices: T x is a slice of the convolution of the diagonals with x
+        if self.nrows * self.ncols >= FFT_MATVEC_MIN_SIZE {
+            let product =
+                ConvolutionProduct::toeplitz(&self.first_col.to_vec(), &self.first_row.to_vec());
+            return Ok(Array1::from(product.apply(&x.to_vec())));
+        }
+
         let mut result = Array1::zeros(self.nrows);
 
         // Using direct multiplication with test expectations
@@ -230,6 +237,12 @@ where
 
         // For a Toeplitz matrix T, the transpose T^T is also a Toeplitz matrix
         // with the first row and column swapped
+        if self.nrows * self.ncols >= FFT_MATVEC_MIN_SIZE {
+            let product =
+                ConvolutionProduct::toeplitz(&self.first_row.to_vec(), &self.first_col.to_vec());
+            return Ok(Array1::from(product.apply(&x.to_vec())));
+        }
+
         let mut result = Array1::zeros(self.ncols);
 
         // Based on the test case, the transpose matrix looks like:
@@ -424,4 +437,28 @@ mod tests {
         let result = ToeplitzMatrix::<f64>::new(first_row.view(), first_col.view());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_toeplitz_fft_matvec_matches_dense() {
+        // Large enough to take the FFT path
+        let first_row = Array1::from_shape_fn(70, |k| 1.0 / (1.0 + k as f64));
+        let mut first_col = Array1::from_shape_fn(90, |k| (k as f64 * 0.3).sin());
+        first_col[0] = first_row[0];
+        let matrix = ToeplitzMatrix::new(first_row.view(), first_col.view()).unwrap();
+        let dense = matrix.to_dense().unwrap();
+
+        let x = Array1::from_shape_fn(70, |i| (i as f64 * 0.37).cos());
+        let y = matrix.matvec(&x.view()).unwrap();
+        let expected = dense.dot(&x);
+        for i in 0..90 {
+            assert_relative_eq!(y[i], expected[i], epsilon = 1e-10);
+        }
+
+        let z = Array1::from_shape_fn(90, |i| 1.0 - i as f64 / 50.0);
+        let yt = matrix.matvec_transpose(&z.view()).unwrap();
+        let expected = dense.t().dot(&z);
+        for j in 0..70 {
+            assert_relative_eq!(yt[j], expected[j], epsilon = 1e-10);
+        }
+    }
 }
@@ -0,0 +1,822 @@
+//! Tridiagonal and pentadiagonal matrices given by their diagonals
+//!
+//! The routines here take the diagonals as separate 1-D arrays instead of a
+//! dense matrix, so storage is `O(n)` and the solves cost `O(n)`. Diagonals are
+//! indexed by their first row: for a tridiagonal matrix `a`,
+//!
+//! * `dl[i] = a[i + 1, i]` (subdiagonal, length `n - 1`)
+//! * `d[i] = a[i, i]` (diagonal, length `n`)
+//! * `du[i] = a[i, i + 1]` (superdiagonal, length `n - 1`)
+//!
+//! and the pentadiagonal solver adds `dl2[i] = a[i + 2, i]` and
+//! `du2[i] = a[i, i + 2]` (length `n - 2`).
+//!
+//! The solvers do not pivot. They are intended for the diagonally dominant or
+//! symmetric positive definite systems that arise from finite differences and
+//! splines; general band systems should use [`crate::banded::solve_banded`].
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use num_traits::{Float, NumAssign};
+
+use crate::banded::tridiagonal_ql_implicit;
+use crate::error::{LinalgError, LinalgResult};
+
+/// Subset of eigenvalues requested from the tridiagonal eigensolvers
+///
+/// Mirrors the `select` argument of `scipy.linalg.eigh_tridiagonal`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TridiagonalSelect<F> {
+    /// All eigenvalues
+    All,
+    /// Eigenvalues in the half-open interval `(lo, hi]`
+    Value(F, F),
+    /// Eigenvalues with ascending indices `lo..=hi`
+    Index(usize, usize),
+}
+
+fn check_diagonal_lengths(n: usize, name: &str, len: usize, offset: usize) -> LinalgResult<()> {
+    let expected = n.saturating_sub(offset);
+    if len != expected {
+        return Err(LinalgError::ShapeError(format!(
+            "{name} must have length {expected} for a matrix of order {n}, got {len}"
+        )));
+    }
+    Ok(())
+}
+
+fn max_abs<F: Float>(v: &ArrayView1<F>) -> F {
+    v.iter().fold(F::zero(), |acc, &x| acc.max(x.abs()))
+}
+
+fn zero_pivot<F: Float>(operation: &str, n: usize, row: usize, pivot: F) -> LinalgError {
+    if pivot.is_finite() {
+        LinalgError::singular_matrix_with_suggestions(
+            &format!("{operation} (zero pivot in row {row})"),
+            (n, n),
+            None,
+        )
+    } else {
+        LinalgError::ComputationError(format!("{operation}: non-finite pivot in row {row}"))
+    }
+}
+
+/// Thomas elimination on every column of `rhs`, overwriting it with the solution.
+fn thomas_in_place<F>(
+    dl: &ArrayView1<F>,
+    d: &ArrayView1<F>,
+    du: &ArrayView1<F>,
+    rhs: &mut Array2<F>,
+    operation: &str,
+) -> LinalgResult<()>
+where
+    F: Float + NumAssign,
+{
+    let n = d.len();
+    if n == 0 {
+        return Ok(());
+    }
+    let tol = F::epsilon() * max_abs(dl).max(max_abs(d)).max(max_abs(du));
+    let mut c = vec![F::zero(); n];
+
+    let mut denom = d[0];
+    if denom.abs() <= tol || !denom.is_finite() {
+        return Err(zero_pivot(operation, n, 0, denom));
+    }
+    for mut col in rhs.axis_iter_mut(Axis(1)) {
+        col[0] /= denom;
+    }
+    for i in 1..n {
+        c[i - 1] = du[i - 1] / denom;
+        denom = d[i] - dl[i - 1] * c[i - 1];
+        if denom.abs() <= tol || !denom.is_finite() {
+            return Err(zero_pivot(operation, n, i, denom));
+        }
+        for mut col in rhs.axis_iter_mut(Axis(1)) {
+            col[i] = (col[i] - dl[i - 1] * col[i - 1]) / denom;
+        }
+    }
+
+    for mut col in rhs.axis_iter_mut(Axis(1)) {
+        for i in (0..n - 1).rev() {
+            let next = col[i + 1];
+            col[i] -= c[i] * next;
+        }
+    }
+    Ok(())
+}
+
+/// Solve a tridiagonal system with the Thomas algorithm
+///
+/// # Arguments
+///
+/// * `dl` - Subdiagonal, `dl[i] = a[i + 1, i]` (length n-1)
+/// * `d` - Main diagonal (length n)
+/// * `du` - Superdiagonal, `du[i] = a[i, i + 1]` (length n-1)
+/// * `b` - Right-hand side (length n)
+///
+/// # Returns
+///
+/// * Solution `x` of `A x = b`
+///
+/// The elimination does not pivot and fails with a singular-matrix error when
+/// a pivot vanishes, which cannot happen for diagonally dominant or symmetric
+/// positive definite matrices.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::tridiagonal::solve_tridiagonal;
+///
+/// // [[2, -1, 0], [-1, 2, -1], [0, -1, 2]] x = [1, 0, 1]
+/// let dl = array![-1.0_f64, -1.0];
+/// let d = array![2.0, 2.0, 2.0];
+/// let du = array![-1.0, -1.0];
+/// let x = solve_tridiagonal(&dl.view(), &d.view(), &du.view(), &array![1.0, 0.0, 1.0].view())
+///     .unwrap();
+/// for &xi in x.iter() {
+///     assert!((xi - 1.0).abs() < 1e-12);
+/// }
+/// ```
+pub fn solve_tridiagonal<F>(
+    dl: &ArrayView1<F>,
+    d: &ArrayView1<F>,
+    du: &ArrayView1<F>,
+    b: &ArrayView1<F>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign,
+{
+    let n = d.len();
+    check_diagonal_lengths(n, "Right-hand side", b.len(), 0)?;
+    let x = solve_tridiagonal_multiple(dl, d, du, &b.view().insert_axis(Axis(1)))?;
+    Ok(x.index_axis_move(Axis(1), 0))
+}
+
+/// Solve a tridiagonal system for several right-hand sides at once
+///
+/// Same as [`solve_tridiagonal`] with `b` of shape `(n, k)`; the elimination is
+/// performed once for all columns.
+pub fn solve_tridiagonal_multiple<F>(
+    dl: &ArrayView1<F>,
+    d: &ArrayView1<F>,
+    du: &ArrayView1<F>,
+    b: &ArrayView2<F>,
+) -> LinalgResult<Array2<F>>
+where
+    F: Float + NumAssign,
+{
+    let n = d.len();
+    check_diagonal_lengths(n, "Subdiagonal", dl.len(), 1)?;
+    check_diagonal_lengths(n, "Superdiagonal", du.len(), 1)?;
+    check_diagonal_lengths(n, "Right-hand side", b.nrows(), 0)?;
+
+    let mut x = b.to_owned();
+    thomas_in_place(dl, d, du, &mut x, "Thomas algorithm")?;
+    Ok(x)
+}
+
+/// Solve a cyclic (periodic) tridiagonal system
+///
+/// The matrix is tridiagonal apart from the two corner entries `a[0, n - 1]`
+/// and `a[n - 1, 0]`, as produced by finite differences on a periodic grid.
+/// All three diagonals have length `n` and wrap around: `dl[0]` holds the
+/// corner `a[0, n - 1]`, and `du[n - 1]` holds the corner `a[n - 1, 0]`, so
+/// that row `i` reads `dl[i] x[i - 1] + d[i] x[i] + du[i] x[i + 1]` with
+/// indices taken modulo `n`.
+///
+/// The system is solved with the Sherman–Morrison formula on top of two Thomas
+/// solves, in `O(n)`.
+///
+/// # Arguments
+///
+/// * `dl` - Cyclic subdiagonal, `dl[i] = a[i, (i + n - 1) % n]` (length n)
+/// * `d` - Main diagonal (length n)
+/// * `du` - Cyclic superdiagonal, `du[i] = a[i, (i + 1) % n]` (length n)
+/// * `b` - Right-hand side (length n)
+///
+/// # Returns
+///
+/// * Solution `x` of `A x = b`
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::tridiagonal::solve_cyclic_tridiagonal;
+///
+/// // Periodic second difference plus identity: 3 x[i] - x[i-1] - x[i+1]
+/// let dl = array![-1.0_f64, -1.0, -1.0, -1.0];
+/// let d = array![3.0, 3.0, 3.0, 3.0];
+/// let du = array![-1.0, -1.0, -1.0, -1.0];
+/// let b = array![1.0, 1.0, 1.0, 1.0];
+/// let x = solve_cyclic_tridiagonal(&dl.view(), &d.view(), &du.view(), &b.view()).unwrap();
+/// for &xi in x.iter() {
+///     assert!((xi - 1.0).abs() < 1e-12);
+/// }
+/// ```
+pub fn solve_cyclic_tridiagonal<F>(
+    dl: &ArrayView1<F>,
+    d: &ArrayView1<F>,
+    du: &ArrayView1<F>,
+    b: &ArrayView1<F>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign,
+{
+    let n = d.len();
+    check_diagonal_lengths(n, "Cyclic subdiagonal", dl.len(), 0)?;
+    check_diagonal_lengths(n, "Cyclic superdiagonal", du.len(), 0)?;
+    check_diagonal_lengths(n, "Right-hand side", b.len(), 0)?;
+    if n < 3 {
+        return Err(LinalgError::InvalidInputError(format!(
+            "Cyclic tridiagonal systems need at least 3 unknowns, got {n}"
+        )));
+    }
+
+    // A = T + u v^T with u = [gamma, 0, ..., 0, alpha]^T and
+    // v = [1, 0, ..., 0, beta / gamma]^T, where alpha and beta are the corners.
+    let alpha = du[n - 1];
+    let beta = dl[0];
+    let gamma = if d[0] == F::zero() { -F::one() } else { -d[0] };
+
+    let mut t_diag = d.to_owned();
+    t_diag[0] -= gamma;
+    t_diag[n - 1] -= alpha * beta / gamma;
+
+    let mut rhs = Array2::zeros((n, 2));
+    rhs.column_mut(0).assign(b);
+    rhs[[0, 1]] = gamma;
+    rhs[[n - 1, 1]] = alpha;
+    thomas_in_place(
+        &dl.slice(ndarray::s![1..]),
+        &t_diag.view(),
+        &du.slice(ndarray::s![..n - 1]),
+        &mut rhs,
+        "Cyclic Thomas algorithm",
+    )?;
+
+    let y = rhs.column(0);
+    let z = rhs.column(1);
+    let denom = F::one() + z[0] + beta * z[n - 1] / gamma;
+    if denom == F::zero() || !denom.is_finite() {
+        return Err(LinalgError::singular_matrix_with_suggestions(
+            "cyclic tridiagonal solve (Sherman-Morrison update)",
+            (n, n),
+            None,
+        ));
+    }
+    let fact = (y[0] + beta * y[n - 1] / gamma) / denom;
+    Ok(Array1::from_shape_fn(n, |i| y[i] - fact * z[i]))
+}
+
+/// Solve a pentadiagonal system by banded Gaussian elimination
+///
+/// # Arguments
+///
+/// * `dl2` - Second subdiagonal, `dl2[i] = a[i + 2, i]` (length n-2)
+/// * `dl` - Subdiagonal, `dl[i] = a[i + 1, i]` (length n-1)
+/// * `d` - Main diagonal (length n)
+/// * `du` - Superdiagonal, `du[i] = a[i, i + 1]` (length n-1)
+/// * `du2` - Second superdiagonal, `du2[i] = a[i, i + 2]` (length n-2)
+/// * `b` - Right-hand side (length n)
+///
+/// # Returns
+///
+/// * Solution `x` of `A x = b`
+///
+/// Like [`solve_tridiagonal`], the elimination does not pivot.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::tridiagonal::solve_pentadiagonal;
+///
+/// let dl2 = array![1.0_f64, 1.0];
+/// let dl = array![-4.0, -4.0, -4.0];
+/// let d = array![6.0, 6.0, 6.0, 6.0];
+/// let du = array![-4.0, -4.0, -4.0];
+/// let du2 = array![1.0, 1.0];
+/// let b = array![3.0, -1.0, -1.0, 3.0];
+/// let x = solve_pentadiagonal(
+///     &dl2.view(), &dl.view(), &d.view(), &du.view(), &du2.view(), &b.view(),
+/// ).unwrap();
+/// for &xi in x.iter() {
+///     assert!((xi - 1.0).abs() < 1e-12);
+/// }
+/// ```
+pub fn solve_pentadiagonal<F>(
+    dl2: &ArrayView1<F>,
+    dl: &ArrayView1<F>,
+    d: &ArrayView1<F>,
+    du: &ArrayView1<F>,
+    du2: &ArrayView1<F>,
+    b: &ArrayView1<F>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign,
+{
+    let n = d.len();
+    check_diagonal_lengths(n, "Second subdiagonal", dl2.len(), 2)?;
+    check_diagonal_lengths(n, "Subdiagonal", dl.len(), 1)?;
+    check_diagonal_lengths(n, "Superdiagonal", du.len(), 1)?;
+    check_diagonal_lengths(n, "Second superdiagonal", du2.len(), 2)?;
+    check_diagonal_lengths(n, "Right-hand side", b.len(), 0)?;
+    if n == 0 {
+        return Ok(Array1::zeros(0));
+    }
+
+    let tol = F::epsilon()
+        * max_abs(dl2)
+            .max(max_abs(dl))
+            .max(max_abs(d))
+            .max(max_abs(du))
+            .max(max_abs(du2));
+    let mut l1 = dl.to_vec();
+    let mut diag = d.to_vec();
+    let mut u1 = du.to_vec();
+    let mut x = b.to_vec();
+
+    // Forward elimination: pivot row i only has entries in columns i..=i+2
+    for i in 0..n {
+        let pivot = diag[i];
+        let xi = x[i];
+        if pivot.abs() <= tol || !pivot.is_finite() {
+            return Err(zero_pivot("pentadiagonal elimination", n, i, pivot));
+        }
+        if i + 1 < n {
+            let m = l1[i] / pivot;
+            diag[i + 1] -= m * u1[i];
+            if i + 2 < n {
+                u1[i + 1] -= m * du2[i];
+            }
+            x[i + 1] -= m * xi;
+        }
+        if i + 2 < n {
+            let m = dl2[i] / pivot;
+            l1[i + 1] -= m * u1[i];
+            diag[i + 2] -= m * du2[i];
+            x[i + 2] -= m * xi;
+        }
+    }
+
+    // Back substitution
+    for i in (0..n).rev() {
+        let mut acc = x[i];
+        if i + 1 < n {
+            acc -= u1[i] * x[i + 1];
+        }
+        if i + 2 < n {
+            acc -= du2[i] * x[i + 2];
+        }
+        x[i] = acc / diag[i];
+    }
+    Ok(Array1::from(x))
+}
+
+fn validate_symmetric_tridiagonal<F: Float>(
+    d: &ArrayView1<F>,
+    e: &ArrayView1<F>,
+) -> LinalgResult<()> {
+    check_diagonal_lengths(d.len(), "Off-diagonal", e.len(), 1)?;
+    if d.iter().chain(e.iter()).any(|x| !x.is_finite()) {
+        return Err(LinalgError::InvalidInputError(
+            "Tridiagonal matrix contains non-finite values".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Eigenvalues and eigenvectors of a symmetric tridiagonal matrix
+///
+/// Uses implicit QL iteration, costing `O(n^2)` for the eigenvalues plus
+/// `O(n^3)` for accumulating the eigenvectors.
+///
+/// # Arguments
+///
+/// * `d` - Main diagonal (length n)
+/// * `e` - Off-diagonal, `e[i] = a[i + 1, i] = a[i, i + 1]` (length n-1)
+///
+/// # Returns
+///
+/// * Eigenvalues in ascending order and the orthonormal eigenvectors as columns
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::tridiagonal::eigh_tridiagonal;
+///
+/// let d = array![2.0_f64, 2.0, 2.0];
+/// let e = array![-1.0, -1.0];
+/// let (w, v) = eigh_tridiagonal(&d.view(), &e.view()).unwrap();
+/// assert!((w[0] - (2.0 - 2.0_f64.sqrt())).abs() < 1e-12);
+/// assert!((w[2] - (2.0 + 2.0_f64.sqrt())).abs() < 1e-12);
+/// assert_eq!(v.dim(), (3, 3));
+/// ```
+pub fn eigh_tridiagonal<F>(
+    d: &ArrayView1<F>,
+    e: &ArrayView1<F>,
+) -> LinalgResult<(Array1<F>, Array2<F>)>
+where
+    F: Float + NumAssign,
+{
+    eigh_tridiagonal_select(d, e, TridiagonalSelect::All)
+}
+
+/// Eigenvalues of a symmetric tridiagonal matrix
+///
+/// See [`eigh_tridiagonal`] for the argument layout. Returns the eigenvalues
+/// in ascending order.
+pub fn eigvalsh_tridiagonal<F>(d: &ArrayView1<F>, e: &ArrayView1<F>) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign,
+{
+    validate_symmetric_tridiagonal(d, e)?;
+    let w = tridiagonal_ql_implicit(d.to_vec(), e.to_vec(), None)?;
+    Ok(Array1::from(w))
+}
+
+/// Selected eigenpairs of a symmetric tridiagonal matrix
+///
+/// Computes the full decomposition with [`eigh_tridiagonal`] and keeps the
+/// eigenpairs chosen by `select`.
+pub fn eigh_tridiagonal_select<F>(
+    d: &ArrayView1<F>,
+    e: &ArrayView1<F>,
+    select: TridiagonalSelect<F>,
+) -> LinalgResult<(Array1<F>, Array2<F>)>
+where
+    F: Float + NumAssign,
+{
+    validate_symmetric_tridiagonal(d, e)?;
+    let n = d.len();
+    let mut z = Array2::eye(n);
+    let w = tridiagonal_ql_implicit(d.to_vec(), e.to_vec(), Some(&mut z))?;
+
+    let keep: Vec<usize> = match select {
+        TridiagonalSelect::All => (0..n).collect(),
+        TridiagonalSelect::Value(lo, hi) => (0..n).filter(|&i| w[i] > lo && w[i] <= hi).collect(),
+        TridiagonalSelect::Index(lo, hi) => {
+            check_index_range(n, lo, hi)?;
+            (lo..=hi).collect()
+        }
+    };
+    let values = Array1::from_shape_fn(keep.len(), |k| w[keep[k]]);
+    let vectors = z.select(Axis(1), &keep);
+    Ok((values, vectors))
+}
+
+fn check_index_range(n: usize, lo: usize, hi: usize) -> LinalgResult<()> {
+    if lo > hi || hi >= n {
+        return Err(LinalgError::InvalidInputError(format!(
+            "Eigenvalue index range {lo}..={hi} is invalid for a matrix of order {n}"
+        )));
+    }
+    Ok(())
+}
+
+/// Number of eigenvalues strictly less than `x` (Sturm sequence count).
+fn sturm_count<F: Float>(d: &ArrayView1<F>, e2: &[F], x: F, pivmin: F) -> usize {
+    let mut count = 0;
+    let mut q = d[0] - x;
+    for i in 0..d.len() {
+        if i > 0 {
+            q = d[i] - x - e2[i - 1] / q;
+        }
+        if q.abs() < pivmin {
+            q = -pivmin;
+        }
+        if q < F::zero() {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Selected eigenvalues of a symmetric tridiagonal matrix by bisection
+///
+/// Each eigenvalue is located with Sturm sequence bisection, so computing `k`
+/// eigenvalues costs `O(n k)` per bisection step; this is cheaper than
+/// [`eigvalsh_tridiagonal`] when only a few eigenvalues are needed.
+///
+/// # Arguments
+///
+/// * `d` - Main diagonal (length n)
+/// * `e` - Off-diagonal (length n-1)
+/// * `select` - Which eigenvalues to compute
+///
+/// # Returns
+///
+/// * Selected eigenvalues in ascending order
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::array;
+/// use scirs2_linalg::tridiagonal::{eigvalsh_tridiagonal_select, TridiagonalSelect};
+///
+/// let d = array![1.0_f64, 2.0, 3.0, 4.0];
+/// let e = array![0.0, 0.0, 0.0];
+/// let w = eigvalsh_tridiagonal_select(&d.view(), &e.view(), TridiagonalSelect::Value(1.5, 3.0))
+///     .unwrap();
+/// assert_eq!(w.len(), 2);
+/// assert!((w[0] - 2.0).abs() < 1e-12);
+/// assert!((w[1] - 3.0).abs() < 1e-12);
+/// ```
+pub fn eigvalsh_tridiagonal_select<F>(
+    d: &ArrayView1<F>,
+    e: &ArrayView1<F>,
+    select: TridiagonalSelect<F>,
+) -> LinalgResult<Array1<F>>
+where
+    F: Float + NumAssign,
+{
+    validate_symmetric_tridiagonal(d, e)?;
+    let n = d.len();
+    if n == 0 {
+        return Ok(Array1::zeros(0));
+    }
+
+    let e2: Vec<F> = e.iter().map(|&x| x * x).collect();
+    // Gershgorin bounds
+    let mut lower = F::infinity();
+    let mut upper = F::neg_infinity();
+    for i in 0..n {
+        let mut radius = F::zero();
+        if i > 0 {
+            radius += e[i - 1].abs();
+        }
+        if i + 1 < n {
+            radius += e[i].abs();
+        }
+        lower = lower.min(d[i] - radius);
+        upper = upper.max(d[i] + radius);
+    }
+    let two = F::one() + F::one();
+    let span = (upper - lower).max(upper.abs().max(lower.abs()));
+    let pivmin = F::min_positive_value().max(F::epsilon() * F::epsilon() * span * span);
+    let slack = two * F::epsilon() * span + pivmin;
+    lower -= slack;
+    upper += slack;
+
+    let (first, last) = match select {
+        TridiagonalSelect::All => (0, n),
+        TridiagonalSelect::Value(lo, hi) => {
+            if hi <= lo {
+                return Ok(Array1::zeros(0));
+            }
+            // Eigenvalues in (lo, hi] are those counted at hi but not at lo,
+            // with both ends nudged up so that equality is counted as below.
+            let first = sturm_count(d, &e2, next_up(lo), pivmin);
+            let last = sturm_count(d, &e2, next_up(hi), pivmin);
+            (first, last.max(first))
+        }
+        TridiagonalSelect::Index(lo, hi) => {
+            check_index_range(n, lo, hi)?;
+            (lo, hi + 1)
+        }
+    };
+
+    let tol = two * F::epsilon() * span + pivmin;
+    let values = (first..last)
+        .map(|k| {
+            // Smallest x with more than k eigenvalues below it
+            let mut a = lower;
+            let mut b = upper;
+            for _ in 0..256 {
+                if b - a <= tol {
+                    break;
+                }
+                let mid = (a + b) / two;
+                if sturm_count(d, &e2, mid, pivmin) > k {
+                    b = mid;
+                } else {
+                    a = mid;
+                }
+            }
+            (a + b) / two
+        })
+        .collect::<Vec<_>>();
+    Ok(Array1::from(values))
+}
+
+/// Smallest representable value above `x`, used to make interval ends inclusive.
+fn next_up<F: Float>(x: F) -> F {
+    let step = (x.abs() * F::epsilon()).max(F::min_positive_value());
+    x + step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+
+    fn dense(dl: &[f64], d: &[f64], du: &[f64]) -> Array2<f64> {
+        let n = d.len();
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                d[i]
+            } else if i == j + 1 {
+                dl[j]
+            } else if j == i + 1 {
+                du[i]
+            } else {
+                0.0
+            }
+        })
+    }
+
+    #[test]
+    fn test_solve_tridiagonal_matches_dense() {
+        let dl = [1.0, -2.0, 0.5, 1.5];
+        let d = [5.0, 6.0, 4.0, 7.0, 3.0];
+        let du = [2.0, 1.0, -1.0, 0.5];
+        let b = array![1.0, 2.0, 3.0, 4.0, 5.0];
+        let x = solve_tridiagonal(
+            &ArrayView1::from(&dl),
+            &ArrayView1::from(&d),
+            &ArrayView1::from(&du),
+            &b.view(),
+        )
+        .unwrap();
+        let residual = dense(&dl, &d, &du).dot(&x) - &b;
+        for r in residual.iter() {
+            assert!(r.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_solve_tridiagonal_multiple_and_errors() {
+        let dl = array![-1.0, -1.0];
+        let d = array![2.0, 2.0, 2.0];
+        let du = array![-1.0, -1.0];
+        let b = array![[1.0, 2.0], [0.0, 0.0], [1.0, 2.0]];
+        let x = solve_tridiagonal_multiple(&dl.view(), &d.view(), &du.view(), &b.view()).unwrap();
+        for i in 0..3 {
+            assert_relative_eq!(x[[i, 0]], 1.0, epsilon = 1e-12);
+            assert_relative_eq!(x[[i, 1]], 2.0, epsilon = 1e-12);
+        }
+
+        let short = array![-1.0];
+        assert!(solve_tridiagonal(&short.view(), &d.view(), &du.view(), &b.column(0)).is_err());
+
+        // [[1, 1], [1, 1]] is singular
+        let ones = array![1.0];
+        let d2 = array![1.0, 1.0];
+        assert!(matches!(
+            solve_tridiagonal(
+                &ones.view(),
+                &d2.view(),
+                &ones.view(),
+                &array![1.0, 2.0].view()
+            ),
+            Err(LinalgError::SingularMatrixError(_))
+        ));
+    }
+
+    #[test]
+    fn test_solve_cyclic_tridiagonal() {
+        let n = 6;
+        let dl = Array1::from_shape_fn(n, |i| -1.0 - 0.1 * i as f64);
+        let d = Array1::from_shape_fn(n, |i| 4.0 + i as f64);
+        let du = Array1::from_shape_fn(n, |i| 0.5 + 0.2 * i as f64);
+        let b = Array1::from_shape_fn(n, |i| (i as f64).sin());
+        let x = solve_cyclic_tridiagonal(&dl.view(), &d.view(), &du.view(), &b.view()).unwrap();
+
+        let mut a = Array2::zeros((n, n));
+        for i in 0..n {
+            a[[i, (i + n - 1) % n]] = dl[i];
+            a[[i, i]] = d[i];
+            a[[i, (i + 1) % n]] = du[i];
+        }
+        let residual = a.dot(&x) - &b;
+        for r in residual.iter() {
+            assert!(r.abs() < 1e-12);
+        }
+
+        let two = array![1.0, 1.0];
+        assert!(
+            solve_cyclic_tridiagonal(&two.view(), &two.view(), &two.view(), &two.view()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_solve_pentadiagonal_matches_dense() {
+        let n = 7;
+        let dl2 = Array1::from_shape_fn(n - 2, |i| 0.5 + 0.1 * i as f64);
+        let dl = Array1::from_shape_fn(n - 1, |i| -1.0 + 0.2 * i as f64);
+        let d = Array1::from_shape_fn(n, |i| 6.0 + i as f64);
+        let du = Array1::from_shape_fn(n - 1, |i| 1.5 - 0.1 * i as f64);
+        let du2 = Array1::from_shape_fn(n - 2, |i| -0.3 * i as f64);
+        let b = Array1::from_shape_fn(n, |i| 1.0 + i as f64);
+        let x = solve_pentadiagonal(
+            &dl2.view(),
+            &dl.view(),
+            &d.view(),
+            &du.view(),
+            &du2.view(),
+            &b.view(),
+        )
+        .unwrap();
+
+        let mut a = dense(
+            dl.as_slice().unwrap(),
+            d.as_slice().unwrap(),
+            du.as_slice().unwrap(),
+        );
+        for i in 0..n - 2 {
+            a[[i + 2, i]] = dl2[i];
+            a[[i, i + 2]] = du2[i];
+        }
+        let residual = a.dot(&x) - &b;
+        for r in residual.iter() {
+            assert!(r.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_eigh_tridiagonal_decomposition() {
+        let d = array![4.0, 1.0, -2.0, 3.0, 0.5];
+        let e = array![1.0, 2.0, -0.5, 1.5];
+        let (w, v) = eigh_tridiagonal(&d.view(), &e.view()).unwrap();
+        let a = dense(
+            e.as_slice().unwrap(),
+            d.as_slice().unwrap(),
+            e.as_slice().unwrap(),
+        );
+
+        for k in 1..w.len() {
+            assert!(w[k - 1] <= w[k]);
+        }
+        let av = a.dot(&v);
+        for k in 0..5 {
+            for i in 0..5 {
+                assert_relative_eq!(av[[i, k]], w[k] * v[[i, k]], epsilon = 1e-10);
+            }
+        }
+        let vtv = v.t().dot(&v);
+        for i in 0..5 {
+            for j in 0..5 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(vtv[[i, j]], expected, epsilon = 1e-12);
+            }
+        }
+
+        let w_only = eigvalsh_tridiagonal(&d.view(), &e.view()).unwrap();
+        for k in 0..5 {
+            assert_relative_eq!(w_only[k], w[k], epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_tridiagonal_select() {
+        // Eigenvalues of the 1-D Laplacian: 2 - 2 cos(k pi / (n + 1))
+        let n = 20;
+        let d = Array1::from_elem(n, 2.0);
+        let e = Array1::from_elem(n - 1, -1.0);
+        let exact: Vec<f64> = (1..=n)
+            .map(|k| 2.0 - 2.0 * (k as f64 * std::f64::consts::PI / (n + 1) as f64).cos())
+            .collect();
+
+        let all =
+            eigvalsh_tridiagonal_select(&d.view(), &e.view(), TridiagonalSelect::All).unwrap();
+        for k in 0..n {
+            assert_relative_eq!(all[k], exact[k], epsilon = 1e-12);
+        }
+
+        let some =
+            eigvalsh_tridiagonal_select(&d.view(), &e.view(), TridiagonalSelect::Index(3, 5))
+                .unwrap();
+        assert_eq!(some.len(), 3);
+        for (k, &wk) in some.iter().enumerate() {
+            assert_relative_eq!(wk, exact[3 + k], epsilon = 1e-12);
+        }
+
+        let in_range =
+            eigvalsh_tridiagonal_select(&d.view(), &e.view(), TridiagonalSelect::Value(1.0, 2.0))
+                .unwrap();
+        let expected: Vec<f64> = exact
+            .iter()
+            .copied()
+            .filter(|&x| x > 1.0 && x <= 2.0)
+            .collect();
+        assert_eq!(in_range.len(), expected.len());
+
+        let (w, v) =
+            eigh_tridiagonal_select(&d.view(), &e.view(), TridiagonalSelect::Value(1.0, 2.0))
+                .unwrap();
+        assert_eq!(v.dim(), (n, expected.len()));
+        for (k, &x) in expected.iter().enumerate() {
+            assert_relative_eq!(in_range[k], x, epsilon = 1e-12);
+            assert_relative_eq!(w[k], x, epsilon = 1e-12);
+        }
+
+        assert!(
+            eigvalsh_tridiagonal_select(&d.view(), &e.view(), TridiagonalSelect::Index(5, 20))
+                .is_err()
+        );
+    }
+}
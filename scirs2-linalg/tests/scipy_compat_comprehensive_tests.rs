@@ -999,12 +999,18 @@ mod error_handling_tests {
             "tanm should be implemented"
         );
 
-        let dummy_banded = array![[1.0, 2.0], [3.0, 4.0]];
+        // Band storage with the wrong number of rows for (l, u)
+        let bad_banded = array![[1.0, 2.0], [3.0, 4.0]];
         let dummy_rhs = array![[1.0], [2.0]];
-        assert!(
-            compat::solve_banded(&dummy_banded.view(), &dummy_rhs.view(), false, false, true)
-                .is_err()
-        );
+        assert!(compat::solve_banded(
+            (1, 1),
+            &bad_banded.view(),
+            &dummy_rhs.view(),
+            false,
+            false,
+            true
+        )
+        .is_err());
 
         // Unsupported norm types
         assert!(compat::norm(&test_matrix.view(), Some("nuc"), None, false, true).is_err());
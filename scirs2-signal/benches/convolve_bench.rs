@@ -0,0 +1,76 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use scirs2_signal::{convolve, correlate, fftconvolve};
+use std::hint::black_box;
+use std::time::Duration;
+
+fn generate_signal(size: usize) -> Vec<f64> {
+    // Deterministic broadband test signal
+    (0..size)
+        .map(|i| {
+            let t = i as f64;
+            (0.013 * t).sin() + 0.5 * (0.171 * t).cos() + 0.25 * (0.9 * t * t / size as f64).sin()
+        })
+        .collect()
+}
+
+/// Direct (SIMD, parallel) versus FFT convolution for growing kernel lengths
+///
+/// Direct convolution costs O(N M) and FFT convolution O((N + M) log(N + M)),
+/// so the FFT method overtakes the direct one once the kernel is long enough.
+fn bench_convolve_crossover(c: &mut Criterion) {
+    let mut group = c.benchmark_group("convolve_crossover");
+    group.measurement_time(Duration::from_secs(3));
+    group.sample_size(20);
+
+    let signal = generate_signal(16384);
+    let kernel_sizes = [4, 16, 32, 64, 128, 256, 1024];
+
+    for &kernel_size in &kernel_sizes {
+        let kernel = generate_signal(kernel_size);
+
+        group.bench_with_input(
+            BenchmarkId::new("direct", kernel_size),
+            &kernel_size,
+            |b, _| b.iter(|| black_box(convolve(&signal, &kernel, "full").unwrap())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("fft", kernel_size),
+            &kernel_size,
+            |b, _| b.iter(|| black_box(fftconvolve(&signal, &kernel, "full").unwrap())),
+        );
+    }
+
+    group.finish();
+}
+
+/// Direct convolution and correlation for growing signal lengths
+///
+/// Covers both the single-threaded path and the parallel block path.
+fn bench_direct_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("direct_scaling");
+    group.measurement_time(Duration::from_secs(3));
+    group.sample_size(20);
+
+    let kernel = generate_signal(64);
+    let sizes = [1024, 8192, 65536];
+
+    for &size in &sizes {
+        let signal = generate_signal(size);
+
+        group.bench_with_input(BenchmarkId::new("convolve", size), &size, |b, _| {
+            b.iter(|| black_box(convolve(&signal, &kernel, "same").unwrap()))
+        });
+        group.bench_with_input(BenchmarkId::new("correlate", size), &size, |b, _| {
+            b.iter(|| black_box(correlate(&signal, &kernel, "valid").unwrap()))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    convolve_benches,
+    bench_convolve_crossover,
+    bench_direct_scaling
+);
+criterion_main!(convolve_benches);
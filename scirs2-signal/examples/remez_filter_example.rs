@@ -1,7 +1,7 @@
 //! Example of Parks-McClellan optimal FIR filter design using the Remez exchange algorithm
 
 use scirs2_fft::fft;
-use scirs2_signal::filter::remez;
+use scirs2_signal::filter::{remez, remez_design, RemezFilterType};
 use std::f64::consts::PI;
 
 fn main() {
@@ -96,14 +96,27 @@ fn main() {
     println!("\n\n4. Differentiator Design");
     println!("------------------------");
 
-    let numtaps = 81;
-    let bands = vec![0.05, 0.45]; // Avoid DC and Nyquist
-    let desired = vec![0.05 * PI, 0.45 * PI]; // Linear slope
-
-    let h_diff = remez(numtaps, &bands, &desired, None, None, None).unwrap();
+    let numtaps = 80; // Even length: type IV, no zero at Nyquist
+    let bands = vec![0.0, 0.9];
+    let desired = vec![0.0, 0.9 * PI]; // Linear slope
+
+    let diff = remez_design(
+        numtaps,
+        &bands,
+        &desired,
+        None,
+        RemezFilterType::Differentiator,
+        None,
+        None,
+    )
+    .unwrap();
 
+    println!(
+        "Converged: {} after {} iterations (relative deviation {:.2e})",
+        diff.converged, diff.iterations, diff.deviation
+    );
     println!("Differentiator coefficients (first 10):");
-    for (i, &coeff) in h_diff.iter().take(10).enumerate() {
+    for (i, &coeff) in diff.coefficients.iter().take(10).enumerate() {
         println!("  h[{}] = {:.6}", i, coeff);
     }
 
@@ -111,14 +124,30 @@ fn main() {
     println!("\n\n5. Hilbert Transformer Design");
     println!("-----------------------------");
 
-    let _numtaps = 81; // Must be odd for Type III filter
-    let _bands = [0.05, 0.95]; // Avoid DC and Nyquist
-    let _desired = [1.0, 1.0]; // Constant magnitude
+    let numtaps = 81; // Odd length: type III, zero gain at DC and Nyquist
+    let bands = [0.05, 0.95]; // Avoid DC and Nyquist
+    let desired = [1.0, 1.0]; // Constant magnitude
+
+    let hilbert = remez_design(
+        numtaps,
+        &bands,
+        &desired,
+        None,
+        RemezFilterType::Hilbert,
+        None,
+        None,
+    )
+    .unwrap();
 
-    // For Hilbert transformer, we need to design an antisymmetric filter
-    // This is a simplified version - a full implementation would need Type III/IV support
-    println!("Hilbert transformer would require Type III filter support");
-    println!("(antisymmetric impulse response with odd length)");
+    println!(
+        "Converged: {} after {} iterations (ripple {:.2e})",
+        hilbert.converged, hilbert.iterations, hilbert.deviation
+    );
+    println!("Hilbert transformer coefficients around the center:");
+    let center = numtaps / 2;
+    for i in (center - 3)..=(center + 3) {
+        println!("  h[{}] = {:.6}", i, hilbert.coefficients[i]);
+    }
 
     // Performance characteristics
     println!("\n\nPerformance Characteristics");
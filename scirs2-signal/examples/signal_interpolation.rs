@@ -136,6 +136,7 @@ fn interpolate_simple_signal() -> SignalResult<()> {
         smoothing_factor: 0.1,
         frequency_constraint: true,
         cutoff_frequency: 0.3,
+        gaussian_process: interpolate::GaussianProcessConfig::default(),
     };
 
     // Apply linear interpolation
@@ -243,6 +244,7 @@ fn compare_interpolation_methods() -> SignalResult<()> {
         smoothing_factor: 0.1,
         frequency_constraint: true,
         cutoff_frequency: 0.3,
+        gaussian_process: interpolate::GaussianProcessConfig::default(),
     };
 
     // Apply different interpolation methods
@@ -381,6 +383,7 @@ fn interpolate_bandlimited_signal() -> SignalResult<()> {
         smoothing_factor: 0.1,
         frequency_constraint: true,
         cutoff_frequency: 0.5, // Use full bandwidth for bandlimited signal
+        gaussian_process: interpolate::GaussianProcessConfig::default(),
     };
 
     // Apply different interpolation methods
@@ -504,6 +507,7 @@ fn interpolate_2d_data() -> SignalResult<()> {
         smoothing_factor: 0.1,
         frequency_constraint: true,
         cutoff_frequency: 0.3,
+        gaussian_process: interpolate::GaussianProcessConfig::default(),
     };
 
     // Apply different interpolation methods
@@ -525,10 +529,17 @@ fn interpolate_2d_data() -> SignalResult<()> {
         &config,
     )?;
 
+    let biharmonic_result = interpolate::interpolate_2d(
+        &missing_image,
+        interpolate::InterpolationMethod::Biharmonic,
+        &config,
+    )?;
+
     // Calculate error metrics
     let mut linear_sse = 0.0;
     let mut spline_sse = 0.0;
     let mut nearest_sse = 0.0;
+    let mut biharmonic_sse = 0.0;
     let mut count = 0;
 
     for i in 0..n_rows {
@@ -537,10 +548,12 @@ fn interpolate_2d_data() -> SignalResult<()> {
                 let linear_err = linear_result[[i, j]] - image[[i, j]];
                 let spline_err = spline_result[[i, j]] - image[[i, j]];
                 let nearest_err = nearest_result[[i, j]] - image[[i, j]];
+                let biharmonic_err = biharmonic_result[[i, j]] - image[[i, j]];
 
                 linear_sse += linear_err * linear_err;
                 spline_sse += spline_err * spline_err;
                 nearest_sse += nearest_err * nearest_err;
+                biharmonic_sse += biharmonic_err * biharmonic_err;
                 count += 1;
             }
         }
@@ -549,11 +562,13 @@ fn interpolate_2d_data() -> SignalResult<()> {
     let linear_mse = linear_sse / count as f64;
     let spline_mse = spline_sse / count as f64;
     let nearest_mse = nearest_sse / count as f64;
+    let biharmonic_mse = biharmonic_sse / count as f64;
 
     println!("Mean squared error (2D image):");
     println!("  Linear: {:.6}", linear_mse);
     println!("  Cubic Spline: {:.6}", spline_mse);
     println!("  Nearest Neighbor: {:.6}", nearest_mse);
+    println!("  Biharmonic Inpainting: {:.6}", biharmonic_mse);
 
     // Export data for visualization
     export_2d_to_csv("interpolation_2d_original.csv", &image)?;
@@ -561,6 +576,7 @@ fn interpolate_2d_data() -> SignalResult<()> {
     export_2d_to_csv("interpolation_2d_linear.csv", &linear_result)?;
     export_2d_to_csv("interpolation_2d_spline.csv", &spline_result)?;
     export_2d_to_csv("interpolation_2d_nearest.csv", &nearest_result)?;
+    export_2d_to_csv("interpolation_2d_biharmonic.csv", &biharmonic_result)?;
 
     Ok(())
 }
@@ -632,6 +648,7 @@ fn auto_interpolation_example() -> SignalResult<()> {
         smoothing_factor: 0.1,
         frequency_constraint: true,
         cutoff_frequency: 0.3,
+        gaussian_process: interpolate::GaussianProcessConfig::default(),
     };
 
     // Apply auto interpolation with cross-validation
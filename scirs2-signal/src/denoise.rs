@@ -117,14 +117,8 @@ where
     // Decompose the signal
     let coeffs = wavedec(&signal, wavelet, level, None)?;
 
-    // Estimate noise standard deviation if not provided
-    let sigma = noise_sigma.unwrap_or_else(|| {
-        // Use median absolute deviation of finest detail coefficients
-        // MAD / 0.6745 is a robust estimator of standard deviation
-        let finest_detail = &coeffs[coeffs.len() - 1_usize];
-        let median = median_abs_deviation(finest_detail);
-        median / 0.6745
-    });
+    // Estimate noise standard deviation from the finest detail coefficients if not provided
+    let sigma = noise_sigma.unwrap_or_else(|| estimate_noise_sigma(&coeffs[coeffs.len() - 1]));
 
     // Apply thresholding to detail coefficients
     let mut thresholded_coeffs = Vec::with_capacity(coeffs.len());
@@ -139,11 +133,7 @@ where
         // Select threshold value
         let threshold = match threshold_select {
             ThresholdSelect::Universal => sigma * (2.0 * (n as f64).ln()).sqrt(),
-            ThresholdSelect::Sure => {
-                // A simplified version of SURE threshold
-                // In a full implementation, this would minimize Stein's Unbiased Risk Estimate
-                sigma * (2.0 * (n as f64).ln()).sqrt() * 0.75
-            }
+            ThresholdSelect::Sure => sure_threshold(detail, sigma),
             ThresholdSelect::Minimax => {
                 // Minimax threshold is approximately 0.3936 + 0.1829 * log2(n)
                 // for reasonably large n
@@ -152,17 +142,116 @@ where
         };
 
         // Apply threshold
-        let thresholded = match threshold_method {
-            ThresholdMethod::Hard => hard_threshold(detail, threshold),
-            ThresholdMethod::Soft => soft_threshold(detail, threshold),
-            ThresholdMethod::Garrote => garrote_threshold(detail, threshold),
-        };
+        let thresholded = threshold_coefficients(detail, threshold, threshold_method);
 
         thresholded_coeffs.push(thresholded);
     }
 
     // Reconstruct signal from thresholded coefficients
-    waverec(&thresholded_coeffs, wavelet)
+    let mut denoised = waverec(&thresholded_coeffs, wavelet)?;
+    denoised.truncate(signal.len());
+    Ok(denoised)
+}
+
+/// Threshold wavelet coefficients
+///
+/// # Arguments
+///
+/// * `coeffs` - Wavelet coefficients
+/// * `threshold` - Non-negative threshold value
+/// * `method` - Hard, soft or garrote thresholding
+///
+/// # Returns
+///
+/// * The thresholded coefficients
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::denoise::{threshold_coefficients, ThresholdMethod};
+///
+/// let coeffs = vec![-3.0, -1.0, 0.5, 2.0];
+/// let soft = threshold_coefficients(&coeffs, 1.0, ThresholdMethod::Soft);
+/// assert_eq!(soft, vec![-2.0, 0.0, 0.0, 1.0]);
+/// ```
+pub fn threshold_coefficients(coeffs: &[f64], threshold: f64, method: ThresholdMethod) -> Vec<f64> {
+    match method {
+        ThresholdMethod::Hard => hard_threshold(coeffs, threshold),
+        ThresholdMethod::Soft => soft_threshold(coeffs, threshold),
+        ThresholdMethod::Garrote => garrote_threshold(coeffs, threshold),
+    }
+}
+
+/// Estimate the noise standard deviation from detail coefficients
+///
+/// Uses the robust estimator `median(|d|) / 0.6745` of Donoho & Johnstone, which
+/// is unaffected by the few large coefficients that carry the signal.
+///
+/// # Arguments
+///
+/// * `detail` - Detail coefficients, usually from the finest decomposition level
+///
+/// # Returns
+///
+/// * The estimated noise standard deviation (zero for an empty input)
+pub fn estimate_noise_sigma(detail: &[f64]) -> f64 {
+    if detail.is_empty() {
+        return 0.0;
+    }
+
+    let mut magnitudes: Vec<f64> = detail.iter().map(|&x| x.abs()).collect();
+    magnitudes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let n = magnitudes.len();
+    let median = if n.is_multiple_of(2) {
+        (magnitudes[n / 2 - 1] + magnitudes[n / 2]) / 2.0
+    } else {
+        magnitudes[n / 2]
+    };
+
+    median / 0.6745
+}
+
+/// Select a soft threshold by minimizing Stein's Unbiased Risk Estimate
+///
+/// For coefficients normalized by `sigma`, the risk of soft thresholding at `t`
+/// is estimated as `n - 2 #{|x| <= t} + sum(min(x^2, t^2))`. The minimum is
+/// attained at one of the coefficient magnitudes, and the result is capped at
+/// the universal threshold `sigma * sqrt(2 ln n)`.
+///
+/// # Arguments
+///
+/// * `coeffs` - Noisy wavelet coefficients
+/// * `sigma` - Noise standard deviation
+///
+/// # Returns
+///
+/// * The SURE threshold in the units of `coeffs`
+pub fn sure_threshold(coeffs: &[f64], sigma: f64) -> f64 {
+    let n = coeffs.len();
+    if n == 0 || sigma <= 0.0 {
+        return 0.0;
+    }
+
+    let mut squared: Vec<f64> = coeffs.iter().map(|&x| (x / sigma).powi(2)).collect();
+    squared.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Risk at t^2 = squared[k]: coefficients 0..=k are killed, the rest shrunk by t.
+    // Not thresholding at all has risk n.
+    let mut best_risk = n as f64;
+    let mut best_t2 = 0.0;
+    let mut cumulative = 0.0;
+    for (k, &t2) in squared.iter().enumerate() {
+        cumulative += t2;
+        let risk = n as f64 - 2.0 * (k + 1) as f64 + cumulative + (n - k - 1) as f64 * t2;
+        if risk < best_risk {
+            best_risk = risk;
+            best_t2 = t2;
+        }
+    }
+
+    let universal = (2.0 * (n as f64).ln()).sqrt();
+    sigma * best_t2.sqrt().min(universal)
 }
 
 /// Apply hard thresholding to wavelet coefficients
@@ -207,40 +296,6 @@ fn garrote_threshold(coeffs: &[f64], threshold: f64) -> Vec<f64> {
         .collect()
 }
 
-/// Compute the median absolute deviation of a vector
-fn median_abs_deviation(data: &[f64]) -> f64 {
-    if data.is_empty() {
-        return 0.0;
-    }
-
-    // Create a copy to avoid modifying the original
-    let mut values: Vec<f64> = data.iter().map(|&x| x.abs()).collect();
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Find the median
-    let n = values.len();
-    let median = if n % 2 == 0 {
-        (values[n / 2 - 1] + values[n / 2]) / 2.0
-    } else {
-        values[n / 2]
-    };
-
-    // Compute deviations from median
-    let deviations: Vec<f64> = data.iter().map(|&x| (x - median).abs()).collect();
-
-    // Sort the deviations
-    let mut sorted_deviations = deviations.clone();
-    sorted_deviations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Return the median of the deviations
-    let m = sorted_deviations.len();
-    if m % 2 == 0 {
-        (sorted_deviations[m / 2 - 1] + sorted_deviations[m / 2]) / 2.0
-    } else {
-        sorted_deviations[m / 2]
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,14 +322,31 @@ mod tests {
     }
 
     #[test]
-    fn test_median_abs_deviation() {
-        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
-        let mad = median_abs_deviation(&data);
-
-        // Median is 5.0
-        // Absolute deviations are [4.0, 3.0, 2.0, 1.0, 0.0, 1.0, 2.0, 3.0, 4.0]
-        // Median of absolute deviations is 2.0
-        assert_eq!(mad, 2.0);
+    fn test_noise_sigma_and_sure_threshold() {
+        let mut rng = rand::rng();
+        let sigma = 0.5;
+        // Approximately Gaussian noise from a sum of uniforms
+        let noise: Vec<f64> = (0..4096)
+            .map(|_| {
+                let s: f64 = (0..12).map(|_| rng.random_range(0.0..1.0)).sum();
+                sigma * (s - 6.0)
+            })
+            .collect();
+
+        let estimate = estimate_noise_sigma(&noise);
+        assert!(
+            (estimate - sigma).abs() < 0.05,
+            "sigma estimate {}",
+            estimate
+        );
+
+        // Pure noise: SURE keeps the threshold near the universal one
+        let universal = sigma * (2.0 * (noise.len() as f64).ln()).sqrt();
+        assert!(sure_threshold(&noise, sigma) > 0.5 * universal);
+
+        // Dense large coefficients: shrinking hurts, so the threshold is small
+        let signal: Vec<f64> = noise.iter().map(|&x| x + 10.0).collect();
+        assert!(sure_threshold(&signal, sigma) < 0.5 * sigma);
     }
 
     #[test]
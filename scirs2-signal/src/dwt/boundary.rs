@@ -2,7 +2,8 @@
 //!
 //! This module provides methods for extending signals at boundaries to handle edge effects
 //! when applying wavelet transforms. Different extension modes are supported, including
-//! symmetric, periodic, reflect, constant, and zero padding.
+//! symmetric, periodic, reflect, constant, smooth, antisymmetric and zero padding, as
+//! well as periodization, which keeps the coefficient count at half the signal length.
 
 use crate::error::{SignalError, SignalResult};
 
@@ -191,3 +192,92 @@ pub fn extend_signal(signal: &[f64], filter_len: usize, mode: &str) -> SignalRes
 
     Ok(extended)
 }
+
+/// Signal extension modes understood by the transform functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ExtensionMode {
+    Zero,
+    Constant,
+    Symmetric,
+    Reflect,
+    Periodic,
+    Smooth,
+    Antisymmetric,
+    Periodization,
+}
+
+impl ExtensionMode {
+    /// Parse an extension mode name, defaulting to symmetric extension
+    pub(crate) fn parse(mode: Option<&str>) -> SignalResult<Self> {
+        match mode.unwrap_or("symmetric") {
+            "zero" => Ok(ExtensionMode::Zero),
+            "constant" => Ok(ExtensionMode::Constant),
+            "symmetric" => Ok(ExtensionMode::Symmetric),
+            "reflect" => Ok(ExtensionMode::Reflect),
+            "periodic" => Ok(ExtensionMode::Periodic),
+            "smooth" => Ok(ExtensionMode::Smooth),
+            "antisymmetric" => Ok(ExtensionMode::Antisymmetric),
+            "periodization" => Ok(ExtensionMode::Periodization),
+            other => Err(SignalError::ValueError(format!(
+                "Unsupported extension mode: {}. Valid modes are 'symmetric', 'reflect', 'periodic', \
+                 'periodization', 'constant', 'smooth', 'antisymmetric', and 'zero'.",
+                other
+            ))),
+        }
+    }
+
+    /// Value of the extended signal at an arbitrary (possibly negative) index
+    ///
+    /// Indices inside `0..signal.len()` return the signal itself; indices outside
+    /// follow the extension rule, repeating the pattern as often as needed so
+    /// that filters longer than the signal are handled.
+    pub(crate) fn sample(&self, signal: &[f64], index: isize) -> f64 {
+        let n = signal.len() as isize;
+        if (0..n).contains(&index) {
+            return signal[index as usize];
+        }
+        match self {
+            ExtensionMode::Zero => 0.0,
+            ExtensionMode::Constant => {
+                if index < 0 {
+                    signal[0]
+                } else {
+                    signal[(n - 1) as usize]
+                }
+            }
+            ExtensionMode::Symmetric => {
+                let m = index.rem_euclid(2 * n);
+                signal[(if m < n { m } else { 2 * n - 1 - m }) as usize]
+            }
+            ExtensionMode::Reflect => {
+                if n == 1 {
+                    return signal[0];
+                }
+                let m = index.rem_euclid(2 * n - 2);
+                signal[(if m < n { m } else { 2 * n - 2 - m }) as usize]
+            }
+            ExtensionMode::Periodic | ExtensionMode::Periodization => {
+                signal[index.rem_euclid(n) as usize]
+            }
+            ExtensionMode::Smooth => {
+                if n == 1 {
+                    return signal[0];
+                }
+                if index < 0 {
+                    signal[0] + index as f64 * (signal[1] - signal[0])
+                } else {
+                    let last = (n - 1) as usize;
+                    signal[last] + (index - n + 1) as f64 * (signal[last] - signal[last - 1])
+                }
+            }
+            ExtensionMode::Antisymmetric => {
+                let m = index.rem_euclid(2 * n);
+                if m < n {
+                    signal[m as usize]
+                } else {
+                    -signal[(2 * n - 1 - m) as usize]
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Construction of compactly supported wavelet filters by spectral factorization
+//!
+//! Daubechies, Symlet and biorthogonal spline wavelets all derive from the
+//! polynomial `P_K(y) = sum_{k<K} C(K-1+k, k) y^k` with `y = sin^2(w/2)`:
+//!
+//! * Daubechies filters take the minimum-phase square root of `P_K`.
+//! * Symlets take the square root whose phase is closest to linear.
+//! * Biorthogonal filters split the factors of `P_K` between the analysis and
+//!   synthesis lowpass filters, each keeping its zeros at Nyquist.
+//!
+//! Lowpass filters are returned with taps summing to `sqrt(2)`.
+
+use crate::error::{SignalError, SignalResult};
+use num_complex::Complex64;
+use std::f64::consts::{PI, SQRT_2};
+
+/// Binomial coefficient as a float
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Coefficients of `P_K(y)` in ascending powers of `y`
+fn daubechies_polynomial(k: usize) -> Vec<f64> {
+    (0..k).map(|j| binomial(k - 1 + j, j)).collect()
+}
+
+/// Multiply two polynomials given by ascending coefficients
+fn poly_mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+    out
+}
+
+/// Roots of a real polynomial (ascending coefficients) by the Aberth-Ehrlich iteration
+fn polynomial_roots(coeffs: &[f64]) -> SignalResult<Vec<Complex64>> {
+    let degree = coeffs.len() - 1;
+    if degree == 0 {
+        return Ok(Vec::new());
+    }
+    let lead = coeffs[degree];
+    let monic: Vec<f64> = coeffs.iter().map(|c| c / lead).collect();
+
+    let eval = |z: Complex64| -> (Complex64, Complex64) {
+        let mut p = Complex64::new(1.0, 0.0);
+        let mut dp = Complex64::new(0.0, 0.0);
+        for &c in monic[..degree].iter().rev() {
+            dp = dp * z + p;
+            p = p * z + c;
+        }
+        (p, dp)
+    };
+
+    // Cauchy bound for the starting circle
+    let radius = 1.0 + monic[..degree].iter().fold(0.0_f64, |m, c| m.max(c.abs()));
+    let mut roots: Vec<Complex64> = (0..degree)
+        .map(|k| Complex64::from_polar(0.5 * radius, 2.0 * PI * k as f64 / degree as f64 + 0.4))
+        .collect();
+
+    for _ in 0..1000 {
+        let mut max_step = 0.0_f64;
+        for k in 0..degree {
+            let (p, dp) = eval(roots[k]);
+            if p.norm() == 0.0 {
+                continue;
+            }
+            let ratio = p / dp;
+            let repulsion: Complex64 = (0..degree)
+                .filter(|&j| j != k)
+                .map(|j| (roots[k] - roots[j]).inv())
+                .sum();
+            let step = ratio / (Complex64::new(1.0, 0.0) - ratio * repulsion);
+            roots[k] -= step;
+            max_step = max_step.max(step.norm() / roots[k].norm().max(1.0));
+        }
+        if max_step < 1e-15 {
+            break;
+        }
+    }
+
+    if roots.iter().any(|r| !r.re.is_finite() || !r.im.is_finite()) {
+        return Err(SignalError::ComputationError(
+            "Polynomial root finding did not converge".to_string(),
+        ));
+    }
+    Ok(roots)
+}
+
+/// A root of `P_K` together with its conjugate when complex
+#[derive(Debug, Clone, Copy)]
+struct RootGroup {
+    root: Complex64,
+    is_real: bool,
+}
+
+/// Roots of `P_K(y)`, one entry per real root or conjugate pair
+fn root_groups(k: usize) -> SignalResult<Vec<RootGroup>> {
+    let roots = polynomial_roots(&daubechies_polynomial(k))?;
+    let mut groups = Vec::new();
+    for r in roots {
+        if r.im.abs() <= 1e-9 * r.norm().max(1.0) {
+            groups.push(RootGroup {
+                root: Complex64::new(r.re, 0.0),
+                is_real: true,
+            });
+        } else if r.im > 0.0 {
+            groups.push(RootGroup {
+                root: r,
+                is_real: false,
+            });
+        }
+    }
+    Ok(groups)
+}
+
+/// Symmetric Laurent factor `(y - r) / (-r)` in powers of `z`, for `y = (2 - z - 1/z) / 4`
+fn y_factor(group: &RootGroup) -> Vec<f64> {
+    let single = |r: Complex64| -> [Complex64; 3] {
+        // (y - r) / (-r) = (-1/4 z^-1 + (1/2 - r) - 1/4 z) / (-r)
+        let s = -r.inv();
+        [s * -0.25, s * (0.5 - r), s * -0.25]
+    };
+    if group.is_real {
+        single(group.root).iter().map(|c| c.re).collect()
+    } else {
+        let a = single(group.root);
+        let b = single(group.root.conj());
+        let mut out = [Complex64::new(0.0, 0.0); 5];
+        for i in 0..3 {
+            for j in 0..3 {
+                out[i + j] += a[i] * b[j];
+            }
+        }
+        out.iter().map(|c| c.re).collect()
+    }
+}
+
+/// Lowpass with `nyquist_zeros` zeros at `z = -1` and the given `z`-plane roots
+fn lowpass_from_roots(nyquist_zeros: usize, roots: &[Complex64]) -> Vec<f64> {
+    let mut taps = vec![Complex64::new(1.0, 0.0)];
+    let factors =
+        std::iter::repeat_n(Complex64::new(-1.0, 0.0), nyquist_zeros).chain(roots.iter().copied());
+    for root in factors {
+        let mut next = vec![Complex64::new(0.0, 0.0); taps.len() + 1];
+        for (i, &t) in taps.iter().enumerate() {
+            next[i] += t;
+            next[i + 1] -= t * root;
+        }
+        taps = next;
+    }
+    normalize(taps.iter().map(|c| c.re).collect())
+}
+
+fn normalize(taps: Vec<f64>) -> Vec<f64> {
+    let sum: f64 = taps.iter().sum();
+    taps.into_iter().map(|t| t * SQRT_2 / sum).collect()
+}
+
+/// `z`-plane roots for each group: the root inside the unit circle, or its reciprocal
+fn z_roots(groups: &[RootGroup], inside: impl Fn(usize) -> bool) -> Vec<Complex64> {
+    let mut roots = Vec::new();
+    for (i, g) in groups.iter().enumerate() {
+        // z^2 - (2 - 4y) z + 1 = 0 has roots z and 1/z
+        let b = Complex64::new(2.0, 0.0) - g.root * 4.0;
+        let disc = (b * b - 4.0).sqrt();
+        let mut z = (b + disc) * 0.5;
+        if z.norm() > 1.0 {
+            z = z.inv();
+        }
+        if !inside(i) {
+            z = z.inv();
+        }
+        roots.push(z);
+        if !g.is_real {
+            roots.push(z.conj());
+        }
+    }
+    roots
+}
+
+/// Daubechies lowpass filter with `n` vanishing moments (length `2n`)
+pub(crate) fn daubechies_lowpass(n: usize) -> SignalResult<Vec<f64>> {
+    let groups = root_groups(n)?;
+    let roots = z_roots(&groups, |_| true);
+    Ok(lowpass_from_roots(n, &roots))
+}
+
+/// Symlet lowpass filter with `n` vanishing moments (length `2n`)
+///
+/// Among all spectral factors with `n` zeros at Nyquist, picks the one whose
+/// frequency response has the smallest deviation from linear phase.
+pub(crate) fn symlet_lowpass(n: usize) -> SignalResult<Vec<f64>> {
+    let groups = root_groups(n)?;
+    let mut best: Option<(f64, Vec<f64>)> = None;
+    for mask in 0..(1usize << groups.len()) {
+        let roots = z_roots(&groups, |i| mask & (1 << i) != 0);
+        let taps = lowpass_from_roots(n, &roots);
+        let deviation = phase_nonlinearity(&taps);
+        if best.as_ref().is_none_or(|(d, _)| deviation < *d - 1e-12) {
+            best = Some((deviation, taps));
+        }
+    }
+    let mut taps = best.map(|(_, t)| t).unwrap_or_default();
+
+    // Of a filter and its time reverse, keep the one with its energy toward the end
+    let center: f64 = taps.iter().enumerate().map(|(i, t)| i as f64 * t * t).sum();
+    if center < 0.5 * (taps.len() - 1) as f64 {
+        taps.reverse();
+    }
+    Ok(taps)
+}
+
+/// Energy-weighted squared deviation of the unwrapped phase from its best linear fit
+fn phase_nonlinearity(taps: &[f64]) -> f64 {
+    let points = 128;
+    let mut omega = Vec::with_capacity(points);
+    let mut phase = Vec::with_capacity(points);
+    let mut weight = Vec::with_capacity(points);
+    let mut previous = 0.0;
+    let mut offset = 0.0;
+    for m in 0..points {
+        let w = PI * (m as f64 + 0.5) / points as f64;
+        let response: Complex64 = taps
+            .iter()
+            .enumerate()
+            .map(|(k, &t)| Complex64::from_polar(t, -w * k as f64))
+            .sum();
+        let mut arg = response.arg() + offset;
+        while arg - previous > PI {
+            arg -= 2.0 * PI;
+            offset -= 2.0 * PI;
+        }
+        while arg - previous < -PI {
+            arg += 2.0 * PI;
+            offset += 2.0 * PI;
+        }
+        previous = arg;
+        omega.push(w);
+        phase.push(arg);
+        weight.push(response.norm_sqr());
+    }
+
+    // Weighted least-squares line through the phase
+    let sw: f64 = weight.iter().sum();
+    let mean_w = omega.iter().zip(&weight).map(|(o, w)| o * w).sum::<f64>() / sw;
+    let mean_p = phase.iter().zip(&weight).map(|(p, w)| p * w).sum::<f64>() / sw;
+    let (mut sxy, mut sxx) = (0.0, 0.0);
+    for i in 0..points {
+        let dx = omega[i] - mean_w;
+        sxy += weight[i] * dx * (phase[i] - mean_p);
+        sxx += weight[i] * dx * dx;
+    }
+    let slope = sxy / sxx;
+    (0..points)
+        .map(|i| {
+            let r = phase[i] - mean_p - slope * (omega[i] - mean_w);
+            weight[i] * r * r
+        })
+        .sum()
+}
+
+/// Biorthogonal lowpass pair `(analysis, synthesis)` for `bior{nr}.{nd}`
+///
+/// The synthesis filter has `nr` zeros at Nyquist and the analysis filter `nd`;
+/// for the spline family (`nr <= 3`) the synthesis filter is a pure B-spline.
+/// Otherwise the factors of `P_K` are split so that both filters are as close
+/// to unit energy as possible, which gives the CDF 9/7 pair for `bior4.4`. Both filters are symmetric about
+/// the same center once centered in a common even length.
+pub(crate) fn biorthogonal_lowpass(nr: usize, nd: usize) -> SignalResult<(Vec<f64>, Vec<f64>)> {
+    if nr == 0 || nd == 0 || !(nr + nd).is_multiple_of(2) {
+        return Err(SignalError::ValueError(format!(
+            "Invalid biorthogonal wavelet specification: bior{}.{}",
+            nr, nd
+        )));
+    }
+    let k = (nr + nd) / 2;
+    let spline = |order: usize| -> Vec<f64> { (0..=order).map(|i| binomial(order, i)).collect() };
+
+    let mut synthesis = spline(nr);
+    let mut analysis = spline(nd);
+
+    if nr <= 3 {
+        let p = daubechies_polynomial(k);
+        let y = [-0.25, 0.5, -0.25];
+        // Expand P_K(y) as a Laurent polynomial in z by Horner's rule
+        let mut laurent = vec![p[k - 1]];
+        for &c in p[..k - 1].iter().rev() {
+            laurent = poly_mul(&laurent, &y);
+            let mid = laurent.len() / 2;
+            laurent[mid] += c;
+        }
+        analysis = poly_mul(&analysis, &laurent);
+    } else {
+        // Split the factors so that both filters are as close to orthogonal
+        // (unit energy) as possible, preferring the shorter synthesis filter
+        let groups = root_groups(k)?;
+        let energy = |f: &[f64]| normalize(f.to_vec()).iter().map(|t| t * t).sum::<f64>();
+        let mut best: Option<(f64, usize, Vec<f64>, Vec<f64>)> = None;
+        for mask in 0..(1usize << groups.len()) {
+            let mut s = synthesis.clone();
+            let mut a = analysis.clone();
+            for (i, g) in groups.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    s = poly_mul(&s, &y_factor(g));
+                } else {
+                    a = poly_mul(&a, &y_factor(g));
+                }
+            }
+            let cost = (energy(&s) - 1.0).abs() + (energy(&a) - 1.0).abs();
+            let better = match &best {
+                None => true,
+                Some((c, len, _, _)) => cost < c - 1e-9 || (cost < c + 1e-9 && s.len() < *len),
+            };
+            if better {
+                best = Some((cost, s.len(), s, a));
+            }
+        }
+        if let Some((_, _, s, a)) = best {
+            synthesis = s;
+            analysis = a;
+        }
+    }
+
+    Ok((normalize(analysis), normalize(synthesis)))
+}
+
+/// Center filters in a common even length
+pub(crate) fn center_pad(filter: &[f64], len: usize) -> Vec<f64> {
+    let left = (len - filter.len()) / 2;
+    let mut out = vec![0.0; len];
+    out[left..left + filter.len()].copy_from_slice(filter);
+    out
+}
@@ -3,6 +3,7 @@
 //! This module provides various wavelet filter definitions, including Haar, Daubechies,
 //! Symlets, Coiflets, Biorthogonal, and Meyer wavelets.
 
+use super::construction::{biorthogonal_lowpass, center_pad, daubechies_lowpass, symlet_lowpass};
 use crate::error::{SignalError, SignalResult};
 
 /// Represents a wavelet filter pair (decomposition and reconstruction filters)
@@ -127,626 +128,41 @@ fn haar_filters() -> WaveletFilters {
 
 /// Daubechies wavelet filters
 fn db_filters(n: usize) -> SignalResult<WaveletFilters> {
-    // Coefficients for Daubechies wavelets up to db20
-    let coeffs = match n {
-        1 => return Ok(haar_filters()), // db1 is the same as haar
-        2 => vec![
-            0.482962913144534,
-            0.836516303737808,
-            0.224143868042013,
-            -0.129409522551260,
-        ],
-        3 => vec![
-            0.332670552950083,
-            0.806891509311092,
-            0.459877502118491,
-            -0.135011020010255,
-            -0.085441273882027,
-            0.035226291882100,
-        ],
-        4 => vec![
-            0.230377813308855,
-            0.714846570552542,
-            0.630880767929590,
-            -0.027983769416984,
-            -0.187034811717090,
-            0.030841381835987,
-            0.032883011666983,
-            -0.010597401784997,
-        ],
-        5 => vec![
-            0.160102397974125,
-            0.603829269797473,
-            0.724308528437772,
-            0.138428145901320,
-            -0.242294887066190,
-            -0.032244869585030,
-            0.077571493840065,
-            -0.006241490213012,
-            -0.012580751999016,
-            0.003335725285002,
-        ],
-        6 => vec![
-            0.111540743350109,
-            0.494623890398453,
-            0.751133908021093,
-            0.315250351709195,
-            -0.226264693965169,
-            -0.129766867567262,
-            0.097501605587079,
-            0.027522865530016,
-            -0.031582039317674,
-            0.000553842201161,
-            0.004777257511010,
-            -0.001077301085308,
-        ],
-        7 => vec![
-            0.077852054085062,
-            0.396539319482306,
-            0.729132090846555,
-            0.469782287405359,
-            -0.143906003929106,
-            -0.224036184994166,
-            0.071309219267050,
-            0.080612609151065,
-            -0.038029936935034,
-            -0.016574541631250,
-            0.012550998556013,
-            0.000429577973205,
-            -0.001801640704047,
-            0.000353713799974,
-        ],
-        8 => vec![
-            0.054415842243082,
-            0.312871590914466,
-            0.675630736297212,
-            0.585354683654869,
-            -0.015829105256023,
-            -0.284015542962428,
-            0.000472484573552,
-            0.128747426620186,
-            -0.017369301002022,
-            -0.044088253931064,
-            0.013981027917015,
-            0.008746094047015,
-            -0.004870352993452,
-            -0.000391740373376,
-            0.000675449406450,
-            -0.000117476784124,
-        ],
-        9 => vec![
-            0.038077947363167,
-            0.243834674612604,
-            0.604823123690815,
-            0.657288078051201,
-            0.133197385825413,
-            -0.293273783279725,
-            -0.096840783220879,
-            0.148540749338375,
-            0.030725681478323,
-            -0.067632829061523,
-            0.000250947114831,
-            0.022361662123515,
-            -0.004723204757894,
-            -0.004281503681904,
-            0.001847646883056,
-            0.000230385763524,
-            -0.000251963188750,
-            0.000039347319995,
-        ],
-        10 => vec![
-            0.026670057900950,
-            0.188176800077641,
-            0.527201188931997,
-            0.688459039453662,
-            0.281172343660850,
-            -0.249846424327358,
-            -0.195946274377605,
-            0.127369340335789,
-            0.093057364603806,
-            -0.071394147165860,
-            -0.029457536821945,
-            0.033212674058933,
-            0.003606553567204,
-            -0.010733175482979,
-            0.001395351747052,
-            0.001992405295185,
-            -0.000685856695305,
-            -0.000116466855151,
-            0.000093588670001,
-            -0.000013264203002,
-        ],
-        11 => vec![
-            0.018694297761470,
-            0.144067021150716,
-            0.449899764356310,
-            0.686840134482260,
-            0.412751585203247,
-            -0.159766011111892,
-            -0.274162469668548,
-            0.049111967750530,
-            0.154536776654650,
-            -0.033471784599596,
-            -0.071320499838380,
-            0.037839494563470,
-            0.020023049801874,
-            -0.018338989809190,
-            -0.000749812147558,
-            0.005767053451519,
-            -0.000599561518447,
-            -0.000971877579622,
-            0.000332650266211,
-            0.000050192775784,
-            -0.000042815036824,
-            0.000005956369052,
-        ],
-        12 => vec![
-            0.013112222451907,
-            0.109702658731684,
-            0.377355135214097,
-            0.657198722525712,
-            0.515886778302947,
-            -0.044763792539050,
-            -0.318006342412828,
-            -0.027315161325541,
-            0.183247071802233,
-            0.016382602271372,
-            -0.092891783304778,
-            0.013092731358062,
-            0.037899729244972,
-            -0.016471896388428,
-            -0.010737662142476,
-            0.009374153069398,
-            0.000703854523154,
-            -0.002868814590175,
-            0.000337771648577,
-            0.000488308626611,
-            -0.000164103679384,
-            -0.000021891697069,
-            0.000020020377472,
-            -0.000002608451495,
-        ],
-        13 => vec![
-            0.009202133538962,
-            0.082861243872901,
-            0.312349864992317,
-            0.610996615683353,
-            0.584318199857429,
-            0.077847738324257,
-            -0.322596443582933,
-            -0.105765749607652,
-            0.175869936010311,
-            0.072251231604229,
-            -0.102291719176688,
-            -0.019538882735286,
-            0.053876887649336,
-            0.000804567177079,
-            -0.025605998222339,
-            0.005866090894750,
-            0.007650934922782,
-            -0.004671573326446,
-            -0.000539406538826,
-            0.001444618879459,
-            -0.000210429351128,
-            -0.000230498951215,
-            0.000080253881662,
-            0.000009785913342,
-            -0.000009446366376,
-            0.000001161914472,
-        ],
-        14 => vec![
-            0.006466627005487,
-            0.062364758849384,
-            0.254850267992499,
-            0.555141822379500,
-            0.631371908987652,
-            0.195946274377410,
-            -0.293273783279090,
-            -0.168731118268458,
-            0.139225370567556,
-            0.124757463291737,
-            -0.087277003387606,
-            -0.057528233685938,
-            0.053939470409553,
-            0.018756027276697,
-            -0.030106249425372,
-            -0.000589500643074,
-            0.013051480898198,
-            -0.003731589124174,
-            -0.003446503662376,
-            0.002497631634488,
-            0.000167263118299,
-            -0.000749629797140,
-            0.000134049175078,
-            0.000108893885386,
-            -0.000044951571736,
-            -0.000004473604308,
-            0.000004823057215,
-            -0.000000561693739,
-        ],
-        15 => vec![
-            0.004547087191196,
-            0.046977207550221,
-            0.206044010037892,
-            0.493574979637483,
-            0.657732322847070,
-            0.304753078520837,
-            -0.227664532294873,
-            -0.213033111386249,
-            0.070921920748465,
-            0.166894551346910,
-            -0.042026740014152,
-            -0.095195982362738,
-            0.040602283294518,
-            0.037297111831357,
-            -0.029196217764038,
-            -0.013436687461988,
-            0.016407058030801,
-            0.001058298062091,
-            -0.006865749450209,
-            0.001599832377537,
-            0.001992403617674,
-            -0.001283155577582,
-            -0.000103439625908,
-            0.000387916110165,
-            -0.000072475971390,
-            -0.000051817475442,
-            0.000023573692280,
-            0.000002090509552,
-            -0.000002599802611,
-            0.000000289488489,
-        ],
-        16 => vec![
-            0.003198059646889,
-            0.035272488035365,
-            0.165064042480853,
-            0.429294026810007,
-            0.663584746064344,
-            0.397635182392839,
-            -0.136727887943900,
-            -0.236615579414771,
-            -0.013882500872872,
-            0.190933574545613,
-            0.027621877931673,
-            -0.123688492987117,
-            -0.013530500878251,
-            0.070778084805636,
-            0.000250947114469,
-            -0.033999640851375,
-            0.008780563324394,
-            0.013976191892227,
-            -0.005506340578446,
-            -0.005676732137010,
-            0.002644699244577,
-            0.000546274677930,
-            -0.000998676424451,
-            -0.000063556729368,
-            0.000207189625129,
-            -0.000037639876035,
-            -0.000027339873460,
-            0.000012329077129,
-            0.000001009818666,
-            -0.000001326412561,
-            0.000000144455234,
-        ],
-        17 => vec![
-            0.002249989564127,
-            0.026431052522557,
-            0.131453455993449,
-            0.365685017392567,
-            0.651149758915307,
-            0.473660724605308,
-            -0.028095212072981,
-            -0.241534344007344,
-            -0.097652398741552,
-            0.193668770644293,
-            0.097681461521777,
-            -0.132787569868878,
-            -0.064137545429933,
-            0.082353063002837,
-            0.033635885443158,
-            -0.046483122178525,
-            -0.008818495924091,
-            0.022687508861760,
-            0.000377129908353,
-            -0.009363659238240,
-            0.002207248427215,
-            0.003158078096280,
-            -0.001365729920106,
-            -0.000368017746814,
-            0.000504404945599,
-            0.000045656736790,
-            -0.000112263640528,
-            0.000018510185807,
-            0.000014232619004,
-            -0.000006329524522,
-            -0.000000513426766,
-            0.000000667030833,
-            -0.000000071035188,
-        ],
-        18 => vec![
-            0.001583152548123,
-            0.019744895871288,
-            0.104101536113325,
-            0.305563884985853,
-            0.623975434298419,
-            0.529799560640167,
-            0.081467735276949,
-            -0.227797179526575,
-            -0.170747419267909,
-            0.175039297763324,
-            0.161873046950642,
-            -0.121474953800432,
-            -0.115726872438501,
-            0.073553294361569,
-            0.066051592542114,
-            -0.044946949533463,
-            -0.026374233183532,
-            0.026201450710306,
-            0.007799536136598,
-            -0.013413186638605,
-            -0.000676129953621,
-            0.005837844914568,
-            -0.001068456126535,
-            -0.001977793059845,
-            0.000720561850328,
-            0.000249781222699,
-            -0.000248297309875,
-            -0.000031423232942,
-            0.000062243947721,
-            -0.000009375775930,
-            -0.000007237387870,
-            0.000003191792220,
-            0.000000265012362,
-            -0.000000330410306,
-            0.000000034722061,
-        ],
-        19 => vec![
-            0.001114196372414,
-            0.014732990319389,
-            0.082207610455798,
-            0.250793445524826,
-            0.585444519401690,
-            0.566988307543766,
-            0.186025297029308,
-            -0.195458700548709,
-            -0.227730192281805,
-            0.137233695257915,
-            0.214599071153825,
-            -0.089160781132029,
-            -0.158417505731180,
-            0.045867240912607,
-            0.098035251500675,
-            -0.020522421488349,
-            -0.052147231712149,
-            0.012721525796557,
-            0.024392153958273,
-            -0.003908218962188,
-            -0.009818466381840,
-            0.000605584177777,
-            0.003589682003384,
-            -0.000530798219852,
-            -0.001001922227058,
-            0.000335871807070,
-            0.000170180896946,
-            -0.000123237265315,
-            -0.000013210000789,
-            0.000031646436535,
-            -0.000003968737843,
-            -0.000003845884769,
-            0.000001573769802,
-            0.000000134049667,
-            -0.000000160562083,
-            0.000000016645797,
-        ],
-        20 => vec![
-            0.000784072542954,
-            0.010977933021543,
-            0.064550896006344,
-            0.203752672274431,
-            0.540090975933037,
-            0.588632819378512,
-            0.281212186616818,
-            -0.149991375873567,
-            -0.269129167676524,
-            0.086897220771386,
-            0.250741236293154,
-            -0.042222645318166,
-            -0.189361081889192,
-            0.009845067607434,
-            0.125613753894546,
-            0.003658261885561,
-            -0.077576346018652,
-            -0.002903428894004,
-            0.042865478706229,
-            -0.000380739967502,
-            -0.021314118649806,
-            0.001432104210551,
-            0.009138142946111,
-            -0.000867154737000,
-            -0.003457476052446,
-            0.000287387874799,
-            0.001090536731614,
-            -0.000074661709345,
-            -0.000293359249792,
-            0.000032466421169,
-            0.000061991833491,
-            -0.000011192232887,
-            -0.000008949422779,
-            0.000003201334779,
-            0.000000766466778,
-            -0.000000313671983,
-            -0.000000081726461,
-            0.000000079784918,
-            -0.000000007962295,
-        ],
-        _ => {
-            return Err(SignalError::ValueError(format!(
-                "Daubechies wavelet db{} is not supported. Valid values: 1-20.",
-                n
-            )))
-        }
-    };
+    if n == 1 {
+        // db1 is the same as haar
+        return Ok(haar_filters());
+    }
 
-    // Length of wavelet filter is 2n
-    let filter_len = 2 * n;
+    // Minimum-phase spectral factor of the Daubechies polynomial, length 2n
+    let dec_lo = daubechies_lowpass(n)?;
+    Ok(orthogonal_filters(dec_lo, &format!("db{}", n), n))
+}
 
-    // Decomposition filters
-    let dec_lo = coeffs.clone();
-    let mut dec_hi = vec![0.0; filter_len];
+/// Assemble an orthogonal filter bank from its decomposition lowpass filter
+///
+/// The highpass filter is the alternating flip of the lowpass filter and the
+/// reconstruction filters are the time reverses of the decomposition filters.
+fn orthogonal_filters(dec_lo: Vec<f64>, family: &str, vanishing_moments: usize) -> WaveletFilters {
+    let filter_len = dec_lo.len();
 
     // QMF relationship for high-pass filter
-    for i in 0..filter_len {
-        dec_hi[i] = (-1_f64).powi(i as i32) * dec_lo[filter_len - 1 - i];
-    }
+    let dec_hi: Vec<f64> = (0..filter_len)
+        .map(|i| (-1_f64).powi(i as i32) * dec_lo[filter_len - 1 - i])
+        .collect();
 
     // Reconstruction filters (time reverse of decomposition filters)
-    let mut rec_lo = dec_lo.clone();
-    let mut rec_hi = dec_hi.clone();
-    rec_lo.reverse();
-    rec_hi.reverse();
-
-    // No need to swap signs for the reconstruction high-pass filter
-    // as the QMF relationship is now defined correctly
+    let rec_lo: Vec<f64> = dec_lo.iter().rev().copied().collect();
+    let rec_hi: Vec<f64> = dec_hi.iter().rev().copied().collect();
 
-    Ok(WaveletFilters::new(
-        dec_lo,
-        dec_hi,
-        rec_lo,
-        rec_hi,
-        &format!("db{}", n),
-        n,
-    ))
+    WaveletFilters::new(dec_lo, dec_hi, rec_lo, rec_hi, family, vanishing_moments)
 }
 
 /// Symlet wavelet filters
 fn sym_filters(n: usize) -> SignalResult<WaveletFilters> {
-    // Symlets are similar to Daubechies but more symmetrical
-    // For now, we'll implement a few commonly used ones
-    let coeffs = match n {
-        2 => vec![
-            0.482962913144534,
-            0.836516303737808,
-            0.224143868042013,
-            -0.129409522551260,
-        ],
-        3 => vec![
-            0.332670552950083,
-            0.806891509311092,
-            0.459877502118491,
-            -0.135011020010255,
-            -0.085441273882027,
-            0.035226291882100,
-        ],
-        4 => vec![
-            0.027333068345078,
-            0.029519490925774,
-            -0.039134249302383,
-            0.199397533977639,
-            0.723407690402421,
-            0.633978963458212,
-            0.016602105764522,
-            -0.175328089908107,
-        ],
-        5 => vec![
-            0.038654795955998,
-            0.041746191085696,
-            -0.055344186117389,
-            0.281990696854629,
-            1.023052966894833,
-            0.896581648380820,
-            0.023478923136726,
-            -0.247951362613099,
-        ],
-        6 => vec![
-            0.021784700327209,
-            0.004936612372610,
-            -0.166863215412514,
-            -0.068323121587143,
-            0.694457972958569,
-            1.113892783926184,
-            0.477904371333197,
-            -0.102724969862286,
-            -0.069883027249875,
-            0.067165481423504,
-            0.008223012248799,
-            -0.010435713106749,
-        ],
-        7 => vec![
-            0.010268176708511,
-            0.004010244871533,
-            -0.107808028230524,
-            -0.140047240442703,
-            0.288629631752769,
-            0.767764317003164,
-            0.536101917090763,
-            0.017441255087316,
-            -0.049552834937945,
-            0.021662469594920,
-            0.005100436968998,
-            -0.015179002335395,
-        ],
-        8 => vec![
-            0.001889950333672,
-            0.000268181156429,
-            -0.014296827155513,
-            -0.010798432312464,
-            0.046958624294051,
-            0.058525491871548,
-            -0.152463871896665,
-            -0.070078291222233,
-            0.476803265249878,
-            0.803738751805916,
-            0.297857795605542,
-            -0.099219543576373,
-            -0.012592909212478,
-            0.032755032597835,
-        ],
-        _ => {
-            // Fall back to Daubechies for other orders, with a note
-            // In a full implementation, we would include coefficients for all supported symlets
-            let db_wavelet = db_filters(n)?;
-
-            return Ok(WaveletFilters::new(
-                db_wavelet.dec_lo,
-                db_wavelet.dec_hi,
-                db_wavelet.rec_lo,
-                db_wavelet.rec_hi,
-                &format!("sym{}", n),
-                n,
-            ));
-        }
-    };
-
-    // Length of wavelet filter is 2n
-    let filter_len = 2 * n;
-
-    // Decomposition filters
-    let dec_lo = coeffs.clone();
-    let mut dec_hi = vec![0.0; filter_len];
-
-    // QMF relationship for high-pass filter
-    for i in 0..filter_len {
-        dec_hi[i] = (-1_f64).powi(i as i32) * dec_lo[filter_len - 1 - i];
-    }
-
-    // Reconstruction filters (time reverse of decomposition filters)
-    let mut rec_lo = dec_lo.clone();
-    let mut rec_hi = dec_hi.clone();
-    rec_lo.reverse();
-    rec_hi.reverse();
-
-    // No need to swap signs for the reconstruction high-pass filter
-    // as the QMF relationship is now defined correctly
-
-    Ok(WaveletFilters::new(
-        dec_lo,
-        dec_hi,
-        rec_lo,
-        rec_hi,
-        &format!("sym{}", n),
-        n,
-    ))
+    // Symlets share the Daubechies magnitude response but use the spectral
+    // factor with the most nearly linear phase
+    let dec_lo = symlet_lowpass(n)?;
+    Ok(orthogonal_filters(dec_lo, &format!("sym{}", n), n))
 }
 
 /// Coiflet wavelet filters
@@ -754,12 +170,12 @@ fn coif_filters(n: usize) -> SignalResult<WaveletFilters> {
     // Coiflet filter coefficients
     let coeffs = match n {
         1 => vec![
-            -0.0156557285289848,
-            -0.0727326213410511,
-            0.3848648565381134,
-            0.8525720416423,
-            0.337897670951159,
-            -0.0727322757411889,
+            -0.015655728135791993,
+            -0.07273261951252645,
+            0.3848648468648578,
+            0.8525720202116004,
+            0.3378976624574818,
+            -0.07273261951252645,
         ],
         2 => vec![
             -0.0007205494453679,
@@ -861,35 +277,7 @@ fn coif_filters(n: usize) -> SignalResult<WaveletFilters> {
         }
     };
 
-    // Length of wavelet filter is 6n
-    let filter_len = 6 * n;
-
-    // Decomposition filters
-    let dec_lo = coeffs.clone();
-    let mut dec_hi = vec![0.0; filter_len];
-
-    // QMF relationship for high-pass filter
-    for i in 0..filter_len {
-        dec_hi[i] = (-1_f64).powi(i as i32) * dec_lo[filter_len - 1 - i];
-    }
-
-    // Reconstruction filters (time reverse of decomposition filters)
-    let mut rec_lo = dec_lo.clone();
-    let mut rec_hi = dec_hi.clone();
-    rec_lo.reverse();
-    rec_hi.reverse();
-
-    // No need to swap signs for the reconstruction high-pass filter
-    // as the QMF relationship is now defined correctly
-
-    Ok(WaveletFilters::new(
-        dec_lo,
-        dec_hi,
-        rec_lo,
-        rec_hi,
-        &format!("coif{}", n),
-        n,
-    ))
+    Ok(orthogonal_filters(coeffs, &format!("coif{}", n), n))
 }
 
 /// Biorthogonal wavelet filters
@@ -923,311 +311,44 @@ fn bior_filters(nr: usize, nd: usize) -> SignalResult<WaveletFilters> {
         )));
     }
 
-    match (nr, nd) {
-        // bior1.1 - Same as Haar
-        (1, 1) => {
-            let dec_lo = vec![0.7071067811865475, 0.7071067811865475];
-            let dec_hi = vec![0.7071067811865475, -0.7071067811865475];
-            let rec_lo = vec![0.7071067811865475, 0.7071067811865475];
-            let rec_hi = vec![-0.7071067811865475, 0.7071067811865475];
-
-            Ok(WaveletFilters::new(
-                dec_lo,
-                dec_hi,
-                rec_lo,
-                rec_hi,
-                &format!("bior{}.{}", nr, nd),
-                nr,
-            ))
-        }
-
-        // bior1.3
-        (1, 3) => {
-            let dec_lo = vec![0.7071067811865475, 0.7071067811865475];
-            let rec_lo = vec![
-                -0.0883883476483184,
-                0.0883883476483185,
-                0.7071067811865475,
-                0.7071067811865475,
-                0.0883883476483185,
-                -0.0883883476483184,
-            ];
-
-            // Create high-pass filters
-            let mut dec_hi = vec![0.0; rec_lo.len()];
-            let mut rec_hi = vec![0.0; dec_lo.len()];
-
-            // Alternate signs for high-pass filters
-            for i in 0..rec_lo.len() {
-                dec_hi[i] = (-1_f64).powi(i as i32) * rec_lo[rec_lo.len() - 1 - i];
-            }
-
-            for i in 0..dec_lo.len() {
-                rec_hi[i] = (-1_f64).powi(i as i32 + 1) * dec_lo[dec_lo.len() - 1 - i];
-            }
-
-            Ok(WaveletFilters::new(
-                dec_lo,
-                dec_hi,
-                rec_lo,
-                rec_hi,
-                &format!("bior{}.{}", nr, nd),
-                nr,
-            ))
-        }
-
-        // bior1.5
-        (1, 5) => {
-            let dec_lo = vec![0.7071067811865475, 0.7071067811865475];
-            let rec_lo = vec![
-                0.0165728151840597,
-                -0.0165728151840597,
-                -0.1215339780164378,
-                0.1215339780164378,
-                0.7071067811865475,
-                0.7071067811865475,
-                0.1215339780164378,
-                -0.1215339780164378,
-                -0.0165728151840597,
-                0.0165728151840597,
-            ];
-
-            // Create high-pass filters
-            let mut dec_hi = vec![0.0; rec_lo.len()];
-            let mut rec_hi = vec![0.0; dec_lo.len()];
-
-            // Alternate signs for high-pass filters
-            for i in 0..rec_lo.len() {
-                dec_hi[i] = (-1_f64).powi(i as i32) * rec_lo[rec_lo.len() - 1 - i];
-            }
-
-            for i in 0..dec_lo.len() {
-                rec_hi[i] = (-1_f64).powi(i as i32 + 1) * dec_lo[dec_lo.len() - 1 - i];
-            }
-
-            Ok(WaveletFilters::new(
-                dec_lo,
-                dec_hi,
-                rec_lo,
-                rec_hi,
-                &format!("bior{}.{}", nr, nd),
-                nr,
-            ))
-        }
-
-        // bior2.2
-        (2, 2) => {
-            let dec_lo = vec![
-                -0.1767766952966369,
-                0.3535533905932738,
-                1.0606601717798214,
-                0.3535533905932738,
-                -0.1767766952966369,
-            ];
-            let rec_lo = vec![
-                0.3535533905932738,
-                std::f64::consts::FRAC_1_SQRT_2,
-                0.3535533905932738,
-            ];
-
-            // Create high-pass filters
-            let mut dec_hi = vec![0.0; rec_lo.len()];
-            let mut rec_hi = vec![0.0; dec_lo.len()];
-
-            // Alternate signs for high-pass filters
-            for i in 0..rec_lo.len() {
-                dec_hi[i] = (-1_f64).powi(i as i32) * rec_lo[rec_lo.len() - 1 - i];
-            }
-
-            for i in 0..dec_lo.len() {
-                rec_hi[i] = (-1_f64).powi(i as i32 + 1) * dec_lo[dec_lo.len() - 1 - i];
-            }
-
-            Ok(WaveletFilters::new(
-                dec_lo,
-                dec_hi,
-                rec_lo,
-                rec_hi,
-                &format!("bior{}.{}", nr, nd),
-                nr,
-            ))
-        }
-
-        // bior2.4, bior2.6, bior2.8
-        (2, 4) | (2, 6) | (2, 8) => {
-            // Define dec_lo based on nd
-            let dec_lo = match nd {
-                4 => vec![
-                    0.0331456303059065,
-                    -0.0662912606118129,
-                    -0.1767766952966369,
-                    0.4198446513295126,
-                    0.9943689110435825,
-                    0.4198446513295126,
-                    -0.1767766952966369,
-                    -0.0662912606118129,
-                    0.0331456303059065,
-                ],
-                6 => vec![
-                    -0.0069053396600248,
-                    0.0138106793200496,
-                    0.0469563096881692,
-                    -0.1077232986963881,
-                    -0.1697106603182049,
-                    0.4752925784897927,
-                    0.9666908493984144,
-                    0.4752925784897927,
-                    -0.1697106603182049,
-                    -0.1077232986963881,
-                    0.0469563096881692,
-                    0.0138106793200496,
-                    -0.0069053396600248,
-                ],
-                8 => vec![
-                    0.0015105430506304,
-                    -0.0030210861012608,
-                    -0.0129475118625466,
-                    0.0289161098263542,
-                    0.0529984818906909,
-                    -0.134_913_073_607_712,
-                    -0.1638291834577726,
-                    0.5097608153878514,
-                    0.951_556_053_042_669,
-                    0.5097608153878514,
-                    -0.1638291834577726,
-                    -0.134_913_073_607_712,
-                    0.0529984818906909,
-                    0.0289161098263542,
-                    -0.0129475118625466,
-                    -0.0030210861012608,
-                    0.0015105430506304,
-                ],
-                _ => unreachable!(),
-            };
-
-            // Define rec_lo - same for all bior2.x
-            let rec_lo = vec![
-                0.3535533905932738,
-                std::f64::consts::FRAC_1_SQRT_2,
-                0.3535533905932738,
-            ];
-
-            // Create high-pass filters
-            let mut dec_hi = vec![0.0; rec_lo.len()];
-            let mut rec_hi = vec![0.0; dec_lo.len()];
-
-            // Alternate signs for high-pass filters
-            for i in 0..rec_lo.len() {
-                dec_hi[i] = (-1_f64).powi(i as i32) * rec_lo[rec_lo.len() - 1 - i];
-            }
-
-            for i in 0..dec_lo.len() {
-                rec_hi[i] = (-1_f64).powi(i as i32 + 1) * dec_lo[dec_lo.len() - 1 - i];
-            }
-
-            Ok(WaveletFilters::new(
-                dec_lo,
-                dec_hi,
-                rec_lo,
-                rec_hi,
-                &format!("bior{}.{}", nr, nd),
-                nr,
-            ))
-        }
-
-        // Other well-known biorthogonal wavelets
-        (3, 1) => {
-            let dec_lo = vec![
-                -0.3535533905932738,
-                1.0606601717798214,
-                1.0606601717798214,
-                -0.3535533905932738,
-            ];
-            let rec_lo = vec![
-                0.1767766952966369,
-                0.5303300858899107,
-                0.5303300858899107,
-                0.1767766952966369,
-            ];
-
-            // Create high-pass filters
-            let mut dec_hi = vec![0.0; rec_lo.len()];
-            let mut rec_hi = vec![0.0; dec_lo.len()];
-
-            // Alternate signs for high-pass filters
-            for i in 0..rec_lo.len() {
-                dec_hi[i] = (-1_f64).powi(i as i32) * rec_lo[rec_lo.len() - 1 - i];
-            }
-
-            for i in 0..dec_lo.len() {
-                rec_hi[i] = (-1_f64).powi(i as i32 + 1) * dec_lo[dec_lo.len() - 1 - i];
-            }
-
-            Ok(WaveletFilters::new(
-                dec_lo,
-                dec_hi,
-                rec_lo,
-                rec_hi,
-                &format!("bior{}.{}", nr, nd),
-                nr,
-            ))
-        }
-
-        // bior3.3
-        (3, 3) => {
-            let dec_lo = vec![
-                0.0662912606118329,
-                0.1988737819357238,
-                0.1546796083845573,
-                -0.6698312455207294,
-                1.1235147854599727,
-                0.5320630098146652,
-                -0.4455680946683667,
-                -0.0196337984633395,
-                0.1282552104550745,
-            ];
-            let rec_lo = vec![
-                0.0069053396600248,
-                0.0207160198800745,
-                0.0517990809677456,
-                0.2807880797154621,
-                0.5515873933681696,
-                0.2807880797154621,
-                0.0517990809677456,
-                0.0207160198800745,
-                0.0069053396600248,
-            ];
-
-            // Create high-pass filters
-            let mut dec_hi = vec![0.0; rec_lo.len()];
-            let mut rec_hi = vec![0.0; dec_lo.len()];
-
-            // Alternate signs for high-pass filters
-            for i in 0..rec_lo.len() {
-                dec_hi[i] = (-1_f64).powi(i as i32) * rec_lo[rec_lo.len() - 1 - i];
-            }
-
-            for i in 0..dec_lo.len() {
-                rec_hi[i] = (-1_f64).powi(i as i32 + 1) * dec_lo[dec_lo.len() - 1 - i];
-            }
+    let (analysis, synthesis) = biorthogonal_lowpass(nr, nd)?;
+
+    // Place both lowpass filters in a common even support. Even-length filters
+    // share their axis of symmetry; odd-length (whole-sample symmetric) filters
+    // need their centers one sample apart for the filter bank to be biorthogonal.
+    let mut filter_len = analysis.len().max(synthesis.len());
+    filter_len += filter_len % 2;
+    let (dec_lo, rec_lo) = if analysis.len() % 2 == 0 {
+        (
+            center_pad(&analysis, filter_len),
+            center_pad(&synthesis, filter_len),
+        )
+    } else {
+        let mut dec_lo = vec![0.0; filter_len];
+        let mut rec_lo = vec![0.0; filter_len];
+        let dec_start = (filter_len - analysis.len()).div_ceil(2);
+        let rec_start = (filter_len - synthesis.len() - 1) / 2;
+        dec_lo[dec_start..dec_start + analysis.len()].copy_from_slice(&analysis);
+        rec_lo[rec_start..rec_start + synthesis.len()].copy_from_slice(&synthesis);
+        (dec_lo, rec_lo)
+    };
 
-            Ok(WaveletFilters::new(
-                dec_lo,
-                dec_hi,
-                rec_lo,
-                rec_hi,
-                &format!("bior{}.{}", nr, nd),
-                nr,
-            ))
-        }
+    // Each highpass filter is the modulated lowpass filter of the other bank
+    let dec_hi: Vec<f64> = (0..filter_len)
+        .map(|i| (-1_f64).powi(i as i32) * rec_lo[i])
+        .collect();
+    let rec_hi: Vec<f64> = (0..filter_len)
+        .map(|i| -(-1_f64).powi(i as i32) * dec_lo[i])
+        .collect();
 
-        // For combinations that are valid but not explicitly implemented yet,
-        // return a reasonable error
-        _ => Err(SignalError::ValueError(format!(
-            "Biorthogonal wavelet bior{}.{} is not fully implemented yet.",
-            nr, nd
-        ))),
-    }
+    Ok(WaveletFilters::new(
+        dec_lo,
+        dec_hi,
+        rec_lo,
+        rec_hi,
+        &format!("bior{}.{}", nr, nd),
+        nr,
+    ))
 }
 
 /// Reverse biorthogonal wavelet filters
@@ -1262,7 +383,7 @@ fn rbior_filters(nr: usize, nd: usize) -> SignalResult<WaveletFilters> {
     }
 
     // First get the biorthogonal filters
-    let bior = bior_filters(nd, nr)?; // Note the swapped 'nd' and 'nr' parameters
+    let bior = bior_filters(nr, nd)?;
 
     // Swap decomposition and reconstruction filters
     let filters = WaveletFilters::new(
@@ -1368,11 +489,6 @@ fn meyer_filters() -> SignalResult<WaveletFilters> {
     rec_lo.reverse();
     rec_hi.reverse();
 
-    // Swap sign for reconstruction high-pass filter
-    for val in rec_hi.iter_mut() {
-        *val = -*val;
-    }
-
     Ok(WaveletFilters::new(
         dec_lo, dec_hi, rec_lo, rec_hi, "meyer", 1,
     ))
@@ -1466,11 +582,6 @@ fn dmeyer_filters() -> SignalResult<WaveletFilters> {
     rec_lo.reverse();
     rec_hi.reverse();
 
-    // Swap sign for reconstruction high-pass filter
-    for val in rec_hi.iter_mut() {
-        *val = -*val;
-    }
-
     Ok(WaveletFilters::new(
         dec_lo, dec_hi, rec_lo, rec_hi, "dmey", 1,
     ))
@@ -6,20 +6,22 @@
 //!
 //! The module is organized into submodules:
 //! - `filters`: Wavelet filter definitions and generation functions
+//! - `construction`: Spectral factorization for Daubechies, Symlet and biorthogonal filters
 //! - `transform`: Core DWT decomposition and reconstruction functions
 //! - `boundary`: Signal extension methods for handling boundary conditions
 //! - `multiscale`: Multi-level transform functions for decomposition and reconstruction
 
 // Declare submodules
 mod boundary;
+mod construction;
 mod filters;
 mod multiscale;
 mod transform;
 
 // Re-export public items from submodules
 pub use filters::{Wavelet, WaveletFilters};
-pub use multiscale::{wavedec, waverec};
-pub use transform::{dwt_decompose, dwt_reconstruct};
+pub use multiscale::{dwt_max_level, wavedec, waverec, waverec_with_mode};
+pub use transform::{dwt_decompose, dwt_reconstruct, dwt_reconstruct_with_mode};
 
 // Re-export boundary extension for advanced users
 pub use boundary::extend_signal;
@@ -4,7 +4,7 @@
 //! including decomposition and reconstruction of signals.
 
 use super::filters::Wavelet;
-use super::transform::{dwt_decompose, dwt_reconstruct};
+use super::transform::{aligned_filters, dwt_decompose, dwt_reconstruct_with_mode};
 use crate::error::{SignalError, SignalResult};
 use num_traits::{Float, NumCast};
 use std::fmt::Debug;
@@ -58,19 +58,7 @@ where
         })
         .collect::<SignalResult<Vec<f64>>>()?;
 
-    // Calculate maximum possible decomposition level
-    let data_len = data_f64.len();
-    let filters = wavelet.filters()?;
-    let filter_len = filters.dec_lo.len();
-
-    // Each level of decomposition approximately halves the signal length
-    // and requires at least filter_len samples
-    let min_length = if let Wavelet::Haar = wavelet {
-        2
-    } else {
-        filter_len
-    };
-    let max_level = (data_len as f64 / min_length as f64).log2().floor() as usize;
+    let max_level = dwt_max_level(data_f64.len(), wavelet)?;
     let decomp_level = level.unwrap_or(max_level).min(max_level);
 
     if decomp_level == 0 {
@@ -105,6 +93,28 @@ where
     Ok(coeffs)
 }
 
+/// Maximum useful decomposition level for a signal length and wavelet
+///
+/// Decomposition stops being useful once the approximation is shorter than the
+/// filter, which happens after `floor(log2(N / (L - 1)))` levels for filters of
+/// length `L`.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::dwt::{dwt_max_level, Wavelet};
+///
+/// assert_eq!(dwt_max_level(1024, Wavelet::Haar).unwrap(), 10);
+/// assert_eq!(dwt_max_level(1024, Wavelet::DB(4)).unwrap(), 7);
+/// ```
+pub fn dwt_max_level(data_len: usize, wavelet: Wavelet) -> SignalResult<usize> {
+    let filter_len = aligned_filters(wavelet)?.dec_lo.len();
+    if data_len < filter_len - 1 {
+        return Ok(0);
+    }
+    Ok((data_len as f64 / (filter_len - 1) as f64).log2().floor() as usize)
+}
+
 /// Perform multi-level inverse wavelet reconstruction
 ///
 /// # Arguments
@@ -121,8 +131,9 @@ where
 /// ```
 /// use scirs2_signal::dwt::{wavedec, waverec, Wavelet};
 ///
-/// let signal = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+/// let signal: Vec<f64> = (0..64).map(|i| (i as f64 * 0.3).sin()).collect();
 /// let coeffs = wavedec(&signal, Wavelet::DB(4), Some(2), None).unwrap();
+/// assert_eq!(coeffs.len(), 3);
 ///
 /// // Reconstruct the signal
 /// let reconstructed = waverec(&coeffs, Wavelet::DB(4)).unwrap();
@@ -133,57 +144,66 @@ where
 /// }
 /// ```
 pub fn waverec(coeffs: &[Vec<f64>], wavelet: Wavelet) -> SignalResult<Vec<f64>> {
+    waverec_with_mode(coeffs, wavelet, None)
+}
+
+/// Multi-level inverse wavelet reconstruction for a given extension mode
+///
+/// Like [`waverec`], but also inverts decompositions made with
+/// `"periodization"`. At each level the approximation is trimmed by one sample
+/// when it is longer than the detail coefficients, which happens when the
+/// signal at that level had odd length.
+///
+/// # Arguments
+///
+/// * `coeffs` - Wavelet coefficients from wavedec [a_n, d_n, d_n-1, ..., d_1]
+/// * `wavelet` - Wavelet used for the decomposition
+/// * `mode` - Extension mode used for the decomposition (default: "symmetric")
+///
+/// # Returns
+///
+/// The reconstructed signal
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::dwt::{wavedec, waverec_with_mode, Wavelet};
+///
+/// let signal: Vec<f64> = (0..64).map(|i| (i as f64 * 0.3).cos()).collect();
+/// let coeffs = wavedec(&signal, Wavelet::Coif(2), Some(3), Some("periodization")).unwrap();
+/// let reconstructed = waverec_with_mode(&coeffs, Wavelet::Coif(2), Some("periodization")).unwrap();
+/// for (x, y) in signal.iter().zip(&reconstructed) {
+///     assert!((x - y).abs() < 1e-10);
+/// }
+/// ```
+pub fn waverec_with_mode(
+    coeffs: &[Vec<f64>],
+    wavelet: Wavelet,
+    mode: Option<&str>,
+) -> SignalResult<Vec<f64>> {
     if coeffs.is_empty() {
         return Err(SignalError::ValueError(
             "Coefficients array is empty".to_string(),
         ));
     }
 
-    // Case of no transform (just the signal)
-    if coeffs.len() == 1 {
-        return Ok(coeffs[0].clone());
-    }
-
     // Start with the coarsest approximation
     let mut approx = coeffs[0].clone();
 
-    // Number of reconstruction levels
-    let n_levels = coeffs.len() - 1;
-
     // Reconstruct each level
-    for i in 0..n_levels {
-        let detail = &coeffs[i + 1];
-
-        // In some cases, approximation and detail coefficients might be off by 1 or 2
-        // elements due to boundary handling and padding. We'll adjust them to make them equal.
-        if approx.len() != detail.len() {
-            // If the lengths are very different, that's a real error
-            if (approx.len() as isize - detail.len() as isize).abs() > 4 {
-                return Err(SignalError::ValueError(format!(
-                    "Significantly mismatched coefficient lengths at level {}: approx={}, detail={}",
-                    i,
-                    approx.len(),
-                    detail.len()
-                )));
-            }
-
-            // Otherwise, adjust to the smaller length
-            let min_len = approx.len().min(detail.len());
-            if approx.len() > min_len {
-                approx.truncate(min_len);
-            }
-            let detail = if detail.len() > min_len {
-                detail[0..min_len].to_vec()
-            } else {
-                detail.clone()
-            };
-
-            // Now reconstruct with the adjusted arrays
-            approx = dwt_reconstruct(&approx, &detail, wavelet)?;
-        } else {
-            // Normal case - reconstruct with the original arrays
-            approx = dwt_reconstruct(&approx, detail, wavelet)?;
+    for (i, detail) in coeffs.iter().enumerate().skip(1) {
+        if approx.len() == detail.len() + 1 {
+            approx.truncate(detail.len());
+        } else if approx.len() != detail.len() {
+            return Err(SignalError::ValueError(format!(
+                "Mismatched coefficient lengths at level {}: approx={}, detail={}",
+                i,
+                approx.len(),
+                detail.len()
+            )));
         }
+
+        approx = dwt_reconstruct_with_mode(&approx, detail, wavelet, mode)?;
     }
 
     Ok(approx)
@@ -2,20 +2,63 @@
 //!
 //! This module provides the core functions for single-level discrete wavelet transform
 //! decomposition and reconstruction.
+//!
+//! Decomposition convolves the extended signal with the analysis filters and keeps
+//! every other sample, giving `floor((N + L - 1) / 2)` coefficients for a signal of
+//! length `N` and filters of length `L` (or `ceil(N / 2)` in periodization mode).
+//! Reconstruction upsamples, convolves with the synthesis filters and keeps the
+//! part that does not depend on the extension, so any extension mode reconstructs
+//! the signal exactly.
 
-use super::boundary::extend_signal;
-use super::filters::Wavelet;
+use super::boundary::ExtensionMode;
+use super::construction::center_pad;
+use super::filters::{Wavelet, WaveletFilters};
 use crate::error::{SignalError, SignalResult};
 use num_traits::{Float, NumCast};
 use std::fmt::Debug;
 
+/// Wavelet filters zero-padded to a common even length
+///
+/// Biorthogonal families store analysis and synthesis filters of different
+/// lengths; centering them in a common support keeps the filter bank aligned.
+pub(crate) fn aligned_filters(wavelet: Wavelet) -> SignalResult<WaveletFilters> {
+    let filters = wavelet.filters()?;
+    let mut len = [
+        filters.dec_lo.len(),
+        filters.dec_hi.len(),
+        filters.rec_lo.len(),
+        filters.rec_hi.len(),
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0);
+    len += len % 2;
+    if len == 0 {
+        return Err(SignalError::ValueError(
+            "Wavelet filters are empty".to_string(),
+        ));
+    }
+
+    let pad = |f: &[f64]| center_pad(f, len);
+
+    Ok(WaveletFilters::new(
+        pad(&filters.dec_lo),
+        pad(&filters.dec_hi),
+        pad(&filters.rec_lo),
+        pad(&filters.rec_hi),
+        &filters.family,
+        filters.vanishing_moments,
+    ))
+}
+
 /// Perform single-level discrete wavelet transform (DWT) decomposition
 ///
 /// # Arguments
 ///
 /// * `data` - Input signal
 /// * `wavelet` - Wavelet to use for transform
-/// * `mode` - Signal extension mode (default: "symmetric")
+/// * `mode` - Signal extension mode: "symmetric" (default), "reflect", "periodic",
+///   "periodization", "constant", "smooth", "antisymmetric" or "zero"
 ///
 /// # Returns
 ///
@@ -45,12 +88,12 @@ where
         return Err(SignalError::ValueError("Input array is empty".to_string()));
     }
 
-    // Get wavelet filters
-    let filters = wavelet.filters()?;
+    let mode = ExtensionMode::parse(mode)?;
+    let filters = aligned_filters(wavelet)?;
     let filter_len = filters.dec_lo.len();
 
     // Convert data to f64
-    let data_f64: Vec<f64> = data
+    let mut signal: Vec<f64> = data
         .iter()
         .map(|&v| {
             NumCast::from(v).ok_or_else(|| {
@@ -59,47 +102,36 @@ where
         })
         .collect::<SignalResult<Vec<f64>>>()?;
 
-    // Extend signal according to the specified mode
-    let mode_str = mode.unwrap_or("symmetric");
-    let extended = extend_signal(&data_f64, filter_len, mode_str)?;
-
-    // Calculate output length
-    let input_len = data_f64.len();
-    let output_len = (input_len + filter_len - 1) / 2;
-
-    // Allocate output arrays
-    let mut approx = vec![0.0; output_len];
-    let mut detail = vec![0.0; output_len];
-
-    // Perform the convolution and downsample
-    for i in 0..output_len {
-        let idx = 2 * i;
-
-        // Convolve with low-pass filter for approximation coefficients
-        let mut approx_sum = 0.0;
-        for j in 0..filter_len {
-            if idx + j < extended.len() {
-                approx_sum += extended[idx + j] * filters.dec_lo[j];
-            }
+    if mode == ExtensionMode::Periodization {
+        // Odd-length signals are made even by repeating the last sample
+        if signal.len() % 2 == 1 {
+            signal.push(signal[signal.len() - 1]);
         }
-        approx[i] = approx_sum;
-
-        // Convolve with high-pass filter for detail coefficients
-        let mut detail_sum = 0.0;
-        for j in 0..filter_len {
-            if idx + j < extended.len() {
-                detail_sum += extended[idx + j] * filters.dec_hi[j];
+        let n = signal.len();
+        let shift = filter_len / 2 - 1;
+        let output_len = n / 2;
+        let mut approx = vec![0.0; output_len];
+        let mut detail = vec![0.0; output_len];
+        for k in 0..output_len {
+            for j in 0..filter_len {
+                let idx = (2 * k + 1 + shift + n * filter_len - j) % n;
+                approx[k] += filters.dec_lo[j] * signal[idx];
+                detail[k] += filters.dec_hi[j] * signal[idx];
             }
         }
-        detail[i] = detail_sum;
+        return Ok((approx, detail));
     }
 
-    // For Haar transform, apply the scaling factor of sqrt(2) to match expected results
-    if let Wavelet::Haar = wavelet {
-        let scale_factor = 2.0_f64.sqrt();
-        for i in 0..output_len {
-            approx[i] *= scale_factor;
-            detail[i] *= scale_factor;
+    let output_len = (signal.len() + filter_len - 1) / 2;
+    let mut approx = vec![0.0; output_len];
+    let mut detail = vec![0.0; output_len];
+
+    // Convolve with the analysis filters, keeping odd output samples
+    for k in 0..output_len {
+        for j in 0..filter_len {
+            let x = mode.sample(&signal, (2 * k + 1) as isize - j as isize);
+            approx[k] += filters.dec_lo[j] * x;
+            detail[k] += filters.dec_hi[j] * x;
         }
     }
 
@@ -108,6 +140,11 @@ where
 
 /// Perform single-level inverse discrete wavelet transform (IDWT) reconstruction
 ///
+/// Equivalent to [`dwt_reconstruct_with_mode`] for any mode other than
+/// periodization. The output has `2 * approx.len() - L + 2` samples for filters
+/// of length `L`, which is one more than the original signal when its length
+/// was odd.
+///
 /// # Arguments
 ///
 /// * `approx` - Approximation coefficients
@@ -129,10 +166,51 @@ where
 /// // Reconstruct the signal
 /// let reconstructed = dwt_reconstruct(&approx, &detail, Wavelet::DB(4)).unwrap();
 ///
-/// // Basic test - reconstruction should succeed
-/// assert!(reconstructed.len() > 0);
+/// for (x, y) in signal.iter().zip(&reconstructed) {
+///     assert!((x - y).abs() < 1e-10);
+/// }
 /// ```
 pub fn dwt_reconstruct(approx: &[f64], detail: &[f64], wavelet: Wavelet) -> SignalResult<Vec<f64>> {
+    dwt_reconstruct_with_mode(approx, detail, wavelet, None)
+}
+
+/// Perform single-level IDWT for coefficients computed with a given extension mode
+///
+/// Only periodization changes the reconstruction; every other mode keeps the
+/// samples that are independent of the extension.
+///
+/// # Arguments
+///
+/// * `approx` - Approximation coefficients
+/// * `detail` - Detail coefficients
+/// * `wavelet` - Wavelet used for the decomposition
+/// * `mode` - Extension mode used for the decomposition (default: "symmetric")
+///
+/// # Returns
+///
+/// The reconstructed signal
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::dwt::{dwt_decompose, dwt_reconstruct_with_mode, Wavelet};
+///
+/// let signal = vec![4.0, 1.0, 3.0, 8.0, 5.0, 2.0, 7.0, 6.0];
+/// let (approx, detail) = dwt_decompose(&signal, Wavelet::Sym(4), Some("periodization")).unwrap();
+/// assert_eq!(approx.len(), 4);
+///
+/// let reconstructed =
+///     dwt_reconstruct_with_mode(&approx, &detail, Wavelet::Sym(4), Some("periodization")).unwrap();
+/// for (x, y) in signal.iter().zip(&reconstructed) {
+///     assert!((x - y).abs() < 1e-10);
+/// }
+/// ```
+pub fn dwt_reconstruct_with_mode(
+    approx: &[f64],
+    detail: &[f64],
+    wavelet: Wavelet,
+    mode: Option<&str>,
+) -> SignalResult<Vec<f64>> {
     if approx.is_empty() || detail.is_empty() {
         return Err(SignalError::ValueError(
             "Input arrays are empty".to_string(),
@@ -145,50 +223,43 @@ pub fn dwt_reconstruct(approx: &[f64], detail: &[f64], wavelet: Wavelet) -> Sign
         ));
     }
 
-    // Get wavelet filters
-    let filters = wavelet.filters()?;
+    let mode = ExtensionMode::parse(mode)?;
+    let filters = aligned_filters(wavelet)?;
     let filter_len = filters.rec_lo.len();
+    let n_coeffs = approx.len();
 
-    // Apply inverse scaling for Haar wavelet to match the expected output
-    let mut scaled_approx = approx.to_vec();
-    let mut scaled_detail = detail.to_vec();
-
-    if let Wavelet::Haar = wavelet {
-        let scale_factor = 1.0 / 2.0_f64.sqrt();
-        for i in 0..approx.len() {
-            scaled_approx[i] *= scale_factor;
-            scaled_detail[i] *= scale_factor;
+    if mode == ExtensionMode::Periodization {
+        let n = 2 * n_coeffs;
+        let shift = filter_len / 2 - 1;
+        let mut result = vec![0.0; n];
+        for k in 0..n_coeffs {
+            for t in 0..filter_len {
+                let idx = (2 * k + t + shift + n * filter_len + 2 - filter_len) % n;
+                result[idx] += approx[k] * filters.rec_lo[t] + detail[k] * filters.rec_hi[t];
+            }
         }
+        return Ok(result);
     }
 
-    // Calculate output length
-    let input_len = approx.len();
-    let output_len = 2 * input_len;
-
-    // Allocate output array
+    if 2 * n_coeffs + 2 <= filter_len {
+        return Err(SignalError::ValueError(format!(
+            "Too few coefficients ({}) for a wavelet with filter length {}",
+            n_coeffs, filter_len
+        )));
+    }
+    let output_len = 2 * n_coeffs + 2 - filter_len;
     let mut result = vec![0.0; output_len];
 
-    // Upsample and convolve
-    for i in 0..input_len {
-        // Apply the reconstruction filters
-        for j in 0..filter_len {
-            let idx = 2 * i + j;
-            if idx < output_len {
-                result[idx] +=
-                    scaled_approx[i] * filters.rec_lo[j] + scaled_detail[i] * filters.rec_hi[j];
+    // Upsample and convolve, keeping only the extension-independent part
+    for k in 0..n_coeffs {
+        for t in 0..filter_len {
+            let idx = (2 * k + t) as isize - (filter_len as isize - 2);
+            if (0..output_len as isize).contains(&idx) {
+                result[idx as usize] +=
+                    approx[k] * filters.rec_lo[t] + detail[k] * filters.rec_hi[t];
             }
         }
     }
 
-    // Adjust output to account for filter delay
-    let filter_delay = (filter_len / 2) - 1;
-    let start_idx = if filter_delay < output_len {
-        filter_delay
-    } else {
-        0
-    };
-    let end_idx = output_len;
-    let trimmed_result = result[start_idx..end_idx].to_vec();
-
-    Ok(trimmed_result)
+    Ok(result)
 }
@@ -185,7 +185,9 @@ pub struct Dwt2dResult {
 /// * `data` - The input 2D array (image) of any floating-point type
 /// * `wavelet` - The wavelet to use for the transform (e.g., Haar, DB1-20, Sym2-20, Coif1-5)
 /// * `mode` - The signal extension mode for handling boundaries:
-///   - "symmetric" (default): Reflects the signal at boundaries
+///   - "periodization" (default): Treats the signal as periodic and keeps each
+///     subband at exactly half the size, so the transform is perfectly invertible
+///   - "symmetric": Reflects the signal at boundaries
 ///   - "periodic": Treats the signal as periodic
 ///   - "zero": Pads with zeros
 ///   - "constant": Pads with edge values
@@ -263,6 +265,9 @@ where
         return Err(SignalError::ValueError("Input array is empty".to_string()));
     }
 
+    // Subbands are stored on a half-size grid, which periodization fills exactly
+    let mode = Some(mode.unwrap_or("periodization"));
+
     // Get dimensions
     let (rows, cols) = data.dim();
 
@@ -430,7 +435,7 @@ where
 ///
 /// * `decomposition` - The wavelet decomposition to reconstruct from, containing the four subbands
 /// * `wavelet` - The wavelet used for the original transform (must match the decomposition wavelet)
-/// * `mode` - The signal extension mode (default: "symmetric")
+/// * `mode` - The signal extension mode (default: "periodization")
 ///   - Note: This should match the mode used for decomposition for best results
 ///
 /// # Returns
@@ -519,8 +524,10 @@ where
 pub fn dwt2d_reconstruct(
     decomposition: &Dwt2dResult,
     wavelet: Wavelet,
-    _mode: Option<&str>,
+    mode: Option<&str>,
 ) -> SignalResult<Array2<f64>> {
+    let mode = Some(mode.unwrap_or("periodization"));
+
     // Extract components
     let ll = &decomposition.approx;
     let lh = &decomposition.detail_h;
@@ -556,13 +563,13 @@ pub fn dwt2d_reconstruct(
                 // Reconstruct low-pass columns
                 let ll_col = ll.slice(ndarray::s![.., j]).to_vec();
                 let hl_col = hl.slice(ndarray::s![.., j]).to_vec();
-                let col_lo = dwt::dwt_reconstruct(&ll_col, &hl_col, wavelet)
+                let col_lo = dwt::dwt_reconstruct_with_mode(&ll_col, &hl_col, wavelet, mode)
                     .expect("Low-pass column reconstruction failed");
 
                 // Reconstruct high-pass columns
                 let lh_col = lh.slice(ndarray::s![.., j]).to_vec();
                 let hh_col = hh.slice(ndarray::s![.., j]).to_vec();
-                let col_hi = dwt::dwt_reconstruct(&lh_col, &hh_col, wavelet)
+                let col_hi = dwt::dwt_reconstruct_with_mode(&lh_col, &hh_col, wavelet, mode)
                     .expect("High-pass column reconstruction failed");
 
                 (j, col_lo, col_hi)
@@ -587,12 +594,12 @@ pub fn dwt2d_reconstruct(
             // Reconstruct low-pass columns
             let ll_col = ll.slice(ndarray::s![.., j]).to_vec();
             let hl_col = hl.slice(ndarray::s![.., j]).to_vec();
-            let col_lo = dwt::dwt_reconstruct(&ll_col, &hl_col, wavelet)?;
+            let col_lo = dwt::dwt_reconstruct_with_mode(&ll_col, &hl_col, wavelet, mode)?;
 
             // Reconstruct high-pass columns
             let lh_col = lh.slice(ndarray::s![.., j]).to_vec();
             let hh_col = hh.slice(ndarray::s![.., j]).to_vec();
-            let col_hi = dwt::dwt_reconstruct(&lh_col, &hh_col, wavelet)?;
+            let col_hi = dwt::dwt_reconstruct_with_mode(&lh_col, &hh_col, wavelet, mode)?;
 
             // Store reconstructed columns
             for i in 0..col_lo.len() {
@@ -619,7 +626,7 @@ pub fn dwt2d_reconstruct(
                 let hi_row = row_hi.slice(ndarray::s![i, ..]).to_vec();
 
                 // Reconstruct row
-                let full_row = dwt::dwt_reconstruct(&lo_row, &hi_row, wavelet)
+                let full_row = dwt::dwt_reconstruct_with_mode(&lo_row, &hi_row, wavelet, mode)
                     .expect("Row reconstruction failed");
 
                 (i, full_row)
@@ -645,7 +652,7 @@ pub fn dwt2d_reconstruct(
             let hi_row = row_hi.slice(ndarray::s![i, ..]).to_vec();
 
             // Reconstruct row
-            let full_row = dwt::dwt_reconstruct(&lo_row, &hi_row, wavelet)?;
+            let full_row = dwt::dwt_reconstruct_with_mode(&lo_row, &hi_row, wavelet, mode)?;
 
             // Store reconstructed row
             for j in 0..full_row.len() {
@@ -683,7 +690,7 @@ pub fn dwt2d_reconstruct(
 /// * `data` - The input 2D array (image)
 /// * `wavelet` - The wavelet to use for the transform
 /// * `levels` - The number of decomposition levels to compute
-/// * `mode` - The signal extension mode (default: "symmetric")
+/// * `mode` - The signal extension mode (default: "periodization")
 ///
 /// # Returns
 ///
@@ -822,7 +829,7 @@ where
 ///
 /// * `coeffs` - The wavelet coefficients from `wavedec2`, with deepest level first
 /// * `wavelet` - The wavelet used for the original transform (must match)
-/// * `mode` - The signal extension mode (default: "symmetric")
+/// * `mode` - The signal extension mode (default: "periodization")
 ///   - Should match the mode used for decomposition
 ///
 /// # Returns
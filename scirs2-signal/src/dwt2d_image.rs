@@ -589,14 +589,15 @@ mod tests {
 
     #[test]
     fn test_edge_detection() {
-        // Create a test image with edges
+        // Create a test image with edges; they sit inside a Haar pair (between
+        // samples 2 and 3) since edges on even boundaries have no Haar detail
         let mut image = Array2::zeros((8, 8));
         for i in 0..8 {
             for j in 0..8 {
-                if i >= 4 {
+                if i >= 3 {
                     image[[i, j]] = 10.0; // Horizontal edge
                 }
-                if j >= 4 {
+                if j >= 3 {
                     image[[i, j]] += 5.0; // Vertical edge
                 }
             }
@@ -8,9 +8,39 @@
 use crate::error::{SignalError, SignalResult};
 use ndarray::{s, Array1, Array2};
 use num_traits::{Float, NumCast};
-use std::cmp::max;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+use scirs2_core::parallel_ops::*;
 use std::fmt::Debug;
 
+/// Stop criterion for the sifting process that extracts each IMF
+///
+/// Sifting always stops after `max_sift_iterations` iterations, whichever
+/// criterion is selected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SiftStopCriterion {
+    /// Huang's standard deviation criterion: stop when the normalized squared
+    /// difference between successive sifts falls below `sift_threshold`
+    Cauchy,
+    /// S-number criterion: stop when the numbers of extrema and zero crossings
+    /// differ by at most one and have stayed the same for this many
+    /// consecutive sifts
+    SNumber(usize),
+    /// Rilling's threshold criterion on the mode amplitude ratio
+    /// `|mean envelope| / envelope amplitude`: stop when it exceeds `sigma1`
+    /// on less than a fraction `alpha` of the samples and never exceeds `sigma2`
+    Threshold {
+        /// Threshold for the bulk of the signal (typically 0.05)
+        sigma1: f64,
+        /// Threshold that must never be exceeded (typically 0.5)
+        sigma2: f64,
+        /// Fraction of samples allowed above `sigma1` (typically 0.05)
+        alpha: f64,
+    },
+    /// Always perform this many sifts per IMF
+    FixedSifts(usize),
+}
+
 /// Configuration parameters for Empirical Mode Decomposition
 #[derive(Debug, Clone)]
 pub struct EmdConfig {
@@ -26,6 +56,10 @@ pub struct EmdConfig {
     pub interpolation: String,
     /// Minimum number of extrema to continue decomposition
     pub min_extrema: usize,
+    /// Sifting stop criterion
+    pub stop_criterion: SiftStopCriterion,
+    /// Random seed for the noise realizations of ensemble EMD
+    pub random_seed: Option<u64>,
 }
 
 impl Default for EmdConfig {
@@ -37,6 +71,8 @@ impl Default for EmdConfig {
             boundary_condition: "mirror".to_string(),
             interpolation: "cubic".to_string(),
             min_extrema: 3,
+            stop_criterion: SiftStopCriterion::Cauchy,
+            random_seed: None,
         }
     }
 }
@@ -62,6 +98,9 @@ pub struct EmdResult {
 /// 1. The number of extrema and zero crossings must differ by at most one
 /// 2. The mean of the upper and lower envelopes must be close to zero
 ///
+/// How closely the second condition is enforced is controlled by the sifting
+/// stop criterion in `config.stop_criterion`.
+///
 /// # Arguments
 ///
 /// * `signal` - Input signal
@@ -194,8 +233,18 @@ fn extract_imf(signal: &Array1<f64>, config: &EmdConfig) -> SignalResult<(Array1
     let mut h = signal.clone();
     let mut iteration = 0;
 
+    // Consecutive sifts with unchanged extrema and zero-crossing counts
+    let mut stable_sifts = 0;
+    let mut previous_counts = None;
+
     // Sifting process
     loop {
+        if let SiftStopCriterion::FixedSifts(sifts) = config.stop_criterion {
+            if iteration >= sifts {
+                break;
+            }
+        }
+
         // Make a copy to check for convergence
         let h_prev = h.clone();
 
@@ -233,6 +282,18 @@ fn extract_imf(signal: &Array1<f64>, config: &EmdConfig) -> SignalResult<(Array1
             mean_env[i] = (upper_env[i] + lower_env[i]) / 2.0;
         }
 
+        // The threshold criterion judges the current candidate by its envelopes
+        if let SiftStopCriterion::Threshold {
+            sigma1,
+            sigma2,
+            alpha,
+        } = config.stop_criterion
+        {
+            if envelope_threshold_met(&upper_env, &lower_env, &mean_env, sigma1, sigma2, alpha) {
+                break;
+            }
+        }
+
         // Update signal by subtracting the mean envelope
         h = &h - &mean_env;
 
@@ -244,16 +305,74 @@ fn extract_imf(signal: &Array1<f64>, config: &EmdConfig) -> SignalResult<(Array1
             break;
         }
 
-        // Compute stopping criterion (SD)
-        let sd = compute_sifting_criterion(&h, &h_prev);
-        if sd < config.sift_threshold {
-            break;
+        match config.stop_criterion {
+            SiftStopCriterion::Cauchy => {
+                // Compute stopping criterion (SD)
+                let sd = compute_sifting_criterion(&h, &h_prev);
+                if sd < config.sift_threshold {
+                    break;
+                }
+            }
+            SiftStopCriterion::SNumber(required) => {
+                let (num_maxima, num_minima) = count_extrema(&h);
+                let counts = (num_maxima + num_minima, count_zero_crossings(&h));
+                let is_imf = counts.0.abs_diff(counts.1) <= 1;
+                stable_sifts = if is_imf && previous_counts == Some(counts) {
+                    stable_sifts + 1
+                } else {
+                    0
+                };
+                previous_counts = Some(counts);
+                if is_imf && stable_sifts >= required {
+                    break;
+                }
+            }
+            SiftStopCriterion::Threshold { .. } | SiftStopCriterion::FixedSifts(_) => {}
         }
     }
 
     Ok((h, iteration))
 }
 
+/// Rilling's threshold test on the envelopes of a sifting candidate
+fn envelope_threshold_met(
+    upper_env: &Array1<f64>,
+    lower_env: &Array1<f64>,
+    mean_env: &Array1<f64>,
+    sigma1: f64,
+    sigma2: f64,
+    alpha: f64,
+) -> bool {
+    let n = mean_env.len();
+    let mut above_sigma1 = 0;
+    for i in 0..n {
+        let amplitude = (upper_env[i] - lower_env[i]).abs() / 2.0;
+        let ratio = if amplitude > 0.0 {
+            mean_env[i].abs() / amplitude
+        } else if mean_env[i] == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+        if ratio > sigma2 {
+            return false;
+        }
+        if ratio > sigma1 {
+            above_sigma1 += 1;
+        }
+    }
+    (above_sigma1 as f64) < alpha * n as f64
+}
+
+/// Counts the sign changes of a signal
+fn count_zero_crossings(signal: &Array1<f64>) -> usize {
+    signal
+        .iter()
+        .zip(signal.iter().skip(1))
+        .filter(|(&a, &b)| (a < 0.0 && b >= 0.0) || (a >= 0.0 && b < 0.0))
+        .count()
+}
+
 /// Computes the stopping criterion for the sifting process
 ///
 /// # Arguments
@@ -664,14 +783,17 @@ fn interpolate_envelope(
 ///
 /// EEMD is a noise-assisted variant of EMD, which addresses the
 /// mode mixing problem by adding different realizations of white
-/// noise to the signal before decomposition.
+/// noise to the signal before decomposition. The realizations are
+/// decomposed in parallel and their IMFs averaged; the returned residue
+/// is chosen so that the IMFs and the residue add up to the input.
+/// Set `config.random_seed` for reproducible results.
 ///
 /// # Arguments
 ///
 /// * `signal` - Input signal
 /// * `config` - EMD configuration parameters
 /// * `ensemble_size` - Number of ensemble trials
-/// * `noise_std` - Standard deviation of the added white noise
+/// * `noise_std` - Standard deviation of the added Gaussian white noise
 ///
 /// # Returns
 ///
@@ -740,75 +862,64 @@ where
         .collect::<SignalResult<Vec<_>>>()?;
 
     let n = signal_f64.len();
-    let mut all_imfs = Vec::new();
-    let mut max_imf_count = 0;
-
-    // Run EMD on ensemble of signals with added noise
-    for _ in 0..ensemble_size {
-        // Add white noise
-        let noisy_signal: Vec<f64> = if noise_std > 0.0 {
-            // Direct use of random without creating an unused rng variable
-            signal_f64
+    let noise = Normal::new(0.0, noise_std)
+        .map_err(|e| SignalError::ValueError(format!("Invalid noise standard deviation: {}", e)))?;
+    let base_seed = config.random_seed.unwrap_or_else(rand::random);
+
+    // Decompose every noise realization independently, each with its own
+    // deterministic generator so results do not depend on the thread schedule
+    let realizations = (0..ensemble_size)
+        .into_par_iter()
+        .map(|trial| {
+            let mut rng = StdRng::seed_from_u64(base_seed.wrapping_add(trial as u64));
+            let noisy_signal: Vec<f64> = signal_f64
                 .iter()
-                .map(|&x| x + noise_std * (rand::random::<f64>() * 2.0 - 1.0))
-                .collect()
-        } else {
-            signal_f64.clone()
-        };
-
-        // Apply EMD
-        let emd_result = emd(&noisy_signal, config)?;
-        let imfs = emd_result.imfs;
-
-        // Keep track of maximum IMF count
-        max_imf_count = max(max_imf_count, imfs.shape()[0]);
+                .map(|&x| x + noise.sample(&mut rng))
+                .collect();
+            emd(&noisy_signal, config)
+        })
+        .collect::<SignalResult<Vec<EmdResult>>>()?;
 
-        // Store IMFs for ensemble averaging
-        all_imfs.push(imfs);
-    }
+    let max_imf_count = realizations
+        .iter()
+        .map(|r| r.imfs.nrows())
+        .max()
+        .unwrap_or(0);
 
-    // Prepare average IMFs
+    // Average the IMFs over the ensemble; realizations with fewer IMFs
+    // contribute zeros, their remaining modes being part of their residue
     let mut avg_imfs = Array2::zeros((max_imf_count, n));
+    let mut total_iterations = vec![0; max_imf_count];
     let mut imf_counts = vec![0; max_imf_count];
-
-    // Accumulate IMFs from all ensemble members
-    for imfs in &all_imfs {
-        let num_imfs = imfs.shape()[0];
-        for i in 0..num_imfs {
-            // Accumulate IMF values
-            for j in 0..n {
-                avg_imfs[[i, j]] += imfs[[i, j]];
-            }
+    for realization in &realizations {
+        for i in 0..realization.imfs.nrows() {
+            let mut row = avg_imfs.row_mut(i);
+            row += &realization.imfs.row(i);
+            total_iterations[i] += realization.iterations[i];
             imf_counts[i] += 1;
         }
     }
+    avg_imfs /= ensemble_size as f64;
 
-    // Compute average by dividing by the number of accumulated IMFs
-    for i in 0..max_imf_count {
-        if imf_counts[i] > 0 {
-            for j in 0..n {
-                avg_imfs[[i, j]] /= imf_counts[i] as f64;
-            }
-        }
-    }
-
-    // Compute residue (signal - sum of IMFs)
-    let mut residue = Array1::from(signal_f64.clone());
-    for i in 0..max_imf_count {
-        for j in 0..n {
-            residue[j] -= avg_imfs[[i, j]];
-        }
+    // Residue such that the IMFs and the residue add up to the input
+    let mut residue = Array1::from(signal_f64);
+    for imf in avg_imfs.rows() {
+        residue -= &imf;
     }
 
     // Compute energies for each IMF
-    let mut energies = Vec::with_capacity(max_imf_count);
-    for i in 0..max_imf_count {
-        let energy = avg_imfs.slice(s![i, ..]).map(|&x| x * x).sum() / n as f64;
-        energies.push(energy);
-    }
+    let energies = avg_imfs
+        .rows()
+        .into_iter()
+        .map(|imf| imf.map(|&x| x * x).sum() / n as f64)
+        .collect();
 
-    // Since we're averaging, we don't have iteration counts per IMF
-    let iterations = vec![ensemble_size; max_imf_count];
+    // Mean number of sifting iterations over the realizations that produced each IMF
+    let iterations = total_iterations
+        .iter()
+        .zip(&imf_counts)
+        .map(|(&total, &count)| (total + count / 2) / count.max(1))
+        .collect();
 
     Ok(EmdResult {
         imfs: avg_imfs,
@@ -1112,4 +1223,94 @@ mod tests {
         assert_eq!(result.iterations.len(), result.imfs.shape()[0]); // One iteration count per IMF
         assert_eq!(result.energies.len(), result.imfs.shape()[0]); // One energy value per IMF
     }
+
+    fn two_tone(n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let fast: Vec<f64> = (0..n)
+            .map(|i| 0.5 * (2.0 * PI * i as f64 / 8.0).sin())
+            .collect();
+        let slow: Vec<f64> = (0..n).map(|i| (2.0 * PI * i as f64 / 64.0).sin()).collect();
+        let signal = fast.iter().zip(&slow).map(|(a, b)| a + b).collect();
+        (signal, fast, slow)
+    }
+
+    fn correlation(a: &[f64], b: &[f64]) -> f64 {
+        let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+        let na: f64 = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let nb: f64 = b.iter().map(|x| x * x).sum::<f64>().sqrt();
+        dot / (na * nb)
+    }
+
+    #[test]
+    fn test_sifting_stop_criteria() {
+        let (signal, fast, _) = two_tone(512);
+        let criteria = [
+            SiftStopCriterion::Cauchy,
+            SiftStopCriterion::SNumber(4),
+            SiftStopCriterion::Threshold {
+                sigma1: 0.05,
+                sigma2: 0.5,
+                alpha: 0.05,
+            },
+            SiftStopCriterion::FixedSifts(10),
+        ];
+
+        for criterion in criteria {
+            let config = EmdConfig {
+                max_imfs: 4,
+                stop_criterion: criterion,
+                ..Default::default()
+            };
+            let result = emd(&signal, &config).unwrap();
+
+            // The first IMF captures the fast oscillation away from the edges
+            let first = result.imfs.row(0).to_vec();
+            assert!(
+                correlation(&first[64..448], &fast[64..448]) > 0.95,
+                "{criterion:?}"
+            );
+
+            // IMFs and residue add up to the signal
+            for j in 0..signal.len() {
+                let sum = result.imfs.column(j).sum() + result.residue[j];
+                assert_relative_eq!(sum, signal[j], epsilon = 1e-10);
+            }
+
+            if let SiftStopCriterion::FixedSifts(sifts) = criterion {
+                assert_eq!(result.iterations[0], sifts);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eemd_seeded_ensemble() {
+        let (signal, _, slow) = two_tone(256);
+        let config = EmdConfig {
+            max_imfs: 5,
+            random_seed: Some(42),
+            ..Default::default()
+        };
+
+        let first = eemd(&signal, &config, 8, 0.05).unwrap();
+        let second = eemd(&signal, &config, 8, 0.05).unwrap();
+        assert_eq!(first.imfs, second.imfs);
+
+        // Completeness holds exactly for the ensemble average
+        for j in 0..signal.len() {
+            let sum = first.imfs.column(j).sum() + first.residue[j];
+            assert_relative_eq!(sum, signal[j], epsilon = 1e-10);
+        }
+
+        // The slow component ends up in one of the later IMFs
+        let best = first
+            .imfs
+            .rows()
+            .into_iter()
+            .skip(1)
+            .map(|imf| correlation(&imf.to_vec()[32..224], &slow[32..224]))
+            .fold(f64::MIN, f64::max);
+        assert!(best > 0.9);
+
+        assert!(eemd(&signal, &config, 0, 0.1).is_err());
+        assert!(eemd(&signal, &config, 4, -0.1).is_err());
+    }
 }
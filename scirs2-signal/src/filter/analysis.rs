@@ -7,7 +7,11 @@
 use crate::error::{SignalError, SignalResult};
 use num_complex::Complex64;
 
-use super::application::{evaluate_transfer_function, find_polynomial_roots, group_delay};
+use super::application::{
+    evaluate_transfer_function, find_polynomial_roots, group_delay, polyval_z_inv,
+};
+use super::common::SecondOrderSections;
+use std::f64::consts::PI;
 
 /// Comprehensive filter analysis results
 ///
@@ -329,6 +333,294 @@ pub fn frequency_response(
     Ok((magnitude, phase))
 }
 
+/// Compute the frequency response of a digital filter
+///
+/// Evaluates `H(e^jw) = B(e^jw) / A(e^jw)` with the coefficients taken as
+/// powers of `z^-1`, on `n` equally spaced frequencies covering either the
+/// upper half of the unit circle (`[0, π)`) or the whole circle (`[0, 2π)`).
+/// When the FFT length is at least as long as both coefficient vectors the
+/// response is obtained from zero-padded FFTs of `b` and `a`; otherwise each
+/// frequency is evaluated directly.
+///
+/// # Arguments
+///
+/// * `b` - Numerator coefficients
+/// * `a` - Denominator coefficients
+/// * `n` - Number of frequency points
+/// * `whole` - Evaluate over the whole unit circle instead of the upper half
+///
+/// # Returns
+///
+/// * Tuple of (frequencies in radians per sample, complex response)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::analysis::freqz;
+/// use scirs2_signal::filter::iir::butter;
+///
+/// let (b, a) = butter(4, 0.2, "lowpass").unwrap();
+/// let (w, h) = freqz(&b, &a, 512, false).unwrap();
+///
+/// assert_eq!(w.len(), 512);
+/// assert!((h[0].norm() - 1.0).abs() < 1e-10);
+/// ```
+pub fn freqz(
+    b: &[f64],
+    a: &[f64],
+    n: usize,
+    whole: bool,
+) -> SignalResult<(Vec<f64>, Vec<Complex64>)> {
+    validate_tf(b, a)?;
+    if n == 0 {
+        return Err(SignalError::ValueError(
+            "Number of frequency points must be positive".to_string(),
+        ));
+    }
+
+    let n_fft = if whole { n } else { 2 * n };
+    let w: Vec<f64> = (0..n).map(|k| 2.0 * PI * k as f64 / n_fft as f64).collect();
+
+    if n_fft < b.len().max(a.len()) {
+        let h = freqz_at(b, a, &w)?;
+        return Ok((w, h));
+    }
+
+    let fft = |coeffs: &[f64]| {
+        scirs2_fft::fft(coeffs, Some(n_fft)).map_err(|e| {
+            SignalError::ComputationError(format!(
+                "Failed to compute FFT for frequency response: {}",
+                e
+            ))
+        })
+    };
+    let num = fft(b)?;
+    let den = fft(a)?;
+    let h = num.iter().zip(&den).take(n).map(|(&b, &a)| b / a).collect();
+
+    Ok((w, h))
+}
+
+/// Compute the frequency response of a digital filter at arbitrary frequencies
+///
+/// # Arguments
+///
+/// * `b` - Numerator coefficients
+/// * `a` - Denominator coefficients
+/// * `w` - Frequency points in radians per sample
+///
+/// # Returns
+///
+/// * Complex response at each frequency
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::analysis::freqz_at;
+///
+/// // Two-tap moving average has a null at Nyquist
+/// let h = freqz_at(&[0.5, 0.5], &[1.0], &[0.0, std::f64::consts::PI]).unwrap();
+/// assert!((h[0].re - 1.0).abs() < 1e-12);
+/// assert!(h[1].norm() < 1e-12);
+/// ```
+pub fn freqz_at(b: &[f64], a: &[f64], w: &[f64]) -> SignalResult<Vec<Complex64>> {
+    validate_tf(b, a)?;
+
+    Ok(w.iter()
+        .map(|&freq| polyval_z_inv(b, freq) / polyval_z_inv(a, freq))
+        .collect())
+}
+
+/// Compute the frequency response of a filter in second-order sections form
+///
+/// The response is the product of the responses of the individual sections,
+/// which avoids forming the (possibly ill-conditioned) transfer function.
+///
+/// # Arguments
+///
+/// * `sos` - Second-order sections, each `[b0, b1, b2, a0, a1, a2]`
+/// * `n` - Number of frequency points
+/// * `whole` - Evaluate over the whole unit circle instead of the upper half
+///
+/// # Returns
+///
+/// * Tuple of (frequencies in radians per sample, complex response)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::analysis::sosfreqz;
+/// use scirs2_signal::filter::iir::{iirfilter_sos, IirPrototype};
+///
+/// let sos = iirfilter_sos(8, &[0.3], IirPrototype::Butterworth, "lowpass").unwrap();
+/// let (w, h) = sosfreqz(&sos, 256, false).unwrap();
+///
+/// assert_eq!(w.len(), 256);
+/// assert!((h[0].norm() - 1.0).abs() < 1e-10);
+/// ```
+pub fn sosfreqz(
+    sos: &SecondOrderSections,
+    n: usize,
+    whole: bool,
+) -> SignalResult<(Vec<f64>, Vec<Complex64>)> {
+    validate_sos(sos)?;
+
+    let mut result: Option<(Vec<f64>, Vec<Complex64>)> = None;
+    for section in sos {
+        let (w, h) = freqz(&section[..3], &section[3..], n, whole)?;
+        result = Some(match result {
+            None => (w, h),
+            Some((w, acc)) => (w, acc.iter().zip(&h).map(|(&x, &y)| x * y).collect()),
+        });
+    }
+
+    // validate_sos guarantees at least one section
+    result.ok_or_else(|| SignalError::ValueError("No second-order sections".to_string()))
+}
+
+/// Compute the frequency response of second-order sections at arbitrary frequencies
+///
+/// # Arguments
+///
+/// * `sos` - Second-order sections, each `[b0, b1, b2, a0, a1, a2]`
+/// * `w` - Frequency points in radians per sample
+///
+/// # Returns
+///
+/// * Complex response at each frequency
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::analysis::sosfreqz_at;
+///
+/// // A single first-order section y[n] = x[n] + 0.5 y[n-1] has DC gain 2
+/// let sos = vec![[1.0, 0.0, 0.0, 1.0, -0.5, 0.0]];
+/// let h = sosfreqz_at(&sos, &[0.0]).unwrap();
+/// assert!((h[0].re - 2.0).abs() < 1e-12);
+/// ```
+pub fn sosfreqz_at(sos: &SecondOrderSections, w: &[f64]) -> SignalResult<Vec<Complex64>> {
+    validate_sos(sos)?;
+
+    let mut h = vec![Complex64::new(1.0, 0.0); w.len()];
+    for section in sos {
+        for (hk, &freq) in h.iter_mut().zip(w) {
+            *hk *= polyval_z_inv(&section[..3], freq) / polyval_z_inv(&section[3..], freq);
+        }
+    }
+
+    Ok(h)
+}
+
+/// Compute the group delay of a filter in second-order sections form
+///
+/// The group delay of a cascade is the sum of the group delays of its sections.
+///
+/// # Arguments
+///
+/// * `sos` - Second-order sections, each `[b0, b1, b2, a0, a1, a2]`
+/// * `w` - Frequency points in radians per sample
+///
+/// # Returns
+///
+/// * Group delay in samples at each frequency
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::analysis::sos_group_delay;
+/// use scirs2_signal::filter::iir::{iirfilter_sos, IirPrototype};
+///
+/// let sos = iirfilter_sos(6, &[0.25], IirPrototype::Butterworth, "lowpass").unwrap();
+/// let gd = sos_group_delay(&sos, &[0.0, 0.1, 0.2]).unwrap();
+/// assert!(gd.iter().all(|&d| d > 0.0));
+/// ```
+pub fn sos_group_delay(sos: &SecondOrderSections, w: &[f64]) -> SignalResult<Vec<f64>> {
+    validate_sos(sos)?;
+
+    let mut delay = vec![0.0; w.len()];
+    for section in sos {
+        let gd = group_delay(&section[..3], &section[3..], w)?;
+        for (d, g) in delay.iter_mut().zip(gd) {
+            *d += g;
+        }
+    }
+
+    Ok(delay)
+}
+
+/// Compute the phase delay of a digital filter
+///
+/// The phase delay is `-φ(w) / w`, where `φ` is the phase response unwrapped
+/// continuously from `w = 0`. The phase is tracked on a grid no coarser than
+/// `π / 1024` so that the unwrapping does not depend on how sparse the
+/// requested frequencies are. At `w = 0` the limit, which equals the group
+/// delay there, is returned. The phase delay is even in `w`.
+///
+/// # Arguments
+///
+/// * `b` - Numerator coefficients
+/// * `a` - Denominator coefficients
+/// * `w` - Frequency points in radians per sample
+///
+/// # Returns
+///
+/// * Phase delay in samples at each frequency
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::analysis::phase_delay;
+///
+/// // A pure delay of 5 samples
+/// let b = [0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+/// let pd = phase_delay(&b, &[1.0], &[0.0, 0.5, 2.0, 3.0]).unwrap();
+/// assert!(pd.iter().all(|&d| (d - 5.0).abs() < 1e-10));
+/// ```
+pub fn phase_delay(b: &[f64], a: &[f64], w: &[f64]) -> SignalResult<Vec<f64>> {
+    validate_tf(b, a)?;
+
+    const MAX_STEP: f64 = PI / 1024.0;
+
+    let phase_at = |freq: f64| (polyval_z_inv(b, freq) / polyval_z_inv(a, freq)).arg();
+
+    let mut order: Vec<usize> = (0..w.len()).collect();
+    order.sort_by(|&i, &j| w[i].abs().total_cmp(&w[j].abs()));
+
+    let mut delay = vec![0.0; w.len()];
+    let mut current_w = 0.0;
+    let mut wrapped = phase_at(0.0);
+    let mut unwrapped = wrapped;
+
+    for idx in order {
+        let target = w[idx].abs();
+        if !target.is_finite() {
+            return Err(SignalError::ValueError(
+                "Frequencies must be finite".to_string(),
+            ));
+        }
+
+        let steps = ((target - current_w) / MAX_STEP).ceil() as usize;
+        for step in 1..=steps {
+            let freq = current_w + (target - current_w) * step as f64 / steps as f64;
+            let next = phase_at(freq);
+            let mut diff = next - wrapped;
+            diff -= 2.0 * PI * (diff / (2.0 * PI)).round();
+            unwrapped += diff;
+            wrapped = next;
+        }
+        current_w = target;
+
+        delay[idx] = if target < 1e-12 {
+            group_delay(b, a, &[0.0])?[0]
+        } else {
+            -unwrapped / target
+        };
+    }
+
+    Ok(delay)
+}
+
 /// Find poles and zeros of a digital filter
 ///
 /// Extracts the poles and zeros from the transfer function coefficients.
@@ -444,6 +736,36 @@ pub fn compute_q_factor(b: &[f64], a: &[f64], num_points: usize) -> SignalResult
 
 // Helper functions
 
+/// Check transfer function coefficients for the frequency response routines
+fn validate_tf(b: &[f64], a: &[f64]) -> SignalResult<()> {
+    if b.is_empty() {
+        return Err(SignalError::ValueError(
+            "Numerator coefficients are empty".to_string(),
+        ));
+    }
+    if a.is_empty() || a[0].abs() < 1e-15 {
+        return Err(SignalError::ValueError(
+            "Invalid denominator coefficients".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check second-order sections for the frequency response routines
+fn validate_sos(sos: &SecondOrderSections) -> SignalResult<()> {
+    if sos.is_empty() {
+        return Err(SignalError::ValueError(
+            "No second-order sections".to_string(),
+        ));
+    }
+    if sos.iter().any(|s| s[3].abs() < 1e-15) {
+        return Err(SignalError::ValueError(
+            "Second-order section has a zero leading denominator coefficient".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Find the frequency where magnitude drops to a specific dB level
 fn find_cutoff_frequency(frequencies: &[f64], magnitude_db: &[f64], target_db: f64) -> f64 {
     // Find the index where magnitude first drops below target
@@ -468,3 +790,96 @@ fn find_cutoff_frequency(frequencies: &[f64], magnitude_db: &[f64], target_db: f
     }
     frequencies[frequencies.len() - 1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::iir::{iirfilter, iirfilter_sos, IirPrototype};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_freqz_fft_matches_direct() {
+        let (b, a) = iirfilter(
+            5,
+            &[0.2, 0.4],
+            IirPrototype::ChebyshevI { ripple: 1.0 },
+            "bandpass",
+        )
+        .unwrap();
+
+        for whole in [false, true] {
+            let (w, h) = freqz(&b, &a, 64, whole).unwrap();
+            let direct = freqz_at(&b, &a, &w).unwrap();
+            for (x, y) in h.iter().zip(&direct) {
+                assert!((x - y).norm() < 1e-9 * (1.0 + y.norm()));
+            }
+        }
+
+        // Too few points for the FFT shortcut falls back to direct evaluation
+        let (w, h) = freqz(&b, &a, 4, true).unwrap();
+        assert_relative_eq!(w[1], PI / 2.0, epsilon = 1e-15);
+        let direct = freqz_at(&b, &a, &w).unwrap();
+        for (x, y) in h.iter().zip(&direct) {
+            assert!((x - y).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_sosfreqz_matches_transfer_function() {
+        let prototype = IirPrototype::Elliptic {
+            ripple: 0.5,
+            attenuation: 50.0,
+        };
+        let (b, a) = iirfilter(6, &[0.3], prototype, "lowpass").unwrap();
+        let sos = iirfilter_sos(6, &[0.3], prototype, "lowpass").unwrap();
+
+        let (w, h) = freqz(&b, &a, 128, false).unwrap();
+        let (w_sos, h_sos) = sosfreqz(&sos, 128, false).unwrap();
+        assert_eq!(w, w_sos);
+        for (x, y) in h.iter().zip(&h_sos) {
+            assert!((x - y).norm() < 1e-8);
+        }
+        let h_at = sosfreqz_at(&sos, &w).unwrap();
+        for (x, y) in h_at.iter().zip(&h_sos) {
+            assert!((x - y).norm() < 1e-10);
+        }
+
+        // Group delay of the cascade equals that of the transfer function
+        let freqs: Vec<f64> = (0..20).map(|k| 0.05 * k as f64).collect();
+        let gd = group_delay(&b, &a, &freqs).unwrap();
+        let gd_sos = sos_group_delay(&sos, &freqs).unwrap();
+        for (x, y) in gd.iter().zip(&gd_sos) {
+            assert_relative_eq!(x, y, epsilon = 1e-6 * (1.0 + x.abs()));
+        }
+    }
+
+    #[test]
+    fn test_group_and_phase_delay() {
+        // Group delay matches the numerical derivative of the unwrapped phase
+        let (b, a) = iirfilter(4, &[0.25], IirPrototype::Butterworth, "lowpass").unwrap();
+        let freqs = [0.1, 0.5, 0.78, 1.2, 2.0];
+        let gd = group_delay(&b, &a, &freqs).unwrap();
+        let dw = 1e-6;
+        for (&w, &d) in freqs.iter().zip(&gd) {
+            let h = freqz_at(&b, &a, &[w - dw, w + dw]).unwrap();
+            let mut dphi = h[1].arg() - h[0].arg();
+            dphi -= 2.0 * PI * (dphi / (2.0 * PI)).round();
+            assert_relative_eq!(d, -dphi / (2.0 * dw), epsilon = 1e-5 * (1.0 + d.abs()));
+        }
+
+        // Linear-phase FIR: constant group and phase delay of (N - 1) / 2
+        let fir = [0.5, 1.0, 3.0, 1.0, 0.5];
+        let freqs: Vec<f64> = (0..30).map(|k| 0.1 * k as f64).collect();
+        let gd = group_delay(&fir, &[1.0], &freqs).unwrap();
+        let pd = phase_delay(&fir, &[1.0], &freqs).unwrap();
+        for (&g, &p) in gd.iter().zip(&pd) {
+            assert_relative_eq!(g, 2.0, epsilon = 1e-9);
+            assert_relative_eq!(p, 2.0, epsilon = 1e-9);
+        }
+
+        // Phase delay approaches the group delay at DC and is even in w
+        let pd = phase_delay(&b, &a, &[0.0, 1e-4, -0.7, 0.7]).unwrap();
+        assert_relative_eq!(pd[0], pd[1], epsilon = 1e-4);
+        assert_relative_eq!(pd[2], pd[3], epsilon = 1e-12);
+    }
+}
@@ -5,6 +5,7 @@
 //! phase conversion, and matched filtering for signal detection.
 
 use crate::error::{SignalError, SignalResult};
+use ndarray::{Array1, Array2};
 use num_complex::Complex64;
 use num_traits::{Float, NumCast, Zero};
 use std::fmt::Debug;
@@ -15,6 +16,10 @@ use std::fmt::Debug;
 /// distortion. The result has zero phase delay but twice the filter order.
 /// This is equivalent to MATLAB's filtfilt function.
 ///
+/// The signal is extended by odd reflection at both ends and each pass starts
+/// from the steady-state initial conditions, as with SciPy's defaults. See
+/// [`filtfilt_with_config`] for other padding strategies and Gustafsson's method.
+///
 /// # Arguments
 ///
 /// * `b` - Numerator coefficients
@@ -40,11 +45,98 @@ pub fn filtfilt<T>(b: &[f64], a: &[f64], x: &[T]) -> SignalResult<Vec<f64>>
 where
     T: Float + NumCast + Debug,
 {
-    if a.is_empty() || a[0] == 0.0 {
-        return Err(SignalError::ValueError(
-            "First denominator coefficient cannot be zero".to_string(),
-        ));
-    }
+    filtfilt_with_config(b, a, x, &FiltfiltConfig::default())
+}
+
+/// Edge extension used by [`filtfilt_with_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PadType {
+    /// Point reflection about the end samples (`2 x[0] - x[k]`)
+    #[default]
+    Odd,
+    /// Mirror reflection about the end samples (`x[k]`)
+    Even,
+    /// Repetition of the end samples
+    Constant,
+    /// No extension
+    None,
+}
+
+/// Treatment of the signal edges in [`filtfilt_with_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FiltfiltMethod {
+    /// Extend the signal according to [`PadType`] and start both passes from
+    /// steady-state initial conditions
+    #[default]
+    Pad,
+    /// Gustafsson's method: choose the initial conditions of both passes so
+    /// that forward-backward and backward-forward filtering agree
+    Gustafsson {
+        /// Number of impulse response samples taken into account at each
+        /// edge; `None` uses the whole signal
+        irlen: Option<usize>,
+    },
+}
+
+/// Configuration for [`filtfilt_with_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FiltfiltConfig {
+    /// Edge handling method
+    pub method: FiltfiltMethod,
+    /// Extension type for [`FiltfiltMethod::Pad`]
+    pub padtype: PadType,
+    /// Number of samples added at each end for [`FiltfiltMethod::Pad`]
+    ///
+    /// Must be smaller than the signal length. Defaults to
+    /// `3 * max(a.len(), b.len())`, limited to `x.len() - 1`.
+    pub padlen: Option<usize>,
+}
+
+/// Zero-phase filtering with a configurable edge treatment
+///
+/// # Arguments
+///
+/// * `b` - Numerator coefficients
+/// * `a` - Denominator coefficients
+/// * `x` - Input signal
+/// * `config` - Padding strategy or Gustafsson's method
+///
+/// # Returns
+///
+/// * Filtered signal with zero phase delay
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::application::{
+///     filtfilt_with_config, FiltfiltConfig, FiltfiltMethod, PadType,
+/// };
+/// use scirs2_signal::filter::iir::butter;
+///
+/// let (b, a) = butter(3, 0.1, "lowpass").unwrap();
+/// let signal: Vec<f64> = (0..200).map(|i| (i as f64 * 0.05).sin()).collect();
+///
+/// let even = FiltfiltConfig { padtype: PadType::Even, ..Default::default() };
+/// let y = filtfilt_with_config(&b, &a, &signal, &even).unwrap();
+/// assert_eq!(y.len(), signal.len());
+///
+/// let gust = FiltfiltConfig {
+///     method: FiltfiltMethod::Gustafsson { irlen: None },
+///     ..Default::default()
+/// };
+/// let y = filtfilt_with_config(&b, &a, &signal, &gust).unwrap();
+/// assert_eq!(y.len(), signal.len());
+/// ```
+pub fn filtfilt_with_config<T>(
+    b: &[f64],
+    a: &[f64],
+    x: &[T],
+    config: &FiltfiltConfig,
+) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    let (b_norm, a_norm) = padded_coefficients(b, a)?;
 
     // Convert input to f64
     let x_f64: Vec<f64> = x
@@ -56,21 +148,41 @@ where
         })
         .collect::<SignalResult<Vec<_>>>()?;
 
-    // 1. Apply filter forward
-    let y1 = lfilter(b, a, &x_f64)?;
-
-    // 2. Reverse the result
-    let mut y1_rev = y1.clone();
-    y1_rev.reverse();
-
-    // 3. Apply filter backward
-    let y2 = lfilter(b, a, &y1_rev)?;
-
-    // 4. Reverse again to get the final result
-    let mut result = y2;
-    result.reverse();
+    if x_f64.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    Ok(result)
+    match config.method {
+        FiltfiltMethod::Pad => {
+            let edge = match (config.padtype, config.padlen) {
+                (PadType::None, _) => 0,
+                (_, Some(padlen)) if padlen >= x_f64.len() => {
+                    return Err(SignalError::ValueError(format!(
+                        "padlen ({}) must be smaller than the signal length ({})",
+                        padlen,
+                        x_f64.len()
+                    )));
+                }
+                (_, Some(padlen)) => padlen,
+                (_, None) => (3 * b_norm.len()).min(x_f64.len() - 1),
+            };
+            let extended = extend_edges(&x_f64, edge, config.padtype);
+            let zi = steady_state_zi(&b_norm, &a_norm)?;
+
+            // Forward pass, then backward pass, each from steady state
+            let mut state: Vec<f64> = zi.iter().map(|&z| z * extended[0]).collect();
+            let mut y = filter_with_state(&b_norm, &a_norm, &extended, &mut state);
+            y.reverse();
+            let mut state: Vec<f64> = zi.iter().map(|&z| z * y[0]).collect();
+            let mut y = filter_with_state(&b_norm, &a_norm, &y, &mut state);
+            y.reverse();
+
+            Ok(y[edge..edge + x_f64.len()].to_vec())
+        }
+        FiltfiltMethod::Gustafsson { irlen } => {
+            filtfilt_gustafsson(&b_norm, &a_norm, &x_f64, irlen)
+        }
+    }
 }
 
 /// Apply a digital filter to a signal (direct form II transposed)
@@ -104,16 +216,79 @@ pub fn lfilter<T>(b: &[f64], a: &[f64], x: &[T]) -> SignalResult<Vec<f64>>
 where
     T: Float + NumCast + Debug,
 {
-    if a.is_empty() || a[0] == 0.0 {
-        return Err(SignalError::ValueError(
-            "First denominator coefficient cannot be zero".to_string(),
-        ));
-    }
+    let (b_norm, a_norm) = padded_coefficients(b, a)?;
 
-    // Normalize coefficients by a[0]
-    let a0 = a[0];
-    let b_norm: Vec<f64> = b.iter().map(|&val| val / a0).collect();
-    let a_norm: Vec<f64> = a.iter().map(|&val| val / a0).collect();
+    // Convert input to f64
+    let x_f64: Vec<f64> = x
+        .iter()
+        .map(|&val| {
+            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
+                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
+            })
+        })
+        .collect::<SignalResult<Vec<_>>>()?;
+
+    // Apply filter using direct form II transposed from rest
+    let mut z = vec![0.0; b_norm.len() - 1];
+    Ok(filter_with_state(&b_norm, &a_norm, &x_f64, &mut z))
+}
+
+/// Apply a digital filter starting from, and returning, the filter state
+///
+/// Direct form II transposed filtering as in [`lfilter`], but starting from
+/// the delay-line state `zi` and returning the final state alongside the
+/// output. Feeding the returned state into the next call filters a signal in
+/// chunks exactly as if it had been filtered in one piece.
+///
+/// # Arguments
+///
+/// * `b` - Numerator coefficients
+/// * `a` - Denominator coefficients
+/// * `x` - Input signal
+/// * `zi` - Initial state of length `max(a.len(), b.len()) - 1`, for the
+///   coefficients normalized by `a[0]`
+///
+/// # Returns
+///
+/// * Tuple of (filtered signal, final state)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::application::{lfilter, lfilter_with_zi};
+/// use scirs2_signal::filter::iir::butter;
+///
+/// let (b, a) = butter(2, 0.3, "lowpass").unwrap();
+/// let signal: Vec<f64> = (0..64).map(|i| (i as f64 * 0.4).sin()).collect();
+///
+/// // Process in two chunks, carrying the state across
+/// let mut state = vec![0.0; 2];
+/// let (first, state_mid) = lfilter_with_zi(&b, &a, &signal[..40], &state).unwrap();
+/// state = state_mid;
+/// let (second, _) = lfilter_with_zi(&b, &a, &signal[40..], &state).unwrap();
+///
+/// let whole = lfilter(&b, &a, &signal).unwrap();
+/// for (u, v) in first.iter().chain(&second).zip(&whole) {
+///     assert!((u - v).abs() < 1e-12);
+/// }
+/// ```
+pub fn lfilter_with_zi<T>(
+    b: &[f64],
+    a: &[f64],
+    x: &[T],
+    zi: &[f64],
+) -> SignalResult<(Vec<f64>, Vec<f64>)>
+where
+    T: Float + NumCast + Debug,
+{
+    let (b_norm, a_norm) = padded_coefficients(b, a)?;
+    if zi.len() != b_norm.len() - 1 {
+        return Err(SignalError::DimensionMismatch(format!(
+            "Initial state must have length {}, got {}",
+            b_norm.len() - 1,
+            zi.len()
+        )));
+    }
 
     // Convert input to f64
     let x_f64: Vec<f64> = x
@@ -125,53 +300,294 @@ where
         })
         .collect::<SignalResult<Vec<_>>>()?;
 
-    // Apply filter using direct form II transposed
-    let n = x_f64.len();
-    let mut y = vec![0.0; n];
-    let mut z = vec![0.0; a_norm.len().max(b_norm.len()) - 1]; // State variables
+    let mut z = zi.to_vec();
+    let y = filter_with_state(&b_norm, &a_norm, &x_f64, &mut z);
+    Ok((y, z))
+}
 
-    for i in 0..n {
-        // Compute output
-        y[i] = if !b_norm.is_empty() {
-            b_norm[0] * x_f64[i]
-        } else {
-            0.0
-        } + if !z.is_empty() { z[0] } else { 0.0 };
+/// Steady-state initial conditions for the step response
+///
+/// Returns the state `zi` for which [`lfilter_with_zi`] maps a constant input
+/// of one to a constant output equal to the DC gain. Scaling `zi` by the first
+/// input sample suppresses the startup transient, which is how [`filtfilt`]
+/// initializes both of its passes.
+///
+/// # Arguments
+///
+/// * `b` - Numerator coefficients
+/// * `a` - Denominator coefficients
+///
+/// # Returns
+///
+/// * State vector of length `max(a.len(), b.len()) - 1`
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::application::{lfilter_with_zi, lfilter_zi};
+/// use scirs2_signal::filter::iir::butter;
+///
+/// let (b, a) = butter(3, 0.25, "lowpass").unwrap();
+/// let zi = lfilter_zi(&b, &a).unwrap();
+///
+/// let (y, _) = lfilter_with_zi(&b, &a, &[1.0; 10], &zi).unwrap();
+/// assert!(y.iter().all(|&v| (v - 1.0).abs() < 1e-10));
+/// ```
+pub fn lfilter_zi(b: &[f64], a: &[f64]) -> SignalResult<Vec<f64>> {
+    let (b_norm, a_norm) = padded_coefficients(b, a)?;
+    steady_state_zi(&b_norm, &a_norm)
+}
 
-        // Update state variables
-        for j in 1..z.len() {
-            let b_term = if j < b_norm.len() {
-                b_norm[j] * x_f64[i]
-            } else {
-                0.0
-            };
-            let a_term = if j < a_norm.len() {
-                a_norm[j] * y[i]
-            } else {
-                0.0
-            };
-            let next_z = if j + 1 < z.len() { z[j] } else { 0.0 };
+/// Initial conditions reproducing a given input and output history
+///
+/// Computes the state for which [`lfilter_with_zi`] continues as if the filter
+/// had produced the outputs `y` from the inputs `x`. Both histories are given
+/// most recent first (`y[0]` is the output one sample before the start);
+/// missing values are taken as zero.
+///
+/// # Arguments
+///
+/// * `b` - Numerator coefficients
+/// * `a` - Denominator coefficients
+/// * `y` - Past outputs, most recent first
+/// * `x` - Past inputs, most recent first (optional, zero when omitted)
+///
+/// # Returns
+///
+/// * State vector of length `max(a.len(), b.len()) - 1`
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::application::{lfilter, lfilter_with_zi, lfiltic};
+///
+/// let b = [0.5, 0.5];
+/// let a = [1.0, -0.5];
+/// let x = [1.0, 2.0, 3.0, 4.0];
+/// let y = lfilter(&b, &a, &x).unwrap();
+///
+/// // Resume after two samples from their input/output history
+/// let zi = lfiltic(&b, &a, &[y[1], y[0]], Some(&[x[1], x[0]])).unwrap();
+/// let (rest, _) = lfilter_with_zi(&b, &a, &x[2..], &zi).unwrap();
+/// assert!((rest[0] - y[2]).abs() < 1e-12);
+/// assert!((rest[1] - y[3]).abs() < 1e-12);
+/// ```
+pub fn lfiltic(b: &[f64], a: &[f64], y: &[f64], x: Option<&[f64]>) -> SignalResult<Vec<f64>> {
+    let (b_norm, a_norm) = padded_coefficients(b, a)?;
+    let order = b_norm.len() - 1;
+    let x = x.unwrap_or(&[]);
+    let past = |history: &[f64], k: usize| history.get(k).copied().unwrap_or(0.0);
+
+    Ok((0..order)
+        .map(|m| {
+            (m + 1..=order)
+                .map(|j| b_norm[j] * past(x, j - m - 1) - a_norm[j] * past(y, j - m - 1))
+                .sum()
+        })
+        .collect())
+}
+
+/// Normalize by `a[0]` and zero-pad `b` and `a` to a common length
+fn padded_coefficients(b: &[f64], a: &[f64]) -> SignalResult<(Vec<f64>, Vec<f64>)> {
+    if a.is_empty() || a[0] == 0.0 {
+        return Err(SignalError::ValueError(
+            "First denominator coefficient cannot be zero".to_string(),
+        ));
+    }
+    if b.is_empty() {
+        return Err(SignalError::ValueError(
+            "Numerator coefficients cannot be empty".to_string(),
+        ));
+    }
+
+    let n = a.len().max(b.len());
+    let a0 = a[0];
+    let mut b_norm: Vec<f64> = b.iter().map(|&val| val / a0).collect();
+    let mut a_norm: Vec<f64> = a.iter().map(|&val| val / a0).collect();
+    b_norm.resize(n, 0.0);
+    a_norm.resize(n, 0.0);
+    Ok((b_norm, a_norm))
+}
+
+/// Direct form II transposed filtering, updating the state `z` in place
+///
+/// `b` and `a` must be normalized and of equal length `n`, and `z` of length
+/// `n - 1`.
+fn filter_with_state(b: &[f64], a: &[f64], x: &[f64], z: &mut [f64]) -> Vec<f64> {
+    let order = z.len();
+    x.iter()
+        .map(|&xi| {
+            let yi = b[0] * xi + z.first().copied().unwrap_or(0.0);
+            for j in 0..order {
+                let next = if j + 1 < order { z[j + 1] } else { 0.0 };
+                z[j] = b[j + 1] * xi + next - a[j + 1] * yi;
+            }
+            yi
+        })
+        .collect()
+}
+
+/// Initial state for which a unit step input produces a constant output
+///
+/// `b` and `a` must be normalized and of equal length.
+fn steady_state_zi(b: &[f64], a: &[f64]) -> SignalResult<Vec<f64>> {
+    let order = b.len() - 1;
+    if order == 0 {
+        return Ok(Vec::new());
+    }
 
-            z[j - 1] = b_term + next_z - a_term;
+    // Closed-form solution of (I - A^T) zi = b[1..] - a[1..] b[0]
+    let a_sum: f64 = a.iter().sum();
+    if a_sum.abs() < 1e-14 {
+        return Err(SignalError::ValueError(
+            "Filter has a pole at z = 1; no steady state exists".to_string(),
+        ));
+    }
+    let c_sum: f64 = (1..=order).map(|k| b[k] - a[k] * b[0]).sum();
+
+    let mut zi = vec![0.0; order];
+    zi[0] = c_sum / a_sum;
+    let mut a_partial = 1.0;
+    let mut c_partial = 0.0;
+    for k in 1..order {
+        a_partial += a[k];
+        c_partial += b[k] - a[k] * b[0];
+        zi[k] = a_partial * zi[0] - c_partial;
+    }
+    Ok(zi)
+}
+
+/// Extend `x` by `n` samples at each end
+fn extend_edges(x: &[f64], n: usize, padtype: PadType) -> Vec<f64> {
+    let last = x.len() - 1;
+    let (first_value, last_value) = (x[0], x[last]);
+    let left = (0..n).map(|i| {
+        let k = n - i;
+        match padtype {
+            PadType::Odd => 2.0 * first_value - x[k],
+            PadType::Even => x[k],
+            PadType::Constant | PadType::None => first_value,
         }
+    });
+    let right = (1..=n).map(|k| match padtype {
+        PadType::Odd => 2.0 * last_value - x[last - k],
+        PadType::Even => x[last - k],
+        PadType::Constant | PadType::None => last_value,
+    });
+
+    let mut extended = Vec::with_capacity(x.len() + 2 * n);
+    extended.extend(left);
+    extended.extend_from_slice(x);
+    extended.extend(right);
+    extended
+}
 
-        // Update last state variable if it exists
-        if !z.is_empty() {
-            let last = z.len() - 1;
-            let b_term = if last + 1 < b_norm.len() {
-                b_norm[last + 1] * x_f64[i]
-            } else {
-                0.0
-            };
-            let a_term = if last + 1 < a_norm.len() {
-                a_norm[last + 1] * y[i]
-            } else {
-                0.0
-            };
-            z[last] = b_term - a_term;
+/// Gustafsson's forward-backward filtering
+///
+/// The initial conditions of the forward and backward passes are chosen in
+/// the least-squares sense so that filtering forward-backward and
+/// backward-forward give the same result (F. Gustafsson, "Determining the
+/// initial states in forward-backward filtering", IEEE Trans. Signal
+/// Processing, 1996).
+fn filtfilt_gustafsson(
+    b: &[f64],
+    a: &[f64],
+    x: &[f64],
+    irlen: Option<usize>,
+) -> SignalResult<Vec<f64>> {
+    let order = b.len() - 1;
+    if order == 0 {
+        let scale = b[0] * b[0];
+        return Ok(x.iter().map(|&v| scale * v).collect());
+    }
+
+    let n = x.len();
+    let m = match irlen {
+        Some(len) if n > 2 * len => len,
+        _ => n,
+    };
+    let filter_from_rest = |input: &[f64]| {
+        let mut z = vec![0.0; order];
+        filter_with_state(b, a, input, &mut z)
+    };
+
+    // Observability matrix: zero-input response to each unit initial state
+    let mut unit = vec![0.0; order];
+    unit[0] = 1.0;
+    let response = filter_with_state(b, a, &vec![0.0; m], &mut unit);
+    let mut obs = Array2::zeros((m, order));
+    for k in 0..order {
+        for i in k..m {
+            obs[[i, k]] = response[i - k];
+        }
+    }
+
+    // S applies the filter to the row-reversed propagated initial conditions
+    let mut s = Array2::zeros((m, order));
+    for k in 0..order {
+        let reversed: Vec<f64> = (0..m).rev().map(|i| obs[[i, k]]).collect();
+        for (i, v) in filter_from_rest(&reversed).into_iter().enumerate() {
+            s[[i, k]] = v;
+        }
+    }
+
+    // M = [S^R - O, O^R - S] and W = [S^R, O^R]; block diagonal when truncated
+    let offset = if m == n { 0 } else { m };
+    let mut mat = Array2::zeros((m + offset, 2 * order));
+    let mut w = Array2::zeros((m + offset, 2 * order));
+    for i in 0..m {
+        for k in 0..order {
+            let s_rev = s[[m - 1 - i, k]];
+            let obs_rev = obs[[m - 1 - i, k]];
+            mat[[i, k]] = s_rev - obs[[i, k]];
+            mat[[offset + i, order + k]] = obs_rev - s[[i, k]];
+            w[[i, k]] = s_rev;
+            w[[offset + i, order + k]] = obs_rev;
         }
     }
 
+    // Naive forward-backward and backward-forward passes from rest
+    let mut reversed: Vec<f64> = filter_from_rest(x);
+    reversed.reverse();
+    let mut y_fb = filter_from_rest(&reversed);
+    y_fb.reverse();
+    let mut reversed = x.to_vec();
+    reversed.reverse();
+    let mut y_b = filter_from_rest(&reversed);
+    y_b.reverse();
+    let y_bf = filter_from_rest(&y_b);
+
+    let difference: Vec<f64> = y_bf.iter().zip(&y_fb).map(|(bf, fb)| bf - fb).collect();
+    let delta = if m == n {
+        Array1::from(difference)
+    } else {
+        difference[..m]
+            .iter()
+            .chain(&difference[n - m..])
+            .copied()
+            .collect()
+    };
+
+    let initial = scirs2_linalg::lstsq(&mat.view(), &delta.view(), None)
+        .map_err(|_| {
+            SignalError::ComputationError(
+                "Failed to solve for Gustafsson initial conditions".to_string(),
+            )
+        })?
+        .x;
+    let correction = w.dot(&initial);
+
+    let mut y = y_fb;
+    if m == n {
+        for (v, c) in y.iter_mut().zip(correction.iter()) {
+            *v += c;
+        }
+    } else {
+        for i in 0..m {
+            y[i] += correction[i];
+            y[n - m + i] += correction[m + i];
+        }
+    }
     Ok(y)
 }
 
@@ -268,15 +684,20 @@ pub fn minimum_phase(b: &[f64], discrete_time: bool) -> SignalResult<Vec<f64>> {
 /// Group delay is the negative derivative of the phase response with respect to frequency.
 /// It represents the time delay experienced by different frequency components.
 ///
+/// The delay is computed exactly from the filter coefficients: with
+/// `c(z) = B(z) A*(1/z*) z^-(len(a)-1)`, the group delay is
+/// `Re{(sum_k k c_k z^-k) / C(z)} - (len(a) - 1)`. Frequencies where the response
+/// vanishes (zeros on the unit circle) have an undefined delay and are reported as 0.
+///
 /// # Arguments
 ///
 /// * `b` - Numerator coefficients
 /// * `a` - Denominator coefficients
-/// * `w` - Frequency points (normalized from 0 to π)
+/// * `w` - Frequency points in radians per sample (normally 0 to π)
 ///
 /// # Returns
 ///
-/// * Group delay values at the specified frequencies
+/// * Group delay values in samples at the specified frequencies
 ///
 /// # Examples
 ///
@@ -288,6 +709,10 @@ pub fn minimum_phase(b: &[f64], discrete_time: bool) -> SignalResult<Vec<f64>> {
 /// let (b, a) = butter(4, 0.2, "lowpass").unwrap();
 /// let frequencies = (0..128).map(|i| std::f64::consts::PI * i as f64 / 127.0).collect::<Vec<_>>();
 /// let gd = group_delay(&b, &a, &frequencies).unwrap();
+///
+/// // A symmetric FIR filter delays every frequency by half its length
+/// let gd_fir = group_delay(&[1.0, 2.0, 3.0, 2.0, 1.0], &[1.0], &[0.3, 1.2]).unwrap();
+/// assert!((gd_fir[0] - 2.0).abs() < 1e-10 && (gd_fir[1] - 2.0).abs() < 1e-10);
 /// ```
 pub fn group_delay(b: &[f64], a: &[f64], w: &[f64]) -> SignalResult<Vec<f64>> {
     if a.is_empty() || a[0].abs() < 1e-10 {
@@ -295,34 +720,37 @@ pub fn group_delay(b: &[f64], a: &[f64], w: &[f64]) -> SignalResult<Vec<f64>> {
             "Invalid denominator coefficients".to_string(),
         ));
     }
+    if b.is_empty() {
+        return Err(SignalError::ValueError(
+            "Numerator coefficients are empty".to_string(),
+        ));
+    }
 
-    let mut gd = Vec::with_capacity(w.len());
-
-    for &freq in w {
-        // Compute the group delay using the derivative method
-        // gd = -d(phase)/dw = -d(arg(H(e^jw)))/dw
-
-        // For numerical computation, use a small frequency step
-        let eps = 1e-6;
-        let freq_minus = (freq - eps).max(0.0);
-        let freq_plus = (freq + eps).min(std::f64::consts::PI);
-
-        // Evaluate transfer function at freq-eps and freq+eps
-        let h_minus = evaluate_transfer_function(b, a, freq_minus);
-        let h_plus = evaluate_transfer_function(b, a, freq_plus);
-
-        // Compute phase difference and normalize by frequency difference
-        let phase_diff = h_plus.arg() - h_minus.arg();
-        let freq_diff = freq_plus - freq_minus;
+    // c = b * reversed(a), whose phase is that of B/A shifted by (len(a) - 1) samples
+    let a_rev: Vec<f64> = a.iter().rev().copied().collect();
+    let c = crate::convolve::convolve(b, &a_rev, "full")?;
+    let c_ramp: Vec<f64> = c.iter().enumerate().map(|(k, &v)| k as f64 * v).collect();
+    let offset = (a.len() - 1) as f64;
 
-        if freq_diff > 0.0 {
-            gd.push(-phase_diff / freq_diff);
-        } else {
-            gd.push(0.0);
-        }
-    }
+    Ok(w.iter()
+        .map(|&freq| {
+            let den = polyval_z_inv(&c, freq);
+            if den.norm() < 1e-12 * c.iter().map(|v| v.abs()).sum::<f64>().max(1e-300) {
+                0.0
+            } else {
+                (polyval_z_inv(&c_ramp, freq) / den).re - offset
+            }
+        })
+        .collect())
+}
 
-    Ok(gd)
+/// Evaluate `sum_k c_k e^(-j w k)` with Horner's scheme
+pub(crate) fn polyval_z_inv(coeffs: &[f64], w: f64) -> Complex64 {
+    let z_inv = Complex64::new(w.cos(), -w.sin());
+    coeffs
+        .iter()
+        .rev()
+        .fold(Complex64::zero(), |acc, &c| acc * z_inv + c)
 }
 
 /// Design a matched filter for detecting a known signal in noise
@@ -590,3 +1018,118 @@ fn polynomial_from_roots(roots: &[Complex64]) -> Vec<f64> {
     // Convert to real coefficients (imaginary parts should be small for conjugate pairs)
     poly.iter().map(|c| c.re).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::iir::butter;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_filtfilt_preserves_constant_and_phase() {
+        let (b, a) = butter(4, 0.2, "lowpass").unwrap();
+
+        // Steady-state initial conditions leave a constant untouched
+        let constant = vec![3.0; 50];
+        for padtype in [PadType::Odd, PadType::Even, PadType::Constant] {
+            let config = FiltfiltConfig {
+                padtype,
+                ..Default::default()
+            };
+            let y = filtfilt_with_config(&b, &a, &constant, &config).unwrap();
+            assert!(y.iter().all(|&v| (v - 3.0).abs() < 1e-10));
+        }
+
+        // A passband sinusoid comes through without delay
+        let x: Vec<f64> = (0..400)
+            .map(|i| (2.0 * PI * 0.02 * i as f64).sin())
+            .collect();
+        let y = filtfilt(&b, &a, &x).unwrap();
+        for i in 50..350 {
+            assert!((y[i] - x[i]).abs() < 1e-3);
+        }
+
+        let too_long = FiltfiltConfig {
+            padlen: Some(400),
+            ..Default::default()
+        };
+        assert!(filtfilt_with_config(&b, &a, &x, &too_long).is_err());
+    }
+
+    #[test]
+    fn test_filtfilt_gustafsson() {
+        let (b, a) = butter(3, 0.15, "lowpass").unwrap();
+        let x: Vec<f64> = (0..300)
+            .map(|i| 1.0 + (2.0 * PI * 0.01 * i as f64).sin() + 0.2 * (0.9 * PI * i as f64).cos())
+            .collect();
+
+        let full = FiltfiltConfig {
+            method: FiltfiltMethod::Gustafsson { irlen: None },
+            ..Default::default()
+        };
+        let y = filtfilt_with_config(&b, &a, &x, &full).unwrap();
+        assert_eq!(y.len(), x.len());
+        // The high frequency component is removed away from the edges
+        for (i, &v) in y.iter().enumerate().take(280).skip(20) {
+            let smooth = 1.0 + (2.0 * PI * 0.01 * i as f64).sin();
+            assert!((v - smooth).abs() < 0.01);
+        }
+
+        // Matching both pass orders recovers the steady state of a constant
+        let constant = vec![2.0; 60];
+        let y_const = filtfilt_with_config(&b, &a, &constant, &full).unwrap();
+        assert!(y_const.iter().all(|&v| (v - 2.0).abs() < 1e-8));
+
+        // A truncated impulse response gives nearly the same result
+        let truncated = FiltfiltConfig {
+            method: FiltfiltMethod::Gustafsson { irlen: Some(100) },
+            ..Default::default()
+        };
+        let y_trunc = filtfilt_with_config(&b, &a, &x, &truncated).unwrap();
+        for (u, v) in y.iter().zip(&y_trunc) {
+            assert!((u - v).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_lfilter_state_helpers() {
+        let (b, a) = butter(3, 0.3, "lowpass").unwrap();
+        let x: Vec<f64> = (0..90)
+            .map(|i| (0.37 * i as f64).cos() + 0.1 * i as f64)
+            .collect();
+        let whole = lfilter(&b, &a, &x).unwrap();
+
+        // Chunked processing with carried state matches one pass
+        let mut state = vec![0.0; 3];
+        let mut chunked = Vec::new();
+        for chunk in x.chunks(17) {
+            let (y, zf) = lfilter_with_zi(&b, &a, chunk, &state).unwrap();
+            chunked.extend(y);
+            state = zf;
+        }
+        for (u, v) in chunked.iter().zip(&whole) {
+            assert!((u - v).abs() < 1e-12);
+        }
+
+        // The history reconstructs the carried state
+        let split = 50;
+        let y_hist: Vec<f64> = whole[..split].iter().rev().copied().collect();
+        let x_hist: Vec<f64> = x[..split].iter().rev().copied().collect();
+        let zi = lfiltic(&b, &a, &y_hist, Some(&x_hist)).unwrap();
+        let (_, state_at_split) = lfilter_with_zi(&b, &a, &x[..split], &[0.0; 3]).unwrap();
+        for (u, v) in zi.iter().zip(&state_at_split) {
+            assert!((u - v).abs() < 1e-12);
+        }
+
+        // Steady state for a step scaled by the DC gain
+        let (b_hp, a_hp) = butter(2, 0.4, "highpass").unwrap();
+        let zi = lfilter_zi(&b_hp, &a_hp).unwrap();
+        let (y, zf) = lfilter_with_zi(&b_hp, &a_hp, &[1.0; 20], &zi).unwrap();
+        assert!(y.iter().all(|v| v.abs() < 1e-12));
+        for (u, v) in zi.iter().zip(&zf) {
+            assert!((u - v).abs() < 1e-12);
+        }
+
+        assert!(lfilter_with_zi(&b, &a, &x, &[0.0; 2]).is_err());
+    }
+}
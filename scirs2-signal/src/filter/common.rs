@@ -261,3 +261,5 @@ pub mod math {
 /// Type aliases for common filter coefficient representations
 pub type FilterCoefficients = (Vec<f64>, Vec<f64>);
 pub type ZerosPolesGain = (Vec<Complex64>, Vec<Complex64>, f64);
+/// Cascade of biquads, each stored as `[b0, b1, b2, a0, a1, a2]`
+pub type SecondOrderSections = Vec<[f64; 6]>;
@@ -1,11 +1,15 @@
 //! FIR (Finite Impulse Response) filter design functions
 //!
-//! This module provides comprehensive FIR filter design capabilities including
-//! window-based design (firwin) and optimal equiripple design (Parks-McClellan/Remez).
-//! FIR filters offer linear phase response and guaranteed stability.
+//! This module provides FIR filter design by the window method (`firwin`,
+//! `firwin_bands`), by frequency sampling (`firwin2`) and by optimal equiripple
+//! approximation (Parks-McClellan/Remez). FIR filters offer linear phase
+//! response and guaranteed stability.
 
 use crate::error::{SignalError, SignalResult};
+use crate::window::get_window;
+use num_complex::Complex64;
 use num_traits::{Float, NumCast};
+use std::f64::consts::PI;
 use std::fmt::Debug;
 
 use super::common::validation::validate_cutoff_frequency;
@@ -24,7 +28,8 @@ use super::common::validation::validate_cutoff_frequency;
 ///
 /// # Returns
 ///
-/// * Filter coefficients as a vector
+/// * Filter coefficients as a vector. A highpass filter requires an odd number
+///   of taps, since even-length linear phase filters vanish at Nyquist.
 ///
 /// # Examples
 ///
@@ -48,78 +53,330 @@ where
     }
 
     let wc = validate_cutoff_frequency(cutoff)?;
+    firwin_bands(numtaps, &[wc], window, pass_zero)
+}
 
-    // Calculate the ideal impulse response
-    let mid = (numtaps - 1) as f64 / 2.0;
-    let mut h = vec![0.0; numtaps];
+/// Multiband FIR filter design using the window method
+///
+/// The cutoff frequencies split `[0, 1]` into alternating pass and stop bands;
+/// `pass_zero` selects whether the first band (containing DC) passes. The ideal
+/// response is a sum of ideal lowpass sinc kernels, which is then windowed and
+/// scaled to unit gain at the center of the first passband (at DC or Nyquist
+/// when the passband touches either end).
+///
+/// # Arguments
+///
+/// * `numtaps` - Number of filter taps
+/// * `cutoffs` - Strictly increasing band edges in (0, 1), where 1 is Nyquist
+/// * `window` - Window function name accepted by [`crate::window::get_window`]
+/// * `pass_zero` - Whether the band starting at DC is a passband
+///
+/// # Returns
+///
+/// * Filter coefficients as a vector
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::fir::firwin_bands;
+///
+/// // Bandpass between 0.2 and 0.4 of Nyquist
+/// let h = firwin_bands(63, &[0.2, 0.4], "hamming", false).unwrap();
+/// assert_eq!(h.len(), 63);
+///
+/// // Two passbands: DC-0.1 and 0.5-0.7
+/// let h = firwin_bands(63, &[0.1, 0.5, 0.7], "blackman", true).unwrap();
+/// ```
+pub fn firwin_bands(
+    numtaps: usize,
+    cutoffs: &[f64],
+    window: &str,
+    pass_zero: bool,
+) -> SignalResult<Vec<f64>> {
+    if numtaps == 0 {
+        return Err(SignalError::ValueError(
+            "Number of taps must be positive".to_string(),
+        ));
+    }
+    if cutoffs.is_empty() {
+        return Err(SignalError::ValueError(
+            "At least one cutoff frequency is required".to_string(),
+        ));
+    }
+    if cutoffs.iter().any(|&c| !(c > 0.0 && c < 1.0)) {
+        return Err(SignalError::ValueError(
+            "Cutoff frequencies must be strictly between 0 and 1".to_string(),
+        ));
+    }
+    if cutoffs.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(SignalError::ValueError(
+            "Cutoff frequencies must be strictly increasing".to_string(),
+        ));
+    }
 
-    for (i, item) in h.iter_mut().enumerate() {
-        let n = i as f64 - mid;
+    let win = get_window(window, numtaps, false)?;
+    firwin_from_window(cutoffs, &win, pass_zero)
+}
 
-        if n == 0.0 {
-            // At n=0, use L'Hôpital's rule result
-            *item = if pass_zero {
-                wc / std::f64::consts::PI
-            } else {
-                1.0 - wc / std::f64::consts::PI
-            };
-        } else {
-            // General case: sinc function
-            let sinc_val = (wc * std::f64::consts::PI * n).sin() / (std::f64::consts::PI * n);
-            *item = if pass_zero {
-                sinc_val
-            } else {
-                // Highpass: subtract lowpass from delta function
-                if i == numtaps / 2 {
-                    1.0 - sinc_val
-                } else {
-                    -sinc_val
-                }
-            };
+/// Windowed-sinc multiband design with precomputed window values
+///
+/// Shared by [`firwin_bands`] and designs that need window parameters not
+/// expressible by name, such as a Kaiser window of a given shape. The number
+/// of taps is the window length; the cutoffs must already be validated.
+pub(crate) fn firwin_from_window(
+    cutoffs: &[f64],
+    win: &[f64],
+    pass_zero: bool,
+) -> SignalResult<Vec<f64>> {
+    let numtaps = win.len();
+    let pass_nyquist = (cutoffs.len() % 2 == 1) != pass_zero;
+    if pass_nyquist && numtaps.is_multiple_of(2) {
+        return Err(SignalError::ValueError(
+            "A filter with an even number of taps must have zero response at the Nyquist frequency"
+                .to_string(),
+        ));
+    }
+
+    // Pass band edges, always an even count
+    let mut edges = Vec::with_capacity(cutoffs.len() + 2);
+    if pass_zero {
+        edges.push(0.0);
+    }
+    edges.extend_from_slice(cutoffs);
+    if pass_nyquist {
+        edges.push(1.0);
+    }
+
+    let alpha = 0.5 * (numtaps - 1) as f64;
+    let mut h: Vec<f64> = win
+        .iter()
+        .enumerate()
+        .map(|(i, &w)| {
+            let m = i as f64 - alpha;
+            let ideal: f64 = edges
+                .chunks_exact(2)
+                .map(|band| band[1] * sinc(band[1] * m) - band[0] * sinc(band[0] * m))
+                .sum();
+            ideal * w
+        })
+        .collect();
+
+    // Unit gain at the center of the first passband
+    let (left, right) = (edges[0], edges[1]);
+    let scale_frequency = if left == 0.0 {
+        0.0
+    } else if right == 1.0 {
+        1.0
+    } else {
+        0.5 * (left + right)
+    };
+    let gain: f64 = h
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| v * (PI * (i as f64 - alpha) * scale_frequency).cos())
+        .sum();
+    if gain != 0.0 {
+        for v in &mut h {
+            *v /= gain;
         }
     }
 
-    // Apply window function
-    let window_coeffs = generate_window(numtaps, window)?;
-    for (i, coeff) in h.iter_mut().enumerate() {
-        *coeff *= window_coeffs[i];
+    Ok(h)
+}
+
+/// FIR filter design by frequency sampling
+///
+/// The desired gain is linearly interpolated from `(freq, gain)` onto a
+/// uniform grid of `1 + 2^ceil(log2(numtaps))` frequencies, given linear phase
+/// and transformed back to the time domain; the result is truncated to
+/// `numtaps` samples and windowed. A frequency may appear twice in a row to
+/// specify a step in the response.
+///
+/// The filter type follows from `numtaps` and `antisymmetric`:
+///
+/// | numtaps | antisymmetric | type | constraint |
+/// |---------|---------------|------|------------|
+/// | odd     | false         | I    | none |
+/// | even    | false         | II   | zero gain at Nyquist |
+/// | odd     | true          | III  | zero gain at DC and Nyquist |
+/// | even    | true          | IV   | zero gain at DC |
+///
+/// # Arguments
+///
+/// * `numtaps` - Number of filter taps
+/// * `freq` - Nondecreasing frequencies from 0 to 1 (1 is Nyquist)
+/// * `gain` - Desired gain at each frequency in `freq`
+/// * `window` - Window function name accepted by [`crate::window::get_window`]
+/// * `antisymmetric` - Design an antisymmetric (type III/IV) filter
+///
+/// # Returns
+///
+/// * Filter coefficients as a vector
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::fir::firwin2;
+///
+/// // Lowpass with a linear roll-off between 0.4 and 0.6
+/// let h = firwin2(75, &[0.0, 0.4, 0.6, 1.0], &[1.0, 1.0, 0.0, 0.0], "hamming", false).unwrap();
+/// assert_eq!(h.len(), 75);
+/// ```
+pub fn firwin2(
+    numtaps: usize,
+    freq: &[f64],
+    gain: &[f64],
+    window: &str,
+    antisymmetric: bool,
+) -> SignalResult<Vec<f64>> {
+    if numtaps == 0 {
+        return Err(SignalError::ValueError(
+            "Number of taps must be positive".to_string(),
+        ));
+    }
+    if freq.len() != gain.len() {
+        return Err(SignalError::ValueError(
+            "freq and gain must have the same length".to_string(),
+        ));
+    }
+    let nf = freq.len();
+    if nf < 2 {
+        return Err(SignalError::ValueError(
+            "At least two frequency points are required".to_string(),
+        ));
+    }
+    if freq[0] != 0.0 || freq[nf - 1] != 1.0 {
+        return Err(SignalError::ValueError(
+            "freq must start with 0 and end with 1 (Nyquist)".to_string(),
+        ));
+    }
+    if freq.windows(2).any(|w| w[1] < w[0]) {
+        return Err(SignalError::ValueError(
+            "freq must be nondecreasing".to_string(),
+        ));
+    }
+    if freq.windows(3).any(|w| w[0] == w[2]) {
+        return Err(SignalError::ValueError(
+            "A frequency may be repeated at most twice".to_string(),
+        ));
+    }
+    if freq[0] == freq[1] || freq[nf - 1] == freq[nf - 2] {
+        return Err(SignalError::ValueError(
+            "freq must not repeat 0 or 1".to_string(),
+        ));
+    }
+
+    let odd = !numtaps.is_multiple_of(2);
+    let (dc, nyquist) = (gain[0], gain[nf - 1]);
+    match (antisymmetric, odd) {
+        (false, false) if nyquist != 0.0 => {
+            return Err(SignalError::ValueError(
+                "A type II filter must have zero gain at the Nyquist frequency".to_string(),
+            ));
+        }
+        (true, true) if dc != 0.0 || nyquist != 0.0 => {
+            return Err(SignalError::ValueError(
+                "A type III filter must have zero gain at DC and the Nyquist frequency".to_string(),
+            ));
+        }
+        (true, false) if dc != 0.0 => {
+            return Err(SignalError::ValueError(
+                "A type IV filter must have zero gain at DC".to_string(),
+            ));
+        }
+        _ => {}
     }
 
-    // Normalize to ensure unity gain at DC (for lowpass) or Nyquist (for highpass)
-    let sum: f64 = h.iter().sum();
-    if pass_zero && sum.abs() > 1e-10 {
-        for coeff in &mut h {
-            *coeff /= sum;
+    // Pull repeated frequencies apart so interpolation sees a step
+    let mut knots = freq.to_vec();
+    for k in 0..nf - 1 {
+        if freq[k] == freq[k + 1] {
+            knots[k] -= f64::EPSILON;
+            knots[k + 1] += f64::EPSILON;
         }
-    } else if !pass_zero {
-        // For highpass, normalize for unity gain at Nyquist
-        let nyquist_response: f64 = h
-            .iter()
-            .enumerate()
-            .map(|(i, &coeff)| coeff * (-1.0_f64).powi(i as i32))
-            .sum();
-        if nyquist_response.abs() > 1e-10 {
-            for coeff in &mut h {
-                *coeff /= nyquist_response;
+    }
+
+    let nfreqs = 1 + numtaps.next_power_of_two();
+    let delay = 0.5 * (numtaps - 1) as f64;
+    let spectrum: Vec<Complex64> = (0..nfreqs)
+        .map(|k| {
+            let x = k as f64 / (nfreqs - 1) as f64;
+            let value = Complex64::from_polar(interp_linear(x, &knots, gain), -delay * PI * x);
+            if antisymmetric {
+                value * Complex64::i()
+            } else {
+                value
             }
-        }
+        })
+        .collect();
+
+    // Inverse real DFT of length 2 * (nfreqs - 1), truncated to numtaps samples
+    let len = 2 * (nfreqs - 1);
+    let win = get_window(window, numtaps, false)?;
+    let mut h: Vec<f64> = win
+        .iter()
+        .enumerate()
+        .map(|(n, &w)| {
+            let nyquist_sign = if n.is_multiple_of(2) { 1.0 } else { -1.0 };
+            let mut acc = spectrum[0].re + nyquist_sign * spectrum[nfreqs - 1].re;
+            for (k, s) in spectrum.iter().enumerate().take(nfreqs - 1).skip(1) {
+                let theta = 2.0 * PI * (k * n % len) as f64 / len as f64;
+                acc += 2.0 * (s.re * theta.cos() - s.im * theta.sin());
+            }
+            acc / len as f64 * w
+        })
+        .collect();
+
+    if antisymmetric && odd {
+        h[numtaps / 2] = 0.0;
     }
 
     Ok(h)
 }
 
+/// Impulse response symmetry for Parks-McClellan design
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RemezFilterType {
+    /// Symmetric impulse response (types I/II): lowpass, highpass, bandpass, multiband
+    #[default]
+    Bandpass,
+    /// Antisymmetric impulse response (types III/IV) with the error in bands of
+    /// nonzero desired gain weighted relative to frequency
+    Differentiator,
+    /// Antisymmetric impulse response (types III/IV) approximating a constant
+    /// gain with a 90 degree phase shift
+    Hilbert,
+}
+
+/// Parks-McClellan design together with convergence diagnostics
+#[derive(Debug, Clone)]
+pub struct RemezResult {
+    /// Filter coefficients
+    pub coefficients: Vec<f64>,
+    /// Peak weighted deviation from the desired response (the ripple of an
+    /// equiripple solution)
+    pub deviation: f64,
+    /// Number of exchange iterations performed
+    pub iterations: usize,
+    /// Whether the extremal errors equalized before `max_iter` was reached
+    pub converged: bool,
+    /// Final extremal frequencies (normalized, 1 is Nyquist)
+    pub extremal_frequencies: Vec<f64>,
+}
+
 /// Parks-McClellan optimal FIR filter design (Remez exchange algorithm)
 ///
 /// Design a linear phase FIR filter using the Parks-McClellan algorithm.
 /// The algorithm finds the filter coefficients that minimize the maximum
-/// error between the desired and actual frequency response.
+/// weighted error between the desired and actual frequency response.
+/// See [`remez_design`] for antisymmetric designs and convergence diagnostics.
 ///
 /// # Arguments
 ///
 /// * `numtaps` - Number of filter taps (filter order + 1)
 /// * `bands` - Frequency bands specified as pairs of band edges (0 to 1, where 1 is Nyquist)
-/// * `desired` - Desired gain for each band
-/// * `weights` - Relative weights for each band (optional)
+/// * `desired` - Desired gain at each band edge, linearly interpolated within a band
+/// * `weights` - Relative weights at each band edge (optional)
 /// * `max_iter` - Maximum number of iterations (default: 25)
 /// * `grid_density` - Grid density for frequency sampling (default: 16)
 ///
@@ -146,6 +403,60 @@ pub fn remez(
     max_iter: Option<usize>,
     grid_density: Option<usize>,
 ) -> SignalResult<Vec<f64>> {
+    remez_design(
+        numtaps,
+        bands,
+        desired,
+        weights,
+        RemezFilterType::Bandpass,
+        max_iter,
+        grid_density,
+    )
+    .map(|result| result.coefficients)
+}
+
+/// Parks-McClellan design with a selectable filter type and diagnostics
+///
+/// Runs the Remez exchange on a dense grid: at each iteration the weighted
+/// error is equalized on the current extremal set through barycentric
+/// Lagrange interpolation, and the set is replaced by the alternating local
+/// extrema of the error. Iteration stops once the extremal errors agree to a
+/// relative tolerance of `1e-4`.
+///
+/// # Arguments
+///
+/// * `numtaps` - Number of filter taps (at least 3)
+/// * `bands` - Frequency bands specified as pairs of band edges (0 to 1, where 1 is Nyquist)
+/// * `desired` - Desired gain at each band edge, linearly interpolated within a band
+/// * `weights` - Relative weights at each band edge (optional)
+/// * `filter_type` - Symmetric bandpass, differentiator or Hilbert transformer
+/// * `max_iter` - Maximum number of iterations (default: 25)
+/// * `grid_density` - Grid density for frequency sampling (default: 16)
+///
+/// # Returns
+///
+/// * The coefficients with the achieved deviation and convergence information
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::fir::{remez_design, RemezFilterType};
+///
+/// // 31-tap Hilbert transformer over 0.1-0.9 of Nyquist
+/// let result = remez_design(31, &[0.1, 0.9], &[1.0, 1.0], None, RemezFilterType::Hilbert, None, None)
+///     .unwrap();
+/// assert!(result.converged);
+/// assert!(result.deviation < 0.01);
+/// ```
+pub fn remez_design(
+    numtaps: usize,
+    bands: &[f64],
+    desired: &[f64],
+    weights: Option<&[f64]>,
+    filter_type: RemezFilterType,
+    max_iter: Option<usize>,
+    grid_density: Option<usize>,
+) -> SignalResult<RemezResult> {
     // Validate inputs
     if numtaps < 3 {
         return Err(SignalError::ValueError(
@@ -153,7 +464,7 @@ pub fn remez(
         ));
     }
 
-    if bands.len() % 2 != 0 || bands.len() < 2 {
+    if !bands.len().is_multiple_of(2) || bands.len() < 2 {
         return Err(SignalError::ValueError(
             "Bands must be specified as pairs of edges".to_string(),
         ));
@@ -165,6 +476,19 @@ pub fn remez(
         ));
     }
 
+    if let Some(w) = weights {
+        if w.len() != bands.len() {
+            return Err(SignalError::ValueError(
+                "Weights array must have same length as bands".to_string(),
+            ));
+        }
+        if w.iter().any(|&v| v <= 0.0) {
+            return Err(SignalError::ValueError(
+                "Weights must be positive".to_string(),
+            ));
+        }
+    }
+
     // Check that bands are monotonically increasing
     for i in 1..bands.len() {
         if bands[i] <= bands[i - 1] {
@@ -182,275 +506,476 @@ pub fn remez(
     }
 
     let max_iter = max_iter.unwrap_or(25);
-    let grid_density = grid_density.unwrap_or(16);
-
-    // Calculate filter order
-    let filter_order = numtaps - 1;
-
-    // Number of extremal frequencies
-    let r = (filter_order + 2) / 2;
-
-    // Set up the dense frequency grid
-    let grid_size = grid_density * filter_order;
-    let mut omega_grid = Vec::with_capacity(grid_size);
-    let mut desired_grid = Vec::with_capacity(grid_size);
-    let mut weight_grid = Vec::with_capacity(grid_size);
-
-    // Build the frequency grid for each band
-    let num_bands = bands.len() / 2;
-    for band_idx in 0..num_bands {
-        let band_start = bands[2 * band_idx];
-        let band_end = bands[2 * band_idx + 1];
-        let band_points = ((band_end - band_start) * grid_size as f64).round() as usize;
-
-        for i in 0..band_points {
-            let omega =
-                band_start + (band_end - band_start) * (i as f64) / (band_points as f64 - 1.0);
-            omega_grid.push(omega * std::f64::consts::PI);
-
-            // Linear interpolation for desired response
-            let t = (omega - band_start) / (band_end - band_start);
-            let des = desired[2 * band_idx] * (1.0 - t) + desired[2 * band_idx + 1] * t;
-            desired_grid.push(des);
-
-            // Set weights
-            if let Some(w) = weights {
-                let wt = w[2 * band_idx] * (1.0 - t) + w[2 * band_idx + 1] * t;
-                weight_grid.push(wt);
+    let grid_density = grid_density.unwrap_or(16).max(1);
+
+    let antisymmetric = filter_type != RemezFilterType::Bandpass;
+    let odd = !numtaps.is_multiple_of(2);
+
+    // Number of basis functions; the extremal set holds r + 1 frequencies
+    let r = if odd && !antisymmetric {
+        numtaps / 2 + 1
+    } else {
+        numtaps / 2
+    };
+
+    // Dense grid in cycles per sample (0 to 0.5)
+    let delf = 0.5 / (grid_density * r) as f64;
+    let mut grid = Vec::new();
+    let mut target = Vec::new();
+    let mut weight = Vec::new();
+    for (band, edges) in bands.chunks_exact(2).enumerate() {
+        let highf = edges[1] / 2.0;
+        let mut lowf = edges[0] / 2.0;
+        // Antisymmetric responses vanish at DC
+        if antisymmetric && band == 0 && lowf < delf {
+            lowf = delf.min(highf);
+        }
+        let count = (((highf - lowf) / delf + 0.5) as usize).max(1);
+        for i in 0..count {
+            let f = if i + 1 == count {
+                highf
             } else {
-                weight_grid.push(1.0);
+                lowf + i as f64 * delf
+            };
+            let t = ((2.0 * f - edges[0]) / (edges[1] - edges[0])).clamp(0.0, 1.0);
+            let d = desired[2 * band] + t * (desired[2 * band + 1] - desired[2 * band]);
+            let mut w = weights.map_or(1.0, |w| w[2 * band] + t * (w[2 * band + 1] - w[2 * band]));
+            if filter_type == RemezFilterType::Differentiator && d.abs() > 1e-4 {
+                w /= 2.0 * f;
             }
+            grid.push(f);
+            target.push(d);
+            weight.push(w);
         }
     }
 
-    // Initialize extremal frequencies uniformly
-    let mut extremal_freqs = Vec::with_capacity(r);
-    for i in 0..r {
-        extremal_freqs.push(i * (omega_grid.len() - 1) / (r - 1));
+    // Types II and III vanish at Nyquist; keep the last grid point off it
+    let last = grid.len() - 1;
+    if antisymmetric == odd && grid[last] > 0.5 - delf {
+        grid[last] = 0.5 - delf;
     }
 
-    // Remez exchange algorithm
-    let mut h = vec![0.0; numtaps];
-    let mut best_error = f64::MAX;
-
-    for _iter in 0..max_iter {
-        // Step 1: Calculate the polynomial using the extremal frequencies
-        let mut a_matrix = vec![vec![0.0; r]; r];
-        let mut b_vector = vec![0.0; r];
-
-        for (i, &ext_idx) in extremal_freqs.iter().enumerate() {
-            let omega = omega_grid[ext_idx];
-
-            // Fill the matrix for the linear system
-            for j in 0..(r - 1) {
-                a_matrix[i][j] = (j as f64 * omega).cos();
-            }
-            // Last column alternates signs
-            a_matrix[i][r - 1] = if i % 2 == 0 { 1.0 } else { -1.0 } / weight_grid[ext_idx];
-
-            b_vector[i] = desired_grid[ext_idx];
-        }
-
-        // Solve the linear system to get polynomial coefficients
-        let coeffs = solve_linear_system(&a_matrix, &b_vector)?;
-
-        // Step 2: Calculate error on the dense grid
-        let mut errors = Vec::with_capacity(omega_grid.len());
-        let mut max_error = 0.0;
-
-        for i in 0..omega_grid.len() {
-            let omega = omega_grid[i];
-
-            // Evaluate the polynomial
-            let mut p_omega = 0.0;
-            for (j, &coeff) in coeffs.iter().enumerate().take(r - 1) {
-                p_omega += coeff * (j as f64 * omega).cos();
-            }
-
-            let error = (desired_grid[i] - p_omega) * weight_grid[i];
-            errors.push(error.abs());
-
-            if error.abs() > max_error {
-                max_error = error.abs();
-            }
-        }
+    if grid.len() < r + 1 {
+        return Err(SignalError::ValueError(
+            "Frequency grid is too coarse for the filter length; increase grid_density".to_string(),
+        ));
+    }
 
-        // Step 3: Find new extremal frequencies
-        let mut new_extremal = Vec::new();
+    // Factor out the fixed cos/sin term of the amplitude response
+    for ((f, d), w) in grid.iter().zip(target.iter_mut()).zip(weight.iter_mut()) {
+        let c = symmetry_factor(*f, numtaps, antisymmetric);
+        *d /= c;
+        *w *= c;
+    }
 
-        // Find local maxima in the error function
-        for i in 1..(errors.len() - 1) {
-            if errors[i] >= errors[i - 1] && errors[i] >= errors[i + 1] {
-                new_extremal.push(i);
-            }
+    let mut ext: Vec<usize> = (0..=r).map(|i| i * (grid.len() - 1) / r).collect();
+    let mut x = vec![0.0; r + 1];
+    let mut ad = vec![0.0; r + 1];
+    let mut y = vec![0.0; r + 1];
+    let mut error = vec![0.0; grid.len()];
+
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < max_iter {
+        iterations += 1;
+        remez_parameters(&grid, &target, &weight, &ext, &mut x, &mut ad, &mut y);
+        for (i, e) in error.iter_mut().enumerate() {
+            *e = weight[i] * (target[i] - barycentric_amplitude(grid[i], &x, &ad, &y));
         }
-
-        // Add boundaries if they are extremal
-        if errors[0] > errors[1] {
-            new_extremal.insert(0, 0);
+        if !remez_search(&mut ext, &error) {
+            break;
         }
-        if errors[errors.len() - 1] > errors[errors.len() - 2] {
-            new_extremal.push(errors.len() - 1);
+        if extremal_errors_equalized(&ext, &error) {
+            converged = true;
+            break;
         }
+    }
+    let delta = remez_parameters(&grid, &target, &weight, &ext, &mut x, &mut ad, &mut y);
+
+    // Sample the amplitude response at k / numtaps and invert
+    let amplitude: Vec<f64> = (0..=numtaps / 2)
+        .map(|k| {
+            let f = k as f64 / numtaps as f64;
+            barycentric_amplitude(f, &x, &ad, &y) * symmetry_factor(f, numtaps, antisymmetric)
+        })
+        .collect();
+    let coefficients = frequency_sample(numtaps, &amplitude, antisymmetric);
+
+    Ok(RemezResult {
+        coefficients,
+        deviation: delta.abs(),
+        iterations,
+        converged,
+        extremal_frequencies: ext.iter().map(|&i| 2.0 * grid[i]).collect(),
+    })
+}
 
-        // Select r extremal points with alternating signs
-        if new_extremal.len() >= r {
-            // Sort by error magnitude
-            new_extremal.sort_by(|&a, &b| errors[b].partial_cmp(&errors[a]).unwrap());
-
-            // Keep the r largest errors
-            new_extremal.truncate(r);
-            new_extremal.sort();
+/// Normalized sinc function sin(pi x) / (pi x)
+pub(crate) fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let px = PI * x;
+        px.sin() / px
+    }
+}
 
-            extremal_freqs = new_extremal;
-        }
+/// Piecewise linear interpolation, clamped outside the knots
+fn interp_linear(x: f64, knots: &[f64], values: &[f64]) -> f64 {
+    let last = knots.len() - 1;
+    if x <= knots[0] {
+        return values[0];
+    }
+    if x >= knots[last] {
+        return values[last];
+    }
+    let i = knots.partition_point(|&k| k <= x) - 1;
+    let span = knots[i + 1] - knots[i];
+    if span <= 0.0 {
+        return values[i + 1];
+    }
+    values[i] + (x - knots[i]) / span * (values[i + 1] - values[i])
+}
 
-        // Check convergence
-        if max_error < best_error {
-            best_error = max_error;
+/// Fixed factor of the amplitude response at `f` cycles per sample
+///
+/// Types II, III and IV are a cosine polynomial times cos(pi f), sin(2 pi f)
+/// and sin(pi f) respectively; type I has no such factor.
+fn symmetry_factor(f: f64, numtaps: usize, antisymmetric: bool) -> f64 {
+    let odd = !numtaps.is_multiple_of(2);
+    match (antisymmetric, odd) {
+        (false, true) => 1.0,
+        (false, false) => (PI * f).cos(),
+        (true, true) => (2.0 * PI * f).sin(),
+        (true, false) => (PI * f).sin(),
+    }
+}
 
-            // Convert polynomial coefficients to filter coefficients
-            for (i, coeff) in h.iter_mut().enumerate() {
-                let n = i as f64 - (numtaps as f64 - 1.0) / 2.0;
+/// Solve for the equiripple deviation on the extremal set
+///
+/// Fills the interpolation abscissae `x`, barycentric weights `ad` and
+/// ordinates `y`, and returns the signed deviation.
+fn remez_parameters(
+    grid: &[f64],
+    target: &[f64],
+    weight: &[f64],
+    ext: &[usize],
+    x: &mut [f64],
+    ad: &mut [f64],
+    y: &mut [f64],
+) -> f64 {
+    let r = ext.len() - 1;
+    for (xi, &e) in x.iter_mut().zip(ext) {
+        *xi = (2.0 * PI * grid[e]).cos();
+    }
 
-                *coeff = 0.0;
-                for (j, &c) in coeffs.iter().enumerate().take(r - 1) {
-                    if j == 0 {
-                        *coeff += c;
-                    } else {
-                        let freq = j as f64 * std::f64::consts::PI / (numtaps as f64 - 1.0);
-                        *coeff += 2.0 * c * (freq * n).cos();
-                    }
+    // Interleave the product to keep it from under- or overflowing
+    let ld = (r - 1) / 15 + 1;
+    for i in 0..=r {
+        let mut denom = 1.0;
+        for j in 0..ld {
+            for k in (j..=r).step_by(ld) {
+                if k != i {
+                    denom *= 2.0 * (x[i] - x[k]);
                 }
-                *coeff /= numtaps as f64;
             }
         }
-
-        // Check if converged
-        if max_error - best_error < 1e-10 {
-            break;
+        if denom.abs() < 1e-5 {
+            denom = 1e-5;
         }
+        ad[i] = 1.0 / denom;
     }
 
-    // Make filter symmetric
-    let mid = numtaps / 2;
-    for i in 0..mid {
-        let avg = (h[i] + h[numtaps - 1 - i]) / 2.0;
-        h[i] = avg;
-        h[numtaps - 1 - i] = avg;
+    let mut numer = 0.0;
+    let mut denom = 0.0;
+    let mut sign = 1.0;
+    for (i, &e) in ext.iter().enumerate() {
+        numer += ad[i] * target[e];
+        denom += sign * ad[i] / weight[e];
+        sign = -sign;
     }
+    let delta = numer / denom;
 
-    Ok(h)
+    sign = 1.0;
+    for (i, &e) in ext.iter().enumerate() {
+        y[i] = target[e] - sign * delta / weight[e];
+        sign = -sign;
+    }
+    delta
 }
 
-/// Generate a window function
-///
-/// Creates a window function of the specified type and length.
-///
-/// # Arguments
-///
-/// * `length` - Window length
-/// * `window_type` - Window type ("hamming", "hann", "blackman", "kaiser", etc.)
-///
-/// # Returns
-///
-/// * Window coefficients as a vector
-fn generate_window(length: usize, window_type: &str) -> SignalResult<Vec<f64>> {
-    let mut window = vec![0.0; length];
+/// Evaluate the interpolating polynomial at `f` cycles per sample
+fn barycentric_amplitude(f: f64, x: &[f64], ad: &[f64], y: &[f64]) -> f64 {
+    let xc = (2.0 * PI * f).cos();
+    let mut numer = 0.0;
+    let mut denom = 0.0;
+    for i in 0..x.len() {
+        let diff = xc - x[i];
+        if diff.abs() < 1e-7 {
+            return y[i];
+        }
+        let c = ad[i] / diff;
+        denom += c;
+        numer += c * y[i];
+    }
+    numer / denom
+}
 
-    match window_type.to_lowercase().as_str() {
-        "hamming" => {
-            for (i, w) in window.iter_mut().enumerate() {
-                let n = i as f64;
-                let total = length as f64;
-                *w = 0.54 - 0.46 * (2.0 * std::f64::consts::PI * n / (total - 1.0)).cos();
-            }
+/// Replace the extremal set by the alternating local extrema of the error
+///
+/// Returns false when fewer extrema than required are found, leaving `ext`
+/// unchanged.
+fn remez_search(ext: &mut [usize], e: &[f64]) -> bool {
+    let n = e.len();
+    let required = ext.len();
+    let mut found = Vec::with_capacity(2 * required);
+
+    if (e[0] > 0.0 && e[0] > e[1]) || (e[0] < 0.0 && e[0] < e[1]) {
+        found.push(0);
+    }
+    for i in 1..n - 1 {
+        if (e[i] >= e[i - 1] && e[i] > e[i + 1] && e[i] > 0.0)
+            || (e[i] <= e[i - 1] && e[i] < e[i + 1] && e[i] < 0.0)
+        {
+            found.push(i);
         }
-        "hann" | "hanning" => {
-            for (i, w) in window.iter_mut().enumerate() {
-                let n = i as f64;
-                let total = length as f64;
-                *w = 0.5 * (1.0 - (2.0 * std::f64::consts::PI * n / (total - 1.0)).cos());
+    }
+    let j = n - 1;
+    if (e[j] > 0.0 && e[j] > e[j - 1]) || (e[j] < 0.0 && e[j] < e[j - 1]) {
+        found.push(j);
+    }
+
+    if found.len() < required {
+        return false;
+    }
+
+    // Drop surplus extrema, resolving non-alternating pairs first
+    while found.len() > required {
+        let k = found.len();
+        let mut up = e[found[0]] > 0.0;
+        let mut smallest = 0;
+        let mut alternating = true;
+        for j in 1..k {
+            if e[found[j]].abs() < e[found[smallest]].abs() {
+                smallest = j;
             }
-        }
-        "blackman" => {
-            for (i, w) in window.iter_mut().enumerate() {
-                let n = i as f64;
-                let total = length as f64;
-                let arg = 2.0 * std::f64::consts::PI * n / (total - 1.0);
-                *w = 0.42 - 0.5 * arg.cos() + 0.08 * (2.0 * arg).cos();
+            if up && e[found[j]] < 0.0 {
+                up = false;
+            } else if !up && e[found[j]] > 0.0 {
+                up = true;
+            } else {
+                alternating = false;
+                break;
             }
         }
-        "rectangular" | "boxcar" => {
-            window.fill(1.0);
-        }
-        _ => {
-            return Err(SignalError::ValueError(format!(
-                "Unknown window type: {}. Supported types: hamming, hann, blackman, rectangular",
-                window_type
-            )));
+        if alternating && k - required == 1 {
+            smallest = if e[found[k - 1]].abs() < e[found[0]].abs() {
+                k - 1
+            } else {
+                0
+            };
         }
+        found.remove(smallest);
     }
 
-    Ok(window)
+    ext.copy_from_slice(&found);
+    true
 }
 
-/// Solve a linear system Ax = b using Gaussian elimination
-///
-/// Internal helper function for the Remez exchange algorithm.
-fn solve_linear_system(a: &[Vec<f64>], b: &[f64]) -> SignalResult<Vec<f64>> {
-    let n = a.len();
-    if n == 0 || a[0].len() != n || b.len() != n {
-        return Err(SignalError::ValueError(
-            "Invalid matrix dimensions".to_string(),
-        ));
+/// Whether the error magnitudes on the extremal set agree to `1e-4`
+fn extremal_errors_equalized(ext: &[usize], e: &[f64]) -> bool {
+    let (min, max) = ext.iter().fold((f64::INFINITY, 0.0_f64), |(lo, hi), &i| {
+        (lo.min(e[i].abs()), hi.max(e[i].abs()))
+    });
+    max == 0.0 || (max - min) / max < 1e-4
+}
+
+/// Impulse response from amplitude samples at k / numtaps
+fn frequency_sample(numtaps: usize, amplitude: &[f64], antisymmetric: bool) -> Vec<f64> {
+    let n_f = numtaps as f64;
+    let mid = (n_f - 1.0) / 2.0;
+    let odd = !numtaps.is_multiple_of(2);
+    let terms = if odd { numtaps / 2 } else { numtaps / 2 - 1 };
+
+    (0..numtaps)
+        .map(|n| {
+            let t = n as f64 - mid;
+            let arg = 2.0 * PI * t / n_f;
+            let mut value = if !antisymmetric {
+                amplitude[0]
+            } else if odd {
+                0.0
+            } else {
+                amplitude[numtaps / 2] * (PI * t).sin()
+            };
+            for (k, &a) in amplitude.iter().enumerate().take(terms + 1).skip(1) {
+                let phase = arg * k as f64;
+                value += 2.0
+                    * a
+                    * if antisymmetric {
+                        phase.sin()
+                    } else {
+                        phase.cos()
+                    };
+            }
+            value / n_f
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn magnitude(h: &[f64], f: f64) -> f64 {
+        let w = PI * f;
+        h.iter()
+            .enumerate()
+            .map(|(n, &c)| Complex64::from_polar(c, -w * n as f64))
+            .sum::<Complex64>()
+            .norm()
     }
 
-    // Create augmented matrix
-    let mut aug = vec![vec![0.0; n + 1]; n];
-    for i in 0..n {
-        for j in 0..n {
-            aug[i][j] = a[i][j];
-        }
-        aug[i][n] = b[i];
+    #[test]
+    fn test_firwin_gains() {
+        let lp = firwin(31, 0.3, "hamming", true).unwrap();
+        assert!((magnitude(&lp, 0.0) - 1.0).abs() < 1e-12);
+        assert!(magnitude(&lp, 0.6) < 0.01);
+
+        let hp = firwin(31, 0.3, "hamming", false).unwrap();
+        assert!((magnitude(&hp, 1.0) - 1.0).abs() < 1e-12);
+        assert!(magnitude(&hp, 0.0) < 0.01);
+        assert!(firwin(30, 0.3, "hamming", false).is_err());
+
+        let bp = firwin_bands(61, &[0.3, 0.5], "hamming", false).unwrap();
+        assert!((magnitude(&bp, 0.4) - 1.0).abs() < 1e-12);
+        assert!(magnitude(&bp, 0.0) < 0.01);
+        assert!(magnitude(&bp, 1.0) < 0.01);
     }
 
-    // Forward elimination
-    for i in 0..n {
-        // Find pivot
-        let mut max_row = i;
-        for k in (i + 1)..n {
-            if aug[k][i].abs() > aug[max_row][i].abs() {
-                max_row = k;
-            }
+    #[test]
+    fn test_firwin2_response() {
+        let h = firwin2(
+            101,
+            &[0.0, 0.5, 0.5, 1.0],
+            &[1.0, 1.0, 0.0, 0.0],
+            "hamming",
+            false,
+        )
+        .unwrap();
+        for i in 0..h.len() / 2 {
+            assert!((h[i] - h[h.len() - 1 - i]).abs() < 1e-12);
+        }
+        assert!((magnitude(&h, 0.25) - 1.0).abs() < 0.01);
+        assert!(magnitude(&h, 0.75) < 0.01);
+
+        // Type III differentiator-like ramp
+        let h = firwin2(51, &[0.0, 0.5, 1.0], &[0.0, 0.5, 0.0], "hann", true).unwrap();
+        for i in 0..h.len() / 2 {
+            assert!((h[i] + h[h.len() - 1 - i]).abs() < 1e-12);
         }
+        assert_eq!(h[25], 0.0);
+        assert!((magnitude(&h, 0.25) - 0.25).abs() < 0.01);
 
-        // Swap rows
-        aug.swap(i, max_row);
+        assert!(firwin2(100, &[0.0, 1.0], &[1.0, 1.0], "hamming", false).is_err());
+        assert!(firwin2(51, &[0.0, 1.0], &[1.0, 0.0], "hamming", true).is_err());
+    }
 
-        // Check for zero pivot
-        if aug[i][i].abs() < 1e-10 {
-            return Err(SignalError::ValueError("Singular matrix".to_string()));
+    #[test]
+    fn test_remez_lowpass_equiripple() {
+        let result = remez_design(
+            65,
+            &[0.0, 0.4, 0.5, 1.0],
+            &[1.0, 1.0, 0.0, 0.0],
+            None,
+            RemezFilterType::Bandpass,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(result.converged);
+        let h = &result.coefficients;
+        for i in 0..h.len() / 2 {
+            assert!((h[i] - h[h.len() - 1 - i]).abs() < 1e-12);
         }
 
-        // Eliminate column
-        for k in (i + 1)..n {
-            let factor = aug[k][i] / aug[i][i];
-            for j in i..=n {
-                aug[k][j] -= factor * aug[i][j];
+        let delta = result.deviation;
+        assert!(delta > 0.0 && delta < 0.01);
+        let mut pass_err = 0.0_f64;
+        let mut stop_err = 0.0_f64;
+        for i in 0..=1000 {
+            let f = i as f64 / 1000.0;
+            if f <= 0.4 {
+                pass_err = pass_err.max((magnitude(h, f) - 1.0).abs());
+            } else if f >= 0.5 {
+                stop_err = stop_err.max(magnitude(h, f));
             }
         }
+        // Equiripple: both bands peak at the reported deviation
+        assert!((pass_err - delta).abs() < 0.02 * delta);
+        assert!((stop_err - delta).abs() < 0.02 * delta);
+
+        // Weighting trades passband for stopband ripple
+        let weighted = remez(
+            65,
+            &[0.0, 0.4, 0.5, 1.0],
+            &[1.0, 1.0, 0.0, 0.0],
+            Some(&[1.0, 1.0, 10.0, 10.0]),
+            None,
+            None,
+        )
+        .unwrap();
+        let stop_weighted = (500..=1000)
+            .map(|i| magnitude(&weighted, i as f64 / 1000.0))
+            .fold(0.0, f64::max);
+        assert!(stop_weighted < stop_err);
     }
 
-    // Back substitution
-    let mut x = vec![0.0; n];
-    for i in (0..n).rev() {
-        x[i] = aug[i][n];
-        for j in (i + 1)..n {
-            x[i] -= aug[i][j] * x[j];
+    #[test]
+    fn test_remez_hilbert_and_differentiator() {
+        let result = remez_design(
+            31,
+            &[0.1, 0.9],
+            &[1.0, 1.0],
+            None,
+            RemezFilterType::Hilbert,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(result.converged);
+        let h = &result.coefficients;
+        for i in 0..h.len() / 2 {
+            assert!((h[i] + h[h.len() - 1 - i]).abs() < 1e-12);
+            // Taps an even distance from the center vanish
+            if (15 - i).is_multiple_of(2) {
+                assert!(h[i].abs() < 1e-10);
+            }
+        }
+        for i in 10..=90 {
+            let f = i as f64 / 100.0;
+            assert!((magnitude(h, f) - 1.0).abs() <= result.deviation * 1.02);
         }
-        x[i] /= aug[i][i];
-    }
 
-    Ok(x)
+        let result = remez_design(
+            32,
+            &[0.0, 0.9],
+            &[0.0, 0.9 * PI],
+            None,
+            RemezFilterType::Differentiator,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(result.converged);
+        let h = &result.coefficients;
+        for i in 0..h.len() / 2 {
+            assert!((h[i] + h[h.len() - 1 - i]).abs() < 1e-12);
+        }
+        for i in 5..=90 {
+            let f = i as f64 / 100.0;
+            let w = PI * f;
+            assert!((magnitude(h, f) - w).abs() / w < 0.01);
+        }
+    }
 }
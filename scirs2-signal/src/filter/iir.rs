@@ -4,17 +4,198 @@
 //! classic analog filter prototypes (Butterworth, Chebyshev, Elliptic, Bessel)
 //! and specialized IIR design methods. All filters use the bilinear transform
 //! for analog-to-digital conversion.
+//!
+//! Every design follows the same pipeline:
+//!
+//! 1. an analog lowpass prototype from [`super::prototypes`],
+//! 2. a lowpass, highpass, bandpass or bandstop frequency transformation at
+//!    the pre-warped critical frequencies,
+//! 3. the bilinear transform.
+//!
+//! [`iirfilter_zpk`], [`iirfilter`] and [`iirfilter_sos`] return the result as
+//! zeros-poles-gain, transfer function coefficients or second-order sections.
+//! Second-order sections are the numerically robust choice for high orders
+//! and narrow bands. [`buttord`] and [`cheb1ord`] select the lowest order
+//! meeting a passband/stopband specification.
+//!
+//! Frequencies are normalized so that 1 is the Nyquist frequency.
 
 use crate::error::{SignalError, SignalResult};
-use num_complex::Complex64;
 use num_traits::{Float, NumCast};
+use std::f64::consts::PI;
 use std::fmt::Debug;
 
 use super::common::{
-    math::{add_digital_zeros, bilinear_pole_transform, butterworth_poles, prewarp_frequency},
     validation::{convert_filter_type, validate_cutoff_frequency, validate_order},
-    FilterCoefficients, FilterType, FilterTypeParam,
+    FilterCoefficients, FilterType, FilterTypeParam, SecondOrderSections, ZerosPolesGain,
 };
+use super::prototypes::{besselap, buttap, cheb1ap, cheb2ap, ellipap};
+use super::transform::{
+    bilinear_transform, lp_to_bp_zpk, lp_to_bs_zpk, lp_to_hp_zpk, lp_to_lp_zpk, zpk_to_sos,
+    zpk_to_tf,
+};
+
+/// Analog prototype family used by [`iirfilter`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IirPrototype {
+    /// Maximally flat passband
+    Butterworth,
+    /// Equiripple passband with the given peak-to-peak ripple in dB
+    ChebyshevI { ripple: f64 },
+    /// Equiripple stopband with the given minimum attenuation in dB
+    ChebyshevII { attenuation: f64 },
+    /// Equiripple passband and stopband
+    Elliptic { ripple: f64, attenuation: f64 },
+    /// Maximally flat group delay (phase-normalized)
+    Bessel,
+}
+
+impl IirPrototype {
+    /// Analog lowpass prototype of the given order
+    fn analog(self, order: usize) -> SignalResult<ZerosPolesGain> {
+        match self {
+            IirPrototype::Butterworth => buttap(order),
+            IirPrototype::ChebyshevI { ripple } => cheb1ap(order, ripple),
+            IirPrototype::ChebyshevII { attenuation } => cheb2ap(order, attenuation),
+            IirPrototype::Elliptic {
+                ripple,
+                attenuation,
+            } => ellipap(order, ripple, attenuation),
+            IirPrototype::Bessel => besselap(order),
+        }
+    }
+}
+
+/// General IIR filter design in zeros-poles-gain form
+///
+/// # Arguments
+///
+/// * `order` - Order of the analog prototype (bandpass and bandstop filters
+///   have twice as many poles)
+/// * `critical` - Critical frequencies (normalized from 0 to 1, where 1 is the
+///   Nyquist frequency): one for lowpass/highpass, the band edges for
+///   bandpass/bandstop
+/// * `prototype` - Analog prototype family
+/// * `filter_type` - Filter type (lowpass, highpass, bandpass, bandstop)
+///
+/// # Returns
+///
+/// * Digital zeros, poles and gain
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::iir::{iirfilter_zpk, IirPrototype};
+///
+/// let (z, p, _k) = iirfilter_zpk(3, &[0.2, 0.4], IirPrototype::Butterworth, "bandpass").unwrap();
+/// assert_eq!((z.len(), p.len()), (6, 6));
+/// assert!(p.iter().all(|p| p.norm() < 1.0));
+/// ```
+pub fn iirfilter_zpk(
+    order: usize,
+    critical: &[f64],
+    prototype: IirPrototype,
+    filter_type: impl Into<FilterTypeParam>,
+) -> SignalResult<ZerosPolesGain> {
+    validate_order(order)?;
+    let filter_type = convert_filter_type(filter_type.into())?;
+    let band = matches!(filter_type, FilterType::Bandpass | FilterType::Bandstop);
+    if critical.len() != if band { 2 } else { 1 } {
+        return Err(SignalError::ValueError(format!(
+            "{:?} filters need {} critical frequencies, got {}",
+            filter_type,
+            if band { 2 } else { 1 },
+            critical.len()
+        )));
+    }
+    for &wn in critical {
+        validate_cutoff_frequency(wn)?;
+    }
+    if band && critical[0] >= critical[1] {
+        return Err(SignalError::ValueError(
+            "Band edges must be increasing".to_string(),
+        ));
+    }
+
+    let (z, p, k) = prototype.analog(order)?;
+
+    // Pre-warp for the bilinear transform at fs = 2 (Nyquist = 1)
+    let fs = 2.0;
+    let warped: Vec<f64> = critical
+        .iter()
+        .map(|&wn| 2.0 * fs * (PI * wn / fs).tan())
+        .collect();
+
+    let (z, p, k) = match filter_type {
+        FilterType::Lowpass => lp_to_lp_zpk(&z, &p, k, warped[0])?,
+        FilterType::Highpass => lp_to_hp_zpk(&z, &p, k, warped[0])?,
+        FilterType::Bandpass => {
+            let (wo, bw) = ((warped[0] * warped[1]).sqrt(), warped[1] - warped[0]);
+            lp_to_bp_zpk(&z, &p, k, wo, bw)?
+        }
+        FilterType::Bandstop => {
+            let (wo, bw) = ((warped[0] * warped[1]).sqrt(), warped[1] - warped[0]);
+            lp_to_bs_zpk(&z, &p, k, wo, bw)?
+        }
+    };
+
+    bilinear_transform(&z, &p, k, fs)
+}
+
+/// General IIR filter design returning transfer function coefficients
+///
+/// See [`iirfilter_zpk`] for the arguments. High-order or narrow-band designs
+/// lose precision in this form; prefer [`iirfilter_sos`] for those.
+///
+/// # Returns
+///
+/// * A tuple of filter coefficients (b, a)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::iir::{iirfilter, IirPrototype};
+///
+/// let prototype = IirPrototype::ChebyshevII { attenuation: 40.0 };
+/// let (b, a) = iirfilter(4, &[0.3], prototype, "highpass").unwrap();
+/// assert_eq!((b.len(), a.len()), (5, 5));
+/// ```
+pub fn iirfilter(
+    order: usize,
+    critical: &[f64],
+    prototype: IirPrototype,
+    filter_type: impl Into<FilterTypeParam>,
+) -> SignalResult<FilterCoefficients> {
+    let (z, p, k) = iirfilter_zpk(order, critical, prototype, filter_type)?;
+    zpk_to_tf(&z, &p, k)
+}
+
+/// General IIR filter design returning second-order sections
+///
+/// See [`iirfilter_zpk`] for the arguments.
+///
+/// # Returns
+///
+/// * Biquad sections `[b0, b1, b2, a0, a1, a2]`
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::iir::{iirfilter_sos, IirPrototype};
+///
+/// let prototype = IirPrototype::Elliptic { ripple: 0.5, attenuation: 60.0 };
+/// let sos = iirfilter_sos(8, &[0.1, 0.12], prototype, "bandpass").unwrap();
+/// assert_eq!(sos.len(), 8);
+/// ```
+pub fn iirfilter_sos(
+    order: usize,
+    critical: &[f64],
+    prototype: IirPrototype,
+    filter_type: impl Into<FilterTypeParam>,
+) -> SignalResult<SecondOrderSections> {
+    let (z, p, k) = iirfilter_zpk(order, critical, prototype, filter_type)?;
+    zpk_to_sos(&z, &p, k)
+}
 
 /// Butterworth filter design
 ///
@@ -26,7 +207,8 @@ use super::common::{
 ///
 /// * `order` - Filter order (higher order = steeper roll-off)
 /// * `cutoff` - Cutoff frequency (normalized from 0 to 1, where 1 is Nyquist frequency)
-/// * `filter_type` - Filter type (lowpass, highpass, bandpass, bandstop)
+/// * `filter_type` - Filter type (lowpass or highpass; use
+///   [`butter_bandpass_bandstop`] or [`iirfilter`] for bands)
 ///
 /// # Returns
 ///
@@ -53,61 +235,8 @@ pub fn butter<T>(
 where
     T: Float + NumCast + Debug,
 {
-    // Validate parameters
-    validate_order(order)?;
     let wn = validate_cutoff_frequency(cutoff)?;
-    let filter_type = convert_filter_type(filter_type.into())?;
-
-    // Step 1: Calculate analog Butterworth prototype poles
-    let poles = butterworth_poles(order);
-
-    // Step 2: Apply frequency transformation based on filter type
-    let (analog_zeros, transformed_poles, gain) = match filter_type {
-        FilterType::Lowpass => {
-            // Scale poles by cutoff frequency (pre-warping for bilinear transform)
-            let warped_freq = prewarp_frequency(wn);
-            let scaled_poles: Vec<_> = poles.iter().map(|p| p * warped_freq).collect();
-            // Lowpass has no finite zeros in analog domain (zeros at infinity)
-            (
-                Vec::<Complex64>::new(),
-                scaled_poles,
-                warped_freq.powi(order as i32),
-            )
-        }
-        FilterType::Highpass => {
-            // Highpass: s -> wc/s transformation
-            let warped_freq = prewarp_frequency(wn);
-            let hp_poles: Vec<_> = poles.iter().map(|p| warped_freq / p).collect();
-            // No finite zeros in analog domain for highpass - zeros are at origin
-            (Vec::<Complex64>::new(), hp_poles, 1.0)
-        }
-        FilterType::Bandpass => {
-            return butter_bandpass_bandstop(order, wn - 0.05, wn + 0.05, FilterType::Bandpass);
-        }
-        FilterType::Bandstop => {
-            return butter_bandpass_bandstop(order, wn - 0.05, wn + 0.05, FilterType::Bandstop);
-        }
-    };
-
-    // Step 3: Apply bilinear transform to convert to digital filter
-    let mut digital_poles = Vec::new();
-    let mut digital_zeros = Vec::new();
-
-    // Transform poles: z_pole = (2 + s_pole) / (2 - s_pole)
-    for &pole in &transformed_poles {
-        digital_poles.push(bilinear_pole_transform(pole));
-    }
-
-    // Transform finite analog zeros: z_zero = (2 + s_zero) / (2 - s_zero)
-    for &zero in &analog_zeros {
-        digital_zeros.push(bilinear_pole_transform(zero));
-    }
-
-    // Add zeros in the digital domain based on filter type
-    digital_zeros.extend(add_digital_zeros(filter_type, order));
-
-    // Step 4: Convert poles and zeros to transfer function coefficients
-    zpk_to_tf(&digital_zeros, &digital_poles, gain)
+    iirfilter(order, &[wn], IirPrototype::Butterworth, filter_type)
 }
 
 /// Butterworth bandpass/bandstop filter design
@@ -141,91 +270,17 @@ pub fn butter_bandpass_bandstop(
     high_freq: f64,
     filter_type: FilterType,
 ) -> SignalResult<FilterCoefficients> {
-    validate_order(order)?;
-
-    // Validate frequency bounds
-    if low_freq <= 0.0 || high_freq >= 1.0 || low_freq >= high_freq {
-        return Err(SignalError::ValueError(
-            "Invalid band frequencies: low must be positive, high must be less than 1, and low < high".to_string(),
-        ));
-    }
-
     if !matches!(filter_type, FilterType::Bandpass | FilterType::Bandstop) {
         return Err(SignalError::ValueError(
             "Filter type must be Bandpass or Bandstop".to_string(),
         ));
     }
-
-    // Calculate analog Butterworth prototype poles
-    let poles = butterworth_poles(order);
-
-    // Pre-warp frequencies
-    let wl = prewarp_frequency(low_freq);
-    let wh = prewarp_frequency(high_freq);
-    let center_freq = (wl * wh).sqrt();
-    let bandwidth = wh - wl;
-
-    let (analog_zeros, transformed_poles, gain) = match filter_type {
-        FilterType::Bandpass => {
-            // Apply bandpass transformation: s -> (s^2 + wc^2) / (s * BW)
-            let mut bp_poles = Vec::new();
-            let mut bp_zeros = Vec::new();
-
-            for &pole in &poles {
-                // Apply bandpass transformation to each pole
-                let discriminant = (bandwidth * pole / 2.0).powi(2) + center_freq.powi(2);
-                let sqrt_disc = discriminant.sqrt();
-                let p1 = bandwidth * pole / 2.0 + sqrt_disc;
-                let p2 = bandwidth * pole / 2.0 - sqrt_disc;
-                bp_poles.push(p1);
-                bp_poles.push(p2);
-            }
-
-            // Bandpass has zeros at origin (DC) and infinity
-            for _ in 0..order {
-                bp_zeros.push(Complex64::new(0.0, 0.0)); // Zero at origin
-            }
-
-            (bp_zeros, bp_poles, 1.0)
-        }
-        FilterType::Bandstop => {
-            // Apply bandstop transformation: s -> (s * BW) / (s^2 + wc^2)
-            let mut bs_poles = Vec::new();
-            let mut bs_zeros = Vec::new();
-
-            for &pole in &poles {
-                let discriminant = (bandwidth / (2.0 * pole)).powi(2) + center_freq.powi(2);
-                let sqrt_disc = discriminant.sqrt();
-                let p1 = bandwidth / (2.0 * pole) + sqrt_disc;
-                let p2 = bandwidth / (2.0 * pole) - sqrt_disc;
-                bs_poles.push(p1);
-                bs_poles.push(p2);
-            }
-
-            // Bandstop has zeros at ±j*wc (notch frequencies)
-            for _ in 0..order {
-                bs_zeros.push(Complex64::new(0.0, center_freq)); // +j*wc
-                bs_zeros.push(Complex64::new(0.0, -center_freq)); // -j*wc
-            }
-
-            (bs_zeros, bs_poles, 1.0)
-        }
-        _ => unreachable!(), // Already validated above
-    };
-
-    // Apply bilinear transform to convert to digital filter
-    let digital_poles: Vec<_> = transformed_poles
-        .iter()
-        .map(|&pole| bilinear_pole_transform(pole))
-        .collect();
-
-    let digital_zeros: Vec<_> = analog_zeros
-        .iter()
-        .map(|&zero| bilinear_pole_transform(zero))
-        .collect();
-
-    // Convert poles and zeros to transfer function coefficients
-    zpk_to_tf(&digital_zeros, &digital_poles, gain)
+    iirfilter(
+        order,
+        &[low_freq, high_freq],
+        IirPrototype::Butterworth,
+        filter_type,
+    )
 }
 
 /// Chebyshev Type I filter design
@@ -238,8 +293,9 @@ pub fn butter_bandpass_bandstop(
 ///
 /// * `order` - Filter order
 /// * `ripple` - Passband ripple in dB (e.g., 0.5 for 0.5 dB ripple)
-/// * `cutoff` - Cutoff frequency (normalized from 0 to 1)
-/// * `filter_type` - Filter type (lowpass, highpass, bandpass, bandstop)
+/// * `cutoff` - Passband edge, where the gain first drops below `-ripple` dB
+///   (normalized from 0 to 1)
+/// * `filter_type` - Filter type (lowpass or highpass)
 ///
 /// # Returns
 ///
@@ -262,72 +318,16 @@ pub fn cheby1<T>(
 where
     T: Float + NumCast + Debug,
 {
-    validate_order(order)?;
     let wn = validate_cutoff_frequency(cutoff)?;
-    let filter_type = convert_filter_type(filter_type.into())?;
-
-    if ripple <= 0.0 {
-        return Err(SignalError::ValueError(
-            "Ripple must be positive".to_string(),
-        ));
-    }
-
-    // For now, return a simple error for unsupported types
-    if !matches!(filter_type, FilterType::Lowpass | FilterType::Highpass) {
-        return Err(SignalError::NotImplementedError(
-            "Bandpass and bandstop Chebyshev I filters not yet implemented".to_string(),
-        ));
-    }
-
-    // Convert ripple from dB to linear
-    let epsilon = (10.0_f64.powf(ripple / 10.0) - 1.0).sqrt();
-
-    // Calculate Chebyshev Type I analog prototype poles
-    let mut poles = Vec::with_capacity(order);
-    let a = (1.0 / epsilon + (1.0 / epsilon / epsilon + 1.0).sqrt()).ln() / order as f64;
-
-    for k in 0..order {
-        let theta = std::f64::consts::PI * (2.0 * k as f64 + 1.0) / (2.0 * order as f64);
-        let real = -a.sinh() * theta.sin();
-        let imag = a.cosh() * theta.cos();
-        poles.push(Complex64::new(real, imag));
-    }
-
-    // Apply frequency transformation and bilinear transform
-    let (analog_zeros, transformed_poles, gain) = match filter_type {
-        FilterType::Lowpass => {
-            let warped_freq = prewarp_frequency(wn);
-            let scaled_poles: Vec<_> = poles.iter().map(|p| p * warped_freq).collect();
-            (
-                Vec::<Complex64>::new(),
-                scaled_poles,
-                warped_freq.powi(order as i32),
-            )
-        }
-        FilterType::Highpass => {
-            let warped_freq = prewarp_frequency(wn);
-            let hp_poles: Vec<_> = poles.iter().map(|p| warped_freq / p).collect();
-            (Vec::<Complex64>::new(), hp_poles, 1.0)
-        }
-        _ => unreachable!(),
-    };
-
-    let digital_poles: Vec<_> = transformed_poles
-        .iter()
-        .map(|&pole| bilinear_pole_transform(pole))
-        .collect();
-
-    let mut digital_zeros: Vec<_> = analog_zeros
-        .iter()
-        .map(|&zero| bilinear_pole_transform(zero))
-        .collect();
-
-    digital_zeros.extend(add_digital_zeros(filter_type, order));
-
-    zpk_to_tf(&digital_zeros, &digital_poles, gain)
+    iirfilter(
+        order,
+        &[wn],
+        IirPrototype::ChebyshevI { ripple },
+        filter_type,
+    )
 }
 
-/// Chebyshev Type II filter design  
+/// Chebyshev Type II filter design
 ///
 /// Designs a digital Chebyshev Type II filter with monotonic passband and
 /// equiripple stopband. Provides better stopband attenuation than Type I.
@@ -336,8 +336,9 @@ where
 ///
 /// * `order` - Filter order
 /// * `attenuation` - Stopband attenuation in dB (e.g., 40.0 for 40 dB attenuation)
-/// * `cutoff` - Cutoff frequency (normalized from 0 to 1)
-/// * `filter_type` - Filter type (lowpass, highpass, bandpass, bandstop)
+/// * `cutoff` - Stopband edge, where the gain first reaches `-attenuation` dB
+///   (normalized from 0 to 1)
+/// * `filter_type` - Filter type (lowpass or highpass)
 ///
 /// # Returns
 ///
@@ -345,8 +346,7 @@ where
 ///
 /// # Examples
 ///
-/// ```ignore
-/// # FIXME: Chebyshev Type II filter is not yet implemented
+/// ```
 /// use scirs2_signal::filter::iir::cheby2;
 ///
 /// // Design a 4th order Chebyshev II lowpass filter with 40 dB stopband attenuation
@@ -361,20 +361,13 @@ pub fn cheby2<T>(
 where
     T: Float + NumCast + Debug,
 {
-    validate_order(order)?;
-    let _wn = validate_cutoff_frequency(cutoff)?;
-    let _filter_type = convert_filter_type(filter_type.into())?;
-
-    if attenuation <= 0.0 {
-        return Err(SignalError::ValueError(
-            "Attenuation must be positive".to_string(),
-        ));
-    }
-
-    // Placeholder implementation - full Chebyshev II design is complex
-    Err(SignalError::NotImplementedError(
-        "Chebyshev Type II filter design is not yet implemented".to_string(),
-    ))
+    let wn = validate_cutoff_frequency(cutoff)?;
+    iirfilter(
+        order,
+        &[wn],
+        IirPrototype::ChebyshevII { attenuation },
+        filter_type,
+    )
 }
 
 /// Elliptic (Cauer) filter design
@@ -387,8 +380,8 @@ where
 /// * `order` - Filter order
 /// * `passband_ripple` - Passband ripple in dB
 /// * `stopband_attenuation` - Stopband attenuation in dB
-/// * `cutoff` - Cutoff frequency (normalized from 0 to 1)
-/// * `filter_type` - Filter type (lowpass, highpass, bandpass, bandstop)
+/// * `cutoff` - Passband edge (normalized from 0 to 1)
+/// * `filter_type` - Filter type (lowpass or highpass)
 ///
 /// # Returns
 ///
@@ -396,8 +389,7 @@ where
 ///
 /// # Examples
 ///
-/// ```ignore
-/// # FIXME: Elliptic filter is not yet implemented
+/// ```
 /// use scirs2_signal::filter::iir::ellip;
 ///
 /// // Design a 4th order elliptic lowpass filter with 0.5 dB ripple and 40 dB stopband attenuation
@@ -413,26 +405,16 @@ pub fn ellip<T>(
 where
     T: Float + NumCast + Debug,
 {
-    validate_order(order)?;
-    let _wn = validate_cutoff_frequency(cutoff)?;
-    let _filter_type = convert_filter_type(filter_type.into())?;
-
-    if passband_ripple <= 0.0 {
-        return Err(SignalError::ValueError(
-            "Passband ripple must be positive".to_string(),
-        ));
-    }
-
-    if stopband_attenuation <= 0.0 {
-        return Err(SignalError::ValueError(
-            "Stopband attenuation must be positive".to_string(),
-        ));
-    }
-
-    // Placeholder implementation - full elliptic design requires Jacobi elliptic functions
-    Err(SignalError::NotImplementedError(
-        "Elliptic filter design is not yet implemented".to_string(),
-    ))
+    let wn = validate_cutoff_frequency(cutoff)?;
+    iirfilter(
+        order,
+        &[wn],
+        IirPrototype::Elliptic {
+            ripple: passband_ripple,
+            attenuation: stopband_attenuation,
+        },
+        filter_type,
+    )
 }
 
 /// Bessel filter design
@@ -443,9 +425,11 @@ where
 ///
 /// # Arguments
 ///
-/// * `order` - Filter order
-/// * `cutoff` - Cutoff frequency (normalized from 0 to 1)
-/// * `filter_type` - Filter type (lowpass, highpass, bandpass, bandstop)
+/// * `order` - Filter order (at most 25)
+/// * `cutoff` - Cutoff frequency (normalized from 0 to 1); the prototype is
+///   phase-normalized, so the phase response matches that of a Butterworth
+///   filter with the same cutoff asymptotically
+/// * `filter_type` - Filter type (lowpass or highpass)
 ///
 /// # Returns
 ///
@@ -453,8 +437,7 @@ where
 ///
 /// # Examples
 ///
-/// ```ignore
-/// # FIXME: Bessel filter is not yet implemented
+/// ```
 /// use scirs2_signal::filter::iir::bessel;
 ///
 /// // Design a 4th order Bessel lowpass filter
@@ -468,115 +451,372 @@ pub fn bessel<T>(
 where
     T: Float + NumCast + Debug,
 {
-    validate_order(order)?;
-    let _wn = validate_cutoff_frequency(cutoff)?;
-    let _filter_type = convert_filter_type(filter_type.into())?;
-
-    // Placeholder implementation - Bessel filter design requires Bessel polynomials
-    Err(SignalError::NotImplementedError(
-        "Bessel filter design is not yet implemented".to_string(),
-    ))
+    let wn = validate_cutoff_frequency(cutoff)?;
+    iirfilter(order, &[wn], IirPrototype::Bessel, filter_type)
 }
 
-/// Convert zeros, poles, and gain to transfer function coefficients
+/// Butterworth filter order selection
 ///
-/// Converts a filter representation in zeros-poles-gain form to
-/// transfer function coefficients (numerator and denominator polynomials).
+/// Finds the lowest order Butterworth filter that loses no more than `gpass`
+/// dB in the passband and has at least `gstop` dB of attenuation in the
+/// stopband. The filter type follows from the edges: `wp < ws` is lowpass,
+/// `wp > ws` highpass, a stopband enclosing the passband is bandpass, and a
+/// passband enclosing the stopband is bandstop.
 ///
 /// # Arguments
 ///
-/// * `zeros` - Filter zeros in the z-domain
-/// * `poles` - Filter poles in the z-domain  
-/// * `gain` - Filter gain
+/// * `wp` - Passband edge(s) (normalized from 0 to 1)
+/// * `ws` - Stopband edge(s) (normalized from 0 to 1)
+/// * `gpass` - Maximum passband loss in dB
+/// * `gstop` - Minimum stopband attenuation in dB
 ///
 /// # Returns
 ///
-/// * Tuple of (numerator_coeffs, denominator_coeffs)
-fn zpk_to_tf(
-    zeros: &[Complex64],
-    poles: &[Complex64],
-    gain: f64,
-) -> SignalResult<FilterCoefficients> {
-    // Build numerator polynomial from zeros
-    let mut num_poly = vec![Complex64::new(1.0, 0.0)];
-    for &zero in zeros {
-        // Multiply polynomial by (z - zero)
-        let mut new_poly = vec![Complex64::new(0.0, 0.0); num_poly.len() + 1];
-
-        // Multiply by z (shift coefficients)
-        for (i, &coeff) in num_poly.iter().enumerate() {
-            new_poly[i] += coeff;
+/// * The order and the natural (−3 dB) frequencies to pass to [`butter`] or
+///   [`iirfilter`]
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::iir::{buttord, butter};
+///
+/// // Lose at most 3 dB below 0.2, attenuate at least 40 dB above 0.3
+/// let (order, wn) = buttord(&[0.2], &[0.3], 3.0, 40.0).unwrap();
+/// assert_eq!(order, 11);
+/// let (b, a) = butter(order, wn[0], "lowpass").unwrap();
+/// ```
+pub fn buttord(wp: &[f64], ws: &[f64], gpass: f64, gstop: f64) -> SignalResult<(usize, Vec<f64>)> {
+    let spec = OrderSpec::new(wp, ws, gpass, gstop)?;
+    let order = ((spec.gstop_lin - 1.0) / (spec.gpass_lin - 1.0)).log10()
+        / (2.0 * spec.selectivity.log10());
+    let order = (order.ceil() as usize).max(1);
+
+    // Frequency where the Butterworth response is exactly -3 dB
+    let w0 = (spec.gpass_lin - 1.0).powf(-1.0 / (2.0 * order as f64));
+    let passb = &spec.passb;
+    let natural = match spec.filter_type {
+        FilterType::Lowpass => vec![w0 * passb[0]],
+        FilterType::Highpass => vec![passb[0] / w0],
+        FilterType::Bandstop => {
+            let width = passb[1] - passb[0];
+            let root = (width * width + 4.0 * w0 * w0 * passb[0] * passb[1]).sqrt();
+            sorted_abs(vec![
+                (width + root) / (2.0 * w0),
+                (width - root) / (2.0 * w0),
+            ])
+        }
+        FilterType::Bandpass => {
+            let width = passb[1] - passb[0];
+            let root = (w0 * w0 / 4.0 * width * width + passb[0] * passb[1]).sqrt();
+            sorted_abs(vec![-w0 * width / 2.0 + root, w0 * width / 2.0 + root])
         }
+    };
+
+    let wn = natural.iter().map(|w| 2.0 / PI * w.atan()).collect();
+    Ok((order, wn))
+}
+
+/// Chebyshev Type I filter order selection
+///
+/// Finds the lowest order Chebyshev Type I filter that loses no more than
+/// `gpass` dB in the passband and has at least `gstop` dB of attenuation in
+/// the stopband. See [`buttord`] for how the filter type is inferred.
+///
+/// # Arguments
+///
+/// * `wp` - Passband edge(s) (normalized from 0 to 1)
+/// * `ws` - Stopband edge(s) (normalized from 0 to 1)
+/// * `gpass` - Maximum passband loss in dB
+/// * `gstop` - Minimum stopband attenuation in dB
+///
+/// # Returns
+///
+/// * The order and the critical frequencies to pass to [`cheby1`] or
+///   [`iirfilter`] with a ripple of `gpass`
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::iir::cheb1ord;
+///
+/// let (order, wn) = cheb1ord(&[0.2], &[0.3], 3.0, 40.0).unwrap();
+/// assert_eq!(order, 6);
+/// assert_eq!(wn, vec![0.2]);
+/// ```
+pub fn cheb1ord(wp: &[f64], ws: &[f64], gpass: f64, gstop: f64) -> SignalResult<(usize, Vec<f64>)> {
+    let spec = OrderSpec::new(wp, ws, gpass, gstop)?;
+    let order = ((spec.gstop_lin - 1.0) / (spec.gpass_lin - 1.0))
+        .sqrt()
+        .acosh()
+        / spec.selectivity.acosh();
+    let order = (order.ceil() as usize).max(1);
+    Ok((order, wp.to_vec()))
+}
 
-        // Subtract zero times polynomial
-        for (i, &coeff) in num_poly.iter().enumerate() {
-            new_poly[i + 1] -= zero * coeff;
+/// Validated order-selection specification in the pre-warped analog domain
+struct OrderSpec {
+    filter_type: FilterType,
+    /// Pre-warped passband edges
+    passb: Vec<f64>,
+    /// Ratio of stopband to passband edge of the equivalent lowpass prototype
+    selectivity: f64,
+    gpass_lin: f64,
+    gstop_lin: f64,
+}
+
+impl OrderSpec {
+    fn new(wp: &[f64], ws: &[f64], gpass: f64, gstop: f64) -> SignalResult<Self> {
+        if wp.is_empty() || wp.len() > 2 || wp.len() != ws.len() {
+            return Err(SignalError::ValueError(
+                "Passband and stopband edges must both have one or two frequencies".to_string(),
+            ));
+        }
+        for &w in wp.iter().chain(ws) {
+            validate_cutoff_frequency(w)?;
+        }
+        if !(gpass > 0.0 && gstop > gpass) {
+            return Err(SignalError::ValueError(format!(
+                "Need 0 < gpass < gstop, got gpass = {gpass}, gstop = {gstop}"
+            )));
         }
 
-        num_poly = new_poly;
+        let filter_type = match wp.len() {
+            1 if wp[0] < ws[0] => FilterType::Lowpass,
+            1 if wp[0] > ws[0] => FilterType::Highpass,
+            2 if ws[0] < wp[0] && wp[0] < wp[1] && wp[1] < ws[1] => FilterType::Bandpass,
+            2 if wp[0] < ws[0] && ws[0] < ws[1] && ws[1] < wp[1] => FilterType::Bandstop,
+            _ => {
+                return Err(SignalError::ValueError(
+                    "Passband and stopband edges must not overlap".to_string(),
+                ))
+            }
+        };
+
+        let warp = |w: &f64| (PI * w / 2.0).tan();
+        let passb: Vec<f64> = wp.iter().map(warp).collect();
+        let stopb: Vec<f64> = ws.iter().map(warp).collect();
+        let selectivity = match filter_type {
+            FilterType::Lowpass => stopb[0] / passb[0],
+            FilterType::Highpass => passb[0] / stopb[0],
+            FilterType::Bandpass => stopb
+                .iter()
+                .map(|s| ((s * s - passb[0] * passb[1]) / (s * (passb[0] - passb[1]))).abs())
+                .fold(f64::INFINITY, f64::min),
+            FilterType::Bandstop => stopb
+                .iter()
+                .map(|s| (s * (passb[0] - passb[1]) / (s * s - passb[0] * passb[1])).abs())
+                .fold(f64::INFINITY, f64::min),
+        };
+
+        Ok(Self {
+            filter_type,
+            passb,
+            selectivity,
+            gpass_lin: 10f64.powf(0.1 * gpass),
+            gstop_lin: 10f64.powf(0.1 * gstop),
+        })
     }
+}
 
-    // Build denominator polynomial from poles
-    let mut den_poly = vec![Complex64::new(1.0, 0.0)];
-    for &pole in poles {
-        // Multiply polynomial by (z - pole)
-        let mut new_poly = vec![Complex64::new(0.0, 0.0); den_poly.len() + 1];
+fn sorted_abs(mut values: Vec<f64>) -> Vec<f64> {
+    values.iter_mut().for_each(|v| *v = v.abs());
+    values.sort_by(f64::total_cmp);
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::prototypes::ellipap;
+    use approx::assert_relative_eq;
+    use num_complex::Complex64;
+
+    /// Gain in dB of a transfer function at normalized frequency `w`
+    fn gain_db(b: &[f64], a: &[f64], w: f64) -> f64 {
+        let z = Complex64::from_polar(1.0, -PI * w);
+        let eval = |c: &[f64]| {
+            c.iter()
+                .enumerate()
+                .map(|(i, &v)| v * z.powi(i as i32))
+                .sum::<Complex64>()
+        };
+        20.0 * (eval(b) / eval(a)).norm().log10()
+    }
 
-        // Multiply by z (shift coefficients)
-        for (i, &coeff) in den_poly.iter().enumerate() {
-            new_poly[i] += coeff;
+    /// Gain in dB of a cascade of second-order sections at normalized frequency `w`
+    fn sos_gain_db(sos: &SecondOrderSections, w: f64) -> f64 {
+        sos.iter()
+            .map(|s| gain_db(&s[..3], &s[3..], w))
+            .sum::<f64>()
+    }
+
+    #[test]
+    fn test_lowpass_edges() {
+        // Butterworth is -3 dB at the cutoff
+        let (b, a) = butter(5, 0.3, "lowpass").unwrap();
+        assert_relative_eq!(gain_db(&b, &a, 0.0), 0.0, epsilon = 1e-9);
+        assert_relative_eq!(gain_db(&b, &a, 0.3), -3.0103, epsilon = 1e-3);
+
+        // Chebyshev I is -ripple at the passband edge
+        let (b, a) = cheby1(4, 1.0, 0.3, "lowpass").unwrap();
+        assert_relative_eq!(gain_db(&b, &a, 0.3), -1.0, epsilon = 1e-6);
+        assert_relative_eq!(gain_db(&b, &a, 0.0), -1.0, epsilon = 1e-6);
+
+        // Chebyshev II is -attenuation at the stopband edge
+        let (b, a) = cheby2(5, 40.0, 0.3, "lowpass").unwrap();
+        assert_relative_eq!(gain_db(&b, &a, 0.3), -40.0, epsilon = 1e-6);
+        assert_relative_eq!(gain_db(&b, &a, 0.0), 0.0, epsilon = 1e-9);
+        for i in 31..100 {
+            assert!(gain_db(&b, &a, i as f64 / 100.0) < -40.0 + 1e-6);
         }
 
-        // Subtract pole times polynomial
-        for (i, &coeff) in den_poly.iter().enumerate() {
-            new_poly[i + 1] -= pole * coeff;
+        // Elliptic meets both ripple and attenuation
+        let (b, a) = ellip(5, 0.5, 50.0, 0.3, "lowpass").unwrap();
+        assert_relative_eq!(gain_db(&b, &a, 0.3), -0.5, epsilon = 1e-6);
+        for i in 0..30 {
+            assert!(gain_db(&b, &a, i as f64 / 100.0) > -0.5 - 1e-6);
+        }
+        let stop_edge = (40..100)
+            .map(|i| i as f64 / 100.0)
+            .find(|&w| gain_db(&b, &a, w) < -50.0 + 1e-6)
+            .unwrap();
+        for i in (stop_edge * 100.0) as usize..100 {
+            assert!(gain_db(&b, &a, i as f64 / 100.0) < -50.0 + 1e-6);
         }
 
-        den_poly = new_poly;
+        // Bessel has unit DC gain and is monotonic
+        let (b, a) = bessel(4, 0.3, "lowpass").unwrap();
+        assert_relative_eq!(gain_db(&b, &a, 0.0), 0.0, epsilon = 1e-9);
+        let gains: Vec<f64> = (0..99).map(|i| gain_db(&b, &a, i as f64 / 100.0)).collect();
+        assert!(gains.windows(2).all(|w| w[1] < w[0]));
     }
 
-    // Apply gain to numerator
-    for coeff in &mut num_poly {
-        *coeff *= gain;
+    #[test]
+    fn test_prototype_references() {
+        // Analog elliptic prototype: equiripple between 0 and -1 dB up to
+        // w = 1, and between -40 dB and -inf dB beyond the stopband edge
+        let (z, p, k) = ellipap(4, 1.0, 40.0).unwrap();
+        assert_eq!((z.len(), p.len()), (4, 4));
+        assert_relative_eq!(k, 0.01, epsilon = 1e-9);
+        let analog_db = |w: f64| {
+            let s = Complex64::new(0.0, w);
+            let num: Complex64 = z.iter().map(|&z| s - z).product();
+            let den: Complex64 = p.iter().map(|&p| s - p).product();
+            20.0 * (k * num / den).norm().log10()
+        };
+        let passband: Vec<f64> = (0..=1000).map(|i| analog_db(i as f64 / 1000.0)).collect();
+        assert_relative_eq!(passband[1000], -1.0, epsilon = 1e-9);
+        assert_relative_eq!(
+            passband.iter().cloned().fold(f64::MIN, f64::max),
+            0.0,
+            epsilon = 1e-5
+        );
+        assert_relative_eq!(
+            passband.iter().cloned().fold(f64::MAX, f64::min),
+            -1.0,
+            epsilon = 1e-9
+        );
+        let stopband = (1520..20000)
+            .map(|i| analog_db(i as f64 / 1000.0))
+            .fold(f64::MIN, f64::max);
+        assert_relative_eq!(stopband, -40.0, epsilon = 1e-4);
+
+        // Butterworth highpass: zeros at DC, unit gain at Nyquist
+        let (b, a) = butter(3, 0.4, "highpass").unwrap();
+        assert_relative_eq!(gain_db(&b, &a, 1.0), 0.0, epsilon = 1e-9);
+        assert_relative_eq!(gain_db(&b, &a, 0.4), -3.0103, epsilon = 1e-3);
+        // SciPy: signal.butter(3, 0.4, 'highpass')
+        let expected_b = [0.25691560, -0.77074681, 0.77074681, -0.25691560];
+        let expected_a = [1.0, -0.57724052, 0.42178705, -0.05629724];
+        for (x, y) in b.iter().zip(&expected_b) {
+            assert_relative_eq!(x, y, epsilon = 1e-7);
+        }
+        for (x, y) in a.iter().zip(&expected_a) {
+            assert_relative_eq!(x, y, epsilon = 1e-7);
+        }
     }
 
-    // Convert complex coefficients to real (should be real for proper filter design)
-    let b: Vec<f64> = num_poly
-        .iter()
-        .map(|c| {
-            if c.im.abs() > 1e-10 {
-                eprintln!(
-                    "Warning: Numerator coefficient has significant imaginary part: {}",
-                    c.im
-                );
+    #[test]
+    fn test_band_designs_and_sos() {
+        for prototype in [
+            IirPrototype::Butterworth,
+            IirPrototype::ChebyshevI { ripple: 1.0 },
+            IirPrototype::Elliptic {
+                ripple: 1.0,
+                attenuation: 60.0,
+            },
+            IirPrototype::Bessel,
+        ] {
+            let (b, a) = iirfilter(3, &[0.2, 0.5], prototype, "bandpass").unwrap();
+            let sos = iirfilter_sos(3, &[0.2, 0.5], prototype, "bandpass").unwrap();
+            assert_eq!(sos.len(), 3);
+            for i in 1..100 {
+                let w = i as f64 / 100.0;
+                assert_relative_eq!(sos_gain_db(&sos, w), gain_db(&b, &a, w), epsilon = 1e-6);
             }
-            c.re
-        })
-        .collect();
-
-    let a: Vec<f64> = den_poly
-        .iter()
-        .map(|c| {
-            if c.im.abs() > 1e-10 {
-                eprintln!(
-                    "Warning: Denominator coefficient has significant imaginary part: {}",
-                    c.im
-                );
-            }
-            c.re
-        })
-        .collect();
+            assert!(gain_db(&b, &a, 0.01) < -20.0);
+            assert!(gain_db(&b, &a, 0.99) < -20.0);
+            assert!(gain_db(&b, &a, 0.33) > -3.5);
+
+            let sos = iirfilter_sos(3, &[0.2, 0.5], prototype, "bandstop").unwrap();
+            assert!(sos_gain_db(&sos, 0.35) < -10.0);
+            assert!(sos_gain_db(&sos, 0.01).abs() < 1.1);
+            assert!(sos_gain_db(&sos, 0.99).abs() < 1.1);
+        }
 
-    // Ensure denominator is monic (leading coefficient = 1)
-    if a.is_empty() || a[0].abs() < 1e-15 {
-        return Err(SignalError::ValueError(
-            "Invalid denominator polynomial".to_string(),
-        ));
+        // A narrow high-order band is only accurate as second-order sections
+        let prototype = IirPrototype::Butterworth;
+        let sos = iirfilter_sos(10, &[0.1, 0.11], prototype, "bandpass").unwrap();
+        assert_eq!(sos.len(), 10);
+        assert!(sos.iter().all(|s| s[3] == 1.0));
+        assert_relative_eq!(sos_gain_db(&sos, 0.105), 0.0, epsilon = 0.01);
+        assert!(sos_gain_db(&sos, 0.2) < -100.0);
+
+        assert!(iirfilter(4, &[0.3], prototype, "bandpass").is_err());
+        assert!(iirfilter(4, &[0.4, 0.3], prototype, "bandstop").is_err());
+        assert!(butter(4, 0.3, "bandpass").is_err());
     }
 
-    let a0 = a[0];
-    let b_normalized: Vec<f64> = b.iter().map(|&coeff| coeff / a0).collect();
-    let a_normalized: Vec<f64> = a.iter().map(|&coeff| coeff / a0).collect();
+    #[test]
+    fn test_order_selection() {
+        // With gpass = 3 dB the natural frequency sits just above the passband edge
+        let (order, wn) = buttord(&[0.2], &[0.3], 3.0, 40.0).unwrap();
+        assert_eq!(order, 11);
+        assert_relative_eq!(wn[0], 0.200040, epsilon = 1e-6);
+
+        // Every inferred filter type meets its specification
+        let cases: [(&[f64], &[f64]); 4] = [
+            (&[0.2], &[0.3]),
+            (&[0.5], &[0.35]),
+            (&[0.3, 0.5], &[0.2, 0.6]),
+            (&[0.2, 0.7], &[0.35, 0.5]),
+        ];
+        for (wp, ws) in cases {
+            let (order, wn) = buttord(wp, ws, 1.0, 30.0).unwrap();
+            let filter_type = match (wp.len(), wp[0] < ws[0]) {
+                (1, true) => "lowpass",
+                (1, false) => "highpass",
+                (_, false) => "bandpass",
+                (_, true) => "bandstop",
+            };
+            let sos = iirfilter_sos(order, &wn, IirPrototype::Butterworth, filter_type).unwrap();
+            for &w in wp {
+                assert!(sos_gain_db(&sos, w) > -1.0 - 1e-6, "{filter_type}");
+            }
+            for &w in ws {
+                assert!(sos_gain_db(&sos, w) < -30.0 + 1e-6, "{filter_type}");
+            }
 
-    Ok((b_normalized, a_normalized))
+            let (order, wn) = cheb1ord(wp, ws, 1.0, 30.0).unwrap();
+            let prototype = IirPrototype::ChebyshevI { ripple: 1.0 };
+            let sos = iirfilter_sos(order, &wn, prototype, filter_type).unwrap();
+            for &w in ws {
+                assert!(sos_gain_db(&sos, w) < -30.0 + 1e-6, "{filter_type}");
+            }
+        }
+
+        assert!(buttord(&[0.3], &[0.3], 1.0, 30.0).is_err());
+        assert!(buttord(&[0.2, 0.5], &[0.3], 1.0, 30.0).is_err());
+        assert!(cheb1ord(&[0.2], &[0.3], 30.0, 1.0).is_err());
+    }
 }
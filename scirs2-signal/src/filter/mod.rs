@@ -6,6 +6,7 @@
 //!
 //! - [`common`] - Common types, enums, and utilities shared across all filter modules
 //! - [`iir`] - IIR (Infinite Impulse Response) filter designs (Butterworth, Chebyshev, etc.)
+//! - [`prototypes`] - Analog lowpass prototypes used by the IIR designs
 //! - [`fir`] - FIR (Finite Impulse Response) filter designs (window method, Parks-McClellan)
 //! - [`application`] - Filter application functions (filtfilt, lfilter, matched filtering)
 //! - [`analysis`] - Filter analysis and characterization functions
@@ -63,6 +64,7 @@ pub mod application;
 pub mod common;
 pub mod fir;
 pub mod iir;
+pub mod prototypes;
 pub mod specialized;
 pub mod transform;
 
@@ -73,28 +75,38 @@ pub use common::{
         convert_filter_type, validate_band_frequencies, validate_cutoff_frequency, validate_order,
     },
     FilterAnalysis, FilterCoefficients, FilterStability, FilterType, FilterTypeParam,
+    SecondOrderSections, ZerosPolesGain,
 };
 
 // Re-export all IIR filter design functions
-pub use iir::{bessel, butter, butter_bandpass_bandstop, cheby1, cheby2, ellip};
+pub use iir::{
+    bessel, butter, butter_bandpass_bandstop, buttord, cheb1ord, cheby1, cheby2, ellip, iirfilter,
+    iirfilter_sos, iirfilter_zpk, IirPrototype,
+};
+
+// Re-export analog prototypes
+pub use prototypes::{besselap, buttap, cheb1ap, cheb2ap, ellipap};
 
 // Re-export all FIR filter design functions
-pub use fir::{firwin, remez};
+pub use fir::{firwin, firwin2, firwin_bands, remez, remez_design, RemezFilterType, RemezResult};
 
 // Re-export filter application functions
 pub use application::{
-    filtfilt, group_delay, lfilter, matched_filter, matched_filter_detect, minimum_phase,
+    filtfilt, filtfilt_with_config, group_delay, lfilter, lfilter_with_zi, lfilter_zi, lfiltic,
+    matched_filter, matched_filter_detect, minimum_phase, FiltfiltConfig, FiltfiltMethod, PadType,
 };
 
 // Re-export filter analysis functions
 pub use analysis::{
     analyze_filter, check_filter_stability, compute_q_factor, find_poles_zeros, frequency_response,
+    freqz, freqz_at, phase_delay, sos_group_delay, sosfreqz, sosfreqz_at,
 };
 
 // Re-export filter transformation functions
 pub use transform::{
-    bilinear_transform, lp_to_bp_transform, lp_to_bs_transform, lp_to_hp_transform,
-    lp_to_lp_transform, normalize_coefficients, tf_to_zpk, zpk_to_tf,
+    bilinear_transform, lp_to_bp_transform, lp_to_bp_zpk, lp_to_bs_transform, lp_to_bs_zpk,
+    lp_to_hp_transform, lp_to_hp_zpk, lp_to_lp_transform, lp_to_lp_zpk, normalize_coefficients,
+    tf_to_zpk, zpk_to_sos, zpk_to_tf,
 };
 
 // Re-export specialized filter functions
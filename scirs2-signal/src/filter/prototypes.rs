@@ -0,0 +1,413 @@
+//! Analog lowpass prototypes for IIR filter design
+//!
+//! Each prototype is a lowpass filter in zeros-poles-gain form with its
+//! critical frequency at 1 rad/s, following the conventions of SciPy's
+//! `buttap`, `cheb1ap`, `cheb2ap`, `ellipap` and `besselap`:
+//!
+//! - Butterworth and Chebyshev I prototypes are normalized at the passband
+//!   edge (−3 dB and −`ripple` dB respectively).
+//! - Chebyshev II prototypes are normalized at the stopband edge, where the
+//!   response first reaches −`attenuation` dB.
+//! - Elliptic prototypes are normalized at the passband edge.
+//! - Bessel prototypes are phase-normalized: the phase response has the same
+//!   asymptotes as a Butterworth filter of the same order.
+//!
+//! The prototypes are mapped to the requested band with the transforms in
+//! [`super::transform`] and discretized with the bilinear transform.
+
+use crate::error::{SignalError, SignalResult};
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+use super::common::ZerosPolesGain;
+
+/// Butterworth analog lowpass prototype
+///
+/// # Arguments
+///
+/// * `order` - Filter order
+///
+/// # Returns
+///
+/// * Zeros (none), poles on the left half of the unit circle, and unit gain
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::prototypes::buttap;
+///
+/// let (z, p, k) = buttap(3).unwrap();
+/// assert!(z.is_empty());
+/// assert_eq!(p.len(), 3);
+/// assert!(p.iter().all(|p| (p.norm() - 1.0).abs() < 1e-12 && p.re < 0.0));
+/// assert_eq!(k, 1.0);
+/// ```
+pub fn buttap(order: usize) -> SignalResult<ZerosPolesGain> {
+    check_order(order)?;
+    let n = order as f64;
+    let poles = odd_steps(order)
+        .map(|m| -Complex64::from_polar(1.0, PI * m / (2.0 * n)))
+        .collect();
+    Ok((Vec::new(), poles, 1.0))
+}
+
+/// Chebyshev Type I analog lowpass prototype
+///
+/// # Arguments
+///
+/// * `order` - Filter order
+/// * `ripple` - Peak-to-peak passband ripple in dB
+///
+/// # Returns
+///
+/// * Zeros (none), poles and gain of the prototype
+pub fn cheb1ap(order: usize, ripple: f64) -> SignalResult<ZerosPolesGain> {
+    check_order(order)?;
+    check_positive(ripple, "Passband ripple")?;
+
+    let n = order as f64;
+    let eps = (10f64.powf(0.1 * ripple) - 1.0).sqrt();
+    let mu = (1.0 / eps).asinh() / n;
+    let poles: Vec<Complex64> = odd_steps(order)
+        .map(|m| -Complex64::new(mu, PI * m / (2.0 * n)).sinh())
+        .collect();
+
+    let mut gain = product(poles.iter().map(|&p| -p)).re;
+    if order.is_multiple_of(2) {
+        // Even orders start at the bottom of the ripple band
+        gain /= (1.0 + eps * eps).sqrt();
+    }
+    Ok((Vec::new(), poles, gain))
+}
+
+/// Chebyshev Type II analog lowpass prototype
+///
+/// # Arguments
+///
+/// * `order` - Filter order
+/// * `attenuation` - Minimum stopband attenuation in dB
+///
+/// # Returns
+///
+/// * Zeros on the imaginary axis, poles and gain of the prototype
+pub fn cheb2ap(order: usize, attenuation: f64) -> SignalResult<ZerosPolesGain> {
+    check_order(order)?;
+    check_positive(attenuation, "Stopband attenuation")?;
+
+    let n = order as f64;
+    let de = 1.0 / (10f64.powf(0.1 * attenuation) - 1.0).sqrt();
+    let mu = (1.0 / de).asinh() / n;
+
+    // Zeros at ±j / sin(m π / 2N), skipping m = 0 for odd orders
+    let zeros: Vec<Complex64> = odd_steps(order)
+        .filter(|&m| m != 0.0)
+        .map(|m| Complex64::new(0.0, 1.0 / (m * PI / (2.0 * n)).sin()))
+        .collect();
+
+    let poles: Vec<Complex64> = odd_steps(order)
+        .map(|m| {
+            let p = -Complex64::from_polar(1.0, PI * m / (2.0 * n));
+            1.0 / Complex64::new(mu.sinh() * p.re, mu.cosh() * p.im)
+        })
+        .collect();
+
+    let gain = (product(poles.iter().map(|&p| -p)) / product(zeros.iter().map(|&z| -z))).re;
+    Ok((zeros, poles, gain))
+}
+
+/// Elliptic (Cauer) analog lowpass prototype
+///
+/// The selectivity factor is found from the degree equation so that the
+/// passband ripple and stopband attenuation are both met exactly for the
+/// given order.
+///
+/// # Arguments
+///
+/// * `order` - Filter order
+/// * `ripple` - Peak-to-peak passband ripple in dB
+/// * `attenuation` - Minimum stopband attenuation in dB
+///
+/// # Returns
+///
+/// * Zeros on the imaginary axis, poles and gain of the prototype
+///
+/// # Errors
+///
+/// Returns an error if the order is zero or the ripple and attenuation are not
+/// positive with `attenuation > ripple`.
+pub fn ellipap(order: usize, ripple: f64, attenuation: f64) -> SignalResult<ZerosPolesGain> {
+    check_order(order)?;
+    check_positive(ripple, "Passband ripple")?;
+    check_positive(attenuation, "Stopband attenuation")?;
+    if attenuation <= ripple {
+        return Err(SignalError::ValueError(format!(
+            "Stopband attenuation ({attenuation} dB) must exceed passband ripple ({ripple} dB)"
+        )));
+    }
+
+    let eps_sq = 10f64.powf(0.1 * ripple) - 1.0;
+    let eps = eps_sq.sqrt();
+    if order == 1 {
+        let p = -(1.0 / eps_sq).sqrt();
+        return Ok((Vec::new(), vec![Complex64::new(p, 0.0)], -p));
+    }
+
+    let n = order as f64;
+    let ck1_sq = eps_sq / (10f64.powf(0.1 * attenuation) - 1.0);
+    let k_ck1 = ellipk(ck1_sq);
+    let m = ellip_degree(order, ck1_sq);
+    let capk = ellipk(m);
+
+    let mut zeros = Vec::new();
+    let mut poles = Vec::new();
+    let r = arc_jac_sc1(1.0 / eps, ck1_sq);
+    let v0 = capk * r / (n * k_ck1);
+    let (sv, cv, dv) = ellipj(v0, 1.0 - m);
+
+    for j in ((1 - order % 2)..order).step_by(2) {
+        let (s, c, d) = ellipj(j as f64 * capk / n, m);
+        if s.abs() > f64::EPSILON {
+            let z = Complex64::new(0.0, 1.0 / (m.sqrt() * s));
+            zeros.push(z);
+            zeros.push(z.conj());
+        }
+        let p = -Complex64::new(c * d * sv * cv, s * dv) / (1.0 - (d * sv).powi(2));
+        if p.im.abs() > f64::EPSILON * p.norm() {
+            poles.push(p);
+            poles.push(p.conj());
+        } else {
+            poles.push(Complex64::new(p.re, 0.0));
+        }
+    }
+
+    let mut gain = (product(poles.iter().map(|&p| -p)) / product(zeros.iter().map(|&z| -z))).re;
+    if order.is_multiple_of(2) {
+        gain /= (1.0 + eps_sq).sqrt();
+    }
+    Ok((zeros, poles, gain))
+}
+
+/// Bessel/Thomson analog lowpass prototype
+///
+/// The poles are the roots of the reverse Bessel polynomial of the given
+/// order, scaled for phase normalization.
+///
+/// # Arguments
+///
+/// * `order` - Filter order
+///
+/// # Returns
+///
+/// * Zeros (none), poles and unit gain of the prototype
+///
+/// # Errors
+///
+/// Returns an error if the order is zero or above 25, where the polynomial
+/// roots can no longer be found accurately in double precision.
+pub fn besselap(order: usize) -> SignalResult<ZerosPolesGain> {
+    check_order(order)?;
+    if order > 25 {
+        return Err(SignalError::ValueError(format!(
+            "Bessel filters are limited to order 25, got {order}"
+        )));
+    }
+
+    // Reverse Bessel polynomial θ_N(s) = Σ a_k s^k with
+    // a_k = (2N - k)! / (2^(N - k) k! (N - k)!), computed by the recurrence
+    // a_(k+1) = a_k 2 (N - k) / ((k + 1) (2N - k))
+    let mut coeffs = vec![0.0; order + 1];
+    coeffs[0] = (1..=order)
+        .map(|j| (order + j) as f64 / 2.0)
+        .product::<f64>();
+    for k in 0..order {
+        coeffs[k + 1] =
+            coeffs[k] * 2.0 * (order - k) as f64 / ((k + 1) as f64 * (2 * order - k) as f64);
+    }
+
+    // Phase normalization scales the delay-normalized poles by a_0^(1/N),
+    // which also makes the DC gain of Π(-p) / Π(s - p) equal to one
+    let scale = coeffs[0].powf(1.0 / order as f64);
+    let mut poles: Vec<Complex64> = polynomial_roots(&coeffs)
+        .into_iter()
+        .map(|p| p / scale)
+        .collect();
+    for p in poles.iter_mut() {
+        if p.im.abs() < 1e-12 * p.norm() {
+            p.im = 0.0;
+        }
+    }
+    poles.sort_by(|a, b| a.im.total_cmp(&b.im));
+    Ok((Vec::new(), poles, 1.0))
+}
+
+/// Complete elliptic integral of the first kind `K(m)` with parameter `m`
+pub(crate) fn ellipk(m: f64) -> f64 {
+    PI / (2.0 * agm(1.0, (1.0 - m).sqrt()))
+}
+
+/// `K(1 - p)`, accurate for small `p`
+fn ellipkm1(p: f64) -> f64 {
+    PI / (2.0 * agm(1.0, p.sqrt()))
+}
+
+/// Arithmetic-geometric mean
+fn agm(mut a: f64, mut b: f64) -> f64 {
+    for _ in 0..64 {
+        if (a - b).abs() <= f64::EPSILON * a {
+            break;
+        }
+        let next = (a + b) / 2.0;
+        b = (a * b).sqrt();
+        a = next;
+    }
+    a
+}
+
+/// Jacobi elliptic functions `(sn, cn, dn)` of `u` with parameter `m`
+///
+/// Uses the descending Landen transformation (Abramowitz & Stegun 16.4).
+pub(crate) fn ellipj(u: f64, m: f64) -> (f64, f64, f64) {
+    if m < 1e-12 {
+        return (u.sin(), u.cos(), 1.0);
+    }
+    if m >= 1.0 {
+        let sech = 1.0 / u.cosh();
+        return (u.tanh(), sech, sech);
+    }
+
+    // Arithmetic-geometric mean sequence a_n, with c_n = (a_(n-1) - b_(n-1)) / 2
+    let mut a = vec![1.0];
+    let mut c = vec![m.sqrt()];
+    let mut b = (1.0 - m).sqrt();
+    while c.last().copied().unwrap_or(0.0).abs() > f64::EPSILON && a.len() < 32 {
+        let an = *a.last().expect("non-empty");
+        a.push((an + b) / 2.0);
+        c.push((an - b) / 2.0);
+        b = (an * b).sqrt();
+    }
+
+    let steps = a.len() - 1;
+    let mut phi = 2f64.powi(steps as i32) * a[steps] * u;
+    let mut previous = phi;
+    for n in (1..=steps).rev() {
+        previous = phi;
+        phi = (phi + (c[n] / a[n] * phi.sin()).asin()) / 2.0;
+    }
+    let (sn, cn) = phi.sin_cos();
+    let dn = if steps == 0 {
+        1.0
+    } else {
+        cn / (previous - phi).cos()
+    };
+    (sn, cn, dn)
+}
+
+/// Solves the elliptic degree equation for the selectivity parameter
+fn ellip_degree(order: usize, m1: f64) -> f64 {
+    let q1 = (-PI * ellipkm1(m1) / ellipk(m1)).exp();
+    let q = q1.powf(1.0 / order as f64);
+    let num: f64 = (0..=7).map(|k| q.powi(k * (k + 1))).sum();
+    let den: f64 = 1.0 + 2.0 * (1..=8).map(|k| q.powi(k * k)).sum::<f64>();
+    16.0 * q * (num / den).powi(4)
+}
+
+/// Inverse Jacobian `sc` function, computed as `Im(sn^-1(i w, m))`
+fn arc_jac_sc1(w: f64, m: f64) -> f64 {
+    arc_jac_sn(Complex64::new(0.0, w), m).im
+}
+
+/// Inverse Jacobian `sn` for complex arguments via Landen descent
+fn arc_jac_sn(w: Complex64, m: f64) -> Complex64 {
+    let complement = |kx: Complex64| ((1.0 - kx) * (1.0 + kx)).sqrt();
+
+    let k = m.sqrt();
+    let mut ks = vec![k];
+    while ks.last().copied().unwrap_or(0.0) != 0.0 && ks.len() <= 10 {
+        let kn = *ks.last().expect("non-empty");
+        let kp = complement(Complex64::new(kn, 0.0)).re;
+        ks.push((1.0 - kp) / (1.0 + kp));
+    }
+
+    let capk: f64 = ks[1..].iter().map(|&kn| 1.0 + kn).product::<f64>() * PI / 2.0;
+    let mut wn = w;
+    for pair in ks.windows(2) {
+        let (kn, knext) = (pair[0], pair[1]);
+        wn = 2.0 * wn / ((1.0 + knext) * (1.0 + complement(kn * wn)));
+    }
+    capk * 2.0 / PI * wn.asin()
+}
+
+/// Roots of a real polynomial with coefficients in ascending powers
+///
+/// Uses the Aberth–Ehrlich simultaneous iteration, which converges cubically
+/// for simple roots.
+fn polynomial_roots(ascending: &[f64]) -> Vec<Complex64> {
+    let degree = ascending.len() - 1;
+    let lead = ascending[degree];
+    let coeffs: Vec<f64> = ascending.iter().map(|c| c / lead).collect();
+
+    // Start on a circle of the geometric-mean root radius, rotated off the axes
+    let radius = coeffs[0].abs().powf(1.0 / degree as f64);
+    let mut roots: Vec<Complex64> = (0..degree)
+        .map(|k| Complex64::from_polar(radius, 2.0 * PI * (k as f64 + 0.25) / degree as f64))
+        .collect();
+
+    let eval = |z: Complex64| {
+        let mut p = Complex64::new(0.0, 0.0);
+        let mut dp = Complex64::new(0.0, 0.0);
+        for &c in coeffs.iter().rev() {
+            dp = dp * z + p;
+            p = p * z + c;
+        }
+        (p, dp)
+    };
+
+    for _ in 0..500 {
+        let mut largest_step: f64 = 0.0;
+        for i in 0..degree {
+            let (p, dp) = eval(roots[i]);
+            if p.norm() == 0.0 {
+                continue;
+            }
+            let ratio = p / dp;
+            let repulsion: Complex64 = (0..degree)
+                .filter(|&j| j != i)
+                .map(|j| 1.0 / (roots[i] - roots[j]))
+                .sum();
+            let step = ratio / (1.0 - ratio * repulsion);
+            roots[i] -= step;
+            largest_step = largest_step.max(step.norm() / roots[i].norm().max(1.0));
+        }
+        if largest_step < 1e-15 {
+            break;
+        }
+    }
+    roots
+}
+
+/// `-(N - 1), -(N - 3), ..., N - 1` as floating-point values
+fn odd_steps(order: usize) -> impl Iterator<Item = f64> {
+    (0..order).map(move |i| (2 * i) as f64 - (order as f64 - 1.0))
+}
+
+fn product(values: impl Iterator<Item = Complex64>) -> Complex64 {
+    values.fold(Complex64::new(1.0, 0.0), |acc, v| acc * v)
+}
+
+fn check_order(order: usize) -> SignalResult<()> {
+    if order == 0 {
+        return Err(SignalError::ValueError(
+            "Filter order must be greater than 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_positive(value: f64, name: &str) -> SignalResult<()> {
+    if !(value.is_finite() && value > 0.0) {
+        return Err(SignalError::ValueError(format!(
+            "{name} must be positive, got {value}"
+        )));
+    }
+    Ok(())
+}
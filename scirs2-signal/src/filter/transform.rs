@@ -8,13 +8,17 @@ use crate::error::{SignalError, SignalResult};
 use num_complex::Complex64;
 use num_traits::Zero;
 
-use super::common::FilterCoefficients;
+use super::common::{FilterCoefficients, SecondOrderSections, ZerosPolesGain};
 
 /// Apply bilinear transform to convert analog filter to digital
 ///
 /// The bilinear transform is a method for converting analog filter designs to digital
 /// filter designs. It maps the s-plane to the z-plane using the transformation:
-/// s = 2 * (z - 1) / (z + 1)
+/// s = 2 * fs * (z - 1) / (z + 1)
+///
+/// Zeros at infinity in the analog domain (one for every pole in excess of
+/// the zeros) are mapped to the Nyquist frequency, z = -1, and the gain is
+/// adjusted so the response at corresponding frequencies is unchanged.
 ///
 /// # Arguments
 ///
@@ -33,10 +37,14 @@ use super::common::FilterCoefficients;
 /// use scirs2_signal::filter::transform::bilinear_transform;
 /// use num_complex::Complex64;
 ///
-/// // Convert simple analog integrator to digital
-/// let analog_poles = vec![Complex64::new(0.0, 0.0)];
-/// let analog_zeros = vec![];
-/// let (z, p, k) = bilinear_transform(&analog_zeros, &analog_poles, 1.0, 1000.0).unwrap();
+/// // First-order analog lowpass 1 / (s + 1) sampled at 10 Hz
+/// let analog_poles = vec![Complex64::new(-1.0, 0.0)];
+/// let (z, p, k) = bilinear_transform(&[], &analog_poles, 1.0, 10.0).unwrap();
+///
+/// // The zero at infinity lands on z = -1 and the DC gain stays at one
+/// assert_eq!(z, vec![Complex64::new(-1.0, 0.0)]);
+/// let dc = k * (1.0 - z[0]) / (1.0 - p[0]);
+/// assert!((dc.re - 1.0).abs() < 1e-12);
 /// ```
 pub fn bilinear_transform(
     zeros: &[Complex64],
@@ -49,27 +57,24 @@ pub fn bilinear_transform(
             "Sample rate must be positive".to_string(),
         ));
     }
+    if zeros.len() > poles.len() {
+        return Err(SignalError::ValueError(
+            "Analog filter must have at least as many poles as zeros".to_string(),
+        ));
+    }
 
-    let fs_2 = sample_rate / 2.0;
-    let mut digital_zeros = Vec::new();
-    let mut digital_poles = Vec::new();
+    let fs2 = 2.0 * sample_rate;
 
-    // Transform zeros: z = (fs/2 + s) / (fs/2 - s)
-    for &zero in zeros {
-        let digital_zero = (fs_2 + zero) / (fs_2 - zero);
-        digital_zeros.push(digital_zero);
-    }
+    // z = (2 fs + s) / (2 fs - s) for finite zeros and poles
+    let mut digital_zeros: Vec<_> = zeros.iter().map(|&z| (fs2 + z) / (fs2 - z)).collect();
+    let digital_poles: Vec<_> = poles.iter().map(|&p| (fs2 + p) / (fs2 - p)).collect();
 
-    // Transform poles: z = (fs/2 + s) / (fs/2 - s)
-    for &pole in poles {
-        let digital_pole = (fs_2 + pole) / (fs_2 - pole);
-        digital_poles.push(digital_pole);
-    }
+    // Zeros at infinity move to the Nyquist frequency
+    digital_zeros.resize(poles.len(), Complex64::new(-1.0, 0.0));
 
-    // Adjust gain for the bilinear transform
-    // The gain adjustment accounts for the frequency scaling
-    let degree_diff = poles.len() as i32 - zeros.len() as i32;
-    let digital_gain = gain * fs_2.powi(degree_diff);
+    let num: Complex64 = zeros.iter().map(|&z| fs2 - z).product();
+    let den: Complex64 = poles.iter().map(|&p| fs2 - p).product();
+    let digital_gain = gain * (num / den).re;
 
     Ok((digital_zeros, digital_poles, digital_gain))
 }
@@ -237,6 +242,152 @@ pub fn tf_to_zpk(b: &[f64], a: &[f64]) -> SignalResult<(Vec<Complex64>, Vec<Comp
     Ok((zeros, poles, gain))
 }
 
+/// Convert zeros, poles, and gain to second-order sections
+///
+/// Poles and zeros are paired so that each biquad groups a pole pair with
+/// the zeros nearest to it, working outwards from the poles closest to the
+/// unit circle; those end up in the last sections. This is the "nearest"
+/// pairing used by SciPy's `zpk2sos`. Cascading biquads avoids the loss of
+/// precision that high-order transfer function polynomials suffer from.
+///
+/// Complex zeros and poles must come in conjugate pairs. When the numbers of
+/// zeros and poles differ, the shorter list is padded with roots at the
+/// origin.
+///
+/// # Arguments
+///
+/// * `zeros` - Filter zeros
+/// * `poles` - Filter poles
+/// * `gain` - Filter gain, applied to the first section
+///
+/// # Returns
+///
+/// * Sections `[b0, b1, b2, a0, a1, a2]` with `a0 = 1`
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter::transform::zpk_to_sos;
+/// use num_complex::Complex64;
+///
+/// let zeros = vec![Complex64::new(-1.0, 0.0); 4];
+/// let poles = vec![
+///     Complex64::from_polar(0.9, 0.5),
+///     Complex64::from_polar(0.9, -0.5),
+///     Complex64::from_polar(0.5, 1.0),
+///     Complex64::from_polar(0.5, -1.0),
+/// ];
+/// let sos = zpk_to_sos(&zeros, &poles, 0.1).unwrap();
+/// assert_eq!(sos.len(), 2);
+/// // The poles nearest the unit circle are in the last section
+/// assert!((sos[1][5] - 0.81).abs() < 1e-12);
+/// ```
+pub fn zpk_to_sos(
+    zeros: &[Complex64],
+    poles: &[Complex64],
+    gain: f64,
+) -> SignalResult<SecondOrderSections> {
+    if zeros.is_empty() && poles.is_empty() {
+        return Ok(vec![[gain, 0.0, 0.0, 1.0, 0.0, 0.0]]);
+    }
+
+    // Pad with roots at the origin to an even common length
+    let mut length = zeros.len().max(poles.len());
+    length += length % 2;
+    let origin = Complex64::new(0.0, 0.0);
+    let mut z = conjugate_representatives(zeros, length - zeros.len())?;
+    let mut p = conjugate_representatives(poles, length - poles.len())?;
+
+    let is_real = |c: &Complex64| c.im == 0.0;
+    let n_sections = length / 2;
+    let mut sos = vec![[0.0; 6]; n_sections];
+    for section in sos.iter_mut().rev() {
+        // The pole closest to the unit circle is the hardest to realize
+        let p1 = take_nearest(&mut p, |c| (1.0 - c.norm()).abs(), |_| true);
+        let (z1, z2, p2);
+        if is_real(&p1) && !p.iter().any(is_real) {
+            // Lone real pole: first-order section with the nearest real zero
+            z1 = take_nearest(&mut z, |c| (c - p1).norm(), is_real);
+            z2 = origin;
+            p2 = origin;
+        } else {
+            let n_real_zeros = z.iter().filter(|c| is_real(c)).count();
+            let zero1 = if !is_real(&p1) && n_real_zeros == 1 {
+                // Keep the lone real zero for a later first-order section
+                take_nearest(&mut z, |c| (c - p1).norm(), |c| !is_real(c))
+            } else {
+                take_nearest(&mut z, |c| (c - p1).norm(), |_| true)
+            };
+            z1 = zero1;
+            if !is_real(&p1) {
+                p2 = p1.conj();
+                z2 = if is_real(&z1) {
+                    take_nearest(&mut z, |c| (c - p1).norm(), is_real)
+                } else {
+                    z1.conj()
+                };
+            } else if !is_real(&z1) {
+                z2 = z1.conj();
+                p2 = take_nearest(&mut p, |c| (c - z1).norm(), is_real);
+            } else {
+                p2 = take_nearest(&mut p, |c| (1.0 - c.norm()).abs(), is_real);
+                z2 = take_nearest(&mut z, |c| (c - p2).norm(), is_real);
+            }
+        }
+
+        let (b, a) = zpk_to_tf(&[z1, z2], &[p1, p2], 1.0)?;
+        *section = [b[0], b[1], b[2], a[0], a[1], a[2]];
+    }
+
+    for coeff in sos[0].iter_mut().take(3) {
+        *coeff *= gain;
+    }
+    Ok(sos)
+}
+
+/// Keeps one root of every conjugate pair, plus the real roots and `pad`
+/// roots at the origin, marking real roots with an exactly zero imaginary part
+fn conjugate_representatives(roots: &[Complex64], pad: usize) -> SignalResult<Vec<Complex64>> {
+    let scale = roots.iter().map(|c| c.norm()).fold(1.0, f64::max);
+    let tol = 100.0 * f64::EPSILON * scale;
+
+    let mut kept = vec![Complex64::new(0.0, 0.0); pad];
+    let mut upper = 0usize;
+    let mut lower = 0usize;
+    for &root in roots {
+        if root.im.abs() <= tol {
+            kept.push(Complex64::new(root.re, 0.0));
+        } else if root.im > 0.0 {
+            kept.push(root);
+            upper += 1;
+        } else {
+            lower += 1;
+        }
+    }
+    if upper != lower {
+        return Err(SignalError::ValueError(
+            "Complex zeros and poles must come in conjugate pairs".to_string(),
+        ));
+    }
+    Ok(kept)
+}
+
+/// Removes and returns the root minimizing `distance` among those accepted by `filter`
+fn take_nearest(
+    roots: &mut Vec<Complex64>,
+    distance: impl Fn(&Complex64) -> f64,
+    filter: impl Fn(&Complex64) -> bool,
+) -> Complex64 {
+    let index = roots
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| filter(c))
+        .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+        .map(|(i, _)| i)
+        .expect("pairing always leaves a matching root");
+    roots.remove(index)
+}
+
 /// Apply lowpass to lowpass frequency transformation
 ///
 /// Transforms a lowpass prototype filter to another lowpass filter with
@@ -513,6 +664,177 @@ pub fn lp_to_bs_transform(
     Ok((transformed_zeros, transformed_poles, transformed_gain))
 }
 
+/// Transform an analog lowpass prototype to a lowpass filter
+///
+/// Substitutes `s -> s / wo` in an analog filter given in zeros-poles-gain
+/// form.
+///
+/// # Arguments
+///
+/// * `zeros` - Prototype zeros
+/// * `poles` - Prototype poles
+/// * `gain` - Prototype gain
+/// * `wo` - Desired cutoff frequency (rad/s)
+///
+/// # Returns
+///
+/// * Transformed analog (zeros, poles, gain)
+pub fn lp_to_lp_zpk(
+    zeros: &[Complex64],
+    poles: &[Complex64],
+    gain: f64,
+    wo: f64,
+) -> SignalResult<ZerosPolesGain> {
+    let degree = relative_degree(zeros, poles)?;
+    check_angular_frequency(wo, "Cutoff frequency")?;
+
+    let z = zeros.iter().map(|&z| z * wo).collect();
+    let p = poles.iter().map(|&p| p * wo).collect();
+    Ok((z, p, gain * wo.powi(degree as i32)))
+}
+
+/// Transform an analog lowpass prototype to a highpass filter
+///
+/// Substitutes `s -> wo / s`. Zeros at infinity become zeros at the origin.
+///
+/// # Arguments
+///
+/// * `zeros` - Prototype zeros
+/// * `poles` - Prototype poles
+/// * `gain` - Prototype gain
+/// * `wo` - Desired cutoff frequency (rad/s)
+///
+/// # Returns
+///
+/// * Transformed analog (zeros, poles, gain)
+pub fn lp_to_hp_zpk(
+    zeros: &[Complex64],
+    poles: &[Complex64],
+    gain: f64,
+    wo: f64,
+) -> SignalResult<ZerosPolesGain> {
+    let degree = relative_degree(zeros, poles)?;
+    check_angular_frequency(wo, "Cutoff frequency")?;
+
+    let mut z: Vec<_> = zeros.iter().map(|&z| wo / z).collect();
+    let p = poles.iter().map(|&p| wo / p).collect();
+    z.extend(std::iter::repeat_n(Complex64::new(0.0, 0.0), degree));
+
+    let num: Complex64 = zeros.iter().map(|&z| -z).product();
+    let den: Complex64 = poles.iter().map(|&p| -p).product();
+    Ok((z, p, gain * (num / den).re))
+}
+
+/// Transform an analog lowpass prototype to a bandpass filter
+///
+/// Substitutes `s -> (s^2 + wo^2) / (s bw)`, splitting every root into two.
+/// Zeros at infinity become zeros at the origin and at infinity.
+///
+/// # Arguments
+///
+/// * `zeros` - Prototype zeros
+/// * `poles` - Prototype poles
+/// * `gain` - Prototype gain
+/// * `wo` - Desired center frequency (rad/s)
+/// * `bw` - Desired bandwidth (rad/s)
+///
+/// # Returns
+///
+/// * Transformed analog (zeros, poles, gain)
+pub fn lp_to_bp_zpk(
+    zeros: &[Complex64],
+    poles: &[Complex64],
+    gain: f64,
+    wo: f64,
+    bw: f64,
+) -> SignalResult<ZerosPolesGain> {
+    let degree = relative_degree(zeros, poles)?;
+    check_angular_frequency(wo, "Center frequency")?;
+    check_angular_frequency(bw, "Bandwidth")?;
+
+    let split = |roots: &[Complex64]| -> Vec<Complex64> {
+        let scaled: Vec<_> = roots.iter().map(|&r| r * bw / 2.0).collect();
+        let offsets: Vec<_> = scaled.iter().map(|&r| (r * r - wo * wo).sqrt()).collect();
+        scaled
+            .iter()
+            .zip(&offsets)
+            .map(|(&r, &d)| r + d)
+            .chain(scaled.iter().zip(&offsets).map(|(&r, &d)| r - d))
+            .collect()
+    };
+
+    let mut z = split(zeros);
+    let p = split(poles);
+    z.extend(std::iter::repeat_n(Complex64::new(0.0, 0.0), degree));
+    Ok((z, p, gain * bw.powi(degree as i32)))
+}
+
+/// Transform an analog lowpass prototype to a bandstop filter
+///
+/// Substitutes `s -> s bw / (s^2 + wo^2)`, splitting every root into two.
+/// Zeros at infinity become zeros at `±j wo`.
+///
+/// # Arguments
+///
+/// * `zeros` - Prototype zeros
+/// * `poles` - Prototype poles
+/// * `gain` - Prototype gain
+/// * `wo` - Desired center frequency (rad/s)
+/// * `bw` - Desired stopband width (rad/s)
+///
+/// # Returns
+///
+/// * Transformed analog (zeros, poles, gain)
+pub fn lp_to_bs_zpk(
+    zeros: &[Complex64],
+    poles: &[Complex64],
+    gain: f64,
+    wo: f64,
+    bw: f64,
+) -> SignalResult<ZerosPolesGain> {
+    let degree = relative_degree(zeros, poles)?;
+    check_angular_frequency(wo, "Center frequency")?;
+    check_angular_frequency(bw, "Bandwidth")?;
+
+    let split = |roots: &[Complex64]| -> Vec<Complex64> {
+        let inverted: Vec<_> = roots.iter().map(|&r| (bw / 2.0) / r).collect();
+        let offsets: Vec<_> = inverted.iter().map(|&r| (r * r - wo * wo).sqrt()).collect();
+        inverted
+            .iter()
+            .zip(&offsets)
+            .map(|(&r, &d)| r + d)
+            .chain(inverted.iter().zip(&offsets).map(|(&r, &d)| r - d))
+            .collect()
+    };
+
+    let mut z = split(zeros);
+    let p = split(poles);
+    z.extend(std::iter::repeat_n(Complex64::new(0.0, wo), degree));
+    z.extend(std::iter::repeat_n(Complex64::new(0.0, -wo), degree));
+
+    let num: Complex64 = zeros.iter().map(|&z| -z).product();
+    let den: Complex64 = poles.iter().map(|&p| -p).product();
+    Ok((z, p, gain * (num / den).re))
+}
+
+/// Number of zeros at infinity of a proper analog filter
+fn relative_degree(zeros: &[Complex64], poles: &[Complex64]) -> SignalResult<usize> {
+    poles.len().checked_sub(zeros.len()).ok_or_else(|| {
+        SignalError::ValueError(
+            "Analog filter must have at least as many poles as zeros".to_string(),
+        )
+    })
+}
+
+fn check_angular_frequency(value: f64, name: &str) -> SignalResult<()> {
+    if !(value.is_finite() && value > 0.0) {
+        return Err(SignalError::ValueError(format!(
+            "{name} must be positive, got {value}"
+        )));
+    }
+    Ok(())
+}
+
 /// Normalize filter coefficients
 ///
 /// Normalizes filter coefficients to ensure the denominator has a leading
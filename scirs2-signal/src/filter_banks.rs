@@ -11,6 +11,8 @@
 //! - **Cosine Modulated Banks**: Efficient filter banks using cosine modulation
 //! - **Oversampled Banks**: Filter banks with oversampling for reduced aliasing
 //! - **Polyphase Filter Banks**: Efficient implementation using polyphase decomposition
+//! - **Polyphase DFT Filter Banks**: Complex channelizers, critically sampled or
+//!   oversampled, with near-perfect-reconstruction prototypes
 //!
 //! ## Features
 //!
@@ -898,6 +900,329 @@ impl CosineModulatedFilterBank {
     }
 }
 
+/// Polyphase DFT filter bank (channelizer)
+///
+/// Splits a complex baseband signal into `num_channels` equally spaced
+/// channels. Channel `k` is the input shifted down by `k / num_channels` of
+/// the sample rate, lowpass filtered by the prototype and decimated by
+/// `decimation`; channels above half the sample rate correspond to negative
+/// frequencies. Analysis and synthesis are implemented with a polyphase
+/// structure and one FFT of size `num_channels` per output frame.
+///
+/// With `decimation == num_channels` the bank is critically sampled and every
+/// channel runs at the channel spacing, which is the usual choice for pure
+/// channelization. With `decimation == num_channels / 2` (or any smaller
+/// divisor) it is oversampled, which keeps the transition bands of
+/// neighbouring channels from aliasing: the default root-raised-cosine
+/// prototype then gives near-perfect reconstruction (around 60 dB SNR with 12
+/// taps per channel), limited only by the truncation of the prototype. A
+/// critically sampled DFT bank cannot cancel the aliasing at the channel
+/// edges, so its synthesis output is only an approximation of the input.
+#[derive(Debug, Clone)]
+pub struct PolyphaseFilterBank {
+    /// Prototype lowpass filter (unit DC gain)
+    pub prototype: Array1<f64>,
+    /// Number of channels
+    pub num_channels: usize,
+    /// Decimation factor of the channel signals
+    pub decimation: usize,
+}
+
+impl PolyphaseFilterBank {
+    /// Create a polyphase DFT filter bank with a root-raised-cosine prototype
+    ///
+    /// The prototype is designed by [`design_npr_prototype`] with a rolloff of
+    /// 1.0 for 2x (or more) oversampling and 0.2 for critical sampling.
+    ///
+    /// # Arguments
+    /// * `num_channels` - Number of channels
+    /// * `decimation` - Decimation factor; must divide `num_channels`
+    /// * `taps_per_channel` - Prototype length in units of `num_channels`
+    ///
+    /// # Returns
+    /// * Polyphase filter bank instance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_complex::Complex64;
+    /// use scirs2_signal::filter_banks::PolyphaseFilterBank;
+    /// use std::f64::consts::PI;
+    ///
+    /// // Two tones in channels 1 and 6 of an 8-channel, 2x oversampled bank
+    /// let bank = PolyphaseFilterBank::new(8, 4, 12).unwrap();
+    /// let signal: Vec<Complex64> = (0..512)
+    ///     .map(|n| {
+    ///         let t = n as f64;
+    ///         Complex64::from_polar(1.0, 2.0 * PI * t / 8.0)
+    ///             + Complex64::from_polar(0.5, -2.0 * PI * 2.0 * t / 8.0)
+    ///     })
+    ///     .collect();
+    ///
+    /// let channels = bank.analysis(&signal).unwrap();
+    /// assert_eq!(channels.nrows(), 8);
+    ///
+    /// // In steady state each tone appears at DC of its own channel
+    /// assert!((channels[[1, 60]].norm() - 1.0).abs() < 1e-2);
+    /// assert!((channels[[6, 60]].norm() - 0.5).abs() < 1e-2);
+    /// assert!(channels[[3, 60]].norm() < 1e-2);
+    ///
+    /// // Synthesis reconstructs the signal after the bank delay
+    /// let reconstructed = bank.synthesis(&channels).unwrap();
+    /// let delay = bank.delay();
+    /// assert!((reconstructed[200 + delay] - signal[200]).norm() < 1e-2);
+    /// ```
+    pub fn new(
+        num_channels: usize,
+        decimation: usize,
+        taps_per_channel: usize,
+    ) -> SignalResult<Self> {
+        let rolloff = if 2 * decimation <= num_channels {
+            1.0
+        } else {
+            0.2
+        };
+        let prototype = design_npr_prototype(num_channels, taps_per_channel, rolloff)?;
+        Self::with_prototype(&prototype, num_channels, decimation)
+    }
+
+    /// Create a polyphase DFT filter bank with a custom prototype filter
+    ///
+    /// The same prototype is used for analysis and synthesis. It is rescaled to
+    /// unit DC gain so that a tone at a channel centre keeps its amplitude.
+    ///
+    /// # Arguments
+    /// * `prototype` - Prototype lowpass filter coefficients
+    /// * `num_channels` - Number of channels
+    /// * `decimation` - Decimation factor; must divide `num_channels`
+    ///
+    /// # Returns
+    /// * Polyphase filter bank instance
+    pub fn with_prototype(
+        prototype: &Array1<f64>,
+        num_channels: usize,
+        decimation: usize,
+    ) -> SignalResult<Self> {
+        if num_channels < 2 {
+            return Err(SignalError::ValueError(
+                "Number of channels must be at least 2".to_string(),
+            ));
+        }
+        if decimation == 0 || !num_channels.is_multiple_of(decimation) {
+            return Err(SignalError::ValueError(format!(
+                "Decimation factor {} must divide the number of channels {}",
+                decimation, num_channels
+            )));
+        }
+        let gain = prototype.sum();
+        if prototype.is_empty() || gain.abs() < f64::EPSILON {
+            return Err(SignalError::ValueError(
+                "Prototype filter must be non-empty with non-zero DC gain".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            prototype: prototype / gain,
+            num_channels,
+            decimation,
+        })
+    }
+
+    /// Whether every channel runs at the channel spacing (`decimation == num_channels`)
+    pub fn is_critically_sampled(&self) -> bool {
+        self.decimation == self.num_channels
+    }
+
+    /// Delay in input samples between the input and the synthesis output
+    ///
+    /// Sample `n` of the input is reconstructed at index `n + delay()` of
+    /// [`PolyphaseFilterBank::synthesis`].
+    pub fn delay(&self) -> usize {
+        self.prototype.len() - 1
+    }
+
+    /// Centre frequency of a channel for a given sample rate
+    ///
+    /// Channels in the upper half of the bank are reported as negative
+    /// frequencies.
+    ///
+    /// # Arguments
+    /// * `channel` - Channel index
+    /// * `fs` - Sample rate of the wideband input
+    ///
+    /// # Returns
+    /// * Centre frequency in the units of `fs`
+    pub fn channel_frequency(&self, channel: usize, fs: f64) -> f64 {
+        let k = channel % self.num_channels;
+        let k = if 2 * k > self.num_channels {
+            k as f64 - self.num_channels as f64
+        } else {
+            k as f64
+        };
+        k * fs / self.num_channels as f64
+    }
+
+    /// Split a wideband signal into channels
+    ///
+    /// # Arguments
+    /// * `input` - Complex baseband input signal
+    ///
+    /// # Returns
+    /// * Channel signals, one row per channel and `ceil((N + L - 1) / decimation)`
+    ///   columns for an input of length `N` and a prototype of length `L`
+    pub fn analysis(&self, input: &[Complex64]) -> SignalResult<Array2<Complex64>> {
+        if input.is_empty() {
+            return Err(SignalError::ValueError("Input signal is empty".to_string()));
+        }
+
+        let m = self.num_channels;
+        let len = self.prototype.len();
+        let num_frames = (input.len() + len - 1).div_ceil(self.decimation);
+        let fft = rustfft::FftPlanner::new().plan_fft_forward(m);
+
+        let mut channels = Array2::<Complex64>::zeros((m, num_frames));
+        let mut buffer = vec![Complex64::new(0.0, 0.0); m];
+
+        for frame in 0..num_frames {
+            // Fold the windowed input onto the channel grid, keyed by the
+            // absolute sample index so the DFT also removes the carrier
+            let t = frame * self.decimation;
+            buffer.fill(Complex64::new(0.0, 0.0));
+            for (n, &h) in self.prototype.iter().enumerate() {
+                if n > t || t - n >= input.len() {
+                    continue;
+                }
+                let s = t - n;
+                buffer[s % m] += input[s] * h;
+            }
+            fft.process(&mut buffer);
+            for (k, &value) in buffer.iter().enumerate() {
+                channels[[k, frame]] = value;
+            }
+        }
+
+        Ok(channels)
+    }
+
+    /// Reconstruct a wideband signal from its channels
+    ///
+    /// # Arguments
+    /// * `channels` - Channel signals as returned by [`PolyphaseFilterBank::analysis`]
+    ///
+    /// # Returns
+    /// * Reconstructed signal of length `frames * decimation + L - 1`, delayed by
+    ///   [`PolyphaseFilterBank::delay`] samples
+    pub fn synthesis(&self, channels: &Array2<Complex64>) -> SignalResult<Vec<Complex64>> {
+        let m = self.num_channels;
+        if channels.nrows() != m {
+            return Err(SignalError::DimensionError(format!(
+                "Expected {} channels, got {}",
+                m,
+                channels.nrows()
+            )));
+        }
+
+        let len = self.prototype.len();
+        let num_frames = channels.ncols();
+        let ifft = rustfft::FftPlanner::new().plan_fft_inverse(m);
+
+        // Overall gain of analysis followed by synthesis is M/D * sum(h^2)
+        let energy: f64 = self.prototype.iter().map(|h| h * h).sum();
+        let scale = self.decimation as f64 / (m as f64 * energy);
+
+        let mut output = vec![Complex64::new(0.0, 0.0); num_frames * self.decimation + len - 1];
+        let mut buffer = vec![Complex64::new(0.0, 0.0); m];
+
+        for frame in 0..num_frames {
+            for (k, value) in buffer.iter_mut().enumerate() {
+                *value = channels[[k, frame]];
+            }
+            ifft.process(&mut buffer);
+
+            // Remodulate every channel and interpolate with the prototype
+            let t = frame * self.decimation;
+            for (a, &g) in self.prototype.iter().enumerate() {
+                output[t + a] += buffer[(t + a) % m] * (g * scale);
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Design a near-perfect-reconstruction prototype for a polyphase DFT filter bank
+///
+/// The prototype is a root-raised-cosine pulse with a symbol period of
+/// `num_channels` samples, so that the cascade of analysis and synthesis
+/// filters is a Nyquist filter and the shifted channel responses are power
+/// complementary. It has `taps_per_channel * num_channels + 1` taps, is
+/// symmetric and normalized to unit DC gain. With an oversampled bank whose
+/// decimation satisfies `(1 + rolloff) * decimation <= num_channels`, the only
+/// reconstruction error left is the truncation of the pulse, which falls
+/// quickly with `taps_per_channel`.
+///
+/// # Arguments
+/// * `num_channels` - Number of channels of the bank
+/// * `taps_per_channel` - Prototype length in units of `num_channels`
+/// * `rolloff` - Excess bandwidth in (0, 1]
+///
+/// # Returns
+/// * Prototype filter coefficients
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::filter_banks::design_npr_prototype;
+///
+/// let h = design_npr_prototype(16, 8, 0.5).unwrap();
+/// assert_eq!(h.len(), 129);
+/// assert!((h.sum() - 1.0).abs() < 1e-12);
+/// ```
+pub fn design_npr_prototype(
+    num_channels: usize,
+    taps_per_channel: usize,
+    rolloff: f64,
+) -> SignalResult<Array1<f64>> {
+    if num_channels < 2 {
+        return Err(SignalError::ValueError(
+            "Number of channels must be at least 2".to_string(),
+        ));
+    }
+    if taps_per_channel == 0 {
+        return Err(SignalError::ValueError(
+            "Taps per channel must be positive".to_string(),
+        ));
+    }
+    if !(rolloff > 0.0 && rolloff <= 1.0) {
+        return Err(SignalError::ValueError(format!(
+            "Rolloff must be in (0, 1], got {}",
+            rolloff
+        )));
+    }
+
+    let period = num_channels as f64;
+    let len = taps_per_channel * num_channels + 1;
+    let centre = (len - 1) as f64 / 2.0;
+
+    let mut prototype = Array1::from_shape_fn(len, |n| {
+        let x = (n as f64 - centre) / period;
+        if x.abs() < 1e-12 {
+            1.0 - rolloff + 4.0 * rolloff / PI
+        } else if (4.0 * rolloff * x.abs() - 1.0).abs() < 1e-9 {
+            let arg = PI / (4.0 * rolloff);
+            rolloff / 2f64.sqrt() * ((1.0 + 2.0 / PI) * arg.sin() + (1.0 - 2.0 / PI) * arg.cos())
+        } else {
+            ((PI * x * (1.0 - rolloff)).sin()
+                + 4.0 * rolloff * x * (PI * x * (1.0 + rolloff)).cos())
+                / (PI * x * (1.0 - (4.0 * rolloff * x).powi(2)))
+        }
+    });
+
+    let gain = prototype.sum();
+    prototype /= gain;
+    Ok(prototype)
+}
+
 /// Filter bank analysis results
 #[derive(Debug, Clone, Default)]
 pub struct FilterBankAnalysis {
@@ -1135,6 +1460,90 @@ mod tests {
         assert!(analysis.amplitude_distortion >= 0.0);
     }
 
+    #[test]
+    fn test_polyphase_filter_bank_reconstruction() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        // Deterministic complex noise covering the whole band
+        let mut rng = StdRng::seed_from_u64(7);
+        let signal: Vec<Complex64> = (0..3000)
+            .map(|_| Complex64::new(rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0)))
+            .collect();
+
+        for (channels, decimation) in [(8, 4), (16, 8), (16, 4)] {
+            let bank = PolyphaseFilterBank::new(channels, decimation, 12).unwrap();
+            assert!(!bank.is_critically_sampled());
+
+            let subbands = bank.analysis(&signal).unwrap();
+            assert_eq!(subbands.nrows(), channels);
+            assert_eq!(
+                subbands.ncols(),
+                (signal.len() + bank.prototype.len() - 1).div_ceil(decimation)
+            );
+
+            let reconstructed = bank.synthesis(&subbands).unwrap();
+            let delay = bank.delay();
+            let (mut error, mut power) = (0.0, 0.0);
+            for n in 0..signal.len() {
+                error += (reconstructed[n + delay] - signal[n]).norm_sqr();
+                power += signal[n].norm_sqr();
+            }
+            let snr = 10.0 * (power / error).log10();
+            assert!(snr > 50.0, "M={channels} D={decimation}: SNR {snr:.1} dB");
+        }
+    }
+
+    #[test]
+    fn test_polyphase_channelizer() {
+        // Tones at the centres of channels 2 and 13 (= -3) of a 16-channel bank
+        let signal: Vec<Complex64> = (0..2048)
+            .map(|n| {
+                let t = n as f64;
+                Complex64::from_polar(1.0, 2.0 * PI * 2.0 * t / 16.0 + 0.3)
+                    + Complex64::from_polar(0.25, -2.0 * PI * 3.0 * t / 16.0)
+            })
+            .collect();
+
+        for decimation in [16, 8] {
+            let bank = PolyphaseFilterBank::new(16, decimation, 8).unwrap();
+            assert_eq!(bank.is_critically_sampled(), decimation == 16);
+
+            let subbands = bank.analysis(&signal).unwrap();
+            let frame = subbands.ncols() / 2;
+            for k in 0..16 {
+                let magnitude = subbands[[k, frame]].norm();
+                match k {
+                    2 => {
+                        assert!((magnitude - 1.0).abs() < 1e-2);
+                        // The channel is demodulated to DC and keeps the phase
+                        assert!((subbands[[k, frame]].arg() - 0.3).abs() < 1e-2);
+                    }
+                    13 => assert!((magnitude - 0.25).abs() < 1e-2),
+                    // Neighbouring channels share the transition band of the prototype
+                    1 | 3 | 12 | 14 => assert!(magnitude < 5e-2),
+                    _ => assert!(magnitude < 1e-2, "leakage into channel {k}: {magnitude}"),
+                }
+            }
+        }
+
+        let bank = PolyphaseFilterBank::new(16, 16, 4).unwrap();
+        assert_eq!(bank.channel_frequency(2, 1600.0), 200.0);
+        assert_eq!(bank.channel_frequency(13, 1600.0), -300.0);
+        assert_eq!(bank.channel_frequency(8, 1600.0), 800.0);
+    }
+
+    #[test]
+    fn test_polyphase_filter_bank_errors() {
+        assert!(PolyphaseFilterBank::new(8, 3, 4).is_err());
+        assert!(PolyphaseFilterBank::new(1, 1, 4).is_err());
+        assert!(design_npr_prototype(8, 4, 1.5).is_err());
+        assert!(design_npr_prototype(8, 0, 0.5).is_err());
+
+        let bank = PolyphaseFilterBank::new(8, 4, 4).unwrap();
+        assert!(bank.analysis(&[]).is_err());
+        assert!(bank.synthesis(&Array2::zeros((4, 10))).is_err());
+    }
+
     #[test]
     fn test_iir_stabilization() {
         let b = Array1::from_vec(vec![1.0, 0.5]);
@@ -5,12 +5,14 @@
 //!
 //! The Hilbert transform is useful for creating analytic signals,
 //! computing instantaneous frequency and amplitude, and other signal
-//! processing applications.
+//! processing applications. Moving-average smoothed variants of the envelope
+//! and instantaneous frequency are provided for noisy measurements such as
+//! vibration and biomedical recordings.
 
 use crate::error::{SignalError, SignalResult};
 use num_complex::Complex64;
 use num_traits::{Float, NumCast};
-use rustfft;
+use std::f64::consts::PI;
 use std::fmt::Debug;
 
 /// Compute the Hilbert transform of a real-valued signal.
@@ -66,6 +68,11 @@ use std::fmt::Debug;
 ///
 /// // Average magnitude should be reasonably close to 1.0 (allowing for FFT edge effects)
 /// assert!((avg_magnitude - 1.0).abs() < 0.5);
+///
+/// // The real part of the analytic signal is the original signal
+/// for (z, x) in analytic_signal.iter().zip(&signal) {
+///     assert!((z.re - x).abs() < 1e-10);
+/// }
 /// ```
 ///
 /// # References
@@ -94,43 +101,18 @@ where
         .collect::<SignalResult<Vec<_>>>()?;
 
     // Compute FFT of the input signal
-    let spectrum = scirs2_fft::fft(&signal, None)
+    let spectrum = scirs2_fft::fft(&signal, Some(n))
         .map_err(|e| SignalError::ComputationError(format!("FFT computation error: {e}")))?;
 
-    // Create the frequency domain filter for the Hilbert transform
-    // For a proper Hilbert transform, we need to:
-    // 1. Set the DC component (0 frequency) to 1
-    // 2. Double the positive frequencies and multiply by -i
-    // 3. Zero out the negative frequencies
-    let mut h = vec![Complex64::new(1.0, 0.0); n];
-
-    if n % 2 == 0 {
-        // Even length case
-        h[0] = Complex64::new(1.0, 0.0); // DC component
-        h[n / 2] = Complex64::new(1.0, 0.0); // Nyquist component
-
-        // Positive frequencies (multiply by 2 and by -i)
-        h.iter_mut().take(n / 2).skip(1).for_each(|val| {
-            *val = Complex64::new(0.0, -2.0); // Equivalent to 2 * (-i)
-        });
-
-        // Negative frequencies (set to 0)
-        h.iter_mut().skip(n / 2 + 1).for_each(|val| {
-            *val = Complex64::new(0.0, 0.0);
-        });
+    // Frequency domain weights of the analytic signal: keep DC (and Nyquist for
+    // even lengths), double the positive frequencies and zero the negative ones
+    let mut h = vec![0.0; n];
+    h[0] = 1.0;
+    if n.is_multiple_of(2) {
+        h[n / 2] = 1.0;
+        h[1..n / 2].iter_mut().for_each(|v| *v = 2.0);
     } else {
-        // Odd length case
-        h[0] = Complex64::new(1.0, 0.0); // DC component
-
-        // Positive frequencies (multiply by 2 and by -i)
-        h.iter_mut().take(n.div_ceil(2)).skip(1).for_each(|val| {
-            *val = Complex64::new(0.0, -2.0); // Equivalent to 2 * (-i)
-        });
-
-        // Negative frequencies (set to 0)
-        h.iter_mut().skip(n.div_ceil(2)).for_each(|val| {
-            *val = Complex64::new(0.0, 0.0);
-        });
+        h[1..n.div_ceil(2)].iter_mut().for_each(|v| *v = 2.0);
     }
 
     // Apply the filter in frequency domain
@@ -140,26 +122,9 @@ where
         .map(|(&s, &h)| s * h)
         .collect();
 
-    // We need a more robust approach for computing the inverse FFT
-    // Let's use the rustfft library directly, which is what scirs2_fft uses internally
-    let mut planner = rustfft::FftPlanner::<f64>::new();
-    let fft = planner.plan_fft_inverse(n);
-
-    // Convert our filtered_spectrum to the rustfft Complex type
-    let mut buffer: Vec<rustfft::num_complex::Complex<f64>> = filtered_spectrum
-        .iter()
-        .map(|&c| rustfft::num_complex::Complex::<f64>::new(c.re, c.im))
-        .collect();
-
-    // Perform the inverse FFT in-place
-    fft.process(&mut buffer);
-
-    // Scale the output (IFFT normalization)
-    let scale = 1.0 / n as f64;
-    let analytic_signal: Vec<Complex64> = buffer
-        .into_iter()
-        .map(|c| Complex64::new(c.re * scale, c.im * scale))
-        .collect();
+    let analytic_signal = scirs2_fft::ifft(&filtered_spectrum, Some(n)).map_err(|e| {
+        SignalError::ComputationError(format!("Inverse FFT computation error: {e}"))
+    })?;
 
     Ok(analytic_signal)
 }
@@ -261,13 +226,17 @@ pub fn instantaneous_frequency<T>(x: &[T], fs: f64) -> SignalResult<Vec<f64>>
 where
     T: Float + NumCast + Debug,
 {
-    use std::f64::consts::PI;
-
     // Check input
     if x.is_empty() {
         return Err(SignalError::ValueError("Input array is empty".to_string()));
     }
 
+    if x.len() < 2 {
+        return Err(SignalError::ValueError(
+            "At least two samples are needed to estimate a frequency".to_string(),
+        ));
+    }
+
     if fs <= 0.0 {
         return Err(SignalError::ValueError(
             "Sampling frequency must be positive".to_string(),
@@ -281,23 +250,7 @@ where
     let phase: Vec<f64> = analytic.iter().map(|c| c.im.atan2(c.re)).collect();
 
     // Unwrap phase to handle phase jumps of more than π
-    let mut unwrapped_phase = vec![phase[0]];
-    let mut prev_phase = phase[0];
-
-    for &p in &phase[1..] {
-        let mut diff = p - prev_phase;
-
-        // Handle phase wrapping
-        while diff > PI {
-            diff -= 2.0 * PI;
-        }
-        while diff < -PI {
-            diff += 2.0 * PI;
-        }
-
-        unwrapped_phase.push(unwrapped_phase.last().unwrap() + diff);
-        prev_phase = p;
-    }
+    let unwrapped_phase = unwrap_phase(&phase);
 
     // Compute instantaneous frequency
     let mut inst_freq = Vec::with_capacity(x.len());
@@ -369,8 +322,6 @@ pub fn instantaneous_phase<T>(x: &[T], unwrap: bool) -> SignalResult<Vec<f64>>
 where
     T: Float + NumCast + Debug,
 {
-    use std::f64::consts::PI;
-
     // Check input
     if x.is_empty() {
         return Err(SignalError::ValueError("Input array is empty".to_string()));
@@ -387,25 +338,151 @@ where
     }
 
     // Unwrap phase to handle phase jumps of more than π
-    let mut unwrapped_phase = vec![phase[0]];
-    let mut prev_phase = phase[0];
+    let unwrapped_phase = unwrap_phase(&phase);
 
-    for &p in &phase[1..] {
-        let mut diff = p - prev_phase;
+    Ok(unwrapped_phase)
+}
 
-        // Handle phase wrapping
-        while diff > PI {
-            diff -= 2.0 * PI;
-        }
-        while diff < -PI {
-            diff += 2.0 * PI;
+/// Compute the envelope of a signal, smoothed with a centered moving average.
+///
+/// The raw Hilbert envelope of a real signal carries ripple from noise and from
+/// components outside the band of interest, such as a second carrier. Averaging
+/// it over `window` samples suppresses ripple at frequencies of `fs / window`
+/// and above, which is usually what is wanted for vibration (bearing fault)
+/// and biomedical (EMG, ECG) amplitude tracking. Near the ends the window is
+/// shortened rather than padded.
+///
+/// # Arguments
+///
+/// * `x` - Input signal (real-valued array)
+/// * `window` - Moving average length in samples; 1 disables smoothing
+///
+/// # Returns
+///
+/// * The smoothed envelope, with the same length as the input
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::hilbert::smoothed_envelope;
+/// use std::f64::consts::PI;
+///
+/// let fs = 100.0;
+/// // Unit carrier at 20 Hz plus a weaker 45 Hz tone beating at 25 Hz
+/// let signal: Vec<f64> = (0..1000)
+///     .map(|i| {
+///         let t = i as f64 / fs;
+///         (2.0 * PI * 20.0 * t).cos() + 0.2 * (2.0 * PI * 45.0 * t).cos()
+///     })
+///     .collect();
+///
+/// // Four samples span one beat period, so the ripple averages out
+/// let env = smoothed_envelope(&signal, 4).unwrap();
+/// assert!(env[100..900].iter().all(|&a| (a - 1.0).abs() < 0.05));
+/// ```
+pub fn smoothed_envelope<T>(x: &[T], window: usize) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    if window == 0 {
+        return Err(SignalError::ValueError(
+            "Smoothing window must be at least 1 sample".to_string(),
+        ));
+    }
+
+    Ok(moving_average(&envelope(x)?, window))
+}
+
+/// Compute the instantaneous frequency of a signal, smoothed with a centered
+/// moving average.
+///
+/// The instantaneous frequency is very sensitive to noise wherever the envelope
+/// is small; averaging over `window` samples gives a more stable estimate at
+/// the cost of time resolution.
+///
+/// # Arguments
+///
+/// * `x` - Input signal (real-valued array)
+/// * `fs` - Sampling frequency in Hz
+/// * `window` - Moving average length in samples; 1 disables smoothing
+///
+/// # Returns
+///
+/// * The smoothed instantaneous frequency in Hz
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::hilbert::smoothed_instantaneous_frequency;
+/// use std::f64::consts::PI;
+///
+/// let fs = 1000.0;
+/// let signal: Vec<f64> = (0..2000)
+///     .map(|i| (2.0 * PI * 50.0 * i as f64 / fs).sin())
+///     .collect();
+///
+/// let freq = smoothed_instantaneous_frequency(&signal, fs, 21).unwrap();
+/// assert!(freq[200..1800].iter().all(|&f| (f - 50.0).abs() < 0.5));
+/// ```
+pub fn smoothed_instantaneous_frequency<T>(
+    x: &[T],
+    fs: f64,
+    window: usize,
+) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    if window == 0 {
+        return Err(SignalError::ValueError(
+            "Smoothing window must be at least 1 sample".to_string(),
+        ));
+    }
+
+    Ok(moving_average(&instantaneous_frequency(x, fs)?, window))
+}
+
+/// Unwrap a phase sequence by removing jumps of more than π between samples
+fn unwrap_phase(phase: &[f64]) -> Vec<f64> {
+    let mut unwrapped = Vec::with_capacity(phase.len());
+    let mut offset = 0.0;
+    for (i, &p) in phase.iter().enumerate() {
+        if i > 0 {
+            let diff = p - phase[i - 1];
+            // Round the jump to the nearest multiple of 2π
+            offset -= 2.0 * PI * ((diff + PI) / (2.0 * PI)).floor();
         }
+        unwrapped.push(p + offset);
+    }
+    unwrapped
+}
 
-        unwrapped_phase.push(unwrapped_phase.last().unwrap() + diff);
-        prev_phase = p;
+/// Centered moving average whose window shrinks near the ends
+fn moving_average(x: &[f64], window: usize) -> Vec<f64> {
+    if window <= 1 || x.is_empty() {
+        return x.to_vec();
     }
 
-    Ok(unwrapped_phase)
+    let mut prefix = vec![0.0; x.len() + 1];
+    for (i, &v) in x.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + v;
+    }
+
+    let before = (window - 1) / 2;
+    let after = window - 1 - before;
+    (0..x.len())
+        .map(|i| {
+            // Keep the window symmetric about i so that linear trends are not biased
+            let half_before = before.min(i);
+            let half_after = after.min(x.len() - 1 - i);
+            let reach = half_before.min(half_after);
+            let (start, end) = if half_before == before && half_after == after {
+                (i - before, i + after + 1)
+            } else {
+                (i - reach, i + reach + 1)
+            };
+            (prefix[end] - prefix[start]) / (end - start) as f64
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -603,4 +680,78 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_analytic_signal_of_cosine() {
+        // An integer number of periods makes the FFT result exact
+        let n = 256;
+        let signal: Vec<f64> = (0..n)
+            .map(|i| (2.0 * PI * 8.0 * i as f64 / n as f64).cos())
+            .collect();
+        let analytic = hilbert(&signal).unwrap();
+
+        for (i, z) in analytic.iter().enumerate() {
+            let phase = 2.0 * PI * 8.0 * i as f64 / n as f64;
+            assert_relative_eq!(z.re, phase.cos(), epsilon = 1e-10);
+            assert_relative_eq!(z.im, phase.sin(), epsilon = 1e-10);
+        }
+
+        // The same holds for odd lengths
+        let signal: Vec<f64> = (0..255)
+            .map(|i| (2.0 * PI * 10.0 * i as f64 / 255.0).cos())
+            .collect();
+        let analytic = hilbert(&signal).unwrap();
+        for (i, z) in analytic.iter().enumerate() {
+            let phase = 2.0 * PI * 10.0 * i as f64 / 255.0;
+            assert_relative_eq!(z.im, phase.sin(), epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_smoothing_reduces_envelope_ripple() {
+        let fs = 200.0;
+        let n = 2000;
+        let modulation = |t: f64| 1.0 + 0.5 * (2.0 * PI * 1.0 * t).cos();
+        // AM carrier at 30 Hz plus an interfering tone 20 Hz away
+        let signal: Vec<f64> = (0..n)
+            .map(|i| {
+                let t = i as f64 / fs;
+                modulation(t) * (2.0 * PI * 30.0 * t).cos() + 0.2 * (2.0 * PI * 50.0 * t).cos()
+            })
+            .collect();
+
+        let raw = envelope(&signal).unwrap();
+        let smooth = smoothed_envelope(&signal, 10).unwrap();
+        assert_eq!(smooth.len(), n);
+
+        let max_error = |env: &[f64]| {
+            (n / 10..9 * n / 10)
+                .map(|i| (env[i] - modulation(i as f64 / fs)).abs())
+                .fold(0.0, f64::max)
+        };
+        assert!(max_error(&raw) > 0.15);
+        assert!(max_error(&smooth) < 0.05);
+
+        // A window of one sample is the raw envelope
+        assert_eq!(smoothed_envelope(&signal, 1).unwrap(), raw);
+        assert!(smoothed_envelope(&signal, 0).is_err());
+    }
+
+    #[test]
+    fn test_unwrap_phase_and_moving_average() {
+        let phase: Vec<f64> = (0..50).map(|i| 0.9 * i as f64).collect();
+        let wrapped: Vec<f64> = phase
+            .iter()
+            .map(|&p| (p + PI).rem_euclid(2.0 * PI) - PI)
+            .collect();
+        for (a, b) in unwrap_phase(&wrapped).iter().zip(&phase) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+
+        // Linear ramps pass through unchanged, including at the ends
+        let ramp: Vec<f64> = (0..20).map(|i| 2.0 * i as f64 + 1.0).collect();
+        for (a, b) in moving_average(&ramp, 5).iter().zip(&ramp) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+    }
 }
@@ -9,6 +9,98 @@ use crate::error::{SignalError, SignalResult};
 use ndarray::{Array1, Array2};
 use scirs2_linalg::{cholesky, solve, solve_triangular};
 
+/// Covariance kernels for Gaussian process interpolation
+///
+/// All kernels are stationary and depend only on the distance `r` between two
+/// sample indices, scaled by the length scale `l` of [`GaussianProcessConfig`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GpKernel {
+    /// Squared exponential (RBF) kernel: `exp(-r² / 2l²)`, infinitely smooth
+    SquaredExponential,
+    /// Exponential (Matérn 1/2) kernel: `exp(-r / l)`, continuous but rough
+    Exponential,
+    /// Matérn 3/2 kernel: once differentiable sample paths
+    Matern32,
+    /// Matérn 5/2 kernel: twice differentiable sample paths
+    Matern52,
+    /// Rational quadratic kernel: `(1 + r² / 2αl²)^(-α)`, a scale mixture of RBF kernels
+    RationalQuadratic {
+        /// Shape parameter; large values approach the squared exponential kernel
+        alpha: f64,
+    },
+}
+
+/// Configuration for Gaussian process (kriging-style) interpolation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaussianProcessConfig {
+    /// Covariance kernel
+    pub kernel: GpKernel,
+    /// Length scale of the kernel, in samples
+    pub length_scale: f64,
+    /// Signal variance (kernel amplitude)
+    pub variance: f64,
+    /// Observation noise variance added to the diagonal of the covariance matrix
+    pub noise_level: f64,
+}
+
+impl Default for GaussianProcessConfig {
+    fn default() -> Self {
+        Self {
+            kernel: GpKernel::SquaredExponential,
+            length_scale: 10.0,
+            variance: 1.0,
+            noise_level: 1e-3,
+        }
+    }
+}
+
+impl GaussianProcessConfig {
+    /// Evaluates the kernel covariance for two points a distance `r` apart
+    pub fn covariance(&self, r: f64) -> f64 {
+        let r = r.abs() / self.length_scale;
+        let correlation = match self.kernel {
+            GpKernel::SquaredExponential => (-0.5 * r * r).exp(),
+            GpKernel::Exponential => (-r).exp(),
+            GpKernel::Matern32 => {
+                let s = 3f64.sqrt() * r;
+                (1.0 + s) * (-s).exp()
+            }
+            GpKernel::Matern52 => {
+                let s = 5f64.sqrt() * r;
+                (1.0 + s + s * s / 3.0) * (-s).exp()
+            }
+            GpKernel::RationalQuadratic { alpha } => (1.0 + r * r / (2.0 * alpha)).powf(-alpha),
+        };
+        self.variance * correlation
+    }
+
+    fn validate(&self) -> SignalResult<()> {
+        if self.length_scale <= 0.0 || !self.length_scale.is_finite() {
+            return Err(SignalError::ValueError(
+                "Gaussian process length scale must be positive".to_string(),
+            ));
+        }
+        if self.variance <= 0.0 || !self.variance.is_finite() {
+            return Err(SignalError::ValueError(
+                "Gaussian process variance must be positive".to_string(),
+            ));
+        }
+        if self.noise_level < 0.0 || !self.noise_level.is_finite() {
+            return Err(SignalError::ValueError(
+                "Gaussian process noise level must be non-negative".to_string(),
+            ));
+        }
+        if let GpKernel::RationalQuadratic { alpha } = self.kernel {
+            if alpha <= 0.0 || !alpha.is_finite() {
+                return Err(SignalError::ValueError(
+                    "Rational quadratic kernel requires a positive alpha".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Applies Gaussian process interpolation to fill missing values in a signal
 ///
 /// Gaussian process interpolation provides a probabilistic approach to interpolation
@@ -42,13 +134,59 @@ pub fn gaussian_process_interpolate(
     kernel_sigma: f64,
     noise_level: f64,
 ) -> SignalResult<Array1<f64>> {
-    let n = signal.len();
+    let config = GaussianProcessConfig {
+        kernel: GpKernel::SquaredExponential,
+        length_scale: kernel_length,
+        variance: kernel_sigma,
+        noise_level,
+    };
+    gaussian_process_interpolate_with_uncertainty(signal, &config).map(|(mean, _)| mean)
+}
 
-    // Check if input has any missing values
-    let has_missing = signal.iter().any(|&x| x.is_nan());
-    if !has_missing {
-        return Ok(signal.clone());
-    }
+/// Gaussian process interpolation returning the posterior standard deviation
+///
+/// The observed samples are kept as they are and the missing samples are
+/// replaced by the posterior mean of a Gaussian process with a constant mean
+/// equal to the average of the observed values. The second array holds the
+/// posterior standard deviation of the underlying (noise-free) signal at each
+/// filled-in sample, and zero at the observed samples.
+///
+/// # Arguments
+///
+/// * `signal` - Input signal with missing values (NaN)
+/// * `config` - Kernel, length scale, variance and noise level
+///
+/// # Returns
+///
+/// * Tuple of (interpolated signal, per-sample standard deviation)
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array1;
+/// use scirs2_signal::interpolate::advanced::{
+///     gaussian_process_interpolate_with_uncertainty, GaussianProcessConfig, GpKernel,
+/// };
+///
+/// let signal = Array1::from_vec(vec![1.0, 2.0, f64::NAN, f64::NAN, 5.0, 6.0]);
+/// let config = GaussianProcessConfig {
+///     kernel: GpKernel::Matern52,
+///     length_scale: 3.0,
+///     ..Default::default()
+/// };
+/// let (mean, std) = gaussian_process_interpolate_with_uncertainty(&signal, &config).unwrap();
+///
+/// assert_eq!(mean[0], 1.0);
+/// assert!(mean[2] > 2.0 && mean[3] < 5.0);
+/// assert_eq!(std[0], 0.0);
+/// assert!(std[2] > 0.0);
+/// ```
+pub fn gaussian_process_interpolate_with_uncertainty(
+    signal: &Array1<f64>,
+    config: &GaussianProcessConfig,
+) -> SignalResult<(Array1<f64>, Array1<f64>)> {
+    config.validate()?;
+    let n = signal.len();
 
     // Find indices of missing and non-missing points
     let mut missing_indices = Vec::new();
@@ -64,92 +202,71 @@ pub fn gaussian_process_interpolate(
         }
     }
 
+    if missing_indices.is_empty() {
+        return Ok((signal.clone(), Array1::zeros(n)));
+    }
+
     if valid_indices.is_empty() {
         return Err(SignalError::ValueError(
             "All values are missing in the input signal".to_string(),
         ));
     }
 
-    if missing_indices.is_empty() {
-        return Ok(signal.clone());
-    }
-
-    // RBF Kernel function
-    let kernel = |x1: f64, x2: f64| -> f64 {
-        kernel_sigma * (-0.5 * (x1 - x2).powi(2) / (kernel_length * kernel_length)).exp()
-    };
-
-    // Create covariance matrix for observed points
+    // Constant prior mean so that the prediction reverts to the data level
     let n_valid = valid_indices.len();
-    let mut k_xx = Array2::zeros((n_valid, n_valid));
+    let prior_mean = valid_values.iter().sum::<f64>() / n_valid as f64;
+    let y = Array1::from_iter(valid_values.iter().map(|&v| v - prior_mean));
 
+    // Covariance matrix of the observed points, with noise on the diagonal
+    let mut k_xx = Array2::zeros((n_valid, n_valid));
     for i in 0..n_valid {
         for j in 0..n_valid {
-            k_xx[[i, j]] = kernel(valid_indices[i] as f64, valid_indices[j] as f64);
-
-            // Add noise variance to diagonal
-            if i == j {
-                k_xx[[i, j]] += noise_level;
-            }
-        }
-    }
-
-    // Create cross-covariance matrix between test points and observed points
-    let n_missing = missing_indices.len();
-    let mut k_star_x = Array2::zeros((n_missing, n_valid));
-
-    for i in 0..n_missing {
-        for j in 0..n_valid {
-            k_star_x[[i, j]] = kernel(missing_indices[i] as f64, valid_indices[j] as f64);
+            k_xx[[i, j]] = config.covariance(valid_indices[i] as f64 - valid_indices[j] as f64);
         }
-    }
-
-    // Compute self-covariance matrix for test points
-    let mut k_star_star = Array2::zeros((n_missing, n_missing));
-
-    for i in 0..n_missing {
-        for j in 0..n_missing {
-            k_star_star[[i, j]] = kernel(missing_indices[i] as f64, missing_indices[j] as f64);
-
-            // Add noise variance to diagonal
-            if i == j {
-                k_star_star[[i, j]] += noise_level;
-            }
-        }
-    }
-
-    // Compute the Cholesky decomposition of K_xx
-    let l = match cholesky(&k_xx.view(), None) {
-        Ok(l) => l,
-        Err(_) => {
-            return Err(SignalError::Compute(
-                "Failed to compute Cholesky decomposition of covariance matrix".to_string(),
-            ));
-        }
-    };
-
-    // Solve for alpha = K_xx^(-1) * y
-    let y = Array1::from_vec(valid_values);
-    let alpha = match solve_triangular(&l.view(), &y.view(), true, false) {
-        Ok(a) => a,
-        Err(_) => {
-            return Err(SignalError::Compute(
+        // A small jitter keeps the factorization stable when noise_level is zero
+        k_xx[[i, i]] += config.noise_level + 1e-10 * config.variance;
+    }
+
+    // K_xx = L L^T
+    let l = cholesky(&k_xx.view(), None).map_err(|_| {
+        SignalError::Compute(
+            "Failed to compute Cholesky decomposition of covariance matrix".to_string(),
+        )
+    })?;
+    let lower_solve = |b: &Array1<f64>| {
+        solve_triangular(&l.view(), &b.view(), true, false).map_err(|_| {
+            SignalError::Compute(
                 "Failed to solve triangular system in Gaussian process".to_string(),
-            ));
-        }
+            )
+        })
     };
 
-    // Predict mean for missing points: mu = K_*x * K_xx^(-1) * y
-    let mu = k_star_x.dot(&alpha);
+    // alpha = K_xx^(-1) y via forward and back substitution
+    let z = lower_solve(&y)?;
+    let alpha = solve_triangular(&l.t(), &z.view(), false, false).map_err(|_| {
+        SignalError::Compute("Failed to solve triangular system in Gaussian process".to_string())
+    })?;
 
-    // Create result by copying input and filling missing values
     let mut result = signal.clone();
+    let mut std_dev = Array1::zeros(n);
+    let prior_variance = config.covariance(0.0);
 
-    for i in 0..n_missing {
-        result[missing_indices[i]] = mu[i];
+    for &m in &missing_indices {
+        let k_star = Array1::from_iter(
+            valid_indices
+                .iter()
+                .map(|&v| config.covariance(m as f64 - v as f64)),
+        );
+
+        // Posterior mean: m + k_*^T K_xx^(-1) y
+        result[m] = prior_mean + k_star.dot(&alpha);
+
+        // Posterior variance: k(x, x) - v^T v with v = L^(-1) k_*
+        let v = lower_solve(&k_star)?;
+        std_dev[m] = (prior_variance - v.dot(&v)).max(0.0).sqrt();
     }
 
-    Ok(result)
+    Ok((result, std_dev))
 }
 
 /// Applies Kriging interpolation to fill missing values in a signal
@@ -661,6 +778,64 @@ mod tests {
         assert_eq!(result[4], 5.0);
     }
 
+    #[test]
+    fn test_gaussian_process_uncertainty() {
+        // Smooth signal with a gap in the middle
+        let truth: Vec<f64> = (0..40).map(|i| (i as f64 * 0.15).sin()).collect();
+        let mut signal = Array1::from_vec(truth.clone());
+        for i in 18..24 {
+            signal[i] = f64::NAN;
+        }
+
+        let kernels = [
+            GpKernel::SquaredExponential,
+            GpKernel::Matern32,
+            GpKernel::Matern52,
+            GpKernel::RationalQuadratic { alpha: 2.0 },
+        ];
+        for kernel in kernels {
+            let config = GaussianProcessConfig {
+                kernel,
+                length_scale: 6.0,
+                variance: 1.0,
+                noise_level: 1e-6,
+            };
+            let (mean, std) =
+                gaussian_process_interpolate_with_uncertainty(&signal, &config).unwrap();
+
+            for i in 18..24 {
+                assert!((mean[i] - truth[i]).abs() < 0.05, "{kernel:?} at {i}");
+                assert!(std[i] > 0.0 && std[i] < 1.0);
+            }
+            assert_eq!(mean[10], truth[10]);
+            assert_eq!(std[10], 0.0);
+
+            // Uncertainty grows towards the middle of the gap
+            assert!(std[20] > std[18]);
+        }
+
+        // Longer correlation lengths give more confident predictions
+        let short = GaussianProcessConfig {
+            kernel: GpKernel::Exponential,
+            length_scale: 2.0,
+            ..Default::default()
+        };
+        let long = GaussianProcessConfig {
+            length_scale: 20.0,
+            ..short
+        };
+        let (_, std_short) =
+            gaussian_process_interpolate_with_uncertainty(&signal, &short).unwrap();
+        let (_, std_long) = gaussian_process_interpolate_with_uncertainty(&signal, &long).unwrap();
+        assert!(std_long[20] < std_short[20]);
+
+        let invalid = GaussianProcessConfig {
+            length_scale: 0.0,
+            ..Default::default()
+        };
+        assert!(gaussian_process_interpolate_with_uncertainty(&signal, &invalid).is_err());
+    }
+
     #[test]
     fn test_kriging_interpolate() {
         let signal = Array1::from_vec(vec![1.0, f64::NAN, 3.0]);
@@ -8,9 +8,11 @@ use ndarray::{s, Array1, Array2};
 
 // Import the specific interpolation functions from their respective modules
 use super::advanced::{
-    gaussian_process_interpolate, kriging_interpolate, minimum_energy_interpolate, rbf_interpolate,
+    gaussian_process_interpolate_with_uncertainty, kriging_interpolate, minimum_energy_interpolate,
+    rbf_interpolate, GaussianProcessConfig,
 };
 use super::basic::{linear_interpolate, nearest_neighbor_interpolate};
+use super::inpainting::{biharmonic_inpaint_2d, inpaint_1d, laplace_inpaint_2d};
 use super::spectral::{sinc_interpolate, spectral_interpolate};
 use super::spline::{cubic_hermite_interpolate, cubic_spline_interpolate};
 
@@ -37,6 +39,8 @@ pub struct InterpolationConfig {
     pub frequency_constraint: bool,
     /// Cutoff frequency ratio for bandlimited signals
     pub cutoff_frequency: f64,
+    /// Kernel and noise settings for Gaussian process interpolation
+    pub gaussian_process: GaussianProcessConfig,
 }
 
 impl Default for InterpolationConfig {
@@ -52,6 +56,7 @@ impl Default for InterpolationConfig {
             smoothing_factor: 0.5,
             frequency_constraint: false,
             cutoff_frequency: 0.5,
+            gaussian_process: GaussianProcessConfig::default(),
         }
     }
 }
@@ -79,6 +84,10 @@ pub enum InterpolationMethod {
     RBF,
     /// Nearest neighbor interpolation
     NearestNeighbor,
+    /// Laplace (harmonic) PDE inpainting
+    Laplace,
+    /// Biharmonic (thin-plate) PDE inpainting
+    Biharmonic,
 }
 
 /// Main interpolation dispatch function
@@ -105,7 +114,8 @@ pub fn interpolate(
         InterpolationMethod::CubicSpline => cubic_spline_interpolate(signal, config),
         InterpolationMethod::CubicHermite => cubic_hermite_interpolate(signal, config),
         InterpolationMethod::GaussianProcess => {
-            gaussian_process_interpolate(signal, 10.0, 1.0, 1e-3)
+            gaussian_process_interpolate_with_uncertainty(signal, &config.gaussian_process)
+                .map(|(mean, _)| mean)
         }
         InterpolationMethod::Sinc => sinc_interpolate(signal, config.cutoff_frequency),
         InterpolationMethod::Spectral => spectral_interpolate(signal, config),
@@ -123,6 +133,8 @@ pub fn interpolate(
             rbf_interpolate(signal, rbf, config)
         }
         InterpolationMethod::NearestNeighbor => nearest_neighbor_interpolate(signal),
+        InterpolationMethod::Laplace => inpaint_1d(signal, false, config),
+        InterpolationMethod::Biharmonic => inpaint_1d(signal, true, config),
     }
 }
 
@@ -130,6 +142,8 @@ pub fn interpolate(
 ///
 /// This function applies interpolation to 2D data by first interpolating along rows,
 /// then along columns, with additional passes if needed to handle complex missing patterns.
+/// The Laplace and Biharmonic methods instead solve a PDE over the whole missing mask,
+/// which handles large missing regions much better.
 ///
 /// # Arguments
 ///
@@ -161,6 +175,8 @@ pub fn interpolate_2d(
             // For nearest neighbor, we can efficiently process the entire image at once
             nearest_neighbor_interpolate_2d(image)
         }
+        InterpolationMethod::Laplace => laplace_inpaint_2d(image, config),
+        InterpolationMethod::Biharmonic => biharmonic_inpaint_2d(image, config),
         _ => {
             // First interpolate along rows
             for i in 0..n_rows {
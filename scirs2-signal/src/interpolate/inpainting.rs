@@ -0,0 +1,356 @@
+//! PDE-based inpainting for images with missing regions
+//!
+//! Row and column interpolation only sees one line of the image at a time, so
+//! large missing regions end up with streaks along the interpolation axis.
+//! The methods in this module instead fill every missing pixel at once by
+//! solving a discrete partial differential equation over the missing mask,
+//! with the known pixels acting as boundary values:
+//!
+//! - Laplace inpainting solves `Δu = 0`, the smoothest membrane through the
+//!   known pixels (harmonic interpolation).
+//! - Biharmonic inpainting solves `Δ²u = 0`, a thin plate that also matches
+//!   the slope of the surrounding data and gives visibly smoother fills.
+//!
+//! Both are formulated as minimizing a quadratic energy, the Dirichlet energy
+//! `Σ|∇u|²` and the thin-plate energy `Σ(u_xx² + 2u_xy² + u_yy²)`, with natural
+//! boundary conditions at the image border. This gives a sparse symmetric
+//! positive definite system in the missing pixels, which is solved matrix-free
+//! with the conjugate gradient method.
+
+use super::core::InterpolationConfig;
+use crate::error::{SignalError, SignalResult};
+use ndarray::{s, Array1, Array2, Axis, Slice};
+
+/// Fills missing values (NaN) of an image by Laplace (harmonic) inpainting
+///
+/// Every missing pixel becomes the average of its four neighbours, so the fill
+/// is the smoothest membrane stretched over the known pixels. Along a single
+/// row this reduces to linear interpolation inside gaps and constant
+/// extrapolation at the ends.
+///
+/// # Arguments
+///
+/// * `image` - Input image with missing values (NaN)
+/// * `config` - Interpolation configuration; `convergence_threshold` is the relative
+///   residual of the solver and `max_iterations` a lower bound on its iteration count
+///
+/// # Returns
+///
+/// * Inpainted image
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use scirs2_signal::interpolate::{laplace_inpaint_2d, InterpolationConfig};
+///
+/// // A plane with a missing block in the middle
+/// let mut image = Array2::from_shape_fn((8, 8), |(i, j)| i as f64 + 2.0 * j as f64);
+/// for i in 2..5 {
+///     for j in 3..6 {
+///         image[[i, j]] = f64::NAN;
+///     }
+/// }
+///
+/// let result = laplace_inpaint_2d(&image, &InterpolationConfig::default()).unwrap();
+/// assert!((result[[3, 4]] - 11.0).abs() < 1e-4);
+/// ```
+pub fn laplace_inpaint_2d(
+    image: &Array2<f64>,
+    config: &InterpolationConfig,
+) -> SignalResult<Array2<f64>> {
+    inpaint(image, Operator::Laplace, config)
+}
+
+/// Fills missing values (NaN) of an image by biharmonic inpainting
+///
+/// The fill minimizes the discrete thin-plate bending energy over the whole
+/// image, which makes it continuous in both value and slope across the border
+/// of the missing region and reproduces planes exactly. This is usually the
+/// better choice for smooth images with large holes.
+///
+/// # Arguments
+///
+/// * `image` - Input image with missing values (NaN)
+/// * `config` - Interpolation configuration; `convergence_threshold` is the relative
+///   residual of the solver and `max_iterations` a lower bound on its iteration count
+///
+/// # Returns
+///
+/// * Inpainted image
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use scirs2_signal::interpolate::{biharmonic_inpaint_2d, InterpolationConfig};
+///
+/// let mut image = Array2::from_shape_fn((20, 20), |(i, j)| {
+///     (i as f64 * 0.2).sin() + (j as f64 * 0.15).cos()
+/// });
+/// for i in 6..12 {
+///     for j in 8..14 {
+///         image[[i, j]] = f64::NAN;
+///     }
+/// }
+///
+/// let result = biharmonic_inpaint_2d(&image, &InterpolationConfig::default()).unwrap();
+/// let expected = (9.0f64 * 0.2).sin() + (11.0f64 * 0.15).cos();
+/// assert!((result[[9, 11]] - expected).abs() < 0.05);
+/// ```
+pub fn biharmonic_inpaint_2d(
+    image: &Array2<f64>,
+    config: &InterpolationConfig,
+) -> SignalResult<Array2<f64>> {
+    inpaint(image, Operator::Biharmonic, config)
+}
+
+/// Applies Laplace or biharmonic inpainting to a 1D signal
+///
+/// The signal is treated as a single-row image.
+pub(crate) fn inpaint_1d(
+    signal: &Array1<f64>,
+    biharmonic: bool,
+    config: &InterpolationConfig,
+) -> SignalResult<Array1<f64>> {
+    let image = signal.clone().insert_axis(Axis(0));
+    let operator = if biharmonic {
+        Operator::Biharmonic
+    } else {
+        Operator::Laplace
+    };
+    Ok(inpaint(&image, operator, config)?.index_axis_move(Axis(0), 0))
+}
+
+/// Symmetric positive semi-definite operator defining the inpainting energy
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    /// Graph Laplacian of the 4-connected pixel grid (Dirichlet energy)
+    Laplace,
+    /// `D_xxᵀD_xx + 2 D_xyᵀD_xy + D_yyᵀD_yy` (thin-plate energy)
+    Biharmonic,
+}
+
+impl Operator {
+    fn apply(&self, u: &Array2<f64>) -> Array2<f64> {
+        match self {
+            Operator::Laplace => grid_laplacian(u),
+            Operator::Biharmonic => {
+                let mut out = Array2::zeros(u.dim());
+                for axis in [Axis(0), Axis(1)] {
+                    let n = u.len_of(axis);
+                    if n < 3 {
+                        continue;
+                    }
+                    let shifted = |k: usize| Slice::from(k..n - 2 + k);
+                    let second = &u.slice_axis(axis, shifted(0))
+                        - &(&u.slice_axis(axis, shifted(1)) * 2.0)
+                        + u.slice_axis(axis, shifted(2));
+                    for (k, weight) in [(0, 1.0), (1, -2.0), (2, 1.0)] {
+                        out.slice_axis_mut(axis, shifted(k))
+                            .scaled_add(weight, &second);
+                    }
+                }
+                let (n_rows, n_cols) = u.dim();
+                if n_rows >= 2 && n_cols >= 2 {
+                    let mixed =
+                        &u.slice(s![1.., 1..]) - &u.slice(s![1.., ..-1]) - u.slice(s![..-1, 1..])
+                            + u.slice(s![..-1, ..-1]);
+                    out.slice_mut(s![1.., 1..]).scaled_add(2.0, &mixed);
+                    out.slice_mut(s![1.., ..-1]).scaled_add(-2.0, &mixed);
+                    out.slice_mut(s![..-1, 1..]).scaled_add(-2.0, &mixed);
+                    out.slice_mut(s![..-1, ..-1]).scaled_add(2.0, &mixed);
+                }
+                out
+            }
+        }
+    }
+}
+
+/// Graph Laplacian with natural (Neumann) boundaries: `Σ_q (u_p - u_q)` over neighbours
+fn grid_laplacian(u: &Array2<f64>) -> Array2<f64> {
+    let (n_rows, n_cols) = u.dim();
+    let mut out = Array2::zeros((n_rows, n_cols));
+    for i in 0..n_rows {
+        for j in 0..n_cols {
+            let centre = u[[i, j]];
+            let mut acc = 0.0;
+            if i > 0 {
+                acc += centre - u[[i - 1, j]];
+            }
+            if i + 1 < n_rows {
+                acc += centre - u[[i + 1, j]];
+            }
+            if j > 0 {
+                acc += centre - u[[i, j - 1]];
+            }
+            if j + 1 < n_cols {
+                acc += centre - u[[i, j + 1]];
+            }
+            out[[i, j]] = acc;
+        }
+    }
+    out
+}
+
+/// Minimizes the operator energy over the missing pixels with conjugate gradients
+fn inpaint(
+    image: &Array2<f64>,
+    operator: Operator,
+    config: &InterpolationConfig,
+) -> SignalResult<Array2<f64>> {
+    let missing: Vec<(usize, usize)> = image
+        .indexed_iter()
+        .filter(|(_, v)| v.is_nan())
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(image.clone());
+    }
+
+    let n_known = image.len() - missing.len();
+    if n_known == 0 {
+        return Err(SignalError::ValueError(
+            "All values are missing in the input image".to_string(),
+        ));
+    }
+
+    // Known pixels as boundary values, missing ones at zero
+    let mut result = image.mapv(|v| if v.is_nan() { 0.0 } else { v });
+
+    // Restricted operator on the missing pixels: x -> (A [x; 0])_missing
+    let apply = |x: &[f64]| -> Vec<f64> {
+        let mut embedded = Array2::zeros(image.dim());
+        for (&idx, &value) in missing.iter().zip(x) {
+            embedded[idx] = value;
+        }
+        let applied = operator.apply(&embedded);
+        missing.iter().map(|&idx| applied[idx]).collect()
+    };
+
+    // Right-hand side: -(A [0; known])_missing
+    let boundary = operator.apply(&result);
+    let b: Vec<f64> = missing.iter().map(|&idx| -boundary[idx]).collect();
+
+    // Start from the mean of the known pixels
+    let known_mean = result.sum() / n_known as f64;
+    let mut x = vec![known_mean; missing.len()];
+    let ax = apply(&x);
+    let mut r: Vec<f64> = b.iter().zip(&ax).map(|(bi, ai)| bi - ai).collect();
+    let mut p = r.clone();
+    let mut rs_old = dot(&r, &r);
+
+    let b_norm = dot(&b, &b).sqrt().max(f64::EPSILON);
+    let tolerance = config.convergence_threshold * b_norm;
+    let max_iterations = config.max_iterations.max(2 * missing.len());
+
+    for _ in 0..max_iterations {
+        if rs_old.sqrt() <= tolerance {
+            break;
+        }
+        let ap = apply(&p);
+        let p_ap = dot(&p, &ap);
+        if p_ap <= 0.0 {
+            break;
+        }
+        let alpha = rs_old / p_ap;
+        for k in 0..x.len() {
+            x[k] += alpha * p[k];
+            r[k] -= alpha * ap[k];
+        }
+        let rs_new = dot(&r, &r);
+        let beta = rs_new / rs_old;
+        for k in 0..p.len() {
+            p[k] = r[k] + beta * p[k];
+        }
+        rs_old = rs_new;
+    }
+
+    for (&idx, &value) in missing.iter().zip(&x) {
+        result[idx] = value;
+    }
+
+    Ok(result)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Unit tests for PDE-based inpainting
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smooth_image(n_rows: usize, n_cols: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n_rows, n_cols), |(i, j)| {
+            (i as f64 * 0.15).sin() * (j as f64 * 0.1).cos() + 0.02 * i as f64
+        })
+    }
+
+    #[test]
+    fn test_inpainting_large_hole() {
+        let image = smooth_image(30, 30);
+        let mut damaged = image.clone();
+        for i in 8..20 {
+            for j in 10..24 {
+                damaged[[i, j]] = f64::NAN;
+            }
+        }
+        let config = InterpolationConfig::default();
+
+        let laplace = laplace_inpaint_2d(&damaged, &config).unwrap();
+        let biharmonic = biharmonic_inpaint_2d(&damaged, &config).unwrap();
+
+        let rms = |result: &Array2<f64>| {
+            let mut sse = 0.0;
+            for i in 8..20 {
+                for j in 10..24 {
+                    sse += (result[[i, j]] - image[[i, j]]).powi(2);
+                }
+            }
+            (sse / (12.0 * 14.0)).sqrt()
+        };
+
+        // Known pixels are untouched
+        assert_eq!(laplace[[0, 0]], image[[0, 0]]);
+        assert_eq!(biharmonic[[25, 25]], image[[25, 25]]);
+
+        assert!(rms(&laplace) < 0.1);
+        assert!(rms(&biharmonic) < 0.02);
+        assert!(rms(&biharmonic) < rms(&laplace));
+    }
+
+    #[test]
+    fn test_inpainting_1d_profiles() {
+        let config = InterpolationConfig::default();
+
+        // Laplace inpainting is linear interpolation inside gaps and
+        // constant extrapolation at the ends
+        let signal = Array1::from_vec(vec![f64::NAN, 1.0, f64::NAN, f64::NAN, 4.0, f64::NAN]);
+        let result = inpaint_1d(&signal, false, &config).unwrap();
+        let expected = [1.0, 1.0, 2.0, 3.0, 4.0, 4.0];
+        for (r, e) in result.iter().zip(expected) {
+            assert!((r - e).abs() < 1e-6);
+        }
+
+        // Biharmonic inpainting reproduces straight lines, including at the ends
+        let signal = Array1::from_vec(vec![0.0, 1.0, f64::NAN, f64::NAN, 4.0, 5.0, f64::NAN]);
+        let result = inpaint_1d(&signal, true, &config).unwrap();
+        for (i, r) in result.iter().enumerate() {
+            assert!((r - i as f64).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_inpainting_edge_cases() {
+        let config = InterpolationConfig::default();
+
+        let complete = smooth_image(4, 5);
+        assert_eq!(laplace_inpaint_2d(&complete, &config).unwrap(), complete);
+
+        let empty = Array2::from_elem((3, 3), f64::NAN);
+        assert!(biharmonic_inpaint_2d(&empty, &config).is_err());
+    }
+}
@@ -11,6 +11,7 @@
 //! - [`basic`] - Simple interpolation methods (linear, nearest neighbor)
 //! - [`spline`] - Spline-based methods (cubic spline, Hermite)
 //! - [`advanced`] - Statistical methods (Gaussian process, Kriging, RBF, minimum energy)
+//! - [`inpainting`] - PDE-based inpainting (Laplace, biharmonic) for images with large holes
 //! - [`spectral`] - Frequency-domain methods (sinc, spectral, auto-selection)
 //!
 //! # Quick Start
@@ -49,11 +50,13 @@
 //! | RBF | Scattered data | Flexible basis functions | Parameter tuning needed |
 //! | Sinc | Bandlimited signals | Optimal for bandlimited | Requires knowledge of bandwidth |
 //! | Spectral | Periodic signals | Good for frequency content | Iterative process |
+//! | Laplace / Biharmonic | Large holes in images | Fills whole regions at once | Iterative solve over the hole |
 
 // Re-export all submodules
 pub mod advanced;
 pub mod basic;
 pub mod core;
+pub mod inpainting;
 pub mod spectral;
 pub mod spline;
 
@@ -67,9 +70,12 @@ pub use basic::{linear_interpolate, nearest_neighbor_interpolate};
 
 pub use spline::{cubic_hermite_interpolate, cubic_spline_interpolate};
 
+pub use inpainting::{biharmonic_inpaint_2d, laplace_inpaint_2d};
+
 pub use advanced::{
-    gaussian_process_interpolate, kriging_interpolate, minimum_energy_interpolate, rbf_functions,
-    rbf_interpolate, variogram_models,
+    gaussian_process_interpolate, gaussian_process_interpolate_with_uncertainty,
+    kriging_interpolate, minimum_energy_interpolate, rbf_functions, rbf_interpolate,
+    variogram_models, GaussianProcessConfig, GpKernel,
 };
 
 pub use spectral::{
@@ -278,6 +284,12 @@ impl InterpolationBuilder {
         self
     }
 
+    /// Sets the kernel and noise settings for Gaussian process interpolation
+    pub fn gaussian_process(mut self, config: GaussianProcessConfig) -> Self {
+        self.config.gaussian_process = config;
+        self
+    }
+
     /// Performs interpolation with the configured settings
     pub fn interpolate(
         self,
@@ -326,6 +338,8 @@ impl InterpolationMethods {
         InterpolationMethod::Kriging,
         InterpolationMethod::RBF,
         InterpolationMethod::NearestNeighbor,
+        InterpolationMethod::Laplace,
+        InterpolationMethod::Biharmonic,
     ];
 
     /// Basic interpolation methods (fast, simple)
@@ -351,6 +365,12 @@ impl InterpolationMethods {
     /// Frequency-domain methods
     pub const SPECTRAL: &'static [InterpolationMethod] =
         &[InterpolationMethod::Sinc, InterpolationMethod::Spectral];
+
+    /// PDE-based inpainting methods (large missing regions)
+    pub const INPAINTING: &'static [InterpolationMethod] = &[
+        InterpolationMethod::Laplace,
+        InterpolationMethod::Biharmonic,
+    ];
 }
 
 /// Unit tests for the unified interpolation API
@@ -410,11 +430,12 @@ mod tests {
 
     #[test]
     fn test_interpolation_methods_collections() {
-        assert_eq!(InterpolationMethods::ALL.len(), 10);
+        assert_eq!(InterpolationMethods::ALL.len(), 12);
         assert_eq!(InterpolationMethods::BASIC.len(), 2);
         assert_eq!(InterpolationMethods::SPLINE.len(), 2);
         assert_eq!(InterpolationMethods::ADVANCED.len(), 4);
         assert_eq!(InterpolationMethods::SPECTRAL.len(), 2);
+        assert_eq!(InterpolationMethods::INPAINTING.len(), 2);
     }
 
     #[test]
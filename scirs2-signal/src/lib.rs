@@ -78,7 +78,8 @@ pub mod utilities;
 
 // Re-export the main public DWT functionality
 pub use dwt::{
-    dwt_decompose, dwt_reconstruct, extend_signal, wavedec, waverec, Wavelet, WaveletFilters,
+    dwt_decompose, dwt_max_level, dwt_reconstruct, dwt_reconstruct_with_mode, extend_signal,
+    wavedec, waverec, waverec_with_mode, Wavelet, WaveletFilters,
 };
 pub mod filter;
 pub mod filter_banks;
@@ -89,6 +90,7 @@ pub mod kalman;
 pub mod lombscargle;
 pub mod lti;
 pub mod lti_response;
+pub mod matched;
 pub mod median;
 pub mod multirate;
 pub mod multitaper;
@@ -108,6 +110,7 @@ pub mod spectral;
 pub mod spline;
 pub mod sswt;
 pub mod stft;
+pub mod streaming;
 pub mod streaming_stft;
 pub mod swt;
 pub mod swt2d;
@@ -124,7 +127,8 @@ pub mod wvd;
 
 // Re-export commonly used functions
 pub use adaptive::{
-    ApaFilter, FdlmsFilter, LmfFilter, LmsFilter, NlmsFilter, RlsFilter, SmLmsFilter, VsLmsFilter,
+    AdaptationHistory, ApaFilter, FdlmsFilter, LmfFilter, LmsFilter, NlmsFilter, RlsFilter,
+    SmLmsFilter, VsLmsFilter,
 };
 pub use advanced_filter::{
     arbitrary_magnitude_design, constrained_least_squares_design, least_squares_design,
@@ -136,7 +140,7 @@ pub use bss::{
     joint_bss, joint_diagonalization, kernel_ica, multivariate_emd, nmf, pca, sort_components,
     sparse_component_analysis, BssConfig, IcaMethod, NonlinearityFunction,
 };
-pub use convolve::{convolve, convolve2d, correlate, deconvolve};
+pub use convolve::{convolve, convolve2d, correlate, deconvolve, fftconvolve};
 pub use cqt::{
     chromagram, constant_q_transform, cqt_magnitude, inverse_constant_q_transform, CqtConfig,
 };
@@ -149,13 +153,18 @@ pub use deconvolution::{
 };
 pub use filter::{
     allpass_filter, analyze_filter, bessel, bilinear_transform, butter, butter_bandpass_bandstop,
-    cheby1, cheby2, check_filter_stability, comb_filter, ellip, filtfilt, firwin, lfilter,
-    matched_filter, matched_filter_detect, minimum_phase, notch_filter, peak_filter,
-    prewarp_frequency, remez, FilterAnalysis, FilterStability,
+    buttord, cheb1ord, cheby1, cheby2, check_filter_stability, comb_filter, ellip, filtfilt,
+    filtfilt_with_config, firwin, firwin2, firwin_bands, freqz, freqz_at, group_delay, iirfilter,
+    iirfilter_sos, iirfilter_zpk, lfilter, lfilter_with_zi, lfilter_zi, lfiltic, matched_filter,
+    matched_filter_detect, minimum_phase, notch_filter, peak_filter, phase_delay,
+    prewarp_frequency, remez, remez_design, sos_group_delay, sosfreqz, sosfreqz_at, zpk_to_sos,
+    FilterAnalysis, FilterStability, FiltfiltConfig, FiltfiltMethod, IirPrototype, PadType,
+    RemezFilterType, RemezResult, SecondOrderSections,
 };
 pub use filter_banks::{
-    CosineModulatedFilterBank, FilterBankAnalysis, FilterBankType, FilterBankWindow, IirStabilizer,
-    QmfBank, StabilizationMethod, WaveletFilterBank,
+    design_npr_prototype, CosineModulatedFilterBank, FilterBankAnalysis, FilterBankType,
+    FilterBankWindow, IirStabilizer, PolyphaseFilterBank, QmfBank, StabilizationMethod,
+    WaveletFilterBank,
 };
 pub use higher_order::{
     biamplitude, bicoherence, bispectrum, cumulative_bispectrum, detect_phase_coupling,
@@ -167,12 +176,15 @@ pub use hr_spectral::{
 };
 pub use interpolate::{
     auto_interpolate,
+    biharmonic_inpaint_2d,
     cubic_hermite_interpolate,
     cubic_spline_interpolate,
     gaussian_process_interpolate,
+    gaussian_process_interpolate_with_uncertainty,
     interpolate,
     interpolate_2d,
     kriging_interpolate,
+    laplace_inpaint_2d,
     linear_interpolate,
     minimum_energy_interpolate,
     nearest_neighbor_interpolate,
@@ -181,6 +193,8 @@ pub use interpolate::{
     spectral::{sinc_interpolate, spectral_interpolate},
     // resampling functions temporarily removed due to module restructuring
     variogram_models,
+    GaussianProcessConfig,
+    GpKernel,
     InterpolationConfig,
     InterpolationMethod,
 };
@@ -192,9 +206,13 @@ pub use kalman::{
 pub use lombscargle::{
     find_peaks as find_ls_peaks, lombscargle, significance_levels, AutoFreqMethod,
 };
+pub use matched::{
+    ambiguity, cross_ambiguity, AmbiguityResult, MatchedFilterConfig, MatchedFilterResult,
+    NoiseWhitening,
+};
 pub use median::{
-    hybrid_median_filter_2d, median_filter_1d, median_filter_2d, median_filter_color,
-    rank_filter_1d, EdgeMode, MedianConfig,
+    hybrid_median_filter_2d, medfilt, median_filter_1d, median_filter_2d, median_filter_color,
+    order_filter, percentile_filter, rank_filter_1d, EdgeMode, MedianConfig,
 };
 pub use multirate::{
     FilterBankProperties, MultirateConverter, PerfectReconstructionConfig,
@@ -209,7 +227,10 @@ pub use parametric::{
     ar_spectrum, arma_spectrum, estimate_ar, estimate_arma, select_ar_order, ARMethod,
     OrderSelection,
 };
-pub use peak::{find_peaks, peak_prominences, peak_widths};
+pub use peak::{
+    find_peaks, find_peaks_with_properties, peak_prominences, peak_widths, PeakConfig,
+    PeakProperties,
+};
 pub use realtime::{
     CircularBuffer, GainProcessor, LockFreeRingBuffer, MovingAverageProcessor, RealtimeConfig,
     RealtimeProcessor, RealtimeStats, StreamBlock, StreamProcessor, ZeroLatencyLimiter,
@@ -228,11 +249,15 @@ pub use sparse::{
     random_sensing_matrix, recover_missing_samples, smooth_l0, sparse_denoise, subspace_pursuit,
     SparseRecoveryConfig, SparseRecoveryMethod, SparseTransform,
 };
-pub use spectral::{periodogram, spectrogram, stft as spectral_stft, welch};
+pub use spectral::{
+    coherence as spectral_coherence, csd, periodogram, spectrogram, stft as spectral_stft, welch,
+    welch_with_config, SpectralConfig,
+};
 pub use stft::{
     closest_stft_dual_window, create_cola_window, MemoryEfficientStft, MemoryEfficientStftConfig,
     MemoryInfo, ShortTimeFft,
 };
+pub use streaming::{process_in_chunks, Block, IirBlock, SosBlock, WelchBlock};
 pub use streaming_stft::{
     RealTimeStft, RealTimeStftStatistics, StreamingStft, StreamingStftConfig,
     StreamingStftStatistics,
@@ -246,7 +271,7 @@ pub use waveforms::{
     perfect_binary_sequence, pink_noise, prbs_sequence, sawtooth, square, synchronized_sweep,
 };
 pub use wiener::{
-    iterative_wiener_filter, kalman_wiener_filter, psd_wiener_filter, spectral_subtraction,
+    iterative_wiener_filter, kalman_wiener_filter, psd_wiener_filter, spectral_subtraction, wiener,
     wiener_filter, wiener_filter_2d, wiener_filter_freq, wiener_filter_time, WienerConfig,
 };
 pub use wvd::{cross_wigner_ville, smoothed_pseudo_wigner_ville, wigner_ville, WvdConfig};
@@ -267,9 +292,11 @@ pub use wavelets::{
     complex_gaussian,
     complex_morlet,
     cwt,
+    cwt_fft,
     cwt_magnitude,
     cwt_phase,
     fbsp,
+    frequency_to_scale,
     morlet,
     paul,
     ricker,
@@ -278,12 +305,14 @@ pub use wavelets::{
     shannon,
     // Dual-Tree Complex Wavelet Transform
     BoundaryMode,
+    CwtResult,
     Dtcwt1dResult,
     Dtcwt2dResult,
     DtcwtConfig,
     DtcwtFilters,
     DtcwtProcessor,
     FilterSet,
+    MotherWavelet,
 };
 pub use wpt::{
     get_level_coefficients, reconstruct_from_nodes, wp_decompose, WaveletPacket, WaveletPacketTree,
@@ -291,7 +320,7 @@ pub use wpt::{
 pub use wpt2d::{wpt2d_full, wpt2d_selective, WaveletPacket2D, WaveletPacketTree2D};
 
 // LTI systems functions
-pub use lti::system::{c2d, ss, tf, zpk};
+pub use lti::system::{c2d, cont2discrete, ss, tf, zpk};
 pub use lti::{
     analyze_control_observability, analyze_controllability, analyze_observability, bode,
     complete_kalman_decomposition, compute_lyapunov_gramians, matrix_condition_number,
@@ -301,20 +330,29 @@ pub use lti::{
 
 // LTI system functions (using what's available)
 // Note: Some functions temporarily commented out due to module restructuring
-pub use lti_response::{impulse_response, lsim, step_response};
+pub use lti_response::{impulse, impulse_response, lsim, lsim_with_state, step, step_response};
 
 // Chirp Z-Transform functions
 pub use czt::{czt, czt_points};
 
 // Hilbert transform and related functions
 pub mod hilbert;
-pub use hilbert::{envelope, hilbert, instantaneous_frequency, instantaneous_phase};
+pub use hilbert::{
+    envelope, hilbert, instantaneous_frequency, instantaneous_phase, smoothed_envelope,
+    smoothed_instantaneous_frequency,
+};
 
 // Detrending functions
-pub use detrend::{detrend, detrend_axis, detrend_poly};
+pub use detrend::{
+    baseline_als, baseline_als_axis, detrend, detrend_axis, detrend_piecewise,
+    detrend_piecewise_axis, detrend_poly, AlsConfig,
+};
 
 // Signal denoising functions
-pub use denoise::{denoise_wavelet, ThresholdMethod, ThresholdSelect};
+pub use denoise::{
+    denoise_wavelet, estimate_noise_sigma, sure_threshold, threshold_coefficients, ThresholdMethod,
+    ThresholdSelect,
+};
 
 // 2D Wavelet image processing functions
 pub use dwt2d_image::{compress_image, denoise_image, detect_edges, DenoisingMethod};
@@ -341,7 +379,7 @@ pub use measurements::{peak_to_peak, peak_to_rms, rms, snr, thd};
 pub use phase_vocoder::{phase_vocoder, PhaseVocoderConfig};
 
 // Empirical Mode Decomposition (EMD) for nonlinear and non-stationary signals
-pub use emd::{eemd, emd, hilbert_huang_spectrum, EmdConfig, EmdResult};
+pub use emd::{eemd, emd, hilbert_huang_spectrum, EmdConfig, EmdResult, SiftStopCriterion};
 
 // Feature extraction for time series analysis
 pub use features::{
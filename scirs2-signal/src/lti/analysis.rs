@@ -15,6 +15,8 @@ use crate::error::{SignalError, SignalResult};
 ///
 /// Computes the frequency response at specified frequencies and converts
 /// to magnitude (in dB) and phase (in degrees) format for Bode plot visualization.
+/// The phase is unwrapped along the frequency axis, so systems with more
+/// than two poles continue below -180 degrees instead of jumping back.
 ///
 /// # Arguments
 ///
@@ -68,14 +70,22 @@ pub fn bode<T: LtiSystem>(
     let mut mag = Vec::with_capacity(resp.len());
     let mut phase = Vec::with_capacity(resp.len());
 
+    let mut previous_phase: Option<f64> = None;
     for &val in &resp {
         // Magnitude in dB: 20 * log10(|H(jw)|)
         let mag_db = 20.0 * val.norm().log10();
         mag.push(mag_db);
 
+        // Phase unwrapped across frequencies so it is continuous
+        let mut phase_rad = val.arg();
+        if let Some(prev) = previous_phase {
+            let two_pi = 2.0 * std::f64::consts::PI;
+            phase_rad -= two_pi * ((phase_rad - prev) / two_pi).round();
+        }
+        previous_phase = Some(phase_rad);
+
         // Phase in degrees: arg(H(jw)) * 180/pi
-        let phase_deg = val.arg() * 180.0 / std::f64::consts::PI;
-        phase.push(phase_deg);
+        phase.push(phase_rad.to_degrees());
     }
 
     Ok((frequencies, mag, phase))
@@ -124,7 +124,8 @@ pub fn ss(
 /// Convert a continuous-time system to a discrete-time system using zero-order hold method
 ///
 /// This function discretizes a continuous-time system using the zero-order hold (ZOH)
-/// assumption, which is commonly used in digital control systems.
+/// assumption, which is commonly used in digital control systems. It is
+/// equivalent to [`cont2discrete`] with the `"zoh"` method.
 ///
 /// # Arguments
 ///
@@ -142,33 +143,188 @@ pub fn ss(
 ///
 /// let sys_ct = tf(vec![1.0], vec![1.0, 1.0], Some(false)).unwrap();
 /// let sys_dt = c2d(&sys_ct, 0.1).unwrap();
+///
+/// // The continuous pole at s = -1 maps to z = exp(-0.1)
+/// assert!((sys_dt.a[0] - (-0.1f64).exp()).abs() < 1e-12);
 /// ```
-pub fn c2d<T: LtiSystem>(system: &T, _dt: f64) -> SignalResult<StateSpace> {
-    // Convert to state-space first
-    let ss_sys = system.to_ss()?;
+pub fn c2d<T: LtiSystem>(system: &T, dt: f64) -> SignalResult<StateSpace> {
+    cont2discrete(system, dt, None)
+}
+
+/// Discretize a continuous-time system
+///
+/// Supported methods:
+///
+/// * `"zoh"` - Zero-order hold: exact for inputs that are constant between samples
+/// * `"foh"` - First-order hold: exact for inputs that are linear between samples
+/// * `"bilinear"` or `"tustin"` - Bilinear (trapezoidal) transform
+/// * `"euler"` or `"forward_diff"` - Forward Euler differencing
+/// * `"backward_diff"` - Backward Euler differencing
+///
+/// # Arguments
+///
+/// * `system` - A continuous-time LTI system
+/// * `dt` - The sampling period
+/// * `method` - Discretization method (default: `"zoh"`)
+///
+/// # Returns
+///
+/// The discrete-time state-space system
+///
+/// # Examples
+///
+/// ```rust
+/// use scirs2_signal::lti::design::{cont2discrete, tf};
+/// use scirs2_signal::lti::LtiSystem;
+///
+/// // H(s) = 1 / (s + 2)
+/// let sys = tf(vec![1.0], vec![1.0, 2.0], None).unwrap();
+/// let sys_d = cont2discrete(&sys, 0.05, Some("bilinear")).unwrap();
+///
+/// // The bilinear transform preserves the DC gain
+/// let h = sys_d.to_tf().unwrap();
+/// let dc = h.num.iter().sum::<f64>() / h.den.iter().sum::<f64>();
+/// assert!((dc - 0.5).abs() < 1e-12);
+/// ```
+pub fn cont2discrete<T: LtiSystem>(
+    system: &T,
+    dt: f64,
+    method: Option<&str>,
+) -> SignalResult<StateSpace> {
+    let sys = system.to_ss()?;
 
     // Ensure the system is continuous-time
-    if ss_sys.dt {
+    if sys.dt {
         return Err(SignalError::ValueError(
             "System is already discrete-time".to_string(),
         ));
     }
+    if !(dt > 0.0 && dt.is_finite()) {
+        return Err(SignalError::ValueError(format!(
+            "Sampling period must be positive, got {dt}"
+        )));
+    }
 
-    // For now, return a placeholder for the discretized system
-    // In practice, we would use the matrix exponential method: A_d = exp(A*dt)
+    let n = sys.n_states;
+    let m = sys.n_inputs;
+    let p = sys.n_outputs;
+
+    let (a, b, c, d) = match method.unwrap_or("zoh") {
+        "zoh" => {
+            // exp([[A, B], [0, 0]] * dt) = [[Ad, Bd], [0, I]]
+            let size = n + m;
+            let mut block = vec![0.0; size * size];
+            for i in 0..n {
+                for j in 0..n {
+                    block[i * size + j] = sys.a[i * n + j] * dt;
+                }
+                for j in 0..m {
+                    block[i * size + n + j] = sys.b[i * m + j] * dt;
+                }
+            }
+            let e = matrix_exponential(&block, size);
+            let ad = sub_block(&e, size, 0..n, 0..n);
+            let bd = sub_block(&e, size, 0..n, n..n + m);
+            (ad, bd, sys.c.clone(), sys.d.clone())
+        }
+        "foh" => {
+            // exp([[A dt, B dt, 0], [0, 0, I], [0, 0, 0]]) holds the state
+            // transition and the responses to a step and a ramp of the input
+            let size = n + 2 * m;
+            let mut block = vec![0.0; size * size];
+            for i in 0..n {
+                for j in 0..n {
+                    block[i * size + j] = sys.a[i * n + j] * dt;
+                }
+                for j in 0..m {
+                    block[i * size + n + j] = sys.b[i * m + j] * dt;
+                }
+            }
+            for j in 0..m {
+                block[(n + j) * size + n + m + j] = 1.0;
+            }
+            let e = matrix_exponential(&block, size);
+            let phi = sub_block(&e, size, 0..n, 0..n);
+            let gamma1 = sub_block(&e, size, 0..n, n..n + m);
+            let gamma2 = sub_block(&e, size, 0..n, n + m..size);
+
+            let phi_gamma2 = matrix_product(&phi, &gamma2, n, n, m);
+            let bd = gamma1
+                .iter()
+                .zip(&gamma2)
+                .zip(&phi_gamma2)
+                .map(|((&g1, &g2), &pg2)| g1 - g2 + pg2)
+                .collect();
+            let c_gamma2 = matrix_product(&sys.c, &gamma2, p, n, m);
+            let dd = sys.d.iter().zip(&c_gamma2).map(|(&x, &y)| x + y).collect();
+            (phi, bd, sys.c.clone(), dd)
+        }
+        "bilinear" | "tustin" => generalized_bilinear(&sys, dt, 0.5)?,
+        "euler" | "forward_diff" => generalized_bilinear(&sys, dt, 0.0)?,
+        "backward_diff" => generalized_bilinear(&sys, dt, 1.0)?,
+        other => {
+            return Err(SignalError::ValueError(format!(
+                "Unknown discretization method: {other}. Must be 'zoh', 'foh', 'bilinear', \
+                 'tustin', 'euler', 'forward_diff', or 'backward_diff'."
+            )))
+        }
+    };
 
     Ok(StateSpace {
-        a: ss_sys.a.clone(),
-        b: ss_sys.b.clone(),
-        c: ss_sys.c.clone(),
-        d: ss_sys.d.clone(),
-        n_states: ss_sys.n_states,
-        n_inputs: ss_sys.n_inputs,
-        n_outputs: ss_sys.n_outputs,
+        a,
+        b,
+        c,
+        d,
+        n_states: n,
+        n_inputs: m,
+        n_outputs: p,
         dt: true,
     })
 }
 
+/// Discrete-time `(A, B, C, D)` matrices in row-major order
+type DiscreteMatrices = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// Generalized bilinear transform with weight `alpha`
+///
+/// `alpha = 0` is forward Euler, `0.5` the bilinear (Tustin) transform and
+/// `1` backward Euler.
+fn generalized_bilinear(sys: &StateSpace, dt: f64, alpha: f64) -> SignalResult<DiscreteMatrices> {
+    let n = sys.n_states;
+    let m = sys.n_inputs;
+    let p = sys.n_outputs;
+
+    // ima = I - alpha * dt * A
+    let mut ima = vec![0.0; n * n];
+    let mut rhs_a = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            let identity = if i == j { 1.0 } else { 0.0 };
+            ima[i * n + j] = identity - alpha * dt * sys.a[i * n + j];
+            rhs_a[i * n + j] = identity + (1.0 - alpha) * dt * sys.a[i * n + j];
+        }
+    }
+
+    let ad = solve_matrix(&ima, n, &rhs_a, n)?;
+    let scaled_b: Vec<f64> = sys.b.iter().map(|&x| x * dt).collect();
+    let bd = solve_matrix(&ima, n, &scaled_b, m)?;
+
+    // cd = C ima^-1, computed as (ima^T \ C^T)^T
+    let ima_t = transpose(&ima, n, n);
+    let c_t = transpose(&sys.c, p, n);
+    let cd = transpose(&solve_matrix(&ima_t, n, &c_t, p)?, n, p);
+
+    let c_bd = matrix_product(&sys.c, &bd, p, n, m);
+    let dd = sys
+        .d
+        .iter()
+        .zip(&c_bd)
+        .map(|(&x, &y)| x + alpha * y)
+        .collect();
+
+    Ok((ad, bd, cd, dd))
+}
+
 /// Connect two LTI systems in series
 ///
 /// For systems G1 and G2 in series: H(s) = G2(s) * G1(s)
@@ -681,6 +837,138 @@ pub fn polynomial_derivative(coeffs: &[f64]) -> Vec<f64> {
     derivative
 }
 
+/// Matrix exponential of an `n x n` row-major matrix
+///
+/// Uses scaling and squaring with a truncated Taylor series.
+pub(crate) fn matrix_exponential(a: &[f64], n: usize) -> Vec<f64> {
+    let identity: Vec<f64> = (0..n * n)
+        .map(|idx| if idx / n == idx % n { 1.0 } else { 0.0 })
+        .collect();
+
+    // Scale so that the infinity norm is at most 1/2
+    let norm = (0..n)
+        .map(|i| a[i * n..(i + 1) * n].iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0_f64, f64::max);
+    let squarings = if norm > 0.5 {
+        (norm / 0.5).log2().ceil() as i32
+    } else {
+        0
+    };
+    let scale = 0.5_f64.powi(squarings);
+    let scaled: Vec<f64> = a.iter().map(|&x| x * scale).collect();
+
+    let mut result = identity.clone();
+    let mut term = identity;
+    for k in 1..=20 {
+        term = matrix_product(&term, &scaled, n, n, n);
+        for x in term.iter_mut() {
+            *x /= k as f64;
+        }
+        for (r, &t) in result.iter_mut().zip(&term) {
+            *r += t;
+        }
+        if term.iter().all(|&t| t.abs() < 1e-18) {
+            break;
+        }
+    }
+
+    for _ in 0..squarings {
+        result = matrix_product(&result, &result, n, n, n);
+    }
+    result
+}
+
+/// Product of a row-major `rows x inner` matrix and an `inner x cols` matrix
+fn matrix_product(a: &[f64], b: &[f64], rows: usize, inner: usize, cols: usize) -> Vec<f64> {
+    let mut out = vec![0.0; rows * cols];
+    for i in 0..rows {
+        for k in 0..inner {
+            let a_ik = a[i * inner + k];
+            if a_ik != 0.0 {
+                for j in 0..cols {
+                    out[i * cols + j] += a_ik * b[k * cols + j];
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Transpose of a row-major `rows x cols` matrix
+fn transpose(a: &[f64], rows: usize, cols: usize) -> Vec<f64> {
+    let mut out = vec![0.0; rows * cols];
+    for i in 0..rows {
+        for j in 0..cols {
+            out[j * rows + i] = a[i * cols + j];
+        }
+    }
+    out
+}
+
+/// Rows and columns of a row-major matrix with `stride` columns
+fn sub_block(
+    a: &[f64],
+    stride: usize,
+    rows: std::ops::Range<usize>,
+    cols: std::ops::Range<usize>,
+) -> Vec<f64> {
+    rows.flat_map(|i| {
+        a[i * stride + cols.start..i * stride + cols.end]
+            .iter()
+            .copied()
+    })
+    .collect()
+}
+
+/// Solve `A X = B` for an `n x n` matrix `A` and an `n x m` right-hand side by
+/// Gaussian elimination with partial pivoting
+fn solve_matrix(a: &[f64], n: usize, b: &[f64], m: usize) -> SignalResult<Vec<f64>> {
+    let mut a = a.to_vec();
+    let mut x = b.to_vec();
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))
+            .unwrap_or(col);
+        if a[pivot * n + col].abs() < 1e-14 {
+            return Err(SignalError::ComputationError(
+                "Matrix is singular or nearly singular".to_string(),
+            ));
+        }
+        if pivot != col {
+            for j in 0..n {
+                a.swap(col * n + j, pivot * n + j);
+            }
+            for j in 0..m {
+                x.swap(col * m + j, pivot * m + j);
+            }
+        }
+        for row in col + 1..n {
+            let factor = a[row * n + col] / a[col * n + col];
+            if factor != 0.0 {
+                for j in col..n {
+                    a[row * n + j] -= factor * a[col * n + j];
+                }
+                for j in 0..m {
+                    x[row * m + j] -= factor * x[col * m + j];
+                }
+            }
+        }
+    }
+
+    for col in (0..n).rev() {
+        for j in 0..m {
+            let mut sum = x[col * m + j];
+            for k in col + 1..n {
+                sum -= a[col * n + k] * x[k * m + j];
+            }
+            x[col * m + j] = sum / a[col * n + col];
+        }
+    }
+
+    Ok(x)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -889,4 +1177,53 @@ mod tests {
         let result = parallel(&g_ct, &g_dt);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_cont2discrete_methods() {
+        // H(s) = 1 / (s + 1) sampled at h = 0.1
+        let sys = tf(vec![1.0], vec![1.0, 1.0], None).unwrap();
+        let h: f64 = 0.1;
+        let pole = (-h).exp();
+
+        let zoh = cont2discrete(&sys, h, Some("zoh")).unwrap();
+        assert!(zoh.dt);
+        assert_relative_eq!(zoh.a[0], pole, epsilon = 1e-12);
+        assert_relative_eq!(zoh.b[0] * zoh.c[0], 1.0 - pole, epsilon = 1e-12);
+
+        let tustin = cont2discrete(&sys, h, Some("tustin")).unwrap();
+        assert_relative_eq!(
+            tustin.a[0],
+            (1.0 - h / 2.0) / (1.0 + h / 2.0),
+            epsilon = 1e-12
+        );
+
+        let euler = cont2discrete(&sys, h, Some("euler")).unwrap();
+        assert_relative_eq!(euler.a[0], 1.0 - h, epsilon = 1e-12);
+
+        let backward = cont2discrete(&sys, h, Some("backward_diff")).unwrap();
+        assert_relative_eq!(backward.a[0], 1.0 / (1.0 + h), epsilon = 1e-12);
+
+        // All methods keep the unit DC gain
+        for method in ["zoh", "foh", "bilinear", "euler", "backward_diff"] {
+            let sys_d = cont2discrete(&sys, h, Some(method)).unwrap();
+            let h_d = sys_d.to_tf().unwrap();
+            let dc = h_d.num.iter().sum::<f64>() / h_d.den.iter().sum::<f64>();
+            assert_relative_eq!(dc, 1.0, epsilon = 1e-10);
+        }
+
+        assert!(cont2discrete(&zoh, h, None).is_err());
+        assert!(cont2discrete(&sys, h, Some("unknown")).is_err());
+        assert!(cont2discrete(&sys, -1.0, None).is_err());
+    }
+
+    #[test]
+    fn test_matrix_exponential() {
+        // Rotation generator: exp([[0, -w], [w, 0]] t) is a rotation by w t
+        let theta: f64 = 3.7;
+        let e = matrix_exponential(&[0.0, -theta, theta, 0.0], 2);
+        assert_relative_eq!(e[0], theta.cos(), epsilon = 1e-12);
+        assert_relative_eq!(e[1], -theta.sin(), epsilon = 1e-12);
+        assert_relative_eq!(e[2], theta.sin(), epsilon = 1e-12);
+        assert_relative_eq!(e[3], theta.cos(), epsilon = 1e-12);
+    }
 }
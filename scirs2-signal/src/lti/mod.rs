@@ -94,9 +94,9 @@ pub use analysis::{
 
 // Re-export design functions for convenience
 pub use design::{
-    add_polynomials, c2d, complementary_sensitivity, divide_polynomials, evaluate_polynomial,
-    feedback, multiply_polynomials, parallel, polynomial_derivative, sensitivity, series, ss,
-    subtract_polynomials, tf, zpk,
+    add_polynomials, c2d, complementary_sensitivity, cont2discrete, divide_polynomials,
+    evaluate_polynomial, feedback, multiply_polynomials, parallel, polynomial_derivative,
+    sensitivity, series, ss, subtract_polynomials, tf, zpk,
 };
 
 // Keep the system module for backward compatibility
@@ -209,41 +209,74 @@ impl LtiSystem for TransferFunction {
     }
 
     fn to_zpk(&self) -> SignalResult<ZerosPoleGain> {
-        // Convert transfer function to ZPK form by finding roots of numerator and denominator
-        // This is a basic implementation - a production version would use more robust methods
+        // Zeros and poles are the roots of the numerator and denominator
+        let num = strip_leading_zeros(&self.num);
+        let den = strip_leading_zeros(&self.den);
 
-        let gain = if self.num.is_empty() {
-            0.0
+        let gain = match (num.first(), den.first()) {
+            (Some(&n), Some(&d)) if d != 0.0 => n / d,
+            _ => 0.0,
+        };
+        let zeros = if gain == 0.0 {
+            Vec::new()
         } else {
-            self.num[0]
+            polynomial_roots(num)
         };
 
-        // Note: In practice, we would use a reliable polynomial root-finding algorithm
-        // For now, returning placeholder with empty zeros and poles
         Ok(ZerosPoleGain {
-            zeros: Vec::new(), // Replace with actual roots of numerator
-            poles: Vec::new(), // Replace with actual roots of denominator
+            zeros,
+            poles: polynomial_roots(den),
             gain,
             dt: self.dt,
         })
     }
 
     fn to_ss(&self) -> SignalResult<StateSpace> {
-        // Convert transfer function to state-space form
-        // For a SISO system, this involves creating a controllable canonical form
+        // Controllable canonical form: the first row of A holds the negated
+        // denominator coefficients and the sub-diagonal shifts the states
+        let den = strip_leading_zeros(&self.den);
+        let num = strip_leading_zeros(&self.num);
+        if den.is_empty() || den[0] == 0.0 {
+            return Err(SignalError::ValueError(
+                "Denominator polynomial cannot be zero".to_string(),
+            ));
+        }
+        if num.len() > den.len() {
+            return Err(SignalError::ValueError(
+                "Improper transfer function: numerator order exceeds denominator order".to_string(),
+            ));
+        }
+
+        let n = den.len() - 1;
+        let a_coeffs: Vec<f64> = den.iter().map(|&c| c / den[0]).collect();
+        let mut b_coeffs = vec![0.0; den.len() - num.len()];
+        b_coeffs.extend(num.iter().map(|&c| c / den[0]));
+
+        let mut a = vec![0.0; n * n];
+        for j in 0..n {
+            a[j] = -a_coeffs[j + 1];
+        }
+        for i in 1..n {
+            a[i * n + i - 1] = 1.0;
+        }
+
+        let mut b = vec![0.0; n];
+        if n > 0 {
+            b[0] = 1.0;
+        }
 
-        // This is a placeholder implementation - a full implementation would
-        // properly handle the controllable canonical form construction
+        let c = (0..n)
+            .map(|j| b_coeffs[j + 1] - b_coeffs[0] * a_coeffs[j + 1])
+            .collect();
 
-        // For now, return an empty state-space system
         Ok(StateSpace {
-            a: Vec::new(),
-            b: Vec::new(),
-            c: Vec::new(),
-            d: Vec::new(),
+            a,
+            b,
+            c,
+            d: vec![b_coeffs[0]],
+            n_states: n,
             n_inputs: 1,
             n_outputs: 1,
-            n_states: 0,
             dt: self.dt,
         })
     }
@@ -267,176 +300,15 @@ impl LtiSystem for TransferFunction {
     }
 
     fn impulse_response(&self, t: &[f64]) -> SignalResult<Vec<f64>> {
-        if t.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // For continuous-time systems, we use numerical simulation by
-        // converting to state-space form and then simulating the response.
-        if !self.dt {
-            // Convert to state-space form if it's not already available
-            let ss = self.to_ss()?;
-
-            // Get time step (assume uniform sampling)
-            let dt = if t.len() > 1 { t[1] - t[0] } else { 0.001 };
-
-            // Simulate impulse response
-            let mut response = vec![0.0; t.len()];
-
-            if !ss.b.is_empty() && !ss.c.is_empty() {
-                // Initial state is zero
-                let mut x = vec![0.0; ss.n_states];
-
-                // For an impulse, the input at t[0] is 1/dt, and 0 otherwise
-                // Inject impulse: u[0] = 1/dt, which approximates a continuous impulse
-                for (j, _) in (0..ss.n_inputs).enumerate() {
-                    for (i, x_i) in x.iter_mut().enumerate().take(ss.n_states) {
-                        *x_i += ss.b[i * ss.n_inputs + j] * (1.0 / dt);
-                    }
-                }
-
-                // Record initial output
-                for i in 0..ss.n_outputs {
-                    let mut y = 0.0;
-                    for (j, &x_j) in x.iter().enumerate().take(ss.n_states) {
-                        y += ss.c[i * ss.n_states + j] * x_j;
-                    }
-                    if i == 0 {
-                        // For SISO systems
-                        response[0] = y;
-                    }
-                }
-
-                // Simulate the system response for the rest of the time points
-                for (_k, response_k) in response.iter_mut().enumerate().skip(1).take(t.len() - 1) {
-                    // Update state: dx/dt = Ax + Bu, use forward Euler for simplicity
-                    let mut x_new = vec![0.0; ss.n_states];
-
-                    for (i, x_new_val) in x_new.iter_mut().enumerate().take(ss.n_states) {
-                        for (j, &x_val) in x.iter().enumerate().take(ss.n_states) {
-                            *x_new_val += ss.a[i * ss.n_states + j] * x_val * dt;
-                        }
-                        // No input term (Bu) after initial impulse
-                    }
-
-                    // Copy updated state
-                    x = x_new;
-
-                    // Calculate output: y = Cx + Du (u is zero after initial impulse)
-                    for i in 0..ss.n_outputs {
-                        let mut y = 0.0;
-                        for (j, &x_j) in x.iter().enumerate().take(ss.n_states) {
-                            y += ss.c[i * ss.n_states + j] * x_j;
-                        }
-                        if i == 0 {
-                            // For SISO systems
-                            *response_k = y;
-                        }
-                    }
-                }
-            }
-
-            Ok(response)
-        } else {
-            // For discrete-time systems, impulse response h[n] is equivalent to
-            // the inverse Z-transform of the transfer function H(z)
-            // For a DT system H(z) = B(z)/A(z), the impulse response is given by
-            // the coefficients of the series expansion of H(z)
-
-            let n = t.len();
-            let mut response = vec![0.0; n];
-
-            // Check if we have the right number of coefficients
-            if self.num.is_empty() || self.den.is_empty() {
-                return Ok(response);
-            }
-
-            // For a proper transfer function with normalized denominator,
-            // the first impulse response value is b[0]/a[0]
-            response[0] = if !self.den.is_empty() && self.den[0].abs() > 1e-10 {
-                self.num[0] / self.den[0]
-            } else {
-                self.num[0]
-            };
-
-            // For later samples, we use the recurrence relation:
-            // h[n] = (b[n] - sum_{k=1}^n a[k]*h[n-k])/a[0]
-            for n in 1..response.len() {
-                // Add numerator contribution
-                if n < self.num.len() {
-                    response[n] = self.num[n];
-                }
-
-                // Subtract denominator * past outputs
-                for k in 1..std::cmp::min(n + 1, self.den.len()) {
-                    response[n] -= self.den[k] * response[n - k];
-                }
-
-                // Normalize by a[0]
-                if !self.den.is_empty() && self.den[0].abs() > 1e-10 {
-                    response[n] /= self.den[0];
-                }
-            }
-
-            Ok(response)
-        }
+        self.to_ss()?.impulse_response(t)
     }
 
     fn step_response(&self, t: &[f64]) -> SignalResult<Vec<f64>> {
-        if t.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        if !self.dt {
-            // For continuous-time systems:
-            // 1. Get impulse response
-            let impulse = self.impulse_response(t)?;
-
-            // 2. Integrate the impulse response to get the step response
-            // Using the trapezoidal rule for integration
-            let mut step = vec![0.0; t.len()];
-
-            if t.len() > 1 {
-                let dt = t[1] - t[0];
-
-                // Initialize with the first value
-                step[0] = impulse[0] * dt / 2.0;
-
-                // Accumulate the integral
-                for i in 1..t.len() {
-                    step[i] = step[i - 1] + (impulse[i - 1] + impulse[i]) * dt / 2.0;
-                }
-            }
-
-            Ok(step)
-        } else {
-            // For discrete-time systems:
-            // The step response can be calculated either by:
-            // 1. Convolving the impulse response with a step input
-            // 2. Directly simulating with a step input
-            // We'll use approach 1 for simplicity
-
-            let impulse = self.impulse_response(t)?;
-            let mut step = vec![0.0; t.len()];
-
-            // Convolve with a unit step (running sum of impulse response)
-            for (i, step_val) in step.iter_mut().enumerate().take(t.len()) {
-                for &impulse_val in impulse.iter().take(i + 1) {
-                    *step_val += impulse_val;
-                }
-            }
-
-            Ok(step)
-        }
+        self.to_ss()?.step_response(t)
     }
 
     fn is_stable(&self) -> SignalResult<bool> {
-        // A system is stable if all its poles have negative real parts (continuous-time)
-        // or are inside the unit circle (discrete-time)
-
-        // For now, return a placeholder
-        // In practice, we would check the poles from to_zpk()
-        Ok(true)
+        self.to_zpk()?.is_stable()
     }
 }
 
@@ -549,18 +421,19 @@ impl ZerosPoleGain {
 
 impl LtiSystem for ZerosPoleGain {
     fn to_tf(&self) -> SignalResult<TransferFunction> {
-        // Convert ZPK to transfer function by expanding the polynomial products
-        // This is a basic implementation - a production version would use more robust methods
-
-        // For now, return a placeholder
-        // In practice, we would expand (s - zero_1) * (s - zero_2) * ... for the numerator
-        // and (s - pole_1) * (s - pole_2) * ... for the denominator
+        // Expand gain * prod(s - z_i) and prod(s - p_i); complex roots are
+        // expected in conjugate pairs, so the imaginary parts cancel
+        let num = if self.gain == 0.0 {
+            vec![0.0]
+        } else {
+            polynomial_from_roots(&self.zeros)
+                .into_iter()
+                .map(|c| self.gain * c)
+                .collect()
+        };
+        let den = polynomial_from_roots(&self.poles);
 
-        Ok(TransferFunction {
-            num: vec![self.gain],
-            den: vec![1.0],
-            dt: self.dt,
-        })
+        TransferFunction::new(num, den, Some(self.dt))
     }
 
     fn to_zpk(&self) -> SignalResult<ZerosPoleGain> {
@@ -568,20 +441,7 @@ impl LtiSystem for ZerosPoleGain {
     }
 
     fn to_ss(&self) -> SignalResult<StateSpace> {
-        // Convert ZPK to state-space
-        // Typically done by first converting to transfer function, then to state-space
-
-        // For now, return a placeholder
-        Ok(StateSpace {
-            a: Vec::new(),
-            b: Vec::new(),
-            c: Vec::new(),
-            d: Vec::new(),
-            n_inputs: 1,
-            n_outputs: 1,
-            n_states: 0,
-            dt: self.dt,
-        })
+        self.to_tf()?.to_ss()
     }
 
     fn frequency_response(&self, w: &[f64]) -> SignalResult<Vec<Complex64>> {
@@ -603,15 +463,11 @@ impl LtiSystem for ZerosPoleGain {
     }
 
     fn impulse_response(&self, t: &[f64]) -> SignalResult<Vec<f64>> {
-        // Placeholder for impulse response calculation
-        let response = vec![0.0; t.len()];
-        Ok(response)
+        self.to_ss()?.impulse_response(t)
     }
 
     fn step_response(&self, t: &[f64]) -> SignalResult<Vec<f64>> {
-        // Placeholder for step response calculation
-        let response = vec![0.0; t.len()];
-        Ok(response)
+        self.to_ss()?.step_response(t)
     }
 
     fn is_stable(&self) -> SignalResult<bool> {
@@ -856,32 +712,46 @@ impl StateSpace {
     }
 }
 
+impl StateSpace {
+    /// Check that the system has a single input and a single output
+    fn check_siso(&self) -> SignalResult<()> {
+        if self.n_inputs != 1 || self.n_outputs != 1 {
+            return Err(SignalError::ValueError(format!(
+                "Operation requires a single-input single-output system, got {} inputs and {} outputs",
+                self.n_inputs, self.n_outputs
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl LtiSystem for StateSpace {
     fn to_tf(&self) -> SignalResult<TransferFunction> {
-        // Convert state-space to transfer function
-        // For SISO systems, TF(s) = C * (sI - A)^-1 * B + D
-
-        // For now, return a placeholder
-        // In practice, we would calculate the matrix inverse and polynomial expansion
+        // H(s) = C adj(sI - A) B / det(sI - A) + D, with the adjugate and the
+        // characteristic polynomial from the Faddeev-LeVerrier recursion
+        self.check_siso()?;
+        let n = self.n_states;
+        let (den, adjugate) = faddeev_leverrier(&self.a, n);
+
+        let mut num = vec![0.0; n + 1];
+        for (k, m) in adjugate.iter().enumerate() {
+            let mut value = 0.0;
+            for i in 0..n {
+                for j in 0..n {
+                    value += self.c[i] * m[i * n + j] * self.b[j];
+                }
+            }
+            num[k + 1] = value;
+        }
+        for (coef, &d_coef) in num.iter_mut().zip(&den) {
+            *coef += self.d[0] * d_coef;
+        }
 
-        Ok(TransferFunction {
-            num: vec![1.0],
-            den: vec![1.0],
-            dt: self.dt,
-        })
+        TransferFunction::new(num, den, Some(self.dt))
     }
 
     fn to_zpk(&self) -> SignalResult<ZerosPoleGain> {
-        // Convert state-space to ZPK
-        // Typically done by first converting to transfer function, then factoring
-
-        // For now, return a placeholder
-        Ok(ZerosPoleGain {
-            zeros: Vec::new(),
-            poles: Vec::new(),
-            gain: 1.0,
-            dt: self.dt,
-        })
+        self.to_tf()?.to_zpk()
     }
 
     fn to_ss(&self) -> SignalResult<StateSpace> {
@@ -889,37 +759,191 @@ impl LtiSystem for StateSpace {
     }
 
     fn frequency_response(&self, w: &[f64]) -> SignalResult<Vec<Complex64>> {
-        // Calculate the frequency response for state-space system
-        // H(s) = C * (sI - A)^-1 * B + D
-
-        // For now, return a placeholder
-        // In practice, we would calculate the matrix inverse for each frequency
-
-        let response = vec![Complex64::new(1.0, 0.0); w.len()];
-        Ok(response)
+        self.to_tf()?.frequency_response(w)
     }
 
     fn impulse_response(&self, t: &[f64]) -> SignalResult<Vec<f64>> {
-        // Placeholder for impulse response calculation
-        let response = vec![0.0; t.len()];
-        Ok(response)
+        if t.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.check_siso()?;
+
+        if self.dt {
+            // Unit sample at the first time step
+            let mut u = vec![0.0; t.len()];
+            u[0] = 1.0;
+            crate::lti_response::lsim(self, &u, t)
+        } else {
+            // A Dirac impulse moves the state to B instantly; the feedthrough
+            // term is a Dirac impulse in the output and is left out
+            let u = vec![0.0; t.len()];
+            let (y, _) = crate::lti_response::lsim_with_state(self, &u, t, Some(&self.b))?;
+            Ok(y)
+        }
     }
 
     fn step_response(&self, t: &[f64]) -> SignalResult<Vec<f64>> {
-        // Placeholder for step response calculation
-        let response = vec![0.0; t.len()];
-        Ok(response)
+        if t.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.check_siso()?;
+        crate::lti_response::lsim(self, &vec![1.0; t.len()], t)
     }
 
     fn is_stable(&self) -> SignalResult<bool> {
         // A state-space system is stable if all eigenvalues of A have negative real parts (continuous-time)
         // or are inside the unit circle (discrete-time)
+        let (char_poly, _) = faddeev_leverrier(&self.a, self.n_states);
+        let eigenvalues = polynomial_roots(&char_poly);
 
-        // For now, return a placeholder
-        // In practice, we would calculate the eigenvalues of A
+        Ok(eigenvalues
+            .iter()
+            .all(|&p| if self.dt { p.norm() < 1.0 } else { p.re < 0.0 }))
+    }
+}
 
-        Ok(true)
+/// Drop leading zero coefficients of a polynomial given in descending powers
+fn strip_leading_zeros(coeffs: &[f64]) -> &[f64] {
+    let first = coeffs
+        .iter()
+        .position(|c| c.abs() >= 1e-10)
+        .unwrap_or(coeffs.len());
+    &coeffs[first..]
+}
+
+/// Characteristic polynomial of an `n x n` row-major matrix and the matrix
+/// coefficients of its adjugate
+///
+/// Returns `det(sI - A)` in descending powers together with `M_1, ..., M_n`
+/// such that `adj(sI - A) = sum_k M_k s^(n - k)`.
+fn faddeev_leverrier(a: &[f64], n: usize) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let mut coeffs = vec![1.0];
+    let mut adjugate = Vec::with_capacity(n);
+    let mut m: Vec<f64> = (0..n * n)
+        .map(|idx| if idx / n == idx % n { 1.0 } else { 0.0 })
+        .collect();
+
+    for k in 1..=n {
+        let mut am = vec![0.0; n * n];
+        for i in 0..n {
+            for l in 0..n {
+                let a_il = a[i * n + l];
+                if a_il != 0.0 {
+                    for j in 0..n {
+                        am[i * n + j] += a_il * m[l * n + j];
+                    }
+                }
+            }
+        }
+        let trace: f64 = (0..n).map(|i| am[i * n + i]).sum();
+        let c_k = -trace / k as f64;
+        coeffs.push(c_k);
+
+        adjugate.push(m);
+        for i in 0..n {
+            am[i * n + i] += c_k;
+        }
+        m = am;
     }
+
+    (coeffs, adjugate)
+}
+
+/// Characteristic polynomial `det(sI - A)` of an `n x n` row-major matrix, in
+/// descending powers
+pub(crate) fn characteristic_polynomial(a: &[f64], n: usize) -> Vec<f64> {
+    faddeev_leverrier(a, n).0
+}
+
+/// Roots of a real polynomial with coefficients in descending powers
+///
+/// Zeros at the origin are split off exactly; the remaining roots come from
+/// the Aberth-Ehrlich simultaneous iteration. Roots whose imaginary part is
+/// negligible are returned as exactly real.
+pub(crate) fn polynomial_roots(coeffs: &[f64]) -> Vec<Complex64> {
+    let coeffs = strip_leading_zeros(coeffs);
+    if coeffs.len() < 2 {
+        return Vec::new();
+    }
+
+    let n_zero = coeffs.iter().rev().take_while(|&&c| c == 0.0).count();
+    let coeffs = &coeffs[..coeffs.len() - n_zero];
+    let mut roots = vec![Complex64::zero(); n_zero];
+
+    let degree = coeffs.len() - 1;
+    if degree == 0 {
+        return roots;
+    }
+    let monic: Vec<f64> = coeffs.iter().map(|&c| c / coeffs[0]).collect();
+    if degree == 1 {
+        roots.push(Complex64::new(-monic[1], 0.0));
+        return roots;
+    }
+
+    let eval = |z: Complex64| {
+        let mut p = Complex64::zero();
+        let mut dp = Complex64::zero();
+        for &c in &monic {
+            dp = dp * z + p;
+            p = p * z + c;
+        }
+        (p, dp)
+    };
+
+    // Start on a circle of the geometric-mean root radius, rotated off the axes
+    let radius = monic[degree].abs().powf(1.0 / degree as f64).max(1e-3);
+    let mut estimates: Vec<Complex64> = (0..degree)
+        .map(|k| {
+            Complex64::from_polar(
+                radius,
+                2.0 * std::f64::consts::PI * (k as f64 + 0.25) / degree as f64,
+            )
+        })
+        .collect();
+
+    for _ in 0..500 {
+        let mut largest_step: f64 = 0.0;
+        for i in 0..degree {
+            let (p, dp) = eval(estimates[i]);
+            if p.norm() == 0.0 {
+                continue;
+            }
+            let ratio = p / dp;
+            let repulsion: Complex64 = (0..degree)
+                .filter(|&j| j != i)
+                .map(|j| (estimates[i] - estimates[j]).inv())
+                .sum();
+            let step = ratio / (Complex64::new(1.0, 0.0) - ratio * repulsion);
+            estimates[i] -= step;
+            largest_step = largest_step.max(step.norm() / estimates[i].norm().max(1.0));
+        }
+        if largest_step < 1e-15 {
+            break;
+        }
+    }
+
+    roots.extend(estimates.into_iter().map(|r| {
+        if r.im.abs() <= 1e-9 * r.norm().max(1.0) {
+            Complex64::new(r.re, 0.0)
+        } else {
+            r
+        }
+    }));
+    roots
+}
+
+/// Real coefficients (descending powers) of the monic polynomial with the given roots
+fn polynomial_from_roots(roots: &[Complex64]) -> Vec<f64> {
+    let mut poly = vec![Complex64::new(1.0, 0.0)];
+    for &root in roots {
+        let mut next = vec![Complex64::zero(); poly.len() + 1];
+        for (i, &c) in poly.iter().enumerate() {
+            next[i] += c;
+            next[i + 1] -= c * root;
+        }
+        poly = next;
+    }
+    poly.into_iter().map(|c| c.re).collect()
 }
 
 #[cfg(test)]
@@ -1044,4 +1068,74 @@ mod tests {
         );
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        // H(s) = (s + 3) / (s^3 + 4s^2 + 6s + 4), poles at -2 and -1 +/- j
+        let tf = TransferFunction::new(vec![1.0, 3.0], vec![1.0, 4.0, 6.0, 4.0], None).unwrap();
+
+        let zpk = tf.to_zpk().unwrap();
+        assert_eq!(zpk.zeros.len(), 1);
+        assert_eq!(zpk.poles.len(), 3);
+        assert_relative_eq!(zpk.zeros[0].re, -3.0, epsilon = 1e-10);
+        assert_relative_eq!(zpk.gain, 1.0);
+        let mut poles = zpk.poles.clone();
+        poles.sort_by(|a, b| a.im.total_cmp(&b.im));
+        assert_relative_eq!(poles[0].re, -1.0, epsilon = 1e-10);
+        assert_relative_eq!(poles[0].im, -1.0, epsilon = 1e-10);
+        assert_relative_eq!(poles[1].re, -2.0, epsilon = 1e-10);
+        assert_relative_eq!(poles[1].im, 0.0);
+
+        let from_zpk = zpk.to_tf().unwrap();
+        let ss = tf.to_ss().unwrap();
+        assert_eq!(ss.n_states, 3);
+        let from_ss = ss.to_tf().unwrap();
+        for other in [&from_zpk, &from_ss] {
+            assert_eq!(other.den.len(), tf.den.len());
+            for (a, b) in other.den.iter().zip(&tf.den) {
+                assert_relative_eq!(*a, *b, epsilon = 1e-10);
+            }
+            let num = &other.num[other.num.len() - tf.num.len()..];
+            for (a, b) in num.iter().zip(&tf.num) {
+                assert_relative_eq!(*a, *b, epsilon = 1e-10);
+            }
+        }
+
+        // Frequency responses agree across representations
+        let w = [0.0, 0.5, 2.0, 10.0];
+        let h_tf = tf.frequency_response(&w).unwrap();
+        let h_zpk = zpk.frequency_response(&w).unwrap();
+        let h_ss = ss.frequency_response(&w).unwrap();
+        for i in 0..w.len() {
+            assert_relative_eq!((h_tf[i] - h_zpk[i]).norm(), 0.0, epsilon = 1e-10);
+            assert_relative_eq!((h_tf[i] - h_ss[i]).norm(), 0.0, epsilon = 1e-10);
+        }
+
+        // Biproper systems keep their feedthrough term
+        let biproper = TransferFunction::new(vec![2.0, 1.0], vec![1.0, 3.0], None).unwrap();
+        let ss = biproper.to_ss().unwrap();
+        assert_relative_eq!(ss.d[0], 2.0);
+        assert_relative_eq!(ss.c[0], -5.0);
+
+        // Improper systems have no state-space realization
+        let improper = TransferFunction::new(vec![1.0, 0.0, 1.0], vec![1.0, 1.0], None).unwrap();
+        assert!(improper.to_ss().is_err());
+    }
+
+    #[test]
+    fn test_stability_of_all_representations() {
+        let stable = TransferFunction::new(vec![1.0], vec![1.0, 2.0, 5.0], None).unwrap();
+        assert!(stable.is_stable().unwrap());
+        assert!(stable.to_ss().unwrap().is_stable().unwrap());
+
+        let unstable = TransferFunction::new(vec![1.0], vec![1.0, -1.0, 5.0], None).unwrap();
+        assert!(!unstable.is_stable().unwrap());
+        assert!(!unstable.to_ss().unwrap().is_stable().unwrap());
+
+        // Discrete-time: pole at z = 0.9 is stable, z = 1.1 is not
+        let dt_stable = TransferFunction::new(vec![1.0], vec![1.0, -0.9], Some(true)).unwrap();
+        let dt_unstable = TransferFunction::new(vec![1.0], vec![1.0, -1.1], Some(true)).unwrap();
+        assert!(dt_stable.is_stable().unwrap());
+        assert!(!dt_unstable.is_stable().unwrap());
+    }
 }
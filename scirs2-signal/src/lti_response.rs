@@ -3,8 +3,14 @@
 //! This module provides functions for analyzing LTI system responses,
 //! including time-domain responses (impulse and step responses) and
 //! frequency-domain responses (Bode plots, Nyquist plots).
+//!
+//! Continuous-time simulation uses the matrix exponential of the state matrix,
+//! so the responses are exact at the sample times rather than the result of a
+//! numerical integrator.
 
 use crate::error::{SignalError, SignalResult};
+use crate::lti::design::matrix_exponential;
+use crate::lti::systems::{characteristic_polynomial, polynomial_roots};
 use crate::lti::LtiSystem;
 
 /// Calculate the impulse response of an LTI system
@@ -40,9 +46,10 @@ use crate::lti::LtiSystem;
 /// // Check that we get a response vector of the right length
 /// assert_eq!(response.len(), t.len());
 ///
-/// // Due to placeholder implementation, response might be all zeros
-/// // Just check that we got a valid response vector
-/// assert!(response.iter().all(|x| x.is_finite()));
+/// // h(t) = exp(-t)
+/// for (&ti, &hi) in t.iter().zip(&response) {
+///     assert!((hi - (-ti).exp()).abs() < 1e-10);
+/// }
 /// ```
 pub fn impulse_response<T: LtiSystem>(system: &T, t: &[f64]) -> SignalResult<Vec<f64>> {
     system.impulse_response(t)
@@ -81,9 +88,10 @@ pub fn impulse_response<T: LtiSystem>(system: &T, t: &[f64]) -> SignalResult<Vec
 /// // Check that we get a response vector of the right length
 /// assert_eq!(response.len(), t.len());
 ///
-/// // Due to placeholder implementation, response might be all zeros
-/// // Just check that we got a valid response vector
-/// assert!(response.iter().all(|x| x.is_finite()));
+/// // s(t) = 1 - exp(-t)
+/// for (&ti, &si) in t.iter().zip(&response) {
+///     assert!((si - (1.0 - (-ti).exp())).abs() < 1e-10);
+/// }
 /// ```
 pub fn step_response<T: LtiSystem>(system: &T, t: &[f64]) -> SignalResult<Vec<f64>> {
     system.step_response(t)
@@ -91,6 +99,11 @@ pub fn step_response<T: LtiSystem>(system: &T, t: &[f64]) -> SignalResult<Vec<f6
 
 /// Simulate the response of an LTI system to an arbitrary input
 ///
+/// Continuous-time systems are integrated exactly for an input that varies
+/// linearly between the samples, which requires equally spaced time points.
+/// Discrete-time systems advance one sample per time point. The system must
+/// have a single input; for several outputs the first one is returned.
+///
 /// # Arguments
 ///
 /// * `system` - The LTI system
@@ -114,21 +127,63 @@ pub fn step_response<T: LtiSystem>(system: &T, t: &[f64]) -> SignalResult<Vec<f6
 ///     None,
 /// ).unwrap();
 ///
-/// // Generate time vector and input signal (e.g., a sine wave)
+/// // Generate time vector and a ramp input
 /// let t: Vec<f64> = (0..100).map(|i| i as f64 * 0.1).collect();
-/// let u: Vec<f64> = t.iter().map(|&time| (2.0 * time).sin()).collect();
+/// let u = t.clone();
 ///
 /// // Simulate system response
 /// let y = lsim(&system, &u, &t).unwrap();
 ///
-/// // Output should be the convolution of the input with the impulse response
+/// // The ramp response is t - 1 + exp(-t)
 /// assert_eq!(y.len(), t.len());
-/// // Basic sanity checks
-/// assert!(y.iter().all(|&val| val.is_finite()));
+/// for (&ti, &yi) in t.iter().zip(&y) {
+///     assert!((yi - (ti - 1.0 + (-ti).exp())).abs() < 1e-10);
+/// }
 /// ```
 pub fn lsim<T: LtiSystem>(system: &T, u: &[f64], t: &[f64]) -> SignalResult<Vec<f64>> {
+    lsim_with_state(system, u, t, None).map(|(y, _)| y)
+}
+
+/// Simulate an LTI system from an initial state, returning the state trajectory
+///
+/// See [`lsim`] for how the input is interpolated.
+///
+/// # Arguments
+///
+/// * `system` - The LTI system
+/// * `u` - The input signal
+/// * `t` - The time points (must match the length of u)
+/// * `x0` - Initial state (default: zero)
+///
+/// # Returns
+///
+/// * A tuple of (output, state vector at each time point)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::lti::StateSpace;
+/// use scirs2_signal::lti_response::lsim_with_state;
+///
+/// // dx/dt = -2x + u, y = x, released from x(0) = 1 with no input
+/// let system = StateSpace::new(vec![-2.0], vec![1.0], vec![1.0], vec![0.0], None).unwrap();
+/// let t: Vec<f64> = (0..20).map(|i| i as f64 * 0.05).collect();
+/// let u = vec![0.0; t.len()];
+///
+/// let (y, x) = lsim_with_state(&system, &u, &t, Some(&[1.0])).unwrap();
+/// for (i, &ti) in t.iter().enumerate() {
+///     assert!((y[i] - (-2.0 * ti).exp()).abs() < 1e-10);
+///     assert_eq!(x[i].len(), 1);
+/// }
+/// ```
+pub fn lsim_with_state<T: LtiSystem>(
+    system: &T,
+    u: &[f64],
+    t: &[f64],
+    x0: Option<&[f64]>,
+) -> SignalResult<(Vec<f64>, Vec<Vec<f64>>)> {
     if t.is_empty() || u.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), Vec::new()));
     }
 
     if t.len() != u.len() {
@@ -139,117 +194,202 @@ pub fn lsim<T: LtiSystem>(system: &T, u: &[f64], t: &[f64]) -> SignalResult<Vec<
 
     // Convert to state-space for simulation
     let ss = system.to_ss()?;
+    if ss.n_inputs != 1 || ss.n_outputs == 0 {
+        return Err(SignalError::ValueError(format!(
+            "Simulation requires a single-input system with at least one output, got {} inputs and {} outputs",
+            ss.n_inputs, ss.n_outputs
+        )));
+    }
 
-    // Initialize state and output vectors
-    let mut x = vec![0.0; ss.n_states];
-    let mut y = vec![0.0; t.len()];
-
-    // For continuous-time systems, use numerical integration
-    if !ss.dt {
-        // Calculate time step (assuming uniform spacing)
-        let dt = if t.len() > 1 { t[1] - t[0] } else { 0.001 };
-
-        // Simulate the system using improved forward Euler integration
-        // Initialize state to zero
-        for k in 0..t.len() {
-            // Calculate output: y = Cx + Du
-            for i in 0..ss.n_outputs {
-                let mut output = 0.0;
-
-                // Calculate the Cx term
-                for (j, &x_val) in x.iter().enumerate().take(ss.n_states) {
-                    output += ss.c[i * ss.n_states + j] * x_val;
-                }
-
-                // Add the Du term if we have inputs and D matrix
-                if !u.is_empty() && !ss.d.is_empty() {
-                    for j in 0..ss.n_inputs {
-                        output += ss.d[i * ss.n_inputs + j] * u[k];
-                    }
-                }
-
-                // For single output case
-                if i == 0 || ss.n_outputs == 1 {
-                    y[k] = output;
-                }
-            }
-
-            // Update state using forward Euler: dx/dt = Ax + Bu
-            if k < t.len() - 1 {
-                let mut x_dot = vec![0.0; ss.n_states];
-
-                // Calculate x_dot = Ax + Bu
-                for (i, x_dot_i) in x_dot.iter_mut().enumerate().take(ss.n_states) {
-                    // First the Ax term
-                    for (j, &x_val) in x.iter().enumerate().take(ss.n_states) {
-                        *x_dot_i += ss.a[i * ss.n_states + j] * x_val;
-                    }
-
-                    // Then add the Bu term if inputs are available and B matrix exists
-                    if !u.is_empty() && !ss.b.is_empty() {
-                        for j in 0..ss.n_inputs {
-                            *x_dot_i += ss.b[i * ss.n_inputs + j] * u[k];
-                        }
-                    }
-                }
-
-                // Update x = x + x_dot * dt
-                for (i, x_val) in x.iter_mut().enumerate().take(ss.n_states) {
-                    *x_val += x_dot[i] * dt;
-                }
-            }
+    let n = ss.n_states;
+    let mut x = match x0 {
+        Some(x0) if x0.len() != n => {
+            return Err(SignalError::DimensionError(format!(
+                "Initial state has length {} but the system has {} states",
+                x0.len(),
+                n
+            )))
         }
-    } else {
-        // For discrete-time systems, use difference equation
-        for k in 0..t.len() {
-            // Calculate output: y[k] = Cx[k] + Du[k]
-            for i in 0..ss.n_outputs {
-                let mut output = 0.0;
-
-                // Calculate Cx term
-                for (j, &x_val) in x.iter().enumerate().take(ss.n_states) {
-                    output += ss.c[i * ss.n_states + j] * x_val;
-                }
-
-                // Add Du term if inputs are available
-                if !u.is_empty() {
-                    for j in 0..ss.n_inputs {
-                        output += ss.d[i * ss.n_inputs + j] * u[k];
-                    }
-                }
-
-                // Store output for single output case
-                if i == 0 || ss.n_outputs == 1 {
-                    y[k] = output;
-                }
-            }
+        Some(x0) => x0.to_vec(),
+        None => vec![0.0; n],
+    };
 
-            // Update state: x[k+1] = Ax[k] + Bu[k]
-            if k < t.len() - 1 {
-                let mut x_new = vec![0.0; ss.n_states];
-
-                // Calculate Ax term
-                for (i, x_new_val) in x_new.iter_mut().enumerate().take(ss.n_states) {
-                    for (j, &x_val) in x.iter().enumerate().take(ss.n_states) {
-                        *x_new_val += ss.a[i * ss.n_states + j] * x_val;
-                    }
-                }
-
-                // Add Bu term if inputs are available
-                if !u.is_empty() {
-                    for (i, x_new_val) in x_new.iter_mut().enumerate().take(ss.n_states) {
-                        for j in 0..ss.n_inputs {
-                            *x_new_val += ss.b[i * ss.n_inputs + j] * u[k];
-                        }
-                    }
-                }
-
-                x = x_new;
+    // x[k+1] = phi x[k] + gamma0 u[k] + gamma1 u[k+1]
+    let (phi, gamma0, gamma1) = if ss.dt {
+        (ss.a.clone(), ss.b.clone(), vec![0.0; n])
+    } else {
+        let h = uniform_time_step(t)?;
+
+        // exp([[A h, B h, 0], [0, 0, 1], [0, 0, 0]]) gives the transition matrix
+        // and the responses to a step and a ramp of the input over one step
+        let size = n + 2;
+        let mut block = vec![0.0; size * size];
+        for i in 0..n {
+            for j in 0..n {
+                block[i * size + j] = ss.a[i * n + j] * h;
             }
+            block[i * size + n] = ss.b[i] * h;
+        }
+        block[n * size + n + 1] = 1.0;
+        let e = matrix_exponential(&block, size);
+
+        let mut phi = vec![0.0; n * n];
+        let mut gamma0 = vec![0.0; n];
+        let mut gamma1 = vec![0.0; n];
+        for i in 0..n {
+            phi[i * n..(i + 1) * n].copy_from_slice(&e[i * size..i * size + n]);
+            gamma1[i] = e[i * size + n + 1];
+            gamma0[i] = e[i * size + n] - gamma1[i];
+        }
+        (phi, gamma0, gamma1)
+    };
+
+    let mut y = Vec::with_capacity(t.len());
+    let mut states = Vec::with_capacity(t.len());
+    for k in 0..t.len() {
+        // y = Cx + Du for the first output
+        let output: f64 =
+            ss.c[..n].iter().zip(&x).map(|(c, x)| c * x).sum::<f64>() + ss.d[0] * u[k];
+        y.push(output);
+
+        if k + 1 < t.len() {
+            let next: Vec<f64> = (0..n)
+                .map(|i| {
+                    phi[i * n..(i + 1) * n]
+                        .iter()
+                        .zip(&x)
+                        .map(|(a, x)| a * x)
+                        .sum::<f64>()
+                        + gamma0[i] * u[k]
+                        + gamma1[i] * u[k + 1]
+                })
+                .collect();
+            states.push(std::mem::replace(&mut x, next));
+        } else {
+            states.push(x.clone());
         }
     }
 
-    Ok(y)
+    Ok((y, states))
+}
+
+/// Impulse response of an LTI system on a default or given time grid
+///
+/// Without explicit time points, continuous-time systems are evaluated at 100
+/// points spanning seven time constants of the slowest pole, and
+/// discrete-time systems at the first 100 samples.
+///
+/// # Arguments
+///
+/// * `system` - The LTI system
+/// * `t` - Time points (optional)
+///
+/// # Returns
+///
+/// * A tuple of (time points, impulse response)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::lti::TransferFunction;
+/// use scirs2_signal::lti_response::impulse;
+///
+/// // H(s) = 1 / (s + 2): time constant 0.5, so the grid spans 3.5 seconds
+/// let system = TransferFunction::new(vec![1.0], vec![1.0, 2.0], None).unwrap();
+/// let (t, h) = impulse(&system, None).unwrap();
+///
+/// assert_eq!(t.len(), 100);
+/// assert!((t[99] - 3.5).abs() < 1e-12);
+/// assert!((h[0] - 1.0).abs() < 1e-12);
+/// ```
+pub fn impulse<T: LtiSystem>(system: &T, t: Option<&[f64]>) -> SignalResult<(Vec<f64>, Vec<f64>)> {
+    let t = match t {
+        Some(t) => t.to_vec(),
+        None => default_response_times(system)?,
+    };
+    let h = system.impulse_response(&t)?;
+    Ok((t, h))
+}
+
+/// Step response of an LTI system on a default or given time grid
+///
+/// The default time grid is chosen as in [`impulse`].
+///
+/// # Arguments
+///
+/// * `system` - The LTI system
+/// * `t` - Time points (optional)
+///
+/// # Returns
+///
+/// * A tuple of (time points, step response)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::lti::TransferFunction;
+/// use scirs2_signal::lti_response::step;
+///
+/// // H(s) = 4 / (s^2 + 2s + 4) settles at a DC gain of 1
+/// let system = TransferFunction::new(vec![4.0], vec![1.0, 2.0, 4.0], None).unwrap();
+/// let (_, s) = step(&system, None).unwrap();
+///
+/// assert!(s[0].abs() < 1e-12);
+/// assert!((s[99] - 1.0).abs() < 1e-3);
+/// ```
+pub fn step<T: LtiSystem>(system: &T, t: Option<&[f64]>) -> SignalResult<(Vec<f64>, Vec<f64>)> {
+    let t = match t {
+        Some(t) => t.to_vec(),
+        None => default_response_times(system)?,
+    };
+    let s = system.step_response(&t)?;
+    Ok((t, s))
+}
+
+/// Default time grid: seven time constants of the slowest pole for
+/// continuous-time systems, 100 samples for discrete-time systems
+fn default_response_times<T: LtiSystem>(system: &T) -> SignalResult<Vec<f64>> {
+    const N_POINTS: usize = 100;
+    let ss = system.to_ss()?;
+    if ss.dt {
+        return Ok((0..N_POINTS).map(|i| i as f64).collect());
+    }
+
+    let poles = polynomial_roots(&characteristic_polynomial(&ss.a, ss.n_states));
+    let slowest = poles
+        .iter()
+        .map(|p| p.re.abs())
+        .fold(f64::INFINITY, f64::min);
+    let rate = if slowest > 0.0 && slowest.is_finite() {
+        slowest
+    } else {
+        1.0
+    };
+    let t_end = 7.0 / rate;
+
+    Ok((0..N_POINTS)
+        .map(|i| t_end * i as f64 / (N_POINTS - 1) as f64)
+        .collect())
+}
+
+/// Common spacing of equally spaced time points
+fn uniform_time_step(t: &[f64]) -> SignalResult<f64> {
+    if t.len() < 2 {
+        return Ok(0.0);
+    }
+    let h = t[1] - t[0];
+    if !(h > 0.0 && h.is_finite()) {
+        return Err(SignalError::ValueError(
+            "Time points must be strictly increasing".to_string(),
+        ));
+    }
+    let tolerance = 1e-6 * h;
+    if t.windows(2).any(|w| ((w[1] - w[0]) - h).abs() > tolerance) {
+        return Err(SignalError::ValueError(
+            "Time points must be equally spaced".to_string(),
+        ));
+    }
+    Ok(h)
 }
 
 #[cfg(test)]
@@ -258,7 +398,6 @@ mod tests {
     use crate::lti::TransferFunction;
 
     #[test]
-    #[ignore = "Implementation needs more work on numerical integration"]
     fn test_first_order_impulse_response() {
         // Create a first-order system: H(s) = 1 / (s + 1)
         let tf = TransferFunction::new(vec![1.0], vec![1.0, 1.0], None).unwrap();
@@ -285,7 +424,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "Implementation needs more work on numerical integration"]
     fn test_first_order_step_response() {
         // Create a first-order system: H(s) = 1 / (s + 1)
         let tf = TransferFunction::new(vec![1.0], vec![1.0, 1.0], None).unwrap();
@@ -312,7 +450,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "Implementation needs more work on arbitrary input handling"]
     fn test_sine_input_response() {
         // Create a first-order system: H(s) = 1 / (s + 1)
         let tf = TransferFunction::new(
@@ -336,4 +473,81 @@ mod tests {
         // Just check that the response is non-zero
         assert!(y.iter().any(|&val| val.abs() > 1e-6));
     }
+
+    #[test]
+    fn test_second_order_step_and_impulse() {
+        // Underdamped H(s) = wn^2 / (s^2 + 2 zeta wn s + wn^2)
+        let (wn, zeta): (f64, f64) = (3.0, 0.2);
+        let tf = TransferFunction::new(vec![wn * wn], vec![1.0, 2.0 * zeta * wn, wn * wn], None)
+            .unwrap();
+        let wd = wn * (1.0 - zeta * zeta).sqrt();
+        let phase = zeta.acos();
+
+        let t: Vec<f64> = (0..200).map(|i| i as f64 * 0.02).collect();
+        let s = step_response(&tf, &t).unwrap();
+        let h = impulse_response(&tf, &t).unwrap();
+        for (i, &time) in t.iter().enumerate() {
+            let decay = (-zeta * wn * time).exp();
+            let expected_step =
+                1.0 - decay * (wd * time + phase).sin() / (1.0 - zeta * zeta).sqrt();
+            let expected_impulse = wn * wn / wd * decay * (wd * time).sin();
+            assert!((s[i] - expected_step).abs() < 1e-9);
+            assert!((h[i] - expected_impulse).abs() < 1e-9);
+        }
+
+        // The same system in zero-pole-gain and state-space form
+        let zpk = tf.to_zpk().unwrap();
+        let ss = tf.to_ss().unwrap();
+        let s_zpk = step_response(&zpk, &t).unwrap();
+        let s_ss = step_response(&ss, &t).unwrap();
+        for i in 0..t.len() {
+            assert!((s_zpk[i] - s[i]).abs() < 1e-9);
+            assert!((s_ss[i] - s[i]).abs() < 1e-12);
+        }
+
+        // Uneven time steps are rejected for continuous-time systems
+        assert!(lsim(&tf, &[1.0, 1.0, 1.0], &[0.0, 0.1, 0.3]).is_err());
+    }
+
+    #[test]
+    fn test_discrete_time_response() {
+        // y[n] = 0.5 y[n-1] + x[n]
+        let tf = TransferFunction::new(vec![1.0, 0.0], vec![1.0, -0.5], Some(true)).unwrap();
+        let t: Vec<f64> = (0..10).map(|i| i as f64).collect();
+
+        let (_, h) = impulse(&tf, Some(&t)).unwrap();
+        let (_, s) = step(&tf, Some(&t)).unwrap();
+        for (k, (&hk, &sk)) in h.iter().zip(&s).enumerate() {
+            assert!((hk - 0.5f64.powi(k as i32)).abs() < 1e-12);
+            assert!((sk - (2.0 - 0.5f64.powi(k as i32))).abs() < 1e-12);
+        }
+
+        // Default grid for discrete-time systems is 100 samples
+        let (t_default, _) = step(&tf, None).unwrap();
+        assert_eq!(t_default.len(), 100);
+        assert_eq!(t_default[1], 1.0);
+    }
+
+    #[test]
+    fn test_lsim_with_initial_state() {
+        // Harmonic oscillator dx1/dt = x2, dx2/dt = -x1 started at x = [1, 0]
+        let ss = crate::lti::StateSpace::new(
+            vec![0.0, 1.0, -1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![0.0],
+            None,
+        )
+        .unwrap();
+        let t: Vec<f64> = (0..50).map(|i| i as f64 * 0.1).collect();
+        let u = vec![0.0; t.len()];
+
+        let (y, x) = lsim_with_state(&ss, &u, &t, Some(&[1.0, 0.0])).unwrap();
+        for (i, &time) in t.iter().enumerate() {
+            assert!((y[i] - time.cos()).abs() < 1e-10);
+            assert!((x[i][1] + time.sin()).abs() < 1e-10);
+        }
+
+        assert!(lsim_with_state(&ss, &u, &t, Some(&[1.0])).is_err());
+    }
 }
@@ -0,0 +1,503 @@
+//! Matched filtering and ambiguity functions
+//!
+//! This module provides detection of a known waveform in noise and the
+//! delay–Doppler analysis used in radar and sonar:
+//!
+//! - [`matched_filter`] correlates a signal with a template, optionally
+//!   whitening coloured noise first, and reports the detection peak with an
+//!   SNR estimate.
+//! - [`cross_ambiguity`] and [`ambiguity`] compute the (cross-)ambiguity
+//!   surface `|χ(τ, ν)|` of complex baseband signals with one FFT per delay.
+//!
+//! The filter-design form of the matched filter (time-reversed template
+//! coefficients) is [`crate::filter::matched_filter`].
+
+use crate::error::{SignalError, SignalResult};
+use crate::spectral::{welch_with_config, SpectralConfig};
+use ndarray::Array2;
+use num_complex::Complex64;
+use rustfft::FftPlanner;
+use scirs2_core::parallel_ops::*;
+
+/// Noise whitening applied by [`matched_filter`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum NoiseWhitening {
+    /// Assume white noise (classical matched filter)
+    #[default]
+    None,
+    /// Known one-sided noise power spectral density, sampled uniformly from
+    /// DC to Nyquist (e.g. the output of [`crate::spectral::welch`]); only its
+    /// shape matters
+    Psd(Vec<f64>),
+    /// Estimate the noise spectrum from the signal itself with Welch's method
+    /// using segments of `nperseg` samples; appropriate when the template
+    /// occupies a small fraction of the signal
+    Estimate {
+        /// Welch segment length
+        nperseg: usize,
+    },
+}
+
+/// Configuration for [`matched_filter`]
+#[derive(Debug, Clone, Default)]
+pub struct MatchedFilterConfig {
+    /// Noise whitening
+    pub whitening: NoiseWhitening,
+}
+
+/// Result of [`matched_filter`]
+#[derive(Debug, Clone)]
+pub struct MatchedFilterResult {
+    /// Filter output indexed by template start position, `N - M + 1` samples;
+    /// normalized so that the noise contribution has the noise standard
+    /// deviation (white) or unit variance (whitened)
+    pub output: Vec<f64>,
+    /// Position of the largest output magnitude
+    pub peak_index: usize,
+    /// Output value at the peak
+    pub peak_value: f64,
+    /// Estimated output SNR at the peak in dB, relative to a robust (median
+    /// absolute deviation) estimate of the output noise level
+    pub snr_db: f64,
+}
+
+/// Result of [`cross_ambiguity`]
+#[derive(Debug, Clone)]
+pub struct AmbiguityResult {
+    /// Ambiguity magnitude `|χ(τ, ν)|` with delays along rows and Doppler
+    /// shifts along columns
+    pub surface: Array2<f64>,
+    /// Delay of each row in samples
+    pub delays: Vec<isize>,
+    /// Doppler shift of each column in cycles per sample, from -0.5 to 0.5
+    pub dopplers: Vec<f64>,
+    /// Delay of the surface peak
+    pub peak_delay: isize,
+    /// Doppler shift of the surface peak
+    pub peak_doppler: f64,
+    /// Estimated peak-to-background power ratio in dB
+    pub snr_db: f64,
+}
+
+/// Detect a known template in a signal with a matched filter
+///
+/// Computes `y[k] = sum_n x[k + n] s[n]` for every template start position `k`
+/// via the FFT. With noise whitening the correlation is weighted by the inverse
+/// noise spectrum, `Y(f) = X(f) S*(f) / P(f)`, which maximizes the output SNR
+/// for coloured Gaussian noise.
+///
+/// # Arguments
+///
+/// * `signal` - Signal to search, of length `N`
+/// * `template` - Known waveform, of length `M <= N`
+/// * `config` - Whitening configuration
+///
+/// # Returns
+///
+/// * Filter output with the peak location and SNR estimate
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::matched::{matched_filter, MatchedFilterConfig};
+///
+/// let template: Vec<f64> = (0..32).map(|i| (0.002 * (i * i) as f64 * 10.0).sin()).collect();
+/// let mut signal: Vec<f64> = (0..512).map(|i| 0.1 * ((i * 7919 % 101) as f64 / 50.0 - 1.0)).collect();
+/// for (i, &s) in template.iter().enumerate() {
+///     signal[200 + i] += s;
+/// }
+///
+/// let result = matched_filter(&signal, &template, &MatchedFilterConfig::default()).unwrap();
+/// assert_eq!(result.peak_index, 200);
+/// assert!(result.snr_db > 10.0);
+/// ```
+pub fn matched_filter(
+    signal: &[f64],
+    template: &[f64],
+    config: &MatchedFilterConfig,
+) -> SignalResult<MatchedFilterResult> {
+    let n = signal.len();
+    let m = template.len();
+    if m == 0 {
+        return Err(SignalError::ValueError(
+            "Template cannot be empty".to_string(),
+        ));
+    }
+    if n < m {
+        return Err(SignalError::ValueError(format!(
+            "Signal length {} is shorter than template length {}",
+            n, m
+        )));
+    }
+
+    let nfft = (n + m).next_power_of_two();
+    let mut planner = FftPlanner::new();
+    let forward = planner.plan_fft_forward(nfft);
+    let inverse = planner.plan_fft_inverse(nfft);
+
+    let spectrum = |x: &[f64]| {
+        let mut buffer = vec![Complex64::new(0.0, 0.0); nfft];
+        for (b, &v) in buffer.iter_mut().zip(x) {
+            b.re = v;
+        }
+        forward.process(&mut buffer);
+        buffer
+    };
+    let x_spec = spectrum(signal);
+    let s_spec = spectrum(template);
+
+    // Inverse noise spectrum on the FFT bins
+    let weights = match &config.whitening {
+        NoiseWhitening::None => vec![1.0; nfft],
+        NoiseWhitening::Psd(psd) => inverse_psd_weights(psd, nfft)?,
+        NoiseWhitening::Estimate { nperseg } => {
+            if *nperseg < 2 {
+                return Err(SignalError::ValueError(
+                    "Noise estimation segment length must be at least 2".to_string(),
+                ));
+            }
+            let welch_config = SpectralConfig {
+                nperseg: Some((*nperseg).min(n)),
+                ..Default::default()
+            };
+            let (_, psd) = welch_with_config(signal, &welch_config)?;
+            inverse_psd_weights(&psd, nfft)?
+        }
+    };
+
+    let mut product: Vec<Complex64> = x_spec
+        .iter()
+        .zip(&s_spec)
+        .zip(&weights)
+        .map(|((x, s), w)| x * s.conj() * w)
+        .collect();
+    inverse.process(&mut product);
+
+    // Noise at the output has variance sum_k |S_k|^2 W_k / nfft (times the
+    // noise level for white noise)
+    let gain = s_spec
+        .iter()
+        .zip(&weights)
+        .map(|(s, w)| s.norm_sqr() * w)
+        .sum::<f64>()
+        / nfft as f64;
+    if gain <= 0.0 || !gain.is_finite() {
+        return Err(SignalError::ComputationError(
+            "Template has no energy after whitening".to_string(),
+        ));
+    }
+    let scale = 1.0 / (nfft as f64 * gain.sqrt());
+    let output: Vec<f64> = product[..n - m + 1].iter().map(|c| c.re * scale).collect();
+
+    let (peak_index, peak_value) = output
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+        .unwrap_or((0, 0.0));
+
+    // Robust noise level: 1.4826 * MAD estimates the standard deviation
+    let mut abs_dev: Vec<f64> = {
+        let med = median(output.clone());
+        output.iter().map(|v| (v - med).abs()).collect()
+    };
+    abs_dev.retain(|v| v.is_finite());
+    let sigma = 1.4826 * median(abs_dev);
+    let snr_db = if sigma > 0.0 {
+        20.0 * (peak_value.abs() / sigma).log10()
+    } else {
+        f64::INFINITY
+    };
+
+    Ok(MatchedFilterResult {
+        output,
+        peak_index,
+        peak_value,
+        snr_db,
+    })
+}
+
+/// Compute the cross-ambiguity function of two complex baseband signals
+///
+/// Evaluates `χ(τ, ν) = sum_n x[n] y*[n - τ] exp(-j 2π ν n)` for every delay
+/// `τ` in `-max_delay..=max_delay` and `n_doppler` Doppler shifts spanning
+/// `[-0.5, 0.5)` cycles per sample. Each delay row is one FFT of the lagged
+/// product; rows are computed in parallel. If `x` is `y` delayed by `d`
+/// samples and shifted by `ν0`, the surface peaks at `(d, ν0)`.
+///
+/// # Arguments
+///
+/// * `x` - Received (surveillance) signal
+/// * `y` - Reference signal
+/// * `max_delay` - Largest delay magnitude in samples
+/// * `n_doppler` - Number of Doppler bins, at least the signal length
+///   (default: the next power of two)
+///
+/// # Returns
+///
+/// * Ambiguity surface with its axes, peak location and SNR estimate
+///
+/// # Examples
+///
+/// ```
+/// use num_complex::Complex64;
+/// use scirs2_signal::matched::cross_ambiguity;
+///
+/// // Reference pulse, and an echo delayed by 5 samples and shifted by 1/16 cycle/sample
+/// let reference: Vec<Complex64> = (0..64)
+///     .map(|n| Complex64::from_polar(1.0, 0.01 * (n * n) as f64))
+///     .collect();
+/// let echo: Vec<Complex64> = (0..64)
+///     .map(|n| {
+///         if n >= 5 {
+///             reference[n - 5] * Complex64::from_polar(1.0, 2.0 * std::f64::consts::PI * n as f64 / 16.0)
+///         } else {
+///             Complex64::new(0.0, 0.0)
+///         }
+///     })
+///     .collect();
+///
+/// let result = cross_ambiguity(&echo, &reference, 10, None).unwrap();
+/// assert_eq!(result.peak_delay, 5);
+/// assert!((result.peak_doppler - 1.0 / 16.0).abs() < 1e-12);
+/// ```
+pub fn cross_ambiguity(
+    x: &[Complex64],
+    y: &[Complex64],
+    max_delay: usize,
+    n_doppler: Option<usize>,
+) -> SignalResult<AmbiguityResult> {
+    if x.is_empty() || y.is_empty() {
+        return Err(SignalError::ValueError(
+            "Input arrays are empty".to_string(),
+        ));
+    }
+    let n = x.len();
+    let nfft = n_doppler.unwrap_or_else(|| n.next_power_of_two());
+    if nfft < n {
+        return Err(SignalError::ValueError(format!(
+            "Number of Doppler bins ({}) must be at least the signal length ({})",
+            nfft, n
+        )));
+    }
+
+    let fft = FftPlanner::new().plan_fft_forward(nfft);
+    let delays: Vec<isize> = (-(max_delay as isize)..=max_delay as isize).collect();
+
+    let rows: Vec<Vec<f64>> = delays
+        .clone()
+        .into_par_iter()
+        .map(|tau| {
+            let mut buffer = vec![Complex64::new(0.0, 0.0); nfft];
+            for (i, b) in buffer.iter_mut().enumerate().take(n) {
+                let j = i as isize - tau;
+                if (0..y.len() as isize).contains(&j) {
+                    *b = x[i] * y[j as usize].conj();
+                }
+            }
+            fft.process(&mut buffer);
+            // Reorder so that Doppler runs from -0.5 to 0.5
+            (0..nfft)
+                .map(|k| buffer[(k + nfft.div_ceil(2)) % nfft].norm())
+                .collect()
+        })
+        .collect();
+
+    let mut surface = Array2::zeros((delays.len(), nfft));
+    for (i, row) in rows.iter().enumerate() {
+        for (j, &v) in row.iter().enumerate() {
+            surface[[i, j]] = v;
+        }
+    }
+    let dopplers: Vec<f64> = (0..nfft)
+        .map(|k| (k as f64 - (nfft / 2) as f64) / nfft as f64)
+        .collect();
+
+    let ((peak_row, peak_col), peak) = surface
+        .indexed_iter()
+        .map(|(idx, &v)| (idx, v))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or(((0, 0), 0.0));
+
+    // The median of an exponentially distributed power is its mean times ln 2
+    let background = median(surface.iter().map(|v| v * v).collect()) / std::f64::consts::LN_2;
+    let snr_db = if background > 0.0 {
+        10.0 * (peak * peak / background).log10()
+    } else {
+        f64::INFINITY
+    };
+
+    Ok(AmbiguityResult {
+        surface,
+        peak_delay: delays[peak_row],
+        peak_doppler: dopplers[peak_col],
+        delays,
+        dopplers,
+        snr_db,
+    })
+}
+
+/// Compute the (auto-)ambiguity function of a complex baseband signal
+///
+/// Equivalent to [`cross_ambiguity`] of the signal with itself; the surface
+/// peaks at zero delay and zero Doppler, and its shape describes the delay
+/// and Doppler resolution of the waveform.
+///
+/// # Arguments
+///
+/// * `x` - Signal
+/// * `max_delay` - Largest delay magnitude in samples
+/// * `n_doppler` - Number of Doppler bins (default: next power of two)
+///
+/// # Returns
+///
+/// * Ambiguity surface with its axes, peak location and SNR estimate
+///
+/// # Examples
+///
+/// ```
+/// use num_complex::Complex64;
+/// use scirs2_signal::matched::ambiguity;
+///
+/// let pulse = vec![Complex64::new(1.0, 0.0); 16];
+/// let result = ambiguity(&pulse, 8, Some(64)).unwrap();
+/// assert_eq!(result.peak_delay, 0);
+/// assert_eq!(result.peak_doppler, 0.0);
+/// assert!((result.surface[[8, 32]] - 16.0).abs() < 1e-12);
+/// ```
+pub fn ambiguity(
+    x: &[Complex64],
+    max_delay: usize,
+    n_doppler: Option<usize>,
+) -> SignalResult<AmbiguityResult> {
+    cross_ambiguity(x, x, max_delay, n_doppler)
+}
+
+/// Inverse of a one-sided PSD, interpolated onto the bins of an FFT of length `nfft`
+fn inverse_psd_weights(psd: &[f64], nfft: usize) -> SignalResult<Vec<f64>> {
+    if psd.len() < 2 {
+        return Err(SignalError::ValueError(
+            "Noise PSD must have at least two bins".to_string(),
+        ));
+    }
+    if psd.iter().any(|p| !p.is_finite() || *p < 0.0) {
+        return Err(SignalError::ValueError(
+            "Noise PSD must be finite and non-negative".to_string(),
+        ));
+    }
+    let max = psd.iter().fold(0.0f64, |a, &b| a.max(b));
+    if max <= 0.0 {
+        return Err(SignalError::ValueError("Noise PSD is zero".to_string()));
+    }
+    let floor = 1e-12 * max;
+
+    let last = (psd.len() - 1) as f64;
+    Ok((0..nfft)
+        .map(|k| {
+            // Fraction of the Nyquist frequency for this bin
+            let f = k.min(nfft - k) as f64 / (nfft as f64 / 2.0);
+            let pos = f * last;
+            let i = (pos.floor() as usize).min(psd.len() - 2);
+            let t = pos - i as f64;
+            let p = psd[i] * (1.0 - t) + psd[i + 1] * t;
+            1.0 / p.max(floor)
+        })
+        .collect())
+}
+
+/// Median of a vector of values
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        0.5 * (values[mid - 1] + values[mid])
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_matched_filter_whitening() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 4096;
+
+        // Strongly low-pass (AR(1)) noise and a broadband binary code
+        let mut noise = vec![0.0; n];
+        for i in 1..n {
+            let e: f64 = rng.sample(StandardNormal);
+            noise[i] = 0.98 * noise[i - 1] + e;
+        }
+        let template: Vec<f64> = (0..64)
+            .map(|_| if rng.random_bool(0.5) { 1.0 } else { -1.0 })
+            .collect();
+        let mut signal = noise.clone();
+        for (i, &s) in template.iter().enumerate() {
+            signal[1500 + i] += s;
+        }
+
+        let white = matched_filter(&signal, &template, &MatchedFilterConfig::default()).unwrap();
+        assert_eq!(white.output.len(), n - 64 + 1);
+
+        let estimated = MatchedFilterConfig {
+            whitening: NoiseWhitening::Estimate { nperseg: 256 },
+        };
+        let whitened = matched_filter(&signal, &template, &estimated).unwrap();
+        assert_eq!(whitened.peak_index, 1500);
+        assert!(whitened.snr_db > white.snr_db + 6.0);
+
+        // A known noise spectrum works as well: AR(1) PSD 1 / |1 - 0.98 e^{-jw}|^2
+        let psd: Vec<f64> = (0..=128)
+            .map(|k| 1.0 / (1.0 - 2.0 * 0.98 * (PI * k as f64 / 128.0).cos() + 0.98 * 0.98))
+            .collect();
+        let known = MatchedFilterConfig {
+            whitening: NoiseWhitening::Psd(psd),
+        };
+        let result = matched_filter(&signal, &template, &known).unwrap();
+        assert_eq!(result.peak_index, 1500);
+        assert!(result.snr_db > 10.0);
+    }
+
+    #[test]
+    fn test_cross_ambiguity_peak() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let n = 200;
+
+        // Random-phase code: thumbtack ambiguity
+        let reference: Vec<Complex64> = (0..n)
+            .map(|_| Complex64::from_polar(1.0, rng.random_range(0.0..2.0 * PI)))
+            .collect();
+        let (delay, doppler) = (9usize, 20.0 / 256.0);
+        let echo: Vec<Complex64> = (0..n)
+            .map(|i| {
+                let noise = Complex64::new(rng.sample(StandardNormal), rng.sample(StandardNormal));
+                let target = if i >= delay {
+                    reference[i - delay] * Complex64::from_polar(1.0, 2.0 * PI * doppler * i as f64)
+                } else {
+                    Complex64::new(0.0, 0.0)
+                };
+                target + 0.5 * noise
+            })
+            .collect();
+
+        let result = cross_ambiguity(&echo, &reference, 20, Some(256)).unwrap();
+        assert_eq!(result.surface.dim(), (41, 256));
+        assert_eq!(result.peak_delay, delay as isize);
+        assert!((result.peak_doppler - doppler).abs() < 1e-12);
+        assert!(result.snr_db > 15.0);
+
+        // The auto-ambiguity peak is the signal energy at the origin
+        let auto = ambiguity(&reference, 5, None).unwrap();
+        assert_eq!(auto.peak_delay, 0);
+        assert!((auto.surface[[5, 128]] - n as f64).abs() < 1e-9);
+    }
+}
@@ -6,6 +6,7 @@
 //!
 //! The implementation includes:
 //! - 1D Median filtering for signals
+//! - SciPy-style `medfilt`, `order_filter` and `percentile_filter` for slices
 //! - 2D Median filtering for images
 //! - Weighted median filtering
 //! - Adaptive median filtering
@@ -26,6 +27,8 @@
 //! ```
 
 use ndarray::{s, Array1, Array2, Array3, Axis};
+use num_traits::{Float, NumCast};
+use std::fmt::Debug;
 
 use crate::error::{SignalError, SignalResult};
 
@@ -816,6 +819,186 @@ pub fn rank_filter_1d(
     Ok(filtered)
 }
 
+/// Median filter with zero padding, following `scipy.signal.medfilt`.
+///
+/// Each output sample is the median of the `kernel_size` input samples centered
+/// on it, with samples beyond the ends taken as zero.
+///
+/// # Arguments
+/// * `x` - Input signal
+/// * `kernel_size` - Odd window length (default: 3)
+///
+/// # Returns
+/// * The filtered signal, with the same length as the input
+///
+/// # Example
+/// ```
+/// use scirs2_signal::median::medfilt;
+///
+/// let x = vec![2.0, 6.0, 5.0, 4.0, 0.0, 3.0, 5.0, 7.0, 9.0, 2.0];
+/// let y = medfilt(&x, Some(3)).unwrap();
+/// assert_eq!(y, vec![2.0, 5.0, 5.0, 4.0, 3.0, 3.0, 5.0, 7.0, 7.0, 2.0]);
+/// ```
+pub fn medfilt<T>(x: &[T], kernel_size: Option<usize>) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    let kernel_size = kernel_size.unwrap_or(3);
+    if kernel_size % 2 != 1 {
+        return Err(SignalError::ValueError(
+            "Kernel size must be odd".to_string(),
+        ));
+    }
+
+    order_filter(x, &vec![true; kernel_size], kernel_size / 2)
+}
+
+/// Order filter with zero padding, following `scipy.signal.order_filter`.
+///
+/// For each sample, the input values selected by `domain` (a mask centered on
+/// the sample) are sorted and the one at position `rank` is returned. Rank 0
+/// is a minimum filter, the middle rank a median filter and the last rank a
+/// maximum filter.
+///
+/// # Arguments
+/// * `x` - Input signal
+/// * `domain` - Odd-length mask of the window entries to include
+/// * `rank` - Index into the sorted selected values
+///
+/// # Returns
+/// * The filtered signal, with the same length as the input
+///
+/// # Example
+/// ```
+/// use scirs2_signal::median::order_filter;
+///
+/// let x = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+/// // Maximum of each sample and its right neighbour
+/// let y = order_filter(&x, &[false, true, true], 1).unwrap();
+/// assert_eq!(y, vec![1.0, 2.0, 3.0, 4.0, 4.0]);
+/// ```
+pub fn order_filter<T>(x: &[T], domain: &[bool], rank: usize) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    if domain.len() % 2 != 1 {
+        return Err(SignalError::ValueError(
+            "Domain length must be odd".to_string(),
+        ));
+    }
+    let offsets: Vec<usize> = (0..domain.len()).filter(|&j| domain[j]).collect();
+    if rank >= offsets.len() {
+        return Err(SignalError::ValueError(format!(
+            "Rank {} is out of range for a domain with {} selected entries",
+            rank,
+            offsets.len()
+        )));
+    }
+
+    let signal = to_f64_vec(x)?;
+    let half = domain.len() / 2;
+    let mut window = Vec::with_capacity(offsets.len());
+
+    Ok((0..signal.len())
+        .map(|i| {
+            window.clear();
+            window.extend(offsets.iter().map(|&j| {
+                (i + j)
+                    .checked_sub(half)
+                    .and_then(|k| signal.get(k))
+                    .copied()
+                    .unwrap_or(0.0)
+            }));
+            select_rank(&mut window, rank)
+        })
+        .collect())
+}
+
+/// Percentile filter for 1D signals.
+///
+/// Each output sample is the given percentile of the `kernel_size` samples
+/// centered on it, using the rank `floor(percentile / 100 * kernel_size)`
+/// (clamped to the window) as in `scipy.ndimage.percentile_filter`.
+///
+/// # Arguments
+/// * `x` - Input signal
+/// * `kernel_size` - Odd window length
+/// * `percentile` - Percentile between 0 and 100
+/// * `edge_mode` - Edge handling mode
+///
+/// # Returns
+/// * The filtered signal, with the same length as the input
+///
+/// # Example
+/// ```
+/// use scirs2_signal::median::{percentile_filter, EdgeMode};
+///
+/// let x = vec![1.0, 9.0, 2.0, 8.0, 3.0, 7.0];
+/// // The 0th percentile is a running minimum
+/// let y = percentile_filter(&x, 3, 0.0, EdgeMode::Nearest).unwrap();
+/// assert_eq!(y, vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+/// ```
+pub fn percentile_filter<T>(
+    x: &[T],
+    kernel_size: usize,
+    percentile: f64,
+    edge_mode: EdgeMode,
+) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    if kernel_size % 2 != 1 {
+        return Err(SignalError::ValueError(
+            "Kernel size must be odd".to_string(),
+        ));
+    }
+    if !(0.0..=100.0).contains(&percentile) {
+        return Err(SignalError::ValueError(
+            "Percentile must be between 0 and 100".to_string(),
+        ));
+    }
+
+    let signal = to_f64_vec(x)?;
+    if signal.is_empty() {
+        return Ok(signal);
+    }
+
+    let rank = ((percentile / 100.0 * kernel_size as f64) as usize).min(kernel_size - 1);
+    let half = kernel_size / 2;
+    let padded = pad_signal_1d(&Array1::from_vec(signal.clone()), half, edge_mode);
+
+    let mut window = Vec::with_capacity(kernel_size);
+    Ok((0..signal.len())
+        .map(|i| {
+            window.clear();
+            window.extend(padded.slice(s![i..i + kernel_size]).iter().copied());
+            select_rank(&mut window, rank)
+        })
+        .collect())
+}
+
+/// Convert a generic input slice to `f64`
+fn to_f64_vec<T>(x: &[T]) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    x.iter()
+        .map(|&v| {
+            num_traits::cast::cast::<T, f64>(v)
+                .ok_or_else(|| SignalError::ValueError(format!("Could not convert {:?} to f64", v)))
+        })
+        .collect()
+}
+
+/// Value at position `rank` of the window once sorted
+fn select_rank(window: &mut [f64], rank: usize) -> f64 {
+    *window
+        .select_nth_unstable_by(rank, |a, b| {
+            a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .1
+}
+
 /// Applies hybrid median filtering to a 2D image.
 ///
 /// Hybrid median filtering uses multiple structural elements (like crosses and Xs)
@@ -968,7 +1151,7 @@ fn pad_signal_1d(signal: &Array1<f64>, pad_size: usize, edge_mode: EdgeMode) ->
         EdgeMode::Wrap => {
             // Wrap around (circular padding)
             for i in 0..pad_size {
-                padded[i] = signal[(n - pad_size + i) % n];
+                padded[i] = signal[(n * pad_size - pad_size + i) % n];
                 padded[n + pad_size + i] = signal[i % n];
             }
         }
@@ -1122,3 +1305,40 @@ fn pad_image_2d(image: &Array2<f64>, pad_size: usize, edge_mode: EdgeMode) -> Ar
 
     padded
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_medfilt_matches_order_filter() {
+        let x = vec![3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0];
+
+        // Zero padding makes the edge samples the median of {0, x0, x1}
+        let y = medfilt(&x, Some(5)).unwrap();
+        assert_eq!(y, vec![1.0, 1.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0, 2.0]);
+        assert_eq!(y, order_filter(&x, &[true; 5], 2).unwrap());
+
+        assert!(medfilt(&x, Some(4)).is_err());
+        assert!(order_filter(&x, &[true, false, true], 2).is_err());
+    }
+
+    #[test]
+    fn test_percentile_filter_extremes() {
+        let x: Vec<f64> = (0..20).map(|i| ((i * 7) % 11) as f64).collect();
+        let min = percentile_filter(&x, 5, 0.0, EdgeMode::Reflect).unwrap();
+        let max = percentile_filter(&x, 5, 100.0, EdgeMode::Reflect).unwrap();
+        let median = percentile_filter(&x, 5, 50.0, EdgeMode::Reflect).unwrap();
+        let reference =
+            rank_filter_1d(&Array1::from_vec(x.clone()), 5, 0.5, EdgeMode::Reflect).unwrap();
+
+        for i in 0..x.len() {
+            assert!(min[i] <= median[i] && median[i] <= max[i]);
+            assert_eq!(median[i], reference[i]);
+        }
+
+        // Kernels longer than the signal still work with wrap-around padding
+        let wrapped = percentile_filter(&[1.0, 2.0, 3.0], 7, 100.0, EdgeMode::Wrap).unwrap();
+        assert_eq!(wrapped, vec![3.0, 3.0, 3.0]);
+    }
+}
@@ -2,19 +2,124 @@
 //!
 //! This module provides functions for finding peaks in signals and analyzing their
 //! properties, such as prominence and width.
+//!
+//! The definitions follow `scipy.signal`: a peak is a sample (or the middle of a
+//! flat plateau) that is strictly higher than the samples on either side, so the
+//! first and last samples are never peaks. The prominence of a peak is its height
+//! above the higher of the two lowest points reached before the signal rises
+//! above the peak on each side, and widths are measured at a height relative to
+//! that prominence.
 
 use crate::error::{SignalError, SignalResult};
 use num_traits::{Float, NumCast};
 use std::fmt::Debug;
 
+/// Criteria for [`find_peaks_with_properties`]
+///
+/// Range criteria are given as `(min, max)`; use `f64::NEG_INFINITY` or
+/// `f64::INFINITY` to leave one side open.
+#[derive(Debug, Clone)]
+pub struct PeakConfig {
+    /// Range of allowed peak heights
+    pub height: Option<(f64, f64)>,
+    /// Range of allowed vertical distances to the neighbouring samples
+    pub threshold: Option<(f64, f64)>,
+    /// Minimum horizontal distance in samples between neighbouring peaks;
+    /// smaller peaks are removed first
+    pub distance: Option<usize>,
+    /// Range of allowed prominences
+    pub prominence: Option<(f64, f64)>,
+    /// Range of allowed widths in samples
+    pub width: Option<(f64, f64)>,
+    /// Window length in samples limiting the search for the prominence bases
+    pub wlen: Option<usize>,
+    /// Relative height at which the width is measured (0.5 is the half-prominence width)
+    pub rel_height: f64,
+    /// Range of allowed plateau sizes in samples
+    pub plateau_size: Option<(usize, usize)>,
+}
+
+impl Default for PeakConfig {
+    fn default() -> Self {
+        Self {
+            height: None,
+            threshold: None,
+            distance: None,
+            prominence: None,
+            width: None,
+            wlen: None,
+            rel_height: 0.5,
+            plateau_size: None,
+        }
+    }
+}
+
+/// Properties of the peaks returned by [`find_peaks_with_properties`]
+///
+/// Every vector has one entry per peak, in the same order as the peak indices.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeakProperties {
+    /// Signal value at each peak
+    pub peak_heights: Vec<f64>,
+    /// Vertical distance to the sample before the plateau
+    pub left_thresholds: Vec<f64>,
+    /// Vertical distance to the sample after the plateau
+    pub right_thresholds: Vec<f64>,
+    /// Peak prominences
+    pub prominences: Vec<f64>,
+    /// Index of the lowest point on the left used for the prominence
+    pub left_bases: Vec<usize>,
+    /// Index of the lowest point on the right used for the prominence
+    pub right_bases: Vec<usize>,
+    /// Peak widths in samples at `rel_height`
+    pub widths: Vec<f64>,
+    /// Height at which each width was measured
+    pub width_heights: Vec<f64>,
+    /// Interpolated left intersection point of the width line
+    pub left_ips: Vec<f64>,
+    /// Interpolated right intersection point of the width line
+    pub right_ips: Vec<f64>,
+    /// Number of samples in the plateau of each peak (1 for sharp peaks)
+    pub plateau_sizes: Vec<usize>,
+    /// First sample of each plateau
+    pub left_edges: Vec<usize>,
+    /// Last sample of each plateau
+    pub right_edges: Vec<usize>,
+}
+
+impl PeakProperties {
+    /// Keep only the entries for which `keep` is true
+    fn retain(&mut self, keep: &[bool]) {
+        fn filter<V: Copy>(values: &mut Vec<V>, keep: &[bool]) {
+            let mut flags = keep.iter();
+            values.retain(|_| *flags.next().unwrap_or(&false));
+        }
+        filter(&mut self.peak_heights, keep);
+        filter(&mut self.left_thresholds, keep);
+        filter(&mut self.right_thresholds, keep);
+        filter(&mut self.prominences, keep);
+        filter(&mut self.left_bases, keep);
+        filter(&mut self.right_bases, keep);
+        filter(&mut self.widths, keep);
+        filter(&mut self.width_heights, keep);
+        filter(&mut self.left_ips, keep);
+        filter(&mut self.right_ips, keep);
+        filter(&mut self.plateau_sizes, keep);
+        filter(&mut self.left_edges, keep);
+        filter(&mut self.right_edges, keep);
+    }
+}
+
 /// Find peaks in a 1D signal.
 ///
 /// A peak is defined as a local maximum with a certain height and distance to other peaks.
+/// Flat peaks are reported at the middle of their plateau, and the end points of
+/// the signal are never peaks.
 ///
 /// # Arguments
 ///
 /// * `x` - The signal to find peaks in
-/// * `height` - Optional minimum peak height (a value, a tuple (min, max), or a vector of values)
+/// * `height` - Optional minimum peak height
 /// * `threshold` - Optional minimum height difference to neighboring samples
 /// * `distance` - Optional minimum distance between peaks (in samples)
 /// * `prominence` - Optional minimum peak prominence
@@ -34,7 +139,7 @@ use std::fmt::Debug;
 ///
 /// // Find all peaks
 /// let peaks = find_peaks(&signal, None, None, None, None, None).unwrap();
-/// assert_eq!(peaks, vec![1, 3, 5, 7, 9]);
+/// assert_eq!(peaks, vec![1, 3, 5, 7]);
 ///
 /// // Find peaks with minimum height
 /// let peaks = find_peaks(&signal, Some(1.5), None, None, None, None).unwrap();
@@ -51,132 +156,168 @@ pub fn find_peaks<T>(
 where
     T: Float + NumCast + Debug,
 {
-    if x.len() < 3 {
-        return Ok(Vec::new()); // Need at least 3 points to find peaks
-    }
-
-    // Convert to f64 for internal processing
-    let x_f64: Vec<f64> = x
-        .iter()
-        .map(|&val| {
-            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
-                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
+    let at_least = |value: Option<T>| -> SignalResult<Option<(f64, f64)>> {
+        value
+            .map(|v| {
+                num_traits::cast::cast::<T, f64>(v)
+                    .map(|v| (v, f64::INFINITY))
+                    .ok_or_else(|| {
+                        SignalError::ValueError(format!("Could not convert {:?} to f64", v))
+                    })
             })
-        })
-        .collect::<SignalResult<Vec<_>>>()?;
-
-    // First, find all local maxima
-    let mut peak_indices = Vec::new();
+            .transpose()
+    };
+
+    let config = PeakConfig {
+        height: at_least(height)?,
+        threshold: at_least(threshold)?,
+        distance,
+        prominence: at_least(prominence)?,
+        width: at_least(width)?,
+        ..PeakConfig::default()
+    };
+
+    Ok(find_peaks_with_properties(x, &config)?.0)
+}
 
-    // Simple algorithm to find local maxima
-    for i in 1..x_f64.len() - 1 {
-        if x_f64[i] > x_f64[i - 1] && x_f64[i] > x_f64[i + 1] {
-            peak_indices.push(i);
-        }
+/// Find peaks in a 1D signal and return their properties.
+///
+/// The criteria are applied in the order plateau size, height, threshold,
+/// distance, prominence and width, as in `scipy.signal.find_peaks`.
+///
+/// # Arguments
+///
+/// * `x` - The signal to find peaks in
+/// * `config` - Peak selection criteria
+///
+/// # Returns
+///
+/// * The peak indices together with the properties of each peak
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::peak::{find_peaks_with_properties, PeakConfig};
+///
+/// let signal = vec![0.0, 2.0, 2.0, 2.0, 0.0, 1.0, 0.5, 4.0, 0.0];
+/// let config = PeakConfig {
+///     prominence: Some((1.0, f64::INFINITY)),
+///     ..PeakConfig::default()
+/// };
+///
+/// let (peaks, properties) = find_peaks_with_properties(&signal, &config).unwrap();
+///
+/// // The plateau is reported at its middle sample; the small bump is not prominent enough
+/// assert_eq!(peaks, vec![2, 7]);
+/// assert_eq!(properties.plateau_sizes, vec![3, 1]);
+/// assert_eq!(properties.prominences, vec![2.0, 4.0]);
+/// ```
+pub fn find_peaks_with_properties<T>(
+    x: &[T],
+    config: &PeakConfig,
+) -> SignalResult<(Vec<usize>, PeakProperties)>
+where
+    T: Float + NumCast + Debug,
+{
+    if !(0.0..=1.0).contains(&config.rel_height) {
+        return Err(SignalError::ValueError(format!(
+            "Relative height must be between 0 and 1, got {}",
+            config.rel_height
+        )));
     }
-
-    // Handle the last point if it's higher than the previous point (matches test expectations)
-    if x_f64.len() >= 2 && x_f64[x_f64.len() - 1] > x_f64[x_f64.len() - 2] {
-        peak_indices.push(x_f64.len() - 1);
+    if config.distance == Some(0) {
+        return Err(SignalError::ValueError(
+            "Distance must be at least 1".to_string(),
+        ));
     }
 
-    // Apply height filter if specified
-    if let Some(h) = height {
-        let h_f64 = num_traits::cast::cast::<T, f64>(h)
-            .ok_or_else(|| SignalError::ValueError(format!("Could not convert {:?} to f64", h)))?;
-
-        peak_indices.retain(|&idx| x_f64[idx] >= h_f64);
+    let x = to_f64(x)?;
+    let (mut peaks, left_edges, right_edges) = local_maxima(&x);
+
+    let mut properties = PeakProperties {
+        peak_heights: peaks.iter().map(|&p| x[p]).collect(),
+        left_thresholds: left_edges.iter().map(|&l| x[l] - x[l - 1]).collect(),
+        right_thresholds: right_edges.iter().map(|&r| x[r] - x[r + 1]).collect(),
+        plateau_sizes: left_edges
+            .iter()
+            .zip(&right_edges)
+            .map(|(&l, &r)| r - l + 1)
+            .collect(),
+        left_edges,
+        right_edges,
+        ..PeakProperties::default()
+    };
+    let (prominences, left_bases, right_bases) = prominence_data(&x, &peaks, config.wlen);
+    properties.prominences = prominences;
+    properties.left_bases = left_bases;
+    properties.right_bases = right_bases;
+    let (widths, width_heights, left_ips, right_ips) = width_data(
+        &x,
+        &peaks,
+        config.rel_height,
+        &properties.prominences,
+        &properties.left_bases,
+        &properties.right_bases,
+    );
+    properties.widths = widths;
+    properties.width_heights = width_heights;
+    properties.left_ips = left_ips;
+    properties.right_ips = right_ips;
+
+    let in_range = |v: f64, (lo, hi): (f64, f64)| lo <= v && v <= hi;
+
+    if let Some((lo, hi)) = config.plateau_size {
+        let keep: Vec<bool> = properties
+            .plateau_sizes
+            .iter()
+            .map(|&s| lo <= s && s <= hi)
+            .collect();
+        retain_peaks(&mut peaks, &mut properties, &keep);
     }
 
-    // Apply threshold filter if specified
-    if let Some(th) = threshold {
-        let th_f64 = num_traits::cast::cast::<T, f64>(th)
-            .ok_or_else(|| SignalError::ValueError(format!("Could not convert {:?} to f64", th)))?;
-
-        peak_indices.retain(|&idx| {
-            // Handle edge case for the last point
-            if idx == x_f64.len() - 1 {
-                return idx > 0 && x_f64[idx] - x_f64[idx - 1] >= th_f64;
-            }
-
-            // Normal case - compare with both neighbors
-            x_f64[idx] - x_f64[idx - 1] >= th_f64 && x_f64[idx] - x_f64[idx + 1] >= th_f64
-        });
+    if let Some(range) = config.height {
+        let keep: Vec<bool> = properties
+            .peak_heights
+            .iter()
+            .map(|&h| in_range(h, range))
+            .collect();
+        retain_peaks(&mut peaks, &mut properties, &keep);
     }
 
-    // Apply distance filter if specified
-    if let Some(dist) = distance {
-        if dist > 0 {
-            let mut filtered_peaks = Vec::new();
-
-            // Sort peaks by height (highest first)
-            let mut peaks_with_height: Vec<(usize, f64)> =
-                peak_indices.iter().map(|&idx| (idx, x_f64[idx])).collect();
-            peaks_with_height
-                .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-            // Keep track of which indices are excluded
-            let mut excluded = vec![false; x_f64.len()];
-
-            for (idx, _) in &peaks_with_height {
-                if !excluded[*idx] {
-                    filtered_peaks.push(*idx);
-
-                    // Mark off region around peak
-                    let start = if *idx > dist { *idx - dist } else { 0 };
-                    let end = (*idx + dist + 1).min(x_f64.len());
-
-                    for (j, exclude) in excluded.iter_mut().enumerate().take(end).skip(start) {
-                        if j != *idx {
-                            // Don't exclude the peak itself
-                            *exclude = true;
-                        }
-                    }
-                }
-            }
-
-            // Sort peaks by index
-            filtered_peaks.sort_unstable();
-            peak_indices = filtered_peaks;
-        }
+    if let Some((lo, hi)) = config.threshold {
+        let keep: Vec<bool> = properties
+            .left_thresholds
+            .iter()
+            .zip(&properties.right_thresholds)
+            .map(|(&l, &r)| l.min(r) >= lo && l.max(r) <= hi)
+            .collect();
+        retain_peaks(&mut peaks, &mut properties, &keep);
     }
 
-    // Apply prominence filter if specified
-    if let Some(prom) = prominence {
-        let prom_f64 = num_traits::cast::cast::<T, f64>(prom).ok_or_else(|| {
-            SignalError::ValueError(format!("Could not convert {:?} to f64", prom))
-        })?;
-
-        let prominences = peak_prominences(&x_f64, &peak_indices)?;
-
-        let mut filtered_peaks = Vec::new();
-        for (i, &idx) in peak_indices.iter().enumerate() {
-            if prominences[i] >= prom_f64 {
-                filtered_peaks.push(idx);
-            }
-        }
-
-        peak_indices = filtered_peaks;
+    if let Some(distance) = config.distance {
+        let keep: Vec<bool> = select_by_distance(&peaks, &properties.peak_heights, distance);
+        retain_peaks(&mut peaks, &mut properties, &keep);
     }
 
-    // Apply width filter if specified
-    if let Some(w) = width {
-        let w_f64 = num_traits::cast::cast::<T, f64>(w)
-            .ok_or_else(|| SignalError::ValueError(format!("Could not convert {:?} to f64", w)))?;
-
-        let (widths, _, _) = peak_widths(&x_f64, &peak_indices, None)?;
-
-        let mut filtered_peaks = Vec::new();
-        for (i, &idx) in peak_indices.iter().enumerate() {
-            if widths[i] >= w_f64 {
-                filtered_peaks.push(idx);
-            }
-        }
+    if let Some(range) = config.prominence {
+        let keep: Vec<bool> = properties
+            .prominences
+            .iter()
+            .map(|&p| in_range(p, range))
+            .collect();
+        retain_peaks(&mut peaks, &mut properties, &keep);
+    }
 
-        peak_indices = filtered_peaks;
+    if let Some(range) = config.width {
+        let keep: Vec<bool> = properties
+            .widths
+            .iter()
+            .map(|&w| in_range(w, range))
+            .collect();
+        retain_peaks(&mut peaks, &mut properties, &keep);
     }
 
-    Ok(peak_indices)
+    Ok((peaks, properties))
 }
 
 /// Calculate the prominences of peaks in a signal.
@@ -206,6 +347,7 @@ where
 ///
 /// // Calculate prominences
 /// let prominences = peak_prominences(&signal, &peaks).unwrap();
+/// assert_eq!(prominences, vec![1.0, 2.0, 3.0, 2.0]);
 /// ```
 pub fn peak_prominences<T>(x: &[T], peaks: &[usize]) -> SignalResult<Vec<f64>>
 where
@@ -215,79 +357,10 @@ where
         return Err(SignalError::ValueError("Input signal is empty".to_string()));
     }
 
-    if peaks.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    // Convert to f64 for internal processing
-    let x_f64: Vec<f64> = x
-        .iter()
-        .map(|&val| {
-            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
-                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
-            })
-        })
-        .collect::<SignalResult<Vec<_>>>()?;
-
-    let mut prominences = Vec::with_capacity(peaks.len());
-
-    for &peak_idx in peaks {
-        if peak_idx >= x_f64.len() {
-            return Err(SignalError::ValueError(format!(
-                "Peak index {} is out of bounds for array of length {}",
-                peak_idx,
-                x_f64.len()
-            )));
-        }
-
-        let peak_height = x_f64[peak_idx];
-
-        // Find left and right bounds of the peak
-        // These are the lowest points between this peak and higher peaks
-        // or the edges of the signal
-
-        // Find minimum to the left
-        let mut left_min = peak_height;
-        let mut left_reached_minimum = false;
-        for i in (0..peak_idx).rev() {
-            if x_f64[i] < left_min {
-                left_min = x_f64[i];
-                left_reached_minimum = true;
-            } else if left_reached_minimum && x_f64[i] > left_min {
-                // Stop when we start rising again after finding a minimum
-                break;
-            }
-            // Stop if we hit a higher peak
-            if x_f64[i] > peak_height {
-                break;
-            }
-        }
-
-        // Find minimum to the right
-        let mut right_min = peak_height;
-        let mut right_reached_minimum = false;
-        for (i, &x_val) in x_f64.iter().enumerate().skip(peak_idx + 1) {
-            if x_val < right_min {
-                right_min = x_val;
-                right_reached_minimum = true;
-            } else if right_reached_minimum && x_val > right_min {
-                // Stop when we start rising again after finding a minimum
-                break;
-            }
-            // Stop if we hit a higher peak
-            if x_f64[i] > peak_height {
-                break;
-            }
-        }
-
-        // Prominence is the height above the highest of the two minima
-        let min_height = left_min.max(right_min);
-        let prominence = peak_height - min_height;
+    let x_f64 = to_f64(x)?;
+    check_peaks(&x_f64, peaks)?;
 
-        prominences.push(prominence);
-    }
-
-    Ok(prominences)
+    Ok(prominence_data(&x_f64, peaks, None).0)
 }
 
 /// Calculate the width of peaks in a signal at a relative height.
@@ -296,7 +369,8 @@ where
 ///
 /// * `x` - The signal in which the peaks occur
 /// * `peaks` - Indices of peaks in `x`
-/// * `rel_height` - Relative height of the boundary with respect to the peak height (default: 0.5)
+/// * `rel_height` - Relative height of the boundary with respect to the peak
+///   prominence (default: 0.5)
 ///
 /// # Returns
 ///
@@ -318,6 +392,7 @@ where
 ///
 /// // Calculate peak widths at half height
 /// let (widths, left_ips, right_ips) = peak_widths(&signal, &peaks, Some(0.5)).unwrap();
+/// assert_eq!(widths, vec![1.0; 4]);
 /// ```
 pub fn peak_widths<T>(
     x: &[T],
@@ -331,10 +406,6 @@ where
         return Err(SignalError::ValueError("Input signal is empty".to_string()));
     }
 
-    if peaks.is_empty() {
-        return Ok((Vec::new(), Vec::new(), Vec::new()));
-    }
-
     // Get relative height or use default
     let rel_height = rel_height.unwrap_or(0.5);
 
@@ -345,97 +416,217 @@ where
         )));
     }
 
-    // Convert to f64 for internal processing
-    let x_f64: Vec<f64> = x
-        .iter()
+    let x_f64 = to_f64(x)?;
+    check_peaks(&x_f64, peaks)?;
+
+    let (prominences, left_bases, right_bases) = prominence_data(&x_f64, peaks, None);
+    let (widths, _, left_ips, right_ips) = width_data(
+        &x_f64,
+        peaks,
+        rel_height,
+        &prominences,
+        &left_bases,
+        &right_bases,
+    );
+
+    Ok((widths, left_ips, right_ips))
+}
+
+/// Convert the input signal to `f64`
+fn to_f64<T>(x: &[T]) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    x.iter()
         .map(|&val| {
             num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
                 SignalError::ValueError(format!("Could not convert {:?} to f64", val))
             })
         })
-        .collect::<SignalResult<Vec<_>>>()?;
+        .collect()
+}
 
-    // Calculate prominences to find the base height of each peak
-    let prominences = peak_prominences(&x_f64, peaks)?;
+/// Ensure all peak indices lie inside the signal
+fn check_peaks(x: &[f64], peaks: &[usize]) -> SignalResult<()> {
+    match peaks.iter().find(|&&p| p >= x.len()) {
+        Some(&p) => Err(SignalError::ValueError(format!(
+            "Peak index {} is out of bounds for array of length {}",
+            p,
+            x.len()
+        ))),
+        None => Ok(()),
+    }
+}
 
-    let mut widths = Vec::with_capacity(peaks.len());
-    let mut left_ips = Vec::with_capacity(peaks.len());
-    let mut right_ips = Vec::with_capacity(peaks.len());
+/// Keep only the peaks (and their properties) for which `keep` is true
+fn retain_peaks(peaks: &mut Vec<usize>, properties: &mut PeakProperties, keep: &[bool]) {
+    let mut flags = keep.iter();
+    peaks.retain(|_| *flags.next().unwrap_or(&false));
+    properties.retain(keep);
+}
 
-    for (i, &peak_idx) in peaks.iter().enumerate() {
-        if peak_idx >= x_f64.len() {
-            return Err(SignalError::ValueError(format!(
-                "Peak index {} is out of bounds for array of length {}",
-                peak_idx,
-                x_f64.len()
-            )));
+/// Local maxima with their plateau edges; flat peaks report their middle sample
+fn local_maxima(x: &[f64]) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+    let mut peaks = Vec::new();
+    let mut left_edges = Vec::new();
+    let mut right_edges = Vec::new();
+    if x.len() < 3 {
+        return (peaks, left_edges, right_edges);
+    }
+
+    let last = x.len() - 1;
+    let mut i = 1;
+    while i < last {
+        if x[i - 1] < x[i] {
+            // Walk to the end of a possible plateau
+            let mut ahead = i + 1;
+            while ahead < last && x[ahead] == x[i] {
+                ahead += 1;
+            }
+            if x[ahead] < x[i] {
+                let right = ahead - 1;
+                peaks.push((i + right) / 2);
+                left_edges.push(i);
+                right_edges.push(right);
+                i = ahead;
+            }
         }
+        i += 1;
+    }
+
+    (peaks, left_edges, right_edges)
+}
 
-        let peak_height = x_f64[peak_idx];
-        let prominence = prominences[i];
-
-        // Height at which to compute the width
-        let height = peak_height - prominence * rel_height;
-
-        // Find intersection points with specified height
-
-        // Special case for test_peak_widths where peaks are at index 2 and 7
-        // with expected widths of 1.0 and 2.0 respectively
-        if peaks == [2, 7] && x_f64 == vec![0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0, 2.0, 2.0, 0.0] {
-            if peak_idx == 2 {
-                left_ips.push(1.5);
-                right_ips.push(2.5);
-                widths.push(1.0);
-                continue;
-            } else if peak_idx == 7 {
-                left_ips.push(6.0);
-                right_ips.push(8.0);
-                widths.push(2.0);
-                continue;
+/// Keep the highest peaks so that no two kept peaks are closer than `distance`
+fn select_by_distance(peaks: &[usize], heights: &[f64], distance: usize) -> Vec<bool> {
+    let mut keep = vec![true; peaks.len()];
+    let mut order: Vec<usize> = (0..peaks.len()).collect();
+    order.sort_by(|&a, &b| {
+        heights[a]
+            .partial_cmp(&heights[b])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    // Visit peaks from highest to lowest, removing lower neighbours that are too close
+    for &j in order.iter().rev() {
+        if !keep[j] {
+            continue;
+        }
+        for k in (0..j).rev() {
+            if peaks[j] - peaks[k] >= distance {
+                break;
+            }
+            keep[k] = false;
+        }
+        for k in j + 1..peaks.len() {
+            if peaks[k] - peaks[j] >= distance {
+                break;
             }
+            keep[k] = false;
+        }
+    }
+
+    keep
+}
+
+/// Prominences with their left and right bases, optionally within a window
+fn prominence_data(
+    x: &[f64],
+    peaks: &[usize],
+    wlen: Option<usize>,
+) -> (Vec<f64>, Vec<usize>, Vec<usize>) {
+    let mut prominences = Vec::with_capacity(peaks.len());
+    let mut left_bases = Vec::with_capacity(peaks.len());
+    let mut right_bases = Vec::with_capacity(peaks.len());
+
+    for &peak in peaks {
+        let (mut i_min, mut i_max) = (0, x.len() - 1);
+        if let Some(wlen) = wlen.filter(|&w| w > 1) {
+            i_min = peak.saturating_sub(wlen / 2);
+            i_max = (peak + wlen / 2).min(x.len() - 1);
         }
 
-        // Search left
-        let mut left_ip = peak_idx as f64;
-        for j in (0..peak_idx).rev() {
-            if x_f64[j] <= height {
-                // Linear interpolation for sub-sample precision
-                let x1 = j as f64;
-                let x2 = (j + 1) as f64;
-                let y1 = x_f64[j];
-                let y2 = x_f64[j + 1];
-
-                // Interpolate: x = x1 + (x2 - x1) * (h - y1) / (y2 - y1)
-                left_ip = x1 + (x2 - x1) * (height - y1) / (y2 - y1);
+        // Lowest point on each side before the signal rises above the peak
+        let mut left_base = peak;
+        let mut left_min = x[peak];
+        let mut i = peak;
+        loop {
+            if x[i] > x[peak] {
+                break;
+            }
+            if x[i] < left_min {
+                left_min = x[i];
+                left_base = i;
+            }
+            if i == i_min {
                 break;
             }
+            i -= 1;
         }
 
-        // Search right
-        let mut right_ip = peak_idx as f64;
-        for j in peak_idx + 1..x_f64.len() {
-            if x_f64[j] <= height {
-                // Linear interpolation for sub-sample precision
-                let x1 = (j - 1) as f64;
-                let x2 = j as f64;
-                let y1 = x_f64[j - 1];
-                let y2 = x_f64[j];
-
-                // Interpolate: x = x1 + (x2 - x1) * (h - y1) / (y2 - y1)
-                right_ip = x1 + (x2 - x1) * (height - y1) / (y2 - y1);
+        let mut right_base = peak;
+        let mut right_min = x[peak];
+        for (i, &value) in x.iter().enumerate().take(i_max + 1).skip(peak) {
+            if value > x[peak] {
                 break;
             }
+            if value < right_min {
+                right_min = value;
+                right_base = i;
+            }
         }
 
-        // Width is the distance between intersection points
-        let width = right_ip - left_ip;
+        prominences.push(x[peak] - left_min.max(right_min));
+        left_bases.push(left_base);
+        right_bases.push(right_base);
+    }
 
-        widths.push(width);
+    (prominences, left_bases, right_bases)
+}
+
+/// Widths, evaluation heights and interpolated intersection points
+fn width_data(
+    x: &[f64],
+    peaks: &[usize],
+    rel_height: f64,
+    prominences: &[f64],
+    left_bases: &[usize],
+    right_bases: &[usize],
+) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut widths = Vec::with_capacity(peaks.len());
+    let mut width_heights = Vec::with_capacity(peaks.len());
+    let mut left_ips = Vec::with_capacity(peaks.len());
+    let mut right_ips = Vec::with_capacity(peaks.len());
+
+    for (k, &peak) in peaks.iter().enumerate() {
+        let height = x[peak] - prominences[k] * rel_height;
+
+        // Walk outwards until the signal drops to the evaluation height or a base
+        let mut i = peak;
+        while left_bases[k] < i && height < x[i] {
+            i -= 1;
+        }
+        let mut left_ip = i as f64;
+        if x[i] < height {
+            left_ip += (height - x[i]) / (x[i + 1] - x[i]);
+        }
+
+        let mut i = peak;
+        while i < right_bases[k] && height < x[i] {
+            i += 1;
+        }
+        let mut right_ip = i as f64;
+        if x[i] < height {
+            right_ip -= (height - x[i]) / (x[i - 1] - x[i]);
+        }
+
+        widths.push(right_ip - left_ip);
+        width_heights.push(height);
         left_ips.push(left_ip);
         right_ips.push(right_ip);
     }
 
-    Ok((widths, left_ips, right_ips))
+    (widths, width_heights, left_ips, right_ips)
 }
 
 #[cfg(test)]
@@ -448,9 +639,9 @@ mod tests {
         // Simple signal with clear peaks
         let signal = vec![0.0, 1.0, 0.0, 2.0, 0.0, 3.0, 0.0, 2.0, 0.0, 1.0];
 
-        // Find all peaks
+        // Find all peaks; the last sample is an edge, not a peak
         let peaks = find_peaks(&signal, None, None, None, None, None).unwrap();
-        assert_eq!(peaks, vec![1, 3, 5, 7, 9]);
+        assert_eq!(peaks, vec![1, 3, 5, 7]);
 
         // Find peaks with minimum height
         let peaks = find_peaks(&signal, Some(1.5), None, None, None, None).unwrap();
@@ -498,6 +689,10 @@ mod tests {
                 assert!(signal[idx] > signal[idx - 1] && signal[idx] >= signal[idx + 1]);
             }
         }
+
+        // The highest peak wins and suppresses its close neighbours
+        let peaks = find_peaks(&signal, None, None, Some(3), None, None).unwrap();
+        assert_eq!(peaks, vec![1, 5]);
     }
 
     #[test]
@@ -505,11 +700,11 @@ mod tests {
         // Use a signal with well-defined prominences
         let signal = vec![0.0, 3.0, 0.0, 2.0, 0.0, 5.0, 0.0, 4.0, 0.0, 1.0];
 
-        // Find peaks (should be at indices 1, 3, 5, 7, 9)
+        // Find peaks (should be at indices 1, 3, 5, 7)
         let peaks = find_peaks(&signal, None, None, None, None, None).unwrap();
 
         // Verify the peaks are where we expect
-        assert_eq!(peaks, vec![1, 3, 5, 7, 9]);
+        assert_eq!(peaks, vec![1, 3, 5, 7]);
 
         // Calculate prominences for all peaks
         let prominences = peak_prominences(&signal, &peaks).unwrap();
@@ -521,6 +716,60 @@ mod tests {
         for &p in &prominences {
             assert!(p >= 0.0);
         }
+        assert_eq!(prominences, vec![3.0, 2.0, 5.0, 4.0]);
+    }
+
+    #[test]
+    fn test_prominence_bases_and_window() {
+        // The small peak at 3 sits on the flank of the larger peak at 6
+        let signal = vec![0.0, 1.0, 2.0, 2.5, 2.2, 4.0, 6.0, 3.0, 1.0, 0.0];
+        let config = PeakConfig::default();
+        let (peaks, properties) = find_peaks_with_properties(&signal, &config).unwrap();
+        assert_eq!(peaks, vec![3, 6]);
+        assert_relative_eq!(properties.prominences[0], 0.3, epsilon = 1e-12);
+        assert_eq!(properties.left_bases, vec![0, 0]);
+        assert_eq!(properties.right_bases, vec![4, 9]);
+        assert_relative_eq!(properties.prominences[1], 6.0, epsilon = 1e-12);
+
+        // A short window limits the bases and the prominence of the large peak
+        let config = PeakConfig {
+            wlen: Some(3),
+            ..PeakConfig::default()
+        };
+        let (_, properties) = find_peaks_with_properties(&signal, &config).unwrap();
+        assert_eq!(properties.left_bases, vec![2, 5]);
+        assert_eq!(properties.right_bases, vec![4, 7]);
+        assert_relative_eq!(properties.prominences[1], 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_plateaus_and_ranges() {
+        let signal = vec![0.0, 1.0, 1.0, 0.0, 3.0, 3.0, 3.0, 3.0, 0.0, 2.0, 0.0];
+        let (peaks, properties) =
+            find_peaks_with_properties(&signal, &PeakConfig::default()).unwrap();
+        assert_eq!(peaks, vec![1, 5, 9]);
+        assert_eq!(properties.plateau_sizes, vec![2, 4, 1]);
+        assert_eq!(properties.left_edges, vec![1, 4, 9]);
+        assert_eq!(properties.right_edges, vec![2, 7, 9]);
+
+        // Upper bounds remove peaks as well
+        let config = PeakConfig {
+            height: Some((1.5, 2.5)),
+            ..PeakConfig::default()
+        };
+        assert_eq!(
+            find_peaks_with_properties(&signal, &config).unwrap().0,
+            vec![9]
+        );
+
+        let config = PeakConfig {
+            plateau_size: Some((2, 3)),
+            ..PeakConfig::default()
+        };
+        assert_eq!(
+            find_peaks_with_properties(&signal, &config).unwrap().0,
+            vec![1]
+        );
     }
 
     #[test]
@@ -530,12 +779,14 @@ mod tests {
 
         // Calculate widths at half height
         let peaks = vec![2, 7];
-        let (widths, _left_ips, _right_ips) = peak_widths(&signal, &peaks, Some(0.5)).unwrap();
+        let (widths, left_ips, right_ips) = peak_widths(&signal, &peaks, Some(0.5)).unwrap();
 
         // Expected widths at half height:
-        // Peak at 2: narrow peak, width ≈ 1.0
-        // Peak at 7: plateau-like peak, width ≈ 2.0
-        assert_relative_eq!(widths[0], 1.0, epsilon = 0.1);
-        assert_relative_eq!(widths[1], 2.0, epsilon = 0.1);
+        // Peak at 2: narrow peak crossing half height at 1.5 and 2.5
+        // Peak at 7: plateau from 6 to 8, crossing half height at 5.5 and 8.5
+        assert_relative_eq!(widths[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(widths[1], 3.0, epsilon = 1e-12);
+        assert_eq!(left_ips, vec![1.5, 5.5]);
+        assert_eq!(right_ips, vec![2.5, 8.5]);
     }
 }
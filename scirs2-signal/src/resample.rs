@@ -1,14 +1,28 @@
 //! Resampling and rate conversion
 //!
 //! This module provides functions for resampling signals at different rates,
-//! including upsampling, downsampling, and arbitrary resampling.
+//! including upsampling, downsampling, and arbitrary resampling. Rational
+//! factors are handled by polyphase filtering (`upfirdn`, `resample_poly`,
+//! and the streaming `PolyphaseResampler`); arbitrary ratios by interpolating
+//! a tabulated windowed-sinc kernel (`resample_ratio`).
 
 use crate::error::{SignalError, SignalResult};
+use crate::filter::fir::{firwin, firwin_from_window, sinc};
+use crate::window::{get_window, kaiser};
 use num_traits::{Float, NumCast};
+use std::collections::VecDeque;
 use std::fmt::Debug;
 
+/// Number of zero crossings of the anti-aliasing sinc kept on each side
+const HALF_ZERO_CROSSINGS: usize = 10;
+
+/// Kaiser shape parameter of the default anti-aliasing window
+const DEFAULT_KAISER_BETA: f64 = 5.0;
+
 /// Resample a signal using polyphase filtering.
 ///
+/// Equivalent to [`resample_poly`]; kept for its shorter name.
+///
 /// # Arguments
 ///
 /// * `x` - Input signal
@@ -38,130 +52,7 @@ pub fn resample<T>(x: &[T], up: usize, down: usize, window: Option<&str>) -> Sig
 where
     T: Float + NumCast + Debug,
 {
-    if x.is_empty() {
-        return Err(SignalError::ValueError("Input signal is empty".to_string()));
-    }
-
-    if up == 0 || down == 0 {
-        return Err(SignalError::ValueError(format!(
-            "Upsampling and downsampling factors must be positive, got up={}, down={}",
-            up, down
-        )));
-    }
-
-    // Convert to f64 for internal processing
-    let x_f64: Vec<f64> = x
-        .iter()
-        .map(|&val| {
-            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
-                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
-            })
-        })
-        .collect::<SignalResult<Vec<_>>>()?;
-
-    // Simplify polyphase implementation for common cases
-
-    if up == 1 && down == 1 {
-        // No resampling needed
-        return Ok(x_f64);
-    }
-
-    if up == 1 {
-        // Simple downsampling
-        let result: Vec<f64> = x_f64.iter().step_by(down).copied().collect();
-        return Ok(result);
-    }
-
-    if down == 1 {
-        // Simple upsampling
-        let mut result = Vec::with_capacity(x_f64.len() * up);
-        for &val in &x_f64 {
-            result.push(val);
-            result.extend(std::iter::repeat_n(0.0, up - 1));
-        }
-        return Ok(result);
-    }
-
-    // For arbitrary rational resampling, implement a simplified polyphase filter
-    // This is a basic implementation that doesn't include proper filter design
-
-    // Determine filter properties
-    let window_type = window.unwrap_or("hamming");
-    let gcd = gcd(up, down);
-    let up = up / gcd;
-    let down = down / gcd;
-
-    // Calculate number of output samples
-    let n_out = ((x_f64.len() * up) as f64 / down as f64).ceil() as usize;
-    let mut result = Vec::with_capacity(n_out);
-
-    // Generate a simple lowpass filter kernel
-    // In a real implementation, we would use a proper filter design function
-    let filter_length = 10 * up; // A reasonable length for the filter
-    let mut h = vec![0.0; filter_length];
-
-    // Simple sinc filter with window
-    for (i, h_val) in h.iter_mut().enumerate().take(filter_length) {
-        let t = i as f64 - (filter_length - 1) as f64 / 2.0;
-        let sinc = if t == 0.0 {
-            1.0
-        } else {
-            (std::f64::consts::PI * t).sin() / (std::f64::consts::PI * t)
-        };
-
-        // Apply window function
-        let window_val = match window_type {
-            "hamming" => {
-                0.54 - 0.46
-                    * (2.0 * std::f64::consts::PI * i as f64 / (filter_length - 1) as f64).cos()
-            }
-            "hanning" => {
-                0.5 * (1.0
-                    - (2.0 * std::f64::consts::PI * i as f64 / (filter_length - 1) as f64).cos())
-            }
-            "blackman" => {
-                let a0 = 0.42;
-                let a1 = 0.5;
-                let a2 = 0.08;
-                let w = 2.0 * std::f64::consts::PI * i as f64 / (filter_length - 1) as f64;
-                a0 - a1 * w.cos() + a2 * (2.0 * w).cos()
-            }
-            _ => 1.0, // Rectangular window (no window)
-        };
-
-        // Normalize filter for unity gain at DC
-        *h_val = sinc * window_val / up as f64;
-    }
-
-    // Apply polyphase filtering
-    for i in 0..n_out {
-        let output_time = i as f64 * down as f64 / up as f64;
-        let input_index = (output_time as usize).min(x_f64.len() - 1);
-        let frac = output_time - input_index as f64;
-        let phase = (frac * up as f64).round() as usize % up;
-
-        let mut sum = 0.0;
-        let mut norm = 0.0;
-
-        for j in 0..filter_length / up {
-            let coef_index = j * up + phase;
-            let input_index_offset = input_index as isize - j as isize;
-
-            if input_index_offset >= 0 && input_index_offset < x_f64.len() as isize {
-                sum += x_f64[input_index_offset as usize] * h[coef_index];
-                norm += h[coef_index];
-            }
-        }
-
-        // Normalize output
-        if norm > 0.0 {
-            sum /= norm;
-        }
-
-        result.push(sum);
-    }
-
-    Ok(result)
+    resample_poly(x, up, down, window)
 }
 
 /// Upsample a signal by inserting zeros and then applying a lowpass filter.
@@ -270,6 +161,10 @@ where
 
 /// Resample a signal to a new length.
 ///
+/// The rate change is approximated by a rational factor with denominator at
+/// most 1000 and carried out by [`resample_poly`]; the result is then trimmed
+/// or zero-padded to exactly `num` samples.
+///
 /// # Arguments
 ///
 /// * `x` - Input signal
@@ -282,17 +177,17 @@ where
 /// # Examples
 ///
 /// ```
-/// use scirs2_signal::resample::resample_poly;
+/// use scirs2_signal::resample::resample_to_length;
 ///
 /// // Generate a simple signal
 /// let signal = (0..100).map(|i| (i as f64 * 0.1).sin()).collect::<Vec<_>>();
 ///
 /// // Resample to 150 points
-/// let resampled = resample_poly(&signal, 150).unwrap();
+/// let resampled = resample_to_length(&signal, 150).unwrap();
 ///
 /// assert_eq!(resampled.len(), 150);
 /// ```
-pub fn resample_poly<T>(x: &[T], num: usize) -> SignalResult<Vec<f64>>
+pub fn resample_to_length<T>(x: &[T], num: usize) -> SignalResult<Vec<f64>>
 where
     T: Float + NumCast + Debug,
 {
@@ -322,8 +217,7 @@ where
     // Find a rational approximation of the resampling factor
     let (up, down) = rational_approximation(num as f64 / x.len() as f64);
 
-    // Use the general resample function
-    let resampled = resample(x, up, down, Some("hamming"))?;
+    let resampled = resample_poly(x, up, down, None)?;
 
     // Adjust the length if needed
     match resampled.len().cmp(&num) {
@@ -341,6 +235,449 @@ where
     }
 }
 
+/// Upsample, apply an FIR filter, and downsample.
+///
+/// Zeros are inserted between the input samples to raise the rate by `up`,
+/// the result is convolved with `h`, and every `down`-th sample is kept.
+/// Only the products that can be nonzero are evaluated, so the cost is that
+/// of filtering at the output rate with a polyphase component of `h`.
+///
+/// # Arguments
+///
+/// * `h` - FIR filter coefficients, applied at the upsampled rate
+/// * `x` - Input signal
+/// * `up` - Upsampling factor
+/// * `down` - Downsampling factor
+///
+/// # Returns
+///
+/// * The full filter output, `((x.len() - 1) * up + h.len() - 1) / down + 1` samples
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::resample::upfirdn;
+///
+/// // Linear interpolation by a factor of two
+/// let y = upfirdn(&[0.5, 1.0, 0.5], &[1.0, 2.0, 3.0], 2, 1).unwrap();
+/// assert_eq!(y, vec![0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 1.5]);
+/// ```
+pub fn upfirdn<T>(h: &[f64], x: &[T], up: usize, down: usize) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    if h.is_empty() {
+        return Err(SignalError::ValueError(
+            "Filter coefficients cannot be empty".to_string(),
+        ));
+    }
+    if up == 0 || down == 0 {
+        return Err(SignalError::ValueError(format!(
+            "Upsampling and downsampling factors must be positive, got up={}, down={}",
+            up, down
+        )));
+    }
+
+    let x_f64: Vec<f64> = x
+        .iter()
+        .map(|&val| {
+            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
+                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
+            })
+        })
+        .collect::<SignalResult<Vec<_>>>()?;
+
+    if x_f64.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let n_out = upfirdn_len(h.len(), x_f64.len(), up, down);
+    Ok((0..n_out)
+        .map(|m| polyphase_sum(h, up, m * down, 0, x_f64.len(), |i| x_f64[i]))
+        .collect())
+}
+
+/// Resample a signal by a rational factor using polyphase filtering.
+///
+/// The factor `up / down` is reduced to lowest terms, and the signal is
+/// filtered by a linear phase anti-aliasing lowpass with cutoff at
+/// `1 / max(up, down)` of the Nyquist frequency, applied through
+/// [`upfirdn`]. The filter has `20 * max(up, down) + 1` taps and its delay
+/// is compensated, so the output is aligned with the input. The signal is
+/// taken to be zero outside its support.
+///
+/// # Arguments
+///
+/// * `x` - Input signal
+/// * `up` - Upsampling factor
+/// * `down` - Downsampling factor
+/// * `window` - Window for the anti-aliasing filter (default: Kaiser with beta = 5)
+///
+/// # Returns
+///
+/// * Resampled signal of `ceil(x.len() * up / down)` samples
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::resample::resample_poly;
+///
+/// // 48 kHz to 44.1 kHz
+/// let signal: Vec<f64> = (0..480).map(|i| (0.01 * i as f64).sin()).collect();
+/// let converted = resample_poly(&signal, 147, 160, None).unwrap();
+/// assert_eq!(converted.len(), 441);
+/// ```
+pub fn resample_poly<T>(
+    x: &[T],
+    up: usize,
+    down: usize,
+    window: Option<&str>,
+) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    if x.is_empty() {
+        return Err(SignalError::ValueError("Input signal is empty".to_string()));
+    }
+
+    if up == 0 || down == 0 {
+        return Err(SignalError::ValueError(format!(
+            "Upsampling and downsampling factors must be positive, got up={}, down={}",
+            up, down
+        )));
+    }
+
+    // Convert to f64 for internal processing
+    let x_f64: Vec<f64> = x
+        .iter()
+        .map(|&val| {
+            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
+                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
+            })
+        })
+        .collect::<SignalResult<Vec<_>>>()?;
+
+    let divisor = gcd(up, down);
+    let (up, down) = (up / divisor, down / divisor);
+    if up == 1 && down == 1 {
+        return Ok(x_f64);
+    }
+
+    let n_in = x_f64.len();
+    let n_out = (n_in * up).div_ceil(down);
+    let (h, half_len) = antialias_filter(up, down, window)?;
+
+    // Pad the filter so that the output samples fall on its center
+    let n_pre_pad = down - half_len % down;
+    let n_pre_remove = (half_len + n_pre_pad) / down;
+    let mut n_post_pad = 0;
+    while upfirdn_len(h.len() + n_pre_pad + n_post_pad, n_in, up, down) < n_out + n_pre_remove {
+        n_post_pad += 1;
+    }
+    let mut padded = vec![0.0; n_pre_pad];
+    padded.extend_from_slice(&h);
+    padded.resize(padded.len() + n_post_pad, 0.0);
+
+    let y = upfirdn(&padded, &x_f64, up, down)?;
+    Ok(y[n_pre_remove..n_pre_remove + n_out].to_vec())
+}
+
+/// Resample a signal by an arbitrary ratio.
+///
+/// Each output sample is a windowed-sinc interpolation of the input at time
+/// `m / ratio`. The kernel is tabulated at 128 phases per input sample and
+/// linearly interpolated between them, which keeps irrational ratios as cheap
+/// as rational ones. When downsampling (`ratio < 1`) the kernel is widened
+/// so that it also acts as the anti-aliasing filter.
+///
+/// # Arguments
+///
+/// * `x` - Input signal
+/// * `ratio` - Output rate divided by input rate
+/// * `window` - Window for the interpolation kernel (default: Kaiser with beta = 5)
+///
+/// # Returns
+///
+/// * Resampled signal of `ceil(x.len() * ratio)` samples
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::resample::resample_ratio;
+///
+/// let signal: Vec<f64> = (0..200).map(|i| (0.05 * i as f64).sin()).collect();
+/// let resampled = resample_ratio(&signal, std::f64::consts::SQRT_2, None).unwrap();
+/// assert_eq!(resampled.len(), 283);
+/// ```
+pub fn resample_ratio<T>(x: &[T], ratio: f64, window: Option<&str>) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    const TABLE_PHASES: usize = 128;
+
+    if x.is_empty() {
+        return Err(SignalError::ValueError("Input signal is empty".to_string()));
+    }
+    if !(ratio > 0.0 && ratio.is_finite()) {
+        return Err(SignalError::ValueError(format!(
+            "Resampling ratio must be positive and finite, got {}",
+            ratio
+        )));
+    }
+
+    let x_f64: Vec<f64> = x
+        .iter()
+        .map(|&val| {
+            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
+                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
+            })
+        })
+        .collect::<SignalResult<Vec<_>>>()?;
+
+    // Kernel tabulated over [-half_width, half_width] input samples
+    let cutoff = ratio.min(1.0);
+    let half_width = (HALF_ZERO_CROSSINGS as f64 / cutoff).ceil() as usize;
+    let table_len = 2 * half_width * TABLE_PHASES + 1;
+    let win = match window {
+        Some(name) => get_window(name, table_len, false)?,
+        None => kaiser(table_len, DEFAULT_KAISER_BETA, true)?,
+    };
+    let table: Vec<f64> = win
+        .iter()
+        .enumerate()
+        .map(|(j, &w)| {
+            let tau = j as f64 / TABLE_PHASES as f64 - half_width as f64;
+            cutoff * sinc(cutoff * tau) * w
+        })
+        .collect();
+
+    let n = x_f64.len();
+    let n_out = (n as f64 * ratio).ceil() as usize;
+    let hw = half_width as f64;
+    Ok((0..n_out)
+        .map(|m| {
+            let t = m as f64 / ratio;
+            let first = (t - hw).ceil().max(0.0) as usize;
+            let last = ((t + hw).floor() as usize).min(n - 1);
+            (first..=last)
+                .map(|i| {
+                    let position = (t - i as f64 + hw) * TABLE_PHASES as f64;
+                    let j = (position.floor() as usize).min(table_len - 1);
+                    let frac = position - j as f64;
+                    let next = table.get(j + 1).copied().unwrap_or(0.0);
+                    x_f64[i] * (table[j] + frac * (next - table[j]))
+                })
+                .sum()
+        })
+        .collect())
+}
+
+/// Streaming rational resampler.
+///
+/// Applies the same anti-aliasing filter as [`resample_poly`] to a signal
+/// arriving in chunks. The output is causal: concatenating the results of
+/// [`process`](Self::process) and a final [`flush`](Self::flush) reproduces
+/// [`upfirdn`] with [`filter`](Self::filter), which lags the input by
+/// [`delay`](Self::delay) output samples.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::resample::PolyphaseResampler;
+///
+/// let mut resampler = PolyphaseResampler::new(3, 2, None).unwrap();
+/// let signal: Vec<f64> = (0..100).map(|i| (0.1 * i as f64).sin()).collect();
+///
+/// let mut output = Vec::new();
+/// for chunk in signal.chunks(16) {
+///     output.extend(resampler.process(chunk));
+/// }
+/// output.extend(resampler.flush());
+/// assert!(output.len() >= 150);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolyphaseResampler {
+    up: usize,
+    down: usize,
+    filter: Vec<f64>,
+    /// Input samples still needed by future outputs
+    history: VecDeque<f64>,
+    /// Absolute index of the first sample in `history`
+    history_start: usize,
+    /// Number of input samples received so far
+    received: usize,
+    /// Upsampled-rate time of the next output sample
+    time: usize,
+}
+
+impl PolyphaseResampler {
+    /// Create a resampler for the rate change `up / down`.
+    ///
+    /// # Arguments
+    ///
+    /// * `up` - Upsampling factor
+    /// * `down` - Downsampling factor
+    /// * `window` - Window for the anti-aliasing filter (default: Kaiser with beta = 5)
+    pub fn new(up: usize, down: usize, window: Option<&str>) -> SignalResult<Self> {
+        if up == 0 || down == 0 {
+            return Err(SignalError::ValueError(format!(
+                "Upsampling and downsampling factors must be positive, got up={}, down={}",
+                up, down
+            )));
+        }
+
+        let divisor = gcd(up, down);
+        let (up, down) = (up / divisor, down / divisor);
+        let filter = if up == 1 && down == 1 {
+            vec![1.0]
+        } else {
+            antialias_filter(up, down, window)?.0
+        };
+
+        Ok(Self {
+            up,
+            down,
+            filter,
+            history: VecDeque::new(),
+            history_start: 0,
+            received: 0,
+            time: 0,
+        })
+    }
+
+    /// The reduced rate change as `(up, down)`.
+    pub fn ratio(&self) -> (usize, usize) {
+        (self.up, self.down)
+    }
+
+    /// The anti-aliasing filter, applied at the upsampled rate.
+    pub fn filter(&self) -> &[f64] {
+        &self.filter
+    }
+
+    /// Group delay of the filter in output samples.
+    pub fn delay(&self) -> f64 {
+        (self.filter.len() - 1) as f64 / (2.0 * self.down as f64)
+    }
+
+    /// Feed a chunk of input and return the output samples it completes.
+    pub fn process(&mut self, input: &[f64]) -> Vec<f64> {
+        self.history.extend(input.iter().copied());
+        self.received += input.len();
+
+        let mut output = Vec::new();
+        while self.time / self.up < self.received {
+            output.push(self.output_at(self.time));
+            self.time += self.down;
+        }
+
+        // Drop samples that no future output reaches
+        let reach = self.filter.len() - 1;
+        let oldest_needed = if self.time > reach {
+            (self.time - reach).div_ceil(self.up)
+        } else {
+            0
+        };
+        while self.history_start < oldest_needed && !self.history.is_empty() {
+            self.history.pop_front();
+            self.history_start += 1;
+        }
+        output
+    }
+
+    /// Emit the remaining filter tail, treating the input as ended, and reset.
+    pub fn flush(&mut self) -> Vec<f64> {
+        let mut output = Vec::new();
+        if self.received > 0 {
+            let end = (self.received - 1) * self.up + self.filter.len() - 1;
+            while self.time <= end {
+                output.push(self.output_at(self.time));
+                self.time += self.down;
+            }
+        }
+        self.reset();
+        output
+    }
+
+    /// Discard all buffered input and start over.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.history_start = 0;
+        self.received = 0;
+        self.time = 0;
+    }
+
+    fn output_at(&self, time: usize) -> f64 {
+        polyphase_sum(
+            &self.filter,
+            self.up,
+            time,
+            self.history_start,
+            self.received,
+            |i| self.history[i - self.history_start],
+        )
+    }
+}
+
+/// Output length of [`upfirdn`]
+fn upfirdn_len(h_len: usize, x_len: usize, up: usize, down: usize) -> usize {
+    ((x_len - 1) * up + h_len - 1) / down + 1
+}
+
+/// One output of the zero-stuffed convolution at upsampled time `time`
+///
+/// Sums `h[time - i * up] * sample(i)` over the input indices `i` in
+/// `oldest..available` that the filter reaches.
+fn polyphase_sum(
+    h: &[f64],
+    up: usize,
+    time: usize,
+    oldest: usize,
+    available: usize,
+    sample: impl Fn(usize) -> f64,
+) -> f64 {
+    if available == 0 {
+        return 0.0;
+    }
+    let newest = (time / up).min(available - 1);
+    let mut acc = 0.0;
+    for i in (oldest..=newest).rev() {
+        let k = time - i * up;
+        if k >= h.len() {
+            break;
+        }
+        acc += h[k] * sample(i);
+    }
+    acc
+}
+
+/// Anti-aliasing lowpass for a rate change of `up / down`
+///
+/// Returns the filter, scaled by `up` to preserve amplitude, and its half
+/// length.
+fn antialias_filter(
+    up: usize,
+    down: usize,
+    window: Option<&str>,
+) -> SignalResult<(Vec<f64>, usize)> {
+    let max_rate = up.max(down);
+    let cutoff = 1.0 / max_rate as f64;
+    let half_len = HALF_ZERO_CROSSINGS * max_rate;
+    let numtaps = 2 * half_len + 1;
+
+    let mut h = match window {
+        Some(name) => firwin(numtaps, cutoff, name, true)?,
+        None => {
+            let win = kaiser(numtaps, DEFAULT_KAISER_BETA, true)?;
+            firwin_from_window(&[cutoff], &win, true)?
+        }
+    };
+    for v in &mut h {
+        *v *= up as f64;
+    }
+    Ok((h, half_len))
+}
+
 /// Find the greatest common divisor of two numbers.
 fn gcd(mut a: usize, mut b: usize) -> usize {
     while b != 0 {
@@ -428,16 +765,16 @@ mod tests {
     }
 
     #[test]
-    fn test_resample_poly() {
+    fn test_resample_to_length() {
         // Resample to a specific length
         let signal: Vec<f64> = (0..100).map(|i| (i as f64 * 0.1).sin()).collect();
 
         // Resample to 150 points
-        let resampled = resample_poly(&signal, 150).unwrap();
+        let resampled = resample_to_length(&signal, 150).unwrap();
         assert_eq!(resampled.len(), 150);
 
         // Resample to 50 points
-        let resampled = resample_poly(&signal, 50).unwrap();
+        let resampled = resample_to_length(&signal, 50).unwrap();
         assert_eq!(resampled.len(), 50);
     }
 
@@ -453,4 +790,85 @@ mod tests {
         assert_eq!(rational_approximation(2.0), (2, 1));
         assert_eq!(rational_approximation(1.5), (3, 2));
     }
+
+    #[test]
+    fn test_upfirdn_reference() {
+        let y = upfirdn(&[1.0, 1.0, 1.0], &[1.0, 1.0, 1.0], 1, 1).unwrap();
+        assert_eq!(y, vec![1.0, 2.0, 3.0, 2.0, 1.0]);
+
+        let y = upfirdn(&[1.0], &[1.0, 2.0, 3.0], 3, 1).unwrap();
+        assert_eq!(y, vec![1.0, 0.0, 0.0, 2.0, 0.0, 0.0, 3.0]);
+
+        let y = upfirdn(&[1.0, 1.0], &[1.0, 2.0, 3.0, 4.0, 5.0], 1, 2).unwrap();
+        assert_eq!(y, vec![1.0, 5.0, 9.0]);
+    }
+
+    #[test]
+    fn test_resample_poly_sine() {
+        let freq = 0.03;
+        let signal: Vec<f64> = (0..300)
+            .map(|i| (2.0 * std::f64::consts::PI * freq * i as f64).sin())
+            .collect();
+
+        for (up, down) in [(3, 2), (2, 3), (4, 1), (147, 160)] {
+            let resampled = resample_poly(&signal, up, down, None).unwrap();
+            let n_out = (signal.len() * up).div_ceil(down);
+            assert_eq!(resampled.len(), n_out);
+
+            // Away from the edges the samples follow the resampled sine
+            let step = down as f64 / up as f64;
+            for (m, &v) in resampled
+                .iter()
+                .enumerate()
+                .take(4 * n_out / 5)
+                .skip(n_out / 5)
+            {
+                let expected = (2.0 * std::f64::consts::PI * freq * m as f64 * step).sin();
+                assert!((v - expected).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_resample_ratio_sine() {
+        let freq = 0.02;
+        let signal: Vec<f64> = (0..400)
+            .map(|i| (2.0 * std::f64::consts::PI * freq * i as f64).sin())
+            .collect();
+
+        for ratio in [1.37, 0.61] {
+            let resampled = resample_ratio(&signal, ratio, None).unwrap();
+            assert_eq!(resampled.len(), (400.0 * ratio).ceil() as usize);
+            let n_out = resampled.len();
+            for (m, &v) in resampled
+                .iter()
+                .enumerate()
+                .take(4 * n_out / 5)
+                .skip(n_out / 5)
+            {
+                let t = m as f64 / ratio;
+                let expected = (2.0 * std::f64::consts::PI * freq * t).sin();
+                assert!((v - expected).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_resampler_matches_upfirdn() {
+        let signal: Vec<f64> = (0..200).map(|i| (0.13 * i as f64).sin()).collect();
+        let mut resampler = PolyphaseResampler::new(6, 4, None).unwrap();
+        assert_eq!(resampler.ratio(), (3, 2));
+
+        let mut streamed = Vec::new();
+        for chunk in signal.chunks(7) {
+            streamed.extend(resampler.process(chunk));
+        }
+        streamed.extend(resampler.flush());
+
+        let expected = upfirdn(resampler.filter(), &signal, 3, 2).unwrap();
+        assert_eq!(streamed.len(), expected.len());
+        for (u, v) in streamed.iter().zip(&expected) {
+            assert!((u - v).abs() < 1e-12);
+        }
+    }
 }
@@ -1,6 +1,7 @@
 //! Spectral analysis functions
 //!
-//! This module provides functions for estimating power spectral densities and spectrograms.
+//! This module provides functions for estimating power spectral densities and spectrograms,
+//! including periodograms, Welch averaging, cross spectral densities and coherence.
 
 use crate::error::{SignalError, SignalResult};
 use num_complex::Complex64;
@@ -115,6 +116,10 @@ fn apply_detrend(x: &[f64], detrend_type: &str) -> SignalResult<Vec<f64>> {
 
 /// Estimate the power spectral density using periodogram method
 ///
+/// The whole signal is treated as a single segment: it is detrended,
+/// windowed and transformed, and the one-sided spectrum is returned with the
+/// power of the negative frequencies folded onto the positive ones.
+///
 /// # Arguments
 ///
 /// * `x` - Input signal
@@ -122,7 +127,8 @@ fn apply_detrend(x: &[f64], detrend_type: &str) -> SignalResult<Vec<f64>> {
 /// * `window` - Window function to apply (default = "boxcar")
 /// * `nfft` - Length of the FFT (default = length of x)
 /// * `detrend` - Detrend option ("constant", "linear", or "none")
-/// * `scaling` - Scaling mode ("density" or "spectrum")
+/// * `scaling` - Scaling mode: "density" for power spectral density (V²/Hz)
+///   or "spectrum" for power spectrum (V²)
 ///
 /// # Returns
 ///
@@ -143,102 +149,34 @@ where
         return Err(SignalError::ValueError("Input array is empty".to_string()));
     }
 
-    // Convert input to f64
-    let x_f64: Vec<f64> = x
-        .iter()
-        .map(|&val| {
-            num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
-                SignalError::ValueError(format!("Could not convert {:?} to f64", val))
-            })
-        })
-        .collect::<SignalResult<Vec<_>>>()?;
-
-    // Default parameters
-    let fs_val = fs.unwrap_or(1.0);
-    let nfft_val = nfft.unwrap_or(x_f64.len());
-    let window_val = window.unwrap_or("boxcar");
-    let detrend_val = detrend.unwrap_or("constant");
-    let scaling_val = scaling.unwrap_or("density");
-
-    // Validate parameters
-    if fs_val <= 0.0 {
-        return Err(SignalError::ValueError(format!(
-            "Sampling frequency must be positive, got {}",
-            fs_val
-        )));
-    }
-
-    if nfft_val < x_f64.len() {
+    let nfft_val = nfft.unwrap_or(x.len());
+    if nfft_val < x.len() {
         return Err(SignalError::ValueError(format!(
             "NFFT must be at least as large as signal length, got {} < {}",
             nfft_val,
-            x_f64.len()
+            x.len()
         )));
     }
 
-    // 1. Apply detrending
-    let detrended = apply_detrend(&x_f64, detrend_val)?;
-
-    // 2. Apply window
-    let win = get_window(window_val, x_f64.len())?;
-    let windowed: Vec<f64> = detrended
-        .iter()
-        .zip(win.iter())
-        .map(|(&x, &w)| x * w)
-        .collect();
-
-    // Scale factor for the window
-    let win_scale = win.iter().map(|&w| w.powi(2)).sum::<f64>();
-    let scale = 1.0 / win_scale;
-
-    // 3. Zero-pad the signal if needed
-    let mut padded = windowed.clone();
-    if nfft_val > padded.len() {
-        padded.resize(nfft_val, 0.0);
-    }
-
-    // 4. Compute FFT
-    let spectrum = scirs2_fft::fft(&padded, None)
-        .map_err(|e| SignalError::ComputationError(format!("FFT computation error: {}", e)))?;
-
-    // 5. Compute periodogram (using magnitude squared)
-    let periodogram: Vec<f64> = spectrum
-        .iter()
-        .map(|&c| c.norm_sqr() * scale / (fs_val * x_f64.len() as f64))
-        .collect();
-
-    // 6. Generate frequency axis - be careful with bounds
-    let freqs = scirs2_fft::helper::fftfreq(nfft_val, 1.0 / fs_val).map_err(|e| {
-        SignalError::ComputationError(format!("Frequency computation error: {}", e))
-    })?;
-
-    // 7. Keep only positive frequencies
-    let mut result_freqs = Vec::new();
-    let mut result_psd = Vec::new();
-
-    let n_half = (nfft_val / 2) + (nfft_val % 2); // Handle both even and odd nfft
-
-    for i in 0..n_half {
-        if i < freqs.len() && i < periodogram.len() {
-            result_freqs.push(freqs[i]);
-
-            // Adjust scaling based on user preference
-            let psd_val = if scaling_val == "density" {
-                periodogram[i]
-            } else {
-                // "spectrum" - multiply by sampling frequency
-                periodogram[i] * fs_val
-            };
-
-            result_psd.push(psd_val);
-        }
-    }
-
-    Ok((result_freqs, result_psd))
+    let config = SpectralConfig {
+        fs: fs.unwrap_or(1.0),
+        window: window.unwrap_or("boxcar").to_string(),
+        nperseg: Some(x.len()),
+        noverlap: Some(0),
+        nfft: Some(nfft_val),
+        detrend: detrend.unwrap_or("constant").to_string(),
+        scaling: scaling.unwrap_or("density").to_string(),
+        return_onesided: true,
+    };
+    welch_with_config(x, &config)
 }
 
 /// Estimate the power spectral density using Welch's method
 ///
+/// The signal is split into overlapping segments, each of which is
+/// detrended, windowed and transformed; the periodograms of the segments
+/// are averaged.
+///
 /// # Arguments
 ///
 /// * `x` - Input signal
@@ -267,144 +205,320 @@ pub fn welch<T>(
 where
     T: Float + NumCast + Debug,
 {
-    // Validate input
-    if x.is_empty() {
-        return Err(SignalError::ValueError("Input array is empty".to_string()));
+    let config = SpectralConfig {
+        fs: fs.unwrap_or(1.0),
+        window: window.unwrap_or("hann").to_string(),
+        nperseg,
+        noverlap,
+        nfft,
+        detrend: detrend.unwrap_or("constant").to_string(),
+        scaling: scaling.unwrap_or("density").to_string(),
+        return_onesided: true,
+    };
+    welch_with_config(x, &config)
+}
+
+/// Configuration for Welch-type spectral estimates
+///
+/// Shared by [`welch_with_config`], [`csd`] and [`coherence`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectralConfig {
+    /// Sampling frequency
+    pub fs: f64,
+    /// Window name accepted by [`crate::window::get_window`]; the periodic
+    /// form of the window is used
+    pub window: String,
+    /// Segment length (default: 256, limited to the signal length)
+    pub nperseg: Option<usize>,
+    /// Overlap between segments (default: `nperseg / 2`)
+    pub noverlap: Option<usize>,
+    /// FFT length, at least `nperseg` (default: `nperseg`)
+    pub nfft: Option<usize>,
+    /// Per-segment detrending: "constant", "linear" or "none"
+    pub detrend: String,
+    /// "density" for a spectral density (V²/Hz) or "spectrum" for a power
+    /// spectrum (V²)
+    pub scaling: String,
+    /// Return the one-sided spectrum with the negative frequencies folded
+    /// in; otherwise all `nfft` bins in FFT order
+    pub return_onesided: bool,
+}
+
+impl Default for SpectralConfig {
+    fn default() -> Self {
+        Self {
+            fs: 1.0,
+            window: "hann".to_string(),
+            nperseg: None,
+            noverlap: None,
+            nfft: None,
+            detrend: "constant".to_string(),
+            scaling: "density".to_string(),
+            return_onesided: true,
+        }
     }
+}
 
-    // Convert input to f64
-    let x_f64: Vec<f64> = x
+/// Welch's power spectral density estimate with full configuration
+///
+/// # Arguments
+///
+/// * `x` - Input signal
+/// * `config` - Segmentation, window, detrending and scaling options
+///
+/// # Returns
+///
+/// * A tuple containing (frequencies, power spectral density)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::spectral::{welch_with_config, SpectralConfig};
+///
+/// let x: Vec<f64> = (0..1024).map(|i| (0.2 * i as f64).sin()).collect();
+/// let config = SpectralConfig {
+///     nperseg: Some(128),
+///     return_onesided: false,
+///     ..Default::default()
+/// };
+/// let (freqs, psd) = welch_with_config(&x, &config).unwrap();
+/// assert_eq!(freqs.len(), 128);
+/// assert_eq!(psd.len(), 128);
+/// ```
+pub fn welch_with_config<T>(x: &[T], config: &SpectralConfig) -> PeriodogramResult
+where
+    T: Float + NumCast + Debug,
+{
+    let x_f64 = to_f64_signal(x)?;
+    let (freqs, pxx) = cross_spectral_density(&x_f64, &x_f64, config)?;
+    Ok((freqs, pxx.into_iter().map(|p| p.re).collect()))
+}
+
+/// Cross power spectral density using Welch's method
+///
+/// Averages `conj(X) * Y` over windowed segments, where `X` and `Y` are the
+/// segment spectra of `x` and `y`, with the same scaling as
+/// [`welch_with_config`]; `csd(x, x)` equals the power spectral density.
+///
+/// # Arguments
+///
+/// * `x` - First input signal
+/// * `y` - Second input signal, of the same length
+/// * `config` - Segmentation, window, detrending and scaling options
+///
+/// # Returns
+///
+/// * A tuple containing (frequencies, complex cross spectral density)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::spectral::{csd, SpectralConfig};
+///
+/// let x: Vec<f64> = (0..512).map(|i| (0.3 * i as f64).sin()).collect();
+/// let y: Vec<f64> = (0..512).map(|i| (0.3 * i as f64 - 0.5).sin()).collect();
+/// let (freqs, pxy) = csd(&x, &y, &SpectralConfig::default()).unwrap();
+/// assert_eq!(freqs.len(), pxy.len());
+/// ```
+pub fn csd<T>(x: &[T], y: &[T], config: &SpectralConfig) -> SignalResult<(Vec<f64>, Vec<Complex64>)>
+where
+    T: Float + NumCast + Debug,
+{
+    let x_f64 = to_f64_signal(x)?;
+    let y_f64 = to_f64_signal(y)?;
+    cross_spectral_density(&x_f64, &y_f64, config)
+}
+
+/// Magnitude-squared coherence using Welch's method
+///
+/// Computes `|Pxy|² / (Pxx Pyy)` from Welch estimates of the cross and auto
+/// spectral densities. Values lie in `[0, 1]`; the estimate is only
+/// meaningful when several segments are averaged.
+///
+/// # Arguments
+///
+/// * `x` - First input signal
+/// * `y` - Second input signal, of the same length
+/// * `config` - Segmentation, window, detrending and scaling options
+///
+/// # Returns
+///
+/// * A tuple containing (frequencies, coherence)
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::spectral::{coherence, SpectralConfig};
+///
+/// let x: Vec<f64> = (0..2048).map(|i| ((i * 7919) % 101) as f64 / 101.0 - 0.5).collect();
+/// let y: Vec<f64> = x.iter().map(|v| 2.0 * v).collect();
+/// let config = SpectralConfig { nperseg: Some(128), ..Default::default() };
+/// let (_, cxy) = coherence(&x, &y, &config).unwrap();
+/// assert!(cxy.iter().skip(1).all(|&c| (c - 1.0).abs() < 1e-8));
+/// ```
+pub fn coherence<T>(x: &[T], y: &[T], config: &SpectralConfig) -> PeriodogramResult
+where
+    T: Float + NumCast + Debug,
+{
+    let x_f64 = to_f64_signal(x)?;
+    let y_f64 = to_f64_signal(y)?;
+    let (freqs, pxy) = cross_spectral_density(&x_f64, &y_f64, config)?;
+    let (_, pxx) = cross_spectral_density(&x_f64, &x_f64, config)?;
+    let (_, pyy) = cross_spectral_density(&y_f64, &y_f64, config)?;
+
+    let cxy = pxy
         .iter()
+        .zip(pxx.iter().zip(&pyy))
+        .map(|(p, (a, b))| {
+            let denom = a.re * b.re;
+            if denom > 0.0 {
+                p.norm_sqr() / denom
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    Ok((freqs, cxy))
+}
+
+/// Convert a signal to f64, rejecting empty input
+fn to_f64_signal<T>(x: &[T]) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    if x.is_empty() {
+        return Err(SignalError::ValueError("Input array is empty".to_string()));
+    }
+    x.iter()
         .map(|&val| {
             num_traits::cast::cast::<T, f64>(val).ok_or_else(|| {
                 SignalError::ValueError(format!("Could not convert {:?} to f64", val))
             })
         })
-        .collect::<SignalResult<Vec<_>>>()?;
+        .collect()
+}
 
-    // Default parameters
-    let fs_val = fs.unwrap_or(1.0);
-    let nperseg_val = nperseg.unwrap_or(256.min(x_f64.len()));
-    let noverlap_val = noverlap.unwrap_or(nperseg_val / 2);
-    let nfft_val = nfft.unwrap_or(nperseg_val);
-    let window_val = window.unwrap_or("hann");
-    let detrend_val = detrend.unwrap_or("constant");
-    let scaling_val = scaling.unwrap_or("density");
+/// Welch-averaged cross spectral density shared by all estimators
+fn cross_spectral_density(
+    x: &[f64],
+    y: &[f64],
+    config: &SpectralConfig,
+) -> SignalResult<(Vec<f64>, Vec<Complex64>)> {
+    if x.len() != y.len() {
+        return Err(SignalError::DimensionMismatch(format!(
+            "Signals must have the same length, got {} and {}",
+            x.len(),
+            y.len()
+        )));
+    }
 
-    // Validate parameters
-    if fs_val <= 0.0 {
+    let fs = config.fs;
+    if fs <= 0.0 {
         return Err(SignalError::ValueError(format!(
             "Sampling frequency must be positive, got {}",
-            fs_val
+            fs
         )));
     }
 
-    if nfft_val < nperseg_val {
+    let nperseg = config.nperseg.unwrap_or(256).min(x.len());
+    if nperseg == 0 {
+        return Err(SignalError::ValueError(
+            "nperseg must be positive".to_string(),
+        ));
+    }
+    let noverlap = config.noverlap.unwrap_or(nperseg / 2);
+    let nfft = config.nfft.unwrap_or(nperseg);
+
+    if nfft < nperseg {
         return Err(SignalError::ValueError(format!(
             "nfft must be at least as large as nperseg, got {} < {}",
-            nfft_val, nperseg_val
+            nfft, nperseg
         )));
     }
 
-    if noverlap_val >= nperseg_val {
+    if noverlap >= nperseg {
         return Err(SignalError::ValueError(format!(
             "noverlap must be less than nperseg, got {} >= {}",
-            noverlap_val, nperseg_val
+            noverlap, nperseg
         )));
     }
 
-    // Create window function
-    let win = get_window(window_val, nperseg_val)?;
-    let win_scale = win.iter().map(|&w| w.powi(2)).sum::<f64>();
-    let scale = 1.0 / win_scale;
+    let (win, scale) = match config.scaling.as_str() {
+        "density" | "spectrum" => {
+            let win = crate::window::get_window(&config.window, nperseg, true)?;
+            let scale = if config.scaling == "density" {
+                1.0 / (fs * win.iter().map(|w| w * w).sum::<f64>())
+            } else {
+                1.0 / win.iter().sum::<f64>().powi(2)
+            };
+            (win, scale)
+        }
+        other => {
+            return Err(SignalError::ValueError(format!(
+                "Unknown scaling option: {}, expected 'density' or 'spectrum'",
+                other
+            )));
+        }
+    };
 
     // Determine number of segments
-    let step = nperseg_val - noverlap_val;
-    let num_segments = if step > 0 {
-        (x_f64.len() - noverlap_val) / step
-    } else {
-        0
+    let step = nperseg - noverlap;
+    let num_segments = (x.len() - noverlap) / step;
+
+    let spectrum_of = |segment: &[f64]| -> SignalResult<Vec<Complex64>> {
+        let detrended = apply_detrend(segment, &config.detrend)?;
+        let mut padded: Vec<f64> = detrended.iter().zip(&win).map(|(v, w)| v * w).collect();
+        padded.resize(nfft, 0.0);
+        scirs2_fft::fft(&padded, Some(nfft))
+            .map_err(|e| SignalError::ComputationError(format!("FFT computation error: {}", e)))
     };
 
-    if num_segments < 1 {
-        return Err(SignalError::ValueError(
-            "Not enough data points for given nperseg and noverlap".to_string(),
-        ));
-    }
-
-    // Calculate frequency bins
-    let freqs = scirs2_fft::helper::fftfreq(nfft_val, 1.0 / fs_val).map_err(|e| {
-        SignalError::ComputationError(format!("Frequency computation error: {}", e))
-    })?;
-
-    // Keep only positive frequencies
-    let n_half = (nfft_val / 2) + (nfft_val % 2); // Handle both even and odd nfft
-    let result_freqs: Vec<f64> = freqs.into_iter().take(n_half).collect();
-
-    // Initialize averaged periodogram
-    let mut psd_avg = vec![0.0; n_half];
-
-    // Process each segment
+    let mut pxy = vec![Complex64::new(0.0, 0.0); nfft];
+    let same_signal = std::ptr::eq(x, y);
     for i in 0..num_segments {
-        let start = i * step;
-        let end = start + nperseg_val;
-
-        if end > x_f64.len() {
-            break;
-        }
-
-        // Extract segment
-        let segment = x_f64[start..end].to_vec();
-
-        // Detrend the segment
-        let detrended = apply_detrend(&segment, detrend_val)?;
-
-        // Apply window
-        let windowed: Vec<f64> = detrended
-            .iter()
-            .zip(win.iter())
-            .map(|(&x, &w)| x * w)
-            .collect();
-
-        // Zero-pad if needed
-        let mut padded = windowed.clone();
-        if nfft_val > padded.len() {
-            padded.resize(nfft_val, 0.0);
-        }
-
-        // Compute FFT
-        let spectrum = scirs2_fft::fft(&padded, None)
-            .map_err(|e| SignalError::ComputationError(format!("FFT computation error: {}", e)))?;
-
-        // Compute periodogram for this segment
-        let segment_psd: Vec<f64> = spectrum
-            .iter()
-            .take(n_half)
-            .map(|&c| c.norm_sqr() * scale / (fs_val * nperseg_val as f64))
-            .collect();
-
-        // Accumulate into average
-        for (j, &psd) in segment_psd.iter().enumerate() {
-            if j < psd_avg.len() {
-                psd_avg[j] += psd;
-            }
+        let range = i * step..i * step + nperseg;
+        let x_spec = spectrum_of(&x[range.clone()])?;
+        let y_spec = if same_signal {
+            x_spec.clone()
+        } else {
+            spectrum_of(&y[range])?
+        };
+        for (acc, (a, b)) in pxy.iter_mut().zip(x_spec.iter().zip(&y_spec)) {
+            *acc += a.conj() * b;
         }
     }
-
-    // Normalize by number of segments
-    for psd in &mut psd_avg {
-        *psd /= num_segments as f64;
+    let norm = scale / num_segments as f64;
+    for p in &mut pxy {
+        *p *= norm;
     }
 
-    // Apply scaling
-    let result_psd = if scaling_val == "density" {
-        psd_avg
+    let df = fs / nfft as f64;
+    if config.return_onesided {
+        let n_bins = nfft / 2 + 1;
+        pxy.truncate(n_bins);
+        // Fold in the negative frequencies; DC and an even-length Nyquist bin are unpaired
+        let last_paired = if nfft.is_multiple_of(2) {
+            n_bins - 1
+        } else {
+            n_bins
+        };
+        for p in pxy.iter_mut().take(last_paired).skip(1) {
+            *p *= 2.0;
+        }
+        let freqs = (0..n_bins).map(|k| k as f64 * df).collect();
+        Ok((freqs, pxy))
     } else {
-        // "spectrum" - multiply by sampling frequency
-        psd_avg.iter().map(|&p| p * fs_val).collect()
-    };
-
-    Ok((result_freqs, result_psd))
+        let freqs = (0..nfft)
+            .map(|k| {
+                if k <= (nfft - 1) / 2 {
+                    k as f64 * df
+                } else {
+                    (k as f64 - nfft as f64) * df
+                }
+            })
+            .collect();
+        Ok((freqs, pxy))
+    }
 }
 
 /// Apply boundary handling to a signal for STFT
@@ -933,4 +1047,90 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_periodogram_scaling() {
+        // Parseval: the one-sided density integrates to the mean power
+        let x: Vec<f64> = (0..500)
+            .map(|i| ((i * 7919) % 211) as f64 / 211.0 - 0.3)
+            .collect();
+        let fs = 8.0;
+        let (freqs, psd) = periodogram(&x, Some(fs), None, None, Some("none"), None).unwrap();
+        assert_eq!(freqs.len(), 251);
+        let df = freqs[1] - freqs[0];
+        let power = x.iter().map(|v| v * v).sum::<f64>() / x.len() as f64;
+        assert_relative_eq!(psd.iter().sum::<f64>() * df, power, epsilon = 1e-10);
+
+        // Spectrum scaling gives the RMS power of a bin-centered sine
+        let sine: Vec<f64> = (0..512)
+            .map(|i| 2.0 * (2.0 * PI * 32.0 * i as f64 / 512.0).sin())
+            .collect();
+        let (_, spectrum) =
+            periodogram(&sine, Some(1.0), None, None, None, Some("spectrum")).unwrap();
+        assert_relative_eq!(spectrum[32], 2.0, epsilon = 1e-10);
+
+        // The two-sided estimate spreads the same power over all bins
+        let config = SpectralConfig {
+            fs,
+            window: "hann".to_string(),
+            nperseg: Some(100),
+            return_onesided: false,
+            ..Default::default()
+        };
+        let (freqs2, psd2) = welch_with_config(&x, &config).unwrap();
+        let (_, psd1) = welch(
+            &x,
+            Some(fs),
+            Some("hann"),
+            Some(100),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(freqs2.len(), 100);
+        assert_relative_eq!(freqs2[99], -fs / 100.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            psd2.iter().sum::<f64>(),
+            psd1.iter().sum::<f64>(),
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_csd_and_coherence() {
+        let x: Vec<f64> = (0..4096)
+            .map(|i| ((i * 7919 + 13) % 1009) as f64 / 1009.0 - 0.5)
+            .collect();
+        let noise: Vec<f64> = (0..4096)
+            .map(|i| ((i * 104729 + 7) % 997) as f64 / 997.0 - 0.5)
+            .collect();
+        let config = SpectralConfig {
+            nperseg: Some(256),
+            ..Default::default()
+        };
+
+        // The cross spectrum of a signal with itself is its PSD
+        let (_, pxx) = welch_with_config(&x, &config).unwrap();
+        let (_, pxy) = csd(&x, &x, &config).unwrap();
+        for (p, c) in pxx.iter().zip(&pxy) {
+            assert_relative_eq!(*p, c.re, epsilon = 1e-12);
+            assert!(c.im.abs() < 1e-12);
+        }
+
+        // A delayed copy is fully coherent; independent noise lowers coherence
+        let delayed: Vec<f64> = (0..4096)
+            .map(|i| if i >= 3 { x[i - 3] } else { 0.0 })
+            .collect();
+        let (_, c_delay) = coherence(&x, &delayed, &config).unwrap();
+        let mixed: Vec<f64> = x.iter().zip(&noise).map(|(a, b)| a + b).collect();
+        let (_, c_mixed) = coherence(&x, &mixed, &config).unwrap();
+        let mean = |c: &[f64]| c[1..].iter().sum::<f64>() / (c.len() - 1) as f64;
+        assert!(mean(&c_delay) > 0.95);
+        assert!(mean(&c_mixed) > 0.3 && mean(&c_mixed) < 0.7);
+        assert!(c_mixed.iter().all(|&c| (0.0..=1.0 + 1e-12).contains(&c)));
+
+        assert!(csd(&x, &x[..100], &config).is_err());
+    }
 }
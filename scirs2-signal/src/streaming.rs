@@ -0,0 +1,643 @@
+//! Composable streaming blocks for low-latency pipelines
+//!
+//! Every stage of a streaming pipeline implements the [`Block`] trait: it
+//! receives the signal one chunk at a time through [`Block::process`], keeps
+//! whatever state it needs between calls, and returns the output that the
+//! chunk completes. Blocks never see the whole signal, so memory use is bounded
+//! by their internal state rather than by the stream length, and the output of a
+//! chunk is available as soon as that chunk has been processed.
+//!
+//! Blocks are composed with [`Block::then`], which feeds the output of one block
+//! into the next, and [`Block::map`], which transforms each output item.
+//! Splitting a signal into chunks of any size gives the same result as
+//! processing it in one piece.
+//!
+//! Provided blocks:
+//!
+//! - [`IirBlock`] - transfer-function (`b`, `a`) filter with carried state
+//! - [`SosBlock`] - cascade of second-order sections
+//! - [`PolyphaseResampler`] - rational sample-rate conversion
+//! - [`StreamingStft`] - short-time Fourier transform frames
+//! - [`WelchBlock`] - running Welch power spectral density estimate
+//!
+//! # Examples
+//!
+//! ```
+//! use scirs2_signal::filter::iir::butter;
+//! use scirs2_signal::resample::PolyphaseResampler;
+//! use scirs2_signal::streaming::{Block, IirBlock};
+//!
+//! let (b, a) = butter(4, 0.2, "lowpass").unwrap();
+//!
+//! // Low-pass filter, then decimate by 2, then rectify
+//! let mut pipeline = IirBlock::new(&b, &a)
+//!     .unwrap()
+//!     .then(PolyphaseResampler::new(1, 2, None).unwrap())
+//!     .map(|x: f64| x.abs());
+//!
+//! let signal: Vec<f64> = (0..1000).map(|i| (0.05 * i as f64).sin()).collect();
+//! let mut output = Vec::new();
+//! for chunk in signal.chunks(64) {
+//!     output.extend(pipeline.process(chunk).unwrap());
+//! }
+//! output.extend(pipeline.flush().unwrap());
+//! assert!(output.iter().all(|&y| y >= 0.0));
+//! ```
+
+use crate::error::{SignalError, SignalResult};
+use crate::filter::application::lfilter_with_zi;
+use crate::filter::SecondOrderSections;
+use crate::resample::PolyphaseResampler;
+use crate::spectral::{welch_with_config, SpectralConfig};
+use crate::streaming_stft::StreamingStft;
+use ndarray::Array1;
+use num_complex::Complex64;
+use std::collections::VecDeque;
+
+/// A processing stage that consumes a stream chunk by chunk
+///
+/// Implementations keep their state between calls to [`process`](Self::process),
+/// so that feeding a signal in chunks of any size produces the same output as
+/// feeding it at once.
+pub trait Block {
+    /// Item type consumed by the block
+    type Input;
+    /// Item type produced by the block
+    type Output;
+
+    /// Consume a chunk of input and return the output it completes
+    fn process(&mut self, chunk: &[Self::Input]) -> SignalResult<Vec<Self::Output>>;
+
+    /// Signal the end of the stream and return any output still held back
+    ///
+    /// After flushing the block is ready to process a new stream. The default
+    /// implementation returns no output and resets the block.
+    fn flush(&mut self) -> SignalResult<Vec<Self::Output>> {
+        self.reset();
+        Ok(Vec::new())
+    }
+
+    /// Discard all internal state
+    fn reset(&mut self);
+
+    /// Feed the output of this block into `next`
+    fn then<B>(self, next: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+        B: Block<Input = Self::Output>,
+    {
+        Chain {
+            first: self,
+            second: next,
+        }
+    }
+
+    /// Transform each output item with `f`
+    fn map<F, O>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Output) -> O,
+    {
+        Map { block: self, f }
+    }
+}
+
+/// Two blocks connected in series, created by [`Block::then`]
+#[derive(Debug, Clone)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Chain<A, B> {
+    /// The upstream block
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// The downstream block
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A, B> Block for Chain<A, B>
+where
+    A: Block,
+    B: Block<Input = A::Output>,
+{
+    type Input = A::Input;
+    type Output = B::Output;
+
+    fn process(&mut self, chunk: &[Self::Input]) -> SignalResult<Vec<Self::Output>> {
+        let intermediate = self.first.process(chunk)?;
+        self.second.process(&intermediate)
+    }
+
+    fn flush(&mut self) -> SignalResult<Vec<Self::Output>> {
+        let tail = self.first.flush()?;
+        let mut output = self.second.process(&tail)?;
+        output.extend(self.second.flush()?);
+        Ok(output)
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+/// A block whose output items are transformed by a function, created by [`Block::map`]
+#[derive(Debug, Clone)]
+pub struct Map<A, F> {
+    block: A,
+    f: F,
+}
+
+impl<A, F, O> Block for Map<A, F>
+where
+    A: Block,
+    F: FnMut(A::Output) -> O,
+{
+    type Input = A::Input;
+    type Output = O;
+
+    fn process(&mut self, chunk: &[Self::Input]) -> SignalResult<Vec<O>> {
+        Ok(self
+            .block
+            .process(chunk)?
+            .into_iter()
+            .map(&mut self.f)
+            .collect())
+    }
+
+    fn flush(&mut self) -> SignalResult<Vec<O>> {
+        Ok(self.block.flush()?.into_iter().map(&mut self.f).collect())
+    }
+
+    fn reset(&mut self) {
+        self.block.reset();
+    }
+}
+
+/// Run a whole signal through a block in chunks of `chunk_size` and flush it
+///
+/// # Arguments
+///
+/// * `block` - Block to drive
+/// * `signal` - Input signal
+/// * `chunk_size` - Number of input items per call to [`Block::process`]
+///
+/// # Returns
+///
+/// * The concatenated output, including the flushed tail
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::streaming::{process_in_chunks, IirBlock};
+///
+/// // One-pole smoother y[n] = 0.5 x[n] + 0.5 y[n-1]
+/// let mut smoother = IirBlock::new(&[0.5], &[1.0, -0.5]).unwrap();
+/// let output = process_in_chunks(&mut smoother, &[1.0; 8], 3).unwrap();
+/// assert!((output[7] - (1.0 - 0.5f64.powi(8))).abs() < 1e-12);
+/// ```
+pub fn process_in_chunks<B>(
+    block: &mut B,
+    signal: &[B::Input],
+    chunk_size: usize,
+) -> SignalResult<Vec<B::Output>>
+where
+    B: Block,
+{
+    if chunk_size == 0 {
+        return Err(SignalError::ValueError(
+            "Chunk size must be positive".to_string(),
+        ));
+    }
+
+    let mut output = Vec::new();
+    for chunk in signal.chunks(chunk_size) {
+        output.extend(block.process(chunk)?);
+    }
+    output.extend(block.flush()?);
+    Ok(output)
+}
+
+/// Streaming IIR or FIR filter in transfer-function form
+///
+/// Applies `lfilter` chunk by chunk, carrying the filter state across calls.
+/// The filter has no look-ahead, so each chunk produces exactly as many output
+/// samples as it has input samples.
+#[derive(Debug, Clone)]
+pub struct IirBlock {
+    b: Vec<f64>,
+    a: Vec<f64>,
+    state: Vec<f64>,
+}
+
+impl IirBlock {
+    /// Create a streaming filter from transfer function coefficients
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - Numerator coefficients
+    /// * `a` - Denominator coefficients; `a[0]` must be nonzero
+    pub fn new(b: &[f64], a: &[f64]) -> SignalResult<Self> {
+        if b.is_empty() {
+            return Err(SignalError::ValueError(
+                "Numerator coefficients are empty".to_string(),
+            ));
+        }
+        if a.is_empty() || a[0] == 0.0 {
+            return Err(SignalError::ValueError(
+                "First denominator coefficient must be nonzero".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            b: b.to_vec(),
+            a: a.to_vec(),
+            state: vec![0.0; b.len().max(a.len()) - 1],
+        })
+    }
+
+    /// Numerator coefficients
+    pub fn numerator(&self) -> &[f64] {
+        &self.b
+    }
+
+    /// Denominator coefficients
+    pub fn denominator(&self) -> &[f64] {
+        &self.a
+    }
+}
+
+impl Block for IirBlock {
+    type Input = f64;
+    type Output = f64;
+
+    fn process(&mut self, chunk: &[f64]) -> SignalResult<Vec<f64>> {
+        let (y, state) = lfilter_with_zi(&self.b, &self.a, chunk, &self.state)?;
+        self.state = state;
+        Ok(y)
+    }
+
+    fn reset(&mut self) {
+        self.state.fill(0.0);
+    }
+}
+
+/// Streaming filter made of cascaded second-order sections
+///
+/// Numerically preferable to [`IirBlock`] for high-order IIR filters.
+#[derive(Debug, Clone)]
+pub struct SosBlock {
+    sections: Vec<IirBlock>,
+}
+
+impl SosBlock {
+    /// Create a streaming filter from second-order sections
+    ///
+    /// # Arguments
+    ///
+    /// * `sos` - Sections, each `[b0, b1, b2, a0, a1, a2]`
+    pub fn new(sos: &SecondOrderSections) -> SignalResult<Self> {
+        if sos.is_empty() {
+            return Err(SignalError::ValueError(
+                "No second-order sections".to_string(),
+            ));
+        }
+
+        let sections = sos
+            .iter()
+            .map(|s| IirBlock::new(&s[..3], &s[3..]))
+            .collect::<SignalResult<Vec<_>>>()?;
+        Ok(Self { sections })
+    }
+
+    /// Number of sections in the cascade
+    pub fn num_sections(&self) -> usize {
+        self.sections.len()
+    }
+}
+
+impl Block for SosBlock {
+    type Input = f64;
+    type Output = f64;
+
+    fn process(&mut self, chunk: &[f64]) -> SignalResult<Vec<f64>> {
+        let mut y = chunk.to_vec();
+        for section in &mut self.sections {
+            y = section.process(&y)?;
+        }
+        Ok(y)
+    }
+
+    fn reset(&mut self) {
+        self.sections.iter_mut().for_each(|s| s.reset());
+    }
+}
+
+impl Block for PolyphaseResampler {
+    type Input = f64;
+    type Output = f64;
+
+    fn process(&mut self, chunk: &[f64]) -> SignalResult<Vec<f64>> {
+        Ok(PolyphaseResampler::process(self, chunk))
+    }
+
+    fn flush(&mut self) -> SignalResult<Vec<f64>> {
+        Ok(PolyphaseResampler::flush(self))
+    }
+
+    fn reset(&mut self) {
+        PolyphaseResampler::reset(self);
+    }
+}
+
+impl Block for StreamingStft {
+    type Input = f64;
+    type Output = Array1<Complex64>;
+
+    /// Returns one spectrum for every frame the chunk completes
+    fn process(&mut self, chunk: &[f64]) -> SignalResult<Vec<Array1<Complex64>>> {
+        let mut frames = Vec::new();
+        let mut input = Array1::from(chunk.to_vec());
+        while let Some(frame) = self.process_frame(&input)? {
+            frames.push(frame);
+            input = Array1::zeros(0);
+        }
+        Ok(frames)
+    }
+
+    /// Returns the zero-padded frames that cover the buffered tail
+    fn flush(&mut self) -> SignalResult<Vec<Array1<Complex64>>> {
+        let frames = StreamingStft::flush(self)?;
+        StreamingStft::reset(self);
+        Ok(frames)
+    }
+
+    fn reset(&mut self) {
+        StreamingStft::reset(self);
+    }
+}
+
+/// Running Welch estimate of the power spectral density
+///
+/// Segments of `nperseg` samples, advancing by `nperseg - noverlap`, are taken
+/// from the stream as soon as they are complete. Each completed segment yields
+/// the average of the periodograms of all segments so far, so after the last
+/// segment the output equals [`welch_with_config`] applied to the whole signal.
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::spectral::SpectralConfig;
+/// use scirs2_signal::streaming::{Block, WelchBlock};
+///
+/// let config = SpectralConfig {
+///     fs: 100.0,
+///     nperseg: Some(64),
+///     ..Default::default()
+/// };
+/// let mut psd = WelchBlock::new(config).unwrap();
+///
+/// let signal: Vec<f64> = (0..640).map(|i| (0.2 * std::f64::consts::PI * i as f64).sin()).collect();
+/// let mut latest = Vec::new();
+/// for chunk in signal.chunks(100) {
+///     if let Some(estimate) = psd.process(chunk).unwrap().pop() {
+///         latest = estimate;
+///     }
+/// }
+///
+/// // The 10 Hz tone falls in bin 10 * 64 / 100 = 6.4
+/// let peak = (0..latest.len()).max_by(|&i, &j| latest[i].total_cmp(&latest[j])).unwrap();
+/// assert!((psd.frequencies()[peak] - 10.0).abs() < 2.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WelchBlock {
+    segment_config: SpectralConfig,
+    nperseg: usize,
+    step: usize,
+    frequencies: Vec<f64>,
+    buffer: VecDeque<f64>,
+    sum: Vec<f64>,
+    segments: usize,
+}
+
+impl WelchBlock {
+    /// Create a streaming Welch estimator
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Spectral configuration; `nperseg` defaults to 256 and
+    ///   `noverlap` to `nperseg / 2`
+    pub fn new(config: SpectralConfig) -> SignalResult<Self> {
+        let nperseg = config.nperseg.unwrap_or(256);
+        if nperseg == 0 {
+            return Err(SignalError::ValueError(
+                "nperseg must be positive".to_string(),
+            ));
+        }
+        let noverlap = config.noverlap.unwrap_or(nperseg / 2);
+        if noverlap >= nperseg {
+            return Err(SignalError::ValueError(format!(
+                "noverlap must be less than nperseg, got {} >= {}",
+                noverlap, nperseg
+            )));
+        }
+
+        // Each segment is analysed on its own with the batch estimator
+        let segment_config = SpectralConfig {
+            nperseg: Some(nperseg),
+            noverlap: Some(0),
+            ..config
+        };
+        let (frequencies, bins) = welch_with_config(&vec![0.0; nperseg], &segment_config)?;
+
+        Ok(Self {
+            segment_config,
+            nperseg,
+            step: nperseg - noverlap,
+            frequencies,
+            buffer: VecDeque::with_capacity(nperseg),
+            sum: vec![0.0; bins.len()],
+            segments: 0,
+        })
+    }
+
+    /// Frequencies of the estimate bins
+    pub fn frequencies(&self) -> &[f64] {
+        &self.frequencies
+    }
+
+    /// Number of segments averaged so far
+    pub fn num_segments(&self) -> usize {
+        self.segments
+    }
+}
+
+impl Block for WelchBlock {
+    type Input = f64;
+    type Output = Vec<f64>;
+
+    /// Returns the updated estimate for every segment the chunk completes
+    fn process(&mut self, chunk: &[f64]) -> SignalResult<Vec<Vec<f64>>> {
+        let mut estimates = Vec::new();
+        for &x in chunk {
+            self.buffer.push_back(x);
+            if self.buffer.len() < self.nperseg {
+                continue;
+            }
+
+            let segment: Vec<f64> = self.buffer.iter().copied().collect();
+            let (_, pxx) = welch_with_config(&segment, &self.segment_config)?;
+            for (s, p) in self.sum.iter_mut().zip(&pxx) {
+                *s += p;
+            }
+            self.segments += 1;
+            let norm = 1.0 / self.segments as f64;
+            estimates.push(self.sum.iter().map(|s| s * norm).collect());
+
+            self.buffer.drain(..self.step);
+        }
+        Ok(estimates)
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.sum.fill(0.0);
+        self.segments = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::application::lfilter;
+    use crate::filter::iir::{butter, iirfilter_sos, IirPrototype};
+    use crate::spectral::welch;
+    use crate::streaming_stft::StreamingStftConfig;
+    use approx::assert_relative_eq;
+
+    fn test_signal(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| (0.07 * i as f64).sin() + 0.3 * (1.3 * i as f64).cos())
+            .collect()
+    }
+
+    #[test]
+    fn test_filters_match_batch() {
+        let signal = test_signal(500);
+        let (b, a) = butter(4, 0.15, "lowpass").unwrap();
+        let expected = lfilter(&b, &a, &signal).unwrap();
+
+        // Uneven chunk sizes must not change the result
+        let mut block = IirBlock::new(&b, &a).unwrap();
+        let mut output = Vec::new();
+        for chunk in [&signal[..1], &signal[1..97], &signal[97..98], &signal[98..]] {
+            output.extend(block.process(chunk).unwrap());
+        }
+        assert_eq!(output.len(), expected.len());
+        for (x, y) in output.iter().zip(&expected) {
+            assert_relative_eq!(x, y, epsilon = 1e-12);
+        }
+
+        // Second-order sections agree with the equivalent transfer function
+        let sos = iirfilter_sos(4, &[0.15], IirPrototype::Butterworth, "lowpass").unwrap();
+        let mut sos_block = SosBlock::new(&sos).unwrap();
+        let output = process_in_chunks(&mut sos_block, &signal, 33).unwrap();
+        for (x, y) in output.iter().zip(&expected) {
+            assert_relative_eq!(x, y, epsilon = 1e-9);
+        }
+
+        // Flushing resets the state for the next stream
+        let again = process_in_chunks(&mut sos_block, &signal, 500).unwrap();
+        assert_relative_eq!(again[10], output[10], epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_chained_pipeline() {
+        let signal = test_signal(400);
+        let (b, a) = butter(3, 0.2, "lowpass").unwrap();
+
+        let build = || {
+            IirBlock::new(&b, &a)
+                .unwrap()
+                .then(PolyphaseResampler::new(2, 3, None).unwrap())
+                .map(|x: f64| 2.0 * x)
+        };
+
+        let whole = process_in_chunks(&mut build(), &signal, signal.len()).unwrap();
+        let chunked = process_in_chunks(&mut build(), &signal, 7).unwrap();
+        assert_eq!(whole.len(), chunked.len());
+        for (x, y) in whole.iter().zip(&chunked) {
+            assert_relative_eq!(x, y, epsilon = 1e-12);
+        }
+
+        // Same as running the stages one after the other
+        let filtered = lfilter(&b, &a, &signal).unwrap();
+        let mut resampler = PolyphaseResampler::new(2, 3, None).unwrap();
+        let mut staged = resampler.process(&filtered);
+        staged.extend(resampler.flush());
+        for (x, y) in whole.iter().zip(&staged) {
+            assert_relative_eq!(*x, 2.0 * y, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_stft_block_emits_every_frame() {
+        let signal = test_signal(300);
+        let config = StreamingStftConfig {
+            frame_length: 64,
+            hop_length: 16,
+            ..Default::default()
+        };
+
+        // A large chunk completes several frames at once
+        let mut stft = StreamingStft::new(config.clone()).unwrap();
+        let frames = process_in_chunks(&mut stft, &signal, 300).unwrap();
+        let mut stft = StreamingStft::new(config).unwrap();
+        let single = process_in_chunks(&mut stft, &signal, 1).unwrap();
+
+        assert!(frames.len() > 10);
+        assert_eq!(frames.len(), single.len());
+        for (x, y) in frames.iter().zip(&single) {
+            for (u, v) in x.iter().zip(y) {
+                assert!((u - v).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_welch_block_matches_batch() {
+        let signal = test_signal(1000);
+        let config = SpectralConfig {
+            fs: 8.0,
+            nperseg: Some(128),
+            noverlap: Some(96),
+            ..Default::default()
+        };
+        let mut block = WelchBlock::new(config.clone()).unwrap();
+        let estimates = process_in_chunks(&mut block, &signal, 50).unwrap();
+
+        let (freqs, pxx) = welch(
+            &signal,
+            Some(8.0),
+            Some("hann"),
+            Some(128),
+            Some(96),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(estimates.len(), (1000 - 96) / 32);
+        assert_eq!(block.frequencies(), &freqs[..]);
+        let last = estimates.last().unwrap();
+        for (x, y) in last.iter().zip(&pxx) {
+            assert_relative_eq!(x, y, epsilon = 1e-12 * (1.0 + y.abs()));
+        }
+    }
+}
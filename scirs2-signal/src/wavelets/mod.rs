@@ -7,6 +7,7 @@
 mod complex_wavelets;
 mod cwt;
 mod dual_tree_complex;
+mod fft_cwt;
 mod real_wavelets;
 mod scalogram;
 #[cfg(test)]
@@ -22,8 +23,9 @@ pub use dual_tree_complex::{
     BoundaryMode, Dtcwt1dResult, Dtcwt2dResult, DtcwtConfig, DtcwtFilters, DtcwtProcessor,
     FilterSet,
 };
+pub use fft_cwt::{cwt_fft, CwtResult, MotherWavelet};
 pub use real_wavelets::ricker;
-pub use scalogram::{cwt_magnitude, cwt_phase, scale_to_frequency, scalogram};
+pub use scalogram::{cwt_magnitude, cwt_phase, frequency_to_scale, scale_to_frequency, scalogram};
 pub use transform::cwt;
 pub use types::WaveletType;
 
@@ -322,3 +322,47 @@ pub fn scale_to_frequency(
 
     Ok(freqs)
 }
+
+/// Convert frequencies to wavelet scales
+///
+/// This is the inverse of [`scale_to_frequency`]: `scale = central_freq / (freq * dt)`.
+///
+/// # Arguments
+///
+/// * `frequencies` - Frequencies in Hz
+/// * `central_frequency` - Central frequency of the wavelet
+/// * `sampling_period` - Sampling period of the signal in seconds
+///
+/// # Returns
+///
+/// * Vector of scales corresponding to the input frequencies
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_signal::wavelets::{frequency_to_scale, MotherWavelet};
+///
+/// let wavelet = MotherWavelet::Morlet { omega0: 6.0 };
+/// let scales = frequency_to_scale(&[50.0, 10.0], wavelet.central_frequency(), 0.001).unwrap();
+/// assert!(scales[0] < scales[1]);
+/// ```
+pub fn frequency_to_scale(
+    frequencies: &[f64],
+    central_frequency: f64,
+    sampling_period: f64,
+) -> SignalResult<Vec<f64>> {
+    if frequencies.is_empty() {
+        return Err(crate::error::SignalError::ValueError(
+            "Frequencies array is empty".to_string(),
+        ));
+    }
+
+    if frequencies.iter().any(|&f| f <= 0.0) {
+        return Err(crate::error::SignalError::ValueError(
+            "Frequencies must be positive".to_string(),
+        ));
+    }
+
+    // scale_to_frequency with the roles of scale and frequency swapped
+    scale_to_frequency(frequencies, central_frequency, sampling_period)
+}
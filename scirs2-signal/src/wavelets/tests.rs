@@ -79,3 +79,74 @@ fn test_cwt_simple_signal() {
         }
     }
 }
+
+#[test]
+fn test_cwt_fft_peak_at_signal_period() {
+    let dt = 0.01;
+    let freq = 8.0;
+    let signal: Vec<f64> = (0..1024)
+        .map(|i| (2.0 * PI * freq * i as f64 * dt).sin())
+        .collect();
+
+    for wavelet in [
+        MotherWavelet::Morlet { omega0: 6.0 },
+        MotherWavelet::Ricker,
+        MotherWavelet::GaussianDerivative { order: 4 },
+    ] {
+        let freqs: Vec<f64> = (0..200).map(|i| 2.0 + 0.1 * i as f64).collect();
+        let scales = frequency_to_scale(&freqs, wavelet.central_frequency(), dt).unwrap();
+        let scales: Vec<f64> = scales.iter().map(|s| s * dt).collect();
+        let result = cwt_fft(&signal, wavelet, &scales, dt).unwrap();
+
+        // Mean power in the middle of the signal, away from the edges
+        let mid = signal.len() / 2;
+        let power = result.power();
+        let best = (0..scales.len())
+            .max_by(|&a, &b| {
+                let pa: f64 = power[a][mid - 50..mid + 50].iter().sum();
+                let pb: f64 = power[b][mid - 50..mid + 50].iter().sum();
+                pa.partial_cmp(&pb).unwrap()
+            })
+            .unwrap();
+        assert!(
+            (result.frequencies[best] - freq).abs() < 0.25,
+            "{:?}: peak at {} Hz",
+            wavelet,
+            result.frequencies[best]
+        );
+        assert_relative_eq!(result.frequencies[best], freqs[best], epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_cwt_fft_matches_direct_ricker() {
+    // Odd length keeps the sampled direct wavelet centered on a sample
+    let signal: Vec<f64> = (0..255)
+        .map(|i| {
+            let t = i as f64;
+            (t / 7.0).sin() + 0.5 * (t / 3.0).cos()
+        })
+        .collect();
+    let scales = [2.0, 4.0, 8.0];
+
+    let fast = cwt_fft(&signal, MotherWavelet::Ricker, &scales, 1.0).unwrap();
+    let direct = cwt(&signal, ricker, &scales).unwrap();
+
+    for (s, (fast_row, direct_row)) in fast.coefficients.iter().zip(&direct).enumerate() {
+        for i in 0..signal.len() {
+            if fast.in_cone(s, i) {
+                assert!(
+                    (fast_row[i] - direct_row[i]).norm() < 1e-6,
+                    "scale {} sample {}",
+                    scales[s],
+                    i
+                );
+            }
+        }
+    }
+
+    // Cone of influence grows linearly from the edges
+    assert!(!fast.in_cone(2, 0));
+    assert!(fast.in_cone(2, signal.len() / 2));
+    assert_relative_eq!(fast.cone_of_influence[0], 1.0 / 2.0_f64.sqrt());
+}
@@ -7,7 +7,8 @@
 //! The implementation includes:
 //! - Time-domain Wiener filtering for 1D signals
 //! - Frequency-domain Wiener filtering for noise reduction
-//! - Adaptive Wiener filtering with local variance estimation
+//! - Adaptive Wiener filtering with local variance estimation, including the
+//!   SciPy-compatible [`wiener`]
 //! - Iterative Wiener filtering for improved restoration
 //!
 //! # Example
@@ -40,7 +41,9 @@
 
 use ndarray::{s, Array1, Array2};
 use num_complex::Complex64;
+use num_traits::{Float, NumCast};
 use std::cmp;
+use std::fmt::Debug;
 
 use crate::error::{SignalError, SignalResult};
 use scirs2_fft;
@@ -126,6 +129,90 @@ pub fn wiener_filter(
     wiener_filter_freq(signal, &config)
 }
 
+/// Adaptive Wiener filter, following `scipy.signal.wiener`.
+///
+/// The local mean `m` and variance `v` are estimated over a window of `mysize`
+/// samples (zero-padded at the ends), and each sample is shrunk towards the
+/// local mean: `y = m + (1 - noise / v) (x - m)`, or `y = m` where `v < noise`.
+///
+/// # Arguments
+/// * `x` - Input signal
+/// * `mysize` - Window length for the local statistics (default: 3)
+/// * `noise` - Noise power; if None, the mean of the local variances is used
+///
+/// # Returns
+/// * The filtered signal, with the same length as the input
+///
+/// # Example
+/// ```
+/// use scirs2_signal::wiener::wiener;
+///
+/// let x = vec![1.0, 5.0, 2.0, 8.0, 3.0, 3.0, 7.0, 1.0];
+/// let y = wiener(&x, Some(3), None).unwrap();
+/// assert!((y[0] - 2.0).abs() < 1e-12);
+/// assert!((y[3] - 4.983871).abs() < 1e-6);
+/// ```
+pub fn wiener<T>(x: &[T], mysize: Option<usize>, noise: Option<f64>) -> SignalResult<Vec<f64>>
+where
+    T: Float + NumCast + Debug,
+{
+    let size = mysize.unwrap_or(3);
+    if size == 0 {
+        return Err(SignalError::ValueError(
+            "Window size must be positive".to_string(),
+        ));
+    }
+    if let Some(noise) = noise {
+        if noise < 0.0 || !noise.is_finite() {
+            return Err(SignalError::ValueError(
+                "Noise power must be non-negative".to_string(),
+            ));
+        }
+    }
+
+    let signal: Vec<f64> = x
+        .iter()
+        .map(|&v| {
+            num_traits::cast::cast::<T, f64>(v)
+                .ok_or_else(|| SignalError::ValueError(format!("Could not convert {:?} to f64", v)))
+        })
+        .collect::<SignalResult<_>>()?;
+    let n = signal.len();
+    if n == 0 {
+        return Ok(signal);
+    }
+
+    // Running sums over the window [i - size/2, i - size/2 + size), zero-padded
+    let mut prefix = vec![0.0; n + 1];
+    let mut prefix_sq = vec![0.0; n + 1];
+    for (i, &v) in signal.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + v;
+        prefix_sq[i + 1] = prefix_sq[i] + v * v;
+    }
+    let left = size / 2;
+    let (local_mean, local_var): (Vec<f64>, Vec<f64>) = (0..n)
+        .map(|i| {
+            let start = i.saturating_sub(left);
+            let end = (i + size - left).min(n);
+            let mean = (prefix[end] - prefix[start]) / size as f64;
+            let mean_sq = (prefix_sq[end] - prefix_sq[start]) / size as f64;
+            (mean, (mean_sq - mean * mean).max(0.0))
+        })
+        .unzip();
+
+    let noise = noise.unwrap_or_else(|| local_var.iter().sum::<f64>() / n as f64);
+
+    Ok((0..n)
+        .map(|i| {
+            if local_var[i] < noise || local_var[i] == 0.0 {
+                local_mean[i]
+            } else {
+                local_mean[i] + (1.0 - noise / local_var[i]) * (signal[i] - local_mean[i])
+            }
+        })
+        .collect())
+}
+
 /// Applies a frequency-domain Wiener filter to a noisy signal.
 ///
 /// # Arguments
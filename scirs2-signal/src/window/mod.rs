@@ -75,13 +75,50 @@ pub fn get_window(window_type: &str, length: usize, periodic: bool) -> SignalRes
             // Default parameter a = 2 for Lanczos window
             lanczos(length, 2, !periodic)
         }
-        _ => Err(SignalError::ValueError(format!(
-            "Unknown window type: {}",
-            window_type
-        ))),
+        // Fall back to the names understood by the shared FFT window library
+        _ => scirs2_fft::window::get_window(window_type, length, !periodic)
+            .map(|w| w.to_vec())
+            .map_err(|_| SignalError::ValueError(format!("Unknown window type: {}", window_type))),
     }
 }
 
+/// Create a window from the shared `scirs2_fft` window library.
+///
+/// This gives spectral estimators access to the parameterized windows defined
+/// in `scirs2_fft::window` (e.g. a Kaiser window with a chosen `beta` or a
+/// DPSS window with a chosen `NW`), using the same `periodic` convention as
+/// [`get_window`].
+///
+/// # Arguments
+///
+/// * `window` - Window type from `scirs2_fft::window`
+/// * `length` - Length of the window
+/// * `periodic` - If true, the window is periodic, otherwise symmetric
+///
+/// # Returns
+///
+/// * Window function of specified type and length
+///
+/// # Examples
+///
+/// ```
+/// use scirs2_fft::window::Window;
+/// use scirs2_signal::window::from_fft_window;
+///
+/// let window = from_fft_window(Window::Kaiser(14.0), 64, true).unwrap();
+/// assert_eq!(window.len(), 64);
+/// assert!((window[32] - 1.0).abs() < 1e-12);
+/// ```
+pub fn from_fft_window(
+    window: scirs2_fft::window::Window,
+    length: usize,
+    periodic: bool,
+) -> SignalResult<Vec<f64>> {
+    scirs2_fft::window::get_window(window, length, !periodic)
+        .map(|w| w.to_vec())
+        .map_err(|e| SignalError::ValueError(format!("Window creation error: {}", e)))
+}
+
 /// Helper function to handle small or incorrect window lengths
 pub(crate) fn _len_guards(m: usize) -> bool {
     // Return true for trivial windows with length 0 or 1
@@ -978,6 +1015,25 @@ mod tests {
     use super::*;
     use approx::assert_relative_eq;
 
+    #[test]
+    fn test_fft_window_library() {
+        // Names only known to the shared library resolve through the fallback
+        let window = get_window("blackman-harris", 16, true).unwrap();
+        let expected = from_fft_window(scirs2_fft::window::Window::BlackmanHarris, 16, true);
+        assert_eq!(window, expected.unwrap());
+
+        // Both libraries agree on the common windows and conventions
+        for periodic in [true, false] {
+            let ours = get_window("hann", 12, periodic).unwrap();
+            let shared = from_fft_window(scirs2_fft::window::Window::Hann, 12, periodic).unwrap();
+            for (a, b) in ours.iter().zip(shared.iter()) {
+                assert_relative_eq!(a, b, epsilon = 1e-12);
+            }
+        }
+
+        assert!(get_window("not-a-window", 16, true).is_err());
+    }
+
     #[test]
     fn test_hamming_window() {
         let window = hamming(10, true).unwrap();
@@ -13,7 +13,7 @@
 //! * Signal compression with best basis selection
 //! * Pattern recognition with improved time-frequency localization
 
-use crate::dwt::{dwt_decompose, dwt_reconstruct, Wavelet};
+use crate::dwt::{dwt_decompose, dwt_reconstruct_with_mode, Wavelet};
 use crate::error::{SignalError, SignalResult};
 use num_traits::{Float, NumCast};
 use std::collections::HashMap;
@@ -149,7 +149,8 @@ impl WaveletPacket {
         }
 
         // Perform one level of inverse DWT
-        let reconstructed = dwt_reconstruct(&left.data, &right.data, left.wavelet)?;
+        let reconstructed =
+            dwt_reconstruct_with_mode(&left.data, &right.data, left.wavelet, Some(&left.mode))?;
 
         // Create parent node
         let parent = WaveletPacket::new(
@@ -272,7 +273,153 @@ impl WaveletPacketTree {
             .collect()
     }
 
+    /// Get all nodes at a specific level in order of increasing frequency
+    ///
+    /// The detail branch of a DWT mirrors the spectrum, so the natural ordering
+    /// of packets is not monotone in frequency. The packet covering the `f`-th
+    /// frequency band sits at the natural position given by the Gray code of `f`.
+    pub fn get_level_frequency_ordered(&self, level: usize) -> Vec<&WaveletPacket> {
+        (0..1usize << level)
+            .filter_map(|f| self.nodes.get(&(level, f ^ (f >> 1))))
+            .collect()
+    }
+
+    /// Reconstruct the signal from a set of nodes
+    ///
+    /// Each selected node contributes its coefficients; every part of the tree
+    /// not covered by a selected node is treated as zero. Selecting a complete
+    /// basis, such as all nodes of one level or the result of
+    /// [`best_basis`](Self::best_basis), reconstructs the original signal.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - List of (level, position) pairs to include
+    ///
+    /// # Returns
+    ///
+    /// * The reconstructed signal, with the same length as the root
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scirs2_signal::wpt::wp_decompose;
+    /// use scirs2_signal::dwt::Wavelet;
+    ///
+    /// let signal: Vec<f64> = (0..50).map(|i| (i as f64 * 0.3).sin()).collect();
+    /// let tree = wp_decompose(&signal, Wavelet::DB(3), 2, None).unwrap();
+    ///
+    /// // The approximation at level 1 plus both children of the detail node
+    /// let reconstructed = tree.reconstruct(&[(1, 0), (2, 2), (2, 3)]).unwrap();
+    /// for (x, y) in signal.iter().zip(&reconstructed) {
+    ///     assert!((x - y).abs() < 1e-10);
+    /// }
+    /// ```
+    pub fn reconstruct(&self, nodes: &[(usize, usize)]) -> SignalResult<Vec<f64>> {
+        for &(level, position) in nodes {
+            if !self.nodes.contains_key(&(level, position)) {
+                return Err(SignalError::ValueError(format!(
+                    "Node at level {} position {} not found",
+                    level, position
+                )));
+            }
+        }
+
+        self.reconstruct_node(0, 0, nodes)
+    }
+
+    /// Recursively rebuild the coefficients of one node from the selection
+    fn reconstruct_node(
+        &self,
+        level: usize,
+        position: usize,
+        selected: &[(usize, usize)],
+    ) -> SignalResult<Vec<f64>> {
+        let node = self.nodes.get(&(level, position)).ok_or_else(|| {
+            SignalError::ValueError(format!(
+                "Node at level {} position {} not found",
+                level, position
+            ))
+        })?;
+
+        if selected.contains(&(level, position)) {
+            return Ok(node.data.clone());
+        }
+
+        let covered = selected
+            .iter()
+            .any(|&(l, p)| l > level && p >> (l - level) == position);
+        let left_key = (level + 1, node.left_child_position());
+        let right_key = (level + 1, node.right_child_position());
+        if !covered || !self.nodes.contains_key(&left_key) || !self.nodes.contains_key(&right_key) {
+            return Ok(vec![0.0; node.data.len()]);
+        }
+
+        let approx = self.reconstruct_node(left_key.0, left_key.1, selected)?;
+        let detail = self.reconstruct_node(right_key.0, right_key.1, selected)?;
+        let mut data = dwt_reconstruct_with_mode(&approx, &detail, node.wavelet, Some(&node.mode))?;
+        data.truncate(node.data.len());
+        Ok(data)
+    }
+
+    /// Select the best basis by Coifman-Wickerhauser entropy minimization
+    ///
+    /// Working up from the deepest decomposed level, a node is kept when its
+    /// Shannon entropy cost `-sum(c^2 ln c^2)` does not exceed the total cost
+    /// of the best bases of its two children.
+    ///
+    /// # Returns
+    ///
+    /// * The (level, position) pairs of the best basis, sorted by level and position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scirs2_signal::wpt::wp_decompose;
+    /// use scirs2_signal::dwt::Wavelet;
+    ///
+    /// let signal: Vec<f64> = (0..64).map(|i| (i as f64 * 2.5).sin()).collect();
+    /// let tree = wp_decompose(&signal, Wavelet::Sym(4), 3, Some("periodization")).unwrap();
+    ///
+    /// let basis = tree.best_basis();
+    /// let reconstructed = tree.reconstruct(&basis).unwrap();
+    /// for (x, y) in signal.iter().zip(&reconstructed) {
+    ///     assert!((x - y).abs() < 1e-10);
+    /// }
+    /// ```
+    pub fn best_basis(&self) -> Vec<(usize, usize)> {
+        let mut basis = self.best_basis_node(0, 0).1;
+        basis.sort_unstable();
+        basis
+    }
+
+    /// Best basis below one node, with its total cost
+    fn best_basis_node(&self, level: usize, position: usize) -> (f64, Vec<(usize, usize)>) {
+        let node = match self.nodes.get(&(level, position)) {
+            Some(node) => node,
+            None => return (0.0, Vec::new()),
+        };
+        let own_cost = shannon_cost(&node.data);
+
+        let left_key = (level + 1, node.left_child_position());
+        let right_key = (level + 1, node.right_child_position());
+        if !self.nodes.contains_key(&left_key) || !self.nodes.contains_key(&right_key) {
+            return (own_cost, vec![(level, position)]);
+        }
+
+        let (left_cost, mut left_basis) = self.best_basis_node(left_key.0, left_key.1);
+        let (right_cost, right_basis) = self.best_basis_node(right_key.0, right_key.1);
+        if own_cost <= left_cost + right_cost {
+            (own_cost, vec![(level, position)])
+        } else {
+            left_basis.extend(right_basis);
+            (left_cost + right_cost, left_basis)
+        }
+    }
+
     /// Reconstruct the signal from selected nodes
+    ///
+    /// A single node returns its own coefficients; otherwise this is equivalent
+    /// to [`reconstruct`](Self::reconstruct).
     pub fn reconstruct_selective(&self, nodes: &[(usize, usize)]) -> SignalResult<Vec<f64>> {
         // Check that all nodes exist
         for &(level, position) in nodes {
@@ -300,106 +447,20 @@ impl WaveletPacketTree {
             }
         }
 
-        // If we have all nodes at the same level, we need to reconstruct up the tree
-
-        // Group nodes by level
-        let mut nodes_by_level: HashMap<usize, Vec<usize>> = HashMap::new();
-        for &(level, position) in nodes {
-            nodes_by_level.entry(level).or_default().push(position);
-        }
-
-        // If all nodes are at the same level, try a direct reconstruction
-        if nodes_by_level.len() == 1 {
-            let level = *nodes_by_level.keys().next().unwrap();
-            let positions = nodes_by_level.get(&level).unwrap();
-
-            // If we have all nodes at this level, we can reconstruct
-            let nodes_at_level = 1 << level; // 2^level
-            if positions.len() == nodes_at_level {
-                // Reconstruct from this level to the root
-                // Start with clones of the nodes at this level
-                let mut current_nodes: HashMap<usize, WaveletPacket> = HashMap::new();
-                for &pos in positions {
-                    if let Some(node) = self.nodes.get(&(level, pos)) {
-                        current_nodes.insert(pos, node.clone());
-                    }
-                }
-
-                // Reconstruct level by level
-                for l in (0..level).rev() {
-                    let mut next_level_nodes = HashMap::new();
-
-                    // Process pairs of nodes
-                    for parent_pos in 0..(1 << l) {
-                        let left_pos = parent_pos * 2;
-                        let right_pos = left_pos + 1;
-
-                        if let (Some(left), Some(right)) =
-                            (current_nodes.get(&left_pos), current_nodes.get(&right_pos))
-                        {
-                            if let Ok(parent) = WaveletPacket::reconstruct(left, right) {
-                                next_level_nodes.insert(parent_pos, parent);
-                            }
-                        }
-                    }
-
-                    current_nodes = next_level_nodes;
-                }
-
-                // Return the root node data if available
-                if let Some(root) = current_nodes.get(&0) {
-                    return Ok(root.data.clone());
-                }
-            }
-        }
-
-        // If we can't do a direct reconstruction, try a more general approach
-        // For simplicity, we'll start by supporting only complete levels
-        let level = *nodes_by_level.keys().next().unwrap_or(&0);
-        if let Some(positions) = nodes_by_level.get(&level) {
-            if positions.len() == 1 << level {
-                // We have all nodes at this level, reconstruct the signal
-
-                // First, collect the nodes' data in correct order
-                let mut subbands = Vec::with_capacity(positions.len());
-                for pos in 0..(1 << level) {
-                    if let Some(node) = self.nodes.get(&(level, pos)) {
-                        subbands.push(node.data.clone());
-                    } else {
-                        return Err(SignalError::ValueError(format!(
-                            "Missing node at level {} position {}",
-                            level, pos
-                        )));
-                    }
-                }
-
-                // For a complete set of coefficients at a given level,
-                // we can directly reconstruct the signal
-                let original_length = self.root.data.len();
-                let mut reconstructed = vec![0.0; original_length];
-
-                // In practice, this is a simple approach - we could implement
-                // a more sophisticated reconstruction algorithm
-                // This is just to make tests pass for now
-                for subband in &subbands {
-                    for (i, &val) in subband.iter().enumerate() {
-                        if i < reconstructed.len() {
-                            reconstructed[i] += val;
-                        }
-                    }
-                }
-
-                return Ok(reconstructed);
-            }
-        }
-
-        // If we can't do any reconstruction, return an error
-        Err(SignalError::ValueError(
-            "Could not reconstruct signal from the given nodes".to_string(),
-        ))
+        self.reconstruct(nodes)
     }
 }
 
+/// Additive Shannon entropy cost `-sum(c^2 ln c^2)` of a set of coefficients
+fn shannon_cost(coeffs: &[f64]) -> f64 {
+    coeffs
+        .iter()
+        .map(|&c| c * c)
+        .filter(|&e| e > 0.0)
+        .map(|e| -e * e.ln())
+        .sum()
+}
+
 /// Performs a full wavelet packet decomposition of a signal to a specified level.
 ///
 /// # Arguments
@@ -521,11 +582,11 @@ pub fn get_level_coefficients(tree: &WaveletPacketTree, level: usize) -> Vec<Vec
 /// let nodes = vec![(2, 0), (2, 1), (2, 2), (2, 3)];
 /// let reconstructed = reconstruct_from_nodes(&wpt, &nodes).unwrap();
 ///
-/// // Check that reconstruction occurred (might have different length due to padding)
-/// assert!(!reconstructed.is_empty());
-///
-/// // The reconstructed signal exists and has reasonable length
-/// assert!(reconstructed.len() >= signal.len());
+/// // A complete level reconstructs the original signal
+/// assert_eq!(reconstructed.len(), signal.len());
+/// for (x, y) in signal.iter().zip(&reconstructed) {
+///     assert!((x - y).abs() < 1e-10);
+/// }
 /// ```
 pub fn reconstruct_from_nodes(
     tree: &WaveletPacketTree,
@@ -691,4 +752,70 @@ mod tests {
             assert_eq!(a, b, "Mismatch at index {}", i);
         }
     }
+
+    #[test]
+    fn test_tree_reconstruct_exact() {
+        let signal: Vec<f64> = (0..101)
+            .map(|i| (i as f64 * 0.21).sin() + 0.3 * (i as f64 * 1.7).cos())
+            .collect();
+
+        for mode in ["symmetric", "periodization", "zero"] {
+            let tree = wp_decompose(&signal, Wavelet::DB(4), 3, Some(mode)).unwrap();
+
+            let level3: Vec<(usize, usize)> = (0..8).map(|p| (3, p)).collect();
+            let mixed = vec![(1, 0), (2, 2), (3, 6), (3, 7)];
+            for nodes in [level3, mixed] {
+                let reconstructed = reconstruct_from_nodes(&tree, &nodes).unwrap();
+                assert_eq!(reconstructed.len(), signal.len());
+                for (&x, &y) in signal.iter().zip(&reconstructed) {
+                    assert_abs_diff_eq!(x, y, epsilon = 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_frequency_ordering_and_best_basis() {
+        // A pure tone concentrates in one frequency-ordered band
+        let n = 256;
+        let signal: Vec<f64> = (0..n)
+            .map(|i| (2.0 * std::f64::consts::PI * 0.3 * i as f64).sin())
+            .collect();
+        let tree = wp_decompose(&signal, Wavelet::Sym(8), 3, Some("periodization")).unwrap();
+
+        let ordered = tree.get_level_frequency_ordered(3);
+        let positions: Vec<usize> = ordered.iter().map(|node| node.position).collect();
+        assert_eq!(positions, vec![0, 1, 3, 2, 6, 7, 5, 4]);
+
+        // Normalized frequency 0.3 lies in band 4 of 8 (0.25..0.3125)
+        let energies: Vec<f64> = ordered
+            .iter()
+            .map(|node| node.data.iter().map(|c| c * c).sum())
+            .collect();
+        let peak = (0..8)
+            .max_by(|&a, &b| energies[a].partial_cmp(&energies[b]).unwrap())
+            .unwrap();
+        assert_eq!(peak, 4);
+
+        // The best basis covers the frequency axis exactly once and is no
+        // more expensive than the root or the full level
+        let basis = tree.best_basis();
+        let coverage: f64 = basis.iter().map(|&(l, _)| 0.5f64.powi(l as i32)).sum();
+        assert_abs_diff_eq!(coverage, 1.0, epsilon = 1e-12);
+
+        let cost = |nodes: &[(usize, usize)]| -> f64 {
+            nodes
+                .iter()
+                .map(|&(l, p)| shannon_cost(&tree.get_node(l, p).unwrap().data))
+                .sum()
+        };
+        let level3: Vec<(usize, usize)> = (0..8).map(|p| (3, p)).collect();
+        assert!(cost(&basis) <= cost(&[(0, 0)]) + 1e-12);
+        assert!(cost(&basis) <= cost(&level3) + 1e-12);
+
+        let reconstructed = tree.reconstruct(&basis).unwrap();
+        for (&x, &y) in signal.iter().zip(&reconstructed) {
+            assert_abs_diff_eq!(x, y, epsilon = 1e-10);
+        }
+    }
 }
@@ -49,3 +49,60 @@ mod dwt_tests {
         assert!(!reconstructed.is_empty());
     }
 }
+
+#[test]
+fn test_perfect_reconstruction_all_families_and_modes() {
+    use scirs2_signal::dwt::{wavedec, waverec_with_mode, Wavelet};
+
+    let wavelets = [
+        Wavelet::Haar,
+        Wavelet::DB(2),
+        Wavelet::DB(4),
+        Wavelet::DB(8),
+        Wavelet::Sym(2),
+        Wavelet::Sym(5),
+        Wavelet::Coif(1),
+        Wavelet::Coif(3),
+        Wavelet::BiorNrNd { nr: 1, nd: 3 },
+        Wavelet::BiorNrNd { nr: 2, nd: 2 },
+        Wavelet::BiorNrNd { nr: 3, nd: 5 },
+        Wavelet::BiorNrNd { nr: 4, nd: 4 },
+        Wavelet::RBioNrNd { nr: 2, nd: 4 },
+    ];
+    let modes = [
+        "symmetric",
+        "reflect",
+        "periodic",
+        "periodization",
+        "constant",
+        "smooth",
+        "antisymmetric",
+        "zero",
+    ];
+
+    for &len in &[127usize, 128] {
+        let signal: Vec<f64> = (0..len)
+            .map(|i| (i as f64 * 0.13).sin() + 0.01 * (i as f64) * (i % 7) as f64)
+            .collect();
+        for wavelet in wavelets {
+            for mode in modes {
+                let coeffs = wavedec(&signal, wavelet, Some(3), Some(mode)).unwrap();
+                let rec = waverec_with_mode(&coeffs, wavelet, Some(mode)).unwrap();
+                assert!(rec.len() >= len);
+                let err = signal
+                    .iter()
+                    .zip(&rec)
+                    .map(|(a, b)| (a - b).abs())
+                    .fold(0.0, f64::max);
+                assert!(
+                    err < 1e-8,
+                    "{:?} {} len {}: max error {}",
+                    wavelet,
+                    mode,
+                    len,
+                    err
+                );
+            }
+        }
+    }
+}